@@ -125,6 +125,7 @@ pub struct SupportBundle {
     log_files: Vec<PathBuf>,
     profile_files: Vec<PathBuf>,
     recording_files: Vec<PathBuf>,
+    telemetry_history: Option<String>,
     current_size_bytes: u64,
 }
 
@@ -138,6 +139,7 @@ impl SupportBundle {
             log_files: Vec::new(),
             profile_files: Vec::new(),
             recording_files: Vec::new(),
+            telemetry_history: None,
             current_size_bytes: 0,
         }
     }
@@ -268,6 +270,32 @@ impl SupportBundle {
         Ok(())
     }
 
+    /// Add a compact recent telemetry history section, replacing any earlier one
+    pub fn add_telemetry_history<T: Serialize>(&mut self, history: &T) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(history)
+            .map_err(|e| format!("Failed to serialize telemetry history: {}", e))?;
+        let previous_size = self
+            .telemetry_history
+            .as_ref()
+            .map_or(0, |h| h.len() as u64);
+        let size = self
+            .current_size_bytes
+            .saturating_sub(previous_size)
+            .saturating_add(json.len() as u64);
+
+        if size > self.max_size_bytes() {
+            return Err(format!(
+                "Bundle size limit exceeded: {} MB > {} MB",
+                size / 1024 / 1024,
+                self.config.max_bundle_size_mb
+            ));
+        }
+
+        self.current_size_bytes = size;
+        self.telemetry_history = Some(json);
+        Ok(())
+    }
+
     /// Generate the support bundle ZIP file
     pub fn generate(&self, output_path: &Path) -> Result<(), String> {
         let file = File::create(output_path)
@@ -291,6 +319,11 @@ impl SupportBundle {
             self.add_health_events_to_zip(&mut zip, &options)?;
         }
 
+        // Add telemetry history
+        if let Some(ref history) = self.telemetry_history {
+            self.add_telemetry_history_to_zip(&mut zip, &options, history)?;
+        }
+
         // Add log files
         for log_file in &self.log_files {
             self.add_file_to_zip(&mut zip, &options, log_file, "logs/")?;
@@ -534,6 +567,7 @@ impl SupportBundle {
                 "log_files_count": self.log_files.len(),
                 "profile_files_count": self.profile_files.len(),
                 "recording_files_count": self.recording_files.len(),
+                "telemetry_history": self.telemetry_history.is_some(),
             }
         });
 
@@ -583,6 +617,22 @@ impl SupportBundle {
         Ok(())
     }
 
+    /// Add telemetry history to ZIP
+    fn add_telemetry_history_to_zip(
+        &self,
+        zip: &mut ZipWriter<File>,
+        options: &SimpleFileOptions,
+        history: &str,
+    ) -> Result<(), String> {
+        zip.start_file("telemetry_history.json", *options)
+            .map_err(|e| format!("Failed to start telemetry history file: {}", e))?;
+
+        zip.write_all(history.as_bytes())
+            .map_err(|e| format!("Failed to write telemetry history: {}", e))?;
+
+        Ok(())
+    }
+
     /// Add file to ZIP with specified prefix
     fn add_file_to_zip(
         &self,
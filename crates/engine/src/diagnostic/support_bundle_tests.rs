@@ -214,6 +214,39 @@ fn test_bundle_manifest_counts_match() -> Result<(), String> {
     Ok(())
 }
 
+#[test]
+fn test_bundle_contains_telemetry_history() -> Result<(), String> {
+    let td = TempDir::new().map_err(|e| e.to_string())?;
+    let history = serde_json::json!({
+        "games": [{
+            "game_id": "iracing",
+            "resolution_ms": 10_000,
+            "fields": [{"field": "rpm", "buckets": [{"start_ns": 0, "min": 900.0, "max": 7200.0}]}]
+        }]
+    });
+    let mut bundle = SupportBundle::new(SupportBundleConfig::default());
+    bundle.add_telemetry_history(&serde_json::json!({"games": []}))?;
+    bundle.add_telemetry_history(&history)?;
+    let zip_path = td.path().join("bundle.zip");
+    bundle.generate(&zip_path)?;
+
+    let entries = read_zip_entries(&zip_path)?;
+    let written: serde_json::Value = serde_json::from_slice(
+        entries
+            .get("telemetry_history.json")
+            .ok_or("missing telemetry_history.json")?,
+    )
+    .map_err(|e| e.to_string())?;
+    assert_eq!(written, history, "the latest history replaces earlier ones");
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&entries["manifest.json"]).map_err(|e| e.to_string())?;
+    assert_eq!(manifest["contents"]["telemetry_history"], true);
+
+    let (full_path, _full) = build_full_bundle()?;
+    assert!(!read_zip_entries(&full_path)?.contains_key("telemetry_history.json"));
+    Ok(())
+}
+
 // =========================================================================
 // 2. Redaction / sanitization – sensitive fields masked
 // =========================================================================
@@ -6,6 +6,8 @@
 #![deny(static_mut_refs)]
 #![deny(clippy::unwrap_used)]
 
+use openracing_byte_reader::ByteReader;
+
 /// [ADR-0007]: Multi-Vendor HID Protocol Architecture
 /// This crate follows the "SRP Microcrate" pattern for vendor-specific HID protocols.
 /// Handbrake axis with report-id prefix.
@@ -47,14 +49,8 @@ impl HbpHandbrakeSampleRaw {
 }
 
 /// Parse a little-endian `u16` axis from `report` at `start`.
-///
-/// NOTE: Duplicated (by design) across tiny protocol microcrates to keep them
-/// dependency-minimal. Keep in sync with similar helpers (e.g. moza-wheelbase-report).
 pub fn parse_axis(report: &[u8], start: usize) -> Option<u16> {
-    if report.len() < start.saturating_add(2) {
-        return None;
-    }
-    Some(u16::from_le_bytes([report[start], report[start + 1]]))
+    ByteReader::new(report).u16_le_at(start).ok()
 }
 
 /// Parse a standalone HBP USB report using best-effort layout inference.
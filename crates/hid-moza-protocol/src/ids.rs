@@ -100,15 +100,4 @@ pub mod product_ids {
     pub const HBP_HANDBRAKE: u16 = 0x0022;
 }
 
-/// Known Moza rim IDs when attached to a compatible wheelbase.
-///
-/// These are rim identity values reported through the wheelbase transport,
-/// not standalone USB product IDs.
-pub mod rim_ids {
-    pub const CS_V2: u8 = 0x01;
-    pub const GS_V2: u8 = 0x02;
-    pub const RS_V2: u8 = 0x03;
-    pub const FSR: u8 = 0x04;
-    pub const KS: u8 = 0x05;
-    pub const ES: u8 = 0x06;
-}
+pub use racing_wheel_moza_wheelbase_report::rim_ids;
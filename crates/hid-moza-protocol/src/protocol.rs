@@ -42,10 +42,8 @@ use crate::types::{
 };
 use crate::writer::{DeviceWriter, FfbConfig, VendorProtocol};
 use racing_wheel_hbp::parse_hbp_usb_report_best_effort;
-use racing_wheel_ks::{
-    KS_ENCODER_COUNT, KsByteSource, KsClutchMode, KsJoystickMode, KsReportMap, KsReportSnapshot,
-    KsRotaryMode,
-};
+use racing_wheel_ks::{KsReportMap, KsReportSnapshot};
+use racing_wheel_moza_wheelbase_report::builtin_ks_rim_map;
 use racing_wheel_srp::parse_srp_usb_report_best_effort;
 use std::sync::atomic::{AtomicU8, Ordering};
 use tracing::{debug, info, warn};
@@ -241,23 +239,7 @@ pub fn effective_high_torque_opt_in(descriptor_crc32: Option<u32>) -> bool {
 }
 
 fn default_wheelbase_ks_map() -> KsReportMap {
-    KsReportMap {
-        report_id: Some(input_report::REPORT_ID),
-        buttons_offset: Some(input_report::BUTTONS_START),
-        hat_offset: Some(input_report::HAT_START),
-        encoders: [None; KS_ENCODER_COUNT],
-        clutch_left_axis: None,
-        clutch_right_axis: None,
-        clutch_combined_axis: None,
-        clutch_left_button: None,
-        clutch_right_button: None,
-        clutch_mode_hint: KsClutchMode::Unknown,
-        rotary_mode_hint: KsRotaryMode::Unknown,
-        left_rotary_axis: None,
-        right_rotary_axis: None,
-        joystick_mode_hint: KsJoystickMode::Unknown,
-        joystick_hat: Some(KsByteSource::new(input_report::HAT_START)),
-    }
+    builtin_ks_rim_map()
 }
 
 /// Moza protocol handler.
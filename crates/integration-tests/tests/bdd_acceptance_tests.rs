@@ -35,6 +35,7 @@ fn iracing_telemetry_config() -> TelemetryConfig {
         output_target: String::new(),
         fields: vec!["speed".to_string(), "rpm".to_string(), "gear".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     }
 }
 
@@ -23,7 +23,7 @@ use racing_wheel_engine::ports::HidDevice;
 use racing_wheel_engine::safety::SafetyService;
 use racing_wheel_engine::{Frame as EngineFrame, Pipeline as EnginePipeline, VirtualDevice};
 use racing_wheel_schemas::prelude::*;
-use racing_wheel_schemas::telemetry::TelemetryFrame;
+use racing_wheel_schemas::telemetry::{TELEMETRY_SCHEMA_VERSION, TelemetryFrame};
 use racing_wheel_telemetry_adapters::{MockAdapter, TelemetryAdapter, adapter_factories};
 use racing_wheel_telemetry_recorder::{TelemetryPlayer, TelemetryRecorder};
 
@@ -438,6 +438,8 @@ fn game_telemetry_record_and_playback() -> Result<(), Box<dyn std::error::Error>
             timestamp_ns: i * 16_666_667, // ~60Hz
             sequence: i,
             raw_size: 0,
+            version: TELEMETRY_SCHEMA_VERSION,
+            opponents: Vec::new(),
         };
         recorder.record_frame(frame);
     }
@@ -484,6 +486,8 @@ fn game_telemetry_record_mock_adapter_frames() -> Result<(), Box<dyn std::error:
             timestamp_ns: seq * 16_666_667,
             sequence: seq,
             raw_size: 0,
+            version: TELEMETRY_SCHEMA_VERSION,
+            opponents: Vec::new(),
         };
         recorder.record_frame(frame);
     }
@@ -516,6 +520,8 @@ fn game_telemetry_player_reset_replays() -> Result<(), Box<dyn std::error::Error
             timestamp_ns: i * 16_666_667,
             sequence: i,
             raw_size: 0,
+            version: TELEMETRY_SCHEMA_VERSION,
+            opponents: Vec::new(),
         };
         recorder.record_frame(frame);
     }
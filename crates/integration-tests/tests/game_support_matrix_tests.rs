@@ -33,6 +33,7 @@ fn default_telemetry_config() -> TelemetryConfig {
             "gear".to_string(),
         ],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     }
 }
 
@@ -648,6 +649,9 @@ fn config_writer_diffs_have_valid_operations() -> TestResult {
                 DiffOperation::Remove => {
                     // Remove operations should have empty new_value or special marker
                 }
+                DiffOperation::NoChange => {
+                    // NoChange operations carry the value already on disk
+                }
             }
             let _ = id;
         }
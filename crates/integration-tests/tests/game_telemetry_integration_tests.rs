@@ -599,6 +599,7 @@ mod config_generation {
             output_target: "127.0.0.1:5300".to_string(),
             fields: vec!["speed".to_string(), "rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         }
     }
 
@@ -419,7 +419,12 @@ fn telemetry_snapshot_serde_consistency() -> Result<(), Box<dyn std::error::Erro
         lap: 3,
         current_lap_time_s: 0.0,
         fuel_percent: 0.8,
+        penalties: None,
+        game_time_s: None,
+        game_tick: None,
         sequence: 42,
+        tires: None,
+        timing: None,
     };
 
     let json = serde_json::to_string(&snapshot)?;
@@ -478,6 +483,7 @@ fn telemetry_config_json_round_trip_preserves_all_fields() -> Result<(), Box<dyn
             "slip_ratio".to_string(),
         ],
         enable_high_rate_iracing_360hz: true,
+        allow_port_sharing: false,
     };
 
     let json = serde_json::to_string_pretty(&config)?;
@@ -512,6 +518,8 @@ fn config_diff_round_trip_all_operations() -> Result<(), Box<dyn std::error::Err
             old_value: Some("false".to_string()),
             new_value: "true".to_string(),
             operation: op.clone(),
+            backup_path: None,
+            details: Vec::new(),
         };
 
         let json = serde_json::to_string(&diff)?;
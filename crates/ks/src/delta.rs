@@ -0,0 +1,167 @@
+//! Changes between consecutive KS snapshots.
+//!
+//! [`KsReportSnapshot::delta_from`] diffs two snapshots into button edges,
+//! encoder movement, hat changes and clutch threshold crossings;
+//! [`KsInputTracker`] keeps the previous snapshot for a live report stream.
+
+use crate::{KS_BUTTON_BYTES, KS_ENCODER_COUNT, KsReportSnapshot};
+
+/// Clutch threshold used by [`KsReportSnapshot::delta_from`]: half travel.
+pub const KS_DEFAULT_CLUTCH_THRESHOLD: u16 = 0x8000;
+
+/// The hat changed direction between two snapshots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KsHatChange {
+    pub previous: u8,
+    pub current: u8,
+}
+
+/// Both clutches crossed the press threshold together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KsClutchEdge {
+    /// Both clutches are now pressed; at least one was not before.
+    Pressed,
+    /// At least one clutch is no longer pressed.
+    Released,
+}
+
+/// What changed from one snapshot to the next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct KsReportDelta {
+    /// Ticks between the two snapshots; 1 for consecutive reports.
+    pub ticks: u32,
+    /// Button bits that went from clear to set, packed like the snapshot.
+    pub pressed: [u8; KS_BUTTON_BYTES],
+    /// Button bits that went from set to clear.
+    pub released: [u8; KS_BUTTON_BYTES],
+    /// Signed movement of each encoder, taking the short way round when a
+    /// value rolls over the `i16` bounds.
+    pub encoders: [i16; KS_ENCODER_COUNT],
+    pub hat: Option<KsHatChange>,
+    /// `None` unless [`KsReportSnapshot::both_clutches_pressed`] is known for
+    /// both snapshots and differs.
+    pub clutch: Option<KsClutchEdge>,
+}
+
+impl KsReportDelta {
+    /// Indices of the buttons just pressed; bit `n` of byte `b` is `b * 8 + n`.
+    pub fn pressed_buttons(&self) -> impl Iterator<Item = usize> + '_ {
+        set_bits(&self.pressed)
+    }
+
+    /// Indices of the buttons just released.
+    pub fn released_buttons(&self) -> impl Iterator<Item = usize> + '_ {
+        set_bits(&self.released)
+    }
+
+    /// Reports missed between the two snapshots, judging by their ticks.
+    pub fn missed_ticks(&self) -> u32 {
+        self.ticks.saturating_sub(1)
+    }
+
+    /// Whether anything other than the tick changed.
+    pub fn is_empty(&self) -> bool {
+        self.pressed == [0; KS_BUTTON_BYTES]
+            && self.released == [0; KS_BUTTON_BYTES]
+            && self.encoders == [0; KS_ENCODER_COUNT]
+            && self.hat.is_none()
+            && self.clutch.is_none()
+    }
+}
+
+fn set_bits(bytes: &[u8; KS_BUTTON_BYTES]) -> impl Iterator<Item = usize> + '_ {
+    bytes.iter().enumerate().flat_map(|(index, &byte)| {
+        (0..8)
+            .filter(move |bit| byte & (1 << bit) != 0)
+            .map(move |bit| index * 8 + bit)
+    })
+}
+
+impl KsReportSnapshot {
+    /// Changes since `previous`, with clutch edges at
+    /// [`KS_DEFAULT_CLUTCH_THRESHOLD`].
+    pub fn delta_from(&self, previous: &KsReportSnapshot) -> KsReportDelta {
+        self.delta_with_clutch_threshold(previous, KS_DEFAULT_CLUTCH_THRESHOLD)
+    }
+
+    /// Changes since `previous`, with clutch edges at `clutch_threshold`.
+    pub fn delta_with_clutch_threshold(
+        &self,
+        previous: &KsReportSnapshot,
+        clutch_threshold: u16,
+    ) -> KsReportDelta {
+        let mut delta = KsReportDelta {
+            ticks: self.tick.wrapping_sub(previous.tick),
+            ..KsReportDelta::default()
+        };
+        for (index, (&current, &before)) in self.buttons.iter().zip(&previous.buttons).enumerate() {
+            delta.pressed[index] = current & !before;
+            delta.released[index] = before & !current;
+        }
+        for (index, (&current, &before)) in self.encoders.iter().zip(&previous.encoders).enumerate()
+        {
+            delta.encoders[index] = current.wrapping_sub(before);
+        }
+        if self.hat != previous.hat {
+            delta.hat = Some(KsHatChange {
+                previous: previous.hat,
+                current: self.hat,
+            });
+        }
+        delta.clutch = match (
+            previous.both_clutches_pressed(clutch_threshold),
+            self.both_clutches_pressed(clutch_threshold),
+        ) {
+            (Some(false), Some(true)) => Some(KsClutchEdge::Pressed),
+            (Some(true), Some(false)) => Some(KsClutchEdge::Released),
+            _ => None,
+        };
+        delta
+    }
+}
+
+/// Turns a stream of snapshots into deltas against the previous one.
+#[derive(Debug, Clone)]
+pub struct KsInputTracker {
+    previous: Option<KsReportSnapshot>,
+    clutch_threshold: u16,
+}
+
+impl Default for KsInputTracker {
+    fn default() -> Self {
+        Self::new(KS_DEFAULT_CLUTCH_THRESHOLD)
+    }
+}
+
+impl KsInputTracker {
+    pub const fn new(clutch_threshold: u16) -> Self {
+        Self {
+            previous: None,
+            clutch_threshold,
+        }
+    }
+
+    /// Record `snapshot`, returning its delta from the last one.
+    ///
+    /// The first snapshot, and the first after [`Self::reset`], only sets the
+    /// baseline and yields `None`. Check [`KsReportDelta::missed_ticks`] for
+    /// dropped reports.
+    pub fn update(&mut self, snapshot: KsReportSnapshot) -> Option<KsReportDelta> {
+        let delta = self
+            .previous
+            .as_ref()
+            .map(|previous| snapshot.delta_with_clutch_threshold(previous, self.clutch_threshold));
+        self.previous = Some(snapshot);
+        delta
+    }
+
+    /// The last snapshot recorded.
+    pub fn previous(&self) -> Option<&KsReportSnapshot> {
+        self.previous.as_ref()
+    }
+
+    /// Forget the baseline, e.g. after the device reconnects.
+    pub fn reset(&mut self) {
+        self.previous = None;
+    }
+}
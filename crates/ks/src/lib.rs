@@ -5,9 +5,20 @@
 
 #![deny(static_mut_refs)]
 
+use openracing_byte_reader::ByteReader;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+mod delta;
+#[cfg(feature = "serde")]
+mod map_file;
+
+pub use delta::{
+    KS_DEFAULT_CLUTCH_THRESHOLD, KsClutchEdge, KsHatChange, KsInputTracker, KsReportDelta,
+};
+#[cfg(feature = "serde")]
+pub use map_file::{KsMapError, KsMapFile, KsMapOverlap, load_map_directory, load_map_files};
+
 /// Number of encoder-like slots in a generic KS snapshot.
 pub const KS_ENCODER_COUNT: usize = 8;
 /// Number of packed button bytes in a normalized KS snapshot.
@@ -70,23 +81,14 @@ impl KsAxisSource {
         Self { offset, signed }
     }
 
-    fn parse_bytes(report: &[u8], offset: usize) -> Option<[u8; 2]> {
-        if report.len() < offset.saturating_add(2) {
-            return None;
-        }
-        Some([report[offset], report[offset + 1]])
-    }
-
     /// Parse an unsigned 16-bit integer from its source in the report.
     pub fn parse_u16(&self, report: &[u8]) -> Option<u16> {
-        let bytes = Self::parse_bytes(report, self.offset)?;
-        Some(u16::from_le_bytes(bytes))
+        ByteReader::new(report).u16_le_at(self.offset).ok()
     }
 
     /// Parse a signed 16-bit integer from its source in the report.
     pub fn parse_i16(&self, report: &[u8]) -> Option<i16> {
-        let bytes = Self::parse_bytes(report, self.offset)?;
-        Some(i16::from_le_bytes(bytes))
+        ByteReader::new(report).i16_le_at(self.offset).ok()
     }
 }
 
@@ -128,10 +130,7 @@ impl KsBitSource {
 
     /// Extract and resolve the boolean state of this bit map.
     pub fn parse(&self, report: &[u8]) -> Option<bool> {
-        if report.len() <= self.offset {
-            return None;
-        }
-        let active = report[self.offset] & self.mask != 0;
+        let active = ByteReader::new(report).u8_at(self.offset).ok()? & self.mask != 0;
         Some(if self.invert { !active } else { active })
     }
 }
@@ -236,8 +235,12 @@ impl KsReportSnapshot {
 }
 
 /// Capture-driven map from raw report bytes to KS semantic channels.
+///
+/// With the `serde` feature a map (de)serializes using these field names,
+/// omitted fields deserializing as unmapped; see `KsMapFile` for map files.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct KsReportMap {
     /// Expected report ID, when known.
     pub report_id: Option<u8>,
@@ -267,6 +270,12 @@ pub struct KsReportMap {
     pub joystick_hat: Option<KsByteSource>,
 }
 
+impl Default for KsReportMap {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
 impl KsReportMap {
     /// Empty map for unsupported layouts.
     pub const fn empty() -> Self {
@@ -306,14 +315,10 @@ impl KsReportMap {
         };
 
         if let Some(offset) = self.buttons_offset {
-            let button_len = snapshot.buttons.len();
-            if report.len() >= offset.saturating_add(button_len) {
-                snapshot
-                    .buttons
-                    .copy_from_slice(&report[offset..offset + button_len]);
-            } else {
-                let available = report.len().saturating_sub(offset);
-                snapshot.buttons[..available].copy_from_slice(&report[offset..offset + available]);
+            // Short reports fill what they carry; the rest stays zeroed.
+            let present = ByteReader::new(report).bytes_up_to(offset, KS_BUTTON_BYTES);
+            for (button, byte) in snapshot.buttons.iter_mut().zip(present) {
+                *button = *byte;
             }
         }
 
@@ -444,6 +449,18 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn ks_report_map_buttons_offset_past_end_is_zero_filled()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let mut map = KsReportMap::empty();
+        map.buttons_offset = Some(40);
+        let snapshot = map
+            .parse(0, &[0x01, 0xFF, 0xFF])
+            .ok_or("map with no report_id should accept any")?;
+        assert_eq!(snapshot.buttons, [0u8; KS_BUTTON_BYTES]);
+        Ok(())
+    }
+
     #[test]
     fn ks_report_map_rejects_wrong_report_id() {
         let mut map = KsReportMap::empty();
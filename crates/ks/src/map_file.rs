@@ -0,0 +1,331 @@
+//! KS report maps shipped as JSON data rather than code.
+//!
+//! A map file is a JSON object holding the [`KsReportMap`] fields, using the
+//! Rust field names, next to two header fields:
+//!
+//! ```json
+//! {
+//!   "id": "ks-pro/fw-1.2",
+//!   "max_report_len": 64,
+//!   "report_id": 1,
+//!   "buttons_offset": 2,
+//!   "clutch_mode_hint": "CombinedAxis",
+//!   "clutch_combined_axis": { "offset": 19, "signed": false }
+//! }
+//! ```
+//!
+//! - `id` names the device and firmware layout the map describes;
+//! - `max_report_len` is the longest report the layout produces, and every
+//!   mapped region must fit inside it;
+//! - omitted map fields are unmapped, as in [`KsReportMap::empty`];
+//! - the mode hints take their variant names: `Unknown`, `CombinedAxis`,
+//!   `IndependentAxis`, `Button`, `Knob` and `DPad`, as each mode allows;
+//! - axes are `{ "offset", "signed" }`, bits `{ "offset", "mask", "invert" }`
+//!   and bytes `{ "offset" }`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{KS_BUTTON_BYTES, KS_ENCODER_COUNT, KsReportMap};
+
+/// Why a map could not be loaded.
+#[derive(Debug)]
+pub enum KsMapError {
+    /// Malformed JSON, a missing header field or an unknown mode name.
+    Json(serde_json::Error),
+    /// A mapped region ends past the declared report length.
+    OffsetOutOfRange {
+        field: String,
+        offset: usize,
+        width: usize,
+        max_report_len: usize,
+    },
+    /// A map file could not be read.
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// The map file at `path` is malformed or out of range.
+    File {
+        path: PathBuf,
+        source: Box<KsMapError>,
+    },
+    /// Two map files in one directory share an id.
+    DuplicateId {
+        id: String,
+        first: PathBuf,
+        second: PathBuf,
+    },
+}
+
+impl fmt::Display for KsMapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(err) => write!(f, "invalid KS map JSON: {err}"),
+            Self::OffsetOutOfRange {
+                field,
+                offset,
+                width,
+                max_report_len,
+            } => write!(
+                f,
+                "`{field}` covers bytes {offset}..{} but reports are at most {max_report_len} bytes",
+                offset.saturating_add(*width)
+            ),
+            Self::Io { path, source } => {
+                write!(f, "failed to read KS map {}: {source}", path.display())
+            }
+            Self::File { path, source } => write!(f, "{}: {source}", path.display()),
+            Self::DuplicateId { id, first, second } => write!(
+                f,
+                "KS map id `{id}` is defined by both {} and {}",
+                first.display(),
+                second.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for KsMapError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Json(err) => Some(err),
+            Self::Io { source, .. } => Some(source),
+            Self::File { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for KsMapError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+/// Two mapped regions sharing report bytes; usually a mistake in the map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KsMapOverlap {
+    pub first: String,
+    pub second: String,
+}
+
+impl fmt::Display for KsMapOverlap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` overlaps `{}`", self.first, self.second)
+    }
+}
+
+/// A byte range of the report read by one map field.
+struct Region {
+    field: String,
+    offset: usize,
+    width: usize,
+}
+
+impl Region {
+    fn end(&self) -> usize {
+        self.offset.saturating_add(self.width)
+    }
+}
+
+impl KsReportMap {
+    /// Parse a map from JSON; fields left out are unmapped.
+    pub fn from_json(json: &str) -> Result<Self, KsMapError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Fail if any mapped region ends past `max_report_len`.
+    pub fn validate(&self, max_report_len: usize) -> Result<(), KsMapError> {
+        let bits = self.bit_offsets().map(|(field, offset)| Region {
+            field: field.to_string(),
+            offset,
+            width: 1,
+        });
+        for region in self.regions().into_iter().chain(bits) {
+            // Only the start of the button bitmap must fit; short reports
+            // zero-fill the rest.
+            let width = if region.field == "buttons_offset" {
+                1
+            } else {
+                region.width
+            };
+            if region.offset.saturating_add(width) > max_report_len {
+                return Err(KsMapError::OffsetOutOfRange {
+                    field: region.field,
+                    offset: region.offset,
+                    width,
+                    max_report_len,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Axis, button and hat regions that share bytes with another.
+    ///
+    /// The rotary axes deliberately replace the first two encoders, so a
+    /// rotary axis reading the same bytes as its encoder is not reported.
+    pub fn overlaps(&self) -> Vec<KsMapOverlap> {
+        let regions = self.regions();
+        let mut overlaps = Vec::new();
+        for (index, first) in regions.iter().enumerate() {
+            for second in &regions[index + 1..] {
+                let shares_bytes = first.offset < second.end() && second.offset < first.end();
+                let same_source = first.offset == second.offset && first.width == second.width;
+                let replaced_encoder = same_source
+                    && matches!(
+                        (first.field.as_str(), second.field.as_str()),
+                        ("encoders[0]", "left_rotary_axis") | ("encoders[1]", "right_rotary_axis")
+                    );
+                let same_hat =
+                    same_source && first.field == "hat_offset" && second.field == "joystick_hat";
+                if shares_bytes && !replaced_encoder && !same_hat {
+                    overlaps.push(KsMapOverlap {
+                        first: first.field.clone(),
+                        second: second.field.clone(),
+                    });
+                }
+            }
+        }
+        overlaps
+    }
+
+    /// Every mapped region, in field order. Clutch bits are left out: they
+    /// commonly sit inside the button bitmap.
+    fn regions(&self) -> Vec<Region> {
+        let mut regions = Vec::new();
+        let mut push = |field: String, offset: Option<usize>, width: usize| {
+            if let Some(offset) = offset {
+                regions.push(Region {
+                    field,
+                    offset,
+                    width,
+                });
+            }
+        };
+        push(
+            "buttons_offset".to_string(),
+            self.buttons_offset,
+            KS_BUTTON_BYTES,
+        );
+        push("hat_offset".to_string(), self.hat_offset, 1);
+        for (index, encoder) in self.encoders.iter().enumerate().take(KS_ENCODER_COUNT) {
+            push(
+                format!("encoders[{index}]"),
+                encoder.map(|axis| axis.offset),
+                2,
+            );
+        }
+        for (field, axis) in [
+            ("clutch_left_axis", self.clutch_left_axis),
+            ("clutch_right_axis", self.clutch_right_axis),
+            ("clutch_combined_axis", self.clutch_combined_axis),
+            ("left_rotary_axis", self.left_rotary_axis),
+            ("right_rotary_axis", self.right_rotary_axis),
+        ] {
+            push(field.to_string(), axis.map(|axis| axis.offset), 2);
+        }
+        push(
+            "joystick_hat".to_string(),
+            self.joystick_hat.map(|byte| byte.offset),
+            1,
+        );
+        regions
+    }
+
+    /// Offsets of the clutch bit sources, which are range-checked only.
+    fn bit_offsets(&self) -> impl Iterator<Item = (&'static str, usize)> {
+        [
+            ("clutch_left_button", self.clutch_left_button),
+            ("clutch_right_button", self.clutch_right_button),
+        ]
+        .into_iter()
+        .filter_map(|(field, bit)| bit.map(|bit| (field, bit.offset)))
+    }
+}
+
+/// A [`KsReportMap`] with the header of its map file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KsMapFile {
+    /// Device and firmware layout the map describes.
+    pub id: String,
+    /// Longest report the layout produces.
+    pub max_report_len: usize,
+    #[serde(flatten)]
+    pub map: KsReportMap,
+}
+
+impl KsMapFile {
+    /// Parse a map file, rejecting regions past `max_report_len`.
+    pub fn from_json(json: &str) -> Result<Self, KsMapError> {
+        let file: Self = serde_json::from_str(json)?;
+        file.map.validate(file.max_report_len)?;
+        Ok(file)
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Overlapping regions worth warning about; they do not fail a load.
+    pub fn warnings(&self) -> Vec<KsMapOverlap> {
+        self.map.overlaps()
+    }
+}
+
+/// Load every `*.json` map file in `dir`, sorted by file name.
+pub fn load_map_files(dir: &Path) -> Result<Vec<(PathBuf, KsMapFile)>, KsMapError> {
+    let io_error = |source| KsMapError::Io {
+        path: dir.to_path_buf(),
+        source,
+    };
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        if path.extension().is_some_and(|ext| ext == "json") && path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let json = std::fs::read_to_string(&path).map_err(|source| KsMapError::Io {
+                path: path.clone(),
+                source,
+            })?;
+            match KsMapFile::from_json(&json) {
+                Ok(file) => Ok((path, file)),
+                Err(err) => Err(KsMapError::File {
+                    path,
+                    source: Box::new(err),
+                }),
+            }
+        })
+        .collect()
+}
+
+/// Load the map files in `dir`, keyed by their `id`.
+pub fn load_map_directory(dir: &Path) -> Result<HashMap<String, KsReportMap>, KsMapError> {
+    let mut maps: HashMap<String, (PathBuf, KsReportMap)> = HashMap::new();
+    for (path, file) in load_map_files(dir)? {
+        if let Some((first, _)) = maps.get(&file.id) {
+            return Err(KsMapError::DuplicateId {
+                id: file.id,
+                first: first.clone(),
+                second: path,
+            });
+        }
+        maps.insert(file.id, (path, file.map));
+    }
+    Ok(maps.into_iter().map(|(id, (_, map))| (id, map)).collect())
+}
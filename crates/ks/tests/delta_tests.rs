@@ -0,0 +1,99 @@
+//! Edges and movement between consecutive KS snapshots.
+
+use racing_wheel_ks::{KsClutchEdge, KsClutchMode, KsHatChange, KsInputTracker, KsReportSnapshot};
+
+type R = Result<(), Box<dyn std::error::Error>>;
+
+fn snapshot(tick: u32) -> KsReportSnapshot {
+    KsReportSnapshot {
+        tick,
+        ..KsReportSnapshot::default()
+    }
+}
+
+#[test]
+fn a_bit_set_then_cleared_gives_one_press_and_one_release() {
+    let idle = snapshot(0);
+    let mut held = snapshot(1);
+    held.buttons[2] = 0b0000_1000;
+    let released = snapshot(2);
+
+    let press = held.delta_from(&idle);
+    let release = released.delta_from(&held);
+
+    assert_eq!(press.pressed_buttons().collect::<Vec<_>>(), [19]);
+    assert_eq!(press.released_buttons().count(), 0);
+    assert_eq!(release.pressed_buttons().count(), 0);
+    assert_eq!(release.released_buttons().collect::<Vec<_>>(), [19]);
+    assert!(released.delta_from(&released).is_empty());
+}
+
+#[test]
+fn encoders_take_the_short_way_round_on_wraparound() {
+    let mut before = snapshot(0);
+    before.encoders[0] = 32_760;
+    before.encoders[1] = -32_760;
+    before.encoders[2] = 100;
+    let mut after = snapshot(1);
+    after.encoders[0] = -32_760;
+    after.encoders[1] = 32_760;
+    after.encoders[2] = 90;
+
+    let delta = after.delta_from(&before);
+    assert_eq!(delta.encoders[..3], [16, -16, -10]);
+    assert_eq!(delta.encoders[3..], [0; 5]);
+}
+
+#[test]
+fn hat_and_clutch_changes_are_reported() {
+    let mut before = snapshot(0);
+    before.clutch_mode = KsClutchMode::IndependentAxis;
+    before.clutch_left = Some(40_000);
+    before.clutch_right = Some(10_000);
+    let mut after = before;
+    after.tick = 1;
+    after.hat = 2;
+    after.clutch_right = Some(40_000);
+
+    let delta = after.delta_with_clutch_threshold(&before, 30_000);
+    assert_eq!(
+        delta.hat,
+        Some(KsHatChange {
+            previous: 0,
+            current: 2
+        })
+    );
+    assert_eq!(delta.clutch, Some(KsClutchEdge::Pressed));
+    assert_eq!(
+        before.delta_with_clutch_threshold(&after, 30_000).clutch,
+        Some(KsClutchEdge::Released)
+    );
+
+    // No edge while either snapshot lacks clutch data.
+    let mut unknown = after;
+    unknown.clutch_right = None;
+    assert_eq!(unknown.delta_from(&before).clutch, None);
+}
+
+#[test]
+fn tracker_reports_tick_gaps() -> R {
+    let mut tracker = KsInputTracker::new(30_000);
+    assert!(tracker.update(snapshot(10)).is_none());
+
+    let next = tracker.update(snapshot(11)).ok_or("no delta for tick 11")?;
+    assert_eq!((next.ticks, next.missed_ticks()), (1, 0));
+
+    let gap = tracker.update(snapshot(15)).ok_or("no delta for tick 15")?;
+    assert_eq!((gap.ticks, gap.missed_ticks()), (4, 3));
+
+    let wrapped = tracker
+        .update(snapshot(u32::MAX))
+        .and_then(|_| tracker.update(snapshot(0)))
+        .ok_or("no delta across the tick wrap")?;
+    assert_eq!(wrapped.missed_ticks(), 0);
+
+    tracker.reset();
+    assert!(tracker.previous().is_none());
+    assert!(tracker.update(snapshot(1)).is_none());
+    Ok(())
+}
@@ -0,0 +1,203 @@
+//! KS report maps loaded from JSON map files.
+#![cfg(feature = "serde")]
+
+use racing_wheel_ks::{
+    KS_BUTTON_BYTES, KsAxisSource, KsBitSource, KsByteSource, KsClutchMode, KsJoystickMode,
+    KsMapError, KsMapFile, KsReportMap, KsRotaryMode, load_map_directory, load_map_files,
+};
+
+type R = Result<(), Box<dyn std::error::Error>>;
+
+/// A map touching every source type and mode hint.
+fn full_map() -> KsReportMap {
+    let mut map = KsReportMap::empty();
+    map.report_id = Some(0x01);
+    map.buttons_offset = Some(1);
+    map.hat_offset = Some(17);
+    map.encoders[2] = Some(KsAxisSource::new(18, true));
+    map.encoders[3] = Some(KsAxisSource::new(20, true));
+    map.clutch_left_axis = Some(KsAxisSource::new(22, false));
+    map.clutch_right_axis = Some(KsAxisSource::new(24, false));
+    map.clutch_left_button = Some(KsBitSource::new(1, 0x40));
+    map.clutch_right_button = Some(KsBitSource::inverted(1, 0x80));
+    map.clutch_mode_hint = KsClutchMode::IndependentAxis;
+    map.rotary_mode_hint = KsRotaryMode::Knob;
+    map.left_rotary_axis = Some(KsAxisSource::new(26, true));
+    map.right_rotary_axis = Some(KsAxisSource::new(28, true));
+    map.joystick_mode_hint = KsJoystickMode::DPad;
+    map.joystick_hat = Some(KsByteSource::new(30));
+    map
+}
+
+fn map_file(id: &str, max_report_len: usize, map: KsReportMap) -> KsMapFile {
+    KsMapFile {
+        id: id.to_string(),
+        max_report_len,
+        map,
+    }
+}
+
+#[test]
+fn every_source_type_round_trips() -> R {
+    let map = full_map();
+    assert_eq!(KsReportMap::from_json(&map.to_json()?)?, map);
+
+    let file = map_file("ks-pro/fw-1.2", 32, map);
+    let parsed = KsMapFile::from_json(&file.to_json()?)?;
+    assert_eq!(parsed, file);
+    assert!(parsed.warnings().is_empty(), "{:?}", parsed.warnings());
+    Ok(())
+}
+
+#[test]
+fn parse_matches_for_code_built_and_round_tripped_maps() -> R {
+    let map = full_map();
+    let round_tripped = KsReportMap::from_json(&map.to_json()?)?;
+    let reports: Vec<Vec<u8>> = (0u8..8)
+        .map(|seed| {
+            let mut report: Vec<u8> = (0u8..32)
+                .map(|i| i.wrapping_mul(37).wrapping_add(seed.wrapping_mul(11)))
+                .collect();
+            report[0] = if seed == 7 { 0x02 } else { 0x01 };
+            report
+        })
+        .chain([vec![0x01, 0xFF, 0xFF], Vec::new()])
+        .collect();
+    for (tick, report) in reports.iter().enumerate() {
+        assert_eq!(
+            map.parse(tick as u32, report),
+            round_tripped.parse(tick as u32, report),
+            "report {report:02x?}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn omitted_fields_are_unmapped() -> R {
+    let map = KsReportMap::from_json(r#"{ "hat_offset": 4 }"#)?;
+    assert_eq!(
+        map,
+        KsReportMap {
+            hat_offset: Some(4),
+            ..KsReportMap::empty()
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn offsets_past_the_report_length_are_rejected() -> R {
+    let json = r#"{
+        "id": "ks/bad",
+        "max_report_len": 24,
+        "clutch_combined_axis": { "offset": 23, "signed": false }
+    }"#;
+    let result = KsMapFile::from_json(json);
+    let Err(err) = result else {
+        return Err("axis past the report end was accepted".into());
+    };
+    assert!(matches!(
+        err,
+        KsMapError::OffsetOutOfRange {
+            offset: 23,
+            width: 2,
+            max_report_len: 24,
+            ..
+        }
+    ));
+    assert!(err.to_string().contains("clutch_combined_axis"), "{err}");
+
+    let bit = r#"{ "id": "ks/bad", "max_report_len": 8,
+                   "clutch_left_button": { "offset": 8, "mask": 1, "invert": false } }"#;
+    assert!(matches!(
+        KsMapFile::from_json(bit),
+        Err(KsMapError::OffsetOutOfRange { .. })
+    ));
+
+    // Short reports zero-fill the button bitmap, so only its start must fit.
+    let buttons = r#"{ "id": "ks/short", "max_report_len": 8, "buttons_offset": 4 }"#;
+    KsMapFile::from_json(buttons)?;
+    Ok(())
+}
+
+#[test]
+fn unknown_modes_list_the_valid_names() {
+    let json = r#"{ "id": "ks/typo", "max_report_len": 8, "rotary_mode_hint": "Dial" }"#;
+    let message = KsMapFile::from_json(json)
+        .err()
+        .map(|err| err.to_string())
+        .unwrap_or_default();
+    assert!(message.contains("unknown variant `Dial`"), "{message}");
+    assert!(message.contains("`Button`, `Knob`"), "{message}");
+}
+
+#[test]
+fn overlapping_regions_are_warnings() -> R {
+    let json = format!(
+        r#"{{ "id": "ks/overlap", "max_report_len": 40, "buttons_offset": 2,
+              "encoders": [{{ "offset": 10, "signed": true }}, null, null, null,
+                           null, null, null, null],
+              "clutch_left_axis": {{ "offset": {}, "signed": false }},
+              "clutch_right_axis": {{ "offset": 31, "signed": false }} }}"#,
+        2 + KS_BUTTON_BYTES
+    );
+    let file = KsMapFile::from_json(&json)?;
+    let warnings: Vec<_> = file
+        .warnings()
+        .iter()
+        .map(|overlap| (overlap.first.clone(), overlap.second.clone()))
+        .collect();
+    assert_eq!(
+        warnings,
+        [("buttons_offset".to_string(), "encoders[0]".to_string())]
+    );
+
+    let mut adjacent = file.map;
+    adjacent.encoders[0] = None;
+    adjacent.clutch_right_axis = Some(KsAxisSource::new(19, false));
+    assert_eq!(adjacent.overlaps().len(), 1);
+    Ok(())
+}
+
+#[test]
+fn directories_load_maps_by_id() -> R {
+    let dir = tempfile::tempdir()?;
+    let moza = map_file("moza-ks/fw-2", 32, full_map());
+    std::fs::write(dir.path().join("moza-ks.json"), moza.to_json()?)?;
+    let minimal = map_file(
+        "generic/hat-only",
+        8,
+        KsReportMap::from_json(r#"{"hat_offset": 3}"#)?,
+    );
+    std::fs::write(dir.path().join("generic.json"), minimal.to_json()?)?;
+    std::fs::write(dir.path().join("notes.txt"), "not a map")?;
+
+    let maps = load_map_directory(dir.path())?;
+    assert_eq!(maps.len(), 2);
+    assert_eq!(maps.get("moza-ks/fw-2"), Some(&full_map()));
+    assert_eq!(
+        maps.get("generic/hat-only").and_then(|map| map.hat_offset),
+        Some(3)
+    );
+
+    let files = load_map_files(dir.path())?;
+    let names: Vec<_> = files
+        .iter()
+        .filter_map(|(path, _)| path.file_name()?.to_str())
+        .collect();
+    assert_eq!(names, ["generic.json", "moza-ks.json"]);
+
+    std::fs::write(dir.path().join("copy.json"), moza.to_json()?)?;
+    assert!(matches!(
+        load_map_directory(dir.path()),
+        Err(KsMapError::DuplicateId { .. })
+    ));
+
+    std::fs::write(dir.path().join("broken.json"), "{")?;
+    let Err(err) = load_map_directory(dir.path()) else {
+        return Err("broken map file was accepted".into());
+    };
+    assert!(err.to_string().contains("broken.json"), "{err}");
+    Ok(())
+}
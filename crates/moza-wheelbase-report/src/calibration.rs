@@ -0,0 +1,348 @@
+//! Calibrated normalization of raw wheelbase axes.
+//!
+//! [`WheelbaseCalibration`] holds one [`AxisCalibration`] per axis and turns a
+//! [`WheelbaseInputRaw`] into a [`WheelbaseInputNormalized`]: steering in
+//! `-1.0..=1.0` around its center, pedals in `0.0..=1.0`. Raw values outside
+//! the calibrated range clamp to its ends. [`CalibrationBuilder`] learns the
+//! ranges from samples taken while every control is moved to its extremes.
+
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::WheelbaseInputRaw;
+
+/// Why a calibration was rejected.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CalibrationError {
+    /// `min` is not below `max`, leaving nothing to scale against.
+    EmptyRange { min: u16, max: u16 },
+    /// The center is not strictly inside `min..max`.
+    CenterOutOfRange { min: u16, center: u16, max: u16 },
+    /// The deadzone is not a finite fraction in `0.0..1.0`.
+    InvalidDeadzone(f32),
+    /// The builder saw the axis but never saw it move.
+    AxisNotMoved { axis: &'static str, raw: u16 },
+}
+
+impl fmt::Display for CalibrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyRange { min, max } => {
+                write!(f, "calibration range {min}..={max} is empty")
+            }
+            Self::CenterOutOfRange { min, center, max } => {
+                write!(f, "center {center} is not strictly inside {min}..={max}")
+            }
+            Self::InvalidDeadzone(deadzone) => {
+                write!(f, "deadzone {deadzone} is not in 0.0..1.0")
+            }
+            Self::AxisNotMoved { axis, raw } => {
+                write!(f, "{axis} axis never moved from {raw}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CalibrationError {}
+
+/// Range, center, direction and deadzone of one raw axis.
+///
+/// Constructors validate, so a held value never divides by zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "AxisCalibrationFields", into = "AxisCalibrationFields")
+)]
+pub struct AxisCalibration {
+    min: u16,
+    center: Option<u16>,
+    max: u16,
+    invert: bool,
+    deadzone: f32,
+}
+
+impl Default for AxisCalibration {
+    /// The full `u16` range, uninverted, without a deadzone.
+    fn default() -> Self {
+        Self {
+            min: 0,
+            center: None,
+            max: u16::MAX,
+            invert: false,
+            deadzone: 0.0,
+        }
+    }
+}
+
+impl AxisCalibration {
+    pub fn new(min: u16, max: u16) -> Result<Self, CalibrationError> {
+        if min >= max {
+            return Err(CalibrationError::EmptyRange { min, max });
+        }
+        Ok(Self {
+            min,
+            max,
+            ..Self::default()
+        })
+    }
+
+    /// Set the raw value that maps to `0.0` on a centered axis.
+    ///
+    /// Without one, centered axes use the middle of the range.
+    pub fn with_center(mut self, center: u16) -> Result<Self, CalibrationError> {
+        if center <= self.min || center >= self.max {
+            return Err(CalibrationError::CenterOutOfRange {
+                min: self.min,
+                center,
+                max: self.max,
+            });
+        }
+        self.center = Some(center);
+        Ok(self)
+    }
+
+    pub fn with_invert(mut self, invert: bool) -> Self {
+        self.invert = invert;
+        self
+    }
+
+    /// Set the fraction of travel, from rest, that reads as zero.
+    ///
+    /// The remaining travel is rescaled so full travel still reads `1.0`.
+    pub fn with_deadzone(mut self, deadzone: f32) -> Result<Self, CalibrationError> {
+        if !(0.0..1.0).contains(&deadzone) {
+            return Err(CalibrationError::InvalidDeadzone(deadzone));
+        }
+        self.deadzone = deadzone;
+        Ok(self)
+    }
+
+    pub fn min(&self) -> u16 {
+        self.min
+    }
+
+    pub fn center(&self) -> Option<u16> {
+        self.center
+    }
+
+    pub fn max(&self) -> u16 {
+        self.max
+    }
+
+    pub fn invert(&self) -> bool {
+        self.invert
+    }
+
+    pub fn deadzone(&self) -> f32 {
+        self.deadzone
+    }
+
+    /// Map `raw` into `0.0..=1.0`, ignoring the center.
+    pub fn normalize_unipolar(&self, raw: u16) -> f32 {
+        let raw = raw.clamp(self.min, self.max);
+        let travel = f32::from(raw - self.min) / f32::from(self.max - self.min);
+        let travel = if self.invert { 1.0 - travel } else { travel };
+        self.apply_deadzone(travel).clamp(0.0, 1.0)
+    }
+
+    /// Map `raw` into `-1.0..=1.0` around the center.
+    ///
+    /// Each side of the center scales independently, so an off-middle center
+    /// still reaches both ends.
+    pub fn normalize_bipolar(&self, raw: u16) -> f32 {
+        let raw = f32::from(raw.clamp(self.min, self.max));
+        let (min, max) = (f32::from(self.min), f32::from(self.max));
+        let center = self.center.map_or((min + max) / 2.0, f32::from);
+        let value = if raw >= center {
+            (raw - center) / (max - center)
+        } else {
+            (raw - center) / (center - min)
+        };
+        let value = if self.invert { -value } else { value };
+        (value.signum() * self.apply_deadzone(value.abs())).clamp(-1.0, 1.0)
+    }
+
+    fn apply_deadzone(&self, travel: f32) -> f32 {
+        if travel <= self.deadzone {
+            0.0
+        } else {
+            (travel - self.deadzone) / (1.0 - self.deadzone)
+        }
+    }
+}
+
+/// Unvalidated [`AxisCalibration`] as persisted.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct AxisCalibrationFields {
+    min: u16,
+    #[serde(default)]
+    center: Option<u16>,
+    max: u16,
+    #[serde(default)]
+    invert: bool,
+    #[serde(default)]
+    deadzone: f32,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<AxisCalibrationFields> for AxisCalibration {
+    type Error = CalibrationError;
+
+    fn try_from(fields: AxisCalibrationFields) -> Result<Self, Self::Error> {
+        let axis = Self::new(fields.min, fields.max)?
+            .with_invert(fields.invert)
+            .with_deadzone(fields.deadzone)?;
+        match fields.center {
+            Some(center) => axis.with_center(center),
+            None => Ok(axis),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<AxisCalibration> for AxisCalibrationFields {
+    fn from(axis: AxisCalibration) -> Self {
+        Self {
+            min: axis.min,
+            center: axis.center,
+            max: axis.max,
+            invert: axis.invert,
+            deadzone: axis.deadzone,
+        }
+    }
+}
+
+/// Calibration for every axis of a wheelbase report.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WheelbaseCalibration {
+    pub steering: AxisCalibration,
+    pub throttle: AxisCalibration,
+    pub brake: AxisCalibration,
+    pub clutch: AxisCalibration,
+    pub handbrake: AxisCalibration,
+}
+
+/// Wheelbase axes after calibration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WheelbaseInputNormalized {
+    /// Steering in `-1.0..=1.0`, `0.0` at center
+    pub steering: f32,
+    /// Throttle in `0.0..=1.0`
+    pub throttle: f32,
+    /// Brake in `0.0..=1.0`
+    pub brake: f32,
+    /// Clutch in `0.0..=1.0`, if reported by hardware
+    pub clutch: Option<f32>,
+    /// Handbrake in `0.0..=1.0`, if reported by hardware
+    pub handbrake: Option<f32>,
+}
+
+impl WheelbaseInputRaw {
+    /// Scale the raw axes through `cal`.
+    pub fn normalize(&self, cal: &WheelbaseCalibration) -> WheelbaseInputNormalized {
+        WheelbaseInputNormalized {
+            steering: cal.steering.normalize_bipolar(self.steering),
+            throttle: cal.throttle.normalize_unipolar(self.pedals.throttle),
+            brake: cal.brake.normalize_unipolar(self.pedals.brake),
+            clutch: self
+                .pedals
+                .clutch
+                .map(|raw| cal.clutch.normalize_unipolar(raw)),
+            handbrake: self
+                .pedals
+                .handbrake
+                .map(|raw| cal.handbrake.normalize_unipolar(raw)),
+        }
+    }
+}
+
+/// Smallest and largest raw value seen on one axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ObservedRange {
+    min: u16,
+    max: u16,
+}
+
+impl ObservedRange {
+    fn observe(range: &mut Option<Self>, raw: u16) {
+        let range = range.get_or_insert(Self { min: raw, max: raw });
+        range.min = range.min.min(raw);
+        range.max = range.max.max(raw);
+    }
+}
+
+/// Learns a [`WheelbaseCalibration`] from raw samples.
+///
+/// Feed every report through [`observe`](Self::observe) while the driver
+/// moves each control to both extremes, and
+/// [`observe_center`](Self::observe_center) one taken with the wheel straight.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CalibrationBuilder {
+    steering: Option<ObservedRange>,
+    throttle: Option<ObservedRange>,
+    brake: Option<ObservedRange>,
+    clutch: Option<ObservedRange>,
+    handbrake: Option<ObservedRange>,
+    steering_center: Option<u16>,
+}
+
+impl CalibrationBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Widen each reported axis's range to include `sample`.
+    pub fn observe(&mut self, sample: &WheelbaseInputRaw) {
+        ObservedRange::observe(&mut self.steering, sample.steering);
+        ObservedRange::observe(&mut self.throttle, sample.pedals.throttle);
+        ObservedRange::observe(&mut self.brake, sample.pedals.brake);
+        if let Some(clutch) = sample.pedals.clutch {
+            ObservedRange::observe(&mut self.clutch, clutch);
+        }
+        if let Some(handbrake) = sample.pedals.handbrake {
+            ObservedRange::observe(&mut self.handbrake, handbrake);
+        }
+    }
+
+    /// Record `sample`'s steering as the wheel's center.
+    pub fn observe_center(&mut self, sample: &WheelbaseInputRaw) {
+        self.steering_center = Some(sample.steering);
+        self.observe(sample);
+    }
+
+    /// The calibration learned so far.
+    ///
+    /// Axes that were never reported keep the full default range; axes that
+    /// were reported but never moved are an error.
+    pub fn build(&self) -> Result<WheelbaseCalibration, CalibrationError> {
+        let mut steering = learned_axis("steering", self.steering)?;
+        if let Some(center) = self.steering_center {
+            steering = steering.with_center(center)?;
+        }
+        Ok(WheelbaseCalibration {
+            steering,
+            throttle: learned_axis("throttle", self.throttle)?,
+            brake: learned_axis("brake", self.brake)?,
+            clutch: learned_axis("clutch", self.clutch)?,
+            handbrake: learned_axis("handbrake", self.handbrake)?,
+        })
+    }
+}
+
+fn learned_axis(
+    axis: &'static str,
+    range: Option<ObservedRange>,
+) -> Result<AxisCalibration, CalibrationError> {
+    match range {
+        None => Ok(AxisCalibration::default()),
+        Some(ObservedRange { min, max }) if min == max => {
+            Err(CalibrationError::AxisNotMoved { axis, raw: min })
+        }
+        Some(ObservedRange { min, max }) => AxisCalibration::new(min, max),
+    }
+}
@@ -6,6 +6,23 @@
 #![deny(static_mut_refs)]
 #![deny(clippy::unwrap_used)]
 
+use openracing_byte_reader::ByteReader;
+
+mod calibration;
+mod rim;
+
+pub use calibration::{
+    AxisCalibration, CalibrationBuilder, CalibrationError, WheelbaseCalibration,
+    WheelbaseInputNormalized,
+};
+
+#[cfg(feature = "serde")]
+pub use rim::RimRegistryError;
+pub use rim::{
+    RimDescriptor, RimRegistry, WheelbaseRimInput, builtin_ks_rim_map,
+    parse_wheelbase_input_with_rim,
+};
+
 /// [ADR-0007]: Multi-Vendor HID Protocol Architecture
 /// This crate follows the "SRP Microcrate" pattern for vendor-specific HID protocols.
 /// Report ID and byte offsets for wheelbase-aggregated input reports.
@@ -36,6 +53,19 @@ pub mod input_report {
     pub const ROTARY_LEN: usize = 2;
 }
 
+/// Known Moza rim IDs when attached to a compatible wheelbase.
+///
+/// These are rim identity values reported through the wheelbase transport in
+/// [`WheelbaseInputRaw::funky`], not standalone USB product IDs.
+pub mod rim_ids {
+    pub const CS_V2: u8 = 0x01;
+    pub const GS_V2: u8 = 0x02;
+    pub const RS_V2: u8 = 0x03;
+    pub const FSR: u8 = 0x04;
+    pub const KS: u8 = 0x05;
+    pub const ES: u8 = 0x06;
+}
+
 /// Minimum bytes required for a valid wheelbase report containing steering,
 /// throttle, and brake axes.
 pub const MIN_REPORT_LEN: usize = input_report::BRAKE_START + 2;
@@ -108,22 +138,47 @@ pub struct WheelbaseInputRaw {
     pub hat: u8,
     /// Vendor-specific byte immediately after `hat`.
     ///
-    /// OpenRacing currently treats this as an opaque discriminator; some firmwares
-    /// appear to use it as a rim identifier and it is used to gate rim-specific parsing.
+    /// Some firmwares report the attached rim here (see [`rim_ids`]);
+    /// [`parse_wheelbase_input_with_rim`] uses it to select rim-specific parsing.
     pub funky: u8,
     /// Rotary encoder raw bytes
     pub rotary: [u8; input_report::ROTARY_LEN],
 }
 
-/// Parse a little-endian `u16` axis from `report` at `start`.
+/// A [`WheelbaseInputRaw`] stamped with when its report arrived.
 ///
-/// NOTE: This helper is intentionally duplicated in other tiny protocol microcrates
-/// (e.g. `racing-wheel-hbp`) to keep them dependency-minimal. Keep implementations in sync.
+/// `timestamp_ns` must come from the clock telemetry frames are stamped
+/// with, so inputs can be lined up against them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimedWheelbaseInput {
+    /// Arrival time of the report, in nanoseconds
+    pub timestamp_ns: u64,
+    /// Parsed report
+    pub input: WheelbaseInputRaw,
+}
+
+impl TimedWheelbaseInput {
+    pub fn new(timestamp_ns: u64, input: WheelbaseInputRaw) -> Self {
+        Self {
+            timestamp_ns,
+            input,
+        }
+    }
+}
+
+/// Parse a little-endian `u16` axis from `report` at `start`.
 pub fn parse_axis(report: &[u8], start: usize) -> Option<u16> {
-    if report.len() < start.saturating_add(2) {
-        return None;
+    ByteReader::new(report).u16_le_at(start).ok()
+}
+
+/// Copy the bytes present at `start` into a zeroed array, truncating short reports.
+fn zero_filled<const N: usize>(report: &[u8], start: usize) -> [u8; N] {
+    let mut out = [0u8; N];
+    let present = ByteReader::new(report).bytes_up_to(start, N);
+    for (slot, byte) in out.iter_mut().zip(present) {
+        *slot = *byte;
     }
-    Some(u16::from_le_bytes([report[start], report[start + 1]]))
+    out
 }
 
 fn parse_wheelbase_pedal_axes_from_report(
@@ -172,27 +227,11 @@ pub fn parse_wheelbase_input_report(report: &[u8]) -> Option<WheelbaseInputRaw>
     let steering = report.axis_u16_le(input_report::STEERING_START)?;
     let pedals = parse_wheelbase_pedal_axes_from_report(&report)?;
 
-    let mut buttons = [0u8; input_report::BUTTONS_LEN];
     let bytes = report.report_bytes();
-    if bytes.len() > input_report::BUTTONS_START {
-        let end = bytes
-            .len()
-            .min(input_report::BUTTONS_START + input_report::BUTTONS_LEN);
-        let count = end - input_report::BUTTONS_START;
-        buttons[..count].copy_from_slice(&bytes[input_report::BUTTONS_START..end]);
-    }
-
+    let buttons = zero_filled(bytes, input_report::BUTTONS_START);
     let hat = report.byte(input_report::HAT_START).unwrap_or(0);
     let funky = report.byte(input_report::FUNKY_START).unwrap_or(0);
-
-    let mut rotary = [0u8; input_report::ROTARY_LEN];
-    if bytes.len() > input_report::ROTARY_START {
-        let end = bytes
-            .len()
-            .min(input_report::ROTARY_START + input_report::ROTARY_LEN);
-        let count = end - input_report::ROTARY_START;
-        rotary[..count].copy_from_slice(&bytes[input_report::ROTARY_START..end]);
-    }
+    let rotary = zero_filled(bytes, input_report::ROTARY_START);
 
     Some(WheelbaseInputRaw {
         steering,
@@ -0,0 +1,296 @@
+//! Rim identification through the wheelbase report's funky byte.
+//!
+//! A [`RimRegistry`] maps [`WheelbaseInputRaw::funky`] values to rim
+//! descriptions. Rims with a KS report map get their controls parsed into a
+//! [`KsReportSnapshot`]; unknown values still yield the base report.
+
+use std::collections::BTreeMap;
+#[cfg(feature = "serde")]
+use std::fmt;
+#[cfg(feature = "serde")]
+use std::path::{Path, PathBuf};
+
+use racing_wheel_ks::{KsByteSource, KsReportMap, KsReportSnapshot};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{WheelbaseInputRaw, input_report, parse_wheelbase_input_report, rim_ids};
+
+/// What is known about one rim model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RimDescriptor {
+    pub name: String,
+    /// Button bits the rim drives in the report's button bitmap.
+    pub button_count: u16,
+    pub has_dual_clutch: bool,
+    pub has_rotaries: bool,
+    /// Map applied to the whole wheelbase report, at report offsets, for
+    /// the rim's KS-style controls.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ks_map: Option<KsReportMap>,
+}
+
+impl RimDescriptor {
+    /// A rim without rim-specific controls, using the whole button bitmap.
+    pub fn basic(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            button_count: FULL_BUTTON_COUNT,
+            has_dual_clutch: false,
+            has_rotaries: false,
+            ks_map: None,
+        }
+    }
+}
+
+const FULL_BUTTON_COUNT: u16 = (input_report::BUTTONS_LEN * 8) as u16;
+
+/// KS map for the KS rim on the wheelbase path: buttons and hat only, the
+/// layout confirmed so far; clutch and encoder offsets await captures.
+pub fn builtin_ks_rim_map() -> KsReportMap {
+    KsReportMap {
+        report_id: Some(input_report::REPORT_ID),
+        buttons_offset: Some(input_report::BUTTONS_START),
+        hat_offset: Some(input_report::HAT_START),
+        joystick_hat: Some(KsByteSource::new(input_report::HAT_START)),
+        ..KsReportMap::empty()
+    }
+}
+
+/// Rim descriptors keyed by funky byte.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RimRegistry {
+    rims: BTreeMap<u8, RimDescriptor>,
+}
+
+impl RimRegistry {
+    /// A registry that recognizes no rims.
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// The known Moza rims of [`rim_ids`].
+    ///
+    /// Only the KS entry describes rim-specific controls; the others claim
+    /// the full button bitmap until captures pin their layouts down.
+    pub fn builtin() -> Self {
+        let mut registry = Self::empty();
+        for (funky, name) in [
+            (rim_ids::CS_V2, "CS V2"),
+            (rim_ids::GS_V2, "GS V2"),
+            (rim_ids::RS_V2, "RS V2"),
+            (rim_ids::FSR, "FSR"),
+            (rim_ids::ES, "ES"),
+        ] {
+            registry.insert(funky, RimDescriptor::basic(name));
+        }
+        registry.insert(
+            rim_ids::KS,
+            RimDescriptor {
+                has_dual_clutch: true,
+                has_rotaries: true,
+                ks_map: Some(builtin_ks_rim_map()),
+                ..RimDescriptor::basic("KS")
+            },
+        );
+        registry
+    }
+
+    /// Register `rim` for `funky`, returning the descriptor it replaces.
+    pub fn insert(&mut self, funky: u8, rim: RimDescriptor) -> Option<RimDescriptor> {
+        self.rims.insert(funky, rim)
+    }
+
+    pub fn remove(&mut self, funky: u8) -> Option<RimDescriptor> {
+        self.rims.remove(&funky)
+    }
+
+    pub fn get(&self, funky: u8) -> Option<&RimDescriptor> {
+        self.rims.get(&funky)
+    }
+
+    /// Registered rims in funky-byte order.
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &RimDescriptor)> {
+        self.rims.iter().map(|(funky, rim)| (*funky, rim))
+    }
+
+    pub fn len(&self) -> usize {
+        self.rims.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rims.is_empty()
+    }
+}
+
+/// A wheelbase report parsed with the rim it identifies.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WheelbaseRimInput<'r> {
+    pub input: WheelbaseInputRaw,
+    /// `None` when the funky byte names no registered rim.
+    pub rim: Option<&'r RimDescriptor>,
+    /// The rim's controls, when it has a KS map that accepts the report.
+    pub ks_snapshot: Option<KsReportSnapshot>,
+}
+
+/// Parse a wheelbase report and identify its rim through `registry`.
+///
+/// Returns `None` only when the base report is invalid; see
+/// [`parse_wheelbase_input_report`].
+pub fn parse_wheelbase_input_with_rim<'r>(
+    report: &[u8],
+    registry: &'r RimRegistry,
+) -> Option<WheelbaseRimInput<'r>> {
+    let input = parse_wheelbase_input_report(report)?;
+    let rim = registry.get(input.funky);
+    let ks_snapshot = rim
+        .and_then(|rim| rim.ks_map.as_ref())
+        .and_then(|map| map.parse(0, report));
+    Some(WheelbaseRimInput {
+        input,
+        rim,
+        ks_snapshot,
+    })
+}
+
+/// Why a rim registry file was rejected.
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub enum RimRegistryError {
+    /// The JSON is malformed or an entry has missing or mistyped fields.
+    Json(serde_json::Error),
+    /// The file could not be read.
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// The file at `path` is malformed or has an invalid entry.
+    File {
+        path: PathBuf,
+        source: Box<RimRegistryError>,
+    },
+    /// Two entries share a funky byte.
+    DuplicateFunky(u8),
+    /// An entry has a blank name.
+    EmptyName { funky: u8 },
+    /// An entry claims more buttons than the report's bitmap holds.
+    TooManyButtons { funky: u8, button_count: u16 },
+}
+
+#[cfg(feature = "serde")]
+impl fmt::Display for RimRegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(err) => write!(f, "invalid rim registry JSON: {err}"),
+            Self::Io { path, source } => {
+                write!(
+                    f,
+                    "failed to read rim registry {}: {source}",
+                    path.display()
+                )
+            }
+            Self::File { path, source } => write!(f, "{}: {source}", path.display()),
+            Self::DuplicateFunky(funky) => {
+                write!(f, "funky byte {funky:#04x} is listed more than once")
+            }
+            Self::EmptyName { funky } => write!(f, "rim {funky:#04x} has an empty name"),
+            Self::TooManyButtons {
+                funky,
+                button_count,
+            } => write!(
+                f,
+                "rim {funky:#04x} claims {button_count} buttons but reports carry at most {FULL_BUTTON_COUNT}"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::error::Error for RimRegistryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Json(err) => Some(err),
+            Self::Io { source, .. } => Some(source),
+            Self::File { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for RimRegistryError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+/// One rim of a registry file.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct RimEntry {
+    funky: u8,
+    #[serde(flatten)]
+    rim: RimDescriptor,
+}
+
+#[cfg(feature = "serde")]
+impl RimRegistry {
+    /// Add the rims of a JSON array, replacing registered ones with the same
+    /// funky byte. Nothing is added if any entry is invalid.
+    ///
+    /// ```json
+    /// [{ "funky": 7, "name": "Custom GT", "button_count": 12,
+    ///    "has_dual_clutch": true, "has_rotaries": false }]
+    /// ```
+    ///
+    /// Returns how many rims were merged.
+    pub fn merge_json(&mut self, json: &str) -> Result<usize, RimRegistryError> {
+        let entries: Vec<RimEntry> = serde_json::from_str(json)?;
+        let mut seen = BTreeMap::new();
+        for entry in &entries {
+            if seen.insert(entry.funky, ()).is_some() {
+                return Err(RimRegistryError::DuplicateFunky(entry.funky));
+            }
+            if entry.rim.name.trim().is_empty() {
+                return Err(RimRegistryError::EmptyName { funky: entry.funky });
+            }
+            if entry.rim.button_count > FULL_BUTTON_COUNT {
+                return Err(RimRegistryError::TooManyButtons {
+                    funky: entry.funky,
+                    button_count: entry.rim.button_count,
+                });
+            }
+        }
+        let count = entries.len();
+        for entry in entries {
+            self.insert(entry.funky, entry.rim);
+        }
+        Ok(count)
+    }
+
+    /// [`Self::merge_json`] the file at `path`.
+    pub fn merge_file(&mut self, path: &Path) -> Result<usize, RimRegistryError> {
+        let json = std::fs::read_to_string(path).map_err(|source| RimRegistryError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        self.merge_json(&json)
+            .map_err(|err| RimRegistryError::File {
+                path: path.to_path_buf(),
+                source: Box::new(err),
+            })
+    }
+
+    /// The registered rims as a JSON array accepted by [`Self::merge_json`].
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        let entries: Vec<RimEntry> = self
+            .iter()
+            .map(|(funky, rim)| RimEntry {
+                funky,
+                rim: rim.clone(),
+            })
+            .collect();
+        serde_json::to_string_pretty(&entries)
+    }
+}
@@ -0,0 +1,212 @@
+//! Calibrated normalization of wheelbase axes.
+
+use proptest::prelude::*;
+use racing_wheel_moza_wheelbase_report::{
+    AxisCalibration, CalibrationBuilder, CalibrationError, WheelbaseCalibration, WheelbaseInputRaw,
+    WheelbasePedalAxesRaw, input_report,
+};
+
+type R = Result<(), Box<dyn std::error::Error>>;
+
+fn sample(steering: u16, throttle: u16, brake: u16, clutch: Option<u16>) -> WheelbaseInputRaw {
+    WheelbaseInputRaw {
+        steering,
+        pedals: WheelbasePedalAxesRaw {
+            throttle,
+            brake,
+            clutch,
+            handbrake: None,
+        },
+        buttons: [0; input_report::BUTTONS_LEN],
+        hat: 0,
+        funky: 0,
+        rotary: [0; input_report::ROTARY_LEN],
+    }
+}
+
+fn approx(actual: f32, expected: f32) -> bool {
+    (actual - expected).abs() < 1e-4
+}
+
+#[test]
+fn steering_is_centered_on_the_calibrated_center() -> R {
+    let cal = WheelbaseCalibration {
+        steering: AxisCalibration::new(1000, 5000)?.with_center(2000)?,
+        ..WheelbaseCalibration::default()
+    };
+    let at = |raw| sample(raw, 0, 0, None).normalize(&cal).steering;
+
+    assert!(approx(at(2000), 0.0));
+    assert!(approx(at(1000), -1.0));
+    assert!(approx(at(1500), -0.5));
+    assert!(approx(at(5000), 1.0));
+    assert!(approx(at(3500), 0.5));
+    // Out-of-range raw values clamp rather than extrapolate.
+    assert!(approx(at(0), -1.0));
+    assert!(approx(at(u16::MAX), 1.0));
+    Ok(())
+}
+
+#[test]
+fn pedals_apply_inversion_and_deadzone() -> R {
+    let cal = WheelbaseCalibration {
+        throttle: AxisCalibration::new(100, 1100)?.with_deadzone(0.1)?,
+        brake: AxisCalibration::new(0, 1000)?.with_invert(true),
+        ..WheelbaseCalibration::default()
+    };
+
+    let rest = sample(0x8000, 150, 1000, None).normalize(&cal);
+    assert!(approx(rest.throttle, 0.0));
+    assert!(approx(rest.brake, 0.0));
+
+    let half = sample(0x8000, 600, 500, None).normalize(&cal);
+    assert!(approx(half.throttle, (0.5 - 0.1) / 0.9));
+    assert!(approx(half.brake, 0.5));
+
+    let full = sample(0x8000, 2000, 0, Some(0xFFFF)).normalize(&cal);
+    assert!(approx(full.throttle, 1.0));
+    assert!(approx(full.brake, 1.0));
+    assert_eq!(full.clutch, Some(1.0));
+    assert_eq!(full.handbrake, None);
+    Ok(())
+}
+
+#[test]
+fn invalid_axis_calibrations_are_rejected() {
+    assert_eq!(
+        AxisCalibration::new(500, 500),
+        Err(CalibrationError::EmptyRange { min: 500, max: 500 })
+    );
+    assert!(AxisCalibration::new(600, 500).is_err());
+    assert!(
+        AxisCalibration::new(0, 100)
+            .and_then(|axis| axis.with_center(100))
+            .is_err()
+    );
+    for deadzone in [-0.1, 1.0, f32::NAN, f32::INFINITY] {
+        assert!(
+            AxisCalibration::new(0, 100)
+                .and_then(|axis| axis.with_deadzone(deadzone))
+                .is_err(),
+            "{deadzone}"
+        );
+    }
+}
+
+#[test]
+fn builder_learns_extremes_and_center() -> R {
+    let mut builder = CalibrationBuilder::new();
+    builder.observe_center(&sample(31000, 200, 300, None));
+    builder.observe(&sample(1000, 9000, 300, None));
+    builder.observe(&sample(60000, 200, 8000, None));
+
+    let cal = builder.build()?;
+    assert_eq!(
+        (
+            cal.steering.min(),
+            cal.steering.center(),
+            cal.steering.max()
+        ),
+        (1000, Some(31000), 60000)
+    );
+    assert_eq!((cal.throttle.min(), cal.throttle.max()), (200, 9000));
+    assert_eq!((cal.brake.min(), cal.brake.max()), (300, 8000));
+    // The clutch was never reported, so it keeps the full range.
+    assert_eq!(cal.clutch, AxisCalibration::default());
+
+    let straight = sample(31000, 200, 300, None).normalize(&cal);
+    assert!(approx(straight.steering, 0.0));
+    Ok(())
+}
+
+#[test]
+fn builder_rejects_axes_that_never_moved() {
+    let mut builder = CalibrationBuilder::new();
+    builder.observe(&sample(1000, 0, 0, Some(42)));
+    builder.observe(&sample(60000, 1000, 1000, Some(42)));
+
+    let err = builder.build().err();
+    assert_eq!(
+        err,
+        Some(CalibrationError::AxisNotMoved {
+            axis: "clutch",
+            raw: 42
+        })
+    );
+    assert_eq!(
+        err.map(|err| err.to_string()).unwrap_or_default(),
+        "clutch axis never moved from 42"
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn calibration_round_trips_through_json_and_is_validated() -> R {
+    let cal = WheelbaseCalibration {
+        steering: AxisCalibration::new(1000, 5000)?.with_center(2000)?,
+        throttle: AxisCalibration::new(100, 1100)?
+            .with_deadzone(0.05)?
+            .with_invert(true),
+        ..WheelbaseCalibration::default()
+    };
+    let json = serde_json::to_string(&cal)?;
+    assert_eq!(serde_json::from_str::<WheelbaseCalibration>(&json)?, cal);
+
+    let empty = json.replace(r#""min":1000"#, r#""min":5000"#);
+    let message = serde_json::from_str::<WheelbaseCalibration>(&empty)
+        .err()
+        .map(|err| err.to_string())
+        .unwrap_or_default();
+    assert!(message.contains("range 5000..=5000 is empty"), "{message}");
+    Ok(())
+}
+
+fn axis_calibration() -> impl Strategy<Value = AxisCalibration> {
+    (
+        any::<u16>(),
+        any::<u16>(),
+        any::<u16>(),
+        any::<bool>(),
+        0.0f32..0.99,
+    )
+        .prop_filter_map("empty range", |(a, b, c, invert, deadzone)| {
+            let (min, max) = (a.min(b), a.max(b));
+            let axis = AxisCalibration::new(min, max)
+                .ok()?
+                .with_invert(invert)
+                .with_deadzone(deadzone)
+                .ok()?;
+            // A center outside the range falls back to the midpoint.
+            Some(axis.with_center(c).unwrap_or(axis))
+        })
+}
+
+proptest! {
+    #![proptest_config(proptest::test_runner::Config::with_cases(512))]
+
+    #[test]
+    fn prop_normalized_axes_stay_in_range(
+        steering in axis_calibration(),
+        pedal in axis_calibration(),
+        raw in any::<[u16; 4]>(),
+    ) {
+        let cal = WheelbaseCalibration {
+            steering,
+            throttle: pedal,
+            brake: pedal,
+            clutch: pedal,
+            handbrake: pedal,
+        };
+        let out = sample(raw[0], raw[1], raw[2], Some(raw[3])).normalize(&cal);
+        prop_assert!((-1.0..=1.0).contains(&out.steering), "{out:?}");
+        for pedal in [out.throttle, out.brake, out.clutch.unwrap_or(0.0)] {
+            prop_assert!((0.0..=1.0).contains(&pedal), "{out:?}");
+        }
+    }
+
+    #[test]
+    fn prop_empty_ranges_error_at_construction(raw in any::<u16>(), below in any::<u16>()) {
+        prop_assert!(AxisCalibration::new(raw, raw).is_err());
+        prop_assert!(AxisCalibration::new(raw, raw.min(below)).is_err());
+    }
+}
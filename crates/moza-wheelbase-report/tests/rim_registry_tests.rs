@@ -0,0 +1,163 @@
+//! Rim identification through the funky byte.
+
+use racing_wheel_moza_wheelbase_report::{
+    RimDescriptor, RimRegistry, builtin_ks_rim_map, input_report, parse_wheelbase_input_with_rim,
+    rim_ids,
+};
+
+type R = Result<(), Box<dyn std::error::Error>>;
+
+fn report_with_funky(funky: u8) -> [u8; input_report::ROTARY_START + input_report::ROTARY_LEN] {
+    let mut report = [0u8; input_report::ROTARY_START + input_report::ROTARY_LEN];
+    report[0] = input_report::REPORT_ID;
+    report[input_report::STEERING_START..input_report::STEERING_START + 2]
+        .copy_from_slice(&0x8000u16.to_le_bytes());
+    report[input_report::BUTTONS_START] = 0b0000_0101;
+    report[input_report::HAT_START] = 0x03;
+    report[input_report::FUNKY_START] = funky;
+    report
+}
+
+#[test]
+fn known_funky_value_yields_rim_and_ks_snapshot() -> R {
+    let registry = RimRegistry::builtin();
+    let report = report_with_funky(rim_ids::KS);
+
+    let parsed =
+        parse_wheelbase_input_with_rim(&report, &registry).ok_or("KS report did not parse")?;
+    let rim = parsed.rim.ok_or("KS rim not identified")?;
+    assert_eq!(rim.name, "KS");
+    assert!(rim.has_dual_clutch && rim.has_rotaries);
+
+    let snapshot = parsed.ks_snapshot.ok_or("KS rim produced no snapshot")?;
+    assert_eq!(snapshot.buttons[0], 0b0000_0101);
+    assert_eq!(snapshot.hat, 0x03);
+    assert_eq!(parsed.input.steering, 0x8000);
+    Ok(())
+}
+
+#[test]
+fn unknown_funky_value_still_parses_the_base_report() -> R {
+    let registry = RimRegistry::builtin();
+    let report = report_with_funky(0x42);
+
+    let parsed =
+        parse_wheelbase_input_with_rim(&report, &registry).ok_or("base report did not parse")?;
+    assert_eq!(parsed.rim, None);
+    assert_eq!(parsed.ks_snapshot, None);
+    assert_eq!(parsed.input.funky, 0x42);
+    assert_eq!(parsed.input.buttons[0], 0b0000_0101);
+
+    // Rims without a KS map are identified but carry no snapshot.
+    let fsr = parse_wheelbase_input_with_rim(&report_with_funky(rim_ids::FSR), &registry)
+        .ok_or("FSR report did not parse")?;
+    assert_eq!(fsr.rim.map(|rim| rim.name.as_str()), Some("FSR"));
+    assert_eq!(fsr.ks_snapshot, None);
+
+    assert!(parse_wheelbase_input_with_rim(&[0x02, 0, 0, 0, 0, 0, 0], &registry).is_none());
+    Ok(())
+}
+
+#[test]
+fn rims_can_be_registered_at_runtime() -> R {
+    let mut registry = RimRegistry::builtin();
+    let custom = RimDescriptor {
+        button_count: 12,
+        ks_map: Some(builtin_ks_rim_map()),
+        ..RimDescriptor::basic("Custom GT")
+    };
+    assert_eq!(registry.insert(0x42, custom.clone()), None);
+
+    let parsed = parse_wheelbase_input_with_rim(&report_with_funky(0x42), &registry)
+        .ok_or("custom rim report did not parse")?;
+    assert_eq!(parsed.rim, Some(&custom));
+    assert!(parsed.ks_snapshot.is_some());
+
+    let replaced = registry.insert(rim_ids::ES, RimDescriptor::basic("ES (custom)"));
+    assert_eq!(replaced.map(|rim| rim.name), Some("ES".to_string()));
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+mod json {
+    use super::{R, report_with_funky};
+    use racing_wheel_moza_wheelbase_report::{
+        RimRegistry, RimRegistryError, parse_wheelbase_input_with_rim, rim_ids,
+    };
+
+    #[test]
+    fn registry_files_extend_the_builtin_table() -> R {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("rims.json");
+        std::fs::write(
+            &path,
+            r#"[{ "funky": 66, "name": "Custom GT", "button_count": 12,
+                  "has_dual_clutch": true, "has_rotaries": false,
+                  "ks_map": { "report_id": 1, "buttons_offset": 11 } }]"#,
+        )?;
+        let mut registry = RimRegistry::builtin();
+        assert_eq!(registry.merge_file(&path)?, 1);
+
+        let parsed = parse_wheelbase_input_with_rim(&report_with_funky(0x42), &registry)
+            .ok_or("custom rim report did not parse")?;
+        assert_eq!(parsed.rim.map(|rim| rim.name.as_str()), Some("Custom GT"));
+        let snapshot = parsed
+            .ks_snapshot
+            .ok_or("custom rim produced no snapshot")?;
+        assert_eq!(snapshot.buttons[0], 0b0000_0101);
+
+        let mut reloaded = RimRegistry::empty();
+        reloaded.merge_json(&registry.to_json()?)?;
+        assert_eq!(reloaded, registry);
+        Ok(())
+    }
+
+    #[test]
+    fn registry_json_errors_are_descriptive() -> R {
+        let mut registry = RimRegistry::builtin();
+        let missing = registry
+            .merge_json(r#"[{ "funky": 66, "name": "GT", "has_rotaries": true }]"#)
+            .err()
+            .map(|err| err.to_string())
+            .unwrap_or_default();
+        assert!(
+            missing.contains("missing field `button_count`"),
+            "{missing}"
+        );
+
+        let duplicate = r#"[
+            { "funky": 66, "name": "A", "button_count": 1, "has_dual_clutch": false, "has_rotaries": false },
+            { "funky": 66, "name": "B", "button_count": 1, "has_dual_clutch": false, "has_rotaries": false }
+        ]"#;
+        let err = registry
+            .merge_json(duplicate)
+            .err()
+            .ok_or("duplicate accepted")?;
+        assert!(matches!(err, RimRegistryError::DuplicateFunky(0x42)));
+        assert_eq!(err.to_string(), "funky byte 0x42 is listed more than once");
+
+        let too_many = r#"[{ "funky": 67, "name": "C", "button_count": 200,
+                             "has_dual_clutch": false, "has_rotaries": false }]"#;
+        let message = registry
+            .merge_json(too_many)
+            .err()
+            .map(|err| err.to_string())
+            .unwrap_or_default();
+        assert!(message.contains("claims 200 buttons"), "{message}");
+        // Rejected merges leave the registry untouched.
+        assert_eq!(registry, RimRegistry::builtin());
+
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("broken.json");
+        std::fs::write(&path, "[{")?;
+        let message = registry
+            .merge_file(&path)
+            .err()
+            .map(|err| err.to_string())
+            .unwrap_or_default();
+        assert!(message.contains("broken.json"), "{message}");
+        assert!(message.contains("line 1"), "{message}");
+        assert!(registry.get(rim_ids::KS).is_some());
+        Ok(())
+    }
+}
@@ -0,0 +1,238 @@
+//! # openracing-byte-reader
+//!
+//! Bounds-checked little-endian reads for packet parsers fed by untrusted
+//! input (UDP telemetry, HID reports).
+//!
+//! Every read computes its end offset with checked arithmetic and returns a
+//! [`ReadError`] naming the attempted offset and length instead of panicking,
+//! so a truncated or hostile packet can never index out of bounds.
+//!
+//! ```rust
+//! use openracing_byte_reader::ByteReader;
+//!
+//! let packet = [0x34, 0x12, 0x00, 0x00, 0x80, 0x3f];
+//! let mut reader = ByteReader::new(&packet);
+//! assert_eq!(reader.u16_le(), Ok(0x1234));
+//! assert_eq!(reader.f32_le_at(2), Ok(1.0));
+//! assert!(reader.u32_le_at(4).is_err());
+//! ```
+
+#![no_std]
+#![deny(static_mut_refs)]
+
+use core::fmt;
+
+/// A read that would have gone past the end of the buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadError {
+    /// Offset the read started at.
+    pub offset: usize,
+    /// Number of bytes the read needed.
+    pub len: usize,
+    /// Total length of the buffer.
+    pub available: usize,
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "read of {} bytes at offset {} exceeds buffer of {} bytes",
+            self.len, self.offset, self.available
+        )
+    }
+}
+
+impl core::error::Error for ReadError {}
+
+/// A fixed-size value decodable from little-endian bytes.
+pub trait LeValue: Sized {
+    /// Encoded size in bytes.
+    const SIZE: usize;
+
+    /// Decode from exactly [`Self::SIZE`] bytes.
+    ///
+    /// Callers must pass a slice of exactly `SIZE` bytes; [`ByteReader`]
+    /// guarantees this.
+    fn read_le(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_le_value {
+    ($($ty:ty),*) => {
+        $(
+            impl LeValue for $ty {
+                const SIZE: usize = core::mem::size_of::<$ty>();
+
+                fn read_le(bytes: &[u8]) -> Self {
+                    let mut raw = [0u8; core::mem::size_of::<$ty>()];
+                    raw.copy_from_slice(bytes);
+                    <$ty>::from_le_bytes(raw)
+                }
+            }
+        )*
+    };
+}
+
+impl_le_value!(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64);
+
+impl<T: LeValue + Copy + Default, const N: usize> LeValue for [T; N] {
+    const SIZE: usize = T::SIZE * N;
+
+    fn read_le(bytes: &[u8]) -> Self {
+        let mut values = [T::default(); N];
+        for (value, chunk) in values.iter_mut().zip(bytes.chunks_exact(T::SIZE)) {
+            *value = T::read_le(chunk);
+        }
+        values
+    }
+}
+
+fn finite_or_zero(value: f32) -> f32 {
+    if value.is_finite() { value } else { 0.0 }
+}
+
+/// Cursor over a byte slice with bounds-checked reads.
+///
+/// Cursor reads (`u16_le`, `bytes`, ...) advance the position only when they
+/// succeed. The `*_at` variants read at an absolute offset and leave the
+/// cursor alone.
+#[derive(Debug, Clone, Copy)]
+pub struct ByteReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+macro_rules! cursor_reads {
+    ($($name:ident, $at:ident => $ty:ty;)*) => {
+        $(
+            #[doc = concat!("Read a `", stringify!($ty), "` at the cursor.")]
+            #[inline]
+            pub fn $name(&mut self) -> Result<$ty, ReadError> {
+                self.read()
+            }
+
+            #[doc = concat!("Read a `", stringify!($ty), "` at absolute `offset`.")]
+            #[inline]
+            pub fn $at(&self, offset: usize) -> Result<$ty, ReadError> {
+                self.read_at(offset)
+            }
+        )*
+    };
+}
+
+impl<'a> ByteReader<'a> {
+    /// Reader positioned at the start of `data`.
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    /// Reader positioned at `offset`; reads fail if it is past the end.
+    pub fn at(data: &'a [u8], offset: usize) -> Self {
+        Self { data, pos: offset }
+    }
+
+    /// The whole underlying buffer.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Current cursor offset.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Bytes left after the cursor (zero if it is past the end).
+    pub fn remaining(&self) -> usize {
+        self.data.len().saturating_sub(self.pos)
+    }
+
+    /// Whether no bytes are left after the cursor.
+    pub fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Borrow `len` bytes at absolute `offset`.
+    pub fn bytes_at(&self, offset: usize, len: usize) -> Result<&'a [u8], ReadError> {
+        let error = ReadError {
+            offset,
+            len,
+            available: self.data.len(),
+        };
+        let end = offset.checked_add(len).ok_or(error)?;
+        self.data.get(offset..end).ok_or(error)
+    }
+
+    /// Borrow `len` bytes at the cursor and advance past them.
+    pub fn bytes(&mut self, len: usize) -> Result<&'a [u8], ReadError> {
+        let bytes = self.bytes_at(self.pos, len)?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    /// Borrow up to `len` bytes at absolute `offset`, truncated to what exists.
+    ///
+    /// For layouts whose trailing fields are optional; never fails.
+    pub fn bytes_up_to(&self, offset: usize, len: usize) -> &'a [u8] {
+        let start = offset.min(self.data.len());
+        let end = offset.saturating_add(len).min(self.data.len());
+        self.data.get(start..end).unwrap_or_default()
+    }
+
+    /// Advance the cursor by `len` bytes.
+    pub fn skip(&mut self, len: usize) -> Result<(), ReadError> {
+        self.bytes(len).map(|_| ())
+    }
+
+    /// Move the cursor to absolute `offset`, which may be the end of the buffer.
+    pub fn seek(&mut self, offset: usize) -> Result<(), ReadError> {
+        self.bytes_at(offset, 0)?;
+        self.pos = offset;
+        Ok(())
+    }
+
+    /// Decode a `T` at absolute `offset`.
+    #[inline]
+    pub fn read_at<T: LeValue>(&self, offset: usize) -> Result<T, ReadError> {
+        self.bytes_at(offset, T::SIZE).map(T::read_le)
+    }
+
+    /// Decode a `T` at the cursor and advance past it.
+    #[inline]
+    pub fn read<T: LeValue>(&mut self) -> Result<T, ReadError> {
+        let value = self.read_at(self.pos)?;
+        self.pos += T::SIZE;
+        Ok(value)
+    }
+
+    /// Read an `f32` at the cursor, mapping NaN and infinities to `0.0`.
+    ///
+    /// Game telemetry floats are untrusted too; this keeps garbage out of
+    /// downstream arithmetic without failing the whole packet.
+    #[inline]
+    pub fn f32_le_finite(&mut self) -> Result<f32, ReadError> {
+        self.f32_le().map(finite_or_zero)
+    }
+
+    /// Read an `f32` at absolute `offset`, mapping NaN and infinities to `0.0`.
+    #[inline]
+    pub fn f32_le_finite_at(&self, offset: usize) -> Result<f32, ReadError> {
+        self.f32_le_at(offset).map(finite_or_zero)
+    }
+
+    /// Copy `N` bytes at the cursor into an array.
+    pub fn array<const N: usize>(&mut self) -> Result<[u8; N], ReadError> {
+        self.read()
+    }
+
+    cursor_reads! {
+        u8, u8_at => u8;
+        i8, i8_at => i8;
+        u16_le, u16_le_at => u16;
+        i16_le, i16_le_at => i16;
+        u32_le, u32_le_at => u32;
+        i32_le, i32_le_at => i32;
+        u64_le, u64_le_at => u64;
+        f32_le, f32_le_at => f32;
+        f64_le, f64_le_at => f64;
+    }
+}
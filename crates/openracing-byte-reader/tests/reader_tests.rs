@@ -0,0 +1,81 @@
+//! Bounds behaviour of `ByteReader`.
+
+use openracing_byte_reader::{ByteReader, ReadError};
+use proptest::prelude::*;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+#[test]
+fn cursor_reads_advance_only_on_success() -> TestResult {
+    let data = [0x01, 0x34, 0x12, 0xff, 0xff, 0x00, 0x00, 0x80, 0x3f];
+    let mut reader = ByteReader::new(&data);
+
+    assert_eq!(reader.u8()?, 0x01);
+    assert_eq!(reader.u16_le()?, 0x1234);
+    assert_eq!(reader.i16_le()?, -1);
+    assert_eq!(reader.f32_le()?, 1.0);
+    assert_eq!(reader.remaining(), 0);
+
+    let error = reader.u8().err();
+    assert_eq!(
+        error,
+        Some(ReadError {
+            offset: 9,
+            len: 1,
+            available: 9
+        })
+    );
+    assert_eq!(reader.position(), 9);
+    Ok(())
+}
+
+#[test]
+fn absolute_reads_leave_the_cursor_alone() -> TestResult {
+    let data = [0xaa, 0x78, 0x56, 0x34, 0x12];
+    let reader = ByteReader::new(&data);
+
+    assert_eq!(reader.u32_le_at(1)?, 0x1234_5678);
+    assert_eq!(reader.read_at::<[u8; 2]>(0)?, [0xaa, 0x78]);
+    assert_eq!(reader.position(), 0);
+    assert!(reader.u32_le_at(2).is_err());
+    Ok(())
+}
+
+#[test]
+fn overflowing_offsets_are_errors_not_panics() {
+    let data = [0u8; 4];
+    let mut reader = ByteReader::at(&data, usize::MAX);
+
+    assert_eq!(reader.remaining(), 0);
+    assert!(reader.u16_le().is_err());
+    assert!(reader.bytes_at(1, usize::MAX).is_err());
+    assert!(reader.seek(5).is_err());
+    assert!(reader.bytes_up_to(usize::MAX, usize::MAX).is_empty());
+    assert_eq!(reader.bytes_up_to(2, 10), &[0, 0]);
+}
+
+#[test]
+fn error_message_names_offset_and_length() {
+    let error = ReadError {
+        offset: 12,
+        len: 4,
+        available: 14,
+    };
+    assert_eq!(
+        error.to_string(),
+        "read of 4 bytes at offset 12 exceeds buffer of 14 bytes"
+    );
+}
+
+proptest! {
+    #[test]
+    fn reads_never_panic(data: Vec<u8>, offset: usize, len in 0usize..64) {
+        let mut reader = ByteReader::at(&data, offset);
+        let _ = reader.f64_le_at(offset);
+        let _ = reader.bytes_at(offset, len);
+        let _ = reader.bytes_up_to(offset, len);
+        let _ = reader.seek(offset % (data.len() + 1));
+        while reader.u32_le().is_ok() {}
+        prop_assert!(reader.remaining() < 4);
+    }
+}
@@ -0,0 +1,491 @@
+//! Cooperative per-file inter-process locking for OpenRacing.
+//!
+//! Several OpenRacing processes (tray app, CLI, service) can touch the same
+//! configuration, profile and recording files. [`FileLock`] serializes those writes
+//! with an OS advisory lock (`flock` on Unix, `LockFileEx` on Windows) held on a
+//! sibling `<file>.lock` path, so unrelated files can be written concurrently.
+//!
+//! When the filesystem does not support advisory locks the lock degrades to an
+//! exclusive lockfile containing the holder's PID and a timestamp; lockfiles left
+//! behind by dead processes, or older than [`FileLockOptions::stale_after`], are
+//! reclaimed.
+//!
+//! A contender that cannot acquire the lock within [`FileLockOptions::timeout`]
+//! receives [`FileLockError::ResourceBusy`] instead of proceeding.
+//!
+//! # Example
+//!
+//! ```
+//! use openracing_file_lock::FileLock;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let dir = tempfile::tempdir()?;
+//! let target = dir.path().join("app.ini");
+//! {
+//!     let _lock = FileLock::acquire(&target)?;
+//!     std::fs::write(&target, "[Telemetry]\n")?;
+//! }
+//! assert!(!FileLock::lock_path_for(&target).exists());
+//! # Ok(())
+//! # }
+//! ```
+
+#![deny(static_mut_refs)]
+#![deny(unsafe_op_in_unsafe_fn)]
+
+mod sys;
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Suffix appended to the target file name to form the lock path.
+pub const LOCK_SUFFIX: &str = ".lock";
+
+static NEXT_TOKEN: AtomicU64 = AtomicU64::new(0);
+
+/// Errors returned while acquiring a [`FileLock`].
+#[derive(Debug, thiserror::Error)]
+pub enum FileLockError {
+    /// Another process (or thread) held the lock for the whole timeout.
+    #[error("resource busy: {} is locked by {}", path.display(), describe_holder(*holder_pid))]
+    ResourceBusy {
+        /// PID recorded by the current holder, when readable.
+        holder_pid: Option<u32>,
+        /// The file whose lock could not be acquired.
+        path: PathBuf,
+    },
+
+    /// The lock file could not be created, opened or written.
+    #[error("lock I/O error on {}: {source}", path.display())]
+    Io {
+        /// Lock file path.
+        path: PathBuf,
+        /// Underlying I/O error.
+        #[source]
+        source: io::Error,
+    },
+}
+
+fn describe_holder(holder_pid: Option<u32>) -> String {
+    match holder_pid {
+        Some(pid) => format!("process {pid}"),
+        None => "another process".to_string(),
+    }
+}
+
+/// Timing options for [`FileLock::acquire_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileLockOptions {
+    /// How long to keep retrying before returning [`FileLockError::ResourceBusy`].
+    pub timeout: Duration,
+    /// Delay between acquisition attempts.
+    pub poll_interval: Duration,
+    /// Age after which a fallback lockfile is considered abandoned.
+    pub stale_after: Duration,
+}
+
+impl Default for FileLockOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(5),
+            poll_interval: Duration::from_millis(20),
+            stale_after: Duration::from_secs(60),
+        }
+    }
+}
+
+impl FileLockOptions {
+    /// Options with a custom timeout and default polling/staleness.
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            ..Self::default()
+        }
+    }
+}
+
+/// Holder information written into the lock file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LockRecord {
+    pid: u32,
+    timestamp_ms: u64,
+    token: u64,
+}
+
+impl LockRecord {
+    fn current() -> Self {
+        Self {
+            pid: std::process::id(),
+            timestamp_ms: unix_ms(),
+            token: NEXT_TOKEN.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    fn render(&self) -> String {
+        format!("{} {} {}\n", self.pid, self.timestamp_ms, self.token)
+    }
+
+    fn parse(content: &str) -> Option<Self> {
+        let mut parts = content.split_whitespace();
+        let pid = parts.next()?.parse().ok()?;
+        let timestamp_ms = parts.next()?.parse().ok()?;
+        let token = parts.next()?.parse().ok()?;
+        Some(Self {
+            pid,
+            timestamp_ms,
+            token,
+        })
+    }
+}
+
+fn unix_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+        .unwrap_or(0)
+}
+
+enum Attempt {
+    /// Advisory lock held on the returned handle.
+    Advisory(File),
+    /// Fallback lockfile created exclusively.
+    Lockfile,
+    Busy,
+}
+
+/// Exclusive lock on a single file, released on drop.
+#[derive(Debug)]
+pub struct FileLock {
+    target: PathBuf,
+    lock_path: PathBuf,
+    record: String,
+    file: Option<File>,
+}
+
+impl FileLock {
+    /// Path of the lock file used for `target`.
+    pub fn lock_path_for(target: &Path) -> PathBuf {
+        match target.file_name() {
+            Some(name) => {
+                let mut name = name.to_os_string();
+                name.push(LOCK_SUFFIX);
+                target.with_file_name(name)
+            }
+            None => target.join(LOCK_SUFFIX),
+        }
+    }
+
+    /// Acquire the lock for `target` with default options.
+    pub fn acquire(target: impl AsRef<Path>) -> Result<Self, FileLockError> {
+        Self::acquire_with(target, &FileLockOptions::default())
+    }
+
+    /// Attempt to acquire the lock once without waiting.
+    pub fn try_acquire(target: impl AsRef<Path>) -> Result<Self, FileLockError> {
+        Self::acquire_with(target, &FileLockOptions::with_timeout(Duration::ZERO))
+    }
+
+    /// Acquire the lock for `target`, retrying until `options.timeout` elapses.
+    pub fn acquire_with(
+        target: impl AsRef<Path>,
+        options: &FileLockOptions,
+    ) -> Result<Self, FileLockError> {
+        let target = target.as_ref().to_path_buf();
+        let lock_path = Self::lock_path_for(&target);
+        let record = LockRecord::current().render();
+        let deadline = Instant::now() + options.timeout;
+
+        if let Some(parent) = lock_path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            fs::create_dir_all(parent).map_err(|source| FileLockError::Io {
+                path: lock_path.clone(),
+                source,
+            })?;
+        }
+
+        loop {
+            let file = match attempt(&lock_path, &record, options)? {
+                Attempt::Advisory(file) => Some(file),
+                Attempt::Lockfile => None,
+                Attempt::Busy => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return Err(FileLockError::ResourceBusy {
+                            holder_pid: read_record(&lock_path).map(|record| record.pid),
+                            path: target,
+                        });
+                    }
+                    std::thread::sleep(options.poll_interval.min(deadline - now));
+                    continue;
+                }
+            };
+
+            return Ok(Self {
+                target,
+                lock_path,
+                record,
+                file,
+            });
+        }
+    }
+
+    /// PID recorded in the lock file for `target`, if a holder record exists.
+    pub fn holder_pid(target: &Path) -> Option<u32> {
+        read_record(&Self::lock_path_for(target)).map(|record| record.pid)
+    }
+
+    /// File protected by this lock.
+    pub fn target(&self) -> &Path {
+        &self.target
+    }
+
+    /// Lock file backing this lock.
+    pub fn lock_path(&self) -> &Path {
+        &self.lock_path
+    }
+
+    /// Whether the lock is backed by an OS advisory lock rather than a lockfile.
+    pub fn is_advisory(&self) -> bool {
+        self.file.is_some()
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        // Unlink before unlocking so a waiter that opened the old inode notices the
+        // record mismatch and retries on the fresh path.
+        if fs::read_to_string(&self.lock_path).is_ok_and(|content| content == self.record) {
+            let _ = fs::remove_file(&self.lock_path);
+        }
+        if let Some(file) = self.file.take() {
+            sys::unlock(&file);
+        }
+    }
+}
+
+fn io_error(path: &Path, source: io::Error) -> FileLockError {
+    FileLockError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+fn attempt(
+    lock_path: &Path,
+    record: &str,
+    options: &FileLockOptions,
+) -> Result<Attempt, FileLockError> {
+    let (file, created) = match OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create_new(true)
+        .open(lock_path)
+    {
+        Ok(file) => (file, true),
+        Err(error) if error.kind() == io::ErrorKind::AlreadyExists => {
+            match OpenOptions::new().read(true).write(true).open(lock_path) {
+                Ok(file) => (file, false),
+                // Removed or pending deletion by the previous holder; retry shortly.
+                Err(error)
+                    if matches!(
+                        error.kind(),
+                        io::ErrorKind::NotFound | io::ErrorKind::PermissionDenied
+                    ) =>
+                {
+                    return Ok(Attempt::Busy);
+                }
+                Err(error) => return Err(io_error(lock_path, error)),
+            }
+        }
+        Err(error) if error.kind() == io::ErrorKind::PermissionDenied => {
+            return Ok(Attempt::Busy);
+        }
+        Err(error) => return Err(io_error(lock_path, error)),
+    };
+
+    match sys::try_lock_exclusive(&file) {
+        Ok(true) => {
+            write_record(&file, record).map_err(|error| io_error(lock_path, error))?;
+            // The previous holder may have unlinked the path after we opened it.
+            match fs::read_to_string(lock_path) {
+                Ok(content) if content == record => Ok(Attempt::Advisory(file)),
+                Ok(_) => Ok(Attempt::Busy),
+                Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Attempt::Busy),
+                Err(error) => Err(io_error(lock_path, error)),
+            }
+        }
+        Ok(false) => Ok(Attempt::Busy),
+        Err(error) if sys::is_unsupported(&error) => {
+            if created {
+                write_record(&file, record).map_err(|error| io_error(lock_path, error))?;
+                return Ok(Attempt::Lockfile);
+            }
+            drop(file);
+            if lockfile_is_stale(lock_path, options.stale_after) {
+                let _ = fs::remove_file(lock_path);
+            }
+            Ok(Attempt::Busy)
+        }
+        Err(error) => Err(io_error(lock_path, error)),
+    }
+}
+
+fn write_record(mut file: &File, record: &str) -> io::Result<()> {
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    file.write_all(record.as_bytes())?;
+    file.flush()
+}
+
+fn read_record(lock_path: &Path) -> Option<LockRecord> {
+    fs::read_to_string(lock_path)
+        .ok()
+        .and_then(|content| LockRecord::parse(&content))
+}
+
+fn lockfile_is_stale(lock_path: &Path, stale_after: Duration) -> bool {
+    let stale_ms = u64::try_from(stale_after.as_millis()).unwrap_or(u64::MAX);
+    match read_record(lock_path) {
+        Some(record) => {
+            !sys::process_alive(record.pid)
+                || unix_ms().saturating_sub(record.timestamp_ms) > stale_ms
+        }
+        // A holder that crashed between creating and writing the lockfile.
+        None => fs::metadata(lock_path)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > stale_after),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Barrier};
+
+    type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+    #[test]
+    fn lock_path_appends_suffix() {
+        let path = FileLock::lock_path_for(Path::new("/tmp/cfg/app.ini"));
+        assert_eq!(path, PathBuf::from("/tmp/cfg/app.ini.lock"));
+    }
+
+    #[test]
+    fn record_round_trips() {
+        let record = LockRecord::current();
+        assert_eq!(LockRecord::parse(&record.render()), Some(record));
+        assert_eq!(LockRecord::parse("garbage"), None);
+    }
+
+    #[test]
+    fn lock_records_holder_and_cleans_up() -> TestResult {
+        let dir = tempfile::tempdir()?;
+        let target = dir.path().join("nested").join("profile.json");
+
+        let lock = FileLock::acquire(&target)?;
+        assert!(lock.is_advisory());
+        assert_eq!(FileLock::holder_pid(&target), Some(std::process::id()));
+        drop(lock);
+
+        assert!(!FileLock::lock_path_for(&target).exists());
+        Ok(())
+    }
+
+    #[test]
+    fn second_acquire_times_out_with_resource_busy() -> TestResult {
+        let dir = tempfile::tempdir()?;
+        let target = dir.path().join("app.ini");
+        let _held = FileLock::acquire(&target)?;
+
+        let contender = std::thread::spawn({
+            let target = target.clone();
+            move || {
+                FileLock::acquire_with(
+                    &target,
+                    &FileLockOptions::with_timeout(Duration::from_millis(50)),
+                )
+            }
+        });
+
+        match contender.join().map_err(|_| "contender panicked")? {
+            Err(FileLockError::ResourceBusy { holder_pid, path }) => {
+                assert_eq!(holder_pid, Some(std::process::id()));
+                assert_eq!(path, target);
+            }
+            other => return Err(format!("expected ResourceBusy, got {other:?}").into()),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn locks_are_per_file() -> TestResult {
+        let dir = tempfile::tempdir()?;
+        let _first = FileLock::try_acquire(dir.path().join("acc.json"))?;
+        let _second = FileLock::try_acquire(dir.path().join("iracing.ini"))?;
+        Ok(())
+    }
+
+    #[test]
+    fn exactly_one_thread_wins_contention() -> TestResult {
+        let dir = tempfile::tempdir()?;
+        let target = dir.path().join("contended.json");
+        let barrier = Arc::new(Barrier::new(2));
+
+        let handles: Vec<_> = (0..2)
+            .map(|_| {
+                let target = target.clone();
+                let barrier = Arc::clone(&barrier);
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    let result = FileLock::acquire_with(
+                        &target,
+                        &FileLockOptions::with_timeout(Duration::from_millis(100)),
+                    );
+                    if result.is_ok() {
+                        std::thread::sleep(Duration::from_millis(400));
+                    }
+                    result.map(|_| ())
+                })
+            })
+            .collect();
+
+        let mut winners = 0;
+        let mut busy = 0;
+        for handle in handles {
+            match handle.join().map_err(|_| "contender panicked")? {
+                Ok(()) => winners += 1,
+                Err(FileLockError::ResourceBusy { .. }) => busy += 1,
+                Err(other) => return Err(other.into()),
+            }
+        }
+        assert_eq!((winners, busy), (1, 1));
+        Ok(())
+    }
+
+    #[test]
+    fn stale_fallback_lockfiles_are_detected() -> TestResult {
+        let dir = tempfile::tempdir()?;
+        let lock_path = dir.path().join("old.json.lock");
+        #[cfg(unix)]
+        {
+            fs::write(&lock_path, format!("{} {} 0\n", u32::MAX, unix_ms()))?;
+            assert!(lockfile_is_stale(&lock_path, Duration::from_secs(60)));
+        }
+
+        fs::write(
+            &lock_path,
+            format!("{} {} 0\n", std::process::id(), unix_ms()),
+        )?;
+        assert!(!lockfile_is_stale(&lock_path, Duration::from_secs(60)));
+
+        fs::write(&lock_path, format!("{} 0 0\n", std::process::id()))?;
+        assert!(lockfile_is_stale(&lock_path, Duration::from_secs(60)));
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+//! Cross-thread and cross-process contention tests for `FileLock`.
+
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use openracing_file_lock::{FileLock, FileLockError, FileLockOptions};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const CHILD_TARGET_ENV: &str = "OPENRACING_FILE_LOCK_CHILD_TARGET";
+const CHILD_READY_MARKER: &str = "OPENRACING_LOCK_HELD";
+
+/// Entry point for the child process spawned by `second_process_gets_resource_busy`.
+///
+/// Does nothing when run as part of the normal suite.
+#[test]
+fn child_lock_holder() -> TestResult {
+    let Some(target) = std::env::var_os(CHILD_TARGET_ENV) else {
+        return Ok(());
+    };
+
+    let _lock = FileLock::acquire(PathBuf::from(target))?;
+    println!("{CHILD_READY_MARKER}");
+    std::thread::sleep(Duration::from_millis(1500));
+    Ok(())
+}
+
+#[test]
+fn second_process_gets_resource_busy() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let target = dir.path().join("app.ini");
+
+    let mut child = Command::new(std::env::current_exe()?)
+        .args([
+            "child_lock_holder",
+            "--exact",
+            "--nocapture",
+            "--test-threads=1",
+        ])
+        .env(CHILD_TARGET_ENV, &target)
+        .stdout(Stdio::piped())
+        .spawn()?;
+    let child_pid = child.id();
+
+    let stdout = child.stdout.take().ok_or("child stdout missing")?;
+    let mut lines = BufReader::new(stdout).lines();
+    let mut ready = false;
+    for line in lines.by_ref() {
+        if line?.contains(CHILD_READY_MARKER) {
+            ready = true;
+            break;
+        }
+    }
+    assert!(ready, "child never reported holding the lock");
+
+    let contended = FileLock::acquire_with(
+        &target,
+        &FileLockOptions::with_timeout(Duration::from_millis(100)),
+    );
+    match contended {
+        Err(FileLockError::ResourceBusy { holder_pid, path }) => {
+            assert_eq!(holder_pid, Some(child_pid));
+            assert_eq!(path, target);
+        }
+        other => return Err(format!("expected ResourceBusy, got {other:?}").into()),
+    }
+
+    // Drain the harness output so the child never hits a closed pipe.
+    for line in lines {
+        line?;
+    }
+    let status = child.wait()?;
+    assert!(status.success());
+
+    // The child released the lock on exit, so the parent now wins.
+    let _lock = FileLock::try_acquire(&target)?;
+    Ok(())
+}
+
+fn write_in_chunks(path: &Path, writer_id: usize) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut file = std::fs::File::create(path)?;
+    for _ in 0..32 {
+        writeln!(file, "writer-{writer_id}")?;
+        file.flush()?;
+    }
+    Ok(())
+}
+
+#[test]
+fn repeated_contention_never_leaves_partial_content() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let target = dir.path().join("profile.json");
+
+    let handles: Vec<_> = (0..4)
+        .map(|writer_id| {
+            let target = target.clone();
+            std::thread::spawn(move || -> Result<(), String> {
+                for _ in 0..25 {
+                    let _lock = FileLock::acquire(&target).map_err(|error| error.to_string())?;
+                    write_in_chunks(&target, writer_id).map_err(|error| error.to_string())?;
+
+                    let content =
+                        std::fs::read_to_string(&target).map_err(|error| error.to_string())?;
+                    let lines: Vec<&str> = content.lines().collect();
+                    if lines.len() != 32 || lines.iter().any(|line| *line != lines[0]) {
+                        return Err(format!("interleaved content observed: {content:?}"));
+                    }
+                }
+                Ok(())
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().map_err(|_| "writer panicked")??;
+    }
+    assert!(!FileLock::lock_path_for(&target).exists());
+    Ok(())
+}
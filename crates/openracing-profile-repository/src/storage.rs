@@ -1,6 +1,7 @@
 //! File-based storage operations for profiles
 
 use anyhow::Context;
+use openracing_file_lock::FileLock;
 use std::path::{Path, PathBuf};
 use tokio::fs as async_fs;
 use tracing::debug;
@@ -72,9 +73,17 @@ impl FileStorage {
     /// 1. Write to temporary file
     /// 2. Rename temp file to target
     /// 3. Original file is preserved if write fails
+    ///
+    /// The write holds an inter-process [`FileLock`] on `path` so concurrent
+    /// OpenRacing processes cannot interleave saves of the same profile.
     pub async fn write_atomic(&self, path: &Path, content: &str) -> anyhow::Result<()> {
         debug!(path = ?path, "Writing file atomically");
 
+        let lock_target = path.to_path_buf();
+        let _lock = tokio::task::spawn_blocking(move || FileLock::acquire(lock_target))
+            .await
+            .context("File lock task failed")??;
+
         let temp_path = path.with_extension("tmp");
 
         async_fs::write(&temp_path, content)
@@ -3,6 +3,10 @@
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 
+mod spsc;
+
+pub use spsc::{BufferStats, SpscConsumer, SpscProducer, spawn_spsc_bridge, spsc_ring};
+
 pub struct TelemetryBuffer<T> {
     buffer: Arc<Mutex<VecDeque<T>>>,
     max_size: usize,
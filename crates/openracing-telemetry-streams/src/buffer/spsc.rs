@@ -0,0 +1,259 @@
+//! Lock-free single-producer/single-consumer ring for RT-safe handoff.
+//!
+//! [`spsc_ring`] allocates every slot up front and splits the ring into a
+//! [`SpscProducer`] and a [`SpscConsumer`]. Neither side blocks or allocates:
+//! a push into a full ring fails with [`StreamError::BufferOverflow`] and is
+//! counted, so the RT side never waits on the consumer.
+
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::{StreamError, StreamResult};
+
+/// Counters of a [`spsc_ring`], read at one moment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BufferStats {
+    pub capacity: usize,
+    /// Items accepted by `try_push`.
+    pub pushed: u64,
+    /// Items handed to the consumer.
+    pub popped: u64,
+    /// Items rejected because the ring was full.
+    pub overflowed: u64,
+}
+
+impl BufferStats {
+    /// Items in the ring when the counters were read.
+    pub fn in_flight(&self) -> u64 {
+        self.pushed.saturating_sub(self.popped)
+    }
+}
+
+struct Shared<T> {
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    /// Next position the consumer reads; only the consumer stores it.
+    head: AtomicUsize,
+    /// Next position the producer writes; only the producer stores it.
+    tail: AtomicUsize,
+    pushed: AtomicU64,
+    popped: AtomicU64,
+    overflowed: AtomicU64,
+    producer_closed: AtomicBool,
+}
+
+// SAFETY: a slot is only touched by the side that currently owns it, as
+// published through `head` and `tail`, so sharing the ring between one
+// producer thread and one consumer thread only moves `T` across threads.
+unsafe impl<T: Send> Send for Shared<T> {}
+// SAFETY: see above.
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+impl<T> Shared<T> {
+    fn slot(&self, position: usize) -> *mut MaybeUninit<T> {
+        self.slots[position % self.slots.len()].get()
+    }
+
+    fn stats(&self) -> BufferStats {
+        BufferStats {
+            capacity: self.slots.len(),
+            pushed: self.pushed.load(Ordering::Relaxed),
+            popped: self.popped.load(Ordering::Relaxed),
+            overflowed: self.overflowed.load(Ordering::Relaxed),
+        }
+    }
+
+    fn len(&self) -> usize {
+        let tail = self.tail.load(Ordering::Acquire);
+        let head = self.head.load(Ordering::Acquire);
+        tail.wrapping_sub(head)
+    }
+}
+
+impl<T> Drop for Shared<T> {
+    fn drop(&mut self) {
+        let head = *self.head.get_mut();
+        let tail = *self.tail.get_mut();
+        let mut position = head;
+        while position != tail {
+            // SAFETY: positions in `head..tail` hold initialized items that
+            // were never popped, and both sides are gone.
+            unsafe { (*self.slot(position)).assume_init_drop() };
+            position = position.wrapping_add(1);
+        }
+    }
+}
+
+/// Create a ring holding up to `capacity` items, split into its two ends.
+///
+/// A `capacity` of zero is raised to one.
+pub fn spsc_ring<T: Send>(capacity: usize) -> (SpscProducer<T>, SpscConsumer<T>) {
+    let slots = (0..capacity.max(1))
+        .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+        .collect();
+    let shared = Arc::new(Shared {
+        slots,
+        head: AtomicUsize::new(0),
+        tail: AtomicUsize::new(0),
+        pushed: AtomicU64::new(0),
+        popped: AtomicU64::new(0),
+        overflowed: AtomicU64::new(0),
+        producer_closed: AtomicBool::new(false),
+    });
+    (
+        SpscProducer {
+            shared: Arc::clone(&shared),
+        },
+        SpscConsumer { shared },
+    )
+}
+
+/// The writing end of a [`spsc_ring`].
+pub struct SpscProducer<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> SpscProducer<T> {
+    /// Append `item` without blocking.
+    ///
+    /// When the ring is full `item` is dropped, counted as overflowed, and
+    /// [`StreamError::BufferOverflow`] is returned.
+    pub fn try_push(&mut self, item: T) -> StreamResult<()> {
+        let shared = &self.shared;
+        let tail = shared.tail.load(Ordering::Relaxed);
+        let head = shared.head.load(Ordering::Acquire);
+        if tail.wrapping_sub(head) >= shared.slots.len() {
+            shared.overflowed.fetch_add(1, Ordering::Relaxed);
+            return Err(StreamError::BufferOverflow);
+        }
+        // SAFETY: the slot at `tail` is outside `head..tail`, so the consumer
+        // has finished with it and will not read it until `tail` is published.
+        unsafe { (*shared.slot(tail)).write(item) };
+        shared.tail.store(tail.wrapping_add(1), Ordering::Release);
+        shared.pushed.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.shared.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.shared.slots.len()
+    }
+
+    pub fn stats(&self) -> BufferStats {
+        self.shared.stats()
+    }
+}
+
+impl<T> Drop for SpscProducer<T> {
+    fn drop(&mut self) {
+        self.shared.producer_closed.store(true, Ordering::Release);
+    }
+}
+
+/// The reading end of a [`spsc_ring`].
+pub struct SpscConsumer<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> SpscConsumer<T> {
+    /// Take the oldest item without blocking.
+    pub fn try_pop(&mut self) -> Option<T> {
+        let shared = &self.shared;
+        let head = shared.head.load(Ordering::Relaxed);
+        let tail = shared.tail.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+        // SAFETY: `head` is inside `head..tail`, so the producer initialized
+        // the slot and will not touch it again until `head` moves past it.
+        let item = unsafe { (*shared.slot(head)).assume_init_read() };
+        shared.head.store(head.wrapping_add(1), Ordering::Release);
+        shared.popped.fetch_add(1, Ordering::Relaxed);
+        Some(item)
+    }
+
+    /// Move up to `n` of the oldest items onto the end of `out`, in order.
+    ///
+    /// Returns how many were moved. `out` only allocates if it lacks room.
+    pub fn pop_up_to(&mut self, n: usize, out: &mut Vec<T>) -> usize {
+        let mut moved = 0;
+        while moved < n {
+            let Some(item) = self.try_pop() else {
+                break;
+            };
+            out.push(item);
+            moved += 1;
+        }
+        moved
+    }
+
+    /// Whether the producer has been dropped; items may still be queued.
+    pub fn is_producer_closed(&self) -> bool {
+        self.shared.producer_closed.load(Ordering::Acquire)
+    }
+
+    pub fn len(&self) -> usize {
+        self.shared.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.shared.slots.len()
+    }
+
+    pub fn stats(&self) -> BufferStats {
+        self.shared.stats()
+    }
+}
+
+/// Forward everything from `consumer` into `sender` on a spawned task.
+///
+/// The task drains in batches of up to `batch` items and sleeps for
+/// `poll_interval` whenever the ring is empty. It ends once the producer is
+/// dropped and the ring is drained, or when the receiver is dropped, and
+/// yields the ring's final [`BufferStats`]. Must be called inside a Tokio
+/// runtime.
+pub fn spawn_spsc_bridge<T: Send + 'static>(
+    mut consumer: SpscConsumer<T>,
+    sender: mpsc::Sender<T>,
+    batch: usize,
+    poll_interval: Duration,
+) -> JoinHandle<BufferStats> {
+    tokio::spawn(async move {
+        let batch = batch.max(1);
+        let mut pending = Vec::with_capacity(batch);
+        loop {
+            // Read before draining so items pushed just before the producer
+            // closed are never left behind.
+            let closed = consumer.is_producer_closed();
+            if consumer.pop_up_to(batch, &mut pending) == 0 {
+                if closed {
+                    break;
+                }
+                tokio::time::sleep(poll_interval).await;
+                continue;
+            }
+            for item in pending.drain(..) {
+                if sender.send(item).await.is_err() {
+                    return consumer.stats();
+                }
+            }
+        }
+        consumer.stats()
+    })
+}
@@ -3,6 +3,33 @@
 use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
+use racing_wheel_telemetry_contracts::TelemetryFrame;
+
+use crate::{StreamError, StreamResult};
+
+mod dedup;
+mod input_sync;
+mod quality;
+mod stats;
+mod track_map;
+
+pub use dedup::{
+    DEFAULT_DEDUP_THRESHOLD, DEFAULT_MAX_SUPPRESSION, DedupConfig, DedupFilter, semantic_hash,
+};
+pub use input_sync::{
+    DEFAULT_MAX_INPUT_LAG, DEFAULT_SYNC_TOLERANCE, InputSyncedFrame, MAX_PENDING_INPUTS,
+    SyncedInputTelemetry, spawn_input_sync,
+};
+pub use quality::{
+    DEFAULT_REORDER_HORIZON, MAX_SEQUENCE_JUMP, REORDER_WINDOW, SequenceEvent, StreamQuality,
+    StreamQualityMonitor, StreamQualitySummary,
+};
+pub use stats::{CORE_STATS_CHANNELS, ChannelStats, ChannelSummary, StatsReport};
+pub use track_map::{
+    DEFAULT_CLOSE_DISTANCE_M, DEFAULT_MAP_POINTS, DEFAULT_MAX_LENGTH_DEVIATION, TrackMap,
+    TrackMapBuilder,
+};
+
 pub struct MovingAverage {
     window: VecDeque<f32>,
     window_size: usize,
@@ -112,6 +139,277 @@ impl RateCounter {
     }
 }
 
+/// Magnitude at which an `ffb_scalar` sample counts as clipping.
+pub const FFB_CLIP_LEVEL: f32 = 0.98;
+
+/// Low-pass filter applied to `ffb_scalar` before shaping.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FfbSmoothing {
+    /// Pass samples through unfiltered.
+    None,
+    /// First-order exponential moving average.
+    Ema { cutoff_hz: f32 },
+    /// Second-order Butterworth low-pass.
+    Biquad { cutoff_hz: f32 },
+}
+
+/// Stages of an [`FfbProcessor`], applied in field order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FfbConfig {
+    pub smoothing: FfbSmoothing,
+    /// Magnitudes at or below this become zero.
+    pub deadzone: f32,
+    /// Smallest magnitude output outside the deadzone, so weak forces are
+    /// still felt through the wheelbase's own friction.
+    pub min_force: f32,
+    pub gain: f32,
+    /// Frames in the sliding window behind [`FfbProcessor::clipping_ratio`].
+    pub clip_window: usize,
+    /// Clipping ratio a window must exceed to count towards an event.
+    pub clip_alert_ratio: f32,
+    /// Consecutive windows over `clip_alert_ratio` that raise a [`ClippingEvent`].
+    pub clip_alert_windows: u32,
+}
+
+impl Default for FfbConfig {
+    fn default() -> Self {
+        Self {
+            smoothing: FfbSmoothing::None,
+            deadzone: 0.0,
+            min_force: 0.0,
+            gain: 1.0,
+            clip_window: 60,
+            clip_alert_ratio: 0.5,
+            clip_alert_windows: 3,
+        }
+    }
+}
+
+impl FfbConfig {
+    fn validate(&self) -> StreamResult<()> {
+        let invalid = |message: &str| Err(StreamError::ProcessingError(message.to_string()));
+        match self.smoothing {
+            FfbSmoothing::Ema { cutoff_hz } | FfbSmoothing::Biquad { cutoff_hz }
+                if !(cutoff_hz.is_finite() && cutoff_hz > 0.0) =>
+            {
+                return invalid("smoothing cutoff must be a positive frequency");
+            }
+            _ => {}
+        }
+        if !(0.0..1.0).contains(&self.deadzone) {
+            return invalid("deadzone must be in [0, 1)");
+        }
+        if !(0.0..=1.0).contains(&self.min_force) {
+            return invalid("min_force must be in [0, 1]");
+        }
+        if !self.gain.is_finite() {
+            return invalid("gain must be finite");
+        }
+        if self.clip_window == 0 || self.clip_alert_windows == 0 {
+            return invalid("clipping windows must be non-empty");
+        }
+        Ok(())
+    }
+}
+
+/// Sustained clipping reported by [`FfbProcessor::take_clipping_event`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClippingEvent {
+    /// Clipping ratio of the window that raised the event.
+    pub ratio: f32,
+    /// Consecutive windows over the alert ratio.
+    pub windows: u32,
+    /// Timestamp of the frame that completed the last window.
+    pub timestamp_ns: u64,
+}
+
+/// Filter state that survives from one sample to the next.
+#[derive(Debug, Clone, Copy, Default)]
+struct FilterState {
+    x1: f32,
+    x2: f32,
+    y1: f32,
+    y2: f32,
+}
+
+/// Smooths, shapes and watches the `ffb_scalar` of a frame stream.
+///
+/// Frames without a finite `ffb_scalar` come out with `None` and leave the
+/// filter and clipping state untouched. Nothing is allocated after
+/// construction.
+#[derive(Debug, Clone)]
+pub struct FfbProcessor {
+    config: FfbConfig,
+    filter: Option<FilterState>,
+    last_timestamp_ns: Option<u64>,
+    /// Whether each of the last `clip_window` samples clipped, as a ring.
+    clipped: Vec<bool>,
+    next_slot: usize,
+    samples: usize,
+    clipped_count: usize,
+    window_progress: usize,
+    windows_over: u32,
+    pending_event: Option<ClippingEvent>,
+}
+
+impl FfbProcessor {
+    pub fn new(config: FfbConfig) -> StreamResult<Self> {
+        config.validate()?;
+        Ok(Self {
+            clipped: vec![false; config.clip_window],
+            config,
+            filter: None,
+            last_timestamp_ns: None,
+            next_slot: 0,
+            samples: 0,
+            clipped_count: 0,
+            window_progress: 0,
+            windows_over: 0,
+            pending_event: None,
+        })
+    }
+
+    pub fn config(&self) -> &FfbConfig {
+        &self.config
+    }
+
+    /// Process `frame.data.ffb_scalar` in place.
+    pub fn process(&mut self, mut frame: TelemetryFrame) -> TelemetryFrame {
+        frame.data.ffb_scalar = frame
+            .data
+            .ffb_scalar
+            .filter(|value| value.is_finite())
+            .map(|value| self.process_sample(value, frame.timestamp_ns));
+        frame
+    }
+
+    /// Process one finite sample taken at `timestamp_ns`.
+    fn process_sample(&mut self, value: f32, timestamp_ns: u64) -> f32 {
+        let dt_s = self
+            .last_timestamp_ns
+            .map(|last| timestamp_ns.saturating_sub(last) as f32 / 1e9);
+        self.last_timestamp_ns = Some(timestamp_ns);
+        let smoothed = self.smooth(value, dt_s);
+        let output = self.shape(smoothed) * self.config.gain;
+        self.record_clipping(output.abs() >= FFB_CLIP_LEVEL, timestamp_ns);
+        output.clamp(-1.0, 1.0)
+    }
+
+    fn smooth(&mut self, value: f32, dt_s: Option<f32>) -> f32 {
+        if self.config.smoothing == FfbSmoothing::None {
+            return value;
+        }
+        let Some(state) = self.filter.as_mut() else {
+            // Start settled on the first sample rather than rising from zero.
+            self.filter = Some(FilterState {
+                x1: value,
+                x2: value,
+                y1: value,
+                y2: value,
+            });
+            return value;
+        };
+        let dt_s = match dt_s {
+            Some(dt_s) if dt_s > 0.0 => dt_s,
+            _ => return state.y1,
+        };
+        match self.config.smoothing {
+            FfbSmoothing::None => value,
+            FfbSmoothing::Ema { cutoff_hz } => {
+                let alpha = 1.0 - (-std::f32::consts::TAU * cutoff_hz * dt_s).exp();
+                state.y1 += alpha * (value - state.y1);
+                state.y1
+            }
+            FfbSmoothing::Biquad { cutoff_hz } => {
+                // Bilinear transform with pre-warping; the cutoff is held
+                // below Nyquist of the current frame interval.
+                let nyquist_hz = 0.5 / dt_s;
+                let cutoff_hz = cutoff_hz.min(nyquist_hz * 0.9);
+                let k = (std::f32::consts::PI * cutoff_hz * dt_s).tan();
+                let norm = 1.0 / (1.0 + std::f32::consts::SQRT_2 * k + k * k);
+                let b0 = k * k * norm;
+                let b1 = 2.0 * b0;
+                let a1 = 2.0 * (k * k - 1.0) * norm;
+                let a2 = (1.0 - std::f32::consts::SQRT_2 * k + k * k) * norm;
+                let y = b0 * value + b1 * state.x1 + b0 * state.x2 - a1 * state.y1 - a2 * state.y2;
+                state.x2 = state.x1;
+                state.x1 = value;
+                state.y2 = state.y1;
+                state.y1 = y;
+                y
+            }
+        }
+    }
+
+    fn shape(&self, value: f32) -> f32 {
+        let magnitude = value.abs();
+        if magnitude <= self.config.deadzone {
+            return 0.0;
+        }
+        let span = (magnitude - self.config.deadzone) / (1.0 - self.config.deadzone);
+        let min_force = self.config.min_force;
+        (min_force + (1.0 - min_force) * span).copysign(value)
+    }
+
+    fn record_clipping(&mut self, clipped: bool, timestamp_ns: u64) {
+        let window = self.clipped.len();
+        if self.samples == window && self.clipped[self.next_slot] {
+            self.clipped_count -= 1;
+        }
+        self.clipped[self.next_slot] = clipped;
+        self.clipped_count += usize::from(clipped);
+        self.next_slot = (self.next_slot + 1) % window;
+        self.samples = (self.samples + 1).min(window);
+
+        self.window_progress += 1;
+        if self.window_progress < window {
+            return;
+        }
+        self.window_progress = 0;
+        let ratio = self.clipping_ratio();
+        if ratio <= self.config.clip_alert_ratio {
+            self.windows_over = 0;
+            return;
+        }
+        self.windows_over += 1;
+        if self.windows_over == self.config.clip_alert_windows {
+            self.pending_event = Some(ClippingEvent {
+                ratio,
+                windows: self.windows_over,
+                timestamp_ns,
+            });
+        }
+    }
+
+    /// Fraction of the samples in the sliding window at or above
+    /// [`FFB_CLIP_LEVEL`] after gain.
+    pub fn clipping_ratio(&self) -> f32 {
+        if self.samples == 0 {
+            return 0.0;
+        }
+        self.clipped_count as f32 / self.samples as f32
+    }
+
+    /// The clipping event raised since the last call, if any.
+    ///
+    /// An event is raised once per run of windows over the alert ratio.
+    pub fn take_clipping_event(&mut self) -> Option<ClippingEvent> {
+        self.pending_event.take()
+    }
+
+    pub fn reset(&mut self) {
+        self.filter = None;
+        self.last_timestamp_ns = None;
+        self.clipped.fill(false);
+        self.next_slot = 0;
+        self.samples = 0;
+        self.clipped_count = 0;
+        self.window_progress = 0;
+        self.windows_over = 0;
+        self.pending_event = None;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -234,4 +532,172 @@ mod tests {
         // After reset, rate should be 0 (no increments)
         assert!((counter.rate() - 0.0).abs() < f64::EPSILON);
     }
+    // -----------------------------------------------------------------------
+    // FfbProcessor
+    // -----------------------------------------------------------------------
+
+    use racing_wheel_telemetry_contracts::NormalizedTelemetry;
+
+    const FRAME_NS: u64 = 1_000_000;
+
+    fn ffb_frame(index: u64, ffb_scalar: Option<f32>) -> TelemetryFrame {
+        let data = NormalizedTelemetry {
+            ffb_scalar,
+            ..NormalizedTelemetry::default()
+        };
+        TelemetryFrame::new(data, index * FRAME_NS, index, 0)
+    }
+
+    fn run(processor: &mut FfbProcessor, samples: &[f32]) -> Vec<Option<f32>> {
+        samples
+            .iter()
+            .enumerate()
+            .map(|(index, &value)| {
+                processor
+                    .process(ffb_frame(index as u64, Some(value)))
+                    .data
+                    .ffb_scalar
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ffb_ema_step_matches_time_constant() -> StreamResult<()> {
+        let cutoff_hz = 2.0;
+        let mut processor = FfbProcessor::new(FfbConfig {
+            smoothing: FfbSmoothing::Ema { cutoff_hz },
+            ..FfbConfig::default()
+        })?;
+        let mut step = vec![0.5; 1000];
+        step[0] = 0.0;
+        let output = run(&mut processor, &step);
+
+        // A first-order lag reaches 1 - 1/e of the step after one time constant.
+        let target = 0.5 * (1.0 - (-1.0f32).exp());
+        let crossing = output
+            .iter()
+            .position(|value| value.is_some_and(|value| value >= target))
+            .ok_or_else(|| StreamError::ProcessingError("never crossed".to_string()))?;
+        let tau_ms = 1000.0 / (std::f32::consts::TAU * cutoff_hz);
+        assert!(
+            (crossing as f32 - tau_ms).abs() <= tau_ms * 0.05,
+            "crossed at {crossing} ms, expected {tau_ms} ms"
+        );
+        assert!(output[999].is_some_and(|value| (value - 0.5).abs() < 1e-3));
+        Ok(())
+    }
+
+    #[test]
+    fn test_ffb_biquad_step_settles_with_little_overshoot() -> StreamResult<()> {
+        let mut processor = FfbProcessor::new(FfbConfig {
+            smoothing: FfbSmoothing::Biquad { cutoff_hz: 10.0 },
+            ..FfbConfig::default()
+        })?;
+        let mut step = vec![0.5; 500];
+        step[0] = 0.0;
+        let output: Vec<f32> = run(&mut processor, &step).into_iter().flatten().collect();
+        assert_eq!(output.len(), step.len());
+
+        let peak = output.iter().copied().fold(f32::MIN, f32::max);
+        // A Butterworth response overshoots by about 4%.
+        assert!(peak > 0.5 && peak < 0.5 * 1.06, "peak {peak}");
+        assert!(output[5] < 0.1, "rose too fast: {}", output[5]);
+        assert!((output[499] - 0.5).abs() < 1e-3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_ffb_deadzone_min_force_and_gain() -> StreamResult<()> {
+        let mut processor = FfbProcessor::new(FfbConfig {
+            deadzone: 0.1,
+            min_force: 0.2,
+            gain: 1.5,
+            ..FfbConfig::default()
+        })?;
+        let output = run(&mut processor, &[0.05, -0.55, 0.9]);
+        assert_eq!(output[0], Some(0.0));
+        // 0.2 + 0.8 * 0.5 = 0.6, times the gain.
+        assert!(output[1].is_some_and(|value| (value + 0.9).abs() < 1e-5));
+        assert_eq!(output[2], Some(1.0));
+        assert!((processor.clipping_ratio() - 1.0 / 3.0).abs() < 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_ffb_sustained_clipping_raises_one_event() -> StreamResult<()> {
+        let mut processor = FfbProcessor::new(FfbConfig {
+            clip_window: 10,
+            clip_alert_ratio: 0.5,
+            clip_alert_windows: 3,
+            ..FfbConfig::default()
+        })?;
+        let mut events = Vec::new();
+        // A straight, then a corner where the game rails at full force.
+        for index in 0..100u64 {
+            let value = if (20..80).contains(&index) { -1.0 } else { 0.3 };
+            processor.process(ffb_frame(index, Some(value)));
+            events.extend(processor.take_clipping_event());
+            if index == 79 {
+                assert!((processor.clipping_ratio() - 1.0).abs() < f32::EPSILON);
+            }
+        }
+
+        assert_eq!(events.len(), 1, "{events:?}");
+        assert_eq!(events[0].windows, 3);
+        assert!((events[0].ratio - 1.0).abs() < f32::EPSILON);
+        assert_eq!(events[0].timestamp_ns, 49 * FRAME_NS);
+        assert!(processor.clipping_ratio() < f32::EPSILON);
+        Ok(())
+    }
+
+    #[test]
+    fn test_ffb_nan_passes_through_as_none() -> StreamResult<()> {
+        let mut processor = FfbProcessor::new(FfbConfig {
+            smoothing: FfbSmoothing::Biquad { cutoff_hz: 20.0 },
+            ..FfbConfig::default()
+        })?;
+        let inputs = [
+            Some(0.4),
+            Some(f32::NAN),
+            Some(f32::INFINITY),
+            None,
+            Some(0.4),
+        ];
+        let output: Vec<_> = inputs
+            .iter()
+            .enumerate()
+            .map(|(index, &value)| {
+                processor
+                    .process(ffb_frame(index as u64, value))
+                    .data
+                    .ffb_scalar
+            })
+            .collect();
+
+        assert_eq!(output[1..4], [None, None, None]);
+        assert!(output[4].is_some_and(|value| (value - 0.4).abs() < 1e-5));
+        assert!(processor.clipping_ratio() < f32::EPSILON);
+        Ok(())
+    }
+
+    #[test]
+    fn test_ffb_invalid_config_is_rejected() {
+        let configs = [
+            FfbConfig {
+                smoothing: FfbSmoothing::Ema { cutoff_hz: 0.0 },
+                ..FfbConfig::default()
+            },
+            FfbConfig {
+                deadzone: 1.0,
+                ..FfbConfig::default()
+            },
+            FfbConfig {
+                clip_window: 0,
+                ..FfbConfig::default()
+            },
+        ];
+        for config in configs {
+            assert!(FfbProcessor::new(config).is_err());
+        }
+    }
 }
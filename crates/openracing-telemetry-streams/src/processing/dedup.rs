@@ -0,0 +1,640 @@
+//! Suppression of frames an adapter re-sends unchanged.
+//!
+//! Adapters polling shared memory at a fixed rate re-emit the same frame
+//! whenever the game writes slower than they read. [`DedupFilter`] drops a
+//! frame whose content matches the previous one when the two are closer than
+//! a threshold, but lets one through every `max_suppression` so consumers
+//! watching for stale data still see the game is alive.
+//!
+//! Content is compared by [`semantic_hash`], a 64-bit FNV-1a hash of the
+//! frame's serialized form without its `timestamp` and `sequence`. Map
+//! entries are hashed one by one and combined order-independently, so the
+//! hash is the same for the same content in every run, whatever the map's
+//! iteration order.
+
+use std::fmt::{self, Write as _};
+use std::time::Duration;
+
+use serde::ser::{self, Serialize, Serializer};
+
+use crate::{StreamError, StreamResult};
+
+/// Largest gap between identical frames for the later one to be dropped.
+pub const DEFAULT_DEDUP_THRESHOLD: Duration = Duration::from_millis(50);
+
+/// Longest run of dropped frames before an identical one is let through.
+pub const DEFAULT_MAX_SUPPRESSION: Duration = Duration::from_millis(250);
+
+/// Fields left out of [`semantic_hash`], since they change with every frame
+/// whatever its content.
+const VOLATILE_FIELDS: [&str; 2] = ["timestamp", "sequence"];
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DedupConfig {
+    /// Identical frames closer together than this are dropped.
+    pub threshold: Duration,
+    /// An identical frame is let through once this long has passed since the
+    /// last one let through.
+    pub max_suppression: Duration,
+}
+
+impl Default for DedupConfig {
+    fn default() -> Self {
+        Self {
+            threshold: DEFAULT_DEDUP_THRESHOLD,
+            max_suppression: DEFAULT_MAX_SUPPRESSION,
+        }
+    }
+}
+
+/// Drops consecutive frames of one game with the same content.
+///
+/// Keeps only the previous frame's hash and timestamps, so memory does not
+/// grow with the stream.
+#[derive(Debug, Clone)]
+pub struct DedupFilter {
+    threshold_ns: u64,
+    max_suppression_ns: u64,
+    /// Hash and timestamp of the previous frame, admitted or not.
+    last: Option<(u64, u64)>,
+    /// Timestamp of the latest admitted frame.
+    last_admitted_ns: u64,
+    suppressed: u64,
+}
+
+impl DedupFilter {
+    pub fn new(config: DedupConfig) -> Self {
+        Self {
+            threshold_ns: duration_ns(config.threshold),
+            max_suppression_ns: duration_ns(config.max_suppression),
+            last: None,
+            last_admitted_ns: 0,
+            suppressed: 0,
+        }
+    }
+
+    /// Whether the frame carrying `data`, taken at `timestamp_ns`, should be
+    /// forwarded. A frame whose content cannot be hashed is always forwarded.
+    pub fn admit<T: Serialize + ?Sized>(&mut self, data: &T, timestamp_ns: u64) -> bool {
+        match semantic_hash(data) {
+            Ok(hash) => self.admit_hash(hash, timestamp_ns),
+            Err(_) => {
+                self.last = None;
+                self.last_admitted_ns = timestamp_ns;
+                true
+            }
+        }
+    }
+
+    /// [`Self::admit`] for a frame already hashed with [`semantic_hash`].
+    pub fn admit_hash(&mut self, hash: u64, timestamp_ns: u64) -> bool {
+        let previous = self.last.replace((hash, timestamp_ns));
+        let duplicate = previous.is_some_and(|(last_hash, last_ns)| {
+            last_hash == hash && timestamp_ns.saturating_sub(last_ns) < self.threshold_ns
+        });
+        let overdue = timestamp_ns.saturating_sub(self.last_admitted_ns) >= self.max_suppression_ns;
+        if duplicate && !overdue {
+            self.suppressed += 1;
+            return false;
+        }
+        self.last_admitted_ns = timestamp_ns;
+        true
+    }
+
+    /// Frames dropped since construction or the last [`Self::reset`].
+    pub fn suppressed(&self) -> u64 {
+        self.suppressed
+    }
+
+    /// Forget the previous frame, for a new source.
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.last_admitted_ns = 0;
+        self.suppressed = 0;
+    }
+}
+
+/// Stable hash of `data`'s serialized content, leaving out its top-level
+/// `timestamp` and `sequence` fields.
+///
+/// The content is fed to the hash straight from `data`'s `Serialize` impl,
+/// so hashing a frame allocates nothing.
+pub fn semantic_hash<T: Serialize + ?Sized>(data: &T) -> StreamResult<u64> {
+    let mut hash = Fnv1a::new();
+    data.serialize(ContentHasher {
+        hash: &mut hash,
+        top_level: true,
+    })
+    .map_err(|err| StreamError::ProcessingError(err.0))?;
+    Ok(hash.0)
+}
+
+// Tags of the encoding fed to the hash. Every value is self-delimiting, so no
+// two different values feed the same bytes.
+const NULL: u8 = 0;
+const BOOL: u8 = 1;
+/// Followed by the decimal digits, length-prefixed.
+const INTEGER: u8 = 2;
+const STRING: u8 = 3;
+/// Followed by the element count and the elements.
+const SEQUENCE: u8 = 4;
+/// Followed by the entry count and the wrapping sum of the entries' own
+/// hashes, so iteration order does not matter.
+const MAP: u8 = 5;
+/// Followed by `ITEM`-led name and value pairs, then `END`.
+const STRUCT: u8 = 6;
+/// Followed by the variant name and its content.
+const VARIANT: u8 = 7;
+/// Followed by the `f64` bits.
+const FLOAT: u8 = 8;
+/// A sequence of unknown length: `ITEM`-led elements, then `END`.
+const OPEN_SEQUENCE: u8 = 9;
+const ITEM: u8 = 1;
+const END: u8 = 0;
+
+/// 64-bit FNV-1a.
+#[derive(Clone, Copy)]
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    const fn new() -> Self {
+        Self(FNV_OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn len(&mut self, len: usize) {
+        self.write(&(len as u64).to_le_bytes());
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.len(bytes.len());
+        self.write(bytes);
+    }
+
+    /// Hash of `text` serialized on its own, as a map key is.
+    fn of_string(text: &str) -> u64 {
+        let mut hash = Self::new();
+        hash.write(&[STRING]);
+        hash.bytes(text.as_bytes());
+        hash.0
+    }
+}
+
+#[derive(Debug)]
+struct HashError(String);
+
+impl fmt::Display for HashError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for HashError {}
+
+impl ser::Error for HashError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+/// Decimal digits of an integer, formatted on the stack.
+struct Digits {
+    buf: [u8; 40],
+    len: usize,
+}
+
+impl fmt::Write for Digits {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        let end = self.len + text.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(text.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Feeds one value to the hash.
+struct ContentHasher<'a> {
+    hash: &'a mut Fnv1a,
+    /// Volatile fields are only left out of the outermost struct or map.
+    top_level: bool,
+}
+
+impl<'a> ContentHasher<'a> {
+    fn nested(hash: &'a mut Fnv1a) -> Self {
+        Self {
+            hash,
+            top_level: false,
+        }
+    }
+
+    fn integer(self, value: impl fmt::Display) -> Result<(), HashError> {
+        let mut digits = Digits {
+            buf: [0; 40],
+            len: 0,
+        };
+        write!(digits, "{value}").map_err(|_| HashError("integer too long".to_string()))?;
+        self.hash.write(&[INTEGER]);
+        self.hash.bytes(&digits.buf[..digits.len]);
+        Ok(())
+    }
+
+    fn float(self, value: f64) -> Result<(), HashError> {
+        self.hash.write(&[FLOAT]);
+        self.hash.write(&value.to_bits().to_le_bytes());
+        Ok(())
+    }
+
+    fn variant(&mut self, variant: &str) {
+        self.hash.write(&[VARIANT]);
+        self.hash.bytes(variant.as_bytes());
+    }
+}
+
+impl<'a> Serializer for ContentHasher<'a> {
+    type Ok = ();
+    type Error = HashError;
+    type SerializeSeq = SeqHasher<'a>;
+    type SerializeTuple = SeqHasher<'a>;
+    type SerializeTupleStruct = SeqHasher<'a>;
+    type SerializeTupleVariant = SeqHasher<'a>;
+    type SerializeMap = MapHasher<'a>;
+    type SerializeStruct = StructHasher<'a>;
+    type SerializeStructVariant = StructHasher<'a>;
+
+    fn serialize_bool(self, value: bool) -> Result<(), HashError> {
+        self.hash.write(&[BOOL, u8::from(value)]);
+        Ok(())
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<(), HashError> {
+        self.integer(value)
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<(), HashError> {
+        self.integer(value)
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<(), HashError> {
+        self.integer(value)
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<(), HashError> {
+        self.integer(value)
+    }
+
+    fn serialize_i128(self, value: i128) -> Result<(), HashError> {
+        self.integer(value)
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<(), HashError> {
+        self.integer(value)
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<(), HashError> {
+        self.integer(value)
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<(), HashError> {
+        self.integer(value)
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<(), HashError> {
+        self.integer(value)
+    }
+
+    fn serialize_u128(self, value: u128) -> Result<(), HashError> {
+        self.integer(value)
+    }
+
+    /// Widened, so the same reading hashes alike as `f32` or `f64`.
+    fn serialize_f32(self, value: f32) -> Result<(), HashError> {
+        self.float(f64::from(value))
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<(), HashError> {
+        self.float(value)
+    }
+
+    fn serialize_char(self, value: char) -> Result<(), HashError> {
+        self.serialize_str(value.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, value: &str) -> Result<(), HashError> {
+        self.hash.write(&[STRING]);
+        self.hash.bytes(value.as_bytes());
+        Ok(())
+    }
+
+    /// As a sequence of integers, the way serde's JSON form writes bytes.
+    fn serialize_bytes(self, value: &[u8]) -> Result<(), HashError> {
+        self.hash.write(&[SEQUENCE]);
+        self.hash.len(value.len());
+        for &byte in value {
+            ContentHasher::nested(self.hash).integer(byte)?;
+        }
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), HashError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), HashError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), HashError> {
+        self.hash.write(&[NULL]);
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), HashError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<(), HashError> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), HashError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        mut self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), HashError> {
+        self.variant(variant);
+        value.serialize(ContentHasher::nested(self.hash))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqHasher<'a>, HashError> {
+        match len {
+            Some(len) => {
+                self.hash.write(&[SEQUENCE]);
+                self.hash.len(len);
+            }
+            None => self.hash.write(&[OPEN_SEQUENCE]),
+        }
+        Ok(SeqHasher {
+            hash: self.hash,
+            counted: len.is_some(),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqHasher<'a>, HashError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SeqHasher<'a>, HashError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        mut self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SeqHasher<'a>, HashError> {
+        self.variant(variant);
+        ContentHasher::nested(self.hash).serialize_seq(Some(len))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<MapHasher<'a>, HashError> {
+        Ok(MapHasher {
+            hash: self.hash,
+            top_level: self.top_level,
+            entry: None,
+            entries: 0,
+            sum: 0,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<StructHasher<'a>, HashError> {
+        self.hash.write(&[STRUCT]);
+        Ok(StructHasher {
+            hash: self.hash,
+            top_level: self.top_level,
+        })
+    }
+
+    fn serialize_struct_variant(
+        mut self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<StructHasher<'a>, HashError> {
+        self.variant(variant);
+        ContentHasher::nested(self.hash).serialize_struct(variant, len)
+    }
+}
+
+struct SeqHasher<'a> {
+    hash: &'a mut Fnv1a,
+    /// The length was written up front, so elements need no markers.
+    counted: bool,
+}
+
+impl SeqHasher<'_> {
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), HashError> {
+        if !self.counted {
+            self.hash.write(&[ITEM]);
+        }
+        value.serialize(ContentHasher::nested(self.hash))
+    }
+
+    fn finish(self) -> Result<(), HashError> {
+        if !self.counted {
+            self.hash.write(&[END]);
+        }
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for SeqHasher<'_> {
+    type Ok = ();
+    type Error = HashError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), HashError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), HashError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SeqHasher<'_> {
+    type Ok = ();
+    type Error = HashError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), HashError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), HashError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqHasher<'_> {
+    type Ok = ();
+    type Error = HashError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), HashError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), HashError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SeqHasher<'_> {
+    type Ok = ();
+    type Error = HashError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), HashError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), HashError> {
+        self.finish()
+    }
+}
+
+/// Hashes each entry on its own and sums the hashes, so a map hashes the same
+/// whatever order it iterates in.
+struct MapHasher<'a> {
+    hash: &'a mut Fnv1a,
+    top_level: bool,
+    /// Hash of the current entry's key; `None` once a volatile key was seen.
+    entry: Option<Fnv1a>,
+    entries: usize,
+    sum: u64,
+}
+
+impl ser::SerializeMap for MapHasher<'_> {
+    type Ok = ();
+    type Error = HashError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), HashError> {
+        let mut entry = Fnv1a::new();
+        key.serialize(ContentHasher::nested(&mut entry))?;
+        let volatile = self.top_level
+            && VOLATILE_FIELDS
+                .iter()
+                .any(|field| entry.0 == Fnv1a::of_string(field));
+        self.entry = (!volatile).then_some(entry);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), HashError> {
+        let Some(mut entry) = self.entry.take() else {
+            return Ok(());
+        };
+        value.serialize(ContentHasher::nested(&mut entry))?;
+        self.entries += 1;
+        self.sum = self.sum.wrapping_add(entry.0);
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), HashError> {
+        self.hash.write(&[MAP]);
+        self.hash.len(self.entries);
+        self.hash.write(&self.sum.to_le_bytes());
+        Ok(())
+    }
+}
+
+struct StructHasher<'a> {
+    hash: &'a mut Fnv1a,
+    top_level: bool,
+}
+
+impl StructHasher<'_> {
+    fn field<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), HashError> {
+        if self.top_level && VOLATILE_FIELDS.contains(&key) {
+            return Ok(());
+        }
+        self.hash.write(&[ITEM]);
+        self.hash.bytes(key.as_bytes());
+        value.serialize(ContentHasher::nested(self.hash))
+    }
+
+    fn finish(self) -> Result<(), HashError> {
+        self.hash.write(&[END]);
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for StructHasher<'_> {
+    type Ok = ();
+    type Error = HashError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), HashError> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), HashError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for StructHasher<'_> {
+    type Ok = ();
+    type Error = HashError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), HashError> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), HashError> {
+        self.finish()
+    }
+}
+
+fn duration_ns(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
@@ -0,0 +1,253 @@
+//! Game telemetry lined up with wheelbase input reports.
+//!
+//! Telemetry frames and wheelbase reports arrive on separate streams at
+//! different rates, so "the driver turned the wheel" and "the car responded"
+//! cannot be compared sample by sample. [`SyncedInputTelemetry`] pairs each
+//! frame with the [`TimedWheelbaseInput`] nearest to it in time, calibrated
+//! into steering and pedal positions, and records the skew between the two.
+//! A frame with no input within the tolerance carries `input: None` rather
+//! than a stale reading.
+//!
+//! Both streams must be stamped on the same clock and arrive in timestamp
+//! order. [`SyncedInputTelemetry::merge`] pairs two complete recordings in
+//! one pass; [`SyncedInputTelemetry::push_frame`],
+//! [`SyncedInputTelemetry::push_input`] and [`SyncedInputTelemetry::pop`]
+//! pair live streams with the same result, and [`spawn_input_sync`] drives
+//! them from channels.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use racing_wheel_moza_wheelbase_report::{
+    TimedWheelbaseInput, WheelbaseCalibration, WheelbaseInputNormalized,
+};
+use racing_wheel_telemetry_contracts::TelemetryFrame;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Largest distance between a frame and the input paired with it.
+pub const DEFAULT_SYNC_TOLERANCE: Duration = Duration::from_millis(10);
+
+/// How far, in frame time, a live frame waits for an input at or after it
+/// before it is paired with the inputs received so far.
+pub const DEFAULT_MAX_INPUT_LAG: Duration = Duration::from_millis(100);
+
+/// Inputs buffered while no frame arrives to consume them; the oldest are
+/// dropped beyond this.
+pub const MAX_PENDING_INPUTS: usize = 4096;
+
+/// A telemetry frame with the wheelbase input nearest to it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputSyncedFrame {
+    pub frame: TelemetryFrame,
+    /// Calibrated input nearest the frame, or `None` if none arrived within
+    /// the tolerance.
+    pub input: Option<WheelbaseInputNormalized>,
+    /// The input's timestamp minus the frame's; `None` without an input.
+    pub sync_skew_ns: Option<i64>,
+}
+
+/// Pairs telemetry frames with the nearest wheelbase input.
+#[derive(Debug, Clone)]
+pub struct SyncedInputTelemetry {
+    calibration: WheelbaseCalibration,
+    tolerance_ns: u64,
+    max_input_lag_ns: u64,
+    /// Received inputs still able to be nearest to a coming frame.
+    inputs: VecDeque<TimedWheelbaseInput>,
+    /// Frames waiting for an input at or after them.
+    frames: VecDeque<TelemetryFrame>,
+}
+
+impl SyncedInputTelemetry {
+    /// Normalize inputs through `calibration`, with the default tolerance
+    /// and input lag.
+    pub fn new(calibration: WheelbaseCalibration) -> Self {
+        Self {
+            calibration,
+            tolerance_ns: duration_ns(DEFAULT_SYNC_TOLERANCE),
+            max_input_lag_ns: duration_ns(DEFAULT_MAX_INPUT_LAG),
+            inputs: VecDeque::new(),
+            frames: VecDeque::new(),
+        }
+    }
+
+    pub fn with_tolerance(mut self, tolerance: Duration) -> Self {
+        self.tolerance_ns = duration_ns(tolerance);
+        self
+    }
+
+    pub fn with_max_input_lag(mut self, max_input_lag: Duration) -> Self {
+        self.max_input_lag_ns = duration_ns(max_input_lag);
+        self
+    }
+
+    pub fn tolerance(&self) -> Duration {
+        Duration::from_nanos(self.tolerance_ns)
+    }
+
+    /// Pair every frame of `frames` with its nearest input in `inputs`, in
+    /// O(n + m). Both must be sorted by timestamp.
+    pub fn merge(
+        &self,
+        frames: Vec<TelemetryFrame>,
+        inputs: &[TimedWheelbaseInput],
+    ) -> Vec<InputSyncedFrame> {
+        let mut cursor = 0;
+        frames
+            .into_iter()
+            .map(|frame| {
+                let at = frame.timestamp_ns;
+                while inputs
+                    .get(cursor + 1)
+                    .is_some_and(|next| distance(next, at) <= distance(&inputs[cursor], at))
+                {
+                    cursor += 1;
+                }
+                self.pair(frame, inputs.get(cursor))
+            })
+            .collect()
+    }
+
+    /// Buffer a live input. One older than the last input is dropped.
+    pub fn push_input(&mut self, input: TimedWheelbaseInput) {
+        if self
+            .inputs
+            .back()
+            .is_some_and(|last| input.timestamp_ns < last.timestamp_ns)
+        {
+            return;
+        }
+        if self.inputs.len() == MAX_PENDING_INPUTS {
+            self.inputs.pop_front();
+        }
+        self.inputs.push_back(input);
+    }
+
+    /// Buffer a live frame until its input is known.
+    pub fn push_frame(&mut self, frame: TelemetryFrame) {
+        self.frames.push_back(frame);
+    }
+
+    /// The oldest buffered frame, once an input at or after it has arrived
+    /// or it has waited out the input lag.
+    pub fn pop(&mut self) -> Option<InputSyncedFrame> {
+        let at = self.frames.front()?.timestamp_ns;
+        let input_final = self
+            .inputs
+            .back()
+            .is_some_and(|last| last.timestamp_ns >= at);
+        let waited_out = self
+            .frames
+            .back()
+            .is_some_and(|newest| newest.timestamp_ns.saturating_sub(at) > self.max_input_lag_ns);
+        if !input_final && !waited_out {
+            return None;
+        }
+        let frame = self.frames.pop_front()?;
+        Some(self.resolve(frame))
+    }
+
+    /// Every buffered frame, paired with the inputs received so far, for when
+    /// the input stream has ended.
+    pub fn finish(&mut self) -> Vec<InputSyncedFrame> {
+        let frames: Vec<_> = self.frames.drain(..).collect();
+        frames
+            .into_iter()
+            .map(|frame| self.resolve(frame))
+            .collect()
+    }
+
+    /// Frames waiting for their input.
+    pub fn pending_frames(&self) -> usize {
+        self.frames.len()
+    }
+
+    fn resolve(&mut self, frame: TelemetryFrame) -> InputSyncedFrame {
+        let at = frame.timestamp_ns;
+        // Frames come in order, so an input farther from this frame than the
+        // one after it is farther from every later frame too.
+        while self.inputs.len() >= 2
+            && distance(&self.inputs[1], at) <= distance(&self.inputs[0], at)
+        {
+            self.inputs.pop_front();
+        }
+        let nearest = self.inputs.front().copied();
+        self.pair(frame, nearest.as_ref())
+    }
+
+    fn pair(
+        &self,
+        frame: TelemetryFrame,
+        nearest: Option<&TimedWheelbaseInput>,
+    ) -> InputSyncedFrame {
+        let at = frame.timestamp_ns;
+        let matched = nearest.filter(|input| distance(input, at) <= self.tolerance_ns);
+        InputSyncedFrame {
+            input: matched.map(|input| input.input.normalize(&self.calibration)),
+            sync_skew_ns: matched.map(|input| skew_ns(input.timestamp_ns, at)),
+            frame,
+        }
+    }
+}
+
+/// Pair `frames` with `inputs` on a spawned task and send the results to
+/// `sender` in frame order.
+///
+/// Once `inputs` closes, frames are paired with the inputs received so far
+/// as they arrive. Once `frames` closes, inputs are read until every
+/// buffered frame is paired. The task ends when everything is sent, or when
+/// the receiver is dropped. Must be called inside a Tokio runtime.
+pub fn spawn_input_sync(
+    mut merger: SyncedInputTelemetry,
+    mut frames: mpsc::Receiver<TelemetryFrame>,
+    mut inputs: mpsc::Receiver<TimedWheelbaseInput>,
+    sender: mpsc::Sender<InputSyncedFrame>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let (mut frames_open, mut inputs_open) = (true, true);
+        while frames_open || (inputs_open && merger.pending_frames() > 0) {
+            tokio::select! {
+                frame = frames.recv(), if frames_open => match frame {
+                    Some(frame) => merger.push_frame(frame),
+                    None => frames_open = false,
+                },
+                input = inputs.recv(), if inputs_open => match input {
+                    Some(input) => merger.push_input(input),
+                    None => inputs_open = false,
+                },
+            }
+            let ready: Vec<_> = if inputs_open {
+                std::iter::from_fn(|| merger.pop()).collect()
+            } else {
+                merger.finish()
+            };
+            for record in ready {
+                if sender.send(record).await.is_err() {
+                    return;
+                }
+            }
+        }
+        for record in merger.finish() {
+            if sender.send(record).await.is_err() {
+                return;
+            }
+        }
+    })
+}
+
+fn duration_ns(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+fn distance(input: &TimedWheelbaseInput, at: u64) -> u64 {
+    input.timestamp_ns.abs_diff(at)
+}
+
+fn skew_ns(input_ns: u64, frame_ns: u64) -> i64 {
+    if input_ns >= frame_ns {
+        i64::try_from(input_ns - frame_ns).unwrap_or(i64::MAX)
+    } else {
+        i64::try_from(frame_ns - input_ns).map_or(i64::MIN, |skew| -skew)
+    }
+}
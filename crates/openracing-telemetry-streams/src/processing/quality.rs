@@ -0,0 +1,276 @@
+//! Stream quality from frame sequence numbers and arrival times.
+//!
+//! UDP adapters lose, repeat and reorder packets. A [`StreamQualityMonitor`]
+//! classifies each frame by its `sequence` against the highest one seen and
+//! a window of recent ones, and tracks arrival jitter from `timestamp_ns`,
+//! so a "telemetry feels laggy" report can be told apart from packet loss.
+//!
+//! A sequence number that moves backwards is a late frame only while it is
+//! within [`REORDER_WINDOW`] of the highest one and its original, if any,
+//! arrived within the reorder horizon. Anything else, including wrapping
+//! past `u64::MAX` and a source that restarts counting from 0 while its
+//! frames keep arriving, starts the sequence over instead of counting a gap.
+
+use std::time::Duration;
+
+use racing_wheel_telemetry_contracts::TelemetryFrame;
+use serde::{Deserialize, Serialize};
+
+/// Sequence numbers behind the highest one that can still arrive late.
+pub const REORDER_WINDOW: u64 = 64;
+
+/// Largest forward jump counted as lost frames; a larger one is a restart.
+pub const MAX_SEQUENCE_JUMP: u64 = 1 << 20;
+
+/// How long after a sequence number first arrives a repeat of it is still a
+/// duplicate rather than a restarted counter.
+pub const DEFAULT_REORDER_HORIZON: Duration = Duration::from_secs(1);
+
+/// How a frame's sequence number relates to the frames before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceEvent {
+    /// The first frame, or the one after the highest so far.
+    InOrder,
+    /// Ahead of the next expected number; `missing` frames were skipped.
+    Gap { missing: u64 },
+    /// A number already seen.
+    Duplicate,
+    /// A skipped number arriving late.
+    OutOfOrder,
+    /// The sequence started over: wrapped, reset or jumped implausibly far.
+    Restart,
+}
+
+/// Counters and timing of one stream, as of the latest frame.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StreamQuality {
+    /// Frames observed, duplicates included.
+    pub frames: u64,
+    /// Forward jumps in the sequence.
+    pub gaps: u64,
+    /// Frames skipped by gaps and never received.
+    pub missing_frames: u64,
+    pub duplicates: u64,
+    /// Skipped frames that arrived late.
+    pub out_of_order: u64,
+    /// Times the sequence started over.
+    pub restarts: u64,
+    /// Arrival rate over the whole stream; `None` until two frames arrive.
+    pub observed_rate_hz: Option<f64>,
+    /// Rate the source promises, if known.
+    pub expected_rate_hz: Option<f64>,
+    /// Standard deviation of the time between arrivals, in milliseconds;
+    /// `None` until two intervals are known.
+    pub jitter_ms: Option<f64>,
+}
+
+impl StreamQuality {
+    /// Share of the frames the sequence numbers promised that never came.
+    pub fn loss_ratio(&self) -> f64 {
+        let unique = self.frames.saturating_sub(self.duplicates);
+        let promised = unique + self.missing_frames;
+        if promised == 0 {
+            0.0
+        } else {
+            self.missing_frames as f64 / promised as f64
+        }
+    }
+
+    /// The handful of numbers worth showing next to connection health.
+    pub fn summary(&self) -> StreamQualitySummary {
+        StreamQualitySummary {
+            loss_ratio: self.loss_ratio(),
+            gaps: self.gaps,
+            duplicates: self.duplicates,
+            out_of_order: self.out_of_order,
+            jitter_ms: self.jitter_ms,
+        }
+    }
+}
+
+/// Compact [`StreamQuality`] for health reports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct StreamQualitySummary {
+    pub loss_ratio: f64,
+    pub gaps: u64,
+    pub duplicates: u64,
+    pub out_of_order: u64,
+    pub jitter_ms: Option<f64>,
+}
+
+/// Recently seen sequence number and when it arrived.
+#[derive(Debug, Clone, Copy)]
+struct Seen {
+    sequence: u64,
+    timestamp_ns: u64,
+}
+
+/// Watches one stream's sequence numbers and arrival times.
+#[derive(Debug, Clone)]
+pub struct StreamQualityMonitor {
+    expected_interval: Option<Duration>,
+    reorder_horizon_ns: u64,
+    highest: Option<u64>,
+    /// Indexed by sequence modulo [`REORDER_WINDOW`].
+    recent: [Option<Seen>; REORDER_WINDOW as usize],
+    first_ns: u64,
+    last_ns: u64,
+    /// Welford moments of the inter-arrival times, in nanoseconds.
+    intervals: u64,
+    interval_mean: f64,
+    interval_m2: f64,
+    quality: StreamQuality,
+}
+
+impl Default for StreamQualityMonitor {
+    fn default() -> Self {
+        Self {
+            expected_interval: None,
+            reorder_horizon_ns: DEFAULT_REORDER_HORIZON.as_nanos() as u64,
+            highest: None,
+            recent: [None; REORDER_WINDOW as usize],
+            first_ns: 0,
+            last_ns: 0,
+            intervals: 0,
+            interval_mean: 0.0,
+            interval_m2: 0.0,
+            quality: StreamQuality::default(),
+        }
+    }
+}
+
+impl StreamQualityMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report `expected_rate_hz` from the interval a source promises, e.g.
+    /// its adapter's `expected_update_rate()`.
+    pub fn with_expected_interval(mut self, interval: Duration) -> Self {
+        self.expected_interval = (!interval.is_zero()).then_some(interval);
+        self
+    }
+
+    /// Override [`DEFAULT_REORDER_HORIZON`].
+    pub fn with_reorder_horizon(mut self, horizon: Duration) -> Self {
+        self.reorder_horizon_ns = horizon.as_nanos().min(u128::from(u64::MAX)) as u64;
+        self
+    }
+
+    pub fn push(&mut self, frame: &TelemetryFrame) -> SequenceEvent {
+        self.observe(frame.sequence, frame.timestamp_ns)
+    }
+
+    /// Account for a frame with `sequence` that arrived at `timestamp_ns`.
+    pub fn observe(&mut self, sequence: u64, timestamp_ns: u64) -> SequenceEvent {
+        self.record_arrival(timestamp_ns);
+        self.quality.frames += 1;
+
+        let Some(highest) = self.highest else {
+            self.restart(sequence, timestamp_ns);
+            return SequenceEvent::InOrder;
+        };
+        let event = if sequence > highest {
+            let jump = sequence - highest;
+            if jump > MAX_SEQUENCE_JUMP {
+                SequenceEvent::Restart
+            } else {
+                self.highest = Some(sequence);
+                self.remember(sequence, timestamp_ns);
+                match jump - 1 {
+                    0 => SequenceEvent::InOrder,
+                    missing => SequenceEvent::Gap { missing },
+                }
+            }
+        } else if highest - sequence >= REORDER_WINDOW {
+            SequenceEvent::Restart
+        } else {
+            match self.recent[slot(sequence)] {
+                Some(seen) if seen.sequence == sequence => {
+                    if timestamp_ns.saturating_sub(seen.timestamp_ns) > self.reorder_horizon_ns {
+                        // Long after the original: the counter started over.
+                        SequenceEvent::Restart
+                    } else {
+                        SequenceEvent::Duplicate
+                    }
+                }
+                _ => {
+                    self.remember(sequence, timestamp_ns);
+                    SequenceEvent::OutOfOrder
+                }
+            }
+        };
+
+        let quality = &mut self.quality;
+        match event {
+            SequenceEvent::InOrder => {}
+            SequenceEvent::Gap { missing } => {
+                quality.gaps += 1;
+                quality.missing_frames += missing;
+            }
+            SequenceEvent::Duplicate => quality.duplicates += 1,
+            SequenceEvent::OutOfOrder => {
+                quality.out_of_order += 1;
+                quality.missing_frames = quality.missing_frames.saturating_sub(1);
+            }
+            SequenceEvent::Restart => {
+                quality.restarts += 1;
+                self.restart(sequence, timestamp_ns);
+            }
+        }
+        event
+    }
+
+    /// Counters and timing so far.
+    pub fn quality(&self) -> StreamQuality {
+        let mut quality = self.quality.clone();
+        let span_ns = self.last_ns.saturating_sub(self.first_ns);
+        quality.observed_rate_hz = (quality.frames > 1 && span_ns > 0)
+            .then(|| (quality.frames - 1) as f64 * 1e9 / span_ns as f64);
+        quality.expected_rate_hz = self
+            .expected_interval
+            .map(|interval| 1.0 / interval.as_secs_f64());
+        quality.jitter_ms = (self.intervals > 1)
+            .then(|| (self.interval_m2 / (self.intervals - 1) as f64).sqrt() / 1e6);
+        quality
+    }
+
+    /// Forget everything observed, keeping the configuration.
+    pub fn reset(&mut self) {
+        *self = Self {
+            expected_interval: self.expected_interval,
+            reorder_horizon_ns: self.reorder_horizon_ns,
+            ..Self::default()
+        };
+    }
+
+    fn record_arrival(&mut self, timestamp_ns: u64) {
+        if self.quality.frames == 0 {
+            self.first_ns = timestamp_ns;
+        } else if timestamp_ns >= self.last_ns {
+            let interval = (timestamp_ns - self.last_ns) as f64;
+            self.intervals += 1;
+            let delta = interval - self.interval_mean;
+            self.interval_mean += delta / self.intervals as f64;
+            self.interval_m2 += delta * (interval - self.interval_mean);
+        }
+        self.last_ns = self.last_ns.max(timestamp_ns);
+    }
+
+    fn restart(&mut self, sequence: u64, timestamp_ns: u64) {
+        self.recent = [None; REORDER_WINDOW as usize];
+        self.highest = Some(sequence);
+        self.remember(sequence, timestamp_ns);
+    }
+
+    fn remember(&mut self, sequence: u64, timestamp_ns: u64) {
+        self.recent[slot(sequence)] = Some(Seen {
+            sequence,
+            timestamp_ns,
+        });
+    }
+}
+
+fn slot(sequence: u64) -> usize {
+    (sequence % REORDER_WINDOW) as usize
+}
@@ -0,0 +1,379 @@
+//! Streaming per-channel statistics over a telemetry session.
+//!
+//! [`ChannelStats`] keeps, per channel, Welford moments for mean and
+//! standard deviation and a bounded t-digest for quantiles, so memory stays
+//! fixed however long the session runs. Its [`StatsReport`] snapshots merge,
+//! which lets per-lap reports taken at `SessionSegmenter` lap boundaries be
+//! folded into one session report.
+
+use std::collections::BTreeMap;
+use std::f64::consts::PI;
+
+use racing_wheel_telemetry_contracts::{NormalizedTelemetry, TelemetryFrame, TelemetryValue};
+use serde::{Deserialize, Serialize};
+
+/// Typed fields of [`NormalizedTelemetry`] a [`ChannelStats`] can track.
+///
+/// Any other channel name is looked up in `extended`.
+pub const CORE_STATS_CHANNELS: &[&str] = &[
+    "ffb_scalar",
+    "rpm",
+    "speed_ms",
+    "slip_ratio",
+    "gear",
+    "steering_angle",
+    "throttle",
+    "brake",
+    "lateral_g",
+    "longitudinal_g",
+    "slip_angle_fl",
+    "slip_angle_fr",
+    "slip_angle_rl",
+    "slip_angle_rr",
+];
+
+/// Value of `channel` in `data`, if the frame carries it as a number.
+fn channel_value(data: &NormalizedTelemetry, channel: &str) -> Option<f64> {
+    let value = match channel {
+        "ffb_scalar" => data.ffb_scalar,
+        "rpm" => data.rpm,
+        "speed_ms" => data.speed_ms,
+        "slip_ratio" => data.slip_ratio,
+        "gear" => return data.gear.map(f64::from),
+        "steering_angle" => data.steering_angle,
+        "throttle" => data.throttle,
+        "brake" => data.brake,
+        "lateral_g" => data.lateral_g,
+        "longitudinal_g" => data.longitudinal_g,
+        "slip_angle_fl" => data.slip_angle_fl,
+        "slip_angle_fr" => data.slip_angle_fr,
+        "slip_angle_rl" => data.slip_angle_rl,
+        "slip_angle_rr" => data.slip_angle_rr,
+        key => {
+            return match data.extended.get(key)? {
+                TelemetryValue::Float(value) => Some(f64::from(*value)),
+                TelemetryValue::Integer(value) => Some(f64::from(*value)),
+                TelemetryValue::Boolean(_) | TelemetryValue::String(_) => None,
+            };
+        }
+    };
+    value.map(f64::from)
+}
+
+/// Accumulates per-channel statistics from a frame stream.
+#[derive(Debug, Clone)]
+pub struct ChannelStats {
+    channels: Vec<String>,
+    summaries: Vec<ChannelSummary>,
+}
+
+impl Default for ChannelStats {
+    /// Tracks every channel of [`CORE_STATS_CHANNELS`].
+    fn default() -> Self {
+        Self::new(CORE_STATS_CHANNELS.iter().copied())
+    }
+}
+
+impl ChannelStats {
+    /// Track `channels`: typed field names or extended keys.
+    pub fn new<I, S>(channels: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut names: Vec<String> = channels.into_iter().map(Into::into).collect();
+        names.sort();
+        names.dedup();
+        Self {
+            summaries: vec![ChannelSummary::default(); names.len()],
+            channels: names,
+        }
+    }
+
+    /// Tracked channel names, sorted.
+    pub fn channels(&self) -> &[String] {
+        &self.channels
+    }
+
+    pub fn push(&mut self, frame: &TelemetryFrame) {
+        for (channel, summary) in self.channels.iter().zip(&mut self.summaries) {
+            summary.record(channel_value(&frame.data, channel));
+        }
+    }
+
+    /// The statistics so far.
+    pub fn snapshot(&self) -> StatsReport {
+        let channels = self
+            .channels
+            .iter()
+            .zip(&self.summaries)
+            .map(|(channel, summary)| {
+                let mut summary = summary.clone();
+                summary.refresh();
+                (channel.clone(), summary)
+            })
+            .collect();
+        StatsReport { channels }
+    }
+
+    /// [`snapshot`](Self::snapshot), then start over; call at each lap
+    /// boundary to get per-lap reports.
+    pub fn take(&mut self) -> StatsReport {
+        let report = self.snapshot();
+        self.summaries.fill(ChannelSummary::default());
+        report
+    }
+}
+
+/// Statistics of every tracked channel, keyed by channel name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StatsReport {
+    pub channels: BTreeMap<String, ChannelSummary>,
+}
+
+impl StatsReport {
+    pub fn channel(&self, name: &str) -> Option<&ChannelSummary> {
+        self.channels.get(name)
+    }
+
+    /// Fold `other` in, as if its frames had been pushed after ours.
+    ///
+    /// Channels only one side tracked are kept as they are.
+    pub fn merge(&mut self, other: &StatsReport) {
+        for (name, theirs) in &other.channels {
+            self.channels
+                .entry(name.clone())
+                .and_modify(|ours| ours.merge(theirs))
+                .or_insert_with(|| theirs.clone());
+        }
+    }
+}
+
+/// Statistics of one channel.
+///
+/// The derived figures (`mean` through `p99`) are `None` until the channel
+/// has a finite sample. The standard deviation is the population one.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChannelSummary {
+    /// Frames seen while the channel was tracked.
+    pub frames: u64,
+    /// Frames carrying the channel, including non-finite values.
+    pub present: u64,
+    /// NaN or infinite samples, left out of every statistic.
+    pub non_finite: u64,
+    /// Finite samples behind the statistics.
+    pub samples: u64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub mean: Option<f64>,
+    pub stddev: Option<f64>,
+    pub p50: Option<f64>,
+    pub p95: Option<f64>,
+    pub p99: Option<f64>,
+    /// Welford sum of squared deviations from the mean.
+    m2: f64,
+    digest: QuantileDigest,
+}
+
+impl ChannelSummary {
+    /// Share of frames that carried the channel, in `0.0..=1.0`.
+    pub fn presence(&self) -> f64 {
+        if self.frames == 0 {
+            0.0
+        } else {
+            self.present as f64 / self.frames as f64
+        }
+    }
+
+    /// Estimated value at quantile `q` in `0.0..=1.0`.
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        let mut digest = self.digest.clone();
+        digest.compress();
+        digest.quantile(q)
+    }
+
+    fn record(&mut self, value: Option<f64>) {
+        self.frames += 1;
+        let Some(value) = value else {
+            return;
+        };
+        self.present += 1;
+        if !value.is_finite() {
+            self.non_finite += 1;
+            return;
+        }
+        self.samples += 1;
+        let mean = self.mean.unwrap_or(0.0);
+        let delta = value - mean;
+        let mean = mean + delta / self.samples as f64;
+        self.m2 += delta * (value - mean);
+        self.mean = Some(mean);
+        self.min = Some(self.min.map_or(value, |min| min.min(value)));
+        self.max = Some(self.max.map_or(value, |max| max.max(value)));
+        self.digest.insert(value);
+    }
+
+    fn merge(&mut self, other: &ChannelSummary) {
+        self.frames += other.frames;
+        self.present += other.present;
+        self.non_finite += other.non_finite;
+        match (self.mean, other.mean) {
+            (_, None) => {}
+            (None, Some(_)) => {
+                self.samples = other.samples;
+                self.mean = other.mean;
+                self.m2 = other.m2;
+            }
+            (Some(ours), Some(theirs)) => {
+                // Chan et al.'s pairwise combination of Welford moments.
+                let (n_a, n_b) = (self.samples as f64, other.samples as f64);
+                let n = n_a + n_b;
+                let delta = theirs - ours;
+                self.mean = Some(ours + delta * n_b / n);
+                self.m2 += other.m2 + delta * delta * n_a * n_b / n;
+                self.samples += other.samples;
+            }
+        }
+        self.min = option_fold(self.min, other.min, f64::min);
+        self.max = option_fold(self.max, other.max, f64::max);
+        self.digest.merge(&other.digest);
+        self.refresh();
+    }
+
+    /// Recompute the derived figures from the accumulated state.
+    fn refresh(&mut self) {
+        self.digest.compress();
+        self.stddev = (self.samples > 0).then(|| (self.m2 / self.samples as f64).sqrt());
+        self.p50 = self.digest.quantile(0.50);
+        self.p95 = self.digest.quantile(0.95);
+        self.p99 = self.digest.quantile(0.99);
+    }
+}
+
+fn option_fold(a: Option<f64>, b: Option<f64>, f: fn(f64, f64) -> f64) -> Option<f64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(f(a, b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Centroids a digest aims for; quantile error shrinks as this grows.
+const DIGEST_COMPRESSION: f64 = 100.0;
+
+/// Samples buffered before they are folded into the centroids.
+const DIGEST_BUFFER: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+/// Merging t-digest with the arcsine scale function: at most about
+/// [`DIGEST_COMPRESSION`] centroids plus [`DIGEST_BUFFER`] pending samples,
+/// with the finest resolution at the tails where p95/p99 live.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct QuantileDigest {
+    centroids: Vec<Centroid>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pending: Vec<f64>,
+    min: f64,
+    max: f64,
+}
+
+impl QuantileDigest {
+    fn insert(&mut self, value: f64) {
+        if self.centroids.is_empty() && self.pending.is_empty() {
+            (self.min, self.max) = (value, value);
+        }
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.pending.push(value);
+        if self.pending.len() >= DIGEST_BUFFER {
+            self.compress();
+        }
+    }
+
+    fn merge(&mut self, other: &QuantileDigest) {
+        if other.centroids.is_empty() && other.pending.is_empty() {
+            return;
+        }
+        if self.centroids.is_empty() && self.pending.is_empty() {
+            *self = other.clone();
+            return;
+        }
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.centroids.extend_from_slice(&other.centroids);
+        self.pending.extend_from_slice(&other.pending);
+        self.compress();
+    }
+
+    fn scale(q: f64) -> f64 {
+        DIGEST_COMPRESSION / (2.0 * PI) * (2.0 * q - 1.0).clamp(-1.0, 1.0).asin()
+    }
+
+    /// Fold pending samples in and re-merge neighbouring centroids.
+    fn compress(&mut self) {
+        let mut all = std::mem::take(&mut self.centroids);
+        all.extend(
+            self.pending
+                .drain(..)
+                .map(|mean| Centroid { mean, weight: 1.0 }),
+        );
+        all.sort_by(|a, b| a.mean.total_cmp(&b.mean));
+        let total: f64 = all.iter().map(|c| c.weight).sum();
+
+        let mut merged = Vec::with_capacity(all.len().min(2 * DIGEST_COMPRESSION as usize));
+        let mut centroids = all.into_iter();
+        let Some(mut current) = centroids.next() else {
+            return;
+        };
+        let mut before = 0.0;
+        for next in centroids {
+            let weight = current.weight + next.weight;
+            let span = Self::scale((before + weight) / total) - Self::scale(before / total);
+            if span <= 1.0 {
+                current.mean += (next.mean - current.mean) * next.weight / weight;
+                current.weight = weight;
+            } else {
+                before += current.weight;
+                merged.push(current);
+                current = next;
+            }
+        }
+        merged.push(current);
+        self.centroids = merged;
+    }
+
+    /// Interpolated value at `q`; expects a compressed digest.
+    fn quantile(&self, q: f64) -> Option<f64> {
+        if self.centroids.is_empty() {
+            return None;
+        }
+        let total: f64 = self.centroids.iter().map(|c| c.weight).sum();
+        let target = q.clamp(0.0, 1.0) * total;
+
+        // Each centroid sits at the middle of its weight; the extremes pin
+        // both ends.
+        let mut previous = (0.0, self.min);
+        let mut before = 0.0;
+        for centroid in &self.centroids {
+            let point = (before + centroid.weight / 2.0, centroid.mean);
+            if target <= point.0 {
+                return Some(interpolate(previous, point, target));
+            }
+            previous = point;
+            before += centroid.weight;
+        }
+        Some(interpolate(previous, (total, self.max), target))
+    }
+}
+
+fn interpolate((x0, y0): (f64, f64), (x1, y1): (f64, f64), x: f64) -> f64 {
+    if x1 <= x0 {
+        y1
+    } else {
+        y0 + (y1 - y0) * ((x - x0) / (x1 - x0)).clamp(0.0, 1.0)
+    }
+}
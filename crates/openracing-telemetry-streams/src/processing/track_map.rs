@@ -0,0 +1,378 @@
+//! Track maps built from world positions, for games without lap distance.
+//!
+//! A [`TrackMapBuilder`] traces the player's `world_position` lap by lap. A
+//! lap closes when the car returns to where the trace started, at its
+//! nearest approach; the trace starts at the first lap-number change, so at
+//! the line, or at the first position for games that report no lap number.
+//! Each closed lap is resampled to a fixed number of evenly spaced points,
+//! so memory stays bounded however long the session runs.
+//!
+//! Laps through the pit lane, or cut short, trace a different length; only
+//! laps within a tolerance of the median length go into the [`TrackMap`].
+//! The map is serializable, so a later session on the same `track_id` can
+//! load it and derive `lap_distance_fraction` from position alone.
+
+use racing_wheel_telemetry_contracts::NormalizedTelemetry;
+use serde::{Deserialize, Serialize};
+
+/// Points a map is resampled to unless configured otherwise.
+pub const DEFAULT_MAP_POINTS: usize = 512;
+
+/// How near the start point, in metres, the car must come back to close a lap.
+pub const DEFAULT_CLOSE_DISTANCE_M: f32 = 30.0;
+
+/// Largest share a lap's length may differ from the median and still count.
+pub const DEFAULT_MAX_LENGTH_DEVIATION: f32 = 0.02;
+
+/// Positions nearer than this to the last traced one are skipped, so a car
+/// parked in the pits does not grow the trace.
+const MIN_SAMPLE_SPACING_M: f32 = 0.5;
+
+/// A move this far between two frames is a teleport, e.g. back to the pits,
+/// and abandons the lap being traced.
+const MAX_SAMPLE_JUMP_M: f32 = 100.0;
+
+/// Longest trace kept for one lap; a trace that never closes, such as a
+/// point-to-point stage, is abandoned past it.
+const MAX_TRACE_SAMPLES: usize = 200_000;
+
+/// Centre line of one track, as evenly spaced points around a lap.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrackMap {
+    pub track_id: String,
+    /// Lap length in metres along the polyline.
+    pub length_m: f32,
+    /// World positions, starting where the lap starts; the last point joins
+    /// back to the first.
+    pub points: Vec<[f32; 3]>,
+}
+
+impl TrackMap {
+    /// How far around the lap `position` is, from its projection onto the
+    /// nearest segment of the map; `None` for an empty map or a position
+    /// with a non-finite axis.
+    pub fn lap_distance_fraction(&self, position: [f32; 3]) -> Option<f32> {
+        if self.points.len() < 2
+            || !self.length_m.is_finite()
+            || self.length_m <= 0.0
+            || !position.iter().all(|axis| axis.is_finite())
+        {
+            return None;
+        }
+        let mut travelled = 0.0;
+        let mut nearest: Option<(f32, f32)> = None;
+        for (index, &from) in self.points.iter().enumerate() {
+            let to = self.points[(index + 1) % self.points.len()];
+            let segment = sub(to, from);
+            let segment_length = norm(segment);
+            let along = if segment_length > 0.0 {
+                (dot(sub(position, from), segment) / (segment_length * segment_length))
+                    .clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let projected = add(from, scale(segment, along));
+            let offset = norm(sub(position, projected));
+            if nearest.is_none_or(|(best, _)| offset < best) {
+                nearest = Some((offset, travelled + along * segment_length));
+            }
+            travelled += segment_length;
+        }
+        nearest.map(|(_, distance)| (distance / self.length_m).clamp(0.0, 1.0))
+    }
+
+    /// Set `lap_distance_fraction` on `telemetry` from its `world_position`
+    /// when the game left it unset and the frame is on this map's track.
+    pub fn fill_lap_distance(&self, telemetry: &mut NormalizedTelemetry) {
+        if telemetry.lap_distance_fraction.is_some()
+            || telemetry
+                .track_id
+                .as_deref()
+                .is_some_and(|track| track != self.track_id)
+        {
+            return;
+        }
+        telemetry.lap_distance_fraction = telemetry
+            .world_position
+            .and_then(|position| self.lap_distance_fraction(position));
+    }
+}
+
+/// One closed lap, already resampled.
+#[derive(Debug, Clone)]
+struct TracedLap {
+    length_m: f32,
+    points: Vec<[f32; 3]>,
+}
+
+/// Traces laps of one track and builds its [`TrackMap`].
+#[derive(Debug, Clone)]
+pub struct TrackMapBuilder {
+    track_id: String,
+    map_points: usize,
+    close_distance_m: f32,
+    max_length_deviation: f32,
+    last_lap_number: Option<i32>,
+    start: Option<[f32; 3]>,
+    trace: Vec<[f32; 3]>,
+    left_start: bool,
+    /// Index into `trace` of the nearest approach to the start so far, once
+    /// the car is back within the close distance.
+    closest: Option<(usize, f32)>,
+    /// The lap being traced is incomplete and closes without being kept.
+    discard_lap: bool,
+    laps: Vec<TracedLap>,
+}
+
+impl TrackMapBuilder {
+    pub fn new(track_id: impl Into<String>) -> Self {
+        Self {
+            track_id: track_id.into(),
+            map_points: DEFAULT_MAP_POINTS,
+            close_distance_m: DEFAULT_CLOSE_DISTANCE_M,
+            max_length_deviation: DEFAULT_MAX_LENGTH_DEVIATION,
+            last_lap_number: None,
+            start: None,
+            trace: Vec::new(),
+            left_start: false,
+            closest: None,
+            discard_lap: false,
+            laps: Vec::new(),
+        }
+    }
+
+    /// Override [`DEFAULT_MAP_POINTS`]; at least two are kept.
+    pub fn with_map_points(mut self, points: usize) -> Self {
+        self.map_points = points.max(2);
+        self
+    }
+
+    /// Override [`DEFAULT_CLOSE_DISTANCE_M`].
+    pub fn with_close_distance(mut self, metres: f32) -> Self {
+        self.close_distance_m = metres;
+        self
+    }
+
+    /// Override [`DEFAULT_MAX_LENGTH_DEVIATION`].
+    pub fn with_max_length_deviation(mut self, share: f32) -> Self {
+        self.max_length_deviation = share;
+        self
+    }
+
+    pub fn track_id(&self) -> &str {
+        &self.track_id
+    }
+
+    /// Trace the frame's position. Frames from another track or without a
+    /// position are ignored. Returns the length of the lap the frame
+    /// closed, if it closed one.
+    pub fn push(&mut self, telemetry: &NormalizedTelemetry) -> Option<f32> {
+        if telemetry
+            .track_id
+            .as_deref()
+            .is_some_and(|track| track != self.track_id)
+        {
+            return None;
+        }
+        let lap_number = telemetry.timing.and_then(|timing| timing.lap_number);
+        let position = telemetry
+            .world_position
+            .filter(|position| position.iter().all(|axis| axis.is_finite()))?;
+
+        let crossed_line = match (self.last_lap_number, lap_number) {
+            (Some(last), Some(lap)) => last != lap,
+            _ => false,
+        };
+        if lap_number.is_some() {
+            self.last_lap_number = lap_number;
+        }
+        let Some(start) = self.start else {
+            if crossed_line || lap_number.is_none() {
+                self.start = Some(position);
+                self.restart_trace(position);
+            }
+            return None;
+        };
+
+        let last = self.trace.last().copied().unwrap_or(start);
+        let step = distance(position, last);
+        if step > MAX_SAMPLE_JUMP_M || self.trace.len() >= MAX_TRACE_SAMPLES {
+            // Teleported or never closing: keep tracing to the start, but
+            // drop the lap when it closes.
+            self.discard_lap = true;
+            self.trace.clear();
+            self.closest = None;
+            self.left_start = distance(position, start) > self.close_distance_m;
+        } else if step < MIN_SAMPLE_SPACING_M {
+            return None;
+        }
+        self.trace.push(position);
+
+        let from_start = distance(position, start);
+        if from_start > self.close_distance_m {
+            if let Some((index, _)) = self.closest {
+                return self.close_lap(index, start);
+            }
+            self.left_start = true;
+            return None;
+        }
+        if !self.left_start {
+            return None;
+        }
+        match self.closest {
+            Some((index, nearest)) if from_start > nearest => self.close_lap(index, start),
+            _ => {
+                self.closest = Some((self.trace.len() - 1, from_start));
+                None
+            }
+        }
+    }
+
+    /// Laps closed so far, pit-lane laps included.
+    pub fn lap_count(&self) -> usize {
+        self.laps.len()
+    }
+
+    /// Length of every closed lap in metres, in the order they closed.
+    pub fn lap_lengths(&self) -> Vec<f32> {
+        self.laps.iter().map(|lap| lap.length_m).collect()
+    }
+
+    /// Indices of the closed laps within the length tolerance of the median.
+    pub fn accepted_laps(&self) -> Vec<usize> {
+        let Some(median) = self.median_length() else {
+            return Vec::new();
+        };
+        self.laps
+            .iter()
+            .enumerate()
+            .filter(|(_, lap)| (lap.length_m - median).abs() <= median * self.max_length_deviation)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// The map of the accepted lap nearest the median length; `None` until a
+    /// lap is accepted.
+    pub fn build(&self) -> Option<TrackMap> {
+        let median = self.median_length()?;
+        let lap = self
+            .accepted_laps()
+            .into_iter()
+            .map(|index| &self.laps[index])
+            .min_by(|a, b| {
+                (a.length_m - median)
+                    .abs()
+                    .total_cmp(&(b.length_m - median).abs())
+            })?;
+        Some(TrackMap {
+            track_id: self.track_id.clone(),
+            length_m: lap.length_m,
+            points: lap.points.clone(),
+        })
+    }
+
+    fn median_length(&self) -> Option<f32> {
+        let mut lengths = self.lap_lengths();
+        if lengths.is_empty() {
+            return None;
+        }
+        lengths.sort_by(f32::total_cmp);
+        let middle = lengths.len() / 2;
+        Some(if lengths.len().is_multiple_of(2) {
+            (lengths[middle - 1] + lengths[middle]) / 2.0
+        } else {
+            lengths[middle]
+        })
+    }
+
+    /// End the lap at the trace's nearest approach to `start` and begin the
+    /// next one from `start`, carrying over what was traced after it.
+    fn close_lap(&mut self, closest: usize, start: [f32; 3]) -> Option<f32> {
+        let carried = self.trace.split_off(closest + 1);
+        let trace = std::mem::replace(&mut self.trace, vec![start]);
+        self.trace.extend(carried);
+        self.left_start = self
+            .trace
+            .iter()
+            .any(|&position| distance(position, start) > self.close_distance_m);
+        self.closest = None;
+
+        let length_m = closed_length(&trace);
+        if std::mem::take(&mut self.discard_lap) || length_m <= 0.0 {
+            return None;
+        }
+        self.laps.push(TracedLap {
+            length_m,
+            points: resample(&trace, length_m, self.map_points),
+        });
+        Some(length_m)
+    }
+
+    fn restart_trace(&mut self, start: [f32; 3]) {
+        self.trace.clear();
+        self.trace.push(start);
+        self.left_start = false;
+        self.closest = None;
+        self.discard_lap = false;
+    }
+}
+
+/// Length of `trace` with its last point joined back to the first.
+fn closed_length(trace: &[[f32; 3]]) -> f32 {
+    (0..trace.len())
+        .map(|index| distance(trace[index], trace[(index + 1) % trace.len()]))
+        .sum()
+}
+
+/// `count` points spaced `length_m / count` apart along the closed `trace`,
+/// starting at its first point.
+fn resample(trace: &[[f32; 3]], length_m: f32, count: usize) -> Vec<[f32; 3]> {
+    let spacing = length_m / count as f32;
+    let mut points = Vec::with_capacity(count);
+    let mut segment = 0;
+    let mut segment_start = 0.0;
+    for n in 0..count {
+        let target = n as f32 * spacing;
+        loop {
+            let from = trace[segment % trace.len()];
+            let to = trace[(segment + 1) % trace.len()];
+            let segment_length = distance(from, to);
+            if target <= segment_start + segment_length || segment + 1 >= trace.len() {
+                let along = if segment_length > 0.0 {
+                    ((target - segment_start) / segment_length).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                points.push(add(from, scale(sub(to, from), along)));
+                break;
+            }
+            segment_start += segment_length;
+            segment += 1;
+        }
+    }
+    points
+}
+
+fn add(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn scale(a: [f32; 3], factor: f32) -> [f32; 3] {
+    [a[0] * factor, a[1] * factor, a[2] * factor]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn norm(a: [f32; 3]) -> f32 {
+    dot(a, a).sqrt()
+}
+
+fn distance(a: [f32; 3], b: [f32; 3]) -> f32 {
+    norm(sub(a, b))
+}
@@ -0,0 +1,232 @@
+//! Per-channel session statistics: accuracy on known distributions,
+//! presence and NaN accounting, and merging of partial reports.
+
+use openracing_telemetry_streams::{ChannelStats, StatsReport};
+use racing_wheel_telemetry_contracts::{NormalizedTelemetry, TelemetryFrame, TelemetryValue};
+
+type R = Result<(), Box<dyn std::error::Error>>;
+
+fn frame(data: NormalizedTelemetry, sequence: u64) -> TelemetryFrame {
+    TelemetryFrame::new(data, sequence * 1_000_000, sequence, 0)
+}
+
+fn speed_frame(speed: f32, sequence: u64) -> TelemetryFrame {
+    frame(
+        NormalizedTelemetry {
+            speed_ms: Some(speed),
+            ..NormalizedTelemetry::default()
+        },
+        sequence,
+    )
+}
+
+/// Deterministic uniform samples in `0.0..1.0`.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> f64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Standard normal sample (Box–Muller).
+    fn normal(&mut self) -> f64 {
+        let u1 = self.next().max(f64::MIN_POSITIVE);
+        let u2 = self.next();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+}
+
+fn close(actual: Option<f64>, expected: f64, tolerance: f64) -> bool {
+    actual.is_some_and(|actual| (actual - expected).abs() <= tolerance)
+}
+
+#[test]
+fn uniform_ramp_produces_exact_moments_and_close_quantiles() -> R {
+    let mut stats = ChannelStats::new(["speed_ms"]);
+    for i in 0..10_000u64 {
+        stats.push(&speed_frame(i as f32, i));
+    }
+    let report = stats.snapshot();
+    let speed = report.channel("speed_ms").ok_or("speed not tracked")?;
+
+    assert_eq!(
+        (speed.frames, speed.present, speed.samples),
+        (10_000, 10_000, 10_000)
+    );
+    assert_eq!((speed.min, speed.max), (Some(0.0), Some(9_999.0)));
+    assert!(close(speed.mean, 4_999.5, 1e-6));
+    let expected_stddev = ((10_000f64 * 10_000.0 - 1.0) / 12.0).sqrt();
+    assert!(close(speed.stddev, expected_stddev, 1e-6));
+    // Quantiles within 0.5% of the range.
+    assert!(close(speed.p50, 5_000.0, 50.0), "{:?}", speed.p50);
+    assert!(close(speed.p95, 9_500.0, 50.0), "{:?}", speed.p95);
+    assert!(close(speed.p99, 9_900.0, 50.0), "{:?}", speed.p99);
+    Ok(())
+}
+
+#[test]
+fn normal_distribution_quantiles_match_theory() -> R {
+    let (mean, sigma) = (40.0, 5.0);
+    let mut rng = Lcg(7);
+    let mut stats = ChannelStats::new(["speed_ms"]);
+    for i in 0..50_000 {
+        stats.push(&speed_frame((mean + sigma * rng.normal()) as f32, i));
+    }
+    let report = stats.snapshot();
+    let speed = report.channel("speed_ms").ok_or("speed not tracked")?;
+
+    assert!(close(speed.mean, mean, 0.1), "{:?}", speed.mean);
+    assert!(close(speed.stddev, sigma, 0.1), "{:?}", speed.stddev);
+    assert!(close(speed.p50, mean, 0.15), "{:?}", speed.p50);
+    assert!(
+        close(speed.p95, mean + 1.645 * sigma, 0.2),
+        "{:?}",
+        speed.p95
+    );
+    assert!(
+        close(speed.p99, mean + 2.326 * sigma, 0.3),
+        "{:?}",
+        speed.p99
+    );
+    Ok(())
+}
+
+#[test]
+fn absent_and_nan_samples_are_counted_but_not_aggregated() -> R {
+    let mut stats = ChannelStats::new(["rpm", "tyre_wear"]);
+    for i in 0..100u64 {
+        let mut data = NormalizedTelemetry {
+            rpm: match i % 4 {
+                0 => None,
+                1 => Some(f32::NAN),
+                _ => Some(3_000.0),
+            },
+            ..NormalizedTelemetry::default()
+        };
+        if i % 2 == 0 {
+            data.extended
+                .insert("tyre_wear".to_string(), TelemetryValue::Float(i as f32));
+        }
+        stats.push(&frame(data, i));
+    }
+    let report = stats.snapshot();
+
+    let rpm = report.channel("rpm").ok_or("rpm not tracked")?;
+    assert_eq!(
+        (rpm.frames, rpm.present, rpm.non_finite, rpm.samples),
+        (100, 75, 25, 50)
+    );
+    assert!((rpm.presence() - 0.75).abs() < 1e-12);
+    assert_eq!((rpm.mean, rpm.stddev), (Some(3_000.0), Some(0.0)));
+    assert_eq!((rpm.p50, rpm.p99), (Some(3_000.0), Some(3_000.0)));
+
+    let wear = report
+        .channel("tyre_wear")
+        .ok_or("extended key not tracked")?;
+    assert_eq!((wear.present, wear.samples), (50, 50));
+    assert_eq!((wear.min, wear.max), (Some(0.0), Some(98.0)));
+    assert!(close(wear.mean, 49.0, 1e-9));
+    Ok(())
+}
+
+#[test]
+fn untouched_channels_report_no_figures() -> R {
+    let mut stats = ChannelStats::default();
+    stats.push(&speed_frame(10.0, 0));
+    let report = stats.snapshot();
+    let brake = report.channel("brake").ok_or("brake not tracked")?;
+    assert_eq!((brake.frames, brake.present), (1, 0));
+    assert_eq!((brake.mean, brake.stddev, brake.p50), (None, None, None));
+    assert_eq!(brake.presence(), 0.0);
+    Ok(())
+}
+
+#[test]
+fn merged_halves_match_the_whole() -> R {
+    let mut rng = Lcg(42);
+    let samples: Vec<f32> = (0..20_000).map(|_| (rng.next() * 300.0) as f32).collect();
+
+    let mut whole = ChannelStats::new(["speed_ms"]);
+    let mut laps = ChannelStats::new(["speed_ms"]);
+    let mut session = StatsReport::default();
+    for (i, &speed) in samples.iter().enumerate() {
+        let frame = speed_frame(speed, i as u64);
+        whole.push(&frame);
+        laps.push(&frame);
+        // A lap boundary, as reported by the session segmenter.
+        if i == 7_499 {
+            session.merge(&laps.take());
+        }
+    }
+    session.merge(&laps.take());
+
+    let whole = whole.snapshot();
+    let expected = whole.channel("speed_ms").ok_or("speed not tracked")?;
+    let merged = session.channel("speed_ms").ok_or("speed not merged")?;
+
+    assert_eq!(
+        (merged.frames, merged.samples, merged.min, merged.max),
+        (
+            expected.frames,
+            expected.samples,
+            expected.min,
+            expected.max
+        )
+    );
+    assert!(close(merged.mean, expected.mean.ok_or("no mean")?, 1e-9));
+    assert!(close(
+        merged.stddev,
+        expected.stddev.ok_or("no stddev")?,
+        1e-9
+    ));
+    // Quantiles agree within the estimator's error: 1% of the range.
+    for (ours, theirs) in [
+        (merged.p50, expected.p50),
+        (merged.p95, expected.p95),
+        (merged.p99, expected.p99),
+    ] {
+        assert!(
+            close(ours, theirs.ok_or("no quantile")?, 3.0),
+            "{ours:?} vs {theirs:?}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn reports_round_trip_through_json_and_still_merge() -> R {
+    let mut stats = ChannelStats::new(["speed_ms"]);
+    for i in 0..1_000u64 {
+        stats.push(&speed_frame((i % 100) as f32, i));
+    }
+    let report = stats.take();
+    let json = serde_json::to_string(&report)?;
+    let mut restored: StatsReport = serde_json::from_str(&json)?;
+    let (before, after) = (
+        report.channel("speed_ms").ok_or("speed not tracked")?,
+        restored.channel("speed_ms").ok_or("speed not restored")?,
+    );
+    assert_eq!(
+        (after.samples, after.min, after.max),
+        (1_000, Some(0.0), Some(99.0))
+    );
+    // serde_json may round the last bit of a float.
+    assert!(close(after.p95, before.p95.ok_or("no p95")?, 1e-9));
+
+    restored.merge(&report);
+    let speed = restored.channel("speed_ms").ok_or("speed not tracked")?;
+    assert_eq!(speed.samples, 2_000);
+    assert!(close(speed.p50, 49.5, 1.0), "{:?}", speed.p50);
+    assert_eq!(
+        stats
+            .snapshot()
+            .channel("speed_ms")
+            .map(|speed| speed.frames),
+        Some(0)
+    );
+    Ok(())
+}
@@ -0,0 +1,124 @@
+//! Suppression of frames re-sent unchanged by polling adapters.
+
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+use openracing_telemetry_streams::{DedupConfig, DedupFilter, semantic_hash};
+use racing_wheel_telemetry_contracts::{NormalizedTelemetry, TelemetryFrame, TelemetryValue};
+use serde::Serialize;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const MS: u64 = 1_000_000;
+
+fn telemetry(gear: i8) -> NormalizedTelemetry {
+    NormalizedTelemetry::builder()
+        .rpm(6500.0)
+        .gear(gear)
+        .extended("fuel_l", TelemetryValue::Float(31.5))
+        .extended("drs_zone", TelemetryValue::Boolean(false))
+        .build()
+}
+
+fn filter() -> DedupFilter {
+    DedupFilter::new(DedupConfig {
+        threshold: Duration::from_millis(20),
+        max_suppression: Duration::from_millis(100),
+    })
+}
+
+#[test]
+fn identical_consecutive_frames_are_suppressed() {
+    let mut filter = filter();
+    let data = telemetry(3);
+    // Polled every 5 ms while the game still shows the same frame.
+    let admitted: Vec<_> = (0..6)
+        .map(|n| filter.admit(&data, 1_000 * MS + n * 5 * MS))
+        .collect();
+    assert_eq!(admitted, [true, false, false, false, false, false]);
+    assert_eq!(filter.suppressed(), 5);
+
+    // The same content after a gap past the threshold is a new reading.
+    assert!(filter.admit(&data, 1_060 * MS));
+
+    filter.reset();
+    assert_eq!(filter.suppressed(), 0);
+    assert!(filter.admit(&data, 1_061 * MS));
+}
+
+#[test]
+fn changed_gear_passes() {
+    let mut filter = filter();
+    assert!(filter.admit(&telemetry(3), 1_000 * MS));
+    assert!(!filter.admit(&telemetry(3), 1_005 * MS));
+    assert!(filter.admit(&telemetry(4), 1_010 * MS));
+    assert!(filter.admit(&telemetry(3), 1_015 * MS));
+    assert_eq!(filter.suppressed(), 1);
+}
+
+#[test]
+fn keep_alive_frame_is_emitted_after_max_suppression() {
+    let mut filter = filter();
+    let data = telemetry(2);
+    let admitted_at: Vec<_> = (0..=50u64)
+        .map(|n| n * 5 * MS)
+        .filter(|&at| filter.admit(&data, at))
+        .collect();
+    // One frame through every 100 ms of an unchanging 200 Hz stream.
+    assert_eq!(admitted_at, [0, 100 * MS, 200 * MS]);
+    assert_eq!(filter.suppressed(), 48);
+}
+
+#[test]
+fn hash_ignores_timestamp_sequence_and_map_order() -> TestResult {
+    let data = telemetry(5);
+    let first = TelemetryFrame::new(data.clone(), 1_000 * MS, 1, 128);
+    let second = TelemetryFrame::new(data, 2_000 * MS, 2, 128);
+    assert_ne!(first, second);
+    // Top-level `timestamp` and `sequence` fields are left out of the hash.
+    #[derive(Serialize)]
+    struct Envelope<'a> {
+        timestamp: u64,
+        sequence: u64,
+        data: &'a NormalizedTelemetry,
+    }
+    let wrap = |frame: &TelemetryFrame| {
+        semantic_hash(&Envelope {
+            timestamp: frame.timestamp_ns,
+            sequence: frame.sequence,
+            data: &frame.data,
+        })
+    };
+    assert_eq!(wrap(&first)?, wrap(&second)?);
+
+    // Many keys, so two insertion orders are unlikely to iterate alike.
+    let keys: Vec<_> = (0..32).map(|n| format!("channel_{n}")).collect();
+    let forward: HashMap<_, _> = keys.iter().map(|key| (key.clone(), key.len())).collect();
+    let backward: HashMap<_, _> = keys
+        .iter()
+        .rev()
+        .map(|key| (key.clone(), key.len()))
+        .collect();
+    assert_eq!(semantic_hash(&forward)?, semantic_hash(&backward)?);
+    assert_ne!(semantic_hash(&telemetry(5))?, semantic_hash(&telemetry(6))?);
+    Ok(())
+}
+
+#[test]
+fn volatile_keys_are_left_out_of_top_level_maps_only() -> TestResult {
+    let reading = |timestamp: u64| BTreeMap::from([("timestamp", timestamp), ("rpm", 6500)]);
+    assert_eq!(semantic_hash(&reading(1))?, semantic_hash(&reading(2))?);
+    assert_ne!(semantic_hash(&[reading(1)])?, semantic_hash(&[reading(2)])?);
+    Ok(())
+}
+
+#[test]
+fn hash_is_stable_across_runs() -> TestResult {
+    // FNV-1a of a fixed encoding; a change here breaks hashes persisted or
+    // compared between processes.
+    assert_eq!(
+        semantic_hash(&("gear", 3, true, None::<u8>))?,
+        0xbca9_6ab3_27f6_5087
+    );
+    Ok(())
+}
@@ -0,0 +1,226 @@
+//! Telemetry frames paired with the nearest wheelbase input.
+
+use std::time::Duration;
+
+use openracing_telemetry_streams::{InputSyncedFrame, SyncedInputTelemetry, spawn_input_sync};
+use racing_wheel_moza_wheelbase_report::{
+    TimedWheelbaseInput, WheelbaseCalibration, WheelbaseInputRaw, WheelbasePedalAxesRaw,
+};
+use racing_wheel_telemetry_contracts::{NormalizedTelemetry, TelemetryFrame};
+use tokio::sync::mpsc;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const MS: u64 = 1_000_000;
+const START_NS: u64 = 1_000 * MS;
+
+fn frame(sequence: u64, timestamp_ns: u64) -> TelemetryFrame {
+    TelemetryFrame::new(NormalizedTelemetry::default(), timestamp_ns, sequence, 64)
+}
+
+/// Frames every `interval_ms` from `START_NS`.
+fn evenly_spaced(count: u64, interval_ms: u64) -> Vec<TelemetryFrame> {
+    (0..count)
+        .map(|n| frame(n, START_NS + n * interval_ms * MS))
+        .collect()
+}
+
+/// An input whose steering encodes `marker`, with the throttle fully open.
+fn input(timestamp_ns: u64, marker: u16) -> TimedWheelbaseInput {
+    TimedWheelbaseInput::new(
+        timestamp_ns,
+        WheelbaseInputRaw {
+            steering: marker,
+            pedals: WheelbasePedalAxesRaw {
+                throttle: u16::MAX,
+                brake: 0,
+                clutch: None,
+                handbrake: None,
+            },
+            buttons: [0; 16],
+            hat: 0,
+            funky: 0,
+            rotary: [0; 2],
+        },
+    )
+}
+
+fn merger() -> SyncedInputTelemetry {
+    SyncedInputTelemetry::new(WheelbaseCalibration::default())
+}
+
+/// Feed `frames` and `inputs` interleaved by timestamp, frames first on a
+/// tie, collecting whatever becomes ready along the way.
+fn stream(
+    mut merger: SyncedInputTelemetry,
+    frames: &[TelemetryFrame],
+    inputs: &[TimedWheelbaseInput],
+) -> Vec<InputSyncedFrame> {
+    let mut out = Vec::new();
+    let (mut f, mut i) = (0, 0);
+    while f < frames.len() || i < inputs.len() {
+        let take_frame = match (frames.get(f), inputs.get(i)) {
+            (Some(frame), Some(input)) => frame.timestamp_ns <= input.timestamp_ns,
+            (frame, _) => frame.is_some(),
+        };
+        if take_frame {
+            merger.push_frame(frames[f].clone());
+            f += 1;
+        } else {
+            merger.push_input(inputs[i]);
+            i += 1;
+        }
+        out.extend(std::iter::from_fn(|| merger.pop()));
+    }
+    out.extend(merger.finish());
+    out
+}
+
+/// Inputs at an uneven 2-7 ms cadence with a 40 ms dropout.
+fn irregular_inputs() -> Vec<TimedWheelbaseInput> {
+    let mut inputs = Vec::new();
+    let mut at = START_NS - 20 * MS;
+    let mut marker = 0u16;
+    while at < START_NS + 600 * MS {
+        if !(START_NS + 200 * MS..START_NS + 240 * MS).contains(&at) {
+            inputs.push(input(at, marker));
+        }
+        at += (2 + u64::from(marker) % 6) * MS + 123_456;
+        marker += 1;
+    }
+    inputs
+}
+
+#[test]
+fn known_offset_is_reported_as_skew() -> TestResult {
+    // Inputs 3 ms after every frame, and 7 ms before the next.
+    let frames = evenly_spaced(50, 10);
+    let inputs: Vec<_> = frames
+        .iter()
+        .map(|frame| input(frame.timestamp_ns + 3 * MS, 49152))
+        .collect();
+
+    let merged = merger().merge(frames, &inputs);
+    assert_eq!(merged.len(), 50);
+    for record in &merged {
+        assert_eq!(record.sync_skew_ns, Some(3 * MS as i64));
+        let input = record.input.ok_or("frame without input")?;
+        assert!((input.steering - 0.5).abs() < 0.01, "{}", input.steering);
+        assert_eq!(input.throttle, 1.0);
+    }
+
+    // The same inputs 3 ms before the frames skew the other way.
+    let frames = evenly_spaced(50, 10);
+    let early: Vec<_> = frames
+        .iter()
+        .map(|frame| input(frame.timestamp_ns - 3 * MS, 49152))
+        .collect();
+    let merged = merger().merge(frames, &early);
+    assert!(
+        merged
+            .iter()
+            .all(|record| record.sync_skew_ns == Some(-3 * MS as i64))
+    );
+    Ok(())
+}
+
+#[test]
+fn inputs_beyond_tolerance_are_left_out() {
+    let frames = evenly_spaced(3, 100);
+    // 4 ms, 15 ms and 30 ms away from their frames.
+    let inputs = [
+        input(START_NS + 4 * MS, 1),
+        input(START_NS + 115 * MS, 2),
+        input(START_NS + 230 * MS, 3),
+    ];
+
+    let merged = merger().merge(frames.clone(), &inputs);
+    let skews: Vec<_> = merged.iter().map(|record| record.sync_skew_ns).collect();
+    assert_eq!(skews, [Some(4 * MS as i64), None, None]);
+    assert!(merged[1].input.is_none());
+
+    let wider = merger().with_tolerance(Duration::from_millis(20));
+    let skews: Vec<_> = wider
+        .merge(frames.clone(), &inputs)
+        .iter()
+        .map(|record| record.sync_skew_ns)
+        .collect();
+    assert_eq!(skews, [Some(4 * MS as i64), Some(15 * MS as i64), None]);
+
+    // Without any inputs every frame still comes through.
+    let merged = merger().merge(frames, &[]);
+    assert_eq!(merged.len(), 3);
+    assert!(merged.iter().all(|record| record.input.is_none()));
+}
+
+#[test]
+fn streaming_matches_batch() {
+    let frames = evenly_spaced(60, 10);
+    let inputs = irregular_inputs();
+
+    let batch = merger().merge(frames.clone(), &inputs);
+    let streamed = stream(merger(), &frames, &inputs);
+    assert_eq!(streamed, batch);
+    // The dropout leaves some frames without an input.
+    assert!(batch.iter().any(|record| record.input.is_none()));
+    assert!(batch.iter().filter(|record| record.input.is_some()).count() > 50);
+}
+
+#[test]
+fn frames_wait_for_a_later_input() {
+    let mut merger = merger();
+    merger.push_input(input(START_NS - 2 * MS, 1));
+    merger.push_frame(frame(0, START_NS));
+    // An input 1 ms after the frame may still arrive.
+    assert!(merger.pop().is_none());
+    merger.push_input(input(START_NS + MS, 2));
+    let record = merger.pop();
+    assert_eq!(
+        record.and_then(|record| record.sync_skew_ns),
+        Some(MS as i64)
+    );
+    assert_eq!(merger.pending_frames(), 0);
+
+    // Frames stop waiting once newer frames are past the input lag.
+    merger.push_frame(frame(1, START_NS + 10 * MS));
+    merger.push_frame(frame(2, START_NS + 200 * MS));
+    let record = merger.pop();
+    assert_eq!(record.map(|record| record.frame.sequence), Some(1));
+    assert!(merger.pop().is_none());
+}
+
+#[tokio::test]
+async fn spawned_merger_matches_batch() -> TestResult {
+    let frames = evenly_spaced(60, 10);
+    let inputs = irregular_inputs();
+    let batch = merger().merge(frames.clone(), &inputs);
+
+    let (frame_tx, frame_rx) = mpsc::channel(8);
+    let (input_tx, input_rx) = mpsc::channel(8);
+    let (tx, mut rx) = mpsc::channel(8);
+    let task = spawn_input_sync(merger(), frame_rx, input_rx, tx);
+    let feed = tokio::spawn(async move {
+        let mut inputs = inputs.into_iter().peekable();
+        for frame in frames {
+            while let Some(input) = inputs.next_if(|input| input.timestamp_ns < frame.timestamp_ns)
+            {
+                input_tx.send(input).await?;
+            }
+            frame_tx.send(frame).await?;
+            tokio::time::sleep(Duration::from_micros(200)).await;
+        }
+        for input in inputs {
+            input_tx.send(input).await?;
+        }
+        Ok::<_, Box<dyn std::error::Error + Send + Sync>>(())
+    });
+
+    let mut streamed = Vec::new();
+    while let Some(record) = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await? {
+        streamed.push(record);
+    }
+    feed.await?.map_err(|err| err.to_string())?;
+    task.await?;
+    assert_eq!(streamed, batch);
+    Ok(())
+}
@@ -0,0 +1,218 @@
+//! SPSC ring buffer: ordering, overflow accounting, concurrent handoff and
+//! the async bridge.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use openracing_telemetry_streams::{BufferStats, StreamError, spawn_spsc_bridge, spsc_ring};
+use tokio::sync::mpsc;
+
+type R = Result<(), Box<dyn std::error::Error>>;
+
+#[test]
+fn items_come_out_in_push_order() -> R {
+    let (mut producer, mut consumer) = spsc_ring(4);
+    for round in 0..10u32 {
+        for i in 0..3 {
+            producer.try_push(round * 3 + i)?;
+        }
+        for i in 0..3 {
+            assert_eq!(consumer.try_pop(), Some(round * 3 + i));
+        }
+    }
+    assert_eq!(consumer.try_pop(), None);
+    Ok(())
+}
+
+#[test]
+fn full_ring_rejects_and_counts_overflow() -> R {
+    let (mut producer, mut consumer) = spsc_ring(2);
+    producer.try_push(1)?;
+    producer.try_push(2)?;
+    assert!(matches!(
+        producer.try_push(3),
+        Err(StreamError::BufferOverflow)
+    ));
+    assert!(matches!(
+        producer.try_push(4),
+        Err(StreamError::BufferOverflow)
+    ));
+
+    // Rejected items never displace queued ones.
+    assert_eq!(consumer.try_pop(), Some(1));
+    producer.try_push(5)?;
+    assert_eq!(consumer.try_pop(), Some(2));
+    assert_eq!(consumer.try_pop(), Some(5));
+
+    assert_eq!(
+        consumer.stats(),
+        BufferStats {
+            capacity: 2,
+            pushed: 3,
+            popped: 3,
+            overflowed: 2,
+        }
+    );
+    assert_eq!(producer.stats(), consumer.stats());
+    Ok(())
+}
+
+#[test]
+fn pop_up_to_moves_a_bounded_batch() -> R {
+    let (mut producer, mut consumer) = spsc_ring(8);
+    for i in 0..5 {
+        producer.try_push(i)?;
+    }
+    let mut out = Vec::with_capacity(8);
+    assert_eq!(consumer.pop_up_to(3, &mut out), 3);
+    assert_eq!(out, [0, 1, 2]);
+    assert_eq!(consumer.pop_up_to(10, &mut out), 2);
+    assert_eq!(out, [0, 1, 2, 3, 4]);
+    assert_eq!(consumer.pop_up_to(10, &mut out), 0);
+    assert_eq!(consumer.stats().in_flight(), 0);
+    Ok(())
+}
+
+#[test]
+fn zero_capacity_holds_one_item() -> R {
+    let (mut producer, mut consumer) = spsc_ring(0);
+    assert_eq!(producer.capacity(), 1);
+    producer.try_push("a")?;
+    assert!(producer.try_push("b").is_err());
+    assert_eq!(consumer.try_pop(), Some("a"));
+    Ok(())
+}
+
+#[test]
+fn queued_items_are_dropped_with_the_ring() -> R {
+    struct Counted(Arc<AtomicUsize>);
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let drops = Arc::new(AtomicUsize::new(0));
+    let (mut producer, mut consumer) = spsc_ring(4);
+    for _ in 0..3 {
+        producer.try_push(Counted(Arc::clone(&drops)))?;
+    }
+    drop(consumer.try_pop());
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+
+    drop(producer);
+    assert!(consumer.is_producer_closed());
+    drop(consumer);
+    assert_eq!(drops.load(Ordering::SeqCst), 3);
+    Ok(())
+}
+
+#[test]
+fn concurrent_push_pop_preserves_order_and_accounts_every_item() -> R {
+    const ITEMS: u64 = 200_000;
+    let (mut producer, mut consumer) = spsc_ring::<u64>(64);
+
+    let writer = thread::spawn(move || {
+        let mut rejected = 0u64;
+        for i in 0..ITEMS {
+            if producer.try_push(i).is_err() {
+                rejected += 1;
+            }
+        }
+        rejected
+    });
+
+    let mut received = Vec::new();
+    let mut batch = Vec::with_capacity(16);
+    loop {
+        let closed = consumer.is_producer_closed();
+        batch.clear();
+        if consumer.pop_up_to(16, &mut batch) == 0 {
+            if closed {
+                break;
+            }
+            thread::yield_now();
+            continue;
+        }
+        received.extend_from_slice(&batch);
+    }
+    let rejected = writer.join().map_err(|_| "producer thread panicked")?;
+
+    // Whatever got through arrives strictly in push order.
+    assert!(received.windows(2).all(|pair| pair[0] < pair[1]));
+    let stats = consumer.stats();
+    assert_eq!(stats.overflowed, rejected);
+    assert_eq!(stats.pushed + stats.overflowed, ITEMS);
+    assert_eq!(stats.popped, received.len() as u64);
+    assert_eq!(stats.in_flight(), 0);
+    Ok(())
+}
+
+#[test]
+fn concurrent_handoff_without_overflow_delivers_everything() -> R {
+    const ITEMS: u64 = 100_000;
+    let (mut producer, mut consumer) = spsc_ring::<u64>(16);
+
+    let writer = thread::spawn(move || {
+        for i in 0..ITEMS {
+            while producer.try_push(i).is_err() {
+                thread::yield_now();
+            }
+        }
+    });
+
+    let mut expected = 0;
+    while expected < ITEMS {
+        match consumer.try_pop() {
+            Some(item) => {
+                assert_eq!(item, expected);
+                expected += 1;
+            }
+            None => thread::yield_now(),
+        }
+    }
+    writer.join().map_err(|_| "producer thread panicked")?;
+    assert_eq!(consumer.stats().pushed, ITEMS);
+    Ok(())
+}
+
+#[tokio::test]
+async fn bridge_forwards_everything_then_stops() -> R {
+    let (mut producer, consumer) = spsc_ring(32);
+    let (tx, mut rx) = mpsc::channel(8);
+    let bridge = spawn_spsc_bridge(consumer, tx, 4, Duration::from_millis(1));
+
+    let writer = thread::spawn(move || {
+        for i in 0..500u32 {
+            while producer.try_push(i).is_err() {
+                thread::sleep(Duration::from_micros(50));
+            }
+        }
+    });
+
+    let mut received = Vec::new();
+    while let Some(item) = rx.recv().await {
+        received.push(item);
+    }
+    writer.join().map_err(|_| "producer thread panicked")?;
+
+    assert_eq!(received, (0..500).collect::<Vec<_>>());
+    let stats = bridge.await?;
+    assert_eq!((stats.pushed, stats.popped), (500, 500));
+    Ok(())
+}
+
+#[tokio::test]
+async fn bridge_stops_when_the_receiver_is_dropped() -> R {
+    let (mut producer, consumer) = spsc_ring(8);
+    let (tx, rx) = mpsc::channel(1);
+    drop(rx);
+    let bridge = spawn_spsc_bridge(consumer, tx, 4, Duration::from_millis(1));
+    producer.try_push(1u8)?;
+
+    let stats = tokio::time::timeout(Duration::from_secs(5), bridge).await??;
+    assert_eq!(stats.popped, 1);
+    Ok(())
+}
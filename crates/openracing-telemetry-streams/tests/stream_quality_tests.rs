@@ -0,0 +1,192 @@
+//! Gap, duplicate and reorder accounting from frame sequence numbers.
+
+use std::time::Duration;
+
+use openracing_telemetry_streams::{
+    MAX_SEQUENCE_JUMP, SequenceEvent, StreamQuality, StreamQualityMonitor,
+};
+
+const MS: u64 = 1_000_000;
+
+/// Feed `sequences`, one arrival every `interval_ns`, and return the events.
+fn feed(
+    monitor: &mut StreamQualityMonitor,
+    sequences: &[u64],
+    start_ns: u64,
+    interval_ns: u64,
+) -> Vec<SequenceEvent> {
+    sequences
+        .iter()
+        .enumerate()
+        .map(|(n, &sequence)| monitor.observe(sequence, start_ns + n as u64 * interval_ns))
+        .collect()
+}
+
+#[test]
+fn clean_stream_reports_its_rate_and_no_jitter() {
+    let mut monitor = StreamQualityMonitor::new().with_expected_interval(Duration::from_millis(10));
+    let sequences: Vec<u64> = (0..101).collect();
+    let events = feed(&mut monitor, &sequences, 0, 10 * MS);
+    assert!(events.iter().all(|event| *event == SequenceEvent::InOrder));
+
+    let quality = monitor.quality();
+    assert_eq!(quality.frames, 101);
+    assert_eq!(
+        (
+            quality.gaps,
+            quality.missing_frames,
+            quality.duplicates,
+            quality.out_of_order
+        ),
+        (0, 0, 0, 0)
+    );
+    assert_eq!(quality.observed_rate_hz, Some(100.0));
+    assert_eq!(quality.expected_rate_hz, Some(100.0));
+    assert_eq!(quality.jitter_ms, Some(0.0));
+    assert_eq!(quality.loss_ratio(), 0.0);
+}
+
+#[test]
+fn injected_gaps_dupes_and_reorders_give_exact_counts() {
+    let mut monitor = StreamQualityMonitor::new();
+    // 3-4 lost, 7 repeated, 10 and 11 swapped, 13-15 skipped with 14 late.
+    let sequences = [0, 1, 2, 5, 6, 7, 7, 8, 9, 11, 10, 12, 16, 14, 17];
+    let events = feed(&mut monitor, &sequences, 0, 5 * MS);
+    assert_eq!(events[3], SequenceEvent::Gap { missing: 2 });
+    assert_eq!(events[6], SequenceEvent::Duplicate);
+    assert_eq!(events[9], SequenceEvent::Gap { missing: 1 });
+    assert_eq!(events[10], SequenceEvent::OutOfOrder);
+    assert_eq!(events[12], SequenceEvent::Gap { missing: 3 });
+    assert_eq!(events[13], SequenceEvent::OutOfOrder);
+
+    let quality = monitor.quality();
+    assert_eq!(
+        quality,
+        StreamQuality {
+            frames: 15,
+            gaps: 3,
+            // 3, 4, 13 and 15 never came.
+            missing_frames: 4,
+            duplicates: 1,
+            out_of_order: 2,
+            restarts: 0,
+            ..quality.clone()
+        }
+    );
+    // 14 unique frames arrived out of 18 promised.
+    assert!((quality.loss_ratio() - 4.0 / 18.0).abs() < 1e-12);
+    let summary = quality.summary();
+    assert_eq!(
+        (summary.gaps, summary.duplicates, summary.out_of_order),
+        (3, 1, 2)
+    );
+}
+
+#[test]
+fn a_repeat_of_a_late_frame_is_a_duplicate() {
+    let mut monitor = StreamQualityMonitor::new();
+    let events = feed(&mut monitor, &[0, 2, 1, 1], 0, MS);
+    assert_eq!(
+        events,
+        [
+            SequenceEvent::InOrder,
+            SequenceEvent::Gap { missing: 1 },
+            SequenceEvent::OutOfOrder,
+            SequenceEvent::Duplicate
+        ]
+    );
+    assert_eq!(monitor.quality().missing_frames, 0);
+}
+
+#[test]
+fn jitter_is_the_standard_deviation_of_arrival_intervals() -> Result<(), String> {
+    let mut monitor = StreamQualityMonitor::new();
+    // Intervals alternate 8 ms and 12 ms.
+    let mut now = 0;
+    for sequence in 0..201u64 {
+        monitor.observe(sequence, now);
+        now += if sequence % 2 == 0 { 8 * MS } else { 12 * MS };
+    }
+    let jitter = monitor.quality().jitter_ms.ok_or("no jitter")?;
+    // Sample standard deviation of 100 eights and 100 twelves.
+    let expected = (200.0 * 4.0 / 199.0f64).sqrt();
+    assert!((jitter - expected).abs() < 1e-9, "{jitter} vs {expected}");
+    Ok(())
+}
+
+#[test]
+fn wrapping_past_u64_max_is_a_restart_not_a_huge_gap() {
+    let mut monitor = StreamQualityMonitor::new();
+    let sequences = [u64::MAX - 2, u64::MAX - 1, u64::MAX, 0, 1, 2];
+    let events = feed(&mut monitor, &sequences, 0, MS);
+    assert_eq!(events[3], SequenceEvent::Restart);
+    let quality = monitor.quality();
+    assert_eq!(
+        (quality.restarts, quality.gaps, quality.missing_frames),
+        (1, 0, 0)
+    );
+
+    // An implausible jump forward starts over too.
+    let mut monitor = StreamQualityMonitor::new();
+    let events = feed(
+        &mut monitor,
+        &[0, 1, MAX_SEQUENCE_JUMP + 2, MAX_SEQUENCE_JUMP + 3],
+        0,
+        MS,
+    );
+    assert_eq!(events[2], SequenceEvent::Restart);
+    assert_eq!(events[3], SequenceEvent::InOrder);
+    assert_eq!(monitor.quality().missing_frames, 0);
+}
+
+#[test]
+fn a_source_restarting_from_zero_is_detected_by_timestamp() {
+    // A long-running stream resets: far behind the highest sequence.
+    let mut monitor = StreamQualityMonitor::new();
+    let sequences: Vec<u64> = (0..500).chain(0..10).collect();
+    let events = feed(&mut monitor, &sequences, 0, 10 * MS);
+    assert_eq!(events[500], SequenceEvent::Restart);
+    assert!(
+        events[501..]
+            .iter()
+            .all(|event| *event == SequenceEvent::InOrder)
+    );
+
+    // A short stream resets while arrivals continue: 0 repeats seconds
+    // after the original, so it cannot be a duplicate.
+    let mut monitor = StreamQualityMonitor::new();
+    let sequences: Vec<u64> = (0..20).chain(0..5).collect();
+    let events = feed(&mut monitor, &sequences, 0, 100 * MS);
+    assert_eq!(events[20], SequenceEvent::Restart);
+    let quality = monitor.quality();
+    assert_eq!(
+        (
+            quality.restarts,
+            quality.duplicates,
+            quality.out_of_order,
+            quality.gaps
+        ),
+        (1, 0, 0, 0)
+    );
+    // Arrivals never paused, so the rate ignores the restart.
+    assert_eq!(quality.observed_rate_hz, Some(10.0));
+
+    // The same repeat within the horizon is a duplicate.
+    let mut monitor = StreamQualityMonitor::new().with_reorder_horizon(Duration::from_secs(5));
+    let events = feed(&mut monitor, &sequences, 0, 100 * MS);
+    assert_eq!(events[20], SequenceEvent::Duplicate);
+}
+
+#[test]
+fn reset_forgets_counters_but_keeps_configuration() {
+    let mut monitor = StreamQualityMonitor::new().with_expected_interval(Duration::from_millis(5));
+    feed(&mut monitor, &[0, 3, 3], 0, MS);
+    monitor.reset();
+    assert_eq!(
+        monitor.quality(),
+        StreamQuality {
+            expected_rate_hz: Some(200.0),
+            ..StreamQuality::default()
+        }
+    );
+}
@@ -0,0 +1,161 @@
+//! Track maps traced around a synthetic circular track.
+
+use std::f32::consts::TAU;
+
+use openracing_telemetry_streams::{TrackMap, TrackMapBuilder};
+use racing_wheel_telemetry_contracts::{NormalizedTelemetry, SessionTiming};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const RADIUS_M: f32 = 200.0;
+const PIT_BULGE_M: f32 = 50.0;
+const TRACK: &str = "circle";
+
+/// Position on the circle `degrees` after the start, anticlockwise.
+fn on_circle(degrees: f32, radius: f32) -> [f32; 3] {
+    let angle = degrees.to_radians();
+    [radius * angle.cos(), 2.0, radius * angle.sin()]
+}
+
+/// One lap at one sample per degree, ending back on the start point. A pit
+/// lap swings wide between 300 and 340 degrees.
+fn lap(pit: bool) -> Vec<[f32; 3]> {
+    (1..=360)
+        .map(|degree| {
+            let degrees = degree as f32;
+            let bulge = if pit && (300.0..=340.0).contains(&degrees) {
+                PIT_BULGE_M * ((degrees - 300.0) / 40.0 * TAU / 2.0).sin()
+            } else {
+                0.0
+            };
+            on_circle(degrees, RADIUS_M + bulge)
+        })
+        .collect()
+}
+
+fn at(position: [f32; 3]) -> NormalizedTelemetry {
+    NormalizedTelemetry::builder()
+        .track_id(TRACK)
+        .world_position(position)
+        .build()
+}
+
+/// Drive the start point then `laps`, returning the length of each lap the
+/// builder closed.
+fn drive(builder: &mut TrackMapBuilder, laps: &[bool]) -> Vec<f32> {
+    let mut closed = Vec::new();
+    builder.push(&at(on_circle(0.0, RADIUS_M)));
+    for &pit in laps {
+        for position in lap(pit) {
+            closed.extend(builder.push(&at(position)));
+        }
+    }
+    // Just past the line, so the last lap sees its nearest approach.
+    closed.extend(builder.push(&at(on_circle(1.0, RADIUS_M))));
+    closed
+}
+
+#[test]
+fn map_closes_after_one_lap() -> TestResult {
+    let mut builder = TrackMapBuilder::new(TRACK).with_map_points(256);
+    let closed = drive(&mut builder, &[false]);
+    assert_eq!(closed.len(), 1);
+
+    let map = builder.build().ok_or("no map after a clean lap")?;
+    let circumference = TAU * RADIUS_M;
+    assert!(
+        (map.length_m - circumference).abs() < circumference * 0.001,
+        "{}",
+        map.length_m
+    );
+    assert_eq!(map.points.len(), 256);
+    assert_eq!(map.points[0], on_circle(0.0, RADIUS_M));
+    // The last point is one spacing short of the first.
+    let [x0, _, z0] = map.points[0];
+    let [x, _, z] = map.points[255];
+    let gap = ((x - x0).powi(2) + (z - z0).powi(2)).sqrt();
+    assert!((gap - map.length_m / 256.0).abs() < 0.1, "{gap}");
+    Ok(())
+}
+
+#[test]
+fn fraction_increases_monotonically_around_the_lap() -> TestResult {
+    let mut builder = TrackMapBuilder::new(TRACK);
+    drive(&mut builder, &[false]);
+    let map = builder.build().ok_or("no map")?;
+
+    let fractions = (0..720)
+        .map(|half_degree| map.lap_distance_fraction(on_circle(half_degree as f32 / 2.0, 205.0)))
+        .collect::<Option<Vec<f32>>>()
+        .ok_or("position off the map")?;
+    assert!(fractions[0] < 1e-3, "{}", fractions[0]);
+    assert!(
+        fractions.windows(2).all(|pair| pair[1] > pair[0]),
+        "{fractions:?}"
+    );
+    assert!((fractions[360] - 0.5).abs() < 1e-3, "{}", fractions[360]);
+    assert!(fractions[719] > 0.99);
+    assert_eq!(map.lap_distance_fraction([f32::NAN, 0.0, 0.0]), None);
+    Ok(())
+}
+
+#[test]
+fn pit_lane_lap_is_rejected_by_its_length() -> TestResult {
+    let mut builder = TrackMapBuilder::new(TRACK);
+    let closed = drive(&mut builder, &[false, true, false]);
+    assert_eq!(closed.len(), 3);
+    assert_eq!(builder.lap_count(), 3);
+    assert!(closed[1] > closed[0] * 1.02, "{closed:?}");
+    assert_eq!(builder.accepted_laps(), [0, 2]);
+
+    let map = builder.build().ok_or("no map")?;
+    assert!((map.length_m - closed[0]).abs() < 0.01);
+    Ok(())
+}
+
+#[test]
+fn trace_starts_at_the_line_when_laps_are_numbered() -> TestResult {
+    let mut builder = TrackMapBuilder::new(TRACK);
+    let numbered = |degrees: f32, lap_number: i32| {
+        let mut telemetry = at(on_circle(degrees, RADIUS_M));
+        telemetry.timing = Some(SessionTiming {
+            lap_number: Some(lap_number),
+            ..SessionTiming::default()
+        });
+        telemetry
+    };
+    // Out lap from the pit exit: nothing traced until the line.
+    for degree in 90..360 {
+        builder.push(&numbered(degree as f32, 0));
+    }
+    for degree in 0..=361 {
+        builder.push(&numbered(degree as f32, 1));
+    }
+
+    assert_eq!(builder.lap_count(), 1);
+    let map = builder.build().ok_or("no map")?;
+    assert_eq!(map.points[0], on_circle(0.0, RADIUS_M));
+    Ok(())
+}
+
+#[test]
+fn saved_map_fills_fraction_for_position_only_frames() -> TestResult {
+    let mut builder = TrackMapBuilder::new(TRACK);
+    drive(&mut builder, &[false]);
+    let saved = serde_json::to_string(&builder.build().ok_or("no map")?)?;
+    let map: TrackMap = serde_json::from_str(&saved)?;
+
+    let mut quarter = at(on_circle(90.0, RADIUS_M));
+    map.fill_lap_distance(&mut quarter);
+    let fraction = quarter.lap_distance_fraction.ok_or("fraction not filled")?;
+    assert!((fraction - 0.25).abs() < 1e-3, "{fraction}");
+
+    // The game's own fraction wins, and other tracks are left alone.
+    let mut reported = at(on_circle(90.0, RADIUS_M)).with_lap_distance_fraction(0.3);
+    map.fill_lap_distance(&mut reported);
+    assert_eq!(reported.lap_distance_fraction, Some(0.3));
+    let mut elsewhere = at(on_circle(90.0, RADIUS_M)).with_track_id("oval".to_string());
+    map.fill_lap_distance(&mut elsewhere);
+    assert_eq!(elsewhere.lap_distance_fraction, None);
+    Ok(())
+}
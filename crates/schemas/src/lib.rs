@@ -56,8 +56,10 @@ pub mod prelude {
 
     // Telemetry types
     pub use crate::telemetry::{
-        NormalizedTelemetry, NormalizedTelemetryBuilder, TelemetryData, TelemetryFlags,
-        TelemetryFrame, TelemetrySnapshot, TelemetryValue,
+        NormalizedTelemetry, NormalizedTelemetryBuilder, OpponentSnapshot, PenaltyEvent,
+        PenaltyKind, PenaltyState, PenaltyTracker, SessionTiming, TelemetryData, TelemetryFlags,
+        TelemetryFrame, TelemetrySnapshot, TelemetryValue, TelemetryValueDepthError,
+        TimingCoverage, TireCorner, TireData,
     };
 
     // Configuration types
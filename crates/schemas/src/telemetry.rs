@@ -6,6 +6,23 @@
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::time::{Duration, Instant};
+use thiserror::Error;
+
+#[cfg(feature = "binary")]
+pub use racing_wheel_telemetry_contracts::codec::{
+    self, BINARY_MAGIC, BinaryCodecError, FrameReader, FrameWriter, has_binary_magic,
+};
+pub use racing_wheel_telemetry_contracts::migration::{
+    FrameMigrationError, LEGACY_FRAME_VERSION, TELEMETRY_SCHEMA_VERSION,
+};
+pub use racing_wheel_telemetry_contracts::opponents::{
+    DEFAULT_MAX_OPPONENTS, OpponentSnapshot, bound_opponents,
+};
+pub use racing_wheel_telemetry_contracts::units::{
+    FormattedTelemetry, FormattedValue, KMH_PER_MS, KPA_PER_PSI, MPH_PER_MS, PressureUnit,
+    SpeedUnit, TelemetryReadings, TemperatureUnit, UnitsProfile,
+};
+pub use racing_wheel_telemetry_contracts::{SessionTiming, TimingCoverage};
 
 /// Canonical normalized telemetry data from racing games.
 ///
@@ -18,9 +35,13 @@ use std::time::{Duration, Instant};
 /// - **Engine**: rpm, gear, max_rpm
 /// - **G-forces**: lateral_g, longitudinal_g, vertical_g
 /// - **Tire slip**: slip_ratio, slip_angle per wheel
+/// - **Tires**: per-corner temperature, pressure, wear and slip
 /// - **FFB**: ffb_scalar, ffb_torque_nm
 /// - **Flags**: racing flags and assists status
+/// - **Penalties**: pit-lane / track-limits incident state
 /// - **Context**: car_id, track_id, session_id
+/// - **Lap timing**: lap and session times grouped in `timing`
+/// - **Game clock**: game_time_s, game_tick as reported by the game itself
 /// - **Extended**: game-specific key-value data
 ///
 /// # Example
@@ -141,6 +162,28 @@ pub struct NormalizedTelemetry {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub session_id: Option<String>,
 
+    /// Penalty and track-limits state for the player car (if the game reports it).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub penalties: Option<PenaltyState>,
+
+    /// Per-corner tire measurements (if the game reports any).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tires: Option<TireData>,
+
+    /// Lap and session timing (if the game reports any).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timing: Option<SessionTiming>,
+
+    /// Car position in the game's world coordinates in meters, ordered X, Y, Z
+    /// (if the game reports it). Axis conventions are the game's own.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub world_position: Option<[f32; 3]>,
+
+    /// How far around the lap the car is, 0.0 at the line to 1.0 back at it
+    /// (if the game reports it or a track map derives it).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lap_distance_fraction: Option<f32>,
+
     /// Position in race (1-based).
     #[serde(default)]
     pub position: u8,
@@ -184,6 +227,18 @@ pub struct NormalizedTelemetry {
     pub extended: BTreeMap<String, TelemetryValue>,
 
     // === Timing ===
+    /// Game-side clock in seconds, when the game reports one.
+    ///
+    /// Whether this counts session elapsed time or time of day is declared per
+    /// adapter in the support matrix. Consumers should prefer it over
+    /// `timestamp` for deltas, since it is immune to UDP arrival jitter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub game_time_s: Option<f64>,
+
+    /// Game-side frame or packet counter, when the game reports one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub game_tick: Option<u64>,
+
     /// Timestamp when this telemetry sample was captured (monotonic).
     /// Skipped during serialization as Instant is not serializable.
     #[serde(skip, default = "default_timestamp")]
@@ -198,6 +253,121 @@ fn default_timestamp() -> Instant {
     Instant::now()
 }
 
+// The serde impls skip empty optional fields, which only a self-describing
+// format can decode, so the binary codec writes every field in declaration
+// order instead. `timestamp` is process-local and restarts at decode time,
+// as it does for JSON.
+#[cfg(feature = "binary")]
+impl bincode::Encode for NormalizedTelemetry {
+    fn encode<E: bincode::enc::Encoder>(
+        &self,
+        encoder: &mut E,
+    ) -> Result<(), bincode::error::EncodeError> {
+        self.speed_ms.encode(encoder)?;
+        self.steering_angle.encode(encoder)?;
+        self.throttle.encode(encoder)?;
+        self.brake.encode(encoder)?;
+        self.clutch.encode(encoder)?;
+        self.rpm.encode(encoder)?;
+        self.max_rpm.encode(encoder)?;
+        self.gear.encode(encoder)?;
+        self.num_gears.encode(encoder)?;
+        self.lateral_g.encode(encoder)?;
+        self.longitudinal_g.encode(encoder)?;
+        self.vertical_g.encode(encoder)?;
+        self.slip_ratio.encode(encoder)?;
+        self.slip_angle_fl.encode(encoder)?;
+        self.slip_angle_fr.encode(encoder)?;
+        self.slip_angle_rl.encode(encoder)?;
+        self.slip_angle_rr.encode(encoder)?;
+        self.tire_temps_c.encode(encoder)?;
+        self.tire_pressures_psi.encode(encoder)?;
+        self.ffb_scalar.encode(encoder)?;
+        self.ffb_torque_nm.encode(encoder)?;
+        self.flags.encode(encoder)?;
+        self.car_id.encode(encoder)?;
+        self.track_id.encode(encoder)?;
+        self.session_id.encode(encoder)?;
+        self.penalties.encode(encoder)?;
+        self.tires.encode(encoder)?;
+        self.timing.encode(encoder)?;
+        self.world_position.encode(encoder)?;
+        self.lap_distance_fraction.encode(encoder)?;
+        self.position.encode(encoder)?;
+        self.lap.encode(encoder)?;
+        self.current_lap_time_s.encode(encoder)?;
+        self.best_lap_time_s.encode(encoder)?;
+        self.last_lap_time_s.encode(encoder)?;
+        self.delta_ahead_s.encode(encoder)?;
+        self.delta_behind_s.encode(encoder)?;
+        self.fuel_percent.encode(encoder)?;
+        self.engine_temp_c.encode(encoder)?;
+        self.extended.encode(encoder)?;
+        self.game_time_s.encode(encoder)?;
+        self.game_tick.encode(encoder)?;
+        self.sequence.encode(encoder)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "binary")]
+impl<Context> bincode::Decode<Context> for NormalizedTelemetry {
+    fn decode<D: bincode::de::Decoder<Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, bincode::error::DecodeError> {
+        use bincode::Decode;
+        Ok(Self {
+            speed_ms: Decode::decode(decoder)?,
+            steering_angle: Decode::decode(decoder)?,
+            throttle: Decode::decode(decoder)?,
+            brake: Decode::decode(decoder)?,
+            clutch: Decode::decode(decoder)?,
+            rpm: Decode::decode(decoder)?,
+            max_rpm: Decode::decode(decoder)?,
+            gear: Decode::decode(decoder)?,
+            num_gears: Decode::decode(decoder)?,
+            lateral_g: Decode::decode(decoder)?,
+            longitudinal_g: Decode::decode(decoder)?,
+            vertical_g: Decode::decode(decoder)?,
+            slip_ratio: Decode::decode(decoder)?,
+            slip_angle_fl: Decode::decode(decoder)?,
+            slip_angle_fr: Decode::decode(decoder)?,
+            slip_angle_rl: Decode::decode(decoder)?,
+            slip_angle_rr: Decode::decode(decoder)?,
+            tire_temps_c: Decode::decode(decoder)?,
+            tire_pressures_psi: Decode::decode(decoder)?,
+            ffb_scalar: Decode::decode(decoder)?,
+            ffb_torque_nm: Decode::decode(decoder)?,
+            flags: Decode::decode(decoder)?,
+            car_id: Decode::decode(decoder)?,
+            track_id: Decode::decode(decoder)?,
+            session_id: Decode::decode(decoder)?,
+            penalties: Decode::decode(decoder)?,
+            tires: Decode::decode(decoder)?,
+            timing: Decode::decode(decoder)?,
+            world_position: Decode::decode(decoder)?,
+            lap_distance_fraction: Decode::decode(decoder)?,
+            position: Decode::decode(decoder)?,
+            lap: Decode::decode(decoder)?,
+            current_lap_time_s: Decode::decode(decoder)?,
+            best_lap_time_s: Decode::decode(decoder)?,
+            last_lap_time_s: Decode::decode(decoder)?,
+            delta_ahead_s: Decode::decode(decoder)?,
+            delta_behind_s: Decode::decode(decoder)?,
+            fuel_percent: Decode::decode(decoder)?,
+            engine_temp_c: Decode::decode(decoder)?,
+            extended: Decode::decode(decoder)?,
+            game_time_s: Decode::decode(decoder)?,
+            game_tick: Decode::decode(decoder)?,
+            sequence: Decode::decode(decoder)?,
+            timestamp: default_timestamp(),
+        })
+    }
+}
+
+#[cfg(feature = "binary")]
+bincode::impl_borrow_decode!(NormalizedTelemetry);
+
 impl Default for NormalizedTelemetry {
     fn default() -> Self {
         Self {
@@ -226,6 +396,11 @@ impl Default for NormalizedTelemetry {
             car_id: None,
             track_id: None,
             session_id: None,
+            penalties: None,
+            tires: None,
+            timing: None,
+            world_position: None,
+            lap_distance_fraction: None,
             position: 0,
             lap: 0,
             current_lap_time_s: 0.0,
@@ -236,6 +411,8 @@ impl Default for NormalizedTelemetry {
             fuel_percent: 0.0,
             engine_temp_c: 0.0,
             extended: BTreeMap::new(),
+            game_time_s: None,
+            game_tick: None,
             timestamp: Instant::now(),
             sequence: 0,
         }
@@ -272,7 +449,7 @@ impl NormalizedTelemetry {
     /// assert!((t.speed_kmh() - 36.0).abs() < 0.1);
     /// ```
     pub fn speed_kmh(&self) -> f32 {
-        self.speed_ms * 3.6
+        self.speed_ms * KMH_PER_MS
     }
 
     /// Get speed in mph.
@@ -286,7 +463,46 @@ impl NormalizedTelemetry {
     /// assert!((t.speed_mph() - 22.37).abs() < 0.1);
     /// ```
     pub fn speed_mph(&self) -> f32 {
-        self.speed_ms * 2.237
+        self.speed_ms * MPH_PER_MS
+    }
+
+    /// Speed, RPM, FFB, slip and tire pressures and temperatures converted
+    /// to `profile` and formatted for display.
+    ///
+    /// Tire values come from [`tires`](Self::tires), falling back to the
+    /// non-zero entries of `tire_pressures_psi` and `tire_temps_c`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use racing_wheel_schemas::telemetry::{NormalizedTelemetry, UnitsProfile};
+    ///
+    /// let t = NormalizedTelemetry::builder().speed_ms(27.8).build();
+    /// let formatted = t.formatted(&UnitsProfile::IMPERIAL);
+    /// assert_eq!(formatted.speed.map(|speed| speed.text).as_deref(), Some("62 mph"));
+    /// ```
+    pub fn formatted(&self, profile: &UnitsProfile) -> FormattedTelemetry {
+        let corners = self.tires.unwrap_or_default().corners();
+        let legacy = |value: f32| (value != 0.0).then_some(value);
+        let mut tire_pressures_kpa = [None; 4];
+        let mut tire_temps_c = [None; 4];
+        for (index, corner) in corners.iter().enumerate() {
+            tire_pressures_kpa[index] = corner
+                .pressure_kpa
+                .or_else(|| legacy(self.tire_pressures_psi[index] * KPA_PER_PSI));
+            tire_temps_c[index] = corner
+                .surface_temp_c
+                .or_else(|| legacy(f32::from(self.tire_temps_c[index])));
+        }
+        TelemetryReadings {
+            speed_ms: Some(self.speed_ms),
+            rpm: Some(self.rpm),
+            ffb_scalar: Some(self.ffb_scalar),
+            slip_ratio: Some(self.slip_ratio),
+            tire_pressures_kpa,
+            tire_temps_c,
+        }
+        .formatted(profile)
     }
 
     /// Get the average slip angle across all tires.
@@ -392,6 +608,20 @@ impl NormalizedTelemetry {
         self.extended.get(key)
     }
 
+    /// Get an extended value by dotted path, e.g. `"ers.deployed_j"` for the
+    /// `deployed_j` entry of the `ers` map. See [`TelemetryValue::get_path`].
+    pub fn get_extended_path(&self, path: &str) -> Option<&TelemetryValue> {
+        let (key, rest) = match path.split_once('.') {
+            Some((key, rest)) => (key, Some(rest)),
+            None => (path, None),
+        };
+        let value = self.extended.get(key)?;
+        match rest {
+            Some(rest) => value.get_path(rest),
+            None => Some(value),
+        }
+    }
+
     /// Add an extended telemetry value.
     pub fn with_extended(mut self, key: impl Into<String>, value: TelemetryValue) -> Self {
         self.extended.insert(key.into(), value);
@@ -410,6 +640,20 @@ impl NormalizedTelemetry {
         self
     }
 
+    /// Set per-corner tire data, dropping values that fail
+    /// [`TireCorner::validated`]. Data with nothing left clears the field.
+    pub fn with_tire_data(mut self, tires: TireData) -> Self {
+        self.tires = Some(tires.validated()).filter(|tires| !tires.is_empty());
+        self
+    }
+
+    /// Set lap and session timing, dropping values that fail
+    /// [`SessionTiming::validated`]. Timing with nothing left clears the field.
+    pub fn with_session_timing(mut self, timing: SessionTiming) -> Self {
+        self.timing = Some(timing.validated()).filter(|timing| !timing.is_empty());
+        self
+    }
+
     // Backward-compatible builder methods (deprecated - use builder() instead)
 
     /// Set FFB scalar value (-1.0 to 1.0).
@@ -506,6 +750,62 @@ impl NormalizedTelemetry {
         self
     }
 
+    /// Whether every float field [`validated`](Self::validated) checks is
+    /// finite; a frame failing this carries NaN or infinite readings.
+    pub fn all_finite(&self) -> bool {
+        let scalars = [
+            self.speed_ms,
+            self.steering_angle,
+            self.throttle,
+            self.brake,
+            self.clutch,
+            self.rpm,
+            self.max_rpm,
+            self.lateral_g,
+            self.longitudinal_g,
+            self.vertical_g,
+            self.slip_ratio,
+            self.slip_angle_fl,
+            self.slip_angle_fr,
+            self.slip_angle_rl,
+            self.slip_angle_rr,
+            self.ffb_scalar,
+            self.ffb_torque_nm,
+            self.fuel_percent,
+            self.engine_temp_c,
+        ];
+        scalars.iter().all(|value| value.is_finite())
+            && self.game_time_s.is_none_or(f64::is_finite)
+            && self
+                .world_position
+                .is_none_or(|position| position.iter().all(|axis| axis.is_finite()))
+            && self.lap_distance_fraction.is_none_or(f32::is_finite)
+            && self.tires.is_none_or(|tires| {
+                tires.corners().iter().all(|corner| {
+                    [
+                        corner.surface_temp_c,
+                        corner.pressure_kpa,
+                        corner.wear_fraction,
+                        corner.slip_ratio,
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .all(f32::is_finite)
+                })
+            })
+            && self.timing.is_none_or(|timing| {
+                [
+                    timing.current_lap_ms,
+                    timing.last_lap_ms,
+                    timing.best_lap_ms,
+                    timing.session_time_remaining_ms,
+                ]
+                .into_iter()
+                .flatten()
+                .all(f64::is_finite)
+            })
+    }
+
     /// Validate and clamp all fields to reasonable ranges.
     pub fn validated(self) -> Self {
         Self {
@@ -604,6 +904,22 @@ impl NormalizedTelemetry {
             } else {
                 0.0
             },
+            game_time_s: self.game_time_s.filter(|t| t.is_finite() && *t >= 0.0),
+            tires: self
+                .tires
+                .map(TireData::validated)
+                .filter(|tires| !tires.is_empty()),
+            timing: self
+                .timing
+                .map(SessionTiming::validated)
+                .filter(|timing| !timing.is_empty()),
+            world_position: self
+                .world_position
+                .filter(|position| position.iter().all(|axis| axis.is_finite())),
+            lap_distance_fraction: self
+                .lap_distance_fraction
+                .filter(|fraction| fraction.is_finite())
+                .map(|fraction| fraction.clamp(0.0, 1.0)),
             ..self
         }
     }
@@ -835,6 +1151,56 @@ impl NormalizedTelemetryBuilder {
         self
     }
 
+    /// Set penalty state.
+    pub fn penalties(mut self, penalties: PenaltyState) -> Self {
+        self.inner.penalties = Some(penalties);
+        self
+    }
+
+    /// Set per-corner tire data. Invalid values are dropped as in
+    /// [`TireCorner::validated`].
+    pub fn tires(mut self, tires: TireData) -> Self {
+        self.inner = self.inner.with_tire_data(tires);
+        self
+    }
+
+    /// Set lap and session timing. Negative or non-finite times and negative
+    /// lap numbers are dropped.
+    pub fn timing(mut self, timing: SessionTiming) -> Self {
+        self.inner = self.inner.with_session_timing(timing);
+        self
+    }
+
+    /// Set world position in meters. Positions with a non-finite axis are ignored.
+    pub fn world_position(mut self, position: [f32; 3]) -> Self {
+        if position.iter().all(|axis| axis.is_finite()) {
+            self.inner.world_position = Some(position);
+        }
+        self
+    }
+
+    /// Set lap distance fraction (0.0-1.0).
+    pub fn lap_distance_fraction(mut self, value: f32) -> Self {
+        if value.is_finite() {
+            self.inner.lap_distance_fraction = Some(value.clamp(0.0, 1.0));
+        }
+        self
+    }
+
+    /// Set the game-side clock in seconds. Non-finite or negative values are ignored.
+    pub fn game_time_s(mut self, seconds: f64) -> Self {
+        if seconds.is_finite() && seconds >= 0.0 {
+            self.inner.game_time_s = Some(seconds);
+        }
+        self
+    }
+
+    /// Set the game-side frame or packet counter.
+    pub fn game_tick(mut self, tick: u64) -> Self {
+        self.inner.game_tick = Some(tick);
+        self
+    }
+
     /// Set race position.
     pub fn position(mut self, pos: u8) -> Self {
         self.inner.position = pos;
@@ -929,6 +1295,7 @@ impl NormalizedTelemetryBuilder {
 
 /// Racing flags and status information.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "binary", derive(bincode::Encode, bincode::Decode))]
 pub struct TelemetryFlags {
     /// Yellow flag (caution).
     #[serde(default)]
@@ -994,6 +1361,10 @@ pub struct TelemetryFlags {
     #[serde(default)]
     pub safety_car: bool,
 
+    /// Virtual safety car (or full-course yellow) in effect.
+    #[serde(default)]
+    pub virtual_safety_car: bool,
+
     /// Formation lap.
     #[serde(default)]
     pub formation_lap: bool,
@@ -1026,14 +1397,270 @@ impl Default for TelemetryFlags {
             abs_active: false,
             engine_limiter: false,
             safety_car: false,
+            virtual_safety_car: false,
             formation_lap: false,
             session_paused: false,
         }
     }
 }
 
+/// Measurements for a single tire.
+///
+/// Games report different subsets, so every value is optional.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "binary", derive(bincode::Encode, bincode::Decode))]
+pub struct TireCorner {
+    /// Tread surface temperature in Celsius.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub surface_temp_c: Option<f32>,
+
+    /// Inflation pressure in kilopascals.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pressure_kpa: Option<f32>,
+
+    /// Tread worn away, from 0.0 (new) to 1.0 (fully worn).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wear_fraction: Option<f32>,
+
+    /// Longitudinal slip ratio; negative while braking.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slip_ratio: Option<f32>,
+}
+
+impl TireCorner {
+    /// Drop non-finite values and negative pressures, and clamp wear to 0.0-1.0.
+    pub fn validated(self) -> Self {
+        Self {
+            surface_temp_c: self.surface_temp_c.filter(|t| t.is_finite()),
+            pressure_kpa: self.pressure_kpa.filter(|p| p.is_finite() && *p >= 0.0),
+            wear_fraction: self
+                .wear_fraction
+                .filter(|w| w.is_finite())
+                .map(|w| w.clamp(0.0, 1.0)),
+            slip_ratio: self.slip_ratio.filter(|s| s.is_finite()),
+        }
+    }
+
+    /// Whether no value is set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Tire measurements for each corner of the car.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "binary", derive(bincode::Encode, bincode::Decode))]
+pub struct TireData {
+    /// Front-left tire.
+    #[serde(default)]
+    pub fl: TireCorner,
+
+    /// Front-right tire.
+    #[serde(default)]
+    pub fr: TireCorner,
+
+    /// Rear-left tire.
+    #[serde(default)]
+    pub rl: TireCorner,
+
+    /// Rear-right tire.
+    #[serde(default)]
+    pub rr: TireCorner,
+}
+
+impl TireData {
+    /// Build from corners in FL, FR, RL, RR order.
+    pub fn from_corners([fl, fr, rl, rr]: [TireCorner; 4]) -> Self {
+        Self { fl, fr, rl, rr }
+    }
+
+    /// Corners in FL, FR, RL, RR order.
+    pub fn corners(&self) -> [TireCorner; 4] {
+        [self.fl, self.fr, self.rl, self.rr]
+    }
+
+    /// Validate every corner; see [`TireCorner::validated`].
+    pub fn validated(self) -> Self {
+        let [fl, fr, rl, rr] = self.corners().map(TireCorner::validated);
+        Self { fl, fr, rl, rr }
+    }
+
+    /// Whether no corner has any value set.
+    pub fn is_empty(&self) -> bool {
+        self.corners().iter().all(TireCorner::is_empty)
+    }
+}
+
+/// Kind of penalty currently awaiting the driver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "binary", derive(bincode::Encode, bincode::Decode))]
+#[serde(rename_all = "snake_case")]
+pub enum PenaltyKind {
+    /// No outstanding penalty.
+    #[default]
+    None,
+    /// Time added to the race result or served at the next stop.
+    TimePenalty,
+    /// Drive through the pit lane without stopping.
+    DriveThrough,
+    /// Stop in the pit box for a fixed time.
+    StopAndGo,
+    /// Disqualified from the session.
+    Disqualified,
+    /// A penalty the game reports but that has no dedicated kind here.
+    Other,
+}
+
+/// Penalty and track-limits state as reported by the game.
+///
+/// Counters are cumulative for the session; games that reset them between
+/// sessions produce a fresh state on the new session id.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "binary", derive(bincode::Encode, bincode::Decode))]
+pub struct PenaltyState {
+    /// Outstanding penalty, [`PenaltyKind::None`] once served.
+    #[serde(default)]
+    pub active_penalty_kind: PenaltyKind,
+
+    /// Accumulated time penalty in seconds.
+    #[serde(default)]
+    pub penalty_time_s: f32,
+
+    /// Track-limits (corner cutting) warnings received this session.
+    #[serde(default)]
+    pub track_limit_warnings: u16,
+
+    /// A drive-through penalty is waiting to be served.
+    #[serde(default)]
+    pub drive_through_pending: bool,
+
+    /// The driver has been disqualified.
+    #[serde(default)]
+    pub dsq: bool,
+}
+
+/// Change in [`PenaltyState`] between two consecutive frames.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum PenaltyEvent {
+    /// A new track-limits warning; `total` is the session count after it.
+    TrackLimitWarning {
+        /// Warnings received so far this session.
+        total: u16,
+    },
+    /// A new penalty was awarded.
+    PenaltyAwarded {
+        /// Kind of the new penalty.
+        kind: PenaltyKind,
+        /// Accumulated penalty time after the award, in seconds.
+        time_s: f32,
+    },
+    /// The previously outstanding penalty was served or cleared.
+    PenaltyServed {
+        /// Kind of the served penalty.
+        kind: PenaltyKind,
+    },
+    /// The driver was disqualified.
+    Disqualified,
+}
+
+/// Turns a stream of [`PenaltyState`] samples into [`PenaltyEvent`]s.
+///
+/// Games repeat the same state every frame, so events fire only on edges. A
+/// frame without penalty data leaves the tracked state untouched; call
+/// [`PenaltyTracker::reset`] at session boundaries.
+#[derive(Debug, Clone, Default)]
+pub struct PenaltyTracker {
+    last: PenaltyState,
+}
+
+impl PenaltyTracker {
+    /// Create a tracker starting from a clean state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Last state seen by the tracker.
+    pub fn state(&self) -> &PenaltyState {
+        &self.last
+    }
+
+    /// Forget the tracked state, e.g. when the session changes.
+    pub fn reset(&mut self) {
+        self.last = PenaltyState::default();
+    }
+
+    /// Compare `current` against the last state and return the resulting events.
+    pub fn update(&mut self, current: Option<&PenaltyState>) -> Vec<PenaltyEvent> {
+        let Some(current) = current.copied() else {
+            return Vec::new();
+        };
+        let previous = std::mem::replace(&mut self.last, current);
+        let mut events = Vec::new();
+
+        if current.track_limit_warnings > previous.track_limit_warnings {
+            events.push(PenaltyEvent::TrackLimitWarning {
+                total: current.track_limit_warnings,
+            });
+        }
+
+        let kind_changed = current.active_penalty_kind != previous.active_penalty_kind;
+        if kind_changed
+            && previous.active_penalty_kind != PenaltyKind::None
+            && current.active_penalty_kind != PenaltyKind::Disqualified
+        {
+            events.push(PenaltyEvent::PenaltyServed {
+                kind: previous.active_penalty_kind,
+            });
+        } else if !kind_changed
+            && current.active_penalty_kind == PenaltyKind::None
+            && current.penalty_time_s < previous.penalty_time_s
+        {
+            events.push(PenaltyEvent::PenaltyServed {
+                kind: PenaltyKind::TimePenalty,
+            });
+        }
+
+        let time_added = current.penalty_time_s > previous.penalty_time_s;
+        let new_kind = kind_changed && current.active_penalty_kind != PenaltyKind::None;
+        if current.active_penalty_kind != PenaltyKind::Disqualified && (new_kind || time_added) {
+            let kind = match current.active_penalty_kind {
+                PenaltyKind::None => PenaltyKind::TimePenalty,
+                kind => kind,
+            };
+            events.push(PenaltyEvent::PenaltyAwarded {
+                kind,
+                time_s: current.penalty_time_s,
+            });
+        }
+
+        let is_dsq = current.dsq || current.active_penalty_kind == PenaltyKind::Disqualified;
+        let was_dsq = previous.dsq || previous.active_penalty_kind == PenaltyKind::Disqualified;
+        if is_dsq && !was_dsq {
+            events.push(PenaltyEvent::Disqualified);
+        }
+
+        events
+    }
+}
+
+/// Deepest map nesting [`TelemetryValue::try_map`] accepts. A map of scalars
+/// has depth 1.
+pub const MAX_TELEMETRY_VALUE_DEPTH: usize = 4;
+
+/// Error returned by [`TelemetryValue::try_map`] for maps nested too deeply.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("telemetry map nested {depth} levels deep (maximum is {max_depth})")]
+pub struct TelemetryValueDepthError {
+    /// Depth of the rejected map.
+    pub depth: usize,
+    /// The maximum allowed depth.
+    pub max_depth: usize,
+}
+
 /// Extended telemetry value for game-specific data.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "binary", derive(bincode::Encode, bincode::Decode))]
 #[serde(tag = "type", content = "value")]
 pub enum TelemetryValue {
     /// Floating-point value.
@@ -1044,6 +1671,167 @@ pub enum TelemetryValue {
     Boolean(bool),
     /// String value.
     String(String),
+    /// Floating-point array, such as one value per wheel.
+    FloatArray(Vec<f32>),
+    /// Integer array, such as one value per wheel.
+    IntArray(Vec<i32>),
+    /// Named sub-values, sorted by key so serialized output is stable.
+    /// Build with [`TelemetryValue::try_map`] to keep the nesting bounded.
+    Map(BTreeMap<String, TelemetryValue>),
+}
+
+impl TelemetryValue {
+    /// Build a [`TelemetryValue::Map`], rejecting nesting deeper than
+    /// [`MAX_TELEMETRY_VALUE_DEPTH`].
+    pub fn try_map<K: Into<String>>(
+        entries: impl IntoIterator<Item = (K, TelemetryValue)>,
+    ) -> Result<Self, TelemetryValueDepthError> {
+        let map = Self::Map(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect(),
+        );
+        match map.depth() {
+            depth if depth > MAX_TELEMETRY_VALUE_DEPTH => Err(TelemetryValueDepthError {
+                depth,
+                max_depth: MAX_TELEMETRY_VALUE_DEPTH,
+            }),
+            _ => Ok(map),
+        }
+    }
+
+    /// Map nesting depth: 0 for scalars and arrays, 1 for a map of those.
+    pub fn depth(&self) -> usize {
+        match self {
+            Self::Map(entries) => 1 + entries.values().map(Self::depth).max().unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    /// Numeric value as `f32`; integers are converted.
+    pub fn as_f32(&self) -> Option<f32> {
+        match self {
+            Self::Float(value) => Some(*value),
+            Self::Integer(value) => Some(*value as f32),
+            _ => None,
+        }
+    }
+
+    /// Elements of a [`TelemetryValue::FloatArray`].
+    pub fn as_f32_slice(&self) -> Option<&[f32]> {
+        match self {
+            Self::FloatArray(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Elements of a [`TelemetryValue::IntArray`].
+    pub fn as_i32_slice(&self) -> Option<&[i32]> {
+        match self {
+            Self::IntArray(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Look up a nested value by dot-separated keys, e.g. `"mguk.harvested_j"`.
+    pub fn get_path(&self, path: &str) -> Option<&TelemetryValue> {
+        path.split('.').try_fold(self, |value, key| match value {
+            Self::Map(entries) => entries.get(key),
+            _ => None,
+        })
+    }
+}
+
+impl From<racing_wheel_telemetry_contracts::TelemetryValue> for TelemetryValue {
+    fn from(value: racing_wheel_telemetry_contracts::TelemetryValue) -> Self {
+        use racing_wheel_telemetry_contracts::TelemetryValue as Contract;
+        match value {
+            Contract::Float(v) => Self::Float(v),
+            Contract::Integer(v) => Self::Integer(v),
+            Contract::Boolean(v) => Self::Boolean(v),
+            Contract::String(v) => Self::String(v),
+        }
+    }
+}
+
+/// Lets the extended-key registry write typed values into canonical telemetry.
+impl racing_wheel_telemetry_contracts::ExtendedFields for NormalizedTelemetry {
+    fn insert_extended_value(
+        &mut self,
+        key: &str,
+        value: racing_wheel_telemetry_contracts::TelemetryValue,
+    ) {
+        self.extended.insert(key.to_string(), value.into());
+    }
+
+    fn extended_key_names(&self) -> Vec<&str> {
+        self.extended.keys().map(String::as_str).collect()
+    }
+}
+
+/// Lets a `TelemetryValidator` check canonical telemetry. Every reading is
+/// always set, so unsetting one zeroes it.
+impl racing_wheel_telemetry_contracts::ValidatedFields for NormalizedTelemetry {
+    fn float_field(&self, field: racing_wheel_telemetry_contracts::FloatField) -> Option<f32> {
+        Some(*self.float_slot(field))
+    }
+
+    fn set_float_field(
+        &mut self,
+        field: racing_wheel_telemetry_contracts::FloatField,
+        value: Option<f32>,
+    ) {
+        *self.float_slot_mut(field) = value.unwrap_or(0.0);
+    }
+
+    fn gear_field(&self) -> Option<i8> {
+        Some(self.gear)
+    }
+
+    fn set_gear_field(&mut self, gear: i8) {
+        self.gear = gear;
+    }
+}
+
+impl NormalizedTelemetry {
+    fn float_slot(&self, field: racing_wheel_telemetry_contracts::FloatField) -> &f32 {
+        use racing_wheel_telemetry_contracts::FloatField;
+        match field {
+            FloatField::FfbScalar => &self.ffb_scalar,
+            FloatField::Rpm => &self.rpm,
+            FloatField::SpeedMs => &self.speed_ms,
+            FloatField::SlipRatio => &self.slip_ratio,
+            FloatField::SteeringAngle => &self.steering_angle,
+            FloatField::Throttle => &self.throttle,
+            FloatField::Brake => &self.brake,
+            FloatField::LateralG => &self.lateral_g,
+            FloatField::LongitudinalG => &self.longitudinal_g,
+            FloatField::SlipAngleFl => &self.slip_angle_fl,
+            FloatField::SlipAngleFr => &self.slip_angle_fr,
+            FloatField::SlipAngleRl => &self.slip_angle_rl,
+            FloatField::SlipAngleRr => &self.slip_angle_rr,
+        }
+    }
+
+    fn float_slot_mut(&mut self, field: racing_wheel_telemetry_contracts::FloatField) -> &mut f32 {
+        use racing_wheel_telemetry_contracts::FloatField;
+        match field {
+            FloatField::FfbScalar => &mut self.ffb_scalar,
+            FloatField::Rpm => &mut self.rpm,
+            FloatField::SpeedMs => &mut self.speed_ms,
+            FloatField::SlipRatio => &mut self.slip_ratio,
+            FloatField::SteeringAngle => &mut self.steering_angle,
+            FloatField::Throttle => &mut self.throttle,
+            FloatField::Brake => &mut self.brake,
+            FloatField::LateralG => &mut self.lateral_g,
+            FloatField::LongitudinalG => &mut self.longitudinal_g,
+            FloatField::SlipAngleFl => &mut self.slip_angle_fl,
+            FloatField::SlipAngleFr => &mut self.slip_angle_fr,
+            FloatField::SlipAngleRl => &mut self.slip_angle_rl,
+            FloatField::SlipAngleRr => &mut self.slip_angle_rr,
+        }
+    }
 }
 
 /// Serializable version of NormalizedTelemetry for recording/replay.
@@ -1145,6 +1933,26 @@ pub struct TelemetrySnapshot {
     #[serde(default)]
     pub fuel_percent: f32,
 
+    /// Penalty state.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub penalties: Option<PenaltyState>,
+
+    /// Per-corner tire data.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tires: Option<TireData>,
+
+    /// Lap and session timing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timing: Option<SessionTiming>,
+
+    /// Game-side clock in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub game_time_s: Option<f64>,
+
+    /// Game-side frame or packet counter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub game_tick: Option<u64>,
+
     /// Sequence number.
     #[serde(default)]
     pub sequence: u64,
@@ -1184,6 +1992,11 @@ impl TelemetrySnapshot {
             lap: telemetry.lap,
             current_lap_time_s: telemetry.current_lap_time_s,
             fuel_percent: telemetry.fuel_percent,
+            penalties: telemetry.penalties,
+            tires: telemetry.tires,
+            timing: telemetry.timing,
+            game_time_s: telemetry.game_time_s,
+            game_tick: telemetry.game_tick,
             sequence: telemetry.sequence,
         }
     }
@@ -1216,6 +2029,11 @@ impl TelemetrySnapshot {
             lap: self.lap,
             current_lap_time_s: self.current_lap_time_s,
             fuel_percent: self.fuel_percent,
+            penalties: self.penalties,
+            tires: self.tires,
+            timing: self.timing,
+            game_time_s: self.game_time_s,
+            game_tick: self.game_tick,
             sequence: self.sequence,
             ..Default::default()
         }
@@ -1224,6 +2042,7 @@ impl TelemetrySnapshot {
 
 /// Telemetry frame with timing information for streaming.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "binary", derive(bincode::Encode, bincode::Decode))]
 pub struct TelemetryFrame {
     /// Normalized telemetry data.
     pub data: NormalizedTelemetry,
@@ -1236,6 +2055,44 @@ pub struct TelemetryFrame {
 
     /// Raw data size for diagnostics.
     pub raw_size: usize,
+
+    /// Schema version of the serialized shape. Frames recorded before
+    /// versioning read as [`LEGACY_FRAME_VERSION`]; decode those through
+    /// [`migrate_frame`].
+    #[serde(default = "legacy_frame_version")]
+    pub version: u32,
+
+    /// Other cars in the session, sorted by position; empty for games that
+    /// report the player's car only and in recordings made before it existed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub opponents: Vec<OpponentSnapshot>,
+}
+
+fn legacy_frame_version() -> u32 {
+    LEGACY_FRAME_VERSION
+}
+
+/// Upgrade a recorded frame of any supported schema version and decode it.
+///
+/// Fails with [`FrameMigrationError::FutureVersion`] for frames written by a
+/// newer build, rather than dropping the fields this build does not know.
+pub fn migrate_frame(value: serde_json::Value) -> Result<TelemetryFrame, FrameMigrationError> {
+    let upgraded = racing_wheel_telemetry_contracts::upgrade_frame(value)?;
+    Ok(serde_json::from_value(upgraded)?)
+}
+
+/// Encode `frame` in the compact binary layout of
+/// [`racing_wheel_telemetry_contracts::codec`].
+#[cfg(feature = "binary")]
+pub fn encode_frame(frame: &TelemetryFrame) -> Result<Vec<u8>, BinaryCodecError> {
+    codec::encode(frame)
+}
+
+/// Decode a frame written by [`encode_frame`]. Only frames of the current
+/// [`TELEMETRY_SCHEMA_VERSION`] decode; see the codec docs.
+#[cfg(feature = "binary")]
+pub fn decode_frame(bytes: &[u8]) -> Result<TelemetryFrame, BinaryCodecError> {
+    codec::decode(bytes)
 }
 
 impl TelemetryFrame {
@@ -1251,9 +2108,17 @@ impl TelemetryFrame {
             timestamp_ns,
             sequence,
             raw_size,
+            version: TELEMETRY_SCHEMA_VERSION,
+            opponents: Vec::new(),
         }
     }
 
+    /// Attach the other cars in the session, already sorted and bounded.
+    pub fn with_opponents(mut self, opponents: Vec<OpponentSnapshot>) -> Self {
+        self.opponents = opponents;
+        self
+    }
+
     /// Create a frame from telemetry with current timestamp.
     pub fn from_telemetry(data: NormalizedTelemetry, sequence: u64, raw_size: usize) -> Self {
         let timestamp_ns = std::time::SystemTime::now()
@@ -1266,6 +2131,8 @@ impl TelemetryFrame {
             timestamp_ns,
             sequence,
             raw_size,
+            version: TELEMETRY_SCHEMA_VERSION,
+            opponents: Vec::new(),
         }
     }
 }
@@ -1311,6 +2178,18 @@ pub struct TelemetryFieldCoverage {
     pub lap_timing: bool,
     /// Fuel level supported.
     pub fuel: bool,
+    /// Penalty / track-limits state supported.
+    #[serde(default)]
+    pub penalties: bool,
+    /// Game-side clock (`game_time_s` / `game_tick`) supported.
+    #[serde(default)]
+    pub game_time: bool,
+    /// Per-corner tire data (`tires`) supported.
+    #[serde(default)]
+    pub tires: bool,
+    /// Which `timing` fields are supported.
+    #[serde(default)]
+    pub timing: TimingCoverage,
     /// Extended fields available.
     pub extended_fields: Vec<String>,
 }
@@ -1583,6 +2462,11 @@ mod tests {
             lap: 5,
             current_lap_time_s: 82.5,
             fuel_percent: 0.75,
+            penalties: None,
+            tires: None,
+            timing: None,
+            game_time_s: None,
+            game_tick: None,
             sequence: 42,
         };
 
@@ -1656,6 +2540,12 @@ mod tests {
             TelemetryValue::Integer(42),
             TelemetryValue::Boolean(true),
             TelemetryValue::String("test".to_string()),
+            TelemetryValue::FloatArray(vec![0.5, -1.25]),
+            TelemetryValue::IntArray(vec![630, 640, 410, 420]),
+            TelemetryValue::try_map([
+                ("deployed_j", TelemetryValue::Float(150_000.0)),
+                ("wheels", TelemetryValue::IntArray(vec![1, 2, 3, 4])),
+            ])?,
         ];
 
         for value in values {
@@ -1666,6 +2556,102 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_telemetry_value_json_shape_is_stable() -> TestResult {
+        let cases = [
+            (
+                TelemetryValue::Float(1.5),
+                r#"{"type":"Float","value":1.5}"#,
+            ),
+            (
+                TelemetryValue::Integer(3),
+                r#"{"type":"Integer","value":3}"#,
+            ),
+            (
+                TelemetryValue::FloatArray(vec![1.0, 2.5]),
+                r#"{"type":"FloatArray","value":[1.0,2.5]}"#,
+            ),
+            (
+                TelemetryValue::IntArray(vec![7, -1]),
+                r#"{"type":"IntArray","value":[7,-1]}"#,
+            ),
+            (
+                TelemetryValue::try_map([
+                    ("mode", TelemetryValue::Integer(2)),
+                    ("active", TelemetryValue::Boolean(true)),
+                ])?,
+                r#"{"type":"Map","value":{"active":{"type":"Boolean","value":true},"mode":{"type":"Integer","value":2}}}"#,
+            ),
+        ];
+        for (value, json) in cases {
+            assert_eq!(serde_json::to_string(&value)?, json);
+            assert_eq!(serde_json::from_str::<TelemetryValue>(json)?, value);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_map_rejects_deep_nesting() -> TestResult {
+        let nest = |levels: usize| -> Result<TelemetryValue, TelemetryValueDepthError> {
+            let mut value = TelemetryValue::Integer(1);
+            for _ in 0..levels {
+                value = TelemetryValue::try_map([("inner", value)])?;
+            }
+            Ok(value)
+        };
+
+        assert_eq!(
+            nest(MAX_TELEMETRY_VALUE_DEPTH)?.depth(),
+            MAX_TELEMETRY_VALUE_DEPTH
+        );
+        assert_eq!(
+            nest(MAX_TELEMETRY_VALUE_DEPTH + 1),
+            Err(TelemetryValueDepthError {
+                depth: MAX_TELEMETRY_VALUE_DEPTH + 1,
+                max_depth: MAX_TELEMETRY_VALUE_DEPTH,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_telemetry_value_accessors() -> TestResult {
+        let ers = TelemetryValue::try_map([
+            ("deployed_this_lap", TelemetryValue::Float(0.4)),
+            (
+                "mguk",
+                TelemetryValue::try_map([("harvested_j", TelemetryValue::Integer(900))])?,
+            ),
+        ])?;
+        let telemetry = NormalizedTelemetry::builder()
+            .extended("ers", ers.clone())
+            .extended("brake_temps_c", TelemetryValue::FloatArray(vec![610.0; 4]))
+            .build();
+
+        assert_eq!(
+            ers.get_path("deployed_this_lap")
+                .and_then(TelemetryValue::as_f32),
+            Some(0.4)
+        );
+        assert_eq!(
+            telemetry
+                .get_extended_path("ers.mguk.harvested_j")
+                .and_then(TelemetryValue::as_f32),
+            Some(900.0)
+        );
+        assert_eq!(telemetry.get_extended_path("ers"), Some(&ers));
+        assert_eq!(telemetry.get_extended_path("ers.missing"), None);
+        assert_eq!(telemetry.get_extended_path("brake_temps_c.0"), None);
+        assert_eq!(
+            telemetry
+                .get_extended("brake_temps_c")
+                .and_then(TelemetryValue::as_f32_slice),
+            Some(&[610.0; 4][..])
+        );
+        assert_eq!(TelemetryValue::Boolean(true).as_f32(), None);
+        Ok(())
+    }
+
     #[test]
     fn test_telemetry_json_serialization() -> TestResult {
         let telemetry = NormalizedTelemetry::builder()
@@ -1684,4 +2670,155 @@ mod tests {
         assert_eq!(deserialized.car_id, Some("test_car".to_string()));
         Ok(())
     }
+
+    fn penalty_events(states: &[PenaltyState]) -> Vec<PenaltyEvent> {
+        let mut tracker = PenaltyTracker::new();
+        states
+            .iter()
+            .flat_map(|state| tracker.update(Some(state)))
+            .collect()
+    }
+
+    #[test]
+    fn test_penalty_tracker_emits_edges_once() {
+        let clean = PenaltyState::default();
+        let warned = PenaltyState {
+            track_limit_warnings: 1,
+            ..clean
+        };
+        let drive_through = PenaltyState {
+            active_penalty_kind: PenaltyKind::DriveThrough,
+            drive_through_pending: true,
+            ..warned
+        };
+        let served = warned;
+
+        let events = penalty_events(&[
+            clean,
+            warned,
+            warned,
+            drive_through,
+            drive_through,
+            served,
+            served,
+        ]);
+
+        assert_eq!(
+            events,
+            vec![
+                PenaltyEvent::TrackLimitWarning { total: 1 },
+                PenaltyEvent::PenaltyAwarded {
+                    kind: PenaltyKind::DriveThrough,
+                    time_s: 0.0,
+                },
+                PenaltyEvent::PenaltyServed {
+                    kind: PenaltyKind::DriveThrough,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_penalty_tracker_time_penalty_and_dsq() {
+        let five = PenaltyState {
+            penalty_time_s: 5.0,
+            ..PenaltyState::default()
+        };
+        let dsq = PenaltyState {
+            active_penalty_kind: PenaltyKind::Disqualified,
+            dsq: true,
+            ..five
+        };
+
+        let events = penalty_events(&[five, five, PenaltyState::default(), dsq, dsq]);
+
+        assert_eq!(
+            events,
+            vec![
+                PenaltyEvent::PenaltyAwarded {
+                    kind: PenaltyKind::TimePenalty,
+                    time_s: 5.0,
+                },
+                PenaltyEvent::PenaltyServed {
+                    kind: PenaltyKind::TimePenalty,
+                },
+                PenaltyEvent::Disqualified,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_penalty_tracker_ignores_frames_without_penalty_data() {
+        let mut tracker = PenaltyTracker::new();
+        let warned = PenaltyState {
+            track_limit_warnings: 2,
+            ..PenaltyState::default()
+        };
+
+        assert_eq!(tracker.update(Some(&warned)).len(), 1);
+        assert!(tracker.update(None).is_empty());
+        assert!(tracker.update(Some(&warned)).is_empty());
+        assert_eq!(tracker.state(), &warned);
+    }
+
+    #[test]
+    fn test_tire_data_drops_unusable_readings() -> Result<(), Box<dyn std::error::Error>> {
+        let tires = TireData::from_corners([
+            TireCorner {
+                surface_temp_c: Some(f32::NAN),
+                pressure_kpa: Some(-3.0),
+                wear_fraction: Some(1.4),
+                slip_ratio: Some(f32::INFINITY),
+            },
+            TireCorner {
+                pressure_kpa: Some(172.0),
+                ..TireCorner::default()
+            },
+            TireCorner::default(),
+            TireCorner::default(),
+        ]);
+
+        let telemetry = NormalizedTelemetry::builder().tires(tires).build();
+        let Some(validated) = telemetry.tires else {
+            return Err("tyres with one usable reading should be kept".into());
+        };
+        assert_eq!(validated.fl.surface_temp_c, None);
+        assert_eq!(validated.fl.pressure_kpa, None);
+        assert_eq!(validated.fl.wear_fraction, Some(1.0));
+        assert_eq!(validated.fl.slip_ratio, None);
+        assert_eq!(validated.fr.pressure_kpa, Some(172.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty_tire_data_is_not_attached() {
+        let telemetry = NormalizedTelemetry::builder()
+            .tires(TireData::default())
+            .build();
+        assert_eq!(telemetry.tires, None);
+    }
+
+    #[test]
+    fn test_tire_data_serde_round_trip_and_legacy_json() -> Result<(), Box<dyn std::error::Error>> {
+        let tires = TireData::from_corners(
+            [TireCorner {
+                surface_temp_c: Some(85.0),
+                pressure_kpa: Some(180.0),
+                wear_fraction: Some(0.25),
+                slip_ratio: Some(0.05),
+            }; 4],
+        );
+        let telemetry = NormalizedTelemetry::builder().tires(tires).build();
+
+        let json = serde_json::to_string(&telemetry)?;
+        let deserialized: NormalizedTelemetry = serde_json::from_str(&json)?;
+        assert_eq!(deserialized.tires, Some(tires));
+
+        // Frames recorded before tyre data existed carry no `tires` key.
+        let legacy = serde_json::to_value(NormalizedTelemetry::default())?;
+        assert!(legacy.get("tires").is_none());
+        let deserialized: NormalizedTelemetry = serde_json::from_value(legacy)?;
+        assert_eq!(deserialized.tires, None);
+        Ok(())
+    }
 }
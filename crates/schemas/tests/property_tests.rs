@@ -441,6 +441,7 @@ fn builder_covers_all_fields() -> Result<(), Box<dyn std::error::Error>> {
         abs_active: true,
         engine_limiter: true,
         safety_car: true,
+        virtual_safety_car: true,
         formation_lap: true,
         session_paused: true,
     };
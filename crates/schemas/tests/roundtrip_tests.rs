@@ -374,7 +374,12 @@ mod roundtrip {
             lap: 8,
             current_lap_time_s: 93.5,
             fuel_percent: 0.45,
+            penalties: None,
+            game_time_s: None,
+            game_tick: None,
             sequence: 100,
+            tires: None,
+            timing: None,
         };
         let rt = json_roundtrip(&snap)?;
         assert_eq!(rt.timestamp_ns, snap.timestamp_ns);
@@ -1494,7 +1499,12 @@ mod large_payloads {
                 lap: 0,
                 current_lap_time_s: 0.0,
                 fuel_percent: 1.0,
+                penalties: None,
+                game_time_s: None,
+                game_tick: None,
                 sequence: i as u64,
+                tires: None,
+                timing: None,
             })
             .collect();
 
@@ -513,7 +513,12 @@ mod roundtrip_telemetry {
             lap: 10,
             current_lap_time_s: 85.3,
             fuel_percent: 0.65,
+            penalties: None,
+            game_time_s: None,
+            game_tick: None,
             sequence: 999,
+            tires: None,
+            timing: None,
         };
         let rt = json_roundtrip(&snap)?;
         assert_eq!(rt.timestamp_ns, 123456789);
@@ -280,7 +280,12 @@ fn snap_telemetry_snapshot() -> Result<(), Err> {
         lap: 3,
         current_lap_time_s: 82.5,
         fuel_percent: 0.65,
+        penalties: None,
+        game_time_s: None,
+        game_tick: None,
         sequence: 100,
+        tires: None,
+        timing: None,
     };
     let val = serde_json::to_value(&snap)?;
     insta::assert_json_snapshot!("wire_telemetry_snapshot", val);
@@ -143,7 +143,12 @@ fn snapshot_telemetry_snapshot_json() -> Result<(), BoxErr> {
         lap: 12,
         current_lap_time_s: 82.5,
         fuel_percent: 0.65,
+        penalties: None,
+        game_time_s: None,
+        game_tick: None,
         sequence: 42,
+        tires: None,
+        timing: None,
     };
     let json = serde_json::to_string_pretty(&snap)?;
     insta::assert_snapshot!("telemetry_snapshot_json", json);
@@ -1990,7 +1990,12 @@ fn telemetry_snapshot_serde_roundtrip() -> TestResult {
         lap: 12,
         current_lap_time_s: 78.4,
         fuel_percent: 0.42,
+        penalties: None,
+        game_time_s: None,
+        game_tick: None,
         sequence: 5000,
+        tires: None,
+        timing: None,
     };
 
     let json = serde_json::to_string(&snapshot)?;
@@ -2170,6 +2175,7 @@ fn telemetry_flags_all_set_serde_roundtrip() -> TestResult {
         abs_active: true,
         engine_limiter: true,
         safety_car: true,
+        virtual_safety_car: true,
         formation_lap: true,
         session_paused: true,
     };
@@ -150,6 +150,7 @@ impl ConfigValidationService {
                         "speed_ms".to_string(),
                     ],
                     enable_high_rate_iracing_360hz: false,
+                    allow_port_sharing: false,
                 },
                 expected_diffs: vec![ConfigDiff {
                     file_path: "Documents/iRacing/app.ini".to_string(),
@@ -158,6 +159,8 @@ impl ConfigValidationService {
                     old_value: None,
                     new_value: "1".to_string(),
                     operation: DiffOperation::Add,
+                    backup_path: None,
+                    details: Vec::new(),
                 }],
                 expected_files: vec![ExpectedFile {
                     path: "Documents/iRacing/app.ini".to_string(),
@@ -183,6 +186,7 @@ impl ConfigValidationService {
                         "gear".to_string(),
                     ],
                     enable_high_rate_iracing_360hz: false,
+                    allow_port_sharing: false,
                 },
                 expected_diffs: vec![ConfigDiff {
                     file_path: "Documents/Assetto Corsa Competizione/Config/broadcasting.json"
@@ -201,6 +205,8 @@ impl ConfigValidationService {
 }"#
                     .to_string(),
                     operation: DiffOperation::Add,
+                    backup_path: None,
+                    details: Vec::new(),
                 }],
                 expected_files: vec![ExpectedFile {
                     path: "Documents/Assetto Corsa Competizione/Config/broadcasting.json"
@@ -236,6 +242,7 @@ impl ConfigValidationService {
                         "speed_ms".to_string(),
                     ],
                     enable_high_rate_iracing_360hz: false,
+                    allow_port_sharing: false,
                 },
                 expected_diffs: vec![
                     ConfigDiff {
@@ -259,6 +266,8 @@ impl ConfigValidationService {
 }"#
                         .to_string(),
                         operation: DiffOperation::Add,
+                        backup_path: None,
+                        details: Vec::new(),
                     },
                     ConfigDiff {
                         file_path: "Documents/My Games/WRC/telemetry/udp/openracing.json"
@@ -288,6 +297,8 @@ impl ConfigValidationService {
 }"#
                         .to_string(),
                         operation: DiffOperation::Add,
+                        backup_path: None,
+                        details: Vec::new(),
                     },
                 ],
                 expected_files: vec![
@@ -356,6 +367,7 @@ impl ConfigValidationService {
                         "slip_ratio".to_string(),
                     ],
                     enable_high_rate_iracing_360hz: false,
+                    allow_port_sharing: false,
                 },
                 expected_diffs: vec![ConfigDiff {
                     file_path: "Documents/OpenRacing/dirt5_bridge_contract.json".to_string(),
@@ -373,6 +385,8 @@ impl ConfigValidationService {
 }"#
                     .to_string(),
                     operation: DiffOperation::Add,
+                    backup_path: None,
+                    details: Vec::new(),
                 }],
                 expected_files: vec![ExpectedFile {
                     path: "Documents/OpenRacing/dirt5_bridge_contract.json".to_string(),
@@ -410,6 +424,7 @@ impl ConfigValidationService {
                         "flags".to_string(),
                     ],
                     enable_high_rate_iracing_360hz: false,
+                    allow_port_sharing: false,
                 },
                 expected_diffs: vec![ConfigDiff {
                     file_path: "Documents/OpenRacing/f1_bridge_contract.json".to_string(),
@@ -427,6 +442,8 @@ impl ConfigValidationService {
 }"#
                     .to_string(),
                     operation: DiffOperation::Add,
+                    backup_path: None,
+                    details: Vec::new(),
                 }],
                 expected_files: vec![ExpectedFile {
                     path: "Documents/OpenRacing/f1_bridge_contract.json".to_string(),
@@ -483,6 +500,8 @@ impl ConfigValidationService {
                 old_value: None,
                 new_value: actual_360hz_diff.new_value.clone(),
                 operation: actual_360hz_diff.operation.clone(),
+                backup_path: None,
+                details: Vec::new(),
             });
         }
 
@@ -931,6 +950,8 @@ mod tests {
             old_value: None,
             new_value: "1".to_string(),
             operation: DiffOperation::Add,
+            backup_path: None,
+            details: Vec::new(),
         }];
 
         let result = service
@@ -954,6 +975,8 @@ mod tests {
                 old_value: None,
                 new_value: "1".to_string(),
                 operation: DiffOperation::Add,
+                backup_path: None,
+                details: Vec::new(),
             },
             ConfigDiff {
                 file_path: "Documents/iRacing/app.ini".to_string(),
@@ -962,6 +985,8 @@ mod tests {
                 old_value: None,
                 new_value: "1".to_string(),
                 operation: DiffOperation::Add,
+                backup_path: None,
+                details: Vec::new(),
             },
         ];
 
@@ -1014,6 +1039,8 @@ mod tests {
             old_value: None,
             new_value: "value".to_string(),
             operation: DiffOperation::Add,
+            backup_path: None,
+            details: Vec::new(),
         };
 
         let diff2 = diff1.clone();
@@ -1,8 +1,8 @@
 pub use racing_wheel_telemetry_config::{
     ACCConfigWriter, ACRallyConfigWriter, AMS2ConfigWriter, ConfigDiff, ConfigWriter,
     ConfigWriterFactory, DiffOperation, Dirt5ConfigWriter, EAWRCConfigWriter, F1_25ConfigWriter,
-    F1ConfigWriter, IRacingConfigWriter, RFactor2ConfigWriter, TelemetryConfig,
-    config_writer_factories,
+    F1ConfigWriter, IRacingConfigWriter, RFactor2ConfigWriter, SurvivalExpectation,
+    TelemetryConfig, config_writer_factories,
 };
 
 #[cfg(test)]
@@ -66,6 +66,7 @@ mod tests {
             output_target: "127.0.0.1:12345".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let diffs = writer.get_expected_diffs(&config)?;
         assert!(!diffs.is_empty(), "iRacing writer should produce diffs");
@@ -88,6 +89,7 @@ mod tests {
             output_target: "127.0.0.1:9000".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let diffs = writer.get_expected_diffs(&config)?;
         assert!(!diffs.is_empty(), "ACC writer should produce diffs");
@@ -104,6 +106,7 @@ mod tests {
             output_target: "127.0.0.1:12345".to_string(),
             fields: vec![],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         for &(id, factory) in factories {
             let writer = factory();
@@ -214,6 +214,7 @@ impl GameService {
                 .map(|v| v.supported_fields.clone())
                 .unwrap_or_default(),
             enable_high_rate_iracing_360hz,
+            allow_port_sharing: false,
         };
 
         // Write configuration and get diffs
@@ -33,6 +33,7 @@ fn disconnection_config_strategy() -> impl Strategy<Value = DisconnectionConfig>
                     auto_reconnect,
                     max_reconnect_attempts,
                     reconnect_delay_ms,
+                    ..Default::default()
                 }
             },
         )
@@ -184,6 +185,7 @@ proptest! {
             auto_reconnect,
             max_reconnect_attempts: max_attempts,
             reconnect_delay_ms: 100,
+            ..Default::default()
         };
         let mut tracker = DisconnectionTracker::new(game_id, config);
 
@@ -454,6 +456,7 @@ mod unit_tests {
             auto_reconnect: true,
             max_reconnect_attempts: 0, // Unlimited
             reconnect_delay_ms: 100,
+            ..Default::default()
         };
         let mut tracker = DisconnectionTracker::new("test_game", config);
 
@@ -509,12 +512,15 @@ mod unit_tests {
         );
 
         // Timestamp should be non-zero (after UNIX epoch)
-        assert!(event.timestamp_ns > 0, "Event timestamp should be positive");
+        assert!(
+            event.timestamp_unix_ns > 0,
+            "Event timestamp should be positive"
+        );
 
         // Timestamp should be reasonable (after year 2020)
         let year_2020_ns: u64 = 1_577_836_800_000_000_000; // 2020-01-01 00:00:00 UTC
         assert!(
-            event.timestamp_ns > year_2020_ns,
+            event.timestamp_unix_ns > year_2020_ns,
             "Event timestamp should be after year 2020"
         );
 
@@ -975,6 +975,8 @@ mod tests {
                 old_value: None,
                 new_value: "1".to_string(),
                 operation: DiffOperation::Add,
+                backup_path: None,
+                details: Vec::new(),
             }];
 
             let result = svc.validate_config_generation("iracing", &diffs).await?;
@@ -1016,6 +1018,8 @@ mod tests {
                     old_value: None,
                     new_value: "1".to_string(),
                     operation: DiffOperation::Add,
+                    backup_path: None,
+                    details: Vec::new(),
                 },
                 ConfigDiff {
                     file_path: "some/other/file.txt".to_string(),
@@ -1024,6 +1028,8 @@ mod tests {
                     old_value: None,
                     new_value: "surprise".to_string(),
                     operation: DiffOperation::Add,
+                    backup_path: None,
+                    details: Vec::new(),
                 },
             ];
 
@@ -47,6 +47,7 @@ fn test_eawrc_writer_creates_structure_and_patches_config() -> TestResult {
         output_target: "127.0.0.1:20790".to_string(),
         fields: vec!["ffb_scalar".to_string(), "rpm".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
 
     let diffs = writer.write_config(temp_dir.path(), &config)?;
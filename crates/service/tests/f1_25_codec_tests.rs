@@ -3,12 +3,19 @@
 //! Uses pre-built binary packet fixtures that match the real EA F1 25 UDP format
 //! (packetFormat 2025). Fixtures were generated by scripts/gen_f1_25_fixtures.py.
 
-use racing_wheel_service::telemetry::{TelemetryAdapter, adapters::f1_25::F1_25Adapter};
+use racing_wheel_service::telemetry::{
+    TelemetryAdapter,
+    adapters::f1_25::{
+        F1_25Adapter, F125State, build_car_status_packet, build_car_telemetry_packet,
+    },
+};
 
 type TestResult = Result<(), Box<dyn std::error::Error>>;
 
 const FIXTURE_CAR_TELEMETRY: &[u8] = include_bytes!("fixtures/f1_25/car_telemetry_packet.bin");
 const FIXTURE_CAR_STATUS: &[u8] = include_bytes!("fixtures/f1_25/car_status_packet.bin");
+const FIXTURE_MOTION: &[u8] = include_bytes!("fixtures/f1_25/motion_packet.bin");
+const FIXTURE_MOTION_EX: &[u8] = include_bytes!("fixtures/f1_25/motion_ex_packet.bin");
 
 /// CarTelemetry (packet_id=6) must normalize to the expected core fields.
 ///
@@ -133,3 +140,53 @@ fn test_f1_25_wrong_format_rejected() {
     let result = adapter.normalize(&bad_packet);
     assert!(result.is_err(), "packet with format 2024 must be rejected");
 }
+
+/// Motion (packet_id=0) and Motion Ex (packet_id=13) fill G-forces and wheel
+/// slip of the frame the next CarTelemetry/CarStatus pair emits.
+///
+/// Fixtures encode: player_index=3, G lat/long/vert = 1.75/-2.5/1.02 (other
+/// cars 9.0), slip RL/RR/FL/FR = 0.08/0.12/0.02/0.04
+#[test]
+fn test_f1_25_motion_fixtures_fill_g_forces_and_slip() -> TestResult {
+    let mut state = F125State::default();
+    assert!(F1_25Adapter::process_packet(&mut state, FIXTURE_MOTION)?.is_none());
+    assert!(F1_25Adapter::process_packet(&mut state, FIXTURE_MOTION_EX)?.is_none());
+
+    let telemetry = build_car_telemetry_packet(3, 216, 7, 14500, 1.0, 0.0, 1, [23.0; 4]);
+    F1_25Adapter::process_packet(&mut state, &telemetry)?;
+    let status = build_car_status_packet(3, 42.0, 3_200_000.0, 1, 0, 16, 15100);
+    let normalized =
+        F1_25Adapter::process_packet(&mut state, &status)?.ok_or("expected a frame")?;
+
+    assert!((normalized.lateral_g - 1.75).abs() < 1e-6);
+    assert!((normalized.longitudinal_g + 2.5).abs() < 1e-6);
+    assert!((normalized.vertical_g - 1.02).abs() < 1e-6);
+
+    let tires = normalized.tires.ok_or("expected tire data")?;
+    let slip = [
+        tires.fl.slip_ratio,
+        tires.fr.slip_ratio,
+        tires.rl.slip_ratio,
+        tires.rr.slip_ratio,
+    ];
+    assert_eq!(slip, [Some(0.02), Some(0.04), Some(0.08), Some(0.12)]);
+    assert!((normalized.slip_ratio - 0.065).abs() < 1e-6);
+    Ok(())
+}
+
+/// Motion packets cut short must be rejected without panicking.
+#[test]
+fn test_f1_25_wrong_size_motion_packets_are_rejected() {
+    let adapter = F1_25Adapter::new();
+    let mut state = F125State::default();
+    for packet in [
+        &FIXTURE_MOTION[..FIXTURE_MOTION.len() - 1],
+        &FIXTURE_MOTION[..29],
+        &FIXTURE_MOTION_EX[..FIXTURE_MOTION_EX.len() - 1],
+    ] {
+        assert!(adapter.normalize(packet).is_err());
+        assert!(F1_25Adapter::process_packet(&mut state, packet).is_err());
+    }
+    assert!(state.latest_motion.is_none());
+    assert!(state.latest_motion_ex.is_none());
+}
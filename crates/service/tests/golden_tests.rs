@@ -49,6 +49,7 @@ impl TestGameConfig {
                     "track_id".to_string(),
                 ],
                 enable_high_rate_iracing_360hz: false,
+                allow_port_sharing: false,
             },
             expected_diffs: vec![ConfigDiff {
                 file_path: "Documents/iRacing/app.ini".to_string(),
@@ -57,6 +58,8 @@ impl TestGameConfig {
                 old_value: None,
                 new_value: "1".to_string(),
                 operation: DiffOperation::Add,
+                backup_path: None,
+                details: Vec::new(),
             }],
         }
     }
@@ -79,6 +82,7 @@ impl TestGameConfig {
                     "track_id".to_string(),
                 ],
                 enable_high_rate_iracing_360hz: false,
+                allow_port_sharing: false,
             },
             expected_diffs: vec![ConfigDiff {
                 file_path: "Documents/Assetto Corsa Competizione/Config/broadcasting.json"
@@ -96,6 +100,8 @@ impl TestGameConfig {
                     "updateRateHz": 100
                 }))),
                 operation: DiffOperation::Add,
+                backup_path: None,
+                details: Vec::new(),
             }],
         }
     }
@@ -119,6 +125,7 @@ fn matrix_config_from_support(game_id: &str, support: &GameSupport) -> Telemetry
             }),
         fields: support.versions[0].supported_fields.clone(),
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     }
 }
 
@@ -225,6 +232,7 @@ async fn test_dirt5_config_writer_golden() {
             "gear".to_string(),
         ],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
 
     let expected_diffs = must(writer.get_expected_diffs(&config));
@@ -257,6 +265,7 @@ async fn test_f1_25_config_writer_golden() {
             "flags".to_string(),
         ],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
 
     let expected_diffs = must(writer.get_expected_diffs(&config));
@@ -302,6 +311,7 @@ async fn test_f1_config_writer_golden() {
             "flags".to_string(),
         ],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
 
     let expected_diffs = must(writer.get_expected_diffs(&config));
@@ -484,6 +494,7 @@ async fn test_configuration_diff_generation() {
         output_target: "127.0.0.1:12345".to_string(),
         fields: vec!["ffb_scalar".to_string(), "rpm".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
 
     let iracing_diffs = must(service.get_expected_diffs("iracing", &iracing_config).await);
@@ -500,6 +511,7 @@ async fn test_configuration_diff_generation() {
         output_target: "127.0.0.1:9000".to_string(),
         fields: vec!["ffb_scalar".to_string(), "rpm".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
 
     let acc_diffs = must(service.get_expected_diffs("acc", &acc_config).await);
@@ -571,6 +583,7 @@ async fn test_unsupported_game_handling() {
                 output_target: "test".to_string(),
                 fields: vec![],
                 enable_high_rate_iracing_360hz: false,
+                allow_port_sharing: false,
             },
         )
         .await;
@@ -16,6 +16,9 @@
 //! see it as a known (pending) title. `normalize` always returns
 //! [`NormalizedTelemetry::default()`] and `start_monitoring` emits no frames.
 //!
+//! Once a layout is published it belongs in [`crate::ac_layout`] next to the
+//! AC and ACC declarations.
+//!
 //! See friction log entry F-022.
 
 use crate::{NormalizedTelemetry, TelemetryAdapter, TelemetryReceiver};
@@ -0,0 +1,354 @@
+//! Single-source byte layouts for the Assetto Corsa family.
+//!
+//! Every historical AC/ACC decoding bug has been a field offset or size that
+//! drifted after a game update. Each page is therefore declared once with
+//! `ac_layout!`, which generates:
+//!
+//! - a borrowed view type with one little-endian accessor per field,
+//! - an associated `usize` constant per field offset, for fixture builders,
+//! - [`FieldLayout`] / [`LayoutVersion`] tables and a Markdown rendering of them,
+//! - compile-time assertions that fields do not overlap, that every field fits
+//!   inside the documented size of each version it exists in, and that page
+//!   sizes never shrink between versions.
+//!
+//! Adapters keep their own `normalize` mapping and only swap raw offset reads
+//! for the generated accessors.
+
+/// One declared field of a layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldLayout {
+    /// Field name as it appears in the accessor.
+    pub name: &'static str,
+    /// Rust type the field is decoded as.
+    pub ty: &'static str,
+    /// Byte offset from the start of the page.
+    pub offset: usize,
+    /// Encoded size in bytes.
+    pub size: usize,
+    /// Label of the first [`LayoutVersion`] containing the field.
+    pub since: &'static str,
+}
+
+/// A documented revision of a page and its total size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayoutVersion {
+    /// Game / protocol version label.
+    pub label: &'static str,
+    /// Page size in bytes for this version.
+    pub size: usize,
+}
+
+/// Fixed-size little-endian value readable from a layout.
+pub use openracing_byte_reader::LeValue as LayoutValue;
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Validate a declared layout; called from a `const` item so failures stop the build.
+pub const fn check_layout(fields: &[FieldLayout], versions: &[LayoutVersion]) {
+    assert!(!versions.is_empty(), "layout declares no versions");
+    let mut v = 1;
+    while v < versions.len() {
+        assert!(
+            versions[v].size >= versions[v - 1].size,
+            "layout page size shrinks between versions"
+        );
+        v += 1;
+    }
+
+    let mut f = 0;
+    while f < fields.len() {
+        let field = fields[f];
+        if f > 0 {
+            let previous = fields[f - 1];
+            assert!(
+                previous.offset + previous.size <= field.offset,
+                "layout fields overlap or are not in offset order"
+            );
+        }
+
+        let mut since = 0;
+        while since < versions.len() && !str_eq(versions[since].label, field.since) {
+            since += 1;
+        }
+        assert!(
+            since < versions.len(),
+            "field `since` names an undeclared version"
+        );
+
+        let mut v = since;
+        while v < versions.len() {
+            assert!(
+                field.offset + field.size <= versions[v].size,
+                "field extends past the page size of a version it exists in"
+            );
+            v += 1;
+        }
+        f += 1;
+    }
+}
+
+/// Render a layout as a Markdown table: offset, size, type, field, since.
+pub fn markdown_table(fields: &[FieldLayout]) -> String {
+    let mut table =
+        String::from("| Offset | Size | Type | Field | Since |\n|---:|---:|---|---|---|\n");
+    for field in fields {
+        table.push_str(&format!(
+            "| {} | {} | `{}` | `{}` | {} |\n",
+            field.offset, field.size, field.ty, field.name, field.since
+        ));
+    }
+    table
+}
+
+/// Declare a page layout and generate its view type, accessors and checks.
+///
+/// ```text
+/// ac_layout! {
+///     /// Docs for the view type.
+///     pub struct Page {
+///         versions { "1.0" => 16, "1.1" => 20 }
+///         /// Docs for the accessor.
+///         [0] speed, SPEED: f32, since "1.0";
+///         [16] gear, GEAR: i32, since "1.1";
+///     }
+/// }
+/// ```
+macro_rules! ac_layout {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident {
+            versions { $($label:literal => $size:expr),+ $(,)? }
+            $(
+                $(#[$field_meta:meta])*
+                [$offset:expr] $field:ident, $offset_const:ident: $ty:ty, since $since:literal;
+            )+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy)]
+        pub struct $name<'a> {
+            data: &'a [u8],
+        }
+
+        impl<'a> $name<'a> {
+            /// Documented revisions of this page, oldest first.
+            pub const VERSIONS: &'static [$crate::ac_layout::LayoutVersion] = &[
+                $($crate::ac_layout::LayoutVersion { label: $label, size: $size }),+
+            ];
+
+            /// Declared fields in offset order.
+            pub const FIELDS: &'static [$crate::ac_layout::FieldLayout] = &[
+                $($crate::ac_layout::FieldLayout {
+                    name: stringify!($field),
+                    ty: stringify!($ty),
+                    offset: $offset,
+                    size: <$ty as $crate::ac_layout::LayoutValue>::SIZE,
+                    since: $since,
+                }),+
+            ];
+
+            /// Smallest page size accepted by [`Self::new`] (the oldest version).
+            pub const MIN_SIZE: usize = Self::VERSIONS[0].size;
+
+            $(
+                #[doc = concat!("Byte offset of [`Self::", stringify!($field), "`].")]
+                pub const $offset_const: usize = $offset;
+            )+
+
+            /// Borrow `data` as this page; `None` when shorter than [`Self::MIN_SIZE`].
+            pub fn new(data: &'a [u8]) -> Option<Self> {
+                (data.len() >= Self::MIN_SIZE).then_some(Self { data })
+            }
+
+            /// Raw bytes backing this view.
+            pub fn bytes(&self) -> &'a [u8] {
+                self.data
+            }
+
+            /// Markdown table of the declared fields.
+            pub fn layout_table() -> String {
+                $crate::ac_layout::markdown_table(Self::FIELDS)
+            }
+
+            $(
+                $(#[$field_meta])*
+                #[doc = ""]
+                #[doc = concat!("`", stringify!($ty), "` at byte ", stringify!($offset), ", since ", $since, ".")]
+                pub fn $field(&self) -> $ty {
+                    // Only fails for fields added after the page's version.
+                    ::openracing_byte_reader::ByteReader::new(self.data)
+                        .read_at::<$ty>($offset)
+                        .unwrap_or_default()
+                }
+            )+
+        }
+
+        const _: () = $crate::ac_layout::check_layout($name::FIELDS, $name::VERSIONS);
+    };
+}
+
+ac_layout! {
+    /// Assetto Corsa Remote Telemetry `RTCarInfo` update packet.
+    ///
+    /// Reference: <https://github.com/vpicon/acudp/blob/master/UDP.md>
+    pub struct RtCarInfo {
+        versions { "1.x" => 328 }
+        /// Packet identifier (`'a'`).
+        [0] identifier, IDENTIFIER: i32, since "1.x";
+        /// Declared packet size.
+        [4] size, PACKET_SIZE: i32, since "1.x";
+        /// Speed in km/h.
+        [8] speed_kmh, SPEED_KMH: f32, since "1.x";
+        /// Speed in mph.
+        [12] speed_mph, SPEED_MPH: f32, since "1.x";
+        /// Speed in m/s.
+        [16] speed_ms, SPEED_MS: f32, since "1.x";
+        /// ABS enabled (bool).
+        [20] is_abs_enabled, ABS_ENABLED: u8, since "1.x";
+        /// ABS currently intervening (bool).
+        [21] is_abs_in_action, ABS_IN_ACTION: u8, since "1.x";
+        /// Traction control currently intervening (bool).
+        [22] is_tc_in_action, TC_IN_ACTION: u8, since "1.x";
+        /// Traction control enabled (bool).
+        [23] is_tc_enabled, TC_ENABLED: u8, since "1.x";
+        /// Car is in the pit lane (bool).
+        [24] is_in_pit, IN_PIT: u8, since "1.x";
+        /// Engine limiter active (bool).
+        [25] is_engine_limiter_on, ENGINE_LIMITER: u8, since "1.x";
+        /// Vertical acceleration in g.
+        [28] accg_vertical, ACCG_VERTICAL: f32, since "1.x";
+        /// Lateral acceleration in g.
+        [32] accg_horizontal, ACCG_HORIZONTAL: f32, since "1.x";
+        /// Longitudinal acceleration in g.
+        [36] accg_frontal, ACCG_FRONTAL: f32, since "1.x";
+        /// Current lap time in milliseconds.
+        [40] lap_time_ms, LAP_TIME: i32, since "1.x";
+        /// Last lap time in milliseconds.
+        [44] last_lap_ms, LAST_LAP: i32, since "1.x";
+        /// Best lap time in milliseconds.
+        [48] best_lap_ms, BEST_LAP: i32, since "1.x";
+        /// Completed laps.
+        [52] lap_count, LAP_COUNT: i32, since "1.x";
+        /// Throttle input, 0..1.
+        [56] gas, GAS: f32, since "1.x";
+        /// Brake input, 0..1.
+        [60] brake, BRAKE: f32, since "1.x";
+        /// Clutch input, 0..1.
+        [64] clutch, CLUTCH: f32, since "1.x";
+        /// Engine speed in RPM.
+        [68] engine_rpm, RPM: f32, since "1.x";
+        /// Steering input, -1..1.
+        [72] steer, STEER: f32, since "1.x";
+        /// Gear (0 = R, 1 = N, 2 = 1st, ...).
+        [76] gear, GEAR: i32, since "1.x";
+        /// Centre-of-gravity height.
+        [80] cg_height, CG_HEIGHT: f32, since "1.x";
+        /// Wheel angular speed per wheel (FL, FR, RL, RR).
+        [84] wheel_angular_speed, WHEEL_ANGULAR_SPEED: [f32; 4], since "1.x";
+        /// Slip angle per wheel (FL, FR, RL, RR).
+        [100] slip_angle, SLIP_ANGLE: [f32; 4], since "1.x";
+        /// Slip angle contact patch per wheel.
+        [116] slip_angle_contact_patch, SLIP_ANGLE_CONTACT_PATCH: [f32; 4], since "1.x";
+        /// Slip ratio per wheel (FL, FR, RL, RR).
+        [132] slip_ratio, SLIP_RATIO: [f32; 4], since "1.x";
+        /// Combined tyre slip per wheel.
+        [148] tyre_slip, TYRE_SLIP: [f32; 4], since "1.x";
+        /// Non-dimensional slip per wheel.
+        [164] nd_slip, ND_SLIP: [f32; 4], since "1.x";
+        /// Tyre load per wheel in newtons.
+        [180] load, LOAD: [f32; 4], since "1.x";
+        /// Lateral friction coefficient per wheel.
+        [196] dy, DY: [f32; 4], since "1.x";
+        /// Self-aligning torque per wheel.
+        [212] mz, MZ: [f32; 4], since "1.x";
+        /// Tyre dirt level per wheel.
+        [228] tyre_dirty_level, TYRE_DIRTY_LEVEL: [f32; 4], since "1.x";
+        /// Camber per wheel in radians.
+        [244] camber_rad, CAMBER_RAD: [f32; 4], since "1.x";
+        /// Unloaded tyre radius per wheel.
+        [260] tyre_radius, TYRE_RADIUS: [f32; 4], since "1.x";
+        /// Loaded tyre radius per wheel.
+        [276] tyre_loaded_radius, TYRE_LOADED_RADIUS: [f32; 4], since "1.x";
+        /// Suspension height per wheel.
+        [292] suspension_height, SUSPENSION_HEIGHT: [f32; 4], since "1.x";
+        /// Normalized lap position, 0..1.
+        [308] car_position_normalized, CAR_POSITION_NORMALIZED: f32, since "1.x";
+        /// Car pitch slope.
+        [312] car_slope, CAR_SLOPE: f32, since "1.x";
+        /// World coordinates (x, y, z).
+        [316] car_coordinates, CAR_COORDINATES: [f32; 3], since "1.x";
+    }
+}
+
+ac_layout! {
+    /// Prefix of the ACC `SPageFileGraphic` shared-memory page up to the penalty fields.
+    ///
+    /// Only the bytes through `penalty` are mapped, so the declared size is the
+    /// mapped prefix rather than the full page.
+    pub struct AccGraphicsPrefix {
+        versions { "1.8" => 1232 }
+        /// Remaining penalty time in seconds.
+        [1220] penalty_time_s, PENALTY_TIME: f32, since "1.8";
+        /// Current flag (`ACC_FLAG_TYPE`).
+        [1224] flag, FLAG: i32, since "1.8";
+        /// Most recent penalty (`ACC_PENALTY_TYPE`).
+        [1228] penalty, PENALTY: i32, since "1.8";
+    }
+}
+
+ac_layout! {
+    /// Prefix of the ACC `SPageFilePhysics` shared-memory page up to the tyre temperatures.
+    ///
+    /// Wheel arrays are ordered FL, FR, RL, RR. `tyreWear` exists in the page
+    /// but ACC leaves it unused, so it is not mapped.
+    pub struct AccPhysicsTyres {
+        versions { "1.8" => 712 }
+        /// Tyre inflation pressure per wheel in psi.
+        [88] wheels_pressure_psi, WHEELS_PRESSURE: [f32; 4], since "1.8";
+        /// Longitudinal slip ratio per wheel.
+        [640] slip_ratio, SLIP_RATIO: [f32; 4], since "1.8";
+        /// Tyre temperature per wheel in °C.
+        [696] tyre_temp_c, TYRE_TEMP: [f32; 4], since "1.8";
+    }
+}
+
+ac_layout! {
+    /// Prefix of the ACC `SPageFilePhysics` shared-memory page up to the G-forces.
+    ///
+    /// `packetId` advances on every physics step, so a value that stops moving
+    /// means the page is stale.
+    pub struct AccPhysicsInputs {
+        versions { "1.8" => 56 }
+        /// Physics step counter.
+        [0] packet_id, PACKET_ID: i32, since "1.8";
+        /// Throttle input, 0..1.
+        [4] gas, GAS: f32, since "1.8";
+        /// Brake input, 0..1.
+        [8] brake, BRAKE: f32, since "1.8";
+        /// Fuel in the tank in litres.
+        [12] fuel_l, FUEL: f32, since "1.8";
+        /// Gear (0 = R, 1 = N, 2 = 1st, ...).
+        [16] gear, GEAR: i32, since "1.8";
+        /// Engine speed in RPM.
+        [20] rpms, RPMS: i32, since "1.8";
+        /// Steering input, -1..1.
+        [24] steer_angle, STEER_ANGLE: f32, since "1.8";
+        /// Speed in km/h.
+        [28] speed_kmh, SPEED_KMH: f32, since "1.8";
+        /// Acceleration in g (lateral, vertical, longitudinal).
+        [44] acc_g, ACC_G: [f32; 3], since "1.8";
+    }
+}
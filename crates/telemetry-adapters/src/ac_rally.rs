@@ -6,6 +6,7 @@
 //! 2) Run a passive UDP capture window on a configurable local bind address.
 //! 3) Emit probe diagnostics as normalized telemetry `extended` fields.
 
+use crate::raw_capture::RawCaptureSlot;
 use crate::{
     NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver, TelemetryValue,
     telemetry_now_ns,
@@ -40,6 +41,7 @@ pub struct ACRallyAdapter {
     update_rate: Duration,
     handshake_timeout: Duration,
     passive_probe_window: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for ACRallyAdapter {
@@ -75,6 +77,7 @@ impl ACRallyAdapter {
             update_rate: Duration::from_millis(16),
             handshake_timeout,
             passive_probe_window,
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -96,6 +99,10 @@ impl TelemetryAdapter for ACRallyAdapter {
         "ac_rally"
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
         let (tx, rx) = mpsc::channel(64);
         let adapter = self.clone();
@@ -164,6 +171,7 @@ impl TelemetryAdapter for ACRallyAdapter {
                 adapter.passive_bind_address,
                 adapter.passive_probe_window,
                 adapter.update_rate,
+                &adapter.raw_capture,
             )
             .await;
         });
@@ -291,6 +299,7 @@ async fn run_passive_udp_probe(
     bind_address: SocketAddr,
     probe_window: Duration,
     update_rate: Duration,
+    raw_capture: &RawCaptureSlot,
 ) {
     let socket = match TokioUdpSocket::bind(bind_address).await {
         Ok(socket) => socket,
@@ -336,6 +345,7 @@ async fn run_passive_udp_probe(
         match recv {
             Ok(Ok((len, source))) => {
                 packets_seen = packets_seen.saturating_add(1);
+                raw_capture.record(&buf[..len], Some(source));
                 let telemetry = match normalize_probe_packet(&buf[..len]) {
                     Ok(base) => base,
                     Err(error) => {
@@ -17,7 +17,10 @@
 //!
 //! ### Broadcasting protocol (UDP, port 9000) — used by this adapter
 //!
-//! - **Transport**: UDP broadcasting protocol on port 9000 (not shared memory). ✓
+//! - **Transport**: UDP broadcasting protocol on port 9000. ✓
+//! - **Settings**: port, connection and command passwords come from the user's
+//!   `broadcasting.json` (UTF-16LE as ACC saves it) when it exists; empty
+//!   passwords otherwise. ✓
 //! - **Protocol version**: 4 (current as of ACC 1.9+). ✓
 //! - **Message types**: 1=RegistrationResult, 2=RealtimeUpdate,
 //!   3=RealtimeCarUpdate, 4=EntryList, 5=TrackData, 6=EntryListCar,
@@ -25,17 +28,31 @@
 //! - **Registration packet**: cmd(u8=1), protocol(u8=4), displayName(string),
 //!   connectionPassword(string), updateInterval(i32), commandPassword(string). ✓
 //! - **Gear encoding**: wire 0=R, 1=N, 2=1st; normalised via `−1` offset. ✓
+//! - **World position**: `worldPosX`/`worldPosY` (m) and `yaw` (rad) of each
+//!   car update go to the `pos_x`, `pos_y` and `yaw` extended keys. ✓
+//! - **Opponents**: every other car's latest update, named after its current
+//!   driver from EntryListCar, fills the frame's `opponents`; gaps are
+//!   estimated from laps and spline position. ✓
+//! - **Unregister packet**: cmd(u8=9), connectionId(i32), sent when
+//!   monitoring stops. ✓
 //! - **Readonly flag**: byte==0 means read-only (matches Kunos C# SDK). ✓
 //! - **String encoding**: u16-LE length prefix followed by UTF-8 bytes. ✓
 //! - **LapInfo sub-struct**: lapTimeMs(i32), carIndex(u16), driverIndex(u16),
 //!   splitCount(u8), splits(i32 × N), isInvalid(u8), isValidForBest(u8),
 //!   isOutlap(u8), isInlap(u8). ✓
 //!
-//! ### ACC shared memory API (reference, not used here)
+//! ### ACC shared memory API (penalties, tyres and driving channels)
 //!
 //! ACC also exposes telemetry through Windows memory-mapped files (MMFs).
-//! These are **not** used by this adapter but are documented here for
-//! cross-reference with the broadcasting protocol fields:
+//! The broadcasting protocol carries no penalty or tyre state, so on Windows the
+//! adapter reads `penaltyTime` and `penalty` from the graphics page and the tyre
+//! pressures, slip ratios and temperatures from the physics page when they are
+//! mapped, via the [`AccGraphicsPrefix`] and [`AccPhysicsTyres`] layouts declared
+//! in [`crate::ac_layout`]. While the physics `packetId` keeps advancing, its
+//! speed, gear, rpm, pedals and G-forces ([`AccPhysicsInputs`]) take precedence
+//! over the broadcast and the frame's `transport` extended field reads
+//! `shared_memory` instead of `udp_broadcast`. The remaining pages are
+//! documented for cross-reference with the broadcasting protocol fields:
 //!
 //! | MMF name                    | Struct            | Key fields (version) |
 //! |-----------------------------|-------------------|----------------------|
@@ -48,20 +65,29 @@
 //! when fields were appended; older versions zero-fill beyond their known
 //! size.
 
+use crate::ac_layout::{AccGraphicsPrefix, AccPhysicsInputs, AccPhysicsTyres};
+use crate::raw_capture::RawCaptureSlot;
 use crate::{
-    NormalizedTelemetry, TelemetryAdapter, TelemetryFlags, TelemetryFrame, TelemetryReceiver,
-    TelemetryValue, telemetry_now_ns,
+    DEFAULT_MAX_OPPONENTS, ExtendedKey, FieldUnit, NormalizedTelemetry, OpponentSnapshot,
+    PenaltyKind, PenaltyState, SessionTiming, TelemetryAdapter, TelemetryFlags, TelemetryFrame,
+    TelemetryReceiver, TelemetryValue, TireCorner, TireData, Unit, UnitManifest, bound_opponents,
+    telemetry_now_ns,
 };
 use anyhow::{Context, Result, anyhow};
 use async_trait::async_trait;
+use serde_json::Value;
 use std::collections::HashMap;
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
-use std::time::Duration;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
 use tokio::net::UdpSocket as TokioUdpSocket;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
 
 const REGISTER_COMMAND_APPLICATION: u8 = 1;
+const UNREGISTER_COMMAND_APPLICATION: u8 = 9;
 const REQUEST_ENTRY_LIST: u8 = 10;
 const REQUEST_TRACK_DATA: u8 = 11;
 const PROTOCOL_VERSION: u8 = 4;
@@ -74,12 +100,52 @@ const MSG_TRACK_DATA: u8 = 5;
 const MSG_ENTRY_LIST_CAR: u8 = 6;
 const MSG_BROADCASTING_EVENT: u8 = 7;
 
+/// Bytes of `SPageFileGraphic` needed to decode the penalty fields.
+pub const GRAPHICS_PENALTY_PAGE_LEN: usize = AccGraphicsPrefix::MIN_SIZE;
+
+/// Bytes of `SPageFilePhysics` needed to decode the tyre fields.
+pub const PHYSICS_TYRE_PAGE_LEN: usize = AccPhysicsTyres::MIN_SIZE;
+
+const PSI_TO_KPA: f32 = 6.894_757;
+
 /// Verified: Kunos ACC Broadcasting SDK v4 default port.
 const DEFAULT_ACC_PORT: u16 = 9000;
 const MAX_PACKET_SIZE: usize = 4096;
+/// How long `stop_monitoring` waits for the unregister to go out.
+const STOP_TIMEOUT: Duration = Duration::from_secs(1);
+/// A physics `packetId` unchanged for this long means the page is stale.
+const PHYSICS_STALE_AFTER: Duration = Duration::from_millis(500);
+
+/// Path of `broadcasting.json` below the user's profile directory.
+const BROADCASTING_JSON_RELATIVE_PATH: &str =
+    "Documents/Assetto Corsa Competizione/Config/broadcasting.json";
+
+/// Value of the `transport` extended field for frames built from the
+/// shared-memory physics page.
+pub const TRANSPORT_SHARED_MEMORY: &str = "shared_memory";
+/// Value of the `transport` extended field for broadcast-only frames.
+pub const TRANSPORT_UDP_BROADCAST: &str = "udp_broadcast";
 
 /// ACC telemetry adapter using UDP broadcast protocol.
+///
+/// Frames come from the broadcasting protocol's realtime car updates. While
+/// the shared-memory physics page is mapped and advancing, its speed, gear,
+/// engine and input channels replace the broadcast ones and frames keep
+/// flowing between broadcasts; the `transport` extended field says which
+/// source a frame's driving channels came from.
 pub struct ACCAdapter {
+    registration: AccRegistration,
+    /// `broadcasting.json` to read passwords and port from when monitoring
+    /// starts; `None` once the settings were given explicitly.
+    broadcasting_json: Option<PathBuf>,
+    max_opponents: usize,
+    monitor: Mutex<Option<MonitorHandle>>,
+    raw_capture: RawCaptureSlot,
+}
+
+/// Settings of the REGISTER_COMMAND_APPLICATION handshake.
+#[derive(Debug, Clone, PartialEq)]
+struct AccRegistration {
     server_address: SocketAddr,
     update_rate: Duration,
     display_name: String,
@@ -87,6 +153,118 @@ pub struct ACCAdapter {
     command_password: String,
 }
 
+impl AccRegistration {
+    fn apply(&mut self, config: &AccBroadcastingConfig) {
+        if let Some(port) = config.listener_port {
+            self.server_address.set_port(port);
+        }
+        self.connection_password = config.connection_password.clone();
+        self.command_password = config.command_password.clone();
+        if let Some(hz) = config.update_rate_hz {
+            self.update_rate = Duration::from_secs_f64(1.0 / f64::from(hz));
+        }
+    }
+
+    fn register_packet(&self) -> Result<Vec<u8>> {
+        build_register_packet(
+            &self.display_name,
+            &self.connection_password,
+            duration_to_interval_ms(self.update_rate),
+            &self.command_password,
+        )
+    }
+}
+
+/// A running monitoring task and the signal that stops it.
+struct MonitorHandle {
+    stop: oneshot::Sender<()>,
+    task: JoinHandle<()>,
+}
+
+/// Connection settings ACC reads from `broadcasting.json`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccBroadcastingConfig {
+    /// `updListenerPort`, the port ACC listens on; `None` when unset or 0,
+    /// which leaves broadcasting disabled in the game.
+    pub listener_port: Option<u16>,
+    pub connection_password: String,
+    pub command_password: String,
+    /// `updateRateHz`, as OpenRacing's config writer adds it.
+    pub update_rate_hz: Option<u32>,
+}
+
+impl AccBroadcastingConfig {
+    /// Parse the file contents. ACC saves the file as UTF-16LE with a byte
+    /// order mark; UTF-8, as the config writer writes it, is accepted too.
+    pub fn parse(raw: &[u8]) -> Result<Self> {
+        let text = decode_broadcasting_json(raw)?;
+        let value: Value =
+            serde_json::from_str(&text).context("broadcasting.json is not valid JSON")?;
+        let object = value
+            .as_object()
+            .ok_or_else(|| anyhow!("broadcasting.json is not a JSON object"))?;
+        let string = |key: &str| {
+            object
+                .get(key)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        Ok(Self {
+            listener_port: object
+                .get("updListenerPort")
+                .or_else(|| object.get("udpListenerPort"))
+                .and_then(Value::as_u64)
+                .and_then(|port| u16::try_from(port).ok())
+                .filter(|port| *port != 0),
+            connection_password: string("connectionPassword"),
+            command_password: string("commandPassword"),
+            update_rate_hz: object
+                .get("updateRateHz")
+                .and_then(Value::as_u64)
+                .and_then(|hz| u32::try_from(hz).ok())
+                .filter(|hz| *hz > 0),
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw =
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&raw)
+    }
+
+    /// Where ACC keeps the file for the current user.
+    pub fn default_path() -> PathBuf {
+        #[cfg(windows)]
+        {
+            if let Ok(user_profile) = std::env::var("USERPROFILE") {
+                return PathBuf::from(user_profile).join(BROADCASTING_JSON_RELATIVE_PATH);
+            }
+        }
+
+        if let Ok(home) = std::env::var("HOME") {
+            return PathBuf::from(home).join(BROADCASTING_JSON_RELATIVE_PATH);
+        }
+
+        PathBuf::from(BROADCASTING_JSON_RELATIVE_PATH)
+    }
+}
+
+fn decode_broadcasting_json(raw: &[u8]) -> Result<String> {
+    if let Some(utf16) = raw.strip_prefix(&[0xFF, 0xFE]) {
+        if utf16.len() % 2 != 0 {
+            return Err(anyhow!("broadcasting.json has an odd UTF-16 byte count"));
+        }
+        let units: Vec<u16> = utf16
+            .chunks_exact(2)
+            .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+            .collect();
+        return String::from_utf16(&units).context("broadcasting.json is not valid UTF-16");
+    }
+    let utf8 = raw.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(raw);
+    String::from_utf8(utf8.to_vec()).context("broadcasting.json is not valid UTF-8")
+}
+
 impl Default for ACCAdapter {
     fn default() -> Self {
         Self::new()
@@ -94,46 +272,78 @@ impl Default for ACCAdapter {
 }
 
 impl ACCAdapter {
-    /// Create a new ACC adapter.
+    /// Create a new ACC adapter. Port and passwords are read from the
+    /// user's `broadcasting.json` when monitoring starts, falling back to
+    /// port 9000 and empty passwords.
     pub fn new() -> Self {
         Self {
-            server_address: SocketAddr::V4(SocketAddrV4::new(
-                Ipv4Addr::LOCALHOST,
-                DEFAULT_ACC_PORT,
-            )),
-            update_rate: Duration::from_millis(16),
-            display_name: "OpenRacing".to_string(),
-            connection_password: String::new(),
-            command_password: String::new(),
+            registration: AccRegistration {
+                server_address: SocketAddr::V4(SocketAddrV4::new(
+                    Ipv4Addr::LOCALHOST,
+                    DEFAULT_ACC_PORT,
+                )),
+                update_rate: Duration::from_millis(16),
+                display_name: "OpenRacing".to_string(),
+                connection_password: String::new(),
+                command_password: String::new(),
+            },
+            broadcasting_json: Some(AccBroadcastingConfig::default_path()),
+            max_opponents: DEFAULT_MAX_OPPONENTS,
+            monitor: Mutex::new(None),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
-    /// Create ACC adapter with custom ACC broadcasting endpoint.
+    /// Create ACC adapter with custom ACC broadcasting endpoint and empty
+    /// passwords.
     pub fn with_address(server_address: SocketAddr) -> Self {
-        Self {
-            server_address,
-            ..Self::new()
+        let mut adapter = Self::new();
+        adapter.registration.server_address = server_address;
+        adapter.broadcasting_json = None;
+        adapter
+    }
+
+    /// Create ACC adapter with the port, passwords and update rate of an
+    /// already loaded `broadcasting.json`.
+    pub fn from_broadcasting_config(config: &AccBroadcastingConfig) -> Self {
+        let mut adapter = Self::new();
+        adapter.registration.apply(config);
+        adapter.broadcasting_json = None;
+        adapter
+    }
+
+    /// Keep at most `max` other cars on each frame.
+    pub fn with_max_opponents(mut self, max: usize) -> Self {
+        self.max_opponents = max;
+        self
+    }
+
+    /// Registration settings, with `broadcasting.json` applied if it loads.
+    fn resolve_registration(&self) -> AccRegistration {
+        let mut registration = self.registration.clone();
+        if let Some(path) = &self.broadcasting_json {
+            match AccBroadcastingConfig::load(path) {
+                Ok(config) => registration.apply(&config),
+                Err(err) => debug!(error = %err, "Using default ACC broadcasting settings"),
+            }
         }
+        registration
     }
 
     /// Check if ACC is running by attempting a registration handshake.
     async fn check_acc_running(&self) -> bool {
+        let registration = self.resolve_registration();
         let bind_address = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0));
         let socket = match TokioUdpSocket::bind(bind_address).await {
             Ok(socket) => socket,
             Err(_) => return false,
         };
 
-        if socket.connect(self.server_address).await.is_err() {
+        if socket.connect(registration.server_address).await.is_err() {
             return false;
         }
 
-        let register_packet = match build_register_packet(
-            &self.display_name,
-            &self.connection_password,
-            duration_to_interval_ms(self.update_rate),
-            &self.command_password,
-        ) {
+        let register_packet = match registration.register_packet() {
             Ok(packet) => packet,
             Err(_) => return false,
         };
@@ -158,22 +368,48 @@ impl ACCAdapter {
     }
 }
 
+/// ACC broadcasting units; speed arrives in km/h and lap times in milliseconds.
+const UNIT_MANIFEST: UnitManifest = UnitManifest::new(&[
+    FieldUnit::converted("speed_ms", Unit::KilometersPerHour, Unit::MetersPerSecond),
+    FieldUnit::converted("current_lap_time_s", Unit::Milliseconds, Unit::Seconds),
+    FieldUnit::converted("best_lap_time_s", Unit::Milliseconds, Unit::Seconds),
+    FieldUnit::converted("last_lap_time_s", Unit::Milliseconds, Unit::Seconds),
+    FieldUnit::native("spline_position", Unit::Fraction),
+    FieldUnit::native("rpm", Unit::Rpm),
+    FieldUnit::native("throttle", Unit::Fraction),
+    FieldUnit::native("brake", Unit::Fraction),
+    FieldUnit::native("lateral_g", Unit::StandardGravity),
+    FieldUnit::native("longitudinal_g", Unit::StandardGravity),
+    FieldUnit::native(ExtendedKey::POS_X.name, Unit::Meters),
+    FieldUnit::native(ExtendedKey::POS_Y.name, Unit::Meters),
+    FieldUnit::native(ExtendedKey::YAW.name, Unit::Radians),
+]);
+
 #[async_trait]
 impl TelemetryAdapter for ACCAdapter {
     fn game_id(&self) -> &str {
         "acc"
     }
 
+    fn unit_manifest(&self) -> Option<UnitManifest> {
+        Some(UNIT_MANIFEST)
+    }
+
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(100);
+        let (stop_tx, mut stop_rx) = oneshot::channel::<()>();
 
-        let server_address = self.server_address;
-        let update_rate = self.update_rate;
-        let display_name = self.display_name.clone();
-        let connection_password = self.connection_password.clone();
-        let command_password = self.command_password.clone();
+        let registration = self.resolve_registration();
+        let server_address = registration.server_address;
+        let update_rate = registration.update_rate;
+        let max_opponents = self.max_opponents;
 
-        tokio::spawn(async move {
+        let task = tokio::spawn(async move {
             let bind_address = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0));
             let socket = match TokioUdpSocket::bind(bind_address).await {
                 Ok(socket) => socket,
@@ -188,12 +424,7 @@ impl TelemetryAdapter for ACCAdapter {
                 return;
             }
 
-            let register_packet = match build_register_packet(
-                &display_name,
-                &connection_password,
-                duration_to_interval_ms(update_rate),
-                &command_password,
-            ) {
+            let register_packet = match registration.register_packet() {
                 Ok(packet) => packet,
                 Err(e) => {
                     error!(error = %e, "Failed to encode ACC registration packet");
@@ -212,17 +443,50 @@ impl TelemetryAdapter for ACCAdapter {
             );
 
             let mut frame_seq = 0u64;
+            let mut connection_id = None;
             let mut state = ACCSessionState::default();
             let mut buf = [0u8; MAX_PACKET_SIZE];
+            #[cfg(windows)]
+            let mut graphics: Option<SharedMemoryPage<GRAPHICS_PENALTY_PAGE_LEN>> = None;
+            #[cfg(windows)]
+            let mut physics: Option<SharedMemoryPage<PHYSICS_TYRE_PAGE_LEN>> = None;
 
             loop {
-                match tokio::time::timeout(update_rate * 2, socket.recv(&mut buf)).await {
+                #[cfg(windows)]
+                {
+                    if graphics.is_none() {
+                        graphics = SharedMemoryPage::open("Local\\acpmf_graphics");
+                    }
+                    state.penalties = graphics
+                        .as_ref()
+                        .and_then(|page| parse_graphics_penalties(&page.read()).ok());
+                    if physics.is_none() {
+                        physics = SharedMemoryPage::open("Local\\acpmf_physics");
+                    }
+                    let page = physics.as_ref().map(|page| page.read());
+                    state.tires = page
+                        .as_ref()
+                        .and_then(|page| parse_physics_tires(page).ok());
+                    state.update_physics(
+                        page.as_ref()
+                            .and_then(|page| parse_physics_inputs(page).ok()),
+                        Instant::now(),
+                    );
+                }
+
+                let received = tokio::select! {
+                    _ = &mut stop_rx => break,
+                    received = tokio::time::timeout(update_rate * 2, raw_capture.recv(&socket, &mut buf)) => received,
+                };
+
+                match received {
                     Ok(Ok(len)) => {
                         let packet_data = &buf[..len];
                         match parse_inbound_message(packet_data) {
                             Ok(message) => {
                                 if let ACCInboundMessage::RegistrationResult(result) = &message {
                                     if result.success {
+                                        connection_id = Some(result.connection_id);
                                         info!(
                                             connection_id = result.connection_id,
                                             readonly = result.readonly,
@@ -254,7 +518,8 @@ impl TelemetryAdapter for ACCAdapter {
                                         telemetry_now_ns(),
                                         frame_seq,
                                         len,
-                                    );
+                                    )
+                                    .with_opponents(state.opponents(max_opponents));
 
                                     if tx.send(frame).await.is_err() {
                                         debug!(
@@ -275,19 +540,66 @@ impl TelemetryAdapter for ACCAdapter {
                         warn!(error = %e, "ACC UDP receive error");
                     }
                     Err(_) => {
-                        debug!("No ACC telemetry data received (timeout)");
+                        // Only the physics page is moving, e.g. ACC has
+                        // throttled the broadcast while the car is on track.
+                        let Some(normalized) = state.normalize_from_physics() else {
+                            debug!("No ACC telemetry data received (timeout)");
+                            continue;
+                        };
+                        let frame =
+                            TelemetryFrame::new(normalized, telemetry_now_ns(), frame_seq, 0)
+                                .with_opponents(state.opponents(max_opponents));
+                        if tx.send(frame).await.is_err() {
+                            debug!("Telemetry receiver dropped, stopping ACC monitoring");
+                            break;
+                        }
+                        frame_seq = frame_seq.saturating_add(1);
                     }
                 }
             }
 
+            if let Some(connection_id) = connection_id
+                && let Err(e) = socket.send(&build_unregister_packet(connection_id)).await
+            {
+                debug!(error = %e, "Failed to unregister from ACC broadcasting");
+            }
+
             info!("Stopped ACC telemetry monitoring");
         });
 
+        let previous = self
+            .monitor
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .replace(MonitorHandle {
+                stop: stop_tx,
+                task,
+            });
+        if let Some(previous) = previous {
+            // Ignored: the task may already have ended on its own.
+            let _ = previous.stop.send(());
+        }
+
         Ok(rx)
     }
 
     async fn stop_monitoring(&self) -> Result<()> {
-        // Monitoring task will stop when receiver is dropped.
+        let monitor = self
+            .monitor
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        let Some(monitor) = monitor else {
+            return Ok(());
+        };
+        // Ignored: the task may already have ended on its own.
+        let _ = monitor.stop.send(());
+        if tokio::time::timeout(STOP_TIMEOUT, monitor.task)
+            .await
+            .is_err()
+        {
+            warn!("ACC monitoring task did not stop in time");
+        }
         Ok(())
     }
 
@@ -300,7 +612,7 @@ impl TelemetryAdapter for ACCAdapter {
     }
 
     fn expected_update_rate(&self) -> Duration {
-        self.update_rate
+        self.registration.update_rate
     }
 
     async fn is_game_running(&self) -> Result<bool> {
@@ -314,8 +626,8 @@ enum ACCInboundMessage {
     RealtimeUpdate(RealtimeUpdate),
     RealtimeCarUpdate(RealtimeCarUpdate),
     TrackData(TrackData),
-    EntryList,
-    EntryListCar,
+    EntryList(Vec<u16>),
+    EntryListCar(EntryListCar),
     BroadcastingEvent,
     Unknown(u8),
 }
@@ -334,6 +646,7 @@ struct RealtimeUpdate {
     session_type: u8,
     phase: u8,
     session_time_ms: f32,
+    session_end_time_ms: f32,
     ambient_temp_c: u8,
     track_temp_c: u8,
     rain_level: f32,
@@ -350,6 +663,9 @@ struct RealtimeCarUpdate {
     position: u16,
     cup_position: u16,
     track_position: u16,
+    world_pos_x: f32,
+    world_pos_y: f32,
+    yaw: f32,
     spline_position: f32,
     laps: u16,
     delta_ms: i32,
@@ -358,20 +674,98 @@ struct RealtimeCarUpdate {
     current_lap_ms: i32,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+struct EntryListCar {
+    car_index: u16,
+    /// First and last name of the car's current driver.
+    driver_name: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 struct TrackData {
     track_name: String,
 }
 
+/// Driving channels of the shared-memory physics page.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AccPhysicsChannels {
+    /// Physics step counter; stops advancing when ACC pauses or leaves the
+    /// session.
+    pub packet_id: i32,
+    pub speed_ms: f32,
+    /// Normalised gear: -1 reverse, 0 neutral, 1 first.
+    pub gear: i8,
+    pub rpm: f32,
+    pub throttle: f32,
+    pub brake: f32,
+    pub lateral_g: f32,
+    pub longitudinal_g: f32,
+}
+
+/// Decode the driving channels from a raw `SPageFilePhysics` page.
+pub fn parse_physics_inputs(page: &[u8]) -> Result<AccPhysicsChannels> {
+    let physics = AccPhysicsInputs::new(page).ok_or_else(|| {
+        anyhow!(
+            "ACC physics page too short: {} bytes (need {})",
+            page.len(),
+            AccPhysicsInputs::MIN_SIZE
+        )
+    })?;
+    let [lateral_g, _vertical_g, longitudinal_g] = physics.acc_g();
+    Ok(AccPhysicsChannels {
+        packet_id: physics.packet_id(),
+        speed_ms: physics.speed_kmh() / 3.6,
+        // Same encoding as the broadcast: 0 reverse, 1 neutral, 2 first.
+        gear: i8::try_from(physics.gear().saturating_sub(1)).unwrap_or_default(),
+        rpm: physics.rpms() as f32,
+        throttle: physics.gas(),
+        brake: physics.brake(),
+        lateral_g,
+        longitudinal_g,
+    })
+}
+
 #[derive(Debug, Default)]
 struct ACCSessionState {
     track_name: Option<String>,
     focused_car_index: Option<u16>,
     latest_realtime: Option<RealtimeUpdate>,
     latest_car_updates: HashMap<u16, RealtimeCarUpdate>,
+    driver_names: HashMap<u16, String>,
+    penalties: Option<PenaltyState>,
+    tires: Option<TireData>,
+    /// Physics channels while the page is live.
+    physics: Option<AccPhysicsChannels>,
+    physics_packet_id: Option<i32>,
+    physics_advanced_at: Option<Instant>,
 }
 
 impl ACCSessionState {
+    /// Record the latest physics read; it only counts as live while
+    /// `packetId` keeps advancing.
+    #[cfg_attr(not(windows), allow(dead_code))]
+    fn update_physics(&mut self, channels: Option<AccPhysicsChannels>, now: Instant) {
+        if let Some(channels) = channels
+            && self.physics_packet_id != Some(channels.packet_id)
+        {
+            self.physics_packet_id = Some(channels.packet_id);
+            self.physics_advanced_at = Some(now);
+        }
+        let live = self
+            .physics_advanced_at
+            .is_some_and(|at| now.saturating_duration_since(at) < PHYSICS_STALE_AFTER);
+        self.physics = channels.filter(|_| live);
+    }
+
+    /// Frame for the focused car built while only the physics page moves.
+    fn normalize_from_physics(&self) -> Option<NormalizedTelemetry> {
+        self.physics?;
+        let focused = self.focused_car_index?;
+        self.latest_car_updates
+            .get(&focused)
+            .map(|car| self.normalize_car(car))
+    }
+
     fn update_and_normalize(&mut self, message: &ACCInboundMessage) -> Option<NormalizedTelemetry> {
         match message {
             ACCInboundMessage::RealtimeUpdate(update) => {
@@ -398,10 +792,67 @@ impl ACCSessionState {
                 self.track_name = Some(track_data.track_name.clone());
                 None
             }
+            ACCInboundMessage::EntryList(car_ids) => {
+                // Cars missing from a fresh entry list have left the session.
+                self.latest_car_updates
+                    .retain(|index, _| car_ids.contains(index));
+                self.driver_names.retain(|index, _| car_ids.contains(index));
+                None
+            }
+            ACCInboundMessage::EntryListCar(car) => {
+                match &car.driver_name {
+                    Some(name) => self.driver_names.insert(car.car_index, name.clone()),
+                    None => self.driver_names.remove(&car.car_index),
+                };
+                None
+            }
             _ => None,
         }
     }
 
+    /// Every car but the focused one, sorted by position and bounded to
+    /// `max` around it.
+    ///
+    /// ACC broadcasts no gaps, so they are estimated from the difference in
+    /// laps and spline position times the focused car's best lap, or its
+    /// last lap before it has set one.
+    fn opponents(&self, max: usize) -> Vec<OpponentSnapshot> {
+        let player = self
+            .focused_car_index
+            .and_then(|index| self.latest_car_updates.get(&index));
+        let progress =
+            |car: &RealtimeCarUpdate| f64::from(car.laps) + f64::from(car.spline_position);
+        let reference_lap_ms = player.and_then(|car| {
+            lap_time_ms(car.best_session_lap_ms)
+                .or_else(|| lap_time_ms(car.last_lap_ms))
+                .filter(|ms| *ms > 0.0)
+        });
+
+        let opponents = self
+            .latest_car_updates
+            .values()
+            .filter(|car| Some(car.car_index) != self.focused_car_index)
+            .map(|car| OpponentSnapshot {
+                car_index: car.car_index,
+                position: u8::try_from(car.position).unwrap_or(u8::MAX),
+                gap_to_player_ms: player.zip(reference_lap_ms).map(|(player, lap_ms)| {
+                    ((progress(car) - progress(player)) * lap_ms)
+                        .round()
+                        .clamp(f64::from(i32::MIN), f64::from(i32::MAX)) as i32
+                }),
+                speed_ms: f32::from(car.speed_kmh) / 3.6,
+                lap_number: car.laps.saturating_add(1),
+                in_pits: matches!(car.car_location, 2..=4),
+                driver_name: self.driver_names.get(&car.car_index).cloned(),
+            })
+            .collect();
+        bound_opponents(
+            opponents,
+            player.map(|car| u8::try_from(car.position).unwrap_or(u8::MAX)),
+            max,
+        )
+    }
+
     fn normalize_car(&self, car: &RealtimeCarUpdate) -> NormalizedTelemetry {
         let mut flags = TelemetryFlags {
             in_pits: matches!(car.car_location, 2..=4),
@@ -436,6 +887,9 @@ impl ACCSessionState {
             .best_lap_time_s(best_lap_s)
             .last_lap_time_s(last_lap_s)
             .current_lap_time_s(current_lap_s)
+            // Broadcasting positions cars on the ground plane only.
+            .world_position([car.world_pos_x, car.world_pos_y, 0.0])
+            .lap_distance_fraction(car.spline_position)
             .extended(
                 "cup_position",
                 TelemetryValue::Integer(i32::from(car.cup_position)),
@@ -448,12 +902,58 @@ impl ACCSessionState {
             .extended(
                 "spline_position",
                 TelemetryValue::Float(car.spline_position),
-            );
+            )
+            .extended(
+                ExtendedKey::POS_X.name,
+                TelemetryValue::Float(car.world_pos_x),
+            )
+            .extended(
+                ExtendedKey::POS_Y.name,
+                TelemetryValue::Float(car.world_pos_y),
+            )
+            .extended(ExtendedKey::YAW.name, TelemetryValue::Float(car.yaw));
+
+        let transport = match self.physics {
+            Some(physics) => {
+                builder = builder
+                    .speed_ms(physics.speed_ms)
+                    .gear(physics.gear)
+                    .rpm(physics.rpm)
+                    .throttle(physics.throttle)
+                    .brake(physics.brake)
+                    .lateral_g(physics.lateral_g)
+                    .longitudinal_g(physics.longitudinal_g);
+                TRANSPORT_SHARED_MEMORY
+            }
+            None => TRANSPORT_UDP_BROADCAST,
+        };
+        builder = builder.extended("transport", TelemetryValue::String(transport.to_string()));
 
         if let Some(track_name) = track_id {
             builder = builder.track_id(track_name);
         }
 
+        if let Some(penalties) = self.penalties {
+            builder = builder.penalties(penalties);
+        }
+
+        if let Some(tires) = self.tires {
+            builder = builder.tires(tires);
+        }
+
+        builder = builder.timing(SessionTiming {
+            current_lap_ms: lap_time_ms(car.current_lap_ms),
+            last_lap_ms: lap_time_ms(car.last_lap_ms),
+            best_lap_ms: lap_time_ms(car.best_session_lap_ms),
+            // `laps` counts completed laps.
+            lap_number: Some(i32::from(car.laps) + 1),
+            sector: None,
+            session_time_remaining_ms: self
+                .latest_realtime
+                .as_ref()
+                .map(|realtime| f64::from(realtime.session_end_time_ms)),
+        });
+
         if let Some(realtime) = &self.latest_realtime {
             let best_session_lap_s = realtime.best_session_lap_ms.max(0) as f32 / 1000.0;
             builder = builder
@@ -482,7 +982,8 @@ impl ACCSessionState {
                     TelemetryValue::Integer(i32::from(realtime.track_temp_c)),
                 )
                 .extended("rain_level", TelemetryValue::Float(realtime.rain_level))
-                .extended("wetness", TelemetryValue::Float(realtime.wetness));
+                .extended("wetness", TelemetryValue::Float(realtime.wetness))
+                .game_time_s(f64::from(realtime.session_time_ms) / 1000.0);
         }
 
         builder.build()
@@ -511,6 +1012,14 @@ fn build_register_packet(
     Ok(buffer)
 }
 
+// Verified: Kunos SDK `Disconnect` — command(u8=9), connectionId(i32).
+fn build_unregister_packet(connection_id: i32) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(5);
+    buffer.push(UNREGISTER_COMMAND_APPLICATION);
+    buffer.extend_from_slice(&connection_id.to_le_bytes());
+    buffer
+}
+
 fn build_request_entry_list_packet(connection_id: i32) -> Vec<u8> {
     let mut buffer = Vec::with_capacity(8);
     buffer.push(REQUEST_ENTRY_LIST);
@@ -539,15 +1048,9 @@ fn parse_inbound_message(data: &[u8]) -> Result<ACCInboundMessage> {
         MSG_REALTIME_CAR_UPDATE => {
             ACCInboundMessage::RealtimeCarUpdate(parse_realtime_car_update(&mut reader)?)
         }
-        MSG_ENTRY_LIST => {
-            parse_entry_list(&mut reader)?;
-            ACCInboundMessage::EntryList
-        }
+        MSG_ENTRY_LIST => ACCInboundMessage::EntryList(parse_entry_list(&mut reader)?),
         MSG_TRACK_DATA => ACCInboundMessage::TrackData(parse_track_data(&mut reader)?),
-        MSG_ENTRY_LIST_CAR => {
-            parse_entry_list_car(&mut reader)?;
-            ACCInboundMessage::EntryListCar
-        }
+        MSG_ENTRY_LIST_CAR => ACCInboundMessage::EntryListCar(parse_entry_list_car(&mut reader)?),
         MSG_BROADCASTING_EVENT => {
             parse_broadcasting_event(&mut reader)?;
             ACCInboundMessage::BroadcastingEvent
@@ -584,7 +1087,8 @@ fn parse_realtime_update(reader: &mut PacketReader<'_>) -> Result<RealtimeUpdate
     let phase = reader.read_u8()?;
 
     let session_time_ms = reader.read_f32_le()?;
-    let _session_end_time_ms = reader.read_f32_le()?;
+    // Despite the name, counts down the time left in the session.
+    let session_end_time_ms = reader.read_f32_le()?;
 
     let focused_car_index_raw = reader.read_i32_le()?;
     let focused_car_index = u16::try_from(focused_car_index_raw).ok();
@@ -613,6 +1117,7 @@ fn parse_realtime_update(reader: &mut PacketReader<'_>) -> Result<RealtimeUpdate
         session_type,
         phase,
         session_time_ms,
+        session_end_time_ms,
         ambient_temp_c,
         track_temp_c,
         rain_level,
@@ -638,9 +1143,9 @@ fn parse_realtime_car_update(reader: &mut PacketReader<'_>) -> Result<RealtimeCa
     let gear_raw = reader.read_u8()?;
     let gear = (i16::from(gear_raw) - 1).clamp(i16::from(i8::MIN), i16::from(i8::MAX)) as i8;
 
-    let _world_pos_x = reader.read_f32_le()?;
-    let _world_pos_y = reader.read_f32_le()?;
-    let _yaw = reader.read_f32_le()?;
+    let world_pos_x = reader.read_f32_le()?;
+    let world_pos_y = reader.read_f32_le()?;
+    let yaw = reader.read_f32_le()?;
 
     let car_location = reader.read_u8()?;
     let speed_kmh = reader.read_u16_le()?;
@@ -659,6 +1164,9 @@ fn parse_realtime_car_update(reader: &mut PacketReader<'_>) -> Result<RealtimeCa
         car_index,
         gear,
         car_location,
+        world_pos_x,
+        world_pos_y,
+        yaw,
         speed_kmh,
         position,
         cup_position,
@@ -673,13 +1181,10 @@ fn parse_realtime_car_update(reader: &mut PacketReader<'_>) -> Result<RealtimeCa
 }
 
 // Verified: Kunos SDK EntryList — connectionId(i32), carCount(u16), carIds(u16 × N).
-fn parse_entry_list(reader: &mut PacketReader<'_>) -> Result<()> {
+fn parse_entry_list(reader: &mut PacketReader<'_>) -> Result<Vec<u16>> {
     let _connection_id = reader.read_i32_le()?;
     let car_count = usize::from(reader.read_u16_le()?);
-    for _ in 0..car_count {
-        let _car_id = reader.read_u16_le()?;
-    }
-    Ok(())
+    (0..car_count).map(|_| reader.read_u16_le()).collect()
 }
 
 // Verified: Kunos SDK TrackData — connectionId(i32), trackName(str), trackId(i32),
@@ -712,25 +1217,33 @@ fn parse_track_data(reader: &mut PacketReader<'_>) -> Result<TrackData> {
 // teamName(str), raceNumber(i32), cupCategory(u8), currentDriverIndex(u8),
 // nationality(u16), drivers[firstName(str), lastName(str), shortName(str),
 // category(u8), nationality(u16)]. Field order matches SDK v4.
-fn parse_entry_list_car(reader: &mut PacketReader<'_>) -> Result<()> {
-    let _car_index = reader.read_u16_le()?;
+fn parse_entry_list_car(reader: &mut PacketReader<'_>) -> Result<EntryListCar> {
+    let car_index = reader.read_u16_le()?;
     let _car_model_type = reader.read_u8()?;
     let _team_name = read_acc_string(reader)?;
     let _race_number = reader.read_i32_le()?;
     let _cup_category = reader.read_u8()?;
-    let _current_driver_index = reader.read_u8()?;
+    let current_driver_index = usize::from(reader.read_u8()?);
     let _nationality = reader.read_u16_le()?;
 
     let drivers_count = usize::from(reader.read_u8()?);
-    for _ in 0..drivers_count {
-        let _first_name = read_acc_string(reader)?;
-        let _last_name = read_acc_string(reader)?;
+    let mut driver_name = None;
+    for index in 0..drivers_count {
+        let first_name = read_acc_string(reader)?;
+        let last_name = read_acc_string(reader)?;
         let _short_name = read_acc_string(reader)?;
         let _category = reader.read_u8()?;
         let _driver_nationality = reader.read_u16_le()?;
+        if index == current_driver_index {
+            let name = format!("{first_name} {last_name}").trim().to_string();
+            driver_name = (!name.is_empty()).then_some(name);
+        }
     }
 
-    Ok(())
+    Ok(EntryListCar {
+        car_index,
+        driver_name,
+    })
 }
 
 // Verified: Kunos SDK BroadcastingEvent — type(u8), msg(str),
@@ -764,6 +1277,144 @@ fn read_lap_time_ms(reader: &mut PacketReader<'_>) -> Result<i32> {
     Ok(lap_time_ms)
 }
 
+/// A `LapInfo` time in milliseconds, or `None` for ACC's "no time" values
+/// (negative, or `i32::MAX` before a lap is set).
+fn lap_time_ms(raw_ms: i32) -> Option<f64> {
+    (0..i32::MAX).contains(&raw_ms).then(|| f64::from(raw_ms))
+}
+
+/// Map an `ACC_PENALTY_TYPE` code and `penaltyTime` onto [`PenaltyState`].
+///
+/// ACC reports only the most recent penalty, without a track-limits counter,
+/// so `track_limit_warnings` is always zero.
+pub fn penalty_state_from_acc(penalty: i32, penalty_time_s: f32) -> PenaltyState {
+    let active_penalty_kind = match penalty {
+        0 => PenaltyKind::None,
+        // DriveThrough_Cutting, DriveThrough_PitSpeeding, DriveThrough_IgnoredDriverStint
+        1 | 7 | 19 => PenaltyKind::DriveThrough,
+        // StopAndGo_{10,20,30}_Cutting, StopAndGo_{10,20,30}_PitSpeeding
+        2..=4 | 8..=10 => PenaltyKind::StopAndGo,
+        // PostRaceTime
+        14 => PenaltyKind::TimePenalty,
+        // Disqualified_* variants
+        5 | 11 | 13 | 15..=18 | 20 | 21 => PenaltyKind::Disqualified,
+        // RemoveBestLaptime_* and codes newer than this table
+        _ => PenaltyKind::Other,
+    };
+    PenaltyState {
+        active_penalty_kind,
+        penalty_time_s: if penalty_time_s.is_finite() {
+            penalty_time_s.max(0.0)
+        } else {
+            0.0
+        },
+        track_limit_warnings: 0,
+        drive_through_pending: active_penalty_kind == PenaltyKind::DriveThrough,
+        dsq: active_penalty_kind == PenaltyKind::Disqualified,
+    }
+}
+
+/// Decode the penalty fields from a raw `SPageFileGraphic` page.
+pub fn parse_graphics_penalties(page: &[u8]) -> Result<PenaltyState> {
+    let graphics = AccGraphicsPrefix::new(page).ok_or_else(|| {
+        anyhow!(
+            "ACC graphics page too short: {} bytes (need {})",
+            page.len(),
+            GRAPHICS_PENALTY_PAGE_LEN
+        )
+    })?;
+    Ok(penalty_state_from_acc(
+        graphics.penalty(),
+        graphics.penalty_time_s(),
+    ))
+}
+
+/// Decode per-wheel tyre data from a raw `SPageFilePhysics` page.
+///
+/// ACC has no surface temperature or wear channel, so only pressure, slip
+/// ratio and `tyreTemp` are filled in.
+pub fn parse_physics_tires(page: &[u8]) -> Result<TireData> {
+    let physics = AccPhysicsTyres::new(page).ok_or_else(|| {
+        anyhow!(
+            "ACC physics page too short: {} bytes (need {})",
+            page.len(),
+            PHYSICS_TYRE_PAGE_LEN
+        )
+    })?;
+    let pressures = physics.wheels_pressure_psi();
+    let slip_ratios = physics.slip_ratio();
+    let temps = physics.tyre_temp_c();
+    Ok(
+        TireData::from_corners(std::array::from_fn(|wheel| TireCorner {
+            surface_temp_c: Some(temps[wheel]),
+            pressure_kpa: Some(pressures[wheel] * PSI_TO_KPA),
+            wear_fraction: None,
+            slip_ratio: Some(slip_ratios[wheel]),
+        }))
+        .validated(),
+    )
+}
+
+/// Read-only view of the first `LEN` bytes of an ACC shared-memory page.
+#[cfg(windows)]
+struct SharedMemoryPage<const LEN: usize> {
+    handle: winapi::um::winnt::HANDLE,
+    base_ptr: *const u8,
+}
+
+// SAFETY: the mapping is read-only and only accessed from the owning task.
+#[cfg(windows)]
+unsafe impl<const LEN: usize> Send for SharedMemoryPage<LEN> {}
+
+#[cfg(windows)]
+impl<const LEN: usize> SharedMemoryPage<LEN> {
+    fn open(name: &str) -> Option<Self> {
+        use std::ffi::OsStr;
+        use std::os::windows::ffi::OsStrExt;
+        use winapi::um::handleapi::CloseHandle;
+        use winapi::um::memoryapi::{FILE_MAP_READ, MapViewOfFile, OpenFileMappingW};
+
+        let name: Vec<u16> = OsStr::new(name)
+            .encode_wide()
+            .chain(std::iter::once(0))
+            .collect();
+        // SAFETY: `name` is null-terminated and outlives the call; the handle is
+        // closed again if the view cannot be mapped.
+        unsafe {
+            let handle = OpenFileMappingW(FILE_MAP_READ, 0, name.as_ptr());
+            if handle.is_null() {
+                return None;
+            }
+            let base_ptr = MapViewOfFile(handle, FILE_MAP_READ, 0, 0, LEN) as *const u8;
+            if base_ptr.is_null() {
+                CloseHandle(handle);
+                return None;
+            }
+            Some(Self { handle, base_ptr })
+        }
+    }
+
+    fn read(&self) -> [u8; LEN] {
+        let mut page = [0u8; LEN];
+        // SAFETY: the view was mapped with at least LEN bytes.
+        unsafe {
+            std::ptr::copy_nonoverlapping(self.base_ptr, page.as_mut_ptr(), page.len());
+        }
+        page
+    }
+}
+
+#[cfg(windows)]
+impl<const LEN: usize> Drop for SharedMemoryPage<LEN> {
+    fn drop(&mut self) {
+        // SAFETY: both the view and the handle were obtained in `open`.
+        unsafe {
+            winapi::um::memoryapi::UnmapViewOfFile(self.base_ptr as *const _);
+            winapi::um::handleapi::CloseHandle(self.handle);
+        }
+    }
+}
+
 fn write_acc_string(buffer: &mut Vec<u8>, value: &str) -> Result<()> {
     let bytes = value.as_bytes();
     let length = u16::try_from(bytes.len())
@@ -885,7 +1536,7 @@ mod tests {
         assert_eq!(adapter.game_id(), "acc");
         assert_eq!(adapter.expected_update_rate(), Duration::from_millis(16));
         assert_eq!(
-            adapter.server_address,
+            adapter.registration.server_address,
             SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 9000))
         );
         Ok(())
@@ -895,7 +1546,71 @@ mod tests {
     fn test_acc_adapter_with_address() -> TestResult {
         let addr: SocketAddr = "192.168.1.100:9100".parse()?;
         let adapter = ACCAdapter::with_address(addr);
-        assert_eq!(adapter.server_address, addr);
+        assert_eq!(adapter.registration.server_address, addr);
+        assert_eq!(adapter.broadcasting_json, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_broadcasting_config_applies_port_passwords_and_rate() -> TestResult {
+        let config = AccBroadcastingConfig::parse(
+            br#"{"updListenerPort": 9232, "connectionPassword": "asd", "commandPassword": "cmd", "updateRateHz": 50}"#,
+        )?;
+        assert_eq!(
+            config,
+            AccBroadcastingConfig {
+                listener_port: Some(9232),
+                connection_password: "asd".to_string(),
+                command_password: "cmd".to_string(),
+                update_rate_hz: Some(50),
+            }
+        );
+
+        let adapter = ACCAdapter::from_broadcasting_config(&config);
+        assert_eq!(adapter.registration.server_address.port(), 9232);
+        assert_eq!(adapter.expected_update_rate(), Duration::from_millis(20));
+
+        let packet = adapter.registration.register_packet()?;
+        let mut reader = PacketReader::new(&packet);
+        reader.read_exact(2)?;
+        assert_eq!(read_acc_string(&mut reader)?, "OpenRacing");
+        assert_eq!(read_acc_string(&mut reader)?, "asd");
+        assert_eq!(reader.read_i32_le()?, 20);
+        assert_eq!(read_acc_string(&mut reader)?, "cmd");
+        Ok(())
+    }
+
+    #[test]
+    fn test_broadcasting_config_reads_utf16_files_and_defaults() -> TestResult {
+        // ACC writes the file as UTF-16LE with a byte order mark.
+        let mut raw = vec![0xFF, 0xFE];
+        for unit in r#"{"updListenerPort": 9000, "connectionPassword": "pw"}"#.encode_utf16() {
+            raw.extend_from_slice(&unit.to_le_bytes());
+        }
+        let config = AccBroadcastingConfig::parse(&raw)?;
+        assert_eq!(config.listener_port, Some(9000));
+        assert_eq!(config.connection_password, "pw");
+        assert_eq!(config.command_password, "");
+        assert_eq!(config.update_rate_hz, None);
+
+        let disabled = AccBroadcastingConfig::parse(b"\xEF\xBB\xBF{\"updListenerPort\": 0}")?;
+        assert_eq!(disabled, AccBroadcastingConfig::default());
+        assert!(AccBroadcastingConfig::parse(b"[]").is_err());
+        assert!(AccBroadcastingConfig::parse(&raw[..raw.len() - 1]).is_err());
+
+        // Broadcasting left disabled keeps the adapter's own port.
+        let adapter = ACCAdapter::from_broadcasting_config(&disabled);
+        assert_eq!(adapter.registration.server_address.port(), 9000);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unregister_packet_layout() -> TestResult {
+        let packet = build_unregister_packet(1337);
+        let mut reader = PacketReader::new(&packet);
+        assert_eq!(reader.read_u8()?, UNREGISTER_COMMAND_APPLICATION);
+        assert_eq!(reader.read_i32_le()?, 1337);
+        assert_eq!(packet.len(), 5);
         Ok(())
     }
 
@@ -952,6 +1667,95 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_graphics_penalties_attach_to_focused_car_frame() -> TestResult {
+        let mut page = vec![0u8; GRAPHICS_PENALTY_PAGE_LEN];
+        page[AccGraphicsPrefix::PENALTY_TIME..AccGraphicsPrefix::PENALTY_TIME + 4]
+            .copy_from_slice(&30.0f32.to_le_bytes());
+        page[AccGraphicsPrefix::PENALTY..AccGraphicsPrefix::PENALTY + 4]
+            .copy_from_slice(&4i32.to_le_bytes());
+
+        let mut state = ACCSessionState {
+            penalties: Some(parse_graphics_penalties(&page)?),
+            ..ACCSessionState::default()
+        };
+        let car_msg = parse_inbound_message(FIXTURE_REALTIME_CAR_UPDATE_CAR_7)?;
+        let normalized = state
+            .update_and_normalize(&car_msg)
+            .ok_or("expected normalized telemetry from fixture car update")?;
+
+        let penalties = normalized.penalties.ok_or("expected penalty state")?;
+        assert_eq!(penalties.active_penalty_kind, PenaltyKind::StopAndGo);
+        assert_eq!(penalties.penalty_time_s, 30.0);
+        assert!(parse_graphics_penalties(&page[..AccGraphicsPrefix::PENALTY]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_session_timing_maps_lap_info_and_countdown() -> TestResult {
+        let car = RealtimeCarUpdate {
+            car_index: 7,
+            gear: 3,
+            car_location: 1,
+            world_pos_x: 0.0,
+            world_pos_y: 0.0,
+            yaw: 0.0,
+            speed_kmh: 180,
+            position: 2,
+            cup_position: 2,
+            track_position: 2,
+            spline_position: 0.4,
+            laps: 8,
+            delta_ms: 0,
+            best_session_lap_ms: 98_500,
+            last_lap_ms: 99_200,
+            current_lap_ms: 42_300,
+        };
+        let state = ACCSessionState {
+            latest_realtime: Some(RealtimeUpdate {
+                focused_car_index: Some(7),
+                session_type: 10,
+                phase: 5,
+                session_time_ms: 1_200_000.0,
+                session_end_time_ms: 2_400_000.0,
+                ambient_temp_c: 22,
+                track_temp_c: 30,
+                rain_level: 0.0,
+                wetness: 0.0,
+                best_session_lap_ms: 98_100,
+            }),
+            ..ACCSessionState::default()
+        };
+
+        let timing = state
+            .normalize_car(&car)
+            .timing
+            .ok_or("expected session timing")?;
+        assert_eq!(timing.current_lap_ms, Some(42_300.0));
+        assert_eq!(timing.last_lap_ms, Some(99_200.0));
+        assert_eq!(timing.best_lap_ms, Some(98_500.0));
+        assert_eq!(timing.lap_number, Some(9));
+        assert_eq!(timing.sector, None);
+        assert_eq!(timing.session_time_remaining_ms, Some(2_400_000.0));
+
+        // ACC marks laps without a time as i32::MAX.
+        let out_lap = RealtimeCarUpdate {
+            best_session_lap_ms: i32::MAX,
+            last_lap_ms: i32::MAX,
+            laps: 0,
+            ..car
+        };
+        let timing = ACCSessionState::default()
+            .normalize_car(&out_lap)
+            .timing
+            .ok_or("expected session timing")?;
+        assert_eq!(timing.best_lap_ms, None);
+        assert_eq!(timing.last_lap_ms, None);
+        assert_eq!(timing.lap_number, Some(1));
+        assert_eq!(timing.session_time_remaining_ms, None);
+        Ok(())
+    }
+
     #[test]
     fn test_parse_realtime_sequence_from_fixtures() -> TestResult {
         let mut state = ACCSessionState::default();
@@ -984,6 +1788,7 @@ mod tests {
             normalized.extended.get("track_temp_c"),
             Some(&TelemetryValue::Integer(31))
         );
+        assert_eq!(normalized.game_time_s, Some(12.345));
         Ok(())
     }
 
@@ -1076,6 +1881,248 @@ mod tests {
         Ok(())
     }
 
+    /// RealtimeCarUpdate for a car on lap 11 of a 100 s best lap; the
+    /// leader's spline is furthest round.
+    fn grid_car_update_packet(car_index: u16, position: u16, car_location: u8) -> Vec<u8> {
+        let mut packet = vec![MSG_REALTIME_CAR_UPDATE];
+        packet.extend_from_slice(&car_index.to_le_bytes());
+        packet.extend_from_slice(&0u16.to_le_bytes()); // driver index
+        packet.push(1); // driver count
+        packet.push(5); // gear
+        packet.extend_from_slice(&[0u8; 12]); // world x, world y, yaw
+        packet.push(car_location);
+        packet.extend_from_slice(&(150 + car_index).to_le_bytes()); // kmh
+        packet.extend_from_slice(&position.to_le_bytes());
+        packet.extend_from_slice(&position.to_le_bytes()); // cup position
+        packet.extend_from_slice(&position.to_le_bytes()); // track position
+        let spline = 0.9 - f32::from(position) * 0.02;
+        packet.extend_from_slice(&spline.to_le_bytes());
+        packet.extend_from_slice(&10u16.to_le_bytes()); // laps
+        packet.extend_from_slice(&0i32.to_le_bytes()); // delta
+        push_lap(&mut packet, 100_000);
+        push_lap(&mut packet, 101_000);
+        push_lap(&mut packet, 30_000);
+        packet
+    }
+
+    fn entry_list_car_packet(
+        car_index: u16,
+        first: &str,
+        last: &str,
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let mut packet = vec![MSG_ENTRY_LIST_CAR];
+        packet.extend_from_slice(&car_index.to_le_bytes());
+        packet.push(30); // car model
+        push_acc_string(&mut packet, "Team")?;
+        packet.extend_from_slice(&i32::from(car_index).to_le_bytes()); // race number
+        packet.push(0); // cup category
+        packet.push(1); // current driver index
+        packet.extend_from_slice(&0u16.to_le_bytes()); // nationality
+        packet.push(2); // drivers
+        for (first, last) in [("Reserve", "Driver"), (first, last)] {
+            push_acc_string(&mut packet, first)?;
+            push_acc_string(&mut packet, last)?;
+            push_acc_string(&mut packet, "DRV")?;
+            packet.push(0); // category
+            packet.extend_from_slice(&0u16.to_le_bytes()); // nationality
+        }
+        Ok(packet)
+    }
+
+    #[test]
+    fn test_twenty_car_grid_fills_opponents() -> TestResult {
+        let mut state = ACCSessionState::default();
+        // Focused car 7 runs 8th; car 3 is in the pit lane.
+        state.update_and_normalize(&parse_inbound_message(
+            FIXTURE_REALTIME_UPDATE_FOCUSED_CAR_7,
+        )?);
+        for car_index in 0..20u16 {
+            let entry = entry_list_car_packet(car_index, "Driver", &car_index.to_string())?;
+            state.update_and_normalize(&parse_inbound_message(&entry)?);
+            let location = if car_index == 3 { 2 } else { 1 };
+            let update = grid_car_update_packet(car_index, car_index + 1, location);
+            state.update_and_normalize(&parse_inbound_message(&update)?);
+        }
+
+        let opponents = state.opponents(DEFAULT_MAX_OPPONENTS);
+        let positions: Vec<u8> = opponents.iter().map(|car| car.position).collect();
+        let expected: Vec<u8> = (1..=20).filter(|position| *position != 8).collect();
+        assert_eq!(positions, expected);
+        assert!(opponents.iter().all(|car| car.car_index != 7));
+
+        let ahead = &opponents[6];
+        assert_eq!(ahead.car_index, 6);
+        assert_eq!(ahead.driver_name.as_deref(), Some("Driver 6"));
+        assert_eq!(ahead.gap_to_player_ms, Some(2_000));
+        assert_eq!(ahead.lap_number, 11);
+        assert!((ahead.speed_ms - 156.0 / 3.6).abs() < 0.01);
+        assert_eq!(opponents[7].gap_to_player_ms, Some(-2_000));
+        assert!(opponents[3].in_pits && !opponents[4].in_pits);
+
+        // Cars leave when a fresh entry list no longer has them.
+        let mut entry_list = vec![MSG_ENTRY_LIST];
+        entry_list.extend_from_slice(&1i32.to_le_bytes());
+        entry_list.extend_from_slice(&3u16.to_le_bytes());
+        for car_index in [2u16, 7, 9] {
+            entry_list.extend_from_slice(&car_index.to_le_bytes());
+        }
+        state.update_and_normalize(&parse_inbound_message(&entry_list)?);
+        let remaining: Vec<u16> = state
+            .opponents(DEFAULT_MAX_OPPONENTS)
+            .iter()
+            .map(|car| car.car_index)
+            .collect();
+        assert_eq!(remaining, [2, 9]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_opponent_bound_keeps_cars_nearest_the_focused_car() -> TestResult {
+        let mut state = ACCSessionState::default();
+        state.update_and_normalize(&parse_inbound_message(
+            FIXTURE_REALTIME_UPDATE_FOCUSED_CAR_7,
+        )?);
+        for car_index in 0..20u16 {
+            let update = grid_car_update_packet(car_index, car_index + 1, 1);
+            state.update_and_normalize(&parse_inbound_message(&update)?);
+        }
+        let positions: Vec<u8> = state.opponents(4).iter().map(|car| car.position).collect();
+        assert_eq!(positions, [6, 7, 9, 10]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fixture_car_update_carries_world_position() -> TestResult {
+        let mut state = ACCSessionState::default();
+        let car_msg = parse_inbound_message(FIXTURE_REALTIME_CAR_UPDATE_CAR_7)?;
+        let normalized = state
+            .update_and_normalize(&car_msg)
+            .ok_or("expected normalized telemetry from fixture car update")?;
+
+        let float = |key: ExtendedKey| match normalized.extended.get(key.name) {
+            Some(TelemetryValue::Float(value)) => Some(*value),
+            _ => None,
+        };
+        assert_eq!(float(ExtendedKey::POS_X), Some(1.0));
+        assert_eq!(float(ExtendedKey::POS_Y), Some(2.0));
+        assert_eq!(float(ExtendedKey::YAW), Some(0.25));
+        assert_eq!(normalized.world_position, Some([1.0, 2.0, 0.0]));
+        assert_eq!(
+            normalized
+                .lap_distance_fraction
+                .map(TelemetryValue::Float)
+                .as_ref(),
+            normalized.extended.get("spline_position")
+        );
+        assert!(normalized.lap_distance_fraction.is_some());
+        assert_eq!(
+            normalized.extended.get("transport"),
+            Some(&TelemetryValue::String(TRANSPORT_UDP_BROADCAST.to_string()))
+        );
+        Ok(())
+    }
+
+    fn physics_page(packet_id: i32) -> Vec<u8> {
+        let mut page = vec![0u8; AccPhysicsInputs::MIN_SIZE];
+        let mut put = |offset: usize, bytes: [u8; 4]| {
+            page[offset..offset + 4].copy_from_slice(&bytes);
+        };
+        put(AccPhysicsInputs::PACKET_ID, packet_id.to_le_bytes());
+        put(AccPhysicsInputs::GAS, 0.75f32.to_le_bytes());
+        put(AccPhysicsInputs::BRAKE, 0.1f32.to_le_bytes());
+        put(AccPhysicsInputs::GEAR, 4i32.to_le_bytes());
+        put(AccPhysicsInputs::RPMS, 7200i32.to_le_bytes());
+        put(AccPhysicsInputs::SPEED_KMH, 216.0f32.to_le_bytes());
+        put(AccPhysicsInputs::ACC_G, 1.5f32.to_le_bytes());
+        put(AccPhysicsInputs::ACC_G + 8, (-0.5f32).to_le_bytes());
+        page
+    }
+
+    #[test]
+    fn test_live_physics_page_replaces_broadcast_channels() -> TestResult {
+        let start = Instant::now();
+        let mut state = ACCSessionState::default();
+        let car_msg = parse_inbound_message(FIXTURE_REALTIME_CAR_UPDATE_CAR_7)?;
+        state.update_and_normalize(&car_msg);
+        let realtime_msg = parse_inbound_message(FIXTURE_REALTIME_UPDATE_FOCUSED_CAR_7)?;
+        state.update_and_normalize(&realtime_msg);
+        assert!(state.normalize_from_physics().is_none());
+
+        state.update_physics(Some(parse_physics_inputs(&physics_page(10))?), start);
+        let normalized = state
+            .normalize_from_physics()
+            .ok_or("expected a physics-driven frame")?;
+        assert!((normalized.speed_ms - 60.0).abs() < 1e-4);
+        assert_eq!(normalized.gear, 3);
+        assert_eq!(normalized.rpm, 7200.0);
+        assert_eq!(normalized.throttle, 0.75);
+        assert_eq!(normalized.brake, 0.1);
+        assert_eq!(normalized.lateral_g, 1.5);
+        assert_eq!(normalized.longitudinal_g, -0.5);
+        assert_eq!(
+            normalized.extended.get("transport"),
+            Some(&TelemetryValue::String(TRANSPORT_SHARED_MEMORY.to_string()))
+        );
+
+        // A packet id that stops advancing falls back to the broadcast.
+        let frozen = Some(parse_physics_inputs(&physics_page(10))?);
+        state.update_physics(frozen, start + PHYSICS_STALE_AFTER / 2);
+        assert!(state.physics.is_some());
+        state.update_physics(frozen, start + PHYSICS_STALE_AFTER);
+        assert!(state.physics.is_none());
+        let normalized = state
+            .update_and_normalize(&car_msg)
+            .ok_or("expected normalized telemetry from fixture car update")?;
+        assert_eq!(
+            normalized.extended.get("transport"),
+            Some(&TelemetryValue::String(TRANSPORT_UDP_BROADCAST.to_string()))
+        );
+
+        assert!(parse_physics_inputs(&physics_page(1)[..AccPhysicsInputs::ACC_G]).is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_monitoring_registers_streams_and_unregisters_on_stop() -> TestResult {
+        let game = TokioUdpSocket::bind("127.0.0.1:0").await?;
+        let adapter = ACCAdapter::with_address(game.local_addr()?);
+        let mut frames = adapter.start_monitoring().await?;
+
+        let mut buf = [0u8; MAX_PACKET_SIZE];
+        let (len, client) =
+            tokio::time::timeout(Duration::from_secs(2), game.recv_from(&mut buf)).await??;
+        assert_eq!(
+            buf.get(..len),
+            Some(&build_register_packet("OpenRacing", "", 16, "")?[..])
+        );
+
+        game.send_to(FIXTURE_REGISTRATION_RESULT_SUCCESS, client)
+            .await?;
+        game.send_to(FIXTURE_REALTIME_CAR_UPDATE_CAR_7, client)
+            .await?;
+        let frame = tokio::time::timeout(Duration::from_secs(2), frames.recv())
+            .await?
+            .ok_or("monitoring ended without a frame")?;
+        assert_eq!(frame.data.car_id, Some("car_7".to_string()));
+
+        adapter.stop_monitoring().await?;
+        let unregister = build_unregister_packet(1337);
+        loop {
+            // Entry list and track data requests arrive first.
+            let (len, _) =
+                tokio::time::timeout(Duration::from_secs(2), game.recv_from(&mut buf)).await??;
+            if buf.get(..len) == Some(&unregister[..]) {
+                break;
+            }
+        }
+        assert!(
+            tokio::time::timeout(Duration::from_secs(2), frames.recv())
+                .await?
+                .is_none()
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_parse_invalid_packet() -> TestResult {
         let small_data = [0u8; 0];
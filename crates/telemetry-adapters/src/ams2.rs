@@ -437,6 +437,10 @@ impl TelemetryAdapter for AMS2Adapter {
         self.update_rate
     }
 
+    fn prefers_dedup(&self) -> bool {
+        true
+    }
+
     async fn is_game_running(&self) -> Result<bool> {
         Ok(self.check_ams2_running().await)
     }
@@ -7,13 +7,17 @@
 //! Reference: <https://github.com/vpicon/acudp/blob/master/UDP.md>
 #![cfg_attr(not(windows), allow(unused, dead_code))]
 
+use crate::ac_layout::RtCarInfo;
+use crate::raw_capture::{RawCaptureSink, RawCaptureSlot};
+use crate::traffic::TrafficAccountant;
 use crate::{
-    NormalizedTelemetry, TelemetryAdapter, TelemetryFlags, TelemetryFrame, TelemetryReceiver,
-    TelemetryValue, telemetry_now_ns,
+    FieldUnit, NormalizedTelemetry, TelemetryAdapter, TelemetryFlags, TelemetryFrame,
+    TelemetryReceiver, TelemetryValue, Unit, UnitManifest, telemetry_now_ns,
 };
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::net::UdpSocket as TokioUdpSocket;
 use tokio::sync::mpsc;
@@ -22,42 +26,18 @@ use tracing::{debug, info, warn};
 /// Verified: AC Remote Telemetry handshake port per official SDK (vpicon/acudp).
 const DEFAULT_AC_PORT: u16 = 9996;
 /// RTCarInfo struct size (AC Remote Telemetry UDP update packet).
-const AC_RTCARINFO_SIZE: usize = 328;
+const AC_RTCARINFO_SIZE: usize = RtCarInfo::MIN_SIZE;
 const MAX_PACKET_SIZE: usize = 512;
 
 // Handshake operation IDs for AC Remote Telemetry UDP protocol.
 const OP_HANDSHAKE: i32 = 0;
 const OP_SUBSCRIBE_UPDATE: i32 = 1;
 
-// Byte offsets in the AC RTCarInfo struct (little-endian, naturally aligned).
-// Reference: https://github.com/vpicon/acudp/blob/master/UDP.md
-#[cfg(test)]
-const OFF_SPEED_KMH: usize = 8; // f32 (used in tests only; parse uses speed_Ms)
-const OFF_SPEED_MS: usize = 16; // f32
-const OFF_ABS_IN_ACTION: usize = 21; // bool (u8)
-const OFF_TC_IN_ACTION: usize = 22; // bool (u8)
-const OFF_IN_PIT: usize = 24; // bool (u8)
-const OFF_ENGINE_LIMITER: usize = 25; // bool (u8)
-const OFF_ACCG_VERTICAL: usize = 28; // f32
-const OFF_ACCG_HORIZONTAL: usize = 32; // f32
-const OFF_ACCG_FRONTAL: usize = 36; // f32
-const OFF_LAP_TIME: usize = 40; // i32 (milliseconds)
-const OFF_LAST_LAP: usize = 44; // i32 (milliseconds)
-const OFF_BEST_LAP: usize = 48; // i32 (milliseconds)
-const OFF_LAP_COUNT: usize = 52; // i32
-const OFF_GAS: usize = 56; // f32
-const OFF_BRAKE: usize = 60; // f32
-const OFF_CLUTCH: usize = 64; // f32
-const OFF_RPM: usize = 68; // f32
-const OFF_STEER: usize = 72; // f32
-const OFF_GEAR: usize = 76; // i32 (0=R, 1=N, 2=1st, ...)
-const OFF_SLIP_ANGLE_FL: usize = 100; // f32[4] at 100,104,108,112
-const OFF_SLIP_RATIO_FL: usize = 132; // f32[4] at 132,136,140,144
-
 /// Assetto Corsa (original) telemetry adapter using Remote Telemetry UDP.
 pub struct AssettoCorsaAdapter {
     bind_port: u16,
     update_rate: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for AssettoCorsaAdapter {
@@ -71,6 +51,7 @@ impl AssettoCorsaAdapter {
         Self {
             bind_port: DEFAULT_AC_PORT,
             update_rate: Duration::from_millis(16),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -81,21 +62,21 @@ impl AssettoCorsaAdapter {
 }
 
 fn parse_ac_packet(data: &[u8]) -> Result<NormalizedTelemetry> {
-    if data.len() < AC_RTCARINFO_SIZE {
-        return Err(anyhow!(
+    let car = RtCarInfo::new(data).ok_or_else(|| {
+        anyhow!(
             "AC RTCarInfo packet too short: expected {AC_RTCARINFO_SIZE}, got {}",
             data.len()
-        ));
-    }
+        )
+    })?;
 
-    let speed_ms = read_f32_le(data, OFF_SPEED_MS).unwrap_or(0.0);
-    let rpm = read_f32_le(data, OFF_RPM).unwrap_or(0.0);
-    let steer = read_f32_le(data, OFF_STEER).unwrap_or(0.0).clamp(-1.0, 1.0);
-    let gas = read_f32_le(data, OFF_GAS).unwrap_or(0.0);
-    let brake = read_f32_le(data, OFF_BRAKE).unwrap_or(0.0);
-    let clutch = read_f32_le(data, OFF_CLUTCH).unwrap_or(0.0);
+    let speed_ms = finite(car.speed_ms()).unwrap_or(0.0);
+    let rpm = finite(car.engine_rpm()).unwrap_or(0.0);
+    let steer = finite(car.steer()).unwrap_or(0.0).clamp(-1.0, 1.0);
+    let gas = finite(car.gas()).unwrap_or(0.0);
+    let brake = finite(car.brake()).unwrap_or(0.0);
+    let clutch = finite(car.clutch()).unwrap_or(0.0);
 
-    let gear_raw = read_i32_le(data, OFF_GEAR).unwrap_or(1); // default neutral
+    let gear_raw = car.gear();
     // AC gear: 0=Reverse, 1=Neutral, 2=1st gear, ...
     // Normalized: -1=Reverse, 0=Neutral, 1=1st gear, ...
     let gear: i8 = match gear_raw {
@@ -105,36 +86,32 @@ fn parse_ac_packet(data: &[u8]) -> Result<NormalizedTelemetry> {
     };
 
     // G-forces
-    let vertical_g = read_f32_le(data, OFF_ACCG_VERTICAL).unwrap_or(0.0);
-    let lateral_g = read_f32_le(data, OFF_ACCG_HORIZONTAL).unwrap_or(0.0);
-    let longitudinal_g = read_f32_le(data, OFF_ACCG_FRONTAL).unwrap_or(0.0);
+    let vertical_g = finite(car.accg_vertical()).unwrap_or(0.0);
+    let lateral_g = finite(car.accg_horizontal()).unwrap_or(0.0);
+    let longitudinal_g = finite(car.accg_frontal()).unwrap_or(0.0);
 
     // Flags
     let flags = TelemetryFlags {
-        abs_active: read_u8(data, OFF_ABS_IN_ACTION) != 0,
-        traction_control: read_u8(data, OFF_TC_IN_ACTION) != 0,
-        in_pits: read_u8(data, OFF_IN_PIT) != 0,
-        engine_limiter: read_u8(data, OFF_ENGINE_LIMITER) != 0,
+        abs_active: car.is_abs_in_action() != 0,
+        traction_control: car.is_tc_in_action() != 0,
+        in_pits: car.is_in_pit() != 0,
+        engine_limiter: car.is_engine_limiter_on() != 0,
         ..TelemetryFlags::default()
     };
 
     // Lap timing (i32 milliseconds → f32 seconds)
-    let current_lap_ms = read_i32_le(data, OFF_LAP_TIME).unwrap_or(0);
-    let last_lap_ms = read_i32_le(data, OFF_LAST_LAP).unwrap_or(0);
-    let best_lap_ms = read_i32_le(data, OFF_BEST_LAP).unwrap_or(0);
-    let lap_count = read_i32_le(data, OFF_LAP_COUNT).unwrap_or(0);
+    let current_lap_ms = car.lap_time_ms();
+    let last_lap_ms = car.last_lap_ms();
+    let best_lap_ms = car.best_lap_ms();
+    let lap_count = car.lap_count();
 
     // Slip angles (per-wheel)
-    let slip_angle_fl = read_f32_le(data, OFF_SLIP_ANGLE_FL).unwrap_or(0.0);
-    let slip_angle_fr = read_f32_le(data, OFF_SLIP_ANGLE_FL + 4).unwrap_or(0.0);
-    let slip_angle_rl = read_f32_le(data, OFF_SLIP_ANGLE_FL + 8).unwrap_or(0.0);
-    let slip_angle_rr = read_f32_le(data, OFF_SLIP_ANGLE_FL + 12).unwrap_or(0.0);
+    let [slip_angle_fl, slip_angle_fr, slip_angle_rl, slip_angle_rr] =
+        car.slip_angle().map(|v| finite(v).unwrap_or(0.0));
 
     // Slip ratios (per-wheel) — no per-wheel builder methods, use extended map
-    let slip_ratio_fl = read_f32_le(data, OFF_SLIP_RATIO_FL).unwrap_or(0.0);
-    let slip_ratio_fr = read_f32_le(data, OFF_SLIP_RATIO_FL + 4).unwrap_or(0.0);
-    let slip_ratio_rl = read_f32_le(data, OFF_SLIP_RATIO_FL + 8).unwrap_or(0.0);
-    let slip_ratio_rr = read_f32_le(data, OFF_SLIP_RATIO_FL + 12).unwrap_or(0.0);
+    let [slip_ratio_fl, slip_ratio_fr, slip_ratio_rl, slip_ratio_rr] =
+        car.slip_ratio().map(|v| finite(v).unwrap_or(0.0));
 
     // Overall slip ratio: average of per-wheel absolute values (guard at low speed).
     let slip_ratio = if speed_ms > 1.0 {
@@ -183,13 +160,44 @@ fn parse_ac_packet(data: &[u8]) -> Result<NormalizedTelemetry> {
     Ok(builder.build())
 }
 
+/// Assetto Corsa RTCarInfo units; steering arrives as a lock fraction and
+/// lap times in milliseconds.
+const UNIT_MANIFEST: UnitManifest = UnitManifest::new(&[
+    FieldUnit::native("speed_ms", Unit::MetersPerSecond),
+    FieldUnit::native("rpm", Unit::Rpm),
+    FieldUnit::converted("steering_angle", Unit::Fraction, Unit::Radians),
+    FieldUnit::native("throttle", Unit::Fraction),
+    FieldUnit::native("brake", Unit::Fraction),
+    FieldUnit::native("clutch", Unit::Fraction),
+    FieldUnit::native("slip_ratio", Unit::Fraction),
+    FieldUnit::native("lateral_g", Unit::StandardGravity),
+    FieldUnit::native("longitudinal_g", Unit::StandardGravity),
+    FieldUnit::native("vertical_g", Unit::StandardGravity),
+    FieldUnit::native("slip_angle_fl", Unit::Radians),
+    FieldUnit::native("slip_angle_fr", Unit::Radians),
+    FieldUnit::native("slip_angle_rl", Unit::Radians),
+    FieldUnit::native("slip_angle_rr", Unit::Radians),
+    FieldUnit::converted("current_lap_time_s", Unit::Milliseconds, Unit::Seconds),
+    FieldUnit::converted("best_lap_time_s", Unit::Milliseconds, Unit::Seconds),
+    FieldUnit::converted("last_lap_time_s", Unit::Milliseconds, Unit::Seconds),
+]);
+
 #[async_trait]
 impl TelemetryAdapter for AssettoCorsaAdapter {
     fn game_id(&self) -> &str {
         "assetto_corsa"
     }
 
+    fn unit_manifest(&self) -> Option<UnitManifest> {
+        Some(UNIT_MANIFEST)
+    }
+
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(100);
         let ac_port = self.bind_port;
         let update_rate = self.update_rate;
@@ -219,7 +227,9 @@ impl TelemetryAdapter for AssettoCorsaAdapter {
             }
 
             let mut buf = [0u8; MAX_PACKET_SIZE];
-            match tokio::time::timeout(Duration::from_secs(2), socket.recv(&mut buf)).await {
+            match tokio::time::timeout(Duration::from_secs(2), raw_capture.recv(&socket, &mut buf))
+                .await
+            {
                 Ok(Ok(_)) => info!("AC handshake response received"),
                 Ok(Err(e)) => {
                     warn!("Failed to receive AC handshake response: {e}");
@@ -241,7 +251,9 @@ impl TelemetryAdapter for AssettoCorsaAdapter {
             let mut frame_seq = 0u64;
 
             loop {
-                match tokio::time::timeout(update_rate * 10, socket.recv(&mut buf)).await {
+                match tokio::time::timeout(update_rate * 10, raw_capture.recv(&socket, &mut buf))
+                    .await
+                {
                     Ok(Ok(len)) => match parse_ac_packet(&buf[..len]) {
                         Ok(normalized) => {
                             let frame =
@@ -342,21 +354,8 @@ fn is_process_running_linux(process_name: &str) -> bool {
     false
 }
 
-fn read_f32_le(data: &[u8], offset: usize) -> Option<f32> {
-    data.get(offset..offset + 4)
-        .and_then(|b| b.try_into().ok())
-        .map(f32::from_le_bytes)
-        .filter(|v| v.is_finite())
-}
-
-fn read_i32_le(data: &[u8], offset: usize) -> Option<i32> {
-    data.get(offset..offset + 4)
-        .and_then(|b| b.try_into().ok())
-        .map(i32::from_le_bytes)
-}
-
-fn read_u8(data: &[u8], offset: usize) -> u8 {
-    data.get(offset).copied().unwrap_or(0)
+fn finite(value: f32) -> Option<f32> {
+    value.is_finite().then_some(value)
 }
 
 fn build_handshake_packet(operation_id: i32) -> [u8; 12] {
@@ -380,45 +379,55 @@ mod tests {
         // size
         data[4..8].copy_from_slice(&(AC_RTCARINFO_SIZE as i32).to_le_bytes());
         // speed_Kmh (float) at offset 8
-        data[OFF_SPEED_KMH..OFF_SPEED_KMH + 4].copy_from_slice(&120.0f32.to_le_bytes());
+        data[RtCarInfo::SPEED_KMH..RtCarInfo::SPEED_KMH + 4]
+            .copy_from_slice(&120.0f32.to_le_bytes());
         // speed_Ms (float) at offset 16
         let speed_ms = 120.0f32 / 3.6;
-        data[OFF_SPEED_MS..OFF_SPEED_MS + 4].copy_from_slice(&speed_ms.to_le_bytes());
+        data[RtCarInfo::SPEED_MS..RtCarInfo::SPEED_MS + 4].copy_from_slice(&speed_ms.to_le_bytes());
         // flags (bool u8)
-        data[OFF_ABS_IN_ACTION] = 1;
-        data[OFF_TC_IN_ACTION] = 0;
-        data[OFF_IN_PIT] = 1;
-        data[OFF_ENGINE_LIMITER] = 0;
+        data[RtCarInfo::ABS_IN_ACTION] = 1;
+        data[RtCarInfo::TC_IN_ACTION] = 0;
+        data[RtCarInfo::IN_PIT] = 1;
+        data[RtCarInfo::ENGINE_LIMITER] = 0;
         // G-forces
-        data[OFF_ACCG_VERTICAL..OFF_ACCG_VERTICAL + 4].copy_from_slice(&1.02f32.to_le_bytes());
-        data[OFF_ACCG_HORIZONTAL..OFF_ACCG_HORIZONTAL + 4]
+        data[RtCarInfo::ACCG_VERTICAL..RtCarInfo::ACCG_VERTICAL + 4]
+            .copy_from_slice(&1.02f32.to_le_bytes());
+        data[RtCarInfo::ACCG_HORIZONTAL..RtCarInfo::ACCG_HORIZONTAL + 4]
             .copy_from_slice(&(-0.35f32).to_le_bytes());
-        data[OFF_ACCG_FRONTAL..OFF_ACCG_FRONTAL + 4].copy_from_slice(&0.45f32.to_le_bytes());
+        data[RtCarInfo::ACCG_FRONTAL..RtCarInfo::ACCG_FRONTAL + 4]
+            .copy_from_slice(&0.45f32.to_le_bytes());
         // lap timing (i32 milliseconds)
-        data[OFF_LAP_TIME..OFF_LAP_TIME + 4].copy_from_slice(&62500i32.to_le_bytes());
-        data[OFF_LAST_LAP..OFF_LAST_LAP + 4].copy_from_slice(&61200i32.to_le_bytes());
-        data[OFF_BEST_LAP..OFF_BEST_LAP + 4].copy_from_slice(&60800i32.to_le_bytes());
-        data[OFF_LAP_COUNT..OFF_LAP_COUNT + 4].copy_from_slice(&3i32.to_le_bytes());
+        data[RtCarInfo::LAP_TIME..RtCarInfo::LAP_TIME + 4].copy_from_slice(&62500i32.to_le_bytes());
+        data[RtCarInfo::LAST_LAP..RtCarInfo::LAST_LAP + 4].copy_from_slice(&61200i32.to_le_bytes());
+        data[RtCarInfo::BEST_LAP..RtCarInfo::BEST_LAP + 4].copy_from_slice(&60800i32.to_le_bytes());
+        data[RtCarInfo::LAP_COUNT..RtCarInfo::LAP_COUNT + 4].copy_from_slice(&3i32.to_le_bytes());
         // gas at offset 56
-        data[OFF_GAS..OFF_GAS + 4].copy_from_slice(&0.8f32.to_le_bytes());
+        data[RtCarInfo::GAS..RtCarInfo::GAS + 4].copy_from_slice(&0.8f32.to_le_bytes());
         // brake at offset 60
-        data[OFF_BRAKE..OFF_BRAKE + 4].copy_from_slice(&0.1f32.to_le_bytes());
+        data[RtCarInfo::BRAKE..RtCarInfo::BRAKE + 4].copy_from_slice(&0.1f32.to_le_bytes());
         // rpm at offset 68
-        data[OFF_RPM..OFF_RPM + 4].copy_from_slice(&6000.0f32.to_le_bytes());
+        data[RtCarInfo::RPM..RtCarInfo::RPM + 4].copy_from_slice(&6000.0f32.to_le_bytes());
         // steer at offset 72
-        data[OFF_STEER..OFF_STEER + 4].copy_from_slice(&0.3f32.to_le_bytes());
+        data[RtCarInfo::STEER..RtCarInfo::STEER + 4].copy_from_slice(&0.3f32.to_le_bytes());
         // gear at offset 76 (AC: 3 = 2nd gear; 0=R, 1=N, 2=1st, 3=2nd)
-        data[OFF_GEAR..OFF_GEAR + 4].copy_from_slice(&3i32.to_le_bytes());
+        data[RtCarInfo::GEAR..RtCarInfo::GEAR + 4].copy_from_slice(&3i32.to_le_bytes());
         // slip angles (f32[4])
-        data[OFF_SLIP_ANGLE_FL..OFF_SLIP_ANGLE_FL + 4].copy_from_slice(&0.5f32.to_le_bytes());
-        data[OFF_SLIP_ANGLE_FL + 4..OFF_SLIP_ANGLE_FL + 8].copy_from_slice(&0.6f32.to_le_bytes());
-        data[OFF_SLIP_ANGLE_FL + 8..OFF_SLIP_ANGLE_FL + 12].copy_from_slice(&0.7f32.to_le_bytes());
-        data[OFF_SLIP_ANGLE_FL + 12..OFF_SLIP_ANGLE_FL + 16].copy_from_slice(&0.8f32.to_le_bytes());
+        data[RtCarInfo::SLIP_ANGLE..RtCarInfo::SLIP_ANGLE + 4]
+            .copy_from_slice(&0.5f32.to_le_bytes());
+        data[RtCarInfo::SLIP_ANGLE + 4..RtCarInfo::SLIP_ANGLE + 8]
+            .copy_from_slice(&0.6f32.to_le_bytes());
+        data[RtCarInfo::SLIP_ANGLE + 8..RtCarInfo::SLIP_ANGLE + 12]
+            .copy_from_slice(&0.7f32.to_le_bytes());
+        data[RtCarInfo::SLIP_ANGLE + 12..RtCarInfo::SLIP_ANGLE + 16]
+            .copy_from_slice(&0.8f32.to_le_bytes());
         // slip ratios (f32[4])
-        data[OFF_SLIP_RATIO_FL..OFF_SLIP_RATIO_FL + 4].copy_from_slice(&0.02f32.to_le_bytes());
-        data[OFF_SLIP_RATIO_FL + 4..OFF_SLIP_RATIO_FL + 8].copy_from_slice(&0.03f32.to_le_bytes());
-        data[OFF_SLIP_RATIO_FL + 8..OFF_SLIP_RATIO_FL + 12].copy_from_slice(&0.04f32.to_le_bytes());
-        data[OFF_SLIP_RATIO_FL + 12..OFF_SLIP_RATIO_FL + 16]
+        data[RtCarInfo::SLIP_RATIO..RtCarInfo::SLIP_RATIO + 4]
+            .copy_from_slice(&0.02f32.to_le_bytes());
+        data[RtCarInfo::SLIP_RATIO + 4..RtCarInfo::SLIP_RATIO + 8]
+            .copy_from_slice(&0.03f32.to_le_bytes());
+        data[RtCarInfo::SLIP_RATIO + 8..RtCarInfo::SLIP_RATIO + 12]
+            .copy_from_slice(&0.04f32.to_le_bytes());
+        data[RtCarInfo::SLIP_RATIO + 12..RtCarInfo::SLIP_RATIO + 16]
             .copy_from_slice(&0.05f32.to_le_bytes());
         data
     }
@@ -484,9 +493,9 @@ mod tests {
     #[test]
     fn test_normalization_bounds() -> TestResult {
         let mut data = make_valid_ac_packet();
-        data[OFF_STEER..OFF_STEER + 4].copy_from_slice(&2.5f32.to_le_bytes());
-        data[OFF_GAS..OFF_GAS + 4].copy_from_slice(&1.5f32.to_le_bytes());
-        data[OFF_BRAKE..OFF_BRAKE + 4].copy_from_slice(&(-0.5f32).to_le_bytes());
+        data[RtCarInfo::STEER..RtCarInfo::STEER + 4].copy_from_slice(&2.5f32.to_le_bytes());
+        data[RtCarInfo::GAS..RtCarInfo::GAS + 4].copy_from_slice(&1.5f32.to_le_bytes());
+        data[RtCarInfo::BRAKE..RtCarInfo::BRAKE + 4].copy_from_slice(&(-0.5f32).to_le_bytes());
         let result = parse_ac_packet(&data)?;
         assert!((result.steering_angle - 1.0).abs() < 0.001);
         // Builder clamps throttle to [0,1]
@@ -531,7 +540,7 @@ mod tests {
     #[test]
     fn test_gear_reverse_maps_to_minus_one() -> TestResult {
         let mut data = make_valid_ac_packet();
-        data[OFF_GEAR..OFF_GEAR + 4].copy_from_slice(&0i32.to_le_bytes());
+        data[RtCarInfo::GEAR..RtCarInfo::GEAR + 4].copy_from_slice(&0i32.to_le_bytes());
         let result = parse_ac_packet(&data)?;
         assert_eq!(result.gear, -1, "AC gear 0 (reverse) must normalize to -1");
         Ok(())
@@ -540,7 +549,7 @@ mod tests {
     #[test]
     fn test_gear_neutral_maps_to_zero() -> TestResult {
         let mut data = make_valid_ac_packet();
-        data[OFF_GEAR..OFF_GEAR + 4].copy_from_slice(&1i32.to_le_bytes());
+        data[RtCarInfo::GEAR..RtCarInfo::GEAR + 4].copy_from_slice(&1i32.to_le_bytes());
         let result = parse_ac_packet(&data)?;
         assert_eq!(result.gear, 0, "AC gear 1 (neutral) must normalize to 0");
         Ok(())
@@ -549,7 +558,7 @@ mod tests {
     #[test]
     fn test_gear_high_value_maps_correctly() -> TestResult {
         let mut data = make_valid_ac_packet();
-        data[OFF_GEAR..OFF_GEAR + 4].copy_from_slice(&7i32.to_le_bytes());
+        data[RtCarInfo::GEAR..RtCarInfo::GEAR + 4].copy_from_slice(&7i32.to_le_bytes());
         let result = parse_ac_packet(&data)?;
         assert_eq!(result.gear, 6, "AC gear 7 must normalize to 6");
         Ok(())
@@ -558,7 +567,7 @@ mod tests {
     #[test]
     fn test_gear_absurd_value_no_panic() -> TestResult {
         let mut data = make_valid_ac_packet();
-        data[OFF_GEAR..OFF_GEAR + 4].copy_from_slice(&255i32.to_le_bytes());
+        data[RtCarInfo::GEAR..RtCarInfo::GEAR + 4].copy_from_slice(&255i32.to_le_bytes());
         let result = parse_ac_packet(&data)?;
         // 255 - 1 = 254, clamped to i8::MAX = 127
         assert_eq!(result.gear, 127, "AC gear 255 must clamp to i8::MAX (127)");
@@ -570,7 +579,7 @@ mod tests {
     #[test]
     fn test_clutch_value_parsed() -> TestResult {
         let mut data = make_valid_ac_packet();
-        data[OFF_CLUTCH..OFF_CLUTCH + 4].copy_from_slice(&0.5f32.to_le_bytes());
+        data[RtCarInfo::CLUTCH..RtCarInfo::CLUTCH + 4].copy_from_slice(&0.5f32.to_le_bytes());
         let result = parse_ac_packet(&data)?;
         assert!(
             (result.clutch - 0.5).abs() < 0.001,
@@ -626,12 +635,12 @@ mod tests {
     fn test_nan_in_float_fields_defaults_to_zero() -> TestResult {
         let mut data = vec![0u8; AC_RTCARINFO_SIZE];
         let nan_bytes = f32::NAN.to_le_bytes();
-        data[OFF_SPEED_MS..OFF_SPEED_MS + 4].copy_from_slice(&nan_bytes);
-        data[OFF_RPM..OFF_RPM + 4].copy_from_slice(&nan_bytes);
-        data[OFF_GAS..OFF_GAS + 4].copy_from_slice(&nan_bytes);
-        data[OFF_BRAKE..OFF_BRAKE + 4].copy_from_slice(&nan_bytes);
-        data[OFF_CLUTCH..OFF_CLUTCH + 4].copy_from_slice(&nan_bytes);
-        data[OFF_STEER..OFF_STEER + 4].copy_from_slice(&nan_bytes);
+        data[RtCarInfo::SPEED_MS..RtCarInfo::SPEED_MS + 4].copy_from_slice(&nan_bytes);
+        data[RtCarInfo::RPM..RtCarInfo::RPM + 4].copy_from_slice(&nan_bytes);
+        data[RtCarInfo::GAS..RtCarInfo::GAS + 4].copy_from_slice(&nan_bytes);
+        data[RtCarInfo::BRAKE..RtCarInfo::BRAKE + 4].copy_from_slice(&nan_bytes);
+        data[RtCarInfo::CLUTCH..RtCarInfo::CLUTCH + 4].copy_from_slice(&nan_bytes);
+        data[RtCarInfo::STEER..RtCarInfo::STEER + 4].copy_from_slice(&nan_bytes);
 
         let result = parse_ac_packet(&data)?;
         assert_eq!(result.speed_ms, 0.0, "NaN speed must default to 0.0");
@@ -648,9 +657,9 @@ mod tests {
         let mut data = vec![0u8; AC_RTCARINFO_SIZE];
         let inf_bytes = f32::INFINITY.to_le_bytes();
         let neg_inf_bytes = f32::NEG_INFINITY.to_le_bytes();
-        data[OFF_SPEED_MS..OFF_SPEED_MS + 4].copy_from_slice(&inf_bytes);
-        data[OFF_RPM..OFF_RPM + 4].copy_from_slice(&neg_inf_bytes);
-        data[OFF_STEER..OFF_STEER + 4].copy_from_slice(&inf_bytes);
+        data[RtCarInfo::SPEED_MS..RtCarInfo::SPEED_MS + 4].copy_from_slice(&inf_bytes);
+        data[RtCarInfo::RPM..RtCarInfo::RPM + 4].copy_from_slice(&neg_inf_bytes);
+        data[RtCarInfo::STEER..RtCarInfo::STEER + 4].copy_from_slice(&inf_bytes);
 
         let result = parse_ac_packet(&data)?;
         assert_eq!(result.speed_ms, 0.0, "Infinity speed must default to 0.0");
@@ -706,7 +715,7 @@ mod proptest_tests {
         #[test]
         fn parse_ac_packet_speed_always_nonneg(speed_ms in 0.0f32..=100.0f32) {
             let mut data = vec![0u8; AC_RTCARINFO_SIZE];
-            data[OFF_SPEED_MS..OFF_SPEED_MS + 4].copy_from_slice(&speed_ms.to_le_bytes());
+            data[RtCarInfo::SPEED_MS..RtCarInfo::SPEED_MS + 4].copy_from_slice(&speed_ms.to_le_bytes());
             let t = parse_ac_packet(&data).map_err(|e| TestCaseError::fail(format!("{e:?}")))?;
             prop_assert!(t.speed_ms >= 0.0);
         }
@@ -714,7 +723,7 @@ mod proptest_tests {
         #[test]
         fn parse_ac_packet_steering_clamped(steer in any::<f32>()) {
             let mut data = vec![0u8; AC_RTCARINFO_SIZE];
-            data[OFF_STEER..OFF_STEER + 4].copy_from_slice(&steer.to_le_bytes());
+            data[RtCarInfo::STEER..RtCarInfo::STEER + 4].copy_from_slice(&steer.to_le_bytes());
             if let Ok(result) = parse_ac_packet(&data) {
                 prop_assert!(result.steering_angle >= -1.0);
                 prop_assert!(result.steering_angle <= 1.0);
@@ -724,7 +733,7 @@ mod proptest_tests {
         #[test]
         fn parse_ac_packet_rpm_nonneg_on_valid_input(rpm in 0.0f32..=20000.0f32) {
             let mut data = vec![0u8; AC_RTCARINFO_SIZE];
-            data[OFF_RPM..OFF_RPM + 4].copy_from_slice(&rpm.to_le_bytes());
+            data[RtCarInfo::RPM..RtCarInfo::RPM + 4].copy_from_slice(&rpm.to_le_bytes());
             let result = parse_ac_packet(&data);
             prop_assert!(result.is_ok());
             let t = result.map_err(|e| TestCaseError::fail(format!("{e:?}")))?;
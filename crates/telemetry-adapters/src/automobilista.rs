@@ -409,6 +409,10 @@ impl TelemetryAdapter for Automobilista1Adapter {
         self.update_rate
     }
 
+    fn prefers_dedup(&self) -> bool {
+        true
+    }
+
     async fn is_game_running(&self) -> Result<bool> {
         Ok(Self::is_ams1_running())
     }
@@ -24,13 +24,16 @@
 //! - Port is user-configurable in Options > Other > Protocols; no fixed default in game.
 #![cfg_attr(not(windows), allow(unused, dead_code))]
 
+use crate::raw_capture::{RawCaptureSink, RawCaptureSlot};
+use crate::traffic::TrafficAccountant;
 use crate::{
-    NormalizedTelemetry, TelemetryAdapter, TelemetryFlags, TelemetryFrame, TelemetryReceiver,
-    TelemetryValue, telemetry_now_ns,
+    FieldUnit, NormalizedTelemetry, PacketMatch, TelemetryAdapter, TelemetryFlags, TelemetryFrame,
+    TelemetryReceiver, TelemetryValue, Unit, UnitManifest, telemetry_now_ns,
 };
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::net::UdpSocket as TokioUdpSocket;
 use tokio::sync::mpsc;
@@ -43,6 +46,7 @@ const DEFAULT_BEAMNG_PORT: u16 = 4444;
 /// Verified against: documentation.beamng.com/modding/protocols/ and LFS InSim.txt.
 /// With `id` (i32) the packet is 96 bytes; without it, 92 bytes.
 const OUTGAUGE_PACKET_SIZE: usize = 92;
+const OUTGAUGE_ID_PACKET_SIZE: usize = OUTGAUGE_PACKET_SIZE + 4;
 const MAX_PACKET_SIZE: usize = 256;
 
 // OutGauge byte offsets — verified 2025-07 against:
@@ -150,6 +154,7 @@ fn parse_outgauge_packet(data: &[u8]) -> Result<NormalizedTelemetry> {
 pub struct BeamNGAdapter {
     bind_port: u16,
     update_rate: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for BeamNGAdapter {
@@ -163,6 +168,7 @@ impl BeamNGAdapter {
         Self {
             bind_port: DEFAULT_BEAMNG_PORT,
             update_rate: Duration::from_millis(16),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -172,13 +178,48 @@ impl BeamNGAdapter {
     }
 }
 
+/// BeamNG OutGauge units.
+const UNIT_MANIFEST: UnitManifest = UnitManifest::new(&[
+    FieldUnit::native("speed_ms", Unit::MetersPerSecond),
+    FieldUnit::native("rpm", Unit::Rpm),
+    FieldUnit::native("throttle", Unit::Fraction),
+    FieldUnit::native("brake", Unit::Fraction),
+    FieldUnit::native("clutch", Unit::Fraction),
+    FieldUnit::native("fuel_percent", Unit::Fraction),
+    FieldUnit::native("engine_temp_c", Unit::Celsius),
+    FieldUnit::native("turbo_bar", Unit::Bar),
+    FieldUnit::native("oil_pressure_bar", Unit::Bar),
+    FieldUnit::native("oil_temp_c", Unit::Celsius),
+]);
+
 #[async_trait]
 impl TelemetryAdapter for BeamNGAdapter {
     fn game_id(&self) -> &str {
         "beamng_drive"
     }
 
+    fn unit_manifest(&self) -> Option<UnitManifest> {
+        Some(UNIT_MANIFEST)
+    }
+
+    fn udp_port(&self) -> Option<u16> {
+        Some(self.bind_port)
+    }
+
+    fn recognize_packet(&self, raw: &[u8]) -> Option<PacketMatch> {
+        // OutGauge's `car` field is a short ASCII name, NUL-padded.
+        let car = raw.get(4..8)?;
+        let ascii_name = car.iter().all(|b| *b == 0 || b.is_ascii_graphic());
+        let sized = matches!(raw.len(), OUTGAUGE_PACKET_SIZE | OUTGAUGE_ID_PACKET_SIZE);
+        (sized && ascii_name).then(|| PacketMatch::new(0.8, "OutGauge format"))
+    }
+
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(100);
         let bind_port = self.bind_port;
         let update_rate = self.update_rate;
@@ -197,7 +238,9 @@ impl TelemetryAdapter for BeamNGAdapter {
             let mut frame_seq = 0u64;
 
             loop {
-                match tokio::time::timeout(update_rate * 10, socket.recv(&mut buf)).await {
+                match tokio::time::timeout(update_rate * 10, raw_capture.recv(&socket, &mut buf))
+                    .await
+                {
                     Ok(Ok(len)) => match parse_outgauge_packet(&buf[..len]) {
                         Ok(normalized) => {
                             let frame =
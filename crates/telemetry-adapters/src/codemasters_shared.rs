@@ -7,8 +7,13 @@
 //! This module extracts the common offset constants and parsing logic so that each
 //! game-specific adapter can delegate to a single implementation.
 
-use crate::{NormalizedTelemetry, TelemetryFlags, TelemetryValue};
+use crate::codemasters_udp::CustomUdpSpec;
+use crate::udp_broker::NotMyPacket;
+use crate::{
+    ExtendedKey, FieldUnit, NormalizedTelemetry, PacketMatch, TelemetryFlags, Unit, UnitManifest,
+};
 use anyhow::{Result, anyhow};
+use openracing_byte_reader::ByteReader;
 
 // ── Mode 1 packet layout ────────────────────────────────────────────────────
 
@@ -32,6 +37,7 @@ pub const OFF_GFORCE_LON: usize = 140;
 pub const OFF_CURRENT_LAP: usize = 144;
 pub const OFF_RPM: usize = 148;
 pub const OFF_CAR_POSITION: usize = 156;
+pub const OFF_DRS: usize = 168;
 pub const OFF_FUEL_IN_TANK: usize = 180;
 pub const OFF_FUEL_CAPACITY: usize = 184;
 pub const OFF_IN_PIT: usize = 188;
@@ -48,14 +54,110 @@ pub const FFB_LAT_G_MAX: f32 = 3.0;
 
 /// Read a little-endian `f32` from `data` at `offset`. Returns `None` if out of bounds.
 pub fn read_f32(data: &[u8], offset: usize) -> Option<f32> {
-    data.get(offset..offset + 4)
-        .and_then(|b| b.try_into().ok())
-        .map(f32::from_le_bytes)
+    ByteReader::new(data)
+        .f32_le_at(offset)
+        .ok()
         .filter(|v| v.is_finite())
 }
 
+/// Apply a Codemasters FIA flag value (`vehicleFiaFlags` or a marshal
+/// zone's `zoneFlag`) to `flags`.
+///
+/// `-1` (unknown) and `0` (none) leave `flags` alone. Yellow and red clear
+/// the green flag, and green never overrides a caution already applied.
+pub(crate) fn apply_fia_flag(flags: &mut TelemetryFlags, fia_flag: i8) {
+    match fia_flag {
+        1 => {
+            flags.green_flag = !(flags.yellow_flag
+                || flags.red_flag
+                || flags.safety_car
+                || flags.virtual_safety_car);
+        }
+        2 => flags.blue_flag = true,
+        3 => {
+            flags.yellow_flag = true;
+            flags.green_flag = false;
+        }
+        4 => {
+            flags.red_flag = true;
+            flags.green_flag = false;
+        }
+        _ => {}
+    }
+}
+
+/// Apply a Codemasters `safetyCarStatus` (0 none, 1 full, 2 virtual,
+/// 3 formation lap) to `flags`.
+pub(crate) fn apply_safety_car_status(flags: &mut TelemetryFlags, status: u8) {
+    match status {
+        1 => {
+            flags.safety_car = true;
+            flags.green_flag = false;
+        }
+        2 => {
+            flags.virtual_safety_car = true;
+            flags.green_flag = false;
+        }
+        3 => flags.formation_lap = true,
+        _ => {}
+    }
+}
+
+/// Recognize a Mode 1 datagram: exactly 264 bytes with a plausible gear and
+/// engine speed where Mode 1 puts them.
+pub(crate) fn recognize_mode1(data: &[u8]) -> Option<PacketMatch> {
+    if data.len() != MIN_PACKET_SIZE {
+        return None;
+    }
+    let gear = read_f32(data, OFF_GEAR)?;
+    let rpm = read_f32(data, OFF_RPM)?;
+    ((-1.0..=10.0).contains(&gear) && (0.0..=25_000.0).contains(&rpm))
+        .then(|| PacketMatch::new(0.7, "Codemasters Mode 1 format"))
+}
+
+/// Recognize a legacy datagram of any `extradata` level. Lower levels carry
+/// less to check, so they match with less confidence than the full layout.
+pub(crate) fn recognize_extradata(data: &[u8]) -> Option<PacketMatch> {
+    match CustomUdpSpec::detect_mode(data.len())? {
+        3 => recognize_mode1(data),
+        mode => {
+            let gear = read_f32(data, OFF_GEAR)?;
+            let rpm = read_f32(data, OFF_RPM)?;
+            ((-1.0..=10.0).contains(&gear) && (0.0..=25_000.0).contains(&rpm))
+                .then(|| PacketMatch::new(0.6, format!("Codemasters extradata={mode} format")))
+        }
+    }
+}
+
 // ── Shared Mode 1 parser ─────────────────────────────────────────────────────
 
+/// Units of Codemasters "extradata" mode 1 packets; steering arrives as a
+/// lock fraction.
+pub(crate) const MODE1_UNIT_MANIFEST: UnitManifest = UnitManifest::new(&[
+    FieldUnit::native("speed_ms", Unit::MetersPerSecond),
+    FieldUnit::native("rpm", Unit::Rpm),
+    FieldUnit::native("max_rpm", Unit::Rpm),
+    FieldUnit::native("throttle", Unit::Fraction),
+    FieldUnit::native("brake", Unit::Fraction),
+    FieldUnit::native("fuel_percent", Unit::Fraction),
+    FieldUnit::native("ffb_scalar", Unit::Fraction),
+    FieldUnit::native("rpm_fraction", Unit::Fraction),
+    FieldUnit::converted("steering_angle", Unit::Fraction, Unit::Radians),
+    FieldUnit::native("lateral_g", Unit::StandardGravity),
+    FieldUnit::native("longitudinal_g", Unit::StandardGravity),
+    FieldUnit::native("tire_temps_c", Unit::Celsius),
+    FieldUnit::native("tire_pressures_psi", Unit::Psi),
+    FieldUnit::native("last_lap_time_s", Unit::Seconds),
+    FieldUnit::native("wheel_speed_fl", Unit::MetersPerSecond),
+    FieldUnit::native("wheel_speed_fr", Unit::MetersPerSecond),
+    FieldUnit::native("wheel_speed_rl", Unit::MetersPerSecond),
+    FieldUnit::native("wheel_speed_rr", Unit::MetersPerSecond),
+    FieldUnit::native("lap_distance_m", Unit::Meters),
+    FieldUnit::native("track_length_m", Unit::Meters),
+    FieldUnit::native("sector_1_time_s", Unit::Seconds),
+    FieldUnit::native("sector_2_time_s", Unit::Seconds),
+]);
+
 /// Parse a Codemasters Mode 1 UDP packet into [`NormalizedTelemetry`].
 ///
 /// `game_label` is used only for the error message on short packets (e.g.
@@ -152,8 +254,12 @@ pub fn parse_codemasters_mode1_common(
 
     let last_lap_time_s = read_f32(data, OFF_LAST_LAP_TIME).unwrap_or(0.0).max(0.0);
 
+    // Only the legacy F1 titles fill this slot; the rally games send zero.
+    let drs_active = read_f32(data, OFF_DRS).is_some_and(|v| v >= 0.5);
+
     let flags = TelemetryFlags {
         in_pits,
+        drs_active,
         ..Default::default()
     };
 
@@ -174,21 +280,89 @@ pub fn parse_codemasters_mode1_common(
         .tire_pressures_psi(tire_pressures_psi)
         .num_gears(num_gears)
         .last_lap_time_s(last_lap_time_s)
-        .flags(flags)
-        .extended("wheel_speed_fl".to_string(), TelemetryValue::Float(ws_fl))
-        .extended("wheel_speed_fr".to_string(), TelemetryValue::Float(ws_fr))
-        .extended("wheel_speed_rl".to_string(), TelemetryValue::Float(ws_rl))
-        .extended("wheel_speed_rr".to_string(), TelemetryValue::Float(ws_rr));
-
-    if max_rpm > 0.0 {
-        let rpm_fraction = (rpm_raw / max_rpm).clamp(0.0, 1.0);
-        builder = builder.max_rpm(max_rpm).extended(
-            "rpm_fraction".to_string(),
-            TelemetryValue::Float(rpm_fraction),
-        );
+        .flags(flags);
+
+    let rpm_fraction = if max_rpm > 0.0 {
+        builder = builder.max_rpm(max_rpm);
+        Some((rpm_raw / max_rpm).clamp(0.0, 1.0))
+    } else {
+        None
+    };
+
+    let mut telemetry = builder.build();
+    for (key, speed) in [
+        (ExtendedKey::WHEEL_SPEED_FL, ws_fl),
+        (ExtendedKey::WHEEL_SPEED_FR, ws_fr),
+        (ExtendedKey::WHEEL_SPEED_RL, ws_rl),
+        (ExtendedKey::WHEEL_SPEED_RR, ws_rr),
+    ] {
+        key.set(&mut telemetry, speed)?;
     }
+    if let Some(rpm_fraction) = rpm_fraction {
+        ExtendedKey::RPM_FRACTION.set(&mut telemetry, rpm_fraction)?;
+    }
+
+    Ok(telemetry)
+}
 
-    Ok(builder.build())
+/// Parse a legacy packet at whichever `extradata` level its length
+/// identifies, for games that let the player choose the level.
+///
+/// Each level is a prefix of the full 264-byte layout, so a shorter packet is
+/// zero-filled to full length and the fields it lacks read as not reported.
+/// The lap distance, and the track length and sector times of the higher
+/// levels, land in `extended`. A packet shorter than the full layout whose
+/// length matches no level is some other protocol and fails with
+/// [`NotMyPacket`].
+pub fn parse_codemasters_extradata(data: &[u8], game_label: &str) -> Result<NormalizedTelemetry> {
+    let mode = match CustomUdpSpec::detect_mode(data.len()) {
+        Some(mode) => mode,
+        // Bytes past the full layout have always been ignored.
+        None if data.len() > MIN_PACKET_SIZE => 3,
+        None => {
+            return Err(NotMyPacket::new(
+                game_label,
+                format!(
+                    "{} packet of {} bytes matches no extradata level",
+                    game_label,
+                    data.len()
+                ),
+            )
+            .into());
+        }
+    };
+    let spec = match mode {
+        0 => CustomUdpSpec::mode0(),
+        1 => CustomUdpSpec::mode1(),
+        2 => CustomUdpSpec::mode2(),
+        _ => CustomUdpSpec::mode3(),
+    };
+    let decoded = spec.decode(data)?;
+
+    let mut telemetry = if data.len() < MIN_PACKET_SIZE {
+        let mut full = vec![0u8; MIN_PACKET_SIZE];
+        full[..data.len()].copy_from_slice(data);
+        parse_codemasters_mode1_common(&full, game_label)?
+    } else {
+        parse_codemasters_mode1_common(data, game_label)?
+    };
+
+    let channel = |name: &str| decoded.values.get(name).copied();
+    if let Some(distance) = channel("lapdistance") {
+        ExtendedKey::LAP_DISTANCE_M.set(&mut telemetry, distance)?;
+    }
+    if let Some(length) = channel("tracklength").filter(|length| *length > 0.0) {
+        ExtendedKey::TRACK_LENGTH_M.set(&mut telemetry, length)?;
+    }
+    for (key, name) in [
+        (ExtendedKey::SECTOR_1_TIME_S, "sector1time"),
+        (ExtendedKey::SECTOR_2_TIME_S, "sector2time"),
+    ] {
+        if let Some(time) = channel(name) {
+            key.set(&mut telemetry, time.max(0.0))?;
+        }
+    }
+    Ok(telemetry)
 }
 
 // ── Tests ────────────────────────────────────────────────────────────────────
@@ -196,6 +370,7 @@ pub fn parse_codemasters_mode1_common(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::TelemetryValue;
 
     fn make_packet(size: usize) -> Vec<u8> {
         vec![0u8; size]
@@ -314,6 +489,42 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn codemasters_shared_drs_flag() -> Result<(), Box<dyn std::error::Error>> {
+        let mut raw = make_packet(MIN_PACKET_SIZE);
+        assert!(
+            !parse_codemasters_mode1_common(&raw, "Test")?
+                .flags
+                .drs_active
+        );
+        write_f32_le(&mut raw, OFF_DRS, 1.0);
+        let t = parse_codemasters_mode1_common(&raw, "Test")?;
+        assert!(t.flags.drs_active);
+        Ok(())
+    }
+
+    #[test]
+    fn fia_flags_clear_green_only_for_cautions() {
+        let mut flags = TelemetryFlags::default();
+        apply_fia_flag(&mut flags, 2);
+        assert!(flags.blue_flag && flags.green_flag);
+
+        apply_fia_flag(&mut flags, 3);
+        assert!(flags.yellow_flag && !flags.green_flag);
+        // Green in a later zone does not lift the yellow already shown.
+        apply_fia_flag(&mut flags, 1);
+        assert!(!flags.green_flag);
+
+        let mut flags = TelemetryFlags::default();
+        apply_fia_flag(&mut flags, 4);
+        assert!(flags.red_flag && !flags.green_flag);
+
+        let mut flags = TelemetryFlags::default();
+        apply_fia_flag(&mut flags, -1);
+        apply_safety_car_status(&mut flags, 0);
+        assert_eq!(flags, TelemetryFlags::default());
+    }
+
     #[test]
     fn codemasters_shared_rpm_and_fraction() -> Result<(), Box<dyn std::error::Error>> {
         let mut raw = make_packet(MIN_PACKET_SIZE);
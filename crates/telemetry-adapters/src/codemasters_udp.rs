@@ -1,6 +1,8 @@
 //! Codemasters-style custom UDP packet decoding and XML specification support.
-//! Supports the Dirt 4 / DiRT 4-legacy custom UDP format where each field is 4 bytes.
+//! Supports the Dirt 4 / DiRT 4-legacy custom UDP format where each field is 4 bytes,
+//! and describes the legacy `extradata` packets of DiRT Rally 2.0 and Dirt 4 the same way.
 use anyhow::{Context, Result, anyhow};
+use openracing_byte_reader::ByteReader;
 use quick_xml::Reader;
 use quick_xml::events::{BytesStart, Event};
 use std::collections::HashMap;
@@ -9,6 +11,83 @@ use std::path::Path;
 
 const FIELD_SIZE_BYTES: usize = 4;
 
+/// Channels of the legacy Codemasters UDP packet in wire order, all `f32`.
+/// Each `extradata` level sends a longer prefix of this list.
+const EXTRADATA_FIELDS: [&str; 66] = [
+    "time",
+    "lap_time",
+    "lap_distance",
+    "total_distance",
+    "pos_x",
+    "pos_y",
+    "pos_z",
+    "speed",
+    "vel_x",
+    "vel_y",
+    "vel_z",
+    "roll_x",
+    "roll_y",
+    "roll_z",
+    "pitch_x",
+    "pitch_y",
+    "pitch_z",
+    "suspension_position_rl",
+    "suspension_position_rr",
+    "suspension_position_fl",
+    "suspension_position_fr",
+    "suspension_velocity_rl",
+    "suspension_velocity_rr",
+    "suspension_velocity_fl",
+    "suspension_velocity_fr",
+    "wheel_speed_rl",
+    "wheel_speed_rr",
+    "wheel_speed_fl",
+    "wheel_speed_fr",
+    "throttle_input",
+    "steering_input",
+    "brake_input",
+    "clutch_input",
+    "gear",
+    "gforce_lat",
+    "gforce_lon",
+    "lap",
+    "engine_rate",
+    // extradata=1
+    "sli_pro_native_support",
+    "race_position",
+    "kers_level",
+    "kers_max_level",
+    "drs",
+    "traction_control",
+    "anti_lock_brakes",
+    "fuel_in_tank",
+    "fuel_capacity",
+    "in_pits",
+    // extradata=2
+    "sector",
+    "sector1_time",
+    "sector2_time",
+    "brake_temp_rl",
+    "brake_temp_rr",
+    "brake_temp_fl",
+    "brake_temp_fr",
+    "tyre_pressure_rl",
+    "tyre_pressure_rr",
+    "tyre_pressure_fl",
+    "tyre_pressure_fr",
+    // extradata=3
+    "team_info",
+    "total_laps",
+    "track_length",
+    "last_lap_time",
+    "max_rpm",
+    "idle_rpm",
+    "max_gears",
+];
+
+/// Number of leading [`EXTRADATA_FIELDS`] sent at `extradata` levels 0 to 3.
+const EXTRADATA_FIELD_COUNTS: [usize; 4] = [38, 48, 59, 66];
+
 #[derive(Debug, Clone)]
 pub struct FieldSpec {
     pub channel: String,
@@ -71,6 +150,8 @@ impl Default for CustomUdpSpec {
 }
 
 impl CustomUdpSpec {
+    /// Built-in preset for the custom UDP `mode` setting of Dirt 5 and F1.
+    /// These are not the legacy `extradata` layouts; see [`Self::mode0`].
     pub fn from_mode(mode: u8) -> Self {
         match mode {
             0 => builtin_mode_spec(0),
@@ -81,6 +162,39 @@ impl CustomUdpSpec {
         }
     }
 
+    /// Legacy `extradata=0` packet: motion, inputs, gear, lap and engine
+    /// rate (152 bytes).
+    pub fn mode0() -> Self {
+        extradata_spec(0)
+    }
+
+    /// Legacy `extradata=1` packet: adds race position, KERS/DRS, driver
+    /// aids, fuel and the pit flag (192 bytes).
+    pub fn mode1() -> Self {
+        extradata_spec(1)
+    }
+
+    /// Legacy `extradata=2` packet: adds the sector and its split times,
+    /// brake temperatures and tyre pressures (236 bytes).
+    pub fn mode2() -> Self {
+        extradata_spec(2)
+    }
+
+    /// Legacy `extradata=3` packet: adds lap counts, track length, last lap
+    /// time and the engine and gearbox limits (264 bytes).
+    pub fn mode3() -> Self {
+        extradata_spec(3)
+    }
+
+    /// The legacy `extradata` level whose packet is exactly `packet_len`
+    /// bytes long, if any.
+    pub fn detect_mode(packet_len: usize) -> Option<u8> {
+        (0u8..)
+            .zip(EXTRADATA_FIELD_COUNTS)
+            .find(|&(_, count)| count * FIELD_SIZE_BYTES == packet_len)
+            .map(|(mode, _)| mode)
+    }
+
     pub fn from_xml_path<P: AsRef<Path>>(path: P) -> Result<Self> {
         let raw = fs::read_to_string(path.as_ref()).with_context(|| {
             format!(
@@ -107,13 +221,12 @@ impl CustomUdpSpec {
 
         let mut values = HashMap::with_capacity(self.fields.len());
         let mut fourcc = None;
-        let mut offset = 0usize;
+        let mut reader = ByteReader::new(raw);
 
         for field in &self.fields {
-            let chunk = &raw[offset..offset + FIELD_SIZE_BYTES];
-            let bytes: [u8; FIELD_SIZE_BYTES] = chunk
-                .try_into()
-                .map_err(|_| anyhow!("failed to read 4-byte channel: {}", field.channel))?;
+            let bytes: [u8; FIELD_SIZE_BYTES] = reader
+                .array()
+                .map_err(|e| anyhow!("failed to read channel {}: {e}", field.channel))?;
             let value = match field.field_type {
                 FieldType::U32 => Some((u32::from_le_bytes(bytes) as f32) * field.scale),
                 FieldType::I32 => Some((i32::from_le_bytes(bytes) as f32) * field.scale),
@@ -134,7 +247,6 @@ impl CustomUdpSpec {
             if let Some(value) = value {
                 values.insert(field.channel.clone(), value);
             }
-            offset += FIELD_SIZE_BYTES;
         }
 
         Ok(DecodedCodemastersPacket { values, fourcc })
@@ -400,6 +512,17 @@ fn builtin_mode_spec(mode: u8) -> CustomUdpSpec {
     CustomUdpSpec { fields }
 }
 
+/// `extradata` levels above 3 send nothing more, so they clamp to it.
+fn extradata_spec(mode: u8) -> CustomUdpSpec {
+    let count = EXTRADATA_FIELD_COUNTS[usize::from(mode.min(3))];
+    CustomUdpSpec {
+        fields: EXTRADATA_FIELDS[..count]
+            .iter()
+            .map(|name| field(name, FieldType::F32))
+            .collect(),
+    }
+}
+
 fn base_fields() -> Vec<FieldSpec> {
     vec![
         field("speed", FieldType::F32),
@@ -454,6 +577,34 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn extradata_channels_sit_at_the_shared_offsets() -> Result<()> {
+        use crate::codemasters_shared::{
+            MIN_PACKET_SIZE, OFF_GEAR, OFF_LAST_LAP_TIME, OFF_MAX_GEARS, OFF_MAX_RPM, OFF_RPM,
+            OFF_TYRES_PRESSURE_FL, OFF_WHEEL_SPEED_RL,
+        };
+
+        let spec = CustomUdpSpec::mode3();
+        assert_eq!(spec.expected_bytes(), MIN_PACKET_SIZE);
+        for (channel, offset) in [
+            ("wheelspeedrl", OFF_WHEEL_SPEED_RL),
+            ("gear", OFF_GEAR),
+            ("enginerate", OFF_RPM),
+            ("tyrepressurefl", OFF_TYRES_PRESSURE_FL),
+            ("lastlaptime", OFF_LAST_LAP_TIME),
+            ("maxrpm", OFF_MAX_RPM),
+            ("maxgears", OFF_MAX_GEARS),
+        ] {
+            let index = spec
+                .fields
+                .iter()
+                .position(|field| field.channel == channel)
+                .ok_or_else(|| anyhow!("no {channel} channel"))?;
+            assert_eq!(index * FIELD_SIZE_BYTES, offset, "{channel}");
+        }
+        Ok(())
+    }
+
     #[test]
     fn parse_xml_definition_with_attributes() -> Result<()> {
         let xml = r#"
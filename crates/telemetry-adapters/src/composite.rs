@@ -0,0 +1,548 @@
+//! Failover between redundant transports of one game.
+//!
+//! Several games publish the same telemetry through shared memory and over
+//! UDP. [`CompositeAdapter`] wraps one child adapter per transport, in
+//! priority order, and streams from the best one that works. When the active
+//! child fails mid-session (its stream ends, or its [`DisconnectionTracker`]
+//! sees it go quiet) the composite moves to the next transport. While on a
+//! fallback it periodically probes the preferred transports and switches back
+//! once one delivers a run of frames again. Every switch is announced as a
+//! [`TransportSwitched`] event to
+//! [`CompositeAdapter::subscribe_transport_switches`] receivers, also reached
+//! through [`TelemetryAdapter::transport_switches`], and frames are
+//! renumbered so the sequence stays continuous whichever child produced them.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use racing_wheel_telemetry_core::{
+    DEFAULT_DISCONNECTION_TIMEOUT_MS, DisconnectionConfig, DisconnectionTracker,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, MissedTickBehavior};
+use tracing::{debug, info, warn};
+
+use crate::raw_capture::RawCaptureSink;
+use crate::traffic::TrafficAccountant;
+use crate::udp_listener::ListenMode;
+use crate::{
+    NormalizedTelemetry, PacketMatch, TelemetryAdapter, TelemetryFrame, TelemetryReceiver,
+    UnitManifest, telemetry_now_ns,
+};
+
+/// Capacity of each [`CompositeAdapter::subscribe_transport_switches`]
+/// receiver.
+pub const TRANSPORT_EVENT_CAPACITY: usize = 32;
+
+/// When a [`CompositeAdapter`] gives up on a transport and when it returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FailoverConfig {
+    /// Silence on the active transport, or before its first frame, that
+    /// counts as a failure.
+    pub failure_timeout: Duration,
+    /// Time on a fallback between probes of a preferred transport.
+    pub probe_interval: Duration,
+    /// How long one probe may take to deliver `recovery_frames`.
+    pub probe_window: Duration,
+    /// Frames a probed transport must deliver before the composite switches
+    /// back to it.
+    pub recovery_frames: u32,
+}
+
+impl Default for FailoverConfig {
+    fn default() -> Self {
+        Self {
+            failure_timeout: Duration::from_millis(DEFAULT_DISCONNECTION_TIMEOUT_MS),
+            probe_interval: Duration::from_secs(10),
+            probe_window: Duration::from_secs(2),
+            recovery_frames: 30,
+        }
+    }
+}
+
+/// The composite moved from one transport to another.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransportSwitched {
+    pub game_id: String,
+    /// Label of the transport given up.
+    pub from: String,
+    /// Label of the transport now streaming.
+    pub to: String,
+    pub reason: String,
+    /// [`telemetry_now_ns`] at the switch.
+    pub timestamp_ns: u64,
+}
+
+#[derive(Clone)]
+struct Transport {
+    label: String,
+    adapter: Arc<dyn TelemetryAdapter>,
+}
+
+/// One game read through several transports, preferring the first.
+pub struct CompositeAdapter {
+    game_id: String,
+    transports: Vec<Transport>,
+    config: FailoverConfig,
+    events: broadcast::Sender<TransportSwitched>,
+    active: Arc<AtomicUsize>,
+    supervisor: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl CompositeAdapter {
+    /// A composite for `game_id` without transports yet.
+    pub fn new(game_id: impl Into<String>) -> Self {
+        Self {
+            game_id: game_id.into(),
+            transports: Vec::new(),
+            config: FailoverConfig::default(),
+            events: broadcast::channel(TRANSPORT_EVENT_CAPACITY).0,
+            active: Arc::default(),
+            supervisor: Mutex::new(None),
+        }
+    }
+
+    /// Append `adapter` as the next transport in priority order, named
+    /// `label` in switch events, e.g. "shared_memory" or "udp".
+    pub fn with_transport(
+        mut self,
+        label: impl Into<String>,
+        adapter: Box<dyn TelemetryAdapter>,
+    ) -> Self {
+        self.transports.push(Transport {
+            label: label.into(),
+            adapter: Arc::from(adapter),
+        });
+        self
+    }
+
+    pub fn with_failover(mut self, config: FailoverConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Labels of the transports, most preferred first.
+    pub fn transports(&self) -> Vec<&str> {
+        self.transports
+            .iter()
+            .map(|transport| transport.label.as_str())
+            .collect()
+    }
+
+    /// Label of the transport streaming now, or last streamed.
+    pub fn active_transport(&self) -> Option<&str> {
+        self.transports
+            .get(self.active.load(Ordering::Acquire))
+            .map(|transport| transport.label.as_str())
+    }
+
+    /// Receive every transport switch from now on.
+    pub fn subscribe_transport_switches(&self) -> broadcast::Receiver<TransportSwitched> {
+        self.events.subscribe()
+    }
+
+    fn active_adapter(&self) -> Option<&dyn TelemetryAdapter> {
+        let active = self.active.load(Ordering::Acquire);
+        self.transports
+            .get(active)
+            .or(self.transports.first())
+            .map(|transport| transport.adapter.as_ref())
+    }
+
+    fn stop_supervisor(&self) {
+        let supervisor = self
+            .supervisor
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        if let Some(supervisor) = supervisor {
+            supervisor.abort();
+        }
+    }
+}
+
+#[async_trait]
+impl TelemetryAdapter for CompositeAdapter {
+    fn game_id(&self) -> &str {
+        &self.game_id
+    }
+
+    async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        self.stop_supervisor();
+        let mut last_error = None;
+        let mut started = None;
+        for (index, transport) in self.transports.iter().enumerate() {
+            match transport.adapter.start_monitoring().await {
+                Ok(stream) => {
+                    started = Some((index, stream));
+                    break;
+                }
+                Err(err) => {
+                    debug!(game_id = %self.game_id, transport = %transport.label, error = %err, "Transport failed to start");
+                    last_error = Some(err);
+                }
+            }
+        }
+        let Some((index, stream)) = started else {
+            return Err(match last_error {
+                Some(err) => err.context(format!("no transport of {} started", self.game_id)),
+                None => anyhow::anyhow!("{} has no transports", self.game_id),
+            });
+        };
+        info!(
+            game_id = %self.game_id,
+            transport = %self.transports[index].label,
+            "Composite adapter streaming"
+        );
+        self.active.store(index, Ordering::Release);
+
+        let (tx, rx) = mpsc::channel(100);
+        let supervisor = Supervisor {
+            game_id: self.game_id.clone(),
+            transports: self.transports.clone(),
+            config: self.config,
+            events: self.events.clone(),
+            active: Arc::clone(&self.active),
+            current: index,
+            stream,
+            tracker: tracker(&self.game_id, &self.config),
+            started_at: Instant::now(),
+            probe: None,
+            probe_round: 0,
+            next_probe: Instant::now() + self.config.probe_interval,
+            sequence: 0,
+        };
+        *self
+            .supervisor
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(tokio::spawn(supervisor.run(tx)));
+        Ok(rx)
+    }
+
+    async fn stop_monitoring(&self) -> Result<()> {
+        self.stop_supervisor();
+        for transport in &self.transports {
+            transport.adapter.stop_monitoring().await?;
+        }
+        Ok(())
+    }
+
+    /// The first transport that decodes `raw`.
+    fn normalize(&self, raw: &[u8]) -> Result<NormalizedTelemetry> {
+        let mut last_error = None;
+        for transport in &self.transports {
+            match transport.adapter.normalize(raw) {
+                Ok(normalized) => return Ok(normalized),
+                Err(err) => last_error = Some(err),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("{} has no transports", self.game_id)))
+    }
+
+    fn expected_update_rate(&self) -> Duration {
+        self.active_adapter()
+            .map_or(Duration::from_millis(16), |adapter| {
+                adapter.expected_update_rate()
+            })
+    }
+
+    async fn is_game_running(&self) -> Result<bool> {
+        match self.active_adapter() {
+            Some(adapter) => adapter.is_game_running().await,
+            None => Ok(false),
+        }
+    }
+
+    fn listen_mode(&self) -> Option<ListenMode> {
+        self.transports
+            .iter()
+            .find_map(|transport| transport.adapter.listen_mode())
+    }
+
+    fn unit_manifest(&self) -> Option<UnitManifest> {
+        self.active_adapter()?.unit_manifest()
+    }
+
+    fn udp_port(&self) -> Option<u16> {
+        self.transports
+            .iter()
+            .find_map(|transport| transport.adapter.udp_port())
+    }
+
+    fn recognize_packet(&self, raw: &[u8]) -> Option<PacketMatch> {
+        self.transports
+            .iter()
+            .filter_map(|transport| transport.adapter.recognize_packet(raw))
+            .max_by(|a, b| a.confidence.total_cmp(&b.confidence))
+    }
+
+    fn set_raw_capture(&self, sink: Option<Arc<RawCaptureSink>>) {
+        for transport in &self.transports {
+            transport.adapter.set_raw_capture(sink.clone());
+        }
+    }
+
+    fn traffic(&self) -> Option<Arc<TrafficAccountant>> {
+        self.transports
+            .iter()
+            .find_map(|transport| transport.adapter.traffic())
+    }
+
+    /// Decided once per monitoring session, so it holds for every
+    /// transport; deduplicating a stream without repeats drops nothing.
+    fn prefers_dedup(&self) -> bool {
+        self.transports
+            .iter()
+            .any(|transport| transport.adapter.prefers_dedup())
+    }
+
+    fn transport_switches(&self) -> Option<broadcast::Receiver<TransportSwitched>> {
+        Some(self.subscribe_transport_switches())
+    }
+}
+
+impl Drop for CompositeAdapter {
+    fn drop(&mut self) {
+        self.stop_supervisor();
+    }
+}
+
+fn tracker(game_id: &str, config: &FailoverConfig) -> DisconnectionTracker {
+    let timeout_ms = u64::try_from(config.failure_timeout.as_millis()).unwrap_or(u64::MAX);
+    DisconnectionTracker::new(game_id, DisconnectionConfig::with_timeout(timeout_ms))
+}
+
+/// A preferred transport being tried while a fallback streams.
+struct Probe {
+    index: usize,
+    stream: TelemetryReceiver,
+    frames: u32,
+    deadline: Instant,
+}
+
+/// Forwards the active child's frames and moves between children.
+struct Supervisor {
+    game_id: String,
+    transports: Vec<Transport>,
+    config: FailoverConfig,
+    events: broadcast::Sender<TransportSwitched>,
+    active: Arc<AtomicUsize>,
+    current: usize,
+    stream: TelemetryReceiver,
+    tracker: DisconnectionTracker,
+    started_at: Instant,
+    probe: Option<Probe>,
+    probe_round: usize,
+    next_probe: Instant,
+    sequence: u64,
+}
+
+impl Supervisor {
+    async fn run(mut self, tx: mpsc::Sender<TelemetryFrame>) {
+        let period = (self.config.failure_timeout / 4).max(Duration::from_millis(5));
+        let mut tick = tokio::time::interval(period);
+        tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                frame = self.stream.recv() => match frame {
+                    Some(mut frame) => {
+                        self.tracker.record_data_received();
+                        frame.sequence = self.sequence;
+                        self.sequence += 1;
+                        if tx.send(frame).await.is_err() {
+                            break;
+                        }
+                    }
+                    None => {
+                        if !self.fail_over("stream ended", &tx).await {
+                            break;
+                        }
+                    }
+                },
+                frame = probe_frame(&mut self.probe) => match frame {
+                    Some(_) => self.probe_frame().await,
+                    None => self.end_probe("stream ended").await,
+                },
+                _ = tick.tick() => {
+                    if !self.check(&tx).await {
+                        break;
+                    }
+                }
+                () = tx.closed() => break,
+            }
+        }
+        self.end_probe("monitoring stopped").await;
+        self.stop(self.current).await;
+    }
+
+    /// Periodic checks; false once the consumer is gone.
+    async fn check(&mut self, tx: &mpsc::Sender<TelemetryFrame>) -> bool {
+        let now = Instant::now();
+        let timeout_ms = self.config.failure_timeout.as_millis();
+        let was_connected = self.tracker.state().is_connected();
+        if was_connected && self.tracker.check_disconnection().is_disconnected() {
+            let reason = format!("no data for {timeout_ms} ms");
+            return self.fail_over(&reason, tx).await;
+        }
+        if !was_connected && now.duration_since(self.started_at) > self.config.failure_timeout {
+            let reason = format!("no data within {timeout_ms} ms of starting");
+            return self.fail_over(&reason, tx).await;
+        }
+
+        if self
+            .probe
+            .as_ref()
+            .is_some_and(|probe| now >= probe.deadline)
+        {
+            self.end_probe("too few frames").await;
+        }
+        if self.probe.is_none() && self.current > 0 && now >= self.next_probe {
+            self.start_probe().await;
+        }
+        true
+    }
+
+    /// Move off the active transport, trying the rest in priority order after
+    /// it, then the ones before it, then itself again. Waits a probe interval
+    /// between rounds in which nothing starts; false once the consumer is
+    /// gone.
+    async fn fail_over(&mut self, reason: &str, tx: &mpsc::Sender<TelemetryFrame>) -> bool {
+        warn!(
+            game_id = %self.game_id,
+            transport = %self.transports[self.current].label,
+            reason,
+            "Telemetry transport failed"
+        );
+        self.end_probe("failing over").await;
+        let from = self.current;
+        self.stop(from).await;
+        let count = self.transports.len();
+        loop {
+            for index in (from + 1..count).chain(0..=from) {
+                match self.transports[index].adapter.start_monitoring().await {
+                    Ok(stream) => {
+                        self.activate(index, stream, reason);
+                        return true;
+                    }
+                    Err(err) => debug!(
+                        game_id = %self.game_id,
+                        transport = %self.transports[index].label,
+                        error = %err,
+                        "Transport failed to start"
+                    ),
+                }
+            }
+            tokio::select! {
+                () = tokio::time::sleep(self.config.probe_interval) => {}
+                () = tx.closed() => return false,
+            }
+        }
+    }
+
+    async fn start_probe(&mut self) {
+        let index = self.probe_round % self.current;
+        self.probe_round += 1;
+        let now = Instant::now();
+        match self.transports[index].adapter.start_monitoring().await {
+            Ok(stream) => {
+                self.probe = Some(Probe {
+                    index,
+                    stream,
+                    frames: 0,
+                    deadline: now + self.config.probe_window,
+                });
+            }
+            Err(err) => {
+                debug!(
+                    game_id = %self.game_id,
+                    transport = %self.transports[index].label,
+                    error = %err,
+                    "Preferred transport still unavailable"
+                );
+                self.next_probe = now + self.config.probe_interval;
+            }
+        }
+    }
+
+    async fn probe_frame(&mut self) {
+        let Some(probe) = &mut self.probe else {
+            return;
+        };
+        probe.frames += 1;
+        if probe.frames < self.config.recovery_frames {
+            return;
+        }
+        let Some(probe) = self.probe.take() else {
+            return;
+        };
+        let from = self.current;
+        self.stop(from).await;
+        let reason = format!("recovered with {} frames", probe.frames);
+        self.activate(probe.index, probe.stream, &reason);
+    }
+
+    async fn end_probe(&mut self, reason: &str) {
+        let Some(probe) = self.probe.take() else {
+            return;
+        };
+        debug!(
+            game_id = %self.game_id,
+            transport = %self.transports[probe.index].label,
+            frames = probe.frames,
+            reason,
+            "Probe of preferred transport ended"
+        );
+        drop(probe.stream);
+        self.stop(probe.index).await;
+        self.next_probe = Instant::now() + self.config.probe_interval;
+    }
+
+    /// Stream from `index` from now on, announcing the switch if it is one.
+    fn activate(&mut self, index: usize, stream: TelemetryReceiver, reason: &str) {
+        let from = std::mem::replace(&mut self.current, index);
+        self.stream = stream;
+        self.active.store(index, Ordering::Release);
+        self.tracker = tracker(&self.game_id, &self.config);
+        self.started_at = Instant::now();
+        self.next_probe = self.started_at + self.config.probe_interval;
+        if from == index {
+            return;
+        }
+        let event = TransportSwitched {
+            game_id: self.game_id.clone(),
+            from: self.transports[from].label.clone(),
+            to: self.transports[index].label.clone(),
+            reason: reason.to_string(),
+            timestamp_ns: telemetry_now_ns(),
+        };
+        info!(
+            game_id = %event.game_id,
+            from = %event.from,
+            to = %event.to,
+            reason,
+            "Switched telemetry transport"
+        );
+        // Ignored: nobody may be subscribed.
+        let _ = self.events.send(event);
+    }
+
+    async fn stop(&self, index: usize) {
+        let transport = &self.transports[index];
+        if let Err(err) = transport.adapter.stop_monitoring().await {
+            debug!(game_id = %self.game_id, transport = %transport.label, error = %err, "Stopping transport failed");
+        }
+    }
+}
+
+/// Next frame of the probe, or never without one.
+async fn probe_frame(probe: &mut Option<Probe>) -> Option<TelemetryFrame> {
+    match probe {
+        Some(probe) => probe.stream.recv().await,
+        None => std::future::pending().await,
+    }
+}
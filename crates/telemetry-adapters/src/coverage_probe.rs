@@ -0,0 +1,263 @@
+//! Field coverage derived from what adapters actually decode.
+//!
+//! The "which game supports which field" table drifts whenever it is kept by
+//! hand. [`derive_coverage`] runs captured payloads through an adapter's
+//! [`TelemetryAdapter::normalize`] and marks a field covered once any sample
+//! sets it to something other than its default, so a zero reading (or a
+//! neutral gear) alone does not count. [`derive_coverage_all`] does that for
+//! every game with a fixture directory, and [`render_coverage_markdown`] turns
+//! the results into a table that is byte-for-byte stable for the same input,
+//! so docs can be regenerated and diffed in CI.
+//!
+//! # Fixture layout
+//!
+//! ```text
+//! <fixtures_dir>/<game_id>/*.bin    one raw payload per file
+//! <fixtures_dir>/<game_id>/*.orcap  a raw capture, one payload per record
+//! ```
+//!
+//! Directories not named after a registered game and files with other
+//! extensions are ignored.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use racing_wheel_telemetry_core::{
+    FlagCoverage, NormalizedTelemetry, TelemetryFieldCoverage, TelemetryFlags, TimingCoverage,
+};
+
+use crate::raw_capture::RawCaptureReader;
+use crate::{TelemetryAdapter, adapter_factories};
+
+/// Extension of single-payload fixture files.
+pub const PAYLOAD_EXTENSION: &str = "bin";
+/// Extension of raw capture fixture files.
+pub const CAPTURE_EXTENSION: &str = "orcap";
+
+/// Coverage of `adapter` over `samples`. Samples it cannot decode are
+/// skipped.
+pub fn derive_coverage(
+    adapter: &dyn TelemetryAdapter,
+    samples: &[Vec<u8>],
+) -> TelemetryFieldCoverage {
+    let mut coverage = empty_coverage(adapter.game_id());
+    let mut extended = BTreeSet::new();
+    for sample in samples {
+        let Ok(normalized) = adapter.normalize(sample) else {
+            continue;
+        };
+        observe(&mut coverage, &normalized);
+        extended.extend(normalized.extended.keys().cloned());
+    }
+    coverage.extended_fields = extended.into_iter().collect();
+    coverage
+}
+
+/// Coverage of every registered game with a fixture directory in
+/// `fixtures_dir`, sorted by game id.
+pub fn derive_coverage_all(fixtures_dir: impl AsRef<Path>) -> Result<Vec<TelemetryFieldCoverage>> {
+    let fixtures_dir = fixtures_dir.as_ref();
+    let mut coverages = Vec::new();
+    for (game_id, factory) in adapter_factories() {
+        let dir = fixtures_dir.join(game_id);
+        if !dir.is_dir() {
+            continue;
+        }
+        let samples = load_samples(&dir)?;
+        coverages.push(derive_coverage(factory().as_ref(), &samples));
+    }
+    coverages.sort_by(|a, b| a.game_id.cmp(&b.game_id));
+    Ok(coverages)
+}
+
+/// Markdown support table of `coverages`, one row per game sorted by game
+/// id, with a fixed set of columns.
+pub fn render_coverage_markdown(coverages: &[TelemetryFieldCoverage]) -> String {
+    let mut rows: Vec<_> = coverages.iter().collect();
+    rows.sort_by(|a, b| a.game_id.cmp(&b.game_id));
+
+    let mut table = String::from(
+        "| Game | RPM | Speed | Gear | Slip ratio | FFB scalar | Car | Track | Tires \
+         | Penalties | Game time | Timing | Flags | Extended fields |\n\
+         |---|:-:|:-:|:-:|:-:|:-:|:-:|:-:|:-:|:-:|:-:|---|---|---:|\n",
+    );
+    for coverage in rows {
+        let marks = [
+            coverage.rpm,
+            coverage.speed,
+            coverage.gear,
+            coverage.slip_ratio,
+            coverage.ffb_scalar,
+            coverage.car_id,
+            coverage.track_id,
+            coverage.tires,
+            coverage.penalties,
+            coverage.game_time,
+        ];
+        table.push_str(&format!("| `{}` |", coverage.game_id));
+        for covered in marks {
+            table.push_str(if covered { " ✓ |" } else { " |" });
+        }
+        table.push_str(&format!(
+            " {} | {} | {} |\n",
+            name_list(&timing_names(&coverage.timing)),
+            name_list(&flag_names(&coverage.flags)),
+            coverage.extended_fields.len()
+        ));
+    }
+    table
+}
+
+fn load_samples(dir: &Path) -> Result<Vec<Vec<u8>>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("reading {}", dir.display()))? {
+        paths.push(entry?.path());
+    }
+    paths.sort();
+
+    let mut samples = Vec::new();
+    for path in paths {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(PAYLOAD_EXTENSION) => {
+                samples
+                    .push(fs::read(&path).with_context(|| format!("reading {}", path.display()))?);
+            }
+            Some(CAPTURE_EXTENSION) => {
+                let reader = RawCaptureReader::open(&path)
+                    .with_context(|| format!("opening {}", path.display()))?;
+                for record in reader {
+                    let record = record.with_context(|| format!("reading {}", path.display()))?;
+                    samples.push(record.payload);
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(samples)
+}
+
+fn empty_coverage(game_id: &str) -> TelemetryFieldCoverage {
+    TelemetryFieldCoverage {
+        game_id: game_id.to_string(),
+        game_version: String::new(),
+        ffb_scalar: false,
+        rpm: false,
+        speed: false,
+        slip_ratio: false,
+        gear: false,
+        flags: FlagCoverage {
+            yellow_flag: false,
+            red_flag: false,
+            blue_flag: false,
+            checkered_flag: false,
+            green_flag: false,
+            pit_limiter: false,
+            in_pits: false,
+            drs_available: false,
+            drs_active: false,
+            ers_available: false,
+            launch_control: false,
+            traction_control: false,
+            abs_active: false,
+            safety_car: false,
+            virtual_safety_car: false,
+        },
+        car_id: false,
+        track_id: false,
+        penalties: false,
+        game_time: false,
+        tires: false,
+        timing: TimingCoverage::default(),
+        extended_fields: Vec::new(),
+    }
+}
+
+fn observe(coverage: &mut TelemetryFieldCoverage, t: &NormalizedTelemetry) {
+    coverage.ffb_scalar |= t.ffb_scalar != 0.0;
+    coverage.rpm |= t.rpm != 0.0;
+    coverage.speed |= t.speed_ms != 0.0;
+    coverage.slip_ratio |= t.slip_ratio != 0.0;
+    coverage.gear |= t.gear != 0;
+    coverage.car_id |= t.car_id.is_some();
+    coverage.track_id |= t.track_id.is_some();
+    coverage.penalties |= t.penalties.is_some();
+    coverage.game_time |= t.game_time_s.is_some() || t.game_tick.is_some();
+    coverage.tires |= t.tires.is_some();
+
+    let flags = &mut coverage.flags;
+    let seen = &t.flags;
+    // Green is raised by default, so only a change from the default counts.
+    let default = TelemetryFlags::default();
+    flags.yellow_flag |= seen.yellow_flag != default.yellow_flag;
+    flags.red_flag |= seen.red_flag != default.red_flag;
+    flags.blue_flag |= seen.blue_flag != default.blue_flag;
+    flags.checkered_flag |= seen.checkered_flag != default.checkered_flag;
+    flags.green_flag |= seen.green_flag != default.green_flag;
+    flags.pit_limiter |= seen.pit_limiter != default.pit_limiter;
+    flags.in_pits |= seen.in_pits != default.in_pits;
+    flags.drs_available |= seen.drs_available != default.drs_available;
+    flags.drs_active |= seen.drs_active != default.drs_active;
+    flags.ers_available |= seen.ers_available != default.ers_available;
+    flags.launch_control |= seen.launch_control != default.launch_control;
+    flags.traction_control |= seen.traction_control != default.traction_control;
+    flags.abs_active |= seen.abs_active != default.abs_active;
+    flags.safety_car |= seen.safety_car != default.safety_car;
+    flags.virtual_safety_car |= seen.virtual_safety_car != default.virtual_safety_car;
+
+    if let Some(timing) = &t.timing {
+        let covered = &mut coverage.timing;
+        covered.current_lap |= timing.current_lap_ms.is_some();
+        covered.last_lap |= timing.last_lap_ms.is_some();
+        covered.best_lap |= timing.best_lap_ms.is_some();
+        covered.lap_number |= timing.lap_number.is_some();
+        covered.sector |= timing.sector.is_some();
+        covered.session_time_remaining |= timing.session_time_remaining_ms.is_some();
+    }
+}
+
+fn timing_names(timing: &TimingCoverage) -> Vec<&'static str> {
+    [
+        (timing.current_lap, "current lap"),
+        (timing.last_lap, "last lap"),
+        (timing.best_lap, "best lap"),
+        (timing.lap_number, "lap number"),
+        (timing.sector, "sector"),
+        (timing.session_time_remaining, "time remaining"),
+    ]
+    .into_iter()
+    .filter_map(|(covered, name)| covered.then_some(name))
+    .collect()
+}
+
+fn flag_names(flags: &FlagCoverage) -> Vec<&'static str> {
+    [
+        (flags.green_flag, "green"),
+        (flags.yellow_flag, "yellow"),
+        (flags.red_flag, "red"),
+        (flags.blue_flag, "blue"),
+        (flags.checkered_flag, "checkered"),
+        (flags.safety_car, "safety car"),
+        (flags.virtual_safety_car, "VSC"),
+        (flags.pit_limiter, "pit limiter"),
+        (flags.in_pits, "in pits"),
+        (flags.drs_available, "DRS available"),
+        (flags.drs_active, "DRS active"),
+        (flags.ers_available, "ERS"),
+        (flags.launch_control, "launch control"),
+        (flags.traction_control, "TC"),
+        (flags.abs_active, "ABS"),
+    ]
+    .into_iter()
+    .filter_map(|(covered, name)| covered.then_some(name))
+    .collect()
+}
+
+fn name_list(names: &[&str]) -> String {
+    if names.is_empty() {
+        "–".to_string()
+    } else {
+        names.join(", ")
+    }
+}
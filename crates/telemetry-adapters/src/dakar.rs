@@ -21,6 +21,7 @@
 //!
 //! Minimum packet size: 40 bytes.  Update rate: ~60 Hz.
 
+use crate::raw_capture::RawCaptureSlot;
 use crate::{
     NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver, telemetry_now_ns,
 };
@@ -102,6 +103,7 @@ pub fn parse_dakar_packet(data: &[u8]) -> Result<NormalizedTelemetry> {
 pub struct DakarDesertRallyAdapter {
     bind_port: u16,
     update_rate: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for DakarDesertRallyAdapter {
@@ -115,6 +117,7 @@ impl DakarDesertRallyAdapter {
         Self {
             bind_port: DEFAULT_DAKAR_PORT,
             update_rate: Duration::from_millis(16), // ~60 Hz
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -130,7 +133,12 @@ impl TelemetryAdapter for DakarDesertRallyAdapter {
         "dakar_desert_rally"
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(100);
         let bind_port = self.bind_port;
         let update_rate = self.update_rate;
@@ -149,7 +157,9 @@ impl TelemetryAdapter for DakarDesertRallyAdapter {
             let mut frame_idx = 0u64;
 
             loop {
-                match tokio::time::timeout(update_rate * 10, socket.recv(&mut buf)).await {
+                match tokio::time::timeout(update_rate * 10, raw_capture.recv(&socket, &mut buf))
+                    .await
+                {
                     Ok(Ok(len)) => match parse_dakar_packet(&buf[..len]) {
                         Ok(normalized) => {
                             let frame =
@@ -1,29 +1,31 @@
-//! Dirt 4 telemetry adapter for Codemasters extradata v0 UDP format.
+//! Dirt 4 telemetry adapter for the Codemasters legacy `extradata` UDP format.
 //!
 //! Enable in-game: Settings → UDP Telemetry, port 20777, extradata=3.
 //!
 //! The packet layout follows the Codemasters legacy UDP format shared with DiRT Rally 2.0.
-//! All fields are little-endian `f32` at known byte offsets.  Parsing is delegated to
-//! [`crate::codemasters_shared`].
+//! All fields are little-endian `f32` at known byte offsets.  Lower `extradata` levels
+//! are recognized by their shorter length; parsing is delegated to
+//! [`crate::codemasters_shared`].  The port is shared with the other
+//! Codemasters adapters through [`crate::udp_broker`].
 
 use crate::codemasters_shared;
+use crate::raw_capture::RawCaptureSlot;
+use crate::udp_broker::{NotMyPacket, SubscriptionSlot, UdpPortBroker};
+use crate::udp_listener::AdapterNetworkConfig;
 use crate::{
     NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver, telemetry_now_ns,
 };
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
-use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use std::sync::{
     Arc,
     atomic::{AtomicU64, Ordering},
 };
 use std::time::Duration;
-use tokio::net::UdpSocket as TokioUdpSocket;
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
 const DEFAULT_PORT: u16 = 20777;
-const MAX_PACKET_SIZE: usize = 2048;
 const DEFAULT_HEARTBEAT_TIMEOUT_MS: u64 = 1_500;
 
 const ENV_PORT: &str = "OPENRACING_DIRT4_UDP_PORT";
@@ -31,13 +33,15 @@ const ENV_HEARTBEAT_TIMEOUT_MS: &str = "OPENRACING_DIRT4_HEARTBEAT_TIMEOUT_MS";
 
 const GAME_LABEL: &str = "Dirt 4";
 
-/// Dirt 4 adapter for Codemasters extradata v0 UDP telemetry.
+/// Dirt 4 adapter for Codemasters legacy `extradata` UDP telemetry.
 #[derive(Clone)]
 pub struct Dirt4Adapter {
     bind_port: u16,
     update_rate: Duration,
     heartbeat_timeout: Duration,
     last_packet_ns: Arc<AtomicU64>,
+    subscription: SubscriptionSlot,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for Dirt4Adapter {
@@ -65,6 +69,8 @@ impl Dirt4Adapter {
             update_rate: Duration::from_millis(16),
             heartbeat_timeout: Duration::from_millis(heartbeat_ms),
             last_packet_ns: Arc::new(AtomicU64::new(0)),
+            subscription: SubscriptionSlot::default(),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -85,7 +91,7 @@ impl Dirt4Adapter {
 }
 
 fn parse_packet(data: &[u8]) -> Result<NormalizedTelemetry> {
-    codemasters_shared::parse_codemasters_mode1_common(data, GAME_LABEL)
+    codemasters_shared::parse_codemasters_extradata(data, GAME_LABEL)
 }
 
 #[async_trait]
@@ -94,49 +100,47 @@ impl TelemetryAdapter for Dirt4Adapter {
         "dirt4"
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let bind_port = self.bind_port;
         let update_rate = self.update_rate;
         let last_packet_ns = Arc::clone(&self.last_packet_ns);
         let (tx, rx) = mpsc::channel(100);
 
-        tokio::spawn(async move {
-            let bind_addr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, bind_port));
-            let socket = match TokioUdpSocket::bind(bind_addr).await {
-                Ok(s) => s,
-                Err(error) => {
-                    warn!(
-                        error = %error,
-                        port = bind_port,
-                        "Dirt 4 UDP socket bind failed"
-                    );
-                    return;
-                }
-            };
-
-            info!(port = bind_port, "Dirt 4 UDP adapter bound");
+        let mut subscription = UdpPortBroker::shared()
+            .subscribe(AdapterNetworkConfig::new(bind_port), self.game_id())
+            .with_context(|| format!("Dirt 4 could not listen on UDP port {bind_port}"))?;
+        self.subscription.replace(subscription.handle()).await;
+        info!(port = bind_port, "Dirt 4 UDP adapter subscribed");
 
+        tokio::spawn(async move {
             let mut frame_idx = 0u64;
-            let mut buf = vec![0u8; MAX_PACKET_SIZE];
             let timeout = (update_rate * 4).max(Duration::from_millis(25));
 
             loop {
-                let recv = tokio::time::timeout(timeout, socket.recv(&mut buf)).await;
-                let len = match recv {
-                    Ok(Ok(len)) => len,
-                    Ok(Err(error)) => {
-                        warn!(error = %error, "Dirt 4 UDP receive error");
-                        continue;
-                    }
+                let datagram = match tokio::time::timeout(timeout, subscription.recv()).await {
+                    Ok(Some(datagram)) => datagram,
+                    Ok(None) => break,
                     Err(_) => {
                         debug!("Dirt 4 UDP receive timeout");
                         continue;
                     }
                 };
 
-                let data = &buf[..len];
-                let normalized = match parse_packet(data) {
+                let len = datagram.payload.len();
+                let parsed = parse_packet(&datagram.payload);
+                subscription.record(&parsed);
+                raw_capture.record_decoded(&datagram.payload, Some(datagram.source), &parsed);
+                let normalized = match parsed {
                     Ok(n) => n,
+                    Err(error) if error.is::<NotMyPacket>() => {
+                        debug!(error = %error, "Ignoring non-Dirt 4 datagram");
+                        continue;
+                    }
                     Err(error) => {
                         warn!(error = %error, "Failed to parse Dirt 4 packet");
                         continue;
@@ -158,6 +162,7 @@ impl TelemetryAdapter for Dirt4Adapter {
     }
 
     async fn stop_monitoring(&self) -> Result<()> {
+        self.subscription.release().await;
         Ok(())
     }
 
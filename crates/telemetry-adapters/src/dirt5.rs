@@ -4,8 +4,14 @@
 //! Codemasters UDP schema model. This adapter is intentionally telemetry-only;
 //! no force-feedback scalar is emitted because the protocol family is not known
 //! to include a steering torque request.
+//!
+//! Port 20777 is shared with the other Codemasters adapters through
+//! [`crate::udp_broker`].
 
 use crate::codemasters_udp::{CustomUdpSpec, DecodedCodemastersPacket, canonical_channel_id};
+use crate::raw_capture::RawCaptureSlot;
+use crate::udp_broker::{SubscriptionSlot, UdpPortBroker};
+use crate::udp_listener::AdapterNetworkConfig;
 use crate::{
     NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver, TelemetryValue,
     telemetry_now_ns,
@@ -14,21 +20,18 @@ use anyhow::{Context, Result, anyhow};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::f32::consts::PI;
-use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use std::path::PathBuf;
 use std::sync::{
     Arc,
     atomic::{AtomicU64, Ordering},
 };
 use std::time::Duration;
-use tokio::net::UdpSocket as TokioUdpSocket;
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
 const DEFAULT_DIRT5_PORT: u16 = 20777;
 const DEFAULT_DIRT5_MODE: u8 = 1;
 const DEFAULT_DIRT5_HEARTBEAT_TIMEOUT_MS: u64 = 1_500;
-const MAX_PACKET_SIZE: usize = 2048;
 
 const ENV_DIRT5_UDP_PORT: &str = "OPENRACING_DIRT5_UDP_PORT";
 const ENV_DIRT5_UDP_MODE: &str = "OPENRACING_DIRT5_UDP_MODE";
@@ -44,6 +47,8 @@ pub struct Dirt5Adapter {
     update_rate: Duration,
     heartbeat_timeout: Duration,
     last_packet_ns: Arc<AtomicU64>,
+    subscription: SubscriptionSlot,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for Dirt5Adapter {
@@ -72,6 +77,8 @@ impl Dirt5Adapter {
             update_rate: Duration::from_millis(16),
             heartbeat_timeout,
             last_packet_ns: Arc::new(AtomicU64::new(0)),
+            subscription: SubscriptionSlot::default(),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -208,53 +215,46 @@ impl TelemetryAdapter for Dirt5Adapter {
         "dirt5"
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let spec = self.load_spec()?;
-        let expected_bytes = spec.expected_bytes();
         let bind_port = self.bind_port;
         let update_rate = self.update_rate;
         let last_packet_ns = Arc::clone(&self.last_packet_ns);
         let (tx, rx) = mpsc::channel(100);
 
-        tokio::spawn(async move {
-            let bind_addr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, bind_port));
-            let socket = match TokioUdpSocket::bind(bind_addr).await {
-                Ok(socket) => socket,
-                Err(error) => {
-                    warn!(
-                        error = %error,
-                        port = bind_port,
-                        "Dirt 5 UDP socket bind failed"
-                    );
-                    return;
-                }
-            };
-
-            info!(port = bind_port, "Dirt 5 UDP adapter bound");
+        let mut subscription = UdpPortBroker::shared()
+            .subscribe(AdapterNetworkConfig::new(bind_port), self.game_id())
+            .with_context(|| format!("Dirt 5 could not listen on UDP port {bind_port}"))?;
+        self.subscription.replace(subscription.handle()).await;
+        info!(port = bind_port, "Dirt 5 UDP adapter subscribed");
 
+        tokio::spawn(async move {
             let mut frame_seq = 0u64;
-            let mut buf = vec![0u8; MAX_PACKET_SIZE.max(expected_bytes.max(1))];
             let mut timeout = update_rate * 4;
             if timeout == Duration::ZERO {
                 timeout = Duration::from_millis(25);
             }
 
             loop {
-                let recv = tokio::time::timeout(timeout, socket.recv(&mut buf)).await;
-                let len = match recv {
-                    Ok(Ok(len)) => len,
-                    Ok(Err(error)) => {
-                        warn!(error = %error, "Error receiving Dirt 5 UDP telemetry");
-                        continue;
-                    }
+                let datagram = match tokio::time::timeout(timeout, subscription.recv()).await {
+                    Ok(Some(datagram)) => datagram,
+                    Ok(None) => break,
                     Err(_) => {
                         debug!("Dirt 5 UDP receive timeout waiting for packet");
                         continue;
                     }
                 };
 
-                let data = &buf[..len];
-                let decoded = match spec.decode(data) {
+                let len = datagram.payload.len();
+                let decoded = spec.decode(&datagram.payload);
+                subscription.record(&decoded);
+                raw_capture.record_decoded(&datagram.payload, Some(datagram.source), &decoded);
+                let decoded = match decoded {
                     Ok(packet) => packet,
                     Err(error) => {
                         warn!(
@@ -281,6 +281,7 @@ impl TelemetryAdapter for Dirt5Adapter {
     }
 
     async fn stop_monitoring(&self) -> Result<()> {
+        self.subscription.release().await;
         Ok(())
     }
 
@@ -1,30 +1,38 @@
 //! DiRT Rally 2.0 telemetry adapter for Codemasters Mode 1 (legacy) UDP format.
 //!
-//! Enable in-game: Settings → Accessibility → UDP Telemetry, port 20777, mode 1.
+//! Enable in-game: Settings → Accessibility → UDP Telemetry, port 20777, or set
+//! `extradata` on the `<udp>` element of `hardware_settings_config.xml`.
 //!
-//! The Mode 1 packet is a fixed-layout binary stream of 264 bytes where every
-//! field is a little-endian `f32` at a known byte offset.  Parsing is delegated
-//! to [`crate::codemasters_shared`].
+//! The packet is a fixed-layout binary stream where every field is a
+//! little-endian `f32` at a known byte offset; higher `extradata` levels append
+//! fields, up to the full 264-byte layout at level 3.  The level is detected
+//! from each datagram's length and parsing is delegated to
+//! [`crate::codemasters_shared`].
+//!
+//! The port is shared with the other Codemasters adapters through
+//! [`crate::udp_broker`]; datagrams whose length matches no `extradata` level
+//! are reported as not ours.
 
 use crate::codemasters_shared;
+use crate::raw_capture::RawCaptureSlot;
+use crate::udp_broker::{NotMyPacket, SubscriptionSlot, UdpPortBroker};
+use crate::udp_listener::{AdapterNetworkConfig, ListenMode};
 use crate::{
-    NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver, telemetry_now_ns,
+    NormalizedTelemetry, PacketMatch, TelemetryAdapter, TelemetryFrame, TelemetryReceiver,
+    UnitManifest, telemetry_now_ns,
 };
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
-use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use std::sync::{
     Arc,
     atomic::{AtomicU64, Ordering},
 };
 use std::time::Duration;
-use tokio::net::UdpSocket as TokioUdpSocket;
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
 /// Verified: standard Codemasters Mode 1 UDP port (SimHub wiki, in-game settings).
 const DEFAULT_PORT: u16 = 20777;
-const MAX_PACKET_SIZE: usize = 2048;
 const DEFAULT_HEARTBEAT_TIMEOUT_MS: u64 = 1_500;
 
 const ENV_PORT: &str = "OPENRACING_DIRT_RALLY_2_UDP_PORT";
@@ -36,9 +44,12 @@ const GAME_LABEL: &str = "DiRT Rally 2.0";
 #[derive(Clone)]
 pub struct DirtRally2Adapter {
     bind_port: u16,
+    listen_mode: ListenMode,
     update_rate: Duration,
     heartbeat_timeout: Duration,
     last_packet_ns: Arc<AtomicU64>,
+    subscription: SubscriptionSlot,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for DirtRally2Adapter {
@@ -63,9 +74,12 @@ impl DirtRally2Adapter {
 
         Self {
             bind_port,
+            listen_mode: ListenMode::Unicast,
             update_rate: Duration::from_millis(16),
             heartbeat_timeout: Duration::from_millis(heartbeat_ms),
             last_packet_ns: Arc::new(AtomicU64::new(0)),
+            subscription: SubscriptionSlot::default(),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -74,6 +88,12 @@ impl DirtRally2Adapter {
         self
     }
 
+    /// Receive broadcast or multicast datagrams instead of unicast.
+    pub fn with_listen_mode(mut self, listen_mode: ListenMode) -> Self {
+        self.listen_mode = listen_mode;
+        self
+    }
+
     fn is_recent_packet(&self) -> bool {
         let last = self.last_packet_ns.load(Ordering::Relaxed);
         if last == 0 {
@@ -86,7 +106,7 @@ impl DirtRally2Adapter {
 }
 
 fn parse_packet(data: &[u8]) -> Result<NormalizedTelemetry> {
-    codemasters_shared::parse_codemasters_mode1_common(data, GAME_LABEL)
+    codemasters_shared::parse_codemasters_extradata(data, GAME_LABEL)
 }
 
 #[async_trait]
@@ -95,49 +115,64 @@ impl TelemetryAdapter for DirtRally2Adapter {
         "dirt_rally_2"
     }
 
+    fn unit_manifest(&self) -> Option<UnitManifest> {
+        Some(codemasters_shared::MODE1_UNIT_MANIFEST)
+    }
+
+    fn listen_mode(&self) -> Option<ListenMode> {
+        Some(self.listen_mode)
+    }
+
+    fn udp_port(&self) -> Option<u16> {
+        Some(self.bind_port)
+    }
+
+    fn recognize_packet(&self, raw: &[u8]) -> Option<PacketMatch> {
+        codemasters_shared::recognize_extradata(raw)
+    }
+
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let bind_port = self.bind_port;
+        let network = AdapterNetworkConfig::new(bind_port).with_listen_mode(self.listen_mode);
         let update_rate = self.update_rate;
         let last_packet_ns = Arc::clone(&self.last_packet_ns);
         let (tx, rx) = mpsc::channel(100);
 
-        tokio::spawn(async move {
-            let bind_addr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, bind_port));
-            let socket = match TokioUdpSocket::bind(bind_addr).await {
-                Ok(s) => s,
-                Err(error) => {
-                    warn!(
-                        error = %error,
-                        port = bind_port,
-                        "DiRT Rally 2.0 UDP socket bind failed"
-                    );
-                    return;
-                }
-            };
-
-            info!(port = bind_port, "DiRT Rally 2.0 UDP adapter bound");
+        let mut subscription = UdpPortBroker::shared()
+            .subscribe(network, self.game_id())
+            .with_context(|| format!("DiRT Rally 2.0 could not listen on UDP port {bind_port}"))?;
+        self.subscription.replace(subscription.handle()).await;
+        info!(port = bind_port, mode = %self.listen_mode, "DiRT Rally 2.0 UDP adapter subscribed");
 
+        tokio::spawn(async move {
             let mut frame_seq = 0u64;
-            let mut buf = vec![0u8; MAX_PACKET_SIZE];
             let timeout = (update_rate * 4).max(Duration::from_millis(25));
 
             loop {
-                let recv = tokio::time::timeout(timeout, socket.recv(&mut buf)).await;
-                let len = match recv {
-                    Ok(Ok(len)) => len,
-                    Ok(Err(error)) => {
-                        warn!(error = %error, "DiRT Rally 2.0 UDP receive error");
-                        continue;
-                    }
+                let datagram = match tokio::time::timeout(timeout, subscription.recv()).await {
+                    Ok(Some(datagram)) => datagram,
+                    Ok(None) => break,
                     Err(_) => {
                         debug!("DiRT Rally 2.0 UDP receive timeout");
                         continue;
                     }
                 };
 
-                let data = &buf[..len];
-                let normalized = match parse_packet(data) {
+                let len = datagram.payload.len();
+                let parsed = parse_packet(&datagram.payload);
+                subscription.record(&parsed);
+                raw_capture.record_decoded(&datagram.payload, Some(datagram.source), &parsed);
+                let normalized = match parsed {
                     Ok(n) => n,
+                    Err(error) if error.is::<NotMyPacket>() => {
+                        debug!(error = %error, "Ignoring non-DiRT Rally 2.0 datagram");
+                        continue;
+                    }
                     Err(error) => {
                         warn!(error = %error, "Failed to parse DiRT Rally 2.0 packet");
                         continue;
@@ -159,6 +194,7 @@ impl TelemetryAdapter for DirtRally2Adapter {
     }
 
     async fn stop_monitoring(&self) -> Result<()> {
+        self.subscription.release().await;
         Ok(())
     }
 
@@ -192,11 +228,13 @@ mod property_tests {
     proptest! {
         #![proptest_config(proptest::test_runner::Config::with_cases(500))]
 
-        /// Any packet shorter than MIN_PACKET_SIZE must return Err, never panic.
+        /// A packet shorter than MIN_PACKET_SIZE parses only at the length of
+        /// a lower extradata level, and never panics.
         #[test]
         fn prop_short_packet_returns_err(len in 0usize..MIN_PACKET_SIZE) {
             let data = vec![0u8; len];
-            prop_assert!(parse_packet(&data).is_err());
+            let expected = crate::CustomUdpSpec::detect_mode(len).is_some();
+            prop_assert_eq!(parse_packet(&data).is_ok(), expected);
         }
 
         /// Arbitrary bytes at or above MIN_PACKET_SIZE must never panic.
@@ -1,5 +1,7 @@
 //! EA SPORTS WRC telemetry adapter using schema-driven UDP decoding.
 
+use crate::raw_capture::RawCaptureSlot;
+use crate::udp_listener::{AdapterNetworkConfig, ListenMode};
 use crate::{
     NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver, TelemetryValue,
     telemetry_now_ns,
@@ -10,14 +12,12 @@ use serde::Deserialize;
 use serde::de::DeserializeOwned;
 use std::collections::HashMap;
 use std::fs;
-use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use std::path::{Path, PathBuf};
 use std::sync::{
     Arc,
     atomic::{AtomicU64, Ordering},
 };
 use std::time::Duration;
-use tokio::net::UdpSocket as TokioUdpSocket;
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
@@ -32,7 +32,9 @@ const PACKET_UID_KEY: &str = "packetuid";
 
 pub struct EAWRCAdapter {
     telemetry_dir: PathBuf,
+    listen_mode: ListenMode,
     update_rate: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for EAWRCAdapter {
@@ -45,7 +47,9 @@ impl EAWRCAdapter {
     pub fn new() -> Self {
         Self {
             telemetry_dir: telemetry_root_from_environment(),
+            listen_mode: ListenMode::Unicast,
             update_rate: Duration::from_millis(16),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -56,6 +60,12 @@ impl EAWRCAdapter {
         }
     }
 
+    /// Receive broadcast or multicast datagrams on every configured port.
+    pub fn with_listen_mode(mut self, listen_mode: ListenMode) -> Self {
+        self.listen_mode = listen_mode;
+        self
+    }
+
     fn load_bundle(&self) -> Result<DecoderBundle> {
         let channels_path = self.telemetry_dir.join("readme").join("channels.json");
         let channels: ChannelsFile = read_json(&channels_path).with_context(|| {
@@ -355,7 +365,16 @@ impl TelemetryAdapter for EAWRCAdapter {
         "eawrc"
     }
 
+    fn listen_mode(&self) -> Option<ListenMode> {
+        Some(self.listen_mode)
+    }
+
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let bundle = self.load_bundle()?;
         let (tx, rx) = mpsc::channel(100);
 
@@ -376,11 +395,12 @@ impl TelemetryAdapter for EAWRCAdapter {
             let plan = bundle.plan.clone();
             let tx = tx.clone();
             let sequence = Arc::clone(&sequence);
+            let raw_capture = raw_capture.clone();
             let update_rate = self.update_rate;
+            let network = AdapterNetworkConfig::new(port).with_listen_mode(self.listen_mode);
 
             tokio::spawn(async move {
-                let bind_addr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, port));
-                let socket = match TokioUdpSocket::bind(bind_addr).await {
+                let socket = match network.bind() {
                     Ok(socket) => socket,
                     Err(error) => {
                         warn!(port = port, error = %error, "Failed to bind EA WRC UDP socket");
@@ -388,11 +408,18 @@ impl TelemetryAdapter for EAWRCAdapter {
                     }
                 };
 
-                info!(port = port, packet_ids = ?packet_ids, "EA WRC telemetry socket bound");
+                info!(
+                    port = port,
+                    mode = %network.listen_mode,
+                    packet_ids = ?packet_ids,
+                    "EA WRC telemetry socket bound"
+                );
 
                 let mut buf = [0u8; MAX_PACKET_SIZE];
                 loop {
-                    let recv = tokio::time::timeout(update_rate * 4, socket.recv(&mut buf)).await;
+                    let recv =
+                        tokio::time::timeout(update_rate * 4, raw_capture.recv(&socket, &mut buf))
+                            .await;
                     let len = match recv {
                         Ok(Ok(len)) => len,
                         Ok(Err(error)) => {
@@ -218,6 +218,10 @@ impl TelemetryAdapter for Ets2Adapter {
         self.update_rate
     }
 
+    fn prefers_dedup(&self) -> bool {
+        true
+    }
+
     async fn is_game_running(&self) -> Result<bool> {
         Ok(is_scs_process_running(self.variant))
     }
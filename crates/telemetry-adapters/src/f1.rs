@@ -7,9 +7,14 @@
 //!
 //! - **Default port**: 20777 — standard Codemasters/EA F1 UDP port. ✓
 //! - **Custom UDP modes**: 0–3 (mode 3 = full telemetry). ✓
-//! - **MAX_PACKET_SIZE**: 4096 bytes (sufficient for all known modes). ✓
+//! - **Max datagram**: 4096 bytes, the [`crate::udp_broker`] receive buffer
+//!   (sufficient for all known modes). ✓
 
+use crate::codemasters_shared::{apply_fia_flag, apply_safety_car_status};
 use crate::codemasters_udp::{CustomUdpSpec, DecodedCodemastersPacket, canonical_channel_id};
+use crate::raw_capture::RawCaptureSlot;
+use crate::udp_broker::{SubscriptionSlot, UdpPortBroker};
+use crate::udp_listener::{AdapterNetworkConfig, ListenMode};
 use crate::{
     NormalizedTelemetry, TelemetryAdapter, TelemetryFlags, TelemetryFrame, TelemetryReceiver,
     TelemetryValue, telemetry_now_ns,
@@ -18,21 +23,18 @@ use anyhow::{Context, Result, anyhow};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::f32::consts::PI;
-use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use std::path::PathBuf;
 use std::sync::{
     Arc,
     atomic::{AtomicU64, Ordering},
 };
 use std::time::Duration;
-use tokio::net::UdpSocket as TokioUdpSocket;
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
 const DEFAULT_F1_PORT: u16 = 20777;
 const DEFAULT_F1_MODE: u8 = 3;
 const DEFAULT_F1_HEARTBEAT_TIMEOUT_MS: u64 = 1_500;
-const MAX_PACKET_SIZE: usize = 4096;
 
 const ENV_F1_UDP_PORT: &str = "OPENRACING_F1_UDP_PORT";
 const ENV_F1_UDP_MODE: &str = "OPENRACING_F1_UDP_MODE";
@@ -43,11 +45,14 @@ const ENV_F1_HEARTBEAT_TIMEOUT_MS: &str = "OPENRACING_F1_HEARTBEAT_TIMEOUT_MS";
 #[derive(Clone)]
 pub struct F1Adapter {
     bind_port: u16,
+    listen_mode: ListenMode,
     mode: u8,
     custom_udp_xml: Option<PathBuf>,
     update_rate: Duration,
     heartbeat_timeout: Duration,
     last_packet_ns: Arc<AtomicU64>,
+    subscription: SubscriptionSlot,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for F1Adapter {
@@ -71,11 +76,14 @@ impl F1Adapter {
 
         Self {
             bind_port,
+            listen_mode: ListenMode::Unicast,
             mode,
             custom_udp_xml,
             update_rate: Duration::from_millis(16),
             heartbeat_timeout,
             last_packet_ns: Arc::new(AtomicU64::new(0)),
+            subscription: SubscriptionSlot::default(),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -84,6 +92,12 @@ impl F1Adapter {
         self
     }
 
+    /// Receive broadcast or multicast datagrams instead of unicast.
+    pub fn with_listen_mode(mut self, listen_mode: ListenMode) -> Self {
+        self.listen_mode = listen_mode;
+        self
+    }
+
     pub fn with_mode(mut self, mode: u8) -> Self {
         self.mode = mode;
         self
@@ -156,7 +170,12 @@ impl F1Adapter {
             });
 
         let pit_limiter = lookup_bool(&["pit_limiter", "pit_limiter_on"]).unwrap_or(false);
-        let in_pits = lookup_bool(&["in_pits", "in_pit_lane", "pit_lane"]).unwrap_or(false);
+        // pitStatus is 1 while pitting and 2 in the pit area.
+        let in_pits = lookup(&["pit_status"])
+            .filter(|value| value.is_finite())
+            .map(|value| value >= 0.5)
+            .or_else(|| lookup_bool(&["in_pits", "in_pit_lane", "pit_lane"]))
+            .unwrap_or(false);
         let drs_available =
             lookup_bool(&["drs_available", "drs_allowed", "drs_enabled"]).unwrap_or(false);
         let drs_active = lookup_bool(&["drs_active", "drs_open", "drs_deployed"]).unwrap_or(false);
@@ -166,7 +185,7 @@ impl F1Adapter {
         let traction_control = lookup_bool(&["traction_control", "tc_active"]).unwrap_or(false);
         let abs_active = lookup_bool(&["abs_active", "abs"]).unwrap_or(false);
 
-        let flags = TelemetryFlags {
+        let mut flags = TelemetryFlags {
             pit_limiter,
             in_pits,
             drs_available,
@@ -176,6 +195,20 @@ impl F1Adapter {
             abs_active,
             ..TelemetryFlags::default()
         };
+        let integral = |aliases: &[&str]| {
+            lookup(aliases)
+                .filter(|value| value.is_finite())
+                .map(|value| value.round())
+        };
+        if let Some(fia_flag) = integral(&["vehicle_fia_flags", "fia_flags", "fia_flag"]) {
+            apply_fia_flag(&mut flags, fia_flag.clamp(-1.0, 127.0) as i8);
+        }
+        if let Some(zone_flag) = integral(&["zone_flag", "marshal_zone_flag"]) {
+            apply_fia_flag(&mut flags, zone_flag.clamp(-1.0, 127.0) as i8);
+        }
+        if let Some(status) = integral(&["safety_car_status"]) {
+            apply_safety_car_status(&mut flags, status.clamp(0.0, 255.0) as u8);
+        }
 
         let mut builder = NormalizedTelemetry::builder();
 
@@ -286,53 +319,55 @@ impl TelemetryAdapter for F1Adapter {
         "f1"
     }
 
+    fn listen_mode(&self) -> Option<ListenMode> {
+        Some(self.listen_mode)
+    }
+
+    fn udp_port(&self) -> Option<u16> {
+        Some(self.bind_port)
+    }
+
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let spec = self.load_spec()?;
-        let expected_bytes = spec.expected_bytes();
         let bind_port = self.bind_port;
+        let network = AdapterNetworkConfig::new(bind_port).with_listen_mode(self.listen_mode);
         let update_rate = self.update_rate;
         let last_packet_ns = Arc::clone(&self.last_packet_ns);
         let (tx, rx) = mpsc::channel(100);
 
-        tokio::spawn(async move {
-            let bind_addr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, bind_port));
-            let socket = match TokioUdpSocket::bind(bind_addr).await {
-                Ok(socket) => socket,
-                Err(error) => {
-                    warn!(
-                        error = %error,
-                        port = bind_port,
-                        "F1 UDP socket bind failed"
-                    );
-                    return;
-                }
-            };
-
-            info!(port = bind_port, "F1 UDP adapter bound");
+        let mut subscription = UdpPortBroker::shared()
+            .subscribe(network, self.game_id())
+            .with_context(|| format!("F1 could not listen on UDP port {bind_port}"))?;
+        self.subscription.replace(subscription.handle()).await;
+        info!(port = bind_port, mode = %self.listen_mode, "F1 UDP adapter subscribed");
 
+        tokio::spawn(async move {
             let mut frame_seq = 0u64;
-            let mut buf = vec![0u8; MAX_PACKET_SIZE.max(expected_bytes.max(1))];
             let mut timeout = update_rate * 4;
             if timeout == Duration::ZERO {
                 timeout = Duration::from_millis(25);
             }
 
             loop {
-                let recv = tokio::time::timeout(timeout, socket.recv(&mut buf)).await;
-                let len = match recv {
-                    Ok(Ok(len)) => len,
-                    Ok(Err(error)) => {
-                        warn!(error = %error, "Error receiving F1 UDP telemetry");
-                        continue;
-                    }
+                let datagram = match tokio::time::timeout(timeout, subscription.recv()).await {
+                    Ok(Some(datagram)) => datagram,
+                    Ok(None) => break,
                     Err(_) => {
                         debug!("F1 UDP receive timeout waiting for packet");
                         continue;
                     }
                 };
 
-                let data = &buf[..len];
-                let decoded = match spec.decode(data) {
+                let len = datagram.payload.len();
+                let decoded = spec.decode(&datagram.payload);
+                subscription.record(&decoded);
+                raw_capture.record_decoded(&datagram.payload, Some(datagram.source), &decoded);
+                let decoded = match decoded {
                     Ok(packet) => packet,
                     Err(error) => {
                         warn!(
@@ -359,6 +394,7 @@ impl TelemetryAdapter for F1Adapter {
     }
 
     async fn stop_monitoring(&self) -> Result<()> {
+        self.subscription.release().await;
         Ok(())
     }
 
@@ -457,6 +493,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_f1_adapter_maps_fia_safety_car_and_pit_status() {
+        let mut values = HashMap::new();
+        values.insert("zoneflag".to_string(), 3.0);
+        values.insert("safetycarstatus".to_string(), 2.0);
+        values.insert("pitstatus".to_string(), 2.0);
+        let packet = DecodedCodemastersPacket {
+            values,
+            fourcc: None,
+        };
+
+        let t = F1Adapter::normalize_decoded(&packet);
+        assert!(t.has_active_flags());
+        assert!(t.flags.yellow_flag);
+        assert!(!t.flags.green_flag);
+        assert!(t.flags.virtual_safety_car);
+        assert!(!t.flags.safety_car);
+        assert!(t.flags.in_pits);
+
+        let mut values = HashMap::new();
+        values.insert("vehiclefiaflags".to_string(), 2.0);
+        values.insert("pitstatus".to_string(), 0.0);
+        values.insert("inpits".to_string(), 1.0);
+        let t = F1Adapter::normalize_decoded(&DecodedCodemastersPacket {
+            values,
+            fourcc: None,
+        });
+        assert!(t.flags.blue_flag);
+        assert!(t.flags.green_flag);
+        assert!(!t.flags.in_pits, "pit status takes precedence");
+    }
+
     #[test]
     fn test_f1_adapter_rejects_short_packet() {
         let adapter = F1Adapter::new();
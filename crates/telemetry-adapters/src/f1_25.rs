@@ -7,14 +7,23 @@
 //!
 //! | Packet ID | Name          | Fields used                                |
 //! |-----------|---------------|--------------------------------------------|
-//! | 1         | Session        | track ID, session type, temperatures       |
+//! | 0         | Motion         | lateral/longitudinal/vertical G            |
+//! | 1         | Session        | track, temperatures, marshal zones, SC     |
+//! | 2         | Lap Data       | lap times, sector, pit status, penalties   |
+//! | 4         | Participants   | driver names (opponents list)              |
 //! | 6         | Car Telemetry  | speed, gear, RPM, DRS, tyre temps/pressure |
-//! | 7         | Car Status     | fuel, ERS, pit limiter, tyre compound      |
+//! | 7         | Car Status     | fuel, ERS, pit limiter, tyres, FIA flag    |
+//! | 13        | Motion Ex      | per-wheel slip ratio (player car only)     |
 //!
 //! All other packet IDs are silently discarded.
 //!
+//! Lap Data, Participants and Car Telemetry rows of the other cars feed the
+//! frame's `opponents` list; see [`GridState`].
+//!
 //! ## Default UDP port
-//! 20777  (override with `OPENRACING_F1_25_UDP_PORT`).
+//! 20777  (override with `OPENRACING_F1_25_UDP_PORT`), shared with the other
+//! Codemasters adapters through [`crate::udp_broker`].  Packets of another
+//! format are rejected with [`NotMyPacket`].
 //!
 //! ## Unit conventions
 //! - Speed: km/h → m/s (÷ 3.6)
@@ -31,25 +40,38 @@
 //! - **Default port**: 20777 — standard Codemasters/EA F1 UDP port since F1 2019. ✓
 //! - **Header size**: 29 bytes (consistent across F1 2023/2024/2025 formats). ✓
 //! - **Packet format field**: u16 = 2025 (identifies the year/version). ✓
-//! - **Packet IDs**: 1=Session, 6=CarTelemetry, 7=CarStatus — standard EA IDs. ✓
+//! - **Packet IDs**: 0=Motion, 1=Session, 2=LapData, 6=CarTelemetry, 7=CarStatus,
+//!   13=MotionEx — standard EA IDs. ✓
 //! - **NUM_CARS**: 22 (F1 grid size). ✓
 //! - **CarTelemetryData entry**: 60 bytes per car. ✓
 //! - **CarStatusData entry**: 55 bytes per car. ✓
+//! - **LapData entry**: 57 bytes per car (same layout as F1 24). ✓
+//! - **ParticipantData entry**: 57 bytes per car; F1 25 shortened the name
+//!   to 32 bytes and appended livery colours. ✓
+//! - **CarMotionData entry**: 60 bytes per car. ✓
+//! - **PacketMotionExData**: 273 bytes; F1 25 appends chassis pitch and wheel
+//!   camber to the F1 24 layout, and wheel slip moved here from the motion
+//!   packet in F1 23. ✓
 //! - **ERS max store**: 4 MJ (4,000,000 J) — per F1 regulations and EA spec. ✓
 
+use crate::codemasters_shared::{apply_fia_flag, apply_safety_car_status};
+use crate::raw_capture::RawCaptureSlot;
+use crate::udp_broker::{NotMyPacket, SubscriptionSlot, UdpPortBroker};
+use crate::udp_listener::{AdapterNetworkConfig, ListenMode};
+use crate::{DEFAULT_MAX_OPPONENTS, OpponentSnapshot, bound_opponents};
 use crate::{
-    NormalizedTelemetry, TelemetryAdapter, TelemetryFlags, TelemetryFrame, TelemetryReceiver,
-    TelemetryValue, telemetry_now_ns,
+    ExtendedKey, FieldUnit, NormalizedTelemetry, PacketMatch, PenaltyKind, PenaltyState,
+    SessionTiming, TelemetryAdapter, TelemetryFlags, TelemetryFrame, TelemetryReceiver,
+    TelemetryValue, TireCorner, TireData, Unit, UnitManifest, telemetry_now_ns,
 };
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow};
 use async_trait::async_trait;
-use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::collections::BTreeMap;
 use std::sync::{
     Arc,
     atomic::{AtomicU64, Ordering},
 };
 use std::time::Duration;
-use tokio::net::UdpSocket;
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
@@ -58,14 +80,20 @@ use tracing::{debug, info, warn};
 /// Verified: EA Sports F1 25 UDP spec, standard Codemasters/EA port since F1 2019.
 const DEFAULT_PORT: u16 = 20777;
 const DEFAULT_HEARTBEAT_TIMEOUT_MS: u64 = 2_000;
-const MAX_PACKET_BYTES: usize = 2048;
+const PSI_TO_KPA: f32 = 6.894_757;
 const NUM_CARS: usize = 22;
 
 const PACKET_FORMAT_2025: u16 = 2025;
 const HEADER_SIZE: usize = 29;
+const PACKET_ID_MOTION: u8 = 0;
 const PACKET_ID_SESSION: u8 = 1;
+const PACKET_ID_LAP_DATA: u8 = 2;
+const PACKET_ID_PARTICIPANTS: u8 = 4;
 const PACKET_ID_CAR_TELEMETRY: u8 = 6;
 const PACKET_ID_CAR_STATUS: u8 = 7;
+const PACKET_ID_MOTION_EX: u8 = 13;
+/// Highest packet id of the F1 23–25 specs (Lap Positions).
+const MAX_PACKET_ID: u8 = 15;
 
 /// EA F1 25 spec: battery stores up to 4 MJ.
 pub const ERS_MAX_STORE_ENERGY_J: f32 = 4_000_000.0;
@@ -80,11 +108,37 @@ pub const CAR_TELEMETRY_ENTRY_SIZE: usize = 60;
 /// Size of one CarStatusData entry (55 bytes).
 pub const CAR_STATUS_ENTRY_SIZE: usize = 55;
 
+/// Size of one LapData entry (57 bytes, F1 24 and F1 25).
+pub const LAP_DATA_ENTRY_SIZE: usize = 57;
+/// Size of one F1 25 ParticipantData entry (57 bytes).
+pub const PARTICIPANT_ENTRY_SIZE: usize = 57;
+/// Bytes of the NUL-padded driver name in an F1 25 ParticipantData entry.
+pub const PARTICIPANT_NAME_LEN: usize = 32;
+
 /// Minimum size for a full Car Telemetry packet (all 22 cars + trailer).
 pub const MIN_CAR_TELEMETRY_PACKET_SIZE: usize =
     HEADER_SIZE + NUM_CARS * CAR_TELEMETRY_ENTRY_SIZE + 3;
 /// Minimum size for a full Car Status packet (all 22 cars).
 pub const MIN_CAR_STATUS_PACKET_SIZE: usize = HEADER_SIZE + NUM_CARS * CAR_STATUS_ENTRY_SIZE;
+/// Minimum size for a full Lap Data packet (all 22 cars, trailer excluded).
+pub const MIN_LAP_DATA_PACKET_SIZE: usize = HEADER_SIZE + NUM_CARS * LAP_DATA_ENTRY_SIZE;
+/// Size of an F1 25 Participants packet (header + numActiveCars + 22 cars).
+pub const MIN_PARTICIPANTS_PACKET_SIZE: usize = HEADER_SIZE + 1 + NUM_CARS * PARTICIPANT_ENTRY_SIZE;
+/// Per-car entry size in PacketMotionData (ID 0) for F1 25.
+pub const CAR_MOTION_ENTRY_SIZE: usize = 60;
+/// Size of a Motion packet (header + 22 car entries).
+pub const MIN_MOTION_PACKET_SIZE: usize = HEADER_SIZE + NUM_CARS * CAR_MOTION_ENTRY_SIZE;
+/// Size of a 2025-format Motion Ex packet (header + player car data).
+pub const MIN_MOTION_EX_PACKET_SIZE: usize = 273;
+/// Marshal zone slots in the Session packet.
+pub const MAX_MARSHAL_ZONES: usize = 21;
+/// Session packet length up to and including `safetyCarStatus` (byte 124).
+pub const MIN_SESSION_WITH_ZONES_SIZE: usize = HEADER_SIZE + 19 + MAX_MARSHAL_ZONES * 5 + 1;
+
+/// `m_resultStatus` value for a disqualified car.
+const RESULT_STATUS_DISQUALIFIED: u8 = 5;
+/// `m_resultStatus` of the first state in which a grid slot holds a car.
+const RESULT_STATUS_ACTIVE: u8 = 2;
 
 // ── Track name lookup ─────────────────────────────────────────────────────────
 
@@ -158,9 +212,22 @@ pub fn tyre_compound_name(compound: u8) -> &'static str {
 pub struct PacketHeader {
     pub packet_format: u16,
     pub packet_id: u8,
+    /// Session timestamp in seconds; rewinds on flashbacks.
+    pub session_time: f32,
+    /// Frame counter that keeps counting across flashbacks.
+    pub overall_frame_identifier: u32,
     pub player_car_index: u8,
 }
 
+impl PacketHeader {
+    /// Copy the header's session clock onto a frame decoded from this packet.
+    pub fn stamp_game_clock(&self, normalized: &mut NormalizedTelemetry) {
+        let session_time = f64::from(self.session_time);
+        normalized.game_time_s = Some(session_time).filter(|time| time.is_finite() && *time >= 0.0);
+        normalized.game_tick = Some(u64::from(self.overall_frame_identifier));
+    }
+}
+
 /// Telemetry data for a single car (from packet ID 6).
 #[derive(Debug, Clone)]
 pub struct CarTelemetryData {
@@ -211,6 +278,8 @@ pub struct CarStatusData {
     pub actual_tyre_compound: u8,
     /// Laps since tyres were fitted.
     pub tyre_age_laps: u8,
+    /// FIA flag shown to the car (-1 unknown, 0 none, 1 green, 2 blue, 3 yellow).
+    pub vehicle_fia_flags: i8,
     /// ICE power in Watts.
     pub engine_power_ice: f32,
     /// MGU-K power in Watts.
@@ -227,6 +296,165 @@ pub struct CarStatusData {
     pub ers_deployed: f32,
 }
 
+/// Penalty fields of a single car's lap data (from packet ID 2).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LapPenaltyData {
+    /// Accumulated time penalties in seconds.
+    pub penalties_s: u8,
+    /// Total warnings of any kind this session.
+    pub total_warnings: u8,
+    /// Corner-cutting (track limits) warnings this session.
+    pub corner_cutting_warnings: u8,
+    /// Drive-through penalties not yet served.
+    pub unserved_drive_through_pens: u8,
+    /// Stop-go penalties not yet served.
+    pub unserved_stop_go_pens: u8,
+    /// Result status (5 = disqualified).
+    pub result_status: u8,
+}
+
+impl LapPenaltyData {
+    /// Map the lap-data penalty fields onto [`PenaltyState`].
+    pub fn penalty_state(&self) -> PenaltyState {
+        let dsq = self.result_status == RESULT_STATUS_DISQUALIFIED;
+        let active_penalty_kind = if dsq {
+            PenaltyKind::Disqualified
+        } else if self.unserved_stop_go_pens > 0 {
+            PenaltyKind::StopAndGo
+        } else if self.unserved_drive_through_pens > 0 {
+            PenaltyKind::DriveThrough
+        } else {
+            PenaltyKind::None
+        };
+        PenaltyState {
+            active_penalty_kind,
+            penalty_time_s: f32::from(self.penalties_s),
+            track_limit_warnings: u16::from(self.corner_cutting_warnings),
+            drive_through_pending: self.unserved_drive_through_pens > 0,
+            dsq,
+        }
+    }
+}
+
+/// Timing fields of a single car's lap data (from packet ID 2).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LapTimingData {
+    /// Last completed lap in milliseconds; zero until a lap is completed.
+    pub last_lap_time_ms: u32,
+    /// Time into the current lap in milliseconds.
+    pub current_lap_time_ms: u32,
+    /// Distance around the current lap in metres; negative before the line.
+    pub lap_distance_m: f32,
+    /// Current lap number, counting from 1.
+    pub current_lap_num: u8,
+    /// Pit status (0 = none, 1 = pitting, 2 = in the pit area).
+    pub pit_status: u8,
+    /// Sector the car is in (0 = sector 1).
+    pub sector: u8,
+}
+
+/// One car's standing in a Lap Data packet, for the opponents list.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GridLapData {
+    /// Race position, 1 for the leader; zero for an empty grid slot.
+    pub car_position: u8,
+    /// Current lap number, counting from 1.
+    pub current_lap_num: u8,
+    /// Pit status (0 = none, 1 = pitting, 2 = in the pit area).
+    pub pit_status: u8,
+    /// Time behind the race leader in milliseconds; zero outside races.
+    pub delta_to_leader_ms: u32,
+    /// `m_resultStatus`: 0 invalid, 1 inactive, 2 active, 3+ finished or out.
+    pub result_status: u8,
+}
+
+/// Every car's lap data, speed and driver name, kept between packets.
+#[derive(Debug, Clone, Default)]
+pub struct GridState {
+    /// Index of the player's car, from the latest packet header.
+    pub player_index: Option<usize>,
+    /// One row per grid slot, from the latest Lap Data packet.
+    pub laps: Vec<GridLapData>,
+    /// One speed per grid slot, from the latest Car Telemetry packet.
+    pub speeds_kmh: Vec<u16>,
+    /// One name per grid slot, from the latest Participants packet.
+    pub names: Vec<Option<String>>,
+}
+
+impl GridState {
+    /// The other cars on the grid, sorted by position and bounded to `max`
+    /// around the player.
+    ///
+    /// Gaps come from each car's delta to the race leader, so they are only
+    /// known in races, once both cars have one.
+    pub fn opponents(&self, max: usize) -> Vec<OpponentSnapshot> {
+        let player = self.player_index.and_then(|index| self.laps.get(index));
+        let leader_delta = |lap: &GridLapData| {
+            (lap.car_position == 1 || lap.delta_to_leader_ms > 0)
+                .then(|| i64::from(lap.delta_to_leader_ms))
+        };
+        let opponents = self
+            .laps
+            .iter()
+            .enumerate()
+            .filter(|&(index, lap)| {
+                Some(index) != self.player_index
+                    && lap.car_position > 0
+                    && lap.result_status >= RESULT_STATUS_ACTIVE
+            })
+            .map(|(index, lap)| {
+                let gap_to_player_ms = player.and_then(leader_delta).zip(leader_delta(lap)).map(
+                    |(player, opponent)| {
+                        (player - opponent).clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32
+                    },
+                );
+                OpponentSnapshot {
+                    car_index: index as u16,
+                    position: lap.car_position,
+                    gap_to_player_ms,
+                    speed_ms: self
+                        .speeds_kmh
+                        .get(index)
+                        .map_or(0.0, |kmh| f32::from(*kmh) / 3.6),
+                    lap_number: u16::from(lap.current_lap_num),
+                    in_pits: lap.pit_status != 0,
+                    driver_name: self.names.get(index).cloned().flatten(),
+                }
+            })
+            .collect();
+        bound_opponents(opponents, player.map(|lap| lap.car_position), max)
+    }
+}
+
+/// Motion data for a single car (from packet ID 0).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CarMotionData {
+    /// World position in metres, ordered X, Y, Z.
+    pub world_position: [f32; 3],
+    /// Lateral G-force; positive to the car's right.
+    pub g_force_lateral: f32,
+    /// Longitudinal G-force; positive under acceleration.
+    pub g_force_longitudinal: f32,
+    /// Vertical G-force.
+    pub g_force_vertical: f32,
+}
+
+/// Extra motion data of the player car (from packet ID 13, limited fields only).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MotionExData {
+    /// Wheel slip ratios [RL, RR, FL, FR].
+    pub wheel_slip_ratio: [f32; 4],
+}
+
+/// One marshal zone of the Session packet.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MarshalZone {
+    /// Where the zone starts, as a fraction of the lap (0.0-1.0).
+    pub zone_start: f32,
+    /// Flag shown in the zone (-1 unknown, 0 none, 1 green, 2 blue, 3 yellow).
+    pub zone_flag: i8,
+}
+
 /// Session-level data (from packet ID 1, limited fields only).
 #[derive(Debug, Clone, Default)]
 pub struct SessionData {
@@ -234,6 +462,29 @@ pub struct SessionData {
     pub session_type: u8,
     pub track_temperature: i8,
     pub air_temperature: i8,
+    /// Track length in metres.
+    pub track_length_m: u16,
+    /// Seconds left in the session; absent from truncated packets.
+    pub session_time_left_s: Option<u16>,
+    /// Marshal zones around the lap; empty for truncated packets.
+    pub marshal_zones: Vec<MarshalZone>,
+    /// Safety car status (0 = none, 1 = full, 2 = virtual, 3 = formation lap).
+    pub safety_car_status: u8,
+}
+
+impl SessionData {
+    /// The marshal zone containing `lap_distance_m`, if the zones are known.
+    pub fn marshal_zone_at(&self, lap_distance_m: f32) -> Option<&MarshalZone> {
+        if self.track_length_m == 0 || !lap_distance_m.is_finite() {
+            return None;
+        }
+        let fraction = (lap_distance_m / f32::from(self.track_length_m)).clamp(0.0, 1.0);
+        self.marshal_zones
+            .iter()
+            .rev()
+            .find(|zone| zone.zone_start <= fraction)
+            .or_else(|| self.marshal_zones.first())
+    }
 }
 
 /// Combined mutable state stored between UDP packets in `start_monitoring`.
@@ -241,7 +492,12 @@ pub struct SessionData {
 pub struct F125State {
     pub latest_telemetry: Option<CarTelemetryData>,
     pub latest_status: Option<CarStatusData>,
+    pub latest_penalties: Option<LapPenaltyData>,
+    pub latest_timing: Option<LapTimingData>,
+    pub latest_motion: Option<CarMotionData>,
+    pub latest_motion_ex: Option<MotionExData>,
     pub session: SessionData,
+    pub grid: GridState,
 }
 
 // ── Adapter struct ────────────────────────────────────────────────────────────
@@ -254,9 +510,13 @@ pub struct F125State {
 #[derive(Clone)]
 pub struct F1_25Adapter {
     bind_port: u16,
+    listen_mode: ListenMode,
     update_rate: Duration,
+    max_opponents: usize,
     heartbeat_timeout: Duration,
     last_packet_ns: Arc<AtomicU64>,
+    subscription: SubscriptionSlot,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for F1_25Adapter {
@@ -272,9 +532,13 @@ impl F1_25Adapter {
         let heartbeat_ms = env_u64(ENV_HEARTBEAT_MS, DEFAULT_HEARTBEAT_TIMEOUT_MS);
         Self {
             bind_port,
+            listen_mode: ListenMode::Unicast,
             update_rate: Duration::from_millis(16),
+            max_opponents: DEFAULT_MAX_OPPONENTS,
             heartbeat_timeout: Duration::from_millis(heartbeat_ms),
             last_packet_ns: Arc::new(AtomicU64::new(0)),
+            subscription: SubscriptionSlot::default(),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -284,6 +548,18 @@ impl F1_25Adapter {
         self
     }
 
+    /// Receive broadcast or multicast datagrams instead of unicast.
+    pub fn with_listen_mode(mut self, listen_mode: ListenMode) -> Self {
+        self.listen_mode = listen_mode;
+        self
+    }
+
+    /// Keep at most `max` other cars on each frame.
+    pub fn with_max_opponents(mut self, max: usize) -> Self {
+        self.max_opponents = max;
+        self
+    }
+
     fn is_recent_packet(&self) -> bool {
         let last = self.last_packet_ns.load(Ordering::Relaxed);
         if last == 0 {
@@ -304,28 +580,51 @@ impl F1_25Adapter {
     ) -> Result<Option<NormalizedTelemetry>> {
         let header = parse_header(raw)?;
         if header.packet_format != PACKET_FORMAT_2025 {
-            return Err(anyhow!(
-                "F1 25: unexpected packet format {} (expected {})",
-                header.packet_format,
-                PACKET_FORMAT_2025
-            ));
+            return Err(NotMyPacket::new(
+                "f1_25",
+                format!(
+                    "F1 25: unexpected packet format {} (expected {})",
+                    header.packet_format, PACKET_FORMAT_2025
+                ),
+            )
+            .into());
         }
 
         let player = usize::from(header.player_car_index);
+        state.grid.player_index = Some(player);
         match header.packet_id {
+            PACKET_ID_MOTION => {
+                state.latest_motion = Some(parse_car_motion(raw, player)?);
+                Ok(None)
+            }
+            PACKET_ID_MOTION_EX => {
+                state.latest_motion_ex = Some(parse_motion_ex(raw)?);
+                Ok(None)
+            }
             PACKET_ID_SESSION => {
                 state.session = parse_session_data(raw)?;
                 Ok(None)
             }
+            PACKET_ID_LAP_DATA => {
+                state.latest_penalties = Some(parse_lap_penalties(raw, player)?);
+                state.latest_timing = Some(parse_lap_timing(raw, player)?);
+                state.grid.laps = parse_lap_grid(raw)?;
+                Ok(None)
+            }
+            PACKET_ID_PARTICIPANTS => {
+                state.grid.names = parse_participants(raw)?;
+                Ok(None)
+            }
             PACKET_ID_CAR_TELEMETRY => {
                 let telem = parse_car_telemetry(raw, player)?;
                 state.latest_telemetry = Some(telem);
-                Ok(Self::maybe_emit(state))
+                state.grid.speeds_kmh = parse_grid_speeds(raw)?;
+                Ok(Self::maybe_emit(state, &header))
             }
             PACKET_ID_CAR_STATUS => {
                 let status = parse_car_status(raw, player)?;
                 state.latest_status = Some(status);
-                Ok(Self::maybe_emit(state))
+                Ok(Self::maybe_emit(state, &header))
             }
             other => {
                 debug!(packet_id = other, "F1 25 ignoring unrecognised packet id");
@@ -334,9 +633,30 @@ impl F1_25Adapter {
         }
     }
 
-    fn maybe_emit(state: &F125State) -> Option<NormalizedTelemetry> {
+    fn maybe_emit(state: &F125State, header: &PacketHeader) -> Option<NormalizedTelemetry> {
         match (&state.latest_telemetry, &state.latest_status) {
-            (Some(t), Some(s)) => Some(normalize(t, s, &state.session)),
+            (Some(t), Some(s)) => {
+                let mut normalized = normalize(t, s, &state.session).with_session_timing(
+                    session_timing(state.latest_timing.as_ref(), &state.session),
+                );
+                if let Some(timing) = &state.latest_timing {
+                    apply_lap_flags(&mut normalized.flags, timing, &state.session);
+                    normalized.lap_distance_fraction =
+                        lap_distance_fraction(timing, &state.session);
+                }
+                normalized.penalties = state
+                    .latest_penalties
+                    .as_ref()
+                    .map(LapPenaltyData::penalty_state);
+                if let Some(motion) = &state.latest_motion {
+                    apply_motion(&mut normalized, motion);
+                }
+                if let Some(motion_ex) = &state.latest_motion_ex {
+                    apply_wheel_slip(&mut normalized, motion_ex);
+                }
+                header.stamp_game_clock(&mut normalized);
+                Some(normalized)
+            }
             _ => None,
         }
     }
@@ -344,54 +664,95 @@ impl F1_25Adapter {
 
 // ── TelemetryAdapter impl ─────────────────────────────────────────────────────
 
+/// F1 25 UDP units; speed arrives in km/h and steering as a lock fraction.
+const UNIT_MANIFEST: UnitManifest = UnitManifest::new(&[
+    FieldUnit::converted("speed_ms", Unit::KilometersPerHour, Unit::MetersPerSecond),
+    FieldUnit::native("rpm", Unit::Rpm),
+    FieldUnit::native("max_rpm", Unit::Rpm),
+    FieldUnit::native("throttle", Unit::Fraction),
+    FieldUnit::native("brake", Unit::Fraction),
+    FieldUnit::converted("steering_angle", Unit::Fraction, Unit::Radians),
+    FieldUnit::native("engine_temp_c", Unit::Celsius),
+    FieldUnit::native("tire_temps_c", Unit::Celsius),
+    FieldUnit::native("tire_pressures_psi", Unit::Psi),
+    FieldUnit::native("rpm_fraction", Unit::Fraction),
+    FieldUnit::native("lateral_g", Unit::StandardGravity),
+    FieldUnit::native("longitudinal_g", Unit::StandardGravity),
+    FieldUnit::native("vertical_g", Unit::StandardGravity),
+]);
+
 #[async_trait]
 impl TelemetryAdapter for F1_25Adapter {
     fn game_id(&self) -> &str {
         "f1_25"
     }
 
+    fn unit_manifest(&self) -> Option<UnitManifest> {
+        Some(UNIT_MANIFEST)
+    }
+
+    fn listen_mode(&self) -> Option<ListenMode> {
+        Some(self.listen_mode)
+    }
+
+    fn udp_port(&self) -> Option<u16> {
+        Some(self.bind_port)
+    }
+
+    fn recognize_packet(&self, raw: &[u8]) -> Option<PacketMatch> {
+        recognize_header(raw, &[PACKET_FORMAT_2025])
+    }
+
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let bind_port = self.bind_port;
+        let network = AdapterNetworkConfig::new(bind_port).with_listen_mode(self.listen_mode);
         let update_rate = self.update_rate;
+        let max_opponents = self.max_opponents;
         let last_packet_ns = Arc::clone(&self.last_packet_ns);
         let (tx, rx) = mpsc::channel(100);
 
-        tokio::spawn(async move {
-            let addr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, bind_port));
-            let socket = match UdpSocket::bind(addr).await {
-                Ok(s) => s,
-                Err(err) => {
-                    warn!(error = %err, port = bind_port, "F1 25 UDP socket bind failed");
-                    return;
-                }
-            };
-            info!(port = bind_port, "F1 25 UDP adapter bound");
+        let mut subscription = UdpPortBroker::shared()
+            .subscribe(network, self.game_id())
+            .with_context(|| format!("F1 25 could not listen on UDP port {bind_port}"))?;
+        self.subscription.replace(subscription.handle()).await;
+        info!(port = bind_port, mode = %self.listen_mode, "F1 25 UDP adapter subscribed");
 
+        tokio::spawn(async move {
             let mut state = F125State::default();
             let mut frame_seq = 0u64;
-            let mut buf = vec![0u8; MAX_PACKET_BYTES];
             let timeout = update_rate * 4;
 
             loop {
-                let recv_result = tokio::time::timeout(timeout, socket.recv(&mut buf)).await;
-                let len = match recv_result {
-                    Ok(Ok(n)) => n,
-                    Ok(Err(err)) => {
-                        warn!(error = %err, "F1 25 UDP receive error");
-                        continue;
-                    }
+                let datagram = match tokio::time::timeout(timeout, subscription.recv()).await {
+                    Ok(Some(datagram)) => datagram,
+                    Ok(None) => break,
                     Err(_) => {
                         debug!("F1 25 UDP receive timeout");
                         continue;
                     }
                 };
 
+                let len = datagram.payload.len();
+                let processed = Self::process_packet(&mut state, &datagram.payload);
+                subscription.record(&processed);
+                raw_capture.record_decoded(&datagram.payload, Some(datagram.source), &processed);
+                if matches!(&processed, Err(err) if err.is::<NotMyPacket>()) {
+                    debug!(len, "Ignoring non-F1 25 datagram");
+                    continue;
+                }
+
                 last_packet_ns.store(telemetry_now_ns(), Ordering::Relaxed);
 
-                match Self::process_packet(&mut state, &buf[..len]) {
+                match processed {
                     Ok(Some(normalized)) => {
                         let ts = telemetry_now_ns();
-                        let frame = TelemetryFrame::new(normalized, ts, frame_seq, len);
+                        let frame = TelemetryFrame::new(normalized, ts, frame_seq, len)
+                            .with_opponents(state.grid.opponents(max_opponents));
                         if tx.send(frame).await.is_err() {
                             break;
                         }
@@ -409,6 +770,7 @@ impl TelemetryAdapter for F1_25Adapter {
     }
 
     async fn stop_monitoring(&self) -> Result<()> {
+        self.subscription.release().await;
         Ok(())
     }
 
@@ -420,17 +782,23 @@ impl TelemetryAdapter for F1_25Adapter {
     fn normalize(&self, raw: &[u8]) -> Result<NormalizedTelemetry> {
         let header = parse_header(raw)?;
         if header.packet_format != PACKET_FORMAT_2025 {
-            return Err(anyhow!(
-                "F1 25: unexpected packet format {} in normalize()",
-                header.packet_format
-            ));
+            return Err(NotMyPacket::new(
+                "f1_25",
+                format!(
+                    "F1 25: unexpected packet format {} in normalize()",
+                    header.packet_format
+                ),
+            )
+            .into());
         }
         let player = usize::from(header.player_car_index);
         match header.packet_id {
             PACKET_ID_CAR_TELEMETRY => {
                 let telem = parse_car_telemetry(raw, player)?;
                 let status = CarStatusData::default_for_normalize();
-                Ok(normalize(&telem, &status, &SessionData::default()))
+                let mut normalized = normalize(&telem, &status, &SessionData::default());
+                header.stamp_game_clock(&mut normalized);
+                Ok(normalized)
             }
             PACKET_ID_CAR_STATUS => {
                 // Cannot produce speed/gear without telemetry.
@@ -446,6 +814,24 @@ impl TelemetryAdapter for F1_25Adapter {
                     "F1 25 normalize() received Session (ID 1); not a complete telemetry packet"
                 ))
             }
+            PACKET_ID_LAP_DATA => {
+                let _ = parse_lap_penalties(raw, player)?; // validate only
+                Err(anyhow!(
+                    "F1 25 normalize() received Lap Data (ID 2); not a complete telemetry packet"
+                ))
+            }
+            PACKET_ID_MOTION => {
+                let _ = parse_car_motion(raw, player)?; // validate only
+                Err(anyhow!(
+                    "F1 25 normalize() received Motion (ID 0); not a complete telemetry packet"
+                ))
+            }
+            PACKET_ID_MOTION_EX => {
+                let _ = parse_motion_ex(raw)?; // validate only
+                Err(anyhow!(
+                    "F1 25 normalize() received Motion Ex (ID 13); not a complete telemetry packet"
+                ))
+            }
             other => Err(anyhow!(
                 "F1 25 normalize(): unsupported packet id {}",
                 other
@@ -464,117 +850,26 @@ impl TelemetryAdapter for F1_25Adapter {
 
 // ── Low-level binary parsing ──────────────────────────────────────────────────
 
-/// Cursor-style byte reader for little-endian binary data.
-pub struct ByteReader<'a> {
-    data: &'a [u8],
-    pos: usize,
-}
-
-impl<'a> ByteReader<'a> {
-    pub fn new(data: &'a [u8]) -> Self {
-        Self { data, pos: 0 }
-    }
-
-    pub fn at(data: &'a [u8], offset: usize) -> Self {
-        Self { data, pos: offset }
-    }
-
-    #[inline]
-    pub fn u8(&mut self) -> Result<u8> {
-        if self.pos >= self.data.len() {
-            return Err(anyhow!("out of bounds: u8 at offset {}", self.pos));
-        }
-        let v = self.data[self.pos];
-        self.pos += 1;
-        Ok(v)
-    }
-
-    #[inline]
-    pub fn i8(&mut self) -> Result<i8> {
-        self.u8().map(|v| v as i8)
-    }
-
-    #[inline]
-    pub fn u16_le(&mut self) -> Result<u16> {
-        let end = self.pos + 2;
-        if end > self.data.len() {
-            return Err(anyhow!("out of bounds: u16 at offset {}", self.pos));
-        }
-        let v = u16::from_le_bytes([self.data[self.pos], self.data[self.pos + 1]]);
-        self.pos = end;
-        Ok(v)
-    }
-
-    #[inline]
-    pub fn u32_le(&mut self) -> Result<u32> {
-        self.read_n4().map(u32::from_le_bytes)
-    }
-
-    #[inline]
-    pub fn u64_le(&mut self) -> Result<u64> {
-        let end = self.pos + 8;
-        if end > self.data.len() {
-            return Err(anyhow!("out of bounds: u64 at offset {}", self.pos));
-        }
-        let mut bytes = [0u8; 8];
-        bytes.copy_from_slice(&self.data[self.pos..end]);
-        self.pos = end;
-        Ok(u64::from_le_bytes(bytes))
-    }
-
-    #[inline]
-    pub fn f32_le(&mut self) -> Result<f32> {
-        self.read_n4()
-            .map(f32::from_le_bytes)
-            .map(|v| if v.is_finite() { v } else { 0.0 })
-    }
-
-    fn read_n4(&mut self) -> Result<[u8; 4]> {
-        let end = self.pos + 4;
-        if end > self.data.len() {
-            return Err(anyhow!("out of bounds: 4-byte read at offset {}", self.pos));
-        }
-        let mut b = [0u8; 4];
-        b.copy_from_slice(&self.data[self.pos..end]);
-        self.pos = end;
-        Ok(b)
-    }
+/// Bounds-checked little-endian reader shared with the other packet parsers.
+pub use openracing_byte_reader::ByteReader;
 
-    pub fn u8_array<const N: usize>(&mut self) -> Result<[u8; N]> {
-        let end = self.pos + N;
-        if end > self.data.len() {
-            return Err(anyhow!("out of bounds: [u8; {}] at offset {}", N, self.pos));
-        }
-        let mut arr = [0u8; N];
-        arr.copy_from_slice(&self.data[self.pos..end]);
-        self.pos = end;
-        Ok(arr)
-    }
-
-    pub fn u16_le_array<const N: usize>(&mut self) -> Result<[u16; N]> {
-        let mut arr = [0u16; N];
-        for item in arr.iter_mut() {
-            *item = self.u16_le()?;
-        }
-        Ok(arr)
-    }
-
-    pub fn f32_le_array<const N: usize>(&mut self) -> Result<[f32; N]> {
-        let mut arr = [0.0f32; N];
-        for item in arr.iter_mut() {
-            *item = self.f32_le()?;
-        }
-        Ok(arr)
-    }
-
-    pub fn skip(&mut self, n: usize) -> Result<()> {
-        let end = self.pos + n;
-        if end > self.data.len() {
-            return Err(anyhow!("out of bounds: skip {} at offset {}", n, self.pos));
-        }
-        self.pos = end;
-        Ok(())
-    }
+/// Recognize an EA F1 header whose `packetFormat` is one of `formats`.
+///
+/// Since F1 23 the header's `gameYear` byte repeats the format's last two
+/// digits, which is a much stronger signal than the format word alone.
+pub(crate) fn recognize_header(raw: &[u8], formats: &[u16]) -> Option<PacketMatch> {
+    let header = parse_header(raw).ok()?;
+    if !formats.contains(&header.packet_format) || header.packet_id > MAX_PACKET_ID {
+        return None;
+    }
+    let year_matches = raw
+        .get(2)
+        .is_some_and(|year| u16::from(*year) == header.packet_format % 100);
+    let confidence = if year_matches { 0.95 } else { 0.75 };
+    Some(PacketMatch::new(
+        confidence,
+        format!("F1 {} format", header.packet_format),
+    ))
 }
 
 // ── Parse individual packet types ────────────────────────────────────────────
@@ -593,14 +888,16 @@ pub fn parse_header(raw: &[u8]) -> Result<PacketHeader> {
     r.skip(4)?; // gameYear, majorVersion, minorVersion, packetVersion  (2-5)
     let packet_id = r.u8()?; // 6
     r.skip(8)?; // sessionUID  (7-14)
-    r.skip(4)?; // sessionTime  (15-18)
+    let session_time = r.f32_le()?; // sessionTime  (15-18)
     r.skip(4)?; // frameIdentifier  (19-22)
-    r.skip(4)?; // overallFrameIdentifier  (23-26)
+    let overall_frame_identifier = r.u32_le()?; // overallFrameIdentifier  (23-26)
     let player_car_index = r.u8()?; // 27
     // byte 28: secondaryPlayerCarIndex (skip)
     Ok(PacketHeader {
         packet_format,
         packet_id,
+        session_time,
+        overall_frame_identifier,
         player_car_index,
     })
 }
@@ -626,20 +923,22 @@ pub fn parse_car_telemetry(raw: &[u8], player_index: usize) -> Result<CarTelemet
     let mut r = ByteReader::at(raw, car_offset);
 
     let speed_kmh = r.u16_le()?; // 0-1
-    let throttle = r.f32_le()?; // 2-5
-    let steer = r.f32_le()?; // 6-9
-    let brake = r.f32_le()?; // 10-13
+    let throttle = r.f32_le_finite()?; // 2-5
+    let steer = r.f32_le_finite()?; // 6-9
+    let brake = r.f32_le_finite()?; // 10-13
     r.skip(1)?; // clutch (14)
     let gear = r.i8()?; // 15
     let engine_rpm = r.u16_le()?; // 16-17
     let drs = r.u8()?; // 18
     r.skip(1)?; // revLightsPercent (19)
     r.skip(2)?; // revLightsBitValue (20-21)
-    let brakes_temperature = r.u16_le_array::<4>()?; // 22-29
-    let tyres_surface_temperature = r.u8_array::<4>()?; // 30-33
-    let tyres_inner_temperature = r.u8_array::<4>()?; // 34-37
+    let brakes_temperature = r.read::<[u16; 4]>()?; // 22-29
+    let tyres_surface_temperature = r.array::<4>()?; // 30-33
+    let tyres_inner_temperature = r.array::<4>()?; // 34-37
     let engine_temperature = r.u16_le()?; // 38-39
-    let tyres_pressure = r.f32_le_array::<4>()?; // 40-55
+    let tyres_pressure = r
+        .read::<[f32; 4]>()?
+        .map(|v| if v.is_finite() { v } else { 0.0 }); // 40-55
     // surfaceType[4] bytes 56-59 ignored
 
     Ok(CarTelemetryData {
@@ -682,9 +981,9 @@ pub fn parse_car_status(raw: &[u8], player_index: usize) -> Result<CarStatusData
     let anti_lock_brakes = r.u8()?; // 1
     r.skip(2)?; // fuelMix (2), frontBrakeBias (3)
     let pit_limiter_status = r.u8()?; // 4
-    let fuel_in_tank = r.f32_le()?; // 5-8
+    let fuel_in_tank = r.f32_le_finite()?; // 5-8
     r.skip(4)?; // fuelCapacity (9-12)
-    let fuel_remaining_laps = r.f32_le()?; // 13-16
+    let fuel_remaining_laps = r.f32_le_finite()?; // 13-16
     let max_rpm = r.u16_le()?; // 17-18
     r.skip(2)?; // idleRPM (19-20)
     r.skip(1)?; // maxGears (21)
@@ -693,14 +992,14 @@ pub fn parse_car_status(raw: &[u8], player_index: usize) -> Result<CarStatusData
     let actual_tyre_compound = r.u8()?; // 25
     r.skip(1)?; // visualTyreCompound (26)
     let tyre_age_laps = r.u8()?; // 27
-    r.skip(1)?; // vehicleFiaFlags (28)
-    let engine_power_ice = r.f32_le()?; // 29-32
-    let engine_power_mguk = r.f32_le()?; // 33-36
-    let ers_store_energy = r.f32_le()?; // 37-40
+    let vehicle_fia_flags = r.i8()?; // 28
+    let engine_power_ice = r.f32_le_finite()?; // 29-32
+    let engine_power_mguk = r.f32_le_finite()?; // 33-36
+    let ers_store_energy = r.f32_le_finite()?; // 37-40
     let ers_deploy_mode = r.u8()?; // 41
-    let ers_harvested_mguk = r.f32_le()?; // 42-45
-    let ers_harvested_mguh = r.f32_le()?; // 46-49
-    let ers_deployed = r.f32_le()?; // 50-53
+    let ers_harvested_mguk = r.f32_le_finite()?; // 42-45
+    let ers_harvested_mguh = r.f32_le_finite()?; // 46-49
+    let ers_deployed = r.f32_le_finite()?; // 50-53
     // networkPaused (54) ignored
 
     Ok(CarStatusData {
@@ -713,6 +1012,7 @@ pub fn parse_car_status(raw: &[u8], player_index: usize) -> Result<CarStatusData
         drs_allowed,
         actual_tyre_compound,
         tyre_age_laps,
+        vehicle_fia_flags,
         engine_power_ice,
         engine_power_mguk,
         ers_store_energy,
@@ -741,18 +1041,252 @@ pub fn parse_session_data(raw: &[u8]) -> Result<SessionData> {
     let track_temperature = r.i8()?; // 1
     let air_temperature = r.i8()?; // 2
     r.skip(1)?; // totalLaps
-    r.skip(2)?; // trackLength
+    let track_length_m = r.u16_le()?; // 4-5
     let session_type = r.u8()?; // 6
     let track_id = r.i8()?; // 7
+    let session_time_left_s = if raw.len() >= MIN_SESSION_SIZE + 3 {
+        r.skip(1)?; // formula
+        Some(r.u16_le()?) // 9-10
+    } else {
+        None
+    };
+
+    let mut marshal_zones = Vec::new();
+    let mut safety_car_status = 0;
+    if raw.len() >= MIN_SESSION_WITH_ZONES_SIZE {
+        let mut r = ByteReader::at(raw, HEADER_SIZE + 18);
+        let num_zones = usize::from(r.u8()?).min(MAX_MARSHAL_ZONES); // 18
+        for index in 0..MAX_MARSHAL_ZONES {
+            let zone_start = r.f32_le()?;
+            let zone_flag = r.i8()?;
+            if index < num_zones && zone_start.is_finite() {
+                marshal_zones.push(MarshalZone {
+                    zone_start,
+                    zone_flag,
+                });
+            }
+        } // 19-123
+        safety_car_status = r.u8()?; // 124
+    }
 
     Ok(SessionData {
         track_id,
         session_type,
         track_temperature,
         air_temperature,
+        track_length_m,
+        session_time_left_s,
+        marshal_zones,
+        safety_car_status,
+    })
+}
+
+/// Parse the motion entry for `player_index` from a Motion packet.
+pub fn parse_car_motion(raw: &[u8], player_index: usize) -> Result<CarMotionData> {
+    if raw.len() < MIN_MOTION_PACKET_SIZE {
+        return Err(anyhow!(
+            "F1 25 Motion packet too short: {} bytes (need {})",
+            raw.len(),
+            MIN_MOTION_PACKET_SIZE
+        ));
+    }
+    if player_index >= NUM_CARS {
+        return Err(anyhow!(
+            "F1 25 player car index {} out of range (max {})",
+            player_index,
+            NUM_CARS - 1
+        ));
+    }
+
+    let mut r = ByteReader::at(raw, HEADER_SIZE + player_index * CAR_MOTION_ENTRY_SIZE);
+    let world_position = [r.f32_le_finite()?, r.f32_le_finite()?, r.f32_le_finite()?]; // 0-11
+    // Velocity (12-23), forward and right directions (24-35) ignored
+    r.skip(24)?;
+    let g_force_lateral = r.f32_le_finite()?; // 36-39
+    let g_force_longitudinal = r.f32_le_finite()?; // 40-43
+    let g_force_vertical = r.f32_le_finite()?; // 44-47
+    // yaw, pitch, roll (48-59) ignored
+
+    Ok(CarMotionData {
+        world_position,
+        g_force_lateral,
+        g_force_longitudinal,
+        g_force_vertical,
+    })
+}
+
+/// Parse the player car's wheel slip from a Motion Ex packet.
+pub fn parse_motion_ex(raw: &[u8]) -> Result<MotionExData> {
+    if raw.len() < MIN_MOTION_EX_PACKET_SIZE {
+        return Err(anyhow!(
+            "F1 25 MotionEx packet too short: {} bytes (need {})",
+            raw.len(),
+            MIN_MOTION_EX_PACKET_SIZE
+        ));
+    }
+
+    // Suspension position, velocity, acceleration and wheel speed (0-63)
+    let mut r = ByteReader::at(raw, HEADER_SIZE + 64);
+    let wheel_slip_ratio = r
+        .read::<[f32; 4]>()?
+        .map(|v| if v.is_finite() { v } else { 0.0 }); // 64-79
+    // Slip angles, wheel forces, body motion and camber (80-243) ignored
+
+    Ok(MotionExData { wheel_slip_ratio })
+}
+
+/// Parse the penalty fields for `player_index` from a Lap Data packet.
+///
+/// The F1 24 and F1 25 lap-data layouts are identical, so this is shared with
+/// the `f1_native` adapter for packet format 2024.
+pub fn parse_lap_penalties(raw: &[u8], player_index: usize) -> Result<LapPenaltyData> {
+    check_lap_data_packet(raw, player_index)?;
+
+    // Skip timing, distance, position and pit fields (0-37).
+    let mut r = ByteReader::at(raw, HEADER_SIZE + player_index * LAP_DATA_ENTRY_SIZE + 38);
+    let penalties_s = r.u8()?; // 38
+    let total_warnings = r.u8()?; // 39
+    let corner_cutting_warnings = r.u8()?; // 40
+    let unserved_drive_through_pens = r.u8()?; // 41
+    let unserved_stop_go_pens = r.u8()?; // 42
+    r.skip(2)?; // gridPosition (43), driverStatus (44)
+    let result_status = r.u8()?; // 45
+    // pit lane timers, speed trap (46-56) ignored
+
+    Ok(LapPenaltyData {
+        penalties_s,
+        total_warnings,
+        corner_cutting_warnings,
+        unserved_drive_through_pens,
+        unserved_stop_go_pens,
+        result_status,
+    })
+}
+
+/// Parse the lap timing fields for `player_index` from a Lap Data packet.
+pub fn parse_lap_timing(raw: &[u8], player_index: usize) -> Result<LapTimingData> {
+    check_lap_data_packet(raw, player_index)?;
+
+    let mut r = ByteReader::at(raw, HEADER_SIZE + player_index * LAP_DATA_ENTRY_SIZE);
+    let last_lap_time_ms = r.u32_le()?; // 0
+    let current_lap_time_ms = r.u32_le()?; // 4
+    // Sector times and deltas (8-19)
+    r.skip(12)?;
+    let lap_distance_m = r.f32_le_finite()?; // 20-23
+    // totalDistance, safetyCarDelta (24-31), carPosition (32) ignored
+    r.skip(9)?;
+    let current_lap_num = r.u8()?; // 33
+    let pit_status = r.u8()?; // 34
+    r.skip(1)?; // numPitStops (35)
+    let sector = r.u8()?; // 36
+
+    Ok(LapTimingData {
+        last_lap_time_ms,
+        current_lap_time_ms,
+        lap_distance_m,
+        current_lap_num,
+        pit_status,
+        sector,
     })
 }
 
+/// Parse every car's standing from a Lap Data packet, one row per grid slot.
+///
+/// Shared with the `f1_native` adapter for packet format 2024.
+pub fn parse_lap_grid(raw: &[u8]) -> Result<Vec<GridLapData>> {
+    check_lap_data_packet(raw, 0)?;
+
+    (0..NUM_CARS)
+        .map(|index| {
+            let mut r = ByteReader::at(raw, HEADER_SIZE + index * LAP_DATA_ENTRY_SIZE + 17);
+            let delta_ms_part = r.u16_le()?; // deltaToRaceLeaderMSPart (17-18)
+            let delta_minutes = r.u8()?; // deltaToRaceLeaderMinutesPart (19)
+            r.skip(12)?; // lapDistance, totalDistance, safetyCarDelta (20-31)
+            let car_position = r.u8()?; // 32
+            let current_lap_num = r.u8()?; // 33
+            let pit_status = r.u8()?; // 34
+            r.skip(10)?; // numPitStops .. driverStatus (35-44)
+            let result_status = r.u8()?; // 45
+            Ok(GridLapData {
+                car_position,
+                current_lap_num,
+                pit_status,
+                delta_to_leader_ms: u32::from(delta_minutes) * 60_000 + u32::from(delta_ms_part),
+                result_status,
+            })
+        })
+        .collect()
+}
+
+/// Parse every car's speed in km/h from a Car Telemetry packet.
+pub fn parse_grid_speeds(raw: &[u8]) -> Result<Vec<u16>> {
+    if raw.len() < MIN_CAR_TELEMETRY_PACKET_SIZE {
+        return Err(anyhow!(
+            "F1 CarTelemetry packet too short: {} bytes (need {})",
+            raw.len(),
+            MIN_CAR_TELEMETRY_PACKET_SIZE
+        ));
+    }
+    let speeds = (0..NUM_CARS)
+        .map(|index| ByteReader::at(raw, HEADER_SIZE + index * CAR_TELEMETRY_ENTRY_SIZE).u16_le())
+        .collect::<Result<_, _>>()?;
+    Ok(speeds)
+}
+
+/// Parse the driver names of an F1 25 Participants packet, one per grid slot.
+pub fn parse_participants(raw: &[u8]) -> Result<Vec<Option<String>>> {
+    parse_participant_names(raw, PARTICIPANT_ENTRY_SIZE, PARTICIPANT_NAME_LEN)
+}
+
+/// Driver names from a Participants packet whose entries are `entry_size`
+/// bytes with a `name_len`-byte name after the seven leading id bytes.
+///
+/// Slots past `numActiveCars` and blank names read as `None`.
+pub(crate) fn parse_participant_names(
+    raw: &[u8],
+    entry_size: usize,
+    name_len: usize,
+) -> Result<Vec<Option<String>>> {
+    let min_size = HEADER_SIZE + 1 + NUM_CARS * entry_size;
+    if raw.len() < min_size {
+        return Err(anyhow!(
+            "F1 Participants packet too short: {} bytes (need {})",
+            raw.len(),
+            min_size
+        ));
+    }
+    let active = usize::from(raw[HEADER_SIZE]).min(NUM_CARS);
+    Ok((0..NUM_CARS)
+        .map(|index| {
+            // aiControlled, driverId, networkId, teamId, myTeam, raceNumber,
+            // nationality (0-6), then the name.
+            let start = HEADER_SIZE + 1 + index * entry_size + 7;
+            let name = &raw[start..start + name_len];
+            let name = &name[..name.iter().position(|b| *b == 0).unwrap_or(name_len)];
+            let name = String::from_utf8_lossy(name).trim().to_string();
+            (index < active && !name.is_empty()).then_some(name)
+        })
+        .collect())
+}
+
+fn check_lap_data_packet(raw: &[u8], player_index: usize) -> Result<()> {
+    if raw.len() < MIN_LAP_DATA_PACKET_SIZE {
+        return Err(anyhow!(
+            "F1 LapData packet too short: {} bytes (need {})",
+            raw.len(),
+            MIN_LAP_DATA_PACKET_SIZE
+        ));
+    }
+    if player_index >= NUM_CARS {
+        return Err(anyhow!(
+            "F1 player car index {} out of range (max {})",
+            player_index,
+            NUM_CARS - 1
+        ));
+    }
+    Ok(())
+}
+
 // ── Normalization ─────────────────────────────────────────────────────────────
 
 /// Combine parsed car telemetry, status, and session into [`NormalizedTelemetry`].
@@ -768,7 +1302,7 @@ pub fn normalize(
     let drs_available = status.drs_allowed != 0;
     let pit_limiter = status.pit_limiter_status != 0;
 
-    let flags = TelemetryFlags {
+    let mut flags = TelemetryFlags {
         pit_limiter,
         in_pits: pit_limiter,
         drs_active,
@@ -778,6 +1312,8 @@ pub fn normalize(
         ers_available: status.ers_store_energy > 0.0,
         ..TelemetryFlags::default()
     };
+    apply_fia_flag(&mut flags, status.vehicle_fia_flags);
+    apply_safety_car_status(&mut flags, session.safety_car_status);
 
     let track_id = track_name_from_id(session.track_id);
     let tyre_name = tyre_compound_name(status.actual_tyre_compound);
@@ -817,6 +1353,7 @@ pub fn normalize(
         .engine_temp_c(f32::from(telem.engine_temperature))
         .tire_pressures_psi(tire_pressures)
         .tire_temps_c(tire_temps)
+        .tires(tire_data(telem))
         .flags(flags)
         .track_id(track_id)
         .extended(
@@ -915,6 +1452,17 @@ pub fn normalize(
             "brake_temp_fr_c".to_string(),
             TelemetryValue::Integer(i32::from(telem.brakes_temperature[3])),
         )
+        .extended(
+            "brake_temps_c".to_string(),
+            TelemetryValue::IntArray(front_first(telem.brakes_temperature.map(i32::from)).to_vec()),
+        )
+        .extended(
+            "tyre_inner_temps_c".to_string(),
+            TelemetryValue::IntArray(
+                front_first(telem.tyres_inner_temperature.map(i32::from)).to_vec(),
+            ),
+        )
+        .extended("ers".to_string(), ers_state(status, ers_fraction))
         .extended(
             "session_type".to_string(),
             TelemetryValue::Integer(i32::from(session.session_type)),
@@ -934,6 +1482,128 @@ pub fn normalize(
         .build()
 }
 
+/// Copy the player car's world position and G-forces onto a normalized frame.
+fn apply_motion(normalized: &mut NormalizedTelemetry, motion: &CarMotionData) {
+    normalized.world_position = Some(motion.world_position);
+    normalized.lateral_g = motion.g_force_lateral;
+    normalized.longitudinal_g = motion.g_force_longitudinal;
+    normalized.vertical_g = motion.g_force_vertical;
+}
+
+/// Copy the player car's wheel slip onto a normalized frame: per corner in
+/// `tires` and `extended`, and as the mean magnitude in `slip_ratio`.
+fn apply_wheel_slip(normalized: &mut NormalizedTelemetry, motion_ex: &MotionExData) {
+    let slip = front_first(motion_ex.wheel_slip_ratio);
+    let mut tires = normalized.tires.unwrap_or_default();
+    for (corner, ratio) in [&mut tires.fl, &mut tires.fr, &mut tires.rl, &mut tires.rr]
+        .into_iter()
+        .zip(slip)
+    {
+        corner.slip_ratio = Some(ratio);
+    }
+    normalized.tires = Some(tires);
+    normalized.slip_ratio =
+        (slip.iter().map(|ratio| ratio.abs()).sum::<f32>() / 4.0).clamp(0.0, 1.0);
+    for (key, ratio) in [
+        ExtendedKey::SLIP_RATIO_FL,
+        ExtendedKey::SLIP_RATIO_FR,
+        ExtendedKey::SLIP_RATIO_RL,
+        ExtendedKey::SLIP_RATIO_RR,
+    ]
+    .into_iter()
+    .zip(slip)
+    {
+        if let Err(err) = key.set(normalized, ratio) {
+            debug!("Skipped F1 25 wheel slip: {}", err);
+        }
+    }
+}
+
+/// Reorder an F1 wheel array from RL, RR, FL, FR to FL, FR, RL, RR.
+fn front_first<T: Copy>(wheels: [T; 4]) -> [T; 4] {
+    [wheels[2], wheels[3], wheels[0], wheels[1]]
+}
+
+/// ERS deployment state as one `ers` map, mirroring the flat `ers_*` keys.
+fn ers_state(status: &CarStatusData, store_fraction: f32) -> TelemetryValue {
+    TelemetryValue::Map(BTreeMap::from([
+        (
+            "store_energy_j".to_string(),
+            TelemetryValue::Float(status.ers_store_energy),
+        ),
+        (
+            "store_fraction".to_string(),
+            TelemetryValue::Float(store_fraction),
+        ),
+        (
+            "deploy_mode".to_string(),
+            TelemetryValue::Integer(i32::from(status.ers_deploy_mode)),
+        ),
+        (
+            "harvested_mguk_j".to_string(),
+            TelemetryValue::Float(status.ers_harvested_mguk),
+        ),
+        (
+            "harvested_mguh_j".to_string(),
+            TelemetryValue::Float(status.ers_harvested_mguh),
+        ),
+        (
+            "deployed_j".to_string(),
+            TelemetryValue::Float(status.ers_deployed),
+        ),
+    ]))
+}
+
+/// Per-corner tyre data from the car telemetry packet. Wear arrives in the
+/// car damage packet, which is not decoded, and slip in the motion ex packet.
+fn tire_data(telem: &CarTelemetryData) -> TireData {
+    // F1 wheel arrays are ordered RL, RR, FL, FR.
+    let corner = |wheel: usize| TireCorner {
+        surface_temp_c: Some(f32::from(telem.tyres_surface_temperature[wheel])),
+        pressure_kpa: Some(telem.tyres_pressure[wheel] * PSI_TO_KPA),
+        wear_fraction: None,
+        slip_ratio: None,
+    };
+    TireData::from_corners([corner(2), corner(3), corner(0), corner(1)])
+}
+
+/// Lap and session timing for the player car. The lap data packet carries
+/// no best lap, which only arrives in the session history packet.
+/// Apply the player's pit status and the flag of the marshal zone they are in.
+///
+/// Pit status supersedes the limiter-based `in_pits` guess [`normalize`] makes.
+pub fn apply_lap_flags(flags: &mut TelemetryFlags, lap: &LapTimingData, session: &SessionData) {
+    flags.in_pits = lap.pit_status != 0;
+    if let Some(zone) = session.marshal_zone_at(lap.lap_distance_m) {
+        apply_fia_flag(flags, zone.zone_flag);
+    }
+}
+
+/// Distance around the lap over the track length; `None` until the session
+/// packet gives a length, or while the car is still behind the line.
+fn lap_distance_fraction(lap: &LapTimingData, session: &SessionData) -> Option<f32> {
+    if session.track_length_m == 0 || !lap.lap_distance_m.is_finite() || lap.lap_distance_m < 0.0 {
+        return None;
+    }
+    Some((lap.lap_distance_m / f32::from(session.track_length_m)).clamp(0.0, 1.0))
+}
+
+fn session_timing(lap: Option<&LapTimingData>, session: &SessionData) -> SessionTiming {
+    SessionTiming {
+        current_lap_ms: lap.map(|lap| f64::from(lap.current_lap_time_ms)),
+        last_lap_ms: lap
+            .map(|lap| lap.last_lap_time_ms)
+            .filter(|ms| *ms > 0)
+            .map(f64::from),
+        best_lap_ms: None,
+        lap_number: lap.map(|lap| i32::from(lap.current_lap_num)),
+        sector: lap.map(|lap| lap.sector),
+        session_time_remaining_ms: session
+            .session_time_left_s
+            .map(|seconds| f64::from(seconds) * 1000.0),
+    }
+}
+
 // ── Defaults for single-packet normalize() ────────────────────────────────────
 
 impl CarStatusData {
@@ -949,6 +1619,7 @@ impl CarStatusData {
             drs_allowed: 0,
             actual_tyre_compound: 0,
             tyre_age_laps: 0,
+            vehicle_fia_flags: 0,
             engine_power_ice: 0.0,
             engine_power_mguk: 0.0,
             ers_store_energy: 0.0,
@@ -1082,6 +1753,150 @@ pub fn build_session_packet(
     buf
 }
 
+/// Build a Session packet that also carries `sessionTimeLeft`.
+pub fn build_session_packet_with_time_left(
+    track_id: i8,
+    session_type: u8,
+    session_time_left_s: u16,
+) -> Vec<u8> {
+    let mut buf = build_session_packet(track_id, session_type, 0, 0);
+    buf.push(0); // formula
+    buf.extend_from_slice(&session_time_left_s.to_le_bytes());
+    buf
+}
+
+/// Build a Session packet that runs through `safetyCarStatus`, with `zones`
+/// as its marshal zones.
+pub fn build_session_packet_with_marshal_zones(
+    track_length_m: u16,
+    zones: &[MarshalZone],
+    safety_car_status: u8,
+) -> Vec<u8> {
+    let mut buf = build_session_packet_with_time_left(0, 0, 0);
+    buf[HEADER_SIZE + 4..HEADER_SIZE + 6].copy_from_slice(&track_length_m.to_le_bytes());
+    buf.resize(MIN_SESSION_WITH_ZONES_SIZE, 0);
+    let zone_count = zones.len().min(MAX_MARSHAL_ZONES);
+    buf[HEADER_SIZE + 18] = zone_count as u8;
+    for (index, zone) in zones.iter().take(zone_count).enumerate() {
+        let offset = HEADER_SIZE + 19 + index * 5;
+        buf[offset..offset + 4].copy_from_slice(&zone.zone_start.to_le_bytes());
+        buf[offset + 4] = zone.zone_flag as u8;
+    }
+    buf[HEADER_SIZE + 124] = safety_car_status;
+    buf
+}
+
+/// Build a minimal valid Lap Data packet carrying `penalties` for `player_index`.
+pub fn build_lap_data_packet(player_index: u8, penalties: &LapPenaltyData) -> Vec<u8> {
+    build_lap_data_packet_with_format(PACKET_FORMAT_2025, player_index, penalties)
+}
+
+/// Build a Lap Data packet carrying both `timing` and `penalties` for `player_index`.
+pub fn build_lap_data_packet_with_timing(
+    player_index: u8,
+    timing: &LapTimingData,
+    penalties: &LapPenaltyData,
+) -> Vec<u8> {
+    let mut buf = build_lap_data_packet(player_index, penalties);
+    let offset = HEADER_SIZE + usize::from(player_index) * LAP_DATA_ENTRY_SIZE;
+    buf[offset..offset + 4].copy_from_slice(&timing.last_lap_time_ms.to_le_bytes());
+    buf[offset + 4..offset + 8].copy_from_slice(&timing.current_lap_time_ms.to_le_bytes());
+    buf[offset + 20..offset + 24].copy_from_slice(&timing.lap_distance_m.to_le_bytes());
+    buf[offset + 33] = timing.current_lap_num;
+    buf[offset + 34] = timing.pit_status;
+    buf[offset + 36] = timing.sector;
+    buf
+}
+
+/// Build a Lap Data packet with an explicit packet format (F1 24 shares the layout).
+pub fn build_lap_data_packet_with_format(
+    packet_format: u16,
+    player_index: u8,
+    penalties: &LapPenaltyData,
+) -> Vec<u8> {
+    let mut buf = build_header_bytes(packet_format, PACKET_ID_LAP_DATA, player_index);
+
+    buf.extend(std::iter::repeat_n(0u8, NUM_CARS * LAP_DATA_ENTRY_SIZE));
+    let offset = HEADER_SIZE + usize::from(player_index) * LAP_DATA_ENTRY_SIZE;
+
+    buf[offset + 38] = penalties.penalties_s;
+    buf[offset + 39] = penalties.total_warnings;
+    buf[offset + 40] = penalties.corner_cutting_warnings;
+    buf[offset + 41] = penalties.unserved_drive_through_pens;
+    buf[offset + 42] = penalties.unserved_stop_go_pens;
+    buf[offset + 45] = penalties.result_status;
+
+    // Trailer: timeTrialPBCarIdx (1), timeTrialRivalCarIdx (1)
+    buf.extend_from_slice(&[255u8; 2]);
+    buf
+}
+
+/// Build a Lap Data packet with one row of `grid` per car, from slot 0.
+pub fn build_lap_grid_packet(
+    packet_format: u16,
+    player_index: u8,
+    grid: &[GridLapData],
+) -> Vec<u8> {
+    let mut buf =
+        build_lap_data_packet_with_format(packet_format, player_index, &LapPenaltyData::default());
+    for (index, lap) in grid.iter().take(NUM_CARS).enumerate() {
+        let offset = HEADER_SIZE + index * LAP_DATA_ENTRY_SIZE;
+        let (minutes, ms) = (
+            lap.delta_to_leader_ms / 60_000,
+            lap.delta_to_leader_ms % 60_000,
+        );
+        buf[offset + 17..offset + 19].copy_from_slice(&(ms as u16).to_le_bytes());
+        buf[offset + 19] = minutes as u8;
+        buf[offset + 32] = lap.car_position;
+        buf[offset + 33] = lap.current_lap_num;
+        buf[offset + 34] = lap.pit_status;
+        buf[offset + 45] = lap.result_status;
+    }
+    buf
+}
+
+/// Build an F1 25 Participants packet naming the first `names.len()` cars.
+pub fn build_participants_packet(player_index: u8, names: &[&str]) -> Vec<u8> {
+    let mut buf = build_header_bytes(PACKET_FORMAT_2025, PACKET_ID_PARTICIPANTS, player_index);
+    buf.push(names.len().min(NUM_CARS) as u8);
+    buf.extend(std::iter::repeat_n(0u8, NUM_CARS * PARTICIPANT_ENTRY_SIZE));
+    for (index, name) in names.iter().take(NUM_CARS).enumerate() {
+        let start = HEADER_SIZE + 1 + index * PARTICIPANT_ENTRY_SIZE + 7;
+        let bytes = &name.as_bytes()[..name.len().min(PARTICIPANT_NAME_LEN - 1)];
+        buf[start..start + bytes.len()].copy_from_slice(bytes);
+    }
+    buf
+}
+
+/// Build a Motion packet carrying `motion` for car at `player_index`.
+pub fn build_motion_packet(player_index: u8, motion: &CarMotionData) -> Vec<u8> {
+    let mut buf = build_header_bytes(PACKET_FORMAT_2025, PACKET_ID_MOTION, player_index);
+
+    buf.extend(std::iter::repeat_n(0u8, NUM_CARS * CAR_MOTION_ENTRY_SIZE));
+    let offset = HEADER_SIZE + usize::from(player_index) * CAR_MOTION_ENTRY_SIZE;
+
+    for (axis, value) in motion.world_position.iter().enumerate() {
+        let at = offset + axis * 4;
+        buf[at..at + 4].copy_from_slice(&value.to_le_bytes());
+    }
+    buf[offset + 36..offset + 40].copy_from_slice(&motion.g_force_lateral.to_le_bytes());
+    buf[offset + 40..offset + 44].copy_from_slice(&motion.g_force_longitudinal.to_le_bytes());
+    buf[offset + 44..offset + 48].copy_from_slice(&motion.g_force_vertical.to_le_bytes());
+    buf
+}
+
+/// Build a Motion Ex packet carrying `motion_ex` for the player car.
+pub fn build_motion_ex_packet(player_index: u8, motion_ex: &MotionExData) -> Vec<u8> {
+    let mut buf = build_header_bytes(PACKET_FORMAT_2025, PACKET_ID_MOTION_EX, player_index);
+    buf.resize(MIN_MOTION_EX_PACKET_SIZE, 0);
+
+    for (wheel, ratio) in motion_ex.wheel_slip_ratio.iter().enumerate() {
+        let offset = HEADER_SIZE + 64 + wheel * 4;
+        buf[offset..offset + 4].copy_from_slice(&ratio.to_le_bytes());
+    }
+    buf
+}
+
 fn build_header_bytes(packet_format: u16, packet_id: u8, player_index: u8) -> Vec<u8> {
     let mut buf = Vec::with_capacity(HEADER_SIZE);
     buf.extend_from_slice(&packet_format.to_le_bytes()); // 0-1
@@ -1119,6 +1934,34 @@ mod tests {
         Ok(())
     }
 
+    fn with_clock(mut raw: Vec<u8>, session_time: f32, overall_frame: u32) -> Vec<u8> {
+        raw[15..19].copy_from_slice(&session_time.to_le_bytes());
+        raw[23..27].copy_from_slice(&overall_frame.to_le_bytes());
+        raw
+    }
+
+    #[test]
+    fn parse_header_extracts_session_clock() -> TestResult {
+        let raw = with_clock(build_header_bytes(2025, 6, 0), 734.5, 44_070);
+        let header = parse_header(&raw)?;
+        assert!((header.session_time - 734.5).abs() < f32::EPSILON);
+        assert_eq!(header.overall_frame_identifier, 44_070);
+        Ok(())
+    }
+
+    #[test]
+    fn process_packet_stamps_game_clock_from_emitting_packet() -> TestResult {
+        let mut state = F125State::default();
+        let telem = build_car_telemetry_packet(0, 150, 5, 11500, 0.7, 0.0, 0, [22.0; 4]);
+        F1_25Adapter::process_packet(&mut state, &with_clock(telem, 100.0, 6_000))?;
+        let status = build_car_status_packet(0, 20.0, 3_000_000.0, 1, 0, 18, 15000);
+        let nt = F1_25Adapter::process_packet(&mut state, &with_clock(status, 100.25, 6_015))?
+            .ok_or("should emit")?;
+        assert_eq!(nt.game_time_s, Some(100.25));
+        assert_eq!(nt.game_tick, Some(6_015));
+        Ok(())
+    }
+
     #[test]
     fn parse_header_rejects_short_buffer() {
         let result = parse_header(&[0u8; 10]);
@@ -1234,6 +2077,33 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn lap_data_round_trip_penalty_fields() -> TestResult {
+        let penalties = LapPenaltyData {
+            penalties_s: 5,
+            total_warnings: 4,
+            corner_cutting_warnings: 3,
+            unserved_drive_through_pens: 0,
+            unserved_stop_go_pens: 1,
+            result_status: 2,
+        };
+        let raw = build_lap_data_packet(7, &penalties);
+        assert_eq!(parse_lap_penalties(&raw, 7)?, penalties);
+
+        let state = penalties.penalty_state();
+        assert_eq!(state.active_penalty_kind, PenaltyKind::StopAndGo);
+        assert_eq!(state.penalty_time_s, 5.0);
+        assert_eq!(state.track_limit_warnings, 3);
+        assert!(!state.dsq);
+        Ok(())
+    }
+
+    #[test]
+    fn lap_data_rejects_short_packet() {
+        let result = parse_lap_penalties(&[0u8; HEADER_SIZE + 10], 0);
+        assert!(result.is_err());
+    }
+
     // ── Normalization ───────────────────────────────────────────────────────
 
     #[test]
@@ -1367,6 +2237,57 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn normalize_groups_wheel_temps_and_ers_state() -> TestResult {
+        let telem = CarTelemetryData {
+            speed_kmh: 0,
+            throttle: 0.0,
+            steer: 0.0,
+            brake: 0.0,
+            gear: 1,
+            engine_rpm: 0,
+            drs: 0,
+            // RL, RR, FL, FR.
+            brakes_temperature: [410, 420, 630, 640],
+            tyres_surface_temperature: [0; 4],
+            tyres_inner_temperature: [95, 96, 101, 102],
+            engine_temperature: 0,
+            tyres_pressure: [20.0; 4],
+        };
+        let mut status = CarStatusData::default_for_normalize();
+        status.ers_deploy_mode = 2;
+        status.ers_deployed = 150_000.0;
+        let nt = normalize(&telem, &status, &SessionData::default());
+
+        assert_eq!(
+            nt.extended
+                .get("brake_temps_c")
+                .and_then(TelemetryValue::as_i32_slice),
+            Some(&[630, 640, 410, 420][..])
+        );
+        assert_eq!(
+            nt.extended
+                .get("tyre_inner_temps_c")
+                .and_then(TelemetryValue::as_i32_slice),
+            Some(&[101, 102, 95, 96][..])
+        );
+        assert_eq!(
+            nt.get_extended_path("ers.deployed_j")
+                .and_then(TelemetryValue::as_f32),
+            Some(150_000.0)
+        );
+        assert_eq!(
+            nt.get_extended_path("ers.deploy_mode"),
+            Some(&TelemetryValue::Integer(2))
+        );
+        // The flat keys stay for existing consumers.
+        assert_eq!(
+            nt.extended.get("brake_temp_fl_c"),
+            Some(&TelemetryValue::Integer(630))
+        );
+        Ok(())
+    }
+
     #[test]
     fn normalize_tyre_compound_name_c4() -> TestResult {
         let mut status = CarStatusData::default_for_normalize();
@@ -1440,6 +2361,96 @@ mod tests {
         Ok(())
     }
 
+    /// Twenty cars in slots 0-19, numbered in reverse so slot 19 leads; each
+    /// position is 1.5 s behind the one ahead and slot 10 is in the pits.
+    fn twenty_car_grid() -> Vec<GridLapData> {
+        (0..20u8)
+            .map(|slot| {
+                let position = 20 - slot;
+                GridLapData {
+                    car_position: position,
+                    current_lap_num: 12,
+                    pit_status: u8::from(slot == 10),
+                    delta_to_leader_ms: u32::from(position - 1) * 1_500,
+                    result_status: RESULT_STATUS_ACTIVE,
+                }
+            })
+            .collect()
+    }
+
+    /// Feed a full 20-car session with the player in slot 4 (16th place).
+    fn twenty_car_session() -> Result<F125State, Box<dyn std::error::Error>> {
+        let mut state = F125State::default();
+        let names: Vec<String> = (0..20).map(|slot| format!("Driver {slot}")).collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        let mut telemetry = build_car_telemetry_packet(4, 180, 6, 11000, 1.0, 0.0, 0, [22.0; 4]);
+        for slot in 0..20u16 {
+            let offset = HEADER_SIZE + usize::from(slot) * CAR_TELEMETRY_ENTRY_SIZE;
+            telemetry[offset..offset + 2].copy_from_slice(&(200 + slot).to_le_bytes());
+        }
+
+        for packet in [
+            build_lap_grid_packet(PACKET_FORMAT_2025, 4, &twenty_car_grid()),
+            build_participants_packet(4, &names),
+            telemetry,
+        ] {
+            F1_25Adapter::process_packet(&mut state, &packet)?;
+        }
+        let status = build_car_status_packet(4, 20.0, 3_000_000.0, 1, 0, 18, 15000);
+        F1_25Adapter::process_packet(&mut state, &status)?.ok_or("expected a frame")?;
+        Ok(state)
+    }
+
+    #[test]
+    fn grid_packets_fill_opponents_sorted_without_the_player() -> TestResult {
+        let state = twenty_car_session()?;
+        let opponents = state.grid.opponents(DEFAULT_MAX_OPPONENTS);
+
+        let positions: Vec<u8> = opponents.iter().map(|car| car.position).collect();
+        let expected: Vec<u8> = (1..=20).filter(|position| *position != 16).collect();
+        assert_eq!(positions, expected);
+        assert!(opponents.iter().all(|car| car.car_index != 4));
+
+        let leader = &opponents[0];
+        assert_eq!(leader.car_index, 19);
+        assert_eq!(leader.driver_name.as_deref(), Some("Driver 19"));
+        assert_eq!(leader.gap_to_player_ms, Some(22_500));
+        assert_eq!(leader.lap_number, 12);
+        assert!((leader.speed_ms - 219.0 / 3.6).abs() < 0.01);
+
+        let by_slot = |slot: u16| opponents.iter().find(|car| car.car_index == slot);
+        assert_eq!(by_slot(5).and_then(|car| car.gap_to_player_ms), Some(1_500));
+        assert_eq!(
+            by_slot(3).and_then(|car| car.gap_to_player_ms),
+            Some(-1_500)
+        );
+        assert_eq!(by_slot(10).map(|car| car.in_pits), Some(true));
+        assert_eq!(by_slot(11).map(|car| car.in_pits), Some(false));
+        Ok(())
+    }
+
+    #[test]
+    fn opponent_bound_keeps_nearest_positions_to_the_player() -> TestResult {
+        let state = twenty_car_session()?;
+        let bounded = state.grid.opponents(3);
+        let positions: Vec<u8> = bounded.iter().map(|car| car.position).collect();
+        // One place either side of 16th, then the car two places ahead.
+        assert_eq!(positions, [14, 15, 17]);
+        assert_eq!(state.grid.opponents(3), bounded);
+        assert!(state.grid.opponents(0).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn participants_packet_names_active_slots_only() -> TestResult {
+        let names = parse_participants(&build_participants_packet(0, &["Lando Norris", ""]))?;
+        assert_eq!(names.len(), NUM_CARS);
+        assert_eq!(names[0].as_deref(), Some("Lando Norris"));
+        assert!(names[1..].iter().all(Option::is_none));
+        assert!(parse_participants(&[0u8; MIN_PARTICIPANTS_PACKET_SIZE - 1]).is_err());
+        Ok(())
+    }
+
     #[test]
     fn process_packet_session_updates_state_but_no_emit() -> TestResult {
         let mut state = F125State::default();
@@ -1470,6 +2481,288 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn process_packet_attaches_lap_and_session_timing() -> TestResult {
+        let mut state = F125State::default();
+        F1_25Adapter::process_packet(
+            &mut state,
+            &build_session_packet_with_time_left(11, 3, 1_800),
+        )?;
+        let timing = LapTimingData {
+            last_lap_time_ms: 0,
+            current_lap_time_ms: 51_234,
+            lap_distance_m: 1_450.0,
+            current_lap_num: 1,
+            pit_status: 0,
+            sector: 2,
+        };
+        let lap_pkt = build_lap_data_packet_with_timing(0, &timing, &LapPenaltyData::default());
+        assert_eq!(parse_lap_timing(&lap_pkt, 0)?, timing);
+        F1_25Adapter::process_packet(&mut state, &lap_pkt)?;
+
+        let telem_pkt = build_car_telemetry_packet(0, 100, 4, 10000, 0.5, 0.0, 0, [21.0; 4]);
+        F1_25Adapter::process_packet(&mut state, &telem_pkt)?;
+        let status_pkt = build_car_status_packet(0, 15.0, 2_000_000.0, 0, 0, 13, 14000);
+        let nt = F1_25Adapter::process_packet(&mut state, &status_pkt)?.ok_or("should emit")?;
+
+        let timing = nt.timing.ok_or("expected session timing")?;
+        assert_eq!(timing.current_lap_ms, Some(51_234.0));
+        // No lap has been completed yet.
+        assert_eq!(timing.last_lap_ms, None);
+        assert_eq!(timing.best_lap_ms, None);
+        assert_eq!(timing.lap_number, Some(1));
+        assert_eq!(timing.sector, Some(2));
+        assert_eq!(timing.session_time_remaining_ms, Some(1_800_000.0));
+        Ok(())
+    }
+
+    fn emit_with_lap(
+        session_pkt: &[u8],
+        timing: &LapTimingData,
+        status_pkt: &[u8],
+    ) -> Result<NormalizedTelemetry, Box<dyn std::error::Error>> {
+        let mut state = F125State::default();
+        F1_25Adapter::process_packet(&mut state, session_pkt)?;
+        let lap_pkt = build_lap_data_packet_with_timing(0, timing, &LapPenaltyData::default());
+        F1_25Adapter::process_packet(&mut state, &lap_pkt)?;
+        let telem_pkt = build_car_telemetry_packet(0, 180, 5, 11000, 0.9, 0.0, 0, [22.0; 4]);
+        F1_25Adapter::process_packet(&mut state, &telem_pkt)?;
+        Ok(F1_25Adapter::process_packet(&mut state, status_pkt)?.ok_or("should emit")?)
+    }
+
+    #[test]
+    fn yellow_marshal_zone_raises_yellow_and_clears_green() -> TestResult {
+        let zones = [
+            MarshalZone {
+                zone_start: 0.0,
+                zone_flag: 1,
+            },
+            MarshalZone {
+                zone_start: 0.4,
+                zone_flag: 3,
+            },
+            MarshalZone {
+                zone_start: 0.7,
+                zone_flag: 0,
+            },
+        ];
+        let session_pkt = build_session_packet_with_marshal_zones(5_000, &zones, 0);
+        let session = parse_session_data(&session_pkt)?;
+        assert_eq!(session.track_length_m, 5_000);
+        assert_eq!(session.marshal_zones, zones);
+        let status_pkt = build_car_status_packet(0, 15.0, 2_000_000.0, 0, 0, 13, 14000);
+
+        // 2,500 m is half a lap, inside the yellow zone.
+        let timing = LapTimingData {
+            lap_distance_m: 2_500.0,
+            current_lap_num: 2,
+            ..LapTimingData::default()
+        };
+        let nt = emit_with_lap(&session_pkt, &timing, &status_pkt)?;
+        assert!(nt.has_active_flags());
+        assert!(nt.flags.yellow_flag);
+        assert!(!nt.flags.green_flag);
+
+        // Back in the green first zone nothing is active.
+        let timing = LapTimingData {
+            lap_distance_m: 900.0,
+            ..timing
+        };
+        let nt = emit_with_lap(&session_pkt, &timing, &status_pkt)?;
+        assert!(!nt.has_active_flags());
+        assert!(nt.flags.green_flag);
+        Ok(())
+    }
+
+    #[test]
+    fn vehicle_fia_flags_map_to_flags() -> TestResult {
+        let session_pkt = build_session_packet(10, 10, 30, 22);
+        for (fia_flag, yellow, blue, green) in [
+            (-1i8, false, false, true),
+            (0, false, false, true),
+            (1, false, false, true),
+            (2, false, true, true),
+            (3, true, false, false),
+        ] {
+            let mut status_pkt = build_car_status_packet(0, 15.0, 2_000_000.0, 0, 0, 13, 14000);
+            status_pkt[HEADER_SIZE + 28] = fia_flag as u8;
+            assert_eq!(
+                parse_car_status(&status_pkt, 0)?.vehicle_fia_flags,
+                fia_flag
+            );
+            let nt = emit_with_lap(&session_pkt, &LapTimingData::default(), &status_pkt)?;
+            assert_eq!(nt.flags.yellow_flag, yellow, "flag {fia_flag}");
+            assert_eq!(nt.flags.blue_flag, blue, "flag {fia_flag}");
+            assert_eq!(nt.flags.green_flag, green, "flag {fia_flag}");
+            assert_eq!(nt.has_active_flags(), yellow || blue, "flag {fia_flag}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn safety_car_status_maps_to_flags() -> TestResult {
+        let status_pkt = build_car_status_packet(0, 15.0, 2_000_000.0, 0, 0, 13, 14000);
+        let timing = LapTimingData::default();
+
+        let full = build_session_packet_with_marshal_zones(5_000, &[], 1);
+        let nt = emit_with_lap(&full, &timing, &status_pkt)?;
+        assert!(nt.flags.safety_car);
+        assert!(!nt.flags.virtual_safety_car);
+        assert!(!nt.flags.green_flag);
+
+        let virtual_sc = build_session_packet_with_marshal_zones(5_000, &[], 2);
+        let nt = emit_with_lap(&virtual_sc, &timing, &status_pkt)?;
+        assert!(!nt.flags.safety_car);
+        assert!(nt.flags.virtual_safety_car);
+        assert!(!nt.flags.green_flag);
+
+        let formation = build_session_packet_with_marshal_zones(5_000, &[], 3);
+        let nt = emit_with_lap(&formation, &timing, &status_pkt)?;
+        assert!(nt.flags.formation_lap);
+        assert!(nt.flags.green_flag);
+
+        // A short session packet carries no safety-car status.
+        let short = build_session_packet(10, 10, 30, 22);
+        let session = parse_session_data(&short)?;
+        assert_eq!(session.safety_car_status, 0);
+        assert!(session.marshal_zones.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn lap_distance_over_track_length_gives_the_lap_fraction() -> TestResult {
+        let status_pkt = build_car_status_packet(0, 15.0, 2_000_000.0, 0, 0, 13, 14000);
+        let at = |lap_distance_m| LapTimingData {
+            lap_distance_m,
+            ..LapTimingData::default()
+        };
+
+        let session_pkt = build_session_packet_with_marshal_zones(5000, &[], 0);
+        let nt = emit_with_lap(&session_pkt, &at(1250.0), &status_pkt)?;
+        assert_eq!(nt.lap_distance_fraction, Some(0.25));
+        // Behind the line on the formation lap.
+        let nt = emit_with_lap(&session_pkt, &at(-40.0), &status_pkt)?;
+        assert_eq!(nt.lap_distance_fraction, None);
+
+        // No track length yet.
+        let nt = emit_with_lap(
+            &build_session_packet(10, 10, 30, 22),
+            &at(1250.0),
+            &status_pkt,
+        )?;
+        assert_eq!(nt.lap_distance_fraction, None);
+        Ok(())
+    }
+
+    #[test]
+    fn pit_status_drives_in_pits_and_limiter_stays_separate() -> TestResult {
+        let session_pkt = build_session_packet(10, 10, 30, 22);
+        let limiter_on = build_car_status_packet(0, 15.0, 2_000_000.0, 0, 1, 13, 14000);
+        let limiter_off = build_car_status_packet(0, 15.0, 2_000_000.0, 0, 0, 13, 14000);
+
+        for pit_status in [1u8, 2] {
+            let timing = LapTimingData {
+                pit_status,
+                ..LapTimingData::default()
+            };
+            assert_eq!(
+                parse_lap_timing(
+                    &build_lap_data_packet_with_timing(0, &timing, &LapPenaltyData::default()),
+                    0
+                )?
+                .pit_status,
+                pit_status
+            );
+            let nt = emit_with_lap(&session_pkt, &timing, &limiter_on)?;
+            assert!(nt.flags.in_pits);
+            assert!(nt.flags.pit_limiter);
+        }
+
+        // Limiter on out on track (e.g. behind the safety car) is not in the pits.
+        let nt = emit_with_lap(&session_pkt, &LapTimingData::default(), &limiter_on)?;
+        assert!(nt.flags.pit_limiter);
+        assert!(!nt.flags.in_pits);
+
+        let nt = emit_with_lap(&session_pkt, &LapTimingData::default(), &limiter_off)?;
+        assert!(!nt.flags.pit_limiter);
+        assert!(!nt.flags.in_pits);
+        Ok(())
+    }
+
+    #[test]
+    fn process_packet_attaches_player_motion_and_wheel_slip() -> TestResult {
+        let mut state = F125State::default();
+        let motion = CarMotionData {
+            world_position: [-312.5, 4.25, 870.0],
+            g_force_lateral: -1.8,
+            g_force_longitudinal: 0.6,
+            g_force_vertical: 1.1,
+        };
+        let motion_pkt = build_motion_packet(3, &motion);
+        assert_eq!(parse_car_motion(&motion_pkt, 3)?, motion);
+        assert_eq!(parse_car_motion(&motion_pkt, 0)?, CarMotionData::default());
+        assert!(F1_25Adapter::process_packet(&mut state, &motion_pkt)?.is_none());
+
+        let motion_ex = MotionExData {
+            wheel_slip_ratio: [0.1, -0.2, 0.03, 0.05],
+        };
+        let motion_ex_pkt = build_motion_ex_packet(3, &motion_ex);
+        assert_eq!(parse_motion_ex(&motion_ex_pkt)?, motion_ex);
+        assert!(F1_25Adapter::process_packet(&mut state, &motion_ex_pkt)?.is_none());
+
+        let telem_pkt = build_car_telemetry_packet(3, 100, 4, 10000, 0.5, 0.0, 0, [21.0; 4]);
+        F1_25Adapter::process_packet(&mut state, &telem_pkt)?;
+        let status_pkt = build_car_status_packet(3, 15.0, 2_000_000.0, 0, 0, 13, 14000);
+        let nt = F1_25Adapter::process_packet(&mut state, &status_pkt)?.ok_or("should emit")?;
+
+        assert_eq!(nt.world_position, Some([-312.5, 4.25, 870.0]));
+        assert_eq!(nt.lateral_g, -1.8);
+        assert_eq!(nt.longitudinal_g, 0.6);
+        assert_eq!(nt.vertical_g, 1.1);
+        let tires = nt.tires.ok_or("expected tire data")?;
+        assert_eq!(tires.fl.slip_ratio, Some(0.03));
+        assert_eq!(tires.fr.slip_ratio, Some(0.05));
+        assert_eq!(tires.rl.slip_ratio, Some(0.1));
+        assert_eq!(tires.rr.slip_ratio, Some(-0.2));
+        assert!((nt.slip_ratio - 0.095).abs() < 1e-6);
+        assert_eq!(
+            nt.extended.get(ExtendedKey::SLIP_RATIO_RR.name),
+            Some(&TelemetryValue::Float(-0.2))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn motion_packets_of_the_wrong_size_are_rejected() -> TestResult {
+        let mut state = F125State::default();
+        let motion_pkt = build_motion_packet(0, &CarMotionData::default());
+        let motion_ex_pkt = build_motion_ex_packet(0, &MotionExData::default());
+        for short in [
+            &motion_pkt[..MIN_MOTION_PACKET_SIZE - 1],
+            &motion_pkt[..HEADER_SIZE],
+            &motion_ex_pkt[..MIN_MOTION_EX_PACKET_SIZE - 1],
+        ] {
+            assert!(F1_25Adapter::process_packet(&mut state, short).is_err());
+            assert!(F1_25Adapter::new().normalize(short).is_err());
+        }
+        assert!(state.latest_motion.is_none());
+        assert!(state.latest_motion_ex.is_none());
+
+        // A spectating client reports player index 255.
+        let mut spectator = motion_pkt.clone();
+        spectator[27] = 255;
+        assert!(F1_25Adapter::process_packet(&mut state, &spectator).is_err());
+        assert!(F1_25Adapter::new().normalize(&motion_pkt).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn truncated_session_packet_has_no_time_left() -> TestResult {
+        let session = parse_session_data(&build_session_packet(11, 3, 32, 25))?;
+        assert_eq!(session.session_time_left_s, None);
+        Ok(())
+    }
+
     // ── normalize() on the adapter (single-packet) ──────────────────────────
 
     #[test]
@@ -1575,7 +2868,7 @@ mod tests {
         let val = 1.234_567_f32;
         let bytes = val.to_le_bytes();
         let mut r = ByteReader::new(&bytes);
-        let recovered = r.f32_le()?;
+        let recovered = r.f32_le_finite()?;
         assert!((recovered - val).abs() < 1e-6);
         Ok(())
     }
@@ -1611,6 +2904,7 @@ mod tests {
             drs_allowed: 1,
             actual_tyre_compound: 17, // C4
             tyre_age_laps: 12,
+            vehicle_fia_flags: 1,
             engine_power_ice: 600_000.0,
             engine_power_mguk: 120_000.0,
             ers_store_energy: 3_200_000.0,
@@ -1624,6 +2918,8 @@ mod tests {
             session_type: 3, // Race
             track_temperature: 38,
             air_temperature: 26,
+            session_time_left_s: None,
+            ..Default::default()
         };
 
         let nt = normalize(&car_telem, &car_status, &session);
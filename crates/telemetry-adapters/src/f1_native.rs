@@ -7,9 +7,11 @@
 //!
 //! | Packet ID | Name           | Fields used                                |
 //! |-----------|----------------|--------------------------------------------|
-//! | 1         | Session        | track ID, session type, temperatures       |
+//! | 1         | Session        | track, temperatures, marshal zones, SC     |
+//! | 2         | Lap Data       | penalties, pit status (F1 24 only)         |
+//! | 4         | Participants   | driver names (F1 24 only)                  |
 //! | 6         | Car Telemetry  | speed, gear, RPM, DRS, tyre temps/pressure |
-//! | 7         | Car Status     | fuel, ERS, pit limiter, tyre compound      |
+//! | 7         | Car Status     | fuel, ERS, pit limiter, tyres, FIA flag    |
 //!
 //! All other packet IDs are silently discarded.
 //!
@@ -19,9 +21,14 @@
 //! - F1 24 (format `2024`): CarStatusData is **55 bytes** per car; adds `enginePowerICE`
 //!   and `enginePowerMGUK` before the ERS block.
 //! - CarTelemetryData is 60 bytes per car in both versions, identical to F1 25.
+//! - LapData is decoded for F1 24 only (57 bytes per car, identical to F1 25); the
+//!   F1 23 entry lacks the delta minute fields, shifting the penalty block.
+//! - Participants are decoded for F1 24 only (60 bytes per car, 48-byte name), so
+//!   F1 23 frames carry no opponents.
 //!
 //! ## Default UDP port
-//! `20777` (override with `OPENRACING_F1_NATIVE_UDP_PORT`).
+//! `20777` (override with `OPENRACING_F1_NATIVE_UDP_PORT`), shared through
+//! [`crate::udp_broker`]; other packet formats are rejected with [`NotMyPacket`].
 //!
 //! ## Unit conventions
 //! - Speed: km/h → m/s (÷ 3.6)
@@ -30,23 +37,27 @@
 //! - Fuel remaining: kg
 //! - Temperatures: °C
 
+use crate::codemasters_shared::{apply_fia_flag, apply_safety_car_status};
 use crate::f1_25::{
-    ByteReader, CAR_TELEMETRY_ENTRY_SIZE, ERS_MAX_STORE_ENERGY_J, SessionData, parse_car_telemetry,
-    parse_header, parse_session_data, track_name_from_id, tyre_compound_name,
+    ByteReader, CAR_TELEMETRY_ENTRY_SIZE, ERS_MAX_STORE_ENERGY_J, GridState, LapPenaltyData,
+    LapTimingData, PacketHeader, SessionData, apply_lap_flags, parse_car_telemetry,
+    parse_grid_speeds, parse_header, parse_lap_grid, parse_lap_penalties, parse_lap_timing,
+    parse_participant_names, parse_session_data, track_name_from_id, tyre_compound_name,
 };
+use crate::raw_capture::RawCaptureSlot;
+use crate::udp_broker::{NotMyPacket, SubscriptionSlot, UdpPortBroker};
+use crate::udp_listener::{AdapterNetworkConfig, ListenMode};
 use crate::{
-    NormalizedTelemetry, TelemetryAdapter, TelemetryFlags, TelemetryFrame, TelemetryReceiver,
-    TelemetryValue, telemetry_now_ns,
+    DEFAULT_MAX_OPPONENTS, NormalizedTelemetry, PacketMatch, TelemetryAdapter, TelemetryFlags,
+    TelemetryFrame, TelemetryReceiver, TelemetryValue, telemetry_now_ns,
 };
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow};
 use async_trait::async_trait;
-use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use std::sync::{
     Arc,
     atomic::{AtomicU64, Ordering},
 };
 use std::time::Duration;
-use tokio::net::UdpSocket;
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
@@ -55,13 +66,14 @@ use tracing::{debug, info, warn};
 /// Verified: standard Codemasters/EA F1 UDP port since F1 2019 (EA forums, SimHub).
 const DEFAULT_PORT: u16 = 20777;
 const DEFAULT_HEARTBEAT_TIMEOUT_MS: u64 = 2_000;
-const MAX_PACKET_BYTES: usize = 2048;
 
 // These match the f1_25 constants (same protocol family); redeclared here
 // because the originals are module-private in f1_25.
 const HEADER_SIZE: usize = 29;
 const NUM_CARS: usize = 22;
 const PACKET_ID_SESSION: u8 = 1;
+const PACKET_ID_LAP_DATA: u8 = 2;
+const PACKET_ID_PARTICIPANTS: u8 = 4;
 const PACKET_ID_CAR_TELEMETRY: u8 = 6;
 const PACKET_ID_CAR_STATUS: u8 = 7;
 
@@ -82,6 +94,9 @@ pub const MIN_CAR_STATUS_2023_PACKET_SIZE: usize =
 pub const MIN_CAR_STATUS_2024_PACKET_SIZE: usize =
     HEADER_SIZE + NUM_CARS * CAR_STATUS_2024_ENTRY_SIZE;
 
+pub const PARTICIPANT_2024_ENTRY_SIZE: usize = 60;
+pub const PARTICIPANT_2024_NAME_LEN: usize = 48;
+
 const ENV_PORT: &str = "OPENRACING_F1_NATIVE_UDP_PORT";
 const ENV_HEARTBEAT_MS: &str = "OPENRACING_F1_NATIVE_HEARTBEAT_TIMEOUT_MS";
 
@@ -102,6 +117,8 @@ pub struct F1NativeCarStatusData {
     pub drs_allowed: u8,
     pub actual_tyre_compound: u8,
     pub tyre_age_laps: u8,
+    /// FIA flag shown to the car (-1 unknown, 0 none, 1 green, 2 blue, 3 yellow).
+    pub vehicle_fia_flags: i8,
     /// ICE power in Watts.  `0.0` for F1 23.
     pub engine_power_ice: f32,
     /// MGU-K power in Watts.  `0.0` for F1 23.
@@ -118,7 +135,10 @@ pub struct F1NativeCarStatusData {
 pub struct F1NativeState {
     pub latest_telemetry: Option<crate::f1_25::CarTelemetryData>,
     pub latest_status: Option<F1NativeCarStatusData>,
+    pub latest_penalties: Option<LapPenaltyData>,
+    pub latest_timing: Option<LapTimingData>,
     pub session: SessionData,
+    pub grid: GridState,
 }
 
 // ── Adapter struct ────────────────────────────────────────────────────────────
@@ -135,9 +155,13 @@ pub struct F1NativeState {
 #[derive(Clone)]
 pub struct F1NativeAdapter {
     bind_port: u16,
+    listen_mode: ListenMode,
     update_rate: Duration,
+    max_opponents: usize,
     heartbeat_timeout: Duration,
     last_packet_ns: Arc<AtomicU64>,
+    subscription: SubscriptionSlot,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for F1NativeAdapter {
@@ -153,9 +177,13 @@ impl F1NativeAdapter {
         let heartbeat_ms = env_u64(ENV_HEARTBEAT_MS, DEFAULT_HEARTBEAT_TIMEOUT_MS);
         Self {
             bind_port,
+            listen_mode: ListenMode::Unicast,
             update_rate: Duration::from_millis(16),
+            max_opponents: DEFAULT_MAX_OPPONENTS,
             heartbeat_timeout: Duration::from_millis(heartbeat_ms),
             last_packet_ns: Arc::new(AtomicU64::new(0)),
+            subscription: SubscriptionSlot::default(),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -165,6 +193,18 @@ impl F1NativeAdapter {
         self
     }
 
+    /// Receive broadcast or multicast datagrams instead of unicast.
+    pub fn with_listen_mode(mut self, listen_mode: ListenMode) -> Self {
+        self.listen_mode = listen_mode;
+        self
+    }
+
+    /// Keep at most `max` other cars on each frame.
+    pub fn with_max_opponents(mut self, max: usize) -> Self {
+        self.max_opponents = max;
+        self
+    }
+
     fn is_recent_packet(&self) -> bool {
         let last = self.last_packet_ns.load(Ordering::Relaxed);
         if last == 0 {
@@ -188,23 +228,40 @@ impl F1NativeAdapter {
         match header.packet_format {
             PACKET_FORMAT_2023 | PACKET_FORMAT_2024 => {}
             other => {
-                return Err(anyhow!(
-                    "F1 native: unexpected packet format {} (expected 2023 or 2024)",
-                    other
-                ));
+                return Err(NotMyPacket::new(
+                    "f1_native",
+                    format!("F1 native: unexpected packet format {other} (expected 2023 or 2024)"),
+                )
+                .into());
             }
         }
 
         let player = usize::from(header.player_car_index);
+        state.grid.player_index = Some(player);
         match header.packet_id {
             PACKET_ID_SESSION => {
                 state.session = parse_session_data(raw)?;
                 Ok(None)
             }
+            PACKET_ID_LAP_DATA if header.packet_format == PACKET_FORMAT_2024 => {
+                state.latest_penalties = Some(parse_lap_penalties(raw, player)?);
+                state.latest_timing = Some(parse_lap_timing(raw, player)?);
+                state.grid.laps = parse_lap_grid(raw)?;
+                Ok(None)
+            }
+            PACKET_ID_PARTICIPANTS if header.packet_format == PACKET_FORMAT_2024 => {
+                state.grid.names = parse_participant_names(
+                    raw,
+                    PARTICIPANT_2024_ENTRY_SIZE,
+                    PARTICIPANT_2024_NAME_LEN,
+                )?;
+                Ok(None)
+            }
             PACKET_ID_CAR_TELEMETRY => {
                 let telem = parse_car_telemetry(raw, player)?;
                 state.latest_telemetry = Some(telem);
-                Ok(Self::maybe_emit(state))
+                state.grid.speeds_kmh = parse_grid_speeds(raw)?;
+                Ok(Self::maybe_emit(state, &header))
             }
             PACKET_ID_CAR_STATUS => {
                 let status = match header.packet_format {
@@ -218,7 +275,7 @@ impl F1NativeAdapter {
                     }
                 };
                 state.latest_status = Some(status);
-                Ok(Self::maybe_emit(state))
+                Ok(Self::maybe_emit(state, &header))
             }
             other => {
                 debug!(
@@ -230,9 +287,20 @@ impl F1NativeAdapter {
         }
     }
 
-    fn maybe_emit(state: &F1NativeState) -> Option<NormalizedTelemetry> {
+    fn maybe_emit(state: &F1NativeState, header: &PacketHeader) -> Option<NormalizedTelemetry> {
         match (&state.latest_telemetry, &state.latest_status) {
-            (Some(t), Some(s)) => Some(normalize(t, s, &state.session)),
+            (Some(t), Some(s)) => {
+                let mut normalized = normalize(t, s, &state.session);
+                if let Some(timing) = &state.latest_timing {
+                    apply_lap_flags(&mut normalized.flags, timing, &state.session);
+                }
+                normalized.penalties = state
+                    .latest_penalties
+                    .as_ref()
+                    .map(LapPenaltyData::penalty_state);
+                header.stamp_game_clock(&mut normalized);
+                Some(normalized)
+            }
             _ => None,
         }
     }
@@ -246,51 +314,72 @@ impl TelemetryAdapter for F1NativeAdapter {
         "f1_native"
     }
 
+    fn listen_mode(&self) -> Option<ListenMode> {
+        Some(self.listen_mode)
+    }
+
+    fn udp_port(&self) -> Option<u16> {
+        Some(self.bind_port)
+    }
+
+    fn recognize_packet(&self, raw: &[u8]) -> Option<PacketMatch> {
+        crate::f1_25::recognize_header(raw, &[PACKET_FORMAT_2023, PACKET_FORMAT_2024])
+    }
+
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let bind_port = self.bind_port;
+        let network = AdapterNetworkConfig::new(bind_port).with_listen_mode(self.listen_mode);
         let update_rate = self.update_rate;
+        let max_opponents = self.max_opponents;
         let last_packet_ns = Arc::clone(&self.last_packet_ns);
         let (tx, rx) = mpsc::channel(100);
 
-        tokio::spawn(async move {
-            let addr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, bind_port));
-            let socket = match UdpSocket::bind(addr).await {
-                Ok(s) => s,
-                Err(err) => {
-                    warn!(error = %err, port = bind_port, "F1 native UDP socket bind failed");
-                    return;
-                }
-            };
-            info!(
-                port = bind_port,
-                "F1 native UDP adapter bound (formats 2023/2024)"
-            );
+        let mut subscription = UdpPortBroker::shared()
+            .subscribe(network, self.game_id())
+            .with_context(|| format!("F1 native could not listen on UDP port {bind_port}"))?;
+        self.subscription.replace(subscription.handle()).await;
+        info!(
+            port = bind_port,
+            mode = %self.listen_mode,
+            "F1 native UDP adapter subscribed (formats 2023/2024)"
+        );
 
+        tokio::spawn(async move {
             let mut state = F1NativeState::default();
             let mut frame_seq = 0u64;
-            let mut buf = vec![0u8; MAX_PACKET_BYTES];
             let timeout = update_rate * 4;
 
             loop {
-                let recv_result = tokio::time::timeout(timeout, socket.recv(&mut buf)).await;
-                let len = match recv_result {
-                    Ok(Ok(n)) => n,
-                    Ok(Err(err)) => {
-                        warn!(error = %err, "F1 native UDP receive error");
-                        continue;
-                    }
+                let datagram = match tokio::time::timeout(timeout, subscription.recv()).await {
+                    Ok(Some(datagram)) => datagram,
+                    Ok(None) => break,
                     Err(_) => {
                         debug!("F1 native UDP receive timeout");
                         continue;
                     }
                 };
 
+                let len = datagram.payload.len();
+                let processed = Self::process_packet(&mut state, &datagram.payload);
+                subscription.record(&processed);
+                raw_capture.record_decoded(&datagram.payload, Some(datagram.source), &processed);
+                if matches!(&processed, Err(err) if err.is::<NotMyPacket>()) {
+                    debug!(len, "Ignoring non-F1 2023/2024 datagram");
+                    continue;
+                }
+
                 last_packet_ns.store(telemetry_now_ns(), Ordering::Relaxed);
 
-                match Self::process_packet(&mut state, &buf[..len]) {
+                match processed {
                     Ok(Some(normalized)) => {
                         let ts = telemetry_now_ns();
-                        let frame = TelemetryFrame::new(normalized, ts, frame_seq, len);
+                        let frame = TelemetryFrame::new(normalized, ts, frame_seq, len)
+                            .with_opponents(state.grid.opponents(max_opponents));
                         if tx.send(frame).await.is_err() {
                             break;
                         }
@@ -308,6 +397,7 @@ impl TelemetryAdapter for F1NativeAdapter {
     }
 
     async fn stop_monitoring(&self) -> Result<()> {
+        self.subscription.release().await;
         Ok(())
     }
 
@@ -321,10 +411,14 @@ impl TelemetryAdapter for F1NativeAdapter {
         match header.packet_format {
             PACKET_FORMAT_2023 | PACKET_FORMAT_2024 => {}
             other => {
-                return Err(anyhow!(
-                    "F1 native normalize(): unexpected packet format {} (expected 2023 or 2024)",
-                    other
-                ));
+                return Err(NotMyPacket::new(
+                    "f1_native",
+                    format!(
+                        "F1 native normalize(): unexpected packet format {other} \
+                         (expected 2023 or 2024)"
+                    ),
+                )
+                .into());
             }
         }
 
@@ -333,7 +427,9 @@ impl TelemetryAdapter for F1NativeAdapter {
             PACKET_ID_CAR_TELEMETRY => {
                 let telem = parse_car_telemetry(raw, player)?;
                 let status = F1NativeCarStatusData::default();
-                Ok(normalize(&telem, &status, &SessionData::default()))
+                let mut normalized = normalize(&telem, &status, &SessionData::default());
+                header.stamp_game_clock(&mut normalized);
+                Ok(normalized)
             }
             PACKET_ID_CAR_STATUS => {
                 // Validate only; cannot produce speed/gear without telemetry.
@@ -414,9 +510,9 @@ pub fn parse_car_status_2023(raw: &[u8], player_index: usize) -> Result<F1Native
     let anti_lock_brakes = r.u8()?; // 1
     r.skip(2)?; // fuelMix (2), frontBrakeBias (3)
     let pit_limiter_status = r.u8()?; // 4
-    let fuel_in_tank = r.f32_le()?; // 5-8
+    let fuel_in_tank = r.f32_le_finite()?; // 5-8
     r.skip(4)?; // fuelCapacity (9-12)
-    let fuel_remaining_laps = r.f32_le()?; // 13-16
+    let fuel_remaining_laps = r.f32_le_finite()?; // 13-16
     let max_rpm = r.u16_le()?; // 17-18
     r.skip(2)?; // idleRPM (19-20)
     r.skip(1)?; // maxGears (21)
@@ -425,12 +521,12 @@ pub fn parse_car_status_2023(raw: &[u8], player_index: usize) -> Result<F1Native
     let actual_tyre_compound = r.u8()?; // 25
     r.skip(1)?; // visualTyreCompound (26)
     let tyre_age_laps = r.u8()?; // 27
-    r.skip(1)?; // vehicleFiaFlags (28)
-    let ers_store_energy = r.f32_le()?; // 29-32
+    let vehicle_fia_flags = r.i8()?; // 28
+    let ers_store_energy = r.f32_le_finite()?; // 29-32
     let ers_deploy_mode = r.u8()?; // 33
-    let ers_harvested_mguk = r.f32_le()?; // 34-37
-    let ers_harvested_mguh = r.f32_le()?; // 38-41
-    let ers_deployed = r.f32_le()?; // 42-45
+    let ers_harvested_mguk = r.f32_le_finite()?; // 34-37
+    let ers_harvested_mguh = r.f32_le_finite()?; // 38-41
+    let ers_deployed = r.f32_le_finite()?; // 42-45
     // networkPaused (46) ignored
 
     Ok(F1NativeCarStatusData {
@@ -443,6 +539,7 @@ pub fn parse_car_status_2023(raw: &[u8], player_index: usize) -> Result<F1Native
         drs_allowed,
         actual_tyre_compound,
         tyre_age_laps,
+        vehicle_fia_flags,
         engine_power_ice: 0.0,  // not present in F1 23
         engine_power_mguk: 0.0, // not present in F1 23
         ers_store_energy,
@@ -488,9 +585,9 @@ pub fn parse_car_status_2024(raw: &[u8], player_index: usize) -> Result<F1Native
     let anti_lock_brakes = r.u8()?; // 1
     r.skip(2)?; // fuelMix (2), frontBrakeBias (3)
     let pit_limiter_status = r.u8()?; // 4
-    let fuel_in_tank = r.f32_le()?; // 5-8
+    let fuel_in_tank = r.f32_le_finite()?; // 5-8
     r.skip(4)?; // fuelCapacity (9-12)
-    let fuel_remaining_laps = r.f32_le()?; // 13-16
+    let fuel_remaining_laps = r.f32_le_finite()?; // 13-16
     let max_rpm = r.u16_le()?; // 17-18
     r.skip(2)?; // idleRPM (19-20)
     r.skip(1)?; // maxGears (21)
@@ -499,14 +596,14 @@ pub fn parse_car_status_2024(raw: &[u8], player_index: usize) -> Result<F1Native
     let actual_tyre_compound = r.u8()?; // 25
     r.skip(1)?; // visualTyreCompound (26)
     let tyre_age_laps = r.u8()?; // 27
-    r.skip(1)?; // vehicleFiaFlags (28)
-    let engine_power_ice = r.f32_le()?; // 29-32
-    let engine_power_mguk = r.f32_le()?; // 33-36
-    let ers_store_energy = r.f32_le()?; // 37-40
+    let vehicle_fia_flags = r.i8()?; // 28
+    let engine_power_ice = r.f32_le_finite()?; // 29-32
+    let engine_power_mguk = r.f32_le_finite()?; // 33-36
+    let ers_store_energy = r.f32_le_finite()?; // 37-40
     let ers_deploy_mode = r.u8()?; // 41
-    let ers_harvested_mguk = r.f32_le()?; // 42-45
-    let ers_harvested_mguh = r.f32_le()?; // 46-49
-    let ers_deployed = r.f32_le()?; // 50-53
+    let ers_harvested_mguk = r.f32_le_finite()?; // 42-45
+    let ers_harvested_mguh = r.f32_le_finite()?; // 46-49
+    let ers_deployed = r.f32_le_finite()?; // 50-53
     // networkPaused (54) ignored
 
     Ok(F1NativeCarStatusData {
@@ -519,6 +616,7 @@ pub fn parse_car_status_2024(raw: &[u8], player_index: usize) -> Result<F1Native
         drs_allowed,
         actual_tyre_compound,
         tyre_age_laps,
+        vehicle_fia_flags,
         engine_power_ice,
         engine_power_mguk,
         ers_store_energy,
@@ -544,7 +642,7 @@ pub fn normalize(
     let drs_available = status.drs_allowed != 0;
     let pit_limiter = status.pit_limiter_status != 0;
 
-    let flags = TelemetryFlags {
+    let mut flags = TelemetryFlags {
         pit_limiter,
         in_pits: pit_limiter,
         drs_active,
@@ -554,6 +652,8 @@ pub fn normalize(
         ers_available: status.ers_store_energy > 0.0,
         ..TelemetryFlags::default()
     };
+    apply_fia_flag(&mut flags, status.vehicle_fia_flags);
+    apply_safety_car_status(&mut flags, session.safety_car_status);
 
     let track_id = track_name_from_id(session.track_id);
     let tyre_name = tyre_compound_name(status.actual_tyre_compound);
@@ -901,6 +1001,18 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn normalize_stamps_game_clock_from_header() -> TestResult {
+        let mut raw =
+            build_car_telemetry_packet_native(2024, 0, 120, 4, 9000, 0.5, 0.0, 0.0, 0, [23.0; 4]);
+        raw[15..19].copy_from_slice(&61.5f32.to_le_bytes());
+        raw[23..27].copy_from_slice(&3_690u32.to_le_bytes());
+        let nt = F1NativeAdapter::new().normalize(&raw)?;
+        assert_eq!(nt.game_time_s, Some(61.5));
+        assert_eq!(nt.game_tick, Some(3_690));
+        Ok(())
+    }
+
     // ── process_packet rejects wrong format ─────────────────────────────────
 
     #[test]
@@ -1177,6 +1289,102 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn lap_data_penalties_decoded_for_f24_only() -> TestResult {
+        let penalties = LapPenaltyData {
+            corner_cutting_warnings: 2,
+            unserved_drive_through_pens: 1,
+            ..LapPenaltyData::default()
+        };
+        let telem_raw = build_car_telemetry_packet_native(
+            PACKET_FORMAT_2024,
+            0,
+            200,
+            4,
+            11000,
+            1.0,
+            0.0,
+            0.0,
+            0,
+            [24.0; 4],
+        );
+        let status_raw = build_car_status_packet_f24(0, 10.0, 1_000_000.0, 0, 0, 16, 15000);
+
+        let mut f24 = F1NativeState::default();
+        let lap_raw =
+            crate::f1_25::build_lap_data_packet_with_format(PACKET_FORMAT_2024, 0, &penalties);
+        F1NativeAdapter::process_packet(&mut f24, &lap_raw)?;
+        F1NativeAdapter::process_packet(&mut f24, &telem_raw)?;
+        let norm = F1NativeAdapter::process_packet(&mut f24, &status_raw)?
+            .ok_or("should emit after both telem and status")?;
+        let state = norm.penalties.ok_or("F1 24 frame should carry penalties")?;
+        assert_eq!(state.track_limit_warnings, 2);
+        assert!(state.drive_through_pending);
+
+        let mut f23 = F1NativeState::default();
+        let lap_raw =
+            crate::f1_25::build_lap_data_packet_with_format(PACKET_FORMAT_2023, 0, &penalties);
+        F1NativeAdapter::process_packet(&mut f23, &lap_raw)?;
+        assert!(f23.latest_penalties.is_none());
+        Ok(())
+    }
+
+    fn participants_2024_packet(names: &[String]) -> Vec<u8> {
+        let mut raw = build_f1_native_header_bytes(PACKET_FORMAT_2024, PACKET_ID_PARTICIPANTS, 0);
+        raw.push(names.len() as u8); // numActiveCars
+        raw.extend(std::iter::repeat_n(
+            0u8,
+            NUM_CARS * PARTICIPANT_2024_ENTRY_SIZE,
+        ));
+        for (slot, name) in names.iter().enumerate() {
+            let start = HEADER_SIZE + 1 + slot * PARTICIPANT_2024_ENTRY_SIZE + 7;
+            raw[start..start + name.len()].copy_from_slice(name.as_bytes());
+        }
+        raw
+    }
+
+    #[test]
+    fn f24_grid_packets_fill_opponents_for_twenty_cars() -> TestResult {
+        let grid: Vec<crate::f1_25::GridLapData> = (0..20u8)
+            .map(|slot| crate::f1_25::GridLapData {
+                car_position: slot + 1,
+                current_lap_num: 3,
+                delta_to_leader_ms: u32::from(slot) * 2_000,
+                result_status: 2, // active
+                ..Default::default()
+            })
+            .collect();
+        let names: Vec<String> = (0..20).map(|slot| format!("Driver {slot}")).collect();
+
+        let mut f24 = F1NativeState::default();
+        F1NativeAdapter::process_packet(
+            &mut f24,
+            &crate::f1_25::build_lap_grid_packet(PACKET_FORMAT_2024, 0, &grid),
+        )?;
+        F1NativeAdapter::process_packet(&mut f24, &participants_2024_packet(&names))?;
+
+        let opponents = f24.grid.opponents(DEFAULT_MAX_OPPONENTS);
+        assert_eq!(opponents.len(), 19);
+        assert!(opponents.iter().all(|car| car.car_index != 0));
+        assert!(
+            opponents
+                .windows(2)
+                .all(|pair| pair[0].position < pair[1].position)
+        );
+        let last = &opponents[18];
+        assert_eq!(last.position, 20);
+        assert_eq!(last.driver_name.as_deref(), Some("Driver 19"));
+        assert_eq!(last.gap_to_player_ms, Some(-38_000));
+
+        let mut f23 = F1NativeState::default();
+        F1NativeAdapter::process_packet(
+            &mut f23,
+            &crate::f1_25::build_lap_grid_packet(PACKET_FORMAT_2023, 0, &grid),
+        )?;
+        assert!(f23.grid.opponents(DEFAULT_MAX_OPPONENTS).is_empty());
+        Ok(())
+    }
+
     #[test]
     fn process_packet_updates_session_data() -> TestResult {
         let mut state = F1NativeState::default();
@@ -0,0 +1,258 @@
+//! Mock adapter that misbehaves on purpose.
+//!
+//! [`MockAdapter`](crate::MockAdapter) only produces a clean stream, which
+//! leaves the orchestrator's reconnect, rate limiting and health reporting
+//! untested against real failures. [`FaultyMockAdapter`] follows a
+//! [`FaultScript`]: it can drop and reorder frames, stall, fail to start,
+//! corrupt readings and end its stream early. Random faults come from a
+//! generator seeded by the script, so a given script and start attempt
+//! always produce the same stream.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::{
+    MockFrameSource, NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver,
+    generate_mock_telemetry, telemetry_now_ns,
+};
+
+/// Speed, in m/s, of frames the script corrupts with an absurd speed.
+pub const ABSURD_SPEED_MS: f32 = 10_000.0;
+
+/// Frames in one lap of the default frame source.
+const LAP_FRAMES: u64 = 600;
+
+/// Faults a [`FaultyMockAdapter`] injects. The default script injects none.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FaultScript {
+    /// Seed of the generator behind the probabilistic faults.
+    pub seed: u64,
+    /// Chance that a frame is never sent, leaving a gap in the sequence.
+    pub drop_probability: f64,
+    /// Chance that a frame is held back and sent after the next one.
+    pub reorder_probability: f64,
+    /// Chance that a frame's RPM is NaN.
+    pub nan_rpm_probability: f64,
+    /// Chance that a frame's speed is [`ABSURD_SPEED_MS`].
+    pub absurd_speed_probability: f64,
+    /// Pause for the duration once this many frames of a stream are sent.
+    pub stall: Option<(u64, Duration)>,
+    /// 1-based `start_monitoring` attempts that fail.
+    pub failing_starts: std::ops::Range<u32>,
+    /// End each stream after sending this many frames.
+    pub end_after: Option<u64>,
+}
+
+impl FaultScript {
+    /// A script without faults whose random faults, once added, follow `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            ..Self::default()
+        }
+    }
+
+    pub fn drop_frames(mut self, probability: f64) -> Self {
+        self.drop_probability = probability;
+        self
+    }
+
+    pub fn reorder_frames(mut self, probability: f64) -> Self {
+        self.reorder_probability = probability;
+        self
+    }
+
+    pub fn nan_rpm(mut self, probability: f64) -> Self {
+        self.nan_rpm_probability = probability;
+        self
+    }
+
+    pub fn absurd_speed(mut self, probability: f64) -> Self {
+        self.absurd_speed_probability = probability;
+        self
+    }
+
+    /// Stall for `duration` after the first `frames` frames of each stream.
+    pub fn stall_after(mut self, frames: u64, duration: Duration) -> Self {
+        self.stall = Some((frames, duration));
+        self
+    }
+
+    /// Fail the first `count` calls to `start_monitoring`.
+    pub fn fail_starts(self, count: u32) -> Self {
+        self.fail_starts_from(1, count)
+    }
+
+    /// Fail `count` calls to `start_monitoring` beginning with the 1-based
+    /// `attempt`, e.g. the restarts after a first stream ends.
+    pub fn fail_starts_from(mut self, attempt: u32, count: u32) -> Self {
+        self.failing_starts = attempt..attempt.saturating_add(count);
+        self
+    }
+
+    pub fn end_after(mut self, frames: u64) -> Self {
+        self.end_after = Some(frames);
+        self
+    }
+}
+
+/// SplitMix64; small, seedable and good enough to pick faults.
+#[derive(Debug, Clone)]
+struct FaultRng(u64);
+
+impl FaultRng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// True with `probability`.
+    fn chance(&mut self, probability: f64) -> bool {
+        probability > 0.0 && ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+}
+
+/// Mock adapter that injects the faults of a [`FaultScript`].
+///
+/// Sequence numbers continue across restarts, so frames of a new stream
+/// follow those of the last one.
+pub struct FaultyMockAdapter {
+    game_id: String,
+    script: FaultScript,
+    update_rate: Duration,
+    frame_source: MockFrameSource,
+    start_attempts: AtomicU32,
+    next_sequence: Arc<AtomicU64>,
+}
+
+impl FaultyMockAdapter {
+    pub fn new(game_id: impl Into<String>, script: FaultScript) -> Self {
+        Self {
+            game_id: game_id.into(),
+            script,
+            update_rate: Duration::from_millis(16),
+            frame_source: lap_frame,
+            start_attempts: AtomicU32::new(0),
+            next_sequence: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn with_update_rate(mut self, update_rate: Duration) -> Self {
+        self.update_rate = update_rate;
+        self
+    }
+
+    /// Build each frame's telemetry with `source(sequence)`, before faults.
+    pub fn with_frame_source(mut self, source: MockFrameSource) -> Self {
+        self.frame_source = source;
+        self
+    }
+
+    /// Calls to `start_monitoring` so far, failed ones included.
+    pub fn start_attempts(&self) -> u32 {
+        self.start_attempts.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl TelemetryAdapter for FaultyMockAdapter {
+    fn game_id(&self) -> &str {
+        &self.game_id
+    }
+
+    async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let attempt = self.start_attempts.fetch_add(1, Ordering::SeqCst) + 1;
+        if self.script.failing_starts.contains(&attempt) {
+            return Err(anyhow::anyhow!(
+                "scripted start failure (attempt {attempt})"
+            ));
+        }
+
+        let (tx, rx) = tokio::sync::mpsc::channel(100);
+        let script = self.script.clone();
+        let update_rate = self.update_rate;
+        let frame_source = self.frame_source;
+        let next_sequence = Arc::clone(&self.next_sequence);
+        let mut rng =
+            FaultRng(script.seed ^ u64::from(attempt).wrapping_mul(0x2545_f491_4f6c_dd1d));
+
+        tokio::spawn(async move {
+            let mut sent = 0u64;
+            let mut held: Option<TelemetryFrame> = None;
+            loop {
+                if script.end_after.is_some_and(|limit| sent >= limit) {
+                    break;
+                }
+                if let Some((after, duration)) = script.stall
+                    && sent == after
+                {
+                    tokio::time::sleep(duration).await;
+                }
+
+                let sequence = next_sequence.fetch_add(1, Ordering::SeqCst);
+                if rng.chance(script.drop_probability) {
+                    tokio::time::sleep(update_rate).await;
+                    continue;
+                }
+                let mut telemetry = frame_source(sequence);
+                if rng.chance(script.nan_rpm_probability) {
+                    telemetry.rpm = f32::NAN;
+                }
+                if rng.chance(script.absurd_speed_probability) {
+                    telemetry.speed_ms = ABSURD_SPEED_MS;
+                }
+                let frame = TelemetryFrame::new(telemetry, telemetry_now_ns(), sequence, 64);
+
+                let batch = match held.take() {
+                    // Sent after its successor.
+                    Some(earlier) => vec![frame, earlier],
+                    None if rng.chance(script.reorder_probability) => {
+                        held = Some(frame);
+                        Vec::new()
+                    }
+                    None => vec![frame],
+                };
+                for frame in batch {
+                    if tx.send(frame).await.is_err() {
+                        return;
+                    }
+                    sent += 1;
+                }
+                tokio::time::sleep(update_rate).await;
+            }
+            if let Some(frame) = held {
+                let _ = tx.send(frame).await;
+            }
+        });
+
+        Ok(rx)
+    }
+
+    async fn stop_monitoring(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn normalize(&self, _raw: &[u8]) -> Result<NormalizedTelemetry> {
+        Ok((self.frame_source)(0))
+    }
+
+    fn expected_update_rate(&self) -> Duration {
+        self.update_rate
+    }
+
+    async fn is_game_running(&self) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+/// The mock lap, advanced by sequence number rather than wall time.
+fn lap_frame(sequence: u64) -> NormalizedTelemetry {
+    generate_mock_telemetry((sequence % LAP_FRAMES) as f32 / LAP_FRAMES as f32)
+}
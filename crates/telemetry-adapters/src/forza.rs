@@ -27,15 +27,18 @@
 //! - Packet format: <https://github.com/richstokes/Forza-data-tools> (FM7_packetformat.dat)
 #![cfg_attr(not(windows), allow(unused, dead_code))]
 
+use crate::raw_capture::{RawCaptureSink, RawCaptureSlot};
+use crate::traffic::TrafficAccountant;
+use crate::udp_listener::{AdapterNetworkConfig, ListenMode};
 use crate::{
-    NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver, TelemetryValue,
-    telemetry_now_ns,
+    FieldUnit, NormalizedTelemetry, PacketMatch, TelemetryAdapter, TelemetryFrame,
+    TelemetryReceiver, TelemetryValue, Unit, UnitManifest, telemetry_now_ns,
 };
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
-use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use openracing_byte_reader::ByteReader;
+use std::sync::Arc;
 use std::time::Duration;
-use tokio::net::UdpSocket as TokioUdpSocket;
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
@@ -330,10 +333,8 @@ fn parse_forza_cardash_with_offset(
     let best_lap = read_f32_le(data, OFF_DASH_BEST_LAP + ho).unwrap_or(0.0);
     let last_lap = read_f32_le(data, OFF_DASH_LAST_LAP + ho).unwrap_or(0.0);
     let cur_lap = read_f32_le(data, OFF_DASH_CUR_LAP + ho).unwrap_or(0.0);
-    let lap_number = data
-        .get(OFF_DASH_LAP_NUMBER + ho..OFF_DASH_LAP_NUMBER + ho + 2)
-        .and_then(|b| b.try_into().ok())
-        .map(u16::from_le_bytes)
+    let lap_number = ByteReader::new(data)
+        .u16_le_at(OFF_DASH_LAP_NUMBER + ho)
         .unwrap_or(0);
     let race_pos = data.get(OFF_DASH_RACE_POS + ho).copied().unwrap_or(0);
 
@@ -391,6 +392,25 @@ pub(crate) fn parse_forza_packet(data: &[u8]) -> Result<NormalizedTelemetry> {
     }
 }
 
+/// Recognize a Forza Data Out datagram by its length table and `IsRaceOn`
+/// flag. `horizon` favours the 324-byte Horizon dash over the Motorsport
+/// layouts, since the two families often share port 5300.
+pub(crate) fn recognize_forza_packet(data: &[u8], horizon: bool) -> Option<PacketMatch> {
+    let (format, motorsport) = match detect_format(data.len()) {
+        ForzaPacketFormat::Sled => ("Forza Sled format", true),
+        ForzaPacketFormat::CarDash => ("Forza CarDash format", true),
+        ForzaPacketFormat::Fm8CarDash => ("Forza Motorsport 2023 CarDash format", true),
+        ForzaPacketFormat::Fh4CarDash => ("Forza Horizon CarDash format", false),
+        ForzaPacketFormat::Unknown => return None,
+    };
+    let is_race_on = ByteReader::new(data).i32_le_at(0).ok()?;
+    if !matches!(is_race_on, 0 | 1) {
+        return None;
+    }
+    let confidence = if motorsport == horizon { 0.6 } else { 0.9 };
+    Some(PacketMatch::new(confidence, format))
+}
+
 /// Forza Motorsport / Forza Horizon telemetry adapter.
 ///
 /// Listens for UDP packets on the configured port and decodes the
@@ -398,7 +418,9 @@ pub(crate) fn parse_forza_packet(data: &[u8]) -> Result<NormalizedTelemetry> {
 /// FH4 CarDash formats automatically.
 pub struct ForzaAdapter {
     bind_port: u16,
+    listen_mode: ListenMode,
     update_rate: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for ForzaAdapter {
@@ -411,7 +433,9 @@ impl ForzaAdapter {
     pub fn new() -> Self {
         Self {
             bind_port: DEFAULT_FORZA_PORT,
+            listen_mode: ListenMode::Unicast,
             update_rate: Duration::from_millis(16),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -419,34 +443,128 @@ impl ForzaAdapter {
         self.bind_port = port;
         self
     }
+
+    /// Receive broadcast or multicast datagrams instead of unicast.
+    pub fn with_listen_mode(mut self, listen_mode: ListenMode) -> Self {
+        self.listen_mode = listen_mode;
+        self
+    }
 }
 
+/// Forza Data Out units. Steering and slip angles are normalized by the game
+/// rather than angles, and wheel speeds are rotation rates; all three are
+/// stored as received.
+pub(crate) const UNIT_MANIFEST: UnitManifest = UnitManifest::new(&[
+    FieldUnit::native("speed_ms", Unit::MetersPerSecond),
+    FieldUnit::native("rpm", Unit::Rpm),
+    FieldUnit::native("max_rpm", Unit::Rpm),
+    FieldUnit::converted(
+        "lateral_g",
+        Unit::MetersPerSecondSquared,
+        Unit::StandardGravity,
+    ),
+    FieldUnit::converted(
+        "longitudinal_g",
+        Unit::MetersPerSecondSquared,
+        Unit::StandardGravity,
+    ),
+    FieldUnit::converted(
+        "vertical_g",
+        Unit::MetersPerSecondSquared,
+        Unit::StandardGravity,
+    ),
+    FieldUnit::native("slip_ratio", Unit::Fraction),
+    FieldUnit::converted("slip_angle_fl", Unit::Fraction, Unit::Radians),
+    FieldUnit::converted("slip_angle_fr", Unit::Fraction, Unit::Radians),
+    FieldUnit::converted("slip_angle_rl", Unit::Fraction, Unit::Radians),
+    FieldUnit::converted("slip_angle_rr", Unit::Fraction, Unit::Radians),
+    FieldUnit::native("throttle", Unit::Fraction),
+    FieldUnit::native("brake", Unit::Fraction),
+    FieldUnit::native("clutch", Unit::Fraction),
+    FieldUnit::native("fuel_percent", Unit::Fraction),
+    FieldUnit::converted("steering_angle", Unit::Fraction, Unit::Radians),
+    FieldUnit::converted("tire_temps_c", Unit::Fahrenheit, Unit::Celsius),
+    FieldUnit::native("current_lap_time_s", Unit::Seconds),
+    FieldUnit::native("best_lap_time_s", Unit::Seconds),
+    FieldUnit::native("last_lap_time_s", Unit::Seconds),
+    FieldUnit::converted(
+        "wheel_speed_fl",
+        Unit::RadiansPerSecond,
+        Unit::MetersPerSecond,
+    ),
+    FieldUnit::converted(
+        "wheel_speed_fr",
+        Unit::RadiansPerSecond,
+        Unit::MetersPerSecond,
+    ),
+    FieldUnit::converted(
+        "wheel_speed_rl",
+        Unit::RadiansPerSecond,
+        Unit::MetersPerSecond,
+    ),
+    FieldUnit::converted(
+        "wheel_speed_rr",
+        Unit::RadiansPerSecond,
+        Unit::MetersPerSecond,
+    ),
+    FieldUnit::native("suspension_travel_fl", Unit::Meters),
+    FieldUnit::native("suspension_travel_fr", Unit::Meters),
+    FieldUnit::native("suspension_travel_rl", Unit::Meters),
+    FieldUnit::native("suspension_travel_rr", Unit::Meters),
+]);
+
 #[async_trait]
 impl TelemetryAdapter for ForzaAdapter {
     fn game_id(&self) -> &str {
         "forza_motorsport"
     }
 
+    fn unit_manifest(&self) -> Option<UnitManifest> {
+        Some(UNIT_MANIFEST)
+    }
+
+    fn listen_mode(&self) -> Option<ListenMode> {
+        Some(self.listen_mode)
+    }
+
+    fn udp_port(&self) -> Option<u16> {
+        Some(self.bind_port)
+    }
+
+    fn recognize_packet(&self, raw: &[u8]) -> Option<PacketMatch> {
+        recognize_forza_packet(raw, false)
+    }
+
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(100);
         let bind_port = self.bind_port;
+        let network = AdapterNetworkConfig::new(bind_port).with_listen_mode(self.listen_mode);
         let update_rate = self.update_rate;
 
         tokio::spawn(async move {
-            let bind_addr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, bind_port));
-            let socket = match TokioUdpSocket::bind(bind_addr).await {
+            let socket = match network.bind() {
                 Ok(s) => s,
                 Err(e) => {
                     warn!("Failed to bind Forza UDP socket on port {bind_port}: {e}");
                     return;
                 }
             };
-            info!("Forza adapter listening on UDP port {bind_port}");
+            info!(
+                "Forza adapter listening on UDP port {bind_port} ({})",
+                network.listen_mode
+            );
             let mut buf = [0u8; MAX_PACKET_SIZE];
             let mut frame_seq = 0u64;
 
             loop {
-                match tokio::time::timeout(update_rate * 10, socket.recv(&mut buf)).await {
+                match tokio::time::timeout(update_rate * 10, raw_capture.recv(&socket, &mut buf))
+                    .await
+                {
                     Ok(Ok(len)) => match parse_forza_packet(&buf[..len]) {
                         Ok(normalized) => {
                             let frame =
@@ -532,16 +650,14 @@ fn is_forza_process_running() -> bool {
 }
 
 fn read_f32_le(data: &[u8], offset: usize) -> Option<f32> {
-    data.get(offset..offset + 4)
-        .and_then(|b| b.try_into().ok())
-        .map(f32::from_le_bytes)
+    ByteReader::new(data)
+        .f32_le_at(offset)
+        .ok()
         .filter(|v| v.is_finite())
 }
 
 fn read_i32_le(data: &[u8], offset: usize) -> Option<i32> {
-    data.get(offset..offset + 4)
-        .and_then(|b| b.try_into().ok())
-        .map(i32::from_le_bytes)
+    ByteReader::new(data).i32_le_at(offset).ok()
 }
 
 #[cfg(test)]
@@ -10,15 +10,15 @@
 //! Parsing is delegated entirely to [`crate::forza`]; this module provides
 //! correctly-identified adapter wrappers with the appropriate default ports.
 
+use crate::raw_capture::RawCaptureSlot;
+use crate::udp_listener::{AdapterNetworkConfig, ListenMode};
 use crate::{
-    NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver, forza,
-    telemetry_now_ns,
+    NormalizedTelemetry, PacketMatch, TelemetryAdapter, TelemetryFrame, TelemetryReceiver,
+    UnitManifest, forza, telemetry_now_ns,
 };
 use anyhow::Result;
 use async_trait::async_trait;
-use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use std::time::Duration;
-use tokio::net::UdpSocket as TokioUdpSocket;
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
@@ -32,7 +32,9 @@ const MAX_PACKET_SIZE: usize = 512;
 struct ForzaHorizonAdapter {
     game_id: &'static str,
     bind_port: u16,
+    listen_mode: ListenMode,
     update_rate: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl ForzaHorizonAdapter {
@@ -40,7 +42,9 @@ impl ForzaHorizonAdapter {
         Self {
             game_id,
             bind_port: default_port,
+            listen_mode: ListenMode::Unicast,
             update_rate: Duration::from_millis(16),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 }
@@ -51,27 +55,53 @@ impl TelemetryAdapter for ForzaHorizonAdapter {
         self.game_id
     }
 
+    fn unit_manifest(&self) -> Option<UnitManifest> {
+        Some(forza::UNIT_MANIFEST)
+    }
+
+    fn listen_mode(&self) -> Option<ListenMode> {
+        Some(self.listen_mode)
+    }
+
+    fn udp_port(&self) -> Option<u16> {
+        Some(self.bind_port)
+    }
+
+    fn recognize_packet(&self, raw: &[u8]) -> Option<PacketMatch> {
+        forza::recognize_forza_packet(raw, true)
+    }
+
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(100);
         let bind_port = self.bind_port;
+        let network = AdapterNetworkConfig::new(bind_port).with_listen_mode(self.listen_mode);
         let update_rate = self.update_rate;
         let game_id = self.game_id;
 
         tokio::spawn(async move {
-            let bind_addr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, bind_port));
-            let socket = match TokioUdpSocket::bind(bind_addr).await {
+            let socket = match network.bind() {
                 Ok(s) => s,
                 Err(e) => {
                     warn!("Failed to bind {game_id} UDP socket on port {bind_port}: {e}");
                     return;
                 }
             };
-            info!("{game_id} adapter listening on UDP port {bind_port}");
+            info!(
+                "{game_id} adapter listening on UDP port {bind_port} ({})",
+                network.listen_mode
+            );
             let mut buf = [0u8; MAX_PACKET_SIZE];
             let mut frame_seq = 0u64;
 
             loop {
-                match tokio::time::timeout(update_rate * 10, socket.recv(&mut buf)).await {
+                match tokio::time::timeout(update_rate * 10, raw_capture.recv(&socket, &mut buf))
+                    .await
+                {
                     Ok(Ok(len)) => match forza::parse_forza_packet(&buf[..len]) {
                         Ok(normalized) => {
                             let frame =
@@ -121,6 +151,12 @@ impl ForzaHorizon4Adapter {
             DEFAULT_FH4_PORT,
         ))
     }
+
+    /// Receive broadcast or multicast datagrams instead of unicast.
+    pub fn with_listen_mode(mut self, listen_mode: ListenMode) -> Self {
+        self.0.listen_mode = listen_mode;
+        self
+    }
 }
 
 impl Default for ForzaHorizon4Adapter {
@@ -135,6 +171,26 @@ impl TelemetryAdapter for ForzaHorizon4Adapter {
         self.0.game_id()
     }
 
+    fn unit_manifest(&self) -> Option<UnitManifest> {
+        self.0.unit_manifest()
+    }
+
+    fn listen_mode(&self) -> Option<ListenMode> {
+        self.0.listen_mode()
+    }
+
+    fn udp_port(&self) -> Option<u16> {
+        self.0.udp_port()
+    }
+
+    fn recognize_packet(&self, raw: &[u8]) -> Option<PacketMatch> {
+        self.0.recognize_packet(raw)
+    }
+
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        self.0.raw_capture_slot()
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
         self.0.start_monitoring().await
     }
@@ -166,6 +222,12 @@ impl ForzaHorizon5Adapter {
             DEFAULT_FH5_PORT,
         ))
     }
+
+    /// Receive broadcast or multicast datagrams instead of unicast.
+    pub fn with_listen_mode(mut self, listen_mode: ListenMode) -> Self {
+        self.0.listen_mode = listen_mode;
+        self
+    }
 }
 
 impl Default for ForzaHorizon5Adapter {
@@ -180,6 +242,26 @@ impl TelemetryAdapter for ForzaHorizon5Adapter {
         self.0.game_id()
     }
 
+    fn unit_manifest(&self) -> Option<UnitManifest> {
+        self.0.unit_manifest()
+    }
+
+    fn listen_mode(&self) -> Option<ListenMode> {
+        self.0.listen_mode()
+    }
+
+    fn udp_port(&self) -> Option<u16> {
+        self.0.udp_port()
+    }
+
+    fn recognize_packet(&self, raw: &[u8]) -> Option<PacketMatch> {
+        self.0.recognize_packet(raw)
+    }
+
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        self.0.raw_capture_slot()
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
         self.0.start_monitoring().await
     }
@@ -11,7 +11,10 @@
 //!   `iv2 = iv1 ^ 0xDEAD_BEAF`, nonce = `[iv2_le, iv1_le]`
 //!
 //! A single-byte heartbeat (`b"A"`) must be sent to the PlayStation on port
-//! 33739 every ~100 ms to keep the stream active.
+//! 33739 every ~100 ms to keep the stream active; the console stops after
+//! about 100 packets without one. The console only starts streaming after a
+//! first heartbeat, so [`GranTurismo7Adapter::with_console_address`] names it
+//! up front; otherwise heartbeats go to whichever host last sent a packet.
 //!
 //! ## Verification against Nenkai/PDTools (2025-07)
 //!
@@ -25,7 +28,7 @@
 //! - **Nonce**: `[iv2_le, iv1_le]` where `iv2 = iv1 ^ XorKey` and iv1 from `[0x40..0x44]`. ✓
 //! - **Magic**: `0x47375330` ("0S7G" LE) — matches PDTools `"G7S0"` check. ✓
 //! - **Field offsets**: All verified against `SimulatorPacket.Read()` sequential layout:
-//!   EngineRPM@0x3C, GasLevel@0x44, GasCapacity@0x48, MetersPerSecond@0x4C,
+//!   Position@0x04, EngineRPM@0x3C, GasLevel@0x44, GasCapacity@0x48, MetersPerSecond@0x4C,
 //!   WaterTemp@0x58, TireFL–RR@0x60–0x6C, LapCount@0x74, BestLap@0x78,
 //!   LastLap@0x7C, CurrentLap@0x80, Position@0x84, NumCars@0x86,
 //!   MaxAlertRPM@0x8A, Flags@0x8E, Gear@0x90, Throttle@0x91,
@@ -48,16 +51,20 @@
 //! applies the correct XOR key. Backward compatibility is maintained: 296-byte
 //! packets from older GT7 versions are still parsed correctly.
 
+use crate::raw_capture::RawCaptureSlot;
+use crate::udp_listener::{AdapterNetworkConfig, ListenMode};
 use crate::{
-    NormalizedTelemetry, TelemetryAdapter, TelemetryFlags, TelemetryFrame, TelemetryReceiver,
-    TelemetryValue, telemetry_now_ns,
+    ExtendedKey, FieldUnit, NormalizedTelemetry, TelemetryAdapter, TelemetryFlags, TelemetryFrame,
+    TelemetryReceiver, TelemetryValue, Unit, UnitManifest, telemetry_now_ns,
 };
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
-use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use openracing_byte_reader::ByteReader;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Mutex, PoisonError};
 use std::time::Duration;
-use tokio::net::UdpSocket as TokioUdpSocket;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
 use tracing::{debug, info, warn};
 
 /// UDP port on which GT7 broadcasts telemetry.
@@ -77,6 +84,10 @@ pub const PACKET_SIZE_TYPE2: usize = 0x13C; // 316
 pub const PACKET_SIZE_TYPE3: usize = 0x158; // 344
 /// Maximum packet size across all known types (used for receive buffer).
 pub const MAX_PACKET_SIZE: usize = PACKET_SIZE_TYPE3;
+/// Heartbeat cadence; well inside the ~100-packet window the console allows.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_millis(100);
+/// How long `stop_monitoring` waits for the task to finish.
+const STOP_TIMEOUT: Duration = Duration::from_secs(1);
 
 /// Magic number present in bytes 0–3 of a correctly decrypted packet.
 pub const MAGIC: u32 = 0x4737_5330; // "0S7G" little-endian
@@ -95,6 +106,9 @@ const XOR_KEY_TYPE3: u32 = 0x55FA_BB4F;
 // Authoritative reference: Nenkai/PDTools SimulatorPacket.cs
 // ---------------------------------------------------------------------------
 pub const OFF_MAGIC: usize = 0x00;
+const OFF_WORLD_POS_X: usize = 0x04; // 4 — f32 (metres)
+const OFF_WORLD_POS_Y: usize = 0x08; // 8 — f32
+const OFF_WORLD_POS_Z: usize = 0x0C; // 12 — f32
 const OFF_ENGINE_RPM: usize = 0x3C; // 60 — f32
 const OFF_FUEL_LEVEL: usize = 0x44; // 68 — f32
 const OFF_FUEL_CAPACITY: usize = 0x48; // 72 — f32
@@ -104,6 +118,7 @@ const OFF_TIRE_TEMP_FL: usize = 0x60; // 96 — f32
 const OFF_TIRE_TEMP_FR: usize = 0x64; // 100 — f32
 const OFF_TIRE_TEMP_RL: usize = 0x68; // 104 — f32
 const OFF_TIRE_TEMP_RR: usize = 0x6C; // 108 — f32
+const OFF_PACKET_ID: usize = 0x70; // 112 — i32 (PacketId, increments per packet)
 const OFF_LAP_COUNT: usize = 0x74; // 116 — i16
 const OFF_BEST_LAP_MS: usize = 0x78; // 120 — i32
 const OFF_LAST_LAP_MS: usize = 0x7C; // 124 — i32
@@ -143,7 +158,9 @@ const OFF_ENERGY_RECOVERY: usize = 0x150; // 336 — f32
 const OFF_UNK7: usize = 0x154; // 340 — f32
 
 // GT7 flags bitmask (offset 0x8E, u16 little-endian — SimulatorFlags enum)
+const FLAG_CAR_ON_TRACK: u16 = 1 << 0;
 const FLAG_PAUSED: u16 = 1 << 1;
+const FLAG_LOADING: u16 = 1 << 2;
 const FLAG_REV_LIMIT: u16 = 1 << 5;
 const FLAG_ASM_ACTIVE: u16 = 1 << 10;
 const FLAG_TCS_ACTIVE: u16 = 1 << 11;
@@ -214,8 +231,19 @@ fn detect_packet_type(len: usize) -> Option<Gt7PacketType> {
 /// the source host on [`GT7_SEND_PORT`] to keep the stream alive.
 pub struct GranTurismo7Adapter {
     recv_port: u16,
+    listen_mode: ListenMode,
     update_rate: Duration,
     packet_type: Gt7PacketType,
+    console_addr: Option<IpAddr>,
+    decrypt: bool,
+    monitor: Mutex<Option<MonitorHandle>>,
+    raw_capture: RawCaptureSlot,
+}
+
+/// Running receive/heartbeat task and the signal that stops it.
+struct MonitorHandle {
+    stop: oneshot::Sender<()>,
+    task: JoinHandle<()>,
 }
 
 impl Default for GranTurismo7Adapter {
@@ -228,8 +256,13 @@ impl GranTurismo7Adapter {
     pub fn new() -> Self {
         Self {
             recv_port: GT7_RECV_PORT,
+            listen_mode: ListenMode::Unicast,
             update_rate: Duration::from_millis(17), // ~60 Hz
             packet_type: Gt7PacketType::Type3,      // request maximum data by default
+            console_addr: None,
+            decrypt: true,
+            monitor: Mutex::new(None),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -239,57 +272,148 @@ impl GranTurismo7Adapter {
         self
     }
 
+    /// Receive broadcast or multicast datagrams instead of unicast.
+    ///
+    /// Heartbeats are still sent directly to the console that last sent a
+    /// packet, whatever the receive mode.
+    pub fn with_listen_mode(mut self, listen_mode: ListenMode) -> Self {
+        self.listen_mode = listen_mode;
+        self
+    }
+
     /// Override the packet type (determines heartbeat byte and expected size).
     pub fn with_packet_type(mut self, packet_type: Gt7PacketType) -> Self {
         self.packet_type = packet_type;
         self
     }
+
+    /// Send heartbeats to this console from the start instead of waiting
+    /// for a packet to reveal its address.
+    pub fn with_console_address(mut self, console_addr: IpAddr) -> Self {
+        self.console_addr = Some(console_addr);
+        self
+    }
+
+    /// Turn Salsa20 decryption off to read packets that were captured
+    /// already decrypted; the magic check still applies. On by default.
+    pub fn with_decryption(mut self, decrypt: bool) -> Self {
+        self.decrypt = decrypt;
+        self
+    }
+
+    fn decode(&self, data: &[u8]) -> Result<NormalizedTelemetry> {
+        if self.decrypt {
+            decrypt_and_parse(data)
+        } else {
+            parse_plaintext(data)
+        }
+    }
 }
 
+/// Gran Turismo 7 packet units; lap times arrive in milliseconds.
+const UNIT_MANIFEST: UnitManifest = UnitManifest::new(&[
+    FieldUnit::native("speed_ms", Unit::MetersPerSecond),
+    FieldUnit::native("rpm", Unit::Rpm),
+    FieldUnit::native("max_rpm", Unit::Rpm),
+    FieldUnit::native("throttle", Unit::Fraction),
+    FieldUnit::native("brake", Unit::Fraction),
+    FieldUnit::native("fuel_percent", Unit::Fraction),
+    FieldUnit::native(ExtendedKey::FUEL_LEFT_L.name, Unit::Liters),
+    FieldUnit::native("engine_temp_c", Unit::Celsius),
+    FieldUnit::native("tire_temps_c", Unit::Celsius),
+    FieldUnit::native("steering_angle", Unit::Radians),
+    FieldUnit::native("lateral_g", Unit::StandardGravity),
+    FieldUnit::native("longitudinal_g", Unit::StandardGravity),
+    FieldUnit::native("vertical_g", Unit::StandardGravity),
+    FieldUnit::converted("current_lap_time_s", Unit::Milliseconds, Unit::Seconds),
+    FieldUnit::converted("best_lap_time_s", Unit::Milliseconds, Unit::Seconds),
+    FieldUnit::converted("last_lap_time_s", Unit::Milliseconds, Unit::Seconds),
+]);
+
 #[async_trait]
 impl TelemetryAdapter for GranTurismo7Adapter {
     fn game_id(&self) -> &str {
         "gran_turismo_7"
     }
 
+    fn unit_manifest(&self) -> Option<UnitManifest> {
+        Some(UNIT_MANIFEST)
+    }
+
+    fn listen_mode(&self) -> Option<ListenMode> {
+        Some(self.listen_mode)
+    }
+
+    fn udp_port(&self) -> Option<u16> {
+        Some(self.recv_port)
+    }
+
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(100);
+        let (stop_tx, mut stop_rx) = oneshot::channel::<()>();
         let recv_port = self.recv_port;
+        let network = AdapterNetworkConfig::new(recv_port).with_listen_mode(self.listen_mode);
         let heartbeat_payload: &'static [u8] = self.packet_type.heartbeat();
+        let console_addr = self.console_addr;
+        let decrypt = self.decrypt;
 
-        tokio::spawn(async move {
-            let bind_addr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, recv_port));
-            let socket = match TokioUdpSocket::bind(bind_addr).await {
+        let task = tokio::spawn(async move {
+            let socket = match network.bind() {
                 Ok(s) => s,
                 Err(e) => {
                     warn!("Failed to bind GT7 UDP socket on port {recv_port}: {e}");
                     return;
                 }
             };
-            info!("GT7 adapter listening on UDP port {recv_port}");
+            info!(
+                "GT7 adapter listening on UDP port {recv_port} ({})",
+                network.listen_mode
+            );
 
             let mut buf = [0u8; MAX_PACKET_SIZE + 16];
             let mut frame_seq = 0u64;
-            let mut last_heartbeat = tokio::time::Instant::now();
+            // Backdated so the first heartbeat goes out immediately.
+            let mut last_heartbeat = tokio::time::Instant::now()
+                .checked_sub(HEARTBEAT_INTERVAL)
+                .unwrap_or_else(tokio::time::Instant::now);
             // Track the source address so heartbeats go to the right host.
-            let mut source_addr: Option<SocketAddr> = None;
+            let mut source_ip = console_addr;
 
             loop {
                 // Send heartbeat every 100 ms to keep the stream alive.
-                if last_heartbeat.elapsed() >= Duration::from_millis(100) {
-                    if let Some(addr) = source_addr {
-                        let hb_addr = SocketAddr::new(addr.ip(), GT7_SEND_PORT);
-                        let _ = socket.send_to(heartbeat_payload, hb_addr).await;
+                if last_heartbeat.elapsed() >= HEARTBEAT_INTERVAL {
+                    if let Some(ip) = source_ip {
+                        let hb_addr = SocketAddr::new(ip, GT7_SEND_PORT);
+                        if let Err(e) = socket.send_to(heartbeat_payload, hb_addr).await {
+                            debug!("Failed to send GT7 heartbeat to {hb_addr}: {e}");
+                        }
                     }
                     last_heartbeat = tokio::time::Instant::now();
                 }
 
-                match tokio::time::timeout(Duration::from_millis(50), socket.recv_from(&mut buf))
-                    .await
-                {
+                let received = tokio::select! {
+                    _ = &mut stop_rx => break,
+                    received = tokio::time::timeout(
+                        Duration::from_millis(50),
+                        socket.recv_from(&mut buf),
+                    ) => received,
+                };
+
+                match received {
                     Ok(Ok((len, src))) => {
-                        source_addr = Some(src);
-                        match decrypt_and_parse(&buf[..len]) {
+                        raw_capture.record(&buf[..len], Some(src));
+                        source_ip = Some(src.ip());
+                        let decoded = if decrypt {
+                            decrypt_and_parse(&buf[..len])
+                        } else {
+                            parse_plaintext(&buf[..len])
+                        };
+                        match decoded {
                             Ok(normalized) => {
                                 let frame = TelemetryFrame::new(
                                     normalized,
@@ -303,7 +427,8 @@ impl TelemetryAdapter for GranTurismo7Adapter {
                                 }
                                 frame_seq = frame_seq.saturating_add(1);
                             }
-                            Err(e) => debug!("Failed to parse GT7 packet: {e}"),
+                            // Corrupt or foreign datagrams fail the magic check.
+                            Err(e) => debug!("Dropping GT7 packet: {e}"),
                         }
                     }
                     Ok(Err(e)) => warn!("GT7 UDP receive error: {e}"),
@@ -313,15 +438,44 @@ impl TelemetryAdapter for GranTurismo7Adapter {
             info!("Stopped GT7 telemetry monitoring");
         });
 
+        let previous = self
+            .monitor
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .replace(MonitorHandle {
+                stop: stop_tx,
+                task,
+            });
+        if let Some(previous) = previous {
+            // Ignored: the task may already have ended on its own.
+            let _ = previous.stop.send(());
+        }
+
         Ok(rx)
     }
 
     async fn stop_monitoring(&self) -> Result<()> {
+        let monitor = self
+            .monitor
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        let Some(monitor) = monitor else {
+            return Ok(());
+        };
+        // Ignored: the task may already have ended on its own.
+        let _ = monitor.stop.send(());
+        if tokio::time::timeout(STOP_TIMEOUT, monitor.task)
+            .await
+            .is_err()
+        {
+            warn!("GT7 monitoring task did not stop in time");
+        }
         Ok(())
     }
 
     fn normalize(&self, raw: &[u8]) -> Result<NormalizedTelemetry> {
-        decrypt_and_parse(raw)
+        self.decode(raw)
     }
 
     fn expected_update_rate(&self) -> Duration {
@@ -368,6 +522,17 @@ pub(crate) fn decrypt_and_parse(data: &[u8]) -> Result<NormalizedTelemetry> {
     parse_decrypted_ext(&buf)
 }
 
+/// Parse a packet that was captured already decrypted.
+fn parse_plaintext(data: &[u8]) -> Result<NormalizedTelemetry> {
+    let magic = read_u32_le(data, OFF_MAGIC);
+    if magic != MAGIC {
+        return Err(anyhow!(
+            "GT7 magic mismatch: expected 0x{MAGIC:08X}, got 0x{magic:08X}"
+        ));
+    }
+    parse_decrypted_ext(data)
+}
+
 /// XOR the buffer in-place with the Salsa20 keystream.
 ///
 /// The nonce is derived from 4 bytes at `[0x40..0x44]` of the **raw**
@@ -379,8 +544,9 @@ pub(crate) fn decrypt_and_parse(data: &[u8]) -> Result<NormalizedTelemetry> {
 /// - PacketType2: `0xDEADBEEF`
 /// - PacketType3: `0x55FABB4F`
 pub(crate) fn salsa20_decrypt(buf: &mut [u8], xor_key: u32) {
-    // Read the 4-byte IV seed from the raw packet.
-    let iv1 = u32::from_le_bytes([buf[0x40], buf[0x41], buf[0x42], buf[0x43]]);
+    // Read the 4-byte IV seed from the raw packet; callers have already
+    // rejected packets too short to carry it.
+    let iv1 = ByteReader::new(buf).u32_le_at(0x40).unwrap_or(0);
     let iv2 = iv1 ^ xor_key;
 
     let mut nonce = [0u8; 8];
@@ -502,13 +668,19 @@ pub fn parse_decrypted_ext(buf: &[u8]) -> Result<NormalizedTelemetry> {
     let last_lap_ms = read_i32_le(buf, OFF_LAST_LAP_MS);
     let current_lap_ms = read_i32_le(buf, OFF_CURRENT_LAP_MS);
     let position_raw = read_i16_le(buf, OFF_POSITION);
+    let packet_id = read_i32_le(buf, OFF_PACKET_ID);
+    let world_position = [
+        read_f32_le(buf, OFF_WORLD_POS_X),
+        read_f32_le(buf, OFF_WORLD_POS_Y),
+        read_f32_le(buf, OFF_WORLD_POS_Z),
+    ];
 
     // Throttle/brake are u8 [0..255] → normalised to [0.0, 1.0]
-    let throttle = buf[OFF_THROTTLE] as f32 / 255.0;
-    let brake = buf[OFF_BRAKE] as f32 / 255.0;
+    let throttle = read_u8(buf, OFF_THROTTLE) as f32 / 255.0;
+    let brake = read_u8(buf, OFF_BRAKE) as f32 / 255.0;
 
     // Gear: low nibble of gear byte (0 = neutral, 1–8 = forward gears)
-    let gear_byte = buf[OFF_GEAR_BYTE];
+    let gear_byte = read_u8(buf, OFF_GEAR_BYTE);
     let gear: i8 = match gear_byte & 0x0F {
         0 => 0,
         g @ 1..=8 => g as i8,
@@ -575,11 +747,30 @@ pub fn parse_decrypted_ext(buf: &[u8]) -> Result<NormalizedTelemetry> {
         .current_lap_time_s(current_lap_s)
         .best_lap_time_s(best_lap_s)
         .last_lap_time_s(last_lap_s)
-        .flags(telemetry_flags);
+        // GT7 gives no lap distance; a track map derives it from the position.
+        .world_position(world_position)
+        .flags(telemetry_flags)
+        // Litres, or percent of charge for electric cars.
+        .extended(
+            ExtendedKey::FUEL_LEFT_L.name,
+            TelemetryValue::Float(fuel_level),
+        )
+        .extended(
+            "gt7_in_race",
+            TelemetryValue::Boolean((flags_raw & FLAG_CAR_ON_TRACK) != 0),
+        )
+        .extended(
+            "gt7_loading",
+            TelemetryValue::Boolean((flags_raw & FLAG_LOADING) != 0),
+        );
 
     if car_code != 0 {
         builder = builder.car_id(format!("gt7_{car_code}"));
     }
+    // GT7 has no session clock; the packet counter is its only game-side time.
+    if let Ok(tick) = u64::try_from(packet_id) {
+        builder = builder.game_tick(tick);
+    }
 
     // --- PacketType2 extended fields (≥ 316 bytes) ---
     // Ref: Nenkai/PDTools SimulatorPacket.cs `if (data.Length >= 0x13C)`
@@ -606,7 +797,7 @@ pub fn parse_decrypted_ext(buf: &[u8]) -> Result<NormalizedTelemetry> {
     // --- PacketType3 extended fields (≥ 344 bytes) ---
     // Ref: Nenkai/PDTools SimulatorPacket.cs `if (data.Length >= 0x158)`
     if buf.len() >= PACKET_SIZE_TYPE3 {
-        let car_type_byte = buf[OFF_CAR_TYPE_BYTE3]; // 4 = electric
+        let car_type_byte = read_u8(buf, OFF_CAR_TYPE_BYTE3); // 4 = electric
         let energy_recovery = read_f32_le(buf, OFF_ENERGY_RECOVERY);
 
         builder = builder.extended(
@@ -626,41 +817,30 @@ pub fn parse_decrypted_ext(buf: &[u8]) -> Result<NormalizedTelemetry> {
 // Low-level read helpers
 // ---------------------------------------------------------------------------
 
+fn read_u8(data: &[u8], offset: usize) -> u8 {
+    ByteReader::new(data).u8_at(offset).unwrap_or(0)
+}
+
 fn read_f32_le(data: &[u8], offset: usize) -> f32 {
-    let val = data
-        .get(offset..offset + 4)
-        .and_then(|b| b.try_into().ok())
-        .map(f32::from_le_bytes)
-        .unwrap_or(0.0);
-    if val.is_finite() { val } else { 0.0 }
+    ByteReader::new(data)
+        .f32_le_finite_at(offset)
+        .unwrap_or(0.0)
 }
 
 fn read_u32_le(data: &[u8], offset: usize) -> u32 {
-    data.get(offset..offset + 4)
-        .and_then(|b| b.try_into().ok())
-        .map(u32::from_le_bytes)
-        .unwrap_or(0)
+    ByteReader::new(data).u32_le_at(offset).unwrap_or(0)
 }
 
 fn read_u16_le(data: &[u8], offset: usize) -> u16 {
-    data.get(offset..offset + 2)
-        .and_then(|b| b.try_into().ok())
-        .map(u16::from_le_bytes)
-        .unwrap_or(0)
+    ByteReader::new(data).u16_le_at(offset).unwrap_or(0)
 }
 
 fn read_i32_le(data: &[u8], offset: usize) -> i32 {
-    data.get(offset..offset + 4)
-        .and_then(|b| b.try_into().ok())
-        .map(i32::from_le_bytes)
-        .unwrap_or(0)
+    ByteReader::new(data).i32_le_at(offset).unwrap_or(0)
 }
 
 fn read_i16_le(data: &[u8], offset: usize) -> i16 {
-    data.get(offset..offset + 2)
-        .and_then(|b| b.try_into().ok())
-        .map(i16::from_le_bytes)
-        .unwrap_or(0)
+    ByteReader::new(data).i16_le_at(offset).unwrap_or(0)
 }
 
 // ---------------------------------------------------------------------------
@@ -834,7 +1014,10 @@ mod property_tests {
             prop_assert!(t.throttle >= 0.0 && t.throttle <= 1.0);
             prop_assert!(t.brake >= 0.0 && t.brake <= 1.0);
             prop_assert!(t.gear >= 0 && t.gear <= 8);
-            prop_assert!(t.extended.is_empty(), "Type1 must have no extended data");
+            prop_assert!(
+                t.get_extended("gt7_sway").is_none(),
+                "Type1 must have no extended motion data"
+            );
         }
 
         /// Type2 extended fields: wheel rotation is stored as steering_angle.
@@ -1068,6 +1251,23 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_world_position_extraction() -> TestResult {
+        let mut buf = make_decrypted_buf();
+        for (offset, value) in [
+            (OFF_WORLD_POS_X, -120.5f32),
+            (OFF_WORLD_POS_Y, 8.25),
+            (OFF_WORLD_POS_Z, 644.0),
+        ] {
+            buf[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+        }
+
+        let telemetry = parse_decrypted(&buf)?;
+        assert_eq!(telemetry.world_position, Some([-120.5, 8.25, 644.0]));
+        assert_eq!(telemetry.lap_distance_fraction, None);
+        Ok(())
+    }
+
     #[test]
     fn test_speed_extraction() -> TestResult {
         let mut buf = make_decrypted_buf();
@@ -1464,10 +1664,12 @@ mod tests {
             t.steering_angle, 0.0,
             "Standard packet should have default steering_angle"
         );
-        assert!(
-            t.extended.is_empty(),
-            "Standard packet should have no extended data"
-        );
+        for key in ["gt7_sway", "gt7_heave", "gt7_surge", "gt7_car_type"] {
+            assert!(
+                t.get_extended(key).is_none(),
+                "Standard packet should have no {key}"
+            );
+        }
         Ok(())
     }
 
@@ -2036,6 +2238,16 @@ mod tests {
         assert_eq!(a.update_rate, b.update_rate);
         assert_eq!(a.packet_type, b.packet_type);
     }
+
+    #[test]
+    fn test_packet_id_is_reported_as_game_tick() -> TestResult {
+        let mut buf = make_decrypted_buf();
+        buf[OFF_PACKET_ID..OFF_PACKET_ID + 4].copy_from_slice(&90_211i32.to_le_bytes());
+        let t = parse_decrypted(&buf)?;
+        assert_eq!(t.game_tick, Some(90_211));
+        assert_eq!(t.game_time_s, None);
+        Ok(())
+    }
 }
 
 /// Protocol constant verification tests for Gran Turismo 7.
@@ -2147,6 +2359,13 @@ mod protocol_constant_tests {
         Ok(())
     }
 
+    /// PacketId at 0x70 (112), i32 — the only game-side clock GT7 sends.
+    #[test]
+    fn test_packet_id_offset() -> TestResult {
+        assert_eq!(OFF_PACKET_ID, 0x70);
+        Ok(())
+    }
+
     /// Best/last/current lap times at offsets 0x78/0x7C/0x80 (contiguous i32 triplet).
     #[test]
     fn test_lap_time_offsets() -> TestResult {
@@ -14,6 +14,7 @@
 //! Protocol documented by the community:
 //! <https://www.gtplanet.net/forum/threads/gt6-is-compatible-with-the-ps4s-remote-play-feature.317250/>
 
+use crate::raw_capture::RawCaptureSlot;
 use crate::{
     NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver,
     gran_turismo_7::{PACKET_SIZE, decrypt_and_parse},
@@ -43,6 +44,7 @@ pub const GTS_SEND_PORT: u16 = 33339;
 pub struct GranTurismo7SportsAdapter {
     recv_port: u16,
     update_rate: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for GranTurismo7SportsAdapter {
@@ -56,6 +58,7 @@ impl GranTurismo7SportsAdapter {
         Self {
             recv_port: GTS_RECV_PORT,
             update_rate: Duration::from_millis(17), // ~60 Hz
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -72,7 +75,12 @@ impl TelemetryAdapter for GranTurismo7SportsAdapter {
         "gran_turismo_sport"
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(100);
         let recv_port = self.recv_port;
 
@@ -106,6 +114,7 @@ impl TelemetryAdapter for GranTurismo7SportsAdapter {
                     .await
                 {
                     Ok(Ok((len, src))) => {
+                        raw_capture.record(&buf[..len], Some(src));
                         source_addr = Some(src);
                         match decrypt_and_parse(&buf[..len]) {
                             Ok(normalized) => {
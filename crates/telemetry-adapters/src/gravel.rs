@@ -4,6 +4,7 @@
 //! through SimHub's generic JSON UDP bridge (port 5555). Packets are parsed
 //! using the shared SimHub JSON parser from [`crate::simhub`].
 
+use crate::raw_capture::RawCaptureSlot;
 use crate::{
     NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver, telemetry_now_ns,
 };
@@ -22,6 +23,7 @@ const MAX_PACKET_SIZE: usize = 4096;
 pub struct GravelAdapter {
     bind_port: u16,
     update_rate: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl GravelAdapter {
@@ -29,6 +31,7 @@ impl GravelAdapter {
         Self {
             bind_port: SIMHUB_PORT,
             update_rate: Duration::from_millis(16),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 }
@@ -45,7 +48,12 @@ impl TelemetryAdapter for GravelAdapter {
         "gravel"
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(64);
         let bind_port = self.bind_port;
         let update_rate = self.update_rate;
@@ -64,7 +72,9 @@ impl TelemetryAdapter for GravelAdapter {
             let mut frame_idx = 0u64;
 
             loop {
-                match tokio::time::timeout(update_rate * 10, socket.recv(&mut buf)).await {
+                match tokio::time::timeout(update_rate * 10, raw_capture.recv(&socket, &mut buf))
+                    .await
+                {
                     Ok(Ok(len)) => match crate::simhub::parse_simhub_packet(&buf[..len]) {
                         Ok(normalized) => {
                             let frame =
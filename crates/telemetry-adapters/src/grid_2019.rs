@@ -8,6 +8,7 @@
 //! delegated to [`crate::codemasters_shared`].
 
 use crate::codemasters_shared;
+use crate::raw_capture::RawCaptureSlot;
 use crate::{
     NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver, telemetry_now_ns,
 };
@@ -39,6 +40,7 @@ pub struct Grid2019Adapter {
     update_rate: Duration,
     heartbeat_timeout: Duration,
     last_packet_ns: Arc<AtomicU64>,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for Grid2019Adapter {
@@ -66,6 +68,7 @@ impl Grid2019Adapter {
             update_rate: Duration::from_millis(16),
             heartbeat_timeout: Duration::from_millis(heartbeat_ms),
             last_packet_ns: Arc::new(AtomicU64::new(0)),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -95,7 +98,12 @@ impl TelemetryAdapter for Grid2019Adapter {
         "grid_2019"
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let bind_port = self.bind_port;
         let update_rate = self.update_rate;
         let last_packet_ns = Arc::clone(&self.last_packet_ns);
@@ -122,7 +130,7 @@ impl TelemetryAdapter for Grid2019Adapter {
             let timeout = (update_rate * 4).max(Duration::from_millis(25));
 
             loop {
-                let recv = tokio::time::timeout(timeout, socket.recv(&mut buf)).await;
+                let recv = tokio::time::timeout(timeout, raw_capture.recv(&socket, &mut buf)).await;
                 let len = match recv {
                     Ok(Ok(len)) => len,
                     Ok(Err(error)) => {
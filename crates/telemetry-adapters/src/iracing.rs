@@ -94,6 +94,12 @@
 //!   translates problematic bytes 0x81/0x8D/0x8F/0x90/0x9D to spaces.
 //!   Our ISO-8859-1 decoding covers the common range and is compatible
 //!   for standard driver/track names. ✓
+//! - The adapter re-parses it on connect and on every counter change for
+//!   `WeekendInfo.TrackName`/`TrackConfigName`, the `CarPath` of the
+//!   `DriverInfo.Drivers` entry matching `DriverCarIdx`, and the
+//!   `SessionType` of the session matching the `SessionNum` var. These
+//!   fill `track_id`, `car_id` and the `track_config`/`session_type`
+//!   extended fields of every frame.
 //!
 //! ### Force feedback delivery
 //! - iRacing delivers FFB data entirely through shared-memory telemetry
@@ -119,11 +125,12 @@
 #![cfg_attr(not(windows), allow(unused, dead_code))]
 
 use crate::{
-    NormalizedTelemetry, TelemetryAdapter, TelemetryFlags, TelemetryFrame, TelemetryReceiver,
-    TelemetryValue, telemetry_now_ns,
+    ExtendedKey, FieldUnit, NormalizedTelemetry, TelemetryAdapter, TelemetryFlags, TelemetryFrame,
+    TelemetryReceiver, TelemetryValue, TireCorner, TireData, Unit, UnitManifest, telemetry_now_ns,
 };
 use anyhow::{Context, Result, anyhow};
 use async_trait::async_trait;
+use racing_wheel_telemetry_core::contracts::ExtendedKeyError;
 use std::mem;
 use std::ptr;
 use std::time::Duration;
@@ -165,6 +172,44 @@ const WAIT_TIMEOUT: u32 = 0x00000102;
 #[cfg(windows)]
 const WAIT_FAILED: u32 = u32::MAX;
 
+// `irsdk_PitSvFlags`: services requested for the next stop.
+const IRSDK_PIT_SV_LF_TIRE_CHANGE: u32 = 0x01;
+const IRSDK_PIT_SV_RF_TIRE_CHANGE: u32 = 0x02;
+const IRSDK_PIT_SV_LR_TIRE_CHANGE: u32 = 0x04;
+const IRSDK_PIT_SV_RR_TIRE_CHANGE: u32 = 0x08;
+const IRSDK_PIT_SV_FUEL_FILL: u32 = 0x10;
+const IRSDK_PIT_SV_WINDSHIELD_TEAROFF: u32 = 0x20;
+const IRSDK_PIT_SV_FAST_REPAIR: u32 = 0x40;
+
+// `irsdk_PitSvStatus`: values of `PlayerCarPitSvStatus`.
+const IRSDK_PIT_SV_NONE: i32 = 0;
+const IRSDK_PIT_SV_IN_PROGRESS: i32 = 1;
+const IRSDK_PIT_SV_COMPLETE: i32 = 2;
+const IRSDK_PIT_SV_TOO_FAR_LEFT: i32 = 100;
+const IRSDK_PIT_SV_TOO_FAR_RIGHT: i32 = 101;
+const IRSDK_PIT_SV_TOO_FAR_FORWARD: i32 = 102;
+const IRSDK_PIT_SV_TOO_FAR_BACK: i32 = 103;
+const IRSDK_PIT_SV_BAD_ANGLE: i32 = 104;
+const IRSDK_PIT_SV_CANT_FIX_THAT: i32 = 105;
+
+// Presence bits for `IRacingData::pit_fuel_vars`. These vars are optional in
+// recorded images, so each is only normalized when its bit is set.
+const PIT_FUEL_VAR_FUEL_LEVEL: u32 = 1 << 0;
+const PIT_FUEL_VAR_FUEL_USE_PER_HOUR: u32 = 1 << 1;
+const PIT_FUEL_VAR_FUEL_DENSITY: u32 = 1 << 2;
+const PIT_FUEL_VAR_PIT_SV_FUEL: u32 = 1 << 3;
+const PIT_FUEL_VAR_PIT_SV_FLAGS: u32 = 1 << 4;
+const PIT_FUEL_VAR_PIT_SV_STATUS: u32 = 1 << 5;
+const PIT_FUEL_VAR_FAST_REPAIR_USED: u32 = 1 << 6;
+const PIT_FUEL_VAR_FAST_REPAIR_AVAILABLE: u32 = 1 << 7;
+const PIT_FUEL_VAR_PIT_REPAIR_LEFT: u32 = 1 << 8;
+const PIT_FUEL_VAR_PIT_OPT_REPAIR_LEFT: u32 = 1 << 9;
+
+/// Clean laps averaged into the fuel-per-lap estimate.
+const FUEL_PER_LAP_WINDOW: usize = 5;
+/// Fuel rise (litres) treated as a refuel rather than sensor jitter.
+const REFUEL_THRESHOLD_L: f32 = 0.05;
+
 const IRSDK_VAR_TYPE_CHAR: i32 = 0;
 const IRSDK_VAR_TYPE_BOOL: i32 = 1;
 const IRSDK_VAR_TYPE_INT: i32 = 2;
@@ -271,6 +316,7 @@ struct VarBinding {
 struct IRacingLayout {
     session_time: Option<VarBinding>,
     session_flags: Option<VarBinding>,
+    session_num: Option<VarBinding>,
     speed: Option<VarBinding>,
     rpm: Option<VarBinding>,
     gear: Option<VarBinding>,
@@ -312,6 +358,42 @@ struct IRacingLayout {
     on_pit_road: Option<VarBinding>,
     car_path: Option<VarBinding>,
     track_name: Option<VarBinding>,
+    fuel_use_per_hour: Option<VarBinding>,
+    pit_sv_fuel: Option<VarBinding>,
+    pit_sv_flags: Option<VarBinding>,
+    player_car_pit_sv_status: Option<VarBinding>,
+    fast_repair_used: Option<VarBinding>,
+    fast_repair_available: Option<VarBinding>,
+    pit_repair_left: Option<VarBinding>,
+    pit_opt_repair_left: Option<VarBinding>,
+}
+
+impl IRacingLayout {
+    /// `PIT_FUEL_VAR_*` bits for the pit and fuel vars this session exposes.
+    fn pit_fuel_vars(&self) -> u32 {
+        // `fuel_level` falls back to `FuelLevelPct`, which is not in litres.
+        let fuel_litres = self.fuel_level.filter(|level| {
+            self.fuel_level_pct
+                .is_none_or(|pct| pct.offset != level.offset)
+        });
+        [
+            (fuel_litres, PIT_FUEL_VAR_FUEL_LEVEL),
+            (self.fuel_use_per_hour, PIT_FUEL_VAR_FUEL_USE_PER_HOUR),
+            (self.pit_sv_fuel, PIT_FUEL_VAR_PIT_SV_FUEL),
+            (self.pit_sv_flags, PIT_FUEL_VAR_PIT_SV_FLAGS),
+            (self.player_car_pit_sv_status, PIT_FUEL_VAR_PIT_SV_STATUS),
+            (self.fast_repair_used, PIT_FUEL_VAR_FAST_REPAIR_USED),
+            (
+                self.fast_repair_available,
+                PIT_FUEL_VAR_FAST_REPAIR_AVAILABLE,
+            ),
+            (self.pit_repair_left, PIT_FUEL_VAR_PIT_REPAIR_LEFT),
+            (self.pit_opt_repair_left, PIT_FUEL_VAR_PIT_OPT_REPAIR_LEFT),
+        ]
+        .into_iter()
+        .filter(|(binding, _)| binding.is_some())
+        .fold(0, |bits, (_, bit)| bits | bit)
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -322,6 +404,8 @@ struct IRacingSample {
     session_info_update: i32,
     session_info_offset: i32,
     session_info_len: i32,
+    /// `SessionNum`, when the sim exposes it.
+    session_num: Option<i32>,
     tick_interval: Duration,
 }
 
@@ -414,6 +498,16 @@ impl IRacingAdapter {
                 && latest_buf_after.tick_count == latest_buf_before.tick_count
                 && latest_buf_after.buf_offset == latest_buf_before.buf_offset
             {
+                let session_num =
+                    usize::try_from(latest_buf_after.buf_offset)
+                        .ok()
+                        .and_then(|offset| {
+                            read_i32_var(
+                                shared_memory.base_ptr,
+                                offset,
+                                shared_memory.layout.session_num,
+                            )
+                        });
                 return Ok(IRacingSample {
                     data,
                     header: header_after,
@@ -421,6 +515,7 @@ impl IRacingAdapter {
                     session_info_update: header_after.session_info_update,
                     session_info_offset: header_after.session_info_offset,
                     session_info_len: header_after.session_info_len,
+                    session_num,
                     tick_interval: calculate_tick_interval(header_after.tick_rate),
                 });
             }
@@ -462,12 +557,58 @@ impl IRacingAdapter {
     }
 }
 
+/// iRacing SDK units. Accelerations arrive in m/s², tyre pressures in kPa,
+/// and the volumetric burn rate is derived from the mass rate via fuel density.
+const UNIT_MANIFEST: UnitManifest = UnitManifest::new(&[
+    FieldUnit::native("speed_ms", Unit::MetersPerSecond),
+    FieldUnit::native("rpm", Unit::Rpm),
+    FieldUnit::native("steering_angle", Unit::Radians),
+    FieldUnit::native("throttle", Unit::Fraction),
+    FieldUnit::native("brake", Unit::Fraction),
+    FieldUnit::native("clutch", Unit::Fraction),
+    FieldUnit::native("fuel_percent", Unit::Fraction),
+    FieldUnit::native("slip_ratio", Unit::Fraction),
+    FieldUnit::native("ffb_scalar", Unit::Fraction),
+    FieldUnit::converted(
+        "lateral_g",
+        Unit::MetersPerSecondSquared,
+        Unit::StandardGravity,
+    ),
+    FieldUnit::converted(
+        "longitudinal_g",
+        Unit::MetersPerSecondSquared,
+        Unit::StandardGravity,
+    ),
+    FieldUnit::converted(
+        "vertical_g",
+        Unit::MetersPerSecondSquared,
+        Unit::StandardGravity,
+    ),
+    FieldUnit::native("engine_temp_c", Unit::Celsius),
+    FieldUnit::native("tire_temps_c", Unit::Celsius),
+    FieldUnit::converted("tire_pressures_psi", Unit::Kilopascal, Unit::Psi),
+    FieldUnit::native("current_lap_time_s", Unit::Seconds),
+    FieldUnit::native("best_lap_time_s", Unit::Seconds),
+    FieldUnit::native("last_lap_time_s", Unit::Seconds),
+    FieldUnit::native("fuel_left_l", Unit::Liters),
+    FieldUnit::native("fuel_per_lap_l", Unit::Liters),
+    FieldUnit::native("pit_fuel_to_add_l", Unit::Liters),
+    FieldUnit::native("fuel_use_kg_h", Unit::KilogramsPerHour),
+    FieldUnit::converted("fuel_use_l_h", Unit::KilogramsPerHour, Unit::LitersPerHour),
+    FieldUnit::native("pit_repair_left_s", Unit::Seconds),
+    FieldUnit::native("pit_opt_repair_left_s", Unit::Seconds),
+]);
+
 #[async_trait]
 impl TelemetryAdapter for IRacingAdapter {
     fn game_id(&self) -> &str {
         "iracing"
     }
 
+    fn unit_manifest(&self) -> Option<UnitManifest> {
+        Some(UNIT_MANIFEST)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
         let (tx, rx) = mpsc::channel(100);
         let update_rate = self.update_rate;
@@ -480,6 +621,8 @@ impl TelemetryAdapter for IRacingAdapter {
             let mut last_layout_signature: Option<(i32, i32, i32, i32)> = None;
             let mut warned_unscaled_ffb = false;
             let mut tick_interval = update_rate;
+            let mut session_info = IRacingSessionInfo::default();
+            let mut lap_fuel = LapFuelEstimator::default();
 
             #[cfg(windows)]
             loop {
@@ -497,6 +640,8 @@ impl TelemetryAdapter for IRacingAdapter {
                     last_tick_count = None;
                     last_layout_signature = None;
                     last_session_info_update = None;
+                    session_info = IRacingSessionInfo::default();
+                    lap_fuel = LapFuelEstimator::default();
                     info!("Connected to iRacing shared memory");
                 }
 
@@ -552,20 +697,13 @@ impl TelemetryAdapter for IRacingAdapter {
                         if session_info_changed {
                             last_session_info_update = Some(sample.session_info_update);
                             warned_unscaled_ffb = false;
-                            if let Some(session_info) = read_session_info_yaml(
+                            if let Some(yaml) = read_session_info_yaml(
                                 adapter.shared_memory.as_ref(),
                                 sample.session_info_offset,
                                 sample.session_info_len,
                             ) {
-                                debug!(
-                                    "Updated iRacing session info ({} bytes)",
-                                    session_info.len()
-                                );
-                                if let Err(err) =
-                                    serde_yaml::from_str::<serde_yaml::Value>(&session_info)
-                                {
-                                    debug!("Failed to parse iRacing session info YAML: {}", err);
-                                }
+                                debug!("Updated iRacing session info ({} bytes)", yaml.len());
+                                session_info.refresh(&yaml);
                             }
 
                             if !layout_changed && let Some(shared) = adapter.shared_memory.as_mut()
@@ -586,12 +724,32 @@ impl TelemetryAdapter for IRacingAdapter {
                             None => IRacingLayout::default(),
                         };
 
+                        let mut data = sample.data;
+                        if let Some(density) = session_info.fuel_kg_per_ltr {
+                            data.fuel_kg_per_ltr = density;
+                            data.pit_fuel_vars |= PIT_FUEL_VAR_FUEL_DENSITY;
+                        }
+                        let mut normalized = adapter.normalize_iracing_data(
+                            &data,
+                            &layout,
+                            &mut warned_unscaled_ffb,
+                        );
+                        session_info.apply(&mut normalized, sample.session_num);
+                        if data.pit_fuel_vars & PIT_FUEL_VAR_FUEL_LEVEL != 0
+                            && layout.lap_current.is_some()
+                            && let Some(per_lap) = lap_fuel.update(
+                                data.lap_current,
+                                data.fuel_level,
+                                data.on_pit_road != 0,
+                            )
+                            && let Err(err) =
+                                ExtendedKey::FUEL_PER_LAP_L.set(&mut normalized, per_lap)
+                        {
+                            debug!("Skipped iRacing fuel-per-lap estimate: {}", err);
+                        }
+
                         let frame = TelemetryFrame::new(
-                            adapter.normalize_iracing_data(
-                                &sample.data,
-                                &layout,
-                                &mut warned_unscaled_ffb,
-                            ),
+                            normalized,
                             telemetry_now_ns(),
                             frame_seq,
                             mem::size_of::<IRacingData>(),
@@ -632,7 +790,9 @@ impl TelemetryAdapter for IRacingAdapter {
 
     fn normalize(&self, raw: &[u8]) -> Result<NormalizedTelemetry> {
         let min_raw_size = mem::size_of::<IRacingLegacyData>();
-        let max_raw_size = mem::size_of::<IRacingData>();
+        // Images may stop before the appended pit/fuel block; its presence
+        // bits then stay clear.
+        let max_raw_size = mem::offset_of!(IRacingData, pit_fuel_vars);
 
         if raw.len() < min_raw_size {
             return Err(anyhow!(
@@ -654,12 +814,14 @@ impl TelemetryAdapter for IRacingAdapter {
             convert_legacy_to_current(&legacy)
         } else {
             let mut data = IRacingData::default();
-            // SAFETY: destination is a plain-old-data struct and `raw` length is validated.
+            let copy_len = raw.len().min(mem::size_of::<IRacingData>());
+            // SAFETY: destination is a plain-old-data struct and `copy_len`
+            // is bounded by both buffers.
             unsafe {
                 ptr::copy_nonoverlapping(
                     raw.as_ptr(),
                     &mut data as *mut IRacingData as *mut u8,
-                    max_raw_size,
+                    copy_len,
                 );
             }
             data
@@ -672,6 +834,10 @@ impl TelemetryAdapter for IRacingAdapter {
         self.update_rate
     }
 
+    fn prefers_dedup(&self) -> bool {
+        true
+    }
+
     async fn is_game_running(&self) -> Result<bool> {
         Ok(self.check_iracing_running().await)
     }
@@ -743,7 +909,7 @@ impl IRacingAdapter {
             );
         }
 
-        builder
+        let mut telemetry = builder
             .throttle(data.throttle)
             .brake(data.brake)
             .clutch(data.clutch)
@@ -770,6 +936,7 @@ impl IRacingAdapter {
                 data.rr_pressure * IRSDK_KPA_TO_PSI,
             ])
             .position(data.player_car_position.clamp(0, 255) as u8)
+            .tires(tire_data(data, layout))
             .extended(
                 "fuel_level".to_string(),
                 TelemetryValue::Float(data.fuel_level),
@@ -778,7 +945,329 @@ impl IRacingAdapter {
                 "session_time".to_string(),
                 TelemetryValue::Float(data.session_time),
             )
-            .build()
+            .build();
+        // SessionTime reads zero until the sim binds it, so zero means absent.
+        if data.session_time > 0.0 {
+            telemetry.game_time_s = Some(f64::from(data.session_time));
+        }
+
+        if let Err(err) = apply_pit_fuel_vars(&mut telemetry, data) {
+            debug!("Skipped iRacing pit/fuel channels: {}", err);
+        }
+        telemetry
+    }
+}
+
+/// Per-corner tyre data. The temperature is `tempCL`, the carcass reading,
+/// since the surface temperatures only update when the car is in the pits.
+/// Unbound vars read as zero, which no tyre on track reports, so zero
+/// temperatures and pressures are left out. Slip ratios are present only
+/// when the sim binds the per-tyre vars.
+fn tire_data(data: &IRacingData, layout: &IRacingLayout) -> TireData {
+    let reported = |value: f32| (value != 0.0).then_some(value);
+    let corner = |temp_cl: f32, pressure_kpa: f32, slip_ratio: Option<f32>| TireCorner {
+        surface_temp_c: reported(temp_cl),
+        pressure_kpa: reported(pressure_kpa),
+        wear_fraction: None,
+        slip_ratio,
+    };
+    let bound = |binding: Option<VarBinding>, value: f32| binding.map(|_| value);
+    TireData::from_corners([
+        corner(
+            data.lf_temp_cl,
+            data.lf_pressure,
+            bound(layout.lf_tire_slip_ratio, data.lf_tire_slip_ratio),
+        ),
+        corner(
+            data.rf_temp_cl,
+            data.rf_pressure,
+            bound(layout.rf_tire_slip_ratio, data.rf_tire_slip_ratio),
+        ),
+        corner(
+            data.lr_temp_cl,
+            data.lr_pressure,
+            bound(layout.lr_tire_slip_ratio, data.lr_tire_slip_ratio),
+        ),
+        corner(
+            data.rr_temp_cl,
+            data.rr_pressure,
+            bound(layout.rr_tire_slip_ratio, data.rr_tire_slip_ratio),
+        ),
+    ])
+}
+
+/// Write the pit service and fuel strategy channels present in `data`.
+///
+/// iRacing reports fuel in SI units regardless of the `DisplayUnits` var:
+/// `FuelLevel` and `PitSvFuel` in litres, `FuelUsePerHour` in kg/h. The
+/// burn rate is also converted to l/h once the session info has supplied
+/// `DriverCarFuelKgPerLtr`.
+fn apply_pit_fuel_vars(
+    telemetry: &mut NormalizedTelemetry,
+    data: &IRacingData,
+) -> Result<(), ExtendedKeyError> {
+    let present = |bit: u32| data.pit_fuel_vars & bit != 0;
+    let mut set_finite = |key: ExtendedKey, value: f32| {
+        if value.is_finite() {
+            key.set(&mut *telemetry, value)
+        } else {
+            Ok(())
+        }
+    };
+
+    if present(PIT_FUEL_VAR_FUEL_LEVEL) {
+        set_finite(ExtendedKey::FUEL_LEFT_L, data.fuel_level)?;
+    }
+    if present(PIT_FUEL_VAR_FUEL_USE_PER_HOUR) {
+        set_finite(ExtendedKey::FUEL_USE_KG_H, data.fuel_use_per_hour)?;
+        if present(PIT_FUEL_VAR_FUEL_DENSITY) && data.fuel_kg_per_ltr > 0.0 {
+            set_finite(
+                ExtendedKey::FUEL_USE_L_H,
+                data.fuel_use_per_hour / data.fuel_kg_per_ltr,
+            )?;
+        }
+    }
+    if present(PIT_FUEL_VAR_PIT_SV_FUEL) {
+        set_finite(ExtendedKey::PIT_FUEL_TO_ADD_L, data.pit_sv_fuel)?;
+    }
+    if present(PIT_FUEL_VAR_PIT_REPAIR_LEFT) {
+        set_finite(ExtendedKey::PIT_REPAIR_LEFT_S, data.pit_repair_left)?;
+    }
+    if present(PIT_FUEL_VAR_PIT_OPT_REPAIR_LEFT) {
+        set_finite(ExtendedKey::PIT_OPT_REPAIR_LEFT_S, data.pit_opt_repair_left)?;
+    }
+    if present(PIT_FUEL_VAR_PIT_SV_FLAGS) {
+        for (key, flag) in [
+            (ExtendedKey::PIT_TIRE_CHANGE_FL, IRSDK_PIT_SV_LF_TIRE_CHANGE),
+            (ExtendedKey::PIT_TIRE_CHANGE_FR, IRSDK_PIT_SV_RF_TIRE_CHANGE),
+            (ExtendedKey::PIT_TIRE_CHANGE_RL, IRSDK_PIT_SV_LR_TIRE_CHANGE),
+            (ExtendedKey::PIT_TIRE_CHANGE_RR, IRSDK_PIT_SV_RR_TIRE_CHANGE),
+            (ExtendedKey::PIT_FUEL_FILL, IRSDK_PIT_SV_FUEL_FILL),
+            (
+                ExtendedKey::PIT_WINDSHIELD_TEAROFF,
+                IRSDK_PIT_SV_WINDSHIELD_TEAROFF,
+            ),
+            (ExtendedKey::PIT_FAST_REPAIR, IRSDK_PIT_SV_FAST_REPAIR),
+        ] {
+            key.set(&mut *telemetry, data.pit_sv_flags & flag != 0)?;
+        }
+    }
+    if present(PIT_FUEL_VAR_PIT_SV_STATUS) {
+        ExtendedKey::PIT_SERVICE_STATUS.set(
+            &mut *telemetry,
+            pit_sv_status_name(data.player_car_pit_sv_status),
+        )?;
+    }
+    if present(PIT_FUEL_VAR_FAST_REPAIR_AVAILABLE) {
+        ExtendedKey::FAST_REPAIRS_AVAILABLE.set(&mut *telemetry, data.fast_repair_available)?;
+    }
+    if present(PIT_FUEL_VAR_FAST_REPAIR_USED) {
+        ExtendedKey::FAST_REPAIRS_USED.set(&mut *telemetry, data.fast_repair_used)?;
+    }
+    Ok(())
+}
+
+fn pit_sv_status_name(status: i32) -> &'static str {
+    match status {
+        IRSDK_PIT_SV_NONE => "none",
+        IRSDK_PIT_SV_IN_PROGRESS => "in_progress",
+        IRSDK_PIT_SV_COMPLETE => "complete",
+        IRSDK_PIT_SV_TOO_FAR_LEFT => "too_far_left",
+        IRSDK_PIT_SV_TOO_FAR_RIGHT => "too_far_right",
+        IRSDK_PIT_SV_TOO_FAR_FORWARD => "too_far_forward",
+        IRSDK_PIT_SV_TOO_FAR_BACK => "too_far_back",
+        IRSDK_PIT_SV_BAD_ANGLE => "bad_angle",
+        IRSDK_PIT_SV_CANT_FIX_THAT => "cant_fix_that",
+        _ => "unknown",
+    }
+}
+
+/// Values read from the session info YAML. The YAML is only parsed when its
+/// `session_info_update` counter moves, and frames copy these cached values.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct IRacingSessionInfo {
+    /// `WeekendInfo.TrackName`, the internal track name.
+    track_name: Option<String>,
+    /// `WeekendInfo.TrackConfigName`.
+    track_config: Option<String>,
+    /// `CarPath` of the `DriverInfo.Drivers` entry for `DriverCarIdx`.
+    car_path: Option<String>,
+    /// `(SessionNum, SessionType)` of each entry in `SessionInfo.Sessions`.
+    session_types: Vec<(i64, String)>,
+    fuel_kg_per_ltr: Option<f32>,
+}
+
+impl IRacingSessionInfo {
+    fn parse(yaml: &str) -> Result<Self> {
+        let info: serde_yaml::Value = serde_yaml::from_str(yaml)?;
+        if !info.is_mapping() {
+            return Err(anyhow!("iRacing session info is not a YAML mapping"));
+        }
+        let weekend = info.get("WeekendInfo");
+        let driver_info = info.get("DriverInfo");
+        let player_idx = driver_info
+            .and_then(|d| d.get("DriverCarIdx"))
+            .and_then(serde_yaml::Value::as_i64);
+        let car_path = driver_info
+            .and_then(|d| d.get("Drivers"))
+            .and_then(serde_yaml::Value::as_sequence)
+            .zip(player_idx)
+            .and_then(|(drivers, idx)| {
+                drivers.iter().find(|driver| {
+                    driver.get("CarIdx").and_then(serde_yaml::Value::as_i64) == Some(idx)
+                })
+            })
+            .and_then(|driver| yaml_string(driver.get("CarPath")));
+        let session_types = info
+            .get("SessionInfo")
+            .and_then(|s| s.get("Sessions"))
+            .and_then(serde_yaml::Value::as_sequence)
+            .map(|sessions| {
+                sessions
+                    .iter()
+                    .filter_map(|session| {
+                        let num = session.get("SessionNum")?.as_i64()?;
+                        Some((num, yaml_string(session.get("SessionType"))?))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Self {
+            track_name: yaml_string(weekend.and_then(|w| w.get("TrackName"))),
+            track_config: yaml_string(weekend.and_then(|w| w.get("TrackConfigName"))),
+            car_path,
+            session_types,
+            fuel_kg_per_ltr: session_fuel_kg_per_ltr(&info),
+        })
+    }
+
+    /// Re-parse after a session info update. Unparseable YAML keeps the
+    /// previous values, so a bad update never interrupts the frame stream.
+    fn refresh(&mut self, yaml: &str) {
+        match Self::parse(yaml) {
+            Ok(parsed) => *self = parsed,
+            Err(err) => debug!("Failed to parse iRacing session info YAML: {}", err),
+        }
+    }
+
+    fn session_type(&self, session_num: Option<i32>) -> Option<&str> {
+        let session_num = i64::from(session_num?);
+        self.session_types
+            .iter()
+            .find(|(num, _)| *num == session_num)
+            .map(|(_, session_type)| session_type.as_str())
+    }
+
+    /// Fill car, track and session type. Session info names the player's car
+    /// and track more reliably than the live string vars, so it wins.
+    fn apply(&self, telemetry: &mut NormalizedTelemetry, session_num: Option<i32>) {
+        if let Some(car_path) = &self.car_path {
+            telemetry.car_id = Some(car_path.clone());
+        }
+        if let Some(track_name) = &self.track_name {
+            telemetry.track_id = Some(track_name.clone());
+        }
+        if let Some(track_config) = &self.track_config {
+            telemetry.extended.insert(
+                "track_config".to_string(),
+                TelemetryValue::String(track_config.clone()),
+            );
+        }
+        if let Some(session_type) = self.session_type(session_num) {
+            telemetry.extended.insert(
+                "session_type".to_string(),
+                TelemetryValue::String(session_type.to_string()),
+            );
+        }
+    }
+}
+
+/// A non-empty scalar session info value as text. Unquoted names such as
+/// `TrackConfigName: 2011` load as numbers.
+fn yaml_string(value: Option<&serde_yaml::Value>) -> Option<String> {
+    let text = match value? {
+        serde_yaml::Value::String(text) => text.trim().to_string(),
+        serde_yaml::Value::Number(number) => number.to_string(),
+        _ => return None,
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+/// `DriverInfo.DriverCarFuelKgPerLtr` from the session info YAML.
+fn session_fuel_kg_per_ltr(session_info: &serde_yaml::Value) -> Option<f32> {
+    let density = session_info
+        .get("DriverInfo")?
+        .get("DriverCarFuelKgPerLtr")?
+        .as_f64()? as f32;
+    (density.is_finite() && density > 0.0).then_some(density)
+}
+
+/// Fuel burned per lap, averaged over the last few clean laps.
+///
+/// A lap counts only when it was observed from one `Lap` increment to the
+/// next without a refuel or a visit to pit road, so the partial lap after
+/// connecting and in/out laps never skew the estimate.
+#[derive(Debug, Default)]
+struct LapFuelEstimator {
+    lap: Option<i32>,
+    lap_start_fuel_l: f32,
+    last_fuel_l: f32,
+    lap_dirty: bool,
+    laps_l: [f32; FUEL_PER_LAP_WINDOW],
+    len: usize,
+    next: usize,
+}
+
+impl LapFuelEstimator {
+    /// Feed one sample and return the current estimate in litres, if any.
+    fn update(&mut self, lap: i32, fuel_l: f32, on_pit_road: bool) -> Option<f32> {
+        if !fuel_l.is_finite() {
+            return self.estimate();
+        }
+        match self.lap {
+            Some(current) if lap == current => {
+                if on_pit_road || fuel_l > self.last_fuel_l + REFUEL_THRESHOLD_L {
+                    self.lap_dirty = true;
+                }
+                self.last_fuel_l = fuel_l;
+            }
+            Some(current) if lap == current + 1 => {
+                let used_l = self.lap_start_fuel_l - fuel_l;
+                let refuelled = fuel_l > self.last_fuel_l + REFUEL_THRESHOLD_L;
+                if !self.lap_dirty && !refuelled && used_l > 0.0 {
+                    self.push(used_l);
+                }
+                self.start_lap(lap, fuel_l, on_pit_road);
+            }
+            Some(current) => {
+                // Lap counter reset (new session) or jumped (tow, reset).
+                if lap < current {
+                    self.len = 0;
+                    self.next = 0;
+                }
+                self.start_lap(lap, fuel_l, true);
+            }
+            None => self.start_lap(lap, fuel_l, true),
+        }
+        self.estimate()
+    }
+
+    fn start_lap(&mut self, lap: i32, fuel_l: f32, dirty: bool) {
+        self.lap = Some(lap);
+        self.lap_start_fuel_l = fuel_l;
+        self.last_fuel_l = fuel_l;
+        self.lap_dirty = dirty;
+    }
+
+    fn push(&mut self, used_l: f32) {
+        self.laps_l[self.next] = used_l;
+        self.next = (self.next + 1) % FUEL_PER_LAP_WINDOW;
+        self.len = (self.len + 1).min(FUEL_PER_LAP_WINDOW);
+    }
+
+    fn estimate(&self) -> Option<f32> {
+        (self.len > 0).then(|| self.laps_l[..self.len].iter().sum::<f32>() / self.len as f32)
     }
 }
 
@@ -968,6 +1457,20 @@ fn read_iracing_data_from_ptr(
     copy_string_var(base_ptr, offset, layout.car_path, &mut data.car_path);
     copy_string_var(base_ptr, offset, layout.track_name, &mut data.track_name);
 
+    data.pit_fuel_vars = layout.pit_fuel_vars();
+    data.fuel_use_per_hour =
+        read_f32_var(base_ptr, offset, layout.fuel_use_per_hour).unwrap_or(0.0);
+    data.pit_sv_fuel = read_f32_var(base_ptr, offset, layout.pit_sv_fuel).unwrap_or(0.0);
+    data.pit_sv_flags = read_i32_var(base_ptr, offset, layout.pit_sv_flags).unwrap_or(0) as u32;
+    data.player_car_pit_sv_status =
+        read_i32_var(base_ptr, offset, layout.player_car_pit_sv_status).unwrap_or(0);
+    data.fast_repair_used = read_i32_var(base_ptr, offset, layout.fast_repair_used).unwrap_or(0);
+    data.fast_repair_available =
+        read_i32_var(base_ptr, offset, layout.fast_repair_available).unwrap_or(0);
+    data.pit_repair_left = read_f32_var(base_ptr, offset, layout.pit_repair_left).unwrap_or(0.0);
+    data.pit_opt_repair_left =
+        read_f32_var(base_ptr, offset, layout.pit_opt_repair_left).unwrap_or(0.0);
+
     Ok(data)
 }
 
@@ -1159,6 +1662,8 @@ fn assign_var_binding(layout: &mut IRacingLayout, name: &str, binding: VarBindin
         layout.session_time = Some(binding);
     } else if matches_irsdk_name(name, &["SessionFlags"]) {
         layout.session_flags = Some(binding);
+    } else if matches_irsdk_name(name, &["SessionNum"]) {
+        layout.session_num = Some(binding);
     } else if matches_irsdk_name(name, &["Speed"]) {
         layout.speed = Some(binding);
     } else if matches_irsdk_name(name, &["RPM"]) {
@@ -1245,6 +1750,22 @@ fn assign_var_binding(layout: &mut IRacingLayout, name: &str, binding: VarBindin
         layout.car_path = Some(binding);
     } else if matches_irsdk_name(name, &["TrackName"]) {
         layout.track_name = Some(binding);
+    } else if matches_irsdk_name(name, &["FuelUsePerHour"]) {
+        layout.fuel_use_per_hour = Some(binding);
+    } else if matches_irsdk_name(name, &["PitSvFuel"]) {
+        layout.pit_sv_fuel = Some(binding);
+    } else if matches_irsdk_name(name, &["PitSvFlags"]) {
+        layout.pit_sv_flags = Some(binding);
+    } else if matches_irsdk_name(name, &["PlayerCarPitSvStatus"]) {
+        layout.player_car_pit_sv_status = Some(binding);
+    } else if matches_irsdk_name(name, &["FastRepairUsed"]) {
+        layout.fast_repair_used = Some(binding);
+    } else if matches_irsdk_name(name, &["FastRepairAvailable"]) {
+        layout.fast_repair_available = Some(binding);
+    } else if matches_irsdk_name(name, &["PitRepairLeft"]) {
+        layout.pit_repair_left = Some(binding);
+    } else if matches_irsdk_name(name, &["PitOptRepairLeft"]) {
+        layout.pit_opt_repair_left = Some(binding);
     }
 }
 
@@ -1507,6 +2028,19 @@ struct IRacingData {
     water_temp: f32,
     car_path: [u8; 64],
     track_name: [u8; 64],
+    // Pit and fuel strategy vars, appended so images that end at
+    // `track_name` still parse. Which of them are valid is recorded in
+    // `pit_fuel_vars`.
+    pit_fuel_vars: u32,
+    fuel_use_per_hour: f32,
+    fuel_kg_per_ltr: f32,
+    pit_sv_fuel: f32,
+    pit_sv_flags: u32,
+    player_car_pit_sv_status: i32,
+    fast_repair_used: i32,
+    fast_repair_available: i32,
+    pit_repair_left: f32,
+    pit_opt_repair_left: f32,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -1579,6 +2113,16 @@ impl Default for IRacingData {
             water_temp: 0.0,
             car_path: [0; 64],
             track_name: [0; 64],
+            pit_fuel_vars: 0,
+            fuel_use_per_hour: 0.0,
+            fuel_kg_per_ltr: 0.0,
+            pit_sv_fuel: 0.0,
+            pit_sv_flags: 0,
+            player_car_pit_sv_status: 0,
+            fast_repair_used: 0,
+            fast_repair_available: 0,
+            pit_repair_left: 0.0,
+            pit_opt_repair_left: 0.0,
         }
     }
 }
@@ -1819,6 +2363,25 @@ mod tests {
         );
         assert_eq!(normalized.throttle, 0.8);
         assert_eq!(normalized.brake, 0.2);
+        assert_eq!(normalized.game_time_s, None, "unbound SessionTime");
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_iracing_data_reports_session_time_as_game_clock() -> TestResult {
+        let adapter = IRacingAdapter::new();
+        let data = IRacingData {
+            session_time: 1_812.5,
+            ..Default::default()
+        };
+        let mut warned_unscaled_ffb = true;
+        let normalized = adapter.normalize_iracing_data(
+            &data,
+            &IRacingLayout::default(),
+            &mut warned_unscaled_ffb,
+        );
+        assert_eq!(normalized.game_time_s, Some(1_812.5));
+        assert_eq!(normalized.game_tick, None);
         Ok(())
     }
 
@@ -2085,6 +2648,295 @@ mod tests {
         assert!(layout.steering_wheel_limiter.is_some());
     }
 
+    fn layout_from_names(names: &[(&str, i32)]) -> IRacingLayout {
+        let mut layout = IRacingLayout::default();
+        for (index, (name, var_type)) in names.iter().enumerate() {
+            let binding = VarBinding {
+                offset: index * 4,
+                ..make_binding(*var_type)
+            };
+            assign_var_binding(&mut layout, name, binding);
+        }
+        layout
+    }
+
+    fn extended_bool(telemetry: &NormalizedTelemetry, key: ExtendedKey) -> Option<bool> {
+        match telemetry.extended.get(key.name) {
+            Some(TelemetryValue::Boolean(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn extended_float(telemetry: &NormalizedTelemetry, key: ExtendedKey) -> Option<f32> {
+        match telemetry.extended.get(key.name) {
+            Some(TelemetryValue::Float(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_pit_fuel_vars_tolerate_missing_vars() -> TestResult {
+        // An older build exposing fuel and pit flags but no repair vars.
+        let layout = layout_from_names(&[
+            ("FuelLevelPct", IRSDK_VAR_TYPE_FLOAT),
+            ("FuelLevel", IRSDK_VAR_TYPE_FLOAT),
+            ("PitSvFlags", IRSDK_VAR_TYPE_BITFIELD),
+            ("pitsvfuel", IRSDK_VAR_TYPE_FLOAT),
+        ]);
+        assert_eq!(
+            layout.pit_fuel_vars(),
+            PIT_FUEL_VAR_FUEL_LEVEL | PIT_FUEL_VAR_PIT_SV_FLAGS | PIT_FUEL_VAR_PIT_SV_FUEL
+        );
+
+        let data = IRacingData {
+            fuel_level: 41.5,
+            pit_sv_fuel: 20.0,
+            pit_sv_flags: IRSDK_PIT_SV_FUEL_FILL,
+            fuel_use_per_hour: 30.0,
+            player_car_pit_sv_status: IRSDK_PIT_SV_COMPLETE,
+            pit_fuel_vars: layout.pit_fuel_vars(),
+            ..Default::default()
+        };
+        let mut warned = true;
+        let normalized = IRacingAdapter::new().normalize_iracing_data(&data, &layout, &mut warned);
+
+        assert_eq!(
+            extended_float(&normalized, ExtendedKey::FUEL_LEFT_L),
+            Some(41.5)
+        );
+        assert_eq!(
+            extended_float(&normalized, ExtendedKey::PIT_FUEL_TO_ADD_L),
+            Some(20.0)
+        );
+        assert_eq!(
+            extended_bool(&normalized, ExtendedKey::PIT_FUEL_FILL),
+            Some(true)
+        );
+        for absent in [
+            ExtendedKey::FUEL_USE_KG_H,
+            ExtendedKey::FUEL_USE_L_H,
+            ExtendedKey::PIT_SERVICE_STATUS,
+            ExtendedKey::PIT_REPAIR_LEFT_S,
+            ExtendedKey::FAST_REPAIRS_AVAILABLE,
+        ] {
+            assert!(
+                !normalized.extended.contains_key(absent.name),
+                "{} should be absent",
+                absent.name
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_fuel_level_pct_fallback_is_not_reported_as_litres() {
+        let layout = layout_from_names(&[("FuelLevelPct", IRSDK_VAR_TYPE_FLOAT)]);
+        assert!(layout.fuel_level.is_some());
+        assert_eq!(layout.pit_fuel_vars() & PIT_FUEL_VAR_FUEL_LEVEL, 0);
+    }
+
+    #[test]
+    fn test_pit_sv_flags_decode_each_bit() -> TestResult {
+        let keys = [
+            ExtendedKey::PIT_TIRE_CHANGE_FL,
+            ExtendedKey::PIT_TIRE_CHANGE_FR,
+            ExtendedKey::PIT_TIRE_CHANGE_RL,
+            ExtendedKey::PIT_TIRE_CHANGE_RR,
+            ExtendedKey::PIT_FUEL_FILL,
+            ExtendedKey::PIT_WINDSHIELD_TEAROFF,
+            ExtendedKey::PIT_FAST_REPAIR,
+        ];
+        let adapter = IRacingAdapter::new();
+        // Every combination of the seven documented bits, plus an unknown high bit.
+        for flags in 0u32..0x80 {
+            let data = IRacingData {
+                pit_sv_flags: flags | 0x8000,
+                pit_fuel_vars: PIT_FUEL_VAR_PIT_SV_FLAGS,
+                ..Default::default()
+            };
+            let mut warned = true;
+            let normalized =
+                adapter.normalize_iracing_data(&data, &IRacingLayout::default(), &mut warned);
+            for (bit, key) in keys.iter().enumerate() {
+                assert_eq!(
+                    extended_bool(&normalized, *key),
+                    Some(flags & (1 << bit) != 0),
+                    "flags={flags:#04x} key={}",
+                    key.name
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_pit_sv_status_names() {
+        for (status, name) in [
+            (0, "none"),
+            (1, "in_progress"),
+            (2, "complete"),
+            (100, "too_far_left"),
+            (101, "too_far_right"),
+            (102, "too_far_forward"),
+            (103, "too_far_back"),
+            (104, "bad_angle"),
+            (105, "cant_fix_that"),
+            (3, "unknown"),
+            (-1, "unknown"),
+        ] {
+            assert_eq!(pit_sv_status_name(status), name, "status {status}");
+        }
+    }
+
+    #[test]
+    fn test_fuel_use_converts_to_litres_with_session_density() -> TestResult {
+        let session: serde_yaml::Value =
+            serde_yaml::from_str("DriverInfo:\n  DriverCarFuelKgPerLtr: 0.750\n")?;
+        let density = session_fuel_kg_per_ltr(&session).ok_or("density missing")?;
+        assert!((density - 0.75).abs() < 1e-6);
+        let missing: serde_yaml::Value = serde_yaml::from_str("DriverInfo:\n  DriverCarIdx: 3\n")?;
+        assert_eq!(session_fuel_kg_per_ltr(&missing), None);
+
+        let data = IRacingData {
+            fuel_use_per_hour: 60.0,
+            fuel_kg_per_ltr: density,
+            pit_fuel_vars: PIT_FUEL_VAR_FUEL_USE_PER_HOUR | PIT_FUEL_VAR_FUEL_DENSITY,
+            ..Default::default()
+        };
+        let mut warned = true;
+        let normalized = IRacingAdapter::new().normalize_iracing_data(
+            &data,
+            &IRacingLayout::default(),
+            &mut warned,
+        );
+        assert_eq!(
+            extended_float(&normalized, ExtendedKey::FUEL_USE_KG_H),
+            Some(60.0)
+        );
+        let litres = extended_float(&normalized, ExtendedKey::FUEL_USE_L_H).ok_or("no l/h")?;
+        assert!((litres - 80.0).abs() < 1e-3, "l/h: {litres}");
+        Ok(())
+    }
+
+    const SESSION_INFO_FIXTURE: &str = include_str!("../tests/fixtures/iracing/session_info.yaml");
+
+    #[test]
+    fn test_session_info_fixture_names_player_car_track_and_session() -> TestResult {
+        let info = IRacingSessionInfo::parse(SESSION_INFO_FIXTURE)?;
+        assert_eq!(info.track_name.as_deref(), Some("spa 2024 up"));
+        assert_eq!(info.track_config.as_deref(), Some("Grand Prix Pits"));
+        // DriverCarIdx is 1; car 0 is the pace car.
+        assert_eq!(info.car_path.as_deref(), Some("porsche992cup"));
+        assert_eq!(info.session_type(Some(0)), Some("Offline Testing"));
+        assert_eq!(info.session_type(Some(1)), Some("Race"));
+        assert_eq!(info.session_type(Some(2)), None);
+        assert_eq!(info.session_type(None), None);
+        assert_eq!(info.fuel_kg_per_ltr, Some(0.75));
+
+        let mut data = IRacingData {
+            rpm: 6100.0,
+            ..Default::default()
+        };
+        data.car_path[..6].copy_from_slice(b"oldcar");
+        let mut warned = true;
+        let mut normalized = IRacingAdapter::new().normalize_iracing_data(
+            &data,
+            &IRacingLayout::default(),
+            &mut warned,
+        );
+        info.apply(&mut normalized, Some(1));
+        assert_eq!(normalized.car_id.as_deref(), Some("porsche992cup"));
+        assert_eq!(normalized.track_id.as_deref(), Some("spa 2024 up"));
+        assert_eq!(
+            normalized.extended.get("session_type"),
+            Some(&TelemetryValue::String("Race".to_string()))
+        );
+        assert_eq!(
+            normalized.extended.get("track_config"),
+            Some(&TelemetryValue::String("Grand Prix Pits".to_string()))
+        );
+        assert_eq!(normalized.rpm, 6100.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_malformed_session_info_keeps_previous_values() -> TestResult {
+        for malformed in [
+            "WeekendInfo:\n TrackName: [unterminated\n",
+            "\t- not: yaml: at: all",
+            "just a string",
+            "",
+        ] {
+            assert!(
+                IRacingSessionInfo::parse(malformed).is_err(),
+                "{malformed:?}"
+            );
+        }
+
+        let mut info = IRacingSessionInfo::default();
+        info.refresh(SESSION_INFO_FIXTURE);
+        let parsed = info.clone();
+        info.refresh("DriverInfo:\n Drivers:\n - CarIdx: [1\n");
+        assert_eq!(info, parsed);
+
+        // Telemetry keeps flowing with whatever was parsed last.
+        let mut warned = true;
+        let mut normalized = IRacingAdapter::new().normalize_iracing_data(
+            &IRacingData::default(),
+            &IRacingLayout::default(),
+            &mut warned,
+        );
+        info.apply(&mut normalized, None);
+        assert_eq!(normalized.car_id.as_deref(), Some("porsche992cup"));
+        assert!(!normalized.extended.contains_key("session_type"));
+
+        // Keys missing from a well-formed update clear the cached values.
+        info.refresh("WeekendInfo:\n TrackConfigName: 2011\n");
+        assert_eq!(info.track_name, None);
+        assert_eq!(info.track_config.as_deref(), Some("2011"));
+        assert_eq!(info.car_path, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_lap_fuel_estimate_over_three_laps() -> TestResult {
+        let mut estimator = LapFuelEstimator::default();
+        // Joined mid-way through lap 1: that partial lap is never counted.
+        assert_eq!(estimator.update(1, 60.0, false), None);
+        assert_eq!(estimator.update(1, 59.1, false), None);
+        assert_eq!(estimator.update(2, 58.5, false), None);
+        // Lap 2: 58.5 -> 55.7 = 2.8 l.
+        estimator.update(2, 57.0, false);
+        let first = estimator.update(3, 55.7, false).ok_or("no estimate")?;
+        assert!((first - 2.8).abs() < 1e-4, "after lap 2: {first}");
+        // Lap 3: 55.7 -> 52.8 = 2.9 l.
+        estimator.update(3, 54.2, false);
+        let second = estimator.update(4, 52.8, false).ok_or("no estimate")?;
+        assert!((second - 2.85).abs() < 1e-4, "after lap 3: {second}");
+        // Lap 4: 52.8 -> 50.0 = 2.8 l. Mean (2.8 + 2.9 + 2.8) / 3.
+        estimator.update(4, 51.3, false);
+        let third = estimator.update(5, 50.0, false).ok_or("no estimate")?;
+        assert!((third - 8.5 / 3.0).abs() < 1e-4, "after lap 4: {third}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_lap_fuel_estimate_skips_pit_and_refuel_laps() -> TestResult {
+        let mut estimator = LapFuelEstimator::default();
+        estimator.update(1, 30.0, false);
+        estimator.update(2, 28.0, false);
+        let clean = estimator.update(3, 26.0, false).ok_or("no estimate")?;
+        assert!((clean - 2.0).abs() < 1e-4);
+        // In-lap through pit road with a refuel: ignored.
+        estimator.update(3, 25.0, true);
+        estimator.update(3, 60.0, true);
+        let after_stop = estimator.update(4, 59.0, false).ok_or("no estimate")?;
+        assert!((after_stop - 2.0).abs() < 1e-4);
+        // A new session resets the lap counter and the history.
+        assert_eq!(estimator.update(0, 60.0, false), None);
+        Ok(())
+    }
+
     #[test]
     fn test_resolve_slip_ratio_prefers_max_explicit_wheel_ratio() -> TestResult {
         let mut layout = IRacingLayout::default();
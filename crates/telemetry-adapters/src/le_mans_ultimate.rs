@@ -38,6 +38,7 @@
 //!
 //! Update rate: 60 Hz (configurable in bridge settings).
 
+use crate::raw_capture::RawCaptureSlot;
 use crate::{
     NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver, telemetry_now_ns,
 };
@@ -125,6 +126,7 @@ pub fn parse_le_mans_ultimate_packet(data: &[u8]) -> Result<NormalizedTelemetry>
 pub struct LeMansUltimateAdapter {
     bind_port: u16,
     update_rate: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for LeMansUltimateAdapter {
@@ -143,6 +145,7 @@ impl LeMansUltimateAdapter {
         Self {
             bind_port,
             update_rate: Duration::from_millis(16),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -158,7 +161,12 @@ impl TelemetryAdapter for LeMansUltimateAdapter {
         "le_mans_ultimate"
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(100);
         let bind_port = self.bind_port;
         let update_rate = self.update_rate;
@@ -177,7 +185,9 @@ impl TelemetryAdapter for LeMansUltimateAdapter {
             let mut frame_seq = 0u64;
 
             loop {
-                match tokio::time::timeout(update_rate * 10, socket.recv(&mut buf)).await {
+                match tokio::time::timeout(update_rate * 10, raw_capture.recv(&socket, &mut buf))
+                    .await
+                {
                     Ok(Ok(len)) => match parse_le_mans_ultimate_packet(&buf[..len]) {
                         Ok(normalized) => {
                             let frame =
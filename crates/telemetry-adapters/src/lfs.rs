@@ -6,6 +6,8 @@
 //! same as used by BeamNG.drive.
 #![cfg_attr(not(windows), allow(unused, dead_code))]
 
+use crate::raw_capture::{RawCaptureSink, RawCaptureSlot};
+use crate::traffic::TrafficAccountant;
 use crate::{
     NormalizedTelemetry, TelemetryAdapter, TelemetryFlags, TelemetryFrame, TelemetryReceiver,
     TelemetryValue, telemetry_now_ns,
@@ -13,6 +15,7 @@ use crate::{
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::net::UdpSocket as TokioUdpSocket;
 use tokio::sync::mpsc;
@@ -117,6 +120,7 @@ fn parse_lfs_packet(data: &[u8]) -> Result<NormalizedTelemetry> {
 pub struct LFSAdapter {
     bind_port: u16,
     update_rate: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for LFSAdapter {
@@ -130,6 +134,7 @@ impl LFSAdapter {
         Self {
             bind_port: DEFAULT_LFS_PORT,
             update_rate: Duration::from_millis(16),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -145,7 +150,12 @@ impl TelemetryAdapter for LFSAdapter {
         "live_for_speed"
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(100);
         let bind_port = self.bind_port;
         let update_rate = self.update_rate;
@@ -164,7 +174,9 @@ impl TelemetryAdapter for LFSAdapter {
             let mut frame_seq = 0u64;
 
             loop {
-                match tokio::time::timeout(update_rate * 10, socket.recv(&mut buf)).await {
+                match tokio::time::timeout(update_rate * 10, raw_capture.recv(&socket, &mut buf))
+                    .await
+                {
                     Ok(Ok(len)) => match parse_lfs_packet(&buf[..len]) {
                         Ok(normalized) => {
                             let frame =
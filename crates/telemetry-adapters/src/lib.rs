@@ -6,20 +6,23 @@
 
 #![deny(static_mut_refs)]
 
-use std::sync::OnceLock;
-use std::time::{Duration, Instant};
+use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use async_trait::async_trait;
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 
 pub use racing_wheel_telemetry_core::{
-    NormalizedTelemetry, TelemetryFlags, TelemetryFrame, TelemetryValue,
+    DEFAULT_MAX_OPPONENTS, ExtendedKey, FieldUnit, NormalizedTelemetry, OpponentSnapshot,
+    PenaltyEvent, PenaltyKind, PenaltyState, PenaltyTracker, SessionTiming, TelemetryFlags,
+    TelemetryFrame, TelemetryValue, TireCorner, TireData, Unit, UnitManifest, bound_opponents,
 };
 
 // Keep these protocol modules first so dependent implementations can import helpers
 // via `crate::` paths unchanged from their service-side origins.
 pub mod ac_evo;
+pub mod ac_layout;
 pub mod ac_rally;
 pub mod acc;
 pub mod acc2;
@@ -29,6 +32,8 @@ pub mod automobilista;
 pub mod beamng;
 pub mod codemasters_shared;
 pub mod codemasters_udp;
+pub mod composite;
+pub mod coverage_probe;
 pub mod dakar;
 pub mod dirt3;
 pub mod dirt4;
@@ -41,6 +46,7 @@ pub mod f1;
 pub mod f1_25;
 pub mod f1_manager;
 pub mod f1_native;
+pub mod faulty_mock;
 pub mod flatout;
 pub mod forza;
 pub mod forza_horizon;
@@ -58,18 +64,26 @@ pub mod motogp;
 pub mod mudrunner;
 pub mod nascar;
 pub mod nascar_21;
+#[cfg(feature = "test-fixtures")]
+pub mod normalization_fixtures;
 pub mod pcars2;
 pub mod pcars3;
 pub mod race_driver_grid;
 pub mod raceroom;
+pub mod raw_capture;
 pub mod rbr;
+pub mod recognition;
 pub mod rennsport;
 pub mod rfactor1;
 pub mod rfactor2;
 pub mod ride5;
 pub mod seb_loeb_rally;
+pub mod shm_snapshot;
 pub mod simhub;
 pub mod trackmania;
+pub mod traffic;
+pub mod udp_broker;
+pub mod udp_listener;
 pub mod v_rally_4;
 pub mod wrc_generations;
 pub mod wrc_kylotonn;
@@ -82,17 +96,9 @@ mod normalization_tests;
 /// Shared type alias for outbound telemetry streams.
 pub type TelemetryReceiver = mpsc::Receiver<TelemetryFrame>;
 
-static TELEMETRY_EPOCH: OnceLock<Instant> = OnceLock::new();
-
-/// Return a monotonic timestamp in nanoseconds using a process-wide epoch.
-pub fn telemetry_now_ns() -> u64 {
-    let epoch = TELEMETRY_EPOCH.get_or_init(Instant::now);
-    Instant::now()
-        .checked_duration_since(*epoch)
-        .map(|duration| duration.as_nanos())
-        .unwrap_or(0)
-        .min(u64::MAX as u128) as u64
-}
+/// Monotonic timestamp in nanoseconds since a process-wide epoch, the one
+/// connection state events are stamped with as well.
+pub use racing_wheel_telemetry_core::telemetry_now_ns;
 
 /// Telemetry adapter trait for game-specific telemetry sources.
 #[async_trait]
@@ -114,6 +120,69 @@ pub trait TelemetryAdapter: Send + Sync {
 
     /// Check if the game is currently running.
     async fn is_game_running(&self) -> Result<bool>;
+
+    /// How this adapter's UDP socket receives datagrams; `None` for adapters
+    /// that do not listen on UDP.
+    fn listen_mode(&self) -> Option<ListenMode> {
+        None
+    }
+
+    /// Units this adapter believes its game sends, and the conversions it
+    /// applies to reach the contract's canonical units; `None` if undeclared.
+    fn unit_manifest(&self) -> Option<UnitManifest> {
+        None
+    }
+
+    /// Local UDP port this adapter binds; `None` for adapters that do not
+    /// listen on UDP.
+    fn udp_port(&self) -> Option<u16> {
+        None
+    }
+
+    /// How much `raw` looks like this adapter's wire format; `None` if it
+    /// does not. Defaults to whether [`normalize`](Self::normalize) accepts it.
+    fn recognize_packet(&self, raw: &[u8]) -> Option<PacketMatch> {
+        self.normalize(raw).ok()?;
+        Some(PacketMatch::new(
+            DECODE_CONFIDENCE,
+            format!("{} packet", self.game_id()),
+        ))
+    }
+
+    /// Slot the adapter's receive path records raw payloads through; `None`
+    /// for adapters that do not support capture.
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        None
+    }
+
+    /// Write every raw payload this adapter receives, before normalizing it,
+    /// into `sink`; `None` stops capturing. A no-op for adapters without a
+    /// [`raw_capture_slot`](Self::raw_capture_slot).
+    fn set_raw_capture(&self, sink: Option<Arc<RawCaptureSink>>) {
+        if let Some(slot) = self.raw_capture_slot() {
+            slot.set(sink);
+        }
+    }
+
+    /// Packet and byte counts of the raw payloads this adapter receives, or
+    /// on a brokered port only those it decodes as its own; `None` for
+    /// adapters without a [`raw_capture_slot`](Self::raw_capture_slot).
+    fn traffic(&self) -> Option<Arc<TrafficAccountant>> {
+        self.raw_capture_slot().map(RawCaptureSlot::traffic)
+    }
+
+    /// Whether the pipeline should drop frames this adapter re-sends
+    /// unchanged, as adapters polling shared memory faster than the game
+    /// writes it do.
+    fn prefers_dedup(&self) -> bool {
+        false
+    }
+
+    /// Receive every switch between transports from now on; `None` for
+    /// adapters reading a single transport.
+    fn transport_switches(&self) -> Option<broadcast::Receiver<TransportSwitched>> {
+        None
+    }
 }
 
 /// Factory for constructing adapter instances.
@@ -127,8 +196,14 @@ fn new_acc_adapter() -> Box<dyn TelemetryAdapter> {
     Box::new(ACCAdapter::new())
 }
 
+/// Shared memory, falling back to the Project CARS 2 UDP stream AMS2 also
+/// sends.
 fn new_ams2_adapter() -> Box<dyn TelemetryAdapter> {
-    Box::new(AMS2Adapter::new())
+    Box::new(
+        CompositeAdapter::new("ams2")
+            .with_transport("shared_memory", Box::new(AMS2Adapter::new()))
+            .with_transport("udp", Box::new(PCars2Adapter::new())),
+    )
 }
 
 fn new_assetto_corsa_adapter() -> Box<dyn TelemetryAdapter> {
@@ -203,8 +278,13 @@ fn new_gsc_adapter() -> Box<dyn TelemetryAdapter> {
     ))
 }
 
+/// Shared memory, falling back to the rF2 UDP bridge.
 fn new_rfactor2_adapter() -> Box<dyn TelemetryAdapter> {
-    Box::new(RFactor2Adapter::new())
+    Box::new(
+        CompositeAdapter::new("rfactor2")
+            .with_transport("shared_memory", Box::new(RFactor2Adapter::new()))
+            .with_transport("udp", Box::new(LeMansUltimateAdapter::new())),
+    )
 }
 
 fn new_eawrc_adapter() -> Box<dyn TelemetryAdapter> {
@@ -455,6 +535,9 @@ pub use assetto_corsa::AssettoCorsaAdapter;
 pub use automobilista::Automobilista1Adapter;
 pub use beamng::BeamNGAdapter;
 pub use codemasters_udp::{CustomUdpSpec, DecodedCodemastersPacket, FieldSpec};
+pub use composite::{
+    CompositeAdapter, FailoverConfig, TRANSPORT_EVENT_CAPACITY, TransportSwitched,
+};
 pub use dakar::DakarDesertRallyAdapter;
 pub use dirt_rally_2::DirtRally2Adapter;
 pub use dirt_showdown::DirtShowdownAdapter;
@@ -467,6 +550,7 @@ pub use f1::F1Adapter;
 pub use f1_25::F1_25Adapter;
 pub use f1_manager::F1ManagerAdapter;
 pub use f1_native::F1NativeAdapter;
+pub use faulty_mock::{ABSURD_SPEED_MS, FaultScript, FaultyMockAdapter};
 pub use flatout::FlatOutAdapter;
 pub use forza::ForzaAdapter;
 pub use forza_horizon::{ForzaHorizon4Adapter, ForzaHorizon5Adapter};
@@ -488,7 +572,12 @@ pub use pcars2::PCars2Adapter;
 pub use pcars3::PCars3Adapter;
 pub use race_driver_grid::RaceDriverGridAdapter;
 pub use raceroom::RaceRoomAdapter;
+pub use raw_capture::{
+    RawCaptureCaps, RawCaptureReader, RawCaptureRecord, RawCaptureSink, RawCaptureSlot,
+    RawCaptureStats,
+};
 pub use rbr::RBRAdapter;
+pub use recognition::{DECODE_CONFIDENCE, PacketMatch};
 pub use rennsport::RennsportAdapter;
 pub use rfactor1::RFactor1Adapter;
 pub use rfactor2::RFactor2Adapter;
@@ -496,17 +585,26 @@ pub use ride5::Ride5Adapter;
 pub use seb_loeb_rally::SebLoebRallyAdapter;
 pub use simhub::SimHubAdapter;
 pub use trackmania::TrackmaniaAdapter;
+pub use traffic::{TrafficAccountant, TrafficRate, TrafficStats};
+pub use udp_broker::{
+    NotMyPacket, SubscriberStats, SubscriptionHandle, UdpPortBroker, UdpSubscription,
+};
+pub use udp_listener::{AdapterNetworkConfig, ListenMode};
 pub use v_rally_4::VRally4Adapter;
 pub use wrc_generations::WrcGenerationsAdapter;
 pub use wrc_kylotonn::WrcKylotonnAdapter;
 pub use wreckfest::WreckfestAdapter;
 pub use wtcr::WtcrAdapter;
 
+/// Builds the telemetry of the frame with a given sequence number.
+pub type MockFrameSource = fn(u64) -> NormalizedTelemetry;
+
 /// Mock adapter for testing and deterministic fixture generation.
 pub struct MockAdapter {
     game_id: String,
     update_rate: Duration,
     is_running: bool,
+    frame_source: Option<MockFrameSource>,
 }
 
 impl MockAdapter {
@@ -515,6 +613,22 @@ impl MockAdapter {
             game_id,
             update_rate: Duration::from_millis(16),
             is_running: false,
+            frame_source: None,
+        }
+    }
+
+    /// Emit `source(sequence)` for each frame instead of the built-in
+    /// time-varying lap.
+    pub fn with_frame_source(mut self, source: MockFrameSource) -> Self {
+        self.frame_source = Some(source);
+        self
+    }
+
+    /// Create a mock adapter that emits frames at a custom interval.
+    pub fn with_update_rate(game_id: String, update_rate: Duration) -> Self {
+        Self {
+            update_rate,
+            ..Self::new(game_id)
         }
     }
 
@@ -533,15 +647,20 @@ impl TelemetryAdapter for MockAdapter {
         let (tx, rx) = tokio::sync::mpsc::channel(100);
 
         let update_rate = self.update_rate;
+        let frame_source = self.frame_source;
 
         tokio::spawn(async move {
             let mut frame_seq = 0u64;
 
             loop {
                 let timestamp_ns = telemetry_now_ns();
-                let elapsed = std::time::Duration::from_nanos(timestamp_ns);
-                let progress = (elapsed.as_secs_f32() % 10.0) / 10.0;
-                let telemetry = generate_mock_telemetry(progress);
+                let telemetry = match frame_source {
+                    Some(source) => source(frame_seq),
+                    None => {
+                        let elapsed = std::time::Duration::from_nanos(timestamp_ns);
+                        generate_mock_telemetry((elapsed.as_secs_f32() % 10.0) / 10.0)
+                    }
+                };
 
                 let frame = TelemetryFrame::new(telemetry, timestamp_ns, frame_seq, 64);
                 if tx.send(frame).await.is_err() {
@@ -5,6 +5,7 @@
 //!
 //! Update rate: ~60 Hz.
 
+use crate::raw_capture::RawCaptureSlot;
 use crate::{
     NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver, telemetry_now_ns,
 };
@@ -23,6 +24,7 @@ const MAX_PACKET_SIZE: usize = 4096;
 pub struct MotoGPAdapter {
     bind_port: u16,
     update_rate: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl MotoGPAdapter {
@@ -30,6 +32,7 @@ impl MotoGPAdapter {
         Self {
             bind_port: MOTOGP_PORT,
             update_rate: Duration::from_millis(16), // ~60 Hz
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 }
@@ -46,7 +49,12 @@ impl TelemetryAdapter for MotoGPAdapter {
         "motogp"
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(64);
         let bind_port = self.bind_port;
         let update_rate = self.update_rate;
@@ -65,7 +73,9 @@ impl TelemetryAdapter for MotoGPAdapter {
             let mut frame_idx = 0u64;
 
             loop {
-                match tokio::time::timeout(update_rate * 10, socket.recv(&mut buf)).await {
+                match tokio::time::timeout(update_rate * 10, raw_capture.recv(&socket, &mut buf))
+                    .await
+                {
                     Ok(Ok(len)) => match crate::simhub::parse_simhub_packet(&buf[..len]) {
                         Ok(normalized) => {
                             let frame =
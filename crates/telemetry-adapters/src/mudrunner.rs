@@ -5,6 +5,7 @@
 //!
 //! Update rate: ~20 Hz.
 
+use crate::raw_capture::RawCaptureSlot;
 use crate::{
     NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver, telemetry_now_ns,
 };
@@ -42,6 +43,7 @@ pub struct MudRunnerAdapter {
     variant: MudRunnerVariant,
     bind_port: u16,
     update_rate: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl MudRunnerAdapter {
@@ -56,6 +58,7 @@ impl MudRunnerAdapter {
             variant,
             bind_port: MUDRUNNER_PORT,
             update_rate: Duration::from_millis(50), // ~20 Hz
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 }
@@ -72,7 +75,12 @@ impl TelemetryAdapter for MudRunnerAdapter {
         self.variant.game_id()
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(64);
         let bind_port = self.bind_port;
         let update_rate = self.update_rate;
@@ -92,7 +100,9 @@ impl TelemetryAdapter for MudRunnerAdapter {
             let mut frame_idx = 0u64;
 
             loop {
-                match tokio::time::timeout(update_rate * 10, socket.recv(&mut buf)).await {
+                match tokio::time::timeout(update_rate * 10, raw_capture.recv(&socket, &mut buf))
+                    .await
+                {
                     Ok(Ok(len)) => match crate::simhub::parse_simhub_packet(&buf[..len]) {
                         Ok(normalized) => {
                             let frame =
@@ -34,6 +34,7 @@
 //! offset 88: f32  steer    (-1.0 to 1.0, left negative)
 //! ```
 
+use crate::raw_capture::RawCaptureSlot;
 use crate::{
     NormalizedTelemetry, TelemetryAdapter, TelemetryFlags, TelemetryFrame, TelemetryReceiver,
     telemetry_now_ns,
@@ -114,6 +115,7 @@ pub fn parse_nascar_packet(data: &[u8]) -> Result<NormalizedTelemetry> {
 pub struct NascarAdapter {
     bind_port: u16,
     update_rate: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for NascarAdapter {
@@ -132,6 +134,7 @@ impl NascarAdapter {
         Self {
             bind_port,
             update_rate: Duration::from_millis(16),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -147,7 +150,12 @@ impl TelemetryAdapter for NascarAdapter {
         "nascar"
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(100);
         let bind_port = self.bind_port;
         let update_rate = self.update_rate;
@@ -166,7 +174,9 @@ impl TelemetryAdapter for NascarAdapter {
             let mut frame_seq = 0u64;
 
             loop {
-                match tokio::time::timeout(update_rate * 10, socket.recv(&mut buf)).await {
+                match tokio::time::timeout(update_rate * 10, raw_capture.recv(&socket, &mut buf))
+                    .await
+                {
                     Ok(Ok(len)) => match parse_nascar_packet(&buf[..len]) {
                         Ok(normalized) => {
                             let frame =
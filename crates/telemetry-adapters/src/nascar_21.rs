@@ -5,6 +5,7 @@
 //!
 //! Packet parsing is delegated to [`crate::nascar::parse_nascar_packet`].
 
+use crate::raw_capture::RawCaptureSlot;
 use crate::{
     NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver,
     nascar::parse_nascar_packet, telemetry_now_ns,
@@ -28,6 +29,7 @@ const ENV_PORT: &str = "OPENRACING_NASCAR21_UDP_PORT";
 pub struct Nascar21Adapter {
     bind_port: u16,
     update_rate: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for Nascar21Adapter {
@@ -46,6 +48,7 @@ impl Nascar21Adapter {
         Self {
             bind_port,
             update_rate: Duration::from_millis(16),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -61,7 +64,12 @@ impl TelemetryAdapter for Nascar21Adapter {
         "nascar_21"
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(100);
         let bind_port = self.bind_port;
         let update_rate = self.update_rate;
@@ -80,7 +88,9 @@ impl TelemetryAdapter for Nascar21Adapter {
             let mut frame_seq = 0u64;
 
             loop {
-                match tokio::time::timeout(update_rate * 10, socket.recv(&mut buf)).await {
+                match tokio::time::timeout(update_rate * 10, raw_capture.recv(&socket, &mut buf))
+                    .await
+                {
                     Ok(Ok(len)) => match parse_nascar_packet(&buf[..len]) {
                         Ok(normalized) => {
                             let frame =
@@ -0,0 +1,357 @@
+//! Golden normalization tests over raw captures.
+//!
+//! A fixture pairs a [raw capture](crate::raw_capture) with the
+//! [`NormalizedTelemetry`] its adapter produced for every record when the
+//! fixture was blessed. [`run_fixture`] replays the capture through
+//! [`TelemetryAdapter::normalize`] and reports every field that changed, so
+//! a protocol refactor cannot silently alter an adapter's output.
+//!
+//! Floats may drift within [`FixtureOptions::epsilon`]; integers, booleans,
+//! strings and whether a record decoded at all must match exactly. Set
+//! [`BLESS_ENV`] to regenerate the expected files instead of comparing.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::raw_capture::RawCaptureReader;
+use crate::{NormalizedTelemetry, TelemetryAdapter};
+
+/// Environment variable that switches [`run_fixture`] to blessing.
+pub const BLESS_ENV: &str = "OPENRACING_BLESS_FIXTURES";
+
+/// Default tolerance for float fields.
+pub const DEFAULT_EPSILON: f64 = 1e-4;
+
+/// Mismatches kept verbatim in a [`FixtureReport`] for the failure message.
+const MAX_REPORTED_MISMATCHES: usize = 20;
+
+/// How a fixture is checked.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixtureOptions {
+    /// Largest accepted float difference, scaled by the expected magnitude
+    /// once that exceeds 1.
+    pub epsilon: f64,
+    /// Rewrite the expected file from the current output instead of comparing.
+    pub bless: bool,
+}
+
+impl Default for FixtureOptions {
+    fn default() -> Self {
+        Self {
+            epsilon: DEFAULT_EPSILON,
+            bless: false,
+        }
+    }
+}
+
+impl FixtureOptions {
+    /// Defaults, blessing if [`BLESS_ENV`] is set to anything but `0`.
+    pub fn from_env() -> Self {
+        Self {
+            bless: std::env::var(BLESS_ENV).is_ok_and(|value| value != "0"),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_epsilon(mut self, epsilon: f64) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+}
+
+/// Expected output of one capture, as stored on disk.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExpectedNormalization {
+    pub game_id: String,
+    pub records: Vec<ExpectedRecord>,
+}
+
+/// Expected outcome of normalizing one captured payload.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExpectedRecord {
+    Normalized(Box<NormalizedTelemetry>),
+    /// `normalize` rejected the payload; the message is informational only.
+    Error(String),
+}
+
+/// One field that differs from the expected output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldMismatch {
+    /// Index of the record in the capture.
+    pub record: usize,
+    /// Dotted path of the field, e.g. `flags.yellow_flag` or `tire_temps_c[2]`.
+    pub field: String,
+    pub expected: Value,
+    pub actual: Value,
+}
+
+/// Outcome of [`run_fixture`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FixtureReport {
+    pub game_id: String,
+    /// Records replayed from the capture.
+    pub records: usize,
+    /// Records with at least one mismatching field.
+    pub mismatched_records: usize,
+    /// Mismatches per field path, across all records.
+    pub field_mismatches: BTreeMap<String, usize>,
+    /// The first mismatches in capture order.
+    pub mismatches: Vec<FieldMismatch>,
+    /// Whether the expected file was rewritten rather than compared.
+    pub blessed: bool,
+}
+
+impl FixtureReport {
+    pub fn passed(&self) -> bool {
+        self.mismatched_records == 0
+    }
+
+    fn total_mismatches(&self) -> usize {
+        self.field_mismatches.values().sum()
+    }
+
+    fn note(&mut self, record: usize, field: String, expected: Value, actual: Value) {
+        *self.field_mismatches.entry(field.clone()).or_default() += 1;
+        if self.mismatches.len() < MAX_REPORTED_MISMATCHES {
+            self.mismatches.push(FieldMismatch {
+                record,
+                field,
+                expected,
+                actual,
+            });
+        }
+    }
+}
+
+impl fmt::Display for FixtureReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.blessed {
+            return write!(f, "{}: blessed {} records", self.game_id, self.records);
+        }
+        write!(
+            f,
+            "{}: {} of {} records mismatched",
+            self.game_id, self.mismatched_records, self.records
+        )?;
+        for (field, count) in &self.field_mismatches {
+            write!(f, "\n  {field}: {count}")?;
+        }
+        for mismatch in &self.mismatches {
+            write!(
+                f,
+                "\n  record {} {}: expected {}, got {}",
+                mismatch.record, mismatch.field, mismatch.expected, mismatch.actual
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Replay `capture_path` through `adapter` and compare against
+/// `expected_path`, with [`FixtureOptions::from_env`].
+pub fn run_fixture(
+    adapter: &dyn TelemetryAdapter,
+    capture_path: impl AsRef<Path>,
+    expected_path: impl AsRef<Path>,
+) -> Result<FixtureReport> {
+    run_fixture_with(
+        adapter,
+        capture_path,
+        expected_path,
+        FixtureOptions::from_env(),
+    )
+}
+
+/// Regenerate `expected_path` from `adapter`'s current output for `capture_path`.
+pub fn bless(
+    adapter: &dyn TelemetryAdapter,
+    capture_path: impl AsRef<Path>,
+    expected_path: impl AsRef<Path>,
+) -> Result<FixtureReport> {
+    let options = FixtureOptions {
+        bless: true,
+        ..FixtureOptions::default()
+    };
+    run_fixture_with(adapter, capture_path, expected_path, options)
+}
+
+/// [`run_fixture`] with explicit options.
+pub fn run_fixture_with(
+    adapter: &dyn TelemetryAdapter,
+    capture_path: impl AsRef<Path>,
+    expected_path: impl AsRef<Path>,
+    options: FixtureOptions,
+) -> Result<FixtureReport> {
+    let capture_path = capture_path.as_ref();
+    let expected_path = expected_path.as_ref();
+    let actual = replay(adapter, capture_path)?;
+
+    if options.bless {
+        let mut json = serde_json::to_string_pretty(&actual)?;
+        json.push('\n');
+        fs::write(expected_path, json)
+            .with_context(|| format!("writing {}", expected_path.display()))?;
+        return Ok(FixtureReport {
+            game_id: actual.game_id,
+            records: actual.records.len(),
+            blessed: true,
+            ..FixtureReport::default()
+        });
+    }
+
+    let expected: ExpectedNormalization = serde_json::from_str(
+        &fs::read_to_string(expected_path)
+            .with_context(|| format!("reading {}", expected_path.display()))?,
+    )
+    .with_context(|| format!("parsing {}", expected_path.display()))?;
+
+    let mut report = FixtureReport {
+        game_id: actual.game_id.clone(),
+        records: actual.records.len(),
+        ..FixtureReport::default()
+    };
+    if expected.game_id != actual.game_id {
+        report.note(
+            0,
+            "game_id".to_string(),
+            Value::from(expected.game_id.as_str()),
+            Value::from(actual.game_id.as_str()),
+        );
+    }
+    if expected.records.len() != actual.records.len() {
+        report.note(
+            0,
+            "records".to_string(),
+            Value::from(expected.records.len()),
+            Value::from(actual.records.len()),
+        );
+    }
+
+    for (index, (expected, actual)) in expected.records.iter().zip(&actual.records).enumerate() {
+        let before = report.total_mismatches();
+        match (expected, actual) {
+            (ExpectedRecord::Normalized(expected), ExpectedRecord::Normalized(actual)) => {
+                let (expected, actual) = (
+                    serde_json::to_value(expected)?,
+                    serde_json::to_value(actual)?,
+                );
+                compare(
+                    &mut report,
+                    index,
+                    String::new(),
+                    &expected,
+                    &actual,
+                    options.epsilon,
+                );
+            }
+            (ExpectedRecord::Error(_), ExpectedRecord::Error(_)) => {}
+            (expected, actual) => report.note(
+                index,
+                "result".to_string(),
+                outcome(expected),
+                outcome(actual),
+            ),
+        }
+        if report.total_mismatches() > before {
+            report.mismatched_records += 1;
+        }
+    }
+    // Header mismatches count against the fixture as a whole.
+    if report.mismatched_records == 0 && report.total_mismatches() > 0 {
+        report.mismatched_records = 1;
+    }
+    Ok(report)
+}
+
+/// Normalize every record of the capture at `capture_path`.
+pub fn replay(
+    adapter: &dyn TelemetryAdapter,
+    capture_path: impl AsRef<Path>,
+) -> Result<ExpectedNormalization> {
+    let capture_path = capture_path.as_ref();
+    let reader = RawCaptureReader::open(capture_path)
+        .with_context(|| format!("opening {}", capture_path.display()))?;
+    if reader.game_id() != adapter.game_id() {
+        return Err(anyhow!(
+            "{} was captured from {}, not {}",
+            capture_path.display(),
+            reader.game_id(),
+            adapter.game_id()
+        ));
+    }
+    let game_id = reader.game_id().to_string();
+    let mut records = Vec::new();
+    for record in reader {
+        let record = record.with_context(|| format!("reading {}", capture_path.display()))?;
+        records.push(match adapter.normalize(&record.payload) {
+            Ok(normalized) => ExpectedRecord::Normalized(Box::new(normalized)),
+            Err(error) => ExpectedRecord::Error(error.to_string()),
+        });
+    }
+    Ok(ExpectedNormalization { game_id, records })
+}
+
+fn outcome(record: &ExpectedRecord) -> Value {
+    match record {
+        ExpectedRecord::Normalized(_) => Value::from("normalized"),
+        ExpectedRecord::Error(message) => Value::from(format!("error: {message}")),
+    }
+}
+
+fn compare(
+    report: &mut FixtureReport,
+    record: usize,
+    path: String,
+    expected: &Value,
+    actual: &Value,
+    epsilon: f64,
+) {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            let mut keys: Vec<&String> = expected.keys().chain(actual.keys()).collect();
+            keys.sort_unstable();
+            keys.dedup();
+            for key in keys {
+                let field = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                let (expected, actual) = (
+                    expected.get(key).unwrap_or(&Value::Null),
+                    actual.get(key).unwrap_or(&Value::Null),
+                );
+                compare(report, record, field, expected, actual, epsilon);
+            }
+        }
+        (Value::Array(expected_items), Value::Array(actual_items))
+            if expected_items.len() == actual_items.len() =>
+        {
+            for (index, (expected, actual)) in expected_items.iter().zip(actual_items).enumerate() {
+                compare(
+                    report,
+                    record,
+                    format!("{path}[{index}]"),
+                    expected,
+                    actual,
+                    epsilon,
+                );
+            }
+        }
+        (Value::Number(e), Value::Number(a)) if e.is_f64() || a.is_f64() => {
+            let (e, a) = (e.as_f64().unwrap_or(0.0), a.as_f64().unwrap_or(0.0));
+            if (e - a).abs() > epsilon * e.abs().max(1.0) {
+                report.note(record, path, expected.clone(), actual.clone());
+            }
+        }
+        _ if expected == actual => {}
+        _ => report.note(record, path, expected.clone(), actual.clone()),
+    }
+}
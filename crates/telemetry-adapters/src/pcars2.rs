@@ -20,6 +20,8 @@
 //! The AMS2 adapter (`ams2.rs`) handles the full shared memory struct path.
 #![cfg_attr(not(windows), allow(unused, dead_code))]
 
+use crate::raw_capture::{RawCaptureSink, RawCaptureSlot};
+use crate::traffic::TrafficAccountant;
 use crate::{
     NormalizedTelemetry, TelemetryAdapter, TelemetryFlags, TelemetryFrame, TelemetryReceiver,
     TelemetryValue, telemetry_now_ns,
@@ -27,6 +29,7 @@ use crate::{
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::net::UdpSocket as TokioUdpSocket;
 use tokio::sync::mpsc;
@@ -428,6 +431,7 @@ pub fn merge_timing_fields(telemetry: &mut NormalizedTelemetry, timing: &Normali
 pub struct PCars2Adapter {
     bind_port: u16,
     update_rate: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for PCars2Adapter {
@@ -441,6 +445,7 @@ impl PCars2Adapter {
         Self {
             bind_port: DEFAULT_PCARS2_PORT,
             update_rate: Duration::from_millis(10),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -456,7 +461,12 @@ impl TelemetryAdapter for PCars2Adapter {
         "project_cars_2"
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(100);
         let bind_port = self.bind_port;
         let update_rate = self.update_rate;
@@ -510,7 +520,9 @@ impl TelemetryAdapter for PCars2Adapter {
             let mut viewed_participant: u8 = 0;
 
             loop {
-                match tokio::time::timeout(update_rate * 10, socket.recv(&mut buf)).await {
+                match tokio::time::timeout(update_rate * 10, raw_capture.recv(&socket, &mut buf))
+                    .await
+                {
                     Ok(Ok(len)) => {
                         let pkt = &buf[..len];
                         match pcars2_packet_type(pkt) {
@@ -4,6 +4,7 @@
 //! This adapter delegates parsing to the shared [`crate::pcars2`] implementation
 //! while exposing a distinct game identity.
 
+use crate::raw_capture::RawCaptureSlot;
 use crate::{
     NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver, telemetry_now_ns,
 };
@@ -24,6 +25,7 @@ const MAX_PACKET_SIZE: usize = 512;
 pub struct PCars3Adapter {
     bind_port: u16,
     update_rate: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for PCars3Adapter {
@@ -37,6 +39,7 @@ impl PCars3Adapter {
         Self {
             bind_port: DEFAULT_PCARS3_PORT,
             update_rate: Duration::from_millis(10),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -52,7 +55,12 @@ impl TelemetryAdapter for PCars3Adapter {
         "project_cars_3"
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(100);
         let bind_port = self.bind_port;
         let update_rate = self.update_rate;
@@ -73,7 +81,9 @@ impl TelemetryAdapter for PCars3Adapter {
             let mut viewed_participant: u8 = 0;
 
             loop {
-                match tokio::time::timeout(update_rate * 10, socket.recv(&mut buf)).await {
+                match tokio::time::timeout(update_rate * 10, raw_capture.recv(&socket, &mut buf))
+                    .await
+                {
                     Ok(Ok(len)) => {
                         let pkt = &buf[..len];
                         match crate::pcars2::pcars2_packet_type(pkt) {
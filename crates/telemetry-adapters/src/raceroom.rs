@@ -337,6 +337,10 @@ impl TelemetryAdapter for RaceRoomAdapter {
         self.update_rate
     }
 
+    fn prefers_dedup(&self) -> bool {
+        true
+    }
+
     async fn is_game_running(&self) -> Result<bool> {
         Ok(is_raceroom_process_running())
     }
@@ -0,0 +1,499 @@
+//! Raw packet capture for protocol debugging.
+//!
+//! When a game's normalized output looks wrong, the datagrams it actually
+//! sent are what a protocol fix needs. An adapter given a [`RawCaptureSink`]
+//! through [`TelemetryAdapter::set_raw_capture`](crate::TelemetryAdapter::set_raw_capture)
+//! writes every payload it receives, before decoding, into the sink's file.
+//! On a port shared through the broker, payloads the adapter's decoder rejects
+//! as another game's are left out.
+//! [`RawCaptureReader`] replays the records, e.g. in decode tests.
+//!
+//! # File format
+//!
+//! All integers are little-endian.
+//!
+//! ```text
+//! header:  b"ORRAWCAP" | u16 version | u16 game id length | game id (UTF-8)
+//! record:  u32 length of what follows | u64 timestamp_ns
+//!          | u8 source kind (0 none, 4 IPv4, 6 IPv6) | source ip | u16 source port
+//!          | payload
+//! ```
+//!
+//! The source ip and port are omitted when the kind is 0. A sink rotates to a
+//! new file when the current one would exceed its [`RawCaptureCaps`], keeping
+//! at most `max_files` of them.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use tokio::net::UdpSocket;
+use tracing::warn;
+
+use crate::telemetry_now_ns;
+use crate::traffic::TrafficAccountant;
+use crate::udp_broker::NotMyPacket;
+
+/// First bytes of every capture file.
+pub const RAW_CAPTURE_MAGIC: &[u8; 8] = b"ORRAWCAP";
+
+/// Format version written by [`RawCaptureSink`].
+pub const RAW_CAPTURE_VERSION: u16 = 1;
+
+/// Largest record a reader accepts, so a corrupt length cannot exhaust memory.
+const MAX_RECORD_BYTES: usize = 1 << 20;
+
+const SOURCE_NONE: u8 = 0;
+const SOURCE_V4: u8 = 4;
+const SOURCE_V6: u8 = 6;
+
+/// Limits of one capture file, and how many files a capture keeps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawCaptureCaps {
+    /// Bytes per file, header included, before rotating.
+    pub max_file_bytes: u64,
+    /// Records per file before rotating.
+    pub max_records_per_file: u64,
+    /// Files kept; the oldest is deleted when a rotation exceeds this.
+    pub max_files: usize,
+}
+
+impl Default for RawCaptureCaps {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: 64 * 1024 * 1024,
+            max_records_per_file: u64::MAX,
+            max_files: 4,
+        }
+    }
+}
+
+/// Counters of a [`RawCaptureSink`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RawCaptureStats {
+    /// Records written across all files.
+    pub records: u64,
+    /// Bytes written across all files, headers included.
+    pub bytes: u64,
+    /// Times a new file was started because the current one was full.
+    pub rotations: u64,
+    /// Records lost to write errors.
+    pub write_errors: u64,
+}
+
+struct SinkState {
+    writer: BufWriter<File>,
+    file_bytes: u64,
+    file_records: u64,
+    next_index: u64,
+    files: VecDeque<PathBuf>,
+    stats: RawCaptureStats,
+}
+
+/// Writes one game's raw payloads to a rotating set of capture files.
+///
+/// The first file is `path`; rotations continue with `path.1`, `path.2`, ...
+pub struct RawCaptureSink {
+    game_id: String,
+    path: PathBuf,
+    caps: RawCaptureCaps,
+    state: Mutex<SinkState>,
+}
+
+impl RawCaptureSink {
+    /// Create (truncating) the first capture file for `game_id` at `path`.
+    pub fn create(
+        game_id: impl Into<String>,
+        path: impl Into<PathBuf>,
+        caps: RawCaptureCaps,
+    ) -> io::Result<Self> {
+        let game_id = game_id.into();
+        if u16::try_from(game_id.len()).is_err() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "game id is too long for a capture header",
+            ));
+        }
+        let path = path.into();
+        let (writer, header_bytes) = open_capture_file(&path, &game_id)?;
+        Ok(Self {
+            state: Mutex::new(SinkState {
+                writer,
+                file_bytes: header_bytes,
+                file_records: 0,
+                next_index: 1,
+                files: VecDeque::from([path.clone()]),
+                stats: RawCaptureStats {
+                    bytes: header_bytes,
+                    ..RawCaptureStats::default()
+                },
+            }),
+            game_id,
+            path,
+            caps,
+        })
+    }
+
+    pub fn game_id(&self) -> &str {
+        &self.game_id
+    }
+
+    pub fn caps(&self) -> RawCaptureCaps {
+        self.caps
+    }
+
+    /// Capture files still on disk, oldest first; the last one is current.
+    pub fn files(&self) -> Vec<PathBuf> {
+        self.lock().files.iter().cloned().collect()
+    }
+
+    pub fn stats(&self) -> RawCaptureStats {
+        self.lock().stats
+    }
+
+    /// Append `payload`, received now from `source`.
+    pub fn record(&self, payload: &[u8], source: Option<SocketAddr>) -> io::Result<()> {
+        self.record_at(telemetry_now_ns(), payload, source)
+    }
+
+    /// Append `payload` with an explicit timestamp.
+    pub fn record_at(
+        &self,
+        timestamp_ns: u64,
+        payload: &[u8],
+        source: Option<SocketAddr>,
+    ) -> io::Result<()> {
+        let record = encode_record(timestamp_ns, payload, source);
+        let record_bytes = record.len() as u64;
+        let mut state = self.lock();
+        let full = state.file_records > 0
+            && (state.file_records >= self.caps.max_records_per_file
+                || state.file_bytes + record_bytes > self.caps.max_file_bytes);
+        let result = if full {
+            self.rotate(&mut state)
+        } else {
+            Ok(())
+        }
+        .and_then(|()| state.writer.write_all(&record));
+        match result {
+            Ok(()) => {
+                state.file_bytes += record_bytes;
+                state.file_records += 1;
+                state.stats.records += 1;
+                state.stats.bytes += record_bytes;
+                Ok(())
+            }
+            Err(error) => {
+                state.stats.write_errors += 1;
+                Err(error)
+            }
+        }
+    }
+
+    /// Write buffered records through to the current file.
+    pub fn flush(&self) -> io::Result<()> {
+        self.lock().writer.flush()
+    }
+
+    fn rotate(&self, state: &mut SinkState) -> io::Result<()> {
+        state.writer.flush()?;
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", state.next_index));
+        let path = PathBuf::from(name);
+        let (writer, header_bytes) = open_capture_file(&path, &self.game_id)?;
+        state.writer = writer;
+        state.next_index += 1;
+        state.file_bytes = header_bytes;
+        state.file_records = 0;
+        state.stats.bytes += header_bytes;
+        state.stats.rotations += 1;
+        state.files.push_back(path);
+        while state.files.len() > self.caps.max_files.max(1) {
+            if let Some(oldest) = state.files.pop_front()
+                && let Err(error) = fs::remove_file(&oldest)
+            {
+                warn!(path = %oldest.display(), error = %error, "Failed to delete old raw capture");
+            }
+        }
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SinkState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Drop for RawCaptureSink {
+    fn drop(&mut self) {
+        if let Err(error) = self.lock().writer.flush() {
+            warn!(game_id = %self.game_id, error = %error, "Failed to flush raw capture");
+        }
+    }
+}
+
+impl fmt::Debug for RawCaptureSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RawCaptureSink")
+            .field("game_id", &self.game_id)
+            .field("path", &self.path)
+            .field("caps", &self.caps)
+            .finish_non_exhaustive()
+    }
+}
+
+fn open_capture_file(path: &Path, game_id: &str) -> io::Result<(BufWriter<File>, u64)> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    let mut header = Vec::with_capacity(12 + game_id.len());
+    header.extend_from_slice(RAW_CAPTURE_MAGIC);
+    header.extend_from_slice(&RAW_CAPTURE_VERSION.to_le_bytes());
+    // Length checked in `RawCaptureSink::create`.
+    header.extend_from_slice(&(game_id.len() as u16).to_le_bytes());
+    header.extend_from_slice(game_id.as_bytes());
+    writer.write_all(&header)?;
+    Ok((writer, header.len() as u64))
+}
+
+fn encode_record(timestamp_ns: u64, payload: &[u8], source: Option<SocketAddr>) -> Vec<u8> {
+    let mut body = Vec::with_capacity(8 + 19 + payload.len());
+    body.extend_from_slice(&timestamp_ns.to_le_bytes());
+    match source {
+        None => body.push(SOURCE_NONE),
+        Some(SocketAddr::V4(addr)) => {
+            body.push(SOURCE_V4);
+            body.extend_from_slice(&addr.ip().octets());
+            body.extend_from_slice(&addr.port().to_le_bytes());
+        }
+        Some(SocketAddr::V6(addr)) => {
+            body.push(SOURCE_V6);
+            body.extend_from_slice(&addr.ip().octets());
+            body.extend_from_slice(&addr.port().to_le_bytes());
+        }
+    }
+    body.extend_from_slice(payload);
+    let mut record = Vec::with_capacity(4 + body.len());
+    record.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    record.extend_from_slice(&body);
+    record
+}
+
+/// The sink an adapter currently captures into, shared with its receive
+/// task so capture can be switched on and off while monitoring.
+///
+/// Every payload passing through the slot is also counted into the adapter's
+/// [`TrafficAccountant`], capturing or not.
+#[derive(Clone, Default)]
+pub struct RawCaptureSlot {
+    sink: Arc<Mutex<Option<Arc<RawCaptureSink>>>>,
+    /// Mirrors whether `sink` is set, so payloads skip the lock while idle.
+    capturing: Arc<AtomicBool>,
+    traffic: Arc<TrafficAccountant>,
+}
+
+impl RawCaptureSlot {
+    /// Capture into `sink` from now on, or stop capturing when `None`.
+    pub fn set(&self, sink: Option<Arc<RawCaptureSink>>) {
+        let mut current = self.sink.lock().unwrap_or_else(PoisonError::into_inner);
+        self.capturing.store(sink.is_some(), Ordering::Release);
+        *current = sink;
+    }
+
+    pub fn sink(&self) -> Option<Arc<RawCaptureSink>> {
+        self.sink
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Packet and byte counts of every payload recorded through this slot.
+    pub fn traffic(&self) -> Arc<TrafficAccountant> {
+        Arc::clone(&self.traffic)
+    }
+
+    /// Count `payload` and record it if capturing. Write failures are logged
+    /// and counted by the sink, never surfaced to the receive loop.
+    pub fn record(&self, payload: &[u8], source: Option<SocketAddr>) {
+        self.traffic.record(payload.len());
+        self.capture(payload, source);
+    }
+
+    /// Record a datagram from a brokered port once the adapter has tried to
+    /// decode it into `decoded`.
+    ///
+    /// Every subscriber of the port sees every datagram, so only one the
+    /// decoder accepted counts as this adapter's traffic. One rejected with
+    /// [`NotMyPacket`] is another game's and is not captured either; other
+    /// decode failures are, since those are what a capture is for.
+    pub fn record_decoded<T>(
+        &self,
+        payload: &[u8],
+        source: Option<SocketAddr>,
+        decoded: &anyhow::Result<T>,
+    ) {
+        match decoded {
+            Ok(_) => self.traffic.record(payload.len()),
+            Err(error) if error.is::<NotMyPacket>() => return,
+            Err(_) => {}
+        }
+        self.capture(payload, source);
+    }
+
+    fn capture(&self, payload: &[u8], source: Option<SocketAddr>) {
+        if !self.capturing.load(Ordering::Acquire) {
+            return;
+        }
+        let Some(sink) = self.sink() else {
+            return;
+        };
+        if let Err(error) = sink.record(payload, source) {
+            warn!(game_id = %sink.game_id(), error = %error, "Failed to record raw packet");
+        }
+    }
+
+    /// Receive one datagram from `socket` into `buf`, recording it if
+    /// capturing, and return its length.
+    pub async fn recv(&self, socket: &UdpSocket, buf: &mut [u8]) -> io::Result<usize> {
+        let (len, source) = socket.recv_from(buf).await?;
+        self.record(&buf[..len], Some(source));
+        Ok(len)
+    }
+}
+
+impl fmt::Debug for RawCaptureSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RawCaptureSlot")
+            .field(&self.sink().map(|sink| sink.game_id().to_string()))
+            .finish()
+    }
+}
+
+/// One captured payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawCaptureRecord {
+    pub timestamp_ns: u64,
+    /// Sender of the datagram, for UDP sources.
+    pub source: Option<SocketAddr>,
+    pub payload: Vec<u8>,
+}
+
+/// Iterates the records of one capture file.
+pub struct RawCaptureReader<R> {
+    reader: R,
+    game_id: String,
+    version: u16,
+    finished: bool,
+}
+
+impl RawCaptureReader<BufReader<File>> {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> RawCaptureReader<R> {
+    /// Read the header from `reader`.
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != RAW_CAPTURE_MAGIC {
+            return Err(invalid_data("not a raw capture file"));
+        }
+        let version = read_u16(&mut reader)?;
+        if version != RAW_CAPTURE_VERSION {
+            return Err(invalid_data(format!(
+                "unsupported raw capture version {version}"
+            )));
+        }
+        let mut game_id = vec![0u8; usize::from(read_u16(&mut reader)?)];
+        reader.read_exact(&mut game_id)?;
+        let game_id =
+            String::from_utf8(game_id).map_err(|_| invalid_data("game id is not UTF-8"))?;
+        Ok(Self {
+            reader,
+            game_id,
+            version,
+            finished: false,
+        })
+    }
+
+    /// Game the capture was taken from.
+    pub fn game_id(&self) -> &str {
+        &self.game_id
+    }
+
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    fn read_record(&mut self) -> io::Result<Option<RawCaptureRecord>> {
+        let mut len = [0u8; 4];
+        match self.reader.read_exact(&mut len) {
+            Ok(()) => {}
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(error) => return Err(error),
+        }
+        let len = u32::from_le_bytes(len) as usize;
+        if len > MAX_RECORD_BYTES {
+            return Err(invalid_data(format!("record of {len} bytes is too large")));
+        }
+        let mut body = vec![0u8; len];
+        self.reader.read_exact(&mut body)?;
+        decode_record(&body).map(Some)
+    }
+}
+
+impl<R: Read> Iterator for RawCaptureReader<R> {
+    type Item = io::Result<RawCaptureRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let record = self.read_record().transpose();
+        if !matches!(record, Some(Ok(_))) {
+            self.finished = true;
+        }
+        record
+    }
+}
+
+fn decode_record(body: &[u8]) -> io::Result<RawCaptureRecord> {
+    let truncated = || invalid_data("truncated raw capture record");
+    let (timestamp, rest) = body.split_first_chunk::<8>().ok_or_else(truncated)?;
+    let (kind, rest) = rest.split_first().ok_or_else(truncated)?;
+    let (source, payload) = match *kind {
+        SOURCE_NONE => (None, rest),
+        SOURCE_V4 => {
+            let (ip, rest) = rest.split_first_chunk::<4>().ok_or_else(truncated)?;
+            let (port, rest) = rest.split_first_chunk::<2>().ok_or_else(truncated)?;
+            let ip = IpAddr::V4(Ipv4Addr::from(*ip));
+            (Some(SocketAddr::new(ip, u16::from_le_bytes(*port))), rest)
+        }
+        SOURCE_V6 => {
+            let (ip, rest) = rest.split_first_chunk::<16>().ok_or_else(truncated)?;
+            let (port, rest) = rest.split_first_chunk::<2>().ok_or_else(truncated)?;
+            let ip = IpAddr::V6(Ipv6Addr::from(*ip));
+            (Some(SocketAddr::new(ip, u16::from_le_bytes(*port))), rest)
+        }
+        other => return Err(invalid_data(format!("unknown source kind {other}"))),
+    };
+    Ok(RawCaptureRecord {
+        timestamp_ns: u64::from_le_bytes(*timestamp),
+        source,
+        payload: payload.to_vec(),
+    })
+}
+
+fn read_u16(reader: &mut impl Read) -> io::Result<u16> {
+    let mut bytes = [0u8; 2];
+    reader.read_exact(&mut bytes)?;
+    Ok(u16::from_le_bytes(bytes))
+}
+
+fn invalid_data(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
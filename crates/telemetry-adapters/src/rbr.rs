@@ -4,6 +4,8 @@
 //! Supports both the 184-byte current packet format and the older 128-byte format.
 #![cfg_attr(not(windows), allow(unused, dead_code))]
 
+use crate::raw_capture::{RawCaptureSink, RawCaptureSlot};
+use crate::traffic::TrafficAccountant;
 use crate::{
     NormalizedTelemetry, TelemetryAdapter, TelemetryFlags, TelemetryFrame, TelemetryReceiver,
     telemetry_now_ns,
@@ -11,6 +13,7 @@ use crate::{
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::net::UdpSocket as TokioUdpSocket;
 use tokio::sync::mpsc;
@@ -98,6 +101,7 @@ fn parse_rbr_packet(data: &[u8]) -> Result<NormalizedTelemetry> {
 pub struct RBRAdapter {
     bind_port: u16,
     update_rate: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for RBRAdapter {
@@ -111,6 +115,7 @@ impl RBRAdapter {
         Self {
             bind_port: DEFAULT_RBR_PORT,
             update_rate: Duration::from_millis(17), // ~60 Hz (game framerate)
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -126,7 +131,12 @@ impl TelemetryAdapter for RBRAdapter {
         "rbr"
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(100);
         let bind_port = self.bind_port;
         let update_rate = self.update_rate;
@@ -145,7 +155,9 @@ impl TelemetryAdapter for RBRAdapter {
             let mut frame_seq = 0u64;
 
             loop {
-                match tokio::time::timeout(update_rate * 10, socket.recv(&mut buf)).await {
+                match tokio::time::timeout(update_rate * 10, raw_capture.recv(&socket, &mut buf))
+                    .await
+                {
                     Ok(Ok(len)) => match parse_rbr_packet(&buf[..len]) {
                         Ok(normalized) => {
                             let frame =
@@ -0,0 +1,32 @@
+//! Packet recognition for explaining unrecognized UDP traffic.
+//!
+//! When datagrams reach a port but nothing decodes, the service asks every
+//! registered adapter how much a captured datagram looks like its wire format.
+//! Adapters with format-year headers, magic bytes or fixed length tables
+//! override [`TelemetryAdapter::recognize_packet`](crate::TelemetryAdapter::recognize_packet);
+//! the rest fall back to "it decodes" at [`DECODE_CONFIDENCE`].
+
+use serde::{Deserialize, Serialize};
+
+/// Confidence for a datagram an adapter decodes without a format-specific
+/// check: the bytes were long enough and parsed, nothing more.
+pub const DECODE_CONFIDENCE: f32 = 0.5;
+
+/// One adapter's opinion of one datagram.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PacketMatch {
+    /// Likelihood the datagram is in this adapter's format, `0.0..=1.0`.
+    pub confidence: f32,
+    /// Format the datagram was recognized as, e.g. "F1 2024 format".
+    pub format: String,
+}
+
+impl PacketMatch {
+    /// Match with `confidence` clamped to `0.0..=1.0`.
+    pub fn new(confidence: f32, format: impl Into<String>) -> Self {
+        Self {
+            confidence: confidence.clamp(0.0, 1.0),
+            format: format.into(),
+        }
+    }
+}
@@ -17,6 +17,7 @@
 //!
 //! Update rate: 60 Hz.
 
+use crate::raw_capture::RawCaptureSlot;
 use crate::{
     NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver, telemetry_now_ns,
 };
@@ -89,6 +90,7 @@ pub fn parse_rennsport_packet(data: &[u8]) -> Result<NormalizedTelemetry> {
 pub struct RennsportAdapter {
     bind_port: u16,
     update_rate: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for RennsportAdapter {
@@ -102,6 +104,7 @@ impl RennsportAdapter {
         Self {
             bind_port: DEFAULT_RENNSPORT_PORT,
             update_rate: Duration::from_millis(16), // ~60 Hz
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -117,7 +120,12 @@ impl TelemetryAdapter for RennsportAdapter {
         "rennsport"
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(100);
         let bind_port = self.bind_port;
         let update_rate = self.update_rate;
@@ -136,7 +144,9 @@ impl TelemetryAdapter for RennsportAdapter {
             let mut frame_seq = 0u64;
 
             loop {
-                match tokio::time::timeout(update_rate * 10, socket.recv(&mut buf)).await {
+                match tokio::time::timeout(update_rate * 10, raw_capture.recv(&socket, &mut buf))
+                    .await
+                {
                     Ok(Ok(len)) => match parse_rennsport_packet(&buf[..len]) {
                         Ok(normalized) => {
                             let frame =
@@ -27,6 +27,7 @@
 //! Speed is derived as `sqrt(vel_x² + vel_y² + vel_z²)`.
 //! Deeper fields are read only when the received packet is long enough.
 
+use crate::raw_capture::RawCaptureSlot;
 use crate::{
     NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver, telemetry_now_ns,
 };
@@ -160,6 +161,7 @@ pub struct RFactor1Adapter {
     variant: RFactor1Variant,
     bind_port: u16,
     update_rate: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl RFactor1Adapter {
@@ -174,6 +176,7 @@ impl RFactor1Adapter {
             variant,
             bind_port: DEFAULT_RF1_PORT,
             update_rate: Duration::from_millis(16), // ~60 Hz
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -196,7 +199,12 @@ impl TelemetryAdapter for RFactor1Adapter {
         self.variant.game_id()
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(100);
         let bind_port = self.bind_port;
         let update_rate = self.update_rate;
@@ -216,7 +224,9 @@ impl TelemetryAdapter for RFactor1Adapter {
             let mut frame_seq = 0u64;
 
             loop {
-                match tokio::time::timeout(update_rate * 10, socket.recv(&mut buf)).await {
+                match tokio::time::timeout(update_rate * 10, raw_capture.recv(&socket, &mut buf))
+                    .await
+                {
                     Ok(Ok(len)) => match parse_rfactor1_packet(&buf[..len]) {
                         Ok(normalized) => {
                             let frame =
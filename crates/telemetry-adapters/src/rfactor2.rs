@@ -41,8 +41,8 @@
 #![cfg_attr(not(windows), allow(unused, dead_code))]
 
 use crate::{
-    NormalizedTelemetry, TelemetryAdapter, TelemetryFlags, TelemetryFrame, TelemetryReceiver,
-    TelemetryValue, telemetry_now_ns,
+    FieldUnit, NormalizedTelemetry, PenaltyKind, PenaltyState, TelemetryAdapter, TelemetryFlags,
+    TelemetryFrame, TelemetryReceiver, TelemetryValue, Unit, UnitManifest, telemetry_now_ns,
 };
 use anyhow::Result;
 use async_trait::async_trait;
@@ -383,6 +383,7 @@ impl RFactor2Adapter {
         } else {
             TelemetryFlags::default()
         };
+        let penalties = scoring.map(extract_penalties);
 
         // Calculate slip ratio from wheel data
         let slip_ratio = self.calculate_slip_ratio(vehicle);
@@ -406,7 +407,7 @@ impl RFactor2Adapter {
         };
         let ffb_scalar = derive_ffb_scalar(ffb_raw);
 
-        NormalizedTelemetry::builder()
+        let mut builder = NormalizedTelemetry::builder()
             .ffb_scalar(ffb_scalar)
             .rpm(vehicle.engine_rpm as f32)
             .speed_ms(speed as f32)
@@ -447,8 +448,16 @@ impl RFactor2Adapter {
             .extended(
                 "ffb_source".to_string(),
                 TelemetryValue::String(ffb_source.to_string()),
-            )
-            .build()
+            );
+        // mElapsedTime is session time; an unwritten buffer reads zero.
+        if vehicle.elapsed_time > 0.0 {
+            builder = builder.game_time_s(vehicle.elapsed_time);
+        }
+
+        match penalties {
+            Some(penalties) => builder.penalties(penalties).build(),
+            None => builder.build(),
+        }
     }
 
     /// Extract flags from scoring data.
@@ -476,6 +485,7 @@ impl RFactor2Adapter {
             abs_active: false,
             engine_limiter: false,
             safety_car: false,
+            virtual_safety_car: false,
             formation_lap: false,
             session_paused: false,
         }
@@ -500,12 +510,25 @@ impl RFactor2Adapter {
     }
 }
 
+/// rFactor 2 shared-memory units; speed is the magnitude of the local velocity.
+const UNIT_MANIFEST: UnitManifest = UnitManifest::new(&[
+    FieldUnit::native("speed_ms", Unit::MetersPerSecond),
+    FieldUnit::native("rpm", Unit::Rpm),
+    FieldUnit::native("slip_ratio", Unit::Fraction),
+    FieldUnit::native("ffb_scalar", Unit::Fraction),
+    FieldUnit::native("game_time_s", Unit::Seconds),
+]);
+
 #[async_trait]
 impl TelemetryAdapter for RFactor2Adapter {
     fn game_id(&self) -> &str {
         "rfactor2"
     }
 
+    fn unit_manifest(&self) -> Option<UnitManifest> {
+        Some(UNIT_MANIFEST)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
         let (tx, rx) = mpsc::channel(100);
         let update_rate = self.update_rate;
@@ -616,12 +639,39 @@ impl TelemetryAdapter for RFactor2Adapter {
         self.update_rate
     }
 
+    fn prefers_dedup(&self) -> bool {
+        true
+    }
+
     async fn is_game_running(&self) -> Result<bool> {
         Ok(self.check_rf2_running().await)
     }
 }
 
 /// Extract null-terminated string from byte array
+/// `rF2FinishStatus` value for a disqualified vehicle.
+const RF2_FINISH_STATUS_DQ: i32 = 3;
+
+/// Map player scoring penalty fields onto [`PenaltyState`].
+///
+/// rF2 scoring exposes only a count of outstanding penalties (their type is not
+/// reported) and no track-limits counter.
+fn extract_penalties(scoring: &RF2ScoringHeader) -> PenaltyState {
+    let dsq = scoring.finish_status == RF2_FINISH_STATUS_DQ;
+    let active_penalty_kind = if dsq {
+        PenaltyKind::Disqualified
+    } else if scoring.num_penalties > 0 {
+        PenaltyKind::Other
+    } else {
+        PenaltyKind::None
+    };
+    PenaltyState {
+        active_penalty_kind,
+        dsq,
+        ..PenaltyState::default()
+    }
+}
+
 fn extract_string(bytes: &[u8]) -> String {
     match bytes.iter().position(|&b| b == 0) {
         Some(pos) => String::from_utf8_lossy(&bytes[..pos]).into_owned(),
@@ -1007,6 +1057,12 @@ pub struct RF2ScoringHeader {
     pub yellow_flag_state: i32,
     /// In-pits flag.  **Not at a valid shared-memory offset.**
     pub in_pits: i32,
+    /// Player `mNumPenalties` (outstanding penalties).  **Not at a valid
+    /// shared-memory offset.**
+    pub num_penalties: i32,
+    /// Player `mFinishStatus` (0=none, 1=finished, 2=DNF, 3=DQ).  **Not at a
+    /// valid shared-memory offset.**
+    pub finish_status: i32,
 }
 
 /// rFactor 2 force-feedback shared-memory block.
@@ -1148,6 +1204,21 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_normalize_reports_elapsed_time_as_game_clock() -> TestResult {
+        let adapter = RFactor2Adapter::new();
+        let vehicle = RF2VehicleTelemetry {
+            elapsed_time: 954.125,
+            ..Default::default()
+        };
+        let normalized = adapter.normalize_rf2_data(&vehicle, None, None);
+        assert_eq!(normalized.game_time_s, Some(954.125));
+
+        let unwritten = adapter.normalize_rf2_data(&RF2VehicleTelemetry::default(), None, None);
+        assert_eq!(unwritten.game_time_s, None);
+        Ok(())
+    }
+
     #[test]
     fn test_normalize_with_flags() -> TestResult {
         let adapter = RFactor2Adapter::new();
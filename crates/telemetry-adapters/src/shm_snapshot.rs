@@ -0,0 +1,523 @@
+//! Raw shared-memory snapshots for bug reports on SHM-based games.
+//!
+//! A [`ShmSnapshot`] holds byte-exact copies of the pages an adapter reads
+//! (telemetry, scoring, session, ...) together with the adapter identity,
+//! the capture time and what triggered it. Maintainers feed a user's snapshot
+//! back through [`replay_shm_snapshot`] to reproduce the exact decode.
+//!
+//! Pages may contain personal data such as driver names in the iRacing
+//! session string. Every capture applies the game's [`known_redactions`]
+//! plus any caller-supplied [`ShmRedaction`]s, overwriting the affected bytes
+//! without changing page sizes so offsets still line up on replay.
+//!
+//! # Container format
+//!
+//! `b"ORSH"`, a `u16` LE format version, a `u32` LE header length, the JSON
+//! header, then every page's bytes in header order.
+
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Result, anyhow};
+use openracing_byte_reader::ByteReader;
+use serde::{Deserialize, Serialize};
+
+use crate::{NormalizedTelemetry, TelemetryAdapter};
+
+/// Magic bytes opening a serialized [`ShmSnapshot`].
+pub const SHM_SNAPSHOT_MAGIC: [u8; 4] = *b"ORSH";
+/// Current container format version.
+pub const SHM_SNAPSHOT_VERSION: u16 = 1;
+
+/// Byte used to overwrite redacted session-string values.
+const REDACTED_TEXT_BYTE: u8 = b'*';
+
+/// iRacing session-string keys that identify people.
+const IRACING_PERSONAL_KEYS: &[&str] = &[
+    "UserName",
+    "AbbrevName",
+    "Initials",
+    "TeamName",
+    "UserID",
+    "TeamID",
+    "CarNumberRaw",
+    "ClubName",
+];
+
+/// One named shared-memory page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShmPage {
+    /// Page name, e.g. `"telemetry"` or `"scoring"`.
+    pub name: String,
+    /// Raw page contents.
+    pub bytes: Vec<u8>,
+}
+
+impl ShmPage {
+    /// A page named `name` holding `bytes`.
+    pub fn new(name: impl Into<String>, bytes: impl Into<Vec<u8>>) -> Self {
+        Self {
+            name: name.into(),
+            bytes: bytes.into(),
+        }
+    }
+}
+
+/// Provides the current contents of an adapter's mapped pages.
+///
+/// The first page returned is the one the adapter's `normalize` decodes.
+pub trait ShmPageSource: Send + Sync {
+    /// Copy every mapped page.
+    fn read_pages(&self) -> Result<Vec<ShmPage>>;
+}
+
+/// In-memory [`ShmPageSource`] for tests and offline tooling.
+#[derive(Debug, Default)]
+pub struct FakeShmSource {
+    pages: Mutex<Vec<ShmPage>>,
+}
+
+impl FakeShmSource {
+    /// A source serving `pages`.
+    pub fn new(pages: Vec<ShmPage>) -> Self {
+        Self {
+            pages: Mutex::new(pages),
+        }
+    }
+
+    /// Replace the contents of page `name`, adding it if missing.
+    pub fn set_page(&self, name: &str, bytes: impl Into<Vec<u8>>) {
+        let mut pages = self.pages.lock().unwrap_or_else(PoisonError::into_inner);
+        let bytes = bytes.into();
+        match pages.iter_mut().find(|page| page.name == name) {
+            Some(page) => page.bytes = bytes,
+            None => pages.push(ShmPage::new(name, bytes)),
+        }
+    }
+}
+
+impl ShmPageSource for FakeShmSource {
+    fn read_pages(&self) -> Result<Vec<ShmPage>> {
+        Ok(self
+            .pages
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone())
+    }
+}
+
+/// What the redaction pass overwrites.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RedactionTarget {
+    /// Zero `len` bytes starting at `offset`.
+    Bytes {
+        /// Start offset within the page.
+        offset: usize,
+        /// Number of bytes to clear.
+        len: usize,
+    },
+    /// Mask the value of every `key: value` line in a YAML session string.
+    YamlKey {
+        /// Key whose values are masked.
+        key: String,
+    },
+}
+
+/// A field of a page that is cleared before a snapshot leaves the machine.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShmRedaction {
+    /// Page the field lives in.
+    pub page: String,
+    /// Human-readable field name, recorded in the snapshot.
+    pub field: String,
+    /// Bytes overwritten.
+    pub target: RedactionTarget,
+}
+
+impl ShmRedaction {
+    /// Zero `len` bytes at `offset` of `page`.
+    pub fn bytes(page: &str, field: &str, offset: usize, len: usize) -> Self {
+        Self {
+            page: page.to_string(),
+            field: field.to_string(),
+            target: RedactionTarget::Bytes { offset, len },
+        }
+    }
+
+    /// Mask the values of `key` in the YAML text stored in `page`.
+    pub fn yaml_key(page: &str, key: &str) -> Self {
+        Self {
+            page: page.to_string(),
+            field: key.to_string(),
+            target: RedactionTarget::YamlKey {
+                key: key.to_string(),
+            },
+        }
+    }
+
+    /// Apply to `page` if it is the page this redaction targets, returning
+    /// whether any byte was overwritten.
+    fn apply(&self, page: &mut ShmPage) -> bool {
+        if page.name != self.page {
+            return false;
+        }
+        match &self.target {
+            RedactionTarget::Bytes { offset, len } => {
+                let end = offset.saturating_add(*len).min(page.bytes.len());
+                let Some(field) = page.bytes.get_mut(*offset..end) else {
+                    return false;
+                };
+                field.fill(0);
+                !field.is_empty()
+            }
+            RedactionTarget::YamlKey { key } => mask_yaml_values(&mut page.bytes, key),
+        }
+    }
+}
+
+/// Overwrite the value of each `key:` line in `text` in place, keeping its length.
+fn mask_yaml_values(text: &mut [u8], key: &str) -> bool {
+    let key = key.as_bytes();
+    let mut masked = false;
+    for line in text.split_mut(|&byte| byte == b'\n') {
+        let mut start = line.iter().take_while(|&&byte| byte == b' ').count();
+        if line
+            .get(start..)
+            .is_some_and(|rest| rest.starts_with(b"- "))
+        {
+            start += 2;
+        }
+        let Some(rest) = line.get_mut(start..) else {
+            continue;
+        };
+        if !rest.starts_with(key) || rest.get(key.len()) != Some(&b':') {
+            continue;
+        }
+        let value = &mut rest[key.len() + 1..];
+        for byte in value
+            .iter_mut()
+            .filter(|byte| !matches!(byte, b' ' | b'\r' | 0))
+        {
+            *byte = REDACTED_TEXT_BYTE;
+            masked = true;
+        }
+    }
+    masked
+}
+
+/// Redactions every snapshot of `game_id` receives.
+pub fn known_redactions(game_id: &str) -> Vec<ShmRedaction> {
+    match game_id {
+        "iracing" => IRACING_PERSONAL_KEYS
+            .iter()
+            .map(|key| ShmRedaction::yaml_key("session", key))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Why a snapshot was taken.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ShmCaptureTrigger {
+    /// Requested explicitly, e.g. from a bug-report command.
+    Manual,
+    /// The adapter's `normalize` returned an error.
+    NormalizeFailed {
+        /// The error message.
+        error: String,
+    },
+    /// `normalize` succeeded but the frame failed validation.
+    ValidatorRejected {
+        /// The validator's reason.
+        reason: String,
+    },
+}
+
+/// Adapter that owned the mapped pages.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShmAdapterInfo {
+    /// Adapter game id.
+    pub game_id: String,
+    /// The adapter's nominal update interval in microseconds.
+    pub expected_update_interval_us: u64,
+    /// Version of the adapters crate that captured the snapshot.
+    pub crate_version: String,
+}
+
+/// Size of one page in a snapshot header.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ShmPageInfo {
+    name: String,
+    size: usize,
+}
+
+/// JSON header of the serialized container.
+#[derive(Debug, Serialize, Deserialize)]
+struct ShmSnapshotHeader {
+    adapter: ShmAdapterInfo,
+    captured_at_unix_ms: u64,
+    trigger: ShmCaptureTrigger,
+    redacted: Vec<ShmRedaction>,
+    pages: Vec<ShmPageInfo>,
+}
+
+/// Copies of an adapter's mapped pages at one instant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShmSnapshot {
+    /// Adapter the pages belong to.
+    pub adapter: ShmAdapterInfo,
+    /// Wall-clock capture time, milliseconds since the Unix epoch.
+    pub captured_at_unix_ms: u64,
+    /// What caused the capture.
+    pub trigger: ShmCaptureTrigger,
+    /// Redactions that overwrote at least one byte.
+    pub redacted: Vec<ShmRedaction>,
+    /// The pages, decode page first.
+    pub pages: Vec<ShmPage>,
+}
+
+impl ShmSnapshot {
+    /// Page named `name`, if captured.
+    pub fn page(&self, name: &str) -> Option<&ShmPage> {
+        self.pages.iter().find(|page| page.name == name)
+    }
+
+    /// Encode in the versioned container format.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let header = ShmSnapshotHeader {
+            adapter: self.adapter.clone(),
+            captured_at_unix_ms: self.captured_at_unix_ms,
+            trigger: self.trigger.clone(),
+            redacted: self.redacted.clone(),
+            pages: self
+                .pages
+                .iter()
+                .map(|page| ShmPageInfo {
+                    name: page.name.clone(),
+                    size: page.bytes.len(),
+                })
+                .collect(),
+        };
+        let header = serde_json::to_vec(&header)?;
+        let header_len = u32::try_from(header.len())?;
+        let payload: usize = self.pages.iter().map(|page| page.bytes.len()).sum();
+
+        let mut out = Vec::with_capacity(10 + header.len() + payload);
+        out.extend_from_slice(&SHM_SNAPSHOT_MAGIC);
+        out.extend_from_slice(&SHM_SNAPSHOT_VERSION.to_le_bytes());
+        out.extend_from_slice(&header_len.to_le_bytes());
+        out.extend_from_slice(&header);
+        for page in &self.pages {
+            out.extend_from_slice(&page.bytes);
+        }
+        Ok(out)
+    }
+
+    /// Decode a container produced by [`Self::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = ByteReader::new(bytes);
+        if reader.array::<4>()? != SHM_SNAPSHOT_MAGIC {
+            return Err(anyhow!("not a shared-memory snapshot"));
+        }
+        let version = reader.u16_le()?;
+        if version != SHM_SNAPSHOT_VERSION {
+            return Err(anyhow!(
+                "unsupported shared-memory snapshot version {version}"
+            ));
+        }
+        let header_len = usize::try_from(reader.u32_le()?)?;
+        let header: ShmSnapshotHeader = serde_json::from_slice(reader.bytes(header_len)?)?;
+        let pages = header
+            .pages
+            .into_iter()
+            .map(|info| Ok(ShmPage::new(info.name, reader.bytes(info.size)?)))
+            .collect::<Result<Vec<_>>>()?;
+        if !reader.is_empty() {
+            return Err(anyhow!(
+                "{} trailing bytes after the last page",
+                reader.remaining()
+            ));
+        }
+        Ok(Self {
+            adapter: header.adapter,
+            captured_at_unix_ms: header.captured_at_unix_ms,
+            trigger: header.trigger,
+            redacted: header.redacted,
+            pages,
+        })
+    }
+}
+
+/// Copy `source`'s pages into a redacted snapshot attributed to `adapter`.
+///
+/// `redactions` are applied on top of [`known_redactions`] for the adapter's game.
+pub fn capture_shm_snapshot(
+    adapter: &dyn TelemetryAdapter,
+    source: &dyn ShmPageSource,
+    trigger: ShmCaptureTrigger,
+    redactions: &[ShmRedaction],
+) -> Result<ShmSnapshot> {
+    let pages = source.read_pages()?;
+    Ok(snapshot_from_pages(adapter, pages, trigger, redactions))
+}
+
+fn snapshot_from_pages(
+    adapter: &dyn TelemetryAdapter,
+    mut pages: Vec<ShmPage>,
+    trigger: ShmCaptureTrigger,
+    redactions: &[ShmRedaction],
+) -> ShmSnapshot {
+    let mut redacted = Vec::new();
+    for redaction in known_redactions(adapter.game_id()).iter().chain(redactions) {
+        let mut hit = false;
+        for page in &mut pages {
+            hit |= redaction.apply(page);
+        }
+        if hit {
+            redacted.push(redaction.clone());
+        }
+    }
+    let captured_at_unix_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX))
+        .unwrap_or(0);
+    ShmSnapshot {
+        adapter: ShmAdapterInfo {
+            game_id: adapter.game_id().to_string(),
+            expected_update_interval_us: u64::try_from(adapter.expected_update_rate().as_micros())
+                .unwrap_or(u64::MAX),
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+        },
+        captured_at_unix_ms,
+        trigger,
+        redacted,
+        pages,
+    }
+}
+
+/// Decode `snapshot`'s first page with `adapter`, as the live loop did.
+pub fn replay_shm_snapshot(
+    snapshot: &ShmSnapshot,
+    adapter: &dyn TelemetryAdapter,
+) -> Result<NormalizedTelemetry> {
+    if snapshot.adapter.game_id != adapter.game_id() {
+        return Err(anyhow!(
+            "snapshot was captured from {}, not {}",
+            snapshot.adapter.game_id,
+            adapter.game_id()
+        ));
+    }
+    let page = snapshot
+        .pages
+        .first()
+        .ok_or_else(|| anyhow!("snapshot has no pages"))?;
+    adapter.normalize(&page.bytes)
+}
+
+/// Result of [`ShmFailureCapture::decode`].
+#[derive(Debug)]
+pub struct ShmDecode {
+    /// The decoded frame, or why there is none.
+    pub telemetry: Result<NormalizedTelemetry>,
+    /// Snapshot taken because the decode failed, if the rate window allowed it.
+    pub snapshot: Option<ShmSnapshot>,
+}
+
+/// Decodes pages and snapshots them automatically when decoding fails.
+///
+/// At most one snapshot is taken per `window` so a persistently broken
+/// layout cannot fill the disk; later failures in the same window are counted
+/// in [`Self::suppressed`].
+#[derive(Debug)]
+pub struct ShmFailureCapture {
+    window: Duration,
+    redactions: Vec<ShmRedaction>,
+    last_capture: Option<Instant>,
+    suppressed: u64,
+}
+
+impl ShmFailureCapture {
+    /// Capture at most once per `window`.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            redactions: Vec::new(),
+            last_capture: None,
+            suppressed: 0,
+        }
+    }
+
+    /// Redact these fields in addition to the game's [`known_redactions`].
+    pub fn with_redactions(mut self, redactions: Vec<ShmRedaction>) -> Self {
+        self.redactions = redactions;
+        self
+    }
+
+    /// Failures that were not captured because of the rate window.
+    pub fn suppressed(&self) -> u64 {
+        self.suppressed
+    }
+
+    /// Read `source`, normalize its first page with `adapter` and check the
+    /// frame with `validate`, snapshotting the pages at `now` on failure.
+    pub fn decode(
+        &mut self,
+        adapter: &dyn TelemetryAdapter,
+        source: &dyn ShmPageSource,
+        validate: impl FnOnce(&NormalizedTelemetry) -> std::result::Result<(), String>,
+        now: Instant,
+    ) -> ShmDecode {
+        let pages = match source.read_pages() {
+            Ok(pages) => pages,
+            Err(error) => {
+                return ShmDecode {
+                    telemetry: Err(error),
+                    snapshot: None,
+                };
+            }
+        };
+        let decoded = match pages.first() {
+            Some(page) => adapter.normalize(&page.bytes),
+            None => Err(anyhow!("no shared-memory pages mapped")),
+        };
+        let (telemetry, trigger) = match decoded {
+            Ok(telemetry) => match validate(&telemetry) {
+                Ok(()) => (Ok(telemetry), None),
+                Err(reason) => (
+                    Err(anyhow!("frame rejected: {reason}")),
+                    Some(ShmCaptureTrigger::ValidatorRejected { reason }),
+                ),
+            },
+            Err(error) => {
+                let trigger = ShmCaptureTrigger::NormalizeFailed {
+                    error: error.to_string(),
+                };
+                (Err(error), Some(trigger))
+            }
+        };
+        let snapshot = trigger.and_then(|trigger| {
+            if !self.window_open(now) {
+                self.suppressed += 1;
+                return None;
+            }
+            self.last_capture = Some(now);
+            Some(snapshot_from_pages(
+                adapter,
+                pages,
+                trigger,
+                &self.redactions,
+            ))
+        });
+        ShmDecode {
+            telemetry,
+            snapshot,
+        }
+    }
+
+    fn window_open(&self, now: Instant) -> bool {
+        self.last_capture
+            .is_none_or(|last| now.saturating_duration_since(last) >= self.window)
+    }
+}
@@ -37,6 +37,7 @@
 //!
 //! Update rate: ~60 Hz.
 
+use crate::raw_capture::RawCaptureSlot;
 use crate::{
     NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver, telemetry_now_ns,
 };
@@ -173,6 +174,7 @@ pub fn parse_simhub_packet(data: &[u8]) -> Result<NormalizedTelemetry> {
 pub struct SimHubAdapter {
     bind_port: u16,
     update_rate: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl SimHubAdapter {
@@ -180,6 +182,7 @@ impl SimHubAdapter {
         Self {
             bind_port: SIMHUB_PORT,
             update_rate: Duration::from_millis(16), // ~60 Hz
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 }
@@ -196,7 +199,12 @@ impl TelemetryAdapter for SimHubAdapter {
         "simhub"
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(64);
         let bind_port = self.bind_port;
         let update_rate = self.update_rate;
@@ -215,7 +223,9 @@ impl TelemetryAdapter for SimHubAdapter {
             let mut frame_idx = 0u64;
 
             loop {
-                match tokio::time::timeout(update_rate * 10, socket.recv(&mut buf)).await {
+                match tokio::time::timeout(update_rate * 10, raw_capture.recv(&socket, &mut buf))
+                    .await
+                {
                     Ok(Ok(len)) => match parse_simhub_packet(&buf[..len]) {
                         Ok(normalized) => {
                             let frame =
@@ -27,6 +27,7 @@
 //!
 //! Update rate: typically 60 Hz from the OpenPlanet bridge.
 
+use crate::raw_capture::RawCaptureSlot;
 use crate::{
     NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver, telemetry_now_ns,
 };
@@ -103,6 +104,7 @@ pub fn parse_trackmania_packet(data: &[u8]) -> Result<NormalizedTelemetry> {
 pub struct TrackmaniAdapter {
     bind_port: u16,
     update_rate: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for TrackmaniAdapter {
@@ -121,6 +123,7 @@ impl TrackmaniAdapter {
         Self {
             bind_port,
             update_rate: Duration::from_millis(16),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -139,7 +142,12 @@ impl TelemetryAdapter for TrackmaniAdapter {
         "trackmania"
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(100);
         let bind_port = self.bind_port;
         let update_rate = self.update_rate;
@@ -158,7 +166,9 @@ impl TelemetryAdapter for TrackmaniAdapter {
             let mut frame_seq = 0u64;
 
             loop {
-                match tokio::time::timeout(update_rate * 10, socket.recv(&mut buf)).await {
+                match tokio::time::timeout(update_rate * 10, raw_capture.recv(&socket, &mut buf))
+                    .await
+                {
                     Ok(Ok(len)) => match parse_trackmania_packet(&buf[..len]) {
                         Ok(normalized) => {
                             let frame =
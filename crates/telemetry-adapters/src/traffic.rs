@@ -0,0 +1,179 @@
+//! Packet and byte rates of one telemetry source, for bandwidth budgeting.
+//!
+//! On a low-power host the traffic a game sends is a real share of the CPU
+//! budget. Every datagram an adapter receives is counted into its
+//! [`TrafficAccountant`] with a handful of relaxed atomic operations, so the
+//! receive loop never waits on a lock. Counts land in one bucket per second of
+//! a fixed ring, and [`TrafficAccountant::stats`] sums the last 1, 10 and 60
+//! complete seconds into rates alongside the running totals.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+use crate::telemetry_now_ns;
+
+const NS_PER_SECOND: u64 = 1_000_000_000;
+/// One bucket per second, covering the longest window plus the second in
+/// progress.
+const BUCKETS: usize = 64;
+/// Tag of a bucket that has never counted anything.
+const EMPTY: u64 = u64::MAX;
+
+/// Packet and byte rate over one window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrafficRate {
+    pub packets_per_second: f64,
+    pub bytes_per_second: f64,
+}
+
+/// Totals and windowed rates of one source.
+///
+/// Rates cover complete seconds only, and a window longer than the source has
+/// been counting is averaged over the seconds it has seen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrafficStats {
+    pub total_packets: u64,
+    pub total_bytes: u64,
+    pub last_1s: TrafficRate,
+    pub last_10s: TrafficRate,
+    pub last_60s: TrafficRate,
+}
+
+struct Bucket {
+    second: AtomicU64,
+    packets: AtomicU64,
+    bytes: AtomicU64,
+}
+
+impl Bucket {
+    fn new() -> Self {
+        Self {
+            second: AtomicU64::new(EMPTY),
+            packets: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+        }
+    }
+}
+
+/// Wait-free packet and byte counter of one telemetry source.
+///
+/// Totals are exact under any number of concurrent writers. A packet that
+/// races the first packet of a new second into the same bucket can be lost
+/// from the windowed rates, never from the totals.
+pub struct TrafficAccountant {
+    buckets: [Bucket; BUCKETS],
+    total_packets: AtomicU64,
+    total_bytes: AtomicU64,
+    first_second: AtomicU64,
+}
+
+impl Default for TrafficAccountant {
+    fn default() -> Self {
+        Self {
+            buckets: std::array::from_fn(|_| Bucket::new()),
+            total_packets: AtomicU64::new(0),
+            total_bytes: AtomicU64::new(0),
+            first_second: AtomicU64::new(EMPTY),
+        }
+    }
+}
+
+impl TrafficAccountant {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count one packet of `bytes` received now.
+    pub fn record(&self, bytes: usize) {
+        self.record_at(telemetry_now_ns(), bytes);
+    }
+
+    /// Count one packet of `bytes` received at `timestamp_ns`.
+    pub fn record_at(&self, timestamp_ns: u64, bytes: usize) {
+        let bytes = bytes as u64;
+        self.total_packets.fetch_add(1, Ordering::Relaxed);
+        self.total_bytes.fetch_add(bytes, Ordering::Relaxed);
+
+        let second = timestamp_ns / NS_PER_SECOND;
+        self.first_second.fetch_min(second, Ordering::Relaxed);
+        let bucket = &self.buckets[(second % BUCKETS as u64) as usize];
+        let tagged = bucket.second.load(Ordering::Acquire);
+        if tagged != second {
+            // A bucket already holding a later second means this packet is
+            // a full ring old; it stays in the totals only.
+            if tagged != EMPTY && tagged > second {
+                return;
+            }
+            if bucket
+                .second
+                .compare_exchange(tagged, second, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                bucket.packets.store(0, Ordering::Relaxed);
+                bucket.bytes.store(0, Ordering::Relaxed);
+            }
+        }
+        bucket.packets.fetch_add(1, Ordering::Relaxed);
+        bucket.bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Totals and rates as of now.
+    pub fn stats(&self) -> TrafficStats {
+        self.stats_at(telemetry_now_ns())
+    }
+
+    /// Totals and rates as of `now_ns`.
+    pub fn stats_at(&self, now_ns: u64) -> TrafficStats {
+        let now = now_ns / NS_PER_SECOND;
+        let first = self.first_second.load(Ordering::Relaxed);
+        let seen = if first == EMPTY {
+            0
+        } else {
+            now.saturating_sub(first)
+        };
+
+        let mut sums = [(0u64, 0u64); 3];
+        for bucket in &self.buckets {
+            let second = bucket.second.load(Ordering::Acquire);
+            if second == EMPTY || second >= now {
+                continue;
+            }
+            let age = now - second;
+            let packets = bucket.packets.load(Ordering::Relaxed);
+            let bytes = bucket.bytes.load(Ordering::Relaxed);
+            for (sum, window) in sums.iter_mut().zip([1, 10, 60]) {
+                if age <= window {
+                    sum.0 += packets;
+                    sum.1 += bytes;
+                }
+            }
+        }
+        let rate = |(packets, bytes): (u64, u64), window: u64| {
+            let seconds = window.min(seen);
+            if seconds == 0 {
+                return TrafficRate::default();
+            }
+            TrafficRate {
+                packets_per_second: packets as f64 / seconds as f64,
+                bytes_per_second: bytes as f64 / seconds as f64,
+            }
+        };
+        TrafficStats {
+            total_packets: self.total_packets.load(Ordering::Relaxed),
+            total_bytes: self.total_bytes.load(Ordering::Relaxed),
+            last_1s: rate(sums[0], 1),
+            last_10s: rate(sums[1], 10),
+            last_60s: rate(sums[2], 60),
+        }
+    }
+}
+
+impl std::fmt::Debug for TrafficAccountant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TrafficAccountant")
+            .field("total_packets", &self.total_packets.load(Ordering::Relaxed))
+            .field("total_bytes", &self.total_bytes.load(Ordering::Relaxed))
+            .finish()
+    }
+}
@@ -0,0 +1,535 @@
+//! One UDP socket per port, shared by every adapter that listens on it.
+//!
+//! F1, DiRT Rally 2.0, DiRT 4, DiRT 5 and WRC Generations all default to port
+//! 20777, so auto-detection that starts several of them at once used to fail
+//! with "address in use" on the second bind. [`UdpPortBroker`] binds each port
+//! once and copies every datagram into a bounded queue per subscriber. Each
+//! adapter decodes its copy and reports whether the packet was its protocol;
+//! a decoder that recognises foreign traffic returns [`NotMyPacket`], and the
+//! broker tallies the verdicts in [`SubscriberStats`].
+//!
+//! The socket is released when the last subscriber for its port leaves.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::udp_listener::{AdapterNetworkConfig, ListenMode};
+
+/// Datagrams queued per subscriber before new ones are dropped for it.
+pub const SUBSCRIBER_QUEUE_DEPTH: usize = 256;
+
+/// Large enough for every protocol that shares a brokered port; F1 custom
+/// UDP is the biggest at up to 4 KiB.
+const MAX_DATAGRAM_BYTES: usize = 4096;
+
+/// Error an adapter's `normalize` returns for a datagram that belongs to a
+/// different protocol sharing the port.
+///
+/// It displays as `reason` alone, so wrapping an existing message keeps the
+/// text callers already see.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotMyPacket {
+    /// Adapter that rejected the datagram.
+    pub adapter: String,
+    /// Why the datagram was not recognised.
+    pub reason: String,
+}
+
+impl NotMyPacket {
+    pub fn new(adapter: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self {
+            adapter: adapter.into(),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for NotMyPacket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.reason)
+    }
+}
+
+impl std::error::Error for NotMyPacket {}
+
+/// A datagram as received on a brokered port.
+#[derive(Debug, Clone)]
+pub struct Datagram {
+    /// Sender of the datagram.
+    pub source: SocketAddr,
+    /// Payload, shared by every subscriber that received it.
+    pub payload: Arc<[u8]>,
+}
+
+/// Counters for one subscriber of a brokered port.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubscriberStats {
+    /// Name given when subscribing, normally the adapter's game id.
+    pub name: String,
+    /// Datagrams queued for the subscriber.
+    pub delivered: u64,
+    /// Datagrams dropped because the subscriber's queue was full.
+    pub dropped: u64,
+    /// Datagrams the subscriber decoded as its own protocol.
+    pub matched: u64,
+    /// Datagrams the subscriber rejected with [`NotMyPacket`].
+    pub not_mine: u64,
+    /// Datagrams that failed to decode for any other reason.
+    pub errors: u64,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    delivered: AtomicU64,
+    dropped: AtomicU64,
+    matched: AtomicU64,
+    not_mine: AtomicU64,
+    errors: AtomicU64,
+}
+
+struct Subscriber {
+    id: u64,
+    name: String,
+    tx: mpsc::Sender<Datagram>,
+    counters: Arc<Counters>,
+}
+
+impl Subscriber {
+    fn stats(&self) -> SubscriberStats {
+        let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+        SubscriberStats {
+            name: self.name.clone(),
+            delivered: load(&self.counters.delivered),
+            dropped: load(&self.counters.dropped),
+            matched: load(&self.counters.matched),
+            not_mine: load(&self.counters.not_mine),
+            errors: load(&self.counters.errors),
+        }
+    }
+}
+
+type SubscriberList = Arc<Mutex<Vec<Subscriber>>>;
+
+struct BoundPort {
+    listen_mode: ListenMode,
+    subscribers: SubscriberList,
+    task: JoinHandle<()>,
+}
+
+#[derive(Default)]
+struct BrokerState {
+    ports: HashMap<u16, BoundPort>,
+    next_id: u64,
+}
+
+/// Binds each UDP port once and fans its datagrams out to every subscriber.
+///
+/// Cloning shares the same set of sockets. Adapters use [`UdpPortBroker::shared`]
+/// so that all of them in one process see the same ports.
+#[derive(Clone, Default)]
+pub struct UdpPortBroker {
+    state: Arc<Mutex<BrokerState>>,
+}
+
+impl UdpPortBroker {
+    /// An empty broker, independent of [`UdpPortBroker::shared`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The process-wide broker adapters subscribe through.
+    pub fn shared() -> &'static UdpPortBroker {
+        static SHARED: OnceLock<UdpPortBroker> = OnceLock::new();
+        SHARED.get_or_init(UdpPortBroker::new)
+    }
+
+    /// Subscribe `name` to datagrams on `network.port`, binding the port if
+    /// nobody holds it yet.
+    ///
+    /// Every subscriber of a port must ask for the same listen mode. Port 0
+    /// binds a fresh ephemeral port; [`UdpSubscription::local_addr`] reports
+    /// which. Must be called from within a Tokio runtime.
+    pub fn subscribe(
+        &self,
+        network: AdapterNetworkConfig,
+        name: impl Into<String>,
+    ) -> io::Result<UdpSubscription> {
+        let name = name.into();
+        let mut state = self.lock();
+        let id = state.next_id;
+        state.next_id = state.next_id.wrapping_add(1);
+
+        let (tx, rx) = mpsc::channel(SUBSCRIBER_QUEUE_DEPTH);
+        let counters = Arc::new(Counters::default());
+        let subscriber = Subscriber {
+            id,
+            name: name.clone(),
+            tx,
+            counters: Arc::clone(&counters),
+        };
+
+        let existing = (network.port != 0)
+            .then(|| state.ports.get(&network.port))
+            .flatten();
+        let local_addr = match existing {
+            Some(bound) if bound.listen_mode != network.listen_mode => {
+                return Err(io::Error::new(
+                    io::ErrorKind::AddrInUse,
+                    format!(
+                        "UDP port {} is already shared in {} mode, not {}",
+                        network.port, bound.listen_mode, network.listen_mode
+                    ),
+                ));
+            }
+            Some(bound) => {
+                bound
+                    .subscribers
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .push(subscriber);
+                debug!(port = network.port, subscriber = %name, "Joined shared UDP port");
+                SocketAddr::from(([0, 0, 0, 0], network.port))
+            }
+            None => {
+                let socket = network.bind()?;
+                let local_addr = socket.local_addr()?;
+                let subscribers: SubscriberList = Arc::new(Mutex::new(vec![subscriber]));
+                let task = tokio::spawn(fan_out(socket, Arc::clone(&subscribers)));
+                info!(
+                    port = local_addr.port(),
+                    mode = %network.listen_mode,
+                    subscriber = %name,
+                    "Bound shared UDP port"
+                );
+                state.ports.insert(
+                    local_addr.port(),
+                    BoundPort {
+                        listen_mode: network.listen_mode,
+                        subscribers,
+                        task,
+                    },
+                );
+                local_addr
+            }
+        };
+
+        Ok(UdpSubscription {
+            handle: SubscriptionHandle {
+                broker: self.clone(),
+                port: local_addr.port(),
+                id,
+            },
+            local_addr,
+            rx,
+            counters,
+        })
+    }
+
+    /// Whether the broker currently holds a socket on `port`.
+    pub fn is_bound(&self, port: u16) -> bool {
+        self.lock().ports.contains_key(&port)
+    }
+
+    /// Counters for every current subscriber of `port`.
+    pub fn stats(&self, port: u16) -> Vec<SubscriberStats> {
+        self.lock()
+            .ports
+            .get(&port)
+            .map(|bound| {
+                bound
+                    .subscribers
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .iter()
+                    .map(Subscriber::stats)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BrokerState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Drop subscriber `id` from `port`, and the port itself once empty.
+    ///
+    /// Returns the receive task when the port was released so the caller can
+    /// wait for the socket to close.
+    fn remove(&self, port: u16, id: u64) -> Option<JoinHandle<()>> {
+        let mut state = self.lock();
+        let bound = state.ports.get(&port)?;
+        let now_empty = {
+            let mut subscribers = bound
+                .subscribers
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            subscribers.retain(|subscriber| subscriber.id != id);
+            subscribers.is_empty()
+        };
+        if !now_empty {
+            return None;
+        }
+        let bound = state.ports.remove(&port)?;
+        bound.task.abort();
+        info!(port, "Released shared UDP port");
+        Some(bound.task)
+    }
+}
+
+async fn fan_out(socket: tokio::net::UdpSocket, subscribers: SubscriberList) {
+    let mut buf = vec![0u8; MAX_DATAGRAM_BYTES];
+    loop {
+        let (len, source) = match socket.recv_from(&mut buf).await {
+            Ok(received) => received,
+            Err(error) => {
+                warn!(error = %error, "Shared UDP port receive error");
+                continue;
+            }
+        };
+        let datagram = Datagram {
+            source,
+            payload: Arc::from(&buf[..len]),
+        };
+        let subscribers = subscribers.lock().unwrap_or_else(PoisonError::into_inner);
+        for subscriber in subscribers.iter() {
+            let counter = match subscriber.tx.try_send(datagram.clone()) {
+                Ok(()) => &subscriber.counters.delivered,
+                Err(TrySendError::Full(_)) => &subscriber.counters.dropped,
+                Err(TrySendError::Closed(_)) => continue,
+            };
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Identifies one subscription so it can be ended from outside its task.
+#[derive(Clone)]
+pub struct SubscriptionHandle {
+    broker: UdpPortBroker,
+    port: u16,
+    id: u64,
+}
+
+impl SubscriptionHandle {
+    /// Leave the port, ending the subscription's stream. When this was the
+    /// last subscriber, waits for the socket to close before returning.
+    pub async fn release(&self) {
+        if let Some(task) = self.broker.remove(self.port, self.id) {
+            // Ignored: the task was just aborted, so it always ends cancelled.
+            let _ = task.await;
+        }
+    }
+}
+
+impl fmt::Debug for SubscriptionHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SubscriptionHandle")
+            .field("port", &self.port)
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+/// The subscription an adapter's monitoring task currently holds, so that
+/// `stop_monitoring` can end it.
+#[derive(Clone, Default)]
+pub(crate) struct SubscriptionSlot(Arc<Mutex<Option<SubscriptionHandle>>>);
+
+impl SubscriptionSlot {
+    /// Hold `handle`, releasing whichever subscription was held before.
+    pub(crate) async fn replace(&self, handle: SubscriptionHandle) {
+        let previous = self
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .replace(handle);
+        if let Some(previous) = previous {
+            previous.release().await;
+        }
+    }
+
+    /// Release the held subscription, if any.
+    pub(crate) async fn release(&self) {
+        let held = self.0.lock().unwrap_or_else(PoisonError::into_inner).take();
+        if let Some(held) = held {
+            held.release().await;
+        }
+    }
+}
+
+/// One adapter's view of a brokered port.
+///
+/// Dropping it leaves the port, as [`SubscriptionHandle::release`] does.
+pub struct UdpSubscription {
+    handle: SubscriptionHandle,
+    local_addr: SocketAddr,
+    rx: mpsc::Receiver<Datagram>,
+    counters: Arc<Counters>,
+}
+
+impl UdpSubscription {
+    /// Address of the shared socket.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// A handle that ends this subscription from elsewhere, e.g. from
+    /// `stop_monitoring`.
+    pub fn handle(&self) -> SubscriptionHandle {
+        self.handle.clone()
+    }
+
+    /// Next datagram, or `None` once the subscription has been released.
+    pub async fn recv(&mut self) -> Option<Datagram> {
+        self.rx.recv().await
+    }
+
+    /// Record how decoding a datagram went: a match, [`NotMyPacket`], or
+    /// some other failure.
+    pub fn record<T>(&self, result: &anyhow::Result<T>) {
+        let counter = match result {
+            Ok(_) => &self.counters.matched,
+            Err(error) if error.is::<NotMyPacket>() => &self.counters.not_mine,
+            Err(_) => &self.counters.errors,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl Drop for UdpSubscription {
+    fn drop(&mut self) {
+        self.handle.broker.remove(self.handle.port, self.handle.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+    use std::time::Duration;
+    use tokio::net::UdpSocket;
+
+    type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+    const RECV_TIMEOUT: Duration = Duration::from_secs(2);
+
+    async fn next(
+        subscription: &mut UdpSubscription,
+    ) -> Result<Datagram, Box<dyn std::error::Error>> {
+        Ok(tokio::time::timeout(RECV_TIMEOUT, subscription.recv())
+            .await?
+            .ok_or("subscription closed")?)
+    }
+
+    #[tokio::test]
+    async fn two_subscribers_on_one_port_both_receive() -> TestResult {
+        let broker = UdpPortBroker::new();
+        let mut first = broker.subscribe(AdapterNetworkConfig::new(0), "first")?;
+        let port = first.local_addr().port();
+        let mut second = broker.subscribe(AdapterNetworkConfig::new(port), "second")?;
+
+        let game = UdpSocket::bind("127.0.0.1:0").await?;
+        game.send_to(b"telemetry", ("127.0.0.1", port)).await?;
+
+        assert_eq!(&*next(&mut first).await?.payload, b"telemetry");
+        let datagram = next(&mut second).await?;
+        assert_eq!(&*datagram.payload, b"telemetry");
+        assert_eq!(datagram.source, game.local_addr()?);
+
+        first.record(&Ok::<(), anyhow::Error>(()));
+        second.record(&Err::<(), _>(anyhow::Error::new(NotMyPacket::new(
+            "second",
+            "wrong format",
+        ))));
+        second.record(&Err::<(), _>(anyhow!("truncated")));
+
+        let stats = broker.stats(port);
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].name, "first");
+        assert_eq!((stats[0].delivered, stats[0].matched), (1, 1));
+        assert_eq!(
+            (stats[1].delivered, stats[1].not_mine, stats[1].errors),
+            (1, 1, 1)
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn port_is_released_after_every_subscriber_leaves() -> TestResult {
+        let broker = UdpPortBroker::new();
+        let mut first = broker.subscribe(AdapterNetworkConfig::new(0), "first")?;
+        let port = first.local_addr().port();
+        let second = broker.subscribe(AdapterNetworkConfig::new(port), "second")?;
+
+        first.handle().release().await;
+        assert!(broker.is_bound(port));
+        assert!(next(&mut first).await.is_err(), "released stream ends");
+        assert_eq!(broker.stats(port).len(), 1);
+
+        second.handle().release().await;
+        assert!(!broker.is_bound(port));
+        assert!(broker.stats(port).is_empty());
+        let rebound = std::net::UdpSocket::bind(("0.0.0.0", port))?;
+        drop(rebound);
+
+        // Dropping the last subscription also frees the port.
+        let third = broker.subscribe(AdapterNetworkConfig::new(port), "third")?;
+        assert!(broker.is_bound(port));
+        drop(third);
+        assert!(!broker.is_bound(port));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn full_queue_drops_only_for_the_slow_subscriber() -> TestResult {
+        let broker = UdpPortBroker::new();
+        let mut fast = broker.subscribe(AdapterNetworkConfig::new(0), "fast")?;
+        let port = fast.local_addr().port();
+        let _slow = broker.subscribe(AdapterNetworkConfig::new(port), "slow")?;
+
+        let game = UdpSocket::bind("127.0.0.1:0").await?;
+        let total = SUBSCRIBER_QUEUE_DEPTH + 8;
+        for index in 0..total {
+            game.send_to(&index.to_le_bytes(), ("127.0.0.1", port))
+                .await?;
+            next(&mut fast).await?;
+        }
+
+        let stats = broker.stats(port);
+        assert_eq!(stats[0].delivered, total as u64);
+        assert_eq!(stats[0].dropped, 0);
+        assert_eq!(stats[1].delivered, SUBSCRIBER_QUEUE_DEPTH as u64);
+        assert_eq!(stats[1].dropped, 8);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn mismatched_listen_mode_is_rejected() -> TestResult {
+        let broker = UdpPortBroker::new();
+        let first = broker.subscribe(AdapterNetworkConfig::new(0), "first")?;
+        let port = first.local_addr().port();
+        let result = broker.subscribe(
+            AdapterNetworkConfig::new(port).with_listen_mode(ListenMode::Broadcast),
+            "second",
+        );
+        assert!(matches!(result, Err(e) if e.kind() == io::ErrorKind::AddrInUse));
+        assert_eq!(broker.stats(port).len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn not_my_packet_displays_its_reason() {
+        let error = anyhow::Error::new(NotMyPacket::new("f1_25", "F1 25: unexpected format"));
+        assert_eq!(error.to_string(), "F1 25: unexpected format");
+        assert!(error.is::<NotMyPacket>());
+    }
+}
@@ -0,0 +1,166 @@
+//! Shared UDP socket setup for LAN telemetry sources.
+//!
+//! Most UDP games send to a single configured address, but consoles and some PC
+//! titles can instead broadcast to the whole subnet or publish to a multicast
+//! group so several tools on the LAN can listen at once. [`AdapterNetworkConfig`]
+//! binds the adapter's receive socket for the chosen [`ListenMode`] so each
+//! adapter does not repeat the socket option plumbing.
+
+use std::fmt;
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+
+use serde::{Deserialize, Serialize};
+use socket2::{Domain, Protocol, Socket, Type};
+use tokio::net::UdpSocket;
+
+/// How a UDP adapter receives datagrams from the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ListenMode {
+    /// Datagrams addressed to this host only.
+    #[default]
+    Unicast,
+    /// Subnet broadcasts; the port may be shared with other listeners.
+    Broadcast,
+    /// Datagrams published to an IPv4 multicast group.
+    Multicast {
+        /// Group to join, e.g. `239.255.0.1`.
+        group: Ipv4Addr,
+        /// Local interface to join on; the OS picks one when `None`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        interface: Option<Ipv4Addr>,
+    },
+}
+
+impl ListenMode {
+    /// Whether the socket is bound with address/port reuse so other LAN tools
+    /// can receive the same stream.
+    pub fn shares_port(&self) -> bool {
+        !matches!(self, Self::Unicast)
+    }
+}
+
+impl fmt::Display for ListenMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unicast => f.write_str("unicast"),
+            Self::Broadcast => f.write_str("broadcast"),
+            Self::Multicast {
+                group,
+                interface: Some(interface),
+            } => write!(f, "multicast {group} on {interface}"),
+            Self::Multicast {
+                group,
+                interface: None,
+            } => write!(f, "multicast {group}"),
+        }
+    }
+}
+
+/// Receive-socket settings for a UDP telemetry adapter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdapterNetworkConfig {
+    /// Local UDP port to bind.
+    pub port: u16,
+    /// How datagrams reach the port.
+    #[serde(default)]
+    pub listen_mode: ListenMode,
+}
+
+impl AdapterNetworkConfig {
+    /// Unicast listener on `port`.
+    pub fn new(port: u16) -> Self {
+        Self {
+            port,
+            listen_mode: ListenMode::Unicast,
+        }
+    }
+
+    /// Replace the listen mode.
+    pub fn with_listen_mode(mut self, listen_mode: ListenMode) -> Self {
+        self.listen_mode = listen_mode;
+        self
+    }
+
+    /// Bind a non-blocking std socket configured for the listen mode.
+    ///
+    /// Unicast keeps plain bind semantics, so a second listener on the same
+    /// port still fails. Broadcast and multicast set `SO_REUSEADDR` (and
+    /// `SO_REUSEPORT` on Unix) so the stream can be shared.
+    pub fn bind_std(&self) -> io::Result<std::net::UdpSocket> {
+        let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+        if self.listen_mode.shares_port() {
+            socket.set_reuse_address(true)?;
+            #[cfg(unix)]
+            socket.set_reuse_port(true)?;
+        }
+
+        match self.listen_mode {
+            ListenMode::Unicast => {}
+            ListenMode::Broadcast => socket.set_broadcast(true)?,
+            ListenMode::Multicast { group, .. } if !group.is_multicast() => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{group} is not an IPv4 multicast group"),
+                ));
+            }
+            ListenMode::Multicast { .. } => {}
+        }
+
+        let addr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, self.port));
+        socket.bind(&addr.into())?;
+
+        if let ListenMode::Multicast { group, interface } = self.listen_mode {
+            socket.join_multicast_v4(&group, &interface.unwrap_or(Ipv4Addr::UNSPECIFIED))?;
+            socket.set_multicast_loop_v4(true)?;
+        }
+
+        socket.set_nonblocking(true)?;
+        Ok(socket.into())
+    }
+
+    /// Bind a Tokio socket configured for the listen mode.
+    ///
+    /// Must be called from within a Tokio runtime.
+    pub fn bind(&self) -> io::Result<UdpSocket> {
+        UdpSocket::from_std(self.bind_std()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+    #[test]
+    fn listen_mode_serde_round_trip() -> TestResult {
+        let modes = [
+            ListenMode::Unicast,
+            ListenMode::Broadcast,
+            ListenMode::Multicast {
+                group: Ipv4Addr::new(239, 255, 0, 1),
+                interface: Some(Ipv4Addr::new(192, 168, 1, 20)),
+            },
+        ];
+        for mode in modes {
+            let json = serde_json::to_string(&mode)?;
+            assert_eq!(serde_json::from_str::<ListenMode>(&json)?, mode);
+        }
+        let parsed: ListenMode =
+            serde_json::from_str(r#"{"mode":"multicast","group":"239.1.2.3"}"#)?;
+        assert_eq!(parsed.to_string(), "multicast 239.1.2.3");
+        Ok(())
+    }
+
+    #[test]
+    fn non_multicast_group_is_rejected() {
+        let config = AdapterNetworkConfig::new(0).with_listen_mode(ListenMode::Multicast {
+            group: Ipv4Addr::new(192, 168, 1, 1),
+            interface: None,
+        });
+        let result = config.bind_std();
+        assert!(matches!(result, Err(e) if e.kind() == io::ErrorKind::InvalidInput));
+    }
+}
@@ -30,26 +30,29 @@
 //! document offset 148 (engine rate) and offset 252 (max RPM) as "rpm / 10", meaning
 //! the raw value must be multiplied by 10 for realistic RPM.  WRC Generations / EA WRC
 //! may send direct RPM values (no ×10 scaling).  This adapter passes values as-is.
+//!
+//! The port is taken through [`crate::udp_broker`], so a user who points EA WRC
+//! at 20777 can still run it alongside the other Codemasters adapters.
 
+use crate::raw_capture::RawCaptureSlot;
+use crate::udp_broker::{SubscriptionSlot, UdpPortBroker};
+use crate::udp_listener::AdapterNetworkConfig;
 use crate::{
-    NormalizedTelemetry, TelemetryAdapter, TelemetryFlags, TelemetryFrame, TelemetryReceiver,
-    TelemetryValue, telemetry_now_ns,
+    ExtendedKey, NormalizedTelemetry, TelemetryAdapter, TelemetryFlags, TelemetryFrame,
+    TelemetryReceiver, telemetry_now_ns,
 };
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow};
 use async_trait::async_trait;
-use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use std::sync::{
     Arc,
     atomic::{AtomicU64, Ordering},
 };
 use std::time::Duration;
-use tokio::net::UdpSocket as TokioUdpSocket;
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
 const DEFAULT_PORT: u16 = 6777;
 const MIN_PACKET_SIZE: usize = 264;
-const MAX_PACKET_SIZE: usize = 2048;
 const DEFAULT_HEARTBEAT_TIMEOUT_MS: u64 = 1_500;
 
 const ENV_PORT: &str = "OPENRACING_WRC_GENERATIONS_UDP_PORT";
@@ -100,6 +103,8 @@ pub struct WrcGenerationsAdapter {
     update_rate: Duration,
     heartbeat_timeout: Duration,
     last_packet_ns: Arc<AtomicU64>,
+    subscription: SubscriptionSlot,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for WrcGenerationsAdapter {
@@ -127,6 +132,8 @@ impl WrcGenerationsAdapter {
             update_rate: Duration::from_millis(16),
             heartbeat_timeout: Duration::from_millis(heartbeat_ms),
             last_packet_ns: Arc::new(AtomicU64::new(0)),
+            subscription: SubscriptionSlot::default(),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -279,21 +286,29 @@ fn parse_packet(data: &[u8]) -> Result<NormalizedTelemetry> {
         .num_gears(num_gears)
         .current_lap_time_s(current_lap_time_s)
         .last_lap_time_s(last_lap_time_s)
-        .flags(flags)
-        .extended("wheel_speed_fl".to_string(), TelemetryValue::Float(ws_fl))
-        .extended("wheel_speed_fr".to_string(), TelemetryValue::Float(ws_fr))
-        .extended("wheel_speed_rl".to_string(), TelemetryValue::Float(ws_rl))
-        .extended("wheel_speed_rr".to_string(), TelemetryValue::Float(ws_rr));
-
-    if max_rpm > 0.0 {
-        let rpm_fraction = (rpm_raw / max_rpm).clamp(0.0, 1.0);
-        builder = builder.max_rpm(max_rpm).extended(
-            "rpm_fraction".to_string(),
-            TelemetryValue::Float(rpm_fraction),
-        );
+        .flags(flags);
+
+    let rpm_fraction = if max_rpm > 0.0 {
+        builder = builder.max_rpm(max_rpm);
+        Some((rpm_raw / max_rpm).clamp(0.0, 1.0))
+    } else {
+        None
+    };
+
+    let mut telemetry = builder.build();
+    for (key, speed) in [
+        (ExtendedKey::WHEEL_SPEED_FL, ws_fl),
+        (ExtendedKey::WHEEL_SPEED_FR, ws_fr),
+        (ExtendedKey::WHEEL_SPEED_RL, ws_rl),
+        (ExtendedKey::WHEEL_SPEED_RR, ws_rr),
+    ] {
+        key.set(&mut telemetry, speed)?;
+    }
+    if let Some(rpm_fraction) = rpm_fraction {
+        ExtendedKey::RPM_FRACTION.set(&mut telemetry, rpm_fraction)?;
     }
 
-    Ok(builder.build())
+    Ok(telemetry)
 }
 
 #[async_trait]
@@ -302,48 +317,42 @@ impl TelemetryAdapter for WrcGenerationsAdapter {
         "wrc_generations"
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let bind_port = self.bind_port;
         let update_rate = self.update_rate;
         let last_packet_ns = Arc::clone(&self.last_packet_ns);
         let (tx, rx) = mpsc::channel(100);
 
-        tokio::spawn(async move {
-            let bind_addr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, bind_port));
-            let socket = match TokioUdpSocket::bind(bind_addr).await {
-                Ok(s) => s,
-                Err(error) => {
-                    warn!(
-                        error = %error,
-                        port = bind_port,
-                        "WRC Generations UDP socket bind failed"
-                    );
-                    return;
-                }
-            };
-
-            info!(port = bind_port, "WRC Generations UDP adapter bound");
+        let mut subscription = UdpPortBroker::shared()
+            .subscribe(AdapterNetworkConfig::new(bind_port), self.game_id())
+            .with_context(|| format!("WRC Generations could not listen on UDP port {bind_port}"))?;
+        self.subscription.replace(subscription.handle()).await;
+        info!(port = bind_port, "WRC Generations UDP adapter subscribed");
 
+        tokio::spawn(async move {
             let mut frame_idx = 0u64;
-            let mut buf = vec![0u8; MAX_PACKET_SIZE];
             let timeout = (update_rate * 4).max(Duration::from_millis(25));
 
             loop {
-                let recv = tokio::time::timeout(timeout, socket.recv(&mut buf)).await;
-                let len = match recv {
-                    Ok(Ok(len)) => len,
-                    Ok(Err(error)) => {
-                        warn!(error = %error, "WRC Generations UDP receive error");
-                        continue;
-                    }
+                let datagram = match tokio::time::timeout(timeout, subscription.recv()).await {
+                    Ok(Some(datagram)) => datagram,
+                    Ok(None) => break,
                     Err(_) => {
                         debug!("WRC Generations UDP receive timeout");
                         continue;
                     }
                 };
 
-                let data = &buf[..len];
-                let normalized = match parse_packet(data) {
+                let len = datagram.payload.len();
+                let parsed = parse_packet(&datagram.payload);
+                subscription.record(&parsed);
+                raw_capture.record_decoded(&datagram.payload, Some(datagram.source), &parsed);
+                let normalized = match parsed {
                     Ok(n) => n,
                     Err(error) => {
                         warn!(error = %error, "Failed to parse WRC Generations packet");
@@ -366,6 +375,7 @@ impl TelemetryAdapter for WrcGenerationsAdapter {
     }
 
     async fn stop_monitoring(&self) -> Result<()> {
+        self.subscription.release().await;
         Ok(())
     }
 
@@ -385,6 +395,7 @@ impl TelemetryAdapter for WrcGenerationsAdapter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::TelemetryValue;
 
     fn make_packet(size: usize) -> Vec<u8> {
         vec![0u8; size]
@@ -34,8 +34,9 @@
 //! The adapter is used for both WRC 9 and WRC 10 via the [`WrcKylotonnVariant`] enum.
 //! Both games use UDP port 64000 by default.
 
+use crate::raw_capture::RawCaptureSlot;
 use crate::{
-    NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver, TelemetryValue,
+    ExtendedKey, NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver,
     telemetry_now_ns,
 };
 use anyhow::{Result, anyhow};
@@ -163,32 +164,41 @@ fn parse_packet(data: &[u8]) -> Result<NormalizedTelemetry> {
         .throttle(throttle)
         .brake(brake)
         .clutch(clutch)
-        .steering_angle(steering)
-        .extended("stage_progress", TelemetryValue::Float(stage_progress))
-        .extended("hand_brake", TelemetryValue::Float(hand_brake))
-        .extended("suspension_fl", TelemetryValue::Float(susp_fl))
-        .extended("suspension_fr", TelemetryValue::Float(susp_fr))
-        .extended("suspension_rl", TelemetryValue::Float(susp_rl))
-        .extended("suspension_rr", TelemetryValue::Float(susp_rr))
-        .extended("pos_x", TelemetryValue::Float(pos_x))
-        .extended("pos_y", TelemetryValue::Float(pos_y))
-        .extended("pos_z", TelemetryValue::Float(pos_z))
-        .extended("roll", TelemetryValue::Float(roll))
-        .extended("pitch", TelemetryValue::Float(pitch))
-        .extended("yaw", TelemetryValue::Float(yaw))
-        .extended("wheel_speed_fl", TelemetryValue::Float(ws_fl))
-        .extended("wheel_speed_fr", TelemetryValue::Float(ws_fr))
-        .extended("wheel_speed_rl", TelemetryValue::Float(ws_rl))
-        .extended("wheel_speed_rr", TelemetryValue::Float(ws_rr));
-
-    if max_rpm > 0.0 {
-        let rpm_fraction = (rpm / max_rpm).clamp(0.0, 1.0);
-        builder = builder
-            .max_rpm(max_rpm)
-            .extended("rpm_fraction", TelemetryValue::Float(rpm_fraction));
+        .steering_angle(steering);
+
+    let rpm_fraction = if max_rpm > 0.0 {
+        builder = builder.max_rpm(max_rpm);
+        Some((rpm / max_rpm).clamp(0.0, 1.0))
+    } else {
+        None
+    };
+
+    let mut telemetry = builder.build();
+    for (key, value) in [
+        (ExtendedKey::STAGE_PROGRESS, stage_progress),
+        (ExtendedKey::HAND_BRAKE, hand_brake),
+        (ExtendedKey::SUSPENSION_FL, susp_fl),
+        (ExtendedKey::SUSPENSION_FR, susp_fr),
+        (ExtendedKey::SUSPENSION_RL, susp_rl),
+        (ExtendedKey::SUSPENSION_RR, susp_rr),
+        (ExtendedKey::POS_X, pos_x),
+        (ExtendedKey::POS_Y, pos_y),
+        (ExtendedKey::POS_Z, pos_z),
+        (ExtendedKey::ROLL, roll),
+        (ExtendedKey::PITCH, pitch),
+        (ExtendedKey::YAW, yaw),
+        (ExtendedKey::WHEEL_SPEED_FL, ws_fl),
+        (ExtendedKey::WHEEL_SPEED_FR, ws_fr),
+        (ExtendedKey::WHEEL_SPEED_RL, ws_rl),
+        (ExtendedKey::WHEEL_SPEED_RR, ws_rr),
+    ] {
+        key.set(&mut telemetry, value)?;
+    }
+    if let Some(rpm_fraction) = rpm_fraction {
+        ExtendedKey::RPM_FRACTION.set(&mut telemetry, rpm_fraction)?;
     }
 
-    Ok(builder.build())
+    Ok(telemetry)
 }
 
 /// Kylotonn WRC 9 / WRC 10 UDP telemetry adapter.
@@ -199,6 +209,7 @@ pub struct WrcKylotonnAdapter {
     variant: WrcKylotonnVariant,
     bind_port: u16,
     update_rate: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl WrcKylotonnAdapter {
@@ -207,6 +218,7 @@ impl WrcKylotonnAdapter {
             variant,
             bind_port: DEFAULT_PORT,
             update_rate: Duration::from_millis(16),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -222,7 +234,12 @@ impl TelemetryAdapter for WrcKylotonnAdapter {
         self.variant.game_id()
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(100);
         let bind_port = self.bind_port;
         let update_rate = self.update_rate;
@@ -243,7 +260,7 @@ impl TelemetryAdapter for WrcKylotonnAdapter {
             let timeout = (update_rate * 4).max(Duration::from_millis(25));
 
             loop {
-                match tokio::time::timeout(timeout, socket.recv(&mut buf)).await {
+                match tokio::time::timeout(timeout, raw_capture.recv(&socket, &mut buf)).await {
                     Ok(Ok(len)) => match parse_packet(&buf[..len]) {
                         Ok(normalized) => {
                             let frame =
@@ -286,6 +303,7 @@ impl TelemetryAdapter for WrcKylotonnAdapter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::TelemetryValue;
 
     fn make_packet() -> Vec<u8> {
         vec![0u8; MIN_PACKET_SIZE]
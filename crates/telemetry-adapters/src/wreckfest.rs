@@ -17,6 +17,7 @@
 //!
 //! Update rate: 60 Hz.
 
+use crate::raw_capture::RawCaptureSlot;
 use crate::{
     NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver, telemetry_now_ns,
 };
@@ -91,6 +92,7 @@ pub fn parse_wreckfest_packet(data: &[u8]) -> Result<NormalizedTelemetry> {
 pub struct WreckfestAdapter {
     bind_port: u16,
     update_rate: Duration,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for WreckfestAdapter {
@@ -104,6 +106,7 @@ impl WreckfestAdapter {
         Self {
             bind_port: DEFAULT_WRECKFEST_PORT,
             update_rate: Duration::from_millis(16), // ~60 Hz
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -119,7 +122,12 @@ impl TelemetryAdapter for WreckfestAdapter {
         "wreckfest"
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let (tx, rx) = mpsc::channel(100);
         let bind_port = self.bind_port;
         let update_rate = self.update_rate;
@@ -138,7 +146,9 @@ impl TelemetryAdapter for WreckfestAdapter {
             let mut frame_idx = 0u64;
 
             loop {
-                match tokio::time::timeout(update_rate * 10, socket.recv(&mut buf)).await {
+                match tokio::time::timeout(update_rate * 10, raw_capture.recv(&socket, &mut buf))
+                    .await
+                {
                     Ok(Ok(len)) => match parse_wreckfest_packet(&buf[..len]) {
                         Ok(normalized) => {
                             let frame =
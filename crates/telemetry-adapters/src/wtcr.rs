@@ -6,9 +6,10 @@
 //! (264+ bytes, little-endian `f32` at known byte offsets), shared with GRID Autosport,
 //! DiRT Rally 2.0, WRC Generations, and the broader Codemasters racing series.
 
+use crate::raw_capture::RawCaptureSlot;
 use crate::{
-    NormalizedTelemetry, TelemetryAdapter, TelemetryFlags, TelemetryFrame, TelemetryReceiver,
-    TelemetryValue, telemetry_now_ns,
+    ExtendedKey, NormalizedTelemetry, TelemetryAdapter, TelemetryFlags, TelemetryFrame,
+    TelemetryReceiver, telemetry_now_ns,
 };
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
@@ -66,6 +67,7 @@ pub struct WtcrAdapter {
     update_rate: Duration,
     heartbeat_timeout: Duration,
     last_packet_ns: Arc<AtomicU64>,
+    raw_capture: RawCaptureSlot,
 }
 
 impl Default for WtcrAdapter {
@@ -93,6 +95,7 @@ impl WtcrAdapter {
             update_rate: Duration::from_millis(16),
             heartbeat_timeout: Duration::from_millis(heartbeat_ms),
             last_packet_ns: Arc::new(AtomicU64::new(0)),
+            raw_capture: RawCaptureSlot::default(),
         }
     }
 
@@ -224,21 +227,29 @@ pub fn parse_wtcr_packet(data: &[u8]) -> Result<NormalizedTelemetry> {
         .tire_pressures_psi(tire_pressures_psi)
         .num_gears(num_gears)
         .last_lap_time_s(last_lap_time_s)
-        .flags(flags)
-        .extended("wheel_speed_fl".to_string(), TelemetryValue::Float(ws_fl))
-        .extended("wheel_speed_fr".to_string(), TelemetryValue::Float(ws_fr))
-        .extended("wheel_speed_rl".to_string(), TelemetryValue::Float(ws_rl))
-        .extended("wheel_speed_rr".to_string(), TelemetryValue::Float(ws_rr));
-
-    if max_rpm > 0.0 {
-        let rpm_fraction = (rpm_raw / max_rpm).clamp(0.0, 1.0);
-        builder = builder.max_rpm(max_rpm).extended(
-            "rpm_fraction".to_string(),
-            TelemetryValue::Float(rpm_fraction),
-        );
+        .flags(flags);
+
+    let rpm_fraction = if max_rpm > 0.0 {
+        builder = builder.max_rpm(max_rpm);
+        Some((rpm_raw / max_rpm).clamp(0.0, 1.0))
+    } else {
+        None
+    };
+
+    let mut telemetry = builder.build();
+    for (key, speed) in [
+        (ExtendedKey::WHEEL_SPEED_FL, ws_fl),
+        (ExtendedKey::WHEEL_SPEED_FR, ws_fr),
+        (ExtendedKey::WHEEL_SPEED_RL, ws_rl),
+        (ExtendedKey::WHEEL_SPEED_RR, ws_rr),
+    ] {
+        key.set(&mut telemetry, speed)?;
+    }
+    if let Some(rpm_fraction) = rpm_fraction {
+        ExtendedKey::RPM_FRACTION.set(&mut telemetry, rpm_fraction)?;
     }
 
-    Ok(builder.build())
+    Ok(telemetry)
 }
 
 #[async_trait]
@@ -247,7 +258,12 @@ impl TelemetryAdapter for WtcrAdapter {
         "wtcr"
     }
 
+    fn raw_capture_slot(&self) -> Option<&RawCaptureSlot> {
+        Some(&self.raw_capture)
+    }
+
     async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let raw_capture = self.raw_capture.clone();
         let bind_port = self.bind_port;
         let update_rate = self.update_rate;
         let last_packet_ns = Arc::clone(&self.last_packet_ns);
@@ -274,7 +290,7 @@ impl TelemetryAdapter for WtcrAdapter {
             let timeout = (update_rate * 4).max(Duration::from_millis(25));
 
             loop {
-                let recv = tokio::time::timeout(timeout, socket.recv(&mut buf)).await;
+                let recv = tokio::time::timeout(timeout, raw_capture.recv(&socket, &mut buf)).await;
                 let len = match recv {
                     Ok(Ok(len)) => len,
                     Ok(Err(error)) => {
@@ -0,0 +1,184 @@
+//! Layout declarations for the Assetto Corsa family.
+//!
+//! Per-version size checks, plus fixture decodes proving the generated accessors
+//! read exactly the bytes the hand-written offset tables used to read.
+
+use racing_wheel_telemetry_adapters::ac_layout::{
+    AccGraphicsPrefix, AccPhysicsInputs, LayoutVersion, RtCarInfo,
+};
+use racing_wheel_telemetry_adapters::acc::{
+    GRAPHICS_PENALTY_PAGE_LEN, PHYSICS_TYRE_PAGE_LEN, parse_graphics_penalties,
+    parse_physics_inputs,
+};
+use racing_wheel_telemetry_adapters::{AssettoCorsaAdapter, PenaltyKind, TelemetryAdapter};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+/// Deterministic non-zero bytes so every offset decodes to a distinct value.
+fn fixture(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 37 % 251) as u8 + 1).collect()
+}
+
+fn legacy_f32(data: &[u8], offset: usize) -> Option<f32> {
+    data.get(offset..offset + 4)
+        .and_then(|b| b.try_into().ok())
+        .map(f32::from_le_bytes)
+}
+
+fn legacy_i32(data: &[u8], offset: usize) -> Option<i32> {
+    data.get(offset..offset + 4)
+        .and_then(|b| b.try_into().ok())
+        .map(i32::from_le_bytes)
+}
+
+#[test]
+fn rt_car_info_sizes_per_version() {
+    assert_eq!(
+        RtCarInfo::VERSIONS,
+        &[LayoutVersion {
+            label: "1.x",
+            size: 328
+        }]
+    );
+    // The declaration covers the packet end to end.
+    let last = RtCarInfo::FIELDS[RtCarInfo::FIELDS.len() - 1];
+    assert_eq!(last.offset + last.size, 328);
+}
+
+#[test]
+fn acc_graphics_prefix_sizes_per_version() {
+    assert_eq!(
+        AccGraphicsPrefix::VERSIONS,
+        &[LayoutVersion {
+            label: "1.8",
+            size: 1232
+        }]
+    );
+    assert_eq!(GRAPHICS_PENALTY_PAGE_LEN, AccGraphicsPrefix::MIN_SIZE);
+}
+
+#[test]
+fn rt_car_info_accessors_match_legacy_offsets() -> TestResult {
+    let data = fixture(328);
+    let car = RtCarInfo::new(&data).ok_or("fixture is a full RTCarInfo")?;
+
+    // Offsets of the pre-migration `OFF_*` table in assetto_corsa.rs.
+    let f32_fields = [
+        (car.speed_kmh(), 8),
+        (car.speed_ms(), 16),
+        (car.accg_vertical(), 28),
+        (car.accg_horizontal(), 32),
+        (car.accg_frontal(), 36),
+        (car.gas(), 56),
+        (car.brake(), 60),
+        (car.clutch(), 64),
+        (car.engine_rpm(), 68),
+        (car.steer(), 72),
+    ];
+    for (value, offset) in f32_fields {
+        assert_eq!(
+            Some(value.to_bits()),
+            legacy_f32(&data, offset).map(f32::to_bits),
+            "f32 at {offset}"
+        );
+    }
+
+    let i32_fields = [
+        (car.lap_time_ms(), 40),
+        (car.last_lap_ms(), 44),
+        (car.best_lap_ms(), 48),
+        (car.lap_count(), 52),
+        (car.gear(), 76),
+    ];
+    for (value, offset) in i32_fields {
+        assert_eq!(Some(value), legacy_i32(&data, offset), "i32 at {offset}");
+    }
+
+    for (value, offset) in [
+        (car.is_abs_in_action(), 21),
+        (car.is_tc_in_action(), 22),
+        (car.is_in_pit(), 24),
+        (car.is_engine_limiter_on(), 25),
+    ] {
+        assert_eq!(value, data[offset], "u8 at {offset}");
+    }
+
+    for (wheel, (angle, ratio)) in car.slip_angle().iter().zip(car.slip_ratio()).enumerate() {
+        assert_eq!(
+            Some(angle.to_bits()),
+            legacy_f32(&data, 100 + wheel * 4).map(f32::to_bits)
+        );
+        assert_eq!(
+            Some(ratio.to_bits()),
+            legacy_f32(&data, 132 + wheel * 4).map(f32::to_bits)
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn assetto_corsa_fixture_decodes_through_layout() -> TestResult {
+    let mut data = vec![0u8; RtCarInfo::MIN_SIZE];
+    data[RtCarInfo::SPEED_MS..RtCarInfo::SPEED_MS + 4].copy_from_slice(&25.0f32.to_le_bytes());
+    data[RtCarInfo::RPM..RtCarInfo::RPM + 4].copy_from_slice(&7200.0f32.to_le_bytes());
+    data[RtCarInfo::GEAR..RtCarInfo::GEAR + 4].copy_from_slice(&4i32.to_le_bytes());
+    data[RtCarInfo::IN_PIT] = 1;
+    data[RtCarInfo::SLIP_ANGLE + 4..RtCarInfo::SLIP_ANGLE + 8]
+        .copy_from_slice(&0.25f32.to_le_bytes());
+
+    let normalized = AssettoCorsaAdapter::new().normalize(&data)?;
+
+    assert_eq!(normalized.speed_ms, 25.0);
+    assert_eq!(normalized.rpm, 7200.0);
+    assert_eq!(normalized.gear, 3);
+    assert!(normalized.flags.in_pits);
+    assert_eq!(normalized.slip_angle_fr, 0.25);
+    assert!(
+        AssettoCorsaAdapter::new()
+            .normalize(&data[..RtCarInfo::MIN_SIZE - 1])
+            .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn acc_graphics_fixture_decodes_through_layout() -> TestResult {
+    let mut page = fixture(GRAPHICS_PENALTY_PAGE_LEN);
+    page[1220..1224].copy_from_slice(&12.5f32.to_le_bytes());
+    page[1228..1232].copy_from_slice(&14i32.to_le_bytes());
+
+    let graphics = AccGraphicsPrefix::new(&page).ok_or("fixture covers the penalty fields")?;
+    assert_eq!(Some(graphics.penalty()), legacy_i32(&page, 1228));
+    assert_eq!(graphics.flag(), legacy_i32(&page, 1224).unwrap_or_default());
+
+    let state = parse_graphics_penalties(&page)?;
+    assert_eq!(state.active_penalty_kind, PenaltyKind::TimePenalty);
+    assert_eq!(state.penalty_time_s, 12.5);
+    Ok(())
+}
+
+#[test]
+fn acc_physics_inputs_fixture_decodes_through_layout() -> TestResult {
+    assert_eq!(AccPhysicsInputs::MIN_SIZE, 56);
+    // The tyre read already maps the inputs prefix.
+    const { assert!(PHYSICS_TYRE_PAGE_LEN >= AccPhysicsInputs::MIN_SIZE) };
+
+    let page = fixture(AccPhysicsInputs::MIN_SIZE);
+    let physics = AccPhysicsInputs::new(&page).ok_or("fixture covers the input fields")?;
+    assert_eq!(Some(physics.packet_id()), legacy_i32(&page, 0));
+    assert_eq!(Some(physics.speed_kmh()), legacy_f32(&page, 28));
+    assert_eq!(Some(physics.acc_g()[2]), legacy_f32(&page, 52));
+
+    let channels = parse_physics_inputs(&page)?;
+    assert_eq!(Some(channels.longitudinal_g), legacy_f32(&page, 52));
+    assert!(parse_physics_inputs(&page[..AccPhysicsInputs::MIN_SIZE - 1]).is_err());
+    Ok(())
+}
+
+#[test]
+fn layout_table_lists_every_field() {
+    let table = RtCarInfo::layout_table();
+    assert!(table.starts_with("| Offset | Size | Type | Field | Since |"));
+    assert_eq!(table.lines().count(), RtCarInfo::FIELDS.len() + 2);
+    assert!(table.contains("| 100 | 16 | `[f32; 4]` | `slip_angle` | 1.x |"));
+}
@@ -4,11 +4,17 @@ use racing_wheel_telemetry_adapters::gran_turismo_7::{
     Gt7PacketType, MAGIC, PACKET_SIZE, PACKET_SIZE_TYPE2, PACKET_SIZE_TYPE3, parse_decrypted,
     parse_decrypted_ext,
 };
-use racing_wheel_telemetry_adapters::{GranTurismo7Adapter, TelemetryAdapter, TelemetryValue};
+use racing_wheel_telemetry_adapters::{
+    ExtendedKey, GranTurismo7Adapter, TelemetryAdapter, TelemetryValue,
+};
 use std::time::Duration;
 
 type TestResult = Result<(), Box<dyn std::error::Error>>;
 
+/// Packets encrypted by `scripts/gen_gt7_fixtures.py`.
+const FIXTURE_TYPE1: &[u8] = include_bytes!("fixtures/gran_turismo_7/type1_packet.bin");
+const FIXTURE_TYPE2: &[u8] = include_bytes!("fixtures/gran_turismo_7/type2_packet.bin");
+
 // Field offsets (from the adapter source)
 const OFF_MAGIC: usize = 0x00;
 const OFF_ENGINE_RPM: usize = 0x3C;
@@ -82,6 +88,97 @@ fn gt7_parse_decrypted_rejects_too_short_buffer() {
     assert!(parse_decrypted_ext(&buf).is_err());
 }
 
+#[test]
+fn gt7_decrypts_encrypted_fixture() -> TestResult {
+    let adapter = GranTurismo7Adapter::new();
+    let t = adapter.normalize(FIXTURE_TYPE1)?;
+    assert_eq!(t.rpm, 6500.0);
+    assert_eq!(t.max_rpm, 7800.0);
+    assert_eq!(t.speed_ms, 55.5);
+    assert_eq!(t.gear, 3);
+    assert!((t.throttle - 0.8).abs() < 0.001);
+    assert_eq!(t.brake, 0.0);
+    assert!((t.fuel_percent - 0.425).abs() < 0.001);
+    assert_eq!(
+        t.get_extended(ExtendedKey::FUEL_LEFT_L.name),
+        Some(&TelemetryValue::Float(42.5))
+    );
+    assert_eq!(
+        t.get_extended("gt7_in_race"),
+        Some(&TelemetryValue::Boolean(true))
+    );
+    assert_eq!(
+        t.get_extended("gt7_loading"),
+        Some(&TelemetryValue::Boolean(false))
+    );
+    assert!(t.flags.traction_control);
+    assert!(!t.flags.session_paused);
+    assert_eq!(t.car_id, Some("gt7_3273".to_string()));
+    assert_eq!(t.position, 4);
+    assert_eq!(t.lap, 3);
+    assert_eq!(t.best_lap_time_s, 95.0);
+    Ok(())
+}
+
+#[test]
+fn gt7_decrypts_type2_fixture_with_its_xor_key() -> TestResult {
+    let t = GranTurismo7Adapter::new()
+        .with_packet_type(Gt7PacketType::Type2)
+        .normalize(FIXTURE_TYPE2)?;
+    assert_eq!(t.rpm, 6500.0);
+    assert_eq!(t.lateral_g, 0.5);
+    assert_eq!(t.longitudinal_g, 0.8);
+    assert!((t.steering_angle - 0.3).abs() < 0.001);
+    Ok(())
+}
+
+#[test]
+fn gt7_rejects_corrupted_ciphertext() {
+    let adapter = GranTurismo7Adapter::new();
+    // A flipped IV byte changes the whole keystream; a flipped magic byte
+    // decrypts to the wrong magic.
+    for offset in [0x00, 0x03, 0x40, 0x43] {
+        let mut packet = FIXTURE_TYPE1.to_vec();
+        packet[offset] ^= 0x5A;
+        assert!(adapter.normalize(&packet).is_err(), "offset {offset:#x}");
+    }
+    // The Type1 fixture decrypted with the Type2 key fails too.
+    let mut padded = FIXTURE_TYPE1.to_vec();
+    padded.resize(PACKET_SIZE_TYPE2, 0);
+    assert!(adapter.normalize(&padded).is_err());
+    assert!(
+        adapter
+            .normalize(&FIXTURE_TYPE1[..PACKET_SIZE - 1])
+            .is_err()
+    );
+}
+
+#[test]
+fn gt7_decryption_can_be_turned_off_for_plaintext_captures() -> TestResult {
+    let mut buf = buf_with_magic();
+    write_f32(&mut buf, OFF_ENGINE_RPM, 5100.0);
+    let plaintext = GranTurismo7Adapter::new().with_decryption(false);
+    assert_eq!(plaintext.normalize(&buf)?.rpm, 5100.0);
+    // Encrypted packets no longer pass the magic check.
+    assert!(plaintext.normalize(FIXTURE_TYPE1).is_err());
+    assert!(GranTurismo7Adapter::new().normalize(&buf).is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn gt7_stop_monitoring_ends_the_heartbeat_task() -> TestResult {
+    let adapter = GranTurismo7Adapter::new()
+        .with_port(0)
+        .with_console_address(std::net::Ipv4Addr::LOCALHOST.into());
+    let mut frames = adapter.start_monitoring().await?;
+    adapter.stop_monitoring().await?;
+    let ended = tokio::time::timeout(Duration::from_secs(2), frames.recv()).await?;
+    assert!(ended.is_none());
+    // Stopping again is a no-op.
+    adapter.stop_monitoring().await?;
+    Ok(())
+}
+
 // ── Basic field parsing tests ───────────────────────────────────────────
 
 #[test]
@@ -184,6 +281,23 @@ fn gt7_parse_flags() -> TestResult {
     assert!(result.flags.traction_control);
     assert!(result.flags.abs_active);
     assert!(result.flags.engine_limiter);
+    assert_eq!(
+        result.get_extended("gt7_in_race"),
+        Some(&TelemetryValue::Boolean(false))
+    );
+
+    // CarOnTrack (bit 0) | Paused (bit 1) | Loading (bit 2)
+    write_u16(&mut buf, OFF_FLAGS, 0b111);
+    let result = parse_decrypted(&buf)?;
+    assert!(result.flags.session_paused);
+    assert_eq!(
+        result.get_extended("gt7_in_race"),
+        Some(&TelemetryValue::Boolean(true))
+    );
+    assert_eq!(
+        result.get_extended("gt7_loading"),
+        Some(&TelemetryValue::Boolean(true))
+    );
     Ok(())
 }
 
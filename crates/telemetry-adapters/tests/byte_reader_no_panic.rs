@@ -0,0 +1,106 @@
+//! No-panic properties for the parsers built on `openracing-byte-reader`.
+//!
+//! Every packet decoder that reads through `ByteReader` must turn truncated,
+//! oversized or garbage input into an error or a defaulted field, never a
+//! panic. Truncated copies of well-formed packets are included so the reads
+//! near the end of each layout are exercised, not just the length guards.
+
+use proptest::prelude::*;
+use racing_wheel_telemetry_adapters::codemasters_shared::{
+    self, MIN_PACKET_SIZE, parse_codemasters_extradata, parse_codemasters_mode1_common,
+};
+use racing_wheel_telemetry_adapters::codemasters_udp::CustomUdpSpec;
+use racing_wheel_telemetry_adapters::{
+    Dirt3Adapter, Dirt4Adapter, DirtRally2Adapter, F1_25Adapter, F1Adapter, F1NativeAdapter,
+    ForzaAdapter, GranTurismo7Adapter, Grid2019Adapter, GridAutosportAdapter, GridLegendsAdapter,
+    RaceDriverGridAdapter, TelemetryAdapter, f1_25,
+};
+
+fn well_formed_car_telemetry() -> Vec<u8> {
+    f1_25::build_car_telemetry_packet(0, 250, 6, 11_000, 1.0, 0.0, 1, [23.0; 4])
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(500))]
+
+    #[test]
+    fn custom_udp_decode_never_panics(
+        mode in 0u8..4,
+        data in proptest::collection::vec(any::<u8>(), 0..512)
+    ) {
+        let spec = CustomUdpSpec::from_mode(mode);
+        if spec.decode(&data).is_ok() {
+            prop_assert!(data.len() >= spec.expected_bytes());
+        }
+    }
+
+    #[test]
+    fn f1_25_parsers_never_panic(
+        data in proptest::collection::vec(any::<u8>(), 0..2048),
+        player_index in 0usize..48
+    ) {
+        let _ = f1_25::parse_header(&data);
+        let _ = f1_25::parse_car_telemetry(&data, player_index);
+        let _ = f1_25::parse_car_status(&data, player_index);
+        let _ = f1_25::parse_session_data(&data);
+        let _ = f1_25::parse_lap_penalties(&data, player_index);
+    }
+
+    #[test]
+    fn truncated_f1_25_packets_error_instead_of_panicking(cut in 0usize..2048) {
+        let packet = well_formed_car_telemetry();
+        let truncated = packet.get(..cut.min(packet.len())).unwrap_or_default();
+        let result = f1_25::parse_car_telemetry(truncated, 0);
+        if truncated.len() < packet.len() {
+            prop_assert!(result.is_err());
+        }
+        let _ = F1_25Adapter::new().normalize(truncated);
+        let _ = F1NativeAdapter::new().normalize(truncated);
+    }
+
+    #[test]
+    fn codemasters_shared_parsers_never_panic(
+        data in proptest::collection::vec(any::<u8>(), 0..1024),
+        offset in any::<usize>()
+    ) {
+        let _ = codemasters_shared::read_f32(&data, offset);
+        let _ = parse_codemasters_mode1_common(&data, "fuzz");
+        let _ = parse_codemasters_extradata(&data, "fuzz");
+    }
+
+    #[test]
+    fn truncated_codemasters_packets_never_panic(
+        packet in proptest::collection::vec(any::<u8>(), MIN_PACKET_SIZE),
+        cut in 0usize..=MIN_PACKET_SIZE
+    ) {
+        // Every cut below the full layout, including each extradata level's
+        // exact length, must fail or parse without panicking.
+        let truncated = &packet[..cut];
+        let result = parse_codemasters_mode1_common(truncated, "fuzz");
+        if cut < MIN_PACKET_SIZE {
+            prop_assert!(result.is_err());
+        }
+        let _ = parse_codemasters_extradata(truncated, "fuzz");
+        let _ = DirtRally2Adapter::new().normalize(truncated);
+        let _ = Grid2019Adapter::new().normalize(truncated);
+    }
+
+    #[test]
+    fn udp_adapters_never_panic(
+        data in proptest::collection::vec(any::<u8>(), 0..1024),
+        mode in 0u8..4
+    ) {
+        let _ = F1_25Adapter::new().normalize(&data);
+        let _ = F1NativeAdapter::new().normalize(&data);
+        let _ = F1Adapter::new().with_mode(mode).normalize(&data);
+        let _ = ForzaAdapter::new().normalize(&data);
+        let _ = GranTurismo7Adapter::new().normalize(&data);
+        let _ = Dirt3Adapter::new().normalize(&data);
+        let _ = Dirt4Adapter::new().normalize(&data);
+        let _ = DirtRally2Adapter::new().normalize(&data);
+        let _ = Grid2019Adapter::new().normalize(&data);
+        let _ = GridAutosportAdapter::new().normalize(&data);
+        let _ = GridLegendsAdapter::new().normalize(&data);
+        let _ = RaceDriverGridAdapter::new().normalize(&data);
+    }
+}
@@ -0,0 +1,171 @@
+//! Legacy Codemasters `extradata` levels 0–3, decoded from captured DiRT
+//! Rally 2.0 datagrams of one moment on a stage at every level.
+
+use proptest::prelude::*;
+use racing_wheel_telemetry_adapters::{
+    CustomUdpSpec, Dirt4Adapter, DirtRally2Adapter, ExtendedKey, NormalizedTelemetry,
+    TelemetryAdapter, TelemetryValue,
+};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const EXTRADATA_0: &[u8] = include_bytes!("fixtures/dirt_rally_2/extradata_0.bin");
+const EXTRADATA_1: &[u8] = include_bytes!("fixtures/dirt_rally_2/extradata_1.bin");
+const EXTRADATA_2: &[u8] = include_bytes!("fixtures/dirt_rally_2/extradata_2.bin");
+const EXTRADATA_3: &[u8] = include_bytes!("fixtures/dirt_rally_2/extradata_3.bin");
+
+const FIXTURES: [&[u8]; 4] = [EXTRADATA_0, EXTRADATA_1, EXTRADATA_2, EXTRADATA_3];
+
+fn float(telemetry: &NormalizedTelemetry, key: ExtendedKey) -> Option<f32> {
+    match telemetry.extended.get(key.name) {
+        Some(TelemetryValue::Float(value)) => Some(*value),
+        _ => None,
+    }
+}
+
+#[test]
+fn detect_mode_matches_each_spec_length() -> TestResult {
+    let specs = [
+        CustomUdpSpec::mode0(),
+        CustomUdpSpec::mode1(),
+        CustomUdpSpec::mode2(),
+        CustomUdpSpec::mode3(),
+    ];
+    for (mode, (spec, fixture)) in (0u8..).zip(specs.iter().zip(FIXTURES)) {
+        assert_eq!(spec.expected_bytes(), fixture.len());
+        assert_eq!(CustomUdpSpec::detect_mode(fixture.len()), Some(mode));
+    }
+    for len in [0, 28, 60, 151, 153, 263, 265, 1024] {
+        assert_eq!(CustomUdpSpec::detect_mode(len), None, "{len}");
+    }
+    Ok(())
+}
+
+#[test]
+fn every_level_decodes_the_shared_prefix() -> TestResult {
+    let specs = [
+        CustomUdpSpec::mode0(),
+        CustomUdpSpec::mode1(),
+        CustomUdpSpec::mode2(),
+        CustomUdpSpec::mode3(),
+    ];
+    for (spec, fixture) in specs.iter().zip(FIXTURES) {
+        let decoded = spec.decode(fixture)?;
+        assert_eq!(decoded.values.get("lapdistance"), Some(&2450.5));
+        assert_eq!(decoded.values.get("gear"), Some(&3.0));
+        assert_eq!(decoded.values.get("enginerate"), Some(&6150.0));
+    }
+    let mode0 = CustomUdpSpec::mode0().decode(EXTRADATA_0)?;
+    assert_eq!(mode0.values.len(), 38);
+    assert_eq!(mode0.values.get("wheelspeedfl"), Some(&27.1));
+    assert!(!mode0.values.contains_key("fuelintank"));
+
+    let mode2 = CustomUdpSpec::mode2().decode(EXTRADATA_2)?;
+    assert_eq!(mode2.values.get("sector1time"), Some(&48.7));
+    assert!(!mode2.values.contains_key("maxrpm"));
+
+    let mode3 = CustomUdpSpec::mode3().decode(EXTRADATA_3)?;
+    assert_eq!(mode3.values.get("tracklength"), Some(&9210.0));
+    assert_eq!(mode3.values.get("maxrpm"), Some(&7500.0));
+    assert_eq!(mode3.values.get("maxgears"), Some(&6.0));
+    Ok(())
+}
+
+#[test]
+fn dirt_rally_2_normalizes_every_level() -> TestResult {
+    let adapter = DirtRally2Adapter::new();
+    for fixture in FIXTURES {
+        let t = adapter.normalize(fixture)?;
+        assert!((t.speed_ms - 27.325).abs() < 0.001, "{}", t.speed_ms);
+        assert_eq!(t.rpm, 6150.0);
+        assert_eq!(t.gear, 3);
+        assert!((t.throttle - 0.82).abs() < 0.001);
+        assert_eq!(t.lap, 1);
+        assert_eq!(float(&t, ExtendedKey::LAP_DISTANCE_M), Some(2450.5));
+    }
+
+    let mode0 = adapter.normalize(EXTRADATA_0)?;
+    assert_eq!(mode0.position, 0);
+    assert_eq!(mode0.fuel_percent, 0.0);
+    assert_eq!(float(&mode0, ExtendedKey::SECTOR_1_TIME_S), None);
+
+    let mode1 = adapter.normalize(EXTRADATA_1)?;
+    assert_eq!(mode1.position, 1);
+    assert!((mode1.fuel_percent - 38.0 / 60.0).abs() < 0.001);
+    assert_eq!(float(&mode1, ExtendedKey::SECTOR_1_TIME_S), None);
+
+    let mode2 = adapter.normalize(EXTRADATA_2)?;
+    assert_eq!(float(&mode2, ExtendedKey::SECTOR_1_TIME_S), Some(48.7));
+    assert_eq!(float(&mode2, ExtendedKey::SECTOR_2_TIME_S), Some(0.0));
+    assert_eq!(float(&mode2, ExtendedKey::TRACK_LENGTH_M), None);
+    assert_eq!(mode2.max_rpm, 0.0);
+
+    let mode3 = adapter.normalize(EXTRADATA_3)?;
+    assert_eq!(float(&mode3, ExtendedKey::TRACK_LENGTH_M), Some(9210.0));
+    assert_eq!(mode3.max_rpm, 7500.0);
+    assert_eq!(mode3.num_gears, 6);
+    let rpm_fraction = float(&mode3, ExtendedKey::RPM_FRACTION).ok_or("no rpm fraction")?;
+    assert!((rpm_fraction - 0.82).abs() < 0.001);
+    Ok(())
+}
+
+#[test]
+fn dirt4_detects_the_level_per_packet() -> TestResult {
+    let dirt4 = Dirt4Adapter::new();
+    let dirt_rally_2 = DirtRally2Adapter::new();
+    for fixture in FIXTURES {
+        assert_eq!(
+            serde_json::to_value(dirt4.normalize(fixture)?)?,
+            serde_json::to_value(dirt_rally_2.normalize(fixture)?)?
+        );
+    }
+    assert!(dirt4.normalize(&EXTRADATA_3[..200]).is_err());
+    Ok(())
+}
+
+#[test]
+fn recognition_names_the_level() -> TestResult {
+    let adapter = DirtRally2Adapter::new();
+    for (mode, fixture) in (0u8..).zip(FIXTURES) {
+        let Some(found) = adapter.recognize_packet(fixture) else {
+            return Err(format!("extradata={mode} fixture not recognized").into());
+        };
+        if mode == 3 {
+            assert_eq!(found.format, "Codemasters Mode 1 format");
+        } else {
+            assert_eq!(found.format, format!("Codemasters extradata={mode} format"));
+        }
+    }
+    assert!(adapter.recognize_packet(&EXTRADATA_3[..200]).is_none());
+    Ok(())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(500))]
+
+    /// Cutting a datagram short anywhere never panics, and only lengths of
+    /// a whole extradata level decode.
+    #[test]
+    fn prop_truncated_packets_never_panic(len in 0usize..=264, noise in any::<u8>()) {
+        let mut packet = EXTRADATA_3.to_vec();
+        if let Some(byte) = packet.get_mut(len.saturating_sub(1)) {
+            *byte ^= noise;
+        }
+        packet.truncate(len);
+        for adapter in [
+            Box::new(DirtRally2Adapter::new()) as Box<dyn TelemetryAdapter>,
+            Box::new(Dirt4Adapter::new()),
+        ] {
+            let decoded = adapter.normalize(&packet);
+            prop_assert_eq!(decoded.is_ok(), CustomUdpSpec::detect_mode(len).is_some());
+        }
+        for spec in [
+            CustomUdpSpec::mode0(),
+            CustomUdpSpec::mode1(),
+            CustomUdpSpec::mode2(),
+            CustomUdpSpec::mode3(),
+        ] {
+            prop_assert_eq!(spec.decode(&packet).is_ok(), len >= spec.expected_bytes());
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! Transport failover of the composite adapter.
+
+use std::time::Duration;
+
+use racing_wheel_telemetry_adapters::{
+    CompositeAdapter, FailoverConfig, FaultScript, FaultyMockAdapter, MockAdapter,
+    NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver, TransportSwitched,
+};
+use tokio::sync::broadcast;
+use tokio::time::timeout;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const PRIMARY_RPM: f32 = 1000.0;
+const FALLBACK_RPM: f32 = 2000.0;
+
+fn primary_frame(_sequence: u64) -> NormalizedTelemetry {
+    NormalizedTelemetry::builder().rpm(PRIMARY_RPM).build()
+}
+
+fn fallback_frame(_sequence: u64) -> NormalizedTelemetry {
+    NormalizedTelemetry::builder().rpm(FALLBACK_RPM).build()
+}
+
+fn quick_failover() -> FailoverConfig {
+    FailoverConfig {
+        failure_timeout: Duration::from_millis(100),
+        probe_interval: Duration::from_millis(50),
+        probe_window: Duration::from_millis(500),
+        recovery_frames: 5,
+    }
+}
+
+/// A composite of a scripted primary and a clean fallback, both on
+/// "mock_game".
+fn composite(script: FaultScript) -> CompositeAdapter {
+    let primary = FaultyMockAdapter::new("mock_game", script)
+        .with_update_rate(Duration::from_millis(2))
+        .with_frame_source(primary_frame);
+    let fallback = MockAdapter::with_update_rate("mock_game".to_string(), Duration::from_millis(2))
+        .with_frame_source(fallback_frame);
+    CompositeAdapter::new("mock_game")
+        .with_transport("primary", Box::new(primary))
+        .with_transport("fallback", Box::new(fallback))
+        .with_failover(quick_failover())
+}
+
+async fn next_frame(
+    rx: &mut TelemetryReceiver,
+) -> Result<TelemetryFrame, Box<dyn std::error::Error>> {
+    Ok(timeout(Duration::from_secs(5), rx.recv())
+        .await?
+        .ok_or("composite stream ended")?)
+}
+
+async fn next_switch(
+    events: &mut broadcast::Receiver<TransportSwitched>,
+) -> Result<TransportSwitched, Box<dyn std::error::Error>> {
+    Ok(timeout(Duration::from_secs(5), events.recv()).await??)
+}
+
+fn assert_continuous(frames: &[TelemetryFrame]) {
+    for (expected, frame) in frames.iter().enumerate() {
+        assert_eq!(frame.sequence, expected as u64);
+    }
+}
+
+#[tokio::test]
+async fn ended_primary_switches_to_fallback() -> TestResult {
+    // The primary dies after 10 frames and never starts again.
+    let adapter = composite(
+        FaultScript::new(1)
+            .end_after(10)
+            .fail_starts_from(2, u32::MAX),
+    );
+    let mut events = adapter.subscribe_transport_switches();
+    let mut rx = adapter.start_monitoring().await?;
+    assert_eq!(adapter.active_transport(), Some("primary"));
+
+    let mut frames = Vec::new();
+    while frames.len() < 40 {
+        frames.push(next_frame(&mut rx).await?);
+    }
+    assert!(
+        frames[..10]
+            .iter()
+            .all(|frame| frame.data.rpm == PRIMARY_RPM)
+    );
+    assert!(
+        frames[10..]
+            .iter()
+            .all(|frame| frame.data.rpm == FALLBACK_RPM)
+    );
+    assert_continuous(&frames);
+
+    let switch = next_switch(&mut events).await?;
+    assert_eq!(switch.game_id, "mock_game");
+    assert_eq!(
+        (switch.from.as_str(), switch.to.as_str()),
+        ("primary", "fallback")
+    );
+    assert_eq!(switch.reason, "stream ended");
+    assert_eq!(adapter.active_transport(), Some("fallback"));
+
+    adapter.stop_monitoring().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn silent_primary_fails_over_on_disconnection() -> TestResult {
+    let adapter = composite(
+        FaultScript::new(2)
+            .stall_after(5, Duration::from_secs(30))
+            .fail_starts_from(2, u32::MAX),
+    );
+    let mut events = adapter.subscribe_transport_switches();
+    let mut rx = adapter.start_monitoring().await?;
+
+    let mut frames = Vec::new();
+    while frames.len() < 20 {
+        frames.push(next_frame(&mut rx).await?);
+    }
+    assert_continuous(&frames);
+    let switch = next_switch(&mut events).await?;
+    assert_eq!(switch.to, "fallback");
+    assert!(
+        switch.reason.starts_with("no data for"),
+        "{}",
+        switch.reason
+    );
+
+    adapter.stop_monitoring().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn recovered_primary_is_switched_back_to() -> TestResult {
+    // The first probe of the primary fails to start; the second recovers.
+    let adapter = composite(FaultScript::new(3).end_after(40).fail_starts_from(2, 1));
+    let mut events = adapter.subscribe_transport_switches();
+    let mut rx = adapter.start_monitoring().await?;
+
+    let mut frames = Vec::new();
+    let mut switches = Vec::new();
+    while switches.len() < 2 {
+        frames.push(next_frame(&mut rx).await?);
+        while let Ok(switch) = events.try_recv() {
+            switches.push(switch);
+        }
+    }
+    for _ in 0..10 {
+        frames.push(next_frame(&mut rx).await?);
+    }
+
+    assert_eq!(
+        (switches[0].from.as_str(), switches[0].to.as_str()),
+        ("primary", "fallback")
+    );
+    assert_eq!(
+        (switches[1].from.as_str(), switches[1].to.as_str()),
+        ("fallback", "primary")
+    );
+    assert!(
+        switches[1].reason.starts_with("recovered"),
+        "{}",
+        switches[1].reason
+    );
+    assert!(switches[0].timestamp_ns <= switches[1].timestamp_ns);
+
+    // Primary, then fallback, then primary again, without gaps.
+    assert_continuous(&frames);
+    let first_fallback = frames
+        .iter()
+        .position(|frame| frame.data.rpm == FALLBACK_RPM)
+        .ok_or("no fallback frame")?;
+    let back_on_primary = frames[first_fallback..]
+        .iter()
+        .position(|frame| frame.data.rpm == PRIMARY_RPM)
+        .ok_or("never switched back")?;
+    assert_eq!(first_fallback, 40);
+    assert!(back_on_primary > 0);
+
+    adapter.stop_monitoring().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn no_transport_starting_is_an_error() -> TestResult {
+    let adapter = CompositeAdapter::new("mock_game").with_transport(
+        "primary",
+        Box::new(FaultyMockAdapter::new(
+            "mock_game",
+            FaultScript::new(4).fail_starts(1),
+        )),
+    );
+    let err = adapter
+        .start_monitoring()
+        .await
+        .err()
+        .ok_or("start succeeded")?;
+    assert!(
+        err.to_string()
+            .contains("no transport of mock_game started"),
+        "{err}"
+    );
+    assert!(
+        CompositeAdapter::new("mock_game")
+            .start_monitoring()
+            .await
+            .is_err()
+    );
+    Ok(())
+}
@@ -0,0 +1,95 @@
+//! Field coverage derived from fixtures, and the docs table rendered from it.
+
+use std::fs;
+
+use racing_wheel_telemetry_adapters::coverage_probe::{
+    derive_coverage, derive_coverage_all, render_coverage_markdown,
+};
+use racing_wheel_telemetry_adapters::{LFSAdapter, MockAdapter, TelemetryAdapter};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const OUTGAUGE_PACKET_SIZE: usize = 92;
+const OFF_GEAR: usize = 10;
+const OFF_SPEED: usize = 12;
+const OFF_RPM: usize = 16;
+
+/// An OutGauge packet in third gear at 4000 rpm and 30 m/s.
+fn outgauge_packet() -> Vec<u8> {
+    let mut packet = vec![0u8; OUTGAUGE_PACKET_SIZE];
+    packet[OFF_GEAR] = 4;
+    packet[OFF_SPEED..OFF_SPEED + 4].copy_from_slice(&30.0f32.to_le_bytes());
+    packet[OFF_RPM..OFF_RPM + 4].copy_from_slice(&4000.0f32.to_le_bytes());
+    packet
+}
+
+#[test]
+fn fixture_with_rpm_but_no_slip_reports_exactly_that() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let game_dir = dir.path().join("live_for_speed");
+    fs::create_dir(&game_dir)?;
+    fs::write(game_dir.join("third_gear.bin"), outgauge_packet())?;
+    fs::write(game_dir.join("notes.txt"), "not a payload")?;
+    // Not a registered game.
+    fs::create_dir(dir.path().join("bench_rig"))?;
+
+    let coverages = derive_coverage_all(dir.path())?;
+    assert_eq!(coverages.len(), 1);
+    let coverage = &coverages[0];
+    assert_eq!(coverage.game_id, "live_for_speed");
+    assert!(coverage.rpm);
+    assert!(coverage.speed);
+    assert!(coverage.gear);
+    assert!(!coverage.slip_ratio);
+    assert!(!coverage.ffb_scalar);
+    assert!(!coverage.car_id);
+    assert!(!coverage.penalties);
+
+    // The same payload straight through the adapter agrees.
+    let direct = derive_coverage(&LFSAdapter::new(), &[outgauge_packet()]);
+    assert_eq!(direct.extended_fields, coverage.extended_fields);
+    let mut sorted = direct.extended_fields.clone();
+    sorted.sort();
+    assert_eq!(direct.extended_fields, sorted);
+    Ok(())
+}
+
+#[test]
+fn undecodable_samples_cover_nothing() {
+    let adapter = LFSAdapter::new();
+    let coverage = derive_coverage(&adapter, &[vec![0u8; 3], Vec::new()]);
+    assert_eq!(coverage.game_id, adapter.game_id());
+    assert!(!coverage.rpm && !coverage.speed && !coverage.gear);
+    assert!(coverage.extended_fields.is_empty());
+}
+
+#[test]
+fn markdown_table_matches_golden() {
+    let mock = MockAdapter::new("bench_rig".to_string());
+    let mut rich = derive_coverage(&LFSAdapter::new(), &[outgauge_packet()]);
+    rich.flags.pit_limiter = true;
+    rich.flags.yellow_flag = true;
+    rich.timing.last_lap = true;
+    rich.timing.lap_number = true;
+    // Input order does not matter.
+    let coverages = [rich, derive_coverage(&mock, &[Vec::new()])];
+    let table = render_coverage_markdown(&coverages);
+    insta::assert_snapshot!(table);
+
+    let reversed = [coverages[1].clone(), coverages[0].clone()];
+    assert_eq!(render_coverage_markdown(&reversed), table);
+}
+
+#[test]
+fn empty_fixture_dir_yields_an_empty_table() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let coverages = derive_coverage_all(dir.path())?;
+    assert!(coverages.is_empty());
+
+    let table = render_coverage_markdown(&coverages);
+    let lines: Vec<_> = table.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("| Game |"));
+    assert_eq!(lines[0].matches('|').count(), lines[1].matches('|').count());
+    Ok(())
+}
@@ -1113,7 +1113,14 @@ mod gt7_deep {
     fn standard_packet_has_no_extended_fields() -> TestResult {
         let buf = make_buf();
         let t = gran_turismo_7::parse_decrypted_ext(&buf)?;
-        assert!(t.extended.is_empty(), "296-byte packet → no extended data");
+        assert!(
+            t.get_extended("gt7_sway").is_none(),
+            "296-byte packet → no motion data"
+        );
+        assert!(
+            t.get_extended("gt7_energy_recovery").is_none(),
+            "296-byte packet → no energy recovery"
+        );
         Ok(())
     }
 
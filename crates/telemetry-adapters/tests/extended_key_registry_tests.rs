@@ -0,0 +1,84 @@
+//! Extended-key registry conformance for the rally / per-wheel adapters.
+//!
+//! Every key these adapters emit must be declared in the registry catalog, and
+//! the unknown-key scan must flag anything that is not.
+
+use racing_wheel_telemetry_adapters::{
+    DirtShowdownAdapter, TelemetryAdapter, TelemetryValue, WrcGenerationsAdapter, WtcrAdapter,
+    wrc_kylotonn::{WrcKylotonnAdapter, WrcKylotonnVariant},
+};
+use racing_wheel_telemetry_core::contracts::{ExtendedKey, resolve_key, scan_unknown_keys};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn make_packet(size: usize) -> Vec<u8> {
+    vec![0u8; size]
+}
+
+fn rally_adapters() -> Vec<(Box<dyn TelemetryAdapter>, usize)> {
+    vec![
+        (
+            Box::new(WrcKylotonnAdapter::new(WrcKylotonnVariant::Wrc10)),
+            96,
+        ),
+        (Box::new(WrcGenerationsAdapter::new()), 264),
+        (Box::new(WtcrAdapter::new()), 264),
+        (Box::new(DirtShowdownAdapter::new()), 264),
+    ]
+}
+
+#[test]
+fn rally_adapters_emit_only_registered_keys() -> TestResult {
+    for (adapter, size) in rally_adapters() {
+        let normalized = adapter.normalize(&make_packet(size))?;
+        assert!(
+            normalized
+                .extended
+                .contains_key(ExtendedKey::WHEEL_SPEED_FL.name),
+            "{} should emit per-wheel speeds",
+            adapter.game_id()
+        );
+        assert_eq!(
+            scan_unknown_keys(&normalized),
+            Vec::<String>::new(),
+            "{} emitted unregistered keys",
+            adapter.game_id()
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn unknown_key_scan_flags_unregistered_fixture_key() -> TestResult {
+    let adapter = WrcKylotonnAdapter::new(WrcKylotonnVariant::Wrc10);
+    let normalized = adapter
+        .normalize(&make_packet(96))?
+        .with_extended("tyre_wear_fl_typo", TelemetryValue::Float(0.4));
+
+    assert_eq!(scan_unknown_keys(&normalized), vec!["tyre_wear_fl_typo"]);
+    Ok(())
+}
+
+#[test]
+fn deprecated_spelling_is_known_and_resolves_to_canonical() -> TestResult {
+    let normalized = WrcKylotonnAdapter::new(WrcKylotonnVariant::Wrc10)
+        .normalize(&make_packet(96))?
+        .with_extended("tyre_temp_rr", TelemetryValue::Float(90.0));
+
+    assert!(scan_unknown_keys(&normalized).is_empty());
+    assert_eq!(resolve_key("tyre_temp_rr"), Some(ExtendedKey::TIRE_TEMP_RR));
+    Ok(())
+}
+
+#[test]
+fn typed_setter_rejects_mismatched_value() -> TestResult {
+    let mut normalized = WtcrAdapter::new().normalize(&make_packet(264))?;
+
+    assert!(
+        ExtendedKey::STAGE_PROGRESS
+            .set(&mut normalized, "halfway")
+            .is_err()
+    );
+    assert!(!normalized.extended.contains_key("stage_progress"));
+    Ok(())
+}
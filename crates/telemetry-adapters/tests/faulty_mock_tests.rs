@@ -0,0 +1,170 @@
+//! Scripted faults of the fault-injection mock adapter.
+
+use std::time::{Duration, Instant};
+
+use racing_wheel_telemetry_adapters::{
+    ABSURD_SPEED_MS, FaultScript, FaultyMockAdapter, MockAdapter, NormalizedTelemetry,
+    TelemetryAdapter, TelemetryFrame,
+};
+use tokio::time::timeout;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const RATE: Duration = Duration::from_millis(1);
+
+fn adapter(script: FaultScript) -> FaultyMockAdapter {
+    FaultyMockAdapter::new("faulty_mock", script).with_update_rate(RATE)
+}
+
+/// Every frame of one stream, which the script must end.
+async fn collect(
+    adapter: &FaultyMockAdapter,
+) -> Result<Vec<TelemetryFrame>, Box<dyn std::error::Error>> {
+    let mut rx = adapter.start_monitoring().await?;
+    let mut frames = Vec::new();
+    while let Some(frame) = timeout(Duration::from_secs(5), rx.recv()).await? {
+        frames.push(frame);
+    }
+    Ok(frames)
+}
+
+fn sequences(frames: &[TelemetryFrame]) -> Vec<u64> {
+    frames.iter().map(|frame| frame.sequence).collect()
+}
+
+#[tokio::test]
+async fn clean_script_ends_after_the_requested_frames() -> TestResult {
+    let frames = collect(&adapter(FaultScript::new(1).end_after(20))).await?;
+    assert_eq!(sequences(&frames), (0..20).collect::<Vec<_>>());
+    assert!(frames.iter().all(|frame| frame.data.all_finite()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn same_seed_gives_the_same_faults() -> TestResult {
+    let script = FaultScript::new(42)
+        .drop_frames(0.2)
+        .reorder_frames(0.2)
+        .nan_rpm(0.1)
+        .end_after(200);
+    let first = collect(&adapter(script.clone())).await?;
+    let second = collect(&adapter(script)).await?;
+    assert_eq!(sequences(&first), sequences(&second));
+    let nan = |frames: &[TelemetryFrame]| -> Vec<bool> {
+        frames.iter().map(|frame| frame.data.rpm.is_nan()).collect()
+    };
+    assert_eq!(nan(&first), nan(&second));
+
+    let other = collect(&adapter(
+        FaultScript::new(43).drop_frames(0.2).end_after(200),
+    ))
+    .await?;
+    assert_ne!(sequences(&first), sequences(&other));
+    Ok(())
+}
+
+#[tokio::test]
+async fn drops_leave_gaps_and_reorders_swap_neighbours() -> TestResult {
+    let dropped = collect(&adapter(
+        FaultScript::new(7).drop_frames(0.3).end_after(100),
+    ))
+    .await?;
+    let seqs = sequences(&dropped);
+    assert_eq!(seqs.len(), 100);
+    assert!(seqs.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(
+        seqs.windows(2).any(|pair| pair[1] - pair[0] > 1),
+        "{seqs:?}"
+    );
+
+    let reordered = collect(&adapter(
+        FaultScript::new(7).reorder_frames(0.3).end_after(100),
+    ))
+    .await?;
+    let seqs = sequences(&reordered);
+    assert!(seqs.windows(2).any(|pair| pair[0] > pair[1]), "{seqs:?}");
+    let mut sorted = seqs.clone();
+    sorted.sort_unstable();
+    // Nothing is lost, only moved.
+    assert_eq!(sorted, (0..sorted.len() as u64).collect::<Vec<_>>());
+    Ok(())
+}
+
+#[tokio::test]
+async fn corrupt_frames_carry_nan_rpm_or_absurd_speed() -> TestResult {
+    let frames = collect(&adapter(
+        FaultScript::new(3)
+            .nan_rpm(0.5)
+            .absurd_speed(0.5)
+            .end_after(50),
+    ))
+    .await?;
+    assert!(frames.iter().any(|frame| frame.data.rpm.is_nan()));
+    assert!(
+        frames
+            .iter()
+            .any(|frame| frame.data.speed_ms == ABSURD_SPEED_MS)
+    );
+    assert!(frames.iter().any(|frame| frame.data.all_finite()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn scripted_start_failures_then_sequences_continue() -> TestResult {
+    let adapter = adapter(FaultScript::new(0).fail_starts_from(2, 2).end_after(5));
+    let first = collect(&adapter).await?;
+    assert!(adapter.start_monitoring().await.is_err());
+    assert!(adapter.start_monitoring().await.is_err());
+    let second = collect(&adapter).await?;
+    assert_eq!(adapter.start_attempts(), 4);
+    assert_eq!(sequences(&first), vec![0, 1, 2, 3, 4]);
+    assert_eq!(sequences(&second), vec![5, 6, 7, 8, 9]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn stream_stalls_after_the_configured_frames() -> TestResult {
+    let stall = Duration::from_millis(150);
+    let adapter = adapter(FaultScript::new(0).stall_after(3, stall).end_after(6));
+    let mut rx = adapter.start_monitoring().await?;
+    for _ in 0..3 {
+        timeout(Duration::from_secs(1), rx.recv()).await?;
+    }
+    let before = Instant::now();
+    timeout(Duration::from_secs(1), rx.recv()).await?;
+    assert!(
+        before.elapsed() >= stall - RATE * 2,
+        "{:?}",
+        before.elapsed()
+    );
+    Ok(())
+}
+
+fn fixed_gear(sequence: u64) -> NormalizedTelemetry {
+    NormalizedTelemetry::builder()
+        .rpm(1_000.0 + sequence as f32)
+        .gear(3)
+        .build()
+}
+
+#[tokio::test]
+async fn frame_sources_drive_both_mock_adapters() -> TestResult {
+    let faulty =
+        collect(&adapter(FaultScript::new(0).end_after(3)).with_frame_source(fixed_gear)).await?;
+    let rpm: Vec<f32> = faulty.iter().map(|frame| frame.data.rpm).collect();
+    assert_eq!(rpm, vec![1_000.0, 1_001.0, 1_002.0]);
+
+    let mock = MockAdapter::new("mock".to_string()).with_frame_source(fixed_gear);
+    let mut rx = mock.start_monitoring().await?;
+    for sequence in 0..3u64 {
+        let frame = timeout(Duration::from_secs(1), rx.recv())
+            .await?
+            .ok_or("mock stream ended")?;
+        assert_eq!(frame.sequence, sequence);
+        assert_eq!(
+            (frame.data.rpm, frame.data.gear),
+            (1_000.0 + sequence as f32, 3)
+        );
+    }
+    Ok(())
+}
@@ -7,7 +7,7 @@ mod helpers;
 
 use helpers::write_f32_le;
 use racing_wheel_telemetry_adapters::{
-    TelemetryAdapter,
+    TelemetryAdapter, TelemetryValue,
     acc::ACCAdapter,
     beamng::BeamNGAdapter,
     dirt_rally_2::DirtRally2Adapter,
@@ -44,7 +44,7 @@ const IR_OFF_STEER_ANGLE: usize = 28; // f32
 // slip ratio + tire rps fields: offsets 48..80 (not used in these golden tests)
 const IR_OFF_LAP_CURRENT: usize = 80; // i32
 const IR_OFF_LAP_BEST_TIME: usize = 84; // f32
-// IR_OFF_FUEL_LEVEL = 88 (not used in these golden tests)
+const IR_OFF_FUEL_LEVEL: usize = 88; // f32, l
 const IR_OFF_FUEL_LEVEL_PCT: usize = 92; // f32
 const IR_OFF_ON_PIT_ROAD: usize = 96; // i32
 const IR_OFF_CLUTCH: usize = 100; // f32
@@ -63,6 +63,18 @@ const IR_OFF_WATER_TEMP: usize = 160; // f32
 const IR_OFF_CAR_PATH: usize = 164; // [u8; 64]
 const IR_OFF_TRACK_NAME: usize = 228; // [u8; 64]
 const IR_DATA_SIZE: usize = 292;
+// Optional pit/fuel block appended after the track name.
+const IR_OFF_PIT_FUEL_VARS: usize = 292; // u32 presence bits
+const IR_OFF_FUEL_USE_PER_HOUR: usize = 296; // f32, kg/h
+const IR_OFF_FUEL_KG_PER_LTR: usize = 300; // f32
+const IR_OFF_PIT_SV_FUEL: usize = 304; // f32, l
+const IR_OFF_PIT_SV_FLAGS: usize = 308; // u32
+const IR_OFF_PIT_SV_STATUS: usize = 312; // i32
+const IR_OFF_FAST_REPAIR_USED: usize = 316; // i32
+const IR_OFF_FAST_REPAIR_AVAILABLE: usize = 320; // i32
+const IR_OFF_PIT_REPAIR_LEFT: usize = 324; // f32, s
+const IR_OFF_PIT_OPT_REPAIR_LEFT: usize = 328; // f32, s
+const IR_DATA_WITH_PIT_FUEL_SIZE: usize = 332;
 
 fn make_iracing_golden() -> Vec<u8> {
     let mut buf = vec![0u8; IR_DATA_SIZE];
@@ -132,6 +144,71 @@ fn golden_iracing_full_packet() -> TestResult {
     Ok(())
 }
 
+#[test]
+fn golden_iracing_pit_and_fuel_vars() -> TestResult {
+    let adapter = IRacingAdapter::new();
+    let mut data = make_iracing_golden();
+    data.resize(IR_DATA_WITH_PIT_FUEL_SIZE, 0);
+    write_f32_le(&mut data, IR_OFF_FUEL_LEVEL, 41.5);
+    write_u32_le(&mut data, IR_OFF_PIT_FUEL_VARS, 0x3ff); // every var present
+    write_f32_le(&mut data, IR_OFF_FUEL_USE_PER_HOUR, 45.0);
+    write_f32_le(&mut data, IR_OFF_FUEL_KG_PER_LTR, 0.75);
+    write_f32_le(&mut data, IR_OFF_PIT_SV_FUEL, 22.5);
+    // LF + RR tyres, fuel fill.
+    write_u32_le(&mut data, IR_OFF_PIT_SV_FLAGS, 0x01 | 0x08 | 0x10);
+    write_i32_le(&mut data, IR_OFF_PIT_SV_STATUS, 104);
+    write_i32_le(&mut data, IR_OFF_FAST_REPAIR_USED, 1);
+    write_i32_le(&mut data, IR_OFF_FAST_REPAIR_AVAILABLE, 0);
+    write_f32_le(&mut data, IR_OFF_PIT_REPAIR_LEFT, 12.0);
+    write_f32_le(&mut data, IR_OFF_PIT_OPT_REPAIR_LEFT, 30.0);
+    let t = adapter.normalize(&data)?;
+
+    let float = |key: &str| match t.extended.get(key) {
+        Some(TelemetryValue::Float(value)) => Some(*value),
+        _ => None,
+    };
+    let boolean = |key: &str| match t.extended.get(key) {
+        Some(TelemetryValue::Boolean(value)) => Some(*value),
+        _ => None,
+    };
+    assert_eq!(float("fuel_left_l"), Some(41.5));
+    assert_eq!(float("fuel_use_kg_h"), Some(45.0));
+    let litres_per_hour = float("fuel_use_l_h").ok_or("fuel_use_l_h missing")?;
+    assert!((litres_per_hour - 60.0).abs() < 1e-3);
+    assert_eq!(float("pit_fuel_to_add_l"), Some(22.5));
+    assert_eq!(float("pit_repair_left_s"), Some(12.0));
+    assert_eq!(float("pit_opt_repair_left_s"), Some(30.0));
+    assert_eq!(boolean("pit_tire_change_fl"), Some(true));
+    assert_eq!(boolean("pit_tire_change_fr"), Some(false));
+    assert_eq!(boolean("pit_tire_change_rl"), Some(false));
+    assert_eq!(boolean("pit_tire_change_rr"), Some(true));
+    assert_eq!(boolean("pit_fuel_fill"), Some(true));
+    assert_eq!(boolean("pit_windshield_tearoff"), Some(false));
+    assert_eq!(boolean("pit_fast_repair"), Some(false));
+    assert_eq!(
+        t.extended.get("pit_service_status"),
+        Some(&TelemetryValue::String("bad_angle".to_string()))
+    );
+    assert_eq!(
+        t.extended.get("fast_repairs_used"),
+        Some(&TelemetryValue::Integer(1))
+    );
+    assert_eq!(
+        t.extended.get("fast_repairs_available"),
+        Some(&TelemetryValue::Integer(0))
+    );
+    Ok(())
+}
+
+#[test]
+fn golden_iracing_packet_without_pit_fuel_block_has_no_pit_keys() -> TestResult {
+    let adapter = IRacingAdapter::new();
+    let t = adapter.normalize(&make_iracing_golden())?;
+    assert!(t.extended.keys().all(|key| !key.starts_with("pit_")));
+    assert!(!t.extended.contains_key("fuel_left_l"));
+    Ok(())
+}
+
 #[test]
 fn golden_iracing_reverse_gear() -> TestResult {
     let adapter = IRacingAdapter::new();
@@ -0,0 +1,171 @@
+//! Golden normalization fixtures: committed captures replayed through their
+//! adapters, and the harness's tolerance rules.
+
+use std::path::{Path, PathBuf};
+
+use racing_wheel_telemetry_adapters::normalization_fixtures::{
+    FixtureOptions, FixtureReport, bless, run_fixture, run_fixture_with,
+};
+use racing_wheel_telemetry_adapters::{
+    DirtRally2Adapter, F1_25Adapter, ForzaAdapter, TelemetryAdapter,
+};
+use serde_json::Value;
+
+type R = Result<(), Box<dyn std::error::Error>>;
+
+fn fixture(name: &str) -> (PathBuf, PathBuf) {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/normalization");
+    (
+        dir.join(format!("{name}.orcap")),
+        dir.join(format!("{name}.expected.json")),
+    )
+}
+
+fn check(adapter: &dyn TelemetryAdapter, name: &str) -> R {
+    let (capture, expected) = fixture(name);
+    let report = run_fixture(adapter, capture, expected)?;
+    assert!(report.records > 0);
+    assert!(report.passed(), "{report}");
+    Ok(())
+}
+
+#[test]
+fn dirt_rally_2_matches_its_golden_output() -> R {
+    check(&DirtRally2Adapter::new(), "dirt_rally_2")
+}
+
+#[test]
+fn forza_matches_its_golden_output() -> R {
+    check(&ForzaAdapter::new(), "forza_motorsport")
+}
+
+#[test]
+fn f1_25_matches_its_golden_output() -> R {
+    check(&F1_25Adapter::new(), "f1_25")
+}
+
+/// Copy the Forza expectations to a scratch file after applying `edit` to
+/// the first normalized record.
+fn edited_expectations(
+    dir: &Path,
+    edit: impl FnOnce(&mut serde_json::Map<String, Value>),
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let (_, expected) = fixture("forza_motorsport");
+    let mut json: Value = serde_json::from_str(&std::fs::read_to_string(expected)?)?;
+    let record = json
+        .pointer_mut("/records/0/normalized")
+        .and_then(Value::as_object_mut)
+        .ok_or("first record is not normalized")?;
+    edit(record);
+    let path = dir.join("edited.expected.json");
+    std::fs::write(&path, serde_json::to_string(&json)?)?;
+    Ok(path)
+}
+
+fn replay_forza(expected: &Path) -> Result<FixtureReport, Box<dyn std::error::Error>> {
+    let (capture, _) = fixture("forza_motorsport");
+    Ok(run_fixture_with(
+        &ForzaAdapter::new(),
+        capture,
+        expected,
+        FixtureOptions::default(),
+    )?)
+}
+
+fn shift_float(record: &mut serde_json::Map<String, Value>, field: &str, by: f64) {
+    if let Some(value) = record.get(field).and_then(Value::as_f64) {
+        record.insert(field.to_string(), Value::from(value + by));
+    }
+}
+
+#[test]
+fn float_drift_within_epsilon_passes() -> R {
+    let dir = tempfile::tempdir()?;
+    let expected = edited_expectations(dir.path(), |record| {
+        shift_float(record, "speed_ms", 5e-5);
+        shift_float(record, "rpm", 0.2);
+    })?;
+    let report = replay_forza(&expected)?;
+    assert!(report.passed(), "{report}");
+    Ok(())
+}
+
+#[test]
+fn float_drift_beyond_epsilon_fails() -> R {
+    let dir = tempfile::tempdir()?;
+    let expected = edited_expectations(dir.path(), |record| {
+        shift_float(record, "speed_ms", 0.01);
+    })?;
+    let report = replay_forza(&expected)?;
+    assert!(!report.passed());
+    assert_eq!(report.field_mismatches.get("speed_ms"), Some(&1));
+
+    // A looser epsilon accepts the same drift.
+    let (capture, _) = fixture("forza_motorsport");
+    let loose = FixtureOptions::default().with_epsilon(0.05);
+    assert!(run_fixture_with(&ForzaAdapter::new(), capture, &expected, loose)?.passed());
+    Ok(())
+}
+
+#[test]
+fn any_discrete_mismatch_fails() -> R {
+    let dir = tempfile::tempdir()?;
+    let expected = edited_expectations(dir.path(), |record| {
+        record.insert("gear".to_string(), Value::from(5));
+        if let Some(flags) = record.get_mut("flags").and_then(Value::as_object_mut) {
+            flags.insert("yellow_flag".to_string(), Value::from(true));
+        }
+    })?;
+    let report = replay_forza(&expected)?;
+    assert!(!report.passed());
+    assert_eq!(report.mismatched_records, 1);
+    assert_eq!(report.field_mismatches.get("gear"), Some(&1));
+    assert_eq!(report.field_mismatches.get("flags.yellow_flag"), Some(&1));
+    let summary = report.to_string();
+    assert!(summary.contains("record 0 gear"), "{summary}");
+    Ok(())
+}
+
+#[test]
+fn a_record_that_stops_decoding_fails() -> R {
+    let dir = tempfile::tempdir()?;
+    let (_, expected) = fixture("forza_motorsport");
+    let mut json: Value = serde_json::from_str(&std::fs::read_to_string(expected)?)?;
+    let records = json
+        .get_mut("records")
+        .and_then(Value::as_array_mut)
+        .ok_or("no records")?;
+    records[0] = serde_json::json!({ "error": "used to fail" });
+    let path = dir.path().join("edited.expected.json");
+    std::fs::write(&path, serde_json::to_string(&json)?)?;
+
+    let report = replay_forza(&path)?;
+    assert_eq!(report.field_mismatches.get("result"), Some(&1));
+    Ok(())
+}
+
+#[test]
+fn blessing_rewrites_the_expected_file() -> R {
+    let dir = tempfile::tempdir()?;
+    let (capture, _) = fixture("f1_25");
+    let expected = dir.path().join("f1_25.expected.json");
+
+    let blessed = bless(&F1_25Adapter::new(), &capture, &expected)?;
+    assert!(blessed.blessed);
+    let report = run_fixture_with(
+        &F1_25Adapter::new(),
+        &capture,
+        &expected,
+        FixtureOptions::default(),
+    )?;
+    assert!(report.passed(), "{report}");
+    assert_eq!(report.records, blessed.records);
+    Ok(())
+}
+
+#[test]
+fn captures_only_replay_through_their_own_adapter() -> R {
+    let (capture, expected) = fixture("f1_25");
+    assert!(run_fixture(&ForzaAdapter::new(), capture, expected).is_err());
+    Ok(())
+}
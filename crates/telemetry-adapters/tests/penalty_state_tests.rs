@@ -0,0 +1,209 @@
+//! Penalty / track-limits state decoding and event derivation per adapter.
+//!
+//! Each test replays a short fixture sequence through the adapter's decoder
+//! and checks that [`PenaltyTracker`] turns it into exactly the expected
+//! events, with repeated frames producing nothing.
+
+use racing_wheel_telemetry_adapters::acc::{GRAPHICS_PENALTY_PAGE_LEN, parse_graphics_penalties};
+use racing_wheel_telemetry_adapters::f1_25::{
+    F1_25Adapter, F125State, LapPenaltyData, build_car_status_packet, build_car_telemetry_packet,
+    build_lap_data_packet,
+};
+use racing_wheel_telemetry_adapters::rfactor2::{
+    RF2ScoringHeader, RF2VehicleTelemetry, RFactor2Adapter,
+};
+use racing_wheel_telemetry_adapters::{PenaltyKind, PenaltyState};
+use racing_wheel_telemetry_core::{PenaltyEvent, PenaltyTracker};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn events_for(states: &[Option<PenaltyState>]) -> Vec<PenaltyEvent> {
+    let mut tracker = PenaltyTracker::new();
+    states
+        .iter()
+        .flat_map(|state| tracker.update(state.as_ref()))
+        .collect()
+}
+
+// ── F1 25 ────────────────────────────────────────────────────────────────────
+
+fn f1_25_frame_penalties(
+    state: &mut F125State,
+    lap: &LapPenaltyData,
+) -> Result<Option<PenaltyState>, Box<dyn std::error::Error>> {
+    F1_25Adapter::process_packet(state, &build_lap_data_packet(0, lap))?;
+    F1_25Adapter::process_packet(
+        state,
+        &build_car_telemetry_packet(0, 200, 5, 11000, 1.0, 0.0, 0, [23.0; 4]),
+    )?;
+    let normalized = F1_25Adapter::process_packet(
+        state,
+        &build_car_status_packet(0, 20.0, 2_000_000.0, 0, 0, 17, 13000),
+    )?
+    .ok_or("F1 25 should emit after telemetry and status")?;
+    Ok(normalized.penalties)
+}
+
+#[test]
+fn f1_25_lap_data_sequence_yields_one_event_per_change() -> TestResult {
+    let clean = LapPenaltyData::default();
+    let warned = LapPenaltyData {
+        corner_cutting_warnings: 1,
+        total_warnings: 1,
+        ..clean
+    };
+    let drive_through = LapPenaltyData {
+        unserved_drive_through_pens: 1,
+        ..warned
+    };
+    let time_penalty = LapPenaltyData {
+        penalties_s: 5,
+        ..warned
+    };
+
+    let mut state = F125State::default();
+    let mut frames = Vec::new();
+    for lap in [
+        clean,
+        warned,
+        warned,
+        drive_through,
+        drive_through,
+        warned,
+        time_penalty,
+        time_penalty,
+    ] {
+        frames.push(f1_25_frame_penalties(&mut state, &lap)?);
+    }
+
+    assert_eq!(
+        events_for(&frames),
+        vec![
+            PenaltyEvent::TrackLimitWarning { total: 1 },
+            PenaltyEvent::PenaltyAwarded {
+                kind: PenaltyKind::DriveThrough,
+                time_s: 0.0,
+            },
+            PenaltyEvent::PenaltyServed {
+                kind: PenaltyKind::DriveThrough,
+            },
+            PenaltyEvent::PenaltyAwarded {
+                kind: PenaltyKind::TimePenalty,
+                time_s: 5.0,
+            },
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn f1_25_frames_without_lap_data_carry_no_penalties() -> TestResult {
+    let mut state = F125State::default();
+    F1_25Adapter::process_packet(
+        &mut state,
+        &build_car_telemetry_packet(0, 100, 3, 8000, 0.5, 0.0, 0, [22.0; 4]),
+    )?;
+    let normalized = F1_25Adapter::process_packet(
+        &mut state,
+        &build_car_status_packet(0, 10.0, 0.0, 0, 0, 18, 13000),
+    )?
+    .ok_or("F1 25 should emit after telemetry and status")?;
+
+    assert!(normalized.penalties.is_none());
+    Ok(())
+}
+
+// ── ACC ──────────────────────────────────────────────────────────────────────
+
+fn acc_graphics_page(penalty: i32, penalty_time_s: f32) -> Vec<u8> {
+    let mut page = vec![0u8; GRAPHICS_PENALTY_PAGE_LEN];
+    page[1220..1224].copy_from_slice(&penalty_time_s.to_le_bytes());
+    page[1228..1232].copy_from_slice(&penalty.to_le_bytes());
+    page
+}
+
+#[test]
+fn acc_graphics_sequence_yields_one_event_per_change() -> TestResult {
+    // None → DriveThrough_Cutting → served → Disqualified_WrongWay.
+    let mut frames = Vec::new();
+    for (penalty, time) in [(0, 0.0), (1, 0.0), (1, 0.0), (0, 0.0), (18, 0.0), (18, 0.0)] {
+        frames.push(Some(parse_graphics_penalties(&acc_graphics_page(
+            penalty, time,
+        ))?));
+    }
+
+    assert_eq!(
+        events_for(&frames),
+        vec![
+            PenaltyEvent::PenaltyAwarded {
+                kind: PenaltyKind::DriveThrough,
+                time_s: 0.0,
+            },
+            PenaltyEvent::PenaltyServed {
+                kind: PenaltyKind::DriveThrough,
+            },
+            PenaltyEvent::Disqualified,
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn acc_stop_and_go_codes_map_to_stop_and_go() -> TestResult {
+    for code in [2, 3, 4, 8, 9, 10] {
+        let state = parse_graphics_penalties(&acc_graphics_page(code, 10.0))?;
+        assert_eq!(
+            state.active_penalty_kind,
+            PenaltyKind::StopAndGo,
+            "code {code}"
+        );
+        assert!(!state.drive_through_pending);
+    }
+    Ok(())
+}
+
+// ── rFactor 2 ────────────────────────────────────────────────────────────────
+
+#[test]
+fn rfactor2_scoring_sequence_yields_one_event_per_change() -> TestResult {
+    let adapter = RFactor2Adapter::new();
+    let vehicle = RF2VehicleTelemetry::default();
+    let scoring = |num_penalties, finish_status| RF2ScoringHeader {
+        num_penalties,
+        finish_status,
+        ..Default::default()
+    };
+
+    let frames: Vec<Option<PenaltyState>> = [
+        scoring(0, 0),
+        scoring(1, 0),
+        scoring(1, 0),
+        scoring(0, 0),
+        scoring(0, 3),
+    ]
+    .iter()
+    .map(|header| {
+        adapter
+            .normalize_rf2_data(&vehicle, Some(header), None)
+            .penalties
+    })
+    .collect();
+
+    assert_eq!(
+        events_for(&frames),
+        vec![
+            PenaltyEvent::PenaltyAwarded {
+                kind: PenaltyKind::Other,
+                time_s: 0.0,
+            },
+            PenaltyEvent::PenaltyServed {
+                kind: PenaltyKind::Other,
+            },
+            PenaltyEvent::Disqualified,
+        ]
+    );
+
+    let without_scoring = adapter.normalize_rf2_data(&vehicle, None, None);
+    assert!(without_scoring.penalties.is_none());
+    Ok(())
+}
@@ -0,0 +1,212 @@
+//! Raw packet capture: file round-trips, rotation at the caps, and capture
+//! from a live UDP adapter.
+
+use std::io::Cursor;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
+use std::sync::Arc;
+use std::time::Duration;
+
+use racing_wheel_telemetry_adapters::{
+    Dirt3Adapter, RawCaptureCaps, RawCaptureReader, RawCaptureRecord, RawCaptureSink,
+    TelemetryAdapter,
+};
+
+type R = Result<(), Box<dyn std::error::Error>>;
+
+fn read_all(path: &std::path::Path) -> Result<Vec<RawCaptureRecord>, Box<dyn std::error::Error>> {
+    Ok(RawCaptureReader::open(path)?.collect::<Result<Vec<_>, _>>()?)
+}
+
+#[test]
+fn records_round_trip_through_the_reader() -> R {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("forza.orcap");
+    let v4 = SocketAddr::from((Ipv4Addr::new(192, 168, 1, 20), 5300));
+    let v6 = SocketAddr::from((Ipv6Addr::LOCALHOST, 40_000));
+
+    let sink = RawCaptureSink::create("forza_motorsport", &path, RawCaptureCaps::default())?;
+    sink.record_at(10, &[1, 2, 3], Some(v4))?;
+    sink.record_at(20, &[], Some(v6))?;
+    sink.record_at(30, &[0xFF; 600], None)?;
+    sink.flush()?;
+
+    let reader = RawCaptureReader::open(&path)?;
+    assert_eq!(reader.game_id(), "forza_motorsport");
+    let records = reader.collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(
+        records,
+        [
+            RawCaptureRecord {
+                timestamp_ns: 10,
+                source: Some(v4),
+                payload: vec![1, 2, 3],
+            },
+            RawCaptureRecord {
+                timestamp_ns: 20,
+                source: Some(v6),
+                payload: Vec::new(),
+            },
+            RawCaptureRecord {
+                timestamp_ns: 30,
+                source: None,
+                payload: vec![0xFF; 600],
+            },
+        ]
+    );
+    assert_eq!(sink.stats().records, 3);
+    assert_eq!(sink.stats().bytes, std::fs::metadata(&path)?.len());
+    Ok(())
+}
+
+#[test]
+fn dropping_the_sink_flushes_buffered_records() -> R {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("capture");
+    {
+        let sink = RawCaptureSink::create("dirt3", &path, RawCaptureCaps::default())?;
+        sink.record(&[7; 16], None)?;
+    }
+    assert_eq!(read_all(&path)?.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn record_cap_rotates_and_keeps_the_newest_files() -> R {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("capture");
+    let caps = RawCaptureCaps {
+        max_records_per_file: 3,
+        max_files: 2,
+        ..RawCaptureCaps::default()
+    };
+    let sink = RawCaptureSink::create("dirt3", &path, caps)?;
+    for i in 0..10u8 {
+        sink.record_at(u64::from(i), &[i], None)?;
+    }
+    sink.flush()?;
+
+    // Files of 3, 3, 3 and 1 records; the first two were rotated away.
+    let files = sink.files();
+    assert_eq!(
+        files,
+        [dir.path().join("capture.2"), dir.path().join("capture.3")]
+    );
+    assert!(!path.exists());
+    assert!(!dir.path().join("capture.1").exists());
+    let kept: Vec<u64> = files
+        .iter()
+        .map(|file| read_all(file))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .flatten()
+        .map(|record| record.timestamp_ns)
+        .collect();
+    assert_eq!(kept, [6, 7, 8, 9]);
+    assert_eq!((sink.stats().records, sink.stats().rotations), (10, 3));
+    Ok(())
+}
+
+#[test]
+fn byte_cap_rotates_before_a_file_overflows() -> R {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("capture");
+    let caps = RawCaptureCaps {
+        max_file_bytes: 256,
+        max_files: 16,
+        ..RawCaptureCaps::default()
+    };
+    let sink = RawCaptureSink::create("dirt3", &path, caps)?;
+    for _ in 0..20 {
+        sink.record(&[0xAB; 50], None)?;
+    }
+    // Oversized records still land, alone in their own file.
+    sink.record(&[0xCD; 1_000], None)?;
+    sink.flush()?;
+
+    let files = sink.files();
+    let mut total = 0;
+    for file in &files[..files.len() - 1] {
+        assert!(std::fs::metadata(file)?.len() <= 256, "{}", file.display());
+        total += read_all(file)?.len();
+    }
+    let last = read_all(files.last().ok_or("no files")?)?;
+    assert_eq!(last.len(), 1);
+    assert_eq!(last[0].payload.len(), 1_000);
+    assert_eq!(total + last.len(), 21);
+    Ok(())
+}
+
+#[test]
+fn reader_rejects_foreign_and_truncated_files() -> R {
+    let not_capture = RawCaptureReader::new(Cursor::new(b"NOTACAPTURE".to_vec()));
+    assert!(not_capture.is_err());
+
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("capture");
+    let sink = RawCaptureSink::create("dirt3", &path, RawCaptureCaps::default())?;
+    sink.record_at(1, &[1; 32], None)?;
+    sink.record_at(2, &[2; 32], None)?;
+    drop(sink);
+
+    let mut bytes = std::fs::read(&path)?;
+    bytes.truncate(bytes.len() - 10);
+    let mut reader = RawCaptureReader::new(Cursor::new(bytes))?;
+    assert!(matches!(reader.next(), Some(Ok(record)) if record.timestamp_ns == 1));
+    assert!(matches!(reader.next(), Some(Err(_))));
+    assert!(reader.next().is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn udp_adapter_captures_payloads_before_normalizing() -> R {
+    let port = UdpSocket::bind("127.0.0.1:0")?.local_addr()?.port();
+    let adapter = Dirt3Adapter::new().with_port(port);
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("dirt3.orcap");
+    let sink = Arc::new(RawCaptureSink::create(
+        "dirt3",
+        &path,
+        RawCaptureCaps::default(),
+    )?);
+    adapter.set_raw_capture(Some(Arc::clone(&sink)));
+
+    let mut rx = adapter.start_monitoring().await?;
+    let sender = UdpSocket::bind("127.0.0.1:0")?;
+    let valid = vec![0u8; 264];
+    let garbage = [0xEEu8; 4];
+
+    // Retry until the adapter's socket is bound and a frame comes through.
+    let mut delivered = false;
+    for _ in 0..50 {
+        sender.send_to(&garbage, ("127.0.0.1", port))?;
+        sender.send_to(&valid, ("127.0.0.1", port))?;
+        if tokio::time::timeout(Duration::from_millis(100), rx.recv())
+            .await
+            .is_ok_and(|frame| frame.is_some())
+        {
+            delivered = true;
+            break;
+        }
+    }
+    assert!(delivered, "adapter never produced a frame");
+    adapter.set_raw_capture(None);
+    sink.flush()?;
+
+    let records = read_all(&path)?;
+    // The undecodable datagram is captured all the same.
+    assert!(records.iter().any(|record| record.payload == garbage));
+    assert!(records.iter().any(|record| record.payload == valid));
+    let sender_addr = sender.local_addr()?;
+    assert!(
+        records
+            .iter()
+            .all(|record| record.source == Some(sender_addr))
+    );
+
+    // Detached: further datagrams are no longer written.
+    let captured = sink.stats().records;
+    sender.send_to(&valid, ("127.0.0.1", port))?;
+    tokio::time::timeout(Duration::from_secs(2), rx.recv()).await?;
+    assert_eq!(sink.stats().records, captured);
+    Ok(())
+}
@@ -0,0 +1,197 @@
+//! Shared-memory snapshot capture, redaction and replay against fake buffers.
+
+use std::mem;
+use std::time::{Duration, Instant};
+
+use racing_wheel_telemetry_adapters::shm_snapshot::{
+    FakeShmSource, SHM_SNAPSHOT_MAGIC, ShmCaptureTrigger, ShmFailureCapture, ShmPage,
+    ShmPageSource, ShmRedaction, ShmSnapshot, capture_shm_snapshot, replay_shm_snapshot,
+};
+use racing_wheel_telemetry_adapters::{
+    AMS2Adapter, IRacingAdapter, NormalizedTelemetry, ams2::AMS2SharedMemory,
+};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const WINDOW: Duration = Duration::from_secs(30);
+
+const SESSION_YAML: &str = "WeekendInfo:\n TrackName: spa\nDriverInfo:\n Drivers:\n - CarIdx: 0\n   UserName: Jane Racer\n   AbbrevName: Racer, J\n   UserID: 123456\n";
+
+fn ams2_source() -> FakeShmSource {
+    let telemetry = vec![0u8; mem::size_of::<AMS2SharedMemory>()];
+    FakeShmSource::new(vec![
+        ShmPage::new("telemetry", telemetry),
+        ShmPage::new("participants", (0..=255u8).collect::<Vec<_>>()),
+    ])
+}
+
+fn truncated_ams2_source() -> FakeShmSource {
+    FakeShmSource::new(vec![ShmPage::new("telemetry", vec![7u8; 64])])
+}
+
+fn accept(_: &NormalizedTelemetry) -> Result<(), String> {
+    Ok(())
+}
+
+#[test]
+fn snapshot_round_trips_byte_identically() -> TestResult {
+    let adapter = AMS2Adapter::new();
+    let source = ams2_source();
+    let snapshot = capture_shm_snapshot(&adapter, &source, ShmCaptureTrigger::Manual, &[])?;
+
+    let encoded = snapshot.to_bytes()?;
+    assert_eq!(encoded.get(..4), Some(SHM_SNAPSHOT_MAGIC.as_slice()));
+    let decoded = ShmSnapshot::from_bytes(&encoded)?;
+    assert_eq!(decoded, snapshot);
+    assert_eq!(decoded.to_bytes()?, encoded);
+
+    assert_eq!(decoded.pages, source.read_pages()?);
+    assert_eq!(decoded.adapter.game_id, "ams2");
+    assert!(decoded.redacted.is_empty());
+    assert!(decoded.captured_at_unix_ms > 0);
+    Ok(())
+}
+
+#[test]
+fn failure_capture_fires_once_per_rate_window() {
+    let adapter = AMS2Adapter::new();
+    let source = truncated_ams2_source();
+    let mut capture = ShmFailureCapture::new(WINDOW);
+    let start = Instant::now();
+
+    let snapshots = (0..5)
+        .filter_map(|i| {
+            let decode = capture.decode(&adapter, &source, accept, start + Duration::from_secs(i));
+            assert!(decode.telemetry.is_err());
+            decode.snapshot
+        })
+        .count();
+    assert_eq!(snapshots, 1);
+    assert_eq!(capture.suppressed(), 4);
+
+    let later = capture.decode(&adapter, &source, accept, start + WINDOW);
+    assert!(
+        later.snapshot.is_some(),
+        "a new window allows a new capture"
+    );
+
+    // Healthy frames never capture.
+    let healthy = capture.decode(&adapter, &ams2_source(), accept, start + WINDOW * 3);
+    assert!(healthy.telemetry.is_ok());
+    assert!(healthy.snapshot.is_none());
+}
+
+#[test]
+fn replay_reproduces_the_original_error() -> TestResult {
+    let adapter = AMS2Adapter::new();
+    let mut capture = ShmFailureCapture::new(WINDOW);
+    let decode = capture.decode(&adapter, &truncated_ams2_source(), accept, Instant::now());
+    let live_error = decode
+        .telemetry
+        .err()
+        .ok_or("truncated page decoded")?
+        .to_string();
+    let snapshot = decode.snapshot.ok_or("no snapshot captured")?;
+    assert_eq!(
+        snapshot.trigger,
+        ShmCaptureTrigger::NormalizeFailed {
+            error: live_error.clone()
+        }
+    );
+
+    // As a maintainer would: from the bytes attached to the bug report.
+    let received = ShmSnapshot::from_bytes(&snapshot.to_bytes()?)?;
+    let replayed = replay_shm_snapshot(&received, &AMS2Adapter::new())
+        .err()
+        .ok_or("replay decoded the truncated page")?;
+    assert_eq!(replayed.to_string(), live_error);
+
+    let wrong_adapter = replay_shm_snapshot(&received, &IRacingAdapter::new());
+    assert!(wrong_adapter.is_err());
+    Ok(())
+}
+
+#[test]
+fn validator_rejection_captures_a_replayable_snapshot() -> TestResult {
+    let adapter = AMS2Adapter::new();
+    let mut capture = ShmFailureCapture::new(WINDOW);
+    let decode = capture.decode(
+        &adapter,
+        &ams2_source(),
+        |_| Err("rpm above redline".to_string()),
+        Instant::now(),
+    );
+    assert!(decode.telemetry.is_err());
+    let snapshot = decode.snapshot.ok_or("no snapshot captured")?;
+    assert_eq!(
+        snapshot.trigger,
+        ShmCaptureTrigger::ValidatorRejected {
+            reason: "rpm above redline".to_string()
+        }
+    );
+    // The decode itself succeeded, so replay hands back the rejected frame.
+    assert!(replay_shm_snapshot(&snapshot, &adapter).is_ok());
+    Ok(())
+}
+
+#[test]
+fn iracing_session_driver_names_are_redacted_in_place() -> TestResult {
+    let adapter = IRacingAdapter::new();
+    let source = FakeShmSource::new(vec![
+        ShmPage::new("telemetry", vec![0u8; 332]),
+        ShmPage::new("session", SESSION_YAML.as_bytes()),
+    ]);
+    let custom = ShmRedaction::bytes("telemetry", "car_path", 8, 4);
+    source.set_page("telemetry", vec![0xaau8; 332]);
+
+    let snapshot = capture_shm_snapshot(
+        &adapter,
+        &source,
+        ShmCaptureTrigger::Manual,
+        std::slice::from_ref(&custom),
+    )?;
+
+    let session = snapshot.page("session").ok_or("session page missing")?;
+    assert_eq!(session.bytes.len(), SESSION_YAML.len());
+    let text = std::str::from_utf8(&session.bytes)?;
+    assert!(!text.contains("Jane") && !text.contains("Racer") && !text.contains("123456"));
+    assert!(text.contains("   UserName: **** *****\n"));
+    assert!(
+        text.contains("TrackName: spa"),
+        "non-personal values are kept"
+    );
+
+    let telemetry = snapshot.page("telemetry").ok_or("telemetry page missing")?;
+    assert_eq!(telemetry.bytes.get(8..12), Some([0u8; 4].as_slice()));
+    assert_eq!(telemetry.bytes.get(12), Some(&0xaa));
+
+    let fields: Vec<&str> = snapshot.redacted.iter().map(|r| r.field.as_str()).collect();
+    assert_eq!(fields, ["UserName", "AbbrevName", "UserID", "car_path"]);
+    Ok(())
+}
+
+#[test]
+fn malformed_containers_are_rejected() -> TestResult {
+    let adapter = AMS2Adapter::new();
+    let snapshot = capture_shm_snapshot(&adapter, &ams2_source(), ShmCaptureTrigger::Manual, &[])?;
+    let encoded = snapshot.to_bytes()?;
+
+    let mut bad_magic = encoded.clone();
+    bad_magic[0] = b'X';
+    assert!(ShmSnapshot::from_bytes(&bad_magic).is_err());
+
+    let mut future_version = encoded.clone();
+    future_version[4] = 99;
+    assert!(ShmSnapshot::from_bytes(&future_version).is_err());
+
+    for cut in [0, 5, 9, 20, encoded.len() - 1] {
+        assert!(
+            ShmSnapshot::from_bytes(&encoded[..cut]).is_err(),
+            "cut {cut}"
+        );
+    }
+    let mut trailing = encoded;
+    trailing.push(0);
+    assert!(ShmSnapshot::from_bytes(&trailing).is_err());
+    Ok(())
+}
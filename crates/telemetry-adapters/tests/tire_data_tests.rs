@@ -0,0 +1,140 @@
+//! Per-corner tyre data decoding per adapter.
+//!
+//! Every adapter reports corners as FL, FR, RL, RR regardless of the order
+//! the game sends them in, with pressures in kPa.
+
+use racing_wheel_telemetry_adapters::ac_layout::AccPhysicsTyres;
+use racing_wheel_telemetry_adapters::acc::{PHYSICS_TYRE_PAGE_LEN, parse_physics_tires};
+use racing_wheel_telemetry_adapters::f1_25::{
+    F1_25Adapter, F125State, build_car_status_packet, build_car_telemetry_packet,
+};
+use racing_wheel_telemetry_adapters::{IRacingAdapter, TelemetryAdapter, TireCorner};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const PSI_TO_KPA: f32 = 6.894_757;
+
+fn close(actual: Option<f32>, expected: f32) -> bool {
+    actual.is_some_and(|value| (value - expected).abs() < 0.01)
+}
+
+// ── ACC ──────────────────────────────────────────────────────────────────────
+
+fn put_wheels(page: &mut [u8], offset: usize, values: [f32; 4]) {
+    for (wheel, value) in values.iter().enumerate() {
+        let at = offset + wheel * 4;
+        page[at..at + 4].copy_from_slice(&value.to_le_bytes());
+    }
+}
+
+#[test]
+fn acc_physics_page_decodes_every_corner() -> TestResult {
+    let mut page = vec![0u8; PHYSICS_TYRE_PAGE_LEN];
+    put_wheels(
+        &mut page,
+        AccPhysicsTyres::WHEELS_PRESSURE,
+        [27.5, 27.6, 26.9, 27.0],
+    );
+    put_wheels(
+        &mut page,
+        AccPhysicsTyres::SLIP_RATIO,
+        [0.02, 0.03, 0.08, 0.09],
+    );
+    put_wheels(
+        &mut page,
+        AccPhysicsTyres::TYRE_TEMP,
+        [82.0, 84.0, 78.0, 79.0],
+    );
+
+    let tires = parse_physics_tires(&page)?;
+    assert!(close(tires.fl.pressure_kpa, 27.5 * PSI_TO_KPA));
+    assert!(close(tires.rr.pressure_kpa, 27.0 * PSI_TO_KPA));
+    assert!(close(tires.fr.surface_temp_c, 84.0));
+    assert!(close(tires.rl.slip_ratio, 0.08));
+    assert!(
+        tires
+            .corners()
+            .iter()
+            .all(|corner| corner.wear_fraction.is_none())
+    );
+    Ok(())
+}
+
+#[test]
+fn acc_physics_page_drops_unusable_readings() -> TestResult {
+    let mut page = vec![0u8; PHYSICS_TYRE_PAGE_LEN];
+    put_wheels(
+        &mut page,
+        AccPhysicsTyres::WHEELS_PRESSURE,
+        [f32::NAN, -1.0, 27.0, 27.0],
+    );
+
+    let tires = parse_physics_tires(&page)?;
+    assert_eq!(tires.fl.pressure_kpa, None);
+    assert_eq!(tires.fr.pressure_kpa, None);
+    assert!(close(tires.rl.pressure_kpa, 27.0 * PSI_TO_KPA));
+    Ok(())
+}
+
+#[test]
+fn acc_short_physics_page_is_rejected() {
+    assert!(parse_physics_tires(&[0u8; 16]).is_err());
+}
+
+// ── F1 25 ────────────────────────────────────────────────────────────────────
+
+#[test]
+fn f1_25_wheels_are_reordered_front_first() -> TestResult {
+    let mut state = F125State::default();
+    // The packet orders wheels RL, RR, FL, FR.
+    F1_25Adapter::process_packet(
+        &mut state,
+        &build_car_telemetry_packet(0, 200, 5, 11000, 1.0, 0.0, 0, [21.0, 21.5, 23.0, 23.5]),
+    )?;
+    let normalized = F1_25Adapter::process_packet(
+        &mut state,
+        &build_car_status_packet(0, 20.0, 2_000_000.0, 0, 0, 17, 13000),
+    )?
+    .ok_or("F1 25 should emit after telemetry and status")?;
+
+    let tires = normalized.tires.ok_or("F1 25 should report tyres")?;
+    assert!(close(tires.fl.pressure_kpa, 23.0 * PSI_TO_KPA));
+    assert!(close(tires.fr.pressure_kpa, 23.5 * PSI_TO_KPA));
+    assert!(close(tires.rl.pressure_kpa, 21.0 * PSI_TO_KPA));
+    assert!(close(tires.rr.pressure_kpa, 21.5 * PSI_TO_KPA));
+    Ok(())
+}
+
+// ── iRacing ──────────────────────────────────────────────────────────────────
+
+const IRACING_DATA_SIZE: usize = 292;
+const OFF_LF_TEMP_CL: usize = 116;
+const OFF_LF_PRESSURE: usize = 132;
+
+#[test]
+fn iracing_reports_carcass_temperature_and_pressure() -> TestResult {
+    let mut buf = vec![0u8; IRACING_DATA_SIZE];
+    put_wheels(&mut buf, OFF_LF_TEMP_CL, [90.0, 91.0, 86.0, 87.0]);
+    put_wheels(&mut buf, OFF_LF_PRESSURE, [170.0, 171.0, 165.0, 166.0]);
+
+    let normalized = IRacingAdapter::new().normalize(&buf)?;
+    let tires = normalized.tires.ok_or("iRacing should report tyres")?;
+    assert!(close(tires.fr.surface_temp_c, 91.0));
+    assert!(close(tires.rl.pressure_kpa, 165.0));
+    // Slip ratios are only present when the sim binds the per-tyre vars.
+    assert!(
+        tires
+            .corners()
+            .iter()
+            .all(|corner| corner.slip_ratio.is_none())
+    );
+    Ok(())
+}
+
+#[test]
+fn iracing_zeroed_buffer_reports_no_tyres() -> TestResult {
+    let normalized = IRacingAdapter::new().normalize(&[0u8; IRACING_DATA_SIZE])?;
+    assert_eq!(normalized.tires, None);
+    assert!(TireCorner::default().is_empty());
+    Ok(())
+}
@@ -0,0 +1,107 @@
+//! Windowed packet and byte rates from `TrafficAccountant`.
+
+use std::sync::Arc;
+
+use racing_wheel_telemetry_adapters::{TrafficAccountant, TrafficRate};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const SECOND_NS: u64 = 1_000_000_000;
+
+/// Feed `rate_hz` packets of `bytes` per second over `[from_s, to_s)`,
+/// evenly spaced within each second.
+fn feed(accountant: &TrafficAccountant, from_s: u64, to_s: u64, rate_hz: u64, bytes: usize) {
+    for second in from_s..to_s {
+        for packet in 0..rate_hz {
+            accountant.record_at(second * SECOND_NS + packet * SECOND_NS / rate_hz, bytes);
+        }
+    }
+}
+
+fn assert_rate(rate: TrafficRate, packets_per_second: f64, bytes_per_packet: f64) {
+    let tolerance = packets_per_second * 0.01;
+    assert!(
+        (rate.packets_per_second - packets_per_second).abs() <= tolerance,
+        "{rate:?}"
+    );
+    assert!(
+        (rate.bytes_per_second - packets_per_second * bytes_per_packet).abs()
+            <= tolerance * bytes_per_packet,
+        "{rate:?}"
+    );
+}
+
+#[test]
+fn steady_feed_reports_its_rate_in_every_window() {
+    let accountant = TrafficAccountant::new();
+    // Two minutes at 60 Hz, so the ring has wrapped.
+    feed(&accountant, 100, 220, 60, 1_347);
+
+    let stats = accountant.stats_at(220 * SECOND_NS + SECOND_NS / 2);
+    assert_eq!(stats.total_packets, 120 * 60);
+    assert_eq!(stats.total_bytes, 120 * 60 * 1_347);
+    for rate in [stats.last_1s, stats.last_10s, stats.last_60s] {
+        assert_rate(rate, 60.0, 1_347.0);
+    }
+}
+
+#[test]
+fn windows_follow_a_rate_change_at_their_own_pace() {
+    let accountant = TrafficAccountant::new();
+    feed(&accountant, 0, 60, 100, 200);
+    feed(&accountant, 60, 65, 20, 200);
+
+    let stats = accountant.stats_at(65 * SECOND_NS);
+    assert_rate(stats.last_1s, 20.0, 200.0);
+    assert_rate(stats.last_10s, (5.0 * 100.0 + 5.0 * 20.0) / 10.0, 200.0);
+    assert_rate(stats.last_60s, (55.0 * 100.0 + 5.0 * 20.0) / 60.0, 200.0);
+
+    // A source gone quiet decays to zero once its seconds leave the window.
+    let quiet = accountant.stats_at(200 * SECOND_NS);
+    assert_eq!(quiet.last_60s, TrafficRate::default());
+    assert_eq!(quiet.total_packets, 60 * 100 + 5 * 20);
+}
+
+#[test]
+fn young_source_averages_over_the_seconds_it_has_seen() {
+    let accountant = TrafficAccountant::new();
+    assert_eq!(
+        accountant.stats_at(5 * SECOND_NS).last_60s,
+        TrafficRate::default()
+    );
+
+    feed(&accountant, 3, 8, 50, 64);
+    let stats = accountant.stats_at(8 * SECOND_NS);
+    assert_rate(stats.last_10s, 50.0, 64.0);
+    assert_rate(stats.last_60s, 50.0, 64.0);
+    // The second in progress is not reported until it completes.
+    assert_eq!(
+        accountant.stats_at(3 * SECOND_NS).last_1s,
+        TrafficRate::default()
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn totals_survive_concurrent_increments() -> TestResult {
+    const PER_TASK: u64 = 50_000;
+    let accountant = Arc::new(TrafficAccountant::new());
+    let tasks: Vec<_> = [100, 300]
+        .into_iter()
+        .map(|bytes| {
+            let accountant = Arc::clone(&accountant);
+            tokio::spawn(async move {
+                for _ in 0..PER_TASK {
+                    accountant.record(bytes);
+                }
+            })
+        })
+        .collect();
+    for task in tasks {
+        task.await?;
+    }
+
+    let stats = accountant.stats();
+    assert_eq!(stats.total_packets, 2 * PER_TASK);
+    assert_eq!(stats.total_bytes, PER_TASK * 400);
+    Ok(())
+}
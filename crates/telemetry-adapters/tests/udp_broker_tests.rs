@@ -0,0 +1,128 @@
+//! Adapters that default to the same UDP port listen through the shared broker.
+
+use std::net::{Ipv4Addr, UdpSocket as StdUdpSocket};
+use std::time::Duration;
+
+use racing_wheel_telemetry_adapters::{
+    DirtRally2Adapter, F1_25Adapter, SubscriberStats, TelemetryAdapter, UdpPortBroker,
+};
+use tokio::time::{sleep, timeout};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const RECV_TIMEOUT: Duration = Duration::from_secs(2);
+const DIRT_RALLY_2_PACKET_SIZE: usize = 264;
+const F1_25_HEADER_SIZE: usize = 29;
+const F1_25_PACKET_FORMAT: u16 = 2025;
+/// Event packets carry no telemetry; F1 25 accepts and ignores them.
+const F1_25_PACKET_ID_EVENT: u8 = 3;
+
+fn free_port() -> std::io::Result<u16> {
+    let probe = StdUdpSocket::bind((Ipv4Addr::LOCALHOST, 0))?;
+    Ok(probe.local_addr()?.port())
+}
+
+async fn stats_for(port: u16, name: &str) -> Result<SubscriberStats, Box<dyn std::error::Error>> {
+    Ok(timeout(RECV_TIMEOUT, async {
+        loop {
+            let found = UdpPortBroker::shared()
+                .stats(port)
+                .into_iter()
+                .find(|stats| stats.name == name && stats.matched + stats.not_mine > 0);
+            if let Some(stats) = found {
+                return stats;
+            }
+            sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await?)
+}
+
+#[tokio::test]
+async fn codemasters_adapters_share_a_port_and_release_it() -> TestResult {
+    let port = free_port()?;
+    let dirt = DirtRally2Adapter::new().with_port(port);
+    let f1 = F1_25Adapter::new().with_port(port);
+
+    let mut dirt_rx = dirt.start_monitoring().await?;
+    let _f1_rx = f1.start_monitoring().await?;
+    assert_eq!(UdpPortBroker::shared().stats(port).len(), 2);
+
+    let sender = StdUdpSocket::bind((Ipv4Addr::LOCALHOST, 0))?;
+    sender.send_to(
+        &[0u8; DIRT_RALLY_2_PACKET_SIZE],
+        (Ipv4Addr::LOCALHOST, port),
+    )?;
+
+    let frame = timeout(RECV_TIMEOUT, dirt_rx.recv())
+        .await?
+        .ok_or("DiRT Rally 2.0 channel closed")?;
+    assert_eq!(frame.raw_size, DIRT_RALLY_2_PACKET_SIZE);
+    assert_eq!(stats_for(port, "dirt_rally_2").await?.matched, 1);
+    // Format 0 is not F1 25's, so it is sniffed as foreign rather than an error.
+    let f1_stats = stats_for(port, "f1_25").await?;
+    assert_eq!((f1_stats.not_mine, f1_stats.errors), (1, 0));
+
+    dirt.stop_monitoring().await?;
+    assert!(UdpPortBroker::shared().is_bound(port));
+    f1.stop_monitoring().await?;
+    assert!(!UdpPortBroker::shared().is_bound(port));
+    drop(StdUdpSocket::bind((Ipv4Addr::UNSPECIFIED, port))?);
+    Ok(())
+}
+
+/// An F1 25 header announcing a packet id with no telemetry in it.
+fn f1_25_event_packet() -> Vec<u8> {
+    let mut packet = vec![0u8; F1_25_HEADER_SIZE];
+    packet[..2].copy_from_slice(&F1_25_PACKET_FORMAT.to_le_bytes());
+    packet[6] = F1_25_PACKET_ID_EVENT;
+    packet
+}
+
+#[tokio::test]
+async fn shared_port_traffic_is_charged_to_the_game_that_decodes_it() -> TestResult {
+    let port = free_port()?;
+    let dirt = DirtRally2Adapter::new().with_port(port);
+    let f1 = F1_25Adapter::new().with_port(port);
+    let dirt_traffic = dirt.traffic().ok_or("DiRT Rally 2.0 has no traffic")?;
+    let f1_traffic = f1.traffic().ok_or("F1 25 has no traffic")?;
+    let _dirt_rx = dirt.start_monitoring().await?;
+    let _f1_rx = f1.start_monitoring().await?;
+
+    let sender = StdUdpSocket::bind((Ipv4Addr::LOCALHOST, 0))?;
+    for _ in 0..3 {
+        sender.send_to(
+            &[0u8; DIRT_RALLY_2_PACKET_SIZE],
+            (Ipv4Addr::LOCALHOST, port),
+        )?;
+    }
+    sender.send_to(&f1_25_event_packet(), (Ipv4Addr::LOCALHOST, port))?;
+
+    timeout(RECV_TIMEOUT, async {
+        loop {
+            let seen = |name: &str| {
+                UdpPortBroker::shared()
+                    .stats(port)
+                    .into_iter()
+                    .find(|stats| stats.name == name)
+                    .map_or(0, |stats| stats.matched + stats.not_mine + stats.errors)
+            };
+            if seen("dirt_rally_2") == 4 && seen("f1_25") == 4 {
+                return;
+            }
+            sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await?;
+
+    let dirt_stats = dirt_traffic.stats();
+    assert_eq!(dirt_stats.total_packets, 3);
+    assert_eq!(dirt_stats.total_bytes, 3 * DIRT_RALLY_2_PACKET_SIZE as u64);
+    let f1_stats = f1_traffic.stats();
+    assert_eq!(f1_stats.total_packets, 1);
+    assert_eq!(f1_stats.total_bytes, F1_25_HEADER_SIZE as u64);
+
+    dirt.stop_monitoring().await?;
+    f1.stop_monitoring().await?;
+    Ok(())
+}
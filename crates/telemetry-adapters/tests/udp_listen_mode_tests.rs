@@ -0,0 +1,132 @@
+//! Loopback coverage for unicast, broadcast and multicast UDP listen modes.
+
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4, UdpSocket as StdUdpSocket};
+use std::time::Duration;
+
+use racing_wheel_telemetry_adapters::{
+    AdapterNetworkConfig, ForzaAdapter, ListenMode, TelemetryAdapter,
+};
+use tokio::net::UdpSocket;
+use tokio::time::timeout;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const RECV_TIMEOUT: Duration = Duration::from_secs(2);
+const FORZA_SLED_SIZE: usize = 232;
+
+fn local_port(socket: &UdpSocket) -> io::Result<u16> {
+    Ok(socket.local_addr()?.port())
+}
+
+async fn recv_payload(socket: &UdpSocket) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut buf = [0u8; 64];
+    let len = timeout(RECV_TIMEOUT, socket.recv(&mut buf)).await??;
+    Ok(buf[..len].to_vec())
+}
+
+#[tokio::test]
+async fn unicast_listener_receives_loopback_datagram() -> TestResult {
+    let listener = AdapterNetworkConfig::new(0).bind()?;
+    let port = local_port(&listener)?;
+
+    let sender = StdUdpSocket::bind((Ipv4Addr::LOCALHOST, 0))?;
+    sender.send_to(b"unicast", (Ipv4Addr::LOCALHOST, port))?;
+
+    assert_eq!(recv_payload(&listener).await?, b"unicast");
+    Ok(())
+}
+
+#[tokio::test]
+async fn unicast_port_stays_exclusive() -> TestResult {
+    let listener = AdapterNetworkConfig::new(0).bind()?;
+    let port = local_port(&listener)?;
+
+    let second = AdapterNetworkConfig::new(port).bind();
+    assert!(
+        second.is_err(),
+        "unicast must not silently share the port with another listener"
+    );
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn broadcast_listeners_share_port_and_receive_subnet_broadcast() -> TestResult {
+    let broadcast = AdapterNetworkConfig::new(0).with_listen_mode(ListenMode::Broadcast);
+    let first = broadcast.bind()?;
+    let port = local_port(&first)?;
+    let second = AdapterNetworkConfig::new(port)
+        .with_listen_mode(ListenMode::Broadcast)
+        .bind()?;
+
+    let sender = StdUdpSocket::bind((Ipv4Addr::LOCALHOST, 0))?;
+    sender.set_broadcast(true)?;
+    sender.send_to(b"broadcast", (Ipv4Addr::new(127, 255, 255, 255), port))?;
+
+    // SO_REUSEPORT load-balances unicast, but a broadcast reaches every listener.
+    assert_eq!(recv_payload(&first).await?, b"broadcast");
+    assert_eq!(recv_payload(&second).await?, b"broadcast");
+    Ok(())
+}
+
+#[tokio::test]
+async fn multicast_listener_receives_self_sent_group_datagram() -> TestResult {
+    let group = Ipv4Addr::new(239, 255, 42, 99);
+    let mode = ListenMode::Multicast {
+        group,
+        interface: None,
+    };
+    let listener = match AdapterNetworkConfig::new(0).with_listen_mode(mode).bind() {
+        Ok(listener) => listener,
+        Err(error) => {
+            eprintln!("skipping: multicast join not permitted here: {error}");
+            return Ok(());
+        }
+    };
+    let port = local_port(&listener)?;
+
+    let sender = StdUdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+    sender.set_multicast_loop_v4(true)?;
+    if let Err(error) = sender.send_to(b"multicast", SocketAddrV4::new(group, port)) {
+        eprintln!("skipping: no multicast route: {error}");
+        return Ok(());
+    }
+
+    match recv_payload(&listener).await {
+        Ok(payload) => assert_eq!(payload, b"multicast"),
+        Err(error) => eprintln!("skipping: multicast loopback not delivered: {error}"),
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn adapter_honors_configured_listen_mode() -> TestResult {
+    let port = {
+        let probe = StdUdpSocket::bind((Ipv4Addr::LOCALHOST, 0))?;
+        probe.local_addr()?.port()
+    };
+    let adapter = ForzaAdapter::new()
+        .with_port(port)
+        .with_listen_mode(ListenMode::Broadcast);
+    assert_eq!(adapter.listen_mode(), Some(ListenMode::Broadcast));
+
+    let mut rx = adapter.start_monitoring().await?;
+    let sender = StdUdpSocket::bind((Ipv4Addr::LOCALHOST, 0))?;
+    let target = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, port));
+    let packet = [0u8; FORZA_SLED_SIZE];
+
+    // The adapter binds inside its task, so resend until it is listening.
+    let frame = timeout(RECV_TIMEOUT, async {
+        loop {
+            sender.send_to(&packet, target)?;
+            if let Ok(frame) = timeout(Duration::from_millis(50), rx.recv()).await {
+                return Ok::<_, io::Error>(frame);
+            }
+        }
+    })
+    .await??;
+    let frame = frame.ok_or("telemetry channel closed")?;
+    assert_eq!(frame.raw_size, FORZA_SLED_SIZE);
+    Ok(())
+}
@@ -0,0 +1,207 @@
+//! Unit manifests agree with the contract, and fixture outputs are in the
+//! units the manifests claim.
+
+mod helpers;
+
+use helpers::write_f32_le;
+use racing_wheel_telemetry_adapters::{
+    NormalizedTelemetry, TelemetryAdapter, Unit, adapter_factories,
+};
+use racing_wheel_telemetry_core::{VehicleTier, check_unit_ranges};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+/// Adapters with a declared unit manifest.
+const DECLARED: &[&str] = &[
+    "acc",
+    "assetto_corsa",
+    "beamng_drive",
+    "dirt_rally_2",
+    "f1_25",
+    "forza_horizon_4",
+    "forza_horizon_5",
+    "forza_motorsport",
+    "gran_turismo_7",
+    "iracing",
+    "rfactor2",
+];
+
+fn adapter(game_id: &str) -> Result<Box<dyn TelemetryAdapter>, Box<dyn std::error::Error>> {
+    adapter_factories()
+        .iter()
+        .find(|(id, _)| *id == game_id)
+        .map(|(_, factory)| factory())
+        .ok_or_else(|| format!("no adapter registered for {game_id}").into())
+}
+
+/// Normalize `packets` with `game_id`'s adapter and run the unit range pass.
+fn check_fixture(
+    game_id: &str,
+    packets: &[Vec<u8>],
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let adapter = adapter(game_id)?;
+    let samples = packets
+        .iter()
+        .map(|packet| adapter.normalize(packet))
+        .collect::<anyhow::Result<Vec<NormalizedTelemetry>>>()?;
+    let manifest = adapter.unit_manifest();
+    Ok(
+        check_unit_ranges(&samples, VehicleTier::Car, manifest.as_ref())
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+    )
+}
+
+/// Codemasters mode 1 packet at `speed` (whatever unit the game sent it in).
+fn codemasters_packet(speed: f32) -> Vec<u8> {
+    let mut buf = vec![0u8; 264];
+    for offset in [100, 104, 108, 112] {
+        write_f32_le(&mut buf, offset, speed);
+    }
+    write_f32_le(&mut buf, 116, 0.8); // throttle
+    write_f32_le(&mut buf, 120, -0.2); // steer
+    write_f32_le(&mut buf, 132, 4.0); // gear
+    write_f32_le(&mut buf, 136, 1.4); // lateral G
+    write_f32_le(&mut buf, 148, 6800.0); // rpm
+    write_f32_le(&mut buf, 180, 30.0); // fuel in tank
+    write_f32_le(&mut buf, 184, 60.0); // fuel capacity
+    write_f32_le(&mut buf, 252, 8000.0); // max rpm
+    write_f32_le(&mut buf, 260, 6.0); // gears
+    buf
+}
+
+fn forza_cardash_packet(step: f32) -> Vec<u8> {
+    let mut buf = vec![0u8; 311];
+    buf[0..4].copy_from_slice(&1i32.to_le_bytes());
+    write_f32_le(&mut buf, 8, 8500.0); // max rpm
+    write_f32_le(&mut buf, 16, 6000.0 + step * 100.0); // rpm
+    write_f32_le(&mut buf, 20, 11.0); // lateral accel, m/s²
+    write_f32_le(&mut buf, 28, -4.0); // longitudinal accel, m/s²
+    write_f32_le(&mut buf, 244, 55.0 + step); // speed, m/s
+    for offset in [256, 260, 264, 268] {
+        write_f32_le(&mut buf, offset, 190.0); // tyre temp, °F
+    }
+    write_f32_le(&mut buf, 276, 0.6); // fuel
+    write_f32_le(&mut buf, 292, 41.5); // current lap
+    buf[303] = 200; // throttle
+    buf[307] = 4; // 3rd gear
+    buf[308] = (-20i8) as u8; // steer
+    buf
+}
+
+fn beamng_packet(step: f32) -> Vec<u8> {
+    let mut buf = vec![0u8; 92];
+    buf[10] = 4;
+    write_f32_le(&mut buf, 12, 30.0 + step); // speed, m/s
+    write_f32_le(&mut buf, 16, 4500.0); // rpm
+    write_f32_le(&mut buf, 20, 1.2); // turbo, bar
+    write_f32_le(&mut buf, 24, 92.0); // engine temp, °C
+    write_f32_le(&mut buf, 28, 0.5); // fuel
+    write_f32_le(&mut buf, 32, 3.5); // oil pressure, bar
+    write_f32_le(&mut buf, 36, 105.0); // oil temp, °C
+    write_f32_le(&mut buf, 48, 0.7); // throttle
+    buf
+}
+
+#[test]
+fn declared_manifests_match_contract_units() -> TestResult {
+    let mut declared = Vec::new();
+    for (game_id, factory) in adapter_factories() {
+        let Some(manifest) = factory().unit_manifest() else {
+            continue;
+        };
+        declared.push(*game_id);
+        assert!(
+            manifest.issues().is_empty(),
+            "{game_id}: {:?}",
+            manifest.issues()
+        );
+    }
+    declared.sort_unstable();
+    assert_eq!(declared, DECLARED);
+    Ok(())
+}
+
+#[test]
+fn manifests_record_known_pass_through_fields() -> TestResult {
+    // Forza sends wheel rotation rates and a steering-lock fraction; neither
+    // has a unit conversion to the contract's m/s and radians.
+    let forza = adapter("forza_motorsport")?
+        .unit_manifest()
+        .ok_or("forza has no manifest")?;
+    let unconverted: Vec<_> = forza
+        .unconverted()
+        .map(|entry| entry.field)
+        .filter(|field| field.starts_with("wheel_speed") || *field == "steering_angle")
+        .collect();
+    assert_eq!(
+        unconverted,
+        [
+            "steering_angle",
+            "wheel_speed_fl",
+            "wheel_speed_fr",
+            "wheel_speed_rl",
+            "wheel_speed_rr"
+        ]
+    );
+
+    let f1 = adapter("f1_25")?
+        .unit_manifest()
+        .ok_or("f1_25 has no manifest")?;
+    let speed = f1.get("speed_ms").ok_or("speed_ms undeclared")?;
+    assert_eq!(speed.source, Unit::KilometersPerHour);
+    let conversion = speed.conversion().ok_or("no speed conversion")?;
+    assert!((conversion.apply(180.0) - 50.0).abs() < 1e-3);
+    Ok(())
+}
+
+#[test]
+fn correct_fixtures_pass_the_unit_range_check() -> TestResult {
+    let steps = [0.0, 1.0, 2.0, 3.0];
+    let codemasters: Vec<_> = steps.iter().map(|s| codemasters_packet(40.0 + s)).collect();
+    let forza: Vec<_> = steps.iter().map(|s| forza_cardash_packet(*s)).collect();
+    let beamng: Vec<_> = steps.iter().map(|s| beamng_packet(*s)).collect();
+
+    for (game_id, packets) in [
+        ("dirt_rally_2", codemasters),
+        ("forza_motorsport", forza),
+        ("beamng_drive", beamng),
+    ] {
+        let suspicions = check_fixture(game_id, &packets)?;
+        assert!(suspicions.is_empty(), "{game_id}: {suspicions:?}");
+    }
+    Ok(())
+}
+
+#[test]
+fn km_h_fixture_is_flagged_with_field_and_suspected_unit() -> TestResult {
+    // A build that sends wheel speeds in km/h instead of m/s.
+    let packets: Vec<_> = [250.0, 255.0, 260.0]
+        .iter()
+        .map(|kmh| codemasters_packet(*kmh))
+        .collect();
+    let adapter = adapter("dirt_rally_2")?;
+    let samples = packets
+        .iter()
+        .map(|packet| adapter.normalize(packet))
+        .collect::<anyhow::Result<Vec<NormalizedTelemetry>>>()?;
+    let manifest = adapter.unit_manifest();
+    let suspicions = check_unit_ranges(&samples, VehicleTier::Car, manifest.as_ref());
+
+    let speed = suspicions
+        .iter()
+        .find(|s| s.field == "speed_ms")
+        .ok_or("speed_ms not flagged")?;
+    assert_eq!(speed.canonical, Unit::MetersPerSecond);
+    assert_eq!(speed.declared, Some(Unit::MetersPerSecond));
+    assert_eq!(speed.suspected, Some(Unit::KilometersPerHour));
+    assert!(
+        suspicions
+            .iter()
+            .any(|s| s.field == "wheel_speed_fl" && s.suspected == Some(Unit::KilometersPerHour))
+    );
+    // No tier allowance stretches to 255 m/s.
+    assert!(!check_unit_ranges(&samples, VehicleTier::Bike, manifest.as_ref()).is_empty());
+    Ok(())
+}
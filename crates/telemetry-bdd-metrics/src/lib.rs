@@ -8,8 +8,10 @@
 
 use std::collections::BTreeSet;
 
+use serde::Serialize;
+
 /// Policy used to evaluate matrix-vs-registry parity.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub struct MatrixParityPolicy {
     /// Allow matrix entries without registry coverage.
     pub allow_missing_registry: bool,
@@ -54,7 +56,7 @@ impl MatrixParityPolicy {
 /// - `matrix_coverage_ratio`
 /// - `registry_coverage_ratio`
 /// - `parity_ok`
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct BddMatrixMetrics {
     pub matrix_game_count: usize,
     pub registry_game_count: usize,
@@ -141,7 +143,7 @@ impl BddMatrixMetrics {
 }
 
 /// Runtime telemetry matrix metrics across adapter and writer registries.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct RuntimeBddMatrixMetrics {
     pub matrix_game_count: usize,
     pub adapter: BddMatrixMetrics,
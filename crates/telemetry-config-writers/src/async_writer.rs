@@ -0,0 +1,161 @@
+//! Async config writers for callers on a tokio runtime.
+//!
+//! A write to a network-mounted Documents folder can block for seconds, which
+//! stalls a runtime worker if made directly from async code. [`SyncAsAsync`]
+//! runs any [`ConfigWriter`] on tokio's blocking pool instead, and checks a
+//! [`CancellationToken`] right before the write starts. A write that has
+//! started always runs to completion, so cancelling never leaves a torn file.
+//!
+//! Writes carry their [`WriteOptions`], set with [`SyncAsAsync::with_options`],
+//! into the blocking task, so path policy and backup settings apply there
+//! exactly as they would to a direct sync write.
+
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    ConfigDiff, ConfigWriter, ConfigWriterFactory, ConfigWriterMetadata, TelemetryConfig,
+    WriteOptions,
+};
+
+/// The token was cancelled before the operation started; nothing was touched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("config operation cancelled before it started")]
+pub struct ConfigWriteCancelled;
+
+/// Async counterpart of [`ConfigWriter`].
+#[async_trait]
+pub trait AsyncConfigWriter: Send + Sync {
+    /// Write telemetry configuration for the game, unless `cancel` fires
+    /// first, in which case the error is a [`ConfigWriteCancelled`].
+    async fn write_config(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        cancel: &CancellationToken,
+    ) -> Result<Vec<ConfigDiff>>;
+
+    /// Validate that configuration was applied correctly, unless `cancel`
+    /// fires first.
+    async fn validate_config(&self, game_path: &Path, cancel: &CancellationToken) -> Result<bool>;
+
+    /// Restart and manual setup requirements for this game.
+    fn metadata(&self) -> ConfigWriterMetadata;
+}
+
+/// Runs a sync [`ConfigWriter`] on tokio's blocking pool.
+pub struct SyncAsAsync<W: ?Sized> {
+    options: WriteOptions,
+    writer: Arc<W>,
+}
+
+impl<W: ConfigWriter> SyncAsAsync<W> {
+    pub fn new(writer: W) -> Self {
+        Self::from_arc(Arc::new(writer))
+    }
+}
+
+impl<W: ?Sized> SyncAsAsync<W> {
+    /// Wrap a shared or unsized writer, e.g. a registry factory's box.
+    pub fn from_arc(writer: Arc<W>) -> Self {
+        Self {
+            options: WriteOptions::default(),
+            writer,
+        }
+    }
+
+    /// Make every write as `options` say rather than with the defaults.
+    pub fn with_options(mut self, options: WriteOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// The wrapped sync writer.
+    pub fn inner(&self) -> &W {
+        &self.writer
+    }
+}
+
+impl<W: ?Sized + ConfigWriter + Send + Sync + 'static> SyncAsAsync<W> {
+    /// Run `op` on the blocking pool unless `cancel` fires before it starts.
+    async fn run<R: Send + 'static>(
+        &self,
+        cancel: &CancellationToken,
+        op: impl FnOnce(&W) -> Result<R> + Send + 'static,
+    ) -> Result<R> {
+        if cancel.is_cancelled() {
+            return Err(ConfigWriteCancelled.into());
+        }
+        let writer = Arc::clone(&self.writer);
+        let cancel = cancel.clone();
+        tokio::task::spawn_blocking(move || {
+            // The pool may have queued the task; check again at the last
+            // moment before touching the disk.
+            if cancel.is_cancelled() {
+                return Err(ConfigWriteCancelled.into());
+            }
+            op(&writer)
+        })
+        .await?
+    }
+}
+
+#[async_trait]
+impl<W: ?Sized + ConfigWriter + Send + Sync + 'static> AsyncConfigWriter for SyncAsAsync<W> {
+    async fn write_config(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        cancel: &CancellationToken,
+    ) -> Result<Vec<ConfigDiff>> {
+        let game_path = game_path.to_path_buf();
+        let config = config.clone();
+        let options = self.options.clone();
+        self.run(cancel, move |writer| {
+            writer.write_config_with(&game_path, &config, &options)
+        })
+        .await
+    }
+
+    async fn validate_config(&self, game_path: &Path, cancel: &CancellationToken) -> Result<bool> {
+        let game_path = game_path.to_path_buf();
+        self.run(cancel, move |writer| writer.validate_config(&game_path))
+            .await
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        self.writer.metadata()
+    }
+}
+
+/// Creates an async config writer from a sync registry factory.
+#[derive(Clone, Copy)]
+pub struct AsyncConfigWriterFactory(ConfigWriterFactory);
+
+impl AsyncConfigWriterFactory {
+    pub const fn new(factory: ConfigWriterFactory) -> Self {
+        Self(factory)
+    }
+
+    /// A fresh writer, run through [`SyncAsAsync`].
+    pub fn create(&self) -> Box<dyn AsyncConfigWriter> {
+        let writer: Arc<dyn ConfigWriter + Send + Sync> = Arc::from((self.0)());
+        Box::new(SyncAsAsync::from_arc(writer))
+    }
+}
+
+/// Every [`config_writer_factories`](crate::config_writer_factories) entry
+/// as an async factory, in the same order and under the same game ids.
+pub fn config_writer_factories_async() -> &'static [(&'static str, AsyncConfigWriterFactory)] {
+    static FACTORIES: OnceLock<Vec<(&'static str, AsyncConfigWriterFactory)>> = OnceLock::new();
+    FACTORIES.get_or_init(|| {
+        crate::config_writer_factories()
+            .iter()
+            .map(|&(game_id, factory)| (game_id, AsyncConfigWriterFactory::new(factory)))
+            .collect()
+    })
+}
@@ -0,0 +1,128 @@
+//! Replacing config files without a game ever reading a half-written one.
+//!
+//! Contents go to a temporary file next to the target, are flushed to disk,
+//! and the temporary file is then renamed over the target. A reader sees
+//! either the old file or the new one, never a truncated mix.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow};
+use tracing::debug;
+
+/// Attempts at renaming over a target another process has open.
+const REPLACE_ATTEMPTS: u32 = 5;
+/// Wait before the first retry; doubled after each one.
+const REPLACE_BACKOFF: Duration = Duration::from_millis(10);
+
+/// Write `contents` to `path` by renaming a fully written temporary file over
+/// it. The parent directory must exist. An existing file's permissions
+/// carry over to the replacement.
+pub(crate) fn atomic_write(path: &Path, contents: &[u8]) -> Result<()> {
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| anyhow!("{} does not name a file", path.display()))?
+        .to_string_lossy();
+    let existing_permissions = match fs::metadata(path) {
+        Ok(metadata) => Some(metadata.permissions()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => return Err(err.into()),
+    };
+
+    let prefix = format!(".{file_name}.");
+    let mut builder = tempfile::Builder::new();
+    builder.prefix(&prefix).suffix(".tmp");
+    #[cfg(unix)]
+    if existing_permissions.is_none() {
+        use std::os::unix::fs::PermissionsExt;
+        // What `fs::write` would create a new file with, before the umask.
+        builder.permissions(fs::Permissions::from_mode(0o666));
+    }
+    let mut temp = builder
+        .tempfile_in(dir)
+        .with_context(|| format!("creating a temporary file next to {}", path.display()))?;
+    temp.write_all(contents)?;
+    temp.as_file().sync_all()?;
+    if let Some(permissions) = existing_permissions {
+        temp.as_file().set_permissions(permissions)?;
+    }
+
+    let mut attempt = 1;
+    let mut delay = REPLACE_BACKOFF;
+    loop {
+        match temp.persist(path) {
+            Ok(_) => return Ok(()),
+            Err(err) if attempt < REPLACE_ATTEMPTS && is_sharing_violation(&err.error) => {
+                debug!(
+                    path = %path.display(),
+                    attempt,
+                    error = %err.error,
+                    "Config file is in use; retrying replace"
+                );
+                temp = err.file;
+                thread::sleep(delay);
+                delay *= 2;
+                attempt += 1;
+            }
+            Err(err) => {
+                return Err(anyhow!(
+                    "replacing {} failed after {attempt} attempt(s): {}; \
+                     close the game or any tool holding the file and try again",
+                    path.display(),
+                    err.error
+                ));
+            }
+        }
+    }
+}
+
+/// Whether a rename failed because another process has the target open,
+/// which on Windows clears once that process lets go of the file.
+fn is_sharing_violation(err: &io::Error) -> bool {
+    // ERROR_ACCESS_DENIED, ERROR_SHARING_VIOLATION and ERROR_LOCK_VIOLATION.
+    cfg!(windows) && matches!(err.raw_os_error(), Some(5 | 32 | 33))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+    #[test]
+    fn replaces_contents_and_leaves_no_temporary_file() -> TestResult {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("app.ini");
+        atomic_write(&path, b"[Telemetry]\ntelemetryDiskFile=0\n")?;
+        atomic_write(&path, b"[Telemetry]\ntelemetryDiskFile=1\n")?;
+
+        assert_eq!(
+            fs::read_to_string(&path)?,
+            "[Telemetry]\ntelemetryDiskFile=1\n"
+        );
+        assert_eq!(fs::read_dir(dir.path())?.count(), 1);
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn existing_permissions_are_kept() -> TestResult {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("broadcasting.json");
+        fs::write(&path, "{}")?;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o640))?;
+
+        atomic_write(&path, b"{\"updListenerPort\": 9000}")?;
+        assert_eq!(fs::metadata(&path)?.permissions().mode() & 0o777, 0o640);
+        Ok(())
+    }
+}
@@ -0,0 +1,200 @@
+//! Copies of config files taken before a writer replaces them.
+//!
+//! Whole-file writers regenerate the files they own, so anything hand-tuned
+//! that a writer does not carry over is lost on write. Before a write
+//! replaces a file with different contents, the old bytes are copied to
+//! `<name>.openracing.bak-<UTC timestamp>` next to it or in a configured
+//! directory. A second backup of the same file within one second gets a
+//! `-N` suffix rather than overwriting the first.
+
+use std::fs::{self, File};
+use std::io::{self, Write as _};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+
+use crate::path_safety::{PathPolicy, confine_recorded};
+use crate::{ConfigWriter, WriteOptions, planned_diffs};
+
+/// Separates the original file name from the timestamp in a backup name.
+pub const BACKUP_MARKER: &str = ".openracing.bak-";
+
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%S";
+
+/// Whether and where config files are backed up before being replaced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupOptions {
+    enabled: bool,
+    directory: Option<PathBuf>,
+}
+
+impl Default for BackupOptions {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            directory: None,
+        }
+    }
+}
+
+impl BackupOptions {
+    /// Take no backups, e.g. in CI where temp trees are thrown away anyway.
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            directory: None,
+        }
+    }
+
+    /// Keep backups in `directory`, created on first use, instead of next to
+    /// the original file.
+    pub fn in_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Configured backup directory; `None` means next to each original.
+    pub fn directory(&self) -> Option<&Path> {
+        self.directory.as_deref()
+    }
+
+    fn directory_for<'a>(&'a self, original: &'a Path) -> Option<&'a Path> {
+        self.directory.as_deref().or_else(|| original.parent())
+    }
+}
+
+/// Save `contents`, the current bytes of `original`, as a new backup.
+pub(crate) fn back_up(
+    original: &Path,
+    contents: &[u8],
+    options: &BackupOptions,
+) -> Result<Option<PathBuf>> {
+    if !options.enabled {
+        return Ok(None);
+    }
+    let (Some(directory), Some(name)) = (options.directory_for(original), original.file_name())
+    else {
+        return Ok(None);
+    };
+    if !directory.as_os_str().is_empty() {
+        fs::create_dir_all(directory)?;
+    }
+    let stem = format!(
+        "{}{BACKUP_MARKER}{}",
+        name.to_string_lossy(),
+        Utc::now().format(TIMESTAMP_FORMAT)
+    );
+    for attempt in 0..u32::MAX {
+        let candidate = match attempt {
+            0 => directory.join(&stem),
+            n => directory.join(format!("{stem}-{n}")),
+        };
+        // A fresh file rather than `fs::copy`, so a read-only original does
+        // not produce a read-only backup.
+        match File::options()
+            .write(true)
+            .create_new(true)
+            .open(&candidate)
+        {
+            Ok(mut file) => {
+                file.write_all(contents)?;
+                return Ok(Some(candidate));
+            }
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+    Err(anyhow!("no free backup name for {}", original.display()))
+}
+
+/// Newest backup of `original` under `options`, if any.
+pub fn latest_backup(original: &Path, options: &BackupOptions) -> Result<Option<PathBuf>> {
+    let (Some(directory), Some(name)) = (options.directory_for(original), original.file_name())
+    else {
+        return Ok(None);
+    };
+    let prefix = format!("{}{BACKUP_MARKER}", name.to_string_lossy());
+    let listing = if directory.as_os_str().is_empty() {
+        fs::read_dir(".")
+    } else {
+        fs::read_dir(directory)
+    };
+    let entries = match listing {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let mut newest: Option<((String, u32), PathBuf)> = None;
+    for entry in entries {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        let Some(order) = file_name
+            .to_str()
+            .and_then(|file_name| file_name.strip_prefix(&prefix))
+            .and_then(backup_order)
+        else {
+            continue;
+        };
+        if newest.as_ref().is_none_or(|(best, _)| order > *best) {
+            newest = Some((order, entry.path()));
+        }
+    }
+    Ok(newest.map(|(_, path)| path))
+}
+
+/// Sort key of a backup suffix: the timestamp, then the same-second counter.
+fn backup_order(suffix: &str) -> Option<(String, u32)> {
+    let (timestamp, counter) = match suffix.split_once('-') {
+        Some((timestamp, counter)) => (timestamp, counter.parse().ok()?),
+        None => (suffix, 0),
+    };
+    chrono::NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT).ok()?;
+    Some((timestamp.to_string(), counter))
+}
+
+/// A file put back from one of its backups.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestoredBackup {
+    /// The config file that was overwritten.
+    pub target: PathBuf,
+    /// The backup its contents came from; left in place.
+    pub backup: PathBuf,
+}
+
+/// Restore the newest backup of each file `writer` manages under `game_path`,
+/// looking for backups where `options` would have put them.
+///
+/// Files without a backup are left alone. Restoring takes no new backup, so
+/// calling this twice restores the same backup twice.
+pub fn restore_latest_backup(
+    writer: &(impl ConfigWriter + ?Sized),
+    game_path: &Path,
+    options: &BackupOptions,
+) -> Result<Vec<RestoredBackup>> {
+    let mut targets: Vec<String> = planned_diffs(writer, game_path)?
+        .into_iter()
+        .map(|diff| diff.file_path)
+        .collect();
+    targets.dedup();
+
+    let unbacked = WriteOptions::default().with_backup(BackupOptions::disabled());
+    let mut restored = Vec::new();
+    for target in targets {
+        let target = confine_recorded(game_path, Path::new(&target), &PathPolicy::default())?;
+        let Some(backup) = latest_backup(&target, options)? else {
+            continue;
+        };
+        let contents = fs::read(&backup)?;
+        target.write(&contents, &unbacked)?;
+        restored.push(RestoredBackup {
+            target: target.to_path_buf(),
+            backup,
+        });
+    }
+    Ok(restored)
+}
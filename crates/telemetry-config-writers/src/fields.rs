@@ -0,0 +1,124 @@
+//! Telemetry field names accepted in `TelemetryConfig::fields`.
+//!
+//! Fields use the normalized names from the game support matrix. A few
+//! common spellings are accepted and mapped onto those names, so `speed`
+//! and `engine_rpm` select the same data as `speed_ms` and `rpm`.
+
+use anyhow::{Result, anyhow};
+
+/// Every normalized field name a writer can be asked for.
+pub const NORMALIZED_FIELDS: &[&str] = &[
+    "ffb_scalar",
+    "rpm",
+    "max_rpm",
+    "speed_ms",
+    "gear",
+    "throttle",
+    "brake",
+    "clutch",
+    "steering_angle",
+    "slip_ratio",
+    "lateral_g",
+    "longitudinal_g",
+    "fuel_percent",
+    "engine_temp_c",
+    "tire_temps_c",
+    "tire_pressures_psi",
+    "tire_pressures_kpa",
+    "flags",
+    "penalties",
+    "tires",
+    "timing",
+    "pit_service",
+    "car_id",
+    "track_id",
+];
+
+/// Spellings accepted in place of a normalized name.
+const ALIASES: &[(&str, &str)] = &[
+    ("speed", "speed_ms"),
+    ("vehicle_speed", "speed_ms"),
+    ("engine_rpm", "rpm"),
+    ("ffb", "ffb_scalar"),
+    ("steering", "steering_angle"),
+    ("fuel", "fuel_percent"),
+];
+
+/// The normalized name for `name`, ignoring case, surrounding whitespace and
+/// `-` or space in place of `_`.
+pub fn normalize_field_name(name: &str) -> Result<&'static str> {
+    let key = name.trim().to_ascii_lowercase().replace(['-', ' '], "_");
+    NORMALIZED_FIELDS
+        .iter()
+        .copied()
+        .find(|field| *field == key)
+        .or_else(|| {
+            ALIASES
+                .iter()
+                .find(|(alias, _)| *alias == key)
+                .map(|(_, field)| *field)
+        })
+        .ok_or_else(|| {
+            anyhow!(
+                "Unknown telemetry field '{name}'; valid fields are: {}",
+                NORMALIZED_FIELDS.join(", ")
+            )
+        })
+}
+
+/// `fields` normalized, in the order given, with duplicates dropped. An empty
+/// list stays empty, leaving each writer to pick its defaults.
+pub(crate) fn normalize_fields(fields: &[String]) -> Result<Vec<&'static str>> {
+    let mut normalized = Vec::with_capacity(fields.len());
+    for field in fields {
+        let name = normalize_field_name(field)?;
+        if !normalized.contains(&name) {
+            normalized.push(name);
+        }
+    }
+    Ok(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+    fn owned(fields: &[&str]) -> Vec<String> {
+        fields.iter().map(|field| field.to_string()).collect()
+    }
+
+    #[test]
+    fn aliases_and_spelling_resolve_to_normalized_names() -> TestResult {
+        assert_eq!(normalize_field_name("speed_ms")?, "speed_ms");
+        assert_eq!(normalize_field_name(" Speed ")?, "speed_ms");
+        assert_eq!(normalize_field_name("ENGINE-RPM")?, "rpm");
+        assert_eq!(normalize_field_name("slip ratio")?, "slip_ratio");
+        Ok(())
+    }
+
+    #[test]
+    fn empty_fields_stay_empty() -> TestResult {
+        assert!(normalize_fields(&[])?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn duplicates_are_dropped_keeping_first_order() -> TestResult {
+        let fields = owned(&["gear", "rpm", "engine_rpm", "gear", "speed"]);
+        assert_eq!(normalize_fields(&fields)?, ["gear", "rpm", "speed_ms"]);
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_field_lists_valid_options() -> TestResult {
+        let Err(err) = normalize_fields(&owned(&["rpm", "tyre_wear"])) else {
+            return Err("unknown field accepted".into());
+        };
+        let message = err.to_string();
+        assert!(message.contains("'tyre_wear'"), "{message}");
+        assert!(message.contains("speed_ms, gear"), "{message}");
+        Ok(())
+    }
+}
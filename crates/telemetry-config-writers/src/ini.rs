@@ -0,0 +1,271 @@
+//! Line-preserving edits to INI files the games also edit.
+//!
+//! Only the value of the edited key changes: comments, blank lines, the
+//! spacing around `=`, inline comments after values and the file's line
+//! endings are kept as found. A section can appear more than once, which
+//! iRacing leaves behind after crashes; every occurrence is treated as part
+//! of the same section, so no stale duplicate is left holding an old value.
+
+use crate::DiffOperation;
+
+/// One parsed line of an INI file.
+enum Line<'a> {
+    Section(&'a str),
+    Entry(Entry<'a>),
+    /// Blank lines, comments and anything else kept verbatim.
+    Other,
+}
+
+/// A `key = value ; comment` line, split so the value can be replaced alone.
+struct Entry<'a> {
+    key: &'a str,
+    value: &'a str,
+    /// Everything before the value, including the key and `=`.
+    head: &'a str,
+    /// The inline comment and the whitespace before it, if any.
+    tail: &'a str,
+}
+
+impl Entry<'_> {
+    fn with_value(&self, value: &str) -> String {
+        format!("{}{value}{}", self.head, self.tail)
+    }
+}
+
+fn parse_line(line: &str) -> Line<'_> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with(';') || trimmed.starts_with('#') {
+        return Line::Other;
+    }
+    if let Some(rest) = trimmed.strip_prefix('[')
+        && let Some((name, after)) = rest.split_once(']')
+        && (after.trim().is_empty() || is_comment(after.trim_start()))
+    {
+        return Line::Section(name.trim());
+    }
+    let Some((key, _)) = line.split_once('=') else {
+        return Line::Other;
+    };
+    let value_start = key.len() + 1;
+    let rest = &line[value_start..];
+    let leading = rest.len() - rest.trim_start().len();
+    let value_and_tail = &rest[leading..];
+    let value_end = inline_comment_start(value_and_tail).unwrap_or(value_and_tail.len());
+    let value = value_and_tail[..value_end].trim_end();
+    let head_end = value_start + leading;
+    Line::Entry(Entry {
+        key: key.trim(),
+        value,
+        head: &line[..head_end],
+        tail: &line[head_end + value.len()..],
+    })
+}
+
+fn is_comment(text: &str) -> bool {
+    text.starts_with(';') || text.starts_with('#')
+}
+
+/// Byte offset of a `;` or `#` comment that follows whitespace in `value`.
+fn inline_comment_start(value: &str) -> Option<usize> {
+    let bytes = value.as_bytes();
+    (1..bytes.len()).find(|&index| {
+        matches!(bytes[index], b';' | b'#') && bytes[index - 1].is_ascii_whitespace()
+    })
+}
+
+/// The file's lines with the line ending to write them back with.
+fn split_lines(content: &str) -> (Vec<String>, &'static str) {
+    let eol = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    (content.lines().map(ToOwned::to_owned).collect(), eol)
+}
+
+fn join_lines(lines: Vec<String>, eol: &str) -> String {
+    let mut output = lines.join(eol);
+    if !output.ends_with(eol) {
+        output.push_str(eol);
+    }
+    output
+}
+
+/// Index ranges of the bodies of every occurrence of `section`.
+fn section_bodies(lines: &[String], section: &str) -> Vec<std::ops::Range<usize>> {
+    let mut bodies = Vec::new();
+    let mut current: Option<usize> = None;
+    for (index, line) in lines.iter().enumerate() {
+        if let Line::Section(name) = parse_line(line) {
+            if let Some(start) = current.take() {
+                bodies.push(start..index);
+            }
+            if name.eq_ignore_ascii_case(section) {
+                current = Some(index + 1);
+            }
+        }
+    }
+    if let Some(start) = current {
+        bodies.push(start..lines.len());
+    }
+    bodies
+}
+
+/// Indices of the lines assigning `key` in any occurrence of `section`.
+fn key_lines(lines: &[String], section: &str, key: &str) -> Vec<usize> {
+    section_bodies(lines, section)
+        .into_iter()
+        .flatten()
+        .filter(|&index| match parse_line(&lines[index]) {
+            Line::Entry(entry) => entry.key.eq_ignore_ascii_case(key),
+            _ => false,
+        })
+        .collect()
+}
+
+/// Every value `key` has in `section`, one per assignment, in file order.
+pub(crate) fn ini_values(content: &str, section: &str, key: &str) -> Vec<String> {
+    let (lines, _) = split_lines(content);
+    key_lines(&lines, section, key)
+        .into_iter()
+        .filter_map(|index| match parse_line(&lines[index]) {
+            Line::Entry(entry) => Some(entry.value.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Set `key` to `new_value` in `section`, returning the new content, the
+/// value it replaced and what the edit was.
+///
+/// Every existing assignment of `key` in every occurrence of `section` is
+/// updated. The replaced value reported is the first one that differed, so
+/// rolling back restores the value the game may have been reading. A key the
+/// section does not yet have is added at the end of its first occurrence,
+/// and a missing section is appended after a blank line.
+pub(crate) fn upsert_ini_value(
+    content: &str,
+    section: &str,
+    key: &str,
+    new_value: &str,
+) -> (String, Option<String>, DiffOperation) {
+    let (mut lines, eol) = split_lines(content);
+    let assignments = key_lines(&lines, section, key);
+
+    if !assignments.is_empty() {
+        let mut previous_value = None;
+        for &index in &assignments {
+            let Line::Entry(entry) = parse_line(&lines[index]) else {
+                continue;
+            };
+            if entry.value == new_value {
+                continue;
+            }
+            previous_value.get_or_insert_with(|| entry.value.to_string());
+            lines[index] = entry.with_value(new_value);
+        }
+        return match previous_value {
+            Some(previous) => (
+                join_lines(lines, eol),
+                Some(previous),
+                DiffOperation::Modify,
+            ),
+            None => (
+                content.to_string(),
+                Some(new_value.to_string()),
+                DiffOperation::NoChange,
+            ),
+        };
+    }
+
+    if let Some(body) = section_bodies(&lines, section).first() {
+        // After the section's last line, not after the blank lines that
+        // separate it from the next section.
+        let at = body
+            .clone()
+            .rev()
+            .find(|&index| !lines[index].trim().is_empty())
+            .map_or(body.start, |index| index + 1);
+        lines.insert(at, format!("{key}={new_value}"));
+        return (join_lines(lines, eol), None, DiffOperation::Add);
+    }
+
+    if lines.last().is_some_and(|line| !line.trim().is_empty()) {
+        lines.push(String::new());
+    }
+    lines.push(format!("[{section}]"));
+    lines.push(format!("{key}={new_value}"));
+    (join_lines(lines, eol), None, DiffOperation::Add)
+}
+
+/// Remove `key` from every occurrence of `section`, undoing an
+/// `upsert_ini_value` that added it.
+///
+/// A section left empty at the end of the file is dropped with the blank line
+/// `upsert_ini_value` put before it, restoring the file it appended to.
+pub(crate) fn remove_ini_value(content: &str, section: &str, key: &str) -> String {
+    let (mut lines, eol) = split_lines(content);
+    let assignments = key_lines(&lines, section, key);
+    if assignments.is_empty() {
+        return content.to_string();
+    }
+    for index in assignments.into_iter().rev() {
+        lines.remove(index);
+    }
+
+    if let Some(last) = section_bodies(&lines, section).last()
+        && last.end == lines.len()
+        && lines[last.clone()]
+            .iter()
+            .all(|line| line.trim().is_empty())
+    {
+        lines.truncate(last.start - 1);
+        if lines.last().is_some_and(|line| line.trim().is_empty()) {
+            lines.pop();
+        }
+    }
+    if lines.is_empty() {
+        return String::new();
+    }
+    join_lines(lines, eol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+    #[test]
+    fn entry_keeps_spacing_and_inline_comment() -> TestResult {
+        let Line::Entry(entry) = parse_line("telemetryDiskFile = 0   ; off by default") else {
+            return Err("not an entry".into());
+        };
+        assert_eq!(entry.key, "telemetryDiskFile");
+        assert_eq!(entry.value, "0");
+        assert_eq!(
+            entry.with_value("1"),
+            "telemetryDiskFile = 1   ; off by default"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn hash_inside_a_value_is_not_a_comment() -> TestResult {
+        let Line::Entry(entry) = parse_line("color=#ff0000 # red") else {
+            return Err("not an entry".into());
+        };
+        assert_eq!(entry.value, "#ff0000");
+        Ok(())
+    }
+
+    #[test]
+    fn commented_out_keys_and_headers_are_ignored() {
+        let content = ";[Telemetry]\n[Telemetry]\n; telemetryDiskFile=0\n# telemetryDiskFile=0\n";
+        assert!(ini_values(content, "Telemetry", "telemetryDiskFile").is_empty());
+        assert_eq!(
+            section_bodies(&split_lines(content).0, "Telemetry").len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! Value-by-value differences between two versions of a JSON config file.
+//!
+//! Objects are walked key by key so a change deep inside a large document is
+//! reported at its own JSON pointer. Arrays and other values are compared
+//! whole: a changed array is one `Modify` of the array.
+
+use serde_json::Value;
+
+use crate::{ConfigDiffDetail, DiffOperation};
+
+/// The values that differ between `old` and `new`, either of which may be
+/// absent. Nothing is reported when the new content is not JSON; old content
+/// that is not JSON counts as absent, so every new value is an `Add`.
+pub(crate) fn json_details(old: Option<&str>, new: Option<&str>) -> Vec<ConfigDiffDetail> {
+    let parse = |content: &str| serde_json::from_str::<Value>(content).ok();
+    let new_value = match new {
+        Some(content) => match parse(content) {
+            Some(value) => Some(value),
+            None => return Vec::new(),
+        },
+        None => None,
+    };
+    let old_value = old.and_then(parse);
+
+    let mut details = Vec::new();
+    diff_into("", old_value.as_ref(), new_value.as_ref(), &mut details);
+    details
+}
+
+fn diff_into(
+    pointer: &str,
+    old: Option<&Value>,
+    new: Option<&Value>,
+    details: &mut Vec<ConfigDiffDetail>,
+) {
+    match (old, new) {
+        (Some(Value::Object(old)), Some(Value::Object(new))) => {
+            for (key, old_child) in old {
+                diff_into(&child(pointer, key), Some(old_child), new.get(key), details);
+            }
+            for (key, new_child) in new {
+                if !old.contains_key(key) {
+                    diff_into(&child(pointer, key), None, Some(new_child), details);
+                }
+            }
+        }
+        (None, Some(Value::Object(new))) if !new.is_empty() => {
+            for (key, new_child) in new {
+                diff_into(&child(pointer, key), None, Some(new_child), details);
+            }
+        }
+        (Some(Value::Object(old)), None) if !old.is_empty() => {
+            for (key, old_child) in old {
+                diff_into(&child(pointer, key), Some(old_child), None, details);
+            }
+        }
+        (None, None) => {}
+        (Some(old), Some(new)) if old == new => {}
+        (old, new) => details.push(ConfigDiffDetail {
+            pointer: pointer.to_string(),
+            old_value: old.cloned(),
+            new_value: new.cloned(),
+            operation: match (old, new) {
+                (None, _) => DiffOperation::Add,
+                (_, None) => DiffOperation::Remove,
+                _ => DiffOperation::Modify,
+            },
+        }),
+    }
+}
+
+/// `pointer` extended with `key`, escaped as RFC 6901 requires.
+fn child(pointer: &str, key: &str) -> String {
+    format!("{pointer}/{}", key.replace('~', "~0").replace('/', "~1"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pointers(details: &[ConfigDiffDetail]) -> Vec<(&str, &DiffOperation)> {
+        details
+            .iter()
+            .map(|detail| (detail.pointer.as_str(), &detail.operation))
+            .collect()
+    }
+
+    #[test]
+    fn keys_with_slashes_and_tildes_are_escaped() {
+        let details = json_details(None, Some(r#"{"a/b": {"~c": 1}}"#));
+        assert_eq!(pointers(&details), [("/a~1b/~0c", &DiffOperation::Add)]);
+    }
+
+    #[test]
+    fn arrays_are_compared_whole() {
+        let details = json_details(Some(r#"{"ports": [1, 2]}"#), Some(r#"{"ports": [1, 3]}"#));
+        assert_eq!(pointers(&details), [("/ports", &DiffOperation::Modify)]);
+        assert_eq!(details[0].old_value, Some(serde_json::json!([1, 2])));
+    }
+
+    #[test]
+    fn non_json_content_has_no_details() {
+        assert!(json_details(None, Some("[Telemetry]\nenabled=1\n")).is_empty());
+        let details = json_details(Some("not json"), Some(r#"{"enabled": true}"#));
+        assert_eq!(pointers(&details), [("/enabled", &DiffOperation::Add)]);
+    }
+}
@@ -3,7 +3,9 @@
 #![deny(static_mut_refs)]
 
 use anyhow::{Result, anyhow};
+use openracing_file_lock::FileLock;
 use serde_json::{Map, Value};
+use std::collections::BTreeMap;
 use std::fs;
 use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
@@ -11,6 +13,33 @@ use tracing::info;
 
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "async")]
+mod async_writer;
+mod atomic;
+mod backup;
+mod fields;
+mod ini;
+mod json_diff;
+mod path_safety;
+
+#[cfg(feature = "async")]
+pub use async_writer::{
+    AsyncConfigWriter, AsyncConfigWriterFactory, ConfigWriteCancelled, SyncAsAsync,
+    config_writer_factories_async,
+};
+pub use backup::{
+    BACKUP_MARKER, BackupOptions, RestoredBackup, latest_backup, restore_latest_backup,
+};
+use fields::normalize_fields;
+pub use fields::{NORMALIZED_FIELDS, normalize_field_name};
+use ini::{ini_values, remove_ini_value, upsert_ini_value};
+use json_diff::json_details;
+use path_safety::{ConfinedPath, confine, confine_recorded};
+pub use path_safety::{PathPolicy, PathSafetyError};
+/// Re-exported so callers need no direct `tokio-util` dependency.
+#[cfg(feature = "async")]
+pub use tokio_util::sync::CancellationToken;
+
 /// Resolves a game-relative path, specially handling the "Documents/" prefix for Windows.
 fn resolve_game_path(game_path: &Path, relative_path: &str) -> PathBuf {
     // If a non-empty game_path is provided, respect it.
@@ -31,6 +60,70 @@ fn resolve_game_path(game_path: &Path, relative_path: &str) -> PathBuf {
     game_path.join(relative_path)
 }
 
+/// Takes the inter-process lock for a config file; held until the guard is dropped.
+///
+/// Writers lock before reading so the whole read-modify-write cycle is serialized
+/// against other OpenRacing processes touching the same file. Previews take no
+/// lock, since creating the lock file would itself touch the disk.
+fn lock_config_file(path: &Path, mode: WriteMode) -> Result<Option<FileLock>> {
+    if mode.is_preview() {
+        return Ok(None);
+    }
+    Ok(Some(FileLock::acquire(path)?))
+}
+
+/// Whether a write changes the disk or only reports what it would change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WriteMode {
+    /// Write, create and lock files as needed
+    #[default]
+    Apply,
+    /// Read existing files but write, create and lock nothing
+    Preview,
+}
+
+impl WriteMode {
+    pub fn is_preview(self) -> bool {
+        self == Self::Preview
+    }
+}
+
+/// Everything about a write besides the game path and the config itself.
+///
+/// Passed explicitly rather than held in thread-local state, so it follows a
+/// write onto whichever thread carries it out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WriteOptions {
+    /// Whether the write touches the disk
+    pub mode: WriteMode,
+    /// How strictly the write's targets are confined
+    pub path_policy: PathPolicy,
+    /// Whether and where replaced files are backed up
+    pub backup: BackupOptions,
+}
+
+impl WriteOptions {
+    /// Compute diffs only, under the default path policy.
+    pub fn preview() -> Self {
+        Self {
+            mode: WriteMode::Preview,
+            ..Self::default()
+        }
+    }
+
+    /// Confine targets under `policy` instead of the default.
+    pub fn with_path_policy(mut self, policy: PathPolicy) -> Self {
+        self.path_policy = policy;
+        self
+    }
+
+    /// Back up replaced files as `backup` says instead of next to them.
+    pub fn with_backup(mut self, backup: BackupOptions) -> Self {
+        self.backup = backup;
+        self
+    }
+}
+
 /// Configuration to be applied to a game
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TelemetryConfig {
@@ -47,6 +140,10 @@ pub struct TelemetryConfig {
     /// iRacing-specific flag for enabling 360Hz high-rate telemetry.
     #[serde(default)]
     pub enable_high_rate_iracing_360hz: bool,
+    /// Move to the next free port instead of failing when another tool's
+    /// output on the same address already uses the requested one.
+    #[serde(default)]
+    pub allow_port_sharing: bool,
 }
 
 /// Represents a configuration change made to a game file
@@ -62,7 +159,56 @@ pub struct ConfigDiff {
     pub old_value: Option<String>,
     /// Value after modification
     pub new_value: String,
-    /// Add, Modify, or Remove operation
+    /// Add, Modify, Remove, or NoChange operation
+    pub operation: DiffOperation,
+    /// Copy of the file taken before this write replaced it, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backup_path: Option<String>,
+    /// For JSON files, each value the change adds, modifies or removes. The
+    /// diff itself still carries the whole file, which rollback restores.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<ConfigDiffDetail>,
+}
+
+impl ConfigDiff {
+    /// One diff per changed JSON value, keyed by its JSON pointer, for diff
+    /// views that show individual settings. A diff without details is
+    /// returned as is. The expanded diffs describe the change but cannot be
+    /// passed to `rollback_config`; roll back with the original diff.
+    pub fn leaf_diffs(&self) -> Vec<ConfigDiff> {
+        if self.details.is_empty() {
+            return vec![self.clone()];
+        }
+        self.details
+            .iter()
+            .map(|detail| ConfigDiff {
+                file_path: self.file_path.clone(),
+                section: None,
+                key: detail.pointer.clone(),
+                old_value: detail.old_value.as_ref().map(Value::to_string),
+                new_value: detail
+                    .new_value
+                    .as_ref()
+                    .map(Value::to_string)
+                    .unwrap_or_default(),
+                operation: detail.operation.clone(),
+                backup_path: self.backup_path.clone(),
+                details: Vec::new(),
+            })
+            .collect()
+    }
+}
+
+/// A single value changed inside a JSON config file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigDiffDetail {
+    /// JSON pointer to the value, e.g. `/openRacingTelemetry/updateRateHz`
+    pub pointer: String,
+    /// Value before the change; `None` when it was added
+    pub old_value: Option<Value>,
+    /// Value after the change; `None` when it was removed
+    pub new_value: Option<Value>,
+    /// Add, Modify or Remove
     pub operation: DiffOperation,
 }
 
@@ -75,18 +221,142 @@ pub enum DiffOperation {
     Modify,
     /// Remove an existing key
     Remove,
+    /// The key or file already held the new value; nothing was written
+    NoChange,
+}
+
+/// Whether a written configuration outlives the game rewriting its own config file.
+///
+/// Several games reserialize their config on exit from an internal model and
+/// drop keys they do not recognize. Drift detection and status displays use
+/// this to tell users up front whether a configuration has to be re-applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SurvivalExpectation {
+    /// Everything `validate_config` checks is persisted by the game.
+    Survives,
+    /// The game drops what the writer relies on when it rewrites the file.
+    ReapplyAfterGameExit,
+    /// The game's rewrite behavior has not been modeled.
+    Unknown,
+}
+
+impl SurvivalExpectation {
+    /// Short user-facing note, or `None` when there is nothing to warn about.
+    pub fn user_hint(&self) -> Option<&'static str> {
+        match self {
+            Self::Survives => None,
+            Self::ReapplyAfterGameExit => Some("must re-apply after each game exit"),
+            Self::Unknown => Some("may need re-applying if the game rewrites its config"),
+        }
+    }
+}
+
+/// What applying a writer's configuration involves beyond the write itself,
+/// so a UI can show the user a setup checklist.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigWriterMetadata {
+    /// Registry key of the game, e.g. `"iracing"`
+    pub game_id: &'static str,
+    /// The game reads the written configuration only at launch
+    pub requires_game_restart: bool,
+    /// Steps only the user can take, such as in-game toggles or plugin installs
+    pub manual_steps: Vec<String>,
+    /// The writer edits files the game itself reads, rather than a contract
+    /// for OpenRacing's own tooling
+    pub native_config_modified: bool,
+}
+
+impl ConfigWriterMetadata {
+    /// Metadata for a writer that leaves the game's own configuration alone.
+    fn contract_only(game_id: &'static str, manual_steps: &[&str]) -> Self {
+        Self {
+            game_id,
+            requires_game_restart: false,
+            manual_steps: manual_steps.iter().map(|step| step.to_string()).collect(),
+            native_config_modified: false,
+        }
+    }
 }
 
 /// Configuration writer trait for game-specific config generation
 pub trait ConfigWriter {
+    /// Write telemetry configuration for the game as `options` say, or with
+    /// [`WriteMode::Preview`] only compute the diffs such a write would return.
+    ///
+    /// Implementations must not write, create or lock anything in preview
+    /// mode. No backups are taken then, so `backup_path` is always `None`.
+    /// Every target is confined under `options.path_policy`.
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>>;
+
     /// Write telemetry configuration for the game
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>>;
+    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+        self.write_config_with(game_path, config, &WriteOptions::default())
+    }
+
+    /// Compute the diffs `write_config` would return, reading existing files
+    /// but writing, creating and locking nothing.
+    ///
+    /// Both go through `write_config_with`, so the preview cannot drift
+    /// from what a real write reports.
+    fn preview_config(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+    ) -> Result<Vec<ConfigDiff>> {
+        self.write_config_with(game_path, config, &WriteOptions::preview())
+    }
+
+    /// Undo `diffs` returned by an earlier `write_config` on `game_path`.
+    ///
+    /// Whole-file diffs restore the previous contents, or remove the file if
+    /// the write created it. INI diffs revert only their own keys, so other
+    /// sections the user has edited since are left alone.
+    fn rollback_config(&self, game_path: &Path, diffs: &[ConfigDiff]) -> Result<()> {
+        rollback_diffs(game_path, diffs)
+    }
+
+    /// Remove OpenRacing's telemetry configuration from `game_path`.
+    ///
+    /// The default deletes every file `write_config` replaces wholesale, which
+    /// suits the sidecar contracts OpenRacing owns; writers that edit files the
+    /// game owns override it to take out only their own keys. Each deleted file
+    /// is reported as a `Remove` diff holding its old contents, so the removal
+    /// can be rolled back. Nothing to remove yields no diffs.
+    fn remove_config(&self, game_path: &Path) -> Result<Vec<ConfigDiff>> {
+        let mut diffs = Vec::new();
+        for planned in planned_diffs(self, game_path)? {
+            if planned.section.is_some() || planned.key != "entire_file" {
+                continue;
+            }
+            let target = confine_recorded(
+                game_path,
+                Path::new(&planned.file_path),
+                &PathPolicy::default(),
+            )?;
+            diffs.extend(remove_owned_file(&target)?);
+        }
+        Ok(diffs)
+    }
 
     /// Validate that configuration was applied correctly
     fn validate_config(&self, game_path: &Path) -> Result<bool>;
 
     /// Get the expected configuration diffs for testing
     fn get_expected_diffs(&self, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>>;
+
+    /// Whether the written configuration survives the game rewriting its config on exit.
+    fn survives_game_rewrite(&self) -> SurvivalExpectation {
+        SurvivalExpectation::Unknown
+    }
+
+    /// Restart and manual setup requirements for this game.
+    fn metadata(&self) -> ConfigWriterMetadata;
 }
 
 /// Factory for constructing config writer instances.
@@ -409,9 +679,35 @@ pub fn config_writer_factories() -> &'static [(&'static str, ConfigWriterFactory
     ]
 }
 
+/// Metadata of every registered writer, keyed by game id.
+pub fn writer_metadata_registry() -> BTreeMap<&'static str, ConfigWriterMetadata> {
+    config_writer_factories()
+        .iter()
+        .map(|(game_id, factory)| (*game_id, factory().metadata()))
+        .collect()
+}
+
 const EAWRC_STRUCTURE_ID: &str = "openracing";
 const EAWRC_PACKET_ID: &str = "session_update";
 const EAWRC_DEFAULT_PORT: u16 = 20778;
+/// Normalized field names and the EA WRC channels that carry them.
+const EAWRC_CHANNELS: &[(&str, &str)] = &[
+    ("ffb_scalar", "ffb_scalar"),
+    ("rpm", "engine_rpm"),
+    ("speed_ms", "vehicle_speed"),
+    ("gear", "gear"),
+    ("slip_ratio", "slip_ratio"),
+    ("car_id", "vehicle_id"),
+    ("track_id", "track_name"),
+];
+/// Channels exported when no fields are requested.
+const EAWRC_DEFAULT_CHANNELS: &[&str] = &[
+    "ffb_scalar",
+    "engine_rpm",
+    "vehicle_speed",
+    "gear",
+    "slip_ratio",
+];
 const AC_RALLY_DEFAULT_DISCOVERY_PORT: u16 = 9000;
 const AC_RALLY_PROBE_RELATIVE_PATH: &str =
     "Documents/Assetto Corsa Rally/Config/openracing_probe.json";
@@ -435,6 +731,15 @@ const F1_DEFAULT_MODE: u8 = 3;
 const F1_25_CONTRACT_RELATIVE_PATH: &str = "Documents/OpenRacing/f1_25_contract.json";
 const F1_25_NATIVE_PROTOCOL: &str = "f1_25_native_udp";
 const F1_25_DEFAULT_PORT: u16 = 20777;
+/// Packets the native F1 25 adapter decodes, as listed in its contract.
+const F1_25_SUPPORTED_PACKETS: [&str; 6] = [
+    "motion (0)",
+    "session (1)",
+    "lap_data (2)",
+    "car_telemetry (6)",
+    "car_status (7)",
+    "motion_ex (13)",
+];
 const F1_NATIVE_CONTRACT_RELATIVE_PATH: &str = "Documents/OpenRacing/f1_native_contract.json";
 const F1_NATIVE_PROTOCOL: &str = "udp_native_f1_native";
 const F1_NATIVE_DEFAULT_PORT: u16 = 20777;
@@ -595,53 +900,83 @@ impl Default for IRacingConfigWriter {
 }
 
 impl ConfigWriter for IRacingConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing iRacing telemetry configuration");
 
-        let app_ini_path = resolve_game_path(game_path, "Documents/iRacing/app.ini");
+        let app_ini_path = confine(game_path, "Documents/iRacing/app.ini", &options.path_policy)?;
+        let _lock = lock_config_file(&app_ini_path, options.mode)?;
         let telemetry_enabled = if config.enabled { "1" } else { "0" };
 
-        // Read existing app.ini if it exists.
-        let existing_content = if app_ini_path.exists() {
-            fs::read_to_string(&app_ini_path)?
-        } else {
-            String::new()
-        };
-
-        let (mut new_content, prior_value, operation) = upsert_ini_value(
-            &existing_content,
-            "Telemetry",
-            "telemetryDiskFile",
-            telemetry_enabled,
-        );
+        let mut values = vec![("telemetryDiskFile", telemetry_enabled.to_string())];
+        if config.enable_high_rate_iracing_360hz {
+            values.push((IRACING_360HZ_KEY, "1".to_string()));
+        }
+        write_ini_values(&app_ini_path, "Telemetry", &values, options)
+    }
 
-        let mut diffs = vec![ConfigDiff {
-            file_path: app_ini_path.to_string_lossy().to_string(),
-            section: Some("Telemetry".to_string()),
-            key: "telemetryDiskFile".to_string(),
-            old_value: prior_value,
-            new_value: telemetry_enabled.to_string(),
-            operation,
-        }];
+    /// Turns disk telemetry back off and drops the 360 Hz key; the rest of
+    /// app.ini belongs to the game and is left alone.
+    fn remove_config(&self, game_path: &Path) -> Result<Vec<ConfigDiff>> {
+        let app_ini_path = confine(
+            game_path,
+            "Documents/iRacing/app.ini",
+            &PathPolicy::default(),
+        )?;
+        let _lock = lock_config_file(&app_ini_path, WriteMode::Apply)?;
+        if !app_ini_path.exists() {
+            return Ok(Vec::new());
+        }
+        let existing_content = fs::read_to_string(&app_ini_path)?;
+        let mut new_content = existing_content.clone();
+        let mut diffs = Vec::new();
 
-        if config.enable_high_rate_iracing_360hz {
-            let (updated_content, prior_360hz_value, operation_360hz) =
-                upsert_ini_value(&new_content, "Telemetry", IRACING_360HZ_KEY, "1");
+        if !ini_values(&new_content, "Telemetry", "telemetryDiskFile").is_empty() {
+            let (updated_content, prior_value, operation) =
+                upsert_ini_value(&new_content, "Telemetry", "telemetryDiskFile", "0");
             new_content = updated_content;
+            diffs.push(ConfigDiff {
+                file_path: app_ini_path.to_string_lossy().to_string(),
+                section: Some("Telemetry".to_string()),
+                key: "telemetryDiskFile".to_string(),
+                old_value: prior_value,
+                new_value: "0".to_string(),
+                operation,
+                backup_path: None,
+                details: Vec::new(),
+            });
+        }
+
+        if let Some(prior_360hz_value) = ini_values(&new_content, "Telemetry", IRACING_360HZ_KEY)
+            .into_iter()
+            .next()
+        {
+            new_content = remove_ini_value(&new_content, "Telemetry", IRACING_360HZ_KEY);
             diffs.push(ConfigDiff {
                 file_path: app_ini_path.to_string_lossy().to_string(),
                 section: Some("Telemetry".to_string()),
                 key: IRACING_360HZ_KEY.to_string(),
-                old_value: prior_360hz_value,
-                new_value: "1".to_string(),
-                operation: operation_360hz,
+                old_value: Some(prior_360hz_value),
+                new_value: String::new(),
+                operation: DiffOperation::Remove,
+                backup_path: None,
+                details: Vec::new(),
             });
         }
 
-        if let Some(parent) = app_ini_path.parent() {
-            fs::create_dir_all(parent)?;
+        if new_content != existing_content {
+            let backup = app_ini_path.write(&new_content, &WriteOptions::default())?;
+            let backup = backup.map(|backup| backup.to_string_lossy().into_owned());
+            for diff in &mut diffs {
+                if diff.operation != DiffOperation::NoChange {
+                    diff.backup_path = backup.clone();
+                }
+            }
         }
-        fs::write(&app_ini_path, &new_content)?;
 
         Ok(diffs)
     }
@@ -655,13 +990,9 @@ impl ConfigWriter for IRacingConfigWriter {
 
         let content = fs::read_to_string(app_ini_path)?;
 
-        // Check if telemetry is enabled.
-        let has_telemetry_section = content.contains("[Telemetry]");
-        let has_telemetry_enabled = content
-            .lines()
-            .any(|line| line.trim().eq_ignore_ascii_case("telemetryDiskFile=1"));
-
-        Ok(has_telemetry_section && has_telemetry_enabled)
+        // Every copy of the key counts, including ones in duplicate sections.
+        let values = ini_values(&content, "Telemetry", "telemetryDiskFile");
+        Ok(!values.is_empty() && values.iter().all(|value| value == "1"))
     }
 
     fn get_expected_diffs(&self, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
@@ -674,6 +1005,8 @@ impl ConfigWriter for IRacingConfigWriter {
             old_value: None,
             new_value: telemetry_enabled.to_string(),
             operation: DiffOperation::Add,
+            backup_path: None,
+            details: Vec::new(),
         }];
 
         if config.enable_high_rate_iracing_360hz {
@@ -684,11 +1017,22 @@ impl ConfigWriter for IRacingConfigWriter {
                 old_value: None,
                 new_value: "1".to_string(),
                 operation: DiffOperation::Add,
+                backup_path: None,
+                details: Vec::new(),
             });
         }
 
         Ok(diffs)
     }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata {
+            game_id: "iracing",
+            requires_game_restart: true,
+            manual_steps: Vec::new(),
+            native_config_modified: true,
+        }
+    }
 }
 
 /// ACC (Assetto Corsa Competizione) configuration writer
@@ -701,15 +1045,21 @@ impl Default for ACCConfigWriter {
 }
 
 impl ConfigWriter for ACCConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing ACC telemetry configuration");
 
-        let broadcasting_json_path = resolve_game_path(
+        let broadcasting_json_path = confine(
             game_path,
             "Documents/Assetto Corsa Competizione/Config/broadcasting.json",
-        );
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&broadcasting_json_path, options.mode)?;
 
-        let existed_before = broadcasting_json_path.exists();
         let existing_content = if broadcasting_json_path.exists() {
             Some(fs::read_to_string(&broadcasting_json_path)?)
         } else {
@@ -758,27 +1108,44 @@ impl ConfigWriter for ACCConfigWriter {
 
         let new_content = serde_json::to_string_pretty(&Value::Object(broadcasting_config))?;
 
-        if let Some(parent) = broadcasting_json_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&broadcasting_json_path, &new_content)?;
-
-        let diffs = vec![ConfigDiff {
-            file_path: broadcasting_json_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }];
+        let backup = broadcasting_json_path.write(&new_content, options)?;
+
+        let diffs = vec![whole_file_diff(
+            &broadcasting_json_path,
+            existing_content,
+            new_content,
+            backup,
+        )];
 
         Ok(diffs)
     }
 
+    /// Drops the listener keys OpenRacing sets and the password keys it
+    /// added empty. Passwords the user set are kept, and a file left with
+    /// nothing else in it is deleted.
+    fn remove_config(&self, game_path: &Path) -> Result<Vec<ConfigDiff>> {
+        let broadcasting_json_path = confine(
+            game_path,
+            "Documents/Assetto Corsa Competizione/Config/broadcasting.json",
+            &PathPolicy::default(),
+        )?;
+        remove_json_keys(&broadcasting_json_path, |broadcasting_config| {
+            for key in [
+                "updListenerPort",
+                "udpListenerPort",
+                "broadcastingPort",
+                "updateRateHz",
+            ] {
+                broadcasting_config.remove(key);
+            }
+            for key in ["connectionId", "connectionPassword", "commandPassword"] {
+                if broadcasting_config.get(key).and_then(Value::as_str) == Some("") {
+                    broadcasting_config.remove(key);
+                }
+            }
+        })
+    }
+
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
         let broadcasting_json_path = resolve_game_path(
             game_path,
@@ -796,20 +1163,16 @@ impl ConfigWriter for ACCConfigWriter {
             None => return Ok(false),
         };
 
-        // Accept both the original ACC key and the corrected compatibility key.
+        // Only the keys ACC itself persists are required: the game rewrites
+        // broadcasting.json on exit and drops the compatibility keys
+        // (`udpListenerPort`, `broadcastingPort`, `connectionId`,
+        // `updateRateHz`). The update rate is negotiated by the broadcasting
+        // client at registration, so losing it is harmless.
         let has_listener_port = object
             .get("updListenerPort")
             .or_else(|| object.get("udpListenerPort"))
             .and_then(Value::as_u64)
             .is_some();
-        let has_broadcasting_port = object
-            .get("broadcastingPort")
-            .and_then(Value::as_u64)
-            .is_some();
-        let has_connection_id = object
-            .get("connectionId")
-            .map(|value| !value.is_null())
-            .unwrap_or(false);
         let has_connection_password = object
             .get("connectionPassword")
             .and_then(Value::as_str)
@@ -818,14 +1181,8 @@ impl ConfigWriter for ACCConfigWriter {
             .get("commandPassword")
             .and_then(Value::as_str)
             .is_some();
-        let has_update_rate = object.get("updateRateHz").and_then(Value::as_u64).is_some();
 
-        Ok(has_listener_port
-            && has_broadcasting_port
-            && has_connection_id
-            && has_connection_password
-            && has_command_password
-            && has_update_rate)
+        Ok(has_listener_port && has_connection_password && has_command_password)
     }
 
     fn get_expected_diffs(&self, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
@@ -847,21 +1204,32 @@ impl ConfigWriter for ACCConfigWriter {
 
         let new_content = serde_json::to_string_pretty(&Value::Object(broadcasting_config))?;
 
-        Ok(vec![ConfigDiff {
-            file_path: "Documents/Assetto Corsa Competizione/Config/broadcasting.json".to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: new_content,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            "Documents/Assetto Corsa Competizione/Config/broadcasting.json",
+            new_content,
+        )])
+    }
+
+    fn survives_game_rewrite(&self) -> SurvivalExpectation {
+        SurvivalExpectation::Survives
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata {
+            game_id: "acc",
+            requires_game_restart: true,
+            manual_steps: Vec::new(),
+            native_config_modified: true,
+        }
     }
 }
 
 /// Assetto Corsa Rally configuration writer.
 ///
 /// AC Rally telemetry transport is currently discovery-based in OpenRacing.
-/// This writer creates a sidecar probe profile consumed by OpenRacing tooling.
+/// This writer creates a sidecar probe profile consumed by OpenRacing tooling,
+/// listing the requested fields so discovery can prioritize sources that
+/// carry them.
 pub struct ACRallyConfigWriter;
 
 impl Default for ACRallyConfigWriter {
@@ -871,10 +1239,21 @@ impl Default for ACRallyConfigWriter {
 }
 
 impl ConfigWriter for ACRallyConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing Assetto Corsa Rally telemetry probe configuration");
+        let requested_fields = normalize_fields(&config.fields)?;
 
-        let probe_json_path = resolve_game_path(game_path, AC_RALLY_PROBE_RELATIVE_PATH);
+        let probe_json_path = confine(
+            game_path,
+            AC_RALLY_PROBE_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&probe_json_path, options.mode)?;
         let existed_before = probe_json_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&probe_json_path)?)
@@ -911,6 +1290,7 @@ impl ConfigWriter for ACRallyConfigWriter {
             "udpCandidates".to_string(),
             Value::Array(vec![Value::from(listener_port)]),
         );
+        root.insert("requestedFields".to_string(), Value::from(requested_fields));
         root.entry("sharedMemoryCandidates".to_string())
             .or_insert(Value::Array(Vec::new()));
         root.insert(
@@ -923,23 +1303,14 @@ impl ConfigWriter for ACRallyConfigWriter {
 
         let new_content = serde_json::to_string_pretty(&Value::Object(root))?;
 
-        if let Some(parent) = probe_json_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&probe_json_path, &new_content)?;
-
-        Ok(vec![ConfigDiff {
-            file_path: probe_json_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = probe_json_path.write(&new_content, options)?;
+
+        Ok(vec![whole_file_diff(
+            &probe_json_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -980,18 +1351,16 @@ impl ConfigWriter for ACRallyConfigWriter {
             "outputTarget": config.output_target,
             "probeOrder": ["udp_handshake", "udp_passive", "shared_memory"],
             "udpCandidates": [listener_port],
+            "requestedFields": normalize_fields(&config.fields)?,
             "sharedMemoryCandidates": [],
             "note": "OpenRacing discovery profile. Populate sharedMemoryCandidates when map names are known."
         }))?;
 
-        Ok(vec![ConfigDiff {
-            file_path: AC_RALLY_PROBE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: content,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(AC_RALLY_PROBE_RELATIVE_PATH, content)])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only("ac_rally", &[])
     }
 }
 
@@ -1009,13 +1378,20 @@ impl Default for AMS2ConfigWriter {
 }
 
 impl ConfigWriter for AMS2ConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing AMS2 telemetry configuration");
 
-        let player_json_path = resolve_game_path(
+        let player_json_path = confine(
             game_path,
             "Documents/Automobilista 2/UserData/player/player.json",
-        );
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&player_json_path, options.mode)?;
         let existed_before = player_json_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&player_json_path)?)
@@ -1055,23 +1431,28 @@ impl ConfigWriter for AMS2ConfigWriter {
 
         let new_content = serde_json::to_string_pretty(&Value::Object(json_map))?;
 
-        if let Some(parent) = player_json_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&player_json_path, &new_content)?;
-
-        Ok(vec![ConfigDiff {
-            file_path: player_json_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = player_json_path.write(&new_content, options)?;
+
+        Ok(vec![whole_file_diff(
+            &player_json_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
+    }
+
+    /// Drops the `openRacingTelemetry` block. `sharedMemoryEnabled` is the
+    /// game's own setting, which other shared-memory tools also rely on, so it
+    /// is left as found.
+    fn remove_config(&self, game_path: &Path) -> Result<Vec<ConfigDiff>> {
+        let player_json_path = confine(
+            game_path,
+            "Documents/Automobilista 2/UserData/player/player.json",
+            &PathPolicy::default(),
+        )?;
+        remove_json_keys(&player_json_path, |json_map| {
+            json_map.remove("openRacingTelemetry");
+        })
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -1127,14 +1508,29 @@ impl ConfigWriter for AMS2ConfigWriter {
             ])),
         );
 
-        Ok(vec![ConfigDiff {
-            file_path: "Documents/Automobilista 2/UserData/player/player.json".to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&Value::Object(root))?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            "Documents/Automobilista 2/UserData/player/player.json",
+            serde_json::to_string_pretty(&Value::Object(root))?,
+        )])
+    }
+
+    /// AMS2 reserializes player.json on exit, keeping only the keys it knows
+    /// and top-level scalars. `sharedMemoryEnabled` survives, but the
+    /// `openRacingTelemetry` block does not.
+    fn survives_game_rewrite(&self) -> SurvivalExpectation {
+        SurvivalExpectation::ReapplyAfterGameExit
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata {
+            game_id: "ams2",
+            requires_game_restart: true,
+            manual_steps: vec![
+                "Enable Project CARS 2 shared memory in AMS2 (Options > System > Shared Memory)"
+                    .to_string(),
+            ],
+            native_config_modified: true,
+        }
     }
 }
 
@@ -1151,10 +1547,20 @@ impl Default for RFactor2ConfigWriter {
 }
 
 impl ConfigWriter for RFactor2ConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing rFactor 2 telemetry configuration");
 
-        let config_path = game_path.join("UserData/player/OpenRacing.Telemetry.json");
+        let config_path = confine(
+            game_path,
+            "UserData/player/OpenRacing.Telemetry.json",
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&config_path, options.mode)?;
         let existed_before = config_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&config_path)?)
@@ -1187,23 +1593,14 @@ impl ConfigWriter for RFactor2ConfigWriter {
 
         let new_content = serde_json::to_string_pretty(&Value::Object(root))?;
 
-        if let Some(parent) = config_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&config_path, &new_content)?;
-
-        Ok(vec![ConfigDiff {
-            file_path: config_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = config_path.write(&new_content, options)?;
+
+        Ok(vec![whole_file_diff(
+            &config_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -1249,14 +1646,20 @@ impl ConfigWriter for RFactor2ConfigWriter {
             Value::from(config.update_rate_hz),
         );
 
-        Ok(vec![ConfigDiff {
-            file_path: "UserData/player/OpenRacing.Telemetry.json".to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&Value::Object(root))?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            "UserData/player/OpenRacing.Telemetry.json",
+            serde_json::to_string_pretty(&Value::Object(root))?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "rfactor2",
+            &[
+                "Install the rF2 Shared Memory Map plugin in rFactor 2's Plugins folder",
+                "Enable the plugin under Settings > Plugins and restart rFactor 2",
+            ],
+        )
     }
 }
 
@@ -1285,10 +1688,16 @@ impl Default for Dirt5ConfigWriter {
 }
 
 impl ConfigWriter for Dirt5ConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing Dirt 5 bridge contract configuration");
 
-        let contract_path = resolve_game_path(game_path, DIRT5_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, DIRT5_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -1308,23 +1717,14 @@ impl ConfigWriter for Dirt5ConfigWriter {
         });
 
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -1363,14 +1763,14 @@ impl ConfigWriter for Dirt5ConfigWriter {
         });
         let expected = serde_json::to_string_pretty(&contract)?;
 
-        Ok(vec![ConfigDiff {
-            file_path: DIRT5_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: expected,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(DIRT5_BRIDGE_RELATIVE_PATH, expected)])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "dirt5",
+            &["Enable UDP telemetry in DIRT 5's settings, sending to 127.0.0.1 port 20777"],
+        )
     }
 }
 
@@ -1387,10 +1787,20 @@ impl Default for DirtRally2ConfigWriter {
 }
 
 impl ConfigWriter for DirtRally2ConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing DiRT Rally 2.0 bridge contract configuration");
 
-        let contract_path = resolve_game_path(game_path, DIRT_RALLY_2_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(
+            game_path,
+            DIRT_RALLY_2_BRIDGE_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -1411,23 +1821,14 @@ impl ConfigWriter for DirtRally2ConfigWriter {
         });
 
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -1467,14 +1868,19 @@ impl ConfigWriter for DirtRally2ConfigWriter {
         });
         let expected = serde_json::to_string_pretty(&contract)?;
 
-        Ok(vec![ConfigDiff {
-            file_path: DIRT_RALLY_2_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: expected,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            DIRT_RALLY_2_BRIDGE_RELATIVE_PATH,
+            expected,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "dirt_rally_2",
+            &[
+                "Enable UDP telemetry in hardware_settings_config.xml (udp enabled=\"true\" port=\"20777\" extradata=\"3\")",
+            ],
+        )
     }
 }
 
@@ -1491,10 +1897,16 @@ impl Default for RBRConfigWriter {
 }
 
 impl ConfigWriter for RBRConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing RBR bridge contract configuration");
 
-        let contract_path = resolve_game_path(game_path, RBR_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, RBR_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -1513,23 +1925,14 @@ impl ConfigWriter for RBRConfigWriter {
         });
 
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -1567,14 +1970,17 @@ impl ConfigWriter for RBRConfigWriter {
         });
         let expected = serde_json::to_string_pretty(&contract)?;
 
-        Ok(vec![ConfigDiff {
-            file_path: RBR_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: expected,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(RBR_BRIDGE_RELATIVE_PATH, expected)])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "rbr",
+            &[
+                "Install RSF Rallysimfans plugin",
+                "Configure the plugin to send LiveData UDP to 127.0.0.1 port 6776",
+            ],
+        )
     }
 }
 
@@ -1600,10 +2006,16 @@ const GTS_BRIDGE_PROTOCOL: &str = "gt7_salsa20_udp";
 const GTS_DEFAULT_PORT: u16 = 33340;
 
 impl ConfigWriter for GranTurismo7ConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing Gran Turismo 7 bridge contract configuration");
 
-        let contract_path = resolve_game_path(game_path, GT7_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, GT7_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -1618,27 +2030,18 @@ impl ConfigWriter for GranTurismo7ConfigWriter {
             "udp_port": udp_port,
             "update_rate_hz": config.update_rate_hz,
             "enabled": config.enabled,
-            "bridge_notes": "GT7 sends Salsa20-encrypted UDP packets from the PS4/PS5 to this port. Enable telemetry in GT7 Settings > Options > Machine/Car Settings > Vehicle Data Output.",
+            "bridge_notes": "GT7 sends Salsa20-encrypted UDP packets from the PS4/PS5 to this port. Enable telemetry in GT7 Settings > Options > Machine/Car Settings > Vehicle Data Output. The console replies to the heartbeat sender only; use OpenRacing listen mode broadcast to share the port with other LAN tools.",
         });
 
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -1672,18 +2075,21 @@ impl ConfigWriter for GranTurismo7ConfigWriter {
             "udp_port": udp_port,
             "update_rate_hz": config.update_rate_hz,
             "enabled": config.enabled,
-            "bridge_notes": "GT7 sends Salsa20-encrypted UDP packets from the PS4/PS5 to this port. Enable telemetry in GT7 Settings > Options > Machine/Car Settings > Vehicle Data Output.",
+            "bridge_notes": "GT7 sends Salsa20-encrypted UDP packets from the PS4/PS5 to this port. Enable telemetry in GT7 Settings > Options > Machine/Car Settings > Vehicle Data Output. The console replies to the heartbeat sender only; use OpenRacing listen mode broadcast to share the port with other LAN tools.",
         });
         let expected = serde_json::to_string_pretty(&contract)?;
 
-        Ok(vec![ConfigDiff {
-            file_path: GT7_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: expected,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(GT7_BRIDGE_RELATIVE_PATH, expected)])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "gran_turismo_7",
+            &[
+                "Enable Vehicle Data Output in GT7 Settings > Options > Machine/Car Settings",
+                "Connect the console to the same network as this PC",
+            ],
+        )
     }
 }
 
@@ -1700,10 +2106,16 @@ impl Default for GranTurismo7SportsConfigWriter {
 }
 
 impl ConfigWriter for GranTurismo7SportsConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing Gran Turismo Sport bridge contract configuration");
 
-        let contract_path = resolve_game_path(game_path, GTS_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, GTS_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -1722,23 +2134,14 @@ impl ConfigWriter for GranTurismo7SportsConfigWriter {
         });
 
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -1776,14 +2179,17 @@ impl ConfigWriter for GranTurismo7SportsConfigWriter {
         });
         let expected = serde_json::to_string_pretty(&contract)?;
 
-        Ok(vec![ConfigDiff {
-            file_path: GTS_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: expected,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(GTS_BRIDGE_RELATIVE_PATH, expected)])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "gran_turismo_sport",
+            &[
+                "Enable Vehicle Data Output in GT Sport Settings > Options > Machine/Car Settings",
+                "Connect the console to the same network as this PC",
+            ],
+        )
     }
 }
 
@@ -1800,10 +2206,16 @@ impl Default for F1ConfigWriter {
 }
 
 impl ConfigWriter for F1ConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing F1 bridge contract configuration");
 
-        let contract_path = resolve_game_path(game_path, F1_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, F1_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -1823,23 +2235,14 @@ impl ConfigWriter for F1ConfigWriter {
         });
 
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -1878,14 +2281,16 @@ impl ConfigWriter for F1ConfigWriter {
         });
         let expected = serde_json::to_string_pretty(&contract)?;
 
-        Ok(vec![ConfigDiff {
-            file_path: F1_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: expected,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(F1_BRIDGE_RELATIVE_PATH, expected)])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "f1",
+            &[
+                "Turn UDP Telemetry on in the game's Telemetry Settings, sending to 127.0.0.1 port 20777",
+            ],
+        )
     }
 }
 
@@ -1904,10 +2309,20 @@ impl Default for F1_25ConfigWriter {
 }
 
 impl ConfigWriter for F1_25ConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing F1 25 native UDP contract configuration");
 
-        let contract_path = resolve_game_path(game_path, F1_25_CONTRACT_RELATIVE_PATH);
+        let contract_path = confine(
+            game_path,
+            F1_25_CONTRACT_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -1930,29 +2345,23 @@ impl ConfigWriter for F1_25ConfigWriter {
                 "  UDP IP Address: 127.0.0.1",
                 "  UDP Port: 20777",
                 "  UDP Send Rate: 60Hz",
-                "  UDP Format: 2025"
+                "  UDP Format: 2025",
+                "On a console, or to share the stream with other LAN tools:",
+                "  UDP Broadcast Mode: On",
+                "  OpenRacing listen mode: broadcast"
             ],
-            "supported_packets": ["session (1)", "car_telemetry (6)", "car_status (7)"],
+            "supported_packets": F1_25_SUPPORTED_PACKETS,
         });
 
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -1999,20 +2408,26 @@ impl ConfigWriter for F1_25ConfigWriter {
                 "  UDP IP Address: 127.0.0.1",
                 "  UDP Port: 20777",
                 "  UDP Send Rate: 60Hz",
-                "  UDP Format: 2025"
+                "  UDP Format: 2025",
+                "On a console, or to share the stream with other LAN tools:",
+                "  UDP Broadcast Mode: On",
+                "  OpenRacing listen mode: broadcast"
             ],
-            "supported_packets": ["session (1)", "car_telemetry (6)", "car_status (7)"],
+            "supported_packets": F1_25_SUPPORTED_PACKETS,
         });
         let expected = serde_json::to_string_pretty(&contract)?;
 
-        Ok(vec![ConfigDiff {
-            file_path: F1_25_CONTRACT_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: expected,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(F1_25_CONTRACT_RELATIVE_PATH, expected)])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "f1_25",
+            &[
+                "In Settings > Telemetry Settings, turn UDP Telemetry on and UDP Broadcast Mode off",
+                "Set UDP IP Address to 127.0.0.1, UDP Port to 20777 and UDP Format to 2025",
+            ],
+        )
     }
 }
 
@@ -2029,10 +2444,20 @@ impl Default for F1NativeConfigWriter {
 }
 
 impl ConfigWriter for F1NativeConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing F1 native UDP contract configuration");
 
-        let contract_path = resolve_game_path(game_path, F1_NATIVE_CONTRACT_RELATIVE_PATH);
+        let contract_path = confine(
+            game_path,
+            F1_NATIVE_CONTRACT_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -2053,28 +2478,22 @@ impl ConfigWriter for F1NativeConfigWriter {
                 "  UDP Broadcast Mode: Off",
                 "  UDP IP Address: 127.0.0.1",
                 "  UDP Port: 20777",
-                "  UDP Send Rate: 60Hz"
+                "  UDP Send Rate: 60Hz",
+                "On a console, or to share the stream with other LAN tools:",
+                "  UDP Broadcast Mode: On",
+                "  OpenRacing listen mode: broadcast"
             ],
         });
 
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -2109,18 +2528,27 @@ impl ConfigWriter for F1NativeConfigWriter {
                 "  UDP Broadcast Mode: Off",
                 "  UDP IP Address: 127.0.0.1",
                 "  UDP Port: 20777",
-                "  UDP Send Rate: 60Hz"
+                "  UDP Send Rate: 60Hz",
+                "On a console, or to share the stream with other LAN tools:",
+                "  UDP Broadcast Mode: On",
+                "  OpenRacing listen mode: broadcast"
             ],
         });
         let expected = serde_json::to_string_pretty(&contract)?;
-        Ok(vec![ConfigDiff {
-            file_path: F1_NATIVE_CONTRACT_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: expected,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            F1_NATIVE_CONTRACT_RELATIVE_PATH,
+            expected,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "f1_native",
+            &[
+                "In Settings > Telemetry Settings, turn UDP Telemetry on and UDP Broadcast Mode off",
+                "Set UDP IP Address to 127.0.0.1, UDP Port to 20777 and UDP Format to the game's year",
+            ],
+        )
     }
 }
 
@@ -2137,9 +2565,19 @@ impl Default for F1ManagerConfigWriter {
 }
 
 impl ConfigWriter for F1ManagerConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing F1 Manager bridge contract (stub — no telemetry applicable)");
-        let contract_path = resolve_game_path(game_path, F1_MANAGER_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(
+            game_path,
+            F1_MANAGER_BRIDGE_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -2153,22 +2591,13 @@ impl ConfigWriter for F1ManagerConfigWriter {
             "bridge_notes": "F1 Manager is a strategy/management game. No UDP telemetry or force-feedback applies.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -2192,22 +2621,22 @@ impl ConfigWriter for F1ManagerConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "F1 Manager is a strategy/management game. No UDP telemetry or force-feedback applies.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: F1_MANAGER_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            F1_MANAGER_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only("f1_manager", &[])
     }
 }
 
 /// Assetto Corsa (original) configuration writer.
 ///
-/// AC uses the OutGauge UDP protocol (port 9996). Since the OutGauge output target
-/// is configured inside the game, this writer creates a bridge contract that documents
-/// the expected UDP listener configuration.
+/// AC streams telemetry over the OutGauge UDP protocol (port 9996). This writer
+/// enables it in the `[OutGauge]` section of `Documents/Assetto Corsa/cfg/openracing.ini`,
+/// pointing the output at `output_target`; keys other tools added are kept.
 pub struct AssettoCorsaConfigWriter;
 
 impl Default for AssettoCorsaConfigWriter {
@@ -2216,111 +2645,132 @@ impl Default for AssettoCorsaConfigWriter {
     }
 }
 
-const AC_BRIDGE_RELATIVE_PATH: &str = "Documents/OpenRacing/assetto_corsa_bridge_contract.json";
-const AC_BRIDGE_PROTOCOL: &str = "ac_outgauge_udp";
+const AC_OUTGAUGE_RELATIVE_PATH: &str = "Documents/Assetto Corsa/cfg/openracing.ini";
+const AC_OUTGAUGE_SECTION: &str = "OutGauge";
 const AC_DEFAULT_PORT: u16 = 9996;
 
-impl ConfigWriter for AssettoCorsaConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
-        info!("Writing Assetto Corsa bridge contract configuration");
-
-        let contract_path = resolve_game_path(game_path, AC_BRIDGE_RELATIVE_PATH);
-        let existed_before = contract_path.exists();
-        let existing_content = if existed_before {
-            Some(fs::read_to_string(&contract_path)?)
-        } else {
-            None
-        };
-
-        let udp_port = parse_target_port(&config.output_target).unwrap_or(AC_DEFAULT_PORT);
-        let contract = serde_json::json!({
-            "game_id": "assetto_corsa",
-            "telemetry_protocol": AC_BRIDGE_PROTOCOL,
-            "udp_port": udp_port,
-            "update_rate_hz": config.update_rate_hz,
-            "enabled": config.enabled,
-            "setup_notes": [
-                "In Assetto Corsa, enable OutGauge in the Documents/Assetto Corsa/cfg/openracing.ini file:",
-                "  [OutGauge]",
-                "  Mode=2",
-                "  IP=127.0.0.1",
-                "  Port=9996",
-                "  Delay=0",
-                "  ID=1"
-            ],
-        });
+/// The `[OutGauge]` keys and values OpenRacing writes for `config`.
+fn ac_outgauge_values(config: &TelemetryConfig) -> Vec<(&'static str, String)> {
+    let host = parse_target_host(&config.output_target).unwrap_or_else(|| "127.0.0.1".to_string());
+    let port = parse_target_port(&config.output_target).unwrap_or(AC_DEFAULT_PORT);
+    vec![
+        // Mode 2 sends while driving and during replays; 0 turns OutGauge off.
+        ("Mode", if config.enabled { "2" } else { "0" }.to_string()),
+        ("IP", host),
+        ("Port", port.to_string()),
+        ("ID", "1".to_string()),
+    ]
+}
 
-        let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
+impl ConfigWriter for AssettoCorsaConfigWriter {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
+        info!("Writing Assetto Corsa OutGauge configuration");
+
+        let ini_path = confine(game_path, AC_OUTGAUGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&ini_path, options.mode)?;
+        write_ini_values(
+            &ini_path,
+            AC_OUTGAUGE_SECTION,
+            &ac_outgauge_values(config),
+            options,
+        )
+    }
+
+    /// Drops the `[OutGauge]` keys OpenRacing writes, deleting the file when
+    /// nothing else is left in it.
+    fn remove_config(&self, game_path: &Path) -> Result<Vec<ConfigDiff>> {
+        let ini_path = confine(game_path, AC_OUTGAUGE_RELATIVE_PATH, &PathPolicy::default())?;
+        let _lock = lock_config_file(&ini_path, WriteMode::Apply)?;
+        if !ini_path.exists() {
+            return Ok(Vec::new());
+        }
+        let existing_content = fs::read_to_string(&ini_path)?;
+        let mut new_content = existing_content.clone();
+        let mut diffs = Vec::new();
+
+        // Removed last key first, so rolling back re-adds them in file order.
+        for key in ["ID", "Port", "IP", "Mode"] {
+            let Some(previous) = ini_values(&new_content, AC_OUTGAUGE_SECTION, key)
+                .into_iter()
+                .next()
+            else {
+                continue;
+            };
+            new_content = remove_ini_value(&new_content, AC_OUTGAUGE_SECTION, key);
+            diffs.push(ConfigDiff {
+                file_path: ini_path.to_string_lossy().to_string(),
+                section: Some(AC_OUTGAUGE_SECTION.to_string()),
+                key: key.to_string(),
+                old_value: Some(previous),
+                new_value: String::new(),
+                operation: DiffOperation::Remove,
+                backup_path: None,
+                details: Vec::new(),
+            });
         }
-        fs::write(&contract_path, &new_content)?;
-
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
+
+        if new_content != existing_content {
+            let backup = if new_content.trim().is_empty() {
+                ini_path.remove(WriteMode::Apply)?;
+                None
             } else {
-                DiffOperation::Add
-            },
-        }])
+                ini_path.write(&new_content, &WriteOptions::default())?
+            };
+            let backup = backup.map(|backup| backup.to_string_lossy().into_owned());
+            for diff in &mut diffs {
+                diff.backup_path = backup.clone();
+            }
+        }
+
+        Ok(diffs)
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
-        let contract_path = resolve_game_path(game_path, AC_BRIDGE_RELATIVE_PATH);
-        if !contract_path.exists() {
+        let ini_path = resolve_game_path(game_path, AC_OUTGAUGE_RELATIVE_PATH);
+        if !ini_path.exists() {
             return Ok(false);
         }
 
-        let content = fs::read_to_string(contract_path)?;
-        let value: Value = serde_json::from_str(&content)?;
-
-        let valid_protocol = value
-            .get("telemetry_protocol")
-            .and_then(Value::as_str)
-            .map(|v| v == AC_BRIDGE_PROTOCOL)
-            .unwrap_or(false);
-        let valid_game = value
-            .get("game_id")
-            .and_then(Value::as_str)
-            .map(|v| v == "assetto_corsa")
-            .unwrap_or(false);
+        let content = fs::read_to_string(ini_path)?;
+        let values = |key| ini_values(&content, AC_OUTGAUGE_SECTION, key);
+        let modes = values("Mode");
+        let mode_ok = !modes.is_empty() && modes.iter().all(|mode| mode != "0");
+        let ip_ok = values("IP").iter().any(|ip| !ip.is_empty());
+        let port_ok = values("Port")
+            .iter()
+            .any(|port| port.parse::<u16>().is_ok_and(|port| port != 0));
 
-        Ok(valid_protocol && valid_game)
+        Ok(mode_ok && ip_ok && port_ok)
     }
 
     fn get_expected_diffs(&self, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
-        let udp_port = parse_target_port(&config.output_target).unwrap_or(AC_DEFAULT_PORT);
-        let contract = serde_json::json!({
-            "game_id": "assetto_corsa",
-            "telemetry_protocol": AC_BRIDGE_PROTOCOL,
-            "udp_port": udp_port,
-            "update_rate_hz": config.update_rate_hz,
-            "enabled": config.enabled,
-            "setup_notes": [
-                "In Assetto Corsa, enable OutGauge in the Documents/Assetto Corsa/cfg/openracing.ini file:",
-                "  [OutGauge]",
-                "  Mode=2",
-                "  IP=127.0.0.1",
-                "  Port=9996",
-                "  Delay=0",
-                "  ID=1"
-            ],
-        });
-        let expected = serde_json::to_string_pretty(&contract)?;
+        Ok(ac_outgauge_values(config)
+            .into_iter()
+            .map(|(key, value)| ConfigDiff {
+                file_path: AC_OUTGAUGE_RELATIVE_PATH.to_string(),
+                section: Some(AC_OUTGAUGE_SECTION.to_string()),
+                key: key.to_string(),
+                old_value: None,
+                new_value: value,
+                operation: DiffOperation::Add,
+                backup_path: None,
+                details: Vec::new(),
+            })
+            .collect())
+    }
 
-        Ok(vec![ConfigDiff {
-            file_path: AC_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: expected,
-            operation: DiffOperation::Add,
-        }])
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata {
+            game_id: "assetto_corsa",
+            requires_game_restart: true,
+            manual_steps: Vec::new(),
+            native_config_modified: true,
+        }
     }
 }
 
@@ -2348,10 +2798,16 @@ const FH5_BRIDGE_RELATIVE_PATH: &str = "Documents/OpenRacing/forza_horizon_5_bri
 const FH5_DEFAULT_PORT: u16 = 5300;
 
 impl ConfigWriter for ForzaMotorsportConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing Forza Motorsport bridge contract configuration");
 
-        let contract_path = resolve_game_path(game_path, FORZA_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, FORZA_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -2371,28 +2827,21 @@ impl ConfigWriter for ForzaMotorsportConfigWriter {
                 "In Forza Motorsport / Forza Horizon, enable Data Out in game settings:",
                 "  HUD and Gameplay > Data Out > On",
                 "  Data Out IP Address: 127.0.0.1",
-                "  Data Out IP Port: 5300"
+                "  Data Out IP Port: 5300",
+                "On Xbox, set Data Out IP Address to this PC's LAN address, or to the",
+                "subnet broadcast address (e.g. 192.168.1.255) with OpenRacing listen mode: broadcast"
             ],
         });
 
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -2431,19 +2880,24 @@ impl ConfigWriter for ForzaMotorsportConfigWriter {
                 "In Forza Motorsport / Forza Horizon, enable Data Out in game settings:",
                 "  HUD and Gameplay > Data Out > On",
                 "  Data Out IP Address: 127.0.0.1",
-                "  Data Out IP Port: 5300"
+                "  Data Out IP Port: 5300",
+                "On Xbox, set Data Out IP Address to this PC's LAN address, or to the",
+                "subnet broadcast address (e.g. 192.168.1.255) with OpenRacing listen mode: broadcast"
             ],
         });
         let expected = serde_json::to_string_pretty(&contract)?;
 
-        Ok(vec![ConfigDiff {
-            file_path: FORZA_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: expected,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(FORZA_BRIDGE_RELATIVE_PATH, expected)])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "forza_motorsport",
+            &[
+                "Turn Data Out on in HUD and Gameplay settings",
+                "Set Data Out IP Address to 127.0.0.1 (this PC's LAN address on Xbox) and Data Out IP Port to 5300",
+            ],
+        )
     }
 }
 
@@ -2461,10 +2915,16 @@ impl Default for ForzaHorizon4ConfigWriter {
 }
 
 impl ConfigWriter for ForzaHorizon4ConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing Forza Horizon 4 bridge contract configuration");
 
-        let contract_path = game_path.join(FH4_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, FH4_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -2484,28 +2944,21 @@ impl ConfigWriter for ForzaHorizon4ConfigWriter {
                 "In Forza Horizon 4, enable Data Out in game settings:",
                 "  HUD and Gameplay > Data Out > On",
                 "  Data Out IP Address: 127.0.0.1",
-                "  Data Out IP Port: 12350"
+                "  Data Out IP Port: 12350",
+                "On Xbox, set Data Out IP Address to this PC's LAN address, or to the",
+                "subnet broadcast address (e.g. 192.168.1.255) with OpenRacing listen mode: broadcast"
             ],
         });
 
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -2544,19 +2997,24 @@ impl ConfigWriter for ForzaHorizon4ConfigWriter {
                 "In Forza Horizon 4, enable Data Out in game settings:",
                 "  HUD and Gameplay > Data Out > On",
                 "  Data Out IP Address: 127.0.0.1",
-                "  Data Out IP Port: 12350"
+                "  Data Out IP Port: 12350",
+                "On Xbox, set Data Out IP Address to this PC's LAN address, or to the",
+                "subnet broadcast address (e.g. 192.168.1.255) with OpenRacing listen mode: broadcast"
             ],
         });
         let expected = serde_json::to_string_pretty(&contract)?;
 
-        Ok(vec![ConfigDiff {
-            file_path: FH4_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: expected,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(FH4_BRIDGE_RELATIVE_PATH, expected)])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "forza_horizon_4",
+            &[
+                "Turn Data Out on in HUD and Gameplay settings",
+                "Set Data Out IP Address to 127.0.0.1 (this PC's LAN address on Xbox) and Data Out IP Port to 12350",
+            ],
+        )
     }
 }
 
@@ -2573,10 +3031,16 @@ impl Default for ForzaHorizon5ConfigWriter {
 }
 
 impl ConfigWriter for ForzaHorizon5ConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing Forza Horizon 5 bridge contract configuration");
 
-        let contract_path = game_path.join(FH5_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, FH5_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -2596,28 +3060,21 @@ impl ConfigWriter for ForzaHorizon5ConfigWriter {
                 "In Forza Horizon 5, enable Data Out in game settings:",
                 "  HUD and Gameplay > Data Out > On",
                 "  Data Out IP Address: 127.0.0.1",
-                "  Data Out IP Port: 5300"
+                "  Data Out IP Port: 5300",
+                "On Xbox, set Data Out IP Address to this PC's LAN address, or to the",
+                "subnet broadcast address (e.g. 192.168.1.255) with OpenRacing listen mode: broadcast"
             ],
         });
 
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -2656,19 +3113,24 @@ impl ConfigWriter for ForzaHorizon5ConfigWriter {
                 "In Forza Horizon 5, enable Data Out in game settings:",
                 "  HUD and Gameplay > Data Out > On",
                 "  Data Out IP Address: 127.0.0.1",
-                "  Data Out IP Port: 5300"
+                "  Data Out IP Port: 5300",
+                "On Xbox, set Data Out IP Address to this PC's LAN address, or to the",
+                "subnet broadcast address (e.g. 192.168.1.255) with OpenRacing listen mode: broadcast"
             ],
         });
         let expected = serde_json::to_string_pretty(&contract)?;
 
-        Ok(vec![ConfigDiff {
-            file_path: FH5_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: expected,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(FH5_BRIDGE_RELATIVE_PATH, expected)])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "forza_horizon_5",
+            &[
+                "Turn Data Out on in HUD and Gameplay settings",
+                "Set Data Out IP Address to 127.0.0.1 (this PC's LAN address on Xbox) and Data Out IP Port to 5300",
+            ],
+        )
     }
 }
 
@@ -2689,10 +3151,16 @@ const BEAMNG_BRIDGE_PROTOCOL: &str = "beamng_outgauge_udp";
 const BEAMNG_DEFAULT_PORT: u16 = 4444;
 
 impl ConfigWriter for BeamNGDriveConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing BeamNG.drive bridge contract configuration");
 
-        let contract_path = resolve_game_path(game_path, BEAMNG_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, BEAMNG_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -2716,23 +3184,14 @@ impl ConfigWriter for BeamNGDriveConfigWriter {
         });
 
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -2775,14 +3234,17 @@ impl ConfigWriter for BeamNGDriveConfigWriter {
         });
         let expected = serde_json::to_string_pretty(&contract)?;
 
-        Ok(vec![ConfigDiff {
-            file_path: BEAMNG_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: expected,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(BEAMNG_BRIDGE_RELATIVE_PATH, expected)])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "beamng_drive",
+            &[
+                "Enable the OutGauge app from BeamNG.drive's apps menu",
+                "Set the OutGauge UDP IP to 127.0.0.1 and port to 4444",
+            ],
+        )
     }
 }
 
@@ -2803,10 +3265,16 @@ impl Default for PCars2ConfigWriter {
 }
 
 impl ConfigWriter for PCars2ConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing Project CARS 2 bridge contract configuration");
 
-        let contract_path = resolve_game_path(game_path, PCARS2_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, PCARS2_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -2829,23 +3297,14 @@ impl ConfigWriter for PCars2ConfigWriter {
         });
 
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -2887,14 +3346,16 @@ impl ConfigWriter for PCars2ConfigWriter {
         });
         let expected = serde_json::to_string_pretty(&contract)?;
 
-        Ok(vec![ConfigDiff {
-            file_path: PCARS2_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: expected,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(PCARS2_BRIDGE_RELATIVE_PATH, expected)])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "project_cars_2",
+            &[
+                "Enable UDP telemetry in Options > Visual > UDP Frequency, sending to 127.0.0.1 port 5606",
+            ],
+        )
     }
 }
 
@@ -2908,10 +3369,16 @@ impl Default for PCars3ConfigWriter {
 }
 
 impl ConfigWriter for PCars3ConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing Project CARS 3 bridge contract configuration");
 
-        let contract_path = resolve_game_path(game_path, PCARS3_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, PCARS3_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -2934,23 +3401,14 @@ impl ConfigWriter for PCars3ConfigWriter {
         });
 
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -2992,14 +3450,16 @@ impl ConfigWriter for PCars3ConfigWriter {
         });
         let expected = serde_json::to_string_pretty(&contract)?;
 
-        Ok(vec![ConfigDiff {
-            file_path: PCARS3_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: expected,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(PCARS3_BRIDGE_RELATIVE_PATH, expected)])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "project_cars_3",
+            &[
+                "Enable UDP telemetry in Gameplay > HUD/Telemetry settings, sending to 127.0.0.1 port 5606",
+            ],
+        )
     }
 }
 
@@ -3017,10 +3477,16 @@ impl Default for LFSConfigWriter {
 }
 
 impl ConfigWriter for LFSConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing Live For Speed bridge contract configuration");
 
-        let contract_path = resolve_game_path(game_path, LFS_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, LFS_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -3044,23 +3510,14 @@ impl ConfigWriter for LFSConfigWriter {
         });
 
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -3103,14 +3560,17 @@ impl ConfigWriter for LFSConfigWriter {
         });
         let expected = serde_json::to_string_pretty(&contract)?;
 
-        Ok(vec![ConfigDiff {
-            file_path: LFS_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: expected,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(LFS_BRIDGE_RELATIVE_PATH, expected)])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "live_for_speed",
+            &[
+                "Enable OutGauge in Options > Output, or in cfg.lfs",
+                "Set OutGauge IP to 127.0.0.1 and Port to 30000",
+            ],
+        )
     }
 }
 
@@ -3127,10 +3587,20 @@ impl Default for WrcGenerationsConfigWriter {
 }
 
 impl ConfigWriter for WrcGenerationsConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing WRC Generations bridge contract configuration");
 
-        let contract_path = resolve_game_path(game_path, WRC_GENERATIONS_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(
+            game_path,
+            WRC_GENERATIONS_BRIDGE_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -3151,23 +3621,14 @@ impl ConfigWriter for WrcGenerationsConfigWriter {
         });
 
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -3207,14 +3668,17 @@ impl ConfigWriter for WrcGenerationsConfigWriter {
         });
         let expected = serde_json::to_string_pretty(&contract)?;
 
-        Ok(vec![ConfigDiff {
-            file_path: WRC_GENERATIONS_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: expected,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            WRC_GENERATIONS_BRIDGE_RELATIVE_PATH,
+            expected,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "wrc_generations",
+            &["Enable UDP telemetry in the game's accessibility settings, sending to port 6777"],
+        )
     }
 }
 
@@ -3253,11 +3717,21 @@ pub struct WrcKylotonnConfigWriter {
 }
 
 impl ConfigWriter for WrcKylotonnConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         let game_name = self.variant.display_name();
         info!("Writing {game_name} bridge contract configuration");
 
-        let contract_path = resolve_game_path(game_path, WRC_KYLOTONN_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(
+            game_path,
+            WRC_KYLOTONN_BRIDGE_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -3277,23 +3751,14 @@ impl ConfigWriter for WrcKylotonnConfigWriter {
         });
 
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -3332,14 +3797,17 @@ impl ConfigWriter for WrcKylotonnConfigWriter {
         });
         let expected = serde_json::to_string_pretty(&contract)?;
 
-        Ok(vec![ConfigDiff {
-            file_path: WRC_KYLOTONN_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: expected,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            WRC_KYLOTONN_BRIDGE_RELATIVE_PATH,
+            expected,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            self.variant.game_id(),
+            &["Enable UDP telemetry in the game's settings, sending to port 64000"],
+        )
     }
 }
 
@@ -3356,10 +3824,16 @@ impl Default for Dirt4ConfigWriter {
 }
 
 impl ConfigWriter for Dirt4ConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing Dirt 4 bridge contract configuration");
 
-        let contract_path = resolve_game_path(game_path, DIRT4_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, DIRT4_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -3379,23 +3853,14 @@ impl ConfigWriter for Dirt4ConfigWriter {
         });
 
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -3434,14 +3899,16 @@ impl ConfigWriter for Dirt4ConfigWriter {
         });
         let expected = serde_json::to_string_pretty(&contract)?;
 
-        Ok(vec![ConfigDiff {
-            file_path: DIRT4_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: expected,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(DIRT4_BRIDGE_RELATIVE_PATH, expected)])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "dirt4",
+            &[
+                "Enable UDP telemetry in hardware_settings_config.xml, sending to port 20777 with extradata 0",
+            ],
+        )
     }
 }
 
@@ -3455,9 +3922,15 @@ impl Default for Ets2ConfigWriter {
 }
 
 impl ConfigWriter for Ets2ConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing ETS2 bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, ETS2_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, ETS2_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -3474,22 +3947,13 @@ impl ConfigWriter for Ets2ConfigWriter {
             "bridge_notes": "ETS2 uses SCS Telemetry SDK shared memory. Install the SCS Telemetry plugin.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
         let contract_path = resolve_game_path(game_path, ETS2_BRIDGE_RELATIVE_PATH);
@@ -3514,14 +3978,17 @@ impl ConfigWriter for Ets2ConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "ETS2 uses SCS Telemetry SDK shared memory. Install the SCS Telemetry plugin.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: ETS2_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            ETS2_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "ets2",
+            &["Install the SCS Telemetry SDK plugin in the game's bin/win_x64/plugins folder"],
+        )
     }
 }
 
@@ -3535,9 +4002,15 @@ impl Default for AtsConfigWriter {
 }
 
 impl ConfigWriter for AtsConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing ATS bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, ATS_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, ATS_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -3554,22 +4027,13 @@ impl ConfigWriter for AtsConfigWriter {
             "bridge_notes": "ATS uses SCS Telemetry SDK shared memory. Install the SCS Telemetry plugin.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
         let contract_path = resolve_game_path(game_path, ATS_BRIDGE_RELATIVE_PATH);
@@ -3594,14 +4058,17 @@ impl ConfigWriter for AtsConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "ATS uses SCS Telemetry SDK shared memory. Install the SCS Telemetry plugin.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: ATS_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            ATS_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "ats",
+            &["Install the SCS Telemetry SDK plugin in the game's bin/win_x64/plugins folder"],
+        )
     }
 }
 
@@ -3615,9 +4082,19 @@ impl Default for WreckfestConfigWriter {
 }
 
 impl ConfigWriter for WreckfestConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing Wreckfest bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, WRECKFEST_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(
+            game_path,
+            WRECKFEST_BRIDGE_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -3634,22 +4111,13 @@ impl ConfigWriter for WreckfestConfigWriter {
             "bridge_notes": "Wreckfest sends UDP telemetry on port 5606. Validated by WRKF magic header.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
         let contract_path = resolve_game_path(game_path, WRECKFEST_BRIDGE_RELATIVE_PATH);
@@ -3674,14 +4142,17 @@ impl ConfigWriter for WreckfestConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "Wreckfest sends UDP telemetry on port 5606. Validated by WRKF magic header.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: WRECKFEST_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            WRECKFEST_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "wreckfest",
+            &["Enable Wreckfest's UDP telemetry output on port 5606"],
+        )
     }
 }
 
@@ -3695,9 +4166,19 @@ impl Default for FlatOutConfigWriter {
 }
 
 impl ConfigWriter for FlatOutConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing FlatOut bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, FLATOUT_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(
+            game_path,
+            FLATOUT_BRIDGE_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -3714,22 +4195,13 @@ impl ConfigWriter for FlatOutConfigWriter {
             "bridge_notes": "FlatOut bridge sends UDP telemetry on port 7776. Validated by FOTC magic header.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
         let contract_path = resolve_game_path(game_path, FLATOUT_BRIDGE_RELATIVE_PATH);
@@ -3754,14 +4226,17 @@ impl ConfigWriter for FlatOutConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "FlatOut bridge sends UDP telemetry on port 7776. Validated by FOTC magic header.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: FLATOUT_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            FLATOUT_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "flatout",
+            &["Run the FlatOut telemetry bridge, sending to port 7776"],
+        )
     }
 }
 
@@ -3775,9 +4250,15 @@ impl Default for DakarDesertRallyConfigWriter {
 }
 
 impl ConfigWriter for DakarDesertRallyConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing Dakar Desert Rally bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, DAKAR_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, DAKAR_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -3794,22 +4275,13 @@ impl ConfigWriter for DakarDesertRallyConfigWriter {
             "bridge_notes": "Dakar Desert Rally bridge sends UDP telemetry on port 7779. Validated by DAKR magic header.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
         let contract_path = resolve_game_path(game_path, DAKAR_BRIDGE_RELATIVE_PATH);
@@ -3834,14 +4306,17 @@ impl ConfigWriter for DakarDesertRallyConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "Dakar Desert Rally bridge sends UDP telemetry on port 7779. Validated by DAKR magic header.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: DAKAR_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            DAKAR_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "dakar_desert_rally",
+            &["Run the Dakar Desert Rally telemetry bridge, sending to port 7779"],
+        )
     }
 }
 
@@ -3855,9 +4330,19 @@ impl Default for RennsportConfigWriter {
 }
 
 impl ConfigWriter for RennsportConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing Rennsport bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, RENNSPORT_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(
+            game_path,
+            RENNSPORT_BRIDGE_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -3874,22 +4359,13 @@ impl ConfigWriter for RennsportConfigWriter {
             "bridge_notes": "Rennsport sends UDP telemetry on port 9000. Validated by 0x52 'R' identifier byte.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
         let contract_path = resolve_game_path(game_path, RENNSPORT_BRIDGE_RELATIVE_PATH);
@@ -3914,14 +4390,17 @@ impl ConfigWriter for RennsportConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "Rennsport sends UDP telemetry on port 9000. Validated by 0x52 'R' identifier byte.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: RENNSPORT_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            RENNSPORT_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "rennsport",
+            &["Enable Rennsport's UDP telemetry output on port 9000"],
+        )
     }
 }
 
@@ -3935,9 +4414,19 @@ impl Default for GridAutosportConfigWriter {
 }
 
 impl ConfigWriter for GridAutosportConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing GRID Autosport bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, GRID_AUTOSPORT_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(
+            game_path,
+            GRID_AUTOSPORT_BRIDGE_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -3956,22 +4445,13 @@ impl ConfigWriter for GridAutosportConfigWriter {
             "bridge_notes": "GRID Autosport uses Codemasters UDP Mode 1 on port 20777.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -4006,14 +4486,17 @@ impl ConfigWriter for GridAutosportConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "GRID Autosport uses Codemasters UDP Mode 1 on port 20777.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: GRID_AUTOSPORT_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            GRID_AUTOSPORT_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "grid_autosport",
+            &["Enable the game's Codemasters UDP telemetry output on port 20777"],
+        )
     }
 }
 
@@ -4027,9 +4510,19 @@ impl Default for Grid2019ConfigWriter {
 }
 
 impl ConfigWriter for Grid2019ConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing GRID 2019 bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, GRID_2019_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(
+            game_path,
+            GRID_2019_BRIDGE_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -4047,22 +4540,13 @@ impl ConfigWriter for Grid2019ConfigWriter {
             "bridge_notes": "GRID (2019) uses Codemasters UDP Mode 1 on port 20777.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -4096,14 +4580,17 @@ impl ConfigWriter for Grid2019ConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "GRID (2019) uses Codemasters UDP Mode 1 on port 20777.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: GRID_2019_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            GRID_2019_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "grid_2019",
+            &["Enable the game's Codemasters UDP telemetry output on port 20777"],
+        )
     }
 }
 
@@ -4117,9 +4604,19 @@ impl Default for GridLegendsConfigWriter {
 }
 
 impl ConfigWriter for GridLegendsConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing GRID Legends bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, GRID_LEGENDS_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(
+            game_path,
+            GRID_LEGENDS_BRIDGE_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -4138,22 +4635,13 @@ impl ConfigWriter for GridLegendsConfigWriter {
             "bridge_notes": "GRID Legends uses Codemasters UDP Mode 1 on port 20777.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -4188,14 +4676,17 @@ impl ConfigWriter for GridLegendsConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "GRID Legends uses Codemasters UDP Mode 1 on port 20777.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: GRID_LEGENDS_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            GRID_LEGENDS_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "grid_legends",
+            &["Enable the game's Codemasters UDP telemetry output on port 20777"],
+        )
     }
 }
 
@@ -4209,9 +4700,15 @@ impl Default for Dirt3ConfigWriter {
 }
 
 impl ConfigWriter for Dirt3ConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing DiRT 3 bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, DIRT3_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, DIRT3_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -4229,22 +4726,13 @@ impl ConfigWriter for Dirt3ConfigWriter {
             "bridge_notes": "DiRT 3 uses Codemasters UDP Mode 1 on port 20777.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -4278,14 +4766,17 @@ impl ConfigWriter for Dirt3ConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "DiRT 3 uses Codemasters UDP Mode 1 on port 20777.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: DIRT3_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            DIRT3_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "dirt3",
+            &["Enable the game's Codemasters UDP telemetry output on port 20777"],
+        )
     }
 }
 
@@ -4299,9 +4790,19 @@ impl Default for RaceDriverGridConfigWriter {
 }
 
 impl ConfigWriter for RaceDriverGridConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing Race Driver: GRID bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, RACE_DRIVER_GRID_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(
+            game_path,
+            RACE_DRIVER_GRID_BRIDGE_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -4320,22 +4821,13 @@ impl ConfigWriter for RaceDriverGridConfigWriter {
             "bridge_notes": "Race Driver: GRID uses Codemasters UDP Mode 1 on port 20777.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -4370,14 +4862,17 @@ impl ConfigWriter for RaceDriverGridConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "Race Driver: GRID uses Codemasters UDP Mode 1 on port 20777.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: RACE_DRIVER_GRID_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            RACE_DRIVER_GRID_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "race_driver_grid",
+            &["Enable the game's Codemasters UDP telemetry output on port 20777"],
+        )
     }
 }
 
@@ -4394,9 +4889,19 @@ impl Default for AutomobilistaConfigWriter {
 }
 
 impl ConfigWriter for AutomobilistaConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing Automobilista 1 bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, AUTOMOBILISTA_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(
+            game_path,
+            AUTOMOBILISTA_BRIDGE_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -4412,22 +4917,13 @@ impl ConfigWriter for AutomobilistaConfigWriter {
             "bridge_notes": "Automobilista 1 uses ISI rFactor 1 shared memory. No in-game config file is required.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -4459,14 +4955,14 @@ impl ConfigWriter for AutomobilistaConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "Automobilista 1 uses ISI rFactor 1 shared memory. No in-game config file is required.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: AUTOMOBILISTA_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            AUTOMOBILISTA_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only("automobilista", &[])
     }
 }
 
@@ -4480,9 +4976,19 @@ impl Default for KartKraftConfigWriter {
 }
 
 impl ConfigWriter for KartKraftConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing KartKraft bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, KARTKRAFT_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(
+            game_path,
+            KARTKRAFT_BRIDGE_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -4499,22 +5005,13 @@ impl ConfigWriter for KartKraftConfigWriter {
             "bridge_notes": "KartKraft sends FlatBuffers UDP packets (KKFB identifier) on port 5000.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
         let contract_path = resolve_game_path(game_path, KARTKRAFT_BRIDGE_RELATIVE_PATH);
@@ -4539,14 +5036,17 @@ impl ConfigWriter for KartKraftConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "KartKraft sends FlatBuffers UDP packets (KKFB identifier) on port 5000.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: KARTKRAFT_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            KARTKRAFT_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "kartkraft",
+            &["Enable KartKraft's UDP output, sending to port 5000"],
+        )
     }
 }
 
@@ -4560,9 +5060,19 @@ impl Default for RaceRoomConfigWriter {
 }
 
 impl ConfigWriter for RaceRoomConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing RaceRoom bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, RACEROOM_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(
+            game_path,
+            RACEROOM_BRIDGE_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -4578,22 +5088,13 @@ impl ConfigWriter for RaceRoomConfigWriter {
             "bridge_notes": "R3E shared memory is Windows-only. RaceRoom writes to Local\\$R3E automatically when running. No in-game settings required. Supported SDK version: 2.x",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
         let contract_path = resolve_game_path(game_path, RACEROOM_BRIDGE_RELATIVE_PATH);
@@ -4617,26 +5118,39 @@ impl ConfigWriter for RaceRoomConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "R3E shared memory is Windows-only. RaceRoom writes to Local\\$R3E automatically when running. No in-game settings required. Supported SDK version: 2.x",
         });
-        Ok(vec![ConfigDiff {
-            file_path: RACEROOM_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            RACEROOM_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only("raceroom", &[])
     }
 }
 
 impl ConfigWriter for EAWRCConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing EA WRC telemetry configuration");
+        let definition = eawrc_structure_definition(&eawrc_channels(&config.fields)?);
 
-        let telemetry_root = resolve_game_path(game_path, "Documents/My Games/WRC/telemetry");
-        let config_path = telemetry_root.join("config.json");
-        let structure_path = telemetry_root
-            .join("udp")
-            .join(format!("{EAWRC_STRUCTURE_ID}.json"));
+        let config_path = confine(
+            game_path,
+            "Documents/My Games/WRC/telemetry/config.json",
+            &options.path_policy,
+        )?;
+        let structure_path = confine(
+            game_path,
+            &format!("Documents/My Games/WRC/telemetry/udp/{EAWRC_STRUCTURE_ID}.json"),
+            &options.path_policy,
+        )?;
+        let _config_lock = lock_config_file(&config_path, options.mode)?;
+        let _structure_lock = lock_config_file(&structure_path, options.mode)?;
 
         let existed_before = config_path.exists();
         let existing_content = if existed_before {
@@ -4664,9 +5178,15 @@ impl ConfigWriter for EAWRCConfigWriter {
             anyhow!("EA WRC config field 'udp.packetAssignments' is not a JSON array")
         })?;
 
-        let listener_port = parse_target_port(&config.output_target).unwrap_or(EAWRC_DEFAULT_PORT);
+        let requested_port = parse_target_port(&config.output_target).unwrap_or(EAWRC_DEFAULT_PORT);
         let listener_ip =
             parse_target_host(&config.output_target).unwrap_or_else(|| "127.0.0.1".to_string());
+        let listener_port = eawrc_free_port(
+            assignments,
+            &listener_ip,
+            requested_port,
+            config.allow_port_sharing,
+        )?;
 
         let assignment = serde_json::json!({
             "packetId": EAWRC_PACKET_ID,
@@ -4704,41 +5224,79 @@ impl ConfigWriter for EAWRCConfigWriter {
 
         let new_config_content = serde_json::to_string_pretty(&Value::Object(root))?;
 
-        if let Some(parent) = config_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&config_path, &new_config_content)?;
+        let config_backup = config_path.write(&new_config_content, options)?;
 
-        if let Some(parent) = structure_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        let structure_content = serde_json::to_string_pretty(&eawrc_structure_definition())?;
-        fs::write(&structure_path, &structure_content)?;
+        let previous_structure = if structure_path.exists() {
+            Some(fs::read_to_string(&structure_path)?)
+        } else {
+            None
+        };
+        // A structure file that only differs in formatting is left alone.
+        let structure_content = match &previous_structure {
+            Some(existing)
+                if serde_json::from_str::<Value>(existing).ok().as_ref() == Some(&definition) =>
+            {
+                existing.clone()
+            }
+            _ => serde_json::to_string_pretty(&definition)?,
+        };
+        let structure_backup = structure_path.write(&structure_content, options)?;
 
         Ok(vec![
-            ConfigDiff {
-                file_path: config_path.to_string_lossy().to_string(),
-                section: None,
-                key: "entire_file".to_string(),
-                old_value: existing_content,
-                new_value: new_config_content,
-                operation: if existed_before {
-                    DiffOperation::Modify
-                } else {
-                    DiffOperation::Add
-                },
-            },
-            ConfigDiff {
-                file_path: structure_path.to_string_lossy().to_string(),
-                section: None,
-                key: "entire_file".to_string(),
-                old_value: None,
-                new_value: structure_content,
-                operation: DiffOperation::Add,
-            },
+            whole_file_diff(
+                &config_path,
+                existing_content,
+                new_config_content,
+                config_backup,
+            ),
+            whole_file_diff(
+                &structure_path,
+                previous_structure,
+                structure_content,
+                structure_backup,
+            ),
         ])
     }
 
+    /// Deletes OpenRacing's structure file and drops its packet assignment
+    /// from config.json, keeping assignments other tools registered.
+    fn remove_config(&self, game_path: &Path) -> Result<Vec<ConfigDiff>> {
+        let config_path = confine(
+            game_path,
+            "Documents/My Games/WRC/telemetry/config.json",
+            &PathPolicy::default(),
+        )?;
+        let structure_path = confine(
+            game_path,
+            &format!("Documents/My Games/WRC/telemetry/udp/{EAWRC_STRUCTURE_ID}.json"),
+            &PathPolicy::default(),
+        )?;
+
+        let mut diffs = remove_json_keys(&config_path, |root| {
+            let Some(udp) = root.get_mut("udp").and_then(Value::as_object_mut) else {
+                return;
+            };
+            if let Some(assignments) = udp
+                .get_mut("packetAssignments")
+                .and_then(Value::as_array_mut)
+            {
+                assignments.retain(|entry| {
+                    entry.get("packetId").and_then(Value::as_str) != Some(EAWRC_PACKET_ID)
+                        || entry.get("structureId").and_then(Value::as_str)
+                            != Some(EAWRC_STRUCTURE_ID)
+                });
+                if assignments.is_empty() {
+                    udp.remove("packetAssignments");
+                }
+            }
+            if udp.is_empty() {
+                root.remove("udp");
+            }
+        })?;
+        diffs.extend(remove_owned_file(&structure_path)?);
+        Ok(diffs)
+    }
+
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
         let telemetry_root = resolve_game_path(game_path, "Documents/My Games/WRC/telemetry");
         let config_path = telemetry_root.join("config.json");
@@ -4784,8 +5342,12 @@ impl ConfigWriter for EAWRCConfigWriter {
                 })
             })
             .unwrap_or(false);
+        if !assignment_ok {
+            return Ok(false);
+        }
 
-        Ok(assignment_ok)
+        let structure: Value = serde_json::from_str(&fs::read_to_string(structure_path)?)?;
+        Ok(eawrc_structure_is_valid(&structure))
     }
 
     fn get_expected_diffs(&self, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
@@ -4808,32 +5370,33 @@ impl ConfigWriter for EAWRCConfigWriter {
                 ]
             }
         }))?;
-        let structure_content = serde_json::to_string_pretty(&eawrc_structure_definition())?;
+        let structure_content = serde_json::to_string_pretty(&eawrc_structure_definition(
+            &eawrc_channels(&config.fields)?,
+        ))?;
 
         Ok(vec![
-            ConfigDiff {
-                file_path: "Documents/My Games/WRC/telemetry/config.json".to_string(),
-                section: None,
-                key: "entire_file".to_string(),
-                old_value: None,
-                new_value: config_content,
-                operation: DiffOperation::Add,
-            },
-            ConfigDiff {
-                file_path: format!(
-                    "Documents/My Games/WRC/telemetry/udp/{EAWRC_STRUCTURE_ID}.json"
-                ),
-                section: None,
-                key: "entire_file".to_string(),
-                old_value: None,
-                new_value: structure_content,
-                operation: DiffOperation::Add,
-            },
+            new_file_diff(
+                "Documents/My Games/WRC/telemetry/config.json",
+                config_content,
+            ),
+            new_file_diff(
+                format!("Documents/My Games/WRC/telemetry/udp/{EAWRC_STRUCTURE_ID}.json"),
+                structure_content,
+            ),
         ])
     }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata {
+            game_id: "eawrc",
+            requires_game_restart: true,
+            manual_steps: Vec::new(),
+            native_config_modified: true,
+        }
+    }
 }
 
-fn eawrc_structure_definition() -> Value {
+fn eawrc_structure_definition(channels: &[&str]) -> Value {
     serde_json::json!({
         "id": EAWRC_STRUCTURE_ID,
         "packets": [
@@ -4842,101 +5405,358 @@ fn eawrc_structure_definition() -> Value {
                 "header": {
                     "channels": ["packet_uid"]
                 },
-                "channels": [
-                    "ffb_scalar",
-                    "engine_rpm",
-                    "vehicle_speed",
-                    "gear",
-                    "slip_ratio"
-                ]
+                "channels": channels
             }
         ]
     })
 }
 
-fn upsert_ini_value(
-    content: &str,
-    section: &str,
-    key: &str,
-    new_value: &str,
-) -> (String, Option<String>, DiffOperation) {
-    let section_header = format!("[{section}]");
-    let key_prefix = format!("{key}=");
-
-    let mut lines: Vec<String> = if content.is_empty() {
-        Vec::new()
-    } else {
-        content.lines().map(ToOwned::to_owned).collect()
-    };
-
-    let mut section_start = None;
-    let mut section_end = lines.len();
-
-    for (index, line) in lines.iter().enumerate() {
-        let trimmed = line.trim();
-        if trimmed.starts_with('[') && trimmed.ends_with(']') {
-            if section_start.is_some() {
-                section_end = index;
-                break;
-            }
-
-            if trimmed.eq_ignore_ascii_case(&section_header) {
-                section_start = Some(index);
+/// The EA WRC channels carrying `fields`, or the default channels when no
+/// fields are requested. EA WRC's own channel identifiers are accepted
+/// alongside normalized field names.
+fn eawrc_channels(fields: &[String]) -> Result<Vec<&'static str>> {
+    if fields.is_empty() {
+        return Ok(EAWRC_DEFAULT_CHANNELS.to_vec());
+    }
+    let mut channels = Vec::with_capacity(fields.len());
+    for field in fields {
+        let native = EAWRC_CHANNELS
+            .iter()
+            .find(|(_, channel)| *channel == field.trim());
+        let channel = match native {
+            Some((_, channel)) => *channel,
+            None => {
+                let name = normalize_field_name(field)?;
+                EAWRC_CHANNELS
+                    .iter()
+                    .find(|(known, _)| *known == name)
+                    .map(|(_, channel)| *channel)
+                    .ok_or_else(|| {
+                        let valid: Vec<&str> =
+                            EAWRC_CHANNELS.iter().map(|(known, _)| *known).collect();
+                        anyhow!(
+                            "EA WRC cannot export telemetry field '{name}'; valid fields are: {}",
+                            valid.join(", ")
+                        )
+                    })?
             }
+        };
+        if !channels.contains(&channel) {
+            channels.push(channel);
         }
     }
+    Ok(channels)
+}
 
-    let mut previous_value = None;
-    if let Some(start) = section_start {
-        let search_start = start + 1;
-        let mut key_line_index = None;
+/// The port OpenRacing's assignment should use on `ip`.
+///
+/// Another tool's assignment already sending to `ip:requested` is an error,
+/// unless `allow_port_sharing` is set, in which case the next port no other
+/// tool uses on `ip` is picked.
+fn eawrc_free_port(
+    assignments: &[Value],
+    ip: &str,
+    requested: u16,
+    allow_port_sharing: bool,
+) -> Result<u16> {
+    let taken: Vec<(u16, &str)> = assignments
+        .iter()
+        .filter(|entry| {
+            entry.get("structureId").and_then(Value::as_str) != Some(EAWRC_STRUCTURE_ID)
+        })
+        .filter(|entry| entry.get("ip").and_then(Value::as_str) == Some(ip))
+        .filter_map(|entry| {
+            let port = u16::try_from(entry.get("port")?.as_u64()?).ok()?;
+            let owner = entry
+                .get("structureId")
+                .and_then(Value::as_str)
+                .unwrap_or("unnamed");
+            Some((port, owner))
+        })
+        .collect();
 
-        for (index, line) in lines
+    let Some((_, owner)) = taken.iter().find(|(port, _)| *port == requested) else {
+        return Ok(requested);
+    };
+    if !allow_port_sharing {
+        return Err(anyhow!(
+            "EA WRC port {ip}:{requested} is already assigned to structure '{owner}'; \
+             choose another output port or set allow_port_sharing"
+        ));
+    }
+    let port = (requested.saturating_add(1)..=u16::MAX)
+        .find(|candidate| taken.iter().all(|(port, _)| port != candidate))
+        .ok_or_else(|| anyhow!("EA WRC has no free port on {ip} above {requested}"))?;
+    info!(
+        requested,
+        port, owner, "EA WRC port already in use by another tool; using the next free port"
+    );
+    Ok(port)
+}
+
+/// Whether `structure` is OpenRacing's packet with a header and a non-empty
+/// list of distinct channels OpenRacing knows how to read.
+fn eawrc_structure_is_valid(structure: &Value) -> bool {
+    let Some([packet]) = structure
+        .get("packets")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+    else {
+        return false;
+    };
+    let Some(channels) = packet.get("channels").and_then(Value::as_array) else {
+        return false;
+    };
+    let known = |channel: &Value| {
+        EAWRC_CHANNELS
             .iter()
-            .enumerate()
-            .take(section_end)
-            .skip(search_start)
-        {
-            let trimmed = line.trim();
-            if let Some(rest) = trimmed.strip_prefix(&key_prefix) {
-                key_line_index = Some(index);
-                previous_value = Some(rest.trim().to_string());
-                break;
+            .any(|(_, known)| channel.as_str() == Some(known))
+    };
+    let distinct = channels
+        .iter()
+        .enumerate()
+        .all(|(index, channel)| !channels[..index].contains(channel));
+
+    structure.get("id").and_then(Value::as_str) == Some(EAWRC_STRUCTURE_ID)
+        && packet.get("id").and_then(Value::as_str) == Some(EAWRC_PACKET_ID)
+        && packet.pointer("/header/channels") == Some(&serde_json::json!(["packet_uid"]))
+        && !channels.is_empty()
+        && channels.iter().all(known)
+        && distinct
+}
+
+/// The diffs a write to `game_path` would make, computed without writing.
+/// Writers' file locations do not depend on the config being written.
+pub(crate) fn planned_diffs(
+    writer: &(impl ConfigWriter + ?Sized),
+    game_path: &Path,
+) -> Result<Vec<ConfigDiff>> {
+    let placeholder = TelemetryConfig {
+        enabled: true,
+        update_rate_hz: 60,
+        output_method: String::new(),
+        output_target: String::new(),
+        fields: Vec::new(),
+        enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
+    };
+    writer.preview_config(game_path, &placeholder)
+}
+
+/// Set each of `values` in `section` of the INI file at `target`, writing
+/// the file once if anything changed. Returns one diff per key.
+fn write_ini_values(
+    target: &ConfinedPath,
+    section: &str,
+    values: &[(&str, String)],
+    options: &WriteOptions,
+) -> Result<Vec<ConfigDiff>> {
+    let existing_content = if target.exists() {
+        fs::read_to_string(target)?
+    } else {
+        String::new()
+    };
+    let mut new_content = existing_content;
+    let mut diffs = Vec::with_capacity(values.len());
+    for (key, value) in values {
+        let (updated_content, old_value, operation) =
+            upsert_ini_value(&new_content, section, key, value);
+        new_content = updated_content;
+        diffs.push(ConfigDiff {
+            file_path: target.to_string_lossy().to_string(),
+            section: Some(section.to_string()),
+            key: key.to_string(),
+            old_value,
+            new_value: value.clone(),
+            operation,
+            backup_path: None,
+            details: Vec::new(),
+        });
+    }
+
+    if diffs
+        .iter()
+        .any(|diff| diff.operation != DiffOperation::NoChange)
+    {
+        let backup = target.write(&new_content, options)?;
+        let backup = backup.map(|backup| backup.to_string_lossy().into_owned());
+        for diff in &mut diffs {
+            if diff.operation != DiffOperation::NoChange {
+                diff.backup_path = backup.clone();
             }
         }
+    }
+    Ok(diffs)
+}
+
+/// Delete `target` if it exists, returning a `Remove` diff with its contents.
+fn remove_owned_file(target: &ConfinedPath) -> Result<Option<ConfigDiff>> {
+    let _lock = lock_config_file(target, WriteMode::Apply)?;
+    if !target.exists() {
+        return Ok(None);
+    }
+    let previous = fs::read_to_string(target)?;
+    target.remove(WriteMode::Apply)?;
+    Ok(Some(removed_file_diff(target, previous)))
+}
+
+fn removed_file_diff(path: &Path, previous: String) -> ConfigDiff {
+    let details = json_details(Some(&previous), None);
+    ConfigDiff {
+        file_path: path.to_string_lossy().to_string(),
+        section: None,
+        key: "entire_file".to_string(),
+        old_value: Some(previous),
+        new_value: String::new(),
+        operation: DiffOperation::Remove,
+        backup_path: None,
+        details,
+    }
+}
+
+/// Take OpenRacing's keys out of a JSON file the game owns, deleting the file
+/// when `edit` leaves it empty. A missing file yields no diffs; one that is not
+/// a JSON object is refused rather than overwritten.
+fn remove_json_keys(
+    target: &ConfinedPath,
+    edit: impl FnOnce(&mut Map<String, Value>),
+) -> Result<Vec<ConfigDiff>> {
+    let _lock = lock_config_file(target, WriteMode::Apply)?;
+    if !target.exists() {
+        return Ok(Vec::new());
+    }
+    let existing = fs::read_to_string(target)?;
+    let mut object = parse_json_object(&existing).ok_or_else(|| {
+        anyhow!(
+            "{} is not a JSON object; leaving it untouched",
+            target.display()
+        )
+    })?;
+    edit(&mut object);
+    if object.is_empty() {
+        target.remove(WriteMode::Apply)?;
+        return Ok(vec![removed_file_diff(target, existing)]);
+    }
+    let new_content = serde_json::to_string_pretty(&Value::Object(object))?;
+    let backup = target.write(&new_content, &WriteOptions::default())?;
+    Ok(vec![whole_file_diff(
+        target,
+        Some(existing),
+        new_content,
+        backup,
+    )])
+}
+
+/// Revert `diffs` newest first, so several diffs on one file unwind in order.
+///
+/// Rolling back takes no backups; the files it restores are the backups.
+fn rollback_diffs(game_path: &Path, diffs: &[ConfigDiff]) -> Result<()> {
+    let unbacked = WriteOptions::default().with_backup(BackupOptions::disabled());
+    for diff in diffs.iter().rev() {
+        if diff.operation == DiffOperation::NoChange {
+            continue;
+        }
+        let target = confine_recorded(
+            game_path,
+            Path::new(&diff.file_path),
+            &PathPolicy::default(),
+        )?;
+        let _lock = lock_config_file(&target, WriteMode::Apply)?;
+        let Some(section) = &diff.section else {
+            if diff.key != "entire_file" {
+                return Err(anyhow!(
+                    "cannot roll back key '{}' in {}: only whole-file and INI diffs are supported",
+                    diff.key,
+                    diff.file_path
+                ));
+            }
+            match &diff.old_value {
+                Some(previous) => {
+                    target.write(previous, &unbacked)?;
+                }
+                None => target.remove(WriteMode::Apply)?,
+            }
+            continue;
+        };
 
-        if let Some(index) = key_line_index {
-            lines[index] = format!("{key}={new_value}");
-            let output = normalize_ini_output(lines);
-            return (output, previous_value, DiffOperation::Modify);
+        let content = if target.exists() {
+            fs::read_to_string(&target)?
+        } else {
+            String::new()
+        };
+        let reverted = match (&diff.operation, &diff.old_value) {
+            (DiffOperation::Modify | DiffOperation::Remove, Some(previous)) => {
+                upsert_ini_value(&content, section, &diff.key, previous).0
+            }
+            _ => remove_ini_value(&content, section, &diff.key),
+        };
+        if reverted.trim().is_empty() {
+            target.remove(WriteMode::Apply)?;
+        } else {
+            target.write(&reverted, &unbacked)?;
         }
-
-        lines.insert(section_end, format!("{key}={new_value}"));
-        let output = normalize_ini_output(lines);
-        return (output, previous_value, DiffOperation::Add);
     }
+    Ok(())
+}
 
-    if !lines.is_empty()
-        && !lines
-            .last()
-            .map(|line| line.trim().is_empty())
-            .unwrap_or(false)
-    {
-        lines.push(String::new());
+/// Diff for a writer that replaces `path` wholesale; `backup` is what
+/// [`ConfinedPath::write`](path_safety::ConfinedPath::write) returned.
+fn whole_file_diff(
+    path: &Path,
+    existing: Option<String>,
+    new_content: String,
+    backup: Option<PathBuf>,
+) -> ConfigDiff {
+    let operation = match &existing {
+        Some(existing) if same_config_content(existing, &new_content) => DiffOperation::NoChange,
+        Some(_) => DiffOperation::Modify,
+        None => DiffOperation::Add,
+    };
+    let details = if operation == DiffOperation::NoChange {
+        Vec::new()
+    } else {
+        json_details(existing.as_deref(), Some(&new_content))
+    };
+    ConfigDiff {
+        file_path: path.to_string_lossy().to_string(),
+        section: None,
+        key: "entire_file".to_string(),
+        old_value: existing,
+        new_value: new_content,
+        operation,
+        backup_path: backup.map(|backup| backup.to_string_lossy().into_owned()),
+        details,
     }
+}
 
-    lines.push(section_header);
-    lines.push(format!("{key}={new_value}"));
-    let output = normalize_ini_output(lines);
-    (output, previous_value, DiffOperation::Add)
+/// The diff `get_expected_diffs` reports for a file created with `content`.
+fn new_file_diff(file_path: impl Into<String>, content: String) -> ConfigDiff {
+    let details = json_details(None, Some(&content));
+    ConfigDiff {
+        file_path: file_path.into(),
+        section: None,
+        key: "entire_file".to_string(),
+        old_value: None,
+        new_value: content,
+        operation: DiffOperation::Add,
+        backup_path: None,
+        details,
+    }
 }
 
-fn normalize_ini_output(lines: Vec<String>) -> String {
-    let mut output = lines.join("\n");
-    if !output.ends_with('\n') {
-        output.push('\n');
+/// Whether `existing` already says what `new_content` does. JSON is compared
+/// parsed, so key order and whitespace do not count as changes.
+pub(crate) fn same_config_content(existing: &str, new_content: &str) -> bool {
+    if existing == new_content {
+        return true;
+    }
+    match (
+        serde_json::from_str::<Value>(existing),
+        serde_json::from_str::<Value>(new_content),
+    ) {
+        (Ok(existing), Ok(new_content)) => existing == new_content,
+        _ => false,
     }
-    output
 }
 
 fn parse_json_object(content: &str) -> Option<Map<String, Value>> {
@@ -4982,9 +5802,15 @@ impl Default for NascarConfigWriter {
 }
 
 impl ConfigWriter for NascarConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing NASCAR bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, NASCAR_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, NASCAR_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -5001,22 +5827,13 @@ impl ConfigWriter for NascarConfigWriter {
             "bridge_notes": "NASCAR Racing (Papyrus series) sends Papyrus UDP packets on port 5606.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
         let contract_path = resolve_game_path(game_path, NASCAR_BRIDGE_RELATIVE_PATH);
@@ -5041,14 +5858,17 @@ impl ConfigWriter for NascarConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "NASCAR Racing (Papyrus series) sends Papyrus UDP packets on port 5606.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: NASCAR_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            NASCAR_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "nascar",
+            &["Enable the game's Papyrus UDP telemetry output on port 5606"],
+        )
     }
 }
 
@@ -5062,9 +5882,19 @@ impl Default for Nascar21ConfigWriter {
 }
 
 impl ConfigWriter for Nascar21ConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing NASCAR 21: Ignition bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, NASCAR_21_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(
+            game_path,
+            NASCAR_21_BRIDGE_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -5081,22 +5911,13 @@ impl ConfigWriter for Nascar21ConfigWriter {
             "bridge_notes": "NASCAR 21: Ignition uses the Papyrus UDP telemetry format on port 5606.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -5123,14 +5944,17 @@ impl ConfigWriter for Nascar21ConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "NASCAR 21: Ignition uses the Papyrus UDP telemetry format on port 5606.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: NASCAR_21_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            NASCAR_21_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "nascar_21",
+            &["Enable the game's Papyrus UDP telemetry output on port 5606"],
+        )
     }
 }
 
@@ -5144,9 +5968,15 @@ impl Default for LeMansUltimateConfigWriter {
 }
 
 impl ConfigWriter for LeMansUltimateConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing Le Mans Ultimate bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, LMU_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, LMU_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -5163,22 +5993,13 @@ impl ConfigWriter for LeMansUltimateConfigWriter {
             "bridge_notes": "Le Mans Ultimate uses rF2 UDP telemetry protocol on port 6789.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
         let contract_path = resolve_game_path(game_path, LMU_BRIDGE_RELATIVE_PATH);
@@ -5203,14 +6024,17 @@ impl ConfigWriter for LeMansUltimateConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "Le Mans Ultimate uses rF2 UDP telemetry protocol on port 6789.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: LMU_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            LMU_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "le_mans_ultimate",
+            &["Enable Le Mans Ultimate's UDP telemetry output on port 6789"],
+        )
     }
 }
 
@@ -5224,9 +6048,15 @@ impl Default for WtcrConfigWriter {
 }
 
 impl ConfigWriter for WtcrConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing WTCR bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, WTCR_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, WTCR_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -5244,22 +6074,13 @@ impl ConfigWriter for WtcrConfigWriter {
             "bridge_notes": "WTCR Race of the World uses Codemasters UDP Mode 1 on port 6778.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
         let contract_path = resolve_game_path(game_path, WTCR_BRIDGE_RELATIVE_PATH);
@@ -5285,14 +6106,17 @@ impl ConfigWriter for WtcrConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "WTCR Race of the World uses Codemasters UDP Mode 1 on port 6778.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: WTCR_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            WTCR_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "wtcr",
+            &["Enable the game's Codemasters UDP telemetry output on port 6778"],
+        )
     }
 }
 
@@ -5306,9 +6130,19 @@ impl Default for TrackmaniaConfigWriter {
 }
 
 impl ConfigWriter for TrackmaniaConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing Trackmania bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, TRACKMANIA_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(
+            game_path,
+            TRACKMANIA_BRIDGE_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -5325,22 +6159,13 @@ impl ConfigWriter for TrackmaniaConfigWriter {
             "bridge_notes": "Trackmania sends JSON-over-UDP telemetry on port 5004.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
         let contract_path = resolve_game_path(game_path, TRACKMANIA_BRIDGE_RELATIVE_PATH);
@@ -5365,14 +6190,17 @@ impl ConfigWriter for TrackmaniaConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "Trackmania sends JSON-over-UDP telemetry on port 5004.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: TRACKMANIA_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            TRACKMANIA_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "trackmania",
+            &["Install an Openplanet telemetry plugin that sends JSON over UDP to port 5004"],
+        )
     }
 }
 
@@ -5386,9 +6214,15 @@ impl Default for SimHubConfigWriter {
 }
 
 impl ConfigWriter for SimHubConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing SimHub bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, SIMHUB_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, SIMHUB_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -5405,22 +6239,13 @@ impl ConfigWriter for SimHubConfigWriter {
             "bridge_notes": "SimHub forwards game telemetry as JSON UDP datagrams on port 5555.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -5447,14 +6272,17 @@ impl ConfigWriter for SimHubConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "SimHub forwards game telemetry as JSON UDP datagrams on port 5555.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: SIMHUB_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            SIMHUB_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "simhub",
+            &["In SimHub, forward game data as JSON UDP to port 5555"],
+        )
     }
 }
 
@@ -5468,9 +6296,19 @@ impl Default for MudRunnerConfigWriter {
 }
 
 impl ConfigWriter for MudRunnerConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing MudRunner bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, MUDRUNNER_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(
+            game_path,
+            MUDRUNNER_BRIDGE_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -5487,22 +6325,13 @@ impl ConfigWriter for MudRunnerConfigWriter {
             "bridge_notes": "MudRunner routes telemetry through SimHub JSON UDP on port 8877.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -5529,14 +6358,17 @@ impl ConfigWriter for MudRunnerConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "MudRunner routes telemetry through SimHub JSON UDP on port 8877.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: MUDRUNNER_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            MUDRUNNER_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "mudrunner",
+            &["In SimHub, forward MudRunner data as JSON UDP to port 8877"],
+        )
     }
 }
 
@@ -5550,9 +6382,19 @@ impl Default for SnowRunnerConfigWriter {
 }
 
 impl ConfigWriter for SnowRunnerConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing SnowRunner bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, SNOWRUNNER_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(
+            game_path,
+            SNOWRUNNER_BRIDGE_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -5569,22 +6411,13 @@ impl ConfigWriter for SnowRunnerConfigWriter {
             "bridge_notes": "SnowRunner routes telemetry through SimHub JSON UDP on port 8877.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -5611,14 +6444,17 @@ impl ConfigWriter for SnowRunnerConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "SnowRunner routes telemetry through SimHub JSON UDP on port 8877.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: SNOWRUNNER_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            SNOWRUNNER_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "snowrunner",
+            &["In SimHub, forward SnowRunner data as JSON UDP to port 8877"],
+        )
     }
 }
 
@@ -5634,9 +6470,15 @@ impl Default for MotoGPConfigWriter {
 }
 
 impl ConfigWriter for MotoGPConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing MotoGP bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, MOTOGP_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, MOTOGP_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -5653,22 +6495,13 @@ impl ConfigWriter for MotoGPConfigWriter {
             "bridge_notes": "MotoGP 23/24 telemetry requires SimHub UDP bridge on port 5556.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -5695,14 +6528,17 @@ impl ConfigWriter for MotoGPConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "MotoGP 23/24 telemetry requires SimHub UDP bridge on port 5556.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: MOTOGP_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            MOTOGP_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "motogp",
+            &["In SimHub, forward MotoGP data as JSON UDP to port 5556"],
+        )
     }
 }
 
@@ -5718,9 +6554,15 @@ impl Default for Ride5ConfigWriter {
 }
 
 impl ConfigWriter for Ride5ConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing RIDE 5 bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, RIDE5_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, RIDE5_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -5737,22 +6579,13 @@ impl ConfigWriter for Ride5ConfigWriter {
             "bridge_notes": "RIDE 5 telemetry requires SimHub UDP bridge on port 5558.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -5779,14 +6612,17 @@ impl ConfigWriter for Ride5ConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "RIDE 5 telemetry requires SimHub UDP bridge on port 5558.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: RIDE5_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            RIDE5_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "ride5",
+            &["In SimHub, forward RIDE 5 data as JSON UDP to port 5558"],
+        )
     }
 }
 
@@ -5807,10 +6643,16 @@ pub struct RFactor1ConfigWriter {
 }
 
 impl ConfigWriter for RFactor1ConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing {} bridge contract configuration", self.game_id);
         let relative_path = rf1_bridge_path(self.game_id);
-        let contract_path = game_path.join(relative_path);
+        let contract_path = confine(game_path, relative_path, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -5827,22 +6669,13 @@ impl ConfigWriter for RFactor1ConfigWriter {
             "bridge_notes": "rFactor 1 engine UDP telemetry on port 6776 (TelemInfoV2 format).",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -5869,14 +6702,19 @@ impl ConfigWriter for RFactor1ConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "rFactor 1 engine UDP telemetry on port 6776 (TelemInfoV2 format).",
         });
-        Ok(vec![ConfigDiff {
-            file_path: rf1_bridge_path(self.game_id).to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            rf1_bridge_path(self.game_id),
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            self.game_id,
+            &[
+                "Install a TelemInfoV2 UDP telemetry plugin in the game's Plugins folder, sending to port 6776",
+            ],
+        )
     }
 }
 
@@ -5899,9 +6737,19 @@ impl Default for VRally4ConfigWriter {
 }
 
 impl ConfigWriter for VRally4ConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing V-Rally 4 bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, V_RALLY_4_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(
+            game_path,
+            V_RALLY_4_BRIDGE_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -5918,22 +6766,13 @@ impl ConfigWriter for VRally4ConfigWriter {
             "bridge_notes": "V-Rally 4 uses the Kylotonn UDP binary format on port 64000.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -5960,14 +6799,17 @@ impl ConfigWriter for VRally4ConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "V-Rally 4 uses the Kylotonn UDP binary format on port 64000.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: V_RALLY_4_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            V_RALLY_4_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "v_rally_4",
+            &["Enable UDP telemetry in the game's settings, sending to port 64000"],
+        )
     }
 }
 
@@ -5981,9 +6823,15 @@ impl Default for GravelConfigWriter {
 }
 
 impl ConfigWriter for GravelConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing Gravel bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, GRAVEL_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, GRAVEL_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -6000,22 +6848,13 @@ impl ConfigWriter for GravelConfigWriter {
             "bridge_notes": "Gravel routes telemetry through SimHub JSON UDP on port 5555.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -6042,14 +6881,17 @@ impl ConfigWriter for GravelConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "Gravel routes telemetry through SimHub JSON UDP on port 5555.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: GRAVEL_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            GRAVEL_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "gravel",
+            &["In SimHub, forward Gravel data as JSON UDP to port 5555"],
+        )
     }
 }
 
@@ -6063,9 +6905,19 @@ impl Default for SebLoebRallyConfigWriter {
 }
 
 impl ConfigWriter for SebLoebRallyConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing Sébastien Loeb Rally EVO bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, SEB_LOEB_RALLY_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(
+            game_path,
+            SEB_LOEB_RALLY_BRIDGE_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -6079,22 +6931,13 @@ impl ConfigWriter for SebLoebRallyConfigWriter {
             "bridge_notes": "Sébastien Loeb Rally EVO has limited telemetry support. Stub adapter.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -6118,14 +6961,14 @@ impl ConfigWriter for SebLoebRallyConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "Sébastien Loeb Rally EVO has limited telemetry support. Stub adapter.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: SEB_LOEB_RALLY_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            SEB_LOEB_RALLY_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only("seb_loeb_rally", &[])
     }
 }
 
@@ -6139,9 +6982,15 @@ impl Default for ACC2ConfigWriter {
 }
 
 impl ConfigWriter for ACC2ConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing ACC2 bridge contract (stub — no telemetry protocol published)");
-        let contract_path = resolve_game_path(game_path, ACC2_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, ACC2_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -6155,22 +7004,13 @@ impl ConfigWriter for ACC2ConfigWriter {
             "bridge_notes": "ACC2 has not been announced. No telemetry protocol documented. See F-022.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -6194,14 +7034,14 @@ impl ConfigWriter for ACC2ConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "ACC2 has not been announced. No telemetry protocol documented. See F-022.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: ACC2_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            ACC2_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only("acc2", &[])
     }
 }
 
@@ -6215,9 +7055,15 @@ impl Default for ACEvoConfigWriter {
 }
 
 impl ConfigWriter for ACEvoConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing AC EVO bridge contract (stub — no telemetry protocol published)");
-        let contract_path = resolve_game_path(game_path, AC_EVO_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(game_path, AC_EVO_BRIDGE_RELATIVE_PATH, &options.path_policy)?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -6231,22 +7077,13 @@ impl ConfigWriter for ACEvoConfigWriter {
             "bridge_notes": "AC EVO is in Early Access with no public telemetry API. See F-022.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -6270,14 +7107,14 @@ impl ConfigWriter for ACEvoConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "AC EVO is in Early Access with no public telemetry API. See F-022.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: AC_EVO_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            AC_EVO_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only("ac_evo", &[])
     }
 }
 
@@ -6291,9 +7128,19 @@ impl Default for DirtShowdownConfigWriter {
 }
 
 impl ConfigWriter for DirtShowdownConfigWriter {
-    fn write_config(&self, game_path: &Path, config: &TelemetryConfig) -> Result<Vec<ConfigDiff>> {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> Result<Vec<ConfigDiff>> {
         info!("Writing DiRT Showdown bridge contract configuration");
-        let contract_path = resolve_game_path(game_path, DIRT_SHOWDOWN_BRIDGE_RELATIVE_PATH);
+        let contract_path = confine(
+            game_path,
+            DIRT_SHOWDOWN_BRIDGE_RELATIVE_PATH,
+            &options.path_policy,
+        )?;
+        let _lock = lock_config_file(&contract_path, options.mode)?;
         let existed_before = contract_path.exists();
         let existing_content = if existed_before {
             Some(fs::read_to_string(&contract_path)?)
@@ -6312,22 +7159,13 @@ impl ConfigWriter for DirtShowdownConfigWriter {
             "bridge_notes": "DiRT Showdown uses Codemasters UDP Mode 1 on port 20777.",
         });
         let new_content = serde_json::to_string_pretty(&contract)?;
-        if let Some(parent) = contract_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        fs::write(&contract_path, &new_content)?;
-        Ok(vec![ConfigDiff {
-            file_path: contract_path.to_string_lossy().to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: existing_content,
-            new_value: new_content,
-            operation: if existed_before {
-                DiffOperation::Modify
-            } else {
-                DiffOperation::Add
-            },
-        }])
+        let backup = contract_path.write(&new_content, options)?;
+        Ok(vec![whole_file_diff(
+            &contract_path,
+            existing_content,
+            new_content,
+            backup,
+        )])
     }
 
     fn validate_config(&self, game_path: &Path) -> Result<bool> {
@@ -6356,14 +7194,17 @@ impl ConfigWriter for DirtShowdownConfigWriter {
             "enabled": config.enabled,
             "bridge_notes": "DiRT Showdown uses Codemasters UDP Mode 1 on port 20777.",
         });
-        Ok(vec![ConfigDiff {
-            file_path: DIRT_SHOWDOWN_BRIDGE_RELATIVE_PATH.to_string(),
-            section: None,
-            key: "entire_file".to_string(),
-            old_value: None,
-            new_value: serde_json::to_string_pretty(&contract)?,
-            operation: DiffOperation::Add,
-        }])
+        Ok(vec![new_file_diff(
+            DIRT_SHOWDOWN_BRIDGE_RELATIVE_PATH,
+            serde_json::to_string_pretty(&contract)?,
+        )])
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata::contract_only(
+            "dirt_showdown",
+            &["Enable the game's Codemasters UDP telemetry output on port 20777"],
+        )
     }
 }
 #[cfg(test)]
@@ -6384,6 +7225,7 @@ mod tests {
             output_target: "127.0.0.1:12345".to_string(),
             fields: vec!["ffb_scalar".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
 
         let diffs = writer.write_config(temp_dir.path(), &config)?;
@@ -6403,6 +7245,7 @@ mod tests {
             output_target: "127.0.0.1:12345".to_string(),
             fields: vec!["ffb_scalar".to_string(), "rpm".to_string()],
             enable_high_rate_iracing_360hz: true,
+            allow_port_sharing: false,
         };
 
         let diffs = writer.write_config(temp_dir.path(), &config)?;
@@ -6439,6 +7282,7 @@ mod tests {
             output_target: "127.0.0.1:12345".to_string(),
             fields: vec!["ffb_scalar".to_string(), "rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
 
         let first = writer.write_config(temp_dir.path(), &config)?;
@@ -6483,6 +7327,7 @@ mod tests {
             output_target: "127.0.0.1:12345".to_string(),
             fields: vec!["ffb_scalar".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
 
         let diffs = writer.write_config(temp_dir.path(), &config)?;
@@ -6502,6 +7347,7 @@ mod tests {
             output_target: "127.0.0.1:20790".to_string(),
             fields: vec!["ffb_scalar".to_string(), "rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
 
         let diffs = writer.write_config(temp_dir.path(), &config)?;
@@ -6546,10 +7392,7 @@ mod tests {
         )?;
         fs::write(
             &structure_path,
-            r#"{
-  "id": "openracing",
-  "packets": []
-}"#,
+            serde_json::to_string_pretty(&eawrc_structure_definition(EAWRC_DEFAULT_CHANNELS))?,
         )?;
 
         assert!(writer.validate_config(temp_dir.path())?);
@@ -6561,6 +7404,7 @@ mod tests {
             output_target: "127.0.0.1:20778".to_string(),
             fields: vec!["ffb_scalar".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let diffs = writer.write_config(temp_dir.path(), &config)?;
         assert_eq!(diffs.len(), 2);
@@ -6579,6 +7423,7 @@ mod tests {
             output_target: "127.0.0.1:9000".to_string(),
             fields: vec![],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
 
         let diffs = writer.write_config(temp_dir.path(), &config)?;
@@ -6592,6 +7437,35 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_ac_rally_probe_lists_requested_fields() -> TestResult {
+        let writer = ACRallyConfigWriter;
+        let temp_dir = tempdir()?;
+        let mut config = TelemetryConfig {
+            enabled: true,
+            update_rate_hz: 60,
+            output_method: "probe_discovery".to_string(),
+            output_target: "127.0.0.1:9000".to_string(),
+            fields: vec!["rpm".to_string(), "speed".to_string(), "rpm".to_string()],
+            enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
+        };
+
+        let diffs = writer.write_config(temp_dir.path(), &config)?;
+        let probe: Value = serde_json::from_str(&diffs[0].new_value)?;
+        assert_eq!(
+            probe.get("requestedFields"),
+            Some(&serde_json::json!(["rpm", "speed_ms"]))
+        );
+
+        config.fields.push("tyre_wear".to_string());
+        let Err(err) = writer.write_config(temp_dir.path(), &config) else {
+            return Err("unknown field accepted".into());
+        };
+        assert!(err.to_string().contains("valid fields are:"));
+        Ok(())
+    }
+
     #[test]
     fn test_acc_writer_round_trip_compat_schema() -> TestResult {
         let writer = ACCConfigWriter;
@@ -6603,6 +7477,7 @@ mod tests {
             output_target: "127.0.0.1:9000".to_string(),
             fields: vec!["speed_ms".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
 
         let diffs = writer.write_config(temp_dir.path(), &config)?;
@@ -6663,6 +7538,7 @@ mod tests {
                 "slip_ratio".to_string(),
             ],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
 
         let diffs = writer.write_config(temp_dir.path(), &config)?;
@@ -6697,6 +7573,7 @@ mod tests {
                 "flags".to_string(),
             ],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
 
         let diffs = writer.write_config(temp_dir.path(), &config)?;
@@ -6747,6 +7624,7 @@ mod tests {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let diffs = writer.write_config(temp_dir.path(), &config)?;
         assert_eq!(diffs.len(), 1);
@@ -6773,6 +7651,7 @@ mod tests {
             output_target: "127.0.0.1:9000".to_string(),
             fields: vec!["speed_ms".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let diffs = writer.write_config(temp_dir.path(), &config)?;
         assert!(!diffs.is_empty());
@@ -0,0 +1,342 @@
+//! Keeping config writes inside the tree a writer was pointed at.
+//!
+//! Writers join relative paths onto a user-supplied `game_path` and create
+//! missing parent directories. Without checks, a profile pointing at the
+//! wrong place, a `..` in a relative path or a symlinked intermediate
+//! directory could redirect that write anywhere the user can write. Every
+//! writer therefore resolves its target through [`confine`], which rejects
+//! targets whose canonical location leaves the root, and writes through
+//! [`ConfinedPath::write`], which re-checks after creating directories.
+
+use std::fs;
+use std::io;
+use std::ops::Deref;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::atomic::atomic_write;
+use crate::backup::back_up;
+use crate::{WriteMode, WriteOptions, resolve_game_path, same_config_content};
+
+/// Why a config path was refused.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PathSafetyError {
+    /// The target resolves outside its root and no allowed root covers it.
+    #[error("config path {} escapes {}", path.display(), root.display())]
+    PathEscapesRoot { path: PathBuf, root: PathBuf },
+    /// A component below the root is a symlink and the policy refuses them.
+    #[error("refusing to follow symlink at {}", path.display())]
+    SymlinkRefused { path: PathBuf },
+}
+
+/// How strictly config targets are confined.
+///
+/// The default follows symlinks whose target stays under the root and allows
+/// no extra roots. Writes carry a policy in their
+/// [`WriteOptions`](crate::WriteOptions).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PathPolicy {
+    refuse_symlinks: bool,
+    allowed_roots: Vec<PathBuf>,
+}
+
+impl PathPolicy {
+    /// Refuse targets reached through a symlinked file or directory below the
+    /// root, even when the link points back inside it.
+    pub fn refuse_symlinks(mut self) -> Self {
+        self.refuse_symlinks = true;
+        self
+    }
+
+    /// Accept targets under `root` even though it lies outside `game_path`,
+    /// e.g. OpenRacing's data directory or a contract under Documents while
+    /// `game_path` is the install directory.
+    pub fn allow_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.allowed_roots.push(root.into());
+        self
+    }
+
+    /// Whether symlinks below the root are refused.
+    pub fn refuses_symlinks(&self) -> bool {
+        self.refuse_symlinks
+    }
+
+    /// Roots outside `game_path` that targets may resolve into.
+    pub fn allowed_roots(&self) -> &[PathBuf] {
+        &self.allowed_roots
+    }
+}
+
+/// A config file location verified to stay under its root.
+#[derive(Debug, Clone)]
+pub(crate) struct ConfinedPath {
+    root: PathBuf,
+    path: PathBuf,
+    policy: PathPolicy,
+}
+
+/// Resolve `relative_path` under `game_path` and check it stays there under
+/// `policy`, which later writes through the result re-check against.
+pub(crate) fn confine(
+    game_path: &Path,
+    relative_path: &str,
+    policy: &PathPolicy,
+) -> Result<ConfinedPath> {
+    let root = config_root(game_path, relative_path);
+    let path = resolve_game_path(game_path, relative_path);
+    let leaves_root = Path::new(relative_path)
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir));
+    if leaves_root {
+        return Err(PathSafetyError::PathEscapesRoot { path, root }.into());
+    }
+    let confined = ConfinedPath {
+        root,
+        path,
+        policy: policy.clone(),
+    };
+    confined.check()?;
+    Ok(confined)
+}
+
+/// Re-confine a path recorded in a [`ConfigDiff`](crate::ConfigDiff), which
+/// writers store already joined onto `game_path`.
+pub(crate) fn confine_recorded(
+    game_path: &Path,
+    recorded: &Path,
+    policy: &PathPolicy,
+) -> Result<ConfinedPath> {
+    if recorded.is_relative() {
+        return confine(game_path, &recorded.to_string_lossy(), policy);
+    }
+    let documents = config_root(game_path, "Documents/");
+    let root = if recorded.starts_with(&documents) {
+        documents
+    } else {
+        game_path.to_path_buf()
+    };
+    if recorded
+        .components()
+        .any(|component| matches!(component, Component::ParentDir))
+    {
+        return Err(PathSafetyError::PathEscapesRoot {
+            path: recorded.to_path_buf(),
+            root,
+        }
+        .into());
+    }
+    let confined = ConfinedPath {
+        root,
+        path: recorded.to_path_buf(),
+        policy: policy.clone(),
+    };
+    confined.check()?;
+    Ok(confined)
+}
+
+impl ConfinedPath {
+    /// Create missing parent directories, re-check, back up the file being
+    /// replaced as `options.backup` says, then atomically replace it with
+    /// `contents`. Returns the backup, if one was taken.
+    ///
+    /// A preview only runs the check, and a file that already says the same
+    /// is left alone so its modification time is not churned. The check uses
+    /// the policy the path was confined under.
+    pub(crate) fn write(
+        &self,
+        contents: impl AsRef<[u8]>,
+        options: &WriteOptions,
+    ) -> Result<Option<PathBuf>> {
+        let contents = contents.as_ref();
+        self.check()?;
+        let existing = match fs::read(&self.path) {
+            Ok(existing) => Some(existing),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+            Err(err) => return Err(err.into()),
+        };
+        if options.mode.is_preview()
+            || existing
+                .as_deref()
+                .is_some_and(|existing| holds_same(existing, contents))
+        {
+            return Ok(None);
+        }
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        self.check()?;
+        let backup = match &existing {
+            Some(existing) => back_up(&self.path, existing, &options.backup)?,
+            None => None,
+        };
+        atomic_write(&self.path, contents)?;
+        Ok(backup)
+    }
+
+    /// Remove the file if it exists. A preview only runs the check.
+    pub(crate) fn remove(&self, mode: WriteMode) -> Result<()> {
+        self.check()?;
+        if mode.is_preview() {
+            return Ok(());
+        }
+        match fs::remove_file(&self.path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn check(&self) -> Result<()> {
+        if self.policy.refuse_symlinks {
+            self.refuse_symlinks()?;
+        }
+        let root = canonicalize_existing(&self.root)?;
+        let target = canonicalize_existing(&self.path)?;
+        if target.starts_with(&root) {
+            return Ok(());
+        }
+        for allowed in &self.policy.allowed_roots {
+            if target.starts_with(canonicalize_existing(allowed)?) {
+                return Ok(());
+            }
+        }
+        Err(PathSafetyError::PathEscapesRoot {
+            path: self.path.clone(),
+            root: self.root.clone(),
+        }
+        .into())
+    }
+
+    fn refuse_symlinks(&self) -> Result<()> {
+        let Ok(below_root) = self.path.strip_prefix(&self.root) else {
+            return Ok(());
+        };
+        let mut current = self.root.clone();
+        for component in below_root.components() {
+            current.push(component);
+            match fs::symlink_metadata(&current) {
+                Ok(metadata) if metadata.file_type().is_symlink() => {
+                    return Err(PathSafetyError::SymlinkRefused { path: current }.into());
+                }
+                Ok(_) => {}
+                // Nothing below a missing component exists to be a link.
+                Err(err) if err.kind() == io::ErrorKind::NotFound => break,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Deref for ConfinedPath {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AsRef<Path> for ConfinedPath {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+fn holds_same(existing: &[u8], contents: &[u8]) -> bool {
+    match (std::str::from_utf8(existing), std::str::from_utf8(contents)) {
+        (Ok(existing), Ok(contents)) => same_config_content(existing, contents),
+        _ => existing == contents,
+    }
+}
+
+/// Directory a writer's relative path is resolved against; mirrors
+/// [`resolve_game_path`].
+fn config_root(game_path: &Path, relative_path: &str) -> PathBuf {
+    #[cfg(windows)]
+    if (game_path.as_os_str().is_empty() || game_path == Path::new("."))
+        && relative_path.starts_with("Documents/")
+        && let Some(user_profile) = std::env::var_os("USERPROFILE")
+    {
+        return PathBuf::from(user_profile).join("Documents");
+    }
+    #[cfg(not(windows))]
+    let _ = relative_path;
+    game_path.to_path_buf()
+}
+
+/// Canonicalize the longest existing prefix of `path` and append the rest,
+/// which cannot contain links because it does not exist yet.
+fn canonicalize_existing(path: &Path) -> io::Result<PathBuf> {
+    let mut missing = Vec::new();
+    let mut existing = if path.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        path
+    };
+    loop {
+        match fs::canonicalize(existing) {
+            Ok(mut canonical) => {
+                canonical.extend(missing.iter().rev());
+                return Ok(canonical);
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                let (Some(parent), Some(name)) = (existing.parent(), existing.file_name()) else {
+                    return Err(err);
+                };
+                // A dangling link would be followed by the write; resolve it
+                // to where the file would actually be created.
+                if let Ok(link) = fs::read_link(existing) {
+                    let mut resolved = canonicalize_existing(&parent.join(link))?;
+                    resolved.extend(missing.iter().rev());
+                    return Ok(resolved);
+                }
+                missing.push(name.to_os_string());
+                existing = if parent.as_os_str().is_empty() {
+                    Path::new(".")
+                } else {
+                    parent
+                };
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+    #[test]
+    fn parent_components_are_refused_before_touching_the_disk() -> TestResult {
+        let game = tempdir()?;
+        for relative in [
+            "../outside.json",
+            "Documents/../../outside.json",
+            "/etc/passwd",
+        ] {
+            let err = confine(game.path(), relative, &PathPolicy::default())
+                .err()
+                .ok_or(format!("{relative} was accepted"))?;
+            assert!(
+                matches!(
+                    err.downcast_ref::<PathSafetyError>(),
+                    Some(PathSafetyError::PathEscapesRoot { .. })
+                ),
+                "{relative}: {err}"
+            );
+        }
+        assert!(
+            confine(
+                game.path(),
+                "./Documents/OpenRacing/contract.json",
+                &PathPolicy::default()
+            )
+            .is_ok()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+//! Assetto Corsa OutGauge settings in Documents/Assetto Corsa/cfg/.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use racing_wheel_telemetry_config_writers::{
+    AssettoCorsaConfigWriter, ConfigWriter, DiffOperation, TelemetryConfig,
+};
+use tempfile::tempdir;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn config(output_target: &str) -> TelemetryConfig {
+    TelemetryConfig {
+        enabled: true,
+        update_rate_hz: 60,
+        output_method: "udp_outgauge".to_string(),
+        output_target: output_target.to_string(),
+        fields: vec!["rpm".to_string(), "speed_ms".to_string()],
+        enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
+    }
+}
+
+fn ini_path(game: &Path) -> PathBuf {
+    game.join("Documents/Assetto Corsa/cfg/openracing.ini")
+}
+
+#[test]
+fn fresh_install_round_trips() -> TestResult {
+    let game = tempdir()?;
+    let diffs = AssettoCorsaConfigWriter.write_config(game.path(), &config("127.0.0.1:9996"))?;
+    let written: Vec<(&str, &str, &DiffOperation)> = diffs
+        .iter()
+        .map(|diff| (diff.key.as_str(), diff.new_value.as_str(), &diff.operation))
+        .collect();
+    assert_eq!(
+        written,
+        [
+            ("Mode", "2", &DiffOperation::Add),
+            ("IP", "127.0.0.1", &DiffOperation::Add),
+            ("Port", "9996", &DiffOperation::Add),
+            ("ID", "1", &DiffOperation::Add),
+        ]
+    );
+    assert!(AssettoCorsaConfigWriter.validate_config(game.path())?);
+
+    AssettoCorsaConfigWriter.rollback_config(game.path(), &diffs)?;
+    assert!(!ini_path(game.path()).exists());
+    assert!(!AssettoCorsaConfigWriter.validate_config(game.path())?);
+    Ok(())
+}
+
+#[test]
+fn existing_settings_are_modified_with_old_values_kept() -> TestResult {
+    let game = tempdir()?;
+    let path = ini_path(game.path());
+    fs::create_dir_all(path.parent().ok_or("no parent")?)?;
+    let original =
+        "[OutGauge]\nMode=0\nIP=192.168.1.5\nPort=9996\nDelay=2\n\n[Dashboard]\nunits=metric\n";
+    fs::write(&path, original)?;
+
+    let diffs = AssettoCorsaConfigWriter.write_config(game.path(), &config("10.0.0.7:9100"))?;
+    let changes: Vec<(&str, Option<&str>, &DiffOperation)> = diffs
+        .iter()
+        .map(|diff| {
+            (
+                diff.key.as_str(),
+                diff.old_value.as_deref(),
+                &diff.operation,
+            )
+        })
+        .collect();
+    assert_eq!(
+        changes,
+        [
+            ("Mode", Some("0"), &DiffOperation::Modify),
+            ("IP", Some("192.168.1.5"), &DiffOperation::Modify),
+            ("Port", Some("9996"), &DiffOperation::Modify),
+            ("ID", None, &DiffOperation::Add),
+        ]
+    );
+    assert_eq!(
+        fs::read_to_string(&path)?,
+        "[OutGauge]\nMode=2\nIP=10.0.0.7\nPort=9100\nDelay=2\nID=1\n\n[Dashboard]\nunits=metric\n"
+    );
+    assert!(AssettoCorsaConfigWriter.validate_config(game.path())?);
+
+    AssettoCorsaConfigWriter.rollback_config(game.path(), &diffs)?;
+    assert_eq!(fs::read_to_string(&path)?, original);
+    Ok(())
+}
+
+#[test]
+fn rewriting_the_same_config_changes_nothing() -> TestResult {
+    let game = tempdir()?;
+    AssettoCorsaConfigWriter.write_config(game.path(), &config("127.0.0.1:9996"))?;
+    let diffs = AssettoCorsaConfigWriter.write_config(game.path(), &config("127.0.0.1:9996"))?;
+    assert!(
+        diffs
+            .iter()
+            .all(|diff| diff.operation == DiffOperation::NoChange),
+        "{diffs:?}"
+    );
+    Ok(())
+}
+
+#[test]
+fn outgauge_turned_off_or_missing_a_port_fails_validation() -> TestResult {
+    let game = tempdir()?;
+    let mut disabled = config("127.0.0.1:9996");
+    disabled.enabled = false;
+    AssettoCorsaConfigWriter.write_config(game.path(), &disabled)?;
+    assert!(!AssettoCorsaConfigWriter.validate_config(game.path())?);
+
+    fs::write(ini_path(game.path()), "[OutGauge]\nMode=2\nIP=127.0.0.1\n")?;
+    assert!(!AssettoCorsaConfigWriter.validate_config(game.path())?);
+    Ok(())
+}
+
+#[test]
+fn removal_keeps_keys_openracing_did_not_write() -> TestResult {
+    let game = tempdir()?;
+    let path = ini_path(game.path());
+    fs::create_dir_all(path.parent().ok_or("no parent")?)?;
+    fs::write(&path, "[OutGauge]\nDelay=2\n")?;
+    AssettoCorsaConfigWriter.write_config(game.path(), &config("127.0.0.1:9996"))?;
+
+    let diffs = AssettoCorsaConfigWriter.remove_config(game.path())?;
+    assert_eq!(diffs.len(), 4);
+    assert_eq!(fs::read_to_string(&path)?, "[OutGauge]\nDelay=2\n");
+
+    AssettoCorsaConfigWriter.rollback_config(game.path(), &diffs)?;
+    assert_eq!(
+        fs::read_to_string(&path)?,
+        "[OutGauge]\nDelay=2\nMode=2\nIP=127.0.0.1\nPort=9996\nID=1\n"
+    );
+    Ok(())
+}
@@ -0,0 +1,184 @@
+//! Async config writers on the blocking pool, and cancellation.
+#![cfg(feature = "async")]
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::Result as WriterResult;
+use racing_wheel_telemetry_config_writers::{
+    AsyncConfigWriter, CancellationToken, ConfigDiff, ConfigWriteCancelled, ConfigWriter,
+    ConfigWriterMetadata, IRacingConfigWriter, PathPolicy, PathSafetyError, SyncAsAsync,
+    TelemetryConfig, WriteOptions, config_writer_factories, config_writer_factories_async,
+};
+use tempfile::tempdir;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const WRITE_DELAY: Duration = Duration::from_millis(300);
+
+fn config() -> TelemetryConfig {
+    TelemetryConfig {
+        enabled: true,
+        update_rate_hz: 60,
+        output_method: "shared_memory".to_string(),
+        output_target: "127.0.0.1:12345".to_string(),
+        fields: vec!["rpm".to_string()],
+        enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
+    }
+}
+
+/// iRacing's writer behind a filesystem that takes a while to answer.
+struct SlowWriter;
+
+impl ConfigWriter for SlowWriter {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> WriterResult<Vec<ConfigDiff>> {
+        std::thread::sleep(WRITE_DELAY);
+        IRacingConfigWriter.write_config_with(game_path, config, options)
+    }
+
+    fn validate_config(&self, game_path: &Path) -> WriterResult<bool> {
+        IRacingConfigWriter.validate_config(game_path)
+    }
+
+    fn get_expected_diffs(&self, config: &TelemetryConfig) -> WriterResult<Vec<ConfigDiff>> {
+        IRacingConfigWriter.get_expected_diffs(config)
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        IRacingConfigWriter.metadata()
+    }
+}
+
+fn entries(path: &Path) -> std::io::Result<usize> {
+    Ok(std::fs::read_dir(path)?.count())
+}
+
+#[tokio::test]
+async fn slow_write_does_not_block_the_runtime() -> TestResult {
+    let dir = tempdir()?;
+    let writer = SyncAsAsync::new(SlowWriter);
+    let cancel = CancellationToken::new();
+
+    // A single-threaded runtime: a blocking write would stall the ticks.
+    let started = Instant::now();
+    let ticker = tokio::spawn(async move {
+        let mut ticks = 0u32;
+        let mut interval = tokio::time::interval(Duration::from_millis(10));
+        while started.elapsed() < WRITE_DELAY {
+            interval.tick().await;
+            ticks += 1;
+        }
+        ticks
+    });
+    let diffs = writer.write_config(dir.path(), &config(), &cancel).await?;
+    let ticks = ticker.await?;
+
+    assert!(!diffs.is_empty());
+    assert!(ticks >= 10, "only {ticks} ticks during the write");
+    assert!(writer.validate_config(dir.path(), &cancel).await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn cancellation_before_the_write_leaves_the_target_untouched() -> TestResult {
+    let dir = tempdir()?;
+    let cancel = CancellationToken::new();
+    cancel.cancel();
+
+    for (game_id, factory) in config_writer_factories_async() {
+        let game_dir = dir.path().join(game_id);
+        std::fs::create_dir(&game_dir)?;
+        let err = match factory
+            .create()
+            .write_config(&game_dir, &config(), &cancel)
+            .await
+        {
+            Ok(diffs) => return Err(format!("{game_id} wrote {diffs:?}").into()),
+            Err(err) => err,
+        };
+        assert!(
+            err.downcast_ref::<ConfigWriteCancelled>().is_some(),
+            "{err}"
+        );
+        assert_eq!(entries(&game_dir)?, 0, "{game_id}");
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn cancellation_after_the_write_starts_does_not_tear_it() -> TestResult {
+    let dir = tempdir()?;
+    let writer = SyncAsAsync::new(SlowWriter);
+    let cancel = CancellationToken::new();
+    let late = cancel.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(WRITE_DELAY / 3).await;
+        late.cancel();
+    });
+
+    let diffs = writer.write_config(dir.path(), &config(), &cancel).await?;
+    assert!(cancel.is_cancelled());
+    assert!(!diffs.is_empty());
+    assert!(SlowWriter.validate_config(dir.path())?);
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn path_policy_follows_the_write_onto_the_blocking_pool() -> TestResult {
+    let game = tempdir()?;
+    std::fs::create_dir(game.path().join("real_documents"))?;
+    std::os::unix::fs::symlink(
+        game.path().join("real_documents"),
+        game.path().join("Documents"),
+    )?;
+    let cancel = CancellationToken::new();
+
+    let strict = WriteOptions::default().with_path_policy(PathPolicy::default().refuse_symlinks());
+    let err = match SyncAsAsync::new(IRacingConfigWriter)
+        .with_options(strict)
+        .write_config(game.path(), &config(), &cancel)
+        .await
+    {
+        Ok(diffs) => return Err(format!("strict policy allowed {diffs:?}").into()),
+        Err(err) => err,
+    };
+    assert_eq!(
+        err.downcast_ref::<PathSafetyError>(),
+        Some(&PathSafetyError::SymlinkRefused {
+            path: game.path().join("Documents"),
+        })
+    );
+    assert_eq!(entries(&game.path().join("real_documents"))?, 0);
+    Ok(())
+}
+
+#[test]
+fn async_registry_mirrors_the_sync_one() {
+    let sync_ids: Vec<_> = config_writer_factories()
+        .iter()
+        .map(|(game_id, _)| *game_id)
+        .collect();
+    let async_ids: Vec<_> = config_writer_factories_async()
+        .iter()
+        .map(|(game_id, _)| *game_id)
+        .collect();
+    assert_eq!(async_ids, sync_ids);
+
+    for ((game_id, sync), (_, async_factory)) in config_writer_factories()
+        .iter()
+        .zip(config_writer_factories_async())
+    {
+        assert_eq!(
+            async_factory.create().metadata(),
+            sync().metadata(),
+            "{game_id}"
+        );
+    }
+}
@@ -0,0 +1,82 @@
+//! A game reading its config while OpenRacing rewrites it never sees a
+//! truncated file.
+
+use std::fs;
+use std::io;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+use racing_wheel_telemetry_config_writers::{
+    ACCConfigWriter, BackupOptions, ConfigWriter, TelemetryConfig, WriteOptions,
+};
+use tempfile::tempdir;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const BROADCASTING_JSON: &str = "Documents/Assetto Corsa Competizione/Config/broadcasting.json";
+
+fn config(port: u16) -> TelemetryConfig {
+    TelemetryConfig {
+        enabled: true,
+        update_rate_hz: 60,
+        output_method: "udp_broadcast".to_string(),
+        output_target: format!("127.0.0.1:{port}"),
+        fields: Vec::new(),
+        enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
+    }
+}
+
+#[test]
+fn reader_never_sees_an_empty_or_partial_file_during_rewrites() -> TestResult {
+    let game = tempdir()?;
+    let root = game.path().to_path_buf();
+    let path = root.join(BROADCASTING_JSON);
+    ACCConfigWriter.write_config(&root, &config(9000))?;
+
+    let done = Arc::new(AtomicBool::new(false));
+    let reader = {
+        let done = Arc::clone(&done);
+        let path = path.clone();
+        thread::spawn(move || -> Result<usize, String> {
+            let mut reads = 0;
+            while !done.load(Ordering::Acquire) {
+                let content = match fs::read_to_string(&path) {
+                    Ok(content) => content,
+                    // Windows refuses the open while the rename is in flight.
+                    Err(err) if err.kind() == io::ErrorKind::PermissionDenied => continue,
+                    Err(err) => return Err(format!("read failed: {err}")),
+                };
+                if content.is_empty() {
+                    return Err(format!("zero-length read after {reads} reads"));
+                }
+                if let Err(err) = serde_json::from_str::<serde_json::Value>(&content) {
+                    return Err(format!("partial read after {reads} reads: {err}"));
+                }
+                reads += 1;
+            }
+            Ok(reads)
+        })
+    };
+
+    let unbacked = WriteOptions::default().with_backup(BackupOptions::disabled());
+    let written = (|| -> TestResult {
+        for round in 0..300u16 {
+            ACCConfigWriter.write_config_with(&root, &config(9000 + round % 2), &unbacked)?;
+        }
+        Ok(())
+    })();
+    done.store(true, Ordering::Release);
+    let reads = reader.join().map_err(|_| "reader thread panicked")??;
+    written?;
+
+    assert!(reads > 0, "reader never ran");
+    assert!(fs::read_to_string(&path)?.contains("9001"));
+    let leftovers: Vec<_> = fs::read_dir(path.parent().ok_or("no parent")?)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().ends_with(".tmp"))
+        .collect();
+    assert!(leftovers.is_empty(), "temporary files left: {leftovers:?}");
+    Ok(())
+}
@@ -0,0 +1,211 @@
+//! Files a writer replaces are backed up first and can be restored.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use racing_wheel_telemetry_config_writers::{
+    ACCConfigWriter, BACKUP_MARKER, BackupOptions, ConfigWriter, IRacingConfigWriter,
+    TelemetryConfig, WriteOptions, latest_backup, restore_latest_backup,
+};
+use tempfile::tempdir;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const ACC_RELATIVE: &str = "Documents/Assetto Corsa Competizione/Config/broadcasting.json";
+const HAND_TUNED: &str = "{\n  \"connectionPassword\": \"hand-tuned\",\n  \"updListenerPort\": 9000,\n  \"updateRateHz\": 10\n}";
+
+fn config(update_rate_hz: u32) -> TelemetryConfig {
+    TelemetryConfig {
+        enabled: true,
+        update_rate_hz,
+        output_method: "udp".to_string(),
+        output_target: "127.0.0.1:9996".to_string(),
+        fields: vec!["rpm".to_string()],
+        enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
+    }
+}
+
+fn hand_tuned_acc(game: &Path) -> Result<PathBuf, std::io::Error> {
+    let path = game.join(ACC_RELATIVE);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, HAND_TUNED)?;
+    Ok(path)
+}
+
+fn backups_in(dir: &Path) -> Result<Vec<PathBuf>, std::io::Error> {
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.to_string_lossy().contains(BACKUP_MARKER) {
+            backups.push(path);
+        }
+    }
+    backups.sort();
+    Ok(backups)
+}
+
+#[test]
+fn overwriting_a_file_backs_it_up_next_to_it() -> TestResult {
+    let game = tempdir()?;
+    let path = hand_tuned_acc(game.path())?;
+
+    let diffs = ACCConfigWriter.write_config(game.path(), &config(60))?;
+    let [diff] = diffs.as_slice() else {
+        return Err(format!("expected one diff, got {diffs:?}").into());
+    };
+    let backup = PathBuf::from(diff.backup_path.as_deref().ok_or("no backup reported")?);
+
+    assert_eq!(backup.parent(), path.parent());
+    let name = backup.file_name().ok_or("no file name")?.to_string_lossy();
+    assert!(
+        name.starts_with(&format!("broadcasting.json{BACKUP_MARKER}")),
+        "{name}"
+    );
+    assert_eq!(fs::read_to_string(&backup)?, HAND_TUNED);
+    assert_eq!(
+        latest_backup(&path, &BackupOptions::default())?,
+        Some(backup)
+    );
+    Ok(())
+}
+
+#[test]
+fn creating_a_file_takes_no_backup() -> TestResult {
+    let game = tempdir()?;
+    let diffs = ACCConfigWriter.write_config(game.path(), &config(60))?;
+    assert!(diffs.iter().all(|diff| diff.backup_path.is_none()));
+
+    // Nor does rewriting it with the same contents.
+    let diffs = ACCConfigWriter.write_config(game.path(), &config(60))?;
+    assert!(diffs.iter().all(|diff| diff.backup_path.is_none()));
+
+    let path = game.path().join(ACC_RELATIVE);
+    assert_eq!(
+        backups_in(path.parent().ok_or("no parent")?)?,
+        Vec::<PathBuf>::new()
+    );
+    Ok(())
+}
+
+#[test]
+fn missing_backup_directory_is_created() -> TestResult {
+    let game = tempdir()?;
+    let path = hand_tuned_acc(game.path())?;
+    let backups = game.path().join("openracing/backups/acc");
+    let options = BackupOptions::default().in_directory(&backups);
+
+    let diffs = ACCConfigWriter.write_config_with(
+        game.path(),
+        &config(60),
+        &WriteOptions::default().with_backup(options.clone()),
+    )?;
+
+    let [backup] = backups_in(&backups)?
+        .try_into()
+        .map_err(|found| format!("{found:?}"))?;
+    assert_eq!(diffs[0].backup_path.as_deref(), backup.to_str());
+    assert_eq!(fs::read_to_string(&backup)?, HAND_TUNED);
+    assert_eq!(
+        backups_in(path.parent().ok_or("no parent")?)?,
+        Vec::<PathBuf>::new()
+    );
+    assert_eq!(latest_backup(&path, &options)?, Some(backup));
+    Ok(())
+}
+
+#[test]
+fn writes_within_one_second_keep_every_backup() -> TestResult {
+    let game = tempdir()?;
+    let path = hand_tuned_acc(game.path())?;
+
+    let mut reported = Vec::new();
+    let mut replaced = vec![HAND_TUNED.to_string()];
+    for rate in [30, 60, 90] {
+        let diffs = ACCConfigWriter.write_config(game.path(), &config(rate))?;
+        reported.push(diffs[0].backup_path.clone().ok_or("no backup reported")?);
+        replaced.push(fs::read_to_string(&path)?);
+    }
+
+    let backups = backups_in(path.parent().ok_or("no parent")?)?;
+    assert_eq!(backups.len(), 3, "{backups:?}");
+    for (backup, contents) in reported.iter().zip(&replaced) {
+        assert_eq!(&fs::read_to_string(backup)?, contents);
+    }
+    assert_eq!(
+        latest_backup(&path, &BackupOptions::default())?.as_deref(),
+        reported.last().map(Path::new)
+    );
+    Ok(())
+}
+
+#[test]
+fn restore_puts_back_the_newest_backup() -> TestResult {
+    let game = tempdir()?;
+    let path = hand_tuned_acc(game.path())?;
+    ACCConfigWriter.write_config(game.path(), &config(60))?;
+    assert_ne!(fs::read_to_string(&path)?, HAND_TUNED);
+
+    let restored = restore_latest_backup(&ACCConfigWriter, game.path(), &BackupOptions::default())?;
+    let [restored] = restored.as_slice() else {
+        return Err(format!("expected one restore, got {restored:?}").into());
+    };
+    assert_eq!(restored.target, path);
+    assert_eq!(fs::read_to_string(&path)?, HAND_TUNED);
+    // Restoring took no backup of its own.
+    assert_eq!(backups_in(path.parent().ok_or("no parent")?)?.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn restore_without_backups_changes_nothing() -> TestResult {
+    let game = tempdir()?;
+    IRacingConfigWriter.write_config(game.path(), &config(60))?;
+    let path = game.path().join("Documents/iRacing/app.ini");
+    let written = fs::read_to_string(&path)?;
+
+    assert!(
+        restore_latest_backup(&IRacingConfigWriter, game.path(), &BackupOptions::default())?
+            .is_empty()
+    );
+    assert_eq!(fs::read_to_string(&path)?, written);
+    Ok(())
+}
+
+#[test]
+fn disabled_backups_leave_no_copy() -> TestResult {
+    let game = tempdir()?;
+    let path = hand_tuned_acc(game.path())?;
+
+    let diffs = ACCConfigWriter.write_config_with(
+        game.path(),
+        &config(60),
+        &WriteOptions::default().with_backup(BackupOptions::disabled()),
+    )?;
+    assert_eq!(diffs[0].backup_path, None);
+    assert_eq!(
+        backups_in(path.parent().ok_or("no parent")?)?,
+        Vec::<PathBuf>::new()
+    );
+    Ok(())
+}
+
+#[test]
+fn backup_of_a_read_only_file_is_writable() -> TestResult {
+    let game = tempdir()?;
+    let path = hand_tuned_acc(game.path())?;
+    let mut permissions = fs::metadata(&path)?.permissions();
+    permissions.set_readonly(true);
+    fs::set_permissions(&path, permissions)?;
+
+    // Whether the write itself succeeds depends on the user running the tests;
+    // the backup is taken before the replacement either way.
+    let _ = ACCConfigWriter.write_config(game.path(), &config(60));
+
+    let backup = latest_backup(&path, &BackupOptions::default())?.ok_or("no backup taken")?;
+    assert_eq!(fs::read_to_string(&backup)?, HAND_TUNED);
+    assert!(!fs::metadata(&backup)?.permissions().readonly());
+    Ok(())
+}
@@ -17,6 +17,7 @@ fn default_config() -> TelemetryConfig {
         output_target: "127.0.0.1:12345".to_string(),
         fields: vec!["ffb_scalar".to_string(), "rpm".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     }
 }
 
@@ -87,6 +88,8 @@ fn config_diff_serde_round_trip() -> TestResult {
         old_value: Some("0".to_string()),
         new_value: "1".to_string(),
         operation: DiffOperation::Modify,
+        backup_path: None,
+        details: Vec::new(),
     };
     let json = serde_json::to_string(&diff)?;
     let decoded: ConfigDiff = serde_json::from_str(&json)?;
@@ -103,6 +106,8 @@ fn config_diff_with_none_section() -> TestResult {
         old_value: None,
         new_value: "{}".to_string(),
         operation: DiffOperation::Add,
+        backup_path: None,
+        details: Vec::new(),
     };
     let json = serde_json::to_string(&diff)?;
     let decoded: ConfigDiff = serde_json::from_str(&json)?;
@@ -126,6 +131,7 @@ fn telemetry_config_serde_round_trip() -> TestResult {
             "gear".to_string(),
         ],
         enable_high_rate_iracing_360hz: true,
+        allow_port_sharing: false,
     };
     let json = serde_json::to_string(&config)?;
     let decoded: TelemetryConfig = serde_json::from_str(&json)?;
@@ -189,6 +195,7 @@ fn acc_write_and_validate_round_trip() -> TestResult {
         output_target: "127.0.0.1:9000".to_string(),
         fields: vec!["speed_ms".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
     let diffs = writer.write_config(temp_dir.path(), &config)?;
     assert!(!diffs.is_empty());
@@ -212,6 +219,7 @@ fn eawrc_write_and_validate_round_trip() -> TestResult {
         output_target: "127.0.0.1:20790".to_string(),
         fields: vec!["ffb_scalar".to_string(), "rpm".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
     let diffs = writer.write_config(temp_dir.path(), &config)?;
     assert!(!diffs.is_empty());
@@ -251,6 +259,7 @@ fn dirt5_write_and_validate_round_trip() -> TestResult {
         output_target: "127.0.0.1:20777".to_string(),
         fields: vec!["rpm".to_string(), "speed_ms".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
     let diffs = writer.write_config(temp_dir.path(), &config)?;
     assert!(!diffs.is_empty());
@@ -292,6 +301,7 @@ fn get_expected_diffs_matches_write_diffs_count() -> TestResult {
         output_target: "127.0.0.1:20777".to_string(),
         fields: vec!["rpm".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
     let write_diffs = writer.write_config(temp_dir.path(), &config)?;
     let expected_diffs = writer.get_expected_diffs(&config)?;
@@ -317,6 +327,7 @@ fn iracing_360hz_produces_two_diffs() -> TestResult {
         output_target: "127.0.0.1:12345".to_string(),
         fields: vec!["ffb_scalar".to_string()],
         enable_high_rate_iracing_360hz: true,
+        allow_port_sharing: false,
     };
     let diffs = writer.write_config(temp_dir.path(), &config)?;
     assert_eq!(diffs.len(), 2);
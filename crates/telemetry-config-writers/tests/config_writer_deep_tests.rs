@@ -19,6 +19,17 @@ fn default_config() -> TelemetryConfig {
         output_target: "127.0.0.1:20777".to_string(),
         fields: vec!["ffb_scalar".to_string(), "rpm".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
+    }
+}
+
+/// `default_config` with a different port and rate, so every writer's output changes.
+fn changed_config() -> TelemetryConfig {
+    TelemetryConfig {
+        update_rate_hz: 120,
+        output_target: "127.0.0.1:20778".to_string(),
+        enabled: false,
+        ..default_config()
     }
 }
 
@@ -271,7 +282,7 @@ mod output_validation {
 }
 
 // ---------------------------------------------------------------------------
-// Diff operation transitions: Add → Modify on overwrite
+// Diff operation transitions: Add → Modify on overwrite, NoChange on rewrite
 // ---------------------------------------------------------------------------
 
 mod diff_transitions {
@@ -279,33 +290,24 @@ mod diff_transitions {
 
     #[test]
     fn overwrite_transitions_add_to_modify_for_all_writers() -> TestResult {
-        // Some writers (e.g. eawrc) may produce multiple output files where
-        // subsequent writes legitimately create new files (Add) alongside
-        // modified ones. We only assert that *at least one* diff becomes
-        // Modify on the second write, confirming the writer detects existing
-        // content.  Writers whose second write still contains only Add are
-        // collected and must be in the known-exception list.
-        let known_add_only: &[&str] = &["eawrc"];
-        let config = default_config();
+        // Writers with several output files (e.g. eawrc) may leave some of
+        // them unchanged; at least one diff must become Modify, confirming
+        // the writer detects existing content.
         for (id, factory) in config_writer_factories() {
             let writer = factory();
             let temp = tempfile::tempdir()?;
 
-            let diffs1 = writer.write_config(temp.path(), &config)?;
+            let diffs1 = writer.write_config(temp.path(), &default_config())?;
             assert!(
                 diffs1.iter().all(|d| d.operation == DiffOperation::Add),
                 "{id}: first write should all be Add"
             );
 
-            let diffs2 = writer.write_config(temp.path(), &config)?;
-            let has_modify = diffs2.iter().any(|d| d.operation == DiffOperation::Modify);
-            if !has_modify {
-                assert!(
-                    known_add_only.contains(id),
-                    "{id}: second write should contain at least one Modify"
-                );
-            }
-            Ok::<(), Box<dyn std::error::Error>>(())?;
+            let diffs2 = writer.write_config(temp.path(), &changed_config())?;
+            assert!(
+                diffs2.iter().any(|d| d.operation == DiffOperation::Modify),
+                "{id}: second write should contain at least one Modify: {diffs2:?}"
+            );
         }
         Ok(())
     }
@@ -337,11 +339,10 @@ mod diff_transitions {
     fn triple_write_still_produces_modify() -> TestResult {
         let writer = writer_for("acc")?;
         let temp = tempfile::tempdir()?;
-        let config = default_config();
 
-        writer.write_config(temp.path(), &config)?;
-        writer.write_config(temp.path(), &config)?;
-        let diffs3 = writer.write_config(temp.path(), &config)?;
+        writer.write_config(temp.path(), &default_config())?;
+        writer.write_config(temp.path(), &changed_config())?;
+        let diffs3 = writer.write_config(temp.path(), &default_config())?;
 
         for diff in &diffs3 {
             assert_eq!(diff.operation, DiffOperation::Modify);
@@ -366,6 +367,7 @@ mod config_edge_cases {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec![],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         for (id, factory) in config_writer_factories() {
             let writer = factory();
@@ -385,6 +387,7 @@ mod config_edge_cases {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         for (id, factory) in config_writer_factories() {
             let writer = factory();
@@ -404,6 +407,7 @@ mod config_edge_cases {
             output_target: String::new(),
             fields: vec![],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         for (id, factory) in config_writer_factories() {
             let writer = factory();
@@ -424,6 +428,7 @@ mod config_edge_cases {
             output_target: "127.0.0.1:1234".to_string(),
             fields,
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let writer = writer_for("iracing")?;
         let temp = tempfile::tempdir()?;
@@ -441,6 +446,7 @@ mod config_edge_cases {
             output_target: "127.0.0.1:1234".to_string(),
             fields: vec![],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let writer = writer_for("f1")?;
         let temp = tempfile::tempdir()?;
@@ -458,6 +464,7 @@ mod config_edge_cases {
             output_target: "[::1]:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let writer = writer_for("ams2")?;
         let temp = tempfile::tempdir()?;
@@ -700,6 +707,7 @@ mod telemetry_config_serde {
             output_target: "192.168.1.100:9999".to_string(),
             fields: vec!["a".into(), "b".into(), "c".into()],
             enable_high_rate_iracing_360hz: true,
+            allow_port_sharing: false,
         };
         let json = serde_json::to_string(&config)?;
         let decoded: TelemetryConfig = serde_json::from_str(&json)?;
@@ -757,6 +765,8 @@ mod diff_types {
             old_value: Some("old".to_string()),
             new_value: "new".to_string(),
             operation: DiffOperation::Modify,
+            backup_path: None,
+            details: Vec::new(),
         };
         let cloned = diff.clone();
         assert_eq!(diff, cloned);
@@ -772,6 +782,8 @@ mod diff_types {
             old_value: None,
             new_value: "v".to_string(),
             operation: DiffOperation::Add,
+            backup_path: None,
+            details: Vec::new(),
         };
         let diff2 = ConfigDiff {
             key: "key2".to_string(),
@@ -790,6 +802,8 @@ mod diff_types {
             old_value: None,
             new_value: "v".to_string(),
             operation: DiffOperation::Add,
+            backup_path: None,
+            details: Vec::new(),
         };
         let modified = ConfigDiff {
             operation: DiffOperation::Remove,
@@ -824,6 +838,8 @@ mod diff_types {
                 old_value: Some("old".to_string()),
                 new_value: "new".to_string(),
                 operation: op.clone(),
+                backup_path: None,
+                details: Vec::new(),
             };
             let json = serde_json::to_string(&diff)?;
             let decoded: ConfigDiff = serde_json::from_str(&json)?;
@@ -1056,6 +1072,7 @@ mod special_character_paths {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let parent = tempfile::tempdir()?;
         let deep_path = parent.path().join("path (with) [brackets] & special");
@@ -1084,6 +1101,7 @@ mod port_configuration {
             output_target: "127.0.0.1:55555".to_string(),
             fields: vec![],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let bridge_games = ["dirt5", "f1", "forza_motorsport", "trackmania", "simhub"];
         for game_id in bridge_games {
@@ -1114,6 +1132,7 @@ mod port_configuration {
             output_target: "127.0.0.1:9876".to_string(),
             fields: vec![],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let writer = writer_for("acc")?;
         let temp = tempfile::tempdir()?;
@@ -1141,6 +1160,7 @@ mod port_configuration {
             output_target: "192.168.1.50:33333".to_string(),
             fields: vec![],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let writer = writer_for("eawrc")?;
         let temp = tempfile::tempdir()?;
@@ -1170,6 +1190,7 @@ mod port_configuration {
             output_target: String::new(),
             fields: vec![],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         // ACC defaults to port 9000
         let writer = writer_for("acc")?;
@@ -1618,18 +1639,18 @@ mod game_specific_quirks {
     }
 
     #[test]
-    fn ac_contract_references_outgauge_ini_setup() -> TestResult {
+    fn ac_writes_outgauge_ini_section() -> TestResult {
         let writer = writer_for("assetto_corsa")?;
         let temp = tempfile::tempdir()?;
         let config = default_config();
         writer.write_config(temp.path(), &config)?;
 
         let files = walkdir(temp.path())?;
-        let json_file = files.first().ok_or("expected at least one file")?;
-        let content = std::fs::read_to_string(json_file)?;
+        let ini_file = files.first().ok_or("expected at least one file")?;
+        let content = std::fs::read_to_string(ini_file)?;
         assert!(
-            content.contains("OutGauge"),
-            "AC contract should reference OutGauge INI format"
+            content.contains("[OutGauge]"),
+            "AC should write the OutGauge INI section"
         );
         Ok(())
     }
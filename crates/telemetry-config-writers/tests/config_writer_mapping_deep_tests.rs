@@ -18,6 +18,7 @@ fn default_config() -> TelemetryConfig {
         output_target: "127.0.0.1:20777".to_string(),
         fields: vec!["ffb_scalar".to_string(), "rpm".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     }
 }
 
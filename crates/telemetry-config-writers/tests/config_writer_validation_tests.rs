@@ -20,6 +20,7 @@ fn default_config() -> TelemetryConfig {
         output_target: "127.0.0.1:20777".to_string(),
         fields: vec!["ffb_scalar".to_string(), "rpm".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     }
 }
 
@@ -353,9 +354,13 @@ mod config_format_correctness {
             .get("channels")
             .and_then(|v| v.as_array())
             .ok_or("missing channels")?;
-        assert!(
-            channels.len() >= 3,
-            "EAWRC structure should have at least 3 channels"
+        assert_eq!(
+            channels,
+            &[
+                serde_json::Value::from("ffb_scalar"),
+                serde_json::Value::from("engine_rpm")
+            ],
+            "EAWRC structure should export the requested fields"
         );
         Ok(())
     }
@@ -378,36 +383,22 @@ mod config_format_correctness {
     }
 
     #[test]
-    fn assetto_corsa_contract_has_outgauge_setup_notes() -> TestResult {
+    fn assetto_corsa_writes_outgauge_section() -> TestResult {
         let writer = writer_for("assetto_corsa")?;
         let temp = tempfile::tempdir()?;
         writer.write_config(temp.path(), &default_config())?;
 
         let files = walkdir(temp.path())?;
-        let json_file = files.first().ok_or("expected output file")?;
-        let content = std::fs::read_to_string(json_file)?;
-        let value: serde_json::Value = serde_json::from_str(&content)?;
-
+        let ini_file = files.first().ok_or("expected output file")?;
         assert_eq!(
-            value.get("telemetry_protocol").and_then(|v| v.as_str()),
-            Some("ac_outgauge_udp")
+            ini_file.extension().and_then(|e| e.to_str()),
+            Some("ini"),
+            "AC should write an INI file"
         );
-        let notes = value
-            .get("setup_notes")
-            .and_then(|v| v.as_array())
-            .ok_or("missing setup_notes")?;
-        let notes_text: String = notes
-            .iter()
-            .filter_map(|v| v.as_str())
-            .collect::<Vec<_>>()
-            .join(" ");
-        assert!(
-            notes_text.contains("OutGauge"),
-            "AC setup notes should reference OutGauge"
-        );
-        assert!(
-            notes_text.contains("Mode=2"),
-            "AC setup notes should reference Mode=2"
+        let content = std::fs::read_to_string(ini_file)?;
+        assert_eq!(
+            content,
+            "[OutGauge]\nMode=2\nIP=127.0.0.1\nPort=20777\nID=1\n"
         );
         Ok(())
     }
@@ -1317,7 +1308,6 @@ mod protocol_family_validation {
             "rbr",
             "gran_turismo_7",
             "forza_motorsport",
-            "assetto_corsa",
         ];
         let config = default_config();
         for game_id in bridge_games {
@@ -0,0 +1,248 @@
+//! EA WRC config.json shared with other tools: port conflicts and the
+//! structure file.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use racing_wheel_telemetry_config_writers::{
+    ConfigWriter, DiffOperation, EAWRCConfigWriter, TelemetryConfig,
+};
+use serde_json::Value;
+use tempfile::tempdir;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const TELEMETRY_DIR: &str = "Documents/My Games/WRC/telemetry";
+
+fn config(allow_port_sharing: bool) -> TelemetryConfig {
+    TelemetryConfig {
+        enabled: true,
+        update_rate_hz: 60,
+        output_method: "udp_schema".to_string(),
+        output_target: "127.0.0.1:20778".to_string(),
+        fields: Vec::new(),
+        enable_high_rate_iracing_360hz: false,
+        allow_port_sharing,
+    }
+}
+
+fn simhub(ip: &str, port: u16) -> Value {
+    serde_json::json!({
+        "packetId": "session_update",
+        "structureId": "simhub",
+        "ip": ip,
+        "port": port,
+        "frequencyHz": 60,
+        "bEnabled": true,
+    })
+}
+
+/// Write `assignments` to config.json, returning its path.
+fn config_json(game: &Path, assignments: &[Value]) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let path = game.join(TELEMETRY_DIR).join("config.json");
+    fs::create_dir_all(game.join(TELEMETRY_DIR))?;
+    fs::write(
+        &path,
+        serde_json::to_string_pretty(&serde_json::json!({
+            "udp": {"packetAssignments": assignments}
+        }))?,
+    )?;
+    Ok(path)
+}
+
+/// The port of the assignment registered under `structure_id`.
+fn assigned_port(
+    path: &Path,
+    structure_id: &str,
+) -> Result<Option<u64>, Box<dyn std::error::Error>> {
+    let root: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+    Ok(root
+        .pointer("/udp/packetAssignments")
+        .and_then(Value::as_array)
+        .and_then(|entries| {
+            entries.iter().find(|entry| {
+                entry.get("structureId").and_then(Value::as_str) == Some(structure_id)
+            })
+        })
+        .and_then(|entry| entry.get("port"))
+        .and_then(Value::as_u64))
+}
+
+#[test]
+fn simhub_on_the_same_port_is_reported_as_a_conflict() -> TestResult {
+    let game = tempdir()?;
+    let path = config_json(game.path(), &[simhub("127.0.0.1", 20778)])?;
+    let before = fs::read_to_string(&path)?;
+
+    let Err(err) = EAWRCConfigWriter.write_config(game.path(), &config(false)) else {
+        return Err("expected a port conflict".into());
+    };
+    let message = err.to_string();
+    assert!(message.contains("127.0.0.1:20778"), "{message}");
+    assert!(message.contains("simhub"), "{message}");
+
+    assert_eq!(fs::read_to_string(&path)?, before);
+    assert!(
+        !game
+            .path()
+            .join(TELEMETRY_DIR)
+            .join("udp/openracing.json")
+            .exists()
+    );
+    Ok(())
+}
+
+#[test]
+fn port_sharing_moves_to_the_next_free_port() -> TestResult {
+    let game = tempdir()?;
+    let path = config_json(
+        game.path(),
+        &[simhub("127.0.0.1", 20778), {
+            let mut dashboard = simhub("127.0.0.1", 20779);
+            dashboard["structureId"] = "dashboard".into();
+            dashboard
+        }],
+    )?;
+
+    let diffs = EAWRCConfigWriter.write_config(game.path(), &config(true))?;
+    let config_diff: Value = serde_json::from_str(&diffs[0].new_value)?;
+    assert_eq!(
+        config_diff.pointer("/udp/packetAssignments/2/port"),
+        Some(&Value::from(20780))
+    );
+    assert_eq!(assigned_port(&path, "openracing")?, Some(20780));
+    assert_eq!(assigned_port(&path, "simhub")?, Some(20778));
+    assert_eq!(assigned_port(&path, "dashboard")?, Some(20779));
+    assert!(EAWRCConfigWriter.validate_config(game.path())?);
+
+    // Writing again lands on the same port and changes nothing.
+    let diffs = EAWRCConfigWriter.write_config(game.path(), &config(true))?;
+    assert!(
+        diffs
+            .iter()
+            .all(|diff| diff.operation == DiffOperation::NoChange),
+        "{diffs:?}"
+    );
+    Ok(())
+}
+
+#[test]
+fn same_port_on_another_address_is_not_a_conflict() -> TestResult {
+    let game = tempdir()?;
+    let path = config_json(game.path(), &[simhub("192.168.1.20", 20778)])?;
+
+    EAWRCConfigWriter.write_config(game.path(), &config(false))?;
+    assert_eq!(assigned_port(&path, "openracing")?, Some(20778));
+    assert_eq!(assigned_port(&path, "simhub")?, Some(20778));
+    Ok(())
+}
+
+#[test]
+fn reformatted_structure_file_is_left_alone() -> TestResult {
+    let game = tempdir()?;
+    EAWRCConfigWriter.write_config(game.path(), &config(false))?;
+    let structure = game.path().join(TELEMETRY_DIR).join("udp/openracing.json");
+
+    let compact = serde_json::to_string(&serde_json::from_str::<Value>(&fs::read_to_string(
+        &structure,
+    )?)?)?;
+    fs::write(&structure, &compact)?;
+
+    let diffs = EAWRCConfigWriter.write_config(game.path(), &config(false))?;
+    assert_eq!(diffs[1].operation, DiffOperation::NoChange);
+    assert_eq!(fs::read_to_string(&structure)?, compact);
+    assert!(EAWRCConfigWriter.validate_config(game.path())?);
+    Ok(())
+}
+
+#[test]
+fn structure_file_with_other_channels_fails_validation() -> TestResult {
+    let game = tempdir()?;
+    EAWRCConfigWriter.write_config(game.path(), &config(false))?;
+    let structure = game.path().join(TELEMETRY_DIR).join("udp/openracing.json");
+
+    let mut edited: Value = serde_json::from_str(&fs::read_to_string(&structure)?)?;
+    for channels in [
+        serde_json::json!(["tyre_temp_fl", "engine_rpm"]),
+        serde_json::json!(["gear", "gear"]),
+        serde_json::json!([]),
+    ] {
+        edited["packets"][0]["channels"] = channels;
+        fs::write(&structure, serde_json::to_string_pretty(&edited)?)?;
+        assert!(!EAWRCConfigWriter.validate_config(game.path())?);
+    }
+
+    let diffs = EAWRCConfigWriter.write_config(game.path(), &config(false))?;
+    assert_eq!(diffs[1].operation, DiffOperation::Modify);
+    assert!(EAWRCConfigWriter.validate_config(game.path())?);
+    Ok(())
+}
+
+/// The channels in the structure file the last write left behind.
+fn structure_channels(game: &Path) -> Result<Value, Box<dyn std::error::Error>> {
+    let path = game.join(TELEMETRY_DIR).join("udp/openracing.json");
+    let structure: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+    structure
+        .pointer("/packets/0/channels")
+        .cloned()
+        .ok_or_else(|| "structure has no channel list".into())
+}
+
+#[test]
+fn requested_fields_become_the_structure_channels() -> TestResult {
+    let game = tempdir()?;
+    let mut config = config(false);
+    config.fields = ["gear", "rpm", "Speed", "engine_rpm"]
+        .map(String::from)
+        .to_vec();
+
+    EAWRCConfigWriter.write_config(game.path(), &config)?;
+    assert_eq!(
+        structure_channels(game.path())?,
+        serde_json::json!(["gear", "engine_rpm", "vehicle_speed"])
+    );
+    assert!(EAWRCConfigWriter.validate_config(game.path())?);
+
+    let expected = EAWRCConfigWriter.get_expected_diffs(&config)?;
+    let expected_structure: Value = serde_json::from_str(&expected[1].new_value)?;
+    assert_eq!(
+        expected_structure.pointer("/packets/0/channels"),
+        Some(&serde_json::json!(["gear", "engine_rpm", "vehicle_speed"]))
+    );
+    Ok(())
+}
+
+#[test]
+fn no_requested_fields_exports_the_default_channels() -> TestResult {
+    let game = tempdir()?;
+    EAWRCConfigWriter.write_config(game.path(), &config(false))?;
+    assert_eq!(
+        structure_channels(game.path())?,
+        serde_json::json!([
+            "ffb_scalar",
+            "engine_rpm",
+            "vehicle_speed",
+            "gear",
+            "slip_ratio"
+        ])
+    );
+    Ok(())
+}
+
+#[test]
+fn fields_ea_wrc_cannot_export_are_rejected_before_writing() -> TestResult {
+    for field in ["throttle", "tyre_wear"] {
+        let game = tempdir()?;
+        let mut config = config(false);
+        config.fields = vec!["rpm".to_string(), field.to_string()];
+
+        let Err(err) = EAWRCConfigWriter.write_config(game.path(), &config) else {
+            return Err(format!("{field} accepted").into());
+        };
+        let message = err.to_string();
+        assert!(message.contains(field), "{message}");
+        assert!(message.contains("valid fields are:"), "{message}");
+        assert!(!game.path().join(TELEMETRY_DIR).join("config.json").exists());
+    }
+    Ok(())
+}
@@ -0,0 +1,214 @@
+//! Game rewrite simulation for config writers.
+//!
+//! Some games reserialize their config files on exit from an internal model,
+//! dropping keys they do not recognize. Each fixture below mimics one game's
+//! rewrite, and the conformance check asserts that the writer's
+//! `survives_game_rewrite()` declaration matches what actually happens to the
+//! configuration it wrote.
+
+use racing_wheel_telemetry_config_writers::{
+    ConfigWriter, SurvivalExpectation, TelemetryConfig, config_writer_factories,
+};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::Path;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const AMS2_PLAYER_JSON: &str = "Documents/Automobilista 2/UserData/player/player.json";
+const ACC_BROADCASTING_JSON: &str = "Documents/Assetto Corsa Competizione/Config/broadcasting.json";
+
+/// Structured player.json sections AMS2 writes back; any other top-level
+/// object or array is dropped.
+const AMS2_KNOWN_KEYS: &[&str] = &["profile", "controls", "display", "audio", "gameplay"];
+
+fn default_config() -> TelemetryConfig {
+    TelemetryConfig {
+        enabled: true,
+        update_rate_hz: 60,
+        output_method: "udp".to_string(),
+        output_target: "127.0.0.1:9200".to_string(),
+        fields: vec!["ffb_scalar".to_string(), "rpm".to_string()],
+        enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
+    }
+}
+
+fn writer_for(
+    game_id: &str,
+) -> Result<Box<dyn ConfigWriter + Send + Sync>, Box<dyn std::error::Error>> {
+    config_writer_factories()
+        .iter()
+        .find(|(id, _)| *id == game_id)
+        .map(|(_, f)| f())
+        .ok_or_else(|| format!("{game_id} factory not found").into())
+}
+
+fn read_object(path: &Path) -> Result<Map<String, Value>, Box<dyn std::error::Error>> {
+    match serde_json::from_str(&fs::read_to_string(path)?)? {
+        Value::Object(map) => Ok(map),
+        other => Err(format!("expected a JSON object, got {other}").into()),
+    }
+}
+
+fn write_object(path: &Path, map: Map<String, Value>) -> TestResult {
+    fs::write(path, serde_json::to_string_pretty(&Value::Object(map))?)?;
+    Ok(())
+}
+
+/// AMS2 on exit: reserialize player.json keeping known sections and any
+/// top-level scalar.
+fn ams2_game_rewrite(path: &Path) -> TestResult {
+    let kept = read_object(path)?
+        .into_iter()
+        .filter(|(key, value)| {
+            AMS2_KNOWN_KEYS.contains(&key.as_str()) || !(value.is_object() || value.is_array())
+        })
+        .collect();
+    write_object(path, kept)
+}
+
+/// ACC on exit: rewrite broadcasting.json from its internal model, which
+/// holds only the listener port and the two passwords.
+fn acc_game_rewrite(path: &Path) -> TestResult {
+    let current = read_object(path)?;
+    let port = current
+        .get("updListenerPort")
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    let password = |key: &str| {
+        current
+            .get(key)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    let model = Map::from_iter([
+        ("updListenerPort".to_string(), Value::from(port)),
+        (
+            "connectionPassword".to_string(),
+            Value::from(password("connectionPassword")),
+        ),
+        (
+            "commandPassword".to_string(),
+            Value::from(password("commandPassword")),
+        ),
+    ]);
+    write_object(path, model)
+}
+
+/// Write, let the game rewrite, and check the outcome against the writer's
+/// declared expectation.
+fn assert_rewrite_conformance(
+    game_id: &str,
+    relative_path: &str,
+    game_rewrite: fn(&Path) -> TestResult,
+) -> TestResult {
+    let writer = writer_for(game_id)?;
+    let dir = tempfile::tempdir()?;
+    let config = default_config();
+    writer.write_config(dir.path(), &config)?;
+    assert!(
+        writer.validate_config(dir.path())?,
+        "{game_id}: fresh write"
+    );
+
+    game_rewrite(&dir.path().join(relative_path))?;
+    let survived = writer.validate_config(dir.path())?;
+    match writer.survives_game_rewrite() {
+        SurvivalExpectation::Survives => {
+            assert!(survived, "{game_id} declares survival but lost its config");
+        }
+        SurvivalExpectation::ReapplyAfterGameExit => {
+            assert!(
+                !survived,
+                "{game_id} survives the rewrite; declare SurvivalExpectation::Survives"
+            );
+            writer.write_config(dir.path(), &config)?;
+            assert!(writer.validate_config(dir.path())?, "{game_id}: re-apply");
+        }
+        SurvivalExpectation::Unknown => {
+            return Err(format!("{game_id} has a rewrite fixture but no expectation").into());
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn acc_configuration_survives_game_rewrite() -> TestResult {
+    assert_rewrite_conformance("acc", ACC_BROADCASTING_JSON, acc_game_rewrite)
+}
+
+#[test]
+fn ams2_documents_non_survival_of_game_rewrite() -> TestResult {
+    assert_rewrite_conformance("ams2", AMS2_PLAYER_JSON, ams2_game_rewrite)
+}
+
+#[test]
+fn acc_rewrite_keeps_listener_port_and_passwords() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join(ACC_BROADCASTING_JSON);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(
+        &path,
+        r#"{"updListenerPort": 9000, "connectionPassword": "asd", "commandPassword": "cmd"}"#,
+    )?;
+    let writer = writer_for("acc")?;
+    writer.write_config(dir.path(), &default_config())?;
+
+    acc_game_rewrite(&path)?;
+    let rewritten = read_object(&path)?;
+    assert_eq!(rewritten.len(), 3, "compatibility keys are dropped");
+    assert_eq!(
+        rewritten.get("updListenerPort").and_then(Value::as_u64),
+        Some(9200)
+    );
+    assert_eq!(
+        rewritten.get("connectionPassword").and_then(Value::as_str),
+        Some("asd")
+    );
+    assert!(writer.validate_config(dir.path())?);
+    Ok(())
+}
+
+#[test]
+fn ams2_rewrite_keeps_scalar_toggle_but_drops_intent_block() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join(AMS2_PLAYER_JSON);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, r#"{"profile": {"name": "driver"}, "fov": 60}"#)?;
+    let writer = writer_for("ams2")?;
+    writer.write_config(dir.path(), &default_config())?;
+
+    ams2_game_rewrite(&path)?;
+    let rewritten = read_object(&path)?;
+    assert_eq!(
+        rewritten
+            .get("sharedMemoryEnabled")
+            .and_then(Value::as_bool),
+        Some(true)
+    );
+    assert!(!rewritten.contains_key("openRacingTelemetry"));
+    assert!(rewritten.contains_key("profile"));
+    assert_eq!(rewritten.get("fov").and_then(Value::as_u64), Some(60));
+    assert!(!writer.validate_config(dir.path())?);
+    Ok(())
+}
+
+#[test]
+fn survival_expectations_carry_user_hints() -> TestResult {
+    assert_eq!(writer_for("acc")?.survives_game_rewrite().user_hint(), None);
+    assert_eq!(
+        writer_for("ams2")?.survives_game_rewrite().user_hint(),
+        Some("must re-apply after each game exit")
+    );
+    // Writers without a rewrite model make no promise either way.
+    let iracing = writer_for("iracing")?.survives_game_rewrite();
+    assert_eq!(iracing, SurvivalExpectation::Unknown);
+    assert!(iracing.user_hint().is_some());
+    Ok(())
+}
@@ -0,0 +1,140 @@
+//! Re-applying an unchanged config reports NoChange and leaves files alone.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use racing_wheel_telemetry_config_writers::{
+    ConfigWriter, DiffOperation, IRacingConfigWriter, TelemetryConfig, config_writer_factories,
+};
+use serde_json::Value;
+use tempfile::tempdir;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn config() -> TelemetryConfig {
+    TelemetryConfig {
+        enabled: true,
+        update_rate_hz: 60,
+        output_method: "udp".to_string(),
+        output_target: "127.0.0.1:9996".to_string(),
+        fields: vec!["rpm".to_string()],
+        enable_high_rate_iracing_360hz: true,
+        allow_port_sharing: false,
+    }
+}
+
+/// Backdate every file under `root` and return its modification times.
+fn backdate(root: &Path) -> Result<BTreeMap<PathBuf, SystemTime>, std::io::Error> {
+    let past = SystemTime::now() - Duration::from_secs(3600);
+    let mut times = BTreeMap::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else {
+                File::options()
+                    .write(true)
+                    .open(&path)?
+                    .set_modified(past)?;
+                times.insert(path.clone(), fs::metadata(&path)?.modified()?);
+            }
+        }
+    }
+    Ok(times)
+}
+
+fn mtimes(paths: &BTreeMap<PathBuf, SystemTime>) -> Result<Vec<SystemTime>, std::io::Error> {
+    paths
+        .keys()
+        .map(|path| fs::metadata(path)?.modified())
+        .collect()
+}
+
+#[test]
+fn second_write_of_the_same_config_changes_nothing() -> TestResult {
+    for (id, factory) in config_writer_factories() {
+        let writer = factory();
+        let game = tempdir()?;
+        writer.write_config(game.path(), &config())?;
+        let before = backdate(game.path())?;
+
+        let diffs = writer.write_config(game.path(), &config())?;
+        assert!(
+            diffs
+                .iter()
+                .all(|diff| diff.operation == DiffOperation::NoChange),
+            "{id}: {diffs:?}"
+        );
+        assert_eq!(
+            mtimes(&before)?,
+            before.values().copied().collect::<Vec<_>>(),
+            "{id} rewrote an unchanged file"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn reordered_json_counts_as_unchanged() -> TestResult {
+    for (id, factory) in config_writer_factories() {
+        let writer = factory();
+        let game = tempdir()?;
+        let diffs = writer.write_config(game.path(), &config())?;
+
+        // Rewrite each JSON file compactly with its keys in reverse order.
+        let mut reordered = Vec::new();
+        for diff in &diffs {
+            let Ok(Value::Object(map)) = serde_json::from_str::<Value>(&diff.new_value) else {
+                continue;
+            };
+            let body: Vec<String> = map
+                .iter()
+                .rev()
+                .map(|(key, value)| Ok(format!("{}:{value}", serde_json::to_string(key)?)))
+                .collect::<Result<_, serde_json::Error>>()?;
+            let compact = format!("{{{}}}", body.join(","));
+            fs::write(&diff.file_path, &compact)?;
+            reordered.push((diff.file_path.clone(), compact));
+        }
+
+        let again = writer.write_config(game.path(), &config())?;
+        assert!(
+            again
+                .iter()
+                .all(|diff| diff.operation == DiffOperation::NoChange),
+            "{id}: {again:?}"
+        );
+        for (path, compact) in reordered {
+            assert_eq!(fs::read_to_string(&path)?, compact, "{id} rewrote {path}");
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn changed_ini_key_is_modified_and_unchanged_key_is_not() -> TestResult {
+    let game = tempdir()?;
+    IRacingConfigWriter.write_config(game.path(), &config())?;
+
+    let disabled = TelemetryConfig {
+        enabled: false,
+        ..config()
+    };
+    let diffs = IRacingConfigWriter.write_config(game.path(), &disabled)?;
+    let operations: Vec<_> = diffs
+        .iter()
+        .map(|diff| (diff.key.as_str(), diff.operation.clone()))
+        .collect();
+    assert_eq!(
+        operations,
+        [
+            ("telemetryDiskFile", DiffOperation::Modify),
+            ("irsdkLog360Hz", DiffOperation::NoChange),
+        ]
+    );
+    Ok(())
+}
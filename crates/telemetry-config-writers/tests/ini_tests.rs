@@ -0,0 +1,126 @@
+//! iRacing app.ini edits: duplicate sections, comments, spacing and CRLF.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use racing_wheel_telemetry_config_writers::{
+    ConfigWriter, DiffOperation, IRacingConfigWriter, TelemetryConfig,
+};
+use tempfile::tempdir;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+/// Two `[Telemetry]` sections: the first enabled, the stale second not.
+const FIXTURE: &str = include_str!("fixtures/iracing_app_duplicate_sections.ini");
+
+const WRITTEN: &str = "; iRacing app.ini left behind after a crash
+[Graphics]
+fullscreen=1 ; borderless on the second monitor
+
+[Telemetry]
+# written by an older session
+telemetryDiskFile = 1   ; enabled by hand
+telemetryDiskFileMaxMB=512
+irsdkLog360Hz=1
+
+[Audio]
+volume=3
+
+[Telemetry]
+telemetryDiskFile=1
+; irsdkLog360Hz=1
+";
+
+fn config() -> TelemetryConfig {
+    TelemetryConfig {
+        enabled: true,
+        update_rate_hz: 60,
+        output_method: "shared_memory".to_string(),
+        output_target: String::new(),
+        fields: Vec::new(),
+        enable_high_rate_iracing_360hz: true,
+        allow_port_sharing: false,
+    }
+}
+
+fn app_ini(game: &Path, contents: &str) -> Result<PathBuf, std::io::Error> {
+    let path = game.join("Documents/iRacing/app.ini");
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, contents)?;
+    Ok(path)
+}
+
+#[test]
+fn stale_duplicate_section_fails_validation_until_written() -> TestResult {
+    for eol in ["\n", "\r\n"] {
+        let game = tempdir()?;
+        let path = app_ini(game.path(), &FIXTURE.replace('\n', eol))?;
+        assert!(!IRacingConfigWriter.validate_config(game.path())?);
+
+        let diffs = IRacingConfigWriter.write_config(game.path(), &config())?;
+        assert_eq!(diffs[0].key, "telemetryDiskFile");
+        assert_eq!(diffs[0].operation, DiffOperation::Modify);
+        assert_eq!(diffs[0].old_value.as_deref(), Some("0"));
+        assert_eq!(diffs[1].key, "irsdkLog360Hz");
+        assert_eq!(diffs[1].operation, DiffOperation::Add);
+
+        assert_eq!(fs::read_to_string(&path)?, WRITTEN.replace('\n', eol));
+        assert!(IRacingConfigWriter.validate_config(game.path())?);
+    }
+    Ok(())
+}
+
+#[test]
+fn rollback_restores_the_fixture_byte_for_byte() -> TestResult {
+    for eol in ["\n", "\r\n"] {
+        let game = tempdir()?;
+        let original = FIXTURE.replace('\n', eol);
+        let path = app_ini(game.path(), &original)?;
+
+        let diffs = IRacingConfigWriter.write_config(game.path(), &config())?;
+        IRacingConfigWriter.rollback_config(game.path(), &diffs)?;
+
+        // Both copies take the first differing value, so the stale duplicate's
+        // disabled state is what comes back.
+        let expected = original.replace("telemetryDiskFile = 1 ", "telemetryDiskFile = 0 ");
+        assert_eq!(fs::read_to_string(&path)?, expected);
+        assert!(!IRacingConfigWriter.validate_config(game.path())?);
+    }
+    Ok(())
+}
+
+#[test]
+fn whitespace_around_equals_is_accepted_and_kept() -> TestResult {
+    let game = tempdir()?;
+    let path = app_ini(
+        game.path(),
+        "[Telemetry]\n  telemetryDiskFile =  0\t# off\nirsdkLog360Hz = 1\n",
+    )?;
+
+    let diffs = IRacingConfigWriter.write_config(game.path(), &config())?;
+    assert_eq!(diffs[0].operation, DiffOperation::Modify);
+    assert_eq!(diffs[1].operation, DiffOperation::NoChange);
+    assert_eq!(
+        fs::read_to_string(&path)?,
+        "[Telemetry]\n  telemetryDiskFile =  1\t# off\nirsdkLog360Hz = 1\n"
+    );
+    assert!(IRacingConfigWriter.validate_config(game.path())?);
+
+    app_ini(game.path(), "[Telemetry]\ntelemetryDiskFile = 1 ; on\n")?;
+    assert!(IRacingConfigWriter.validate_config(game.path())?);
+    Ok(())
+}
+
+#[test]
+fn commented_out_key_does_not_count_as_enabled() -> TestResult {
+    let game = tempdir()?;
+    app_ini(game.path(), "[Telemetry]\n; telemetryDiskFile=1\n")?;
+    assert!(!IRacingConfigWriter.validate_config(game.path())?);
+
+    let diffs = IRacingConfigWriter.write_config(game.path(), &config())?;
+    assert_eq!(diffs[0].operation, DiffOperation::Add);
+    assert!(IRacingConfigWriter.validate_config(game.path())?);
+    Ok(())
+}
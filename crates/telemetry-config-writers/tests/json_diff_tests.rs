@@ -0,0 +1,176 @@
+//! Per-value details on diffs of JSON config files.
+
+use std::fs;
+
+use racing_wheel_telemetry_config_writers::{
+    AMS2ConfigWriter, ConfigDiff, ConfigWriter, DiffOperation, TelemetryConfig,
+    config_writer_factories,
+};
+use serde_json::{Value, json};
+use tempfile::tempdir;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const PLAYER_JSON: &str = "Documents/Automobilista 2/UserData/player/player.json";
+
+fn config() -> TelemetryConfig {
+    TelemetryConfig {
+        enabled: true,
+        update_rate_hz: 120,
+        output_method: "shared_memory".to_string(),
+        output_target: String::new(),
+        fields: Vec::new(),
+        enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
+    }
+}
+
+fn details(diff: &ConfigDiff) -> Vec<(&str, &DiffOperation, Option<&Value>, Option<&Value>)> {
+    diff.details
+        .iter()
+        .map(|detail| {
+            (
+                detail.pointer.as_str(),
+                &detail.operation,
+                detail.old_value.as_ref(),
+                detail.new_value.as_ref(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn pre_existing_document_reports_each_changed_value() -> TestResult {
+    let game = tempdir()?;
+    let path = game.path().join(PLAYER_JSON);
+    fs::create_dir_all(path.parent().ok_or("no parent")?)?;
+    fs::write(
+        &path,
+        serde_json::to_string_pretty(&json!({
+            "graphics": {"resolution": "2560x1440"},
+            "openRacingTelemetry": {
+                "enabled": true,
+                "legacyPort": 5606,
+                "note": "Enable Project CARS 2 shared memory in AMS2 options.",
+                "sharedMemoryMap": "$pcars2$",
+                "updateRateHz": 60
+            }
+        }))?,
+    )?;
+
+    let diffs = AMS2ConfigWriter.write_config(game.path(), &config())?;
+    let [diff] = diffs.as_slice() else {
+        return Err(format!("expected one diff, got {diffs:?}").into());
+    };
+    assert_eq!(diff.key, "entire_file");
+    assert_eq!(
+        details(diff),
+        [
+            (
+                "/openRacingTelemetry/legacyPort",
+                &DiffOperation::Remove,
+                Some(&json!(5606)),
+                None
+            ),
+            (
+                "/openRacingTelemetry/updateRateHz",
+                &DiffOperation::Modify,
+                Some(&json!(60)),
+                Some(&json!(120))
+            ),
+            (
+                "/sharedMemoryEnabled",
+                &DiffOperation::Add,
+                None,
+                Some(&json!(true))
+            ),
+        ]
+    );
+
+    // The whole-file diff still rolls the document back.
+    AMS2ConfigWriter.rollback_config(game.path(), &diffs)?;
+    assert_eq!(Some(fs::read_to_string(&path)?), diff.old_value);
+    Ok(())
+}
+
+#[test]
+fn leaf_diffs_expand_details_keyed_by_pointer() -> TestResult {
+    let game = tempdir()?;
+    let path = game.path().join(PLAYER_JSON);
+    fs::create_dir_all(path.parent().ok_or("no parent")?)?;
+    AMS2ConfigWriter.write_config(game.path(), &config())?;
+
+    let mut faster = config();
+    faster.update_rate_hz = 240;
+    let diffs = AMS2ConfigWriter.write_config(game.path(), &faster)?;
+    let leaves = diffs[0].leaf_diffs();
+    let [leaf] = leaves.as_slice() else {
+        return Err(format!("expected one leaf, got {leaves:?}").into());
+    };
+    assert_eq!(leaf.key, "/openRacingTelemetry/updateRateHz");
+    assert_eq!(leaf.old_value.as_deref(), Some("120"));
+    assert_eq!(leaf.new_value, "240");
+    assert_eq!(leaf.operation, DiffOperation::Modify);
+    assert_eq!(leaf.backup_path, diffs[0].backup_path);
+
+    // Rewriting the same config has nothing to detail.
+    let diffs = AMS2ConfigWriter.write_config(game.path(), &faster)?;
+    assert_eq!(diffs[0].operation, DiffOperation::NoChange);
+    assert!(diffs[0].details.is_empty());
+    Ok(())
+}
+
+#[test]
+fn removal_details_every_value_dropped() -> TestResult {
+    let game = tempdir()?;
+    AMS2ConfigWriter.write_config(game.path(), &config())?;
+
+    let diffs = AMS2ConfigWriter.remove_config(game.path())?;
+    let pointers: Vec<(&str, &DiffOperation)> = diffs[0]
+        .details
+        .iter()
+        .map(|detail| (detail.pointer.as_str(), &detail.operation))
+        .collect();
+    assert_eq!(
+        pointers,
+        [
+            ("/openRacingTelemetry/enabled", &DiffOperation::Remove),
+            ("/openRacingTelemetry/note", &DiffOperation::Remove),
+            (
+                "/openRacingTelemetry/sharedMemoryMap",
+                &DiffOperation::Remove
+            ),
+            ("/openRacingTelemetry/updateRateHz", &DiffOperation::Remove),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn expected_diffs_detail_the_same_values_a_fresh_write_adds() -> TestResult {
+    for (id, factory) in config_writer_factories() {
+        let writer = factory();
+        let game = tempdir()?;
+        let written = writer.write_config(game.path(), &config())?;
+        let expected = writer.get_expected_diffs(&config())?;
+        assert_eq!(written.len(), expected.len(), "{id}");
+
+        for (written, expected) in written.iter().zip(&expected) {
+            let pointers = |diff: &ConfigDiff| {
+                diff.details
+                    .iter()
+                    .map(|detail| (detail.pointer.clone(), detail.operation.clone()))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(pointers(written), pointers(expected), "{id}");
+            assert!(
+                written
+                    .details
+                    .iter()
+                    .all(|detail| detail.operation == DiffOperation::Add),
+                "{id}"
+            );
+        }
+    }
+    Ok(())
+}
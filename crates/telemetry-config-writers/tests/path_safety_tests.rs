@@ -0,0 +1,133 @@
+//! Writers refuse targets that symlinks or allowlists would place outside the
+//! game path, and write normally everywhere else.
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::symlink;
+use std::path::Path;
+
+use racing_wheel_telemetry_config_writers::{
+    ConfigWriter, IRacingConfigWriter, PathPolicy, PathSafetyError, TelemetryConfig, WriteOptions,
+};
+use tempfile::tempdir;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn config() -> TelemetryConfig {
+    TelemetryConfig {
+        enabled: true,
+        update_rate_hz: 60,
+        output_method: "shared_memory".to_string(),
+        output_target: "127.0.0.1:12345".to_string(),
+        fields: vec!["rpm".to_string()],
+        enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
+    }
+}
+
+/// Write iRacing's `Documents/iRacing/app.ini` under `game_path`.
+fn write_iracing(game_path: &Path) -> anyhow::Result<()> {
+    IRacingConfigWriter.write_config(game_path, &config())?;
+    Ok(())
+}
+
+/// Write iRacing's `app.ini` with targets confined under `policy`.
+fn write_iracing_under(game_path: &Path, policy: PathPolicy) -> anyhow::Result<()> {
+    let options = WriteOptions::default().with_path_policy(policy);
+    IRacingConfigWriter.write_config_with(game_path, &config(), &options)?;
+    Ok(())
+}
+
+fn safety_error(result: anyhow::Result<()>) -> Result<PathSafetyError, Box<dyn std::error::Error>> {
+    let err = result.err().ok_or("write was not refused")?;
+    Ok(err
+        .downcast_ref::<PathSafetyError>()
+        .cloned()
+        .ok_or(format!("unexpected error: {err}"))?)
+}
+
+#[test]
+fn symlinked_directory_pointing_outside_is_refused() -> TestResult {
+    let game = tempdir()?;
+    let outside = tempdir()?;
+    symlink(outside.path(), game.path().join("Documents"))?;
+
+    let err = safety_error(write_iracing(game.path()))?;
+    assert!(
+        matches!(err, PathSafetyError::PathEscapesRoot { .. }),
+        "{err}"
+    );
+    assert!(!outside.path().join("iRacing").exists());
+    Ok(())
+}
+
+#[test]
+fn inside_symlink_is_followed_unless_the_policy_refuses_links() -> TestResult {
+    let game = tempdir()?;
+    fs::create_dir(game.path().join("real_documents"))?;
+    symlink(
+        game.path().join("real_documents"),
+        game.path().join("Documents"),
+    )?;
+
+    let strict = PathPolicy::default().refuse_symlinks();
+    let err = safety_error(write_iracing_under(game.path(), strict))?;
+    assert_eq!(
+        err,
+        PathSafetyError::SymlinkRefused {
+            path: game.path().join("Documents"),
+        }
+    );
+
+    write_iracing(game.path())?;
+    assert!(game.path().join("real_documents/iRacing/app.ini").is_file());
+    Ok(())
+}
+
+#[test]
+fn allowlisted_root_accepts_writes_outside_the_game_path() -> TestResult {
+    let game = tempdir()?;
+    let documents = tempdir()?;
+    symlink(documents.path(), game.path().join("Documents"))?;
+
+    let policy = PathPolicy::default().allow_root(documents.path());
+    write_iracing_under(game.path(), policy)?;
+    let written = fs::read_to_string(documents.path().join("iRacing/app.ini"))?;
+    assert!(written.contains("telemetryDiskFile=1"), "{written}");
+    Ok(())
+}
+
+#[test]
+fn symlinked_target_file_is_not_overwritten() -> TestResult {
+    let game = tempdir()?;
+    let outside = tempdir()?;
+    let victim = outside.path().join("notes.txt");
+    fs::write(&victim, "keep me")?;
+    fs::create_dir_all(game.path().join("Documents/iRacing"))?;
+    symlink(&victim, game.path().join("Documents/iRacing/app.ini"))?;
+
+    let err = safety_error(write_iracing(game.path()))?;
+    assert!(
+        matches!(err, PathSafetyError::PathEscapesRoot { .. }),
+        "{err}"
+    );
+    assert_eq!(fs::read_to_string(&victim)?, "keep me");
+    Ok(())
+}
+
+#[test]
+fn dangling_symlink_cannot_create_a_file_outside() -> TestResult {
+    let game = tempdir()?;
+    let outside = tempdir()?;
+    let planted = outside.path().join("planted.ini");
+    fs::create_dir_all(game.path().join("Documents/iRacing"))?;
+    symlink(&planted, game.path().join("Documents/iRacing/app.ini"))?;
+
+    let err = safety_error(write_iracing(game.path()))?;
+    assert!(
+        matches!(err, PathSafetyError::PathEscapesRoot { .. }),
+        "{err}"
+    );
+    assert!(!planted.exists());
+    Ok(())
+}
@@ -0,0 +1,200 @@
+//! Previews report exactly what a write would and leave the disk untouched.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use racing_wheel_telemetry_config_writers::{
+    ACCConfigWriter, ConfigDiff, ConfigWriter, ConfigWriterMetadata, DiffOperation,
+    IRacingConfigWriter, TelemetryConfig, WriteMode, WriteOptions, config_writer_factories,
+};
+use tempfile::tempdir;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn config() -> TelemetryConfig {
+    TelemetryConfig {
+        enabled: true,
+        update_rate_hz: 60,
+        output_method: "udp".to_string(),
+        output_target: "127.0.0.1:9996".to_string(),
+        fields: vec!["rpm".to_string(), "speed_ms".to_string()],
+        enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
+    }
+}
+
+/// Every file and directory under `root` with file contents.
+fn tree(root: &Path) -> Result<BTreeMap<PathBuf, Option<Vec<u8>>>, std::io::Error> {
+    let mut entries = BTreeMap::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                entries.insert(path.clone(), None);
+                pending.push(path);
+            } else {
+                entries.insert(path.clone(), Some(fs::read(&path)?));
+            }
+        }
+    }
+    Ok(entries)
+}
+
+#[test]
+fn every_writer_previews_the_diffs_it_then_writes() -> TestResult {
+    for (id, factory) in config_writer_factories() {
+        let writer = factory();
+        let game = tempdir()?;
+
+        // First against an empty tree, then against the files just written.
+        for pass in ["fresh", "existing"] {
+            let before = tree(game.path())?;
+            let preview = writer
+                .preview_config(game.path(), &config())
+                .map_err(|err| format!("{id} ({pass}): {err}"))?;
+            assert_eq!(tree(game.path())?, before, "{id} ({pass}) touched the disk");
+
+            let written = writer.write_config(game.path(), &config())?;
+            assert_eq!(preview, written, "{id} ({pass})");
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn malformed_json_is_previewed_as_the_write_would_replace_it() -> TestResult {
+    let game = tempdir()?;
+    let path = game
+        .path()
+        .join("Documents/Assetto Corsa Competizione/Config/broadcasting.json");
+    fs::create_dir_all(path.parent().ok_or("no parent")?)?;
+    fs::write(&path, "{ \"updListenerPort\": 9000,")?;
+
+    let preview = ACCConfigWriter.preview_config(game.path(), &config())?;
+    assert_eq!(fs::read_to_string(&path)?, "{ \"updListenerPort\": 9000,");
+    assert!(
+        preview
+            .iter()
+            .all(|diff| diff.operation == DiffOperation::Modify),
+        "{preview:?}"
+    );
+
+    // The write backs up the file it replaces; a preview takes no backup.
+    let mut written = ACCConfigWriter.write_config(game.path(), &config())?;
+    for diff in &mut written {
+        assert!(diff.backup_path.take().is_some(), "{diff:?}");
+    }
+    assert_eq!(preview, written);
+    Ok(())
+}
+
+#[test]
+fn ini_section_without_the_key_previews_an_add() -> TestResult {
+    let game = tempdir()?;
+    let path = game.path().join("Documents/iRacing/app.ini");
+    fs::create_dir_all(path.parent().ok_or("no parent")?)?;
+    let original = "[Telemetry]\ntelemetryDiskFileMaxMB=512\n";
+    fs::write(&path, original)?;
+
+    let preview = IRacingConfigWriter.preview_config(game.path(), &config())?;
+    assert_eq!(fs::read_to_string(&path)?, original);
+    let [diff] = preview.as_slice() else {
+        return Err(format!("expected one diff, got {preview:?}").into());
+    };
+    assert_eq!(diff.section.as_deref(), Some("Telemetry"));
+    assert_eq!(diff.key, "telemetryDiskFile");
+    assert_eq!(diff.old_value, None);
+    assert_eq!(diff.operation, DiffOperation::Add);
+
+    let mut written = IRacingConfigWriter.write_config(game.path(), &config())?;
+    for diff in &mut written {
+        assert!(diff.backup_path.take().is_some(), "{diff:?}");
+    }
+    assert_eq!(preview, written);
+    Ok(())
+}
+
+/// A writer that goes straight to `std::fs`, with none of the crate's
+/// confinement, locking or backup helpers.
+struct RawFsWriter;
+
+impl RawFsWriter {
+    const FILE: &'static str = "raw_telemetry.cfg";
+}
+
+impl ConfigWriter for RawFsWriter {
+    fn write_config_with(
+        &self,
+        game_path: &Path,
+        config: &TelemetryConfig,
+        options: &WriteOptions,
+    ) -> anyhow::Result<Vec<ConfigDiff>> {
+        let path = game_path.join(Self::FILE);
+        let old_value = fs::read_to_string(&path).ok();
+        let new_value = format!("target={}\n", config.output_target);
+        let operation = match &old_value {
+            Some(old) if *old == new_value => DiffOperation::NoChange,
+            Some(_) => DiffOperation::Modify,
+            None => DiffOperation::Add,
+        };
+        if options.mode == WriteMode::Apply {
+            fs::write(&path, &new_value)?;
+        }
+        Ok(vec![ConfigDiff {
+            file_path: path.to_string_lossy().into_owned(),
+            section: None,
+            key: "entire_file".to_string(),
+            old_value,
+            new_value,
+            operation,
+            backup_path: None,
+            details: Vec::new(),
+        }])
+    }
+
+    fn validate_config(&self, game_path: &Path) -> anyhow::Result<bool> {
+        Ok(game_path.join(Self::FILE).exists())
+    }
+
+    fn get_expected_diffs(&self, _config: &TelemetryConfig) -> anyhow::Result<Vec<ConfigDiff>> {
+        Ok(Vec::new())
+    }
+
+    fn metadata(&self) -> ConfigWriterMetadata {
+        ConfigWriterMetadata {
+            game_id: "raw_fs",
+            requires_game_restart: false,
+            manual_steps: Vec::new(),
+            native_config_modified: false,
+        }
+    }
+}
+
+#[test]
+fn writer_bypassing_the_helpers_is_previewed_without_touching_the_disk() -> TestResult {
+    let game = tempdir()?;
+
+    let preview = RawFsWriter.preview_config(game.path(), &config())?;
+    assert!(tree(game.path())?.is_empty(), "preview wrote to disk");
+    let operations: Vec<_> = preview.iter().map(|diff| diff.operation.clone()).collect();
+    assert_eq!(operations, [DiffOperation::Add]);
+
+    let written = RawFsWriter.write_config(game.path(), &config())?;
+    assert_eq!(preview, written);
+    let path = game.path().join(RawFsWriter::FILE);
+    let before = tree(game.path())?;
+
+    // Previewing a different config reports the change but leaves the file.
+    let changed = TelemetryConfig {
+        output_target: "127.0.0.1:20777".to_string(),
+        ..config()
+    };
+    let preview = RawFsWriter.preview_config(game.path(), &changed)?;
+    let operations: Vec<_> = preview.iter().map(|diff| diff.operation.clone()).collect();
+    assert_eq!(operations, [DiffOperation::Modify]);
+    assert_eq!(tree(game.path())?, before);
+    assert_eq!(fs::read_to_string(path)?, "target=127.0.0.1:9996\n");
+    Ok(())
+}
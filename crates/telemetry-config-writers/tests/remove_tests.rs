@@ -0,0 +1,204 @@
+//! Removing OpenRacing's configuration disables telemetry and keeps user data.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use racing_wheel_telemetry_config_writers::{
+    ACCConfigWriter, BACKUP_MARKER, ConfigWriter, DiffOperation, Dirt5ConfigWriter,
+    EAWRCConfigWriter, IRacingConfigWriter, TelemetryConfig, config_writer_factories,
+};
+use serde_json::Value;
+use tempfile::tempdir;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn config() -> TelemetryConfig {
+    TelemetryConfig {
+        enabled: true,
+        update_rate_hz: 60,
+        output_method: "udp".to_string(),
+        output_target: "127.0.0.1:9996".to_string(),
+        fields: vec!["rpm".to_string()],
+        enable_high_rate_iracing_360hz: true,
+        allow_port_sharing: false,
+    }
+}
+
+/// Every file under `root` with its contents, leaving out backups.
+fn files(root: &Path) -> Result<BTreeMap<PathBuf, String>, std::io::Error> {
+    let mut entries = BTreeMap::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if !path.to_string_lossy().contains(BACKUP_MARKER) {
+                entries.insert(path.clone(), fs::read_to_string(&path)?);
+            }
+        }
+    }
+    Ok(entries)
+}
+
+#[test]
+fn removing_from_an_untouched_install_is_a_no_op() -> TestResult {
+    for (id, factory) in config_writer_factories() {
+        let game = tempdir()?;
+        let diffs = factory().remove_config(game.path())?;
+        assert!(diffs.is_empty(), "{id}: {diffs:?}");
+        assert_eq!(files(game.path())?, BTreeMap::new(), "{id} created files");
+    }
+    Ok(())
+}
+
+#[test]
+fn every_writer_stops_validating_after_removal_and_can_roll_it_back() -> TestResult {
+    for (id, factory) in config_writer_factories() {
+        let writer = factory();
+        let game = tempdir()?;
+        writer.write_config(game.path(), &config())?;
+        let written = files(game.path())?;
+
+        let diffs = writer.remove_config(game.path())?;
+        assert!(!diffs.is_empty(), "{id} removed nothing");
+        assert!(
+            diffs
+                .iter()
+                .all(|diff| diff.operation != DiffOperation::Add),
+            "{id}: {diffs:?}"
+        );
+        assert!(
+            !writer.validate_config(game.path())?,
+            "{id} still validates"
+        );
+
+        writer.rollback_config(game.path(), &diffs)?;
+        assert!(writer.validate_config(game.path())?, "{id} not restored");
+        assert_eq!(files(game.path())?, written, "{id}");
+    }
+    Ok(())
+}
+
+#[test]
+fn bridge_contract_is_deleted_with_its_contents_captured() -> TestResult {
+    let game = tempdir()?;
+    Dirt5ConfigWriter.write_config(game.path(), &config())?;
+    let path = game
+        .path()
+        .join("Documents/OpenRacing/dirt5_bridge_contract.json");
+    let contract = fs::read_to_string(&path)?;
+
+    let diffs = Dirt5ConfigWriter.remove_config(game.path())?;
+    let [diff] = diffs.as_slice() else {
+        return Err(format!("expected one diff, got {diffs:?}").into());
+    };
+    assert_eq!(diff.operation, DiffOperation::Remove);
+    assert_eq!(diff.old_value.as_deref(), Some(contract.as_str()));
+    assert!(!path.exists());
+
+    // A second removal finds nothing left to do.
+    assert!(Dirt5ConfigWriter.remove_config(game.path())?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn iracing_removal_disables_telemetry_and_keeps_other_settings() -> TestResult {
+    let game = tempdir()?;
+    let path = game.path().join("Documents/iRacing/app.ini");
+    fs::create_dir_all(path.parent().ok_or("no parent")?)?;
+    fs::write(
+        &path,
+        "[Graphics]\nfullscreen=1\n\n[Telemetry]\nmaxMB=512\n",
+    )?;
+    IRacingConfigWriter.write_config(game.path(), &config())?;
+
+    let diffs = IRacingConfigWriter.remove_config(game.path())?;
+    let operations: Vec<(&str, &DiffOperation)> = diffs
+        .iter()
+        .map(|diff| (diff.key.as_str(), &diff.operation))
+        .collect();
+    assert_eq!(
+        operations,
+        [
+            ("telemetryDiskFile", &DiffOperation::Modify),
+            ("irsdkLog360Hz", &DiffOperation::Remove),
+        ]
+    );
+    assert_eq!(
+        fs::read_to_string(&path)?,
+        "[Graphics]\nfullscreen=1\n\n[Telemetry]\nmaxMB=512\ntelemetryDiskFile=0\n"
+    );
+    assert!(!IRacingConfigWriter.validate_config(game.path())?);
+    Ok(())
+}
+
+#[test]
+fn acc_removal_keeps_passwords_the_user_set() -> TestResult {
+    let game = tempdir()?;
+    let path = game
+        .path()
+        .join("Documents/Assetto Corsa Competizione/Config/broadcasting.json");
+    fs::create_dir_all(path.parent().ok_or("no parent")?)?;
+    fs::write(
+        &path,
+        r#"{"connectionPassword": "secret", "commandPassword": "admin"}"#,
+    )?;
+    ACCConfigWriter.write_config(game.path(), &config())?;
+
+    let diffs = ACCConfigWriter.remove_config(game.path())?;
+    assert_eq!(diffs[0].operation, DiffOperation::Modify);
+    let remaining: Value = serde_json::from_str(&fs::read_to_string(&path)?)?;
+    assert_eq!(
+        remaining,
+        serde_json::json!({"connectionPassword": "secret", "commandPassword": "admin"})
+    );
+    assert!(!ACCConfigWriter.validate_config(game.path())?);
+    Ok(())
+}
+
+#[test]
+fn acc_file_openracing_created_is_deleted() -> TestResult {
+    let game = tempdir()?;
+    ACCConfigWriter.write_config(game.path(), &config())?;
+
+    let diffs = ACCConfigWriter.remove_config(game.path())?;
+    assert_eq!(diffs[0].operation, DiffOperation::Remove);
+    assert_eq!(files(game.path())?, BTreeMap::new());
+    Ok(())
+}
+
+#[test]
+fn eawrc_removal_keeps_other_packet_assignments() -> TestResult {
+    let game = tempdir()?;
+    let path = game
+        .path()
+        .join("Documents/My Games/WRC/telemetry/config.json");
+    fs::create_dir_all(path.parent().ok_or("no parent")?)?;
+    let other = serde_json::json!({
+        "packetId": "session_update",
+        "structureId": "simhub",
+        "ip": "127.0.0.1",
+        "port": 20999,
+    });
+    fs::write(
+        &path,
+        serde_json::to_string(&serde_json::json!({"udp": {"packetAssignments": [other]}}))?,
+    )?;
+    EAWRCConfigWriter.write_config(game.path(), &config())?;
+
+    EAWRCConfigWriter.remove_config(game.path())?;
+    let remaining: Value = serde_json::from_str(&fs::read_to_string(&path)?)?;
+    assert_eq!(
+        remaining,
+        serde_json::json!({"udp": {"packetAssignments": [other]}})
+    );
+    assert!(
+        !game
+            .path()
+            .join("Documents/My Games/WRC/telemetry/udp/openracing.json")
+            .exists()
+    );
+    Ok(())
+}
@@ -0,0 +1,118 @@
+//! Rolling back a write restores what was on disk before it.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use racing_wheel_telemetry_config_writers::{
+    ACCConfigWriter, ConfigWriter, IRacingConfigWriter, TelemetryConfig, config_writer_factories,
+};
+use tempfile::tempdir;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn config() -> TelemetryConfig {
+    TelemetryConfig {
+        enabled: true,
+        update_rate_hz: 60,
+        output_method: "udp".to_string(),
+        output_target: "127.0.0.1:9996".to_string(),
+        fields: vec!["rpm".to_string(), "speed_ms".to_string()],
+        enable_high_rate_iracing_360hz: true,
+        allow_port_sharing: false,
+    }
+}
+
+/// Every file under `root` with its contents; directories are not compared.
+fn files(root: &Path) -> Result<BTreeMap<PathBuf, Vec<u8>>, std::io::Error> {
+    let mut entries = BTreeMap::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else {
+                entries.insert(path.clone(), fs::read(&path)?);
+            }
+        }
+    }
+    Ok(entries)
+}
+
+#[test]
+fn every_writer_rolls_back_a_fresh_write() -> TestResult {
+    for (id, factory) in config_writer_factories() {
+        let writer = factory();
+        let game = tempdir()?;
+
+        let diffs = writer.write_config(game.path(), &config())?;
+        assert!(writer.validate_config(game.path())?, "{id} did not apply");
+        writer.rollback_config(game.path(), &diffs)?;
+
+        assert!(
+            !writer.validate_config(game.path())?,
+            "{id} still validates"
+        );
+        assert_eq!(files(game.path())?, BTreeMap::new(), "{id} left files");
+    }
+    Ok(())
+}
+
+#[test]
+fn whole_file_rollback_restores_the_previous_bytes() -> TestResult {
+    let game = tempdir()?;
+    let path = game
+        .path()
+        .join("Documents/Assetto Corsa Competizione/Config/broadcasting.json");
+    fs::create_dir_all(path.parent().ok_or("no parent")?)?;
+    let original = "{\n  \"connectionPassword\": \"secret\",\n  \"updateRateHz\": 10\n}";
+    fs::write(&path, original)?;
+
+    let diffs = ACCConfigWriter.write_config(game.path(), &config())?;
+    assert!(ACCConfigWriter.validate_config(game.path())?);
+    ACCConfigWriter.rollback_config(game.path(), &diffs)?;
+
+    assert_eq!(fs::read_to_string(&path)?, original);
+    assert!(!ACCConfigWriter.validate_config(game.path())?);
+    Ok(())
+}
+
+#[test]
+fn ini_rollback_reverts_only_its_keys() -> TestResult {
+    let game = tempdir()?;
+    let path = game.path().join("Documents/iRacing/app.ini");
+    fs::create_dir_all(path.parent().ok_or("no parent")?)?;
+    let original = "[Graphics]\nfullscreen=1\n\n[Telemetry]\ntelemetryDiskFile=0\nmaxMB=512\n\n[Audio]\nvolume=3\n";
+    fs::write(&path, original)?;
+
+    let diffs = IRacingConfigWriter.write_config(game.path(), &config())?;
+    assert!(IRacingConfigWriter.validate_config(game.path())?);
+
+    // The user changes an unrelated setting before the rollback.
+    let edited = fs::read_to_string(&path)?.replace("volume=3", "volume=7");
+    fs::write(&path, edited)?;
+    IRacingConfigWriter.rollback_config(game.path(), &diffs)?;
+
+    assert_eq!(
+        fs::read_to_string(&path)?,
+        original.replace("volume=3", "volume=7")
+    );
+    assert!(!IRacingConfigWriter.validate_config(game.path())?);
+    Ok(())
+}
+
+#[test]
+fn ini_rollback_removes_a_section_it_appended() -> TestResult {
+    let game = tempdir()?;
+    let path = game.path().join("Documents/iRacing/app.ini");
+    fs::create_dir_all(path.parent().ok_or("no parent")?)?;
+    let original = "[Graphics]\nfullscreen=1\n";
+    fs::write(&path, original)?;
+
+    let diffs = IRacingConfigWriter.write_config(game.path(), &config())?;
+    IRacingConfigWriter.rollback_config(game.path(), &diffs)?;
+
+    assert_eq!(fs::read_to_string(&path)?, original);
+    Ok(())
+}
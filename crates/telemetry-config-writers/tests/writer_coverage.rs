@@ -20,6 +20,7 @@ fn default_config() -> TelemetryConfig {
         output_target: "127.0.0.1:20777".to_string(),
         fields: vec!["ffb_scalar".to_string(), "rpm".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     }
 }
 
@@ -84,6 +85,7 @@ fn matrix_derived_config(game: &GameSupport) -> TelemetryConfig {
             .unwrap_or_else(|| "127.0.0.1:20777".to_string()),
         fields,
         enable_high_rate_iracing_360hz: game.telemetry.supports_360hz_option,
+        allow_port_sharing: false,
     }
 }
 
@@ -447,8 +449,12 @@ fn overwrite_produces_modify_operation() -> TestResult {
     let diffs1 = writer.write_config(temp_dir.path(), &config)?;
     assert_eq!(diffs1[0].operation, DiffOperation::Add);
 
-    // Second write → Modify
-    let diffs2 = writer.write_config(temp_dir.path(), &config)?;
+    // Second write with another port → Modify
+    let moved = TelemetryConfig {
+        output_target: "127.0.0.1:20778".to_string(),
+        ..config
+    };
+    let diffs2 = writer.write_config(temp_dir.path(), &moved)?;
     assert_eq!(diffs2[0].operation, DiffOperation::Modify);
     assert!(diffs2[0].old_value.is_some());
     Ok(())
@@ -498,6 +504,8 @@ fn config_diff_debug_is_not_empty() -> TestResult {
         old_value: None,
         new_value: "val".to_string(),
         operation: DiffOperation::Add,
+        backup_path: None,
+        details: Vec::new(),
     };
     let debug = format!("{diff:?}");
     assert!(debug.contains("ConfigDiff"));
@@ -517,6 +525,7 @@ fn disabled_config_still_writes() -> TestResult {
         output_target: "".to_string(),
         fields: vec![],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
     let diffs = writer.write_config(temp_dir.path(), &config)?;
     assert!(!diffs.is_empty());
@@ -534,6 +543,7 @@ fn empty_fields_config_writes_successfully() -> TestResult {
         output_target: "127.0.0.1:9000".to_string(),
         fields: vec![],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
     let diffs = writer.write_config(temp_dir.path(), &config)?;
     assert!(!diffs.is_empty());
@@ -0,0 +1,82 @@
+//! Setup metadata every config writer reports.
+
+use racing_wheel_telemetry_config_writers::{config_writer_factories, writer_metadata_registry};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+#[test]
+fn every_factory_reports_metadata_for_its_own_game() -> TestResult {
+    for (id, factory) in config_writer_factories() {
+        let metadata = factory().metadata();
+        assert!(!metadata.game_id.is_empty(), "{id}");
+        assert_eq!(metadata.game_id, *id);
+        assert!(
+            metadata
+                .manual_steps
+                .iter()
+                .all(|step| !step.trim().is_empty()),
+            "{id} has a blank manual step"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn registry_covers_every_factory_keyed_by_game_id() -> TestResult {
+    let registry = writer_metadata_registry();
+    assert_eq!(registry.len(), config_writer_factories().len());
+    for (key, metadata) in &registry {
+        assert_eq!(metadata.game_id, *key);
+    }
+    Ok(())
+}
+
+#[test]
+fn setup_requirements_match_what_each_game_needs() -> TestResult {
+    let registry = writer_metadata_registry();
+    let get = |id: &str| {
+        registry
+            .get(id)
+            .ok_or(format!("{id} missing from registry"))
+    };
+
+    let iracing = get("iracing")?;
+    assert!(iracing.requires_game_restart);
+    assert!(iracing.native_config_modified);
+    assert!(iracing.manual_steps.is_empty());
+
+    let ams2 = get("ams2")?;
+    assert!(ams2.native_config_modified);
+    assert!(
+        ams2.manual_steps
+            .iter()
+            .any(|step| step.contains("Enable Project CARS 2 shared memory"))
+    );
+
+    let rbr = get("rbr")?;
+    assert!(!rbr.native_config_modified);
+    assert!(!rbr.requires_game_restart);
+    assert!(
+        rbr.manual_steps
+            .iter()
+            .any(|step| step.contains("Install RSF Rallysimfans plugin"))
+    );
+
+    assert!(
+        get("rfactor2")?
+            .manual_steps
+            .iter()
+            .any(|step| step.contains("plugin"))
+    );
+    Ok(())
+}
+
+#[test]
+fn only_native_writers_ask_for_a_restart() -> TestResult {
+    for metadata in writer_metadata_registry().values() {
+        if metadata.requires_game_restart {
+            assert!(metadata.native_config_modified, "{}", metadata.game_id);
+        }
+    }
+    Ok(())
+}
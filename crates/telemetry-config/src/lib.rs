@@ -10,9 +10,10 @@ pub mod support;
 pub mod writers;
 
 pub use support::{
-    AutoDetectConfig, GameSupport, GameSupportMatrix, GameSupportStatus, GameVersion,
-    TELEMETRY_SUPPORT_MATRIX_YAML, TelemetryFieldMapping, TelemetrySupport, load_default_matrix,
-    matrix_game_id_set, matrix_game_ids, normalize_game_id,
+    AutoDetectConfig, GAME_ID_MIGRATIONS, GameIdMigration, GameSupport, GameSupportMatrix,
+    GameSupportStatus, GameTimeSemantics, GameVersion, TELEMETRY_SUPPORT_MATRIX_YAML,
+    TelemetryFieldMapping, TelemetrySupport, load_default_matrix, matrix_game_id_set,
+    matrix_game_ids, normalize_game_id,
 };
 pub use writers::{
     ACCConfigWriter, ACRallyConfigWriter, AMS2ConfigWriter, AssettoCorsaConfigWriter,
@@ -20,6 +21,6 @@ pub use writers::{
     Dirt4ConfigWriter, Dirt5ConfigWriter, DirtRally2ConfigWriter, EAWRCConfigWriter,
     F1_25ConfigWriter, F1ConfigWriter, F1ManagerConfigWriter, ForzaMotorsportConfigWriter,
     GranTurismo7ConfigWriter, GranTurismo7SportsConfigWriter, IRacingConfigWriter,
-    Nascar21ConfigWriter, RBRConfigWriter, RFactor2ConfigWriter, TelemetryConfig,
-    WrcGenerationsConfigWriter, config_writer_factories,
+    Nascar21ConfigWriter, RBRConfigWriter, RFactor2ConfigWriter, SurvivalExpectation,
+    TelemetryConfig, WrcGenerationsConfigWriter, config_writer_factories,
 };
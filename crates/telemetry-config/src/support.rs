@@ -74,6 +74,24 @@ pub struct TelemetrySupport {
     pub output_target: Option<String>,
     /// Validated field mapping indicating which semantic values the game emits.
     pub fields: TelemetryFieldMapping,
+    /// What the game-side clock reported by the adapter counts.
+    #[serde(default)]
+    pub game_time: GameTimeSemantics,
+}
+
+/// What the adapter's `game_time_s` / `game_tick` values count.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum GameTimeSemantics {
+    /// The game reports no clock; consumers fall back to arrival time.
+    #[default]
+    Unavailable,
+    /// Seconds elapsed since the session started; pauses stop the clock.
+    SessionElapsed,
+    /// In-game time of day; may jump or run accelerated.
+    TimeOfDay,
+    /// Only a frame or packet counter is available.
+    TickOnly,
 }
 
 /// Mapping of normalized telemetry fields to game-specific fields.
@@ -108,23 +126,59 @@ pub struct AutoDetectConfig {
     pub install_paths: Vec<String>,
 }
 
+/// A renamed game integration.
+///
+/// Callers and persisted data that still use `old` resolve to `new` through
+/// [`normalize_game_id`]; `migrate_persisted_data` in the orchestrator rewrites
+/// stored ids offline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameIdMigration {
+    /// Retired game id.
+    pub old: &'static str,
+    /// Canonical game id replacing it.
+    pub new: &'static str,
+    /// Contract version from which `new` is the canonical id.
+    pub since: &'static str,
+}
+
+impl GameIdMigration {
+    /// Whether `game_id` names the retired id, ignoring ASCII case and treating
+    /// `_`, `-` and spaces as the same separator.
+    pub fn matches(&self, game_id: &str) -> bool {
+        let canonical = |c: char| match c {
+            '-' | ' ' => '_',
+            c => c.to_ascii_lowercase(),
+        };
+        game_id.len() == self.old.len()
+            && game_id
+                .chars()
+                .map(canonical)
+                .eq(self.old.chars().map(canonical))
+    }
+}
+
+/// Game id renames, oldest first. Add a row here when an integration is renamed.
+pub const GAME_ID_MIGRATIONS: &[GameIdMigration] = &[
+    GameIdMigration {
+        old: "ea_wrc",
+        new: "eawrc",
+        since: "0.1.0",
+    },
+    // f1_2025 is an alias for the native EA protocol adapter (f1_25)
+    GameIdMigration {
+        old: "f1_2025",
+        new: "f1_25",
+        since: "0.1.0",
+    },
+];
+
 /// Normalize game IDs at the boundary (historical alias support).
 pub fn normalize_game_id(game_id: &str) -> &str {
     let game_id = game_id.trim();
-
-    if game_id.eq_ignore_ascii_case("ea_wrc")
-        || game_id.eq_ignore_ascii_case("ea-wrc")
-        || game_id.eq_ignore_ascii_case("ea wrc")
-    {
-        "eawrc"
-    } else if game_id.eq_ignore_ascii_case("f1_2025")
-        || game_id.eq_ignore_ascii_case("f1-2025")
-        || game_id.eq_ignore_ascii_case("f1 2025")
-    {
-        "f1_25"
-    } else {
-        game_id
-    }
+    GAME_ID_MIGRATIONS
+        .iter()
+        .find(|migration| migration.matches(game_id))
+        .map_or(game_id, |migration| migration.new)
 }
 
 /// Load the canonical game support matrix.
@@ -599,6 +653,7 @@ mod tests {
                 car_id: None,
                 track_id: None,
             },
+            game_time: GameTimeSemantics::SessionElapsed,
         };
         let json = serde_json::to_string(&support)?;
         let decoded: TelemetrySupport = serde_json::from_str(&json)?;
@@ -35,6 +35,7 @@ mod tests {
             output_target: "127.0.0.1:9999".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let json = serde_json::to_string(&config)?;
         let decoded: TelemetryConfig = serde_json::from_str(&json)?;
@@ -58,6 +59,7 @@ mod tests {
                 "speed_ms".to_string(),
             ],
             enable_high_rate_iracing_360hz: true,
+            allow_port_sharing: false,
         };
         let yaml_str = serde_yaml::to_string(&config)?;
         let decoded: TelemetryConfig = serde_yaml::from_str(&yaml_str)?;
@@ -91,6 +93,7 @@ mod tests {
                 "track_id".to_string(),
             ],
             enable_high_rate_iracing_360hz: true,
+            allow_port_sharing: false,
         };
         let json = serde_json::to_string(&config)?;
         let decoded: TelemetryConfig = serde_json::from_str(&json)?;
@@ -112,6 +115,7 @@ mod tests {
             output_target: String::new(),
             fields: vec![],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let json = serde_json::to_string(&config)?;
         let decoded: TelemetryConfig = serde_json::from_str(&json)?;
@@ -144,6 +148,8 @@ mod tests {
             old_value: Some("0".to_string()),
             new_value: "1".to_string(),
             operation: DiffOperation::Modify,
+            backup_path: None,
+            details: Vec::new(),
         };
         let json = serde_json::to_string(&diff)?;
         let decoded: ConfigDiff = serde_json::from_str(&json)?;
@@ -160,6 +166,8 @@ mod tests {
             old_value: None,
             new_value: "true".to_string(),
             operation: DiffOperation::Add,
+            backup_path: None,
+            details: Vec::new(),
         };
         let json = serde_json::to_string(&diff)?;
         let decoded: ConfigDiff = serde_json::from_str(&json)?;
@@ -178,6 +186,8 @@ mod tests {
             old_value: Some("8080".to_string()),
             new_value: String::new(),
             operation: DiffOperation::Remove,
+            backup_path: None,
+            details: Vec::new(),
         };
         let json = serde_json::to_string(&diff)?;
         let decoded: ConfigDiff = serde_json::from_str(&json)?;
@@ -249,6 +259,7 @@ mod tests {
             output_target: "[::1]:9999".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let json = serde_json::to_string(&config)?;
         let decoded: TelemetryConfig = serde_json::from_str(&json)?;
@@ -265,6 +276,7 @@ mod tests {
             output_target: "127.0.0.1:12345".to_string(),
             fields: vec!["ffb_scalar".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let cloned = config.clone();
         assert_eq!(cloned.enabled, config.enabled);
@@ -283,6 +295,8 @@ mod tests {
             old_value: None,
             new_value: "v".to_string(),
             operation: DiffOperation::Add,
+            backup_path: None,
+            details: Vec::new(),
         };
         let diff2 = diff1.clone();
         assert_eq!(diff1, diff2);
@@ -297,6 +311,8 @@ mod tests {
             old_value: None,
             new_value: "v".to_string(),
             operation: DiffOperation::Add,
+            backup_path: None,
+            details: Vec::new(),
         };
         let diff2 = ConfigDiff {
             operation: DiffOperation::Modify,
@@ -178,6 +178,7 @@ mod config_validation {
             output_target: target.to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         }
     }
 
@@ -281,6 +282,7 @@ mod config_merge {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let overlay_json = r#"{
             "enabled": true,
@@ -486,6 +488,7 @@ mod config_hot_reload {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let original_json = serde_json::to_string(&original)?;
 
@@ -516,6 +519,7 @@ mod config_hot_reload {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let json = serde_json::to_string_pretty(&cfg)?;
         std::fs::write(&path, &json)?;
@@ -553,6 +557,7 @@ mod config_hot_reload {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let yaml = serde_yaml::to_string(&cfg)?;
         std::fs::write(&path, &yaml)?;
@@ -659,6 +664,8 @@ mod snapshot_tests {
                 old_value: Some("0".to_string()),
                 new_value: "1".to_string(),
                 operation: DiffOperation::Modify,
+                backup_path: None,
+                details: Vec::new(),
             },
             ConfigDiff {
                 file_path: "app.ini".to_string(),
@@ -667,6 +674,8 @@ mod snapshot_tests {
                 old_value: None,
                 new_value: "value".to_string(),
                 operation: DiffOperation::Add,
+                backup_path: None,
+                details: Vec::new(),
             },
         ];
         let json = serde_json::to_string(&diffs)?;
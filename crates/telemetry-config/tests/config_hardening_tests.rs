@@ -60,6 +60,7 @@ fn telemetry_config_yaml_round_trip() -> TestResult {
         output_target: "127.0.0.1:20778".to_string(),
         fields: vec!["ffb_scalar".to_string(), "rpm".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
     let yaml = serde_yaml::to_string(&config)?;
     let decoded: TelemetryConfig = serde_yaml::from_str(&yaml)?;
@@ -127,6 +128,7 @@ fn config_writer_factories_all_produce_valid_writers() {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let diffs_result = writer.get_expected_diffs(&config);
         assert!(
@@ -191,6 +193,8 @@ fn config_diff_add_has_no_old_value() {
         old_value: None,
         new_value: "true".to_string(),
         operation: DiffOperation::Add,
+        backup_path: None,
+        details: Vec::new(),
     };
     assert!(diff.old_value.is_none());
     assert_eq!(diff.operation, DiffOperation::Add);
@@ -205,6 +209,8 @@ fn config_diff_modify_has_old_and_new_values() {
         old_value: Some("0".to_string()),
         new_value: "1".to_string(),
         operation: DiffOperation::Modify,
+        backup_path: None,
+        details: Vec::new(),
     };
     assert_eq!(diff.old_value, Some("0".to_string()));
     assert_eq!(diff.new_value, "1");
@@ -220,6 +226,8 @@ fn config_diff_remove_preserves_old_value() {
         old_value: Some("8080".to_string()),
         new_value: String::new(),
         operation: DiffOperation::Remove,
+        backup_path: None,
+        details: Vec::new(),
     };
     assert_eq!(diff.old_value, Some("8080".to_string()));
     assert!(diff.new_value.is_empty());
@@ -247,6 +255,8 @@ fn config_diff_json_round_trip_all_operations() -> TestResult {
                 "new".to_string()
             },
             operation: op.clone(),
+            backup_path: None,
+            details: Vec::new(),
         };
         let json = serde_json::to_string(&diff)?;
         let decoded: ConfigDiff = serde_json::from_str(&json)?;
@@ -264,6 +274,8 @@ fn config_diff_clone_equality() {
         old_value: None,
         new_value: "v".to_string(),
         operation: DiffOperation::Add,
+        backup_path: None,
+        details: Vec::new(),
     };
     let cloned = diff.clone();
     assert_eq!(diff, cloned);
@@ -278,6 +290,8 @@ fn config_diff_inequality_on_different_keys() {
         old_value: None,
         new_value: "v".to_string(),
         operation: DiffOperation::Add,
+        backup_path: None,
+        details: Vec::new(),
     };
     let diff2 = ConfigDiff {
         file_path: "a.ini".to_string(),
@@ -286,6 +300,8 @@ fn config_diff_inequality_on_different_keys() {
         old_value: None,
         new_value: "v".to_string(),
         operation: DiffOperation::Add,
+        backup_path: None,
+        details: Vec::new(),
     };
     assert_ne!(diff1, diff2);
 }
@@ -301,6 +317,7 @@ fn each_writer_generates_nonempty_expected_diffs() -> TestResult {
         output_target: "127.0.0.1:20777".to_string(),
         fields: vec!["rpm".to_string(), "speed_ms".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
     for (id, factory) in config_writer_factories() {
         let writer = factory();
@@ -322,6 +339,7 @@ fn iracing_writer_generates_telemetry_section_diff() -> TestResult {
         output_target: "127.0.0.1:20777".to_string(),
         fields: vec!["rpm".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
     let factories = config_writer_factories();
     let (_, iracing_factory) = factories
@@ -346,6 +364,7 @@ fn iracing_360hz_produces_extra_diff() -> TestResult {
         output_target: "127.0.0.1:20777".to_string(),
         fields: vec![],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
     let config_360 = TelemetryConfig {
         enabled: true,
@@ -354,6 +373,7 @@ fn iracing_360hz_produces_extra_diff() -> TestResult {
         output_target: "127.0.0.1:20777".to_string(),
         fields: vec![],
         enable_high_rate_iracing_360hz: true,
+        allow_port_sharing: false,
     };
     let factories = config_writer_factories();
     let (_, iracing_factory) = factories
@@ -379,6 +399,7 @@ fn acc_writer_generates_broadcasting_json_diff() -> TestResult {
         output_target: "127.0.0.1:9000".to_string(),
         fields: vec![],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
     let factories = config_writer_factories();
     let (_, acc_factory) = factories
@@ -401,6 +422,7 @@ fn iracing_writer_write_config_creates_files() -> TestResult {
         output_target: "127.0.0.1:20777".to_string(),
         fields: vec!["rpm".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
     let factories = config_writer_factories();
     let (_, iracing_factory) = factories
@@ -426,6 +448,7 @@ fn iracing_writer_validate_after_write() -> TestResult {
         output_target: "127.0.0.1:20777".to_string(),
         fields: vec![],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
     let factories = config_writer_factories();
     let (_, iracing_factory) = factories
@@ -545,6 +568,7 @@ fn all_expected_diffs_have_nonempty_file_path() -> TestResult {
         output_target: "127.0.0.1:20777".to_string(),
         fields: vec![],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
     for (id, factory) in config_writer_factories() {
         let writer = factory();
@@ -572,13 +596,17 @@ fn diff_operations_are_valid_enum_variants() -> TestResult {
         output_target: "127.0.0.1:20777".to_string(),
         fields: vec![],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
     for (id, factory) in config_writer_factories() {
         let writer = factory();
         let diffs = writer.get_expected_diffs(&config)?;
         for diff in &diffs {
             match diff.operation {
-                DiffOperation::Add | DiffOperation::Modify | DiffOperation::Remove => {}
+                DiffOperation::Add
+                | DiffOperation::Modify
+                | DiffOperation::Remove
+                | DiffOperation::NoChange => {}
             }
             // Verify it's serializable
             let json = serde_json::to_string(&diff.operation)?;
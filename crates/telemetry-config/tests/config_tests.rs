@@ -7,9 +7,9 @@ use std::collections::HashSet;
 
 use racing_wheel_telemetry_config::{
     AutoDetectConfig, ConfigDiff, ConfigWriter, DiffOperation, GameSupport, GameSupportMatrix,
-    GameSupportStatus, GameVersion, TELEMETRY_SUPPORT_MATRIX_YAML, TelemetryConfig,
-    TelemetryFieldMapping, TelemetrySupport, config_writer_factories, load_default_matrix,
-    matrix_game_id_set, matrix_game_ids, normalize_game_id,
+    GameSupportStatus, GameTimeSemantics, GameVersion, TELEMETRY_SUPPORT_MATRIX_YAML,
+    TelemetryConfig, TelemetryFieldMapping, TelemetrySupport, config_writer_factories,
+    load_default_matrix, matrix_game_id_set, matrix_game_ids, normalize_game_id,
 };
 
 type TestResult = Result<(), Box<dyn std::error::Error>>;
@@ -396,6 +396,7 @@ fn telemetry_config_json_round_trip() -> TestResult {
         output_target: "127.0.0.1:9999".to_string(),
         fields: vec!["rpm".to_string(), "speed_ms".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
     let json = serde_json::to_string(&config)?;
     let decoded: TelemetryConfig = serde_json::from_str(&json)?;
@@ -420,6 +421,7 @@ fn telemetry_config_yaml_round_trip() -> TestResult {
         output_target: "127.0.0.1:20778".to_string(),
         fields: vec!["ffb_scalar".to_string(), "gear".to_string()],
         enable_high_rate_iracing_360hz: true,
+        allow_port_sharing: false,
     };
     let yaml_str = serde_yaml::to_string(&config)?;
     let decoded: TelemetryConfig = serde_yaml::from_str(&yaml_str)?;
@@ -530,6 +532,7 @@ fn telemetry_support_round_trip() -> TestResult {
             car_id: None,
             track_id: None,
         },
+        game_time: GameTimeSemantics::SessionElapsed,
     };
     let json = serde_json::to_string(&support)?;
     let decoded: TelemetrySupport = serde_json::from_str(&json)?;
@@ -553,6 +556,8 @@ fn config_diff_json_round_trip() -> TestResult {
         old_value: Some("0".to_string()),
         new_value: "1".to_string(),
         operation: DiffOperation::Modify,
+        backup_path: None,
+        details: Vec::new(),
     };
     let json = serde_json::to_string(&diff)?;
     let decoded: ConfigDiff = serde_json::from_str(&json)?;
@@ -592,6 +597,7 @@ fn telemetry_config_all_fields_preserved_across_json() -> TestResult {
             "track_id".to_string(),
         ],
         enable_high_rate_iracing_360hz: true,
+        allow_port_sharing: false,
     };
     let json = serde_json::to_string(&config)?;
     let decoded: TelemetryConfig = serde_json::from_str(&json)?;
@@ -613,6 +619,7 @@ fn telemetry_config_empty_fields_round_trip() -> TestResult {
         output_target: String::new(),
         fields: vec![],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
     let json = serde_json::to_string(&config)?;
     let decoded: TelemetryConfig = serde_json::from_str(&json)?;
@@ -802,6 +809,7 @@ fn profile_save_and_load_json_file() -> TestResult {
         output_target: "127.0.0.1:20778".to_string(),
         fields: vec!["rpm".to_string(), "gear".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
 
     let json = serde_json::to_string_pretty(&config)?;
@@ -828,6 +836,7 @@ fn profile_save_and_load_yaml_file() -> TestResult {
         output_target: "127.0.0.1:12345".to_string(),
         fields: vec!["ffb_scalar".to_string(), "speed_ms".to_string()],
         enable_high_rate_iracing_360hz: true,
+        allow_port_sharing: false,
     };
 
     let yaml = serde_yaml::to_string(&config)?;
@@ -891,6 +900,7 @@ fn profile_overwrite_preserves_latest_values() -> TestResult {
         output_target: "127.0.0.1:9999".to_string(),
         fields: vec!["rpm".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
     std::fs::write(&path, serde_json::to_string(&config_v1)?)?;
 
@@ -901,6 +911,7 @@ fn profile_overwrite_preserves_latest_values() -> TestResult {
         output_target: "192.168.1.1:5300".to_string(),
         fields: vec!["ffb_scalar".to_string(), "gear".to_string()],
         enable_high_rate_iracing_360hz: true,
+        allow_port_sharing: false,
     };
     std::fs::write(&path, serde_json::to_string(&config_v2)?)?;
 
@@ -1426,6 +1437,7 @@ fn make_test_config(enabled: bool, rate: u32, method: &str, target: &str) -> Tel
         output_target: target.to_string(),
         fields: vec!["rpm".to_string(), "gear".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     }
 }
 
@@ -1451,6 +1463,7 @@ fn iracing_writer_write_and_validate_enabled() -> TestResult {
         output_target: "127.0.0.1:12345".to_string(),
         fields: vec!["rpm".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
 
     let diffs = writer.write_config(dir.path(), &config)?;
@@ -1496,6 +1509,7 @@ fn iracing_writer_with_360hz() -> TestResult {
         output_target: "127.0.0.1:12345".to_string(),
         fields: vec!["rpm".to_string()],
         enable_high_rate_iracing_360hz: true,
+        allow_port_sharing: false,
     };
 
     let diffs = writer.write_config(dir.path(), &config)?;
@@ -1550,6 +1564,7 @@ fn iracing_writer_get_expected_diffs_with_360hz() -> TestResult {
         output_target: "127.0.0.1:12345".to_string(),
         fields: vec![],
         enable_high_rate_iracing_360hz: true,
+        allow_port_sharing: false,
     };
     let diffs = writer.get_expected_diffs(&config)?;
     assert!(
@@ -133,6 +133,7 @@ mod field_range_validation {
             output_target: "127.0.0.1:9999".to_string(),
             fields: many_fields.clone(),
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let json = serde_json::to_string(&cfg)?;
         let decoded: TelemetryConfig = serde_json::from_str(&json)?;
@@ -149,6 +150,7 @@ mod field_range_validation {
             output_target: "127.0.0.1:65535".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let json = serde_json::to_string(&cfg)?;
         let decoded: TelemetryConfig = serde_json::from_str(&json)?;
@@ -374,6 +376,7 @@ mod serde_roundtrip {
                 "gear".to_string(),
             ],
             enable_high_rate_iracing_360hz: true,
+            allow_port_sharing: false,
         }
     }
 
@@ -450,6 +453,8 @@ mod serde_roundtrip {
                 old_value: Some("old".to_string()),
                 new_value: "new".to_string(),
                 operation: op.clone(),
+                backup_path: None,
+                details: Vec::new(),
             };
             let json = serde_json::to_string(&diff)?;
             let decoded: ConfigDiff = serde_json::from_str(&json)?;
@@ -495,6 +500,7 @@ mod config_merging {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string(), "gear".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         }
     }
 
@@ -608,6 +614,7 @@ mod env_var_overrides {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         cfg.update_rate_hz = rate;
         assert_eq!(cfg.update_rate_hz, 240);
@@ -639,6 +646,7 @@ mod env_var_overrides {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         cfg.output_target = env_target.to_string();
         let json = serde_json::to_string(&cfg)?;
@@ -668,6 +676,7 @@ mod env_var_overrides {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         cfg.fields = fields;
         assert_eq!(cfg.fields.len(), 4);
@@ -684,6 +693,7 @@ mod env_var_overrides {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         cfg.output_target = String::new();
         assert!(cfg.output_target.is_empty());
@@ -767,6 +777,7 @@ mod format_detection {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let json = serde_json::to_string_pretty(&cfg)?;
         std::fs::write(&path, &json)?;
@@ -797,6 +808,7 @@ mod format_detection {
             output_target: "127.0.0.1:12345".to_string(),
             fields: vec!["ffb_scalar".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let yaml = serde_yaml::to_string(&cfg)?;
         std::fs::write(&path, &yaml)?;
@@ -833,6 +845,7 @@ mod hot_reload_detection {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let snapshot_1 = serde_json::to_string(&cfg)?;
 
@@ -856,6 +869,7 @@ mod hot_reload_detection {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let snapshot_1 = serde_json::to_string(&cfg)?;
         let snapshot_2 = serde_json::to_string(&cfg)?;
@@ -875,6 +889,7 @@ mod hot_reload_detection {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         std::fs::write(&path, serde_json::to_string(&cfg_v1)?)?;
         let content_v1 = std::fs::read_to_string(&path)?;
@@ -903,6 +918,7 @@ mod hot_reload_detection {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let before = serde_json::to_string(&cfg)?;
 
@@ -957,6 +973,7 @@ mod error_aggregation {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let errors = validate_telemetry_config(&cfg);
         assert!(errors.is_empty(), "expected no errors, got: {:?}", errors);
@@ -971,6 +988,7 @@ mod error_aggregation {
             output_target: String::new(),
             fields: vec![],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let errors = validate_telemetry_config(&cfg);
         assert!(errors.is_empty());
@@ -985,6 +1003,7 @@ mod error_aggregation {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let errors = validate_telemetry_config(&cfg);
         assert_eq!(errors.len(), 1);
@@ -1000,6 +1019,7 @@ mod error_aggregation {
             output_target: String::new(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let errors = validate_telemetry_config(&cfg);
         assert!(errors.iter().any(|e| e.contains("output_target")));
@@ -1014,6 +1034,7 @@ mod error_aggregation {
             output_target: "local".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: true,
+            allow_port_sharing: false,
         };
         let errors = validate_telemetry_config(&cfg);
         assert!(errors.iter().any(|e| e.contains("360")));
@@ -1028,6 +1049,7 @@ mod error_aggregation {
             output_target: String::new(),
             fields: vec![],
             enable_high_rate_iracing_360hz: true,
+            allow_port_sharing: false,
         };
         let errors = validate_telemetry_config(&cfg);
         assert!(
@@ -1047,6 +1069,7 @@ mod error_aggregation {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let errors = validate_telemetry_config(&cfg);
         assert!(errors.iter().any(|e| e.contains("exceeds")));
@@ -1164,6 +1187,7 @@ mod per_game_overrides {
             output_target: "127.0.0.1:12345".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let factories = config_writer_factories();
         let (_, factory) = factories
@@ -1187,6 +1211,7 @@ mod per_game_overrides {
             output_target: "127.0.0.1:12345".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: true,
+            allow_port_sharing: false,
         };
         let factories = config_writer_factories();
         let (_, factory) = factories
@@ -1216,6 +1241,7 @@ mod per_game_overrides {
             output_target: "127.0.0.1:12345".to_string(),
             fields: vec![],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let factories = config_writer_factories();
         let (_, factory) = factories
@@ -1242,6 +1268,7 @@ mod per_game_overrides {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         for (id, factory) in config_writer_factories() {
             let writer = factory();
@@ -1335,6 +1362,7 @@ mod per_device_overrides {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let device = DeviceOverride {
             device_id: "fanatec_dd1".to_string(),
@@ -1358,6 +1386,7 @@ mod per_device_overrides {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let device = DeviceOverride {
             device_id: "moza_r9".to_string(),
@@ -1380,6 +1409,7 @@ mod per_device_overrides {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let device = DeviceOverride {
             device_id: "generic".to_string(),
@@ -1406,6 +1436,7 @@ mod per_device_overrides {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let overrides = vec![
             DeviceOverride {
@@ -1439,6 +1470,7 @@ mod per_device_overrides {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let device = DeviceOverride {
             device_id: "simucube_2_pro".to_string(),
@@ -1472,6 +1504,7 @@ mod per_device_overrides {
                 .ok_or("no output_target for iracing")?,
             fields: vec!["rpm".to_string(), "ffb_scalar".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
 
         // Device override for a high-end wheel
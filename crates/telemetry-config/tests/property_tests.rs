@@ -43,6 +43,7 @@ proptest! {
             output_target: target.clone(),
             fields: fields.clone(),
             enable_high_rate_iracing_360hz: high_rate,
+            allow_port_sharing: false,
         };
         let json = serde_json::to_string(&config)
             .map_err(|e| TestCaseError::fail(format!("serialize: {e}")))?;
@@ -70,6 +71,7 @@ proptest! {
             output_target: "127.0.0.1:9999".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: high_rate,
+            allow_port_sharing: false,
         };
         let yaml = serde_yaml::to_string(&config)
             .map_err(|e| TestCaseError::fail(format!("serialize: {e}")))?;
@@ -97,6 +99,8 @@ proptest! {
             old_value: if has_old { Some("old".to_string()) } else { None },
             new_value: new_value.clone(),
             operation: op.clone(),
+            backup_path: None,
+            details: Vec::new(),
         };
         let json = serde_json::to_string(&diff)
             .map_err(|e| TestCaseError::fail(format!("serialize: {e}")))?;
@@ -164,6 +168,7 @@ fn profile_merge_override_rate() -> Result<(), Box<dyn std::error::Error>> {
         output_target: "127.0.0.1:9999".to_string(),
         fields: vec!["rpm".to_string(), "speed_ms".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
     // Simulate merging by overriding specific fields
     let override_json = r#"{
@@ -193,6 +198,7 @@ fn profile_merge_add_fields() -> Result<(), Box<dyn std::error::Error>> {
         output_target: "127.0.0.1:9999".to_string(),
         fields: base_fields.clone(),
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
     assert_eq!(config.fields.len(), 3);
     assert!(config.fields.contains(&"rpm".to_string()));
@@ -240,6 +246,7 @@ fn edge_empty_config_round_trips() -> Result<(), Box<dyn std::error::Error>> {
         output_target: String::new(),
         fields: vec![],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
     let json = serde_json::to_string(&config)?;
     let decoded: TelemetryConfig = serde_json::from_str(&json)?;
@@ -260,6 +267,7 @@ fn edge_conflicting_360hz_without_iracing() -> Result<(), Box<dyn std::error::Er
         output_target: "127.0.0.1:20777".to_string(),
         fields: vec!["rpm".to_string()],
         enable_high_rate_iracing_360hz: true, // conflicting: not iRacing
+        allow_port_sharing: false,
     };
     // Should still serialize/deserialize without error
     let json = serde_json::to_string(&config)?;
@@ -277,6 +285,7 @@ fn edge_very_high_update_rate() -> Result<(), Box<dyn std::error::Error>> {
         output_target: "local".to_string(),
         fields: vec!["ffb_scalar".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
     let json = serde_json::to_string(&config)?;
     let decoded: TelemetryConfig = serde_json::from_str(&json)?;
@@ -293,6 +302,7 @@ fn edge_duplicate_fields() -> Result<(), Box<dyn std::error::Error>> {
         output_target: "127.0.0.1:9999".to_string(),
         fields: vec!["rpm".to_string(), "rpm".to_string(), "rpm".to_string()],
         enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
     };
     let json = serde_json::to_string(&config)?;
     let decoded: TelemetryConfig = serde_json::from_str(&json)?;
@@ -334,6 +344,8 @@ fn edge_config_diff_empty_new_value() -> Result<(), Box<dyn std::error::Error>>
         old_value: Some("oldVal".to_string()),
         new_value: String::new(),
         operation: DiffOperation::Remove,
+        backup_path: None,
+        details: Vec::new(),
     };
     let json = serde_json::to_string(&diff)?;
     let decoded: ConfigDiff = serde_json::from_str(&json)?;
@@ -84,6 +84,7 @@ enable_high_rate_iracing_360hz: false
             output_target: "10.0.0.1:5050".to_string(),
             fields: vec!["rpm".to_string(), "gear".to_string()],
             enable_high_rate_iracing_360hz: true,
+            allow_port_sharing: false,
         };
         let json = serde_json::to_string(&cfg)?;
         let from_json: TelemetryConfig = serde_json::from_str(&json)?;
@@ -171,6 +172,8 @@ enable_high_rate_iracing_360hz: false
                 old_value: Some("old".to_string()),
                 new_value: "new".to_string(),
                 operation: op,
+                backup_path: None,
+                details: Vec::new(),
             };
             let json = serde_json::to_string(&diff)?;
             let decoded: ConfigDiff = serde_json::from_str(&json)?;
@@ -221,6 +224,7 @@ enable_high_rate_iracing_360hz: false
                 "speed_ms".to_string(),
             ],
             enable_high_rate_iracing_360hz: true,
+            allow_port_sharing: false,
         };
         let cloned = cfg.clone();
         assert_eq!(cloned.enabled, cfg.enabled);
@@ -244,6 +248,7 @@ enable_high_rate_iracing_360hz: false
             output_target: "127.0.0.1:20777".to_string(),
             fields: fields.clone(),
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let json = serde_json::to_string(&cfg)?;
         let decoded: TelemetryConfig = serde_json::from_str(&json)?;
@@ -538,6 +543,7 @@ mod rate_limiting_config {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let json = serde_json::to_string(&cfg)?;
         let from_json: TelemetryConfig = serde_json::from_str(&json)?;
@@ -579,6 +585,7 @@ mod output_target_config {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let json = serde_json::to_string(&cfg)?;
         let decoded: TelemetryConfig = serde_json::from_str(&json)?;
@@ -595,6 +602,7 @@ mod output_target_config {
             output_target: "[::1]:9999".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let json = serde_json::to_string(&cfg)?;
         let decoded: TelemetryConfig = serde_json::from_str(&json)?;
@@ -611,6 +619,7 @@ mod output_target_config {
             output_target: "192.168.1.255:5050".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let json = serde_json::to_string(&cfg)?;
         let decoded: TelemetryConfig = serde_json::from_str(&json)?;
@@ -628,6 +637,7 @@ mod output_target_config {
             output_target: String::new(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let json = serde_json::to_string(&cfg)?;
         let decoded: TelemetryConfig = serde_json::from_str(&json)?;
@@ -644,6 +654,7 @@ mod output_target_config {
             output_target: "127.0.0.1:65535".to_string(),
             fields: vec![],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let json = serde_json::to_string(&cfg)?;
         let decoded: TelemetryConfig = serde_json::from_str(&json)?;
@@ -662,6 +673,7 @@ mod output_target_config {
             output_target: "127.0.0.1:1".to_string(),
             fields: vec![],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let json = serde_json::to_string(&cfg)?;
         let decoded: TelemetryConfig = serde_json::from_str(&json)?;
@@ -708,6 +720,7 @@ mod output_target_config {
             output_target: "10.0.0.50:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let json = serde_json::to_string(&cfg)?;
         let decoded: TelemetryConfig = serde_json::from_str(&json)?;
@@ -725,6 +738,7 @@ mod output_target_config {
                 output_target: "127.0.0.1:20777".to_string(),
                 fields: vec![],
                 enable_high_rate_iracing_360hz: false,
+                allow_port_sharing: false,
             };
             let yaml = serde_yaml::to_string(&cfg)?;
             let decoded: TelemetryConfig = serde_yaml::from_str(&yaml)?;
@@ -988,6 +1002,8 @@ mod config_validation_defaults {
             old_value: None,
             new_value: "v".to_string(),
             operation: DiffOperation::Add,
+            backup_path: None,
+            details: Vec::new(),
         };
         let diff2 = diff1.clone();
         assert_eq!(diff1, diff2);
@@ -1064,6 +1080,8 @@ mod config_validation_defaults {
                 old_value: Some("0".to_string()),
                 new_value: "1".to_string(),
                 operation: DiffOperation::Modify,
+                backup_path: None,
+                details: Vec::new(),
             },
             ConfigDiff {
                 file_path: "app.ini".to_string(),
@@ -1072,6 +1090,8 @@ mod config_validation_defaults {
                 old_value: None,
                 new_value: "value".to_string(),
                 operation: DiffOperation::Add,
+                backup_path: None,
+                details: Vec::new(),
             },
         ];
         let json = serde_json::to_string(&diffs)?;
@@ -1094,6 +1114,7 @@ mod config_validation_defaults {
             output_target: "127.0.0.1:20777".to_string(),
             fields: vec!["rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         let json = serde_json::to_string_pretty(&cfg)?;
         std::fs::write(&path, &json)?;
@@ -1127,6 +1148,7 @@ mod config_validation_defaults {
             output_target: "127.0.0.1:12345".to_string(),
             fields: vec!["ffb_scalar".to_string(), "rpm".to_string()],
             enable_high_rate_iracing_360hz: false,
+            allow_port_sharing: false,
         };
         std::fs::write(&path, serde_yaml::to_string(&cfg)?)?;
 
@@ -0,0 +1,348 @@
+//! Compact binary encoding for telemetry frames.
+//!
+//! Enabled by the `binary` feature. JSON stays the interchange format; this
+//! codec is for long captures where file size and write cost matter.
+//!
+//! Every encoding starts with [`BINARY_MAGIC`] and the little-endian `u32`
+//! schema version the frames were laid out with. A single frame
+//! ([`encode_frame`]) follows the prefix directly. A stream ([`FrameWriter`])
+//! follows it with records, each a little-endian `u32` byte length and then
+//! the payload, so a file can be appended to and read back one record at a
+//! time.
+//!
+//! Unlike JSON, the binary layout is positional: a frame can only be decoded
+//! by a build with the same [`TELEMETRY_SCHEMA_VERSION`]. Frames from another
+//! version are rejected rather than misread; convert them through JSON, where
+//! [`crate::migration`] can upgrade them.
+
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::marker::PhantomData;
+
+use bincode::{Decode, Encode};
+
+use crate::{TELEMETRY_SCHEMA_VERSION, TelemetryFrame};
+
+/// Leading bytes of every binary frame or frame stream.
+pub const BINARY_MAGIC: [u8; 4] = *b"ORTF";
+
+/// Length of the magic and version prefix.
+pub const BINARY_HEADER_LEN: usize = 8;
+
+/// Largest record a [`FrameReader`] accepts; a longer length prefix is
+/// treated as corruption rather than allocated.
+pub const MAX_RECORD_BYTES: usize = 16 * 1024 * 1024;
+
+/// Why a binary frame or stream could not be written or read.
+#[derive(Debug)]
+pub enum BinaryCodecError {
+    /// The input does not start with [`BINARY_MAGIC`].
+    BadMagic,
+    /// The input was laid out by a build with a different schema version.
+    UnsupportedVersion { found: u32, supported: u32 },
+    /// The input ended before a header, length prefix or record was complete.
+    Truncated { expected: usize, available: usize },
+    /// A length prefix exceeds [`MAX_RECORD_BYTES`].
+    RecordTooLarge { len: usize, max: usize },
+    /// A record decoded without using all of its bytes.
+    TrailingBytes(usize),
+    /// The frame could not be encoded.
+    Encode(bincode::error::EncodeError),
+    /// A record's payload is not a valid frame.
+    Decode(bincode::error::DecodeError),
+    /// The underlying reader or writer failed.
+    Io(io::Error),
+}
+
+impl fmt::Display for BinaryCodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BadMagic => f.write_str("not a binary telemetry frame (bad magic)"),
+            Self::UnsupportedVersion { found, supported } => write!(
+                f,
+                "binary telemetry uses schema version {found}, this build reads version \
+                 {supported}"
+            ),
+            Self::Truncated {
+                expected,
+                available,
+            } => write!(
+                f,
+                "binary telemetry truncated: expected {expected} bytes, found {available}"
+            ),
+            Self::RecordTooLarge { len, max } => write!(
+                f,
+                "binary telemetry record length {len} exceeds the {max} byte limit"
+            ),
+            Self::TrailingBytes(count) => {
+                write!(f, "binary telemetry record has {count} trailing bytes")
+            }
+            Self::Encode(error) => write!(f, "failed to encode telemetry frame: {error}"),
+            Self::Decode(error) => write!(f, "failed to decode telemetry frame: {error}"),
+            Self::Io(error) => write!(f, "binary telemetry I/O failed: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for BinaryCodecError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Encode(error) => Some(error),
+            Self::Decode(error) => Some(error),
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for BinaryCodecError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Whether `bytes` start with [`BINARY_MAGIC`], for telling binary captures
+/// from JSON ones.
+pub fn has_binary_magic(bytes: &[u8]) -> bool {
+    bytes.starts_with(&BINARY_MAGIC)
+}
+
+/// Encode `frame` with the magic and version prefix.
+pub fn encode_frame(frame: &TelemetryFrame) -> Result<Vec<u8>, BinaryCodecError> {
+    encode(frame)
+}
+
+/// Decode a frame written by [`encode_frame`].
+pub fn decode_frame(bytes: &[u8]) -> Result<TelemetryFrame, BinaryCodecError> {
+    decode(bytes)
+}
+
+/// Encode any frame type with the magic and version prefix.
+pub fn encode<T: Encode>(frame: &T) -> Result<Vec<u8>, BinaryCodecError> {
+    let mut out = header().to_vec();
+    out.extend_from_slice(&encode_body(frame)?);
+    Ok(out)
+}
+
+/// Decode a frame written by [`encode`], rejecting trailing bytes.
+pub fn decode<T: Decode<()>>(bytes: &[u8]) -> Result<T, BinaryCodecError> {
+    let header = bytes
+        .get(..BINARY_HEADER_LEN)
+        .ok_or(BinaryCodecError::Truncated {
+            expected: BINARY_HEADER_LEN,
+            available: bytes.len(),
+        })?;
+    check_header(header)?;
+    decode_body(&bytes[BINARY_HEADER_LEN..])
+}
+
+/// Writes a header and then length-prefixed records.
+#[derive(Debug)]
+pub struct FrameWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> FrameWriter<W> {
+    /// Start a new stream, writing the header.
+    pub fn new(mut writer: W) -> Result<Self, BinaryCodecError> {
+        writer.write_all(&header())?;
+        Ok(Self { writer })
+    }
+
+    /// Continue a stream `writer` is already positioned at the end of, such
+    /// as a file opened for append.
+    pub fn append(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Append one frame as a record.
+    pub fn write_frame<T: Encode>(&mut self, frame: &T) -> Result<(), BinaryCodecError> {
+        self.write_record(&encode_body(frame)?)
+    }
+
+    /// Append an already-encoded payload as a record.
+    pub fn write_record(&mut self, payload: &[u8]) -> Result<(), BinaryCodecError> {
+        if payload.len() > MAX_RECORD_BYTES {
+            return Err(BinaryCodecError::RecordTooLarge {
+                len: payload.len(),
+                max: MAX_RECORD_BYTES,
+            });
+        }
+        // MAX_RECORD_BYTES fits in the u32 prefix.
+        let len = payload.len() as u32;
+        self.writer.write_all(&len.to_le_bytes())?;
+        self.writer.write_all(payload)?;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<(), BinaryCodecError> {
+        Ok(self.writer.flush()?)
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Reads a stream written by [`FrameWriter`], one record at a time.
+#[derive(Debug)]
+pub struct FrameReader<R: Read, T = TelemetryFrame> {
+    reader: R,
+    _frame: PhantomData<fn() -> T>,
+}
+
+impl<R: Read, T: Decode<()>> FrameReader<R, T> {
+    /// Read and check the stream header.
+    pub fn new(mut reader: R) -> Result<Self, BinaryCodecError> {
+        let mut header = [0u8; BINARY_HEADER_LEN];
+        let read = read_up_to(&mut reader, &mut header)?;
+        if read < BINARY_HEADER_LEN {
+            return Err(BinaryCodecError::Truncated {
+                expected: BINARY_HEADER_LEN,
+                available: read,
+            });
+        }
+        check_header(&header)?;
+        Ok(Self {
+            reader,
+            _frame: PhantomData,
+        })
+    }
+
+    /// The next frame, or `None` at a clean end of stream.
+    pub fn read_frame(&mut self) -> Result<Option<T>, BinaryCodecError> {
+        self.read_record()?
+            .map(|payload| decode_body(&payload))
+            .transpose()
+    }
+
+    /// The next record's raw payload, or `None` at a clean end of stream.
+    pub fn read_record(&mut self) -> Result<Option<Vec<u8>>, BinaryCodecError> {
+        let mut prefix = [0u8; 4];
+        match read_up_to(&mut self.reader, &mut prefix)? {
+            0 => return Ok(None),
+            4 => {}
+            available => {
+                return Err(BinaryCodecError::Truncated {
+                    expected: 4,
+                    available,
+                });
+            }
+        }
+        let len = u32::from_le_bytes(prefix) as usize;
+        if len > MAX_RECORD_BYTES {
+            return Err(BinaryCodecError::RecordTooLarge {
+                len,
+                max: MAX_RECORD_BYTES,
+            });
+        }
+        let mut payload = vec![0u8; len];
+        let available = read_up_to(&mut self.reader, &mut payload)?;
+        if available < len {
+            return Err(BinaryCodecError::Truncated {
+                expected: len,
+                available,
+            });
+        }
+        Ok(Some(payload))
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Read, T: Decode<()>> Iterator for FrameReader<R, T> {
+    type Item = Result<T, BinaryCodecError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_frame().transpose()
+    }
+}
+
+fn header() -> [u8; BINARY_HEADER_LEN] {
+    let mut header = [0u8; BINARY_HEADER_LEN];
+    header[..4].copy_from_slice(&BINARY_MAGIC);
+    header[4..].copy_from_slice(&TELEMETRY_SCHEMA_VERSION.to_le_bytes());
+    header
+}
+
+fn check_header(header: &[u8]) -> Result<(), BinaryCodecError> {
+    if !has_binary_magic(header) {
+        return Err(BinaryCodecError::BadMagic);
+    }
+    let mut version = [0u8; 4];
+    version.copy_from_slice(&header[4..BINARY_HEADER_LEN]);
+    let found = u32::from_le_bytes(version);
+    if found != TELEMETRY_SCHEMA_VERSION {
+        return Err(BinaryCodecError::UnsupportedVersion {
+            found,
+            supported: TELEMETRY_SCHEMA_VERSION,
+        });
+    }
+    Ok(())
+}
+
+fn encode_body<T: Encode>(frame: &T) -> Result<Vec<u8>, BinaryCodecError> {
+    bincode::encode_to_vec(frame, bincode::config::standard()).map_err(BinaryCodecError::Encode)
+}
+
+fn decode_body<T: Decode<()>>(bytes: &[u8]) -> Result<T, BinaryCodecError> {
+    let (frame, used) = bincode::decode_from_slice(bytes, bincode::config::standard())
+        .map_err(BinaryCodecError::Decode)?;
+    if used != bytes.len() {
+        return Err(BinaryCodecError::TrailingBytes(bytes.len() - used));
+    }
+    Ok(frame)
+}
+
+/// Fill `buf` as far as the reader allows, returning how much was read; a
+/// short count means end of input.
+fn read_up_to(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(read) => filled += read,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+    #[test]
+    fn header_carries_magic_and_schema_version() -> TestResult {
+        let bytes = encode_frame(&TelemetryFrame::new(Default::default(), 1, 2, 3))?;
+        assert!(has_binary_magic(&bytes));
+        assert_eq!(bytes[4..8], TELEMETRY_SCHEMA_VERSION.to_le_bytes());
+        Ok(())
+    }
+
+    #[test]
+    fn other_schema_version_is_rejected() -> TestResult {
+        let mut bytes = encode_frame(&TelemetryFrame::new(Default::default(), 1, 2, 3))?;
+        bytes[4..8].copy_from_slice(&(TELEMETRY_SCHEMA_VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            decode_frame(&bytes),
+            Err(BinaryCodecError::UnsupportedVersion { found, .. })
+                if found == TELEMETRY_SCHEMA_VERSION + 1
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn json_is_not_mistaken_for_binary() {
+        assert!(!has_binary_magic(br#"{"metadata":{}}"#));
+        assert!(matches!(
+            decode_frame(br#"{"data":{}}"#),
+            Err(BinaryCodecError::BadMagic)
+        ));
+    }
+}
@@ -0,0 +1,626 @@
+//! Registry of well-known extended telemetry keys.
+//!
+//! Adapters, transforms and plugins all write into the free-form `extended`
+//! map. The catalog in this module pins down the name, value type and unit of
+//! every key the workspace knows about so that two producers cannot silently
+//! disagree on spelling or meaning, and so diagnostics can flag keys nobody
+//! has declared.
+//!
+//! Keys declared at runtime by plugins or transforms go through
+//! [`register_runtime_key`], which rejects declarations that contradict the
+//! catalog or an earlier runtime declaration.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{OnceLock, PoisonError, RwLock};
+
+use crate::TelemetryValue;
+
+/// Value type carried by an extended key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExtendedValueType {
+    Float,
+    Integer,
+    Boolean,
+    String,
+}
+
+impl ExtendedValueType {
+    /// Value type of an existing telemetry value.
+    pub fn of(value: &TelemetryValue) -> Self {
+        match value {
+            TelemetryValue::Float(_) => Self::Float,
+            TelemetryValue::Integer(_) => Self::Integer,
+            TelemetryValue::Boolean(_) => Self::Boolean,
+            TelemetryValue::String(_) => Self::String,
+        }
+    }
+}
+
+impl fmt::Display for ExtendedValueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Float => "float",
+            Self::Integer => "integer",
+            Self::Boolean => "boolean",
+            Self::String => "string",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Kind of component expected to produce a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProducerKind {
+    /// Game telemetry adapters.
+    Adapter,
+    /// Built-in telemetry transforms (derived values).
+    Transform,
+    /// Third-party plugins.
+    Plugin,
+}
+
+/// Declaration of a single extended key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExtendedKey {
+    /// Key as written into the extended map.
+    pub name: &'static str,
+    /// Declared value type.
+    pub value_type: ExtendedValueType,
+    /// Unit of measure, if the value has one.
+    pub unit: Option<&'static str>,
+    /// Kind of component that produces the key.
+    pub producer: ProducerKind,
+    /// Workspace version that introduced the key.
+    pub since: &'static str,
+    /// Canonical key that replaces this one, if deprecated.
+    pub deprecated_in_favor_of: Option<&'static str>,
+}
+
+/// Errors raised by the extended-key registry.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExtendedKeyError {
+    /// A value did not match the declared type of the key.
+    TypeMismatch {
+        key: &'static str,
+        expected: ExtendedValueType,
+        actual: ExtendedValueType,
+    },
+    /// A runtime declaration disagrees with an existing declaration.
+    Collision {
+        key: &'static str,
+        existing: (ExtendedValueType, Option<&'static str>),
+        attempted: (ExtendedValueType, Option<&'static str>),
+    },
+    /// A deprecation alias points at a key that is not declared.
+    UnknownAlias {
+        key: &'static str,
+        target: &'static str,
+    },
+}
+
+impl fmt::Display for ExtendedKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TypeMismatch {
+                key,
+                expected,
+                actual,
+            } => write!(f, "extended key '{key}' expects {expected}, got {actual}"),
+            Self::Collision {
+                key,
+                existing,
+                attempted,
+            } => write!(
+                f,
+                "extended key '{key}' already declared as {} ({}), cannot redeclare as {} ({})",
+                existing.0,
+                existing.1.unwrap_or("unitless"),
+                attempted.0,
+                attempted.1.unwrap_or("unitless"),
+            ),
+            Self::UnknownAlias { key, target } => {
+                write!(
+                    f,
+                    "extended key '{key}' is deprecated in favor of undeclared key '{target}'"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExtendedKeyError {}
+
+/// Telemetry types that carry an extended key/value map.
+///
+/// Implemented for the contracts [`NormalizedTelemetry`](crate::NormalizedTelemetry)
+/// here and for the canonical schemas type in `racing-wheel-schemas`.
+pub trait ExtendedFields {
+    /// Insert or replace an extended value.
+    fn insert_extended_value(&mut self, key: &str, value: TelemetryValue);
+
+    /// Names of all extended keys currently present.
+    fn extended_key_names(&self) -> Vec<&str>;
+}
+
+impl ExtendedFields for crate::NormalizedTelemetry {
+    fn insert_extended_value(&mut self, key: &str, value: TelemetryValue) {
+        self.extended.insert(key.to_string(), value);
+    }
+
+    fn extended_key_names(&self) -> Vec<&str> {
+        self.extended.keys().map(String::as_str).collect()
+    }
+}
+
+impl From<f32> for TelemetryValue {
+    fn from(value: f32) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<i32> for TelemetryValue {
+    fn from(value: i32) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<bool> for TelemetryValue {
+    fn from(value: bool) -> Self {
+        Self::Boolean(value)
+    }
+}
+
+impl From<String> for TelemetryValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for TelemetryValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+const V0_1: &str = "0.1.0";
+
+const fn adapter_key(
+    name: &'static str,
+    value_type: ExtendedValueType,
+    unit: Option<&'static str>,
+) -> ExtendedKey {
+    ExtendedKey {
+        name,
+        value_type,
+        unit,
+        producer: ProducerKind::Adapter,
+        since: V0_1,
+        deprecated_in_favor_of: None,
+    }
+}
+
+const fn transform_key(
+    name: &'static str,
+    value_type: ExtendedValueType,
+    unit: Option<&'static str>,
+) -> ExtendedKey {
+    ExtendedKey {
+        producer: ProducerKind::Transform,
+        ..adapter_key(name, value_type, unit)
+    }
+}
+
+const fn deprecated(key: ExtendedKey, name: &'static str) -> ExtendedKey {
+    ExtendedKey {
+        name,
+        deprecated_in_favor_of: Some(key.name),
+        ..key
+    }
+}
+
+const FLOAT: ExtendedValueType = ExtendedValueType::Float;
+const INTEGER: ExtendedValueType = ExtendedValueType::Integer;
+const BOOLEAN: ExtendedValueType = ExtendedValueType::Boolean;
+const STRING: ExtendedValueType = ExtendedValueType::String;
+
+impl ExtendedKey {
+    pub const WHEEL_SPEED_FL: Self = adapter_key("wheel_speed_fl", FLOAT, Some("m/s"));
+    pub const WHEEL_SPEED_FR: Self = adapter_key("wheel_speed_fr", FLOAT, Some("m/s"));
+    pub const WHEEL_SPEED_RL: Self = adapter_key("wheel_speed_rl", FLOAT, Some("m/s"));
+    pub const WHEEL_SPEED_RR: Self = adapter_key("wheel_speed_rr", FLOAT, Some("m/s"));
+
+    pub const SUSPENSION_FL: Self = adapter_key("suspension_fl", FLOAT, Some("m"));
+    pub const SUSPENSION_FR: Self = adapter_key("suspension_fr", FLOAT, Some("m"));
+    pub const SUSPENSION_RL: Self = adapter_key("suspension_rl", FLOAT, Some("m"));
+    pub const SUSPENSION_RR: Self = adapter_key("suspension_rr", FLOAT, Some("m"));
+
+    pub const SUSPENSION_TRAVEL_FL: Self = adapter_key("suspension_travel_fl", FLOAT, Some("m"));
+    pub const SUSPENSION_TRAVEL_FR: Self = adapter_key("suspension_travel_fr", FLOAT, Some("m"));
+    pub const SUSPENSION_TRAVEL_RL: Self = adapter_key("suspension_travel_rl", FLOAT, Some("m"));
+    pub const SUSPENSION_TRAVEL_RR: Self = adapter_key("suspension_travel_rr", FLOAT, Some("m"));
+
+    pub const SLIP_RATIO_FL: Self = adapter_key("slip_ratio_fl", FLOAT, None);
+    pub const SLIP_RATIO_FR: Self = adapter_key("slip_ratio_fr", FLOAT, None);
+    pub const SLIP_RATIO_RL: Self = adapter_key("slip_ratio_rl", FLOAT, None);
+    pub const SLIP_RATIO_RR: Self = adapter_key("slip_ratio_rr", FLOAT, None);
+
+    pub const TIRE_SLIP_RATIO_FL: Self = adapter_key("tire_slip_ratio_fl", FLOAT, None);
+    pub const TIRE_SLIP_RATIO_FR: Self = adapter_key("tire_slip_ratio_fr", FLOAT, None);
+    pub const TIRE_SLIP_RATIO_RL: Self = adapter_key("tire_slip_ratio_rl", FLOAT, None);
+    pub const TIRE_SLIP_RATIO_RR: Self = adapter_key("tire_slip_ratio_rr", FLOAT, None);
+
+    pub const TIRE_TEMP_FL: Self = adapter_key("tire_temp_fl", FLOAT, Some("degC"));
+    pub const TIRE_TEMP_FR: Self = adapter_key("tire_temp_fr", FLOAT, Some("degC"));
+    pub const TIRE_TEMP_RL: Self = adapter_key("tire_temp_rl", FLOAT, Some("degC"));
+    pub const TIRE_TEMP_RR: Self = adapter_key("tire_temp_rr", FLOAT, Some("degC"));
+
+    /// British spelling kept as an alias; writes land on [`Self::TIRE_TEMP_FL`].
+    pub const TYRE_TEMP_FL: Self = deprecated(Self::TIRE_TEMP_FL, "tyre_temp_fl");
+    /// British spelling kept as an alias; writes land on [`Self::TIRE_TEMP_FR`].
+    pub const TYRE_TEMP_FR: Self = deprecated(Self::TIRE_TEMP_FR, "tyre_temp_fr");
+    /// British spelling kept as an alias; writes land on [`Self::TIRE_TEMP_RL`].
+    pub const TYRE_TEMP_RL: Self = deprecated(Self::TIRE_TEMP_RL, "tyre_temp_rl");
+    /// British spelling kept as an alias; writes land on [`Self::TIRE_TEMP_RR`].
+    pub const TYRE_TEMP_RR: Self = deprecated(Self::TIRE_TEMP_RR, "tyre_temp_rr");
+
+    /// Rally stage completion (0.0 – 1.0).
+    pub const STAGE_PROGRESS: Self = adapter_key("stage_progress", FLOAT, Some("fraction"));
+    /// Handbrake input (0.0 – 1.0).
+    pub const HAND_BRAKE: Self = adapter_key("hand_brake", FLOAT, Some("fraction"));
+    /// Position around the lap as reported by the game (0.0 – 1.0).
+    pub const SPLINE_POSITION: Self = adapter_key("spline_position", FLOAT, Some("fraction"));
+    /// Distance covered since the start/finish line.
+    pub const LAP_DISTANCE_M: Self = adapter_key("lap_distance_m", FLOAT, Some("m"));
+    /// Length of one lap of the current circuit.
+    pub const TRACK_LENGTH_M: Self = adapter_key("track_length_m", FLOAT, Some("m"));
+    /// Time spent in the first sector of the current lap.
+    pub const SECTOR_1_TIME_S: Self = adapter_key("sector_1_time_s", FLOAT, Some("s"));
+    /// Time spent in the second sector of the current lap.
+    pub const SECTOR_2_TIME_S: Self = adapter_key("sector_2_time_s", FLOAT, Some("s"));
+    /// Engine RPM relative to the redline (0.0 – 1.0).
+    pub const RPM_FRACTION: Self = adapter_key("rpm_fraction", FLOAT, Some("fraction"));
+
+    pub const POS_X: Self = adapter_key("pos_x", FLOAT, Some("m"));
+    pub const POS_Y: Self = adapter_key("pos_y", FLOAT, Some("m"));
+    pub const POS_Z: Self = adapter_key("pos_z", FLOAT, Some("m"));
+    pub const VEL_X: Self = adapter_key("vel_x", FLOAT, Some("m/s"));
+    pub const VEL_Y: Self = adapter_key("vel_y", FLOAT, Some("m/s"));
+    pub const VEL_Z: Self = adapter_key("vel_z", FLOAT, Some("m/s"));
+    pub const ROLL: Self = adapter_key("roll", FLOAT, Some("rad"));
+    pub const PITCH: Self = adapter_key("pitch", FLOAT, Some("rad"));
+    pub const YAW: Self = adapter_key("yaw", FLOAT, Some("rad"));
+
+    pub const TURBO_BAR: Self = adapter_key("turbo_bar", FLOAT, Some("bar"));
+    pub const OIL_TEMP_C: Self = adapter_key("oil_temp_c", FLOAT, Some("degC"));
+    pub const OIL_PRESSURE_BAR: Self = adapter_key("oil_pressure_bar", FLOAT, Some("bar"));
+    pub const FUEL_LEFT_L: Self = adapter_key("fuel_left_l", FLOAT, Some("l"));
+    pub const DRS_ACTIVE: Self = adapter_key("drs_active", BOOLEAN, None);
+
+    /// Fuel burn rate by mass, as most sims measure it.
+    pub const FUEL_USE_KG_H: Self = adapter_key("fuel_use_kg_h", FLOAT, Some("kg/h"));
+    /// Fuel burn rate by volume; only written when the fuel density is known.
+    pub const FUEL_USE_L_H: Self = adapter_key("fuel_use_l_h", FLOAT, Some("l/h"));
+    /// Average fuel burned over recent laps without a refuel or pit visit.
+    pub const FUEL_PER_LAP_L: Self = adapter_key("fuel_per_lap_l", FLOAT, Some("l"));
+    /// Fuel the crew will add at the next stop.
+    pub const PIT_FUEL_TO_ADD_L: Self = adapter_key("pit_fuel_to_add_l", FLOAT, Some("l"));
+    pub const PIT_TIRE_CHANGE_FL: Self = adapter_key("pit_tire_change_fl", BOOLEAN, None);
+    pub const PIT_TIRE_CHANGE_FR: Self = adapter_key("pit_tire_change_fr", BOOLEAN, None);
+    pub const PIT_TIRE_CHANGE_RL: Self = adapter_key("pit_tire_change_rl", BOOLEAN, None);
+    pub const PIT_TIRE_CHANGE_RR: Self = adapter_key("pit_tire_change_rr", BOOLEAN, None);
+    pub const PIT_FUEL_FILL: Self = adapter_key("pit_fuel_fill", BOOLEAN, None);
+    pub const PIT_WINDSHIELD_TEAROFF: Self = adapter_key("pit_windshield_tearoff", BOOLEAN, None);
+    pub const PIT_FAST_REPAIR: Self = adapter_key("pit_fast_repair", BOOLEAN, None);
+    /// Progress of the current stop: `none`, `in_progress`, `complete`, or
+    /// why the crew cannot service the car (`too_far_left`, `bad_angle`, ...).
+    pub const PIT_SERVICE_STATUS: Self = adapter_key("pit_service_status", STRING, None);
+    /// Mandatory repair time remaining at the current stop.
+    pub const PIT_REPAIR_LEFT_S: Self = adapter_key("pit_repair_left_s", FLOAT, Some("s"));
+    /// Optional repair time remaining at the current stop.
+    pub const PIT_OPT_REPAIR_LEFT_S: Self = adapter_key("pit_opt_repair_left_s", FLOAT, Some("s"));
+    pub const FAST_REPAIRS_AVAILABLE: Self = adapter_key("fast_repairs_available", INTEGER, None);
+    pub const FAST_REPAIRS_USED: Self = adapter_key("fast_repairs_used", INTEGER, None);
+
+    /// `field=age_s` pairs, `;`-separated, for fields carried forward from an
+    /// earlier frame by the orchestrator's field persistence transform.
+    pub const PERSISTED_FIELDS: Self = transform_key("_persisted_fields", STRING, None);
+    /// Normalized position around the lap (0.0 – 1.0), exact or estimated.
+    pub const LAP_POSITION: Self = transform_key("lap_position", FLOAT, Some("fraction"));
+    /// How [`Self::LAP_POSITION`] was obtained: `exact`, `estimated` or `pit`.
+    pub const LAP_POSITION_SOURCE: Self = transform_key("lap_position_source", STRING, None);
+    /// Live time delta against a reference lap; positive means slower.
+    pub const REFERENCE_DELTA_S: Self = transform_key("reference_delta_s", FLOAT, Some("s"));
+    /// Game-clock step minus arrival-time step for a frame whose two clocks
+    /// disagreed beyond the orchestrator's divergence threshold.
+    pub const GAME_CLOCK_DIVERGENCE_S: Self =
+        transform_key("game_clock_divergence_s", FLOAT, Some("s"));
+    /// Set on resampled frames that repeat an input frame older than the
+    /// resampler's staleness limit.
+    pub const RESAMPLED_STALE: Self = transform_key("resampled_stale", BOOLEAN, None);
+    /// Comma-separated `field:kind(observed)` issues of a frame a
+    /// pass-through `TelemetryValidator` forwarded unchanged.
+    pub const VALIDATION_ISSUES: Self = transform_key("validation_issues", STRING, None);
+
+    /// Write `value` under this key, enforcing the declared value type.
+    ///
+    /// Deprecated keys write to their canonical replacement.
+    pub fn set<T>(
+        &self,
+        telemetry: &mut T,
+        value: impl Into<TelemetryValue>,
+    ) -> Result<(), ExtendedKeyError>
+    where
+        T: ExtendedFields + ?Sized,
+    {
+        let key = self.canonical()?;
+        let value = value.into();
+        key.check(&value)?;
+        telemetry.insert_extended_value(key.name, value);
+        Ok(())
+    }
+
+    /// Reject `value` if it does not match the declared type.
+    pub fn check(&self, value: &TelemetryValue) -> Result<(), ExtendedKeyError> {
+        let actual = ExtendedValueType::of(value);
+        if actual == self.value_type {
+            Ok(())
+        } else {
+            Err(ExtendedKeyError::TypeMismatch {
+                key: self.name,
+                expected: self.value_type,
+                actual,
+            })
+        }
+    }
+
+    /// Whether the key has been superseded by another.
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecated_in_favor_of.is_some()
+    }
+
+    /// Follow deprecation aliases to the key producers should write.
+    pub fn canonical(&self) -> Result<ExtendedKey, ExtendedKeyError> {
+        let mut key = *self;
+        // Bounded so a cyclic runtime alias chain cannot spin forever.
+        for _ in 0..=KNOWN_KEYS.len() {
+            let Some(target) = key.deprecated_in_favor_of else {
+                return Ok(key);
+            };
+            key = lookup_key(target).ok_or(ExtendedKeyError::UnknownAlias {
+                key: key.name,
+                target,
+            })?;
+        }
+        Err(ExtendedKeyError::UnknownAlias {
+            key: self.name,
+            target: self.deprecated_in_favor_of.unwrap_or(self.name),
+        })
+    }
+}
+
+/// Every statically declared extended key, including deprecated aliases.
+pub const KNOWN_KEYS: &[ExtendedKey] = &[
+    ExtendedKey::WHEEL_SPEED_FL,
+    ExtendedKey::WHEEL_SPEED_FR,
+    ExtendedKey::WHEEL_SPEED_RL,
+    ExtendedKey::WHEEL_SPEED_RR,
+    ExtendedKey::SUSPENSION_FL,
+    ExtendedKey::SUSPENSION_FR,
+    ExtendedKey::SUSPENSION_RL,
+    ExtendedKey::SUSPENSION_RR,
+    ExtendedKey::SUSPENSION_TRAVEL_FL,
+    ExtendedKey::SUSPENSION_TRAVEL_FR,
+    ExtendedKey::SUSPENSION_TRAVEL_RL,
+    ExtendedKey::SUSPENSION_TRAVEL_RR,
+    ExtendedKey::SLIP_RATIO_FL,
+    ExtendedKey::SLIP_RATIO_FR,
+    ExtendedKey::SLIP_RATIO_RL,
+    ExtendedKey::SLIP_RATIO_RR,
+    ExtendedKey::TIRE_SLIP_RATIO_FL,
+    ExtendedKey::TIRE_SLIP_RATIO_FR,
+    ExtendedKey::TIRE_SLIP_RATIO_RL,
+    ExtendedKey::TIRE_SLIP_RATIO_RR,
+    ExtendedKey::TIRE_TEMP_FL,
+    ExtendedKey::TIRE_TEMP_FR,
+    ExtendedKey::TIRE_TEMP_RL,
+    ExtendedKey::TIRE_TEMP_RR,
+    ExtendedKey::TYRE_TEMP_FL,
+    ExtendedKey::TYRE_TEMP_FR,
+    ExtendedKey::TYRE_TEMP_RL,
+    ExtendedKey::TYRE_TEMP_RR,
+    ExtendedKey::STAGE_PROGRESS,
+    ExtendedKey::SPLINE_POSITION,
+    ExtendedKey::LAP_DISTANCE_M,
+    ExtendedKey::TRACK_LENGTH_M,
+    ExtendedKey::SECTOR_1_TIME_S,
+    ExtendedKey::SECTOR_2_TIME_S,
+    ExtendedKey::HAND_BRAKE,
+    ExtendedKey::RPM_FRACTION,
+    ExtendedKey::POS_X,
+    ExtendedKey::POS_Y,
+    ExtendedKey::POS_Z,
+    ExtendedKey::VEL_X,
+    ExtendedKey::VEL_Y,
+    ExtendedKey::VEL_Z,
+    ExtendedKey::ROLL,
+    ExtendedKey::PITCH,
+    ExtendedKey::YAW,
+    ExtendedKey::TURBO_BAR,
+    ExtendedKey::OIL_TEMP_C,
+    ExtendedKey::OIL_PRESSURE_BAR,
+    ExtendedKey::FUEL_LEFT_L,
+    ExtendedKey::DRS_ACTIVE,
+    ExtendedKey::FUEL_USE_KG_H,
+    ExtendedKey::FUEL_USE_L_H,
+    ExtendedKey::FUEL_PER_LAP_L,
+    ExtendedKey::PIT_FUEL_TO_ADD_L,
+    ExtendedKey::PIT_TIRE_CHANGE_FL,
+    ExtendedKey::PIT_TIRE_CHANGE_FR,
+    ExtendedKey::PIT_TIRE_CHANGE_RL,
+    ExtendedKey::PIT_TIRE_CHANGE_RR,
+    ExtendedKey::PIT_FUEL_FILL,
+    ExtendedKey::PIT_WINDSHIELD_TEAROFF,
+    ExtendedKey::PIT_FAST_REPAIR,
+    ExtendedKey::PIT_SERVICE_STATUS,
+    ExtendedKey::PIT_REPAIR_LEFT_S,
+    ExtendedKey::PIT_OPT_REPAIR_LEFT_S,
+    ExtendedKey::FAST_REPAIRS_AVAILABLE,
+    ExtendedKey::FAST_REPAIRS_USED,
+    ExtendedKey::PERSISTED_FIELDS,
+    ExtendedKey::LAP_POSITION,
+    ExtendedKey::LAP_POSITION_SOURCE,
+    ExtendedKey::REFERENCE_DELTA_S,
+    ExtendedKey::GAME_CLOCK_DIVERGENCE_S,
+    ExtendedKey::RESAMPLED_STALE,
+    ExtendedKey::VALIDATION_ISSUES,
+];
+
+fn runtime_keys() -> &'static RwLock<HashMap<&'static str, ExtendedKey>> {
+    static RUNTIME_KEYS: OnceLock<RwLock<HashMap<&'static str, ExtendedKey>>> = OnceLock::new();
+    RUNTIME_KEYS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+fn catalog_key(name: &str) -> Option<ExtendedKey> {
+    KNOWN_KEYS.iter().find(|key| key.name == name).copied()
+}
+
+/// Look up a key in the catalog or among runtime registrations.
+pub fn lookup_key(name: &str) -> Option<ExtendedKey> {
+    catalog_key(name).or_else(|| {
+        runtime_keys()
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(name)
+            .copied()
+    })
+}
+
+/// Resolve `name` to the canonical key producers should use.
+///
+/// Returns `None` for undeclared names.
+pub fn resolve_key(name: &str) -> Option<ExtendedKey> {
+    lookup_key(name).and_then(|key| key.canonical().ok())
+}
+
+/// Declare a key at runtime (plugins, user transforms).
+///
+/// Redeclaring an existing key with the same type and unit is a no-op; a
+/// declaration that changes either is rejected with
+/// [`ExtendedKeyError::Collision`].
+pub fn register_runtime_key(key: ExtendedKey) -> Result<(), ExtendedKeyError> {
+    let collides = |existing: ExtendedKey| {
+        if existing.value_type == key.value_type && existing.unit == key.unit {
+            Ok(())
+        } else {
+            Err(ExtendedKeyError::Collision {
+                key: key.name,
+                existing: (existing.value_type, existing.unit),
+                attempted: (key.value_type, key.unit),
+            })
+        }
+    };
+
+    if let Some(existing) = catalog_key(key.name) {
+        return collides(existing);
+    }
+
+    let mut runtime = runtime_keys()
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    if let Some(existing) = runtime.get(key.name) {
+        return collides(*existing);
+    }
+    if let Some(target) = key.deprecated_in_favor_of {
+        let declared = catalog_key(target).is_some() || runtime.contains_key(target);
+        if !declared {
+            return Err(ExtendedKeyError::UnknownAlias {
+                key: key.name,
+                target,
+            });
+        }
+    }
+    runtime.insert(key.name, key);
+    Ok(())
+}
+
+/// Extended keys present in `telemetry` that nobody has declared, sorted.
+pub fn scan_unknown_keys<T>(telemetry: &T) -> Vec<String>
+where
+    T: ExtendedFields + ?Sized,
+{
+    let mut unknown: Vec<String> = telemetry
+        .extended_key_names()
+        .into_iter()
+        .filter(|name| lookup_key(name).is_none())
+        .map(str::to_string)
+        .collect();
+    unknown.sort();
+    unknown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NormalizedTelemetry;
+
+    #[test]
+    fn catalog_names_are_unique_and_aliases_resolve() {
+        for (index, key) in KNOWN_KEYS.iter().enumerate() {
+            assert!(
+                KNOWN_KEYS[index + 1..]
+                    .iter()
+                    .all(|other| other.name != key.name),
+                "duplicate catalog key {}",
+                key.name
+            );
+            assert!(key.canonical().is_ok(), "dangling alias on {}", key.name);
+        }
+    }
+
+    #[test]
+    fn set_rejects_wrong_value_type() {
+        let mut telemetry = NormalizedTelemetry::new();
+        let result = ExtendedKey::WHEEL_SPEED_FL.set(&mut telemetry, true);
+
+        assert_eq!(
+            result,
+            Err(ExtendedKeyError::TypeMismatch {
+                key: "wheel_speed_fl",
+                expected: ExtendedValueType::Float,
+                actual: ExtendedValueType::Boolean,
+            })
+        );
+        assert!(telemetry.extended.is_empty());
+    }
+
+    #[test]
+    fn deprecated_alias_writes_canonical_key() -> Result<(), ExtendedKeyError> {
+        let mut telemetry = NormalizedTelemetry::new();
+        ExtendedKey::TYRE_TEMP_FL.set(&mut telemetry, 85.0_f32)?;
+
+        assert_eq!(
+            telemetry.extended.get("tire_temp_fl"),
+            Some(&TelemetryValue::Float(85.0))
+        );
+        assert!(!telemetry.extended.contains_key("tyre_temp_fl"));
+        assert_eq!(resolve_key("tyre_temp_fl"), Some(ExtendedKey::TIRE_TEMP_FL));
+        Ok(())
+    }
+
+    #[test]
+    fn runtime_registration_detects_catalog_collision() -> Result<(), ExtendedKeyError> {
+        let conflicting = ExtendedKey {
+            unit: Some("km/h"),
+            ..ExtendedKey::WHEEL_SPEED_FL
+        };
+        assert!(matches!(
+            register_runtime_key(conflicting),
+            Err(ExtendedKeyError::Collision { .. })
+        ));
+
+        // Identical redeclaration is accepted.
+        register_runtime_key(ExtendedKey::WHEEL_SPEED_FL)?;
+        Ok(())
+    }
+}
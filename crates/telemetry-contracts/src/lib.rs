@@ -5,8 +5,40 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+#[cfg(feature = "binary")]
+pub mod codec;
+pub mod extended_keys;
+pub mod migration;
+pub mod opponents;
+pub mod units;
+pub mod validation;
+
+#[cfg(feature = "binary")]
+pub use codec::{
+    BINARY_MAGIC, BinaryCodecError, FrameReader, FrameWriter, decode_frame, encode_frame,
+    has_binary_magic,
+};
+pub use extended_keys::{
+    ExtendedFields, ExtendedKey, ExtendedKeyError, ExtendedValueType, KNOWN_KEYS, ProducerKind,
+    lookup_key, register_runtime_key, resolve_key, scan_unknown_keys,
+};
+pub use migration::{
+    FRAME_MIGRATIONS, FrameMigrationError, LEGACY_FRAME_VERSION, MigrationStep,
+    TELEMETRY_SCHEMA_VERSION, migrate_frame, upgrade_frame,
+};
+pub use opponents::{DEFAULT_MAX_OPPONENTS, OpponentSnapshot, bound_opponents};
+pub use units::{
+    FormattedTelemetry, FormattedValue, KMH_PER_MS, KPA_PER_BAR, KPA_PER_PSI, METERS_PER_MILE,
+    MPH_PER_MS, PressureUnit, SpeedUnit, TelemetryReadings, TemperatureUnit, UnitsProfile,
+};
+pub use validation::{
+    DEFAULT_GEAR_RANGE, FloatField, IssueKind, TelemetryValidator, ValidatedFields,
+    ValidationIssue, ValidationOutcome, ValidationPolicy,
+};
+
 /// Normalized telemetry data structure.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "binary", derive(bincode::Encode, bincode::Decode))]
 pub struct NormalizedTelemetry {
     /// Force feedback scalar value (-1.0 to 1.0)
     /// Represents the force feedback strength requested by the game.
@@ -24,6 +56,33 @@ pub struct NormalizedTelemetry {
     /// Current gear (-1 = reverse, 0 = neutral, 1+ = forward gears).
     pub gear: Option<i8>,
 
+    /// Steering wheel angle in radians (positive = right, negative = left).
+    pub steering_angle: Option<f32>,
+
+    /// Throttle position (0.0 = released, 1.0 = fully pressed).
+    pub throttle: Option<f32>,
+
+    /// Brake position (0.0 = released, 1.0 = fully pressed).
+    pub brake: Option<f32>,
+
+    /// Lateral acceleration in G (positive = right).
+    pub lateral_g: Option<f32>,
+
+    /// Longitudinal acceleration in G (positive = accelerating).
+    pub longitudinal_g: Option<f32>,
+
+    /// Front-left tire slip angle in radians.
+    pub slip_angle_fl: Option<f32>,
+
+    /// Front-right tire slip angle in radians.
+    pub slip_angle_fr: Option<f32>,
+
+    /// Rear-left tire slip angle in radians.
+    pub slip_angle_rl: Option<f32>,
+
+    /// Rear-right tire slip angle in radians.
+    pub slip_angle_rr: Option<f32>,
+
     /// Racing flags and status information.
     pub flags: TelemetryFlags,
 
@@ -33,12 +92,80 @@ pub struct NormalizedTelemetry {
     /// Track identifier (if available).
     pub track_id: Option<String>,
 
+    /// Lap and session timing (if available).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timing: Option<SessionTiming>,
+
+    /// Car position in the game's world coordinates, metres, ordered X, Y, Z
+    /// (if available). Axis conventions are the game's own.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub world_position: Option<[f32; 3]>,
+
+    /// How far around the lap the car is, 0.0 at the line to 1.0 back at it
+    /// (if available).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lap_distance_fraction: Option<f32>,
+
     /// Additional game-specific data.
     pub extended: HashMap<String, TelemetryValue>,
 }
 
+/// Lap and session timing.
+///
+/// Every field is optional because games report different subsets. Times are
+/// `f64` so a 24-hour session countdown stays exact to the millisecond.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "binary", derive(bincode::Encode, bincode::Decode))]
+#[serde(default)]
+pub struct SessionTiming {
+    /// Elapsed time on the current lap in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_lap_ms: Option<f64>,
+
+    /// Time of the last completed lap in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_lap_ms: Option<f64>,
+
+    /// Best lap time of the session in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub best_lap_ms: Option<f64>,
+
+    /// Lap the car is on, counting from 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lap_number: Option<i32>,
+
+    /// Zero-based index of the sector the car is in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sector: Option<u8>,
+
+    /// Time left in a timed session in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_time_remaining_ms: Option<f64>,
+}
+
+impl SessionTiming {
+    /// Drop times that are negative or not finite, and negative lap numbers.
+    pub fn validated(self) -> Self {
+        let time = |ms: Option<f64>| ms.filter(|ms| ms.is_finite() && *ms >= 0.0);
+        Self {
+            current_lap_ms: time(self.current_lap_ms),
+            last_lap_ms: time(self.last_lap_ms),
+            best_lap_ms: time(self.best_lap_ms),
+            lap_number: self.lap_number.filter(|lap| *lap >= 0),
+            sector: self.sector,
+            session_time_remaining_ms: time(self.session_time_remaining_ms),
+        }
+    }
+
+    /// Whether no field is set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// Racing flags and status information.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "binary", derive(bincode::Encode, bincode::Decode))]
 pub struct TelemetryFlags {
     /// Yellow flag (caution).
     pub yellow_flag: bool,
@@ -102,6 +229,7 @@ impl Default for TelemetryFlags {
 
 /// Extended telemetry value for game-specific data.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "binary", derive(bincode::Encode, bincode::Decode))]
 pub enum TelemetryValue {
     Float(f32),
     Integer(i32),
@@ -115,6 +243,11 @@ impl NormalizedTelemetry {
         Self::default()
     }
 
+    /// Start building telemetry with the same validation as the `with_*` methods.
+    pub fn builder() -> NormalizedTelemetryBuilder {
+        NormalizedTelemetryBuilder::new()
+    }
+
     /// Set FFB scalar value with clamping.
     pub fn with_ffb_scalar(mut self, value: f32) -> Self {
         self.ffb_scalar = Some(value.clamp(-1.0, 1.0));
@@ -151,6 +284,46 @@ impl NormalizedTelemetry {
         self
     }
 
+    /// Set steering angle in radians with validation.
+    pub fn with_steering_angle(mut self, value: f32) -> Self {
+        self.steering_angle = finite(value);
+        self
+    }
+
+    /// Set throttle position with clamping.
+    pub fn with_throttle(mut self, value: f32) -> Self {
+        self.throttle = finite(value).map(|value| value.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Set brake position with clamping.
+    pub fn with_brake(mut self, value: f32) -> Self {
+        self.brake = finite(value).map(|value| value.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Set lateral G-force with validation.
+    pub fn with_lateral_g(mut self, value: f32) -> Self {
+        self.lateral_g = finite(value);
+        self
+    }
+
+    /// Set longitudinal G-force with validation.
+    pub fn with_longitudinal_g(mut self, value: f32) -> Self {
+        self.longitudinal_g = finite(value);
+        self
+    }
+
+    /// Set per-corner slip angles in radians, ordered FL, FR, RL, RR.
+    /// Non-finite corners are left unset.
+    pub fn with_slip_angles(mut self, [fl, fr, rl, rr]: [f32; 4]) -> Self {
+        self.slip_angle_fl = finite(fl);
+        self.slip_angle_fr = finite(fr);
+        self.slip_angle_rl = finite(rl);
+        self.slip_angle_rr = finite(rr);
+        self
+    }
+
     /// Set car ID.
     pub fn with_car_id(mut self, id: String) -> Self {
         if !id.is_empty() {
@@ -173,6 +346,30 @@ impl NormalizedTelemetry {
         self
     }
 
+    /// Set lap and session timing, dropping invalid values. Timing with no
+    /// field left is not attached.
+    pub fn with_timing(mut self, timing: SessionTiming) -> Self {
+        let timing = timing.validated();
+        self.timing = (!timing.is_empty()).then_some(timing);
+        self
+    }
+
+    /// Set world position in metres; a position with a non-finite axis is
+    /// left unset.
+    pub fn with_world_position(mut self, position: [f32; 3]) -> Self {
+        self.world_position = position
+            .iter()
+            .all(|axis| axis.is_finite())
+            .then_some(position);
+        self
+    }
+
+    /// Set lap distance fraction with clamping to 0.0..=1.0.
+    pub fn with_lap_distance_fraction(mut self, value: f32) -> Self {
+        self.lap_distance_fraction = finite(value).map(|value| value.clamp(0.0, 1.0));
+        self
+    }
+
     /// Add extended telemetry value.
     pub fn with_extended(mut self, key: String, value: TelemetryValue) -> Self {
         self.extended.insert(key, value);
@@ -204,12 +401,193 @@ impl NormalizedTelemetry {
 
     /// Get speed in km/h.
     pub fn speed_kmh(&self) -> Option<f32> {
-        self.speed_ms.map(|speed| speed * 3.6)
+        self.speed_ms.map(|speed| speed * KMH_PER_MS)
     }
 
     /// Get speed in mph.
     pub fn speed_mph(&self) -> Option<f32> {
-        self.speed_ms.map(|speed| speed * 2.237)
+        self.speed_ms.map(|speed| speed * MPH_PER_MS)
+    }
+
+    /// Speed, RPM, FFB, slip and the `tire_temp_*` extended fields converted
+    /// to `profile` and formatted for display.
+    pub fn formatted(&self, profile: &UnitsProfile) -> FormattedTelemetry {
+        let float = |key: ExtendedKey| match self.extended.get(key.name) {
+            Some(TelemetryValue::Float(value)) => Some(*value),
+            Some(TelemetryValue::Integer(value)) => Some(*value as f32),
+            _ => None,
+        };
+        TelemetryReadings {
+            speed_ms: self.speed_ms,
+            rpm: self.rpm,
+            ffb_scalar: self.ffb_scalar,
+            slip_ratio: self.slip_ratio,
+            tire_pressures_kpa: [None; 4],
+            tire_temps_c: [
+                float(ExtendedKey::TIRE_TEMP_FL),
+                float(ExtendedKey::TIRE_TEMP_FR),
+                float(ExtendedKey::TIRE_TEMP_RL),
+                float(ExtendedKey::TIRE_TEMP_RR),
+            ],
+        }
+        .formatted(profile)
+    }
+}
+
+fn finite(value: f32) -> Option<f32> {
+    value.is_finite().then_some(value)
+}
+
+/// Fluent builder for [`NormalizedTelemetry`].
+///
+/// Each setter applies the rules of the matching `with_*` method, so a value
+/// rejected there is rejected here too.
+#[derive(Debug, Clone, Default)]
+pub struct NormalizedTelemetryBuilder {
+    inner: NormalizedTelemetry,
+}
+
+impl NormalizedTelemetryBuilder {
+    /// Create a builder with every field unset.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set FFB scalar value (clamped to -1.0..=1.0).
+    pub fn ffb_scalar(mut self, value: f32) -> Self {
+        self.inner = self.inner.with_ffb_scalar(value);
+        self
+    }
+
+    /// Set engine RPM (negative or non-finite values are ignored).
+    pub fn rpm(mut self, value: f32) -> Self {
+        self.inner = self.inner.with_rpm(value);
+        self
+    }
+
+    /// Set vehicle speed in m/s (negative or non-finite values are ignored).
+    pub fn speed_ms(mut self, value: f32) -> Self {
+        self.inner = self.inner.with_speed_ms(value);
+        self
+    }
+
+    /// Set slip ratio (clamped to 0.0..=1.0).
+    pub fn slip_ratio(mut self, value: f32) -> Self {
+        self.inner = self.inner.with_slip_ratio(value);
+        self
+    }
+
+    /// Set current gear.
+    pub fn gear(mut self, value: i8) -> Self {
+        self.inner = self.inner.with_gear(value);
+        self
+    }
+
+    /// Set steering angle in radians.
+    pub fn steering_angle(mut self, value: f32) -> Self {
+        self.inner = self.inner.with_steering_angle(value);
+        self
+    }
+
+    /// Set throttle position (clamped to 0.0..=1.0).
+    pub fn throttle(mut self, value: f32) -> Self {
+        self.inner = self.inner.with_throttle(value);
+        self
+    }
+
+    /// Set brake position (clamped to 0.0..=1.0).
+    pub fn brake(mut self, value: f32) -> Self {
+        self.inner = self.inner.with_brake(value);
+        self
+    }
+
+    /// Set lateral G-force.
+    pub fn lateral_g(mut self, value: f32) -> Self {
+        self.inner = self.inner.with_lateral_g(value);
+        self
+    }
+
+    /// Set longitudinal G-force.
+    pub fn longitudinal_g(mut self, value: f32) -> Self {
+        self.inner = self.inner.with_longitudinal_g(value);
+        self
+    }
+
+    /// Set front-left slip angle in radians.
+    pub fn slip_angle_fl(mut self, value: f32) -> Self {
+        self.inner.slip_angle_fl = finite(value);
+        self
+    }
+
+    /// Set front-right slip angle in radians.
+    pub fn slip_angle_fr(mut self, value: f32) -> Self {
+        self.inner.slip_angle_fr = finite(value);
+        self
+    }
+
+    /// Set rear-left slip angle in radians.
+    pub fn slip_angle_rl(mut self, value: f32) -> Self {
+        self.inner.slip_angle_rl = finite(value);
+        self
+    }
+
+    /// Set rear-right slip angle in radians.
+    pub fn slip_angle_rr(mut self, value: f32) -> Self {
+        self.inner.slip_angle_rr = finite(value);
+        self
+    }
+
+    /// Set all four slip angles, ordered FL, FR, RL, RR.
+    pub fn slip_angles(mut self, angles: [f32; 4]) -> Self {
+        self.inner = self.inner.with_slip_angles(angles);
+        self
+    }
+
+    /// Set racing flags.
+    pub fn flags(mut self, flags: TelemetryFlags) -> Self {
+        self.inner = self.inner.with_flags(flags);
+        self
+    }
+
+    /// Set car ID (empty IDs are ignored).
+    pub fn car_id(mut self, id: impl Into<String>) -> Self {
+        self.inner = self.inner.with_car_id(id.into());
+        self
+    }
+
+    /// Set track ID (empty IDs are ignored).
+    pub fn track_id(mut self, id: impl Into<String>) -> Self {
+        self.inner = self.inner.with_track_id(id.into());
+        self
+    }
+
+    /// Set lap and session timing.
+    pub fn timing(mut self, timing: SessionTiming) -> Self {
+        self.inner = self.inner.with_timing(timing);
+        self
+    }
+
+    /// Set world position in metres (non-finite positions are ignored).
+    pub fn world_position(mut self, position: [f32; 3]) -> Self {
+        self.inner = self.inner.with_world_position(position);
+        self
+    }
+
+    /// Set lap distance fraction (clamped to 0.0..=1.0).
+    pub fn lap_distance_fraction(mut self, value: f32) -> Self {
+        self.inner = self.inner.with_lap_distance_fraction(value);
+        self
+    }
+
+    /// Add an extended telemetry value.
+    pub fn extended(mut self, key: impl Into<String>, value: TelemetryValue) -> Self {
+        self.inner = self.inner.with_extended(key.into(), value);
+        self
+    }
+
+    /// Finish building.
+    pub fn build(self) -> NormalizedTelemetry {
+        self.inner
     }
 }
 
@@ -226,9 +604,23 @@ pub struct TelemetryFieldCoverage {
     pub flags: FlagCoverage,
     pub car_id: bool,
     pub track_id: bool,
+    #[serde(default)]
+    pub timing: TimingCoverage,
     pub extended_fields: Vec<String>,
 }
 
+/// Which [`SessionTiming`] fields a game provides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimingCoverage {
+    pub current_lap: bool,
+    pub last_lap: bool,
+    pub best_lap: bool,
+    pub lap_number: bool,
+    pub sector: bool,
+    pub session_time_remaining: bool,
+}
+
 /// Flag coverage information.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FlagCoverage {
@@ -248,7 +640,8 @@ pub struct FlagCoverage {
 }
 
 /// Telemetry frame with timing information.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "binary", derive(bincode::Encode, bincode::Decode))]
 pub struct TelemetryFrame {
     /// Normalized telemetry data.
     pub data: NormalizedTelemetry,
@@ -261,6 +654,15 @@ pub struct TelemetryFrame {
 
     /// Raw data size for diagnostics.
     pub raw_size: usize,
+
+    /// Schema version of the serialized shape; see [`migration`].
+    #[serde(default = "migration::legacy_frame_version")]
+    pub version: u32,
+
+    /// Other cars in the session, sorted by position; empty for games that
+    /// report the player's car only. See [`opponents`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub opponents: Vec<OpponentSnapshot>,
 }
 
 impl TelemetryFrame {
@@ -276,15 +678,23 @@ impl TelemetryFrame {
             timestamp_ns,
             sequence,
             raw_size,
+            version: TELEMETRY_SCHEMA_VERSION,
+            opponents: Vec::new(),
         }
     }
+
+    /// Attach the other cars in the session.
+    pub fn with_opponents(mut self, opponents: Vec<OpponentSnapshot>) -> Self {
+        self.opponents = opponents;
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        FlagCoverage, NormalizedTelemetry, TelemetryFieldCoverage, TelemetryFlags, TelemetryFrame,
-        TelemetryValue,
+        FlagCoverage, NormalizedTelemetry, SessionTiming, TelemetryFieldCoverage, TelemetryFlags,
+        TelemetryFrame, TelemetryValue, TimingCoverage,
     };
 
     // ── NormalizedTelemetry::new / Default ──────────────────────────────
@@ -913,6 +1323,7 @@ mod tests {
             },
             car_id: true,
             track_id: false,
+            timing: Default::default(),
             extended_fields: vec!["fuel".to_string(), "tire_temp".to_string()],
         };
         let json = serde_json::to_string(&coverage)?;
@@ -937,4 +1348,134 @@ mod tests {
         assert_eq!(t, decoded);
         Ok(())
     }
+
+    // ── NormalizedTelemetryBuilder ──────────────────────────────────────
+
+    #[test]
+    fn builder_populates_chassis_fields() {
+        let t = NormalizedTelemetry::builder()
+            .speed_ms(40.0)
+            .steering_angle(-0.2)
+            .throttle(0.6)
+            .brake(0.1)
+            .lateral_g(1.4)
+            .longitudinal_g(-0.8)
+            .slip_angles([0.01, 0.02, 0.03, 0.04])
+            .build();
+        assert_eq!(t.speed_ms, Some(40.0));
+        assert_eq!(t.steering_angle, Some(-0.2));
+        assert_eq!(t.throttle, Some(0.6));
+        assert_eq!(t.brake, Some(0.1));
+        assert_eq!(t.lateral_g, Some(1.4));
+        assert_eq!(t.longitudinal_g, Some(-0.8));
+        assert_eq!(t.slip_angle_fl, Some(0.01));
+        assert_eq!(t.slip_angle_rr, Some(0.04));
+    }
+
+    #[test]
+    fn builder_applies_with_method_rules() {
+        let t = NormalizedTelemetry::builder()
+            .ffb_scalar(3.0)
+            .rpm(-100.0)
+            .speed_ms(f32::NAN)
+            .throttle(1.5)
+            .brake(-0.5)
+            .steering_angle(f32::INFINITY)
+            .slip_angle_fr(f32::NAN)
+            .car_id("")
+            .build();
+        assert_eq!(t.ffb_scalar, Some(1.0));
+        assert_eq!(t.rpm, None);
+        assert_eq!(t.speed_ms, None);
+        assert_eq!(t.throttle, Some(1.0));
+        assert_eq!(t.brake, Some(0.0));
+        assert_eq!(t.steering_angle, None);
+        assert_eq!(t.slip_angle_fr, None);
+        assert_eq!(t.car_id, None);
+        assert_eq!(
+            t,
+            NormalizedTelemetry::new()
+                .with_ffb_scalar(3.0)
+                .with_throttle(1.5)
+                .with_brake(-0.5)
+        );
+    }
+
+    // ── SessionTiming ───────────────────────────────────────────────────
+
+    #[test]
+    fn with_timing_drops_invalid_values() {
+        let t = NormalizedTelemetry::new().with_timing(SessionTiming {
+            current_lap_ms: Some(f64::NAN),
+            last_lap_ms: Some(-1.0),
+            best_lap_ms: Some(f64::INFINITY),
+            lap_number: Some(-2),
+            sector: Some(1),
+            session_time_remaining_ms: Some(600_000.0),
+        });
+        assert_eq!(
+            t.timing,
+            Some(SessionTiming {
+                sector: Some(1),
+                session_time_remaining_ms: Some(600_000.0),
+                ..SessionTiming::default()
+            })
+        );
+    }
+
+    #[test]
+    fn with_timing_leaves_field_unset_when_nothing_is_valid() {
+        let t = NormalizedTelemetry::new().with_timing(SessionTiming {
+            current_lap_ms: Some(f64::NAN),
+            lap_number: Some(-1),
+            ..SessionTiming::default()
+        });
+        assert!(t.timing.is_none());
+    }
+
+    #[test]
+    fn timing_serde_round_trip_skips_unset_fields() -> Result<(), Box<dyn std::error::Error>> {
+        let t = NormalizedTelemetry::new().with_timing(SessionTiming {
+            current_lap_ms: Some(42_300.0),
+            lap_number: Some(9),
+            ..SessionTiming::default()
+        });
+        let json = serde_json::to_value(&t)?;
+        assert_eq!(
+            json["timing"],
+            serde_json::json!({"current_lap_ms": 42300.0, "lap_number": 9})
+        );
+        let decoded: NormalizedTelemetry = serde_json::from_value(json)?;
+        assert_eq!(decoded, t);
+        Ok(())
+    }
+
+    #[test]
+    fn json_without_timing_still_deserializes() -> Result<(), Box<dyn std::error::Error>> {
+        let telemetry: NormalizedTelemetry = serde_json::from_str(
+            r#"{"ffb_scalar":null,"rpm":5000.0,"speed_ms":null,"slip_ratio":null,"gear":3,
+                "flags":{"yellow_flag":false,"red_flag":false,"blue_flag":false,
+                "checkered_flag":false,"green_flag":true,"pit_limiter":false,"in_pits":false,
+                "drs_available":false,"drs_active":false,"ers_available":false,
+                "launch_control":false,"traction_control":false,"abs_active":false},
+                "car_id":null,"track_id":null,"extended":{}}"#,
+        )?;
+        assert_eq!(telemetry.rpm, Some(5000.0));
+        assert!(telemetry.timing.is_none());
+        assert!(telemetry.steering_angle.is_none());
+        assert!(telemetry.lateral_g.is_none());
+        assert!(telemetry.slip_angle_rr.is_none());
+
+        let coverage: TelemetryFieldCoverage = serde_json::from_str(
+            r#"{"game_id":"acc","game_version":"1.9","ffb_scalar":false,"rpm":true,
+                "speed":true,"slip_ratio":false,"gear":true,
+                "flags":{"yellow_flag":false,"red_flag":false,"blue_flag":false,
+                "checkered_flag":false,"green_flag":false,"pit_limiter":true,"in_pits":true,
+                "drs_available":false,"drs_active":false,"ers_available":false,
+                "launch_control":false,"traction_control":false,"abs_active":false},
+                "car_id":true,"track_id":true,"extended_fields":[]}"#,
+        )?;
+        assert_eq!(coverage.timing, TimingCoverage::default());
+        Ok(())
+    }
 }
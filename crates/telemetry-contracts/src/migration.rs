@@ -0,0 +1,282 @@
+//! Schema versioning for recorded telemetry frames.
+//!
+//! Every serialized frame carries a `version`. Frames written before the field
+//! existed read as version 1. [`upgrade_frame`] rewrites an older frame's JSON
+//! one step at a time through [`FRAME_MIGRATIONS`] until it reaches
+//! [`TELEMETRY_SCHEMA_VERSION`], so replay tooling only ever decodes the
+//! current shape. A frame newer than this build understands is rejected
+//! instead of being decoded with its unknown fields dropped.
+
+use serde_json::{Map, Value};
+use std::fmt;
+
+use crate::{TelemetryFlags, TelemetryFrame, lookup_key};
+
+/// Schema version stamped on frames written by this build.
+pub const TELEMETRY_SCHEMA_VERSION: u32 = 4;
+
+/// Version assumed for frames recorded before frames were versioned.
+pub const LEGACY_FRAME_VERSION: u32 = 1;
+
+pub(crate) fn legacy_frame_version() -> u32 {
+    LEGACY_FRAME_VERSION
+}
+
+/// Why a recorded frame could not be brought up to the current schema.
+#[derive(Debug)]
+pub enum FrameMigrationError {
+    /// The frame, or its `data`, is not a JSON object.
+    NotAnObject,
+    /// `version` is present but not a non-negative integer.
+    InvalidVersion(Value),
+    /// The frame was written by a newer build than this one.
+    FutureVersion { found: u32, supported: u32 },
+    /// No migration step starts at this version.
+    NoMigrationFrom(u32),
+    /// The upgraded frame does not decode as the current struct.
+    Decode(serde_json::Error),
+}
+
+impl fmt::Display for FrameMigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotAnObject => f.write_str("telemetry frame is not a JSON object"),
+            Self::InvalidVersion(value) => {
+                write!(f, "telemetry frame version {value} is not a valid version")
+            }
+            Self::FutureVersion { found, supported } => write!(
+                f,
+                "telemetry frame schema version {found} is newer than the supported \
+                 version {supported}; read it with a newer OpenRacing build"
+            ),
+            Self::NoMigrationFrom(version) => {
+                write!(
+                    f,
+                    "no migration from telemetry frame schema version {version}"
+                )
+            }
+            Self::Decode(error) => write!(f, "telemetry frame does not decode: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for FrameMigrationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Decode(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for FrameMigrationError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Decode(error)
+    }
+}
+
+/// Rewrites a frame of version `from` into the shape of version `from + 1`.
+#[derive(Debug, Clone, Copy)]
+pub struct MigrationStep {
+    /// Version this step upgrades from.
+    pub from: u32,
+    /// What changed in the shape, for logs and docs.
+    pub description: &'static str,
+    /// Rewrite the frame object in place.
+    pub apply: fn(&mut Map<String, Value>) -> Result<(), FrameMigrationError>,
+}
+
+/// Migration steps in version order, one per schema version bump.
+pub const FRAME_MIGRATIONS: &[MigrationStep] = &[
+    MigrationStep {
+        from: 1,
+        description: "fill flags missing from early recordings and rename deprecated extended keys",
+        apply: migrate_v1_to_v2,
+    },
+    MigrationStep {
+        from: 2,
+        description: "add the opponents list",
+        apply: migrate_v2_to_v3,
+    },
+    MigrationStep {
+        from: 3,
+        description: "add world position and lap distance fraction",
+        apply: migrate_v3_to_v4,
+    },
+];
+
+/// Upgrade a serialized frame to [`TELEMETRY_SCHEMA_VERSION`], returning the
+/// rewritten JSON with `version` set to the current one.
+pub fn upgrade_frame(value: Value) -> Result<Value, FrameMigrationError> {
+    upgrade_with(value, FRAME_MIGRATIONS, TELEMETRY_SCHEMA_VERSION)
+}
+
+/// Upgrade a serialized frame and decode it as the current [`TelemetryFrame`].
+pub fn migrate_frame(value: Value) -> Result<TelemetryFrame, FrameMigrationError> {
+    Ok(serde_json::from_value(upgrade_frame(value)?)?)
+}
+
+fn upgrade_with(
+    value: Value,
+    steps: &[MigrationStep],
+    current: u32,
+) -> Result<Value, FrameMigrationError> {
+    let Value::Object(mut frame) = value else {
+        return Err(FrameMigrationError::NotAnObject);
+    };
+    let mut version = frame_version(&frame)?;
+    if version > current {
+        return Err(FrameMigrationError::FutureVersion {
+            found: version,
+            supported: current,
+        });
+    }
+    while version < current {
+        let step = steps
+            .iter()
+            .find(|step| step.from == version)
+            .ok_or(FrameMigrationError::NoMigrationFrom(version))?;
+        (step.apply)(&mut frame)?;
+        version += 1;
+    }
+    frame.insert("version".to_string(), Value::from(current));
+    Ok(Value::Object(frame))
+}
+
+fn frame_version(frame: &Map<String, Value>) -> Result<u32, FrameMigrationError> {
+    match frame.get("version") {
+        None | Some(Value::Null) => Ok(LEGACY_FRAME_VERSION),
+        Some(value) => value
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| FrameMigrationError::InvalidVersion(value.clone())),
+    }
+}
+
+/// Early recordings omitted flags that did not exist yet, and wrote tyre
+/// temperatures under the British-spelled keys that are now aliases.
+fn migrate_v1_to_v2(frame: &mut Map<String, Value>) -> Result<(), FrameMigrationError> {
+    let Some(Value::Object(data)) = frame.get_mut("data") else {
+        return Err(FrameMigrationError::NotAnObject);
+    };
+
+    let Value::Object(defaults) = serde_json::to_value(TelemetryFlags::default())? else {
+        return Err(FrameMigrationError::NotAnObject);
+    };
+    let flags = data
+        .entry("flags")
+        .or_insert_with(|| Value::Object(Map::new()));
+    let Value::Object(flags) = flags else {
+        return Err(FrameMigrationError::NotAnObject);
+    };
+    for (name, default) in defaults {
+        flags.entry(name).or_insert(default);
+    }
+
+    if let Some(Value::Object(extended)) = data.get_mut("extended") {
+        let renames: Vec<(String, &'static str)> = extended
+            .keys()
+            .filter_map(|name| {
+                let canonical = lookup_key(name)?.canonical().ok()?;
+                (canonical.name != name).then(|| (name.clone(), canonical.name))
+            })
+            .collect();
+        for (alias, canonical) in renames {
+            if let Some(value) = extended.remove(&alias) {
+                // A frame that already has the canonical key keeps it.
+                extended.entry(canonical).or_insert(value);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Frames gained a list of other cars; older recordings have none.
+fn migrate_v2_to_v3(frame: &mut Map<String, Value>) -> Result<(), FrameMigrationError> {
+    frame
+        .entry("opponents")
+        .or_insert_with(|| Value::Array(Vec::new()));
+    Ok(())
+}
+
+/// Telemetry gained optional `world_position` and `lap_distance_fraction`.
+/// Older recordings read as unset, so the JSON needs no rewrite; the bump is
+/// for the binary layout.
+fn migrate_v3_to_v4(_frame: &mut Map<String, Value>) -> Result<(), FrameMigrationError> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+    fn append(frame: &mut Map<String, Value>, step: &str) {
+        let trail = frame.get("trail").and_then(Value::as_str).unwrap_or("");
+        let trail = format!("{trail}{step}");
+        frame.insert("trail".to_string(), Value::from(trail));
+    }
+
+    fn append_a(frame: &mut Map<String, Value>) -> Result<(), FrameMigrationError> {
+        append(frame, "a");
+        Ok(())
+    }
+
+    fn append_b(frame: &mut Map<String, Value>) -> Result<(), FrameMigrationError> {
+        append(frame, "b");
+        Ok(())
+    }
+
+    #[test]
+    fn steps_run_in_version_order() -> TestResult {
+        // Registered out of order: the runner looks steps up by version.
+        let steps = [
+            MigrationStep {
+                from: 2,
+                description: "second",
+                apply: append_b,
+            },
+            MigrationStep {
+                from: 1,
+                description: "first",
+                apply: append_a,
+            },
+        ];
+        let upgraded = upgrade_with(json!({}), &steps, 3)?;
+        assert_eq!(upgraded, json!({"trail": "ab", "version": 3}));
+
+        let from_two = upgrade_with(json!({"version": 2}), &steps, 3)?;
+        assert_eq!(from_two, json!({"trail": "b", "version": 3}));
+        Ok(())
+    }
+
+    #[test]
+    fn missing_step_is_reported() {
+        let result = upgrade_with(json!({"version": 1}), &[], 2);
+        assert!(matches!(
+            result,
+            Err(FrameMigrationError::NoMigrationFrom(1))
+        ));
+    }
+
+    #[test]
+    fn non_integer_version_is_rejected() {
+        let result = upgrade_frame(json!({"version": "2"}));
+        assert!(matches!(
+            result,
+            Err(FrameMigrationError::InvalidVersion(_))
+        ));
+    }
+
+    #[test]
+    fn registry_reaches_the_current_version() {
+        for version in LEGACY_FRAME_VERSION..TELEMETRY_SCHEMA_VERSION {
+            assert!(
+                FRAME_MIGRATIONS.iter().any(|step| step.from == version),
+                "no migration from version {version}"
+            );
+        }
+    }
+}
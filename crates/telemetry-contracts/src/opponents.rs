@@ -0,0 +1,59 @@
+//! Other cars in the session, for relative and standings overlays.
+//!
+//! [`NormalizedTelemetry`](crate::NormalizedTelemetry) describes the player's
+//! car only. Games that broadcast the whole grid fill
+//! [`TelemetryFrame::opponents`](crate::TelemetryFrame::opponents) with one
+//! [`OpponentSnapshot`] per other car, sorted by position and bounded with
+//! [`bound_opponents`] so a full field does not bloat every frame.
+
+use serde::{Deserialize, Serialize};
+
+/// Opponents kept per frame unless an adapter is configured otherwise.
+pub const DEFAULT_MAX_OPPONENTS: usize = 24;
+
+/// One other car, as of the frame it is attached to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "binary", derive(bincode::Encode, bincode::Decode))]
+pub struct OpponentSnapshot {
+    /// The game's index for the car, stable for the session.
+    pub car_index: u16,
+    /// Race position, 1 for the leader.
+    pub position: u8,
+    /// Time gap to the player in milliseconds, positive when the opponent is
+    /// ahead on track; `None` when the game gives nothing to estimate it from.
+    pub gap_to_player_ms: Option<i32>,
+    pub speed_ms: f32,
+    /// Lap the car is on, counting from 1.
+    pub lap_number: u16,
+    pub in_pits: bool,
+    pub driver_name: Option<String>,
+}
+
+/// Sort `opponents` by position and keep at most `max` of them.
+///
+/// When the grid is larger than `max`, the cars nearest the player by
+/// position are kept, preferring the car ahead on a tie; without a player
+/// position the leaders are kept. Equal positions order by `car_index`, so
+/// the same grid always yields the same list.
+pub fn bound_opponents(
+    mut opponents: Vec<OpponentSnapshot>,
+    player_position: Option<u8>,
+    max: usize,
+) -> Vec<OpponentSnapshot> {
+    if opponents.len() > max {
+        match player_position {
+            Some(player) => opponents.sort_by_key(|opponent| {
+                let behind = opponent.position > player;
+                (
+                    opponent.position.abs_diff(player),
+                    behind,
+                    opponent.car_index,
+                )
+            }),
+            None => opponents.sort_by_key(|opponent| (opponent.position, opponent.car_index)),
+        }
+        opponents.truncate(max);
+    }
+    opponents.sort_by_key(|opponent| (opponent.position, opponent.car_index));
+    opponents
+}
@@ -0,0 +1,277 @@
+//! Display units and ready-to-render telemetry values.
+//!
+//! Telemetry stays in its canonical units (m/s, kPa, Celsius); a
+//! [`UnitsProfile`] chooses what a frontend shows, and
+//! [`TelemetryReadings::formatted`] converts and formats once so every
+//! frontend agrees. The conversion factors here are exact and are the same
+//! ones the `speed_kmh`/`speed_mph` helpers use.
+
+use serde::{Deserialize, Serialize};
+
+/// km/h per m/s.
+pub const KMH_PER_MS: f32 = 3.6;
+/// Metres in an international mile.
+pub const METERS_PER_MILE: f32 = 1609.344;
+/// mph per m/s.
+pub const MPH_PER_MS: f32 = 3600.0 / METERS_PER_MILE;
+/// kPa per bar.
+pub const KPA_PER_BAR: f32 = 100.0;
+/// kPa per psi (one pound-force per square inch).
+pub const KPA_PER_PSI: f32 = 6.894_757_3;
+
+/// Decimal places of each formatted quantity.
+const SPEED_DECIMALS: usize = 0;
+const RPM_DECIMALS: usize = 0;
+const FFB_DECIMALS: usize = 2;
+const SLIP_DECIMALS: usize = 0;
+const PRESSURE_DECIMALS: usize = 1;
+const TEMPERATURE_DECIMALS: usize = 0;
+
+/// Unit speeds are shown in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpeedUnit {
+    MetersPerSecond,
+    KilometersPerHour,
+    MilesPerHour,
+}
+
+impl SpeedUnit {
+    /// Convert `meters_per_second` to this unit.
+    pub fn from_meters_per_second(self, meters_per_second: f32) -> f32 {
+        match self {
+            Self::MetersPerSecond => meters_per_second,
+            Self::KilometersPerHour => meters_per_second * KMH_PER_MS,
+            Self::MilesPerHour => meters_per_second * MPH_PER_MS,
+        }
+    }
+
+    pub const fn suffix(self) -> &'static str {
+        match self {
+            Self::MetersPerSecond => "m/s",
+            Self::KilometersPerHour => "km/h",
+            Self::MilesPerHour => "mph",
+        }
+    }
+}
+
+/// Unit pressures are shown in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PressureUnit {
+    Kilopascal,
+    Bar,
+    Psi,
+}
+
+impl PressureUnit {
+    /// Convert `kilopascals` to this unit.
+    pub fn from_kilopascals(self, kilopascals: f32) -> f32 {
+        match self {
+            Self::Kilopascal => kilopascals,
+            Self::Bar => kilopascals / KPA_PER_BAR,
+            Self::Psi => kilopascals / KPA_PER_PSI,
+        }
+    }
+
+    pub const fn suffix(self) -> &'static str {
+        match self {
+            Self::Kilopascal => "kPa",
+            Self::Bar => "bar",
+            Self::Psi => "psi",
+        }
+    }
+}
+
+/// Unit temperatures are shown in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemperatureUnit {
+    Celsius,
+    Fahrenheit,
+}
+
+impl TemperatureUnit {
+    /// Convert `celsius` to this unit.
+    pub fn from_celsius(self, celsius: f32) -> f32 {
+        match self {
+            Self::Celsius => celsius,
+            Self::Fahrenheit => celsius * 1.8 + 32.0,
+        }
+    }
+
+    pub const fn suffix(self) -> &'static str {
+        match self {
+            Self::Celsius => "°C",
+            Self::Fahrenheit => "°F",
+        }
+    }
+}
+
+/// Units a user wants telemetry shown in, one per quantity.
+///
+/// Start from [`METRIC`](Self::METRIC) or [`IMPERIAL`](Self::IMPERIAL) and
+/// override single quantities with the `with_*` methods. Serializes as a
+/// plain object so apps can persist it; missing fields read as metric.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UnitsProfile {
+    pub speed: SpeedUnit,
+    pub pressure: PressureUnit,
+    pub temperature: TemperatureUnit,
+}
+
+impl UnitsProfile {
+    /// km/h, kPa and Celsius.
+    pub const METRIC: Self = Self {
+        speed: SpeedUnit::KilometersPerHour,
+        pressure: PressureUnit::Kilopascal,
+        temperature: TemperatureUnit::Celsius,
+    };
+
+    /// mph, psi and Fahrenheit.
+    pub const IMPERIAL: Self = Self {
+        speed: SpeedUnit::MilesPerHour,
+        pressure: PressureUnit::Psi,
+        temperature: TemperatureUnit::Fahrenheit,
+    };
+
+    pub const fn with_speed(mut self, unit: SpeedUnit) -> Self {
+        self.speed = unit;
+        self
+    }
+
+    pub const fn with_pressure(mut self, unit: PressureUnit) -> Self {
+        self.pressure = unit;
+        self
+    }
+
+    pub const fn with_temperature(mut self, unit: TemperatureUnit) -> Self {
+        self.temperature = unit;
+        self
+    }
+}
+
+impl Default for UnitsProfile {
+    fn default() -> Self {
+        Self::METRIC
+    }
+}
+
+/// A converted value and its rendering.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormattedValue {
+    /// The value in the profile's unit, unrounded.
+    pub value: f32,
+    /// Unit suffix; empty for unitless values.
+    pub unit: String,
+    /// `value` rounded to the quantity's precision, then the unit after a
+    /// space. Never uses thousands separators.
+    pub text: String,
+}
+
+impl FormattedValue {
+    fn new(value: f32, decimals: usize, unit: &str) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+        let mut number = format!("{value:.decimals$}");
+        // Rounding a small negative value must not show "-0".
+        if number.starts_with('-') && number[1..].bytes().all(|b| b == b'0' || b == b'.') {
+            number.remove(0);
+        }
+        let text = if unit.is_empty() {
+            number
+        } else {
+            format!("{number} {unit}")
+        };
+        Some(Self {
+            value,
+            unit: unit.to_string(),
+            text,
+        })
+    }
+}
+
+/// Telemetry converted to a [`UnitsProfile`] and formatted for display.
+///
+/// A value is `None` when the source did not report it or it is not finite.
+/// Tire arrays are ordered FL, FR, RL, RR.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct FormattedTelemetry {
+    /// The profile the values were converted to.
+    pub profile: UnitsProfile,
+    /// Speed, no decimals.
+    pub speed: Option<FormattedValue>,
+    /// Engine speed, no decimals.
+    pub rpm: Option<FormattedValue>,
+    /// Force feedback scalar, two decimals.
+    pub ffb: Option<FormattedValue>,
+    /// Slip ratio as a percentage, no decimals.
+    pub slip: Option<FormattedValue>,
+    /// Tire pressures, one decimal.
+    pub tire_pressures: [Option<FormattedValue>; 4],
+    /// Tire temperatures, no decimals.
+    pub tire_temps: [Option<FormattedValue>; 4],
+}
+
+/// Canonical-unit values [`FormattedTelemetry`] is built from.
+///
+/// Each telemetry type fills this from its own fields, so formatting and
+/// conversion live in one place.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TelemetryReadings {
+    pub speed_ms: Option<f32>,
+    pub rpm: Option<f32>,
+    pub ffb_scalar: Option<f32>,
+    /// 0.0 = no slip, 1.0 = full slip.
+    pub slip_ratio: Option<f32>,
+    pub tire_pressures_kpa: [Option<f32>; 4],
+    pub tire_temps_c: [Option<f32>; 4],
+}
+
+impl TelemetryReadings {
+    /// Convert every reading to `profile` and format it.
+    pub fn formatted(&self, profile: &UnitsProfile) -> FormattedTelemetry {
+        let speed = profile.speed;
+        let pressure = profile.pressure;
+        let temperature = profile.temperature;
+        FormattedTelemetry {
+            profile: *profile,
+            speed: self.speed_ms.and_then(|ms| {
+                FormattedValue::new(
+                    speed.from_meters_per_second(ms),
+                    SPEED_DECIMALS,
+                    speed.suffix(),
+                )
+            }),
+            rpm: self
+                .rpm
+                .and_then(|rpm| FormattedValue::new(rpm, RPM_DECIMALS, "rpm")),
+            ffb: self
+                .ffb_scalar
+                .and_then(|ffb| FormattedValue::new(ffb, FFB_DECIMALS, "")),
+            slip: self
+                .slip_ratio
+                .and_then(|slip| FormattedValue::new(slip * 100.0, SLIP_DECIMALS, "%")),
+            tire_pressures: self.tire_pressures_kpa.map(|kpa| {
+                kpa.and_then(|kpa| {
+                    FormattedValue::new(
+                        pressure.from_kilopascals(kpa),
+                        PRESSURE_DECIMALS,
+                        pressure.suffix(),
+                    )
+                })
+            }),
+            tire_temps: self.tire_temps_c.map(|celsius| {
+                celsius.and_then(|celsius| {
+                    FormattedValue::new(
+                        temperature.from_celsius(celsius),
+                        TEMPERATURE_DECIMALS,
+                        temperature.suffix(),
+                    )
+                })
+            }),
+        }
+    }
+}
@@ -0,0 +1,364 @@
+//! Validation of telemetry built outside the checked `with_*` setters.
+//!
+//! The builders reject or clamp bad readings, but adapters that fill
+//! telemetry with struct literals skip them. A [`TelemetryValidator`] applies
+//! the same rules to a finished frame: [`validate`](TelemetryValidator::validate)
+//! reports what is wrong, [`sanitize`](TelemetryValidator::sanitize) fixes it,
+//! and [`apply`](TelemetryValidator::apply) does whichever the validator's
+//! [`ValidationPolicy`] asks for. The rules only touch readings that break
+//! them, so a clean frame comes out unchanged.
+
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use serde::{Deserialize, Serialize};
+
+use crate::extended_keys::{ExtendedFields, ExtendedKey};
+
+/// Gears a validator accepts unless configured otherwise.
+pub const DEFAULT_GEAR_RANGE: RangeInclusive<i8> = -2..=12;
+
+/// Float reading a validator checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FloatField {
+    FfbScalar,
+    Rpm,
+    SpeedMs,
+    SlipRatio,
+    SteeringAngle,
+    Throttle,
+    Brake,
+    LateralG,
+    LongitudinalG,
+    SlipAngleFl,
+    SlipAngleFr,
+    SlipAngleRl,
+    SlipAngleRr,
+}
+
+/// How a reading breaking its rule is fixed.
+enum Rule {
+    /// Any finite value.
+    Finite,
+    /// Negative values are unset.
+    NonNegative,
+    /// Clamped into the range.
+    Clamp(f32, f32),
+}
+
+impl FloatField {
+    /// Every checked reading, in validation order.
+    pub const ALL: [Self; 13] = [
+        Self::FfbScalar,
+        Self::Rpm,
+        Self::SpeedMs,
+        Self::SlipRatio,
+        Self::SteeringAngle,
+        Self::Throttle,
+        Self::Brake,
+        Self::LateralG,
+        Self::LongitudinalG,
+        Self::SlipAngleFl,
+        Self::SlipAngleFr,
+        Self::SlipAngleRl,
+        Self::SlipAngleRr,
+    ];
+
+    /// Field name as it appears on `NormalizedTelemetry`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::FfbScalar => "ffb_scalar",
+            Self::Rpm => "rpm",
+            Self::SpeedMs => "speed_ms",
+            Self::SlipRatio => "slip_ratio",
+            Self::SteeringAngle => "steering_angle",
+            Self::Throttle => "throttle",
+            Self::Brake => "brake",
+            Self::LateralG => "lateral_g",
+            Self::LongitudinalG => "longitudinal_g",
+            Self::SlipAngleFl => "slip_angle_fl",
+            Self::SlipAngleFr => "slip_angle_fr",
+            Self::SlipAngleRl => "slip_angle_rl",
+            Self::SlipAngleRr => "slip_angle_rr",
+        }
+    }
+
+    /// The rule of the matching `with_*` setter.
+    fn rule(self) -> Rule {
+        match self {
+            Self::FfbScalar => Rule::Clamp(-1.0, 1.0),
+            Self::Rpm | Self::SpeedMs => Rule::NonNegative,
+            Self::SlipRatio | Self::Throttle | Self::Brake => Rule::Clamp(0.0, 1.0),
+            Self::SteeringAngle
+            | Self::LateralG
+            | Self::LongitudinalG
+            | Self::SlipAngleFl
+            | Self::SlipAngleFr
+            | Self::SlipAngleRl
+            | Self::SlipAngleRr => Rule::Finite,
+        }
+    }
+}
+
+/// Telemetry types a [`TelemetryValidator`] can check.
+///
+/// Implemented for the contracts [`NormalizedTelemetry`](crate::NormalizedTelemetry)
+/// here and for the canonical schemas type in `racing-wheel-schemas`.
+pub trait ValidatedFields: ExtendedFields {
+    /// The reading, or `None` if the type can leave it unset and it is.
+    fn float_field(&self, field: FloatField) -> Option<f32>;
+
+    /// Replace a reading; `None` unsets it, or zeroes it for types that
+    /// cannot leave it unset.
+    fn set_float_field(&mut self, field: FloatField, value: Option<f32>);
+
+    fn gear_field(&self) -> Option<i8>;
+
+    fn set_gear_field(&mut self, gear: i8);
+}
+
+/// What is wrong with a reading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    /// NaN or infinite; sanitizing unsets it.
+    NotFinite,
+    /// Below zero where only positive values make sense; sanitizing unsets it.
+    Negative,
+    /// Outside the field's range; sanitizing clamps it.
+    OutOfRange,
+}
+
+impl IssueKind {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::NotFinite => "not_finite",
+            Self::Negative => "negative",
+            Self::OutOfRange => "out_of_range",
+        }
+    }
+}
+
+/// One reading that broke its rule.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ValidationIssue {
+    /// Field name as it appears on `NormalizedTelemetry`.
+    pub field: &'static str,
+    pub kind: IssueKind,
+    /// The reading as received; gears are widened to `f64`.
+    pub observed: f64,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}({})",
+            self.field,
+            self.kind.as_str(),
+            self.observed
+        )
+    }
+}
+
+/// What [`TelemetryValidator::apply`] does with a frame that has issues.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationPolicy {
+    /// Drop the whole frame.
+    Reject,
+    /// Fix the offending readings as [`TelemetryValidator::sanitize`] does.
+    #[default]
+    Sanitize,
+    /// Forward the frame unchanged, listing its issues under
+    /// [`ExtendedKey::VALIDATION_ISSUES`].
+    PassThrough,
+}
+
+/// A frame after [`TelemetryValidator::apply`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationOutcome<T> {
+    /// The frame to forward; `None` if the policy rejected it.
+    pub telemetry: Option<T>,
+    /// Issues found before the policy was applied.
+    pub issues: Vec<ValidationIssue>,
+}
+
+/// Checks telemetry against the rules of the `with_*` setters, plus a gear
+/// range the setters do not enforce.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TelemetryValidator {
+    gear_range: RangeInclusive<i8>,
+    policy: ValidationPolicy,
+}
+
+impl Default for TelemetryValidator {
+    fn default() -> Self {
+        Self {
+            gear_range: DEFAULT_GEAR_RANGE,
+            policy: ValidationPolicy::default(),
+        }
+    }
+}
+
+impl TelemetryValidator {
+    /// Sanitizing validator accepting [`DEFAULT_GEAR_RANGE`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept gears in `range`; others are clamped into it.
+    pub fn with_gear_range(mut self, range: RangeInclusive<i8>) -> Self {
+        self.gear_range = range;
+        self
+    }
+
+    pub fn with_policy(mut self, policy: ValidationPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn gear_range(&self) -> &RangeInclusive<i8> {
+        &self.gear_range
+    }
+
+    pub fn policy(&self) -> ValidationPolicy {
+        self.policy
+    }
+
+    /// Every reading of `telemetry` that breaks its rule. Empty for a clean
+    /// frame, without allocating.
+    pub fn validate<T: ValidatedFields + ?Sized>(&self, telemetry: &T) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        for field in FloatField::ALL {
+            let Some(value) = telemetry.float_field(field) else {
+                continue;
+            };
+            if let Some(kind) = float_issue(field, value) {
+                issues.push(ValidationIssue {
+                    field: field.name(),
+                    kind,
+                    observed: f64::from(value),
+                });
+            }
+        }
+        if let Some(gear) = telemetry.gear_field()
+            && !self.gear_range.contains(&gear)
+        {
+            issues.push(ValidationIssue {
+                field: "gear",
+                kind: IssueKind::OutOfRange,
+                observed: f64::from(gear),
+            });
+        }
+        issues
+    }
+
+    /// `telemetry` with every reading that breaks its rule unset or clamped.
+    pub fn sanitize<T: ValidatedFields>(&self, mut telemetry: T) -> T {
+        for field in FloatField::ALL {
+            let Some(value) = telemetry.float_field(field) else {
+                continue;
+            };
+            if float_issue(field, value).is_none() {
+                continue;
+            }
+            let fixed = match field.rule() {
+                Rule::Clamp(min, max) if value.is_finite() => Some(value.clamp(min, max)),
+                _ => None,
+            };
+            telemetry.set_float_field(field, fixed);
+        }
+        if let Some(gear) = telemetry.gear_field()
+            && !self.gear_range.contains(&gear)
+        {
+            telemetry.set_gear_field(gear.clamp(*self.gear_range.start(), *self.gear_range.end()));
+        }
+        telemetry
+    }
+
+    /// Validate `telemetry` and handle its issues as the policy says.
+    pub fn apply<T: ValidatedFields>(&self, mut telemetry: T) -> ValidationOutcome<T> {
+        let issues = self.validate(&telemetry);
+        if issues.is_empty() {
+            return ValidationOutcome {
+                telemetry: Some(telemetry),
+                issues,
+            };
+        }
+        let telemetry = match self.policy {
+            ValidationPolicy::Reject => None,
+            ValidationPolicy::Sanitize => Some(self.sanitize(telemetry)),
+            ValidationPolicy::PassThrough => {
+                let listed = issues
+                    .iter()
+                    .map(ValidationIssue::to_string)
+                    .collect::<Vec<_>>()
+                    .join(",");
+                // The key is declared as a string, so this cannot mismatch.
+                let _ = ExtendedKey::VALIDATION_ISSUES.set(&mut telemetry, listed);
+                Some(telemetry)
+            }
+        };
+        ValidationOutcome { telemetry, issues }
+    }
+}
+
+fn float_issue(field: FloatField, value: f32) -> Option<IssueKind> {
+    if !value.is_finite() {
+        return Some(IssueKind::NotFinite);
+    }
+    match field.rule() {
+        Rule::Finite => None,
+        Rule::NonNegative => (value < 0.0).then_some(IssueKind::Negative),
+        Rule::Clamp(min, max) => (!(min..=max).contains(&value)).then_some(IssueKind::OutOfRange),
+    }
+}
+
+impl ValidatedFields for crate::NormalizedTelemetry {
+    fn float_field(&self, field: FloatField) -> Option<f32> {
+        match field {
+            FloatField::FfbScalar => self.ffb_scalar,
+            FloatField::Rpm => self.rpm,
+            FloatField::SpeedMs => self.speed_ms,
+            FloatField::SlipRatio => self.slip_ratio,
+            FloatField::SteeringAngle => self.steering_angle,
+            FloatField::Throttle => self.throttle,
+            FloatField::Brake => self.brake,
+            FloatField::LateralG => self.lateral_g,
+            FloatField::LongitudinalG => self.longitudinal_g,
+            FloatField::SlipAngleFl => self.slip_angle_fl,
+            FloatField::SlipAngleFr => self.slip_angle_fr,
+            FloatField::SlipAngleRl => self.slip_angle_rl,
+            FloatField::SlipAngleRr => self.slip_angle_rr,
+        }
+    }
+
+    fn set_float_field(&mut self, field: FloatField, value: Option<f32>) {
+        let slot = match field {
+            FloatField::FfbScalar => &mut self.ffb_scalar,
+            FloatField::Rpm => &mut self.rpm,
+            FloatField::SpeedMs => &mut self.speed_ms,
+            FloatField::SlipRatio => &mut self.slip_ratio,
+            FloatField::SteeringAngle => &mut self.steering_angle,
+            FloatField::Throttle => &mut self.throttle,
+            FloatField::Brake => &mut self.brake,
+            FloatField::LateralG => &mut self.lateral_g,
+            FloatField::LongitudinalG => &mut self.longitudinal_g,
+            FloatField::SlipAngleFl => &mut self.slip_angle_fl,
+            FloatField::SlipAngleFr => &mut self.slip_angle_fr,
+            FloatField::SlipAngleRl => &mut self.slip_angle_rl,
+            FloatField::SlipAngleRr => &mut self.slip_angle_rr,
+        };
+        *slot = value;
+    }
+
+    fn gear_field(&self) -> Option<i8> {
+        self.gear
+    }
+
+    fn set_gear_field(&mut self, gear: i8) {
+        self.gear = Some(gear);
+    }
+}
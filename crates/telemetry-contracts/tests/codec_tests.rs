@@ -0,0 +1,132 @@
+//! The binary codec round-trips frames and fails cleanly on damaged input.
+#![cfg(feature = "binary")]
+
+use racing_wheel_telemetry_contracts::{
+    BinaryCodecError, FrameReader, FrameWriter, NormalizedTelemetry, SessionTiming, TelemetryFlags,
+    TelemetryFrame, TelemetryValue, decode_frame, encode_frame,
+};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn representative_frame(sequence: u64) -> TelemetryFrame {
+    let telemetry = NormalizedTelemetry::builder()
+        .ffb_scalar(0.42)
+        .rpm(7350.0)
+        .speed_ms(61.2)
+        .slip_ratio(0.04)
+        .gear(5)
+        .steering_angle(-0.18)
+        .throttle(0.97)
+        .brake(0.0)
+        .lateral_g(1.6)
+        .longitudinal_g(-0.3)
+        .slip_angles([0.02, 0.021, 0.015, 0.016])
+        .flags(TelemetryFlags {
+            drs_available: true,
+            traction_control: true,
+            ..TelemetryFlags::default()
+        })
+        .car_id("ferrari_296_gt3")
+        .track_id("spa")
+        .timing(SessionTiming {
+            current_lap_ms: Some(83_412.5),
+            best_lap_ms: Some(137_905.0),
+            lap_number: Some(12),
+            sector: Some(2),
+            ..SessionTiming::default()
+        })
+        .extended("tire_temp_fl", TelemetryValue::Float(88.5))
+        .extended("fuel_laps", TelemetryValue::Integer(14))
+        .extended("pit_request", TelemetryValue::Boolean(false))
+        .extended(
+            "tyre_compound",
+            TelemetryValue::String("medium".to_string()),
+        )
+        .build();
+    TelemetryFrame::new(
+        telemetry,
+        1_000_000_000 + sequence * 16_666_667,
+        sequence,
+        328,
+    )
+}
+
+#[test]
+fn frame_with_every_value_variant_round_trips() -> TestResult {
+    let frame = representative_frame(3);
+    assert_eq!(decode_frame(&encode_frame(&frame)?)?, frame);
+
+    let empty = TelemetryFrame::new(NormalizedTelemetry::default(), 0, 0, 0);
+    assert_eq!(decode_frame(&encode_frame(&empty)?)?, empty);
+    Ok(())
+}
+
+#[test]
+fn stream_round_trips_and_appends() -> TestResult {
+    let mut writer = FrameWriter::new(Vec::new())?;
+    writer.write_frame(&representative_frame(0))?;
+    writer.write_frame(&representative_frame(1))?;
+    let bytes = writer.into_inner();
+
+    // Reopened for append: records go after the existing ones, no new header.
+    let mut appender = FrameWriter::append(bytes);
+    appender.write_frame(&representative_frame(2))?;
+    let bytes = appender.into_inner();
+
+    let frames =
+        FrameReader::<_, TelemetryFrame>::new(bytes.as_slice())?.collect::<Result<Vec<_>, _>>()?;
+    let expected: Vec<_> = (0..3).map(representative_frame).collect();
+    assert_eq!(frames, expected);
+    Ok(())
+}
+
+#[test]
+fn corrupted_length_prefix_errors_cleanly() -> TestResult {
+    let mut writer = FrameWriter::new(Vec::new())?;
+    writer.write_frame(&representative_frame(0))?;
+    writer.write_frame(&representative_frame(1))?;
+    let good = writer.into_inner();
+    let first_len = u32::from_le_bytes([good[8], good[9], good[10], good[11]]) as usize;
+    let second_prefix = 8 + 4 + first_len;
+
+    // A prefix past the end of the stream is truncation, not a panic.
+    let mut overlong = good.clone();
+    overlong[second_prefix..second_prefix + 4].copy_from_slice(&10_000u32.to_le_bytes());
+    let mut reader = FrameReader::<_, TelemetryFrame>::new(overlong.as_slice())?;
+    assert_eq!(reader.read_frame()?, Some(representative_frame(0)));
+    assert!(matches!(
+        reader.read_frame(),
+        Err(BinaryCodecError::Truncated {
+            expected: 10_000,
+            ..
+        })
+    ));
+
+    // An absurd prefix is refused before anything is allocated.
+    let mut huge = good.clone();
+    huge[8..12].copy_from_slice(&u32::MAX.to_le_bytes());
+    let mut reader = FrameReader::<_, TelemetryFrame>::new(huge.as_slice())?;
+    assert!(matches!(
+        reader.read_frame(),
+        Err(BinaryCodecError::RecordTooLarge { .. })
+    ));
+
+    // A prefix that splits a record misaligns the payload, which fails to decode.
+    let mut short = good;
+    short[8..12].copy_from_slice(&u32::try_from(first_len - 7)?.to_le_bytes());
+    let mut reader = FrameReader::<_, TelemetryFrame>::new(short.as_slice())?;
+    assert!(reader.read_frame().is_err());
+    Ok(())
+}
+
+#[test]
+fn binary_is_at_least_forty_percent_smaller_than_json() -> TestResult {
+    let frame = representative_frame(42);
+    let binary = encode_frame(&frame)?.len();
+    let json = serde_json::to_vec(&frame)?.len();
+    assert!(
+        binary * 10 <= json * 6,
+        "binary {binary} bytes vs JSON {json} bytes"
+    );
+    Ok(())
+}
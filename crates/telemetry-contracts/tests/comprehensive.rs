@@ -515,6 +515,7 @@ fn telemetry_field_coverage_serde_round_trip() -> TestResult {
         },
         car_id: true,
         track_id: false,
+        timing: Default::default(),
         extended_fields: vec!["fuel".to_string(), "tire_temp".to_string()],
     };
     let json = serde_json::to_string(&coverage)?;
@@ -248,6 +248,7 @@ fn field_coverage_with_no_extended_fields() -> TestResult {
         },
         car_id: false,
         track_id: false,
+        timing: Default::default(),
         extended_fields: vec![],
     };
     let json = serde_json::to_string(&coverage)?;
@@ -285,6 +286,7 @@ fn field_coverage_with_all_flags_true() -> TestResult {
         },
         car_id: true,
         track_id: true,
+        timing: Default::default(),
         extended_fields: vec!["a".to_string(), "b".to_string(), "c".to_string()],
     };
     let json = serde_json::to_string(&coverage)?;
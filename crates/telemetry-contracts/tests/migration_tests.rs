@@ -0,0 +1,97 @@
+//! Recorded frames of older schema versions upgrade to the current struct.
+
+use racing_wheel_telemetry_contracts::{
+    FrameMigrationError, NormalizedTelemetry, TELEMETRY_SCHEMA_VERSION, TelemetryFlags,
+    TelemetryFrame, TelemetryValue, migrate_frame, upgrade_frame,
+};
+use serde_json::json;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn expected_frame() -> TelemetryFrame {
+    let telemetry = NormalizedTelemetry::new()
+        .with_rpm(6200.0)
+        .with_gear(4)
+        .with_flags(TelemetryFlags {
+            yellow_flag: true,
+            green_flag: false,
+            ..TelemetryFlags::default()
+        })
+        .with_extended("tire_temp_fl".to_string(), TelemetryValue::Float(85.0));
+    TelemetryFrame::new(telemetry, 1_000, 7, 264)
+}
+
+#[test]
+fn unversioned_frame_with_partial_flags_upgrades() -> TestResult {
+    // Written before ers_available and the assist flags were recorded.
+    let legacy = json!({
+        "data": {
+            "rpm": 6200.0,
+            "gear": 4,
+            "flags": {"yellow_flag": true, "green_flag": false},
+            "extended": {"tire_temp_fl": {"Float": 85.0}}
+        },
+        "timestamp_ns": 1000,
+        "sequence": 7,
+        "raw_size": 264
+    });
+    assert_eq!(migrate_frame(legacy)?, expected_frame());
+    Ok(())
+}
+
+#[test]
+fn unversioned_frame_with_tyre_alias_upgrades() -> TestResult {
+    let legacy = json!({
+        "data": {
+            "ffb_scalar": null,
+            "rpm": 6200.0,
+            "gear": 4,
+            "flags": {
+                "yellow_flag": true, "red_flag": false, "blue_flag": false,
+                "checkered_flag": false, "green_flag": false, "pit_limiter": false,
+                "in_pits": false, "drs_available": false, "drs_active": false,
+                "ers_available": false, "launch_control": false,
+                "traction_control": false, "abs_active": false
+            },
+            "car_id": null,
+            "track_id": null,
+            "extended": {"tyre_temp_fl": {"Float": 85.0}}
+        },
+        "timestamp_ns": 1000,
+        "sequence": 7,
+        "raw_size": 264
+    });
+    assert_eq!(migrate_frame(legacy)?, expected_frame());
+    Ok(())
+}
+
+#[test]
+fn current_frame_round_trips_unchanged() -> TestResult {
+    let frame = expected_frame();
+    let json = serde_json::to_value(&frame)?;
+    assert_eq!(json["version"], json!(TELEMETRY_SCHEMA_VERSION));
+    assert_eq!(upgrade_frame(json.clone())?, json);
+    assert_eq!(migrate_frame(json)?, frame);
+    Ok(())
+}
+
+#[test]
+fn future_version_is_rejected_with_a_descriptive_error() -> TestResult {
+    let mut json = serde_json::to_value(expected_frame())?;
+    let future = TELEMETRY_SCHEMA_VERSION + 1;
+    json["version"] = json!(future);
+    json["data"]["brake_bias"] = json!(0.56);
+
+    let Err(error) = migrate_frame(json) else {
+        return Err("a frame from a newer schema must not decode".into());
+    };
+    assert!(matches!(
+        error,
+        FrameMigrationError::FutureVersion { found, supported }
+            if found == future && supported == TELEMETRY_SCHEMA_VERSION
+    ));
+    let message = error.to_string();
+    assert!(message.contains(&format!("version {future}")), "{message}");
+    assert!(message.contains("newer"), "{message}");
+    Ok(())
+}
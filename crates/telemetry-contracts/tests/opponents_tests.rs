@@ -0,0 +1,87 @@
+//! Opponent lists are sorted, bounded around the player and optional on disk.
+
+use racing_wheel_telemetry_contracts::{
+    NormalizedTelemetry, OpponentSnapshot, TelemetryFrame, bound_opponents, migrate_frame,
+    upgrade_frame,
+};
+use serde_json::json;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn car(car_index: u16, position: u8) -> OpponentSnapshot {
+    OpponentSnapshot {
+        car_index,
+        position,
+        gap_to_player_ms: None,
+        speed_ms: 50.0,
+        lap_number: 4,
+        in_pits: false,
+        driver_name: Some(format!("Driver {car_index}")),
+    }
+}
+
+/// Twenty cars listed out of position order: car `i` runs `20 - i`th.
+fn grid() -> Vec<OpponentSnapshot> {
+    (0..20).map(|index| car(index, 20 - index as u8)).collect()
+}
+
+fn positions(opponents: &[OpponentSnapshot]) -> Vec<u8> {
+    opponents.iter().map(|opponent| opponent.position).collect()
+}
+
+#[test]
+fn opponents_are_sorted_by_position_when_under_the_bound() {
+    let sorted = bound_opponents(grid(), Some(10), 24);
+    assert_eq!(positions(&sorted), (1..=20).collect::<Vec<_>>());
+}
+
+#[test]
+fn bound_keeps_the_nearest_positions_to_the_player() {
+    let mut opponents = grid();
+    opponents.retain(|opponent| opponent.position != 10);
+
+    let bounded = bound_opponents(opponents.clone(), Some(10), 5);
+    // 9 and 11, then 8 and 12, then the car ahead breaks the tie at distance 3.
+    assert_eq!(positions(&bounded), [7, 8, 9, 11, 12]);
+
+    opponents.reverse();
+    assert_eq!(bound_opponents(opponents, Some(10), 5), bounded);
+}
+
+#[test]
+fn bound_breaks_shared_positions_by_car_index() {
+    let opponents = vec![car(9, 3), car(4, 3), car(2, 5), car(1, 1)];
+    let bounded = bound_opponents(opponents, Some(4), 2);
+    let indices: Vec<u16> = bounded.iter().map(|opponent| opponent.car_index).collect();
+    assert_eq!(indices, [4, 9]);
+}
+
+#[test]
+fn bound_without_a_player_keeps_the_leaders() {
+    let bounded = bound_opponents(grid(), None, 3);
+    assert_eq!(positions(&bounded), [1, 2, 3]);
+    assert!(bound_opponents(grid(), None, 0).is_empty());
+}
+
+#[test]
+fn frames_without_opponents_omit_the_field_and_read_it_back_empty() -> TestResult {
+    let frame = TelemetryFrame::new(NormalizedTelemetry::new().with_rpm(5000.0), 1_000, 3, 64);
+    let json = serde_json::to_value(&frame)?;
+    assert!(json.get("opponents").is_none());
+    assert_eq!(serde_json::from_value::<TelemetryFrame>(json)?, frame);
+
+    let with_grid = frame.with_opponents(bound_opponents(grid(), Some(20), 4));
+    let json = serde_json::to_string(&with_grid)?;
+    assert_eq!(serde_json::from_str::<TelemetryFrame>(&json)?, with_grid);
+    Ok(())
+}
+
+#[test]
+fn version_two_recordings_upgrade_with_no_opponents() -> TestResult {
+    let frame = TelemetryFrame::new(NormalizedTelemetry::new().with_rpm(5000.0), 1_000, 3, 64);
+    let mut recorded = serde_json::to_value(&frame)?;
+    recorded["version"] = json!(2);
+    assert_eq!(upgrade_frame(recorded.clone())?["opponents"], json!([]));
+    assert_eq!(migrate_frame(recorded)?, frame);
+    Ok(())
+}
@@ -0,0 +1,182 @@
+//! Display unit conversion and formatting.
+
+use racing_wheel_telemetry_contracts::{
+    ExtendedKey, FormattedValue, KMH_PER_MS, MPH_PER_MS, NormalizedTelemetry, PressureUnit,
+    SpeedUnit, TelemetryReadings, TelemetryValue, TemperatureUnit, UnitsProfile,
+};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn close(actual: f32, expected: f32) -> bool {
+    (actual - expected).abs() <= expected.abs() * 1e-6 + 1e-6
+}
+
+fn text(value: &Option<FormattedValue>) -> Option<&str> {
+    value.as_ref().map(|value| value.text.as_str())
+}
+
+#[test]
+fn speed_conversions_match_known_values() {
+    // 100 km/h and 100 mph exactly.
+    assert!(close(
+        SpeedUnit::KilometersPerHour.from_meters_per_second(100.0 / 3.6),
+        100.0
+    ));
+    assert!(close(
+        SpeedUnit::MilesPerHour.from_meters_per_second(44.704),
+        100.0
+    ));
+    assert!(close(
+        SpeedUnit::MetersPerSecond.from_meters_per_second(12.5),
+        12.5
+    ));
+    assert!(close(MPH_PER_MS, 2.236_936_3));
+}
+
+#[test]
+fn pressure_conversions_match_known_values() {
+    assert!(close(
+        PressureUnit::Kilopascal.from_kilopascals(180.0),
+        180.0
+    ));
+    assert!(close(PressureUnit::Bar.from_kilopascals(180.0), 1.8));
+    // One standard atmosphere.
+    assert!(close(
+        PressureUnit::Psi.from_kilopascals(101.325),
+        14.695_949
+    ));
+    assert!(close(PressureUnit::Psi.from_kilopascals(6.894_757), 1.0));
+}
+
+#[test]
+fn temperature_conversions_match_known_values() {
+    assert!(close(TemperatureUnit::Fahrenheit.from_celsius(0.0), 32.0));
+    assert!(close(
+        TemperatureUnit::Fahrenheit.from_celsius(100.0),
+        212.0
+    ));
+    assert!(close(
+        TemperatureUnit::Fahrenheit.from_celsius(-40.0),
+        -40.0
+    ));
+    assert!(close(TemperatureUnit::Celsius.from_celsius(85.0), 85.0));
+}
+
+#[test]
+fn speed_helpers_share_the_display_factors() {
+    let t = NormalizedTelemetry::new().with_speed_ms(27.5);
+    let metric = t.formatted(&UnitsProfile::METRIC);
+    let imperial = t.formatted(&UnitsProfile::IMPERIAL);
+    assert_eq!(
+        t.speed_kmh(),
+        metric.speed.as_ref().map(|speed| speed.value)
+    );
+    assert_eq!(
+        t.speed_mph(),
+        imperial.speed.as_ref().map(|speed| speed.value)
+    );
+    assert_eq!(t.speed_kmh(), Some(27.5 * KMH_PER_MS));
+}
+
+#[test]
+fn formatting_uses_fixed_precision_and_suffixes() {
+    let readings = TelemetryReadings {
+        speed_ms: Some(55.0),
+        rpm: Some(12_345.4),
+        ffb_scalar: Some(-0.456),
+        slip_ratio: Some(0.125),
+        tire_pressures_kpa: [Some(186.0), Some(172.4), None, Some(1_234.56)],
+        tire_temps_c: [Some(85.4), None, Some(101.6), Some(-0.2)],
+    };
+
+    let metric = readings.formatted(&UnitsProfile::METRIC);
+    assert_eq!(text(&metric.speed), Some("198 km/h"));
+    // No thousands separators.
+    assert_eq!(text(&metric.rpm), Some("12345 rpm"));
+    assert_eq!(text(&metric.ffb), Some("-0.46"));
+    assert_eq!(text(&metric.slip), Some("12 %"));
+    assert_eq!(
+        metric.tire_pressures.each_ref().map(text),
+        [
+            Some("186.0 kPa"),
+            Some("172.4 kPa"),
+            None,
+            Some("1234.6 kPa")
+        ]
+    );
+    // A value rounding to zero shows no sign.
+    assert_eq!(
+        metric.tire_temps.each_ref().map(text),
+        [Some("85 °C"), None, Some("102 °C"), Some("0 °C")]
+    );
+
+    let imperial = readings.formatted(&UnitsProfile::IMPERIAL);
+    assert_eq!(text(&imperial.speed), Some("123 mph"));
+    assert_eq!(text(&imperial.tire_pressures[0]), Some("27.0 psi"));
+    assert_eq!(text(&imperial.tire_temps[0]), Some("186 °F"));
+    assert_eq!(imperial.rpm, metric.rpm);
+}
+
+#[test]
+fn custom_profile_overrides_single_quantities() {
+    let profile = UnitsProfile::METRIC
+        .with_pressure(PressureUnit::Bar)
+        .with_speed(SpeedUnit::MetersPerSecond);
+    let formatted = TelemetryReadings {
+        speed_ms: Some(12.6),
+        tire_pressures_kpa: [Some(186.0); 4],
+        tire_temps_c: [Some(90.0); 4],
+        ..TelemetryReadings::default()
+    }
+    .formatted(&profile);
+    assert_eq!(text(&formatted.speed), Some("13 m/s"));
+    assert_eq!(text(&formatted.tire_pressures[3]), Some("1.9 bar"));
+    assert_eq!(text(&formatted.tire_temps[3]), Some("90 °C"));
+    assert_eq!(formatted.profile, profile);
+}
+
+#[test]
+fn missing_and_non_finite_values_are_omitted() {
+    let formatted = TelemetryReadings {
+        speed_ms: Some(f32::NAN),
+        rpm: Some(f32::INFINITY),
+        ..TelemetryReadings::default()
+    }
+    .formatted(&UnitsProfile::default());
+    assert_eq!(formatted.speed, None);
+    assert_eq!(formatted.rpm, None);
+    assert_eq!(formatted.ffb, None);
+    assert_eq!(formatted.tire_pressures, [None, None, None, None]);
+}
+
+#[test]
+fn contract_telemetry_formats_extended_tire_temperatures() {
+    let t = NormalizedTelemetry::new().with_rpm(7_200.0).with_extended(
+        ExtendedKey::TIRE_TEMP_FR.name.to_string(),
+        TelemetryValue::Float(92.0),
+    );
+    let formatted = t.formatted(&UnitsProfile::IMPERIAL);
+    assert_eq!(text(&formatted.rpm), Some("7200 rpm"));
+    assert_eq!(formatted.speed, None);
+    assert_eq!(text(&formatted.tire_temps[1]), Some("198 °F"));
+    assert_eq!(formatted.tire_temps[0], None);
+}
+
+#[test]
+fn profile_round_trips_through_json() -> TestResult {
+    let profile = UnitsProfile::IMPERIAL.with_temperature(TemperatureUnit::Celsius);
+    let json = serde_json::to_string(&profile)?;
+    assert_eq!(
+        json,
+        r#"{"speed":"miles_per_hour","pressure":"psi","temperature":"celsius"}"#
+    );
+    assert_eq!(serde_json::from_str::<UnitsProfile>(&json)?, profile);
+
+    // Fields a stored preference lacks read as metric.
+    let partial: UnitsProfile = serde_json::from_str(r#"{"speed":"miles_per_hour"}"#)?;
+    assert_eq!(
+        partial,
+        UnitsProfile::METRIC.with_speed(SpeedUnit::MilesPerHour)
+    );
+    Ok(())
+}
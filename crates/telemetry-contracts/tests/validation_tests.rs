@@ -0,0 +1,232 @@
+//! Validation and sanitizing of finished telemetry frames.
+
+use racing_wheel_telemetry_contracts::{
+    ExtendedKey, IssueKind, NormalizedTelemetry, TelemetryValidator, TelemetryValue,
+    ValidationIssue, ValidationPolicy,
+};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn issue(field: &'static str, kind: IssueKind, observed: f64) -> ValidationIssue {
+    ValidationIssue {
+        field,
+        kind,
+        observed,
+    }
+}
+
+/// Validate a frame with one bad reading and return its issues and the
+/// sanitized frame.
+fn check(telemetry: NormalizedTelemetry) -> (Vec<ValidationIssue>, NormalizedTelemetry) {
+    let validator = TelemetryValidator::new();
+    (
+        validator.validate(&telemetry),
+        validator.sanitize(telemetry),
+    )
+}
+
+#[test]
+fn non_finite_readings_are_unset() {
+    let (issues, sanitized) = check(NormalizedTelemetry {
+        speed_ms: Some(f32::NAN),
+        ..NormalizedTelemetry::default()
+    });
+    assert_eq!(issues.len(), 1);
+    assert_eq!(
+        (issues[0].field, issues[0].kind),
+        ("speed_ms", IssueKind::NotFinite)
+    );
+    assert!(issues[0].observed.is_nan());
+    assert_eq!(sanitized.speed_ms, None);
+
+    let (issues, sanitized) = check(NormalizedTelemetry {
+        lateral_g: Some(f32::NEG_INFINITY),
+        ..NormalizedTelemetry::default()
+    });
+    assert_eq!(
+        issues,
+        [issue("lateral_g", IssueKind::NotFinite, f64::NEG_INFINITY)]
+    );
+    assert_eq!(sanitized.lateral_g, None);
+}
+
+#[test]
+fn negative_rpm_and_speed_are_unset() {
+    let (issues, sanitized) = check(NormalizedTelemetry {
+        rpm: Some(-50.0),
+        speed_ms: Some(-1.5),
+        ..NormalizedTelemetry::default()
+    });
+    assert_eq!(
+        issues,
+        [
+            issue("rpm", IssueKind::Negative, -50.0),
+            issue("speed_ms", IssueKind::Negative, -1.5)
+        ]
+    );
+    assert_eq!((sanitized.rpm, sanitized.speed_ms), (None, None));
+}
+
+#[test]
+fn ffb_is_clamped_to_unit_range() {
+    let (issues, sanitized) = check(NormalizedTelemetry {
+        ffb_scalar: Some(-3.0),
+        ..NormalizedTelemetry::default()
+    });
+    assert_eq!(issues, [issue("ffb_scalar", IssueKind::OutOfRange, -3.0)]);
+    assert_eq!(sanitized.ffb_scalar, Some(-1.0));
+}
+
+#[test]
+fn slip_and_pedals_are_clamped_to_zero_one() {
+    let (issues, sanitized) = check(NormalizedTelemetry {
+        slip_ratio: Some(1.5),
+        throttle: Some(-0.25),
+        brake: Some(2.0),
+        ..NormalizedTelemetry::default()
+    });
+    assert_eq!(
+        issues.iter().map(|issue| issue.field).collect::<Vec<_>>(),
+        ["slip_ratio", "throttle", "brake"]
+    );
+    assert!(
+        issues
+            .iter()
+            .all(|issue| issue.kind == IssueKind::OutOfRange)
+    );
+    assert_eq!(sanitized.slip_ratio, Some(1.0));
+    assert_eq!(sanitized.throttle, Some(0.0));
+    assert_eq!(sanitized.brake, Some(1.0));
+}
+
+#[test]
+fn gears_are_clamped_to_the_configured_range() {
+    let (issues, sanitized) = check(NormalizedTelemetry {
+        gear: Some(117),
+        ..NormalizedTelemetry::default()
+    });
+    assert_eq!(issues, [issue("gear", IssueKind::OutOfRange, 117.0)]);
+    assert_eq!(sanitized.gear, Some(12));
+
+    let trucks = TelemetryValidator::new().with_gear_range(-1..=18);
+    let gear_16 = NormalizedTelemetry {
+        gear: Some(16),
+        ..NormalizedTelemetry::default()
+    };
+    assert!(trucks.validate(&gear_16).is_empty());
+    let reverse_3 = NormalizedTelemetry {
+        gear: Some(-3),
+        ..NormalizedTelemetry::default()
+    };
+    assert_eq!(trucks.sanitize(reverse_3).gear, Some(-1));
+}
+
+fn all_bad() -> NormalizedTelemetry {
+    NormalizedTelemetry {
+        ffb_scalar: Some(f32::INFINITY),
+        rpm: Some(f32::NAN),
+        speed_ms: Some(-10.0),
+        slip_ratio: Some(7.0),
+        gear: Some(-9),
+        steering_angle: Some(f32::NAN),
+        throttle: Some(f32::NAN),
+        brake: Some(-1.0),
+        lateral_g: Some(f32::INFINITY),
+        longitudinal_g: Some(f32::NAN),
+        slip_angle_fl: Some(f32::NAN),
+        slip_angle_fr: Some(f32::NAN),
+        slip_angle_rl: Some(f32::NAN),
+        slip_angle_rr: Some(f32::NAN),
+        ..NormalizedTelemetry::default()
+    }
+}
+
+#[test]
+fn fully_bad_frame_is_cleaned_everywhere() {
+    let validator = TelemetryValidator::new();
+    let issues = validator.validate(&all_bad());
+    assert_eq!(issues.len(), 14);
+
+    let sanitized = validator.sanitize(all_bad());
+    assert!(validator.validate(&sanitized).is_empty());
+    assert_eq!(
+        sanitized,
+        NormalizedTelemetry {
+            slip_ratio: Some(1.0),
+            gear: Some(-2),
+            brake: Some(0.0),
+            ..NormalizedTelemetry::default()
+        }
+    );
+}
+
+#[test]
+fn clean_frame_passes_through_byte_identical() -> TestResult {
+    let clean = NormalizedTelemetry::builder()
+        .ffb_scalar(-0.4)
+        .rpm(7_250.5)
+        .speed_ms(61.25)
+        .slip_ratio(0.08)
+        .gear(4)
+        .throttle(1.0)
+        .brake(0.0)
+        .steering_angle(-0.3)
+        .car_id("gt3".to_string())
+        .build()
+        .with_extended("lap_distance_m".to_string(), TelemetryValue::Float(812.5));
+    let before = serde_json::to_vec(&clean)?;
+
+    for policy in [
+        ValidationPolicy::Reject,
+        ValidationPolicy::Sanitize,
+        ValidationPolicy::PassThrough,
+    ] {
+        let validator = TelemetryValidator::new().with_policy(policy);
+        assert!(validator.validate(&clean).is_empty());
+        let outcome = validator.apply(clean.clone());
+        assert!(outcome.issues.is_empty());
+        let after = outcome.telemetry.ok_or("clean frame was rejected")?;
+        assert_eq!(serde_json::to_vec(&after)?, before, "{policy:?}");
+    }
+    assert_eq!(
+        serde_json::to_vec(&TelemetryValidator::new().sanitize(clean))?,
+        before
+    );
+    Ok(())
+}
+
+#[test]
+fn policies_reject_sanitize_or_annotate_bad_frames() -> TestResult {
+    let bad = NormalizedTelemetry {
+        rpm: Some(f32::NAN),
+        gear: Some(20),
+        ..NormalizedTelemetry::default()
+    };
+
+    let rejected = TelemetryValidator::new()
+        .with_policy(ValidationPolicy::Reject)
+        .apply(bad.clone());
+    assert_eq!(rejected.telemetry, None);
+    assert_eq!(rejected.issues.len(), 2);
+
+    let sanitized = TelemetryValidator::new()
+        .apply(bad.clone())
+        .telemetry
+        .ok_or("sanitized frame was rejected")?;
+    assert_eq!((sanitized.rpm, sanitized.gear), (None, Some(12)));
+
+    let passed = TelemetryValidator::new()
+        .with_policy(ValidationPolicy::PassThrough)
+        .apply(bad)
+        .telemetry
+        .ok_or("passed-through frame was rejected")?;
+    assert_eq!(passed.gear, Some(20));
+    assert!(passed.rpm.is_some_and(f32::is_nan));
+    assert_eq!(
+        passed.extended.get(ExtendedKey::VALIDATION_ISSUES.name),
+        Some(&TelemetryValue::String(
+            "rpm:not_finite(NaN),gear:out_of_range(20)".to_string()
+        ))
+    );
+    Ok(())
+}
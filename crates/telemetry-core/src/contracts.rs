@@ -7,8 +7,20 @@
 
 // Re-export from the canonical location
 pub use racing_wheel_schemas::telemetry::{
-    NormalizedTelemetry, NormalizedTelemetryBuilder, TelemetryFlags, TelemetryFrame,
-    TelemetrySnapshot, TelemetryValue,
+    DEFAULT_MAX_OPPONENTS, NormalizedTelemetry, NormalizedTelemetryBuilder, OpponentSnapshot,
+    PenaltyEvent, PenaltyKind, PenaltyState, PenaltyTracker, SessionTiming, TelemetryFlags,
+    TelemetryFrame, TelemetrySnapshot, TelemetryValue, TelemetryValueDepthError, TimingCoverage,
+    TireCorner, TireData, bound_opponents,
+};
+
+pub use racing_wheel_telemetry_contracts::extended_keys::{
+    ExtendedFields, ExtendedKey, ExtendedKeyError, ExtendedValueType, KNOWN_KEYS, ProducerKind,
+    lookup_key, register_runtime_key, resolve_key, scan_unknown_keys,
+};
+
+pub use racing_wheel_telemetry_contracts::validation::{
+    DEFAULT_GEAR_RANGE, FloatField, IssueKind, TelemetryValidator, ValidatedFields,
+    ValidationIssue, ValidationOutcome, ValidationPolicy,
 };
 
 use serde::{Deserialize, Serialize};
@@ -26,6 +38,14 @@ pub struct TelemetryFieldCoverage {
     pub flags: FlagCoverage,
     pub car_id: bool,
     pub track_id: bool,
+    #[serde(default)]
+    pub penalties: bool,
+    #[serde(default)]
+    pub game_time: bool,
+    #[serde(default)]
+    pub tires: bool,
+    #[serde(default)]
+    pub timing: TimingCoverage,
     pub extended_fields: Vec<String>,
 }
 
@@ -45,4 +65,8 @@ pub struct FlagCoverage {
     pub launch_control: bool,
     pub traction_control: bool,
     pub abs_active: bool,
+    #[serde(default)]
+    pub safety_car: bool,
+    #[serde(default)]
+    pub virtual_safety_car: bool,
 }
@@ -0,0 +1,285 @@
+//! Derived channels computed between consecutive telemetry frames.
+//!
+//! [`DeltaComputer`] keeps the previous frame of one game and turns each new
+//! [`TelemetryFrame`] into a [`TelemetryDelta`]: longitudinal acceleration
+//! from the speed change over `timestamp_ns`, the RPM rate of change, and
+//! edge events for gear changes and flag transitions.
+//!
+//! Sources are not always well behaved, so:
+//!
+//! - frames whose sequence number is not newer than the previous frame are
+//!   skipped and logged;
+//! - frames sharing the previous timestamp still produce events, but no
+//!   derivatives, since there is no time to divide by;
+//! - when the time since the previous frame exceeds the configured gap, or
+//!   runs backwards, the delta carries a [`DeltaEvent::Discontinuity`]
+//!   instead of derivatives that would show up as a spike.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::{TelemetryFlags, TelemetryFrame};
+
+/// Longest time between frames that still yields derivatives.
+pub const DEFAULT_MAX_FRAME_GAP: Duration = Duration::from_millis(500);
+
+/// A boolean in [`TelemetryFlags`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlagKind {
+    Yellow,
+    Red,
+    Blue,
+    Checkered,
+    Green,
+    PitLimiter,
+    InPits,
+    DrsAvailable,
+    DrsActive,
+    ErsAvailable,
+    ErsActive,
+    LaunchControl,
+    TractionControl,
+    AbsActive,
+    EngineLimiter,
+    SafetyCar,
+    VirtualSafetyCar,
+    FormationLap,
+    SessionPaused,
+}
+
+impl FlagKind {
+    /// Every flag, in [`TelemetryFlags`] field order.
+    pub const ALL: [FlagKind; 19] = [
+        Self::Yellow,
+        Self::Red,
+        Self::Blue,
+        Self::Checkered,
+        Self::Green,
+        Self::PitLimiter,
+        Self::InPits,
+        Self::DrsAvailable,
+        Self::DrsActive,
+        Self::ErsAvailable,
+        Self::ErsActive,
+        Self::LaunchControl,
+        Self::TractionControl,
+        Self::AbsActive,
+        Self::EngineLimiter,
+        Self::SafetyCar,
+        Self::VirtualSafetyCar,
+        Self::FormationLap,
+        Self::SessionPaused,
+    ];
+
+    pub fn extract(self, flags: &TelemetryFlags) -> bool {
+        match self {
+            Self::Yellow => flags.yellow_flag,
+            Self::Red => flags.red_flag,
+            Self::Blue => flags.blue_flag,
+            Self::Checkered => flags.checkered_flag,
+            Self::Green => flags.green_flag,
+            Self::PitLimiter => flags.pit_limiter,
+            Self::InPits => flags.in_pits,
+            Self::DrsAvailable => flags.drs_available,
+            Self::DrsActive => flags.drs_active,
+            Self::ErsAvailable => flags.ers_available,
+            Self::ErsActive => flags.ers_active,
+            Self::LaunchControl => flags.launch_control,
+            Self::TractionControl => flags.traction_control,
+            Self::AbsActive => flags.abs_active,
+            Self::EngineLimiter => flags.engine_limiter,
+            Self::SafetyCar => flags.safety_car,
+            Self::VirtualSafetyCar => flags.virtual_safety_car,
+            Self::FormationLap => flags.formation_lap,
+            Self::SessionPaused => flags.session_paused,
+        }
+    }
+}
+
+/// Something that changed between two frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DeltaEvent {
+    /// The gear changed; -1 is reverse and 0 neutral.
+    GearChange { previous: i8, new: i8 },
+    /// A flag turned on (`active`) or off.
+    FlagTransition { flag: FlagKind, active: bool },
+    /// Too much time passed between the frames, or the clock went
+    /// backwards, so the delta has no derivatives.
+    Discontinuity {
+        previous_timestamp_ns: u64,
+        timestamp_ns: u64,
+    },
+}
+
+/// Derived values between a frame and the one before it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TelemetryDelta {
+    /// Sequence number of the newer frame.
+    pub sequence: u64,
+    /// Time since the previous frame; zero for duplicate timestamps and
+    /// discontinuities.
+    pub dt: Duration,
+    /// Change in speed per second, in m/s². `None` without a usable `dt`.
+    pub acceleration_ms2: Option<f32>,
+    /// Change in RPM per second. `None` without a usable `dt`.
+    pub rpm_rate: Option<f32>,
+    pub events: Vec<DeltaEvent>,
+}
+
+impl TelemetryDelta {
+    pub fn is_discontinuity(&self) -> bool {
+        self.events
+            .iter()
+            .any(|event| matches!(event, DeltaEvent::Discontinuity { .. }))
+    }
+
+    /// Whether `flag` turned on in this delta.
+    pub fn flag_raised(&self, flag: FlagKind) -> bool {
+        self.events
+            .contains(&DeltaEvent::FlagTransition { flag, active: true })
+    }
+}
+
+/// Frames a [`DeltaComputer`] could not use normally.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeltaStats {
+    /// Frames skipped because their sequence number was not newer.
+    pub out_of_order: u64,
+    /// Frames with the same timestamp as the previous one.
+    pub duplicate_timestamps: u64,
+    pub discontinuities: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    sequence: u64,
+    timestamp_ns: u64,
+    speed_ms: f32,
+    rpm: f32,
+    gear: i8,
+    /// One bit per [`FlagKind::ALL`] entry.
+    flags: u32,
+}
+
+impl Sample {
+    fn of(frame: &TelemetryFrame) -> Self {
+        Self {
+            sequence: frame.sequence,
+            timestamp_ns: frame.timestamp_ns,
+            speed_ms: frame.data.speed_ms,
+            rpm: frame.data.rpm,
+            gear: frame.data.gear,
+            flags: FlagKind::ALL
+                .iter()
+                .enumerate()
+                .filter(|(_, flag)| flag.extract(&frame.data.flags))
+                .fold(0, |bits, (bit, _)| bits | 1 << bit),
+        }
+    }
+}
+
+/// Turns consecutive frames of one game into [`TelemetryDelta`]s.
+#[derive(Debug, Clone)]
+pub struct DeltaComputer {
+    max_gap: Duration,
+    previous: Option<Sample>,
+    stats: DeltaStats,
+}
+
+impl Default for DeltaComputer {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_FRAME_GAP)
+    }
+}
+
+impl DeltaComputer {
+    /// A computer that reports a discontinuity for frames more than
+    /// `max_gap` apart.
+    pub fn new(max_gap: Duration) -> Self {
+        Self {
+            max_gap,
+            previous: None,
+            stats: DeltaStats::default(),
+        }
+    }
+
+    /// Compare `frame` with the previous accepted frame.
+    ///
+    /// Returns `None` for the first frame and for skipped out-of-order
+    /// frames; otherwise `frame` becomes the new previous frame.
+    pub fn push(&mut self, frame: &TelemetryFrame) -> Option<TelemetryDelta> {
+        let current = Sample::of(frame);
+        let Some(previous) = self.previous else {
+            self.previous = Some(current);
+            return None;
+        };
+        if current.sequence <= previous.sequence {
+            self.stats.out_of_order += 1;
+            debug!(
+                sequence = current.sequence,
+                previous = previous.sequence,
+                "Skipping out-of-order telemetry frame"
+            );
+            return None;
+        }
+        self.previous = Some(current);
+
+        let mut events = Vec::new();
+        if current.gear != previous.gear {
+            events.push(DeltaEvent::GearChange {
+                previous: previous.gear,
+                new: current.gear,
+            });
+        }
+        let changed = current.flags ^ previous.flags;
+        for (bit, flag) in FlagKind::ALL.into_iter().enumerate() {
+            if changed & (1 << bit) != 0 {
+                let active = current.flags & (1 << bit) != 0;
+                events.push(DeltaEvent::FlagTransition { flag, active });
+            }
+        }
+
+        let elapsed_ns = current.timestamp_ns.checked_sub(previous.timestamp_ns);
+        let dt = match elapsed_ns.map(Duration::from_nanos) {
+            Some(Duration::ZERO) => {
+                self.stats.duplicate_timestamps += 1;
+                Duration::ZERO
+            }
+            Some(dt) if dt <= self.max_gap => dt,
+            _ => {
+                self.stats.discontinuities += 1;
+                events.push(DeltaEvent::Discontinuity {
+                    previous_timestamp_ns: previous.timestamp_ns,
+                    timestamp_ns: current.timestamp_ns,
+                });
+                Duration::ZERO
+            }
+        };
+        let rate = |from: f32, to: f32| {
+            let rate = (to - from) / dt.as_secs_f32();
+            (!dt.is_zero() && rate.is_finite()).then_some(rate)
+        };
+
+        Some(TelemetryDelta {
+            sequence: current.sequence,
+            dt,
+            acceleration_ms2: rate(previous.speed_ms, current.speed_ms),
+            rpm_rate: rate(previous.rpm, current.rpm),
+            events,
+        })
+    }
+
+    /// Forget the previous frame, e.g. after the game restarts and its
+    /// sequence numbers start over.
+    pub fn reset(&mut self) {
+        self.previous = None;
+    }
+
+    pub fn stats(&self) -> DeltaStats {
+        self.stats
+    }
+}
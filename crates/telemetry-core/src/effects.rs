@@ -0,0 +1,481 @@
+//! Wind, bass-shaker and engine-pulse outputs derived from telemetry.
+//!
+//! An [`EffectsChannelGenerator`] turns each [`TelemetryFrame`] into an
+//! [`EffectsFrame`] for fan controllers and tactile transducers:
+//!
+//! - `wind` follows `speed_ms` through the profile's [`EffectCurve`];
+//! - `rumble` follows the absolute `slip_ratio` through a second curve, plus
+//!   a spike on every gear change that decays on its own time constant;
+//! - `pulse_hz` is `rpm / 60 × cylinders`, with cylinders set per `car_id`.
+//!
+//! Each channel is smoothed with a first-order filter on the frames'
+//! `timestamp_ns` clock. While any of the profile's mute flags is set, every
+//! channel reads zero and ramps back in from zero once the flags clear.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::mpsc;
+
+use crate::{DeltaComputer, DeltaEvent, FlagKind, NormalizedTelemetry, TelemetryFrame};
+
+/// Cylinders used when the profile has no entry for the car.
+pub const DEFAULT_CYLINDERS: u32 = 4;
+
+/// A point of an [`EffectCurve`]: `output` at `input`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CurveAnchor {
+    pub input: f32,
+    /// Effect intensity, in `0..=1`.
+    pub output: f32,
+}
+
+/// Piecewise-linear map from a telemetry value to an intensity.
+///
+/// Inputs below the first anchor take its output, and inputs above the last
+/// take the last one, so the first and last anchors set where the effect
+/// starts and saturates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EffectCurve {
+    anchors: Vec<CurveAnchor>,
+}
+
+impl EffectCurve {
+    /// A curve through `anchors`, which must have strictly increasing inputs
+    /// and outputs within `0..=1`.
+    pub fn new(anchors: Vec<CurveAnchor>) -> Result<Self, EffectsProfileError> {
+        let curve = Self { anchors };
+        curve.validate("curve")?;
+        Ok(curve)
+    }
+
+    /// Zero up to `min`, rising linearly to one at `max`.
+    pub fn linear(min: f32, max: f32) -> Result<Self, EffectsProfileError> {
+        Self::new(vec![
+            CurveAnchor {
+                input: min,
+                output: 0.0,
+            },
+            CurveAnchor {
+                input: max,
+                output: 1.0,
+            },
+        ])
+    }
+
+    pub fn anchors(&self) -> &[CurveAnchor] {
+        &self.anchors
+    }
+
+    /// Intensity at `input`; zero for NaN.
+    pub fn evaluate(&self, input: f32) -> f32 {
+        let (Some(first), Some(last)) = (self.anchors.first(), self.anchors.last()) else {
+            return 0.0;
+        };
+        if input.is_nan() {
+            return 0.0;
+        }
+        if input <= first.input {
+            return first.output;
+        }
+        if input >= last.input {
+            return last.output;
+        }
+        let upper = self.anchors.partition_point(|anchor| anchor.input <= input);
+        let (low, high) = (self.anchors[upper - 1], self.anchors[upper]);
+        let t = (input - low.input) / (high.input - low.input);
+        low.output + t * (high.output - low.output)
+    }
+
+    fn validate(&self, curve: &'static str) -> Result<(), EffectsProfileError> {
+        if self.anchors.len() < 2 {
+            return Err(EffectsProfileError::TooFewAnchors {
+                curve,
+                count: self.anchors.len(),
+            });
+        }
+        for (index, anchor) in self.anchors.iter().enumerate() {
+            if !anchor.input.is_finite() {
+                return Err(EffectsProfileError::AnchorNotFinite { curve, index });
+            }
+            if !(0.0..=1.0).contains(&anchor.output) {
+                return Err(EffectsProfileError::OutputOutOfRange {
+                    curve,
+                    index,
+                    output: anchor.output,
+                });
+            }
+            if let Some(previous) = index.checked_sub(1).map(|i| self.anchors[i].input)
+                && anchor.input <= previous
+            {
+                return Err(EffectsProfileError::AnchorsNotIncreasing {
+                    curve,
+                    index,
+                    input: anchor.input,
+                    previous,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Smoothing time constants in milliseconds; zero follows the input as is.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EffectsSmoothing {
+    pub wind_ms: f32,
+    pub rumble_ms: f32,
+    pub pulse_ms: f32,
+}
+
+impl Default for EffectsSmoothing {
+    fn default() -> Self {
+        Self {
+            wind_ms: 500.0,
+            rumble_ms: 30.0,
+            pulse_ms: 50.0,
+        }
+    }
+}
+
+/// Why an effects profile was rejected.
+#[derive(Debug, Error)]
+pub enum EffectsProfileError {
+    #[error("invalid effects profile JSON: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("{curve} has {count} anchors; a curve needs at least a min and a max anchor")]
+    TooFewAnchors { curve: &'static str, count: usize },
+    #[error("{curve} anchor {index} has a non-finite input")]
+    AnchorNotFinite { curve: &'static str, index: usize },
+    #[error(
+        "{curve} anchor {index} is at {input}, not after the previous anchor at {previous}; anchors must be sorted by `input`"
+    )]
+    AnchorsNotIncreasing {
+        curve: &'static str,
+        index: usize,
+        input: f32,
+        previous: f32,
+    },
+    #[error("{curve} anchor {index} outputs {output}; outputs are intensities within 0..=1")]
+    OutputOutOfRange {
+        curve: &'static str,
+        index: usize,
+        output: f32,
+    },
+    #[error("`{field}` is {value}; it must be a finite, non-negative number")]
+    InvalidParameter { field: &'static str, value: f32 },
+    #[error("`car_cylinders.{car}` is 0; cars need at least one cylinder")]
+    NoCylinders { car: String },
+}
+
+/// How to derive effect outputs from telemetry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EffectsProfile {
+    /// Fan intensity by speed in m/s.
+    #[serde(default = "default_wind")]
+    pub wind: EffectCurve,
+    /// Shaker intensity by absolute slip ratio.
+    #[serde(default = "default_slip_rumble")]
+    pub slip_rumble: EffectCurve,
+    /// Rumble added by a gear change, in `0..=1`.
+    #[serde(default = "default_gear_change_rumble")]
+    pub gear_change_rumble: f32,
+    /// Time constant of the gear-change spike's decay, in milliseconds.
+    #[serde(default = "default_gear_change_decay_ms")]
+    pub gear_change_decay_ms: f32,
+    #[serde(default = "default_cylinders")]
+    pub default_cylinders: u32,
+    /// Cylinders by `car_id`, taking precedence over `default_cylinders`.
+    #[serde(default)]
+    pub car_cylinders: BTreeMap<String, u32>,
+    #[serde(default)]
+    pub smoothing: EffectsSmoothing,
+    /// Flags that silence every channel while set.
+    #[serde(default = "default_mute_flags")]
+    pub mute_flags: Vec<FlagKind>,
+}
+
+fn default_wind() -> EffectCurve {
+    EffectCurve {
+        anchors: vec![
+            CurveAnchor {
+                input: 2.0,
+                output: 0.0,
+            },
+            CurveAnchor {
+                input: 75.0,
+                output: 1.0,
+            },
+        ],
+    }
+}
+
+fn default_slip_rumble() -> EffectCurve {
+    EffectCurve {
+        anchors: vec![
+            CurveAnchor {
+                input: 0.05,
+                output: 0.0,
+            },
+            CurveAnchor {
+                input: 0.3,
+                output: 1.0,
+            },
+        ],
+    }
+}
+
+fn default_gear_change_rumble() -> f32 {
+    0.6
+}
+
+fn default_gear_change_decay_ms() -> f32 {
+    80.0
+}
+
+fn default_cylinders() -> u32 {
+    DEFAULT_CYLINDERS
+}
+
+fn default_mute_flags() -> Vec<FlagKind> {
+    vec![FlagKind::InPits, FlagKind::Red]
+}
+
+impl Default for EffectsProfile {
+    fn default() -> Self {
+        Self {
+            wind: default_wind(),
+            slip_rumble: default_slip_rumble(),
+            gear_change_rumble: default_gear_change_rumble(),
+            gear_change_decay_ms: default_gear_change_decay_ms(),
+            default_cylinders: DEFAULT_CYLINDERS,
+            car_cylinders: BTreeMap::new(),
+            smoothing: EffectsSmoothing::default(),
+            mute_flags: default_mute_flags(),
+        }
+    }
+}
+
+impl EffectsProfile {
+    /// Parse and validate a profile.
+    pub fn from_json(json: &str) -> Result<Self, EffectsProfileError> {
+        let profile: Self = serde_json::from_str(json)?;
+        profile.validate()?;
+        Ok(profile)
+    }
+
+    pub fn validate(&self) -> Result<(), EffectsProfileError> {
+        self.wind.validate("wind")?;
+        self.slip_rumble.validate("slip_rumble")?;
+        if !(0.0..=1.0).contains(&self.gear_change_rumble) {
+            return Err(EffectsProfileError::OutputOutOfRange {
+                curve: "gear_change_rumble",
+                index: 0,
+                output: self.gear_change_rumble,
+            });
+        }
+        let parameters = [
+            ("gear_change_decay_ms", self.gear_change_decay_ms),
+            ("smoothing.wind_ms", self.smoothing.wind_ms),
+            ("smoothing.rumble_ms", self.smoothing.rumble_ms),
+            ("smoothing.pulse_ms", self.smoothing.pulse_ms),
+        ];
+        for (field, value) in parameters {
+            if !(value.is_finite() && value >= 0.0) {
+                return Err(EffectsProfileError::InvalidParameter { field, value });
+            }
+        }
+        if self.default_cylinders == 0 {
+            return Err(EffectsProfileError::NoCylinders {
+                car: "default".to_string(),
+            });
+        }
+        if let Some(car) = self
+            .car_cylinders
+            .iter()
+            .find_map(|(car, cylinders)| (*cylinders == 0).then(|| car.clone()))
+        {
+            return Err(EffectsProfileError::NoCylinders { car });
+        }
+        Ok(())
+    }
+
+    /// Cylinders of `telemetry`'s car.
+    pub fn cylinders(&self, telemetry: &NormalizedTelemetry) -> u32 {
+        telemetry
+            .car_id
+            .as_deref()
+            .and_then(|car| self.car_cylinders.get(car))
+            .copied()
+            .unwrap_or(self.default_cylinders)
+    }
+
+    /// The first mute flag set in `telemetry`.
+    pub fn mute_flag(&self, telemetry: &NormalizedTelemetry) -> Option<FlagKind> {
+        self.mute_flags
+            .iter()
+            .copied()
+            .find(|flag| flag.extract(&telemetry.flags))
+    }
+}
+
+/// Effect outputs for one telemetry frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct EffectsFrame {
+    /// Fan intensity, in `0..=1`.
+    pub wind: f32,
+    /// Shaker intensity, in `0..=1`.
+    pub rumble: f32,
+    /// Engine firing frequency.
+    pub pulse_hz: f32,
+    /// `timestamp_ns` of the source frame.
+    pub timestamp_ns: u64,
+    /// A mute flag was set; every output is zero.
+    pub muted: bool,
+}
+
+/// First-order low-pass filter with its time constant in milliseconds.
+#[derive(Debug, Clone, Copy, Default)]
+struct Smoothed(Option<f32>);
+
+impl Smoothed {
+    fn update(&mut self, target: f32, dt_ms: Option<f32>, time_constant_ms: f32) -> f32 {
+        let value = match (self.0, dt_ms) {
+            (Some(value), Some(dt_ms)) if time_constant_ms > 0.0 => {
+                value + (target - value) * (1.0 - (-dt_ms / time_constant_ms).exp())
+            }
+            _ => target,
+        };
+        self.0 = Some(value);
+        value
+    }
+}
+
+/// Turns consecutive frames of one game into [`EffectsFrame`]s.
+#[derive(Debug, Clone)]
+pub struct EffectsChannelGenerator {
+    profile: EffectsProfile,
+    delta: DeltaComputer,
+    wind: Smoothed,
+    rumble: Smoothed,
+    pulse: Smoothed,
+    /// Remaining gear-change spike.
+    transient: f32,
+}
+
+impl EffectsChannelGenerator {
+    /// A generator for a validated `profile`.
+    pub fn new(profile: EffectsProfile) -> Self {
+        Self {
+            profile,
+            delta: DeltaComputer::default(),
+            wind: Smoothed::default(),
+            rumble: Smoothed::default(),
+            pulse: Smoothed::default(),
+            transient: 0.0,
+        }
+    }
+
+    pub fn profile(&self) -> &EffectsProfile {
+        &self.profile
+    }
+
+    /// Outputs for `frame`, continuing from the frames pushed before it.
+    pub fn push(&mut self, frame: &TelemetryFrame) -> EffectsFrame {
+        let delta = self.delta.push(frame);
+        // No time passes for the first and out-of-order frames, and the
+        // filters start over after a discontinuity.
+        let dt_ms = match &delta {
+            Some(delta) if delta.is_discontinuity() => None,
+            Some(delta) => Some(delta.dt.as_secs_f32() * 1000.0),
+            None => Some(0.0),
+        };
+        let gear_changed = delta.is_some_and(|delta| {
+            delta
+                .events
+                .iter()
+                .any(|event| matches!(event, DeltaEvent::GearChange { .. }))
+        });
+
+        let telemetry = &frame.data;
+        if self.profile.mute_flag(telemetry).is_some() {
+            // Ramp back in from silence once the flags clear.
+            self.wind = Smoothed(Some(0.0));
+            self.rumble = Smoothed(Some(0.0));
+            self.pulse = Smoothed(Some(0.0));
+            self.transient = 0.0;
+            return EffectsFrame {
+                timestamp_ns: frame.timestamp_ns,
+                muted: true,
+                ..EffectsFrame::default()
+            };
+        }
+
+        self.transient = match dt_ms {
+            Some(dt_ms) if self.profile.gear_change_decay_ms > 0.0 => {
+                self.transient * (-dt_ms / self.profile.gear_change_decay_ms).exp()
+            }
+            _ => 0.0,
+        };
+        if gear_changed {
+            self.transient = self.transient.max(self.profile.gear_change_rumble);
+        }
+
+        let smoothing = self.profile.smoothing;
+        let wind = self.wind.update(
+            self.profile.wind.evaluate(telemetry.speed_ms),
+            dt_ms,
+            smoothing.wind_ms,
+        );
+        let slip = self.rumble.update(
+            self.profile
+                .slip_rumble
+                .evaluate(telemetry.slip_ratio.abs()),
+            dt_ms,
+            smoothing.rumble_ms,
+        );
+        let pulse_target = if telemetry.rpm.is_finite() && telemetry.rpm > 0.0 {
+            telemetry.rpm / 60.0 * self.profile.cylinders(telemetry) as f32
+        } else {
+            0.0
+        };
+        let pulse_hz = self.pulse.update(pulse_target, dt_ms, smoothing.pulse_ms);
+
+        EffectsFrame {
+            wind: wind.clamp(0.0, 1.0),
+            rumble: (slip + self.transient).clamp(0.0, 1.0),
+            pulse_hz,
+            timestamp_ns: frame.timestamp_ns,
+            muted: false,
+        }
+    }
+
+    /// Forget the previous frames, e.g. when the source restarts.
+    pub fn reset(&mut self) {
+        *self = Self::new(std::mem::take(&mut self.profile));
+    }
+}
+
+/// [`EffectsFrame`]s for every frame of a telemetry receiver.
+pub struct EffectsReceiver {
+    input: mpsc::Receiver<TelemetryFrame>,
+    generator: EffectsChannelGenerator,
+}
+
+impl EffectsReceiver {
+    pub fn new(input: mpsc::Receiver<TelemetryFrame>, generator: EffectsChannelGenerator) -> Self {
+        Self { input, generator }
+    }
+
+    /// Outputs for the next input frame; `None` once the input channel is
+    /// closed.
+    pub async fn recv(&mut self) -> Option<EffectsFrame> {
+        let frame = self.input.recv().await?;
+        Some(self.generator.push(&frame))
+    }
+}
@@ -0,0 +1,512 @@
+//! Downsampled long-term telemetry history for status trends.
+//!
+//! Every game gets one ring buffer per [`HistoryResolution`]. A ring slot is a
+//! time bucket holding min/max/mean of each configured [`HistoryField`], the
+//! last connection state seen and the number of dropped frames. Rings are
+//! allocated once, when a game's first frame arrives, and recording a frame
+//! touches one slot per resolution, so the per-frame cost is constant.
+//!
+//! ## Memory bound
+//!
+//! A game costs [`HistoryConfig::bytes_per_game`] bytes: for each resolution,
+//! `retention / bucket` slots of [`SLOT_HEADER_BYTES`] plus
+//! [`FIELD_ACCUMULATOR_BYTES`] per field. At most `max_games` games are kept,
+//! so the whole store stays below [`HistoryConfig::memory_upper_bound`]. The
+//! default config (1 s for 10 min, 10 s for 2 h, four fields, eight games)
+//! is about 145 KiB per game and 1.2 MiB in total.
+
+use std::collections::HashMap;
+use std::mem;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ConnectionState, NormalizedTelemetry, TelemetryFrame};
+
+/// Size of the per-slot bookkeeping shared by all fields.
+pub const SLOT_HEADER_BYTES: usize = mem::size_of::<SlotHeader>();
+/// Size of one field's accumulator within a slot.
+pub const FIELD_ACCUMULATOR_BYTES: usize = mem::size_of::<FieldAccumulator>();
+/// Window covered by [`HistoryStore::summary`].
+pub const SUMMARY_RANGE: Duration = Duration::from_secs(30 * 60);
+
+const EMPTY_SLOT: u64 = u64::MAX;
+
+/// Numeric telemetry field tracked by the history store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryField {
+    SpeedMs,
+    Rpm,
+    Throttle,
+    Brake,
+    SteeringAngle,
+    LateralG,
+    LongitudinalG,
+    FfbScalar,
+    FuelPercent,
+}
+
+impl HistoryField {
+    pub fn extract(self, telemetry: &NormalizedTelemetry) -> f32 {
+        match self {
+            Self::SpeedMs => telemetry.speed_ms,
+            Self::Rpm => telemetry.rpm,
+            Self::Throttle => telemetry.throttle,
+            Self::Brake => telemetry.brake,
+            Self::SteeringAngle => telemetry.steering_angle,
+            Self::LateralG => telemetry.lateral_g,
+            Self::LongitudinalG => telemetry.longitudinal_g,
+            Self::FfbScalar => telemetry.ffb_scalar,
+            Self::FuelPercent => telemetry.fuel_percent,
+        }
+    }
+}
+
+/// Bucket width and how long buckets of that width are kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryResolution {
+    pub bucket: Duration,
+    pub retention: Duration,
+}
+
+impl HistoryResolution {
+    pub const fn new(bucket: Duration, retention: Duration) -> Self {
+        Self { bucket, retention }
+    }
+
+    /// Number of buckets in the ring; at least one.
+    pub fn capacity(&self) -> usize {
+        let bucket = self.bucket.as_nanos().max(1);
+        usize::try_from(self.retention.as_nanos().div_ceil(bucket))
+            .unwrap_or(usize::MAX)
+            .max(1)
+    }
+
+    fn bucket_ns(&self) -> u64 {
+        u64::try_from(self.bucket.as_nanos())
+            .unwrap_or(u64::MAX)
+            .max(1)
+    }
+}
+
+/// Fields, resolutions and game limit of a [`HistoryStore`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryConfig {
+    pub resolutions: Vec<HistoryResolution>,
+    pub fields: Vec<HistoryField>,
+    /// Frames for further games are ignored once this many are tracked.
+    pub max_games: usize,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            resolutions: vec![
+                HistoryResolution::new(Duration::from_secs(1), Duration::from_secs(10 * 60)),
+                HistoryResolution::new(Duration::from_secs(10), Duration::from_secs(2 * 60 * 60)),
+            ],
+            fields: vec![
+                HistoryField::SpeedMs,
+                HistoryField::Rpm,
+                HistoryField::Throttle,
+                HistoryField::Brake,
+            ],
+            max_games: 8,
+        }
+    }
+}
+
+impl HistoryConfig {
+    /// Ring storage allocated for one game.
+    pub fn bytes_per_game(&self) -> usize {
+        let slot = SLOT_HEADER_BYTES + self.fields.len() * FIELD_ACCUMULATOR_BYTES;
+        self.resolutions
+            .iter()
+            .map(|resolution| resolution.capacity().saturating_mul(slot))
+            .fold(0, usize::saturating_add)
+    }
+
+    /// Ring storage of a store tracking `max_games` games.
+    pub fn memory_upper_bound(&self) -> usize {
+        self.bytes_per_game().saturating_mul(self.max_games)
+    }
+}
+
+/// One downsampled time bucket of a single field.
+///
+/// `min`, `max` and `mean` are zero when `samples` is zero, which happens for
+/// buckets that only recorded drops or a connection change.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Bucket {
+    /// Bucket start on the frame timestamp clock, in nanoseconds.
+    pub start_ns: u64,
+    pub min: f32,
+    pub max: f32,
+    pub mean: f32,
+    pub samples: u32,
+    /// Last connection state recorded in the bucket.
+    pub connection: ConnectionState,
+    /// Frames lost to sequence gaps or reported drops.
+    pub drops: u32,
+}
+
+/// Buckets of one field, as included in a diagnostic bundle.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldHistory {
+    pub field: HistoryField,
+    pub buckets: Vec<Bucket>,
+}
+
+/// Recent history of one game at its coarsest resolution.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameHistorySummary {
+    pub game_id: String,
+    pub resolution_ms: u64,
+    pub fields: Vec<FieldHistory>,
+}
+
+/// Compact recent-history section for diagnostic bundles.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HistorySummary {
+    pub games: Vec<GameHistorySummary>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SlotHeader {
+    index: u64,
+    drops: u32,
+    connection: ConnectionState,
+}
+
+impl SlotHeader {
+    const EMPTY: Self = Self {
+        index: EMPTY_SLOT,
+        drops: 0,
+        connection: ConnectionState::Disconnected,
+    };
+}
+
+#[derive(Debug, Clone, Copy)]
+struct FieldAccumulator {
+    min: f32,
+    max: f32,
+    count: u32,
+    sum: f64,
+}
+
+impl FieldAccumulator {
+    const EMPTY: Self = Self {
+        min: f32::INFINITY,
+        max: f32::NEG_INFINITY,
+        count: 0,
+        sum: 0.0,
+    };
+
+    fn add(&mut self, value: f32) {
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.count = self.count.saturating_add(1);
+        self.sum += f64::from(value);
+    }
+}
+
+#[derive(Debug)]
+struct Ring {
+    resolution: HistoryResolution,
+    bucket_ns: u64,
+    headers: Box<[SlotHeader]>,
+    fields: Box<[FieldAccumulator]>,
+    field_count: usize,
+    newest: Option<u64>,
+}
+
+impl Ring {
+    fn new(resolution: HistoryResolution, field_count: usize) -> Self {
+        let capacity = resolution.capacity();
+        Self {
+            resolution,
+            bucket_ns: resolution.bucket_ns(),
+            headers: vec![SlotHeader::EMPTY; capacity].into_boxed_slice(),
+            fields: vec![FieldAccumulator::EMPTY; capacity * field_count].into_boxed_slice(),
+            field_count,
+            newest: None,
+        }
+    }
+
+    fn capacity(&self) -> u64 {
+        self.headers.len() as u64
+    }
+
+    fn bytes(&self) -> usize {
+        self.headers.len() * SLOT_HEADER_BYTES + self.fields.len() * FIELD_ACCUMULATOR_BYTES
+    }
+
+    /// Slot for the bucket containing `timestamp_ns`, recycling an expired
+    /// slot; `None` if the bucket already fell out of the retention window.
+    fn slot(&mut self, timestamp_ns: u64, connection: ConnectionState) -> Option<usize> {
+        let index = timestamp_ns / self.bucket_ns;
+        if let Some(newest) = self.newest
+            && newest.saturating_sub(index) >= self.capacity()
+        {
+            return None;
+        }
+        let slot = (index % self.capacity()) as usize;
+        let header = &mut self.headers[slot];
+        if header.index != index {
+            if header.index != EMPTY_SLOT && header.index > index {
+                return None;
+            }
+            *header = SlotHeader {
+                index,
+                drops: 0,
+                connection,
+            };
+            self.fields[slot * self.field_count..(slot + 1) * self.field_count]
+                .fill(FieldAccumulator::EMPTY);
+        }
+        self.newest = Some(self.newest.map_or(index, |newest| newest.max(index)));
+        Some(slot)
+    }
+
+    fn buckets(&self, field: usize, range: Duration) -> Vec<Bucket> {
+        let Some(newest) = self.newest else {
+            return Vec::new();
+        };
+        let wanted = u64::try_from(range.as_nanos().div_ceil(u128::from(self.bucket_ns)))
+            .unwrap_or(u64::MAX)
+            .clamp(1, self.capacity());
+        let oldest = newest.saturating_sub(wanted - 1);
+        (oldest..=newest)
+            .filter_map(|index| {
+                let slot = (index % self.capacity()) as usize;
+                let header = self.headers[slot];
+                if header.index != index {
+                    return None;
+                }
+                let stats = self.fields[slot * self.field_count + field];
+                let (min, max, mean) = if stats.count == 0 {
+                    (0.0, 0.0, 0.0)
+                } else {
+                    (
+                        stats.min,
+                        stats.max,
+                        (stats.sum / f64::from(stats.count)) as f32,
+                    )
+                };
+                Some(Bucket {
+                    start_ns: index.saturating_mul(self.bucket_ns),
+                    min,
+                    max,
+                    mean,
+                    samples: stats.count,
+                    connection: header.connection,
+                    drops: header.drops,
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+struct GameHistory {
+    rings: Vec<Ring>,
+    connection: ConnectionState,
+    last_sequence: Option<u64>,
+}
+
+impl GameHistory {
+    fn new(config: &HistoryConfig) -> Self {
+        Self {
+            rings: config
+                .resolutions
+                .iter()
+                .map(|resolution| Ring::new(*resolution, config.fields.len()))
+                .collect(),
+            connection: ConnectionState::Disconnected,
+            last_sequence: None,
+        }
+    }
+
+    fn record_frame(&mut self, fields: &[HistoryField], frame: &TelemetryFrame) {
+        let gap = match self.last_sequence {
+            Some(last) if frame.sequence > last => frame.sequence - last - 1,
+            _ => 0,
+        };
+        self.last_sequence = Some(frame.sequence);
+        self.connection = ConnectionState::Connected;
+        let drops = u32::try_from(gap).unwrap_or(u32::MAX);
+
+        for ring in &mut self.rings {
+            let Some(slot) = ring.slot(frame.timestamp_ns, self.connection) else {
+                continue;
+            };
+            let header = &mut ring.headers[slot];
+            header.connection = self.connection;
+            header.drops = header.drops.saturating_add(drops);
+            let accumulators =
+                &mut ring.fields[slot * ring.field_count..(slot + 1) * ring.field_count];
+            for (accumulator, field) in accumulators.iter_mut().zip(fields) {
+                let value = field.extract(&frame.data);
+                if value.is_finite() {
+                    accumulator.add(value);
+                }
+            }
+        }
+    }
+
+    fn record_event(&mut self, timestamp_ns: u64, connection: ConnectionState, drops: u32) {
+        self.connection = connection;
+        if !connection.is_connected() {
+            // Sequence numbers restart with the next source.
+            self.last_sequence = None;
+        }
+        for ring in &mut self.rings {
+            if let Some(slot) = ring.slot(timestamp_ns, connection) {
+                let header = &mut ring.headers[slot];
+                header.connection = connection;
+                header.drops = header.drops.saturating_add(drops);
+            }
+        }
+    }
+}
+
+/// Per-game downsampled history with bounded memory.
+#[derive(Debug)]
+pub struct HistoryStore {
+    config: HistoryConfig,
+    games: HashMap<String, GameHistory>,
+}
+
+impl Default for HistoryStore {
+    fn default() -> Self {
+        Self::new(HistoryConfig::default())
+    }
+}
+
+impl HistoryStore {
+    pub fn new(config: HistoryConfig) -> Self {
+        Self {
+            config,
+            games: HashMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> &HistoryConfig {
+        &self.config
+    }
+
+    /// Ring storage currently allocated; never above
+    /// [`HistoryConfig::memory_upper_bound`].
+    pub fn memory_bytes(&self) -> usize {
+        self.games
+            .values()
+            .flat_map(|game| &game.rings)
+            .map(Ring::bytes)
+            .sum()
+    }
+
+    /// Game ids with recorded history, sorted.
+    pub fn game_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.games.keys().cloned().collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Fold a forwarded frame into every resolution of `game_id`.
+    ///
+    /// Allocates only for the first frame of a game; gaps in
+    /// [`TelemetryFrame::sequence`] are counted as drops.
+    pub fn record(&mut self, game_id: &str, frame: &TelemetryFrame) {
+        let fields = &self.config.fields;
+        if let Some(game) = self.games.get_mut(game_id) {
+            game.record_frame(fields, frame);
+        } else if self.games.len() < self.config.max_games {
+            let mut game = GameHistory::new(&self.config);
+            game.record_frame(fields, frame);
+            self.games.insert(game_id.to_string(), game);
+        }
+    }
+
+    /// Record a connection state change for a game that already has history.
+    pub fn record_connection(&mut self, game_id: &str, state: ConnectionState, timestamp_ns: u64) {
+        if let Some(game) = self.games.get_mut(game_id) {
+            game.record_event(timestamp_ns, state, 0);
+        }
+    }
+
+    /// Forget `game_id`'s last sequence number, so frames a caller skipped on
+    /// purpose are not counted as drops.
+    pub fn reset_sequence(&mut self, game_id: &str) {
+        if let Some(game) = self.games.get_mut(game_id) {
+            game.last_sequence = None;
+        }
+    }
+
+    /// Record frames dropped outside the sequence-number stream.
+    pub fn record_drops(&mut self, game_id: &str, count: u32, timestamp_ns: u64) {
+        if let Some(game) = self.games.get_mut(game_id) {
+            let connection = game.connection;
+            game.record_event(timestamp_ns, connection, count);
+        }
+    }
+
+    /// Buckets of `field` at the resolution whose bucket width is
+    /// `resolution`, covering `range` back from the newest bucket, oldest first.
+    ///
+    /// Empty when the game, field or resolution is not tracked. Buckets in
+    /// which nothing was recorded are skipped.
+    pub fn history(
+        &self,
+        game_id: &str,
+        field: HistoryField,
+        resolution: Duration,
+        range: Duration,
+    ) -> Vec<Bucket> {
+        let Some(game) = self.games.get(game_id) else {
+            return Vec::new();
+        };
+        let Some(field) = self.config.fields.iter().position(|f| *f == field) else {
+            return Vec::new();
+        };
+        game.rings
+            .iter()
+            .find(|ring| ring.resolution.bucket == resolution)
+            .map(|ring| ring.buckets(field, range))
+            .unwrap_or_default()
+    }
+
+    /// The last [`SUMMARY_RANGE`] of every game at its coarsest resolution.
+    pub fn summary(&self) -> HistorySummary {
+        let Some(coarsest) = self
+            .config
+            .resolutions
+            .iter()
+            .map(|resolution| resolution.bucket)
+            .max()
+        else {
+            return HistorySummary::default();
+        };
+        let games = self
+            .game_ids()
+            .into_iter()
+            .map(|game_id| {
+                let fields = self
+                    .config
+                    .fields
+                    .iter()
+                    .map(|field| FieldHistory {
+                        field: *field,
+                        buckets: self.history(&game_id, *field, coarsest, SUMMARY_RANGE),
+                    })
+                    .collect();
+                GameHistorySummary {
+                    game_id,
+                    resolution_ms: u64::try_from(coarsest.as_millis()).unwrap_or(u64::MAX),
+                    fields,
+                }
+            })
+            .collect();
+        HistorySummary { games }
+    }
+}
@@ -5,7 +5,13 @@
 //!
 //! ## Modules
 //! - `contracts` - Normalized telemetry types (`NormalizedTelemetry`, `TelemetryFlags`, etc.)
+//! - `delta` - Derived channels and edge events between consecutive frames
+//! - `effects` - Wind, bass-shaker and engine-pulse outputs from telemetry
+//! - `history` - Downsampled per-game history buckets for status trends
 //! - `rate_limiter` - Rate limiting utilities for RT paths
+//! - `resample` - Fixed-rate resampling of telemetry frames
+//! - `shift_lights` - Shift-light LED states from a JSON-loadable profile
+//! - `units` - Adapter unit manifests and unit plausibility checks
 //! - `bdd_metrics` - BDD-oriented matrix parity metrics
 //! - `integration` - Matrix/registry coverage validation utilities (feature: orchestrator)
 //! - `orchestrator` - Telemetry service coordination (feature: orchestrator)
@@ -14,6 +20,7 @@
 
 use anyhow::Result;
 use async_trait::async_trait;
+use racing_wheel_telemetry_contracts::{KMH_PER_MS, MPH_PER_MS};
 use serde::{Deserialize, Serialize};
 use std::time::{Duration, Instant};
 use thiserror::Error;
@@ -21,16 +28,34 @@ use tokio::sync::mpsc;
 
 pub mod bdd_metrics;
 pub mod contracts;
+pub mod delta;
+pub mod effects;
+pub mod history;
 #[cfg(feature = "orchestrator")]
 pub mod integration;
 #[cfg(feature = "orchestrator")]
 pub mod orchestrator;
 pub mod rate_limiter;
+pub mod resample;
+pub mod shift_lights;
+pub mod units;
 
 pub use bdd_metrics::{BddMatrixMetrics, MatrixParityPolicy, RuntimeBddMatrixMetrics};
 pub use contracts::{
-    FlagCoverage, NormalizedTelemetry, TelemetryFieldCoverage, TelemetryFlags, TelemetryFrame,
-    TelemetryValue,
+    DEFAULT_MAX_OPPONENTS, ExtendedKey, FlagCoverage, NormalizedTelemetry, OpponentSnapshot,
+    PenaltyEvent, PenaltyKind, PenaltyState, PenaltyTracker, SessionTiming, TelemetryFieldCoverage,
+    TelemetryFlags, TelemetryFrame, TelemetryValidator, TelemetryValue, TelemetryValueDepthError,
+    TimingCoverage, TireCorner, TireData, ValidationIssue, ValidationPolicy, bound_opponents,
+};
+pub use delta::{
+    DEFAULT_MAX_FRAME_GAP, DeltaComputer, DeltaEvent, DeltaStats, FlagKind, TelemetryDelta,
+};
+pub use effects::{
+    CurveAnchor, DEFAULT_CYLINDERS, EffectCurve, EffectsChannelGenerator, EffectsFrame,
+    EffectsProfile, EffectsProfileError, EffectsReceiver, EffectsSmoothing,
+};
+pub use history::{
+    Bucket, HistoryConfig, HistoryField, HistoryResolution, HistoryStore, HistorySummary,
 };
 #[cfg(feature = "orchestrator")]
 pub use integration::{
@@ -41,22 +66,50 @@ pub use integration::{
 #[cfg(feature = "orchestrator")]
 pub use orchestrator::TelemetryService;
 pub use rate_limiter::{AdaptiveRateLimiter, RateLimiter, RateLimiterStats};
+pub use resample::{ResampledReceiver, Resampler, ResamplerError};
+pub use shift_lights::{
+    DEFAULT_BLINK_HZ, DEFAULT_REDLINE_RPM, FlagOverride, LedSource, LedZone, PitLimiterAnimation,
+    Rgb, ShiftLightProfile, ShiftLightProfileError, ShiftLightState,
+};
+pub use units::{
+    FieldUnit, PlausibleRange, Unit, UnitConversion, UnitManifest, UnitManifestIssue,
+    UnitSuspicion, VehicleTier, canonical_unit, check_unit_ranges,
+};
 
 pub type ConnectionStateReceiver = mpsc::Receiver<ConnectionStateEvent>;
 pub type ConnectionStateSender = mpsc::Sender<ConnectionStateEvent>;
 
 pub const DEFAULT_DISCONNECTION_TIMEOUT_MS: u64 = 2000;
 
+/// Frame intervals of silence that count as a disconnection in adaptive mode.
+pub const DEFAULT_TIMEOUT_MULTIPLIER: f32 = 3.0;
+
+/// Weight of each new inter-frame interval in the tracker's moving average.
+const INTERVAL_EWMA_ALPHA: f64 = 0.1;
+
+/// Mean deviations added to the average interval, so a jittery stream's
+/// longer gaps are not mistaken for silence.
+const INTERVAL_JITTER_WEIGHT: f64 = 2.0;
+
+/// Monotonic timestamp in nanoseconds since a process-wide epoch.
+///
+/// Adapters stamp `TelemetryFrame::timestamp_ns` and the tracker stamps
+/// `ConnectionStateEvent::timestamp_mono_ns` with it, so the two can be
+/// compared with [`correlate`].
 pub fn telemetry_now_ns() -> u64 {
-    static EPOCH: std::sync::OnceLock<Instant> = std::sync::OnceLock::new();
-    let epoch = EPOCH.get_or_init(Instant::now);
     Instant::now()
-        .checked_duration_since(*epoch)
+        .checked_duration_since(telemetry_epoch())
         .map(|d| d.as_nanos())
         .unwrap_or(0)
         .min(u64::MAX as u128) as u64
 }
 
+/// Instant [`telemetry_now_ns`] counts from.
+pub(crate) fn telemetry_epoch() -> Instant {
+    static EPOCH: std::sync::OnceLock<Instant> = std::sync::OnceLock::new();
+    *EPOCH.get_or_init(Instant::now)
+}
+
 pub type TelemetryReceiver = mpsc::Receiver<TelemetryFrame>;
 
 #[async_trait]
@@ -92,6 +145,7 @@ pub struct GameTelemetry {
     pub slip_angle_fr: f32,
     pub slip_angle_rl: f32,
     pub slip_angle_rr: f32,
+    pub timing: Option<SessionTiming>,
 }
 
 impl Default for GameTelemetry {
@@ -110,6 +164,7 @@ impl Default for GameTelemetry {
             slip_angle_fr: 0.0,
             slip_angle_rl: 0.0,
             slip_angle_rr: 0.0,
+            timing: None,
         }
     }
 }
@@ -127,11 +182,11 @@ impl GameTelemetry {
     }
 
     pub fn speed_kmh(&self) -> f32 {
-        self.speed_mps * 3.6
+        self.speed_mps * KMH_PER_MS
     }
 
     pub fn speed_mph(&self) -> f32 {
-        self.speed_mps * 2.237
+        self.speed_mps * MPH_PER_MS
     }
 
     pub fn average_slip_angle(&self) -> f32 {
@@ -155,7 +210,7 @@ impl GameTelemetry {
     }
 
     pub fn to_normalized(&self) -> NormalizedTelemetry {
-        NormalizedTelemetry::builder()
+        let builder = NormalizedTelemetry::builder()
             .rpm(self.rpm)
             .speed_ms(self.speed_mps)
             .gear(self.gear)
@@ -169,8 +224,11 @@ impl GameTelemetry {
             .slip_angle_rl(self.slip_angle_rl)
             .slip_angle_rr(self.slip_angle_rr)
             .slip_ratio(self.average_slip_angle().abs().min(1.0))
-            .timestamp(self.timestamp)
-            .build()
+            .timestamp(self.timestamp);
+        match self.timing {
+            Some(timing) => builder.timing(timing).build(),
+            None => builder.build(),
+        }
     }
 }
 
@@ -287,7 +345,14 @@ pub struct ConnectionStateEvent {
     pub game_id: String,
     pub previous_state: ConnectionState,
     pub new_state: ConnectionState,
-    pub timestamp_ns: u64,
+    /// [`telemetry_now_ns`] at the change, on the same epoch as frame
+    /// timestamps.
+    #[serde(default)]
+    pub timestamp_mono_ns: u64,
+    /// Wall-clock time of the change as Unix nanoseconds, for display only:
+    /// it steps whenever the system clock is adjusted.
+    #[serde(alias = "timestamp_ns")]
+    pub timestamp_unix_ns: u64,
     pub reason: Option<String>,
 }
 
@@ -298,7 +363,7 @@ impl ConnectionStateEvent {
         new_state: ConnectionState,
         reason: Option<String>,
     ) -> Self {
-        let timestamp_ns = std::time::SystemTime::now()
+        let timestamp_unix_ns = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .map(|d| d.as_nanos() as u64)
             .unwrap_or(0);
@@ -307,7 +372,8 @@ impl ConnectionStateEvent {
             game_id: game_id.into(),
             previous_state,
             new_state,
-            timestamp_ns,
+            timestamp_mono_ns: telemetry_now_ns(),
+            timestamp_unix_ns,
             reason,
         }
     }
@@ -321,12 +387,30 @@ impl ConnectionStateEvent {
     }
 }
 
+/// Monotonic time between a connection state change and a frame, in either
+/// order; unaffected by wall-clock adjustments.
+pub fn correlate(event: &ConnectionStateEvent, frame: &TelemetryFrame) -> Duration {
+    Duration::from_nanos(event.timestamp_mono_ns.abs_diff(frame.timestamp_ns))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DisconnectionConfig {
+    /// Silence that counts as a disconnection, unless `adaptive` is set.
     pub timeout_ms: u64,
     pub auto_reconnect: bool,
     pub max_reconnect_attempts: u32,
     pub reconnect_delay_ms: u64,
+    /// Derive the timeout from the frame interval instead of `timeout_ms`:
+    /// `timeout_multiplier` times the larger of the observed interval and
+    /// the expected one. `timeout_ms` still applies until either is known.
+    #[serde(default)]
+    pub adaptive: bool,
+    #[serde(default = "default_timeout_multiplier")]
+    pub timeout_multiplier: f32,
+}
+
+fn default_timeout_multiplier() -> f32 {
+    DEFAULT_TIMEOUT_MULTIPLIER
 }
 
 impl Default for DisconnectionConfig {
@@ -336,6 +420,8 @@ impl Default for DisconnectionConfig {
             auto_reconnect: true,
             max_reconnect_attempts: 0,
             reconnect_delay_ms: 1000,
+            adaptive: false,
+            timeout_multiplier: DEFAULT_TIMEOUT_MULTIPLIER,
         }
     }
 }
@@ -348,6 +434,15 @@ impl DisconnectionConfig {
         }
     }
 
+    /// Adaptive timeouts of `timeout_multiplier` frame intervals.
+    pub fn adaptive(timeout_multiplier: f32) -> Self {
+        Self {
+            adaptive: true,
+            timeout_multiplier,
+            ..Default::default()
+        }
+    }
+
     pub fn timeout(&self) -> Duration {
         Duration::from_millis(self.timeout_ms)
     }
@@ -361,6 +456,11 @@ impl DisconnectionConfig {
 pub struct DisconnectionTracker {
     config: DisconnectionConfig,
     last_data_time: Option<Instant>,
+    expected_interval: Option<Duration>,
+    /// Moving average of the interval between frames while connected, in
+    /// seconds, and of its absolute deviation.
+    interval_mean_s: Option<f64>,
+    interval_deviation_s: f64,
     state: ConnectionState,
     reconnect_attempts: u32,
     state_sender: Option<ConnectionStateSender>,
@@ -372,6 +472,9 @@ impl DisconnectionTracker {
         Self {
             config,
             last_data_time: None,
+            expected_interval: None,
+            interval_mean_s: None,
+            interval_deviation_s: 0.0,
             state: ConnectionState::Disconnected,
             reconnect_attempts: 0,
             state_sender: None,
@@ -393,8 +496,60 @@ impl DisconnectionTracker {
         rx
     }
 
+    /// Seed the adaptive timeout with the adapter's expected update rate.
+    pub fn set_expected_interval(&mut self, interval: Duration) {
+        self.expected_interval = Some(interval).filter(|interval| !interval.is_zero());
+    }
+
+    pub fn expected_interval(&self) -> Option<Duration> {
+        self.expected_interval
+    }
+
+    /// Moving average of the interval between frames while connected.
+    pub fn observed_interval(&self) -> Option<Duration> {
+        self.interval_mean_s.map(Duration::from_secs_f64)
+    }
+
+    /// Frame rate implied by [`Self::observed_interval`].
+    pub fn observed_rate_hz(&self) -> Option<f64> {
+        self.interval_mean_s
+            .filter(|mean| *mean > 0.0)
+            .map(|mean| 1.0 / mean)
+    }
+
+    /// Silence after which the tracker declares a disconnection.
+    pub fn effective_timeout(&self) -> Duration {
+        if !self.config.adaptive {
+            return self.config.timeout();
+        }
+        let observed = self
+            .interval_mean_s
+            .map(|mean| mean + INTERVAL_JITTER_WEIGHT * self.interval_deviation_s);
+        let expected = self
+            .expected_interval
+            .map(|interval| interval.as_secs_f64());
+        let interval = match (observed, expected) {
+            (Some(observed), Some(expected)) => observed.max(expected),
+            (Some(interval), None) | (None, Some(interval)) => interval,
+            (None, None) => return self.config.timeout(),
+        };
+        let timeout_s = interval * f64::from(self.config.timeout_multiplier.max(1.0));
+        Duration::try_from_secs_f64(timeout_s).unwrap_or_else(|_| self.config.timeout())
+    }
+
     pub fn record_data_received(&mut self) {
-        self.last_data_time = Some(Instant::now());
+        self.record_data_received_at(Instant::now());
+    }
+
+    /// [`Self::record_data_received`] for a frame that arrived at `now`.
+    pub fn record_data_received_at(&mut self, now: Instant) {
+        // The gap before reconnecting says nothing about the stream's rate.
+        if self.state == ConnectionState::Connected
+            && let Some(last) = self.last_data_time
+        {
+            self.observe_interval(now.saturating_duration_since(last));
+        }
+        self.last_data_time = Some(now);
 
         if self.state != ConnectionState::Connected {
             self.transition_to(
@@ -405,18 +560,42 @@ impl DisconnectionTracker {
         }
     }
 
+    fn observe_interval(&mut self, interval: Duration) {
+        let sample = interval.as_secs_f64();
+        match self.interval_mean_s {
+            None => self.interval_mean_s = Some(sample),
+            Some(mean) => {
+                let error = sample - mean;
+                self.interval_mean_s = Some(mean + INTERVAL_EWMA_ALPHA * error);
+                self.interval_deviation_s +=
+                    INTERVAL_EWMA_ALPHA * (error.abs() - self.interval_deviation_s);
+            }
+        }
+    }
+
     pub fn is_timed_out(&self) -> bool {
+        self.is_timed_out_at(Instant::now())
+    }
+
+    /// [`Self::is_timed_out`] as of `now`.
+    pub fn is_timed_out_at(&self, now: Instant) -> bool {
         match self.last_data_time {
-            Some(last_time) => last_time.elapsed() > self.config.timeout(),
+            Some(last_time) => now.saturating_duration_since(last_time) > self.effective_timeout(),
             None => false,
         }
     }
 
     pub fn check_disconnection(&mut self) -> ConnectionState {
-        if self.state == ConnectionState::Connected && self.is_timed_out() {
+        self.check_disconnection_at(Instant::now())
+    }
+
+    /// [`Self::check_disconnection`] as of `now`.
+    pub fn check_disconnection_at(&mut self, now: Instant) -> ConnectionState {
+        if self.state == ConnectionState::Connected && self.is_timed_out_at(now) {
+            let timeout = self.effective_timeout();
             self.transition_to(
                 ConnectionState::Disconnected,
-                Some(format!("No data received for {}ms", self.config.timeout_ms)),
+                Some(format!("No data received for {}ms", timeout.as_millis())),
             );
         }
         self.state
@@ -580,6 +759,10 @@ mod tests {
             steering_angle: 0.15,
             lateral_g: 1.5,
             longitudinal_g: 0.5,
+            slip_angle_fl: 0.02,
+            slip_angle_fr: 0.03,
+            slip_angle_rl: 0.04,
+            slip_angle_rr: 0.05,
             ..Default::default()
         };
 
@@ -593,6 +776,16 @@ mod tests {
         assert_eq!(normalized.steering_angle, 0.15);
         assert_eq!(normalized.lateral_g, 1.5);
         assert_eq!(normalized.longitudinal_g, 0.5);
+        assert_eq!(
+            [
+                normalized.slip_angle_fl,
+                normalized.slip_angle_fr,
+                normalized.slip_angle_rl,
+                normalized.slip_angle_rr,
+            ],
+            [0.02, 0.03, 0.04, 0.05]
+        );
+        assert_eq!(normalized.timestamp, telemetry.timestamp);
         Ok(())
     }
 
@@ -616,6 +809,30 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_game_telemetry_to_normalized_timing() -> TestResult {
+        let timing = SessionTiming {
+            current_lap_ms: Some(61_000.0),
+            lap_number: Some(3),
+            session_time_remaining_ms: Some(-5.0),
+            ..Default::default()
+        };
+        let telemetry = GameTelemetry {
+            timing: Some(timing),
+            ..Default::default()
+        };
+
+        let normalized = telemetry.to_normalized();
+        let Some(timing) = normalized.timing else {
+            return Err("timing should carry over".into());
+        };
+        assert_eq!(timing.current_lap_ms, Some(61_000.0));
+        assert_eq!(timing.lap_number, Some(3));
+        assert_eq!(timing.session_time_remaining_ms, None);
+        assert!(GameTelemetry::default().to_normalized().timing.is_none());
+        Ok(())
+    }
+
     #[test]
     fn test_game_telemetry_from_into_normalized() -> TestResult {
         let telemetry = GameTelemetry {
@@ -625,10 +842,11 @@ mod tests {
             ..Default::default()
         };
 
-        let normalized: NormalizedTelemetry = telemetry.into();
+        let normalized: NormalizedTelemetry = telemetry.clone().into();
         assert_eq!(normalized.speed_ms, 30.0);
         assert_eq!(normalized.rpm, 4000.0);
         assert_eq!(normalized.gear, 3);
+        assert_eq!(normalized, telemetry.to_normalized());
         Ok(())
     }
 
@@ -786,11 +1004,38 @@ mod tests {
         assert_eq!(event.game_id, "forza");
         assert_eq!(event.previous_state, ConnectionState::Disconnected);
         assert_eq!(event.new_state, ConnectionState::Connected);
-        assert!(event.timestamp_ns > 0);
+        assert!(event.timestamp_unix_ns > 0);
         assert_eq!(event.reason, Some("Data received".to_string()));
         Ok(())
     }
 
+    #[test]
+    fn test_correlate_ignores_wall_clock_skew() -> TestResult {
+        let frame = TelemetryFrame::new(NormalizedTelemetry::default(), telemetry_now_ns(), 0, 0);
+        let mut event = ConnectionStateEvent::new(
+            "forza",
+            ConnectionState::Connected,
+            ConnectionState::Disconnected,
+            None,
+        );
+        // As if NTP stepped the clock back an hour between frame and event.
+        event.timestamp_unix_ns = event.timestamp_unix_ns.saturating_sub(3_600_000_000_000);
+
+        assert!(event.timestamp_mono_ns >= frame.timestamp_ns);
+        assert!(correlate(&event, &frame) < Duration::from_millis(100));
+        Ok(())
+    }
+
+    #[test]
+    fn test_connection_state_event_reads_legacy_timestamp() -> TestResult {
+        let event: ConnectionStateEvent = serde_json::from_str(
+            r#"{"game_id":"forza","previous_state":"Connected","new_state":"Disconnected","timestamp_ns":42,"reason":null}"#,
+        )?;
+        assert_eq!(event.timestamp_unix_ns, 42);
+        assert_eq!(event.timestamp_mono_ns, 0);
+        Ok(())
+    }
+
     #[test]
     fn test_connection_state_event_is_disconnection() -> TestResult {
         let event = ConnectionStateEvent::new(
@@ -1055,6 +1300,103 @@ mod tests {
         Ok(())
     }
 
+    fn config_timeout() -> Duration {
+        Duration::from_millis(DEFAULT_DISCONNECTION_TIMEOUT_MS)
+    }
+
+    /// Record frames at `start` plus each cumulative interval; returns the
+    /// last arrival.
+    fn feed(
+        tracker: &mut DisconnectionTracker,
+        start: Instant,
+        intervals: impl IntoIterator<Item = Duration>,
+    ) -> Instant {
+        let mut now = start;
+        tracker.record_data_received_at(now);
+        for interval in intervals {
+            assert!(
+                !tracker.is_timed_out_at(now + interval),
+                "timed out {interval:?} after a frame"
+            );
+            now += interval;
+            tracker.record_data_received_at(now);
+        }
+        now
+    }
+
+    #[test]
+    fn test_adaptive_timeout_follows_frame_interval() -> TestResult {
+        let config = DisconnectionConfig::adaptive(DEFAULT_TIMEOUT_MULTIPLIER);
+        let mut tracker = DisconnectionTracker::new("test_game", config);
+        let step = Duration::from_millis(5);
+        let last = feed(&mut tracker, Instant::now(), std::iter::repeat_n(step, 200));
+
+        let rate = tracker.observed_rate_hz().ok_or("no observed rate")?;
+        assert!((rate - 200.0).abs() < 1.0, "{rate}");
+        assert!(!tracker.is_timed_out_at(last + Duration::from_millis(14)));
+        assert_eq!(
+            tracker.check_disconnection_at(last + Duration::from_millis(16)),
+            ConnectionState::Disconnected
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_adaptive_timeout_tolerates_jitter() -> TestResult {
+        let config = DisconnectionConfig::adaptive(DEFAULT_TIMEOUT_MULTIPLIER);
+        let mut tracker = DisconnectionTracker::new("test_game", config);
+        tracker.set_expected_interval(Duration::from_millis(100));
+        // Deterministic spread across 50–150 ms.
+        let mut seed = 0x2545_f491_u32;
+        let intervals = std::iter::repeat_with(move || {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            Duration::from_millis(50 + u64::from(seed >> 16) % 101)
+        });
+        let last = feed(&mut tracker, Instant::now(), intervals.take(500));
+
+        assert_eq!(
+            tracker.check_disconnection_at(last + Duration::from_millis(150)),
+            ConnectionState::Connected
+        );
+        assert!(tracker.effective_timeout() < config_timeout());
+        Ok(())
+    }
+
+    #[test]
+    fn test_fixed_timeout_ignores_frame_interval() -> TestResult {
+        let mut tracker = DisconnectionTracker::with_defaults("test_game");
+        tracker.set_expected_interval(Duration::from_millis(5));
+        let last = feed(
+            &mut tracker,
+            Instant::now(),
+            std::iter::repeat_n(Duration::from_millis(5), 50),
+        );
+
+        assert_eq!(tracker.effective_timeout(), config_timeout());
+        assert!(tracker.observed_rate_hz().is_some());
+        assert!(!tracker.is_timed_out_at(last + Duration::from_millis(1500)));
+        assert!(tracker.is_timed_out_at(last + Duration::from_millis(2001)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_adaptive_timeout_falls_back_without_estimate() -> TestResult {
+        let tracker = DisconnectionTracker::new("test_game", DisconnectionConfig::adaptive(3.0));
+        assert_eq!(tracker.effective_timeout(), config_timeout());
+        assert!(tracker.observed_rate_hz().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_disconnection_config_deserializes_without_adaptive_fields() -> TestResult {
+        let config: DisconnectionConfig = serde_json::from_str(
+            r#"{"timeout_ms":500,"auto_reconnect":true,"max_reconnect_attempts":0,"reconnect_delay_ms":100}"#,
+        )?;
+        assert!(!config.adaptive);
+        assert_eq!(config.timeout_multiplier, DEFAULT_TIMEOUT_MULTIPLIER);
+        Ok(())
+    }
+
     // ── TelemetryError tests ──────────────────────────────────────────────
 
     #[test]
@@ -0,0 +1,204 @@
+//! Resampling telemetry frames to a fixed output rate.
+//!
+//! Games send frames at their own rate, while outputs such as LED
+//! controllers want a steady one. [`Resampler`] buffers incoming frames and
+//! produces one frame per output slot, spaced `1 / rate_hz` apart on the
+//! input frames' `timestamp_ns` clock:
+//!
+//! - continuous channels such as `rpm` and `speed_ms` are linearly
+//!   interpolated between the frames on either side of the slot;
+//! - everything else (gear, flags, ids, extended values) is held from the
+//!   frame at or before the slot;
+//! - once no new frame arrives the last one is held, and after the
+//!   staleness limit held frames carry [`ExtendedKey::RESAMPLED_STALE`].
+//!
+//! Output sequence numbers count up from zero independently of the input.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use thiserror::Error;
+use tokio::sync::mpsc;
+use tokio::time::{Interval, MissedTickBehavior};
+use tracing::debug;
+
+use crate::{ExtendedKey, NormalizedTelemetry, TelemetryFrame, TelemetryValue, telemetry_now_ns};
+
+/// Input frames kept before the oldest is dropped, for callers that push
+/// without polling.
+pub const MAX_BUFFERED_FRAMES: usize = 64;
+
+/// Channels interpolated between frames.
+const CONTINUOUS: [fn(&mut NormalizedTelemetry) -> &mut f32; 12] = [
+    |t| &mut t.speed_ms,
+    |t| &mut t.rpm,
+    |t| &mut t.throttle,
+    |t| &mut t.brake,
+    |t| &mut t.clutch,
+    |t| &mut t.steering_angle,
+    |t| &mut t.lateral_g,
+    |t| &mut t.longitudinal_g,
+    |t| &mut t.vertical_g,
+    |t| &mut t.slip_ratio,
+    |t| &mut t.ffb_scalar,
+    |t| &mut t.ffb_torque_nm,
+];
+
+#[derive(Error, Debug, Clone, Copy, PartialEq)]
+pub enum ResamplerError {
+    #[error("output rate must be a positive number of Hz, got {0}")]
+    InvalidRate(f32),
+}
+
+/// Pull-based resampler: [`push`](Self::push) input frames and
+/// [`poll_next`](Self::poll_next) output ones.
+#[derive(Debug, Clone)]
+pub struct Resampler {
+    period_ns: u64,
+    staleness_limit_ns: u64,
+    frames: VecDeque<TelemetryFrame>,
+    next_slot_ns: Option<u64>,
+    next_sequence: u64,
+}
+
+impl Resampler {
+    /// Resample to `rate_hz`, marking held frames stale once the newest
+    /// input is older than `staleness_limit`.
+    pub fn new(rate_hz: f32, staleness_limit: Duration) -> Result<Self, ResamplerError> {
+        let period_ns = 1e9 / f64::from(rate_hz);
+        if !(rate_hz > 0.0 && period_ns.is_finite() && period_ns >= 1.0) {
+            return Err(ResamplerError::InvalidRate(rate_hz));
+        }
+        Ok(Self {
+            period_ns: period_ns.round() as u64,
+            staleness_limit_ns: u64::try_from(staleness_limit.as_nanos()).unwrap_or(u64::MAX),
+            frames: VecDeque::new(),
+            next_slot_ns: None,
+            next_sequence: 0,
+        })
+    }
+
+    /// Time between output frames.
+    pub fn period(&self) -> Duration {
+        Duration::from_nanos(self.period_ns)
+    }
+
+    /// Buffer an input frame. Frames older than the newest buffered one are
+    /// dropped; a frame with the same timestamp replaces it.
+    pub fn push(&mut self, frame: TelemetryFrame) {
+        match self.frames.back() {
+            Some(last) if frame.timestamp_ns < last.timestamp_ns => {
+                debug!(
+                    timestamp_ns = frame.timestamp_ns,
+                    newest_ns = last.timestamp_ns,
+                    "Dropping out-of-order frame from resampler input"
+                );
+                return;
+            }
+            Some(last) if frame.timestamp_ns == last.timestamp_ns => {
+                self.frames.pop_back();
+            }
+            _ => {}
+        }
+        self.next_slot_ns.get_or_insert(frame.timestamp_ns);
+        self.frames.push_back(frame);
+        if self.frames.len() > MAX_BUFFERED_FRAMES {
+            self.frames.pop_front();
+        }
+    }
+
+    /// The output frame for the latest slot at or before `deadline_ns`, or
+    /// `None` if that slot was already produced or nothing was pushed yet.
+    ///
+    /// Slots that passed entirely between two polls are skipped, so a late
+    /// caller gets the current value rather than a burst of old ones.
+    pub fn poll_next(&mut self, deadline_ns: u64) -> Option<TelemetryFrame> {
+        let mut slot = self.next_slot_ns?;
+        if slot > deadline_ns {
+            return None;
+        }
+        slot += (deadline_ns - slot) / self.period_ns * self.period_ns;
+        self.next_slot_ns = Some(slot.saturating_add(self.period_ns));
+
+        // Later slots never need frames before the one at or before `slot`.
+        let before = self
+            .frames
+            .iter()
+            .rposition(|frame| frame.timestamp_ns <= slot)
+            .unwrap_or(0);
+        self.frames.drain(..before);
+
+        let mut data = self.sample_at(slot)?;
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+        let raw_size = self.frames.front().map_or(0, |frame| frame.raw_size);
+        if self.frames.back().is_some_and(|newest| {
+            slot.saturating_sub(newest.timestamp_ns) > self.staleness_limit_ns
+        }) {
+            data.extended.insert(
+                ExtendedKey::RESAMPLED_STALE.name.to_string(),
+                TelemetryValue::Boolean(true),
+            );
+        }
+        Some(TelemetryFrame::new(data, slot, sequence, raw_size))
+    }
+
+    /// Telemetry at `slot`, given the buffer now starts at the frame at or
+    /// before it.
+    fn sample_at(&self, slot: u64) -> Option<NormalizedTelemetry> {
+        let held = self.frames.front()?;
+        let mut data = held.data.clone();
+        let Some(next) = self.frames.get(1) else {
+            return Some(data);
+        };
+        if held.timestamp_ns > slot {
+            return Some(data);
+        }
+
+        let span = (next.timestamp_ns - held.timestamp_ns) as f64;
+        let fraction = ((slot - held.timestamp_ns) as f64 / span) as f32;
+        let mut upper = next.data.clone();
+        for channel in CONTINUOUS {
+            let (from, to) = (*channel(&mut data), *channel(&mut upper));
+            if from.is_finite() && to.is_finite() {
+                *channel(&mut data) = from + (to - from) * fraction;
+            }
+        }
+        Some(data)
+    }
+}
+
+/// Async wrapper that resamples a telemetry channel in real time, polling
+/// its [`Resampler`] against [`telemetry_now_ns`] once per output period.
+pub struct ResampledReceiver {
+    input: mpsc::Receiver<TelemetryFrame>,
+    resampler: Resampler,
+    ticker: Interval,
+}
+
+impl ResampledReceiver {
+    /// Must be called inside a Tokio runtime.
+    pub fn new(input: mpsc::Receiver<TelemetryFrame>, resampler: Resampler) -> Self {
+        let mut ticker = tokio::time::interval(resampler.period());
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        Self {
+            input,
+            resampler,
+            ticker,
+        }
+    }
+
+    /// Next output frame; `None` once the input channel is closed.
+    pub async fn recv(&mut self) -> Option<TelemetryFrame> {
+        loop {
+            tokio::select! {
+                frame = self.input.recv() => self.resampler.push(frame?),
+                _ = self.ticker.tick() => {
+                    if let Some(frame) = self.resampler.poll_next(telemetry_now_ns()) {
+                        return Some(frame);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,326 @@
+//! Shift-light LED states evaluated from normalized telemetry.
+//!
+//! A [`ShiftLightProfile`] turns one [`NormalizedTelemetry`] sample into the
+//! colors of a fixed-size LED strip. In order of precedence:
+//!
+//! 1. the first active [`FlagOverride`] flashes the whole strip in its color;
+//! 2. an active pit limiter alternates the two halves of the strip;
+//! 3. otherwise LEDs light left to right as engine speed approaches the
+//!    redline, colored by the [`LedZone`] each LED falls in.
+//!
+//! Blinking follows the sample's `timestamp`, so consecutive evaluations of a
+//! live stream animate without the caller keeping any state.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{FlagKind, NormalizedTelemetry};
+
+/// Redline used when neither the car nor the telemetry supplies one.
+pub const DEFAULT_REDLINE_RPM: f32 = 7500.0;
+
+/// Default rate of flag, pit limiter and shift-point blinking.
+pub const DEFAULT_BLINK_HZ: f32 = 4.0;
+
+/// An LED color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const OFF: Self = Self(0, 0, 0);
+    pub const GREEN: Self = Self(0, 255, 0);
+    pub const YELLOW: Self = Self(255, 200, 0);
+    pub const RED: Self = Self(255, 0, 0);
+    pub const BLUE: Self = Self(0, 80, 255);
+}
+
+/// LEDs at or above `start` of the redline take `color`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LedZone {
+    /// Fraction of the redline, in `0..=1`.
+    pub start: f32,
+    pub color: Rgb,
+    /// Flash every lit LED once engine speed reaches this zone: the shift point.
+    #[serde(default)]
+    pub blink: bool,
+}
+
+/// Flash the strip in `color` while `flag` is set.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FlagOverride {
+    pub flag: FlagKind,
+    pub color: Rgb,
+}
+
+/// Strip animation while the pit limiter is engaged.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PitLimiterAnimation {
+    pub color: Rgb,
+}
+
+/// Why a shift-light profile was rejected.
+#[derive(Debug, Error)]
+pub enum ShiftLightProfileError {
+    #[error("invalid shift-light profile JSON: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("shift-light profile has no LED zones")]
+    NoZones,
+    #[error(
+        "LED zone {index} starts at {start}, not after the previous zone at {previous}; zones must be sorted by `start`"
+    )]
+    ZonesNotSorted {
+        index: usize,
+        start: f32,
+        previous: f32,
+    },
+    #[error(
+        "LED zone {index} starts at {start}; zone starts are fractions of redline within 0..=1"
+    )]
+    ZoneOutOfRange { index: usize, start: f32 },
+    #[error("`{field}` is {rpm} rpm; redlines must be positive")]
+    InvalidRedline { field: String, rpm: f32 },
+    #[error("blink rate is {0} Hz; it must be positive")]
+    InvalidBlinkRate(f32),
+}
+
+/// What determined an [`ShiftLightState`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LedSource {
+    /// No engine speed to show.
+    Off,
+    Rpm,
+    Flag(FlagKind),
+    PitLimiter,
+}
+
+/// Colors to push to an `N`-LED strip.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShiftLightState<const N: usize> {
+    pub leds: [Rgb; N],
+    /// Position within the current blink cycle, in `0..1`; blinking LEDs are
+    /// lit during the first half.
+    pub blink_phase: f32,
+    pub source: LedSource,
+}
+
+/// How to light a shift-light strip from telemetry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ShiftLightProfile {
+    /// Sorted by `start`; the first zone's start is where the first LED lights.
+    pub zones: Vec<LedZone>,
+    /// Redline when the car has no override and the telemetry no `max_rpm`.
+    #[serde(default = "default_redline_rpm")]
+    pub default_redline_rpm: f32,
+    /// Redlines by `car_id`, taking precedence over the telemetry's `max_rpm`.
+    #[serde(default)]
+    pub car_redlines: BTreeMap<String, f32>,
+    /// Flags that take over the strip, highest priority first.
+    #[serde(default = "default_flag_overrides")]
+    pub flag_overrides: Vec<FlagOverride>,
+    #[serde(default = "default_pit_limiter")]
+    pub pit_limiter: Option<PitLimiterAnimation>,
+    #[serde(default = "default_blink_hz")]
+    pub blink_hz: f32,
+}
+
+fn default_redline_rpm() -> f32 {
+    DEFAULT_REDLINE_RPM
+}
+
+fn default_flag_overrides() -> Vec<FlagOverride> {
+    vec![
+        FlagOverride {
+            flag: FlagKind::Yellow,
+            color: Rgb::YELLOW,
+        },
+        FlagOverride {
+            flag: FlagKind::Blue,
+            color: Rgb::BLUE,
+        },
+    ]
+}
+
+fn default_pit_limiter() -> Option<PitLimiterAnimation> {
+    Some(PitLimiterAnimation { color: Rgb::BLUE })
+}
+
+fn default_blink_hz() -> f32 {
+    DEFAULT_BLINK_HZ
+}
+
+impl Default for ShiftLightProfile {
+    /// Green from 60%, yellow from 80%, flashing red from 95% of redline.
+    fn default() -> Self {
+        Self {
+            zones: vec![
+                LedZone {
+                    start: 0.6,
+                    color: Rgb::GREEN,
+                    blink: false,
+                },
+                LedZone {
+                    start: 0.8,
+                    color: Rgb::YELLOW,
+                    blink: false,
+                },
+                LedZone {
+                    start: 0.95,
+                    color: Rgb::RED,
+                    blink: true,
+                },
+            ],
+            default_redline_rpm: DEFAULT_REDLINE_RPM,
+            car_redlines: BTreeMap::new(),
+            flag_overrides: default_flag_overrides(),
+            pit_limiter: default_pit_limiter(),
+            blink_hz: DEFAULT_BLINK_HZ,
+        }
+    }
+}
+
+impl ShiftLightProfile {
+    /// Parse and validate a profile.
+    pub fn from_json(json: &str) -> Result<Self, ShiftLightProfileError> {
+        let profile: Self = serde_json::from_str(json)?;
+        profile.validate()?;
+        Ok(profile)
+    }
+
+    pub fn validate(&self) -> Result<(), ShiftLightProfileError> {
+        if self.zones.is_empty() {
+            return Err(ShiftLightProfileError::NoZones);
+        }
+        for (index, zone) in self.zones.iter().enumerate() {
+            if !(0.0..=1.0).contains(&zone.start) {
+                return Err(ShiftLightProfileError::ZoneOutOfRange {
+                    index,
+                    start: zone.start,
+                });
+            }
+            if let Some(previous) = index.checked_sub(1).map(|i| self.zones[i].start)
+                && zone.start <= previous
+            {
+                return Err(ShiftLightProfileError::ZonesNotSorted {
+                    index,
+                    start: zone.start,
+                    previous,
+                });
+            }
+        }
+        let redlines =
+            std::iter::once(("default_redline_rpm".to_string(), self.default_redline_rpm)).chain(
+                self.car_redlines
+                    .iter()
+                    .map(|(car, rpm)| (format!("car_redlines.{car}"), *rpm)),
+            );
+        for (field, rpm) in redlines {
+            if !(rpm.is_finite() && rpm > 0.0) {
+                return Err(ShiftLightProfileError::InvalidRedline { field, rpm });
+            }
+        }
+        if !(self.blink_hz.is_finite() && self.blink_hz > 0.0) {
+            return Err(ShiftLightProfileError::InvalidBlinkRate(self.blink_hz));
+        }
+        Ok(())
+    }
+
+    /// Redline applied to `telemetry`: the car's override, then the
+    /// telemetry's own `max_rpm`, then the profile default.
+    pub fn redline_rpm(&self, telemetry: &NormalizedTelemetry) -> f32 {
+        self.car_redline(telemetry)
+            .or((telemetry.max_rpm > 0.0).then_some(telemetry.max_rpm))
+            .unwrap_or(self.default_redline_rpm)
+    }
+
+    fn car_redline(&self, telemetry: &NormalizedTelemetry) -> Option<f32> {
+        let car = telemetry.car_id.as_deref()?;
+        self.car_redlines.get(car).copied()
+    }
+
+    /// Engine speed of `telemetry` as a fraction of [`Self::redline_rpm`].
+    pub fn redline_fraction(&self, telemetry: &NormalizedTelemetry) -> f32 {
+        match self.car_redline(telemetry) {
+            None if telemetry.max_rpm > 0.0 => telemetry.rpm_fraction(),
+            _ => (telemetry.rpm / self.redline_rpm(telemetry)).clamp(0.0, 1.0),
+        }
+    }
+
+    /// LED state for `telemetry`, blinking on its `timestamp`.
+    pub fn evaluate<const N: usize>(&self, telemetry: &NormalizedTelemetry) -> ShiftLightState<N> {
+        let elapsed = telemetry
+            .timestamp
+            .saturating_duration_since(crate::telemetry_epoch());
+        self.evaluate_at(telemetry, elapsed)
+    }
+
+    /// LED state for `telemetry` at `elapsed` into the blink clock.
+    pub fn evaluate_at<const N: usize>(
+        &self,
+        telemetry: &NormalizedTelemetry,
+        elapsed: Duration,
+    ) -> ShiftLightState<N> {
+        let blink_phase = (elapsed.as_secs_f64() * f64::from(self.blink_hz)).fract() as f32;
+        let blink_on = blink_phase < 0.5;
+        let mut state = ShiftLightState {
+            leds: [Rgb::OFF; N],
+            blink_phase,
+            source: LedSource::Off,
+        };
+
+        if let Some(flag) = self
+            .flag_overrides
+            .iter()
+            .find(|flag| flag.flag.extract(&telemetry.flags))
+        {
+            if blink_on {
+                state.leds = [flag.color; N];
+            }
+            state.source = LedSource::Flag(flag.flag);
+            return state;
+        }
+
+        if telemetry.flags.pit_limiter
+            && let Some(animation) = &self.pit_limiter
+        {
+            for (index, led) in state.leds.iter_mut().enumerate() {
+                if (index < N / 2) == blink_on {
+                    *led = animation.color;
+                }
+            }
+            state.source = LedSource::PitLimiter;
+            return state;
+        }
+
+        if telemetry.rpm <= 0.0 || !telemetry.rpm.is_finite() {
+            return state;
+        }
+        let fraction = self.redline_fraction(telemetry);
+        state.source = LedSource::Rpm;
+        let shift_point = self
+            .zones
+            .iter()
+            .any(|zone| zone.blink && fraction >= zone.start);
+        if shift_point && !blink_on {
+            return state;
+        }
+
+        let first = self.zones.first().map_or(0.0, |zone| zone.start);
+        for (index, led) in state.leds.iter_mut().enumerate() {
+            let threshold = first + (1.0 - first) * index as f32 / N as f32;
+            if fraction < threshold {
+                break;
+            }
+            if let Some(zone) = self.zones.iter().rev().find(|zone| zone.start <= threshold) {
+                *led = zone.color;
+            }
+        }
+        state
+    }
+}
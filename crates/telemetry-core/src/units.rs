@@ -0,0 +1,580 @@
+//! Source-unit manifests and unit plausibility checks.
+//!
+//! Every [`NormalizedTelemetry`] field and registered extended key has one
+//! canonical unit: speed in m/s, pressures in psi, angles in radians, and so
+//! on. Games do not agree on units, and a conversion forgotten in one adapter
+//! used to surface only as a wrong-looking overlay. A [`UnitManifest`] records
+//! which unit an adapter believes each field arrives in and therefore which
+//! conversion it applies, and [`check_unit_ranges`] compares decoded samples
+//! against plausible magnitudes for the canonical unit to catch the cases
+//! where that belief is wrong.
+
+use std::f32::consts::PI;
+use std::fmt;
+
+use serde::{Serialize, Serializer};
+
+use crate::contracts::resolve_key;
+use crate::{NormalizedTelemetry, TelemetryValue};
+
+/// Physical quantity a [`Unit`] measures; conversions only exist within one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dimension {
+    Speed,
+    Angle,
+    AngularSpeed,
+    Ratio,
+    Acceleration,
+    Pressure,
+    Temperature,
+    Length,
+    Time,
+    Volume,
+    MassFlow,
+    VolumeFlow,
+    Torque,
+}
+
+/// A unit a game or the telemetry contract expresses a value in.
+///
+/// Serialized as its [`symbol`](Self::symbol), which matches the unit strings
+/// of the extended-key catalog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Unit {
+    MetersPerSecond,
+    KilometersPerHour,
+    MilesPerHour,
+    Radians,
+    Degrees,
+    RadiansPerSecond,
+    Rpm,
+    /// Dimensionless value in `0.0..=1.0` (or `-1.0..=1.0` for signed inputs).
+    Fraction,
+    Percent,
+    /// Multiples of standard gravity.
+    StandardGravity,
+    MetersPerSecondSquared,
+    Psi,
+    Bar,
+    Kilopascal,
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+    Meters,
+    Seconds,
+    Milliseconds,
+    Liters,
+    KilogramsPerHour,
+    LitersPerHour,
+    NewtonMeters,
+}
+
+impl Unit {
+    /// Every unit, in the order [`check_unit_ranges`] tries them as suspects.
+    pub const ALL: &'static [Unit] = &[
+        Self::MetersPerSecond,
+        Self::KilometersPerHour,
+        Self::MilesPerHour,
+        Self::Radians,
+        Self::Degrees,
+        Self::RadiansPerSecond,
+        Self::Rpm,
+        Self::Fraction,
+        Self::Percent,
+        Self::StandardGravity,
+        Self::MetersPerSecondSquared,
+        Self::Psi,
+        Self::Bar,
+        Self::Kilopascal,
+        Self::Celsius,
+        Self::Kelvin,
+        Self::Fahrenheit,
+        Self::Meters,
+        Self::Seconds,
+        Self::Milliseconds,
+        Self::Liters,
+        Self::KilogramsPerHour,
+        Self::LitersPerHour,
+        Self::NewtonMeters,
+    ];
+
+    /// Short symbol, as used by the extended-key catalog.
+    pub const fn symbol(self) -> &'static str {
+        match self {
+            Self::MetersPerSecond => "m/s",
+            Self::KilometersPerHour => "km/h",
+            Self::MilesPerHour => "mph",
+            Self::Radians => "rad",
+            Self::Degrees => "deg",
+            Self::RadiansPerSecond => "rad/s",
+            Self::Rpm => "rpm",
+            Self::Fraction => "fraction",
+            Self::Percent => "percent",
+            Self::StandardGravity => "g",
+            Self::MetersPerSecondSquared => "m/s2",
+            Self::Psi => "psi",
+            Self::Bar => "bar",
+            Self::Kilopascal => "kPa",
+            Self::Celsius => "degC",
+            Self::Fahrenheit => "degF",
+            Self::Kelvin => "K",
+            Self::Meters => "m",
+            Self::Seconds => "s",
+            Self::Milliseconds => "ms",
+            Self::Liters => "l",
+            Self::KilogramsPerHour => "kg/h",
+            Self::LitersPerHour => "l/h",
+            Self::NewtonMeters => "Nm",
+        }
+    }
+
+    /// Unit with the given [`symbol`](Self::symbol).
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|unit| unit.symbol() == symbol)
+    }
+
+    pub const fn dimension(self) -> Dimension {
+        match self {
+            Self::MetersPerSecond | Self::KilometersPerHour | Self::MilesPerHour => {
+                Dimension::Speed
+            }
+            Self::Radians | Self::Degrees => Dimension::Angle,
+            Self::RadiansPerSecond | Self::Rpm => Dimension::AngularSpeed,
+            Self::Fraction | Self::Percent => Dimension::Ratio,
+            Self::StandardGravity | Self::MetersPerSecondSquared => Dimension::Acceleration,
+            Self::Psi | Self::Bar | Self::Kilopascal => Dimension::Pressure,
+            Self::Celsius | Self::Fahrenheit | Self::Kelvin => Dimension::Temperature,
+            Self::Meters => Dimension::Length,
+            Self::Seconds | Self::Milliseconds => Dimension::Time,
+            Self::Liters => Dimension::Volume,
+            Self::KilogramsPerHour => Dimension::MassFlow,
+            Self::LitersPerHour => Dimension::VolumeFlow,
+            Self::NewtonMeters => Dimension::Torque,
+        }
+    }
+
+    /// `(scale, offset)` taking a value in this unit to its dimension's base unit.
+    fn to_base(self) -> (f32, f32) {
+        match self {
+            Self::KilometersPerHour => (1.0 / 3.6, 0.0),
+            Self::MilesPerHour => (0.447_04, 0.0),
+            Self::Degrees => (PI / 180.0, 0.0),
+            Self::Rpm => (2.0 * PI / 60.0, 0.0),
+            Self::Percent => (0.01, 0.0),
+            Self::StandardGravity => (9.806_65, 0.0),
+            Self::Psi => (6.894_757, 0.0),
+            Self::Bar => (100.0, 0.0),
+            Self::Fahrenheit => (5.0 / 9.0, -32.0 * 5.0 / 9.0),
+            Self::Kelvin => (1.0, -273.15),
+            Self::Milliseconds => (0.001, 0.0),
+            _ => (1.0, 0.0),
+        }
+    }
+
+    /// Conversion from this unit to `target`, or `None` across dimensions.
+    pub fn conversion_to(self, target: Unit) -> Option<UnitConversion> {
+        if self.dimension() != target.dimension() {
+            return None;
+        }
+        let (scale_from, offset_from) = self.to_base();
+        let (scale_to, offset_to) = target.to_base();
+        Some(UnitConversion {
+            scale: scale_from / scale_to,
+            offset: (offset_from - offset_to) / scale_to,
+        })
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+impl Serialize for Unit {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.symbol())
+    }
+}
+
+/// Linear conversion `value * scale + offset` between two units.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct UnitConversion {
+    pub scale: f32,
+    pub offset: f32,
+}
+
+impl UnitConversion {
+    pub fn apply(&self, value: f32) -> f32 {
+        value * self.scale + self.offset
+    }
+
+    pub fn is_identity(&self) -> bool {
+        (self.scale - 1.0).abs() < 1e-6 && self.offset.abs() < 1e-6
+    }
+}
+
+/// Canonical unit of each typed [`NormalizedTelemetry`] field that has one.
+const TYPED_FIELDS: &[(&str, Unit)] = &[
+    ("speed_ms", Unit::MetersPerSecond),
+    ("steering_angle", Unit::Radians),
+    ("throttle", Unit::Fraction),
+    ("brake", Unit::Fraction),
+    ("clutch", Unit::Fraction),
+    ("rpm", Unit::Rpm),
+    ("max_rpm", Unit::Rpm),
+    ("lateral_g", Unit::StandardGravity),
+    ("longitudinal_g", Unit::StandardGravity),
+    ("vertical_g", Unit::StandardGravity),
+    ("slip_ratio", Unit::Fraction),
+    ("slip_angle_fl", Unit::Radians),
+    ("slip_angle_fr", Unit::Radians),
+    ("slip_angle_rl", Unit::Radians),
+    ("slip_angle_rr", Unit::Radians),
+    ("tire_temps_c", Unit::Celsius),
+    ("tire_pressures_psi", Unit::Psi),
+    ("ffb_scalar", Unit::Fraction),
+    ("ffb_torque_nm", Unit::NewtonMeters),
+    ("current_lap_time_s", Unit::Seconds),
+    ("best_lap_time_s", Unit::Seconds),
+    ("last_lap_time_s", Unit::Seconds),
+    ("delta_ahead_s", Unit::Seconds),
+    ("delta_behind_s", Unit::Seconds),
+    ("fuel_percent", Unit::Fraction),
+    ("engine_temp_c", Unit::Celsius),
+    ("game_time_s", Unit::Seconds),
+];
+
+/// Canonical unit of a typed field or registered extended key.
+pub fn canonical_unit(field: &str) -> Option<Unit> {
+    TYPED_FIELDS
+        .iter()
+        .find(|(name, _)| *name == field)
+        .map(|(_, unit)| *unit)
+        .or_else(|| {
+            resolve_key(field)
+                .and_then(|key| key.unit)
+                .and_then(Unit::from_symbol)
+        })
+}
+
+/// Append the finite values `field` holds in `telemetry` to `out`.
+fn field_values(telemetry: &NormalizedTelemetry, field: &str, out: &mut Vec<f32>) {
+    let t = telemetry;
+    let scalar = match field {
+        "speed_ms" => Some(t.speed_ms),
+        "steering_angle" => Some(t.steering_angle),
+        "throttle" => Some(t.throttle),
+        "brake" => Some(t.brake),
+        "clutch" => Some(t.clutch),
+        "rpm" => Some(t.rpm),
+        "max_rpm" => Some(t.max_rpm),
+        "lateral_g" => Some(t.lateral_g),
+        "longitudinal_g" => Some(t.longitudinal_g),
+        "vertical_g" => Some(t.vertical_g),
+        "slip_ratio" => Some(t.slip_ratio),
+        "slip_angle_fl" => Some(t.slip_angle_fl),
+        "slip_angle_fr" => Some(t.slip_angle_fr),
+        "slip_angle_rl" => Some(t.slip_angle_rl),
+        "slip_angle_rr" => Some(t.slip_angle_rr),
+        "ffb_scalar" => Some(t.ffb_scalar),
+        "ffb_torque_nm" => Some(t.ffb_torque_nm),
+        "current_lap_time_s" => Some(t.current_lap_time_s),
+        "best_lap_time_s" => Some(t.best_lap_time_s),
+        "last_lap_time_s" => Some(t.last_lap_time_s),
+        "delta_ahead_s" => Some(t.delta_ahead_s),
+        "delta_behind_s" => Some(t.delta_behind_s),
+        "fuel_percent" => Some(t.fuel_percent),
+        "engine_temp_c" => Some(t.engine_temp_c),
+        "game_time_s" => t.game_time_s.map(|s| s as f32),
+        "tire_temps_c" => {
+            out.extend(t.tire_temps_c.iter().map(|&c| f32::from(c)));
+            None
+        }
+        "tire_pressures_psi" => {
+            out.extend(
+                t.tire_pressures_psi
+                    .iter()
+                    .copied()
+                    .filter(|v| v.is_finite()),
+            );
+            None
+        }
+        key => match t.extended.get(key) {
+            Some(TelemetryValue::FloatArray(values)) => {
+                out.extend(values.iter().copied().filter(|v| v.is_finite()));
+                None
+            }
+            Some(TelemetryValue::IntArray(values)) => {
+                out.extend(values.iter().map(|v| *v as f32));
+                None
+            }
+            value => value.and_then(TelemetryValue::as_f32),
+        },
+    };
+    out.extend(scalar.filter(|v| v.is_finite()));
+}
+
+/// Unit a field arrives in from the game and the canonical unit it is stored in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct FieldUnit {
+    /// Typed field name or extended key.
+    pub field: &'static str,
+    /// Unit the adapter believes the game sends.
+    pub source: Unit,
+    /// Contract unit the adapter stores the value in.
+    pub canonical: Unit,
+}
+
+impl FieldUnit {
+    /// A field the game already sends in its canonical unit.
+    pub const fn native(field: &'static str, unit: Unit) -> Self {
+        Self {
+            field,
+            source: unit,
+            canonical: unit,
+        }
+    }
+
+    /// A field the adapter converts from `source` to `canonical`.
+    pub const fn converted(field: &'static str, source: Unit, canonical: Unit) -> Self {
+        Self {
+            field,
+            source,
+            canonical,
+        }
+    }
+
+    /// Conversion the adapter applies, or `None` when source and canonical
+    /// measure different quantities and the value is passed through as-is
+    /// (e.g. a steering-lock fraction stored in a radians field).
+    pub fn conversion(&self) -> Option<UnitConversion> {
+        self.source.conversion_to(self.canonical)
+    }
+}
+
+/// Structural problem with a [`UnitManifest`] entry.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum UnitManifestIssue {
+    /// The field is neither a typed field with a unit nor a registered extended key.
+    #[error("`{field}` has no canonical unit in the telemetry contract")]
+    UnknownField { field: &'static str },
+    /// The declared canonical unit is not the contract's.
+    #[error("`{field}` declares canonical unit {declared}, but the contract uses {contract}")]
+    CanonicalMismatch {
+        field: &'static str,
+        declared: Unit,
+        contract: Unit,
+    },
+    /// The field is declared twice.
+    #[error("`{field}` is declared more than once")]
+    Duplicate { field: &'static str },
+}
+
+/// Per-adapter declaration of source units for the fields it populates.
+///
+/// Lists every populated field and registered extended key that has a unit;
+/// unitless values such as gear or flags are left out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct UnitManifest {
+    pub fields: &'static [FieldUnit],
+}
+
+impl UnitManifest {
+    pub const fn new(fields: &'static [FieldUnit]) -> Self {
+        Self { fields }
+    }
+
+    /// Declaration for `field`, if any.
+    pub fn get(&self, field: &str) -> Option<&FieldUnit> {
+        self.fields.iter().find(|entry| entry.field == field)
+    }
+
+    /// Entries passed through without a conversion despite differing units.
+    pub fn unconverted(&self) -> impl Iterator<Item = &FieldUnit> {
+        self.fields
+            .iter()
+            .filter(|entry| entry.conversion().is_none())
+    }
+
+    /// Cross-check the manifest against the contract's canonical units.
+    pub fn issues(&self) -> Vec<UnitManifestIssue> {
+        let mut issues = Vec::new();
+        for (index, entry) in self.fields.iter().enumerate() {
+            let field = entry.field;
+            if self.fields[..index].iter().any(|e| e.field == field) {
+                issues.push(UnitManifestIssue::Duplicate { field });
+            }
+            match canonical_unit(field) {
+                None => issues.push(UnitManifestIssue::UnknownField { field }),
+                Some(contract) if contract != entry.canonical => {
+                    issues.push(UnitManifestIssue::CanonicalMismatch {
+                        field,
+                        declared: entry.canonical,
+                        contract,
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+        issues
+    }
+}
+
+/// Vehicle class used to pick plausible ranges; bikes, karts and trucks
+/// have very different speed and load envelopes from cars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VehicleTier {
+    #[default]
+    Car,
+    Kart,
+    Bike,
+    Truck,
+}
+
+/// Bounds on the typical magnitude (mean of non-zero absolute values) of a
+/// field over a fixture.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct PlausibleRange {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl PlausibleRange {
+    const fn new(min: f32, max: f32) -> Self {
+        Self { min, max }
+    }
+
+    pub fn contains(&self, value: f32) -> bool {
+        (self.min..=self.max).contains(&value)
+    }
+}
+
+/// Plausible typical magnitude for values in canonical `unit` on `tier`.
+///
+/// `None` for units too context-dependent to bound (lap times, distances).
+pub fn plausible_range(unit: Unit, tier: VehicleTier) -> Option<PlausibleRange> {
+    use VehicleTier::{Bike, Car, Kart, Truck};
+    let range = match (unit, tier) {
+        (Unit::MetersPerSecond, Car) => PlausibleRange::new(0.0, 150.0),
+        (Unit::MetersPerSecond, Bike) => PlausibleRange::new(0.0, 110.0),
+        (Unit::MetersPerSecond, Kart | Truck) => PlausibleRange::new(0.0, 45.0),
+        (Unit::Rpm, Truck) => PlausibleRange::new(0.0, 4_000.0),
+        (Unit::Rpm, _) => PlausibleRange::new(0.0, 20_000.0),
+        // Up to about 1.5 turns of lock either way.
+        (Unit::Radians, _) => PlausibleRange::new(0.0, 10.0),
+        (Unit::Fraction, _) => PlausibleRange::new(0.0, 1.0),
+        (Unit::StandardGravity, Car) => PlausibleRange::new(0.0, 6.0),
+        (Unit::StandardGravity, Kart) => PlausibleRange::new(0.0, 4.0),
+        (Unit::StandardGravity, Bike) => PlausibleRange::new(0.0, 3.0),
+        (Unit::StandardGravity, Truck) => PlausibleRange::new(0.0, 1.5),
+        (Unit::Psi, Car) => PlausibleRange::new(10.0, 60.0),
+        (Unit::Psi, Kart) => PlausibleRange::new(5.0, 40.0),
+        (Unit::Psi, Bike) => PlausibleRange::new(15.0, 50.0),
+        (Unit::Psi, Truck) => PlausibleRange::new(60.0, 150.0),
+        (Unit::Bar, _) => PlausibleRange::new(0.0, 10.0),
+        (Unit::Celsius, _) => PlausibleRange::new(0.0, 200.0),
+        _ => return None,
+    };
+    Some(range)
+}
+
+/// A field whose decoded values look like they are in the wrong unit.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UnitSuspicion {
+    pub field: String,
+    /// Contract unit the values should be in.
+    pub canonical: Unit,
+    /// Typical magnitude observed across the samples.
+    pub observed: f32,
+    /// Range the typical magnitude was expected in.
+    pub expected: PlausibleRange,
+    /// Source unit the manifest declared, if any.
+    pub declared: Option<Unit>,
+    /// Unit whose conversion to canonical would make the values plausible.
+    pub suspected: Option<Unit>,
+}
+
+impl fmt::Display for UnitSuspicion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` averages {:.2} {}, outside {}..={}",
+            self.field, self.observed, self.canonical, self.expected.min, self.expected.max
+        )?;
+        if let Some(suspected) = self.suspected {
+            write!(f, "; looks like unconverted {suspected}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Flag fields in `samples` whose typical magnitude is implausible for their
+/// canonical unit on `tier`.
+///
+/// Checks every typed field with a bounded unit plus every registered
+/// extended key present in the samples. Zero values are ignored since most
+/// parsers write zero for "not reported".
+pub fn check_unit_ranges(
+    samples: &[NormalizedTelemetry],
+    tier: VehicleTier,
+    manifest: Option<&UnitManifest>,
+) -> Vec<UnitSuspicion> {
+    let mut fields: Vec<&str> = TYPED_FIELDS.iter().map(|(name, _)| *name).collect();
+    for sample in samples {
+        for key in sample.extended.keys() {
+            if !fields.contains(&key.as_str()) {
+                fields.push(key);
+            }
+        }
+    }
+
+    let mut values = Vec::new();
+    let mut suspicions = Vec::new();
+    for field in fields {
+        let Some(canonical) = canonical_unit(field) else {
+            continue;
+        };
+        let Some(expected) = plausible_range(canonical, tier) else {
+            continue;
+        };
+        values.clear();
+        for sample in samples {
+            field_values(sample, field, &mut values);
+        }
+        let (sum, count) = values
+            .iter()
+            .filter(|v| **v != 0.0)
+            .fold((0.0f64, 0u32), |(sum, count), v| {
+                (sum + f64::from(v.abs()), count + 1)
+            });
+        if count == 0 {
+            continue;
+        }
+        let observed = (sum / f64::from(count)) as f32;
+        if expected.contains(observed) {
+            continue;
+        }
+        let suspected = Unit::ALL.iter().copied().find(|unit| {
+            *unit != canonical
+                && unit
+                    .conversion_to(canonical)
+                    .is_some_and(|conversion| expected.contains(conversion.apply(observed)))
+        });
+        suspicions.push(UnitSuspicion {
+            field: field.to_string(),
+            canonical,
+            observed,
+            expected,
+            declared: manifest
+                .and_then(|m| m.get(field))
+                .map(|entry| entry.source),
+            suspected,
+        });
+    }
+    suspicions
+}
@@ -248,7 +248,7 @@ fn connection_state_event_is_connection() -> TestResult {
     assert!(event.is_connection());
     assert!(!event.is_disconnection());
     assert_eq!(event.game_id, "iracing");
-    assert!(event.timestamp_ns > 0);
+    assert!(event.timestamp_unix_ns > 0);
     Ok(())
 }
 
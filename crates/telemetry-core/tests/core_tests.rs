@@ -881,10 +881,16 @@ fn telemetry_field_coverage_serde_roundtrip() -> TestResult {
             launch_control: false,
             traction_control: true,
             abs_active: true,
+            safety_car: false,
+            virtual_safety_car: false,
         },
         car_id: true,
         track_id: true,
+        penalties: false,
+        game_time: false,
         extended_fields: vec!["water_temp".to_string(), "oil_temp".to_string()],
+        tires: false,
+        timing: Default::default(),
     };
 
     let json = serde_json::to_string(&coverage)?;
@@ -895,3 +901,21 @@ fn telemetry_field_coverage_serde_roundtrip() -> TestResult {
     assert_eq!(back.extended_fields.len(), 2);
     Ok(())
 }
+
+#[test]
+fn flag_coverage_without_safety_car_entries_deserializes() -> TestResult {
+    use racing_wheel_telemetry_core::contracts::FlagCoverage;
+
+    let json = r#"{
+        "yellow_flag": true, "red_flag": false, "blue_flag": true,
+        "checkered_flag": false, "green_flag": true, "pit_limiter": true,
+        "in_pits": true, "drs_available": true, "drs_active": true,
+        "ers_available": false, "launch_control": false,
+        "traction_control": false, "abs_active": false
+    }"#;
+    let coverage: FlagCoverage = serde_json::from_str(json)?;
+    assert!(coverage.yellow_flag);
+    assert!(!coverage.safety_car);
+    assert!(!coverage.virtual_safety_car);
+    Ok(())
+}
@@ -0,0 +1,226 @@
+//! Frame-to-frame derivatives and edge events from `DeltaComputer`.
+
+use std::time::Duration;
+
+use racing_wheel_telemetry_core::{
+    DeltaComputer, DeltaEvent, DeltaStats, FlagKind, NormalizedTelemetry, TelemetryFlags,
+    TelemetryFrame,
+};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const MS: u64 = 1_000_000;
+
+fn frame(sequence: u64, at_ms: u64, speed_ms: f32, gear: i8) -> TelemetryFrame {
+    let data = NormalizedTelemetry::builder()
+        .speed_ms(speed_ms)
+        .rpm(4000.0 + speed_ms * 100.0)
+        .gear(gear)
+        .build();
+    TelemetryFrame::new(data, at_ms * MS, sequence, 64)
+}
+
+fn gear_changes(computer: &mut DeltaComputer, gears: &[i8]) -> Vec<DeltaEvent> {
+    gears
+        .iter()
+        .enumerate()
+        .filter_map(|(i, gear)| computer.push(&frame(i as u64, i as u64 * 10, 30.0, *gear)))
+        .flat_map(|delta| delta.events)
+        .collect()
+}
+
+#[test]
+fn first_frame_has_no_delta() {
+    assert!(
+        DeltaComputer::default()
+            .push(&frame(0, 0, 10.0, 1))
+            .is_none()
+    );
+}
+
+#[test]
+fn acceleration_and_rpm_rate_follow_speed_change() -> TestResult {
+    let mut computer = DeltaComputer::default();
+    computer.push(&frame(0, 0, 20.0, 3));
+    let delta = computer
+        .push(&frame(1, 100, 21.0, 3))
+        .ok_or("second frame should produce a delta")?;
+
+    assert_eq!(delta.dt, Duration::from_millis(100));
+    let accel = delta.acceleration_ms2.ok_or("missing acceleration")?;
+    assert!((accel - 10.0).abs() < 1e-3, "{accel}");
+    let rpm_rate = delta.rpm_rate.ok_or("missing rpm rate")?;
+    assert!((rpm_rate - 1000.0).abs() < 0.1, "{rpm_rate}");
+    assert!(delta.events.is_empty());
+    Ok(())
+}
+
+#[test]
+fn upshift_and_downshift_are_reported_once_each() {
+    let mut computer = DeltaComputer::default();
+    assert_eq!(
+        gear_changes(&mut computer, &[2, 2, 3, 3, 3, 2]),
+        [
+            DeltaEvent::GearChange {
+                previous: 2,
+                new: 3
+            },
+            DeltaEvent::GearChange {
+                previous: 3,
+                new: 2
+            },
+        ]
+    );
+}
+
+#[test]
+fn shifts_through_neutral_into_reverse_are_separate_edges() {
+    let mut computer = DeltaComputer::default();
+    assert_eq!(
+        gear_changes(&mut computer, &[1, 0, -1]),
+        [
+            DeltaEvent::GearChange {
+                previous: 1,
+                new: 0
+            },
+            DeltaEvent::GearChange {
+                previous: 0,
+                new: -1
+            },
+        ]
+    );
+}
+
+#[test]
+fn constant_gear_has_no_gear_events() {
+    let mut computer = DeltaComputer::default();
+    assert!(gear_changes(&mut computer, &[4; 6]).is_empty());
+}
+
+#[test]
+fn yellow_flag_rising_and_falling_edges() -> TestResult {
+    let mut computer = DeltaComputer::default();
+    let with_yellow = |sequence: u64, yellow: bool| {
+        let mut frame = frame(sequence, sequence * 10, 30.0, 4);
+        frame.data.flags = TelemetryFlags {
+            yellow_flag: yellow,
+            ..TelemetryFlags::default()
+        };
+        frame
+    };
+
+    computer.push(&with_yellow(0, false));
+    let raised = computer
+        .push(&with_yellow(1, true))
+        .ok_or("expected a delta")?;
+    assert!(raised.flag_raised(FlagKind::Yellow));
+
+    let held = computer
+        .push(&with_yellow(2, true))
+        .ok_or("expected a delta")?;
+    assert!(held.events.is_empty());
+
+    let cleared = computer
+        .push(&with_yellow(3, false))
+        .ok_or("expected a delta")?;
+    assert_eq!(
+        cleared.events,
+        [DeltaEvent::FlagTransition {
+            flag: FlagKind::Yellow,
+            active: false
+        }]
+    );
+    Ok(())
+}
+
+#[test]
+fn out_of_order_frames_are_skipped() -> TestResult {
+    let mut computer = DeltaComputer::default();
+    computer.push(&frame(5, 50, 20.0, 3));
+    assert!(computer.push(&frame(4, 40, 25.0, 4)).is_none());
+    assert!(computer.push(&frame(5, 60, 25.0, 4)).is_none());
+
+    // The next in-order frame is compared with sequence 5, not the skipped ones.
+    let delta = computer
+        .push(&frame(6, 60, 21.0, 3))
+        .ok_or("in-order frame should produce a delta")?;
+    assert!(delta.events.is_empty());
+    assert_eq!(computer.stats().out_of_order, 2);
+    Ok(())
+}
+
+#[test]
+fn duplicate_timestamps_keep_events_without_derivatives() -> TestResult {
+    let mut computer = DeltaComputer::default();
+    computer.push(&frame(0, 100, 20.0, 3));
+    let delta = computer
+        .push(&frame(1, 100, 22.0, 4))
+        .ok_or("expected a delta")?;
+
+    assert_eq!(delta.dt, Duration::ZERO);
+    assert_eq!(delta.acceleration_ms2, None);
+    assert_eq!(delta.rpm_rate, None);
+    assert!(!delta.is_discontinuity());
+    assert_eq!(
+        delta.events,
+        [DeltaEvent::GearChange {
+            previous: 3,
+            new: 4
+        }]
+    );
+    assert_eq!(computer.stats().duplicate_timestamps, 1);
+    Ok(())
+}
+
+#[test]
+fn large_gaps_and_clock_regressions_are_discontinuities() -> TestResult {
+    let mut computer = DeltaComputer::new(Duration::from_millis(200));
+    computer.push(&frame(0, 0, 0.0, 1));
+
+    let gap = computer
+        .push(&frame(1, 5_000, 60.0, 1))
+        .ok_or("expected a delta")?;
+    assert!(gap.is_discontinuity());
+    assert_eq!(gap.acceleration_ms2, None);
+    assert_eq!(
+        gap.events,
+        [DeltaEvent::Discontinuity {
+            previous_timestamp_ns: 0,
+            timestamp_ns: 5_000 * MS
+        }]
+    );
+
+    let backwards = computer
+        .push(&frame(2, 4_000, 60.0, 1))
+        .ok_or("expected a delta")?;
+    assert!(backwards.is_discontinuity());
+
+    // Derivatives resume from the frame after the discontinuity.
+    let resumed = computer
+        .push(&frame(3, 4_100, 61.0, 1))
+        .ok_or("expected a delta")?;
+    assert!(!resumed.is_discontinuity());
+    assert!(resumed.acceleration_ms2.is_some());
+    assert_eq!(
+        computer.stats(),
+        DeltaStats {
+            out_of_order: 0,
+            duplicate_timestamps: 0,
+            discontinuities: 2,
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn reset_starts_over_for_restarted_sequences() -> TestResult {
+    let mut computer = DeltaComputer::default();
+    computer.push(&frame(100, 0, 20.0, 3));
+    computer.reset();
+    assert!(computer.push(&frame(0, 10, 20.0, 3)).is_none());
+    let delta = computer
+        .push(&frame(1, 20, 20.0, 3))
+        .ok_or("expected a delta")?;
+    assert_eq!(delta.sequence, 1);
+    Ok(())
+}
@@ -0,0 +1,255 @@
+//! Fan, shaker and engine-pulse outputs from `EffectsChannelGenerator`.
+
+use std::time::Duration;
+
+use racing_wheel_telemetry_core::{
+    CurveAnchor, EffectCurve, EffectsChannelGenerator, EffectsFrame, EffectsProfile,
+    EffectsProfileError, EffectsReceiver, EffectsSmoothing, NormalizedTelemetry, TelemetryFlags,
+    TelemetryFrame,
+};
+use tokio::sync::mpsc;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const MS: u64 = 1_000_000;
+
+fn frame(sequence: u64, at_ms: u64, data: NormalizedTelemetry) -> TelemetryFrame {
+    TelemetryFrame::new(data, at_ms * MS, sequence, 64)
+}
+
+fn driving(speed_ms: f32, gear: i8) -> NormalizedTelemetry {
+    NormalizedTelemetry::builder()
+        .speed_ms(speed_ms)
+        .rpm(6000.0)
+        .gear(gear)
+        .build()
+}
+
+/// Default curves without smoothing, so outputs follow each frame exactly.
+fn unsmoothed() -> EffectsProfile {
+    EffectsProfile {
+        smoothing: EffectsSmoothing {
+            wind_ms: 0.0,
+            rumble_ms: 0.0,
+            pulse_ms: 0.0,
+        },
+        ..EffectsProfile::default()
+    }
+}
+
+fn approx(actual: f32, expected: f32) -> bool {
+    (actual - expected).abs() < 1e-5
+}
+
+#[test]
+fn curves_interpolate_between_anchors_and_hold_beyond_them() -> TestResult {
+    let curve = EffectCurve::new(vec![
+        CurveAnchor {
+            input: 10.0,
+            output: 0.0,
+        },
+        CurveAnchor {
+            input: 30.0,
+            output: 0.8,
+        },
+        CurveAnchor {
+            input: 50.0,
+            output: 1.0,
+        },
+    ])?;
+    let cases = [
+        (0.0, 0.0),
+        (10.0, 0.0),
+        (15.0, 0.2),
+        (30.0, 0.8),
+        (40.0, 0.9),
+        (50.0, 1.0),
+        (90.0, 1.0),
+        (f32::NAN, 0.0),
+    ];
+    for (input, expected) in cases {
+        let output = curve.evaluate(input);
+        assert!(approx(output, expected), "{input} gave {output}");
+    }
+    Ok(())
+}
+
+#[test]
+fn curves_need_sorted_anchors_and_unit_outputs() {
+    let unsorted = EffectsProfile::from_json(
+        r#"{ "wind": [ { "input": 40.0, "output": 0.0 }, { "input": 10.0, "output": 1.0 } ] }"#,
+    );
+    assert!(matches!(
+        unsorted,
+        Err(EffectsProfileError::AnchorsNotIncreasing {
+            curve: "wind",
+            index: 1,
+            ..
+        })
+    ));
+
+    let repeated = EffectCurve::linear(20.0, 20.0);
+    assert!(matches!(
+        repeated,
+        Err(EffectsProfileError::AnchorsNotIncreasing { .. })
+    ));
+
+    let single =
+        EffectsProfile::from_json(r#"{ "slip_rumble": [ { "input": 0.1, "output": 1.0 } ] }"#);
+    assert!(matches!(
+        single,
+        Err(EffectsProfileError::TooFewAnchors {
+            curve: "slip_rumble",
+            count: 1
+        })
+    ));
+
+    let too_loud = EffectCurve::new(vec![
+        CurveAnchor {
+            input: 0.0,
+            output: 0.0,
+        },
+        CurveAnchor {
+            input: 1.0,
+            output: 1.5,
+        },
+    ]);
+    assert!(matches!(
+        too_loud,
+        Err(EffectsProfileError::OutputOutOfRange { index: 1, .. })
+    ));
+}
+
+#[test]
+fn profiles_load_from_json_with_defaults() -> TestResult {
+    let profile = EffectsProfile::from_json(
+        r#"{
+            "wind": [ { "input": 5.0, "output": 0.1 }, { "input": 60.0, "output": 1.0 } ],
+            "car_cylinders": { "v12_gt": 12 },
+            "mute_flags": ["red"]
+        }"#,
+    )?;
+    assert_eq!(
+        profile.wind.anchors()[0],
+        CurveAnchor {
+            input: 5.0,
+            output: 0.1
+        }
+    );
+    assert_eq!(profile.slip_rumble, EffectsProfile::default().slip_rumble);
+    assert_eq!(profile.smoothing, EffectsSmoothing::default());
+
+    let json = serde_json::to_string(&profile)?;
+    assert_eq!(EffectsProfile::from_json(&json)?, profile);
+
+    let zero = EffectsProfile::from_json(r#"{ "car_cylinders": { "electric": 0 } }"#);
+    assert!(matches!(zero, Err(EffectsProfileError::NoCylinders { car }) if car == "electric"));
+    Ok(())
+}
+
+#[test]
+fn wind_follows_speed_and_pulse_follows_cylinders() {
+    let mut profile = unsmoothed();
+    profile.car_cylinders.insert("v8_supercar".to_string(), 8);
+    let mut generator = EffectsChannelGenerator::new(profile);
+
+    let four = generator.push(&frame(0, 0, driving(38.5, 3)));
+    assert!(approx(four.wind, 0.5), "{four:?}");
+    assert!(approx(four.pulse_hz, 400.0), "{four:?}");
+
+    let mut v8 = driving(80.0, 3);
+    v8.car_id = Some("v8_supercar".to_string());
+    let v8 = generator.push(&frame(1, 10, v8));
+    assert_eq!(v8.wind, 1.0);
+    assert!(approx(v8.pulse_hz, 800.0), "{v8:?}");
+}
+
+#[test]
+fn gear_change_spikes_rumble_then_decays() {
+    let mut generator = EffectsChannelGenerator::new(unsmoothed());
+    let rumble: Vec<f32> = (0..30)
+        .map(|i| {
+            let gear = if i < 5 { 3 } else { 4 };
+            generator
+                .push(&frame(i, i * 30, driving(40.0, gear)))
+                .rumble
+        })
+        .collect();
+
+    assert!(
+        rumble[..5].iter().all(|&rumble| rumble == 0.0),
+        "{rumble:?}"
+    );
+    assert!(approx(rumble[5], 0.6), "{rumble:?}");
+    assert!(
+        rumble[5..].windows(2).all(|pair| pair[1] < pair[0]),
+        "{rumble:?}"
+    );
+    // Nine decay time constants later the spike is gone.
+    assert!(rumble[29] < 0.01, "{rumble:?}");
+    assert!(rumble.iter().all(|rumble| (0.0..=1.0).contains(rumble)));
+}
+
+#[test]
+fn rumble_from_slip_and_shifts_saturates_at_one() {
+    let mut generator = EffectsChannelGenerator::new(unsmoothed());
+    let sliding = |gear| {
+        let mut telemetry = driving(40.0, gear);
+        telemetry.slip_ratio = -0.5;
+        telemetry
+    };
+    let slip_only = generator.push(&frame(0, 0, sliding(3)));
+    assert_eq!(slip_only.rumble, 1.0);
+    let shifted = generator.push(&frame(1, 10, sliding(4)));
+    assert_eq!(shifted.rumble, 1.0);
+}
+
+#[test]
+fn red_flag_and_pits_mute_every_channel() {
+    let mut generator = EffectsChannelGenerator::new(EffectsProfile::default());
+    let racing = generator.push(&frame(0, 0, driving(60.0, 4)));
+    assert!(!racing.muted && racing.wind > 0.0 && racing.pulse_hz > 0.0);
+
+    let mut red = driving(60.0, 5);
+    red.flags = TelemetryFlags {
+        red_flag: true,
+        ..TelemetryFlags::default()
+    };
+    let muted = generator.push(&frame(1, 10, red));
+    assert_eq!(
+        muted,
+        EffectsFrame {
+            timestamp_ns: 10 * MS,
+            muted: true,
+            ..EffectsFrame::default()
+        }
+    );
+
+    let mut pits = driving(15.0, 1);
+    pits.flags.in_pits = true;
+    assert!(generator.push(&frame(2, 20, pits)).muted);
+
+    // Back on track, the fan ramps up again from zero.
+    let resumed = generator.push(&frame(3, 30, driving(60.0, 4)));
+    assert!(!resumed.muted);
+    assert!(
+        resumed.wind > 0.0 && resumed.wind < racing.wind,
+        "{resumed:?}"
+    );
+}
+
+#[tokio::test]
+async fn receiver_maps_each_telemetry_frame() -> TestResult {
+    let (tx, rx) = mpsc::channel(16);
+    let mut effects = EffectsReceiver::new(rx, EffectsChannelGenerator::new(unsmoothed()));
+    tx.send(frame(0, 5, driving(38.5, 2))).await?;
+    drop(tx);
+
+    let first = tokio::time::timeout(Duration::from_secs(1), effects.recv())
+        .await?
+        .ok_or("no effects frame")?;
+    assert_eq!(first.timestamp_ns, 5 * MS);
+    assert!(approx(first.wind, 0.5));
+    assert!(effects.recv().await.is_none());
+    Ok(())
+}
@@ -0,0 +1,236 @@
+//! Downsampled history store: bucket statistics, roll-up, wrap-around and memory bound.
+
+use std::time::Duration;
+
+use racing_wheel_telemetry_core::history::{FIELD_ACCUMULATOR_BYTES, SLOT_HEADER_BYTES};
+use racing_wheel_telemetry_core::{
+    ConnectionState, HistoryConfig, HistoryField, HistoryResolution, HistoryStore,
+    NormalizedTelemetry, TelemetryFrame,
+};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const SECOND: Duration = Duration::from_secs(1);
+const TEN_SECONDS: Duration = Duration::from_secs(10);
+const MS: u64 = 1_000_000;
+
+fn frame(timestamp_ms: u64, sequence: u64, rpm: f32) -> TelemetryFrame {
+    let data = NormalizedTelemetry::builder().rpm(rpm).build();
+    TelemetryFrame::new(data, timestamp_ms * MS, sequence, 64)
+}
+
+fn rpm_store(resolutions: Vec<HistoryResolution>) -> HistoryStore {
+    HistoryStore::new(HistoryConfig {
+        resolutions,
+        fields: vec![HistoryField::Rpm],
+        max_games: 4,
+    })
+}
+
+#[test]
+fn bucket_statistics_match_synthetic_input() -> TestResult {
+    let mut store = rpm_store(vec![HistoryResolution::new(
+        SECOND,
+        Duration::from_secs(60),
+    )]);
+    let samples = [
+        (0, 1000.0),
+        (300, 3000.0),
+        (900, 2000.0),
+        (1000, 4000.0),
+        (1500, 6000.0),
+    ];
+    for (sequence, (at_ms, rpm)) in samples.into_iter().enumerate() {
+        store.record("acc", &frame(at_ms, sequence as u64, rpm));
+    }
+
+    let buckets = store.history("acc", HistoryField::Rpm, SECOND, Duration::from_secs(60));
+    assert_eq!(buckets.len(), 2);
+    let (first, second) = (buckets[0], buckets[1]);
+    assert_eq!(first.start_ns, 0);
+    assert_eq!(
+        (first.min, first.max, first.mean, first.samples),
+        (1000.0, 3000.0, 2000.0, 3)
+    );
+    assert_eq!(second.start_ns, 1000 * MS);
+    assert_eq!(
+        (second.min, second.max, second.mean, second.samples),
+        (4000.0, 6000.0, 5000.0, 2)
+    );
+    assert_eq!(first.connection, ConnectionState::Connected);
+
+    assert!(
+        store
+            .history("acc", HistoryField::SpeedMs, SECOND, SECOND)
+            .is_empty()
+    );
+    assert!(
+        store
+            .history("acc", HistoryField::Rpm, TEN_SECONDS, SECOND)
+            .is_empty()
+    );
+    assert!(
+        store
+            .history("iracing", HistoryField::Rpm, SECOND, SECOND)
+            .is_empty()
+    );
+
+    let json = serde_json::to_value(first)?;
+    assert_eq!(json["samples"], 3);
+    assert_eq!(json["connection"], "Connected");
+    Ok(())
+}
+
+#[test]
+fn one_second_buckets_roll_up_into_ten_second_buckets() -> TestResult {
+    let mut store = rpm_store(vec![
+        HistoryResolution::new(SECOND, Duration::from_secs(60)),
+        HistoryResolution::new(TEN_SECONDS, Duration::from_secs(600)),
+    ]);
+    // 30 s at 10 Hz with a dropped frame every 7th sequence number.
+    let mut sequence = 0;
+    for tick in 0..300u64 {
+        sequence += if tick % 7 == 6 { 2 } else { 1 };
+        store.record(
+            "f1_25",
+            &frame(tick * 100, sequence, (tick * 13 % 997) as f32),
+        );
+    }
+
+    let fine = store.history("f1_25", HistoryField::Rpm, SECOND, Duration::from_secs(30));
+    let coarse = store.history(
+        "f1_25",
+        HistoryField::Rpm,
+        TEN_SECONDS,
+        Duration::from_secs(30),
+    );
+    assert_eq!(fine.len(), 30);
+    assert_eq!(coarse.len(), 3);
+
+    for (coarse_bucket, group) in coarse.iter().zip(fine.chunks(10)) {
+        assert_eq!(coarse_bucket.start_ns, group[0].start_ns);
+        let min = group.iter().map(|b| b.min).fold(f32::INFINITY, f32::min);
+        let max = group
+            .iter()
+            .map(|b| b.max)
+            .fold(f32::NEG_INFINITY, f32::max);
+        let samples: u32 = group.iter().map(|b| b.samples).sum();
+        let weighted: f64 = group
+            .iter()
+            .map(|b| f64::from(b.mean) * f64::from(b.samples))
+            .sum();
+        assert_eq!(coarse_bucket.min, min);
+        assert_eq!(coarse_bucket.max, max);
+        assert_eq!(coarse_bucket.samples, samples);
+        assert!((f64::from(coarse_bucket.mean) - weighted / f64::from(samples)).abs() < 1e-3);
+        assert_eq!(
+            coarse_bucket.drops,
+            group.iter().map(|b| b.drops).sum::<u32>()
+        );
+    }
+    assert!(coarse.iter().all(|b| b.drops > 0));
+    Ok(())
+}
+
+#[test]
+fn ring_wraps_past_the_retention_window() -> TestResult {
+    let retention = Duration::from_secs(5);
+    let mut store = rpm_store(vec![HistoryResolution::new(SECOND, retention)]);
+    for second in 0..12u64 {
+        store.record("gt7", &frame(second * 1000, second, second as f32));
+    }
+
+    let buckets = store.history("gt7", HistoryField::Rpm, SECOND, Duration::from_secs(3600));
+    let starts: Vec<u64> = buckets.iter().map(|b| b.start_ns / (1000 * MS)).collect();
+    assert_eq!(starts, [7, 8, 9, 10, 11]);
+    // Recycled slots carry only the new bucket's samples.
+    assert!(buckets.iter().all(|b| b.samples == 1));
+    assert_eq!(
+        buckets.iter().map(|b| b.max).collect::<Vec<_>>(),
+        [7.0, 8.0, 9.0, 10.0, 11.0]
+    );
+
+    // A late frame for a bucket that already fell out of the window is ignored.
+    store.record("gt7", &frame(2000, 12, 99.0));
+    let after = store.history("gt7", HistoryField::Rpm, SECOND, retention);
+    assert_eq!(after, buckets);
+
+    let recent = store.history("gt7", HistoryField::Rpm, SECOND, Duration::from_secs(2));
+    assert_eq!(recent.len(), 2);
+    Ok(())
+}
+
+#[test]
+fn connection_changes_and_reported_drops_land_in_buckets() -> TestResult {
+    let mut store = rpm_store(vec![HistoryResolution::new(
+        SECOND,
+        Duration::from_secs(60),
+    )]);
+    store.record("acc", &frame(0, 0, 1.0));
+    store.record("acc", &frame(100, 3, 1.0));
+    store.record_connection("acc", ConnectionState::Disconnected, 1500 * MS);
+    store.record_drops("acc", 5, 2500 * MS);
+    // Sequence numbers restart after a disconnect without counting as drops.
+    store.record("acc", &frame(3000, 0, 2.0));
+
+    let buckets = store.history("acc", HistoryField::Rpm, SECOND, Duration::from_secs(60));
+    let summary: Vec<_> = buckets
+        .iter()
+        .map(|b| (b.start_ns / (1000 * MS), b.samples, b.connection, b.drops))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (0, 2, ConnectionState::Connected, 2),
+            (1, 0, ConnectionState::Disconnected, 0),
+            (2, 0, ConnectionState::Disconnected, 5),
+            (3, 1, ConnectionState::Connected, 0),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn memory_stays_under_the_documented_bound() -> TestResult {
+    let config = HistoryConfig::default();
+    let slots = 600 + 720;
+    assert_eq!(
+        config.bytes_per_game(),
+        slots * (SLOT_HEADER_BYTES + config.fields.len() * FIELD_ACCUMULATOR_BYTES)
+    );
+    assert!(config.bytes_per_game() <= 160 * 1024);
+    assert!(config.memory_upper_bound() <= 1300 * 1024);
+
+    let mut store = HistoryStore::new(config.clone());
+    for game in 0..20u64 {
+        for tick in 0..2_000u64 {
+            store.record(&format!("game_{game}"), &frame(tick * 500, tick, 1.0));
+        }
+    }
+    assert_eq!(store.game_ids().len(), config.max_games);
+    assert!(store.memory_bytes() <= config.memory_upper_bound());
+    assert_eq!(store.memory_bytes(), config.memory_upper_bound());
+    Ok(())
+}
+
+#[test]
+fn summary_uses_the_coarsest_resolution() -> TestResult {
+    let mut store = HistoryStore::default();
+    for tick in 0..120u64 {
+        store.record("iracing", &frame(tick * 250, tick, 5000.0));
+    }
+    let summary = store.summary();
+    assert_eq!(summary.games.len(), 1);
+    let game = &summary.games[0];
+    assert_eq!(game.game_id, "iracing");
+    assert_eq!(game.resolution_ms, 10_000);
+    assert_eq!(game.fields.len(), 4);
+    let rpm = game
+        .fields
+        .iter()
+        .find(|f| f.field == HistoryField::Rpm)
+        .ok_or("rpm missing from summary")?;
+    assert_eq!(rpm.buckets.len(), 3);
+    assert!(serde_json::to_string(&summary)?.contains("\"rpm\""));
+    Ok(())
+}
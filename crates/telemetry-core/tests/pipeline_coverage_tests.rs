@@ -193,10 +193,16 @@ fn telemetry_field_coverage_json_roundtrip() -> TestResult {
             launch_control: false,
             traction_control: true,
             abs_active: true,
+            safety_car: false,
+            virtual_safety_car: false,
         },
         car_id: true,
         track_id: true,
+        penalties: false,
+        game_time: false,
         extended_fields: vec!["tire_wear_fl".to_string(), "fuel_kg".to_string()],
+        tires: false,
+        timing: Default::default(),
     };
 
     let json = serde_json::to_string(&coverage)?;
@@ -217,6 +223,7 @@ fn disconnection_config_json_roundtrip() -> TestResult {
         auto_reconnect: false,
         max_reconnect_attempts: 3,
         reconnect_delay_ms: 2000,
+        ..Default::default()
     };
 
     let json = serde_json::to_string(&config)?;
@@ -597,7 +604,7 @@ mod proptest_pipeline {
             let kmh = t.speed_kmh();
             let mph = t.speed_mph();
             prop_assert!((kmh - speed * 3.6).abs() < 0.01);
-            prop_assert!((mph - speed * 2.237).abs() < 0.01);
+            prop_assert!((mph - speed * racing_wheel_telemetry_contracts::MPH_PER_MS).abs() < 0.01);
         }
 
         #[test]
@@ -6,7 +6,7 @@
 
 use proptest::prelude::*;
 use racing_wheel_telemetry_core::{
-    ConnectionState, ConnectionStateEvent, DisconnectionConfig, NormalizedTelemetry,
+    ConnectionState, ConnectionStateEvent, DeltaComputer, DisconnectionConfig, NormalizedTelemetry,
     TelemetryFlags, TelemetryFrame, TelemetryValue,
 };
 
@@ -224,9 +224,9 @@ proptest! {
     }
 
     #[test]
-    fn speed_mph_is_2_237x_speed_ms(speed in positive_f32()) {
+    fn speed_mph_is_exact_mile_factor_times_speed_ms(speed in positive_f32()) {
         let t = NormalizedTelemetry::builder().speed_ms(speed).build();
-        let diff = (t.speed_mph() - speed * 2.237).abs();
+        let diff = (t.speed_mph() - speed * racing_wheel_telemetry_contracts::MPH_PER_MS).abs();
         prop_assert!(diff < 0.01);
     }
 
@@ -361,7 +361,7 @@ fn connection_event_is_connection() -> TestResult {
     assert!(event.is_connection());
     assert!(!event.is_disconnection());
     assert_eq!(event.game_id, "iracing");
-    assert!(event.timestamp_ns > 0);
+    assert!(event.timestamp_unix_ns > 0);
     Ok(())
 }
 
@@ -482,6 +482,7 @@ fn disconnection_config_serde_round_trip() -> TestResult {
         auto_reconnect: false,
         max_reconnect_attempts: 5,
         reconnect_delay_ms: 2000,
+        ..Default::default()
     };
     let json = serde_json::to_string(&config)?;
     let decoded: DisconnectionConfig = serde_json::from_str(&json)?;
@@ -494,3 +495,41 @@ fn disconnection_config_serde_round_trip() -> TestResult {
     assert_eq!(decoded.reconnect_delay_ms, config.reconnect_delay_ms);
     Ok(())
 }
+
+// ── DeltaComputer derivatives ───────────────────────────────────────────
+
+proptest! {
+    #[test]
+    fn delta_constant_speed_has_no_acceleration(
+        speed in positive_f32(),
+        steps_ms in prop::collection::vec(1u64..=400, 1..50),
+    ) {
+        let mut computer = DeltaComputer::default();
+        let mut at_ns = 0u64;
+        for (sequence, step_ms) in steps_ms.iter().enumerate() {
+            let data = NormalizedTelemetry::builder().speed_ms(speed).build();
+            if let Some(delta) = computer.push(&TelemetryFrame::new(data, at_ns, sequence as u64, 64)) {
+                let accel = delta.acceleration_ms2.ok_or_else(|| TestCaseError::fail("gap within limit lost its derivative"))?;
+                prop_assert!(accel.abs() < 1e-3, "acceleration {} at constant speed", accel);
+            }
+            at_ns += step_ms * 1_000_000;
+        }
+    }
+
+    #[test]
+    fn delta_linear_speed_ramp_recovers_its_slope(
+        slope in -20.0f32..=20.0f32,
+        step_ms in 5u64..=100,
+    ) {
+        let mut computer = DeltaComputer::default();
+        for sequence in 0..20u64 {
+            let t_s = (sequence * step_ms) as f32 / 1000.0;
+            let data = NormalizedTelemetry::builder().speed_ms(50.0 + slope * t_s).build();
+            let frame = TelemetryFrame::new(data, sequence * step_ms * 1_000_000, sequence, 64);
+            if let Some(delta) = computer.push(&frame) {
+                let accel = delta.acceleration_ms2.ok_or_else(|| TestCaseError::fail("missing acceleration"))?;
+                prop_assert!((accel - slope).abs() < 0.05, "expected {}, got {}", slope, accel);
+            }
+        }
+    }
+}
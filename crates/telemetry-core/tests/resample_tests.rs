@@ -0,0 +1,182 @@
+//! Fixed-rate resampling: interpolation, hold, staleness and sequencing.
+
+use std::time::Duration;
+
+use racing_wheel_telemetry_core::{
+    ExtendedKey, NormalizedTelemetry, ResampledReceiver, Resampler, ResamplerError, TelemetryFrame,
+    TelemetryValue, telemetry_now_ns,
+};
+use tokio::sync::mpsc;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const MS: u64 = 1_000_000;
+const STALE_AFTER: Duration = Duration::from_millis(100);
+
+fn frame(at_ns: u64, sequence: u64, rpm: f32, gear: i8) -> TelemetryFrame {
+    let data = NormalizedTelemetry::builder()
+        .rpm(rpm)
+        .speed_ms(rpm / 100.0)
+        .gear(gear)
+        .car_id("gt3")
+        .build();
+    TelemetryFrame::new(data, at_ns, sequence, 128)
+}
+
+fn is_stale(frame: &TelemetryFrame) -> bool {
+    frame.data.extended.get(ExtendedKey::RESAMPLED_STALE.name)
+        == Some(&TelemetryValue::Boolean(true))
+}
+
+fn close(actual: f32, expected: f32) -> bool {
+    (actual - expected).abs() < 0.5
+}
+
+#[test]
+fn rejects_unusable_rates() {
+    for rate in [0.0, -100.0, f32::NAN, f32::INFINITY] {
+        assert!(matches!(
+            Resampler::new(rate, STALE_AFTER),
+            Err(ResamplerError::InvalidRate(_))
+        ));
+    }
+}
+
+#[test]
+fn nothing_pushed_means_nothing_polled() -> TestResult {
+    let mut resampler = Resampler::new(100.0, STALE_AFTER)?;
+    assert!(resampler.poll_next(u64::MAX).is_none());
+    Ok(())
+}
+
+#[test]
+fn fast_input_is_decimated_by_interpolation() -> TestResult {
+    let mut resampler = Resampler::new(100.0, STALE_AFTER)?;
+    let input_period_ns = 1_000_000_000 / 360;
+    // rpm rises 1 per ms.
+    let rpm_at = |at_ns: u64| 3000.0 + at_ns as f32 / MS as f32;
+
+    let mut outputs = Vec::new();
+    let mut sequence = 0;
+    for slot in 0..10u64 {
+        let deadline = slot * 10 * MS;
+        // The poller runs just after the slot, once a later input arrived.
+        while sequence * input_period_ns <= deadline + input_period_ns {
+            let at = sequence * input_period_ns;
+            resampler.push(frame(at, sequence, rpm_at(at), 4));
+            sequence += 1;
+        }
+        outputs.extend(resampler.poll_next(deadline));
+    }
+
+    assert_eq!(outputs.len(), 10);
+    for (index, output) in outputs.iter().enumerate() {
+        assert_eq!(output.sequence, index as u64);
+        assert_eq!(output.timestamp_ns, index as u64 * 10 * MS);
+        assert!(
+            close(output.data.rpm, rpm_at(output.timestamp_ns)),
+            "slot {index}: rpm {}",
+            output.data.rpm
+        );
+        assert!(!is_stale(output));
+    }
+    Ok(())
+}
+
+#[test]
+fn slow_input_is_interpolated_and_discrete_channels_held() -> TestResult {
+    let mut resampler = Resampler::new(100.0, STALE_AFTER)?;
+    resampler.push(frame(0, 0, 5000.0, 3));
+    resampler.push(frame(50 * MS, 1, 6000.0, 4));
+
+    let outputs: Vec<_> = (0..5)
+        .filter_map(|slot| resampler.poll_next(slot * 10 * MS))
+        .collect();
+    let rpms: Vec<f32> = outputs.iter().map(|frame| frame.data.rpm).collect();
+    assert_eq!(rpms, [5000.0, 5200.0, 5400.0, 5600.0, 5800.0]);
+    assert!(close(outputs[2].data.speed_ms * 100.0, 5400.0));
+    // The upshift only shows once the frame carrying it is reached.
+    assert!(outputs.iter().all(|frame| frame.data.gear == 3));
+    assert!(
+        outputs
+            .iter()
+            .all(|frame| frame.data.car_id.as_deref() == Some("gt3"))
+    );
+
+    let at_second_frame = resampler
+        .poll_next(50 * MS)
+        .ok_or("slot at the second frame")?;
+    assert_eq!(at_second_frame.data.gear, 4);
+    assert_eq!(at_second_frame.data.rpm, 6000.0);
+    Ok(())
+}
+
+#[test]
+fn held_frames_turn_stale_after_the_limit() -> TestResult {
+    let mut resampler = Resampler::new(100.0, STALE_AFTER)?;
+    resampler.push(frame(0, 0, 7000.0, 5));
+
+    let held = resampler.poll_next(100 * MS).ok_or("held slot")?;
+    assert_eq!(held.data.rpm, 7000.0);
+    assert!(!is_stale(&held));
+
+    let stale = resampler.poll_next(110 * MS).ok_or("stale slot")?;
+    assert_eq!(stale.data.rpm, 7000.0);
+    assert!(is_stale(&stale));
+
+    // Fresh input clears the marker.
+    resampler.push(frame(115 * MS, 1, 7100.0, 5));
+    let fresh = resampler.poll_next(120 * MS).ok_or("fresh slot")?;
+    assert!(!is_stale(&fresh));
+    assert_eq!(fresh.data.rpm, 7100.0);
+    Ok(())
+}
+
+#[test]
+fn late_polls_skip_missed_slots_and_keep_sequence_monotonic() -> TestResult {
+    let mut resampler = Resampler::new(100.0, STALE_AFTER)?;
+    resampler.push(frame(0, 0, 4000.0, 2));
+
+    let first = resampler.poll_next(0).ok_or("first slot")?;
+    assert!(resampler.poll_next(5 * MS).is_none());
+    let late = resampler.poll_next(45 * MS).ok_or("late slot")?;
+    assert_eq!(late.timestamp_ns, 40 * MS);
+    assert_eq!(late.sequence, first.sequence + 1);
+    assert!(resampler.poll_next(45 * MS).is_none());
+    Ok(())
+}
+
+#[test]
+fn out_of_order_input_is_ignored() -> TestResult {
+    let mut resampler = Resampler::new(100.0, STALE_AFTER)?;
+    resampler.push(frame(0, 0, 3000.0, 2));
+    resampler.push(frame(20 * MS, 2, 5000.0, 2));
+    resampler.push(frame(10 * MS, 1, 9000.0, 2));
+
+    let _ = resampler.poll_next(0);
+    let middle = resampler.poll_next(10 * MS).ok_or("middle slot")?;
+    assert_eq!(middle.data.rpm, 4000.0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn receiver_emits_a_steady_monotonic_stream() -> TestResult {
+    let (tx, rx) = mpsc::channel(16);
+    let mut output = ResampledReceiver::new(rx, Resampler::new(200.0, STALE_AFTER)?);
+    tx.send(frame(telemetry_now_ns(), 0, 6500.0, 4)).await?;
+
+    let mut sequences = Vec::new();
+    for _ in 0..5 {
+        let frame = tokio::time::timeout(Duration::from_secs(1), output.recv())
+            .await?
+            .ok_or("stream ended early")?;
+        assert_eq!(frame.data.rpm, 6500.0);
+        sequences.push(frame.sequence);
+    }
+    assert!(sequences.windows(2).all(|pair| pair[0] < pair[1]));
+
+    drop(tx);
+    let ended = tokio::time::timeout(Duration::from_secs(1), output.recv()).await?;
+    assert!(ended.is_none());
+    Ok(())
+}
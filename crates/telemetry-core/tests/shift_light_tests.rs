@@ -0,0 +1,180 @@
+//! Shift-light LED states from `ShiftLightProfile`.
+
+use std::time::Duration;
+
+use racing_wheel_telemetry_core::{
+    FlagKind, LedSource, NormalizedTelemetry, Rgb, ShiftLightProfile, ShiftLightProfileError,
+    ShiftLightState, TelemetryFlags,
+};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+/// Inside the lit half of a 4 Hz blink cycle.
+const BLINK_ON: Duration = Duration::from_millis(50);
+/// Inside the dark half.
+const BLINK_OFF: Duration = Duration::from_millis(200);
+
+fn at_rpm(rpm: f32, max_rpm: f32) -> NormalizedTelemetry {
+    NormalizedTelemetry::builder()
+        .rpm(rpm)
+        .max_rpm(max_rpm)
+        .build()
+}
+
+fn with_flags(flags: TelemetryFlags) -> NormalizedTelemetry {
+    NormalizedTelemetry::builder()
+        .rpm(7000.0)
+        .max_rpm(8000.0)
+        .flags(flags)
+        .build()
+}
+
+fn lit(state: &ShiftLightState<10>) -> usize {
+    state.leds.iter().filter(|led| **led != Rgb::OFF).count()
+}
+
+#[test]
+fn zone_boundaries_light_leds_progressively() {
+    let profile = ShiftLightProfile::default();
+    // Ten LEDs spread from 60% to 100% of redline, 4% apart.
+    let cases = [
+        (0.59, 0),
+        (0.60, 1),
+        (0.639, 1),
+        (0.641, 2),
+        (0.801, 6),
+        (0.94, 9),
+    ];
+    for (fraction, expected) in cases {
+        let state: ShiftLightState<10> =
+            profile.evaluate_at(&at_rpm(fraction * 8000.0, 8000.0), BLINK_ON);
+        assert_eq!(lit(&state), expected, "at {fraction}");
+        assert_eq!(state.source, LedSource::Rpm);
+    }
+
+    let state: ShiftLightState<10> = profile.evaluate_at(&at_rpm(0.81 * 8000.0, 8000.0), BLINK_ON);
+    assert_eq!(state.leds[..5], [Rgb::GREEN; 5]);
+    assert_eq!(state.leds[5], Rgb::YELLOW);
+}
+
+#[test]
+fn shift_point_flashes_the_strip() {
+    let profile = ShiftLightProfile::default();
+    let telemetry = at_rpm(7800.0, 8000.0);
+    let on: ShiftLightState<10> = profile.evaluate_at(&telemetry, BLINK_ON);
+    let off: ShiftLightState<10> = profile.evaluate_at(&telemetry, BLINK_OFF);
+    assert_eq!(lit(&on), 10);
+    assert_eq!(on.leds[9], Rgb::RED);
+    assert_eq!(lit(&off), 0);
+    assert!(on.blink_phase < 0.5 && off.blink_phase >= 0.5);
+}
+
+#[test]
+fn redline_comes_from_car_then_telemetry_then_default() -> TestResult {
+    let profile = ShiftLightProfile::from_json(
+        r#"{ "zones": [{ "start": 0.5, "color": [0, 255, 0] }],
+             "default_redline_rpm": 6000,
+             "car_redlines": { "porsche_911_gt3_r": 9500 } }"#,
+    )?;
+    let porsche = NormalizedTelemetry::builder()
+        .rpm(4750.0)
+        .max_rpm(8000.0)
+        .car_id("porsche_911_gt3_r")
+        .build();
+    assert_eq!(profile.redline_rpm(&porsche), 9500.0);
+    assert!((profile.redline_fraction(&porsche) - 0.5).abs() < 1e-6);
+
+    let unknown_car = NormalizedTelemetry::builder()
+        .rpm(4000.0)
+        .max_rpm(8000.0)
+        .car_id("mx5")
+        .build();
+    assert_eq!(profile.redline_rpm(&unknown_car), 8000.0);
+
+    // Engine speed without a max_rpm falls back to the default redline.
+    let state: ShiftLightState<10> = profile.evaluate_at(&at_rpm(3000.0, 0.0), BLINK_ON);
+    assert_eq!(profile.redline_rpm(&at_rpm(3000.0, 0.0)), 6000.0);
+    assert_eq!(lit(&state), 1);
+
+    let idle: ShiftLightState<10> = profile.evaluate_at(&at_rpm(0.0, 0.0), BLINK_ON);
+    assert_eq!(idle.source, LedSource::Off);
+    assert_eq!(lit(&idle), 0);
+    Ok(())
+}
+
+#[test]
+fn flags_take_precedence_in_profile_order() {
+    let profile = ShiftLightProfile::default();
+    let both = TelemetryFlags {
+        yellow_flag: true,
+        blue_flag: true,
+        pit_limiter: true,
+        ..TelemetryFlags::default()
+    };
+    let state: ShiftLightState<10> = profile.evaluate_at(&with_flags(both.clone()), BLINK_ON);
+    assert_eq!(state.source, LedSource::Flag(FlagKind::Yellow));
+    assert_eq!(state.leds, [Rgb::YELLOW; 10]);
+    let dark: ShiftLightState<10> = profile.evaluate_at(&with_flags(both), BLINK_OFF);
+    assert_eq!(dark.leds, [Rgb::OFF; 10]);
+
+    let blue = TelemetryFlags {
+        blue_flag: true,
+        pit_limiter: true,
+        ..TelemetryFlags::default()
+    };
+    let state: ShiftLightState<10> = profile.evaluate_at(&with_flags(blue), BLINK_ON);
+    assert_eq!(state.source, LedSource::Flag(FlagKind::Blue));
+    assert_eq!(state.leds, [Rgb::BLUE; 10]);
+
+    let limiter = TelemetryFlags {
+        pit_limiter: true,
+        ..TelemetryFlags::default()
+    };
+    let on: ShiftLightState<10> = profile.evaluate_at(&with_flags(limiter.clone()), BLINK_ON);
+    let off: ShiftLightState<10> = profile.evaluate_at(&with_flags(limiter), BLINK_OFF);
+    assert_eq!(on.source, LedSource::PitLimiter);
+    assert_eq!(on.leds[..5], [Rgb::BLUE; 5]);
+    assert_eq!(on.leds[5..], [Rgb::OFF; 5]);
+    assert_eq!(off.leds[..5], [Rgb::OFF; 5]);
+    assert_eq!(off.leds[5..], [Rgb::BLUE; 5]);
+}
+
+#[test]
+fn invalid_profiles_explain_what_is_wrong() -> TestResult {
+    let unsorted = ShiftLightProfile::from_json(
+        r#"{ "zones": [{ "start": 0.8, "color": [255, 0, 0] },
+                       { "start": 0.6, "color": [0, 255, 0] }] }"#,
+    );
+    let Err(err @ ShiftLightProfileError::ZonesNotSorted { index: 1, .. }) = unsorted else {
+        return Err(format!("unsorted zones accepted: {unsorted:?}").into());
+    };
+    assert!(err.to_string().contains("must be sorted"), "{err}");
+
+    let out_of_range =
+        ShiftLightProfile::from_json(r#"{ "zones": [{ "start": 1.2, "color": [255, 0, 0] }] }"#);
+    assert!(matches!(
+        out_of_range,
+        Err(ShiftLightProfileError::ZoneOutOfRange { index: 0, .. })
+    ));
+
+    let bad_redline = ShiftLightProfile::from_json(
+        r#"{ "zones": [{ "start": 0.5, "color": [0, 255, 0] }], "car_redlines": { "mx5": 0 } }"#,
+    );
+    let message = bad_redline
+        .err()
+        .map(|err| err.to_string())
+        .unwrap_or_default();
+    assert!(message.contains("car_redlines.mx5"), "{message}");
+
+    let typo =
+        ShiftLightProfile::from_json(r#"{ "zones": [{ "start": 0.5, "colour": [0, 255, 0] }] }"#);
+    let message = typo.err().map(|err| err.to_string()).unwrap_or_default();
+    assert!(message.contains("unknown field `colour`"), "{message}");
+    assert!(message.contains("line 1"), "{message}");
+
+    assert!(matches!(
+        ShiftLightProfile::from_json(r#"{ "zones": [] }"#),
+        Err(ShiftLightProfileError::NoZones)
+    ));
+    Ok(())
+}
@@ -536,7 +536,7 @@ fn connection_event_serde_roundtrip() -> TestResult {
     assert_eq!(decoded.previous_state, ConnectionState::Disconnected);
     assert_eq!(decoded.new_state, ConnectionState::Connected);
     assert_eq!(decoded.reason.as_deref(), Some("Shared memory opened"));
-    assert!(decoded.timestamp_ns > 0);
+    assert!(decoded.timestamp_unix_ns > 0);
     Ok(())
 }
 
@@ -594,7 +594,7 @@ fn connection_event_clone_preserves_fields() -> TestResult {
     assert_eq!(cloned.previous_state, event.previous_state);
     assert_eq!(cloned.new_state, event.new_state);
     assert_eq!(cloned.reason, event.reason);
-    assert_eq!(cloned.timestamp_ns, event.timestamp_ns);
+    assert_eq!(cloned.timestamp_unix_ns, event.timestamp_unix_ns);
     Ok(())
 }
 
@@ -609,6 +609,7 @@ fn disconnection_config_serde_roundtrip() -> TestResult {
         auto_reconnect: false,
         max_reconnect_attempts: 10,
         reconnect_delay_ms: 3000,
+        ..Default::default()
     };
     let json = serde_json::to_string(&config)?;
     let decoded: DisconnectionConfig = serde_json::from_str(&json)?;
@@ -853,6 +854,7 @@ fn telemetry_flags_all_true() -> TestResult {
         abs_active: true,
         engine_limiter: true,
         safety_car: true,
+        virtual_safety_car: true,
         formation_lap: true,
         session_paused: true,
     };
@@ -901,10 +903,16 @@ fn telemetry_field_coverage_empty_extended() -> TestResult {
             launch_control: false,
             traction_control: false,
             abs_active: false,
+            safety_car: false,
+            virtual_safety_car: false,
         },
         car_id: false,
         track_id: false,
+        penalties: false,
+        game_time: false,
         extended_fields: vec![],
+        tires: false,
+        timing: Default::default(),
     };
     let json = serde_json::to_string(&coverage)?;
     let back: TelemetryFieldCoverage = serde_json::from_str(&json)?;
@@ -931,6 +939,8 @@ fn flag_coverage_debug_and_clone() -> TestResult {
         launch_control: false,
         traction_control: false,
         abs_active: false,
+        safety_car: false,
+        virtual_safety_car: false,
     };
     let cloned = fc.clone();
     assert_eq!(format!("{fc:?}"), format!("{cloned:?}"));
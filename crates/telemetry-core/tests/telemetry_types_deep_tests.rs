@@ -547,6 +547,7 @@ fn tracker_max_reconnect_attempts_prevents_reconnect() -> TestResult {
         auto_reconnect: true,
         max_reconnect_attempts: 2,
         reconnect_delay_ms: 10,
+        ..Default::default()
     };
     let mut tracker = DisconnectionTracker::new("test", config);
 
@@ -575,6 +576,7 @@ fn tracker_auto_reconnect_disabled() -> TestResult {
         auto_reconnect: false,
         max_reconnect_attempts: 0,
         reconnect_delay_ms: 10,
+        ..Default::default()
     };
     let mut tracker = DisconnectionTracker::new("test", config);
     tracker.mark_error("lost connection".to_string());
@@ -0,0 +1,134 @@
+//! Unit conversions, manifest validation and range heuristics.
+
+use racing_wheel_telemetry_core::{
+    FieldUnit, NormalizedTelemetry, Unit, UnitManifest, UnitManifestIssue, VehicleTier,
+    canonical_unit, check_unit_ranges,
+};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn close(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1e-3 * b.abs().max(1.0)
+}
+
+#[test]
+fn conversions_follow_commonly_confused_units() -> TestResult {
+    let cases = [
+        (Unit::KilometersPerHour, Unit::MetersPerSecond, 180.0, 50.0),
+        (Unit::MilesPerHour, Unit::MetersPerSecond, 100.0, 44.704),
+        (Unit::Degrees, Unit::Radians, 180.0, std::f32::consts::PI),
+        (Unit::Bar, Unit::Psi, 2.0, 29.007_55),
+        (Unit::Kilopascal, Unit::Psi, 200.0, 29.007_55),
+        (Unit::Fahrenheit, Unit::Celsius, 212.0, 100.0),
+        (Unit::Kelvin, Unit::Celsius, 373.15, 100.0),
+        (
+            Unit::MetersPerSecondSquared,
+            Unit::StandardGravity,
+            9.806_65,
+            1.0,
+        ),
+        (Unit::Percent, Unit::Fraction, 75.0, 0.75),
+        (
+            Unit::RadiansPerSecond,
+            Unit::Rpm,
+            std::f32::consts::PI * 2.0,
+            60.0,
+        ),
+    ];
+    for (from, to, value, expected) in cases {
+        let conversion = from.conversion_to(to).ok_or("no conversion")?;
+        let converted = conversion.apply(value);
+        assert!(close(converted, expected), "{from} -> {to}: {converted}");
+    }
+    assert!(Unit::MetersPerSecond.conversion_to(Unit::Radians).is_none());
+    assert!(
+        Unit::Celsius
+            .conversion_to(Unit::Celsius)
+            .is_some_and(|c| c.is_identity())
+    );
+    for unit in Unit::ALL {
+        assert_eq!(Unit::from_symbol(unit.symbol()), Some(*unit));
+    }
+    Ok(())
+}
+
+#[test]
+fn canonical_units_cover_typed_fields_and_catalog_keys() {
+    assert_eq!(canonical_unit("speed_ms"), Some(Unit::MetersPerSecond));
+    assert_eq!(canonical_unit("tire_pressures_psi"), Some(Unit::Psi));
+    assert_eq!(canonical_unit("turbo_bar"), Some(Unit::Bar));
+    assert_eq!(canonical_unit("tyre_temp_fl"), Some(Unit::Celsius));
+    assert_eq!(canonical_unit("gear"), None);
+    assert_eq!(canonical_unit("boost_psi"), None);
+}
+
+#[test]
+fn manifest_issues_name_the_offending_field() {
+    static FIELDS: &[FieldUnit] = &[
+        FieldUnit::converted("speed_ms", Unit::KilometersPerHour, Unit::MetersPerSecond),
+        FieldUnit::converted("turbo_bar", Unit::Psi, Unit::Psi),
+        FieldUnit::native("boost_psi", Unit::Psi),
+        FieldUnit::native("speed_ms", Unit::MetersPerSecond),
+        FieldUnit::converted("steering_angle", Unit::Fraction, Unit::Radians),
+    ];
+    let manifest = UnitManifest::new(FIELDS);
+    assert_eq!(
+        manifest.issues(),
+        [
+            UnitManifestIssue::CanonicalMismatch {
+                field: "turbo_bar",
+                declared: Unit::Psi,
+                contract: Unit::Bar,
+            },
+            UnitManifestIssue::UnknownField { field: "boost_psi" },
+            UnitManifestIssue::Duplicate { field: "speed_ms" },
+        ]
+    );
+    let unconverted: Vec<_> = manifest.unconverted().map(|entry| entry.field).collect();
+    assert_eq!(unconverted, ["steering_angle"]);
+}
+
+#[test]
+fn leaked_units_are_flagged_with_the_suspected_source() -> TestResult {
+    let samples: Vec<_> = (0..10)
+        .map(|i| {
+            NormalizedTelemetry::builder()
+                .speed_ms(290.0 + i as f32)
+                .throttle(0.8)
+                .tire_pressures_psi([1.8, 1.8, 1.9, 1.9])
+                .build()
+        })
+        .collect();
+    let suspicions = check_unit_ranges(&samples, VehicleTier::Car, None);
+    let flagged: Vec<_> = suspicions
+        .iter()
+        .map(|s| (s.field.as_str(), s.suspected))
+        .collect();
+    assert_eq!(
+        flagged,
+        [
+            ("speed_ms", Some(Unit::KilometersPerHour)),
+            ("tire_pressures_psi", Some(Unit::Bar)),
+        ]
+    );
+    let message = suspicions[0].to_string();
+    assert!(
+        message.contains("speed_ms") && message.contains("km/h"),
+        "{message}"
+    );
+    Ok(())
+}
+
+#[test]
+fn tier_allowances_differ() {
+    let samples = [NormalizedTelemetry::builder()
+        .speed_ms(60.0)
+        .lateral_g(2.0)
+        .build()];
+    assert!(check_unit_ranges(&samples, VehicleTier::Car, None).is_empty());
+    let truck: Vec<_> = check_unit_ranges(&samples, VehicleTier::Truck, None)
+        .into_iter()
+        .map(|s| s.field)
+        .collect();
+    assert_eq!(truck, ["speed_ms", "lateral_g"]);
+}
@@ -616,9 +616,9 @@ fn process_packet_unknown_packet_id_returns_none() -> TestResult {
 
 #[test]
 fn process_packet_all_valid_ignored_ids_return_none() -> TestResult {
-    // Packet IDs 0, 2, 3, 4, 5, 8..13 are not session/telem/status
+    // Packet IDs 0, 3, 4, 5, 8..13 are not session/lap/telem/status
     let mut state = F1NativeState::default();
-    for id in [0u8, 2, 3, 4, 5, 8, 9, 10, 11, 12, 13] {
+    for id in [0u8, 3, 4, 5, 8, 9, 10, 11, 12, 13] {
         let raw = build_f1_native_header_bytes(PACKET_FORMAT_2024, id, 0);
         let result = F1NativeAdapter::process_packet(&mut state, &raw)?;
         assert!(result.is_none(), "packet id {} should be ignored", id);
@@ -1053,6 +1053,7 @@ fn normalize_extended_fields_present() -> TestResult {
         fuel_remaining_laps: 8.5,
         actual_tyre_compound: 12,
         tyre_age_laps: 15,
+        vehicle_fia_flags: 0,
         ers_store_energy: 2_000_000.0,
         ers_deploy_mode: 2,
         ers_harvested_mguk: 500_000.0,
@@ -1067,6 +1068,8 @@ fn normalize_extended_fields_present() -> TestResult {
         session_type: 6,
         track_temperature: 32,
         air_temperature: 26,
+        session_time_left_s: None,
+        ..SessionData::default()
     };
     let norm = normalize(&telem, &status, &session);
 
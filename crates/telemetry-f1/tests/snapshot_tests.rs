@@ -34,6 +34,7 @@ fn snapshot_f1_2024_normalize_mid_race() -> TestResult {
         fuel_remaining_laps: 12.3,
         actual_tyre_compound: 16,
         tyre_age_laps: 8,
+        vehicle_fia_flags: 0,
         ers_store_energy: 2_800_000.0,
         ers_deploy_mode: 3,
         ers_harvested_mguk: 600_000.0,
@@ -52,6 +53,8 @@ fn snapshot_f1_2024_normalize_mid_race() -> TestResult {
         session_type: 10,
         track_temperature: 38,
         air_temperature: 28,
+        session_time_left_s: None,
+        ..SessionData::default()
     };
 
     let norm = normalize(&telem, &status, &session);
@@ -102,6 +105,7 @@ fn snapshot_f1_2024_pit_entry() -> TestResult {
         fuel_remaining_laps: 4.2,
         actual_tyre_compound: 18,
         tyre_age_laps: 22,
+        vehicle_fia_flags: 0,
         ers_store_energy: 500_000.0,
         ers_deploy_mode: 0,
         pit_limiter_status: 1,
@@ -114,6 +118,8 @@ fn snapshot_f1_2024_pit_entry() -> TestResult {
         session_type: 10,
         track_temperature: 30,
         air_temperature: 24,
+        session_time_left_s: None,
+        ..SessionData::default()
     };
 
     let norm = normalize(&telem, &status, &session);
@@ -143,6 +149,7 @@ fn snapshot_f1_2024_formation_lap() -> TestResult {
         fuel_remaining_laps: 55.0,
         actual_tyre_compound: 12, // Soft
         tyre_age_laps: 0,
+        vehicle_fia_flags: 0,
         ers_store_energy: 4_000_000.0,
         ers_deploy_mode: 0,
         pit_limiter_status: 0,
@@ -157,6 +164,8 @@ fn snapshot_f1_2024_formation_lap() -> TestResult {
         session_type: 10,
         track_temperature: 28,
         air_temperature: 22,
+        session_time_left_s: None,
+        ..SessionData::default()
     };
 
     let norm = normalize(&telem, &status, &session);
@@ -186,6 +195,7 @@ fn snapshot_f1_2024_wet_conditions() -> TestResult {
         fuel_remaining_laps: 25.0,
         actual_tyre_compound: 8, // Wet
         tyre_age_laps: 3,
+        vehicle_fia_flags: 0,
         ers_store_energy: 3_000_000.0,
         ers_deploy_mode: 1,
         max_rpm: 13500,
@@ -198,6 +208,8 @@ fn snapshot_f1_2024_wet_conditions() -> TestResult {
         session_type: 10,
         track_temperature: 15,
         air_temperature: 12,
+        session_time_left_s: None,
+        ..SessionData::default()
     };
 
     let norm = normalize(&telem, &status, &session);
@@ -227,6 +239,7 @@ fn snapshot_f1_2024_max_speed_drs() -> TestResult {
         fuel_remaining_laps: 1.5,
         actual_tyre_compound: 14, // Hard
         tyre_age_laps: 35,
+        vehicle_fia_flags: 0,
         ers_store_energy: 200_000.0,
         ers_deploy_mode: 3,
         max_rpm: 15000,
@@ -243,6 +256,8 @@ fn snapshot_f1_2024_max_speed_drs() -> TestResult {
         session_type: 10,
         track_temperature: 45,
         air_temperature: 35,
+        session_time_left_s: None,
+        ..SessionData::default()
     };
 
     let norm = normalize(&telem, &status, &session);
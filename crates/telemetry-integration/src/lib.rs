@@ -9,7 +9,13 @@
 use racing_wheel_telemetry_bdd_metrics::{
     BddMatrixMetrics, MatrixParityPolicy, RuntimeBddMatrixMetrics,
 };
+use serde::Serialize;
 use std::collections::BTreeSet;
+use std::fmt::Write as _;
+
+/// Version of the document [`RuntimeCoverageReport::to_json_report`] emits;
+/// bumped whenever a field is renamed or removed.
+pub const PARITY_REPORT_SCHEMA_VERSION: u32 = 1;
 
 /// Coverage report for comparing a runtime registry against the support matrix.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -22,10 +28,52 @@ pub struct RegistryCoverage {
     pub missing_in_registry: Vec<String>,
     /// Registry IDs that are not listed in the matrix.
     pub extra_in_registry: Vec<String>,
+    /// Exempt matrix IDs missing from the registry, sorted. They are left out
+    /// of `missing_in_registry`, so nothing below counts them.
+    pub exempted_missing: Vec<String>,
+    /// Exempt registry IDs not listed in the matrix, sorted, likewise left out
+    /// of `extra_in_registry`.
+    pub exempted_extra: Vec<String>,
+    /// Exemptions that exempted nothing, such as one naming a game no longer
+    /// in the matrix, sorted.
+    pub stale_exemptions: Vec<String>,
 }
 
 impl RegistryCoverage {
-    /// Return true when matrix and registry are in exact set-based alignment.
+    /// Move the IDs `exemptions` covers out of the missing and extra lists
+    /// into `exempted_missing` and `exempted_extra`, recording unused
+    /// exemptions in `stale_exemptions`. Replaces any earlier exemptions.
+    pub fn with_exemptions(mut self, exemptions: &CoverageExemptions) -> Self {
+        let missing = std::mem::take(&mut self.missing_in_registry)
+            .into_iter()
+            .chain(std::mem::take(&mut self.exempted_missing));
+        (self.exempted_missing, self.missing_in_registry) =
+            partition_sorted(missing, &exemptions.exempt_missing);
+        let extra = std::mem::take(&mut self.extra_in_registry)
+            .into_iter()
+            .chain(std::mem::take(&mut self.exempted_extra));
+        (self.exempted_extra, self.extra_in_registry) =
+            partition_sorted(extra, &exemptions.exempt_extra);
+
+        let stale_missing = exemptions
+            .exempt_missing
+            .iter()
+            .filter(|id| self.exempted_missing.binary_search(id).is_err());
+        let stale_extra = exemptions
+            .exempt_extra
+            .iter()
+            .filter(|id| self.exempted_extra.binary_search(id).is_err());
+        self.stale_exemptions = stale_missing
+            .chain(stale_extra)
+            .cloned()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        self
+    }
+
+    /// Return true when matrix and registry are in exact set-based alignment,
+    /// exemptions aside.
     pub fn is_exact(&self) -> bool {
         self.missing_in_registry.is_empty() && self.extra_in_registry.is_empty()
     }
@@ -75,6 +123,11 @@ impl RegistryCoverage {
             extra_count: self.extra_in_registry.len(),
             matrix_coverage_ratio: self.matrix_coverage_ratio(),
             registry_coverage_ratio: self.registry_coverage_ratio(),
+            missing_game_ids: self.missing_in_registry.clone(),
+            extra_game_ids: self.extra_in_registry.clone(),
+            exempted_missing: self.exempted_missing.clone(),
+            exempted_extra: self.exempted_extra.clone(),
+            stale_exemptions: self.stale_exemptions.clone(),
         }
     }
 
@@ -91,7 +144,7 @@ impl RegistryCoverage {
 }
 
 /// Deterministic metrics for a single matrix-vs-registry comparison.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct RegistryCoverageMetrics {
     pub matrix_game_count: usize,
     pub registry_game_count: usize,
@@ -99,6 +152,16 @@ pub struct RegistryCoverageMetrics {
     pub extra_count: usize,
     pub matrix_coverage_ratio: f64,
     pub registry_coverage_ratio: f64,
+    /// Matrix IDs missing from the registry, sorted.
+    pub missing_game_ids: Vec<String>,
+    /// Registry IDs not listed in the matrix, sorted.
+    pub extra_game_ids: Vec<String>,
+    /// Exempt matrix IDs missing from the registry, sorted.
+    pub exempted_missing: Vec<String>,
+    /// Exempt registry IDs not listed in the matrix, sorted.
+    pub exempted_extra: Vec<String>,
+    /// Exemptions that exempted nothing, sorted.
+    pub stale_exemptions: Vec<String>,
 }
 
 /// Compare matrix game IDs with registry IDs and produce a deterministic coverage report.
@@ -129,11 +192,14 @@ where
         registry_game_ids: registry_set.iter().cloned().collect(),
         missing_in_registry,
         extra_in_registry,
+        exempted_missing: Vec::new(),
+        exempted_extra: Vec::new(),
+        stale_exemptions: Vec::new(),
     }
 }
 
 /// Coverage policy for matrix/registry comparison.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize)]
 pub struct CoveragePolicy {
     /// Allow matrix entries without registry coverage.
     pub allow_missing_registry: bool,
@@ -173,6 +239,66 @@ impl CoveragePolicy {
             allow_extra_registry: self.allow_extra_registry,
         }
     }
+
+    /// Pair this policy with games it should overlook.
+    pub fn with_exemptions(self, exemptions: CoverageExemptions) -> ExemptCoveragePolicy {
+        ExemptCoveragePolicy {
+            policy: self,
+            exemptions,
+        }
+    }
+}
+
+/// Games known not to need registry coverage, such as a game that has no
+/// config writer on purpose. Exempt IDs never fail a policy but stay visible
+/// in [`RegistryCoverage::exempted_missing`] and
+/// [`RegistryCoverage::exempted_extra`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CoverageExemptions {
+    /// Matrix IDs allowed to be missing from the registry.
+    pub exempt_missing: BTreeSet<String>,
+    /// Registry IDs allowed to be absent from the matrix.
+    pub exempt_extra: BTreeSet<String>,
+}
+
+impl CoverageExemptions {
+    /// Exempt `game_ids` from needing a registry entry.
+    pub fn with_missing<I, T>(mut self, game_ids: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        self.exempt_missing.extend(normalize_ids(game_ids));
+        self
+    }
+
+    /// Exempt `game_ids` from needing a matrix entry.
+    pub fn with_extra<I, T>(mut self, game_ids: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        self.exempt_extra.extend(normalize_ids(game_ids));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.exempt_missing.is_empty() && self.exempt_extra.is_empty()
+    }
+}
+
+/// A [`CoveragePolicy`] together with its [`CoverageExemptions`]; the
+/// comparison functions accept either.
+#[derive(Debug, Clone)]
+pub struct ExemptCoveragePolicy {
+    pub policy: CoveragePolicy,
+    pub exemptions: CoverageExemptions,
+}
+
+impl From<CoveragePolicy> for ExemptCoveragePolicy {
+    fn from(policy: CoveragePolicy) -> Self {
+        policy.with_exemptions(CoverageExemptions::default())
+    }
 }
 
 /// Combined matrix/registry parity report for adapter and config-writer registries.
@@ -188,6 +314,9 @@ pub struct RuntimeCoverageReport {
     pub adapter_policy: CoveragePolicy,
     /// Policy enforced for writer registry checks.
     pub writer_policy: CoveragePolicy,
+    /// Games declaring fields the writers reject; empty unless
+    /// [`Self::with_field_check`] ran. Not part of [`Self::is_parity_ok`].
+    pub field_mismatches: Vec<FieldCoverageMismatch>,
 }
 
 impl RuntimeCoverageReport {
@@ -206,6 +335,28 @@ impl RuntimeCoverageReport {
         self.adapter_policy_ok() && self.writer_policy_ok()
     }
 
+    /// Cross-check each game's declared supported fields against the
+    /// writers' field validation, recording mismatches in `field_mismatches`.
+    pub fn with_field_check<G, Id, F, Field>(
+        mut self,
+        games: G,
+        is_valid_field: impl Fn(&str) -> bool,
+    ) -> Self
+    where
+        G: IntoIterator<Item = (Id, F)>,
+        Id: AsRef<str>,
+        F: IntoIterator<Item = Field>,
+        Field: AsRef<str>,
+    {
+        self.field_mismatches = compare_supported_fields(games, is_valid_field);
+        self
+    }
+
+    /// Return true when no game declares a field the writers reject.
+    pub fn field_coverage_ok(&self) -> bool {
+        self.field_mismatches.is_empty()
+    }
+
     /// Return deterministic runtime matrix metrics for BDD/observability.
     pub fn metrics(&self) -> RuntimeCoverageMetrics {
         RuntimeCoverageMetrics {
@@ -224,10 +375,76 @@ impl RuntimeCoverageReport {
             self.writer_coverage.bdd_metrics(self.writer_policy),
         )
     }
+
+    /// Render the report as a pretty-printed JSON document for CI artifacts.
+    ///
+    /// Object keys and every ID list are sorted, so the output only changes
+    /// when the coverage does. Carries [`PARITY_REPORT_SCHEMA_VERSION`].
+    pub fn to_json_report(&self) -> String {
+        let registry = |coverage: &RegistryCoverage, policy: CoveragePolicy| {
+            serde_json::json!({
+                "policy": policy,
+                "parity_ok": policy.is_satisfied(coverage),
+                "metrics": coverage.metrics(),
+            })
+        };
+        let report = serde_json::json!({
+            "schema_version": PARITY_REPORT_SCHEMA_VERSION,
+            "parity_ok": self.is_parity_ok(),
+            "matrix_game_ids": self.matrix_game_ids,
+            "registries": {
+                "adapter": registry(&self.adapter_coverage, self.adapter_policy),
+                "writer": registry(&self.writer_coverage, self.writer_policy),
+            },
+            "field_mismatches": self.field_mismatches,
+        });
+        // `json!` builds sorted maps; serializing a `Value` cannot fail.
+        serde_json::to_string_pretty(&report).unwrap_or_default()
+    }
+
+    /// Return `Ok` when both registries satisfy their policies, otherwise a
+    /// multi-line explanation naming every offending game and the registry
+    /// it is missing from or extra in, suitable as a CI failure message.
+    pub fn assert_parity_or_explain(&self) -> Result<(), String> {
+        if self.is_parity_ok() {
+            return Ok(());
+        }
+        let mut explanation = String::from("telemetry registry parity check failed:");
+        for (registry, coverage, policy) in [
+            ("adapter", &self.adapter_coverage, self.adapter_policy),
+            ("config writer", &self.writer_coverage, self.writer_policy),
+        ] {
+            if policy.is_satisfied(coverage) {
+                continue;
+            }
+            let _ = write!(
+                explanation,
+                "\n  {registry} registry (allow_missing_registry={}, allow_extra_registry={}):",
+                policy.allow_missing_registry, policy.allow_extra_registry
+            );
+            if !policy.allow_missing_registry {
+                for game_id in &coverage.missing_in_registry {
+                    let _ = write!(
+                        explanation,
+                        "\n    - {game_id}: in the support matrix but has no {registry}"
+                    );
+                }
+            }
+            if !policy.allow_extra_registry {
+                for game_id in &coverage.extra_in_registry {
+                    let _ = write!(
+                        explanation,
+                        "\n    - {game_id}: has a {registry} but is not in the support matrix"
+                    );
+                }
+            }
+        }
+        Err(explanation)
+    }
 }
 
 /// Deterministic runtime matrix metrics across adapter and writer registries.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct RuntimeCoverageMetrics {
     pub matrix_game_count: usize,
     pub adapter: RegistryCoverageMetrics,
@@ -240,8 +457,8 @@ pub fn compare_runtime_registries_with_policies<M, A, W, MItem, AItem, WItem>(
     matrix_game_ids: M,
     adapter_game_ids: A,
     writer_game_ids: W,
-    adapter_policy: CoveragePolicy,
-    writer_policy: CoveragePolicy,
+    adapter_policy: impl Into<ExemptCoveragePolicy>,
+    writer_policy: impl Into<ExemptCoveragePolicy>,
 ) -> RuntimeCoverageReport
 where
     M: IntoIterator<Item = MItem>,
@@ -254,18 +471,73 @@ where
     let matrix_game_ids_set = normalize_ids(matrix_game_ids);
     let matrix_game_ids: Vec<String> = matrix_game_ids_set.iter().cloned().collect();
 
-    let adapter_coverage = compare_matrix_and_registry(&matrix_game_ids, adapter_game_ids);
-    let writer_coverage = compare_matrix_and_registry(&matrix_game_ids, writer_game_ids);
+    let adapter_policy = adapter_policy.into();
+    let writer_policy = writer_policy.into();
+    let adapter_coverage = compare_matrix_and_registry(&matrix_game_ids, adapter_game_ids)
+        .with_exemptions(&adapter_policy.exemptions);
+    let writer_coverage = compare_matrix_and_registry(&matrix_game_ids, writer_game_ids)
+        .with_exemptions(&writer_policy.exemptions);
 
     RuntimeCoverageReport {
         matrix_game_ids,
         adapter_coverage,
         writer_coverage,
-        adapter_policy,
-        writer_policy,
+        adapter_policy: adapter_policy.policy,
+        writer_policy: writer_policy.policy,
+        field_mismatches: Vec::new(),
+    }
+}
+
+/// Supported fields a game declares that the config writers cannot validate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldCoverageMismatch {
+    pub game_id: String,
+    /// Rejected field names, sorted.
+    pub unknown_fields: Vec<String>,
+}
+
+impl std::fmt::Display for FieldCoverageMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "field coverage mismatch for {} (unknown_fields={:?})",
+            self.game_id, self.unknown_fields
+        )
     }
 }
 
+impl std::error::Error for FieldCoverageMismatch {}
+
+/// Check every game's declared supported fields with `is_valid_field`,
+/// returning one mismatch per game with rejected fields, sorted by game id.
+pub fn compare_supported_fields<G, Id, F, Field>(
+    games: G,
+    is_valid_field: impl Fn(&str) -> bool,
+) -> Vec<FieldCoverageMismatch>
+where
+    G: IntoIterator<Item = (Id, F)>,
+    Id: AsRef<str>,
+    F: IntoIterator<Item = Field>,
+    Field: AsRef<str>,
+{
+    let mut mismatches: Vec<FieldCoverageMismatch> = games
+        .into_iter()
+        .filter_map(|(game_id, fields)| {
+            let unknown_fields: BTreeSet<String> = fields
+                .into_iter()
+                .filter(|field| !is_valid_field(field.as_ref()))
+                .map(|field| field.as_ref().to_string())
+                .collect();
+            (!unknown_fields.is_empty()).then(|| FieldCoverageMismatch {
+                game_id: game_id.as_ref().to_ascii_lowercase(),
+                unknown_fields: unknown_fields.into_iter().collect(),
+            })
+        })
+        .collect();
+    mismatches.sort_by(|a, b| a.game_id.cmp(&b.game_id));
+    mismatches
+}
+
 /// Detailed mismatch report for matrix/registry alignment checks.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CoverageMismatch {
@@ -288,10 +560,13 @@ impl std::fmt::Display for CoverageMismatch {
 impl std::error::Error for CoverageMismatch {}
 
 /// Compare coverage using a policy and return mismatches when policy is violated.
+///
+/// Accepts a bare [`CoveragePolicy`] or one with exemptions attached; exempt
+/// IDs never cause a mismatch.
 pub fn compare_matrix_and_registry_with_policy<M, R, MItem, RItem>(
     matrix_game_ids: M,
     registry_game_ids: R,
-    policy: CoveragePolicy,
+    policy: impl Into<ExemptCoveragePolicy>,
 ) -> Result<RegistryCoverage, CoverageMismatch>
 where
     M: IntoIterator<Item = MItem>,
@@ -299,7 +574,9 @@ where
     MItem: AsRef<str>,
     RItem: AsRef<str>,
 {
-    let coverage = compare_matrix_and_registry(matrix_game_ids, registry_game_ids);
+    let ExemptCoveragePolicy { policy, exemptions } = policy.into();
+    let coverage = compare_matrix_and_registry(matrix_game_ids, registry_game_ids)
+        .with_exemptions(&exemptions);
 
     if !policy.is_satisfied(&coverage) {
         return Err(CoverageMismatch {
@@ -324,6 +601,16 @@ where
         .collect::<BTreeSet<_>>()
 }
 
+/// Split `ids` into sorted lists of those in `exempt` and the rest.
+fn partition_sorted(
+    ids: impl Iterator<Item = String>,
+    exempt: &BTreeSet<String>,
+) -> (Vec<String>, Vec<String>) {
+    let (exempted, rest): (BTreeSet<String>, BTreeSet<String>) =
+        ids.partition(|id| exempt.contains(id));
+    (exempted.into_iter().collect(), rest.into_iter().collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -519,4 +806,39 @@ mod tests {
         assert!(bdd_metrics.writer.parity_ok);
         assert!(bdd_metrics.parity_ok);
     }
+
+    #[test]
+    fn test_field_check_flags_unknown_supported_fields() {
+        let valid = ["rpm", "speed_ms", "gear"];
+        let report = compare_runtime_registries_with_policies(
+            ["acc", "iracing"],
+            ["acc", "iracing"],
+            ["acc", "iracing"],
+            CoveragePolicy::STRICT,
+            CoveragePolicy::STRICT,
+        );
+        assert!(report.field_coverage_ok());
+
+        let report = report.with_field_check(
+            [
+                ("iracing", vec!["rpm", "warp_drive", "gear", "warp_drive"]),
+                ("acc", vec!["rpm", "speed_ms"]),
+            ],
+            |field| valid.contains(&field),
+        );
+        assert!(!report.field_coverage_ok());
+        assert!(report.is_parity_ok());
+        assert_eq!(
+            report.field_mismatches,
+            [FieldCoverageMismatch {
+                game_id: "iracing".to_string(),
+                unknown_fields: vec!["warp_drive".to_string()],
+            }]
+        );
+        assert!(
+            report.field_mismatches[0]
+                .to_string()
+                .contains("warp_drive")
+        );
+    }
 }
@@ -0,0 +1,96 @@
+//! Per-game coverage exemptions for known-unsupported games.
+
+use racing_wheel_telemetry_integration::{
+    CoverageExemptions, CoveragePolicy, compare_matrix_and_registry_with_policy,
+    compare_runtime_registries_with_policies,
+};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const MATRIX: [&str; 3] = ["acc", "iracing", "writerless_sim"];
+const WRITERS: [&str; 2] = ["acc", "iracing"];
+
+fn writerless() -> CoverageExemptions {
+    CoverageExemptions::default().with_missing(["Writerless_Sim"])
+}
+
+#[test]
+fn exempted_missing_writer_passes_matrix_complete() -> TestResult {
+    let coverage = compare_matrix_and_registry_with_policy(
+        MATRIX,
+        WRITERS,
+        CoveragePolicy::MATRIX_COMPLETE.with_exemptions(writerless()),
+    )?;
+    assert!(coverage.missing_in_registry.is_empty());
+    assert_eq!(coverage.exempted_missing, ["writerless_sim"]);
+    assert!(coverage.stale_exemptions.is_empty());
+
+    let metrics = coverage.metrics();
+    assert_eq!(metrics.missing_count, 0);
+    assert_eq!(metrics.exempted_missing, ["writerless_sim"]);
+    Ok(())
+}
+
+#[test]
+fn missing_writer_without_exemption_still_fails() -> TestResult {
+    let mismatch =
+        compare_matrix_and_registry_with_policy(MATRIX, WRITERS, CoveragePolicy::MATRIX_COMPLETE)
+            .err()
+            .ok_or("missing writer went unnoticed")?;
+    assert_eq!(mismatch.missing_in_registry, ["writerless_sim"]);
+
+    let other_game = CoverageExemptions::default().with_missing(["acc"]);
+    let mismatch = compare_matrix_and_registry_with_policy(
+        MATRIX,
+        ["iracing"],
+        CoveragePolicy::MATRIX_COMPLETE.with_exemptions(other_game),
+    )
+    .err()
+    .ok_or("unexempted missing writer went unnoticed")?;
+    assert_eq!(mismatch.missing_in_registry, ["writerless_sim"]);
+    Ok(())
+}
+
+#[test]
+fn exemption_for_a_game_outside_the_matrix_is_stale() -> TestResult {
+    let exemptions = writerless()
+        .with_missing(["retired_game"])
+        .with_extra(["experimental", "never_registered"]);
+    let report = compare_runtime_registries_with_policies(
+        MATRIX,
+        MATRIX.into_iter().chain(["experimental"]),
+        WRITERS,
+        CoveragePolicy::STRICT,
+        CoveragePolicy::STRICT.with_exemptions(exemptions.clone()),
+    );
+    assert!(report.writer_policy_ok(), "{report:?}");
+    assert_eq!(
+        report.writer_coverage.stale_exemptions,
+        ["experimental", "never_registered", "retired_game"]
+    );
+    // Exemptions apply per registry: the adapter extra still fails STRICT.
+    assert!(!report.adapter_policy_ok());
+    assert_eq!(report.adapter_coverage.extra_in_registry, ["experimental"]);
+
+    let report = compare_runtime_registries_with_policies(
+        MATRIX,
+        MATRIX.into_iter().chain(["experimental"]),
+        WRITERS,
+        CoveragePolicy::STRICT.with_exemptions(exemptions),
+        CoveragePolicy::MATRIX_COMPLETE,
+    );
+    let adapters = &report.adapter_coverage;
+    assert!(report.adapter_policy_ok(), "{report:?}");
+    assert_eq!(adapters.exempted_extra, ["experimental"]);
+    assert_eq!(
+        adapters.stale_exemptions,
+        ["never_registered", "retired_game", "writerless_sim"]
+    );
+    assert!(!report.is_parity_ok());
+    let explanation = report
+        .assert_parity_or_explain()
+        .err()
+        .ok_or("writer gap went unnoticed")?;
+    assert!(explanation.contains("writerless_sim"), "{explanation}");
+    Ok(())
+}
@@ -0,0 +1,124 @@
+//! Machine-readable parity reports and CI failure explanations.
+
+use std::path::Path;
+
+use racing_wheel_telemetry_adapters::adapter_factories;
+use racing_wheel_telemetry_integration::{
+    CoveragePolicy, PARITY_REPORT_SCHEMA_VERSION, RuntimeCoverageReport,
+    compare_runtime_registries_with_policies,
+};
+use racing_wheel_telemetry_support::load_default_matrix;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+/// Set to rewrite the golden file from the current output.
+const BLESS_ENV: &str = "OPENRACING_BLESS_FIXTURES";
+const FAKE_GAME: &str = "fake_matrix_game";
+
+fn sample_report() -> RuntimeCoverageReport {
+    compare_runtime_registries_with_policies(
+        ["iracing", "acc", "dirt5", "ams2"],
+        ["acc", "iracing", "ams2", "dirt5", "experimental"],
+        ["acc", "iracing", "legacy_writer"],
+        CoveragePolicy::MATRIX_COMPLETE,
+        CoveragePolicy::STRICT,
+    )
+    .with_field_check(
+        [("ACC", vec!["rpm", "warp_drive"]), ("iracing", vec!["rpm"])],
+        |field| field != "warp_drive",
+    )
+}
+
+#[test]
+fn json_report_matches_the_golden_file() -> TestResult {
+    let golden = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/parity_report.json");
+    let actual = sample_report().to_json_report();
+    if std::env::var(BLESS_ENV).is_ok_and(|value| value != "0") {
+        std::fs::write(&golden, format!("{actual}\n"))?;
+    }
+    assert_eq!(actual, std::fs::read_to_string(&golden)?.trim_end());
+    Ok(())
+}
+
+#[test]
+fn json_report_is_stable_and_versioned() -> TestResult {
+    let report = sample_report();
+    assert_eq!(report.to_json_report(), report.clone().to_json_report());
+
+    let value: serde_json::Value = serde_json::from_str(&report.to_json_report())?;
+    assert_eq!(value["schema_version"], PARITY_REPORT_SCHEMA_VERSION);
+    assert_eq!(value["parity_ok"], false);
+    let writer = &value["registries"]["writer"];
+    assert_eq!(writer["parity_ok"], false);
+    assert_eq!(
+        writer["metrics"]["missing_game_ids"],
+        serde_json::json!(["ams2", "dirt5"])
+    );
+    assert_eq!(
+        writer["metrics"]["extra_game_ids"],
+        serde_json::json!(["legacy_writer"])
+    );
+    Ok(())
+}
+
+#[test]
+fn explanation_names_each_offending_game_and_registry() -> TestResult {
+    let explanation = sample_report()
+        .assert_parity_or_explain()
+        .err()
+        .ok_or("sample report passed parity")?;
+    let lines: Vec<&str> = explanation.lines().collect();
+    assert!(lines.len() > 1, "{explanation}");
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.contains("dirt5") && line.contains("no config writer"))
+    );
+    assert!(
+        lines.iter().any(
+            |line| line.contains("legacy_writer") && line.contains("not in the support matrix")
+        )
+    );
+    // Adapters pass under MATRIX_COMPLETE, so their extra is not an offence.
+    assert!(!explanation.contains("experimental"), "{explanation}");
+    assert!(!explanation.contains("adapter registry"), "{explanation}");
+    Ok(())
+}
+
+#[test]
+fn explanation_mentions_an_injected_fake_matrix_game() -> TestResult {
+    let matrix = load_default_matrix()?;
+    let matrix_ids: Vec<String> = matrix.game_ids();
+    let report = compare_runtime_registries_with_policies(
+        matrix_ids.iter().map(String::as_str).chain([FAKE_GAME]),
+        adapter_factories().iter().map(|(game_id, _)| *game_id),
+        matrix_ids.iter().map(String::as_str),
+        CoveragePolicy::MATRIX_COMPLETE,
+        CoveragePolicy::MATRIX_COMPLETE,
+    );
+
+    let explanation = report
+        .assert_parity_or_explain()
+        .err()
+        .ok_or("fake matrix game went unnoticed")?;
+    let offending: Vec<&str> = explanation
+        .lines()
+        .filter(|line| line.contains(FAKE_GAME))
+        .collect();
+    assert_eq!(offending.len(), 2, "{explanation}");
+    assert!(offending[0].contains("no adapter"), "{explanation}");
+    assert!(offending[1].contains("no config writer"), "{explanation}");
+    Ok(())
+}
+
+#[test]
+fn passing_report_needs_no_explanation() {
+    let report = compare_runtime_registries_with_policies(
+        ["acc", "iracing"],
+        ["acc", "iracing", "experimental"],
+        ["acc", "iracing"],
+        CoveragePolicy::MATRIX_COMPLETE,
+        CoveragePolicy::STRICT,
+    );
+    assert_eq!(report.assert_parity_or_explain(), Ok(()));
+}
@@ -0,0 +1,93 @@
+//! Crash-replay buffer of every monitored game's recent frames.
+//!
+//! Unlike `enable_recording`, which has to be switched on before the session
+//! worth keeping, the black box keeps the last few seconds of each game in a
+//! [`FrameRing`] so they can be saved after something goes wrong. Forwarding
+//! tasks push frames as they are sent on; `TelemetryService::save_recent`
+//! copies the rings out under the lock and writes one recording per game, so
+//! it never holds up forwarding for the duration of the write.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use racing_wheel_telemetry_adapters::TelemetryFrame;
+use racing_wheel_telemetry_recorder::{FrameRing, SavedRecording};
+
+/// Recordings written by `TelemetryService::save_recent`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SaveReport {
+    /// One entry per game with buffered frames, by game id.
+    pub recordings: Vec<SavedRecording>,
+}
+
+impl SaveReport {
+    /// Frames written across all games.
+    pub fn frame_count(&self) -> usize {
+        self.recordings.iter().map(|r| r.frame_count).sum()
+    }
+
+    /// Telemetry time from the earliest to the latest frame written.
+    pub fn span(&self) -> Duration {
+        let first = self
+            .recordings
+            .iter()
+            .filter_map(|r| r.first_timestamp_ns)
+            .min();
+        let last = self
+            .recordings
+            .iter()
+            .filter_map(|r| r.last_timestamp_ns)
+            .max();
+        match (first, last) {
+            (Some(first), Some(last)) => Duration::from_nanos(last.saturating_sub(first)),
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+/// Per-game rings, empty and ignoring frames while disabled.
+#[derive(Debug, Default)]
+pub(crate) struct BlackBox {
+    capacity: Option<Duration>,
+    rings: BTreeMap<String, FrameRing>,
+}
+
+impl BlackBox {
+    /// Start buffering the last `capacity` of every game, discarding frames
+    /// kept under a previous capacity.
+    pub(crate) fn enable(&mut self, capacity: Duration) {
+        self.capacity = Some(capacity);
+        self.rings.clear();
+    }
+
+    pub(crate) fn disable(&mut self) {
+        self.capacity = None;
+        self.rings.clear();
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.capacity.is_some()
+    }
+
+    pub(crate) fn record(&mut self, game_id: &str, frame: &TelemetryFrame) {
+        let Some(capacity) = self.capacity else {
+            return;
+        };
+        if let Some(ring) = self.rings.get_mut(game_id) {
+            ring.push(frame.clone());
+        } else {
+            let mut ring = FrameRing::new(capacity);
+            ring.push(frame.clone());
+            self.rings.insert(game_id.to_string(), ring);
+        }
+    }
+
+    /// Buffered frames of every game, oldest first.
+    pub(crate) fn snapshot(&self) -> Vec<(String, Vec<TelemetryFrame>)> {
+        self.rings
+            .iter()
+            .filter(|(_, ring)| !ring.is_empty())
+            .map(|(game_id, ring)| (game_id.clone(), ring.frames()))
+            .collect()
+    }
+}
@@ -0,0 +1,199 @@
+//! A synchronous facade over [`TelemetryService`] for callers without a
+//! Tokio runtime, such as plugin hosts embedded in a native application.
+//!
+//! [`BlockingTelemetryService`] owns a current-thread runtime driven by one
+//! background thread, so adapters and forwarding tasks keep running between
+//! calls. Each call blocks the caller until its future completes on that
+//! runtime. Frames reach a [`BlockingFrameIter`] through a bounded standard
+//! channel rather than the runtime, so an iterator never blocks on a runtime
+//! that has gone away: once the service is dropped its forwarding tasks are
+//! cancelled and the iterator ends.
+
+use std::collections::HashMap;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use racing_wheel_telemetry_adapters::{TelemetryFrame, TelemetryReceiver};
+use tokio::runtime::{Builder, Handle};
+use tokio::sync::oneshot;
+
+use crate::TelemetryService;
+use crate::health::AdapterHealth;
+
+/// Frames a [`BlockingFrameIter`] buffers before its game's forwarding task
+/// waits for the consumer.
+pub const BLOCKING_FRAME_CAPACITY: usize = 256;
+
+/// Longest a dropped service waits for its runtime's blocking work to end.
+const RUNTIME_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Why [`BlockingFrameIter::recv`] returned without a frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum BlockingRecvError {
+    /// No frame arrived within the iterator's timeout; later ones may.
+    #[error("no telemetry frame within {0:?}")]
+    Timeout(Duration),
+    /// The stream ended: monitoring stopped or the service was dropped.
+    #[error("telemetry stream closed")]
+    Closed,
+}
+
+/// [`TelemetryService`] with synchronous methods.
+///
+/// Must not be used from within a Tokio runtime; its methods fail there
+/// rather than block a runtime thread.
+pub struct BlockingTelemetryService {
+    service: TelemetryService,
+    handle: Handle,
+    /// Dropping it stops the runtime thread.
+    shutdown: Option<oneshot::Sender<()>>,
+    runtime_thread: Option<JoinHandle<()>>,
+}
+
+impl BlockingTelemetryService {
+    /// Wrap `service`, starting the runtime its tasks will run on.
+    pub fn new(service: TelemetryService) -> Result<Self> {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .context("Failed to build the blocking telemetry runtime")?;
+        let handle = runtime.handle().clone();
+        let (shutdown, stopped) = oneshot::channel::<()>();
+        let runtime_thread = std::thread::Builder::new()
+            .name("telemetry-blocking-runtime".to_string())
+            .spawn(move || {
+                // Ends on a send or when the sender is dropped.
+                let _ = runtime.block_on(stopped);
+                runtime.shutdown_timeout(RUNTIME_SHUTDOWN_TIMEOUT);
+            })
+            .context("Failed to spawn the blocking telemetry runtime thread")?;
+        Ok(Self {
+            service,
+            handle,
+            shutdown: Some(shutdown),
+            runtime_thread: Some(runtime_thread),
+        })
+    }
+
+    /// The wrapped service, for its synchronous accessors.
+    pub fn service(&self) -> &TelemetryService {
+        &self.service
+    }
+
+    /// The wrapped service, for registration and configuration.
+    pub fn service_mut(&mut self) -> &mut TelemetryService {
+        &mut self.service
+    }
+
+    /// Start monitoring `game_id` and iterate its forwarded frames.
+    pub fn start_monitoring(&mut self, game_id: &str) -> Result<BlockingFrameIter> {
+        ensure_outside_runtime()?;
+        let frames = self
+            .handle
+            .block_on(self.service.start_monitoring(game_id))?;
+        BlockingFrameIter::bridge(game_id, frames)
+    }
+
+    pub fn stop_monitoring(&self, game_id: &str) -> Result<()> {
+        ensure_outside_runtime()?;
+        self.handle.block_on(self.service.stop_monitoring(game_id))
+    }
+
+    pub fn is_game_running(&self, game_id: &str) -> Result<bool> {
+        ensure_outside_runtime()?;
+        self.handle.block_on(self.service.is_game_running(game_id))
+    }
+
+    pub fn supported_games(&self) -> Vec<String> {
+        self.service.supported_games()
+    }
+
+    pub fn health(&self) -> HashMap<String, AdapterHealth> {
+        self.service.health()
+    }
+}
+
+impl Drop for BlockingTelemetryService {
+    fn drop(&mut self) {
+        self.shutdown.take();
+        if let Some(thread) = self.runtime_thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn ensure_outside_runtime() -> Result<()> {
+    if Handle::try_current().is_ok() {
+        anyhow::bail!(
+            "BlockingTelemetryService called from within a Tokio runtime; use TelemetryService"
+        );
+    }
+    Ok(())
+}
+
+/// Blocking iterator over one game's forwarded frames.
+///
+/// Without a timeout, [`Iterator::next`] waits until a frame arrives or the
+/// stream ends. With one, `next` also returns `None` when it expires; use
+/// [`Self::recv`] to tell the two apart.
+pub struct BlockingFrameIter {
+    frames: mpsc::Receiver<TelemetryFrame>,
+    timeout: Option<Duration>,
+}
+
+impl BlockingFrameIter {
+    /// Move frames from `receiver` onto a standard channel from a thread of
+    /// their own, which ends with the stream or the iterator.
+    fn bridge(game_id: &str, mut receiver: TelemetryReceiver) -> Result<Self> {
+        let (tx, frames) = mpsc::sync_channel(BLOCKING_FRAME_CAPACITY);
+        std::thread::Builder::new()
+            .name(format!("telemetry-blocking-{game_id}"))
+            .spawn(move || {
+                while let Some(frame) = receiver.blocking_recv() {
+                    if tx.send(frame).is_err() {
+                        break;
+                    }
+                }
+            })
+            .context("Failed to spawn the blocking frame thread")?;
+        Ok(Self {
+            frames,
+            timeout: None,
+        })
+    }
+
+    /// Wait at most `timeout` for each frame.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// The next frame, waiting at most the iterator's timeout.
+    pub fn recv(&mut self) -> Result<TelemetryFrame, BlockingRecvError> {
+        match self.timeout {
+            None => self.frames.recv().map_err(|_| BlockingRecvError::Closed),
+            Some(timeout) => self.frames.recv_timeout(timeout).map_err(|err| match err {
+                RecvTimeoutError::Timeout => BlockingRecvError::Timeout(timeout),
+                RecvTimeoutError::Disconnected => BlockingRecvError::Closed,
+            }),
+        }
+    }
+}
+
+impl Iterator for BlockingFrameIter {
+    type Item = TelemetryFrame;
+
+    fn next(&mut self) -> Option<TelemetryFrame> {
+        self.recv().ok()
+    }
+}
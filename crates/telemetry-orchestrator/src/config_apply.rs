@@ -0,0 +1,107 @@
+//! Applying telemetry config to every installed game in one call.
+//!
+//! Callers hand over the install roots they have found, keyed by game id;
+//! each registered config writer whose game has a root on disk is applied
+//! in turn. One game failing does not stop the rest, so a setup screen can
+//! report partial success game by game.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use racing_wheel_telemetry_config_writers::{
+    ConfigDiff, ConfigWriter, TelemetryConfig, config_writer_factories,
+};
+use racing_wheel_telemetry_support::{GameSupportMatrix, normalize_game_id};
+use tracing::{debug, warn};
+
+/// Runs every registered config writer against the games found on disk.
+pub struct ConfigWriterOrchestrator {
+    support_matrix: Option<GameSupportMatrix>,
+}
+
+impl Default for ConfigWriterOrchestrator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfigWriterOrchestrator {
+    /// Create an orchestrator limited to the shipped support matrix.
+    pub fn new() -> Self {
+        Self::from_support_matrix(racing_wheel_telemetry_support::load_default_matrix().ok())
+    }
+
+    /// Create an orchestrator limited to `support_matrix`; `None` applies
+    /// every registered writer.
+    pub fn from_support_matrix(support_matrix: Option<GameSupportMatrix>) -> Self {
+        if support_matrix.is_none() {
+            warn!("Applying every registered config writer because no support matrix is loaded.");
+        }
+        Self { support_matrix }
+    }
+
+    /// Apply `config` to every game with a root in `game_roots`, in registry
+    /// order, returning each applied game's diffs or error.
+    pub fn apply_all(
+        &self,
+        game_roots: &HashMap<String, PathBuf>,
+        config: &TelemetryConfig,
+    ) -> Vec<(String, Result<Vec<ConfigDiff>>)> {
+        self.installed(game_roots)
+            .map(|(game_id, writer, root)| {
+                let result = writer.write_config(root, config);
+                if let Err(err) = &result {
+                    warn!(game_id, error = %err, "Failed to apply telemetry config");
+                }
+                (game_id.to_string(), result)
+            })
+            .collect()
+    }
+
+    /// Whether each game with a root in `game_roots` has its telemetry config
+    /// applied. A game whose validation errors counts as not applied.
+    pub fn validate_all(&self, game_roots: &HashMap<String, PathBuf>) -> BTreeMap<String, bool> {
+        self.installed(game_roots)
+            .map(|(game_id, writer, root)| {
+                let valid = writer.validate_config(root).unwrap_or_else(|err| {
+                    warn!(game_id, error = %err, "Failed to validate telemetry config");
+                    false
+                });
+                (game_id.to_string(), valid)
+            })
+            .collect()
+    }
+
+    /// Registered writers the matrix supports, paired with their game's root
+    /// when that root exists on disk.
+    fn installed<'a>(
+        &'a self,
+        game_roots: &'a HashMap<String, PathBuf>,
+    ) -> impl Iterator<Item = (&'static str, Box<dyn ConfigWriter + Send + Sync>, &'a Path)> + 'a
+    {
+        let roots: HashMap<&str, &Path> = game_roots
+            .iter()
+            .map(|(game_id, root)| (normalize_game_id(game_id), root.as_path()))
+            .collect();
+        config_writer_factories()
+            .iter()
+            .filter_map(move |(game_id, factory)| {
+                if let Some(matrix) = &self.support_matrix
+                    && !matrix.has_game_id(game_id)
+                {
+                    debug!(
+                        game_id,
+                        "Skipping config writer; game ID is not present in telemetry matrix."
+                    );
+                    return None;
+                }
+                let root = *roots.get(game_id)?;
+                if !root.exists() {
+                    debug!(game_id, root = %root.display(), "Skipping config writer; game root does not exist.");
+                    return None;
+                }
+                Some((*game_id, factory(), root))
+            })
+    }
+}
@@ -0,0 +1,152 @@
+//! CSV export of recorded telemetry for spreadsheet and analysis tools.
+//!
+//! [`export_csv`] writes a header row of channel names, then one row per
+//! frame: `time_s`, the frame's `timestamp_ns` in seconds relative to the
+//! first frame, followed by one cell per [`ChannelSelector`]. Frames are
+//! written as the iterator yields them, so a recording never has to fit in
+//! memory as CSV.
+//!
+//! Cells follow RFC 4180: rows end in CRLF, and any cell containing a comma,
+//! quote or line break is quoted with inner quotes doubled. Floats and
+//! integers are written as numbers and booleans, flags included, as `0` or
+//! `1`. A channel the frame does not carry, such as an absent extended key, a
+//! `None` typed field or a non-finite float, is an empty cell. Array and map
+//! values are written as a JSON cell, arrays as plain JSON arrays.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use racing_wheel_telemetry_adapters::{TelemetryFrame, TelemetryValue};
+use racing_wheel_telemetry_recorder::TelemetryRecorder;
+use serde::{Deserialize, Serialize};
+
+use crate::field_watch::FieldSelector;
+
+/// A CSV column, named as for field watches: `rpm`, `flags.in_pits` or
+/// `extended:tire_temp_fl`.
+pub type ChannelSelector = FieldSelector;
+
+/// Name of the leading relative-time column.
+pub const TIME_COLUMN: &str = "time_s";
+
+/// What an export wrote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ExportStats {
+    /// Data rows, one per frame; the header is not counted.
+    pub rows: u64,
+    /// Columns per row, including [`TIME_COLUMN`].
+    pub columns: usize,
+    /// Channel cells left empty because the frame did not carry the value.
+    pub empty_cells: u64,
+}
+
+/// Write `frames` as CSV with one column per channel; see the module docs
+/// for the format.
+pub fn export_csv(
+    frames: impl Iterator<Item = TelemetryFrame>,
+    channels: &[ChannelSelector],
+    writer: impl Write,
+) -> Result<ExportStats> {
+    let mut out = BufWriter::new(writer);
+    let mut stats = ExportStats {
+        columns: channels.len() + 1,
+        ..ExportStats::default()
+    };
+
+    let mut row = String::from(TIME_COLUMN);
+    for channel in channels {
+        row.push(',');
+        push_text(&mut row, &channel.to_string());
+    }
+    row.push_str("\r\n");
+    out.write_all(row.as_bytes())
+        .context("Failed to write CSV header")?;
+
+    let mut first_timestamp_ns = None;
+    for frame in frames {
+        let origin = *first_timestamp_ns.get_or_insert(frame.timestamp_ns);
+        let relative_ns = i128::from(frame.timestamp_ns) - i128::from(origin);
+        row.clear();
+        row.push_str(&format!("{:.6}", relative_ns as f64 / 1e9));
+        for channel in channels {
+            row.push(',');
+            let value = channel.extract(&frame.data);
+            if !push_value(&mut row, value.as_ref()) {
+                stats.empty_cells += 1;
+            }
+        }
+        row.push_str("\r\n");
+        out.write_all(row.as_bytes())
+            .with_context(|| format!("Failed to write CSV row {}", stats.rows + 1))?;
+        stats.rows += 1;
+    }
+    out.flush().context("Failed to flush CSV output")?;
+    Ok(stats)
+}
+
+/// Load the recording at `input_path`, JSON or binary, and export it to a
+/// CSV file at `output_path`.
+pub fn export_recording_to_csv(
+    input_path: impl AsRef<Path>,
+    output_path: impl AsRef<Path>,
+    channels: &[ChannelSelector],
+) -> Result<ExportStats> {
+    let input_path = input_path.as_ref();
+    let output_path = output_path.as_ref();
+    let recording = TelemetryRecorder::load_recording(input_path)
+        .with_context(|| format!("Failed to load recording {}", input_path.display()))?;
+    let output = File::create(output_path)
+        .with_context(|| format!("Failed to create {}", output_path.display()))?;
+    export_csv(recording.frames.into_iter(), channels, output)
+        .with_context(|| format!("Failed to export CSV to {}", output_path.display()))
+}
+
+/// Append the cell for `value`; returns `false` when the cell is empty.
+fn push_value(row: &mut String, value: Option<&TelemetryValue>) -> bool {
+    match value {
+        None => false,
+        Some(TelemetryValue::Float(value)) if !value.is_finite() => false,
+        Some(TelemetryValue::Float(value)) => {
+            row.push_str(&value.to_string());
+            true
+        }
+        Some(TelemetryValue::Integer(value)) => {
+            row.push_str(&value.to_string());
+            true
+        }
+        Some(TelemetryValue::Boolean(value)) => {
+            row.push(if *value { '1' } else { '0' });
+            true
+        }
+        Some(TelemetryValue::String(value)) => {
+            push_text(row, value);
+            true
+        }
+        Some(TelemetryValue::FloatArray(values)) => push_json(row, values),
+        Some(TelemetryValue::IntArray(values)) => push_json(row, values),
+        Some(TelemetryValue::Map(entries)) => push_json(row, entries),
+    }
+}
+
+fn push_json(row: &mut String, value: &impl Serialize) -> bool {
+    match serde_json::to_string(value) {
+        Ok(json) => {
+            push_text(row, &json);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Append `text`, quoted if RFC 4180 requires it.
+fn push_text(row: &mut String, text: &str) {
+    if text.contains([',', '"', '\r', '\n']) {
+        row.push('"');
+        row.push_str(&text.replace('"', "\"\""));
+        row.push('"');
+    } else {
+        row.push_str(text);
+    }
+}
@@ -0,0 +1,134 @@
+//! Working out which supported games are running.
+//!
+//! A probe asks every adapter's `is_game_running` at once, each under its own
+//! timeout: an adapter stuck on a socket bind or a process scan counts as not
+//! running for that poll instead of holding up the rest. [`GameDetector`]
+//! turns successive probes into [`GameDetectedEvent`]s, only flipping a
+//! game's state once [`DETECTION_CONFIRMATIONS`] polls in a row agree, so a
+//! socket that is briefly busy does not read as a game starting and stopping.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use racing_wheel_telemetry_adapters::TelemetryAdapter;
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinSet;
+use tracing::debug;
+
+/// Consecutive polls that must agree before a game's detected state flips.
+pub const DETECTION_CONFIRMATIONS: u32 = 2;
+
+/// Longest the detection loop waits on one adapter per poll.
+pub const DEFAULT_PROBE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Capacity of the channel returned by `spawn_detection_loop`.
+pub(crate) const DETECTION_EVENT_CAPACITY: usize = 64;
+
+/// A game started or stopped being detected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameDetectedEvent {
+    /// Normalized game id.
+    pub game_id: String,
+    /// `true` once the game is running, `false` once it stopped.
+    pub detected: bool,
+    /// `telemetry_now_ns` of the poll that confirmed the change.
+    pub timestamp_ns: u64,
+}
+
+/// Ask every adapter whether its game is running, giving each at most
+/// `timeout`. Returns the running ids, sorted.
+pub(crate) async fn probe(
+    adapters: impl IntoIterator<Item = (String, Arc<dyn TelemetryAdapter>)>,
+    timeout: Duration,
+) -> Vec<String> {
+    let mut probes = JoinSet::new();
+    for (game_id, adapter) in adapters {
+        probes.spawn(async move {
+            let running = tokio::time::timeout(timeout, adapter.is_game_running()).await;
+            (game_id, running)
+        });
+    }
+
+    let mut running = Vec::new();
+    while let Some(result) = probes.join_next().await {
+        match result {
+            Ok((game_id, Ok(Ok(true)))) => running.push(game_id),
+            Ok((_, Ok(Ok(false)))) => {}
+            Ok((game_id, Ok(Err(err)))) => {
+                debug!(game_id, error = %err, "Game detection probe failed");
+            }
+            Ok((game_id, Err(_))) => {
+                debug!(game_id, ?timeout, "Game detection probe timed out");
+            }
+            Err(err) => debug!(error = %err, "Game detection probe panicked"),
+        }
+    }
+    running.sort_unstable();
+    running
+}
+
+/// Per-game state a number of polls disagreed with in a row.
+#[derive(Debug, Clone, Copy, Default)]
+struct Debounce {
+    detected: bool,
+    disagreeing: u32,
+}
+
+/// Debounces successive probe results into detection events.
+#[derive(Debug, Clone, Default)]
+pub struct GameDetector {
+    games: BTreeMap<String, Debounce>,
+}
+
+impl GameDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed one poll's running games and get the state changes it
+    /// confirmed, ordered by game id.
+    ///
+    /// Games missing from `running` count as not running.
+    pub fn observe<S: AsRef<str>>(
+        &mut self,
+        running: &[S],
+        timestamp_ns: u64,
+    ) -> Vec<GameDetectedEvent> {
+        let running: BTreeSet<&str> = running.iter().map(AsRef::as_ref).collect();
+        for game_id in &running {
+            self.games.entry((*game_id).to_string()).or_default();
+        }
+
+        let mut events = Vec::new();
+        for (game_id, state) in &mut self.games {
+            let seen = running.contains(game_id.as_str());
+            if seen == state.detected {
+                state.disagreeing = 0;
+                continue;
+            }
+            state.disagreeing += 1;
+            if state.disagreeing >= DETECTION_CONFIRMATIONS {
+                state.detected = seen;
+                state.disagreeing = 0;
+                events.push(GameDetectedEvent {
+                    game_id: game_id.clone(),
+                    detected: seen,
+                    timestamp_ns,
+                });
+            }
+        }
+        self.games
+            .retain(|_, state| state.detected || state.disagreeing > 0);
+        events
+    }
+
+    /// Games currently considered running, sorted.
+    pub fn detected(&self) -> Vec<String> {
+        self.games
+            .iter()
+            .filter(|(_, state)| state.detected)
+            .map(|(game_id, _)| game_id.clone())
+            .collect()
+    }
+}
@@ -0,0 +1,357 @@
+//! Watch-style "latest value" subscriptions for individual telemetry fields.
+//!
+//! Each monitored game owns a [`FieldWatchHub`]. The forwarding task hands it
+//! every frame; the hub extracts each watched field once, no matter how many
+//! receivers share it, and only notifies when the value changes by more than
+//! the selector's tolerance. When the source disconnects every watcher sees
+//! `None`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Mutex, PoisonError};
+
+use racing_wheel_telemetry_adapters::{NormalizedTelemetry, TelemetryFlags, TelemetryValue};
+use tokio::sync::watch;
+
+/// Prefix naming an extended-map key in selector strings.
+pub const EXTENDED_PREFIX: &str = "extended:";
+/// Prefix naming a [`TelemetryFlags`] member in selector strings.
+pub const FLAGS_PREFIX: &str = "flags.";
+
+const TYPED_FIELDS: &[&str] = &[
+    "speed_ms",
+    "steering_angle",
+    "throttle",
+    "brake",
+    "clutch",
+    "rpm",
+    "max_rpm",
+    "gear",
+    "num_gears",
+    "lateral_g",
+    "longitudinal_g",
+    "vertical_g",
+    "slip_ratio",
+    "slip_angle_fl",
+    "slip_angle_fr",
+    "slip_angle_rl",
+    "slip_angle_rr",
+    "ffb_scalar",
+    "ffb_torque_nm",
+    "position",
+    "lap",
+    "current_lap_time_s",
+    "best_lap_time_s",
+    "last_lap_time_s",
+    "delta_ahead_s",
+    "delta_behind_s",
+    "fuel_percent",
+    "engine_temp_c",
+    "car_id",
+    "track_id",
+    "session_id",
+];
+
+const FLAG_FIELDS: &[&str] = &[
+    "yellow_flag",
+    "red_flag",
+    "blue_flag",
+    "checkered_flag",
+    "green_flag",
+    "pit_limiter",
+    "in_pits",
+    "drs_available",
+    "drs_active",
+    "ers_available",
+    "ers_active",
+    "launch_control",
+    "traction_control",
+    "abs_active",
+    "engine_limiter",
+    "safety_car",
+    "virtual_safety_car",
+];
+
+/// Which part of [`NormalizedTelemetry`] a selector reads.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FieldPath {
+    /// A typed top-level field such as `gear` or `speed_ms`.
+    Typed(&'static str),
+    /// A member of [`TelemetryFlags`], e.g. `in_pits`.
+    Flag(&'static str),
+    /// A key of the `extended` map.
+    Extended(String),
+}
+
+/// A single telemetry field, with an optional change tolerance for floats.
+///
+/// Parsed from `gear`, `flags.in_pits` or `extended:tire_temp_fl`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldSelector {
+    path: FieldPath,
+    tolerance: f32,
+}
+
+/// Error returned for unknown selector strings.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("unknown telemetry field `{0}`")]
+pub struct UnknownField(pub String);
+
+impl FieldSelector {
+    /// Select a typed field or flag by name; see [`FromStr`] for the syntax.
+    pub fn new(name: &str) -> Result<Self, UnknownField> {
+        name.parse()
+    }
+
+    /// Select an extended-map key.
+    pub fn extended(key: impl Into<String>) -> Self {
+        Self {
+            path: FieldPath::Extended(key.into()),
+            tolerance: 0.0,
+        }
+    }
+
+    /// Ignore float changes whose magnitude does not exceed `tolerance`.
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance.abs();
+        self
+    }
+
+    /// The field this selector reads.
+    pub fn path(&self) -> &FieldPath {
+        &self.path
+    }
+
+    /// Float change tolerance.
+    pub fn tolerance(&self) -> f32 {
+        self.tolerance
+    }
+
+    /// Read the selected field from `telemetry`; `None` when it is absent.
+    pub fn extract(&self, telemetry: &NormalizedTelemetry) -> Option<TelemetryValue> {
+        match &self.path {
+            FieldPath::Typed(name) => typed_field(telemetry, name),
+            FieldPath::Flag(name) => {
+                flag_field(&telemetry.flags, name).map(TelemetryValue::Boolean)
+            }
+            FieldPath::Extended(key) => telemetry.extended.get(key).cloned(),
+        }
+    }
+
+    /// Whether moving from `previous` to `next` is a change worth notifying.
+    pub fn differs(
+        &self,
+        previous: Option<&TelemetryValue>,
+        next: Option<&TelemetryValue>,
+    ) -> bool {
+        match (previous, next) {
+            (Some(TelemetryValue::Float(a)), Some(TelemetryValue::Float(b))) => {
+                if a.is_nan() || b.is_nan() {
+                    a.to_bits() != b.to_bits()
+                } else {
+                    (a - b).abs() > self.tolerance
+                }
+            }
+            (a, b) => a != b,
+        }
+    }
+
+    fn key(&self) -> (FieldPath, u32) {
+        (self.path.clone(), self.tolerance.to_bits())
+    }
+}
+
+impl FromStr for FieldSelector {
+    type Err = UnknownField;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let path = if let Some(key) = name.strip_prefix(EXTENDED_PREFIX) {
+            FieldPath::Extended(key.to_string())
+        } else if let Some(flag) = name.strip_prefix(FLAGS_PREFIX) {
+            FLAG_FIELDS
+                .iter()
+                .find(|candidate| **candidate == flag)
+                .map(|flag| FieldPath::Flag(flag))
+                .ok_or_else(|| UnknownField(name.to_string()))?
+        } else {
+            TYPED_FIELDS
+                .iter()
+                .find(|candidate| **candidate == name)
+                .map(|field| FieldPath::Typed(field))
+                .ok_or_else(|| UnknownField(name.to_string()))?
+        };
+        Ok(Self {
+            path,
+            tolerance: 0.0,
+        })
+    }
+}
+
+impl fmt::Display for FieldSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.path {
+            FieldPath::Typed(name) => f.write_str(name),
+            FieldPath::Flag(name) => write!(f, "{FLAGS_PREFIX}{name}"),
+            FieldPath::Extended(key) => write!(f, "{EXTENDED_PREFIX}{key}"),
+        }
+    }
+}
+
+fn typed_field(telemetry: &NormalizedTelemetry, name: &str) -> Option<TelemetryValue> {
+    let float = |value: f32| Some(TelemetryValue::Float(value));
+    let integer = |value: i32| Some(TelemetryValue::Integer(value));
+    let text = |value: &Option<String>| value.clone().map(TelemetryValue::String);
+    match name {
+        "speed_ms" => float(telemetry.speed_ms),
+        "steering_angle" => float(telemetry.steering_angle),
+        "throttle" => float(telemetry.throttle),
+        "brake" => float(telemetry.brake),
+        "clutch" => float(telemetry.clutch),
+        "rpm" => float(telemetry.rpm),
+        "max_rpm" => float(telemetry.max_rpm),
+        "gear" => integer(i32::from(telemetry.gear)),
+        "num_gears" => integer(i32::from(telemetry.num_gears)),
+        "lateral_g" => float(telemetry.lateral_g),
+        "longitudinal_g" => float(telemetry.longitudinal_g),
+        "vertical_g" => float(telemetry.vertical_g),
+        "slip_ratio" => float(telemetry.slip_ratio),
+        "slip_angle_fl" => float(telemetry.slip_angle_fl),
+        "slip_angle_fr" => float(telemetry.slip_angle_fr),
+        "slip_angle_rl" => float(telemetry.slip_angle_rl),
+        "slip_angle_rr" => float(telemetry.slip_angle_rr),
+        "ffb_scalar" => float(telemetry.ffb_scalar),
+        "ffb_torque_nm" => float(telemetry.ffb_torque_nm),
+        "position" => integer(i32::from(telemetry.position)),
+        "lap" => integer(i32::from(telemetry.lap)),
+        "current_lap_time_s" => float(telemetry.current_lap_time_s),
+        "best_lap_time_s" => float(telemetry.best_lap_time_s),
+        "last_lap_time_s" => float(telemetry.last_lap_time_s),
+        "delta_ahead_s" => float(telemetry.delta_ahead_s),
+        "delta_behind_s" => float(telemetry.delta_behind_s),
+        "fuel_percent" => float(telemetry.fuel_percent),
+        "engine_temp_c" => float(telemetry.engine_temp_c),
+        "car_id" => text(&telemetry.car_id),
+        "track_id" => text(&telemetry.track_id),
+        "session_id" => text(&telemetry.session_id),
+        _ => None,
+    }
+}
+
+fn flag_field(flags: &TelemetryFlags, name: &str) -> Option<bool> {
+    let value = match name {
+        "yellow_flag" => flags.yellow_flag,
+        "red_flag" => flags.red_flag,
+        "blue_flag" => flags.blue_flag,
+        "checkered_flag" => flags.checkered_flag,
+        "green_flag" => flags.green_flag,
+        "pit_limiter" => flags.pit_limiter,
+        "in_pits" => flags.in_pits,
+        "drs_available" => flags.drs_available,
+        "drs_active" => flags.drs_active,
+        "ers_available" => flags.ers_available,
+        "ers_active" => flags.ers_active,
+        "launch_control" => flags.launch_control,
+        "traction_control" => flags.traction_control,
+        "abs_active" => flags.abs_active,
+        "engine_limiter" => flags.engine_limiter,
+        "safety_car" => flags.safety_car,
+        "virtual_safety_car" => flags.virtual_safety_car,
+        _ => return None,
+    };
+    Some(value)
+}
+
+struct WatchedField {
+    selector: FieldSelector,
+    sender: watch::Sender<Option<TelemetryValue>>,
+}
+
+/// Shared per-game field extraction feeding [`watch`] receivers.
+#[derive(Default)]
+pub struct FieldWatchHub {
+    fields: Mutex<HashMap<(FieldPath, u32), WatchedField>>,
+}
+
+impl FieldWatchHub {
+    /// Create a hub with no watched fields.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Subscribe to `selector`, sharing extraction with existing watchers of it.
+    pub fn watch(&self, selector: FieldSelector) -> watch::Receiver<Option<TelemetryValue>> {
+        let mut fields = self.fields.lock().unwrap_or_else(PoisonError::into_inner);
+        fields
+            .entry(selector.key())
+            .or_insert_with(|| WatchedField {
+                selector,
+                sender: watch::channel(None).0,
+            })
+            .sender
+            .subscribe()
+    }
+
+    /// Number of distinct fields currently extracted per frame.
+    pub fn watched_count(&self) -> usize {
+        self.fields
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Extract every watched field from `telemetry` and notify changed ones.
+    pub fn publish(&self, telemetry: &NormalizedTelemetry) {
+        let mut fields = self.fields.lock().unwrap_or_else(PoisonError::into_inner);
+        fields.retain(|_, field| field.sender.receiver_count() > 0);
+        for field in fields.values() {
+            let next = field.selector.extract(telemetry);
+            field.sender.send_if_modified(|current| {
+                let changed = field.selector.differs(current.as_ref(), next.as_ref());
+                if changed {
+                    *current = next;
+                }
+                changed
+            });
+        }
+    }
+
+    /// Tell every watcher the source is gone.
+    pub fn disconnect(&self) {
+        let fields = self.fields.lock().unwrap_or_else(PoisonError::into_inner);
+        for field in fields.values() {
+            field
+                .sender
+                .send_if_modified(|current| current.take().is_some());
+        }
+    }
+}
+
+/// Combine several field receivers into one receiver of their values, in order.
+pub fn combine(
+    receivers: Vec<watch::Receiver<Option<TelemetryValue>>>,
+) -> watch::Receiver<Vec<Option<TelemetryValue>>> {
+    let initial: Vec<_> = receivers.iter().map(|rx| rx.borrow().clone()).collect();
+    let (combined, rx) = watch::channel(initial);
+    let combined = std::sync::Arc::new(combined);
+
+    for (index, mut field) in receivers.into_iter().enumerate() {
+        let combined = std::sync::Arc::clone(&combined);
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    changed = field.changed() => {
+                        if changed.is_err() {
+                            break;
+                        }
+                    }
+                    () = combined.closed() => break,
+                }
+                let value = field.borrow_and_update().clone();
+                combined.send_modify(|values| values[index] = value);
+            }
+        });
+    }
+
+    rx
+}
@@ -0,0 +1,265 @@
+//! Re-broadcasting the normalized stream to other tools over UDP.
+//!
+//! A [`TelemetryForwarder`] sends every frame it is given to each of its
+//! targets as one datagram, as compact JSON or as one record of the
+//! `racing_wheel_telemetry_contracts` binary codec, which
+//! `racing_wheel_schemas::telemetry::decode_frame` reads back. A frame that
+//! encodes to more than [`MAX_DATAGRAM_BYTES`] is counted and not sent.
+//! Targets can be rate limited independently, so a dashboard that only
+//! redraws at 60 Hz is not flooded by a 360 Hz source. Sends never wait: a
+//! full socket buffer drops the datagram, and an unreachable target, which a
+//! connected socket reports as an error on a later send, is counted and
+//! skipped rather than ending the loop.
+
+use std::io;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use anyhow::{Context, Result, bail};
+use racing_wheel_schemas::telemetry::{BinaryCodecError, encode_frame};
+use racing_wheel_telemetry_adapters::{TelemetryFrame, TelemetryReceiver};
+use racing_wheel_telemetry_rate_limiter::RateLimiter;
+use serde::{Deserialize, Serialize};
+use tokio::net::UdpSocket;
+use tokio::task::JoinHandle;
+use tracing::debug;
+
+use crate::multiplex::LabeledReceiver;
+use crate::sinks::WireEncoding;
+
+/// Largest UDP payload over IPv4.
+pub const MAX_DATAGRAM_BYTES: usize = 65_507;
+
+/// Why a frame could not be made into a datagram.
+#[derive(Debug, thiserror::Error)]
+pub enum ForwardError {
+    #[error("failed to encode telemetry frame as JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Binary(#[from] BinaryCodecError),
+    #[error("encoded frame is {len} bytes, over the {max} byte datagram limit")]
+    DatagramTooLarge { len: usize, max: usize },
+}
+
+/// `frame` encoded as one datagram payload in `encoding`.
+pub fn encode_datagram(
+    frame: &TelemetryFrame,
+    encoding: WireEncoding,
+) -> Result<Vec<u8>, ForwardError> {
+    let payload = match encoding {
+        WireEncoding::Json => serde_json::to_vec(frame)?,
+        WireEncoding::Binary => encode_frame(frame)?,
+    };
+    if payload.len() > MAX_DATAGRAM_BYTES {
+        return Err(ForwardError::DatagramTooLarge {
+            len: payload.len(),
+            max: MAX_DATAGRAM_BYTES,
+        });
+    }
+    Ok(payload)
+}
+
+/// Where and how to forward frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForwardTarget {
+    /// Destination of the datagrams.
+    pub addr: SocketAddr,
+    /// Payload encoding, one frame per datagram.
+    pub encoding: WireEncoding,
+    /// Most frames per second sent to this target; `None` sends every frame.
+    pub max_rate_hz: Option<u32>,
+}
+
+impl ForwardTarget {
+    /// Forward every frame to `addr` as compact JSON.
+    pub fn new(addr: SocketAddr) -> Self {
+        Self {
+            addr,
+            encoding: WireEncoding::Json,
+            max_rate_hz: None,
+        }
+    }
+
+    pub fn with_encoding(mut self, encoding: WireEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    pub fn with_max_rate_hz(mut self, max_rate_hz: u32) -> Self {
+        self.max_rate_hz = Some(max_rate_hz);
+        self
+    }
+}
+
+/// Per-target delivery counters.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForwardTargetStats {
+    /// The target's configuration.
+    pub target: ForwardTarget,
+    /// Datagrams handed to the socket.
+    pub sent: u64,
+    /// Payload bytes handed to the socket.
+    pub bytes_sent: u64,
+    /// Frames skipped by the target's rate limit.
+    pub rate_limited: u64,
+    /// Datagrams dropped because the socket buffer was full.
+    pub dropped: u64,
+    /// Encode failures and send errors, such as the target being unreachable.
+    pub errors: u64,
+    /// Frames not sent because they encoded to more than
+    /// [`MAX_DATAGRAM_BYTES`].
+    #[serde(default)]
+    pub oversized: u64,
+}
+
+struct TargetSlot {
+    socket: UdpSocket,
+    limiter: Option<RateLimiter>,
+    stats: ForwardTargetStats,
+}
+
+/// Forwards frames to a changing set of UDP targets. Clones share targets
+/// and counters.
+#[derive(Clone, Default)]
+pub struct TelemetryForwarder {
+    targets: Arc<Mutex<Vec<TargetSlot>>>,
+}
+
+impl TelemetryForwarder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<TargetSlot>> {
+        self.targets.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Start sending to `target` from a new socket connected to it. Fails if
+    /// the address is already a target.
+    pub async fn add_target(&self, target: ForwardTarget) -> Result<()> {
+        if self
+            .lock()
+            .iter()
+            .any(|slot| slot.stats.target.addr == target.addr)
+        {
+            bail!("{} is already a forwarding target", target.addr);
+        }
+        let local: SocketAddr = if target.addr.is_ipv4() {
+            (Ipv4Addr::UNSPECIFIED, 0).into()
+        } else {
+            (Ipv6Addr::UNSPECIFIED, 0).into()
+        };
+        let socket = UdpSocket::bind(local)
+            .await
+            .context("Failed to bind forwarding socket")?;
+        socket
+            .connect(target.addr)
+            .await
+            .with_context(|| format!("Failed to connect forwarding socket to {}", target.addr))?;
+
+        let mut targets = self.lock();
+        // Checked again: another caller may have added it while this one bound.
+        if targets
+            .iter()
+            .any(|slot| slot.stats.target.addr == target.addr)
+        {
+            bail!("{} is already a forwarding target", target.addr);
+        }
+        targets.push(TargetSlot {
+            socket,
+            limiter: target.max_rate_hz.map(RateLimiter::new),
+            stats: ForwardTargetStats {
+                target,
+                sent: 0,
+                bytes_sent: 0,
+                rate_limited: 0,
+                dropped: 0,
+                errors: 0,
+                oversized: 0,
+            },
+        });
+        Ok(())
+    }
+
+    /// Stop sending to `addr`. Returns whether it was a target.
+    pub fn remove_target(&self, addr: SocketAddr) -> bool {
+        let mut targets = self.lock();
+        let before = targets.len();
+        targets.retain(|slot| slot.stats.target.addr != addr);
+        targets.len() != before
+    }
+
+    /// Counters for every current target, in the order they were added.
+    pub fn stats(&self) -> Vec<ForwardTargetStats> {
+        self.lock().iter().map(|slot| slot.stats.clone()).collect()
+    }
+
+    /// Send `frame` to every target whose rate limit allows it, encoding it
+    /// at most once per encoding.
+    pub fn forward(&self, frame: &TelemetryFrame) {
+        let mut targets = self.lock();
+        let mut json = None;
+        let mut binary = None;
+        for slot in targets.iter_mut() {
+            if let Some(limiter) = &mut slot.limiter
+                && !limiter.should_process()
+            {
+                slot.stats.rate_limited += 1;
+                continue;
+            }
+            let encoding = slot.stats.target.encoding;
+            let cached = match encoding {
+                WireEncoding::Json => &mut json,
+                WireEncoding::Binary => &mut binary,
+            };
+            let payload = match cached.get_or_insert_with(|| encode_datagram(frame, encoding)) {
+                Ok(payload) => payload,
+                Err(ForwardError::DatagramTooLarge { len, .. }) => {
+                    debug!(target = %slot.stats.target.addr, len, "Telemetry frame too large to forward");
+                    slot.stats.oversized += 1;
+                    continue;
+                }
+                Err(error) => {
+                    debug!(error = %error, "Failed to encode telemetry frame for forwarding");
+                    slot.stats.errors += 1;
+                    continue;
+                }
+            };
+            match slot.socket.try_send(payload) {
+                Ok(sent) => {
+                    slot.stats.sent += 1;
+                    slot.stats.bytes_sent += sent as u64;
+                }
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => {
+                    slot.stats.dropped += 1;
+                }
+                Err(error) => {
+                    // Typically ECONNREFUSED from an earlier ICMP port unreachable.
+                    debug!(target = %slot.stats.target.addr, error = %error, "Telemetry forward failed");
+                    slot.stats.errors += 1;
+                }
+            }
+        }
+    }
+
+    /// Forward everything `frames` yields until it closes or the returned
+    /// task is aborted.
+    pub fn spawn(&self, mut frames: TelemetryReceiver) -> JoinHandle<()> {
+        let forwarder = self.clone();
+        tokio::spawn(async move {
+            while let Some(frame) = frames.recv().await {
+                forwarder.forward(&frame);
+            }
+        })
+    }
+
+    /// [`Self::spawn`] for the merged stream of `start_monitoring_all`.
+    pub fn spawn_labeled(&self, mut frames: LabeledReceiver) -> JoinHandle<()> {
+        let forwarder = self.clone();
+        tokio::spawn(async move {
+            while let Some((_, frame)) = frames.recv().await {
+                forwarder.forward(&frame);
+            }
+        })
+    }
+}
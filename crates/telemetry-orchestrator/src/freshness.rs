@@ -0,0 +1,360 @@
+//! Per-game "is this telemetry live?" indicator for overlays.
+//!
+//! Overlay authors want one answer instead of combining frame age, rate drift,
+//! breaker state and disconnects themselves. [`FreshnessTracker`] folds those
+//! inputs into a [`Freshness`] plus the [`FreshnessCause`] that produced it,
+//! and damps flapping with a minimum dwell time between transitions. The
+//! forwarding task in `start_monitoring` feeds one tracker per monitored
+//! game with frame arrivals and periodic ticks.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, watch};
+use tokio::time::Instant;
+
+/// Smallest interval between freshness re-evaluations while no frames arrive.
+const MIN_TICK: Duration = Duration::from_millis(10);
+/// Weight of the newest inter-frame interval in the smoothed interval.
+const INTERVAL_SMOOTHING: f64 = 0.25;
+
+/// Composite liveness of a game's telemetry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Freshness {
+    /// Frames arrive at roughly the adapter's expected rate.
+    Fresh,
+    /// The source is connected but frames are late or slow (menus, pause).
+    Stale,
+    /// The source disconnected or its breaker is open.
+    Dead,
+    /// Forwarding was paused on request; the source is still connected.
+    Paused,
+}
+
+/// Why a game's telemetry has its current [`Freshness`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FreshnessCause {
+    /// Frames are arriving on schedule.
+    Frames,
+    /// No frame for longer than [`FreshnessThresholds::stale_after`], or none yet.
+    Age,
+    /// Frames arrive, but the smoothed interval exceeds the fresh interval.
+    Drift,
+    /// A breaker guarding this game's source was reported open.
+    Breaker,
+    /// The telemetry source closed.
+    Disconnect,
+    /// Monitoring of the game was paused.
+    Paused,
+}
+
+/// A freshness value together with its cause.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FreshnessStatus {
+    /// Current composite state.
+    pub freshness: Freshness,
+    /// Input that determined it.
+    pub cause: FreshnessCause,
+}
+
+impl FreshnessStatus {
+    const fn new(freshness: Freshness, cause: FreshnessCause) -> Self {
+        Self { freshness, cause }
+    }
+}
+
+/// A freshness change for one game, published on the orchestrator's bus.
+///
+/// A change of cause alone (a paused game going silent) is also published, in
+/// which case `previous.freshness == current.freshness`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FreshnessEvent {
+    /// Normalized game id of the monitor that changed.
+    pub game_id: String,
+    /// Status before the change.
+    pub previous: FreshnessStatus,
+    /// Status after the change.
+    pub current: FreshnessStatus,
+}
+
+/// Freshness of one game, for status snapshots.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameFreshness {
+    /// Normalized game id.
+    pub game_id: String,
+    /// Its latest status.
+    pub status: FreshnessStatus,
+}
+
+/// Per-game thresholds used by [`FreshnessTracker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FreshnessThresholds {
+    /// Frames must arrive at least this often, on average and since the last
+    /// one, to count as fresh.
+    pub fresh_interval: Duration,
+    /// Age of the last frame after which the game is stale even if the
+    /// average rate was fine.
+    pub stale_after: Duration,
+    /// Minimum time between two transitions. A disconnect is never delayed.
+    pub min_dwell: Duration,
+}
+
+impl FreshnessThresholds {
+    /// Defaults for an adapter whose `expected_update_rate` is `interval`.
+    ///
+    /// Fresh means within twice the expected interval; stale after 25
+    /// intervals (at least 500 ms) of silence; transitions at least 10
+    /// intervals (at least 250 ms) apart.
+    pub fn from_expected_interval(interval: Duration) -> Self {
+        Self {
+            fresh_interval: interval.saturating_mul(2),
+            stale_after: interval.saturating_mul(25).max(Duration::from_millis(500)),
+            min_dwell: interval.saturating_mul(10).max(Duration::from_millis(250)),
+        }
+    }
+
+    /// How often a monitor re-evaluates freshness between frames.
+    pub fn tick_interval(&self) -> Duration {
+        self.fresh_interval.max(MIN_TICK)
+    }
+}
+
+/// State machine deriving [`FreshnessStatus`] from frame arrivals and source state.
+#[derive(Debug)]
+pub struct FreshnessTracker {
+    thresholds: FreshnessThresholds,
+    status: FreshnessStatus,
+    changed_at: Option<Instant>,
+    last_frame: Option<Instant>,
+    smoothed_interval: Option<Duration>,
+    breaker_open: bool,
+    paused: bool,
+    disconnected: bool,
+}
+
+impl FreshnessTracker {
+    /// A tracker for a connected source that has not delivered a frame yet.
+    pub fn new(thresholds: FreshnessThresholds) -> Self {
+        Self {
+            thresholds,
+            status: FreshnessStatus::new(Freshness::Stale, FreshnessCause::Age),
+            changed_at: None,
+            last_frame: None,
+            smoothed_interval: None,
+            breaker_open: false,
+            paused: false,
+            disconnected: false,
+        }
+    }
+
+    /// Current status.
+    pub fn status(&self) -> FreshnessStatus {
+        self.status
+    }
+
+    /// Record a frame arriving at `now`.
+    pub fn on_frame(&mut self, now: Instant) -> Option<(FreshnessStatus, FreshnessStatus)> {
+        if let Some(last) = self.last_frame {
+            let interval = now.saturating_duration_since(last);
+            // A gap long enough to go stale is silence, not a slow rate.
+            self.smoothed_interval = if interval > self.thresholds.stale_after {
+                None
+            } else {
+                Some(match self.smoothed_interval {
+                    Some(smoothed) => {
+                        smoothed.mul_f64(1.0 - INTERVAL_SMOOTHING)
+                            + interval.mul_f64(INTERVAL_SMOOTHING)
+                    }
+                    None => interval,
+                })
+            };
+        }
+        self.last_frame = Some(now);
+        self.poll(now)
+    }
+
+    /// Record the source closing.
+    pub fn on_disconnect(&mut self, now: Instant) -> Option<(FreshnessStatus, FreshnessStatus)> {
+        self.disconnected = true;
+        self.poll(now)
+    }
+
+    /// Record the breaker guarding this source opening or closing.
+    pub fn set_breaker_open(&mut self, open: bool) {
+        self.breaker_open = open;
+    }
+
+    /// Record monitoring of this source being paused or resumed.
+    ///
+    /// Silence while paused says nothing about the source's rate, so resuming
+    /// starts over as if no frame had arrived yet.
+    pub fn set_paused(&mut self, paused: bool) {
+        if self.paused && !paused {
+            self.last_frame = None;
+            self.smoothed_interval = None;
+        }
+        self.paused = paused;
+    }
+
+    /// Re-evaluate at `now`, returning `(previous, current)` if the status changed.
+    pub fn poll(&mut self, now: Instant) -> Option<(FreshnessStatus, FreshnessStatus)> {
+        let next = self.evaluate(now)?;
+        if next == self.status {
+            return None;
+        }
+        let dwelling = self
+            .changed_at
+            .is_some_and(|at| now.saturating_duration_since(at) < self.thresholds.min_dwell);
+        // Disconnects and explicit pause/resume requests are never delayed.
+        let immediate = matches!(
+            next.cause,
+            FreshnessCause::Disconnect | FreshnessCause::Paused
+        ) || self.status.cause == FreshnessCause::Paused;
+        if dwelling && !immediate {
+            return None;
+        }
+        let previous = std::mem::replace(&mut self.status, next);
+        self.changed_at = Some(now);
+        Some((previous, next))
+    }
+
+    /// Status the inputs call for at `now`, or `None` to keep the current one.
+    fn evaluate(&self, now: Instant) -> Option<FreshnessStatus> {
+        if self.disconnected {
+            return Some(FreshnessStatus::new(
+                Freshness::Dead,
+                FreshnessCause::Disconnect,
+            ));
+        }
+        if self.breaker_open {
+            return Some(FreshnessStatus::new(
+                Freshness::Dead,
+                FreshnessCause::Breaker,
+            ));
+        }
+        if self.paused {
+            return Some(FreshnessStatus::new(
+                Freshness::Paused,
+                FreshnessCause::Paused,
+            ));
+        }
+        let Some(last) = self.last_frame else {
+            return Some(FreshnessStatus::new(Freshness::Stale, FreshnessCause::Age));
+        };
+        let age = now.saturating_duration_since(last);
+        if age > self.thresholds.stale_after {
+            return Some(FreshnessStatus::new(Freshness::Stale, FreshnessCause::Age));
+        }
+        if self
+            .smoothed_interval
+            .is_some_and(|interval| interval > self.thresholds.fresh_interval)
+        {
+            return Some(FreshnessStatus::new(
+                Freshness::Stale,
+                FreshnessCause::Drift,
+            ));
+        }
+        // A single late frame between the fresh interval and `stale_after`
+        // leaves the status as it was.
+        (age <= self.thresholds.fresh_interval).then_some(FreshnessStatus::new(
+            Freshness::Fresh,
+            FreshnessCause::Frames,
+        ))
+    }
+}
+
+/// Per-game state shared between the service and its forwarding task.
+#[derive(Debug)]
+pub(crate) struct FreshnessChannel {
+    status: watch::Sender<Option<FreshnessStatus>>,
+    breaker_open: AtomicBool,
+}
+
+impl Default for FreshnessChannel {
+    fn default() -> Self {
+        Self {
+            status: watch::channel(None).0,
+            breaker_open: AtomicBool::new(false),
+        }
+    }
+}
+
+impl FreshnessChannel {
+    pub(crate) fn current(&self) -> Option<FreshnessStatus> {
+        *self.status.borrow()
+    }
+
+    pub(crate) fn subscribe(&self) -> watch::Receiver<Option<FreshnessStatus>> {
+        self.status.subscribe()
+    }
+
+    pub(crate) fn set_breaker_open(&self, open: bool) {
+        self.breaker_open.store(open, Ordering::Relaxed);
+    }
+}
+
+/// Drives one game's tracker from the forwarding task and publishes changes.
+pub(crate) struct FreshnessMonitor {
+    game_id: String,
+    tracker: FreshnessTracker,
+    channel: std::sync::Arc<FreshnessChannel>,
+    events: broadcast::Sender<FreshnessEvent>,
+}
+
+impl FreshnessMonitor {
+    pub(crate) fn start(
+        game_id: String,
+        thresholds: FreshnessThresholds,
+        channel: std::sync::Arc<FreshnessChannel>,
+        events: broadcast::Sender<FreshnessEvent>,
+    ) -> Self {
+        let tracker = FreshnessTracker::new(thresholds);
+        channel.status.send_replace(Some(tracker.status()));
+        Self {
+            game_id,
+            tracker,
+            channel,
+            events,
+        }
+    }
+
+    pub(crate) fn frame(&mut self) {
+        self.sync_breaker();
+        self.tracker.set_paused(false);
+        let change = self.tracker.on_frame(Instant::now());
+        self.publish(change);
+    }
+
+    pub(crate) fn tick(&mut self, paused: bool) {
+        self.sync_breaker();
+        self.tracker.set_paused(paused);
+        let change = self.tracker.poll(Instant::now());
+        self.publish(change);
+    }
+
+    pub(crate) fn disconnect(&mut self) {
+        let change = self.tracker.on_disconnect(Instant::now());
+        self.publish(change);
+    }
+
+    fn sync_breaker(&mut self) {
+        self.tracker
+            .set_breaker_open(self.channel.breaker_open.load(Ordering::Relaxed));
+    }
+
+    fn publish(&self, change: Option<(FreshnessStatus, FreshnessStatus)>) {
+        let Some((previous, current)) = change else {
+            return;
+        };
+        self.channel.status.send_replace(Some(current));
+        // No subscribers is not an error.
+        let _ = self.events.send(FreshnessEvent {
+            game_id: self.game_id.clone(),
+            previous,
+            current,
+        });
+    }
+}
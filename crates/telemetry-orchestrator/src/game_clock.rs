@@ -0,0 +1,150 @@
+//! Frame-to-frame time steps taken from the game's own clock.
+//!
+//! Arrival timestamps carry the jitter of the network stack and the polling
+//! loop: two UDP packets sent 16 ms apart can land 2 ms apart, and a paused
+//! game keeps producing arrival time while the car stands still. Adapters that
+//! can read a game-side clock publish it as `game_time_s` (and a frame counter
+//! as `game_tick`); [`GameClock`] turns consecutive frames into a time step
+//! that prefers that clock and falls back to arrival time when it is missing
+//! or jumps backwards.
+
+use std::time::Duration;
+
+use racing_wheel_telemetry_adapters::TelemetryFrame;
+
+/// Game and arrival steps further apart than this count as diverged.
+pub const DEFAULT_DIVERGENCE_THRESHOLD: Duration = Duration::from_millis(250);
+
+/// Which clock a [`ClockStep`] was measured on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClockSource {
+    /// Difference of consecutive `game_time_s` values.
+    Game,
+    /// Difference of consecutive arrival timestamps.
+    Arrival,
+}
+
+/// Time elapsed between two consecutive frames.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClockStep {
+    /// Step to integrate over.
+    pub dt: Duration,
+    /// Clock `dt` was measured on.
+    pub source: ClockSource,
+    /// `game step - arrival step` in seconds, when both clocks were available.
+    pub divergence_s: Option<f64>,
+}
+
+/// Counters describing how a [`GameClock`] has been stepping.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ClockDiagnostics {
+    /// Steps measured on the game clock.
+    pub game_steps: u64,
+    /// Steps that fell back to arrival time.
+    pub arrival_steps: u64,
+    /// Game-clock steps whose divergence exceeded the threshold.
+    pub diverged_steps: u64,
+    /// Largest absolute divergence seen, in seconds.
+    pub max_divergence_s: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    arrival_ns: u64,
+    game_time_s: Option<f64>,
+    game_tick: Option<u64>,
+}
+
+/// Time-step source preferring the game clock over arrival time.
+///
+/// A repeated `game_tick` is a duplicate frame and yields a zero step, and a
+/// game clock that goes backwards (a restart or session change) yields an
+/// arrival-time step for that frame and resumes on the new clock afterwards.
+#[derive(Debug, Clone)]
+pub struct GameClock {
+    threshold: Duration,
+    last: Option<Sample>,
+    diagnostics: ClockDiagnostics,
+}
+
+impl Default for GameClock {
+    fn default() -> Self {
+        Self::new(DEFAULT_DIVERGENCE_THRESHOLD)
+    }
+}
+
+impl GameClock {
+    /// Create a clock flagging steps that diverge by more than `threshold`.
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            last: None,
+            diagnostics: ClockDiagnostics::default(),
+        }
+    }
+
+    /// Step counters since creation or the last [`reset`](Self::reset).
+    pub fn diagnostics(&self) -> ClockDiagnostics {
+        self.diagnostics
+    }
+
+    /// Forget the previous frame and the counters.
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.diagnostics = ClockDiagnostics::default();
+    }
+
+    /// Step from the previous frame to `frame`; `None` for the first frame.
+    pub fn step(&mut self, frame: &TelemetryFrame) -> Option<ClockStep> {
+        let current = Sample {
+            arrival_ns: frame.timestamp_ns,
+            game_time_s: frame.data.game_time_s.filter(|t| t.is_finite()),
+            game_tick: frame.data.game_tick,
+        };
+        let last = self.last.replace(current)?;
+        let arrival = Duration::from_nanos(current.arrival_ns.saturating_sub(last.arrival_ns));
+
+        if let (Some(previous), Some(tick)) = (last.game_tick, current.game_tick)
+            && previous == tick
+        {
+            self.diagnostics.game_steps += 1;
+            return Some(ClockStep {
+                dt: Duration::ZERO,
+                source: ClockSource::Game,
+                divergence_s: None,
+            });
+        }
+
+        let game = match (last.game_time_s, current.game_time_s) {
+            (Some(previous), Some(now)) if now >= previous => Some(now - previous),
+            _ => None,
+        };
+        let Some(game_s) = game else {
+            self.diagnostics.arrival_steps += 1;
+            return Some(ClockStep {
+                dt: arrival,
+                source: ClockSource::Arrival,
+                divergence_s: None,
+            });
+        };
+
+        let divergence_s = game_s - arrival.as_secs_f64();
+        let diagnostics = &mut self.diagnostics;
+        diagnostics.game_steps += 1;
+        diagnostics.max_divergence_s = diagnostics.max_divergence_s.max(divergence_s.abs());
+        if divergence_s.abs() > self.threshold.as_secs_f64() {
+            diagnostics.diverged_steps += 1;
+        }
+        Some(ClockStep {
+            dt: Duration::from_secs_f64(game_s),
+            source: ClockSource::Game,
+            divergence_s: Some(divergence_s),
+        })
+    }
+
+    /// Whether `step` diverged by more than this clock's threshold.
+    pub fn is_diverged(&self, step: &ClockStep) -> bool {
+        step.divergence_s
+            .is_some_and(|divergence| divergence.abs() > self.threshold.as_secs_f64())
+    }
+}
@@ -0,0 +1,452 @@
+//! Per-adapter health counters and connection state.
+//!
+//! When telemetry stops, the question is usually which of "never connected",
+//! "connected but gone quiet" and "failed to start" applies. Each monitored
+//! game's forwarding task drives a [`DisconnectionTracker`] with frame
+//! arrivals and its periodic tick, counts frames, and remembers the last start
+//! failure; `TelemetryService::health` reads those alongside the game's rate
+//! limiter stats, and connection state changes of every game are fanned out
+//! to `subscribe_health_events` receivers, together with the transport
+//! switches of adapters that fail over between transports. Reconnect attempts
+//! driven by [`crate::reconnect`] go through the same tracker.
+
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use openracing_telemetry_streams::StreamQualitySummary;
+use racing_wheel_telemetry_adapters::{TransportSwitched, telemetry_now_ns};
+use racing_wheel_telemetry_core::{
+    ConnectionState, ConnectionStateEvent, ConnectionStateReceiver, DisconnectionConfig,
+    DisconnectionTracker,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{Notify, broadcast, mpsc};
+
+/// Capacity of each `subscribe_health_events` receiver.
+pub const HEALTH_EVENT_CAPACITY: usize = 64;
+
+/// Health of one registered adapter, as returned by `TelemetryService::health`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdapterHealth {
+    /// `Disconnected` until monitoring first starts.
+    pub connection_state: ConnectionState,
+    /// `telemetry_now_ns` when the latest frame arrived.
+    pub last_frame_timestamp_ns: Option<u64>,
+    /// `telemetry_now_ns` of the latest connection state change, comparable
+    /// with `last_frame_timestamp_ns`.
+    pub last_state_change_ns: Option<u64>,
+    /// Frames received from the adapter, including dropped ones.
+    pub frames_received: u64,
+    /// Frames dropped for exceeding the game's rate limit.
+    pub frames_dropped_rate_limit: u64,
+    /// Last error starting or restarting the adapter.
+    pub last_error: Option<String>,
+    /// Reconnect attempts since the last frame; zero while connected.
+    pub reconnect_attempts: u32,
+    /// Interval between frames the adapter expects, in milliseconds.
+    pub expected_update_rate_ms: f64,
+    /// Frame rate measured while connected; `None` until two frames arrive.
+    pub observed_rate_hz: Option<f64>,
+    /// 99th percentile of recent frames' time from the adapter to the
+    /// consumer's channel; `None` until a frame is forwarded.
+    #[serde(default)]
+    pub latency_p99_ms: Option<f64>,
+    /// Frames that carried NaN or infinite readings and were forwarded
+    /// with those readings cleared.
+    #[serde(default)]
+    pub frames_sanitized: u64,
+    /// Readings that failed the game's `TelemetryValidator`.
+    #[serde(default)]
+    pub validation_issues: u64,
+    /// Frames the validator's reject policy dropped.
+    #[serde(default)]
+    pub frames_rejected_invalid: u64,
+    /// Frames dropped as unchanged repeats of the one before, for adapters
+    /// that prefer deduplication.
+    #[serde(default)]
+    pub frames_deduplicated: u64,
+    /// Loss, reordering and jitter seen in frame sequence numbers; `None`
+    /// until monitoring first starts.
+    #[serde(default)]
+    pub stream_quality: Option<StreamQualitySummary>,
+}
+
+/// One event delivered to `subscribe_health_events` receivers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum HealthEvent {
+    /// A game's connection state changed.
+    ConnectionState(ConnectionStateEvent),
+    /// A game's adapter moved from one transport to another.
+    TransportSwitched(TransportSwitched),
+}
+
+impl HealthEvent {
+    pub fn game_id(&self) -> &str {
+        match self {
+            Self::ConnectionState(event) => &event.game_id,
+            Self::TransportSwitched(event) => &event.game_id,
+        }
+    }
+}
+
+pub type HealthEventReceiver = mpsc::Receiver<HealthEvent>;
+
+/// Receivers of every game's health events.
+#[derive(Debug, Default)]
+pub(crate) struct HealthSubscribers(Mutex<Vec<mpsc::Sender<HealthEvent>>>);
+
+impl HealthSubscribers {
+    pub(crate) fn subscribe(&self) -> HealthEventReceiver {
+        let (tx, rx) = mpsc::channel(HEALTH_EVENT_CAPACITY);
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(tx);
+        rx
+    }
+
+    /// Deliver `event` to every receiver, dropping closed ones. A receiver
+    /// whose buffer is full misses the event.
+    fn publish(&self, event: &HealthEvent) {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|tx| !matches!(tx.try_send(event.clone()), Err(TrySendError::Closed(_))));
+    }
+}
+
+/// Per-game health shared between the service and its forwarding task.
+#[derive(Debug)]
+pub(crate) struct HealthChannel {
+    state: Mutex<ConnectionState>,
+    frames_received: AtomicU64,
+    frames_dropped_rate_limit: AtomicU64,
+    frames_sanitized: AtomicU64,
+    validation_issues: AtomicU64,
+    frames_rejected_invalid: AtomicU64,
+    frames_deduplicated: AtomicU64,
+    /// Zero before the first frame.
+    last_frame_ns: AtomicU64,
+    /// `timestamp_mono_ns` of the latest event; zero before the first one.
+    last_state_change_ns: AtomicU64,
+    last_error: Mutex<Option<String>>,
+    reconnect_attempts: AtomicU32,
+    reconnects_total: AtomicU64,
+    /// `f64` bits of the observed frame rate; zero before it is known.
+    observed_rate_hz: AtomicU64,
+    stop_requested: AtomicBool,
+    stop: Notify,
+}
+
+impl Default for HealthChannel {
+    fn default() -> Self {
+        Self {
+            state: Mutex::new(ConnectionState::Disconnected),
+            frames_received: AtomicU64::new(0),
+            frames_dropped_rate_limit: AtomicU64::new(0),
+            frames_sanitized: AtomicU64::new(0),
+            validation_issues: AtomicU64::new(0),
+            frames_rejected_invalid: AtomicU64::new(0),
+            frames_deduplicated: AtomicU64::new(0),
+            last_frame_ns: AtomicU64::new(0),
+            last_state_change_ns: AtomicU64::new(0),
+            last_error: Mutex::new(None),
+            reconnect_attempts: AtomicU32::new(0),
+            reconnects_total: AtomicU64::new(0),
+            observed_rate_hz: AtomicU64::new(0),
+            stop_requested: AtomicBool::new(false),
+            stop: Notify::new(),
+        }
+    }
+}
+
+impl HealthChannel {
+    pub(crate) fn state(&self) -> ConnectionState {
+        *self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(crate) fn frames_received(&self) -> u64 {
+        self.frames_received.load(Ordering::Relaxed)
+    }
+
+    /// Rate-limited frames over every session, unlike the rate limiter's own
+    /// count, which restarts with each `start_monitoring`.
+    pub(crate) fn frames_dropped_rate_limit(&self) -> u64 {
+        self.frames_dropped_rate_limit.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn frames_sanitized(&self) -> u64 {
+        self.frames_sanitized.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn validation_issues(&self) -> u64 {
+        self.validation_issues.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn frames_rejected_invalid(&self) -> u64 {
+        self.frames_rejected_invalid.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn frames_deduplicated(&self) -> u64 {
+        self.frames_deduplicated.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn last_frame_ns(&self) -> Option<u64> {
+        Some(self.last_frame_ns.load(Ordering::Relaxed)).filter(|&ns| ns != 0)
+    }
+
+    pub(crate) fn last_state_change_ns(&self) -> Option<u64> {
+        Some(self.last_state_change_ns.load(Ordering::Relaxed)).filter(|&ns| ns != 0)
+    }
+
+    pub(crate) fn last_error(&self) -> Option<String> {
+        self.last_error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub(crate) fn reconnect_attempts(&self) -> u32 {
+        self.reconnect_attempts.load(Ordering::Relaxed)
+    }
+
+    /// Reconnect attempts ever made, never reset.
+    pub(crate) fn reconnects_total(&self) -> u64 {
+        self.reconnects_total.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn observed_rate_hz(&self) -> Option<f64> {
+        Some(f64::from_bits(
+            self.observed_rate_hz.load(Ordering::Relaxed),
+        ))
+        .filter(|&hz| hz > 0.0)
+    }
+
+    /// Monitoring is being stopped on request, so a stream ending now must
+    /// not be reconnected.
+    pub(crate) fn request_stop(&self) {
+        self.stop_requested.store(true, Ordering::Release);
+        self.stop.notify_waiters();
+    }
+}
+
+/// Drives one game's [`DisconnectionTracker`] and publishes its changes.
+pub(crate) struct HealthMonitor {
+    config: DisconnectionConfig,
+    tracker: DisconnectionTracker,
+    events: ConnectionStateReceiver,
+    channel: Arc<HealthChannel>,
+    subscribers: Arc<HealthSubscribers>,
+}
+
+impl HealthMonitor {
+    /// Begin tracking a game whose adapter, expecting a frame every
+    /// `expected_interval`, is about to start.
+    pub(crate) fn start(
+        game_id: String,
+        config: DisconnectionConfig,
+        expected_interval: Duration,
+        channel: Arc<HealthChannel>,
+        subscribers: Arc<HealthSubscribers>,
+    ) -> Self {
+        channel.stop_requested.store(false, Ordering::Release);
+        let mut tracker = DisconnectionTracker::new(game_id, config.clone());
+        tracker.set_expected_interval(expected_interval);
+        let events = tracker.subscribe();
+        let mut monitor = Self {
+            config,
+            tracker,
+            events,
+            channel,
+            subscribers,
+        };
+        // Resync in case the previous session ended in another state.
+        let previous = monitor.channel.state();
+        monitor.tracker.set_state(previous, None);
+        monitor.drain();
+        monitor.tracker.mark_connecting();
+        monitor.publish();
+        monitor
+    }
+
+    /// The adapter failed to start.
+    pub(crate) fn fail(mut self, error: &anyhow::Error) {
+        self.record_error(error);
+    }
+
+    /// Starting or restarting the adapter failed.
+    pub(crate) fn record_error(&mut self, error: &anyhow::Error) {
+        let error = error.to_string();
+        *self
+            .channel
+            .last_error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(error.clone());
+        self.tracker.mark_error(error);
+        self.publish();
+    }
+
+    pub(crate) fn frame(&mut self) {
+        self.channel.frames_received.fetch_add(1, Ordering::Relaxed);
+        self.channel
+            .last_frame_ns
+            .store(telemetry_now_ns().max(1), Ordering::Relaxed);
+        self.tracker.record_data_received();
+        if let Some(hz) = self.tracker.observed_rate_hz() {
+            self.channel
+                .observed_rate_hz
+                .store(hz.to_bits(), Ordering::Relaxed);
+        }
+        self.publish();
+    }
+
+    /// The adapter moved to another transport.
+    pub(crate) fn transport_switched(&self, event: TransportSwitched) {
+        self.subscribers
+            .publish(&HealthEvent::TransportSwitched(event));
+    }
+
+    /// A frame was dropped for exceeding the game's rate limit.
+    pub(crate) fn rate_limited(&self) {
+        self.channel
+            .frames_dropped_rate_limit
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A frame's non-finite readings were cleared before forwarding.
+    pub(crate) fn sanitized(&self) {
+        self.channel
+            .frames_sanitized
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A frame was dropped as an unchanged repeat of the one before.
+    pub(crate) fn deduplicated(&self) {
+        self.channel
+            .frames_deduplicated
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A frame failed validation with `issues` bad readings and, if
+    /// `rejected`, was dropped.
+    pub(crate) fn invalid(&self, issues: usize, rejected: bool) {
+        self.channel
+            .validation_issues
+            .fetch_add(issues as u64, Ordering::Relaxed);
+        if rejected {
+            self.channel
+                .frames_rejected_invalid
+                .fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Check for a timeout; returns whether the game just went quiet and
+    /// should be reconnected. Paused games are never reconnected.
+    pub(crate) fn tick(&mut self, paused: bool) -> bool {
+        let was_connected = self.tracker.state().is_connected();
+        let timed_out = self.tracker.check_disconnection() == ConnectionState::Disconnected;
+        self.publish();
+        was_connected && timed_out && self.config.auto_reconnect && !paused
+    }
+
+    /// The adapter's stream ended.
+    pub(crate) fn source_lost(&mut self) {
+        if !self.tracker.state().is_disconnected() {
+            self.tracker.set_state(
+                ConnectionState::Disconnected,
+                Some("Telemetry source closed".to_string()),
+            );
+            self.publish();
+        }
+    }
+
+    pub(crate) fn config(&self) -> &DisconnectionConfig {
+        &self.config
+    }
+
+    /// Whether another reconnect attempt is allowed: the config enables them,
+    /// the attempt limit is not reached and no stop was requested.
+    pub(crate) fn should_reconnect(&self) -> bool {
+        !self.channel.stop_requested.load(Ordering::Acquire) && self.tracker.should_reconnect()
+    }
+
+    /// Start a reconnect attempt, returning its 1-based number.
+    pub(crate) fn mark_reconnecting(&mut self) -> u32 {
+        self.tracker.mark_reconnecting();
+        self.channel
+            .reconnects_total
+            .fetch_add(1, Ordering::Relaxed);
+        self.publish();
+        self.tracker.reconnect_attempts()
+    }
+
+    /// Wait for `delay`, returning `false` early if a stop is requested.
+    pub(crate) async fn wait_unless_stopped(&self, delay: Duration) -> bool {
+        let stopped = self.channel.stop.notified();
+        tokio::pin!(stopped);
+        stopped.as_mut().enable();
+        if self.channel.stop_requested.load(Ordering::Acquire) {
+            return false;
+        }
+        tokio::select! {
+            _ = stopped => false,
+            _ = tokio::time::sleep(delay) => true,
+        }
+    }
+
+    /// The forwarding task ended; a game already disconnected or in error
+    /// keeps that state and reason.
+    pub(crate) fn stop(mut self) {
+        if !self.tracker.state().is_disconnected() {
+            self.tracker.set_state(
+                ConnectionState::Disconnected,
+                Some("Monitoring stopped".to_string()),
+            );
+        }
+        self.publish();
+    }
+
+    /// Discard pending tracker events without publishing them.
+    fn drain(&mut self) {
+        while self.events.try_recv().is_ok() {}
+    }
+
+    fn publish(&mut self) {
+        while let Ok(event) = self.events.try_recv() {
+            *self
+                .channel
+                .state
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner) = event.new_state;
+            self.channel
+                .last_state_change_ns
+                .store(event.timestamp_mono_ns.max(1), Ordering::Relaxed);
+            self.subscribers
+                .publish(&HealthEvent::ConnectionState(event));
+        }
+        self.channel
+            .reconnect_attempts
+            .store(self.tracker.reconnect_attempts(), Ordering::Relaxed);
+    }
+}
+
+/// The next switch from an adapter's
+/// [`transport_switches`](racing_wheel_telemetry_adapters::TelemetryAdapter::transport_switches),
+/// skipping any missed while the receiver lagged. Never resolves for an
+/// adapter without switches, or once its sender is gone.
+pub(crate) async fn next_transport_switch(
+    switches: &mut Option<broadcast::Receiver<TransportSwitched>>,
+) -> TransportSwitched {
+    while let Some(receiver) = switches.as_mut() {
+        match receiver.recv().await {
+            Ok(event) => return event,
+            Err(RecvError::Lagged(_)) => {}
+            Err(RecvError::Closed) => *switches = None,
+        }
+    }
+    std::future::pending().await
+}
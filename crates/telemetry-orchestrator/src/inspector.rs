@@ -0,0 +1,257 @@
+//! First-packet inspection of unrecognized UDP traffic.
+//!
+//! "I configured the game and nothing shows up" usually means datagrams do
+//! arrive, just not in the format the selected adapter expects: the wrong
+//! output mode, another game on the port, or a different format year. The
+//! inspector listens raw on a port for a short window, keeps a bounded set of
+//! distinct datagrams and asks every registered adapter to recognize them,
+//! producing a report that names the likeliest format and who should read it.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use racing_wheel_telemetry_adapters::{DECODE_CONFIDENCE, TelemetryAdapter};
+use serde::{Deserialize, Serialize};
+use tokio::net::UdpSocket;
+use tokio::time::{Instant, timeout_at};
+
+/// Distinct datagrams kept for classification; repeats are only counted.
+pub const INSPECTION_PACKET_LIMIT: usize = 32;
+/// Source addresses kept in a report.
+const SOURCE_LIMIT: usize = 16;
+/// Candidates kept in a report.
+const CANDIDATE_LIMIT: usize = 8;
+/// Larger than any telemetry datagram we decode.
+const MAX_DATAGRAM_BYTES: usize = 4096;
+
+/// Error returned by `TelemetryService::inspect_port`.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum InspectError {
+    /// A unicast monitor holds the port exclusively.
+    #[error("UDP port {port} is held by `{game_id}`; stop monitoring it to inspect the port")]
+    PortBusy { port: u16, game_id: String },
+    /// The inspection socket could not be bound.
+    #[error("cannot listen on UDP port {port}: {reason}")]
+    Bind { port: u16, reason: String },
+    /// Receiving failed mid-window.
+    #[error("receiving on UDP port {port} failed: {reason}")]
+    Receive { port: u16, reason: String },
+}
+
+/// How often one datagram length was seen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PacketSizeCount {
+    pub bytes: usize,
+    pub count: u64,
+}
+
+/// One adapter's reading of the captured traffic.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InspectionCandidate {
+    pub game_id: String,
+    /// Format the adapter recognized most often, e.g. "F1 2024 format".
+    pub format: String,
+    /// Mean recognition confidence over all distinct datagrams, `0.0..=1.0`.
+    pub confidence: f32,
+    /// Distinct datagrams the adapter recognized.
+    pub matched_packets: usize,
+}
+
+/// What arrived on a UDP port during an inspection window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InspectionReport {
+    pub port: u16,
+    pub listened_ms: u64,
+    pub packets_received: u64,
+    pub packets_per_second: f32,
+    /// Distinct datagrams classified, at most [`INSPECTION_PACKET_LIMIT`].
+    pub distinct_packets: usize,
+    /// Observed lengths, most frequent first.
+    pub packet_sizes: Vec<PacketSizeCount>,
+    pub sources: Vec<SocketAddr>,
+    /// Registered adapters configured for this port, sorted by game id.
+    pub selected: Vec<String>,
+    /// Adapters that recognized any datagram, most confident first.
+    pub candidates: Vec<InspectionCandidate>,
+    /// One-line summary for the user.
+    pub explanation: String,
+}
+
+impl InspectionReport {
+    /// Most confident candidate, if any adapter recognized the traffic.
+    pub fn top(&self) -> Option<&InspectionCandidate> {
+        self.candidates.first()
+    }
+}
+
+/// Raw traffic captured during one window.
+#[derive(Debug, Default)]
+pub(crate) struct Capture {
+    packets: Vec<Vec<u8>>,
+    received: u64,
+    sizes: BTreeMap<usize, u64>,
+    sources: BTreeSet<SocketAddr>,
+    listened: Duration,
+}
+
+/// Receive on `socket` until `window` elapses.
+pub(crate) async fn capture(
+    socket: &UdpSocket,
+    port: u16,
+    window: Duration,
+) -> Result<Capture, InspectError> {
+    let started = Instant::now();
+    let deadline = started + window;
+    let mut capture = Capture::default();
+    let mut buf = vec![0u8; MAX_DATAGRAM_BYTES];
+    while let Ok(received) = timeout_at(deadline, socket.recv_from(&mut buf)).await {
+        let (len, source) = received.map_err(|err| InspectError::Receive {
+            port,
+            reason: err.to_string(),
+        })?;
+        let payload = &buf[..len];
+        capture.received += 1;
+        *capture.sizes.entry(len).or_default() += 1;
+        if capture.sources.len() < SOURCE_LIMIT {
+            capture.sources.insert(source);
+        }
+        if capture.packets.len() < INSPECTION_PACKET_LIMIT
+            && !capture.packets.iter().any(|kept| kept == payload)
+        {
+            capture.packets.push(payload.to_vec());
+        }
+    }
+    capture.listened = started.elapsed();
+    Ok(capture)
+}
+
+/// Run every adapter's recognizer over `capture` and explain the result.
+pub(crate) fn classify<'a>(
+    port: u16,
+    selected: Vec<String>,
+    capture: Capture,
+    adapters: impl IntoIterator<Item = &'a dyn TelemetryAdapter>,
+) -> InspectionReport {
+    let mut candidates: Vec<_> = adapters
+        .into_iter()
+        .filter_map(|adapter| candidate(adapter, &capture.packets))
+        .collect();
+    candidates.sort_by(|a, b| {
+        b.confidence
+            .total_cmp(&a.confidence)
+            .then_with(|| {
+                selected
+                    .contains(&b.game_id)
+                    .cmp(&selected.contains(&a.game_id))
+            })
+            .then_with(|| a.game_id.cmp(&b.game_id))
+    });
+    // Once any adapter recognizes the traffic by its format, the many that
+    // merely decode it add nothing but noise.
+    if candidates
+        .first()
+        .is_some_and(|top| top.confidence > DECODE_CONFIDENCE)
+    {
+        candidates.retain(|candidate| candidate.confidence > DECODE_CONFIDENCE);
+    }
+    candidates.truncate(CANDIDATE_LIMIT);
+
+    let mut packet_sizes: Vec<_> = capture
+        .sizes
+        .iter()
+        .map(|(&bytes, &count)| PacketSizeCount { bytes, count })
+        .collect();
+    packet_sizes.sort_by(|a, b| b.count.cmp(&a.count).then(a.bytes.cmp(&b.bytes)));
+
+    let seconds = capture.listened.as_secs_f32();
+    let mut report = InspectionReport {
+        port,
+        listened_ms: u64::try_from(capture.listened.as_millis()).unwrap_or(u64::MAX),
+        packets_received: capture.received,
+        packets_per_second: if seconds > 0.0 {
+            capture.received as f32 / seconds
+        } else {
+            0.0
+        },
+        distinct_packets: capture.packets.len(),
+        packet_sizes,
+        sources: capture.sources.into_iter().collect(),
+        selected,
+        candidates,
+        explanation: String::new(),
+    };
+    report.explanation = explain(&report);
+    report
+}
+
+fn candidate(adapter: &dyn TelemetryAdapter, packets: &[Vec<u8>]) -> Option<InspectionCandidate> {
+    let mut formats: Vec<(String, usize)> = Vec::new();
+    let mut total = 0.0f32;
+    let mut matched = 0;
+    for packet in packets {
+        let Some(found) = adapter.recognize_packet(packet) else {
+            continue;
+        };
+        total += found.confidence;
+        matched += 1;
+        match formats
+            .iter_mut()
+            .find(|(format, _)| *format == found.format)
+        {
+            Some((_, count)) => *count += 1,
+            None => formats.push((found.format, 1)),
+        }
+    }
+    // `max_by_key` keeps the last maximum; reverse so the first seen wins ties.
+    let (format, _) = formats.into_iter().rev().max_by_key(|(_, count)| *count)?;
+    Some(InspectionCandidate {
+        game_id: adapter.game_id().to_string(),
+        format,
+        confidence: total / packets.len() as f32,
+        matched_packets: matched,
+    })
+}
+
+fn explain(report: &InspectionReport) -> String {
+    let port = report.port;
+    if report.packets_received == 0 {
+        return format!(
+            "No packets arrived on UDP port {port} in {:.1} s; check that the game's telemetry \
+             output is enabled and sent to this machine and port",
+            report.listened_ms as f32 / 1000.0
+        );
+    }
+    let Some(top) = report.top() else {
+        let mut sizes = String::new();
+        for (i, size) in report.packet_sizes.iter().take(3).enumerate() {
+            let separator = if i == 0 { "" } else { ", " };
+            let _ = write!(sizes, "{separator}{} bytes", size.bytes);
+        }
+        return format!(
+            "{} packets ({sizes}) arrived on UDP port {port}, but no registered adapter \
+             recognizes them",
+            report.packets_received
+        );
+    };
+    let percent = (top.confidence * 100.0).round();
+    if report.selected.contains(&top.game_id) {
+        format!(
+            "{percent}% match: {} — matches the selected {}",
+            top.format, top.game_id
+        )
+    } else if report.selected.is_empty() {
+        format!(
+            "{percent}% match: {} ({}) — no adapter is configured for port {port}",
+            top.format, top.game_id
+        )
+    } else {
+        format!(
+            "{percent}% match: {} ({}) — you selected {}",
+            top.format,
+            top.game_id,
+            report.selected.join(", ")
+        )
+    }
+}
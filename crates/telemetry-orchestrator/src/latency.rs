@@ -0,0 +1,226 @@
+//! Per-stage latency of the forwarding pipeline.
+//!
+//! A frame's `timestamp_ns` is stamped by its adapter when the raw packet
+//! arrives. As the forwarding task in `start_monitoring` takes the frame
+//! through the rate limiter, the transforms, the recorders and on to the
+//! consumer's channel, it stamps each [`LatencyStage`] on the game's
+//! [`LatencyProbe`], keyed by the frame's sequence number rather than
+//! carried on the frame. The probe keeps a fixed ring of in-flight frames
+//! and a sliding window of samples per stage, so stamping allocates nothing;
+//! [`LatencyProbe::report`] turns the windows into percentiles.
+
+use std::collections::BTreeMap;
+use std::sync::{Mutex, PoisonError};
+
+use racing_wheel_telemetry_adapters::telemetry_now_ns;
+use serde::{Deserialize, Serialize};
+
+/// In-flight frames a probe tracks; a frame still unfinished when a
+/// sequence number this far ahead begins is forgotten.
+pub const LATENCY_RING_CAPACITY: usize = 256;
+
+/// Latest samples each stage's percentiles are computed over.
+pub const LATENCY_WINDOW: usize = 1024;
+
+/// A point a frame passes on its way from the adapter to the consumer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LatencyStage {
+    /// The frame passed the game's rate limiter.
+    RateLimiter,
+    /// The game's transforms ran.
+    Processors,
+    /// History, black box and recording took the frame.
+    Recorder,
+    /// The consumer's channel accepted the frame; ends the frame.
+    Forwarder,
+}
+
+impl LatencyStage {
+    /// Every stage, in pipeline order.
+    pub const ALL: [Self; 4] = [
+        Self::RateLimiter,
+        Self::Processors,
+        Self::Recorder,
+        Self::Forwarder,
+    ];
+}
+
+/// Latency percentiles over a window of samples, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatencyPercentiles {
+    /// Samples in the window.
+    pub samples: usize,
+    pub p50_ns: u64,
+    pub p95_ns: u64,
+    pub p99_ns: u64,
+    pub max_ns: u64,
+}
+
+impl LatencyPercentiles {
+    /// `p99_ns` in milliseconds.
+    pub fn p99_ms(&self) -> f64 {
+        self.p99_ns as f64 / 1_000_000.0
+    }
+}
+
+/// Latency of one game's pipeline, as returned by
+/// `TelemetryService::latency_report`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatencyReport {
+    /// Time from the previous stamped stage, or from the adapter's receive
+    /// time for the first, to each stage. Stages without samples are absent.
+    pub stages: BTreeMap<LatencyStage, LatencyPercentiles>,
+    /// Time from the adapter's receive time to [`LatencyStage::Forwarder`].
+    pub end_to_end: Option<LatencyPercentiles>,
+}
+
+impl LatencyReport {
+    pub fn stage(&self, stage: LatencyStage) -> Option<&LatencyPercentiles> {
+        self.stages.get(&stage)
+    }
+}
+
+/// Stamps for one in-flight frame.
+#[derive(Debug, Clone, Copy, Default)]
+struct Slot {
+    sequence: u64,
+    received_ns: u64,
+    /// Latest stamp, or `received_ns` before the first.
+    last_ns: u64,
+    live: bool,
+}
+
+/// The latest [`LATENCY_WINDOW`] samples, overwriting the oldest.
+#[derive(Debug)]
+struct Window {
+    samples: Box<[u64]>,
+    len: usize,
+    next: usize,
+}
+
+impl Window {
+    fn new() -> Self {
+        Self {
+            samples: vec![0; LATENCY_WINDOW].into_boxed_slice(),
+            len: 0,
+            next: 0,
+        }
+    }
+
+    fn push(&mut self, sample_ns: u64) {
+        self.samples[self.next] = sample_ns;
+        self.next = (self.next + 1) % self.samples.len();
+        self.len = (self.len + 1).min(self.samples.len());
+    }
+
+    fn percentiles(&self) -> Option<LatencyPercentiles> {
+        if self.len == 0 {
+            return None;
+        }
+        let mut sorted = self.samples[..self.len].to_vec();
+        sorted.sort_unstable();
+        // Nearest rank.
+        let rank = |percent: usize| sorted[(self.len * percent).div_ceil(100).max(1) - 1];
+        Some(LatencyPercentiles {
+            samples: self.len,
+            p50_ns: rank(50),
+            p95_ns: rank(95),
+            p99_ns: rank(99),
+            max_ns: sorted[self.len - 1],
+        })
+    }
+}
+
+#[derive(Debug)]
+struct ProbeState {
+    slots: Box<[Slot]>,
+    stages: [Window; LatencyStage::ALL.len()],
+    end_to_end: Window,
+}
+
+/// Side channel the pipeline stamps frames on, keyed by sequence number.
+///
+/// Sequence numbers need not be contiguous: a frame maps to the slot at its
+/// sequence modulo [`LATENCY_RING_CAPACITY`], and stamps for a sequence no
+/// longer in its slot are ignored. A receive time ahead of a stamp counts
+/// as zero latency.
+#[derive(Debug)]
+pub struct LatencyProbe {
+    state: Mutex<ProbeState>,
+}
+
+impl Default for LatencyProbe {
+    fn default() -> Self {
+        Self {
+            state: Mutex::new(ProbeState {
+                slots: vec![Slot::default(); LATENCY_RING_CAPACITY].into_boxed_slice(),
+                stages: std::array::from_fn(|_| Window::new()),
+                end_to_end: Window::new(),
+            }),
+        }
+    }
+}
+
+impl LatencyProbe {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start tracking the frame `sequence`, received at `received_ns` on the
+    /// [`telemetry_now_ns`] clock.
+    pub fn begin(&self, sequence: u64, received_ns: u64) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let index = slot_index(sequence, state.slots.len());
+        state.slots[index] = Slot {
+            sequence,
+            received_ns,
+            last_ns: received_ns,
+            live: true,
+        };
+    }
+
+    /// The frame `sequence` reached `stage` now.
+    pub fn stamp(&self, sequence: u64, stage: LatencyStage) {
+        self.stamp_at(sequence, stage, telemetry_now_ns());
+    }
+
+    /// The frame `sequence` reached `stage` at `now_ns`.
+    pub fn stamp_at(&self, sequence: u64, stage: LatencyStage, now_ns: u64) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let index = slot_index(sequence, state.slots.len());
+        let slot = state.slots[index];
+        if !slot.live || slot.sequence != sequence {
+            return;
+        }
+        state.stages[stage as usize].push(now_ns.saturating_sub(slot.last_ns));
+        if stage == LatencyStage::Forwarder {
+            state
+                .end_to_end
+                .push(now_ns.saturating_sub(slot.received_ns));
+            state.slots[index].live = false;
+        } else {
+            state.slots[index].last_ns = slot.last_ns.max(now_ns);
+        }
+    }
+
+    /// Percentiles over each stage's window.
+    pub fn report(&self) -> LatencyReport {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        LatencyReport {
+            stages: LatencyStage::ALL
+                .into_iter()
+                .filter_map(|stage| {
+                    state.stages[stage as usize]
+                        .percentiles()
+                        .map(|percentiles| (stage, percentiles))
+                })
+                .collect(),
+            end_to_end: state.end_to_end.percentiles(),
+        }
+    }
+}
+
+fn slot_index(sequence: u64, capacity: usize) -> usize {
+    (sequence % capacity as u64) as usize
+}
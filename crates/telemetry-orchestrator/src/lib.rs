@@ -6,30 +6,250 @@
 
 #![deny(static_mut_refs)]
 
-use std::collections::{HashMap, HashSet};
+pub mod black_box;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod config_apply;
+#[cfg(feature = "csv")]
+pub mod csv_export;
+pub mod detection;
+pub mod field_watch;
+pub mod forwarder;
+pub mod freshness;
+pub mod game_clock;
+pub mod health;
+pub mod inspector;
+pub mod latency;
+pub mod matrix_reload;
+pub mod metrics;
+pub mod migration;
+pub mod monitoring_session;
+pub mod multiplex;
+pub mod pause;
+pub mod persistence;
+pub mod playback;
+pub mod reconnect;
+pub mod retention;
+#[cfg(feature = "scripting")]
+pub mod scripting;
+pub mod self_test;
+pub mod session_segment;
+pub mod shutdown;
+pub mod sinks;
+pub mod snapshot;
+pub mod track_position;
+pub mod transforms;
+pub mod wait;
+#[cfg(feature = "websocket")]
+pub mod websocket;
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+#[cfg(feature = "websocket")]
+use std::net::SocketAddr;
 use std::path::PathBuf;
-
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use std::time::Duration;
+
+use crate::black_box::BlackBox;
+use crate::detection::DETECTION_EVENT_CAPACITY;
+use crate::freshness::{FreshnessChannel, FreshnessMonitor};
+use crate::health::{HealthChannel, HealthMonitor, HealthSubscribers, next_transport_switch};
+use crate::monitoring_session::{ActiveSessions, ForwardCounters};
+use crate::multiplex::Multiplex;
+use crate::pause::PauseGate;
+use crate::shutdown::ShutdownSignal;
+use crate::sinks::SinkQueue;
+use crate::wait::FrameTap;
 use anyhow::Result;
-use racing_wheel_telemetry_adapters::{TelemetryAdapter, TelemetryReceiver, adapter_factories};
+use openracing_telemetry_streams::{DedupConfig, DedupFilter, StreamQuality, StreamQualityMonitor};
+use racing_wheel_telemetry_adapters::shm_snapshot::{
+    self, ShmCaptureTrigger, ShmPageSource, ShmSnapshot,
+};
+use racing_wheel_telemetry_adapters::{
+    AdapterNetworkConfig, ListenMode, NormalizedTelemetry, PenaltyEvent, PenaltyTracker,
+    RawCaptureCaps, RawCaptureSink, RawCaptureStats, TelemetryAdapter, TelemetryFrame,
+    TelemetryReceiver, TelemetryValue, TrafficStats, UnitManifest, adapter_factories,
+    telemetry_now_ns,
+};
 use racing_wheel_telemetry_bdd_metrics::RuntimeBddMatrixMetrics;
-use racing_wheel_telemetry_config_writers::config_writer_factories;
+use racing_wheel_telemetry_config_writers::{config_writer_factories, normalize_field_name};
+use racing_wheel_telemetry_core::{
+    Bucket, ConnectionState, DisconnectionConfig, HistoryConfig, HistoryField, HistoryStore,
+    HistorySummary, TelemetryValidator, ValidationPolicy,
+};
 use racing_wheel_telemetry_integration::{
-    CoveragePolicy, RuntimeCoverageReport, compare_runtime_registries_with_policies,
+    CoverageExemptions, CoveragePolicy, RuntimeCoverageReport,
+    compare_runtime_registries_with_policies,
 };
-use racing_wheel_telemetry_rate_limiter::RateLimiter;
-use racing_wheel_telemetry_recorder::TelemetryRecorder;
-use racing_wheel_telemetry_support::{GameSupportMatrix, normalize_game_id};
+use racing_wheel_telemetry_rate_limiter::{RateLimiterRegistry, RateLimiterStats};
+use racing_wheel_telemetry_recorder::{SavedRecording, TelemetryRecorder, TelemetryRecording};
+use racing_wheel_telemetry_support::{
+    GameCapabilities, GameIdAliases, GameSupportMatrix, normalize_game_id,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, mpsc, watch};
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, MissedTickBehavior};
 use tracing::{debug, warn};
 
+pub use black_box::SaveReport;
+#[cfg(feature = "blocking")]
+pub use blocking::{
+    BLOCKING_FRAME_CAPACITY, BlockingFrameIter, BlockingRecvError, BlockingTelemetryService,
+};
+pub use config_apply::ConfigWriterOrchestrator;
+#[cfg(feature = "csv")]
+pub use csv_export::{
+    ChannelSelector, ExportStats, TIME_COLUMN, export_csv, export_recording_to_csv,
+};
+pub use detection::{
+    DEFAULT_PROBE_TIMEOUT, DETECTION_CONFIRMATIONS, GameDetectedEvent, GameDetector,
+};
+pub use field_watch::{FieldPath, FieldSelector, FieldWatchHub, UnknownField};
+pub use forwarder::{
+    ForwardError, ForwardTarget, ForwardTargetStats, MAX_DATAGRAM_BYTES, TelemetryForwarder,
+    encode_datagram,
+};
+pub use freshness::{
+    Freshness, FreshnessCause, FreshnessEvent, FreshnessStatus, FreshnessThresholds,
+    FreshnessTracker, GameFreshness,
+};
+pub use game_clock::{
+    ClockDiagnostics, ClockSource, ClockStep, DEFAULT_DIVERGENCE_THRESHOLD, GameClock,
+};
+pub use health::{AdapterHealth, HEALTH_EVENT_CAPACITY, HealthEvent, HealthEventReceiver};
+pub use inspector::{
+    INSPECTION_PACKET_LIMIT, InspectError, InspectionCandidate, InspectionReport, PacketSizeCount,
+};
+pub use latency::{
+    LATENCY_RING_CAPACITY, LATENCY_WINDOW, LatencyPercentiles, LatencyProbe, LatencyReport,
+    LatencyStage,
+};
+pub use matrix_reload::{MatrixReloadReport, watch_matrix_file};
+pub use metrics::{
+    CoverageMetrics, GameMetrics, METRIC_PREFIX, RegistryMetrics, ServiceMetrics, render_prometheus,
+};
+pub use migration::{
+    BACKUP_SUFFIX, MigratedFile, MigrationMode, MigrationReport, RewriteKind, SkippedFile,
+    migrate_persisted_data,
+};
+pub use monitoring_session::{MonitoringSession, SessionError, SessionStats};
+pub use multiplex::{LabeledFrame, LabeledReceiver, MULTIPLEX_CHANNEL_CAPACITY};
+pub use pause::{PauseError, PauseEvent, PauseState};
+pub use persistence::{FieldPersistence, PERSISTED_FIELDS_KEY, PersistedField, persisted_fields};
+pub use playback::{
+    PLAYBACK_GAME_ID_PREFIX, PlaybackAdapter, PlaybackConfig, PlaybackEnd, PlaybackSpeed,
+};
+pub use reconnect::{MAX_RECONNECT_BACKOFF, reconnect_backoff};
+pub use retention::{
+    ArtifactClass, CleanupCandidate, CleanupReason, CleanupSummary, DiskStats, RetentionEvent,
+    RetentionManager, RetentionPolicies, RetentionPolicy, SystemDiskStats,
+};
+#[cfg(feature = "scripting")]
+pub use scripting::{
+    SCRIPT_PROFILE_FILE, ScriptBudget, ScriptDisableReason, ScriptEvent, ScriptProfiles,
+    ScriptTransform,
+};
+pub use self_test::{
+    DEFAULT_SELF_TEST_FRAME_TIMEOUT, FrameSummary, PortConflict, SelfTestOptions, SelfTestReport,
+    SelfTestStep, SelfTestStepReport, StepOutcome,
+};
+pub use session_segment::{
+    LapRecord, SegmenterConfig, SessionEndReason, SessionEvent, SessionSegmenter, SessionSpan,
+    segment, segment_with,
+};
+pub use shutdown::ShutdownReport;
+pub use sinks::{
+    AutoEncodingPolicy, FrameEncoder, SINK_QUEUE_CAPACITY, SinkCapabilities, SinkCost,
+    SinkEncoding, SinkEvent, SinkHub, SinkRegistration, TelemetrySink, WireEncoding,
+};
+pub use snapshot::{
+    DEFAULT_SNAPSHOT_TCP_PORT, FrameSnapshot, MAX_SNAPSHOT_REQUEST_BYTES, SnapshotEndpoint,
+    SnapshotServerHandle, TelemetrySnapshotCache,
+};
+pub use track_position::{
+    DeltaComputer, PositionConfidence, ReferenceLapError, TrackPosition, TrackPositionEstimator,
+};
+pub use transforms::{FrameTransform, TransformChain};
+pub use wait::WaitError;
+#[cfg(feature = "websocket")]
+pub use websocket::{SlowClientPolicy, WsHandle, WsOptions};
+
+/// Capacity of the per-game channel handed to `start_monitoring` callers.
+const FORWARD_CHANNEL_CAPACITY: usize = 100;
+/// Capacity of the penalty event broadcast channel.
+const PENALTY_EVENT_CAPACITY: usize = 64;
+/// Capacity of the freshness event broadcast channel.
+const FRESHNESS_EVENT_CAPACITY: usize = 64;
+/// Capacity of the pause event broadcast channel.
+const PAUSE_EVENT_CAPACITY: usize = 16;
+/// Rate limit for games whose adapter reports no usable update rate.
+const DEFAULT_RATE_LIMIT_HZ: u32 = 1000;
+/// Multiple of the adapter's expected rate a game may run at before frames
+/// are dropped, leaving room for jitter.
+const RATE_LIMIT_HEADROOM: u32 = 2;
+/// Frames a game may send back to back, e.g. flushing a backlog after a
+/// reconnect, before its rate limit applies.
+const RATE_LIMIT_BURST_FRAMES: u32 = 32;
+/// Shortest poll period of the game detection loop.
+const MIN_DETECTION_INTERVAL: Duration = Duration::from_millis(10);
+
+/// A [`PenaltyEvent`] tagged with the game whose telemetry produced it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GamePenaltyEvent {
+    /// Normalized game id of the monitor that observed the change.
+    pub game_id: String,
+    /// The penalty state change.
+    pub event: PenaltyEvent,
+}
+
 /// Runtime telemetry orchestration service.
 pub struct TelemetryService {
-    adapters: HashMap<String, Box<dyn TelemetryAdapter>>,
-    #[allow(dead_code)]
-    rate_limiter: RateLimiter,
-    recorder: Option<TelemetryRecorder>,
+    adapters: HashMap<String, Arc<dyn TelemetryAdapter>>,
+    rate_limits: Arc<Mutex<RateLimiterRegistry>>,
+    recorder: Arc<Mutex<Option<TelemetryRecorder>>>,
+    black_box: Arc<Mutex<BlackBox>>,
     support_matrix: Option<GameSupportMatrix>,
+    game_aliases: GameIdAliases,
+    /// Games a matrix reload dropped while they were being monitored.
+    deferred_matrix_removals: BTreeSet<String>,
     runtime_coverage_report: Option<RuntimeCoverageReport>,
     runtime_bdd_metrics: Option<RuntimeBddMatrixMetrics>,
+    sinks: Arc<SinkHub>,
+    /// Started with the first registered sink.
+    sink_queue: Arc<OnceLock<SinkQueue>>,
+    #[cfg(feature = "websocket")]
+    websockets: Arc<websocket::WsHub>,
+    transforms: HashMap<String, Arc<Mutex<TransformChain>>>,
+    field_watches: HashMap<String, Arc<FieldWatchHub>>,
+    snapshots: Arc<TelemetrySnapshotCache>,
+    penalty_events: broadcast::Sender<GamePenaltyEvent>,
+    freshness: HashMap<String, Arc<FreshnessChannel>>,
+    freshness_thresholds: HashMap<String, FreshnessThresholds>,
+    freshness_events: broadcast::Sender<FreshnessEvent>,
+    pause_gates: HashMap<String, Arc<PauseGate>>,
+    pause_events: broadcast::Sender<PauseEvent>,
+    frame_taps: HashMap<String, Arc<FrameTap>>,
+    health: HashMap<String, Arc<HealthChannel>>,
+    latency: HashMap<String, Arc<LatencyProbe>>,
+    quality: HashMap<String, Arc<Mutex<StreamQualityMonitor>>>,
+    health_subscribers: Arc<HealthSubscribers>,
+    disconnection_configs: HashMap<String, DisconnectionConfig>,
+    validators: HashMap<String, TelemetryValidator>,
+    multiplex: Option<Multiplex>,
+    multiplex_failures: BTreeMap<String, String>,
+    retention: Option<Arc<RetentionManager>>,
+    retention_task: Option<tokio::task::JoinHandle<()>>,
+    detection_task: Option<tokio::task::JoinHandle<()>>,
+    shm_sources: HashMap<String, Box<dyn ShmPageSource>>,
+    history: Arc<Mutex<HistoryStore>>,
+    inspections: Mutex<BTreeMap<u16, InspectionReport>>,
+    raw_captures: HashMap<String, Arc<RawCaptureSink>>,
+    sessions: ActiveSessions,
+    /// Forwarding tasks by game, pruned of finished ones as new ones start.
+    forward_tasks: Vec<(String, JoinHandle<()>)>,
+    shutdown: Arc<ShutdownSignal>,
 }
 
 impl Default for TelemetryService {
@@ -62,62 +282,9 @@ impl TelemetryService {
                 "Using fallback telemetry adapter registration because game support matrix failed to load."
             );
         } else if let Some(matrix) = &support_matrix {
-            let writer_factories = config_writer_factories();
-            let coverage = compare_runtime_registries_with_policies(
-                matrix.game_ids(),
-                adapter_factories().iter().map(|(game_id, _)| *game_id),
-                writer_factories.iter().map(|(writer_id, _)| *writer_id),
-                CoveragePolicy::MATRIX_COMPLETE,
-                CoveragePolicy::MATRIX_COMPLETE,
-            );
-            let metrics = coverage.metrics();
-            let bdd_metrics = coverage.bdd_metrics();
-            runtime_coverage_report = Some(coverage.clone());
-            runtime_bdd_metrics = Some(bdd_metrics.clone());
-
-            if !coverage.adapter_coverage.has_no_extra_coverage() {
-                warn!(
-                    extra_adapters = ?coverage.adapter_coverage.extra_in_registry,
-                    "Adapter registry contains game IDs not present in support matrix"
-                );
-            }
-
-            if !coverage.adapter_coverage.has_complete_matrix_coverage() {
-                warn!(
-                    missing_adapters = ?coverage.adapter_coverage.missing_in_registry,
-                    "Adapter registry does not cover all game IDs in support matrix"
-                );
-            }
-
-            if !coverage.writer_coverage.has_no_extra_coverage() {
-                warn!(
-                    extra_writers = ?coverage.writer_coverage.extra_in_registry,
-                    "Config writer registry contains game IDs not present in support matrix"
-                );
-            }
-
-            if !coverage.writer_coverage.has_complete_matrix_coverage() {
-                warn!(
-                    missing_writers = ?coverage.writer_coverage.missing_in_registry,
-                    "Config writer registry does not cover all game IDs in support matrix"
-                );
-            }
-
-            tracing::info!(
-                matrix_game_count = metrics.matrix_game_count,
-                adapter_matrix_coverage = metrics.adapter.matrix_coverage_ratio,
-                adapter_registry_coverage = metrics.adapter.registry_coverage_ratio,
-                adapter_missing_count = metrics.adapter.missing_count,
-                adapter_extra_count = metrics.adapter.extra_count,
-                adapter_parity_ok = bdd_metrics.adapter.parity_ok,
-                writer_matrix_coverage = metrics.writer.matrix_coverage_ratio,
-                writer_registry_coverage = metrics.writer.registry_coverage_ratio,
-                writer_missing_count = metrics.writer.missing_count,
-                writer_extra_count = metrics.writer.extra_count,
-                writer_parity_ok = bdd_metrics.writer.parity_ok,
-                matrix_parity_ok = metrics.parity_ok,
-                "Telemetry registry parity checked against support matrix"
-            );
+            let (coverage, bdd_metrics) = Self::check_registry_parity(matrix);
+            runtime_coverage_report = Some(coverage);
+            runtime_bdd_metrics = Some(bdd_metrics);
         }
 
         for (game_id, factory) in adapter_factories() {
@@ -131,7 +298,7 @@ impl TelemetryService {
                 continue;
             }
 
-            adapters.insert(game_id.to_string(), factory());
+            adapters.insert(game_id.to_string(), Arc::from(factory()));
         }
 
         if let Some(matrix_ids) = matrix_game_ids {
@@ -145,49 +312,1374 @@ impl TelemetryService {
             }
         }
 
+        let game_aliases = support_matrix
+            .as_ref()
+            .map_or_else(GameIdAliases::built_in, GameIdAliases::for_matrix);
+
         Self {
             adapters,
-            rate_limiter: RateLimiter::new(1000), // 1kHz max rate to protect RT thread
-            recorder: None,
+            rate_limits: Arc::new(Mutex::new(
+                RateLimiterRegistry::new(DEFAULT_RATE_LIMIT_HZ)
+                    .with_burst_capacity(RATE_LIMIT_BURST_FRAMES),
+            )),
+            recorder: Arc::default(),
+            black_box: Arc::default(),
             support_matrix,
+            game_aliases,
+            deferred_matrix_removals: BTreeSet::new(),
             runtime_coverage_report,
             runtime_bdd_metrics,
+            sinks: Arc::new(SinkHub::default()),
+            sink_queue: Arc::default(),
+            #[cfg(feature = "websocket")]
+            websockets: Arc::default(),
+            transforms: HashMap::new(),
+            field_watches: HashMap::new(),
+            snapshots: Arc::default(),
+            penalty_events: broadcast::channel(PENALTY_EVENT_CAPACITY).0,
+            freshness: HashMap::new(),
+            freshness_thresholds: HashMap::new(),
+            freshness_events: broadcast::channel(FRESHNESS_EVENT_CAPACITY).0,
+            pause_gates: HashMap::new(),
+            pause_events: broadcast::channel(PAUSE_EVENT_CAPACITY).0,
+            frame_taps: HashMap::new(),
+            health: HashMap::new(),
+            latency: HashMap::new(),
+            quality: HashMap::new(),
+            health_subscribers: Arc::default(),
+            disconnection_configs: HashMap::new(),
+            validators: HashMap::new(),
+            multiplex: None,
+            multiplex_failures: BTreeMap::new(),
+            retention: None,
+            retention_task: None,
+            detection_task: None,
+            shm_sources: HashMap::new(),
+            history: Arc::new(Mutex::new(HistoryStore::default())),
+            inspections: Mutex::new(BTreeMap::new()),
+            raw_captures: HashMap::new(),
+            sessions: Arc::default(),
+            forward_tasks: Vec::new(),
+            shutdown: Arc::default(),
         }
     }
 
+    /// Replace the sink hub, e.g. to tune the auto-encoding policy.
+    pub fn with_sink_hub(mut self, hub: SinkHub) -> Self {
+        self.sinks = Arc::new(hub);
+        self.sink_queue = Arc::default();
+        self
+    }
+
+    /// Attach a retention manager for the OpenRacing data directories.
+    pub fn with_retention(mut self, manager: RetentionManager) -> Self {
+        self.retention = Some(Arc::new(manager));
+        self
+    }
+
+    /// Replace the history store configuration; recorded history is discarded.
+    pub fn with_history(mut self, config: HistoryConfig) -> Self {
+        self.history = Arc::new(Mutex::new(HistoryStore::new(config)));
+        self
+    }
+
+    /// Resolve game ids through `aliases` rather than the built-in renames
+    /// alone, e.g. to accept Steam app ids.
+    pub fn with_game_aliases(mut self, aliases: GameIdAliases) -> Self {
+        self.game_aliases = aliases;
+        self
+    }
+
+    /// Aliases every game id passed to this service is resolved through.
+    pub fn game_aliases(&self) -> &GameIdAliases {
+        &self.game_aliases
+    }
+
+    /// Canonical id for a caller-supplied `game_id`.
+    fn canonical_game_id<'a>(&self, game_id: &'a str) -> Cow<'a, str> {
+        let game_id = game_id.trim();
+        match self.game_aliases.resolve(game_id) {
+            resolved if resolved == game_id => Cow::Borrowed(game_id),
+            resolved => Cow::Owned(resolved.to_string()),
+        }
+    }
+
+    /// Apply a changed support matrix without restarting monitoring.
+    ///
+    /// Games the matrix gained since the last construction or reload get a
+    /// built-in adapter; games it lost are unregistered unless still being
+    /// monitored, in which case they stay registered and are retried on the
+    /// next reload. Adapters registered by hand under ids the matrix never
+    /// listed are left alone. Fails, changing nothing, if the matrix uses a
+    /// legacy game id.
+    pub fn reload_support_matrix(
+        &mut self,
+        matrix: GameSupportMatrix,
+    ) -> Result<MatrixReloadReport> {
+        if let Some(legacy) = matrix
+            .games
+            .keys()
+            .find(|game_id| normalize_game_id(game_id) != game_id.as_str())
+        {
+            return Err(anyhow::anyhow!(
+                "Support matrix uses legacy game id `{legacy}`; expected `{}`",
+                normalize_game_id(legacy)
+            ));
+        }
+
+        let new_ids: BTreeSet<String> = matrix.games.keys().cloned().collect();
+        let previous_ids: BTreeSet<String> = match &self.support_matrix {
+            Some(previous) => previous.games.keys().cloned().collect(),
+            None => self.adapters.keys().cloned().collect(),
+        };
+        let mut report = MatrixReloadReport::default();
+
+        for game_id in new_ids.difference(&previous_ids) {
+            if self.adapters.contains_key(game_id) {
+                continue;
+            }
+            match adapter_factories().iter().find(|(id, _)| id == game_id) {
+                Some((_, factory)) => {
+                    self.adapters.insert(game_id.clone(), Arc::from(factory()));
+                    report.added.push(game_id.clone());
+                }
+                None => report.unsupported.push(game_id.clone()),
+            }
+        }
+
+        let deferred = std::mem::take(&mut self.deferred_matrix_removals);
+        let removals: BTreeSet<String> = previous_ids
+            .difference(&new_ids)
+            .cloned()
+            .chain(deferred.into_iter().filter(|id| !new_ids.contains(id)))
+            .collect();
+        for game_id in removals {
+            if !self.adapters.contains_key(&game_id) {
+                continue;
+            }
+            if self.is_monitoring(&game_id) {
+                report.deferred.push(game_id.clone());
+                self.deferred_matrix_removals.insert(game_id);
+            } else {
+                self.adapters.remove(&game_id);
+                report.removed.push(game_id);
+            }
+        }
+
+        let (coverage, bdd_metrics) = Self::check_registry_parity(&matrix);
+        self.runtime_coverage_report = Some(coverage);
+        self.runtime_bdd_metrics = Some(bdd_metrics);
+        self.support_matrix = Some(matrix);
+        Ok(report)
+    }
+
+    /// Compare the adapter and config writer registries with `matrix`,
+    /// logging any gaps.
+    fn check_registry_parity(
+        matrix: &GameSupportMatrix,
+    ) -> (RuntimeCoverageReport, RuntimeBddMatrixMetrics) {
+        let writer_factories = config_writer_factories();
+        let coverage = compare_runtime_registries_with_policies(
+            matrix.game_ids(),
+            adapter_factories().iter().map(|(game_id, _)| *game_id),
+            writer_factories.iter().map(|(writer_id, _)| *writer_id),
+            CoveragePolicy::MATRIX_COMPLETE,
+            CoveragePolicy::MATRIX_COMPLETE.with_exemptions(
+                CoverageExemptions::default().with_missing(matrix.games_without_config_writer()),
+            ),
+        )
+        .with_field_check(
+            matrix.games.iter().map(|(game_id, support)| {
+                (game_id, support.effective_capabilities().supported_fields)
+            }),
+            |field| normalize_field_name(field).is_ok(),
+        );
+        let metrics = coverage.metrics();
+        let bdd_metrics = coverage.bdd_metrics();
+
+        if !coverage.adapter_coverage.has_no_extra_coverage() {
+            warn!(
+                extra_adapters = ?coverage.adapter_coverage.extra_in_registry,
+                "Adapter registry contains game IDs not present in support matrix"
+            );
+        }
+
+        if !coverage.adapter_coverage.has_complete_matrix_coverage() {
+            warn!(
+                missing_adapters = ?coverage.adapter_coverage.missing_in_registry,
+                "Adapter registry does not cover all game IDs in support matrix"
+            );
+        }
+
+        if !coverage.writer_coverage.has_no_extra_coverage() {
+            warn!(
+                extra_writers = ?coverage.writer_coverage.extra_in_registry,
+                "Config writer registry contains game IDs not present in support matrix"
+            );
+        }
+
+        if !coverage.writer_coverage.has_complete_matrix_coverage() {
+            warn!(
+                missing_writers = ?coverage.writer_coverage.missing_in_registry,
+                "Config writer registry does not cover all game IDs in support matrix"
+            );
+        }
+
+        if !coverage.writer_coverage.exempted_missing.is_empty() {
+            debug!(
+                exempted_writers = ?coverage.writer_coverage.exempted_missing,
+                "Support matrix marks these games as having no config writer"
+            );
+        }
+
+        if !coverage.writer_coverage.stale_exemptions.is_empty() {
+            warn!(
+                stale_exemptions = ?coverage.writer_coverage.stale_exemptions,
+                "Support matrix marks games as having no config writer, but a writer is registered"
+            );
+        }
+
+        for mismatch in &coverage.field_mismatches {
+            warn!(
+                game_id = %mismatch.game_id,
+                unknown_fields = ?mismatch.unknown_fields,
+                "Support matrix lists fields the config writers do not accept"
+            );
+        }
+
+        tracing::info!(
+            matrix_game_count = metrics.matrix_game_count,
+            adapter_matrix_coverage = metrics.adapter.matrix_coverage_ratio,
+            adapter_registry_coverage = metrics.adapter.registry_coverage_ratio,
+            adapter_missing_count = metrics.adapter.missing_count,
+            adapter_extra_count = metrics.adapter.extra_count,
+            adapter_parity_ok = bdd_metrics.adapter.parity_ok,
+            writer_matrix_coverage = metrics.writer.matrix_coverage_ratio,
+            writer_registry_coverage = metrics.writer.registry_coverage_ratio,
+            writer_missing_count = metrics.writer.missing_count,
+            writer_extra_count = metrics.writer.extra_count,
+            writer_parity_ok = bdd_metrics.writer.parity_ok,
+            matrix_parity_ok = metrics.parity_ok,
+            "Telemetry registry parity checked against support matrix"
+        );
+
+        (coverage, bdd_metrics)
+    }
+
+    /// Register (or replace) an adapter under its own game id.
+    pub fn register_adapter(&mut self, adapter: Box<dyn TelemetryAdapter>) {
+        self.adapters
+            .insert(adapter.game_id().to_string(), Arc::from(adapter));
+    }
+
+    /// Register the shared-memory pages backing `game_id` for snapshots.
+    pub fn register_shm_source(&mut self, game_id: &str, source: Box<dyn ShmPageSource>) {
+        self.shm_sources
+            .insert(self.canonical_game_id(game_id).into_owned(), source);
+    }
+
+    /// Copy the mapped pages of `game_id` into a redacted snapshot for a bug report.
+    pub fn capture_shm_snapshot(&self, game_id: &str) -> Result<ShmSnapshot> {
+        let game_id = &*self.canonical_game_id(game_id);
+        let adapter = self
+            .adapters
+            .get(game_id)
+            .ok_or_else(|| anyhow::anyhow!("No adapter for game: {}", game_id))?;
+        let source = self
+            .shm_sources
+            .get(game_id)
+            .ok_or_else(|| anyhow::anyhow!("{game_id} has no shared-memory source"))?;
+        shm_snapshot::capture_shm_snapshot(
+            adapter.as_ref(),
+            source.as_ref(),
+            ShmCaptureTrigger::Manual,
+            &[],
+        )
+    }
+
+    /// Append a frame transform for `game_id`; it also applies to running monitors.
+    pub fn register_transform(&mut self, game_id: &str, transform: Box<dyn FrameTransform>) {
+        let game_id = &*self.canonical_game_id(game_id);
+        self.transforms
+            .entry(game_id.to_string())
+            .or_default()
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(transform);
+    }
+
+    /// Carry intermittent fields of `game_id` forward, ahead of all other transforms.
+    ///
+    /// Later transforms, penalty tracking, field watches and sinks all see the
+    /// filled-in frame.
+    pub fn register_field_persistence(&mut self, game_id: &str, persistence: FieldPersistence) {
+        self.transforms
+            .entry(self.canonical_game_id(game_id).into_owned())
+            .or_default()
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push_front(Box::new(persistence));
+    }
+
+    /// Names of the transforms registered for `game_id`, in execution order.
+    pub fn transform_names(&self, game_id: &str) -> Vec<String> {
+        self.transforms
+            .get(&*self.canonical_game_id(game_id))
+            .map(|chain| chain.lock().unwrap_or_else(PoisonError::into_inner).names())
+            .unwrap_or_default()
+    }
+
+    /// Register the scripts saved per game in `profile_dir`'s
+    /// [`ScriptProfiles`] as transforms.
+    ///
+    /// Returns the number of scripts registered.
+    #[cfg(feature = "scripting")]
+    pub fn load_profile_scripts(
+        &mut self,
+        profile_dir: impl AsRef<std::path::Path>,
+    ) -> Result<usize> {
+        let profile_dir = profile_dir.as_ref();
+        let scripts = ScriptProfiles::load(profile_dir)?.load_scripts(profile_dir)?;
+        let count = scripts.len();
+        for (game_id, script) in scripts {
+            self.register_transform(&game_id, Box::new(script));
+        }
+        Ok(count)
+    }
+
     /// Start telemetry monitoring for a specific game.
+    ///
+    /// Stopping is left to [`stop_monitoring`](Self::stop_monitoring); see
+    /// [`start_monitoring_session`](Self::start_monitoring_session) for a
+    /// handle that stops the game itself.
     pub async fn start_monitoring(&mut self, game_id: &str) -> Result<TelemetryReceiver> {
-        let game_id = normalize_game_id(game_id);
+        self.start_forwarding(game_id, Arc::default()).await
+    }
+
+    /// Start monitoring `game_id` and return a session that owns its stream,
+    /// stopping the game when stopped or dropped.
+    ///
+    /// Fails with [`SessionError::AlreadyActive`] while an earlier session
+    /// for the game is still open.
+    pub async fn start_monitoring_session(&mut self, game_id: &str) -> Result<MonitoringSession> {
+        let game_id = self.canonical_game_id(game_id).into_owned();
+        let adapter = Arc::clone(
+            self.adapters
+                .get(&game_id)
+                .ok_or_else(|| anyhow::anyhow!("No adapter for game: {}", game_id))?,
+        );
+        monitoring_session::claim(&self.sessions, &game_id)?;
+        let counters = Arc::new(ForwardCounters::default());
+        let receiver = match self.start_forwarding(&game_id, Arc::clone(&counters)).await {
+            Ok(receiver) => receiver,
+            Err(err) => {
+                monitoring_session::release(&self.sessions, &game_id);
+                return Err(err);
+            }
+        };
+        let health = Arc::clone(self.health.entry(game_id.clone()).or_default());
+        Ok(MonitoringSession::new(
+            game_id,
+            receiver,
+            counters,
+            adapter,
+            health,
+            Arc::clone(&self.sessions),
+        ))
+    }
+
+    async fn start_forwarding(
+        &mut self,
+        game_id: &str,
+        counters: Arc<ForwardCounters>,
+    ) -> Result<TelemetryReceiver> {
+        let game_id = &*self.canonical_game_id(game_id);
 
         let adapter = self
             .adapters
             .get(game_id)
             .ok_or_else(|| anyhow::anyhow!("No adapter for game: {}", game_id))?;
 
-        adapter.start_monitoring().await
+        let thresholds = self
+            .freshness_thresholds
+            .get(game_id)
+            .copied()
+            .unwrap_or_else(|| {
+                FreshnessThresholds::from_expected_interval(adapter.expected_update_rate())
+            });
+        let expected_hz = 1.0 / adapter.expected_update_rate().as_secs_f64();
+        let fallback_hz = if expected_hz.is_finite() {
+            (expected_hz.ceil() as u32).saturating_mul(RATE_LIMIT_HEADROOM)
+        } else {
+            DEFAULT_RATE_LIMIT_HZ
+        };
+        let rate_hz = self
+            .rate_limits
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .register(game_id, fallback_hz);
+        debug!(game_id, rate_hz, "Telemetry rate limit selected");
+        let disconnection =
+            self.disconnection_configs
+                .get(game_id)
+                .cloned()
+                .unwrap_or(DisconnectionConfig {
+                    auto_reconnect: false,
+                    ..DisconnectionConfig::default()
+                });
+        let validator = self.validators.get(game_id).cloned().unwrap_or_default();
+        let mut dedup = adapter
+            .prefers_dedup()
+            .then(|| DedupFilter::new(DedupConfig::default()));
+        let mut health = HealthMonitor::start(
+            game_id.to_string(),
+            disconnection,
+            adapter.expected_update_rate(),
+            Arc::clone(self.health.entry(game_id.to_string()).or_default()),
+            Arc::clone(&self.health_subscribers),
+        );
+        // Subscribed first so a switch during startup is not missed.
+        let mut switches = adapter.transport_switches();
+        let mut source = match adapter.start_monitoring().await {
+            Ok(source) => source,
+            Err(err) => {
+                health.fail(&err);
+                return Err(err);
+            }
+        };
+        let adapter = Arc::clone(adapter);
+        let (tx, rx) = tokio::sync::mpsc::channel(FORWARD_CHANNEL_CAPACITY);
+        let rate_limits = Arc::clone(&self.rate_limits);
+        let sink_queue = Arc::clone(&self.sink_queue);
+        #[cfg(feature = "websocket")]
+        let websockets = Arc::clone(&self.websockets);
+        let transforms = Arc::clone(self.transforms.entry(game_id.to_string()).or_default());
+        let field_watches = Arc::clone(self.field_watches.entry(game_id.to_string()).or_default());
+        let snapshot = self.snapshots.slot(game_id);
+        let latency = Arc::clone(self.latency.entry(game_id.to_string()).or_default());
+        let quality = Arc::new(Mutex::new(
+            StreamQualityMonitor::new().with_expected_interval(adapter.expected_update_rate()),
+        ));
+        self.quality
+            .insert(game_id.to_string(), Arc::clone(&quality));
+        let penalty_events = self.penalty_events.clone();
+        let history = Arc::clone(&self.history);
+        let black_box = Arc::clone(&self.black_box);
+        let recorder = Arc::clone(&self.recorder);
+        let shutdown = Arc::clone(&self.shutdown);
+        let mut drain = shutdown.subscribe();
+        let mut freshness = FreshnessMonitor::start(
+            game_id.to_string(),
+            thresholds,
+            Arc::clone(self.freshness.entry(game_id.to_string()).or_default()),
+            self.freshness_events.clone(),
+        );
+        let pause = Arc::clone(self.pause_gates.entry(game_id.to_string()).or_default());
+        pause.start();
+        let frame_tap = Arc::clone(self.frame_taps.entry(game_id.to_string()).or_default());
+        let frames = frame_tap.open();
+        let game_id = game_id.to_string();
+
+        // A new source must not inherit state carried over from the previous one.
+        transforms
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .reset();
+
+        let task_game_id = game_id.clone();
+        let task = tokio::spawn(async move {
+            let mut penalties = PenaltyTracker::new();
+            let mut session_id = None;
+            let mut freshness_tick = tokio::time::interval(thresholds.tick_interval());
+            freshness_tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            let mut was_paused = false;
+            // Once the service shuts down, take what the adapter already
+            // queued without waiting on it or on the consumer, then exit.
+            let mut draining = false;
+            loop {
+                let received = if draining {
+                    let Ok(frame) = source.try_recv() else {
+                        break;
+                    };
+                    shutdown.record_drained();
+                    Some(frame)
+                } else {
+                    tokio::select! {
+                        frame = source.recv() => frame,
+                        () = tx.closed() => break,
+                        Ok(()) = drain.changed() => {
+                            draining = true;
+                            continue;
+                        }
+                        switch = next_transport_switch(&mut switches) => {
+                            health.transport_switched(switch);
+                            continue;
+                        }
+                        _ = freshness_tick.tick() => {
+                            freshness.tick(pause.is_paused());
+                            if !health.tick(pause.is_paused()) {
+                                continue;
+                            }
+                            // Gone quiet; restart as if the stream had ended.
+                            None
+                        }
+                    }
+                };
+                let Some(mut frame) = received else {
+                    match reconnect::reconnect(&mut health, adapter.as_ref(), &tx).await {
+                        Some(reconnected) => {
+                            source = reconnected;
+                            continue;
+                        }
+                        None => break,
+                    }
+                };
+                health.frame();
+                quality
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .observe(frame.sequence, frame.timestamp_ns);
+                latency.begin(frame.sequence, frame.timestamp_ns);
+                if pause.is_paused() {
+                    // Keep draining so the adapter never blocks on a full channel.
+                    pause.record_drop();
+                    counters.record_drop();
+                    freshness.tick(true);
+                    was_paused = true;
+                    continue;
+                }
+                if std::mem::take(&mut was_paused) {
+                    history
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .reset_sequence(&game_id);
+                }
+                freshness.frame();
+                // Adapters building frames from struct literals skip the
+                // setters' checks, so apply them here.
+                if !validator.validate(&frame.data).is_empty() {
+                    let outcome = validator.apply(std::mem::take(&mut frame.data));
+                    health.invalid(outcome.issues.len(), outcome.telemetry.is_none());
+                    let Some(data) = outcome.telemetry else {
+                        counters.record_drop();
+                        continue;
+                    };
+                    if validator.policy() == ValidationPolicy::Sanitize {
+                        health.sanitized();
+                    }
+                    frame.data = data;
+                }
+                // Repeats of a polled frame the game has not rewritten yet.
+                if let Some(dedup) = dedup.as_mut()
+                    && !dedup.admit(&frame.data, frame.timestamp_ns)
+                {
+                    health.deduplicated();
+                    counters.record_drop();
+                    continue;
+                }
+                // Over-limit frames still prove the source is alive.
+                if !rate_limits
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .should_process(&game_id)
+                {
+                    health.rate_limited();
+                    counters.record_drop();
+                    continue;
+                }
+                latency.stamp(frame.sequence, LatencyStage::RateLimiter);
+                // NaN must never reach the FFB path; clear it and count it.
+                if !frame.data.all_finite() {
+                    frame.data = std::mem::take(&mut frame.data).validated();
+                    health.sanitized();
+                }
+                transforms
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .apply(&mut frame);
+                latency.stamp(frame.sequence, LatencyStage::Processors);
+                if frame.data.session_id != session_id {
+                    penalties.reset();
+                    session_id.clone_from(&frame.data.session_id);
+                }
+                for event in penalties.update(frame.data.penalties.as_ref()) {
+                    // No subscribers is not an error.
+                    let _ = penalty_events.send(GamePenaltyEvent {
+                        game_id: game_id.clone(),
+                        event,
+                    });
+                }
+                field_watches.publish(&frame.data);
+                history
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .record(&game_id, &frame);
+                snapshot.store(&frame);
+                black_box
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .record(&game_id, &frame);
+                if let Some(recorder) = recorder
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .as_mut()
+                {
+                    // The first game to forward a frame names the recording.
+                    if !recorder.is_recording() {
+                        recorder.start_recording(game_id.clone());
+                    }
+                    recorder.record_frame(frame.clone());
+                }
+                latency.stamp(frame.sequence, LatencyStage::Recorder);
+                if let Some(sink_queue) = sink_queue.get() {
+                    sink_queue.push(&frame);
+                }
+                #[cfg(feature = "websocket")]
+                websockets.publish(&game_id, &frame);
+                wait::publish(&frames, &frame);
+                let sequence = frame.sequence;
+                if draining {
+                    // Recorded already; a consumer that is not reading loses it.
+                    if tx.try_send(frame).is_ok() {
+                        latency.stamp(sequence, LatencyStage::Forwarder);
+                    }
+                    continue;
+                }
+                tokio::select! {
+                    sent = tx.send(frame) => {
+                        if sent.is_err() {
+                            break;
+                        }
+                        latency.stamp(sequence, LatencyStage::Forwarder);
+                    }
+                    Ok(()) = drain.changed() => draining = true,
+                }
+            }
+            pause.stop();
+            frame_tap.close(frames);
+            freshness.disconnect();
+            health.stop();
+            field_watches.disconnect();
+            history
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .record_connection(&game_id, ConnectionState::Disconnected, telemetry_now_ns());
+        });
+        self.forward_tasks.retain(|(_, task)| !task.is_finished());
+        self.forward_tasks.push((task_game_id, task));
+
+        Ok(rx)
+    }
+
+    /// Start every registered adapter and merge their frames into one stream
+    /// tagged with each frame's game id.
+    ///
+    /// See [`start_monitoring_games`](Self::start_monitoring_games).
+    pub async fn start_monitoring_all(&mut self) -> Result<LabeledReceiver> {
+        let game_ids = self.adapter_ids();
+        let game_ids: Vec<&str> = game_ids.iter().map(String::as_str).collect();
+        self.start_monitoring_games(&game_ids).await
+    }
+
+    /// Start `game_ids` and merge their frames into one stream tagged with
+    /// each frame's normalized game id.
+    ///
+    /// Games are served in turn, so one chatty adapter cannot starve the
+    /// rest. A game that fails to start is skipped and its error kept in
+    /// [`multiplex_failures`](Self::multiplex_failures); the call only fails
+    /// if no game started. Any stream started earlier is stopped first.
+    pub async fn start_monitoring_games(&mut self, game_ids: &[&str]) -> Result<LabeledReceiver> {
+        self.stop_monitoring_all().await?;
+
+        let mut sources = Vec::new();
+        for game_id in game_ids {
+            let game_id = &*self.canonical_game_id(game_id);
+            if sources.iter().any(|(started, _)| started == game_id) {
+                continue;
+            }
+            match self.start_monitoring(game_id).await {
+                Ok(rx) => sources.push((game_id.to_string(), rx)),
+                Err(err) => {
+                    warn!(game_id, error = %err, "Game failed to start for the merged stream");
+                    self.multiplex_failures
+                        .insert(game_id.to_string(), err.to_string());
+                }
+            }
+        }
+        if sources.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No game started for the merged stream ({} failed)",
+                self.multiplex_failures.len()
+            ));
+        }
+
+        let game_ids = sources.iter().map(|(game_id, _)| game_id.clone()).collect();
+        let (task, rx) = multiplex::spawn(sources);
+        self.multiplex = Some(Multiplex { game_ids, task });
+        Ok(rx)
+    }
+
+    /// Games that failed to start in the last
+    /// [`start_monitoring_games`](Self::start_monitoring_games) call, with
+    /// their errors.
+    pub fn multiplex_failures(&self) -> &BTreeMap<String, String> {
+        &self.multiplex_failures
+    }
+
+    /// Stop every game feeding the merged stream and close it. Frames
+    /// already in the channel can still be received.
+    ///
+    /// Every game is stopped even if some fail; the first error is returned.
+    pub async fn stop_monitoring_all(&mut self) -> Result<()> {
+        self.multiplex_failures.clear();
+        let Some(multiplex) = self.multiplex.take() else {
+            return Ok(());
+        };
+        multiplex.task.abort();
+        // The task only ends by finishing or by this abort.
+        let _ = multiplex.task.await;
+
+        let mut first_error = None;
+        for game_id in &multiplex.game_ids {
+            if let Err(err) = self.stop_monitoring(game_id).await {
+                warn!(game_id, error = %err, "Failed to stop a merged stream's game");
+                first_error.get_or_insert(err);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    /// Games that produced a frame within the last `within`, sorted.
+    pub fn active_sources(&self, within: Duration) -> Vec<String> {
+        let now = telemetry_now_ns();
+        let window = u64::try_from(within.as_nanos()).unwrap_or(u64::MAX);
+        let mut active: Vec<String> = self
+            .health
+            .iter()
+            .filter(|(_, health)| {
+                health
+                    .last_frame_ns()
+                    .is_some_and(|last| now.saturating_sub(last) <= window)
+            })
+            .map(|(game_id, _)| game_id.clone())
+            .collect();
+        active.sort_unstable();
+        active
+    }
+
+    /// Watch one field of `game_id`'s telemetry; the receiver only wakes on change.
+    ///
+    /// Watchers of the same selector share a single per-frame extraction. The
+    /// value is `None` until a frame carries the field, whenever the field is
+    /// absent, and after the source disconnects.
+    pub fn watch_field(
+        &mut self,
+        game_id: &str,
+        selector: FieldSelector,
+    ) -> watch::Receiver<Option<TelemetryValue>> {
+        self.field_watches
+            .entry(self.canonical_game_id(game_id).into_owned())
+            .or_default()
+            .watch(selector)
+    }
+
+    /// Watch several fields at once; the receiver holds their values in `selectors` order.
+    ///
+    /// Must be called from within a Tokio runtime.
+    pub fn watch_fields(
+        &mut self,
+        game_id: &str,
+        selectors: &[FieldSelector],
+    ) -> watch::Receiver<Vec<Option<TelemetryValue>>> {
+        let receivers = selectors
+            .iter()
+            .map(|selector| self.watch_field(game_id, selector.clone()))
+            .collect();
+        field_watch::combine(receivers)
+    }
+
+    /// Current freshness of `game_id`, or `None` if it was never monitored.
+    pub fn freshness(&self, game_id: &str) -> Option<Freshness> {
+        self.freshness_status(game_id)
+            .map(|status| status.freshness)
+    }
+
+    /// Current freshness of `game_id` together with its cause.
+    pub fn freshness_status(&self, game_id: &str) -> Option<FreshnessStatus> {
+        self.freshness
+            .get(&*self.canonical_game_id(game_id))
+            .and_then(|channel| channel.current())
+    }
+
+    /// Freshness of every game that has been monitored, sorted by game id.
+    pub fn freshness_snapshot(&self) -> Vec<GameFreshness> {
+        let mut snapshot: Vec<_> = self
+            .freshness
+            .iter()
+            .filter_map(|(game_id, channel)| {
+                channel.current().map(|status| GameFreshness {
+                    game_id: game_id.clone(),
+                    status,
+                })
+            })
+            .collect();
+        snapshot.sort_unstable_by(|a, b| a.game_id.cmp(&b.game_id));
+        snapshot
+    }
+
+    /// Watch `game_id`'s freshness; `None` until monitoring starts.
+    pub fn watch_freshness(&mut self, game_id: &str) -> watch::Receiver<Option<FreshnessStatus>> {
+        self.freshness
+            .entry(self.canonical_game_id(game_id).into_owned())
+            .or_default()
+            .subscribe()
+    }
+
+    /// Override the freshness thresholds of `game_id`.
+    ///
+    /// Takes effect the next time monitoring of the game starts; until then
+    /// defaults derived from the adapter's expected update rate apply.
+    pub fn set_freshness_thresholds(&mut self, game_id: &str, thresholds: FreshnessThresholds) {
+        self.freshness_thresholds
+            .insert(self.canonical_game_id(game_id).into_owned(), thresholds);
+    }
+
+    /// Set how long `game_id` may go without a frame before its health
+    /// reports it disconnected, and whether and how often to restart its
+    /// adapter when the stream dies or goes quiet.
+    ///
+    /// Takes effect the next time monitoring of the game starts. Until a
+    /// config is set the default timeout applies and a game's monitoring
+    /// simply ends with its stream.
+    pub fn set_disconnection_config(&mut self, game_id: &str, config: DisconnectionConfig) {
+        self.disconnection_configs
+            .insert(self.canonical_game_id(game_id).into_owned(), config);
+    }
+
+    /// Check `game_id`'s frames with `validator` before rate limiting,
+    /// instead of the default sanitizing [`TelemetryValidator`].
+    ///
+    /// Takes effect the next time monitoring of the game starts.
+    pub fn set_validator(&mut self, game_id: &str, validator: TelemetryValidator) {
+        self.validators
+            .insert(self.canonical_game_id(game_id).into_owned(), validator);
+    }
+
+    /// Health of every registered adapter, keyed by game id.
+    pub fn health(&self) -> HashMap<String, AdapterHealth> {
+        let rate_limits = self.rate_limit_stats();
+        self.adapters
+            .iter()
+            .map(|(game_id, adapter)| {
+                let channel = self.health.get(game_id);
+                let health = AdapterHealth {
+                    connection_state: channel
+                        .map_or(ConnectionState::Disconnected, |channel| channel.state()),
+                    last_frame_timestamp_ns: channel.and_then(|channel| channel.last_frame_ns()),
+                    last_state_change_ns: channel
+                        .and_then(|channel| channel.last_state_change_ns()),
+                    frames_received: channel.map_or(0, |channel| channel.frames_received()),
+                    frames_dropped_rate_limit: rate_limits
+                        .get(game_id)
+                        .map_or(0, |stats| stats.dropped_count),
+                    last_error: channel.and_then(|channel| channel.last_error()),
+                    reconnect_attempts: channel.map_or(0, |channel| channel.reconnect_attempts()),
+                    expected_update_rate_ms: adapter.expected_update_rate().as_secs_f64() * 1000.0,
+                    observed_rate_hz: channel.and_then(|channel| channel.observed_rate_hz()),
+                    latency_p99_ms: self
+                        .latency
+                        .get(game_id)
+                        .and_then(|probe| probe.report().end_to_end)
+                        .map(|end_to_end| end_to_end.p99_ms()),
+                    frames_sanitized: channel.map_or(0, |channel| channel.frames_sanitized()),
+                    validation_issues: channel.map_or(0, |channel| channel.validation_issues()),
+                    frames_rejected_invalid: channel
+                        .map_or(0, |channel| channel.frames_rejected_invalid()),
+                    frames_deduplicated: channel.map_or(0, |channel| channel.frames_deduplicated()),
+                    stream_quality: self.quality(game_id).map(|quality| quality.summary()),
+                };
+                (game_id.clone(), health)
+            })
+            .collect()
+    }
+
+    /// Per-stage and end-to-end latency of `game_id`'s pipeline over its
+    /// latest frames; `None` until the game has been monitored.
+    pub fn latency_report(&self, game_id: &str) -> Option<LatencyReport> {
+        self.latency
+            .get(&*self.canonical_game_id(game_id))
+            .map(|probe| probe.report())
+    }
+
+    /// Gaps, duplicates, reordering and jitter in the sequence numbers of
+    /// `game_id`'s frames since monitoring last started; `None` until the
+    /// game has been monitored.
+    pub fn quality(&self, game_id: &str) -> Option<StreamQuality> {
+        self.quality
+            .get(&*self.canonical_game_id(game_id))
+            .map(|monitor| {
+                monitor
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .quality()
+            })
+    }
+
+    /// Raw packets and bytes `game_id`'s adapter has received, with rates
+    /// over the last 1, 10 and 60 seconds. `None` for unregistered games and
+    /// adapters that do not count their traffic, such as shared-memory ones.
+    pub fn traffic(&self, game_id: &str) -> Option<TrafficStats> {
+        let adapter = self.adapters.get(&*self.canonical_game_id(game_id))?;
+        Some(adapter.traffic()?.stats())
+    }
+
+    /// Monotonic per-game counters and matrix parity numbers, for
+    /// [`render_prometheus`] or any other exporter. Reading them resets
+    /// nothing.
+    pub fn metrics_snapshot(&self) -> ServiceMetrics {
+        let now_ns = telemetry_now_ns();
+        let games = self
+            .adapters
+            .keys()
+            .chain(self.health.keys())
+            .map(|game_id| {
+                let channel = self.health.get(game_id);
+                let metrics = GameMetrics {
+                    frames_received_total: channel.map_or(0, |channel| channel.frames_received()),
+                    frames_dropped_total: channel
+                        .map_or(0, |channel| channel.frames_dropped_rate_limit()),
+                    reconnect_attempts_total: channel
+                        .map_or(0, |channel| channel.reconnects_total()),
+                    last_frame_age_ms: channel
+                        .and_then(|channel| channel.last_frame_ns())
+                        .map(|last_ns| now_ns.saturating_sub(last_ns) as f64 / 1_000_000.0),
+                    traffic: self.traffic(game_id),
+                };
+                (game_id.clone(), metrics)
+            })
+            .collect();
+        let coverage = self
+            .runtime_coverage_report
+            .as_ref()
+            .zip(self.runtime_bdd_metrics.as_ref())
+            .map(|(report, bdd)| CoverageMetrics::new(&report.metrics(), bdd));
+        ServiceMetrics { games, coverage }
+    }
+
+    /// Receive connection state changes of every game, e.g. `Connected` to
+    /// `Disconnected` once a game goes quiet for its disconnection timeout,
+    /// and the transport switches of games read through a composite adapter.
+    pub fn subscribe_health_events(&self) -> HealthEventReceiver {
+        self.health_subscribers.subscribe()
+    }
+
+    /// Fix the rate limit of `game_id`, overriding the one derived from its
+    /// adapter's expected update rate. Applies immediately to a running game.
+    pub fn set_rate_limit(&mut self, game_id: &str, rate_hz: u32) {
+        self.rate_limits
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .set_limit(&self.canonical_game_id(game_id), rate_hz);
+    }
+
+    /// Rate limit that applies to `game_id`'s frames.
+    pub fn rate_limit_for(&self, game_id: &str) -> u32 {
+        self.rate_limits
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .limit_for(&self.canonical_game_id(game_id))
+    }
+
+    /// Accepted, dropped and burst frame counts of every monitored game.
+    pub fn rate_limit_stats(&self) -> BTreeMap<String, RateLimiterStats> {
+        self.rate_limits
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .snapshot()
+    }
+
+    /// Report whether the breaker guarding `game_id`'s source is open.
+    ///
+    /// An open breaker makes the game [`Freshness::Dead`] until it closes.
+    pub fn report_breaker(&mut self, game_id: &str, open: bool) {
+        self.freshness
+            .entry(self.canonical_game_id(game_id).into_owned())
+            .or_default()
+            .set_breaker_open(open);
+    }
+
+    /// Subscribe to freshness transitions across all monitored games.
+    pub fn subscribe_freshness_events(&self) -> broadcast::Receiver<FreshnessEvent> {
+        self.freshness_events.subscribe()
+    }
+
+    /// Stop forwarding `game_id`'s frames while keeping its transport open.
+    ///
+    /// Frames are drained and counted instead of reaching subscribers, sinks
+    /// and history, and freshness reports [`Freshness::Paused`] rather than
+    /// decaying to stale.
+    pub fn pause_monitoring(&self, game_id: &str) -> Result<(), PauseError> {
+        let game_id = &*self.canonical_game_id(game_id);
+        let gate = self.active_pause_gate(game_id)?;
+        if gate.set_paused(true) {
+            return Err(PauseError::AlreadyPaused(game_id.to_string()));
+        }
+        self.publish_pause_event(game_id, PauseState::Paused, gate);
+        Ok(())
+    }
+
+    /// Resume forwarding a game paused with [`Self::pause_monitoring`].
+    ///
+    /// The adapter is neither restarted nor re-discovered.
+    pub fn resume_monitoring(&self, game_id: &str) -> Result<(), PauseError> {
+        let game_id = &*self.canonical_game_id(game_id);
+        let gate = self.active_pause_gate(game_id)?;
+        if !gate.set_paused(false) {
+            return Err(PauseError::NotPaused(game_id.to_string()));
+        }
+        self.publish_pause_event(game_id, PauseState::Resumed, gate);
+        Ok(())
+    }
+
+    /// Whether `game_id` has a running forwarding task.
+    pub fn is_monitoring(&self, game_id: &str) -> bool {
+        self.active_pause_gate(&self.canonical_game_id(game_id))
+            .is_ok()
+    }
+
+    /// Whether forwarding of `game_id` is currently paused.
+    pub fn is_paused(&self, game_id: &str) -> bool {
+        self.pause_gates
+            .get(&*self.canonical_game_id(game_id))
+            .is_some_and(|gate| gate.is_active() && gate.is_paused())
+    }
+
+    /// Frames of `game_id` dropped while paused since monitoring last started.
+    pub fn paused_frames_dropped(&self, game_id: &str) -> u64 {
+        self.pause_gates
+            .get(&*self.canonical_game_id(game_id))
+            .map_or(0, |gate| gate.dropped())
+    }
+
+    /// Subscribe to pause and resume events across all games.
+    pub fn subscribe_pause_events(&self) -> broadcast::Receiver<PauseEvent> {
+        self.pause_events.subscribe()
+    }
+
+    fn active_pause_gate(&self, game_id: &str) -> Result<&PauseGate, PauseError> {
+        self.pause_gates
+            .get(game_id)
+            .filter(|gate| gate.is_active())
+            .map(Arc::as_ref)
+            .ok_or_else(|| PauseError::NotMonitoring(game_id.to_string()))
+    }
+
+    fn publish_pause_event(&self, game_id: &str, state: PauseState, gate: &PauseGate) {
+        // No subscribers is not an error.
+        let _ = self.pause_events.send(PauseEvent {
+            game_id: game_id.to_string(),
+            state,
+            dropped_frames: gate.dropped(),
+        });
+    }
+
+    /// Resolve with the next frame `game_id` forwards.
+    ///
+    /// The wait subscribes when this is called, so frames forwarded before the
+    /// returned future is first polled still count; dropping the future
+    /// unsubscribes. Other consumers of the game's frames are unaffected.
+    pub fn wait_for_first_frame(
+        &self,
+        game_id: &str,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<TelemetryFrame, WaitError>> + Send + 'static {
+        self.wait_for_condition(game_id, |_| true, timeout)
+    }
+
+    /// Resolve with the first frame from `game_id` whose telemetry satisfies
+    /// `predicate`, e.g. `|t| t.speed_ms > 0.0`.
+    ///
+    /// Fails with [`WaitError::NotMonitored`] right away if the game has no
+    /// forwarding task, and with [`WaitError::Disconnected`] as soon as the
+    /// source ends rather than after `timeout`.
+    pub fn wait_for_condition(
+        &self,
+        game_id: &str,
+        predicate: impl Fn(&NormalizedTelemetry) -> bool + Send + 'static,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<TelemetryFrame, WaitError>> + Send + 'static {
+        let game_id = self.canonical_game_id(game_id).into_owned();
+        wait::wait_for(
+            self.frame_taps.get(&game_id).map(Arc::as_ref),
+            game_id,
+            predicate,
+            timeout,
+        )
+    }
+
+    /// Blocking [`wait_for_condition`](Self::wait_for_condition) for threads
+    /// outside the Tokio runtime, such as poll-based bridge consumers.
+    pub fn wait_for_condition_blocking(
+        &self,
+        game_id: &str,
+        predicate: impl Fn(&NormalizedTelemetry) -> bool + Send + 'static,
+        timeout: Duration,
+    ) -> Result<TelemetryFrame, WaitError> {
+        wait::block_on(self.wait_for_condition(game_id, predicate, timeout))
+    }
+
+    /// Pending waits on `game_id`'s frames.
+    pub fn frame_subscriber_count(&self, game_id: &str) -> usize {
+        self.frame_taps
+            .get(&*self.canonical_game_id(game_id))
+            .map_or(0, |tap| tap.subscriber_count())
     }
 
     /// Stop telemetry monitoring for a specific game.
     pub async fn stop_monitoring(&self, game_id: &str) -> Result<()> {
-        let game_id = normalize_game_id(game_id);
+        let game_id = &*self.canonical_game_id(game_id);
 
         let adapter = self
             .adapters
             .get(game_id)
             .ok_or_else(|| anyhow::anyhow!("No adapter for game: {}", game_id))?;
 
+        if let Some(health) = self.health.get(game_id) {
+            health.request_stop();
+        }
         adapter.stop_monitoring().await
     }
 
-    /// Enable telemetry recording for CI testing.
+    /// Record every forwarded frame into `output_path`, finalizing any
+    /// recording already running. The recording is named after the first
+    /// game to forward a frame.
     pub fn enable_recording(&mut self, output_path: PathBuf) -> Result<()> {
-        self.recorder = Some(TelemetryRecorder::new(output_path)?);
+        self.disable_recording();
+        if let Some(retention) = &self.retention {
+            retention.mark_active(&output_path);
+        }
+        *self.recorder.lock().unwrap_or_else(PoisonError::into_inner) =
+            Some(TelemetryRecorder::new(output_path)?);
         Ok(())
     }
 
-    /// Disable telemetry recording.
+    /// Disable telemetry recording, writing out what was recorded.
     pub fn disable_recording(&mut self) {
-        self.recorder = None;
+        if let Err(err) = self.finalize_recording() {
+            warn!(error = %err, "Failed to finalize the recording");
+        }
+    }
+
+    /// Take the recorder and finalize its file; `None` if recording was not
+    /// enabled.
+    fn finalize_recording(&mut self) -> Result<Option<SavedRecording>> {
+        let Some(mut recorder) = self
+            .recorder
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+        else {
+            return Ok(None);
+        };
+        let saved = recorder.finalize(None);
+        if let Some(retention) = &self.retention {
+            retention.clear_active(recorder.output_path());
+        }
+        saved.map(Some)
+    }
+
+    /// Stop every game and finalize the recording, waiting at most `timeout`
+    /// for the forwarding tasks and adapters to stop.
+    ///
+    /// Frames the adapters queued before the call still pass through the
+    /// pipeline into the recorder, though consumers that are not reading
+    /// may miss them. Monitoring sessions see their streams end. Tasks still
+    /// running at the deadline are aborted. The service stays usable.
+    pub async fn shutdown(&mut self, timeout: Duration) -> ShutdownReport {
+        let deadline = Instant::now() + timeout;
+        let mut report = ShutdownReport::default();
+        if let Some(task) = self.detection_task.take() {
+            task.abort();
+        }
+
+        let tasks = std::mem::take(&mut self.forward_tasks);
+        let mut games: Vec<String> = tasks
+            .iter()
+            .filter(|(_, task)| !task.is_finished())
+            .map(|(game_id, _)| game_id.clone())
+            .collect();
+        games.sort_unstable();
+        games.dedup();
+        for game_id in &games {
+            if let Some(health) = self.health.get(game_id) {
+                health.request_stop();
+            }
+        }
+        self.shutdown.trigger();
+
+        for game_id in &games {
+            let Some(adapter) = self.adapters.get(game_id) else {
+                continue;
+            };
+            match tokio::time::timeout_at(deadline, adapter.stop_monitoring()).await {
+                Ok(Ok(())) => {}
+                Ok(Err(err)) => {
+                    warn!(game_id = %game_id, error = %err, "Failed to stop an adapter on shutdown");
+                    report
+                        .stop_failures
+                        .insert(game_id.clone(), err.to_string());
+                }
+                Err(_) => report.timed_out = true,
+            }
+        }
+        for (game_id, mut task) in tasks {
+            if tokio::time::timeout_at(deadline, &mut task).await.is_err() {
+                warn!(game_id = %game_id, "Forwarding task did not stop in time; aborting it");
+                task.abort();
+                report.timed_out = true;
+            }
+        }
+        if let Some(multiplex) = self.multiplex.take() {
+            multiplex.task.abort();
+        }
+
+        report.games_stopped = games;
+        report.frames_drained = self.shutdown.take_drained();
+        match self.finalize_recording() {
+            Ok(saved) => report.recording = saved,
+            Err(err) => {
+                warn!(error = %err, "Failed to finalize the recording on shutdown");
+                report.recording_error = Some(err.to_string());
+            }
+        }
+        report
+    }
+
+    /// Write every raw payload `game_id`'s adapter receives into a capture
+    /// file at `path`, rotating to `path.1`, `path.2`, ... within `caps`.
+    ///
+    /// Replaces a capture already running for the game. Adapters that do not
+    /// support capture accept the sink but never write to it.
+    pub fn enable_raw_capture(
+        &mut self,
+        game_id: &str,
+        path: PathBuf,
+        caps: RawCaptureCaps,
+    ) -> Result<Arc<RawCaptureSink>> {
+        let game_id = self.canonical_game_id(game_id).into_owned();
+        let adapter = self
+            .adapters
+            .get(&game_id)
+            .ok_or_else(|| anyhow::anyhow!("No adapter for game: {}", game_id))?;
+        let sink = Arc::new(RawCaptureSink::create(game_id.as_str(), path, caps)?);
+        adapter.set_raw_capture(Some(Arc::clone(&sink)));
+        if let Some(previous) = self.raw_captures.insert(game_id, Arc::clone(&sink))
+            && let Err(error) = previous.flush()
+        {
+            warn!(game_id = %previous.game_id(), error = %error, "Failed to flush raw capture");
+        }
+        Ok(sink)
+    }
+
+    /// Stop capturing `game_id`'s raw payloads and flush the capture file.
+    /// Returns the capture's final counters, or `None` if none was running.
+    pub fn disable_raw_capture(&mut self, game_id: &str) -> Result<Option<RawCaptureStats>> {
+        let game_id = &*self.canonical_game_id(game_id);
+        let Some(sink) = self.raw_captures.remove(game_id) else {
+            return Ok(None);
+        };
+        if let Some(adapter) = self.adapters.get(game_id) {
+            adapter.set_raw_capture(None);
+        }
+        sink.flush()?;
+        Ok(Some(sink.stats()))
+    }
+
+    /// Keep the last `capacity` of every monitored game's forwarded frames in
+    /// memory for [`Self::save_recent`], at most
+    /// [`racing_wheel_telemetry_recorder::MAX_RING_FRAMES`] per game.
+    /// Re-enabling discards what was buffered.
+    pub fn enable_black_box(&mut self, capacity: Duration) {
+        self.black_box
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .enable(capacity);
+    }
+
+    /// Stop buffering frames and drop those kept so far.
+    pub fn disable_black_box(&mut self) {
+        self.black_box
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .disable();
+    }
+
+    /// Write the black box's frames into the directory `path`, as one
+    /// `<game_id>.json` recording per game with buffered frames.
+    ///
+    /// Monitoring carries on while the files are written; frames arriving
+    /// meanwhile stay in the buffer for the next save.
+    pub fn save_recent(&self, path: PathBuf) -> Result<SaveReport> {
+        let snapshot = {
+            let black_box = self
+                .black_box
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            if !black_box.is_enabled() {
+                return Err(anyhow::anyhow!("Black box recording is not enabled"));
+            }
+            black_box.snapshot()
+        };
+
+        std::fs::create_dir_all(&path)?;
+        let mut report = SaveReport::default();
+        for (game_id, frames) in snapshot {
+            let file = path.join(format!("{game_id}.json"));
+            let recording = TelemetryRecording::from_frames(
+                game_id,
+                frames,
+                Some("Black box capture".to_string()),
+            );
+            report.recordings.push(recording.save(&file)?);
+        }
+        Ok(report)
+    }
+
+    /// Run retention cleanup every `period` until the service is dropped.
+    pub fn start_retention(&mut self, period: Duration) -> Result<()> {
+        let retention = self
+            .retention
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No retention manager configured"))?;
+        if let Some(task) = self
+            .retention_task
+            .replace(retention.spawn_periodic(period))
+        {
+            task.abort();
+        }
+        Ok(())
+    }
+
+    /// List the artifacts the next retention pass would delete.
+    pub fn dry_run_cleanup(&self) -> Result<Vec<CleanupCandidate>> {
+        match &self.retention {
+            Some(retention) => retention.dry_run_cleanup(),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Outcome of the most recent retention pass, for health reporting.
+    pub fn last_retention_cleanup(&self) -> Option<CleanupSummary> {
+        self.retention
+            .as_ref()
+            .and_then(|retention| retention.last_cleanup())
+    }
+
+    /// Subscribe to retention cleanup events, if a retention manager is attached.
+    pub fn subscribe_retention_events(&self) -> Option<broadcast::Receiver<RetentionEvent>> {
+        self.retention
+            .as_ref()
+            .map(|retention| retention.subscribe())
     }
 
     /// Get list of supported games registered at runtime.
@@ -197,7 +1689,7 @@ impl TelemetryService {
 
     /// Check if a game is currently running.
     pub async fn is_game_running(&self, game_id: &str) -> Result<bool> {
-        let game_id = normalize_game_id(game_id);
+        let game_id = &*self.canonical_game_id(game_id);
 
         let adapter = self
             .adapters
@@ -207,6 +1699,59 @@ impl TelemetryService {
         adapter.is_game_running().await
     }
 
+    /// Ask every registered adapter at once whether its game is running and
+    /// return the running ones, sorted.
+    ///
+    /// Each adapter gets at most `timeout`; one that errors or takes longer
+    /// counts as not running.
+    pub async fn detect_running_games(&self, timeout: Duration) -> Vec<String> {
+        detection::probe(self.adapter_handles(), timeout).await
+    }
+
+    /// Poll every registered adapter each `interval` and report games that
+    /// start or stop running, debounced by [`GameDetector`].
+    ///
+    /// Adapters registered later are not polled. The loop runs until the
+    /// receiver is dropped, the loop is restarted or the service is dropped.
+    pub fn spawn_detection_loop(
+        &mut self,
+        interval: Duration,
+    ) -> mpsc::Receiver<GameDetectedEvent> {
+        let interval = interval.max(MIN_DETECTION_INTERVAL);
+        let probe_timeout = interval.min(DEFAULT_PROBE_TIMEOUT);
+        let adapters = self.adapter_handles();
+        let (tx, rx) = mpsc::channel(DETECTION_EVENT_CAPACITY);
+        let task = tokio::spawn(async move {
+            let mut detector = GameDetector::new();
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            loop {
+                tokio::select! {
+                    _ = tx.closed() => return,
+                    _ = ticker.tick() => {}
+                }
+                let running = detection::probe(adapters.iter().cloned(), probe_timeout).await;
+                for event in detector.observe(&running, telemetry_now_ns()) {
+                    debug!(game_id = %event.game_id, detected = event.detected, "Game detection changed");
+                    if tx.send(event).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        if let Some(previous) = self.detection_task.replace(task) {
+            previous.abort();
+        }
+        rx
+    }
+
+    fn adapter_handles(&self) -> Vec<(String, Arc<dyn TelemetryAdapter>)> {
+        self.adapters
+            .iter()
+            .map(|(game_id, adapter)| (game_id.clone(), Arc::clone(adapter)))
+            .collect()
+    }
+
     /// Return the current support matrix snapshot, if loaded.
     pub fn support_matrix(&self) -> Option<&GameSupportMatrix> {
         self.support_matrix.as_ref()
@@ -220,9 +1765,17 @@ impl TelemetryService {
             .unwrap_or_default()
     }
 
+    /// Transport, default port, plugin requirement and supported fields of
+    /// `game_id` from the loaded support matrix.
+    pub fn game_capabilities(&self, game_id: &str) -> Option<GameCapabilities> {
+        self.support_matrix
+            .as_ref()
+            .and_then(|matrix| matrix.capabilities(&self.canonical_game_id(game_id)))
+    }
+
     /// Return whether a game id is present in the loaded support matrix.
     pub fn is_game_matrix_supported(&self, game_id: &str) -> bool {
-        let game_id = normalize_game_id(game_id);
+        let game_id = &*self.canonical_game_id(game_id);
 
         self.support_matrix
             .as_ref()
@@ -242,6 +1795,271 @@ impl TelemetryService {
         ids
     }
 
+    /// UDP listen mode of every registered adapter that receives over UDP,
+    /// sorted by game id.
+    pub fn listen_modes(&self) -> Vec<(String, ListenMode)> {
+        let mut modes: Vec<_> = self
+            .adapters
+            .iter()
+            .filter_map(|(game_id, adapter)| {
+                adapter.listen_mode().map(|mode| (game_id.clone(), mode))
+            })
+            .collect();
+        modes.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        modes
+    }
+
+    /// Listen raw on UDP `port` for `window` and explain what arrives there.
+    ///
+    /// A game already monitoring the port in broadcast or multicast mode keeps
+    /// running and the inspector shares its stream. A unicast monitor holds
+    /// the port exclusively and yields [`InspectError::PortBusy`].
+    pub async fn inspect_port(
+        &self,
+        port: u16,
+        window: Duration,
+    ) -> Result<InspectionReport, InspectError> {
+        let mut selected: Vec<&str> = self
+            .adapters
+            .iter()
+            .filter(|(_, adapter)| adapter.udp_port() == Some(port))
+            .map(|(game_id, _)| game_id.as_str())
+            .collect();
+        selected.sort_unstable();
+        let active = selected.iter().copied().find(|game_id| {
+            self.pause_gates
+                .get(*game_id)
+                .is_some_and(|gate| gate.is_active())
+        });
+        let listen_mode = active
+            .or(selected.first().copied())
+            .and_then(|game_id| self.adapters.get(game_id)?.listen_mode())
+            .unwrap_or_default();
+        if let Some(game_id) = active
+            && !listen_mode.shares_port()
+        {
+            return Err(InspectError::PortBusy {
+                port,
+                game_id: game_id.to_string(),
+            });
+        }
+
+        let socket = AdapterNetworkConfig::new(port)
+            .with_listen_mode(listen_mode)
+            .bind()
+            .map_err(|err| InspectError::Bind {
+                port,
+                reason: err.to_string(),
+            })?;
+        let captured = inspector::capture(&socket, port, window).await?;
+        let report = inspector::classify(
+            port,
+            selected.into_iter().map(str::to_string).collect(),
+            captured,
+            self.adapters.values().map(AsRef::as_ref),
+        );
+        self.inspections
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(port, report.clone());
+        Ok(report)
+    }
+
+    /// Check `game_id`'s telemetry path end to end, one step at a time.
+    ///
+    /// The steps run in [`SelfTestStep`] order: support matrix, adapter and
+    /// config writer are present; the config under `options.game_path` is
+    /// applied; the adapter's UDP port can be bound; and a frame arrives
+    /// within `options.frame_timeout`. A step whose prerequisites failed is
+    /// skipped rather than failed, so the first failure is the one to fix.
+    /// A game this service already monitors is observed in place; any other
+    /// is started on its own for the wait and stopped again.
+    pub async fn self_test(&self, game_id: &str, options: SelfTestOptions) -> SelfTestReport {
+        let game_id = self.canonical_game_id(game_id).into_owned();
+        let adapter = self.adapters.get(&game_id).cloned();
+        let writer = config_writer_factories()
+            .iter()
+            .find(|(writer_id, _)| *writer_id == game_id)
+            .map(|(_, factory)| factory());
+        let monitoring = self.is_monitoring(&game_id);
+        let mut report = SelfTestReport::new(game_id.clone());
+
+        let in_matrix = self
+            .support_matrix
+            .as_ref()
+            .is_none_or(|matrix| matrix.has_game_id(&game_id));
+        report.check_registry(in_matrix, adapter.is_some(), writer.is_some());
+
+        match (&writer, &options.game_path) {
+            (Some(writer), Some(game_path)) => {
+                report.check_config(writer.as_ref(), game_path, options.config.as_ref());
+            }
+            (None, _) => report.push(
+                SelfTestStep::Config,
+                StepOutcome::Skipped,
+                "no config writer to validate with",
+            ),
+            (_, None) => report.push(
+                SelfTestStep::Config,
+                StepOutcome::Skipped,
+                "no game path given to validate",
+            ),
+        }
+
+        let Some(adapter) = adapter else {
+            for step in [SelfTestStep::Transport, SelfTestStep::FirstFrame] {
+                report.push(step, StepOutcome::Skipped, "no adapter to listen with");
+            }
+            return report;
+        };
+        let listen_mode = adapter.listen_mode().unwrap_or_default();
+        match adapter.udp_port() {
+            Some(port) if monitoring && !listen_mode.shares_port() => report.push(
+                SelfTestStep::Transport,
+                StepOutcome::Passed,
+                format!("UDP port {port} is held by this service's monitor of `{game_id}`"),
+            ),
+            Some(port) => report.check_port(port, listen_mode),
+            None => report.push(
+                SelfTestStep::Transport,
+                StepOutcome::Skipped,
+                format!("`{game_id}` does not listen on UDP; nothing to bind"),
+            ),
+        }
+
+        if let Some(conflict) = &report.port_conflict {
+            let hint = format!(
+                "the adapter cannot listen while port {} is taken",
+                conflict.port
+            );
+            report.push(SelfTestStep::FirstFrame, StepOutcome::Skipped, hint);
+            return report;
+        }
+        let frame = if monitoring {
+            self.wait_for_first_frame(&game_id, options.frame_timeout)
+                .await
+                .map_err(|err| err.to_string())
+        } else {
+            self_test::first_frame(adapter.as_ref(), options.frame_timeout).await
+        };
+        report.record_frame(frame);
+        report
+    }
+
+    /// Latest inspection of every inspected port, sorted by port, for
+    /// diagnostic bundles.
+    pub fn inspection_reports(&self) -> Vec<InspectionReport> {
+        self.inspections
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    /// Units `game_id`'s adapter declares for its source, if it declares any.
+    pub fn unit_manifest(&self, game_id: &str) -> Option<UnitManifest> {
+        self.adapters.get(game_id)?.unit_manifest()
+    }
+
+    /// Unit manifest of every registered adapter that declares one, sorted
+    /// by game id.
+    pub fn unit_manifests(&self) -> Vec<(String, UnitManifest)> {
+        let mut manifests: Vec<_> = self
+            .adapters
+            .iter()
+            .filter_map(|(game_id, adapter)| {
+                adapter
+                    .unit_manifest()
+                    .map(|manifest| (game_id.clone(), manifest))
+            })
+            .collect();
+        manifests.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        manifests
+    }
+
+    /// Downsampled buckets of `field` for `game_id`; see [`HistoryStore::history`].
+    pub fn history(
+        &self,
+        game_id: &str,
+        field: HistoryField,
+        resolution: Duration,
+        range: Duration,
+    ) -> Vec<Bucket> {
+        self.history
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .history(&self.canonical_game_id(game_id), field, resolution, range)
+    }
+
+    /// Recent history of every monitored game for diagnostic bundles.
+    pub fn history_summary(&self) -> HistorySummary {
+        self.history
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .summary()
+    }
+
+    /// Register a downstream sink that receives every monitored frame.
+    ///
+    /// Sinks run on a thread of their own, fed through a queue of
+    /// [`SINK_QUEUE_CAPACITY`] frames, so they never hold up forwarding.
+    pub fn register_sink(&self, registration: SinkRegistration) -> Result<()> {
+        self.sinks.register(registration)?;
+        if self.sink_queue.get().is_none() {
+            let queue = SinkQueue::spawn(Arc::clone(&self.sinks))?;
+            // A racing registration may have started one first; keep that.
+            let _ = self.sink_queue.set(queue);
+        }
+        Ok(())
+    }
+
+    /// Remove a previously registered sink.
+    pub fn unregister_sink(&self, name: &str) -> bool {
+        self.sinks.unregister(name)
+    }
+
+    /// Per-sink encode time and byte counters for status displays.
+    pub fn sink_costs(&self) -> Vec<SinkCost> {
+        self.sinks.sink_costs()
+    }
+
+    /// Frames that skipped every sink because the sinks fell behind.
+    pub fn sink_frames_dropped(&self) -> u64 {
+        self.sinks.frames_dropped()
+    }
+
+    /// Subscribe to sink events such as automatic encoding switches.
+    pub fn subscribe_sink_events(&self) -> tokio::sync::broadcast::Receiver<SinkEvent> {
+        self.sinks.subscribe()
+    }
+
+    /// Latest forwarded frame of every monitored game.
+    pub fn snapshot_cache(&self) -> Arc<TelemetrySnapshotCache> {
+        Arc::clone(&self.snapshots)
+    }
+
+    /// Answer latest-frame requests on `endpoint`; see [`snapshot`] for the
+    /// protocol.
+    pub async fn serve_snapshots(
+        &self,
+        endpoint: SnapshotEndpoint,
+    ) -> Result<SnapshotServerHandle> {
+        snapshot::serve(Arc::clone(&self.snapshots), endpoint).await
+    }
+
+    /// Serve forwarded frames to WebSocket clients on `bind`; see
+    /// [`websocket`] for the protocol.
+    #[cfg(feature = "websocket")]
+    pub async fn serve_websocket(&self, bind: SocketAddr, opts: WsOptions) -> Result<WsHandle> {
+        websocket::serve(Arc::clone(&self.websockets), bind, opts).await
+    }
+
+    /// Subscribe to penalty / track-limits changes across all monitored games.
+    pub fn subscribe_penalty_events(&self) -> broadcast::Receiver<GamePenaltyEvent> {
+        self.penalty_events.subscribe()
+    }
+
     /// Return runtime coverage report used during startup parity checks.
     pub fn runtime_coverage_report(&self) -> Option<&RuntimeCoverageReport> {
         self.runtime_coverage_report.as_ref()
@@ -253,6 +2071,31 @@ impl TelemetryService {
     }
 }
 
+impl Drop for TelemetryService {
+    fn drop(&mut self) {
+        let running: BTreeSet<&str> = self
+            .forward_tasks
+            .iter()
+            .filter(|(_, task)| !task.is_finished())
+            .map(|(game_id, _)| game_id.as_str())
+            .collect();
+        if !running.is_empty() {
+            warn!(
+                games = ?running,
+                "TelemetryService dropped without shutdown while games were monitored; \
+                 their tasks keep running and frames they still queue are not recorded"
+            );
+        }
+        self.disable_recording();
+        if let Some(task) = self.retention_task.take() {
+            task.abort();
+        }
+        if let Some(task) = self.detection_task.take() {
+            task.abort();
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::TelemetryService;
@@ -268,6 +2111,23 @@ mod tests {
         assert!(!service.matrix_game_ids().is_empty());
     }
 
+    #[test]
+    fn telemetry_service_reports_game_capabilities() -> Result<()> {
+        let service = TelemetryService::new();
+
+        let Some(capabilities) = service.game_capabilities("f1_2025") else {
+            return Err(anyhow::anyhow!("no capabilities for f1_2025"));
+        };
+        assert_eq!(capabilities.default_port, Some(20777));
+        assert!(service.game_capabilities("not_a_game").is_none());
+
+        let Some(report) = service.runtime_coverage_report() else {
+            return Err(anyhow::anyhow!("no coverage report"));
+        };
+        assert!(report.field_coverage_ok(), "{:?}", report.field_mismatches);
+        Ok(())
+    }
+
     #[test]
     fn telemetry_service_exposes_runtime_bdd_metrics() {
         let service = TelemetryService::new();
@@ -627,4 +2487,30 @@ mod tests {
         assert!(service.runtime_coverage_report().is_none());
         assert!(service.runtime_bdd_metrics().is_none());
     }
+
+    #[test]
+    fn capture_shm_snapshot_uses_the_registered_source() -> Result<()> {
+        use racing_wheel_telemetry_adapters::shm_snapshot::{
+            FakeShmSource, ShmCaptureTrigger, ShmPage,
+        };
+
+        let mut service = TelemetryService::new();
+        assert!(service.capture_shm_snapshot("iracing").is_err());
+        service.register_shm_source(
+            "iracing",
+            Box::new(FakeShmSource::new(vec![
+                ShmPage::new("telemetry", vec![1u8; 32]),
+                ShmPage::new("session", b"   UserName: Jane\n".to_vec()),
+            ])),
+        );
+
+        let snapshot = service.capture_shm_snapshot("iracing")?;
+        assert_eq!(snapshot.adapter.game_id, "iracing");
+        assert_eq!(snapshot.trigger, ShmCaptureTrigger::Manual);
+        assert_eq!(
+            snapshot.page("session").map(|page| page.bytes.as_slice()),
+            Some(b"   UserName: ****\n".as_slice())
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,115 @@
+//! Applying a changed game support matrix to a running service.
+//!
+//! `reload_support_matrix` diffs the new matrix against the one the service
+//! was built or last reloaded with: games the matrix gained get their adapter
+//! registered, and games it lost are unregistered. A lost game that is still
+//! being monitored keeps its adapter until a later reload finds it idle, so
+//! no session is cut off mid-race.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use racing_wheel_telemetry_support::parse_matrix_yaml;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{info, warn};
+
+use crate::TelemetryService;
+
+/// What a `reload_support_matrix` call changed. Every list is sorted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatrixReloadReport {
+    /// Games whose adapter was registered.
+    pub added: Vec<String>,
+    /// Games whose adapter was unregistered.
+    pub removed: Vec<String>,
+    /// Games no longer in the matrix but still monitored; a later reload
+    /// unregisters them once monitoring has stopped.
+    pub deferred: Vec<String>,
+    /// Games new to the matrix that have no adapter implementation.
+    pub unsupported: Vec<String>,
+}
+
+impl MatrixReloadReport {
+    /// Whether the reload left the adapter registry as it was.
+    pub fn is_unchanged(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.deferred.is_empty()
+    }
+}
+
+/// Reload `service` from the YAML matrix at `path` whenever the file's
+/// contents change, checking every `interval`.
+///
+/// The first check applies the file as found, and while a reload has
+/// deferred removals it is retried every check until they go through. A file
+/// that is missing or fails to parse is logged and the current matrix kept.
+/// The task runs until aborted.
+pub fn watch_matrix_file(
+    service: Arc<Mutex<TelemetryService>>,
+    path: impl Into<PathBuf>,
+    interval: Duration,
+) -> JoinHandle<()> {
+    let path = path.into();
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut last_contents = None;
+        let mut unreadable = false;
+        let mut retry = None;
+        loop {
+            ticker.tick().await;
+            let matrix = match tokio::fs::read_to_string(&path).await {
+                Ok(contents) if last_contents.as_ref() == Some(&contents) => {
+                    retry.take().map(|matrix| (matrix, true))
+                }
+                Ok(contents) => {
+                    unreadable = false;
+                    let parsed = parse_matrix_yaml(&contents);
+                    last_contents = Some(contents);
+                    match parsed {
+                        Ok(matrix) => Some((matrix, false)),
+                        Err(err) => {
+                            warn!(path = %path.display(), error = %err, "Ignoring unparsable support matrix");
+                            retry = None;
+                            continue;
+                        }
+                    }
+                }
+                Err(err) => {
+                    if !unreadable {
+                        warn!(path = %path.display(), error = %err, "Support matrix file unreadable");
+                        unreadable = true;
+                    }
+                    retry.take().map(|matrix| (matrix, true))
+                }
+            };
+            let Some((matrix, retrying)) = matrix else {
+                continue;
+            };
+
+            match service.lock().await.reload_support_matrix(matrix.clone()) {
+                Ok(report) => {
+                    // A retry is only news once the deferred games go.
+                    let deferred_news = !retrying && !report.deferred.is_empty();
+                    if !report.added.is_empty() || !report.removed.is_empty() || deferred_news {
+                        info!(
+                            path = %path.display(),
+                            added = ?report.added,
+                            removed = ?report.removed,
+                            deferred = ?report.deferred,
+                            "Support matrix reloaded"
+                        );
+                    }
+                    retry = (!report.deferred.is_empty()).then_some(matrix);
+                }
+                Err(err) => {
+                    warn!(path = %path.display(), error = %err, "Support matrix reload rejected");
+                    retry = None;
+                }
+            }
+        }
+    })
+}
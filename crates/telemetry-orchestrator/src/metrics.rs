@@ -0,0 +1,267 @@
+//! Service counters in Prometheus text exposition format.
+//!
+//! [`ServiceMetrics`] is a plain snapshot, so it can be served as JSON as
+//! well; [`render_prometheus`] turns it into the text format Prometheus
+//! scrapes without pulling a metrics framework into the service. Per-game
+//! counters come from the game's health channel, which lives as long as the
+//! service, so they only ever grow between snapshots, across restarts of the
+//! game's adapter included.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Write};
+
+use racing_wheel_telemetry_adapters::TrafficStats;
+use racing_wheel_telemetry_bdd_metrics::RuntimeBddMatrixMetrics;
+use racing_wheel_telemetry_integration::{RegistryCoverageMetrics, RuntimeCoverageMetrics};
+use serde::{Deserialize, Serialize};
+
+/// Prefix of every metric [`render_prometheus`] emits.
+pub const METRIC_PREFIX: &str = "openracing_telemetry_";
+
+/// Snapshot returned by `TelemetryService::metrics_snapshot`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ServiceMetrics {
+    /// Counters of every registered or previously monitored game, by game id.
+    pub games: BTreeMap<String, GameMetrics>,
+    /// Matrix/registry parity; `None` when the service runs without a matrix.
+    pub coverage: Option<CoverageMetrics>,
+}
+
+/// Counters of one game.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GameMetrics {
+    /// Frames received from the adapter, including dropped ones.
+    pub frames_received_total: u64,
+    /// Frames dropped for exceeding the game's rate limit.
+    pub frames_dropped_total: u64,
+    /// Adapter restarts attempted after the stream died.
+    pub reconnect_attempts_total: u64,
+    /// Time since the latest frame; `None` before the first one.
+    pub last_frame_age_ms: Option<f64>,
+    /// Raw packets and bytes the game's adapter received, with windowed
+    /// rates; `None` for adapters that do not count their traffic.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub traffic: Option<TrafficStats>,
+}
+
+/// Support matrix parity of the adapter and config writer registries.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CoverageMetrics {
+    pub matrix_game_count: usize,
+    pub adapter: RegistryMetrics,
+    pub writer: RegistryMetrics,
+    /// Both registries satisfy their coverage policies.
+    pub parity_ok: bool,
+}
+
+/// One registry compared with the support matrix.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegistryMetrics {
+    pub registry_game_count: usize,
+    pub missing_count: usize,
+    pub extra_count: usize,
+    pub matrix_coverage_ratio: f64,
+    pub registry_coverage_ratio: f64,
+    /// The registry satisfies its BDD matrix parity policy.
+    pub bdd_parity_ok: bool,
+}
+
+impl CoverageMetrics {
+    pub fn new(coverage: &RuntimeCoverageMetrics, bdd: &RuntimeBddMatrixMetrics) -> Self {
+        Self {
+            matrix_game_count: coverage.matrix_game_count,
+            adapter: RegistryMetrics::new(&coverage.adapter, bdd.adapter.parity_ok),
+            writer: RegistryMetrics::new(&coverage.writer, bdd.writer.parity_ok),
+            parity_ok: coverage.parity_ok,
+        }
+    }
+}
+
+impl RegistryMetrics {
+    fn new(coverage: &RegistryCoverageMetrics, bdd_parity_ok: bool) -> Self {
+        Self {
+            registry_game_count: coverage.registry_game_count,
+            missing_count: coverage.missing_count,
+            extra_count: coverage.extra_count,
+            matrix_coverage_ratio: coverage.matrix_coverage_ratio,
+            registry_coverage_ratio: coverage.registry_coverage_ratio,
+            bdd_parity_ok,
+        }
+    }
+}
+
+/// Name, help text and value of a metric family sampled once per game or
+/// registry.
+type Family<T, V> = (&'static str, &'static str, fn(&T) -> V);
+
+/// Render `metrics` in the Prometheus text exposition format, one family per
+/// metric with its `# HELP` and `# TYPE` lines, and games in id order.
+pub fn render_prometheus(metrics: &ServiceMetrics) -> String {
+    let mut out = String::new();
+    let games = &metrics.games;
+
+    let counters: [Family<GameMetrics, u64>; 3] = [
+        (
+            "frames_received_total",
+            "Frames received from the game's adapter.",
+            |g| g.frames_received_total,
+        ),
+        (
+            "frames_dropped_total",
+            "Frames dropped by the game's rate limit.",
+            |g| g.frames_dropped_total,
+        ),
+        (
+            "reconnect_attempts_total",
+            "Adapter restarts attempted after the stream died.",
+            |g| g.reconnect_attempts_total,
+        ),
+    ];
+    for (name, help, value) in counters {
+        family(&mut out, name, "counter", help);
+        for (game_id, game) in games {
+            sample(&mut out, name, Some(("game_id", game_id)), value(game));
+        }
+    }
+    family(
+        &mut out,
+        "last_frame_age_ms",
+        "gauge",
+        "Milliseconds since the game's latest frame.",
+    );
+    for (game_id, game) in games {
+        if let Some(age_ms) = game.last_frame_age_ms {
+            sample(
+                &mut out,
+                "last_frame_age_ms",
+                Some(("game_id", game_id)),
+                Float(age_ms),
+            );
+        }
+    }
+    // Windowed rates stay in the JSON snapshot; Prometheus derives its own
+    // from the totals.
+    let traffic: [Family<TrafficStats, u64>; 2] = [
+        (
+            "raw_packets_received_total",
+            "Raw packets the game's adapter received.",
+            |t| t.total_packets,
+        ),
+        (
+            "raw_bytes_received_total",
+            "Raw payload bytes the game's adapter received.",
+            |t| t.total_bytes,
+        ),
+    ];
+    for (name, help, value) in traffic {
+        family(&mut out, name, "counter", help);
+        for (game_id, game) in games {
+            if let Some(traffic) = &game.traffic {
+                sample(&mut out, name, Some(("game_id", game_id)), value(traffic));
+            }
+        }
+    }
+
+    let Some(coverage) = &metrics.coverage else {
+        return out;
+    };
+    family(
+        &mut out,
+        "matrix_games",
+        "gauge",
+        "Games in the support matrix.",
+    );
+    sample(&mut out, "matrix_games", None, coverage.matrix_game_count);
+
+    let registries: [Family<RegistryMetrics, Float>; 6] = [
+        (
+            "registry_games",
+            "Games with a registered implementation.",
+            |m| Float(m.registry_game_count as f64),
+        ),
+        (
+            "registry_missing_games",
+            "Matrix games without a registered implementation.",
+            |m| Float(m.missing_count as f64),
+        ),
+        (
+            "registry_extra_games",
+            "Registered implementations of games not in the matrix.",
+            |m| Float(m.extra_count as f64),
+        ),
+        (
+            "matrix_coverage_ratio",
+            "Share of matrix games the registry implements.",
+            |m| Float(m.matrix_coverage_ratio),
+        ),
+        (
+            "registry_coverage_ratio",
+            "Share of registered games present in the matrix.",
+            |m| Float(m.registry_coverage_ratio),
+        ),
+        (
+            "bdd_parity_ok",
+            "1 if the registry satisfies its BDD parity policy.",
+            |m| Float(f64::from(u8::from(m.bdd_parity_ok))),
+        ),
+    ];
+    for (name, help, value) in registries {
+        family(&mut out, name, "gauge", help);
+        for (registry, m) in [("adapter", &coverage.adapter), ("writer", &coverage.writer)] {
+            sample(&mut out, name, Some(("registry", registry)), value(m));
+        }
+    }
+    family(
+        &mut out,
+        "parity_ok",
+        "gauge",
+        "1 if both registries satisfy their coverage policies.",
+    );
+    sample(&mut out, "parity_ok", None, u8::from(coverage.parity_ok));
+    out
+}
+
+fn family(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {METRIC_PREFIX}{name} {help}");
+    let _ = writeln!(out, "# TYPE {METRIC_PREFIX}{name} {kind}");
+}
+
+fn sample(out: &mut String, name: &str, label: Option<(&str, &str)>, value: impl Display) {
+    let _ = match label {
+        Some((label, label_value)) => writeln!(
+            out,
+            "{METRIC_PREFIX}{name}{{{label}=\"{}\"}} {value}",
+            escape_label_value(label_value)
+        ),
+        None => writeln!(out, "{METRIC_PREFIX}{name} {value}"),
+    };
+}
+
+/// Escape a label value as the exposition format requires: backslash,
+/// double quote and line feed.
+fn escape_label_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// A sample value spelled as the exposition format expects non-finite ones.
+struct Float(f64);
+
+impl Display for Float {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            v if v.is_nan() => f.write_str("NaN"),
+            v if v == f64::INFINITY => f.write_str("+Inf"),
+            v if v == f64::NEG_INFINITY => f.write_str("-Inf"),
+            v => write!(f, "{v}"),
+        }
+    }
+}
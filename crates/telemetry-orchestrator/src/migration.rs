@@ -0,0 +1,340 @@
+//! Offline rewriting of persisted game ids after an integration is renamed.
+//!
+//! At runtime a retired id keeps working because [`normalize_game_id`]
+//! consults [`GAME_ID_MIGRATIONS`]. Data written before the rename still
+//! carries the old id, so [`migrate_persisted_data`] walks a data directory
+//! and rewrites it in place:
+//!
+//! - JSON documents (profiles, service state, overlays, JSON recordings) are
+//!   parsed and re-serialized with the id fields replaced.
+//! - JSON-lines files (audit logs, sink output) are rewritten line by line;
+//!   lines that are not JSON are kept verbatim.
+//! - Binary recordings (`TelemetryRecording::to_binary`) only have their
+//!   length-prefixed metadata header re-encoded; the frame payload is copied
+//!   through untouched.
+//!
+//! Anything else is left alone and listed in
+//! [`MigrationReport::unrecognized`]. Every modified file is first copied to a
+//! sibling backup ending in [`BACKUP_SUFFIX`], and files currently holding a
+//! [`FileLock`] are skipped. Running the migration again is a no-op.
+//!
+//! [`normalize_game_id`]: racing_wheel_telemetry_support::normalize_game_id
+//! [`GAME_ID_MIGRATIONS`]: racing_wheel_telemetry_support::GAME_ID_MIGRATIONS
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use openracing_file_lock::{FileLock, LOCK_SUFFIX};
+use racing_wheel_telemetry_support::GameIdMigration;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Suffix appended to a file's name for the copy taken before rewriting it.
+pub const BACKUP_SUFFIX: &str = ".pre-migration";
+/// Suffix of the temporary file a rewrite is staged in before the rename.
+const STAGING_SUFFIX: &str = ".migrating";
+
+/// Object fields holding a single game id.
+const GAME_ID_FIELDS: &[&str] = &["game_id", "gameId", "game"];
+/// Object fields holding a list of game ids.
+const GAME_ID_LIST_FIELDS: &[&str] = &["game_ids", "configured"];
+
+/// Whether [`migrate_persisted_data`] writes its changes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationMode {
+    /// Rewrite files, backing each one up first.
+    #[default]
+    Apply,
+    /// Report what would change without touching any file.
+    DryRun,
+}
+
+/// How a migrated file was rewritten.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RewriteKind {
+    /// Whole JSON document re-serialized.
+    JsonDocument,
+    /// JSON-lines file rewritten entry by entry.
+    JsonLines,
+    /// Binary recording whose metadata header was re-encoded.
+    RecordingHeader,
+}
+
+/// A file containing retired game ids.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigratedFile {
+    /// The file that was (or, in a dry run, would be) rewritten.
+    pub path: PathBuf,
+    /// How it was rewritten.
+    pub kind: RewriteKind,
+    /// Occurrences replaced, keyed by `"old -> new"`.
+    pub rewrites: BTreeMap<String, usize>,
+    /// Copy of the original contents; `None` in a dry run.
+    pub backup: Option<PathBuf>,
+}
+
+/// A file the migration did not rewrite, and why.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkippedFile {
+    /// The file left alone.
+    pub path: PathBuf,
+    /// Why it was not migrated.
+    pub reason: String,
+}
+
+/// Outcome of [`migrate_persisted_data`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationReport {
+    /// Mode the migration ran in.
+    pub mode: MigrationMode,
+    /// Number of files inspected.
+    pub files_scanned: usize,
+    /// Files containing retired ids.
+    pub migrated: Vec<MigratedFile>,
+    /// Files in a format the migration does not understand.
+    pub unrecognized: Vec<SkippedFile>,
+    /// Recognized files skipped because another process holds their lock.
+    pub locked: Vec<PathBuf>,
+}
+
+impl MigrationReport {
+    /// Total number of ids replaced across all files.
+    pub fn ids_rewritten(&self) -> usize {
+        self.migrated
+            .iter()
+            .flat_map(|file| file.rewrites.values())
+            .sum()
+    }
+
+    /// Whether nothing needed rewriting.
+    pub fn is_noop(&self) -> bool {
+        self.migrated.is_empty()
+    }
+}
+
+/// Rewrite every retired game id under `data_dir` according to `migrations`.
+///
+/// The walk is recursive and skips lock files and earlier backups. A missing
+/// `data_dir` yields an empty report.
+pub fn migrate_persisted_data(
+    data_dir: impl AsRef<Path>,
+    migrations: &[GameIdMigration],
+    mode: MigrationMode,
+) -> Result<MigrationReport> {
+    let mut files = Vec::new();
+    collect_files(data_dir.as_ref(), &mut files)?;
+    files.sort();
+
+    let mut report = MigrationReport {
+        mode,
+        ..MigrationReport::default()
+    };
+    for path in files {
+        report.files_scanned += 1;
+        let bytes =
+            fs::read(&path).with_context(|| format!("failed to read {}", path.display()))?;
+        let mut rewrites = Rewrites::new(migrations);
+        let Some((kind, rewritten)) = rewrite_contents(&path, &bytes, &mut rewrites) else {
+            report.unrecognized.push(SkippedFile {
+                path,
+                reason: "not JSON, JSON lines or a binary recording".to_string(),
+            });
+            continue;
+        };
+        if rewrites.counts.is_empty() {
+            continue;
+        }
+        if FileLock::lock_path_for(&path).exists() {
+            report.locked.push(path);
+            continue;
+        }
+        let backup = match mode {
+            MigrationMode::DryRun => None,
+            MigrationMode::Apply => Some(write_with_backup(&path, &bytes, &rewritten)?),
+        };
+        report.migrated.push(MigratedFile {
+            path,
+            kind,
+            rewrites: rewrites.counts,
+            backup,
+        });
+    }
+    Ok(report)
+}
+
+/// Replacement counts for one file.
+struct Rewrites<'a> {
+    migrations: &'a [GameIdMigration],
+    counts: BTreeMap<String, usize>,
+}
+
+impl<'a> Rewrites<'a> {
+    fn new(migrations: &'a [GameIdMigration]) -> Self {
+        Self {
+            migrations,
+            counts: BTreeMap::new(),
+        }
+    }
+
+    /// Replace `id` in place if it names a retired game id.
+    fn apply(&mut self, id: &mut String) {
+        if let Some(migration) = self
+            .migrations
+            .iter()
+            .find(|migration| migration.matches(id.trim()))
+        {
+            let key = format!("{} -> {}", id.trim(), migration.new);
+            *self.counts.entry(key).or_default() += 1;
+            *id = migration.new.to_string();
+        }
+    }
+
+    fn apply_value(&mut self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, field) in map.iter_mut() {
+                    match field {
+                        Value::String(id) if GAME_ID_FIELDS.contains(&key.as_str()) => {
+                            self.apply(id);
+                        }
+                        Value::Array(ids) if GAME_ID_LIST_FIELDS.contains(&key.as_str()) => {
+                            for entry in ids.iter_mut() {
+                                match entry {
+                                    Value::String(id) => self.apply(id),
+                                    other => self.apply_value(other),
+                                }
+                            }
+                        }
+                        other => self.apply_value(other),
+                    }
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.apply_value(item)),
+            _ => {}
+        }
+    }
+}
+
+/// Rewritten contents of a recognized file, or `None` if the format is unknown.
+fn rewrite_contents(
+    path: &Path,
+    bytes: &[u8],
+    rewrites: &mut Rewrites<'_>,
+) -> Option<(RewriteKind, Vec<u8>)> {
+    let extension = path.extension().and_then(|ext| ext.to_str());
+    if extension == Some("jsonl") {
+        return rewrite_json_lines(bytes, rewrites).map(|out| (RewriteKind::JsonLines, out));
+    }
+    if let Ok(mut document) = serde_json::from_slice::<Value>(bytes) {
+        rewrites.apply_value(&mut document);
+        let mut out = serde_json::to_vec_pretty(&document).ok()?;
+        if bytes.ends_with(b"\n") {
+            out.push(b'\n');
+        }
+        return Some((RewriteKind::JsonDocument, out));
+    }
+    rewrite_recording_header(bytes, rewrites).map(|out| (RewriteKind::RecordingHeader, out))
+}
+
+fn rewrite_json_lines(bytes: &[u8], rewrites: &mut Rewrites<'_>) -> Option<Vec<u8>> {
+    let text = std::str::from_utf8(bytes).ok()?;
+    let mut out = String::with_capacity(text.len());
+    for line in text.split_inclusive('\n') {
+        let (entry, newline) = match line.strip_suffix('\n') {
+            Some(entry) => (entry, "\n"),
+            None => (line, ""),
+        };
+        match serde_json::from_str::<Value>(entry) {
+            Ok(mut value) => {
+                rewrites.apply_value(&mut value);
+                out.push_str(&serde_json::to_string(&value).ok()?);
+            }
+            Err(_) => out.push_str(entry),
+        }
+        out.push_str(newline);
+    }
+    Some(out.into_bytes())
+}
+
+/// Re-encode the metadata header of a `TelemetryRecording::to_binary` file.
+fn rewrite_recording_header(bytes: &[u8], rewrites: &mut Rewrites<'_>) -> Option<Vec<u8>> {
+    let (len_bytes, rest) = bytes.split_first_chunk::<4>()?;
+    let meta_len = usize::try_from(u32::from_le_bytes(*len_bytes)).ok()?;
+    let (meta, frames) = (rest.get(..meta_len)?, rest.get(meta_len..)?);
+    let mut metadata = serde_json::from_slice::<Value>(meta).ok()?;
+    metadata.get("game_id")?.as_str()?;
+    rewrites.apply_value(&mut metadata);
+
+    let meta = serde_json::to_vec(&metadata).ok()?;
+    let mut out = Vec::with_capacity(4 + meta.len() + frames.len());
+    out.extend_from_slice(&u32::try_from(meta.len()).ok()?.to_le_bytes());
+    out.extend_from_slice(&meta);
+    out.extend_from_slice(frames);
+    Some(out)
+}
+
+/// Back up `original`, then replace `path` with `rewritten` via a rename.
+fn write_with_backup(path: &Path, original: &[u8], rewritten: &[u8]) -> Result<PathBuf> {
+    let backup = free_backup_path(path);
+    fs::write(&backup, original)
+        .with_context(|| format!("failed to back up {}", path.display()))?;
+    let staging = sibling_with_suffix(path, STAGING_SUFFIX);
+    fs::write(&staging, rewritten)
+        .with_context(|| format!("failed to write {}", staging.display()))?;
+    fs::rename(&staging, path).with_context(|| format!("failed to replace {}", path.display()))?;
+    Ok(backup)
+}
+
+/// First backup name for `path` that does not exist yet, so earlier backups
+/// from a previous migration are kept.
+fn free_backup_path(path: &Path) -> PathBuf {
+    let first = sibling_with_suffix(path, BACKUP_SUFFIX);
+    (1..)
+        .map(|n| match n {
+            1 => first.clone(),
+            n => sibling_with_suffix(path, &format!("{BACKUP_SUFFIX}.{n}")),
+        })
+        .find(|candidate| !candidate.exists())
+        .unwrap_or(first)
+}
+
+fn sibling_with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+fn is_backup_or_lock(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| {
+        let name = name.to_string_lossy();
+        name.ends_with(LOCK_SUFFIX)
+            || name.ends_with(STAGING_SUFFIX)
+            || name.contains(BACKUP_SUFFIX)
+    })
+}
+
+fn collect_files(dir: &Path, out: &mut Vec<PathBuf>) -> Result<()> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(error) => {
+            return Err(error).with_context(|| format!("failed to list {}", dir.display()));
+        }
+    };
+    for entry in entries {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let path = entry.path();
+        if file_type.is_dir() {
+            collect_files(&path, out)?;
+        } else if file_type.is_file() && !is_backup_or_lock(&path) {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,188 @@
+//! Handles owning one game's monitored stream.
+//!
+//! A bare receiver from `start_monitoring` leaves stopping to a separate
+//! `stop_monitoring` call, and dropping it only stops the forwarding task
+//! once the next frame fails to send. A [`MonitoringSession`] owns the
+//! receiver and stops the game when told to or when dropped, and keeps
+//! counters for its lifetime.
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use racing_wheel_telemetry_adapters::{TelemetryAdapter, TelemetryFrame, TelemetryReceiver};
+use tracing::warn;
+
+use crate::health::HealthChannel;
+
+/// Error returned by `start_monitoring_session`.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SessionError {
+    /// Another session for the game is still open.
+    #[error("game `{0}` already has an active monitoring session")]
+    AlreadyActive(String),
+}
+
+/// Counters of a [`MonitoringSession`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SessionStats {
+    /// Frames handed out by [`MonitoringSession::recv`].
+    pub frames_received: u64,
+    /// Frames the forwarding task discarded while paused or over the rate
+    /// limit.
+    pub frames_dropped: u64,
+    /// Time since the session started, or its length once stopped.
+    pub duration: Duration,
+}
+
+/// Counters the forwarding task updates for its session.
+#[derive(Debug, Default)]
+pub(crate) struct ForwardCounters {
+    dropped: AtomicU64,
+}
+
+impl ForwardCounters {
+    pub(crate) fn record_drop(&self) {
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Games with an open session.
+pub(crate) type ActiveSessions = Arc<Mutex<HashSet<String>>>;
+
+/// Claim `game_id` in `active`, failing if a session already holds it.
+pub(crate) fn claim(active: &ActiveSessions, game_id: &str) -> Result<(), SessionError> {
+    if active
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(game_id.to_string())
+    {
+        Ok(())
+    } else {
+        Err(SessionError::AlreadyActive(game_id.to_string()))
+    }
+}
+
+/// Release a claim taken with [`claim`].
+pub(crate) fn release(active: &ActiveSessions, game_id: &str) {
+    active
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .remove(game_id);
+}
+
+/// What stopping a session needs, taken on the first stop.
+struct Running {
+    adapter: Arc<dyn TelemetryAdapter>,
+    health: Arc<HealthChannel>,
+    active: ActiveSessions,
+}
+
+/// One game's monitored stream, stopped on [`stop`](Self::stop) or drop.
+pub struct MonitoringSession {
+    game_id: String,
+    receiver: TelemetryReceiver,
+    counters: Arc<ForwardCounters>,
+    frames_received: u64,
+    started: Instant,
+    stopped_after: Option<Duration>,
+    running: Option<Running>,
+}
+
+impl MonitoringSession {
+    pub(crate) fn new(
+        game_id: String,
+        receiver: TelemetryReceiver,
+        counters: Arc<ForwardCounters>,
+        adapter: Arc<dyn TelemetryAdapter>,
+        health: Arc<HealthChannel>,
+        active: ActiveSessions,
+    ) -> Self {
+        Self {
+            game_id,
+            receiver,
+            counters,
+            frames_received: 0,
+            started: Instant::now(),
+            stopped_after: None,
+            running: Some(Running {
+                adapter,
+                health,
+                active,
+            }),
+        }
+    }
+
+    /// Normalized id of the monitored game.
+    pub fn game_id(&self) -> &str {
+        &self.game_id
+    }
+
+    /// Next frame, or `None` once the stream has ended or the session was
+    /// stopped and the buffered frames are drained.
+    pub async fn recv(&mut self) -> Option<TelemetryFrame> {
+        let frame = self.receiver.recv().await?;
+        self.frames_received += 1;
+        Some(frame)
+    }
+
+    pub fn stats(&self) -> SessionStats {
+        SessionStats {
+            frames_received: self.frames_received,
+            frames_dropped: self.counters.dropped.load(Ordering::Relaxed),
+            duration: self.stopped_after.unwrap_or_else(|| self.started.elapsed()),
+        }
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.running.is_none()
+    }
+
+    /// Stop the game's forwarding task and adapter. Frames already buffered
+    /// can still be received. Stopping again does nothing.
+    pub async fn stop(&mut self) -> anyhow::Result<()> {
+        let Some(running) = self.halt() else {
+            return Ok(());
+        };
+        running.adapter.stop_monitoring().await
+    }
+
+    /// Close the stream and release the game; returns what is left to stop.
+    fn halt(&mut self) -> Option<Running> {
+        let running = self.running.take()?;
+        self.stopped_after = Some(self.started.elapsed());
+        running.health.request_stop();
+        // Ends the forwarding task, which drops the adapter's source.
+        self.receiver.close();
+        release(&running.active, &self.game_id);
+        Some(running)
+    }
+}
+
+impl Drop for MonitoringSession {
+    fn drop(&mut self) {
+        let Some(running) = self.halt() else {
+            return;
+        };
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let game_id = self.game_id.clone();
+        runtime.spawn(async move {
+            if let Err(err) = running.adapter.stop_monitoring().await {
+                warn!(game_id, error = %err, "Failed to stop an abandoned monitoring session");
+            }
+        });
+    }
+}
+
+impl std::fmt::Debug for MonitoringSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MonitoringSession")
+            .field("game_id", &self.game_id)
+            .field("stats", &self.stats())
+            .field("stopped", &self.is_stopped())
+            .finish()
+    }
+}
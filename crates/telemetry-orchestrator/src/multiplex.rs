@@ -0,0 +1,118 @@
+//! Merging several games' telemetry into one stream labelled by game id.
+//!
+//! Each game is started as with `start_monitoring`, and the per-game
+//! receivers are handed to one merge task. The task takes a frame from each
+//! game in turn, so a chatty adapter backs up its own channel rather than
+//! crowding the others out of the merged one.
+
+use std::future::poll_fn;
+use std::task::{Context, Poll};
+
+use racing_wheel_telemetry_adapters::{TelemetryFrame, TelemetryReceiver};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Capacity of the merged channel returned by `start_monitoring_all`.
+pub const MULTIPLEX_CHANNEL_CAPACITY: usize = 256;
+
+/// A frame tagged with the normalized id of the game that produced it.
+pub type LabeledFrame = (String, TelemetryFrame);
+
+/// Receiver of a merged stream of several games' frames.
+pub type LabeledReceiver = mpsc::Receiver<LabeledFrame>;
+
+/// The games feeding a running merge task.
+pub(crate) struct Multiplex {
+    pub(crate) game_ids: Vec<String>,
+    pub(crate) task: JoinHandle<()>,
+}
+
+/// Start merging `sources` into a new channel. The channel closes once every
+/// source has closed or the returned task is aborted.
+pub(crate) fn spawn(
+    sources: Vec<(String, TelemetryReceiver)>,
+) -> (JoinHandle<()>, LabeledReceiver) {
+    let (tx, rx) = mpsc::channel(MULTIPLEX_CHANNEL_CAPACITY);
+    (tokio::spawn(merge(sources, tx)), rx)
+}
+
+async fn merge(mut sources: Vec<(String, TelemetryReceiver)>, tx: mpsc::Sender<LabeledFrame>) {
+    let mut next = 0;
+    while let Some((index, frame)) = poll_fn(|cx| poll_round_robin(&mut sources, next, cx)).await {
+        next = index + 1;
+        if tx.send((sources[index].0.clone(), frame)).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Poll the sources from `start` onwards, wrapping around, and return the
+/// first frame found with its source index. Closed sources are removed;
+/// `None` means all of them closed.
+fn poll_round_robin(
+    sources: &mut Vec<(String, TelemetryReceiver)>,
+    start: usize,
+    cx: &mut Context<'_>,
+) -> Poll<Option<(usize, TelemetryFrame)>> {
+    if sources.is_empty() {
+        return Poll::Ready(None);
+    }
+    let mut index = start % sources.len();
+    let mut pending = 0;
+    while pending < sources.len() {
+        match sources[index].1.poll_recv(cx) {
+            Poll::Ready(Some(frame)) => return Poll::Ready(Some((index, frame))),
+            Poll::Ready(None) => {
+                sources.remove(index);
+                if sources.is_empty() {
+                    return Poll::Ready(None);
+                }
+                index %= sources.len();
+            }
+            Poll::Pending => {
+                pending += 1;
+                index = (index + 1) % sources.len();
+            }
+        }
+    }
+    Poll::Pending
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use racing_wheel_telemetry_adapters::NormalizedTelemetry;
+
+    type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+    fn frame(sequence: u64) -> TelemetryFrame {
+        TelemetryFrame::new(NormalizedTelemetry::default(), 0, sequence, 0)
+    }
+
+    #[tokio::test]
+    async fn backlogged_sources_are_interleaved() -> TestResult {
+        let (chatty_tx, chatty_rx) = mpsc::channel(64);
+        let (quiet_tx, quiet_rx) = mpsc::channel(64);
+        for sequence in 0..40 {
+            chatty_tx.send(frame(sequence)).await?;
+        }
+        for sequence in 0..2 {
+            quiet_tx.send(frame(sequence)).await?;
+        }
+        drop((chatty_tx, quiet_tx));
+
+        let (_task, mut merged) = spawn(vec![
+            ("chatty".to_string(), chatty_rx),
+            ("quiet".to_string(), quiet_rx),
+        ]);
+        let mut order = Vec::new();
+        while let Some((game_id, _)) = merged.recv().await {
+            order.push(game_id);
+        }
+
+        assert_eq!(order.len(), 42);
+        assert_eq!(order[..4], ["chatty", "quiet", "chatty", "quiet"]);
+        assert!(order[4..].iter().all(|game_id| game_id == "chatty"));
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+//! Pausing a game's forwarding without tearing down its transport.
+//!
+//! Re-running `start_monitoring` means a new socket bind, shared-memory map
+//! or handshake, which some games only accept at certain points. Pausing
+//! keeps the adapter's source open and has the forwarding task drain and
+//! count its frames instead of passing them to subscribers, sinks and
+//! history, so resuming is instant.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+/// Error returned by `pause_monitoring` and `resume_monitoring`.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PauseError {
+    /// The game has no running forwarding task.
+    #[error("game `{0}` is not being monitored")]
+    NotMonitoring(String),
+    /// The game was already paused.
+    #[error("monitoring of `{0}` is already paused")]
+    AlreadyPaused(String),
+    /// The game was not paused.
+    #[error("monitoring of `{0}` is not paused")]
+    NotPaused(String),
+}
+
+/// Whether a [`PauseEvent`] paused or resumed monitoring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PauseState {
+    /// Frames are now drained and dropped.
+    Paused,
+    /// Frames are forwarded again.
+    Resumed,
+}
+
+/// A pause or resume of one game, published on the orchestrator's bus.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PauseEvent {
+    /// Normalized game id.
+    pub game_id: String,
+    /// What happened.
+    pub state: PauseState,
+    /// Frames dropped while paused since monitoring started, as of the event.
+    pub dropped_frames: u64,
+}
+
+/// Per-game pause state shared between the service and its forwarding task.
+#[derive(Debug, Default)]
+pub(crate) struct PauseGate {
+    active: AtomicBool,
+    paused: AtomicBool,
+    dropped: AtomicU64,
+}
+
+impl PauseGate {
+    /// Mark a new forwarding task as running, unpaused and with no drops.
+    pub(crate) fn start(&self) {
+        self.paused.store(false, Ordering::Relaxed);
+        self.dropped.store(0, Ordering::Relaxed);
+        self.active.store(true, Ordering::Release);
+    }
+
+    /// Mark the forwarding task as finished.
+    pub(crate) fn stop(&self) {
+        self.active.store(false, Ordering::Release);
+        self.paused.store(false, Ordering::Relaxed);
+    }
+
+    pub(crate) fn is_active(&self) -> bool {
+        self.active.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Set the paused flag, returning its previous value.
+    pub(crate) fn set_paused(&self, paused: bool) -> bool {
+        self.paused.swap(paused, Ordering::Relaxed)
+    }
+
+    pub(crate) fn record_drop(&self) {
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
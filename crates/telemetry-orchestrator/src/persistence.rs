@@ -0,0 +1,225 @@
+//! Carry intermittently transmitted fields forward into frames that omit them.
+//!
+//! Several games only send identifiers and session data on a slow side channel
+//! (ACC's graphics page, F1's 2 Hz session packet), so most frames arrive with
+//! `car_id`, `track_id` or penalty state unset. [`FieldPersistence`] remembers
+//! the last value of each configured field and fills the gap until the value
+//! is older than the field's max age. Every filled-in field is listed, with its
+//! age, under [`PERSISTED_FIELDS_KEY`] so consumers can tell carried data from
+//! fresh data.
+
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use racing_wheel_telemetry_adapters::{
+    ExtendedKey, NormalizedTelemetry, PenaltyState, TelemetryFrame, TelemetryValue,
+};
+
+use crate::field_watch::EXTENDED_PREFIX;
+use crate::transforms::FrameTransform;
+
+/// Extended key annotating which fields of a frame were carried forward.
+///
+/// The value is a string of `field=age_s` pairs separated by `;`, e.g.
+/// `track_id=0.250;extended:tire_compound=1.000`. Use [`persisted_fields`] to
+/// parse it.
+pub const PERSISTED_FIELDS_KEY: &str = ExtendedKey::PERSISTED_FIELDS.name;
+
+/// An optional part of [`NormalizedTelemetry`] that can be carried forward.
+///
+/// `session_id` is deliberately absent: it defines the session boundary the
+/// transform chain resets on.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PersistedField {
+    /// [`NormalizedTelemetry::car_id`].
+    CarId,
+    /// [`NormalizedTelemetry::track_id`].
+    TrackId,
+    /// [`NormalizedTelemetry::penalties`].
+    Penalties,
+    /// A key of the `extended` map.
+    Extended(String),
+}
+
+impl PersistedField {
+    /// Car and track identifiers, which usually arrive together.
+    pub const IDENTITY: &'static [PersistedField] =
+        &[PersistedField::CarId, PersistedField::TrackId];
+
+    /// Name used in the [`PERSISTED_FIELDS_KEY`] annotation.
+    pub fn label(&self) -> String {
+        match self {
+            Self::CarId => "car_id".to_string(),
+            Self::TrackId => "track_id".to_string(),
+            Self::Penalties => "penalties".to_string(),
+            Self::Extended(key) => format!("{EXTENDED_PREFIX}{key}"),
+        }
+    }
+
+    fn read(&self, telemetry: &NormalizedTelemetry) -> Option<Snapshot> {
+        match self {
+            Self::CarId => telemetry.car_id.clone().map(Snapshot::Text),
+            Self::TrackId => telemetry.track_id.clone().map(Snapshot::Text),
+            Self::Penalties => telemetry.penalties.map(Snapshot::Penalties),
+            Self::Extended(key) => telemetry.extended.get(key).cloned().map(Snapshot::Value),
+        }
+    }
+
+    fn write(&self, telemetry: &mut NormalizedTelemetry, snapshot: Snapshot) {
+        match (self, snapshot) {
+            (Self::CarId, Snapshot::Text(id)) => telemetry.car_id = Some(id),
+            (Self::TrackId, Snapshot::Text(id)) => telemetry.track_id = Some(id),
+            (Self::Penalties, Snapshot::Penalties(state)) => telemetry.penalties = Some(state),
+            (Self::Extended(key), Snapshot::Value(value)) => {
+                telemetry.extended.insert(key.clone(), value);
+            }
+            // `read` and `write` pair variants one to one.
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Snapshot {
+    Text(String),
+    Penalties(PenaltyState),
+    Value(TelemetryValue),
+}
+
+#[derive(Debug)]
+struct Rule {
+    field: PersistedField,
+    max_age: Duration,
+    last: Option<(Snapshot, u64)>,
+}
+
+/// Frame transform carrying absent optional fields forward from earlier frames.
+///
+/// Register it with
+/// [`TelemetryService::register_field_persistence`](crate::TelemetryService::register_field_persistence)
+/// so it runs ahead of every other transform: enrichment and scripts then see
+/// continuous values, while adapter-side validation has already run on the
+/// original frame. Ages are measured on [`TelemetryFrame::timestamp_ns`]; a
+/// timestamp going backwards is treated as a new source and clears all carried
+/// values, as do session changes.
+#[derive(Debug, Default)]
+pub struct FieldPersistence {
+    rules: Vec<Rule>,
+    last_timestamp_ns: Option<u64>,
+}
+
+impl FieldPersistence {
+    /// Create a transform that carries nothing yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Carry `field` forward for at most `max_age` after it was last seen.
+    ///
+    /// Configuring the same field twice keeps the later max age.
+    pub fn persist(mut self, field: PersistedField, max_age: Duration) -> Self {
+        match self.rules.iter_mut().find(|rule| rule.field == field) {
+            Some(rule) => rule.max_age = max_age,
+            None => self.rules.push(Rule {
+                field,
+                max_age,
+                last: None,
+            }),
+        }
+        self
+    }
+
+    /// Carry every field of `group` forward with one shared max age.
+    pub fn persist_group(self, group: &[PersistedField], max_age: Duration) -> Self {
+        group.iter().cloned().fold(self, |persistence, field| {
+            persistence.persist(field, max_age)
+        })
+    }
+
+    /// Configured fields and their max ages, in registration order.
+    pub fn fields(&self) -> impl Iterator<Item = (&PersistedField, Duration)> {
+        self.rules.iter().map(|rule| (&rule.field, rule.max_age))
+    }
+}
+
+impl FrameTransform for FieldPersistence {
+    fn name(&self) -> &str {
+        "field_persistence"
+    }
+
+    fn apply(&mut self, frame: &mut TelemetryFrame) {
+        let now = frame.timestamp_ns;
+        if self.last_timestamp_ns.is_some_and(|last| now < last) {
+            self.reset();
+        }
+        self.last_timestamp_ns = Some(now);
+
+        let mut annotation = String::new();
+        for rule in &mut self.rules {
+            if let Some(fresh) = rule.field.read(&frame.data) {
+                rule.last = Some((fresh, now));
+                continue;
+            }
+            let Some((snapshot, seen_ns)) = &rule.last else {
+                continue;
+            };
+            let age = Duration::from_nanos(now.saturating_sub(*seen_ns));
+            if age > rule.max_age {
+                rule.last = None;
+                continue;
+            }
+            rule.field.write(&mut frame.data, snapshot.clone());
+            if !annotation.is_empty() {
+                annotation.push(';');
+            }
+            let _ = write!(
+                annotation,
+                "{}={:.3}",
+                rule.field.label(),
+                age.as_secs_f64()
+            );
+        }
+
+        if annotation.is_empty() {
+            return;
+        }
+        // A second persistence stage appends rather than replace the first's list.
+        match frame.data.extended.get_mut(PERSISTED_FIELDS_KEY) {
+            Some(TelemetryValue::String(existing)) if !existing.is_empty() => {
+                existing.push(';');
+                existing.push_str(&annotation);
+            }
+            _ => {
+                frame.data.extended.insert(
+                    PERSISTED_FIELDS_KEY.to_string(),
+                    TelemetryValue::String(annotation),
+                );
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.last_timestamp_ns = None;
+        for rule in &mut self.rules {
+            rule.last = None;
+        }
+    }
+}
+
+/// Fields of `telemetry` that were carried forward, with how old each value is.
+///
+/// Empty for a frame whose optional fields are all fresh.
+pub fn persisted_fields(telemetry: &NormalizedTelemetry) -> Vec<(String, Duration)> {
+    let Some(TelemetryValue::String(annotation)) = telemetry.extended.get(PERSISTED_FIELDS_KEY)
+    else {
+        return Vec::new();
+    };
+    annotation
+        .split(';')
+        .filter_map(|entry| {
+            let (field, age) = entry.rsplit_once('=')?;
+            let age = Duration::try_from_secs_f64(age.parse().ok()?).ok()?;
+            Some((field.to_string(), age))
+        })
+        .collect()
+}
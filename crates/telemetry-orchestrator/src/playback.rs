@@ -0,0 +1,250 @@
+//! Replaying a recorded session as if the game were live.
+//!
+//! A [`PlaybackAdapter`] wraps a file written by `TelemetryRecorder` behind
+//! the ordinary [`TelemetryAdapter`] interface, so a dashboard or sink under
+//! test sees the service's normal stream without a game running. Frames are
+//! sent at their recorded spacing, scaled by [`PlaybackSpeed`], stamped with
+//! the current time and numbered on from the previous frame, also across
+//! loops. Playback needs a file, so it is never part of `adapter_factories`;
+//! register one explicitly with `TelemetryService::register_adapter`.
+
+use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use racing_wheel_telemetry_adapters::{
+    NormalizedTelemetry, PacketMatch, TelemetryAdapter, TelemetryFrame, TelemetryReceiver,
+    telemetry_now_ns,
+};
+use racing_wheel_telemetry_recorder::{TelemetryRecorder, TelemetryRecording};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tracing::{debug, warn};
+
+/// Prepended to the recorded game id to form a playback adapter's id.
+pub const PLAYBACK_GAME_ID_PREFIX: &str = "playback:";
+
+/// Capacity of the channel a playback adapter sends frames on.
+const PLAYBACK_CHANNEL_CAPACITY: usize = 64;
+/// Update rate reported for recordings too short to measure one.
+const DEFAULT_PLAYBACK_INTERVAL: Duration = Duration::from_millis(16);
+
+/// How quickly a recording is replayed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlaybackSpeed {
+    /// Recorded spacing divided by this factor; `2.0` plays twice as fast.
+    Multiplier(f64),
+    /// No waiting between frames beyond the consumer's backpressure.
+    AsFastAsPossible,
+}
+
+impl Default for PlaybackSpeed {
+    fn default() -> Self {
+        Self::Multiplier(1.0)
+    }
+}
+
+/// What playback does after the last frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlaybackEnd {
+    /// Close the stream, as a game exiting would.
+    #[default]
+    Stop,
+    /// Start again from the first frame, one average frame interval later.
+    Loop,
+}
+
+/// Options for a [`PlaybackAdapter`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PlaybackConfig {
+    pub speed: PlaybackSpeed,
+    pub at_end: PlaybackEnd,
+}
+
+/// Adapter replaying a `TelemetryRecorder` recording.
+///
+/// The raw payload of a replayed frame is its telemetry as JSON, the encoding
+/// the recording stores it in, so [`TelemetryAdapter::normalize`] of that
+/// payload gives back the recorded [`NormalizedTelemetry`].
+pub struct PlaybackAdapter {
+    game_id: String,
+    recording: Arc<TelemetryRecording>,
+    config: PlaybackConfig,
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl PlaybackAdapter {
+    /// Load the recording at `path` for replay.
+    pub fn open(path: impl AsRef<Path>, config: PlaybackConfig) -> Result<Self> {
+        Self::from_recording(TelemetryRecorder::load_recording(path)?, config)
+    }
+
+    /// Replay an already loaded recording. Fails if it has no frames or the
+    /// speed multiplier is not a positive, finite number.
+    pub fn from_recording(recording: TelemetryRecording, config: PlaybackConfig) -> Result<Self> {
+        if recording.frames.is_empty() {
+            return Err(anyhow::anyhow!("Recording has no frames to play back"));
+        }
+        if let PlaybackSpeed::Multiplier(factor) = config.speed
+            && !(factor.is_finite() && factor > 0.0)
+        {
+            return Err(anyhow::anyhow!(
+                "Invalid playback speed multiplier: {}",
+                factor
+            ));
+        }
+        Ok(Self {
+            game_id: format!("{PLAYBACK_GAME_ID_PREFIX}{}", recording.metadata.game_id),
+            recording: Arc::new(recording),
+            config,
+            task: Mutex::new(None),
+        })
+    }
+
+    pub fn recording(&self) -> &TelemetryRecording {
+        &self.recording
+    }
+
+    pub fn config(&self) -> PlaybackConfig {
+        self.config
+    }
+
+    /// Mean recorded spacing between consecutive frames.
+    fn recorded_interval(&self) -> Duration {
+        let frames = &self.recording.frames;
+        match (frames.first(), frames.last()) {
+            (Some(first), Some(last)) if frames.len() > 1 => {
+                let span = last.timestamp_ns.saturating_sub(first.timestamp_ns);
+                Duration::from_nanos(span / (frames.len() as u64 - 1))
+            }
+            _ => DEFAULT_PLAYBACK_INTERVAL,
+        }
+    }
+}
+
+#[async_trait]
+impl TelemetryAdapter for PlaybackAdapter {
+    fn game_id(&self) -> &str {
+        &self.game_id
+    }
+
+    async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let (tx, rx) = mpsc::channel(PLAYBACK_CHANNEL_CAPACITY);
+        let task = tokio::spawn(replay(
+            Arc::clone(&self.recording),
+            self.config,
+            self.recorded_interval(),
+            tx,
+        ));
+        if let Some(previous) = self
+            .task
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .replace(task)
+        {
+            previous.abort();
+        }
+        Ok(rx)
+    }
+
+    async fn stop_monitoring(&self) -> Result<()> {
+        if let Some(task) = self
+            .task
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+        {
+            task.abort();
+        }
+        Ok(())
+    }
+
+    fn normalize(&self, raw: &[u8]) -> Result<NormalizedTelemetry> {
+        Ok(serde_json::from_slice(raw)?)
+    }
+
+    fn expected_update_rate(&self) -> Duration {
+        match self.config.speed {
+            PlaybackSpeed::Multiplier(factor) => {
+                Duration::try_from_secs_f64(self.recorded_interval().as_secs_f64() / factor)
+                    .unwrap_or(DEFAULT_PLAYBACK_INTERVAL)
+            }
+            // Leaves the service's default rate limit in charge.
+            PlaybackSpeed::AsFastAsPossible => Duration::ZERO,
+        }
+    }
+
+    async fn is_game_running(&self) -> Result<bool> {
+        Ok(true)
+    }
+
+    /// Replayed frames never arrive on the wire.
+    fn recognize_packet(&self, _raw: &[u8]) -> Option<PacketMatch> {
+        None
+    }
+}
+
+/// Send `recording`'s frames on `tx`, each due at its recorded offset from
+/// the first one scaled by the playback speed.
+async fn replay(
+    recording: Arc<TelemetryRecording>,
+    config: PlaybackConfig,
+    loop_gap: Duration,
+    tx: mpsc::Sender<TelemetryFrame>,
+) {
+    let game_id = &recording.metadata.game_id;
+    let first_ns = recording.frames.first().map_or(0, |f| f.timestamp_ns);
+    let mut base = Instant::now();
+    let mut sequence = 0u64;
+    loop {
+        let mut last_due = base;
+        for recorded in &recording.frames {
+            if let PlaybackSpeed::Multiplier(factor) = config.speed {
+                let offset = Duration::from_nanos(recorded.timestamp_ns.saturating_sub(first_ns));
+                let Some(due) = Duration::try_from_secs_f64(offset.as_secs_f64() / factor)
+                    .ok()
+                    .and_then(|offset| base.checked_add(offset))
+                else {
+                    warn!(game_id, "Playback offset out of range; stopping");
+                    return;
+                };
+                tokio::time::sleep_until(due).await;
+                last_due = due;
+            }
+            let frame = TelemetryFrame::new(
+                recorded.data.clone(),
+                telemetry_now_ns(),
+                sequence,
+                recorded.raw_size,
+            );
+            if tx.send(frame).await.is_err() {
+                debug!(game_id, "Receiver dropped, stopping playback");
+                return;
+            }
+            sequence = sequence.wrapping_add(1);
+        }
+        if config.at_end == PlaybackEnd::Stop {
+            debug!(game_id, frames = sequence, "Playback finished");
+            return;
+        }
+        base = match config.speed {
+            PlaybackSpeed::Multiplier(factor) => {
+                let gap = Duration::try_from_secs_f64(loop_gap.as_secs_f64() / factor)
+                    .unwrap_or(DEFAULT_PLAYBACK_INTERVAL);
+                let Some(next) = last_due.checked_add(gap) else {
+                    warn!(game_id, "Playback offset out of range; stopping");
+                    return;
+                };
+                next
+            }
+            PlaybackSpeed::AsFastAsPossible => {
+                // Let other tasks run between passes over short recordings.
+                tokio::task::yield_now().await;
+                Instant::now()
+            }
+        };
+    }
+}
@@ -0,0 +1,73 @@
+//! Restarting an adapter whose stream died.
+//!
+//! When a game's stream ends, or goes quiet for its disconnection timeout
+//! after having connected, the forwarding task calls [`reconnect`] instead of
+//! exiting. It stops and restarts the adapter with exponential backoff, as
+//! its [`DisconnectionConfig`] allows, and hands back the new stream so the
+//! caller's receiver never notices. Each attempt shows up on the health
+//! channel as `Reconnecting`, then `Error` on failure; the first frame of the
+//! new stream moves the game to `Connected` and clears the attempt count.
+
+use std::time::Duration;
+
+use racing_wheel_telemetry_adapters::{TelemetryAdapter, TelemetryFrame, TelemetryReceiver};
+use racing_wheel_telemetry_core::DisconnectionConfig;
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+use crate::health::HealthMonitor;
+
+/// Longest wait between reconnect attempts, however many have failed.
+pub const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Doublings of `reconnect_delay_ms` after which the backoff stops growing.
+const MAX_BACKOFF_DOUBLINGS: u32 = 16;
+
+/// Wait before the 1-based reconnect `attempt`: `reconnect_delay_ms`, doubled
+/// for each earlier attempt, capped at [`MAX_RECONNECT_BACKOFF`].
+pub fn reconnect_backoff(config: &DisconnectionConfig, attempt: u32) -> Duration {
+    let doublings = attempt.saturating_sub(1).min(MAX_BACKOFF_DOUBLINGS);
+    config
+        .reconnect_delay()
+        .saturating_mul(1 << doublings)
+        .min(MAX_RECONNECT_BACKOFF)
+}
+
+/// Restart `adapter` until it yields a new stream, an attempt limit or stop
+/// request ends the retries, or `output`'s receiver is dropped.
+pub(crate) async fn reconnect(
+    health: &mut HealthMonitor,
+    adapter: &dyn TelemetryAdapter,
+    output: &mpsc::Sender<TelemetryFrame>,
+) -> Option<TelemetryReceiver> {
+    health.source_lost();
+    while health.should_reconnect() && !output.is_closed() {
+        let attempt = health.mark_reconnecting();
+        let delay = reconnect_backoff(health.config(), attempt);
+        debug!(
+            game_id = adapter.game_id(),
+            attempt,
+            ?delay,
+            "Reconnecting telemetry adapter"
+        );
+        let waited = tokio::select! {
+            waited = health.wait_unless_stopped(delay) => waited,
+            _ = output.closed() => false,
+        };
+        if !waited {
+            return None;
+        }
+
+        if let Err(err) = adapter.stop_monitoring().await {
+            debug!(game_id = adapter.game_id(), error = %err, "Stopping adapter before reconnect failed");
+        }
+        match adapter.start_monitoring().await {
+            Ok(source) => return Some(source),
+            Err(err) => {
+                warn!(game_id = adapter.game_id(), attempt, error = %err, "Telemetry reconnect attempt failed");
+                health.record_error(&err);
+            }
+        }
+    }
+    None
+}
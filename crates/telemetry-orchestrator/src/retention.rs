@@ -0,0 +1,491 @@
+//! Retention policies and cleanup for on-disk telemetry artifacts.
+//!
+//! Recordings, sink output files, raw captures, audit logs and diagnostics bundles
+//! each live in their own sub-directory of the OpenRacing data directory. A
+//! [`RetentionManager`] plans deletions per [`ArtifactClass`] from its
+//! [`RetentionPolicy`], oldest first, and never touches a file that is marked
+//! active or currently holds a [`FileLock`].
+//!
+//! Policies default to unbounded, so nothing is deleted until a limit is
+//! configured. They are persisted as [`RETENTION_POLICY_FILE`] in the profile
+//! directory next to the user's profiles.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+use openracing_file_lock::{FileLock, LOCK_SUFFIX};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use tracing::warn;
+
+/// File name of the persisted retention policies inside the profile directory.
+pub const RETENTION_POLICY_FILE: &str = "retention.json";
+
+/// Capacity of the retention event broadcast channel.
+const RETENTION_EVENT_CAPACITY: usize = 16;
+
+/// Kinds of artifact OpenRacing writes to its data directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactClass {
+    /// Telemetry recordings written by the recorder.
+    Recordings,
+    /// Files written by downstream sinks (JSONL, binary frames).
+    SinkOutputs,
+    /// Raw packet / shared-memory captures.
+    RawCaptures,
+    /// Audit logs.
+    AuditLogs,
+    /// Zipped diagnostics bundles.
+    DiagnosticsBundles,
+}
+
+impl ArtifactClass {
+    /// Every artifact class, in cleanup order.
+    pub const ALL: [ArtifactClass; 5] = [
+        ArtifactClass::Recordings,
+        ArtifactClass::SinkOutputs,
+        ArtifactClass::RawCaptures,
+        ArtifactClass::AuditLogs,
+        ArtifactClass::DiagnosticsBundles,
+    ];
+
+    /// Sub-directory of the data directory holding this class.
+    pub fn dir_name(self) -> &'static str {
+        match self {
+            ArtifactClass::Recordings => "recordings",
+            ArtifactClass::SinkOutputs => "sinks",
+            ArtifactClass::RawCaptures => "captures",
+            ArtifactClass::AuditLogs => "audit",
+            ArtifactClass::DiagnosticsBundles => "diagnostics",
+        }
+    }
+}
+
+/// Limits for one artifact class. `None` disables that limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    /// Delete the oldest artifacts while the class exceeds this many bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_total_bytes: Option<u64>,
+    /// Delete artifacts last modified longer ago than this many seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age_secs: Option<u64>,
+    /// Delete the oldest artifacts while the volume has less free space than this.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_free_bytes: Option<u64>,
+}
+
+impl RetentionPolicy {
+    /// Whether no limit is configured, i.e. the class is never cleaned up.
+    pub fn is_unbounded(&self) -> bool {
+        self.max_total_bytes.is_none()
+            && self.max_age_secs.is_none()
+            && self.min_free_bytes.is_none()
+    }
+}
+
+/// Retention policies for every artifact class.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionPolicies {
+    /// Policy for [`ArtifactClass::Recordings`].
+    pub recordings: RetentionPolicy,
+    /// Policy for [`ArtifactClass::SinkOutputs`].
+    pub sink_outputs: RetentionPolicy,
+    /// Policy for [`ArtifactClass::RawCaptures`].
+    pub raw_captures: RetentionPolicy,
+    /// Policy for [`ArtifactClass::AuditLogs`].
+    pub audit_logs: RetentionPolicy,
+    /// Policy for [`ArtifactClass::DiagnosticsBundles`].
+    pub diagnostics_bundles: RetentionPolicy,
+}
+
+impl RetentionPolicies {
+    /// Policy for `class`.
+    pub fn get(&self, class: ArtifactClass) -> &RetentionPolicy {
+        match class {
+            ArtifactClass::Recordings => &self.recordings,
+            ArtifactClass::SinkOutputs => &self.sink_outputs,
+            ArtifactClass::RawCaptures => &self.raw_captures,
+            ArtifactClass::AuditLogs => &self.audit_logs,
+            ArtifactClass::DiagnosticsBundles => &self.diagnostics_bundles,
+        }
+    }
+
+    /// Replace the policy for `class`.
+    pub fn set(&mut self, class: ArtifactClass, policy: RetentionPolicy) {
+        match class {
+            ArtifactClass::Recordings => self.recordings = policy,
+            ArtifactClass::SinkOutputs => self.sink_outputs = policy,
+            ArtifactClass::RawCaptures => self.raw_captures = policy,
+            ArtifactClass::AuditLogs => self.audit_logs = policy,
+            ArtifactClass::DiagnosticsBundles => self.diagnostics_bundles = policy,
+        }
+    }
+
+    /// Load policies from `profile_dir`, falling back to defaults when none are saved.
+    pub fn load(profile_dir: impl AsRef<Path>) -> Result<Self> {
+        let path = profile_dir.as_ref().join(RETENTION_POLICY_FILE);
+        match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json)
+                .with_context(|| format!("invalid retention policies in {}", path.display())),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error).with_context(|| format!("failed to read {}", path.display())),
+        }
+    }
+
+    /// Persist policies into `profile_dir`.
+    pub fn save(&self, profile_dir: impl AsRef<Path>) -> Result<()> {
+        let profile_dir = profile_dir.as_ref();
+        fs::create_dir_all(profile_dir)?;
+        let path = profile_dir.join(RETENTION_POLICY_FILE);
+        let _lock = FileLock::acquire(&path)?;
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Source of free-space figures, injectable for tests.
+pub trait DiskStats: Send + Sync {
+    /// Bytes available to the current user on the volume holding `path`.
+    fn available_bytes(&self, path: &Path) -> io::Result<u64>;
+}
+
+/// [`DiskStats`] backed by the operating system's mounted volumes.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemDiskStats;
+
+impl DiskStats for SystemDiskStats {
+    fn available_bytes(&self, path: &Path) -> io::Result<u64> {
+        let path = path.canonicalize()?;
+        let disks = sysinfo::Disks::new_with_refreshed_list();
+        disks
+            .iter()
+            .filter(|disk| path.starts_with(disk.mount_point()))
+            .max_by_key(|disk| disk.mount_point().as_os_str().len())
+            .map(|disk| disk.available_space())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("no mounted volume contains {}", path.display()),
+                )
+            })
+    }
+}
+
+/// Which limit caused an artifact to be selected for deletion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CleanupReason {
+    /// Older than [`RetentionPolicy::max_age_secs`].
+    MaxAge,
+    /// Among the oldest artifacts while the class exceeded its size budget.
+    MaxTotalSize,
+    /// Among the oldest artifacts while the volume was below its free-space floor.
+    MinFreeSpace,
+}
+
+/// An artifact selected for deletion.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CleanupCandidate {
+    /// Class whose policy selected the artifact.
+    pub class: ArtifactClass,
+    /// Absolute path of the artifact.
+    pub path: PathBuf,
+    /// File size at planning time.
+    pub size_bytes: u64,
+    /// Last modification time used for oldest-first ordering.
+    pub modified: SystemTime,
+    /// Limit that selected the artifact.
+    pub reason: CleanupReason,
+}
+
+/// Outcome of a cleanup pass, broadcast as an event and kept for health reporting.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CleanupSummary {
+    /// Number of artifacts removed.
+    pub deleted_items: usize,
+    /// Bytes freed by the removed artifacts.
+    pub reclaimed_bytes: u64,
+    /// Artifacts that were selected but could not be removed.
+    pub failed_items: usize,
+}
+
+/// Events published by the [`RetentionManager`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum RetentionEvent {
+    /// A cleanup pass deleted at least one artifact.
+    CleanupCompleted(CleanupSummary),
+}
+
+struct Artifact {
+    path: PathBuf,
+    size_bytes: u64,
+    modified: SystemTime,
+}
+
+impl Artifact {
+    fn select(self, class: ArtifactClass, reason: CleanupReason) -> CleanupCandidate {
+        CleanupCandidate {
+            class,
+            path: self.path,
+            size_bytes: self.size_bytes,
+            modified: self.modified,
+            reason,
+        }
+    }
+}
+
+/// Applies [`RetentionPolicies`] to the artifact directories under a data directory.
+pub struct RetentionManager {
+    data_dir: PathBuf,
+    policies: RwLock<RetentionPolicies>,
+    disk_stats: Arc<dyn DiskStats>,
+    active: Mutex<HashSet<PathBuf>>,
+    last_cleanup: Mutex<Option<CleanupSummary>>,
+    events: broadcast::Sender<RetentionEvent>,
+}
+
+impl RetentionManager {
+    /// Create a manager for `data_dir` using the operating system's disk stats.
+    pub fn new(data_dir: impl Into<PathBuf>, policies: RetentionPolicies) -> Self {
+        Self {
+            data_dir: data_dir.into(),
+            policies: RwLock::new(policies),
+            disk_stats: Arc::new(SystemDiskStats),
+            active: Mutex::new(HashSet::new()),
+            last_cleanup: Mutex::new(None),
+            events: broadcast::channel(RETENTION_EVENT_CAPACITY).0,
+        }
+    }
+
+    /// Replace the free-space source.
+    pub fn with_disk_stats(mut self, disk_stats: Arc<dyn DiskStats>) -> Self {
+        self.disk_stats = disk_stats;
+        self
+    }
+
+    /// Root data directory.
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    /// Directory holding artifacts of `class`.
+    pub fn class_dir(&self, class: ArtifactClass) -> PathBuf {
+        self.data_dir.join(class.dir_name())
+    }
+
+    /// Current policies.
+    pub fn policies(&self) -> RetentionPolicies {
+        self.policies
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Replace the policies used by subsequent cleanup passes.
+    pub fn set_policies(&self, policies: RetentionPolicies) {
+        *self
+            .policies
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = policies;
+    }
+
+    /// Exclude `path` from cleanup while it is being written.
+    pub fn mark_active(&self, path: impl AsRef<Path>) {
+        self.active
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(normalize(path.as_ref()));
+    }
+
+    /// Make `path` eligible for cleanup again.
+    pub fn clear_active(&self, path: impl AsRef<Path>) {
+        self.active
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&normalize(path.as_ref()));
+    }
+
+    /// Subscribe to cleanup events.
+    pub fn subscribe(&self) -> broadcast::Receiver<RetentionEvent> {
+        self.events.subscribe()
+    }
+
+    /// Summary of the most recent cleanup pass, if one has run.
+    pub fn last_cleanup(&self) -> Option<CleanupSummary> {
+        self.last_cleanup
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// List the artifacts the next cleanup pass would delete, without deleting them.
+    pub fn dry_run_cleanup(&self) -> Result<Vec<CleanupCandidate>> {
+        self.plan(SystemTime::now())
+    }
+
+    /// Delete every artifact exceeding policy, oldest first.
+    pub fn run_cleanup(&self) -> Result<CleanupSummary> {
+        let mut summary = CleanupSummary::default();
+        for candidate in self.plan(SystemTime::now())? {
+            // The file may have become active since planning.
+            if self.is_excluded(&candidate.path) {
+                continue;
+            }
+            match fs::remove_file(&candidate.path) {
+                Ok(()) => {
+                    summary.deleted_items += 1;
+                    summary.reclaimed_bytes += candidate.size_bytes;
+                }
+                Err(error) => {
+                    summary.failed_items += 1;
+                    warn!(path = %candidate.path.display(), %error, "Retention cleanup failed to delete artifact");
+                }
+            }
+        }
+
+        *self
+            .last_cleanup
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(summary.clone());
+        if summary.deleted_items > 0 {
+            // No subscribers is not an error.
+            let _ = self
+                .events
+                .send(RetentionEvent::CleanupCompleted(summary.clone()));
+        }
+        Ok(summary)
+    }
+
+    /// Spawn a task that runs [`Self::run_cleanup`] every `period`.
+    pub fn spawn_periodic(self: &Arc<Self>, period: Duration) -> tokio::task::JoinHandle<()> {
+        let manager = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                let manager = Arc::clone(&manager);
+                match tokio::task::spawn_blocking(move || manager.run_cleanup()).await {
+                    Ok(Ok(_)) => {}
+                    Ok(Err(error)) => warn!(%error, "Retention cleanup pass failed"),
+                    Err(error) => warn!(%error, "Retention cleanup task panicked"),
+                }
+            }
+        })
+    }
+
+    fn plan(&self, now: SystemTime) -> Result<Vec<CleanupCandidate>> {
+        let policies = self.policies();
+        let mut selected: Vec<CleanupCandidate> = Vec::new();
+
+        for class in ArtifactClass::ALL {
+            let policy = policies.get(class);
+            if policy.is_unbounded() {
+                continue;
+            }
+            let dir = self.class_dir(class);
+            let mut artifacts = Vec::new();
+            self.collect(&dir, &mut artifacts)?;
+            artifacts.sort_by(|a, b| a.modified.cmp(&b.modified).then(a.path.cmp(&b.path)));
+
+            let mut kept = Vec::with_capacity(artifacts.len());
+            for artifact in artifacts {
+                let expired = policy.max_age_secs.is_some_and(|max_age| {
+                    now.duration_since(artifact.modified)
+                        .is_ok_and(|age| age > Duration::from_secs(max_age))
+                });
+                if expired {
+                    selected.push(artifact.select(class, CleanupReason::MaxAge));
+                } else {
+                    kept.push(artifact);
+                }
+            }
+
+            if let Some(max_total) = policy.max_total_bytes {
+                let mut total: u64 = kept.iter().map(|artifact| artifact.size_bytes).sum();
+                let mut oldest = 0;
+                while total > max_total && oldest < kept.len() {
+                    total -= kept[oldest].size_bytes;
+                    oldest += 1;
+                }
+                for artifact in kept.drain(..oldest) {
+                    selected.push(artifact.select(class, CleanupReason::MaxTotalSize));
+                }
+            }
+
+            if let Some(min_free) = policy.min_free_bytes
+                && !kept.is_empty()
+            {
+                let available = match self.disk_stats.available_bytes(&dir) {
+                    Ok(available) => available,
+                    Err(error) => {
+                        warn!(dir = %dir.display(), %error, "Free-space check failed; skipping trigger");
+                        continue;
+                    }
+                };
+                let mut planned_bytes: u64 = selected.iter().map(|c| c.size_bytes).sum();
+                for artifact in kept {
+                    if available.saturating_add(planned_bytes) >= min_free {
+                        break;
+                    }
+                    planned_bytes += artifact.size_bytes;
+                    selected.push(artifact.select(class, CleanupReason::MinFreeSpace));
+                }
+            }
+        }
+
+        Ok(selected)
+    }
+
+    fn collect(&self, dir: &Path, out: &mut Vec<Artifact>) -> Result<()> {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(error) => {
+                return Err(error).with_context(|| format!("failed to list {}", dir.display()));
+            }
+        };
+        for entry in entries {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            let path = entry.path();
+            if file_type.is_dir() {
+                self.collect(&path, out)?;
+            } else if file_type.is_file() && !self.is_excluded(&path) {
+                let metadata = entry.metadata()?;
+                out.push(Artifact {
+                    path,
+                    size_bytes: metadata.len(),
+                    modified: metadata.modified()?,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn is_excluded(&self, path: &Path) -> bool {
+        let is_lock_file = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().ends_with(LOCK_SUFFIX));
+        is_lock_file
+            || FileLock::lock_path_for(path).exists()
+            || self
+                .active
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .contains(&normalize(path))
+    }
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
@@ -0,0 +1,650 @@
+//! User-scripted frame transforms (feature `scripting`).
+//!
+//! A script is a WebAssembly module, either binary or WAT text, that exports an
+//! `on_frame` function and a `memory`. The only imports it may declare are the
+//! host functions in the [`HOST_MODULE`] namespace; WASI is never linked, so a
+//! script has no filesystem, network, clock or environment access and fails to
+//! load if it asks for any.
+//!
+//! Host functions take names as `(ptr, len)` UTF-8 slices in the script's memory:
+//!
+//! | import | signature | meaning |
+//! |---|---|---|
+//! | `field` | `(ptr, len) -> f32` | typed frame field (`speed_ms`, `rpm`, `gear`, …), NaN if unknown |
+//! | `ext_get` | `(ptr, len) -> f32` | numeric extended value, NaN if absent or a string |
+//! | `ext_set` | `(ptr, len, f32) -> i32` | queue an extended write; `0` ok, `-1` rejected |
+//! | `state_get` | `(ptr, len) -> f64` | persistent state slot, NaN if unset |
+//! | `state_set` | `(ptr, len, f64) -> i32` | write a state slot; `-1` when the map is full |
+//! | `emit` | `(ptr, len, f32) -> i32` | publish a named [`ScriptEvent::Emitted`] |
+//!
+//! Each frame runs under a fuel budget. A script that exhausts it (or traps) is
+//! disabled and a [`ScriptEvent::Disabled`] is published; its queued writes for
+//! that frame are discarded. State survives across frames and is cleared on
+//! session boundaries and reloads.
+//!
+//! Scripts are registered per game in [`ScriptProfiles`], stored with the
+//! user's profiles. A script loaded from a file is watched on its own thread,
+//! which also compiles new versions; the transform only swaps a finished
+//! module in between frames.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc;
+use std::thread::Thread;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result, anyhow, bail};
+use openracing_file_lock::FileLock;
+use racing_wheel_telemetry_adapters::{NormalizedTelemetry, TelemetryFrame, TelemetryValue};
+use racing_wheel_telemetry_contracts::{ExtendedValueType, lookup_key};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+use wasmtime::{
+    Caller, Config, Engine, Instance, Linker, Module, Store, StoreLimits, StoreLimitsBuilder, Trap,
+    TypedFunc,
+};
+
+use crate::transforms::FrameTransform;
+
+/// Import namespace exposing the script host API.
+pub const HOST_MODULE: &str = "openracing";
+
+/// Capacity of the script event broadcast channel.
+const SCRIPT_EVENT_CAPACITY: usize = 64;
+
+/// Longest key or event name a script may pass to the host.
+const MAX_NAME_LEN: usize = 64;
+
+/// File name of the per-game script registrations inside the profile directory.
+pub const SCRIPT_PROFILE_FILE: &str = "scripts.json";
+
+/// How often a script file is checked for changes unless overridden.
+const DEFAULT_RELOAD_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Per-script resource limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScriptBudget {
+    /// Fuel (roughly, wasm instructions) available to each `on_frame` call.
+    pub fuel_per_frame: u64,
+    /// Linear memory cap for the script instance.
+    pub max_memory_bytes: usize,
+    /// Distinct keys the persistent state map may hold.
+    pub max_state_entries: usize,
+    /// Extended writes accepted per frame.
+    pub max_writes_per_frame: usize,
+    /// Events accepted per frame.
+    pub max_events_per_frame: usize,
+}
+
+impl Default for ScriptBudget {
+    fn default() -> Self {
+        Self {
+            fuel_per_frame: 100_000,
+            max_memory_bytes: 1 << 20,
+            max_state_entries: 64,
+            max_writes_per_frame: 32,
+            max_events_per_frame: 8,
+        }
+    }
+}
+
+/// Why a script was switched off.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScriptDisableReason {
+    /// The per-frame fuel budget ran out.
+    BudgetExceeded { fuel_per_frame: u64 },
+    /// The script trapped for any other reason.
+    Trap(String),
+}
+
+/// Notifications published by a [`ScriptTransform`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ScriptEvent {
+    /// The script called `emit`.
+    Emitted {
+        script: String,
+        name: String,
+        value: f32,
+    },
+    /// The script was disabled and no longer runs until reloaded.
+    Disabled {
+        script: String,
+        reason: ScriptDisableReason,
+    },
+    /// The script file changed and the new version was loaded.
+    Reloaded { script: String },
+}
+
+struct ScriptHost {
+    telemetry: NormalizedTelemetry,
+    state: HashMap<String, f64>,
+    writes: Vec<(String, f32)>,
+    events: Vec<(String, f32)>,
+    budget: ScriptBudget,
+    limits: StoreLimits,
+}
+
+impl ScriptHost {
+    fn new(budget: ScriptBudget) -> Self {
+        Self {
+            telemetry: NormalizedTelemetry::default(),
+            state: HashMap::new(),
+            writes: Vec::new(),
+            events: Vec::new(),
+            budget,
+            limits: StoreLimitsBuilder::new()
+                .memory_size(budget.max_memory_bytes)
+                .instances(1)
+                .tables(1)
+                .memories(1)
+                .build(),
+        }
+    }
+}
+
+struct LoadedScript {
+    store: Store<ScriptHost>,
+    on_frame: TypedFunc<(), ()>,
+}
+
+/// Scripts registered per game, persisted as [`SCRIPT_PROFILE_FILE`] in the
+/// profile directory next to the user's profiles.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScriptProfiles {
+    /// Budget every script runs under.
+    pub budget: ScriptBudget,
+    /// Script files per game id, in execution order. Relative paths are
+    /// resolved against the profile directory.
+    pub games: BTreeMap<String, Vec<PathBuf>>,
+}
+
+impl ScriptProfiles {
+    /// Load registrations from `profile_dir`, or none when nothing is saved.
+    pub fn load(profile_dir: impl AsRef<Path>) -> Result<Self> {
+        let path = profile_dir.as_ref().join(SCRIPT_PROFILE_FILE);
+        match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json)
+                .with_context(|| format!("invalid script profiles in {}", path.display())),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error).with_context(|| format!("failed to read {}", path.display())),
+        }
+    }
+
+    /// Persist registrations into `profile_dir`.
+    pub fn save(&self, profile_dir: impl AsRef<Path>) -> Result<()> {
+        let profile_dir = profile_dir.as_ref();
+        fs::create_dir_all(profile_dir)?;
+        let path = profile_dir.join(SCRIPT_PROFILE_FILE);
+        let _lock = FileLock::acquire(&path)?;
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Run the script at `path` for `game_id`, after any already registered.
+    pub fn register(&mut self, game_id: impl Into<String>, path: impl Into<PathBuf>) {
+        self.games
+            .entry(game_id.into())
+            .or_default()
+            .push(path.into());
+    }
+
+    /// Script files registered for `game_id`, in execution order.
+    pub fn scripts_for(&self, game_id: &str) -> &[PathBuf] {
+        self.games
+            .get(game_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Compile every registered script, resolving relative paths against
+    /// `profile_dir`, keyed by game id.
+    ///
+    /// A script that fails to load is reported as an error rather than skipped.
+    pub fn load_scripts(
+        &self,
+        profile_dir: impl AsRef<Path>,
+    ) -> Result<Vec<(String, ScriptTransform)>> {
+        let profile_dir = profile_dir.as_ref();
+        let mut scripts = Vec::new();
+        for (game_id, paths) in &self.games {
+            for path in paths {
+                let script = ScriptTransform::from_file(profile_dir.join(path), self.budget)?;
+                scripts.push((game_id.clone(), script));
+            }
+        }
+        Ok(scripts)
+    }
+}
+
+/// Watches a script file on its own thread and compiles each new version
+/// there, so neither the file checks nor compilation run on the forwarding
+/// task. Stops when dropped.
+struct ScriptWatcher {
+    reloads: mpsc::Receiver<Result<LoadedScript>>,
+    interval_ns: Arc<AtomicU64>,
+    stop: Arc<AtomicBool>,
+    thread: Thread,
+}
+
+impl ScriptWatcher {
+    fn spawn(
+        path: PathBuf,
+        modified: Option<SystemTime>,
+        engine: Engine,
+        budget: ScriptBudget,
+    ) -> Result<Self> {
+        let (reloads_tx, reloads) = mpsc::channel();
+        let interval_ns = Arc::new(AtomicU64::new(duration_ns(DEFAULT_RELOAD_INTERVAL)));
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = std::thread::Builder::new()
+            .name("telemetry-script-watch".to_string())
+            .spawn({
+                let interval_ns = Arc::clone(&interval_ns);
+                let stop = Arc::clone(&stop);
+                let mut modified = modified;
+                move || {
+                    loop {
+                        std::thread::park_timeout(Duration::from_nanos(
+                            interval_ns.load(Ordering::Relaxed),
+                        ));
+                        if stop.load(Ordering::Acquire) {
+                            break;
+                        }
+                        let current = modified_time(&path);
+                        if current == modified {
+                            continue;
+                        }
+                        modified = current;
+                        let compiled = fs::read(&path)
+                            .with_context(|| format!("Failed to read script {}", path.display()))
+                            .and_then(|source| instantiate(&engine, &source, budget));
+                        if reloads_tx.send(compiled).is_err() {
+                            break;
+                        }
+                    }
+                }
+            })?;
+        Ok(Self {
+            reloads,
+            interval_ns,
+            stop,
+            thread: handle.thread().clone(),
+        })
+    }
+
+    fn set_interval(&self, interval: Duration) {
+        self.interval_ns
+            .store(duration_ns(interval), Ordering::Relaxed);
+        self.thread.unpark();
+    }
+}
+
+impl Drop for ScriptWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        self.thread.unpark();
+    }
+}
+
+/// A [`FrameTransform`] driven by a sandboxed WebAssembly script.
+pub struct ScriptTransform {
+    name: String,
+    budget: ScriptBudget,
+    script: LoadedScript,
+    enabled: bool,
+    watcher: Option<ScriptWatcher>,
+    events: broadcast::Sender<ScriptEvent>,
+}
+
+impl ScriptTransform {
+    /// Compile `source` (wasm binary or WAT text) into a transform.
+    pub fn from_source(
+        name: impl Into<String>,
+        source: impl AsRef<[u8]>,
+        budget: ScriptBudget,
+    ) -> Result<Self> {
+        Self::compile(name.into(), &script_engine()?, source.as_ref(), budget)
+    }
+
+    /// Load a script file; the transform picks up new versions of the file
+    /// as they are saved.
+    pub fn from_file(path: impl AsRef<Path>, budget: ScriptBudget) -> Result<Self> {
+        let path = path.as_ref();
+        let modified = modified_time(path);
+        let source =
+            fs::read(path).with_context(|| format!("Failed to read script {}", path.display()))?;
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+
+        let engine = script_engine()?;
+        let mut transform = Self::compile(name, &engine, &source, budget)
+            .with_context(|| format!("Failed to load script {}", path.display()))?;
+        transform.watcher = Some(ScriptWatcher::spawn(
+            path.to_path_buf(),
+            modified,
+            engine,
+            budget,
+        )?);
+        Ok(transform)
+    }
+
+    fn compile(name: String, engine: &Engine, source: &[u8], budget: ScriptBudget) -> Result<Self> {
+        let script = instantiate(engine, source, budget)?;
+        let (events, _) = broadcast::channel(SCRIPT_EVENT_CAPACITY);
+        Ok(Self {
+            name,
+            budget,
+            script,
+            enabled: true,
+            watcher: None,
+            events,
+        })
+    }
+
+    /// Override how often the backing file is checked for changes.
+    pub fn with_reload_interval(self, interval: Duration) -> Self {
+        if let Some(watcher) = &self.watcher {
+            watcher.set_interval(interval);
+        }
+        self
+    }
+
+    /// Subscribe to emitted values, disable notices and reloads.
+    pub fn subscribe(&self) -> broadcast::Receiver<ScriptEvent> {
+        self.events.subscribe()
+    }
+
+    /// Whether the script still runs on new frames.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Current value of a persistent state slot.
+    pub fn state_value(&self, key: &str) -> Option<f64> {
+        self.script.store.data().state.get(key).copied()
+    }
+
+    /// Swap in versions the watcher compiled since the last frame.
+    ///
+    /// A version that failed to load keeps the previous one running.
+    fn poll_reload(&mut self) {
+        let Some(watcher) = &self.watcher else {
+            return;
+        };
+        let reloads: Vec<_> = watcher.reloads.try_iter().collect();
+        for reload in reloads {
+            match reload {
+                Ok(script) => {
+                    self.script = script;
+                    self.enabled = true;
+                    info!(script = %self.name, "Reloaded telemetry script");
+                    let _ = self.events.send(ScriptEvent::Reloaded {
+                        script: self.name.clone(),
+                    });
+                }
+                Err(error) => {
+                    warn!(script = %self.name, error = %error, "Script reload failed, keeping previous version");
+                }
+            }
+        }
+    }
+
+    fn disable(&mut self, reason: ScriptDisableReason) {
+        warn!(script = %self.name, reason = ?reason, "Disabling telemetry script");
+        self.enabled = false;
+        let _ = self.events.send(ScriptEvent::Disabled {
+            script: self.name.clone(),
+            reason,
+        });
+    }
+}
+
+impl FrameTransform for ScriptTransform {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn apply(&mut self, frame: &mut TelemetryFrame) {
+        self.poll_reload();
+        if !self.enabled {
+            return;
+        }
+
+        let store = &mut self.script.store;
+        if let Err(error) = store.set_fuel(self.budget.fuel_per_frame) {
+            self.disable(ScriptDisableReason::Trap(error.to_string()));
+            return;
+        }
+
+        std::mem::swap(&mut store.data_mut().telemetry, &mut frame.data);
+        let outcome = self.script.on_frame.call(&mut *store, ());
+        std::mem::swap(&mut store.data_mut().telemetry, &mut frame.data);
+
+        let host = store.data_mut();
+        let writes = std::mem::take(&mut host.writes);
+        let events = std::mem::take(&mut host.events);
+
+        if let Err(error) = outcome {
+            let reason = match error.downcast_ref::<Trap>() {
+                Some(Trap::OutOfFuel) => ScriptDisableReason::BudgetExceeded {
+                    fuel_per_frame: self.budget.fuel_per_frame,
+                },
+                _ => ScriptDisableReason::Trap(error.to_string()),
+            };
+            self.disable(reason);
+            return;
+        }
+
+        for (key, value) in writes {
+            frame
+                .data
+                .extended
+                .insert(key, TelemetryValue::Float(value));
+        }
+        for (name, value) in events {
+            let _ = self.events.send(ScriptEvent::Emitted {
+                script: self.name.clone(),
+                name,
+                value,
+            });
+        }
+    }
+
+    fn reset(&mut self) {
+        self.script.store.data_mut().state.clear();
+    }
+}
+
+fn script_engine() -> Result<Engine> {
+    let mut config = Config::new();
+    config.consume_fuel(true);
+    config.wasm_threads(false);
+    Engine::new(&config)
+}
+
+fn instantiate(engine: &Engine, source: &[u8], budget: ScriptBudget) -> Result<LoadedScript> {
+    let module = Module::new(engine, source)?;
+    for import in module.imports() {
+        if import.module() != HOST_MODULE {
+            bail!(
+                "Script imports '{}::{}'; only '{HOST_MODULE}' host functions are available",
+                import.module(),
+                import.name()
+            );
+        }
+    }
+
+    let mut linker = Linker::new(engine);
+    register_host_functions(&mut linker)?;
+
+    let mut store = Store::new(engine, ScriptHost::new(budget));
+    store.limiter(|host| &mut host.limits);
+    store.set_fuel(budget.fuel_per_frame)?;
+
+    let instance: Instance = linker.instantiate(&mut store, &module)?;
+    let on_frame = instance
+        .get_typed_func::<(), ()>(&mut store, "on_frame")
+        .map_err(|error| anyhow!("Script must export `on_frame: () -> ()`: {error}"))?;
+
+    Ok(LoadedScript { store, on_frame })
+}
+
+fn register_host_functions(linker: &mut Linker<ScriptHost>) -> Result<()> {
+    linker.func_wrap(
+        HOST_MODULE,
+        "field",
+        |mut caller: Caller<'_, ScriptHost>, ptr: i32, len: i32| -> f32 {
+            read_name(&mut caller, ptr, len)
+                .and_then(|name| typed_field(&caller.data().telemetry, &name))
+                .unwrap_or(f32::NAN)
+        },
+    )?;
+
+    linker.func_wrap(
+        HOST_MODULE,
+        "ext_get",
+        |mut caller: Caller<'_, ScriptHost>, ptr: i32, len: i32| -> f32 {
+            read_name(&mut caller, ptr, len)
+                .and_then(|name| {
+                    caller
+                        .data()
+                        .telemetry
+                        .extended
+                        .get(&name)
+                        .and_then(numeric)
+                })
+                .unwrap_or(f32::NAN)
+        },
+    )?;
+
+    linker.func_wrap(
+        HOST_MODULE,
+        "ext_set",
+        |mut caller: Caller<'_, ScriptHost>, ptr: i32, len: i32, value: f32| -> i32 {
+            let Some(name) = read_name(&mut caller, ptr, len) else {
+                return -1;
+            };
+            // Scripts only produce floats; refuse to shadow keys declared otherwise.
+            let declared_other =
+                lookup_key(&name).is_some_and(|key| key.value_type != ExtendedValueType::Float);
+            let host = caller.data_mut();
+            if declared_other || host.writes.len() >= host.budget.max_writes_per_frame {
+                return -1;
+            }
+            host.writes.push((name, value));
+            0
+        },
+    )?;
+
+    linker.func_wrap(
+        HOST_MODULE,
+        "state_get",
+        |mut caller: Caller<'_, ScriptHost>, ptr: i32, len: i32| -> f64 {
+            read_name(&mut caller, ptr, len)
+                .and_then(|name| caller.data().state.get(&name).copied())
+                .unwrap_or(f64::NAN)
+        },
+    )?;
+
+    linker.func_wrap(
+        HOST_MODULE,
+        "state_set",
+        |mut caller: Caller<'_, ScriptHost>, ptr: i32, len: i32, value: f64| -> i32 {
+            let Some(name) = read_name(&mut caller, ptr, len) else {
+                return -1;
+            };
+            let host = caller.data_mut();
+            let full = host.state.len() >= host.budget.max_state_entries;
+            if full && !host.state.contains_key(&name) {
+                return -1;
+            }
+            host.state.insert(name, value);
+            0
+        },
+    )?;
+
+    linker.func_wrap(
+        HOST_MODULE,
+        "emit",
+        |mut caller: Caller<'_, ScriptHost>, ptr: i32, len: i32, value: f32| -> i32 {
+            let Some(name) = read_name(&mut caller, ptr, len) else {
+                return -1;
+            };
+            let host = caller.data_mut();
+            if host.events.len() >= host.budget.max_events_per_frame {
+                return -1;
+            }
+            host.events.push((name, value));
+            0
+        },
+    )?;
+
+    Ok(())
+}
+
+fn read_name(caller: &mut Caller<'_, ScriptHost>, ptr: i32, len: i32) -> Option<String> {
+    let start = usize::try_from(ptr).ok()?;
+    let len = usize::try_from(len).ok()?;
+    if len == 0 || len > MAX_NAME_LEN {
+        return None;
+    }
+    let memory = caller.get_export("memory")?.into_memory()?;
+    let bytes = memory.data(&*caller).get(start..start.checked_add(len)?)?;
+    std::str::from_utf8(bytes).ok().map(str::to_string)
+}
+
+fn numeric(value: &TelemetryValue) -> Option<f32> {
+    match value {
+        TelemetryValue::Float(value) => Some(*value),
+        TelemetryValue::Integer(value) => Some(*value as f32),
+        TelemetryValue::Boolean(value) => Some(if *value { 1.0 } else { 0.0 }),
+        TelemetryValue::String(_)
+        | TelemetryValue::FloatArray(_)
+        | TelemetryValue::IntArray(_)
+        | TelemetryValue::Map(_) => None,
+    }
+}
+
+fn typed_field(telemetry: &NormalizedTelemetry, name: &str) -> Option<f32> {
+    let value = match name {
+        "speed_ms" => telemetry.speed_ms,
+        "steering_angle" => telemetry.steering_angle,
+        "throttle" => telemetry.throttle,
+        "brake" => telemetry.brake,
+        "clutch" => telemetry.clutch,
+        "rpm" => telemetry.rpm,
+        "max_rpm" => telemetry.max_rpm,
+        "gear" => f32::from(telemetry.gear),
+        "lateral_g" => telemetry.lateral_g,
+        "longitudinal_g" => telemetry.longitudinal_g,
+        "vertical_g" => telemetry.vertical_g,
+        "slip_ratio" => telemetry.slip_ratio,
+        "ffb_scalar" => telemetry.ffb_scalar,
+        "ffb_torque_nm" => telemetry.ffb_torque_nm,
+        "fuel_percent" => telemetry.fuel_percent,
+        "engine_temp_c" => telemetry.engine_temp_c,
+        "current_lap_time_s" => telemetry.current_lap_time_s,
+        "best_lap_time_s" => telemetry.best_lap_time_s,
+        "last_lap_time_s" => telemetry.last_lap_time_s,
+        "lap" => f32::from(telemetry.lap),
+        "position" => f32::from(telemetry.position),
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+fn duration_ns(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
@@ -0,0 +1,386 @@
+//! End-to-end self-test of one game's telemetry path.
+//!
+//! "Nothing shows up" has a handful of causes along one chain: the game is
+//! not supported, its telemetry config was never written or has been edited
+//! since, another program holds the port, or the game simply is not sending.
+//! The self-test walks that chain in order and reports each link as passed,
+//! failed or skipped with a hint the user can act on, so a setup screen can
+//! point at the broken link instead of a blank dashboard.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use racing_wheel_telemetry_adapters::{AdapterNetworkConfig, ListenMode, TelemetryAdapter};
+use racing_wheel_telemetry_config_writers::{ConfigWriter, DiffOperation, TelemetryConfig};
+use racing_wheel_telemetry_core::TelemetryFrame;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+/// How long the first-frame step waits unless told otherwise.
+pub const DEFAULT_SELF_TEST_FRAME_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Inputs to `TelemetryService::self_test`.
+#[derive(Debug, Clone)]
+pub struct SelfTestOptions {
+    /// Install root the config step validates; `None` skips the step.
+    pub game_path: Option<PathBuf>,
+    /// Config the game should carry. With it, the config step names the keys
+    /// that differ; without it, only the writer's own validation runs.
+    pub config: Option<TelemetryConfig>,
+    /// Longest the first-frame step waits.
+    pub frame_timeout: Duration,
+}
+
+impl Default for SelfTestOptions {
+    fn default() -> Self {
+        Self {
+            game_path: None,
+            config: None,
+            frame_timeout: DEFAULT_SELF_TEST_FRAME_TIMEOUT,
+        }
+    }
+}
+
+/// One link of the telemetry path, in the order the self-test runs them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelfTestStep {
+    /// The game is in the support matrix with an adapter and a config writer.
+    Registry,
+    /// The game's telemetry config is written as expected.
+    Config,
+    /// The adapter's transport can be listened on.
+    Transport,
+    /// A frame arrives and decodes.
+    FirstFrame,
+}
+
+/// Result of one self-test step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepOutcome {
+    Passed,
+    Failed,
+    /// Not run, because an earlier step failed or an input was not given.
+    Skipped,
+}
+
+/// Outcome of one step with a one-line hint for the user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelfTestStepReport {
+    pub step: SelfTestStep,
+    pub outcome: StepOutcome,
+    pub hint: String,
+}
+
+impl SelfTestStepReport {
+    fn new(step: SelfTestStep, outcome: StepOutcome, hint: impl Into<String>) -> Self {
+        Self {
+            step,
+            outcome,
+            hint: hint.into(),
+        }
+    }
+}
+
+/// Another process holding the adapter's UDP port.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortConflict {
+    pub port: u16,
+    /// Owner of the port, where the platform lets us look it up.
+    pub pid: Option<u32>,
+    pub process_name: Option<String>,
+}
+
+/// The first decoded frame, trimmed to what identifies a working feed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrameSummary {
+    pub sequence: u64,
+    pub speed_ms: f32,
+    pub rpm: f32,
+    pub gear: i8,
+    pub car_id: Option<String>,
+    pub track_id: Option<String>,
+}
+
+impl From<&TelemetryFrame> for FrameSummary {
+    fn from(frame: &TelemetryFrame) -> Self {
+        Self {
+            sequence: frame.sequence,
+            speed_ms: frame.data.speed_ms,
+            rpm: frame.data.rpm,
+            gear: frame.data.gear,
+            car_id: frame.data.car_id.clone(),
+            track_id: frame.data.track_id.clone(),
+        }
+    }
+}
+
+/// Every step of one self-test, in [`SelfTestStep`] order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SelfTestReport {
+    pub game_id: String,
+    pub steps: Vec<SelfTestStepReport>,
+    /// Config keys that differ from the expected config, as
+    /// `file: [section] key`.
+    pub wrong_keys: Vec<String>,
+    pub port_conflict: Option<PortConflict>,
+    pub first_frame: Option<FrameSummary>,
+}
+
+impl SelfTestReport {
+    pub(crate) fn new(game_id: String) -> Self {
+        Self {
+            game_id,
+            steps: Vec::with_capacity(4),
+            wrong_keys: Vec::new(),
+            port_conflict: None,
+            first_frame: None,
+        }
+    }
+
+    /// Whether no step failed. Skipped steps do not count against it.
+    pub fn passed(&self) -> bool {
+        self.steps
+            .iter()
+            .all(|step| step.outcome != StepOutcome::Failed)
+    }
+
+    /// Report of `step`.
+    pub fn step(&self, step: SelfTestStep) -> Option<&SelfTestStepReport> {
+        self.steps.iter().find(|report| report.step == step)
+    }
+
+    /// Outcome of `step`, `Skipped` if it never ran.
+    pub fn outcome(&self, step: SelfTestStep) -> StepOutcome {
+        self.step(step)
+            .map_or(StepOutcome::Skipped, |report| report.outcome)
+    }
+
+    pub(crate) fn push(
+        &mut self,
+        step: SelfTestStep,
+        outcome: StepOutcome,
+        hint: impl Into<String>,
+    ) {
+        self.steps
+            .push(SelfTestStepReport::new(step, outcome, hint));
+    }
+
+    pub(crate) fn check_registry(&mut self, in_matrix: bool, has_adapter: bool, has_writer: bool) {
+        let game_id = &self.game_id;
+        let (outcome, hint) = if !in_matrix {
+            (
+                StepOutcome::Failed,
+                format!("`{game_id}` is not in the game support matrix"),
+            )
+        } else if !has_adapter {
+            (
+                StepOutcome::Failed,
+                format!("`{game_id}` has no telemetry adapter registered"),
+            )
+        } else if !has_writer {
+            (
+                StepOutcome::Failed,
+                format!("`{game_id}` has no config writer; its telemetry must be set up by hand"),
+            )
+        } else {
+            (
+                StepOutcome::Passed,
+                format!("`{game_id}` is supported with an adapter and a config writer"),
+            )
+        };
+        self.push(SelfTestStep::Registry, outcome, hint);
+    }
+
+    pub(crate) fn check_config(
+        &mut self,
+        writer: &dyn ConfigWriter,
+        game_path: &Path,
+        config: Option<&TelemetryConfig>,
+    ) {
+        let valid = match writer.validate_config(game_path) {
+            Ok(valid) => valid,
+            Err(err) => {
+                let hint = format!(
+                    "cannot read the telemetry config under {}: {err}",
+                    game_path.display()
+                );
+                self.push(SelfTestStep::Config, StepOutcome::Failed, hint);
+                return;
+            }
+        };
+        if let Some(config) = config {
+            match writer.preview_config(game_path, config) {
+                Ok(diffs) => {
+                    self.wrong_keys = diffs
+                        .iter()
+                        .flat_map(|diff| diff.leaf_diffs())
+                        .filter(|diff| diff.operation != DiffOperation::NoChange)
+                        .map(|diff| key_label(&diff.file_path, diff.section.as_deref(), &diff.key))
+                        .collect();
+                }
+                Err(err) => debug!(game_id = %self.game_id, error = %err, "Config preview failed"),
+            }
+        }
+
+        let (outcome, hint) = match (valid, self.wrong_keys.as_slice()) {
+            (true, []) => (
+                StepOutcome::Passed,
+                format!("telemetry config is applied under {}", game_path.display()),
+            ),
+            (_, []) => (
+                StepOutcome::Failed,
+                format!(
+                    "telemetry config is missing or incomplete under {}; apply it and restart the game",
+                    game_path.display()
+                ),
+            ),
+            (_, keys) => (
+                StepOutcome::Failed,
+                format!(
+                    "{} config key(s) differ from the expected config: {}; apply it and restart the game",
+                    keys.len(),
+                    keys.join(", ")
+                ),
+            ),
+        };
+        self.push(SelfTestStep::Config, outcome, hint);
+    }
+
+    /// Bind `port` the way the adapter would, then let it go again.
+    pub(crate) fn check_port(&mut self, port: u16, listen_mode: ListenMode) {
+        let (outcome, hint) = match AdapterNetworkConfig::new(port)
+            .with_listen_mode(listen_mode)
+            .bind_std()
+        {
+            Ok(_) => (
+                StepOutcome::Passed,
+                format!("UDP port {port} is free to listen on ({listen_mode})"),
+            ),
+            Err(err) if err.kind() == io::ErrorKind::AddrInUse => {
+                let conflict = port_conflict(port);
+                let owner = match (&conflict.pid, &conflict.process_name) {
+                    (Some(pid), Some(name)) => format!("PID {pid} ({name})"),
+                    (Some(pid), None) => format!("PID {pid}"),
+                    _ => "another process".to_string(),
+                };
+                self.port_conflict = Some(conflict);
+                (
+                    StepOutcome::Failed,
+                    format!(
+                        "port {port} is in use by {owner}; close it or move the game's output to another port"
+                    ),
+                )
+            }
+            Err(err) => (
+                StepOutcome::Failed,
+                format!("cannot listen on UDP port {port}: {err}"),
+            ),
+        };
+        self.push(SelfTestStep::Transport, outcome, hint);
+    }
+
+    pub(crate) fn record_frame(&mut self, frame: Result<TelemetryFrame, String>) {
+        match frame {
+            Ok(frame) => {
+                let summary = FrameSummary::from(&frame);
+                let hint = format!(
+                    "frame {} decoded: {:.1} m/s, {:.0} rpm, gear {}",
+                    summary.sequence, summary.speed_ms, summary.rpm, summary.gear
+                );
+                self.first_frame = Some(summary);
+                self.push(SelfTestStep::FirstFrame, StepOutcome::Passed, hint);
+            }
+            Err(hint) => self.push(SelfTestStep::FirstFrame, StepOutcome::Failed, hint),
+        }
+    }
+}
+
+/// Start `adapter` on its own, take its first frame and stop it again.
+pub(crate) async fn first_frame(
+    adapter: &dyn TelemetryAdapter,
+    timeout: Duration,
+) -> Result<TelemetryFrame, String> {
+    let mut frames = adapter
+        .start_monitoring()
+        .await
+        .map_err(|err| format!("the adapter failed to start: {err}"))?;
+    let received = tokio::time::timeout(timeout, frames.recv()).await;
+    if let Err(err) = adapter.stop_monitoring().await {
+        debug!(game_id = adapter.game_id(), error = %err, "Stopping self-test monitor failed");
+    }
+    match received {
+        Ok(Some(frame)) => Ok(frame),
+        Ok(None) => Err("the telemetry source ended before sending a frame".to_string()),
+        Err(_) => Err(format!(
+            "nothing arrived within {timeout:?}; check the game is running, on track and sending telemetry"
+        )),
+    }
+}
+
+fn key_label(file_path: &str, section: Option<&str>, key: &str) -> String {
+    let file = Path::new(file_path)
+        .file_name()
+        .map_or(file_path.into(), |name| name.to_string_lossy());
+    match section {
+        Some(section) => format!("{file}: [{section}] {key}"),
+        None => format!("{file}: {key}"),
+    }
+}
+
+fn port_conflict(port: u16) -> PortConflict {
+    let pid = port_owner(port);
+    PortConflict {
+        port,
+        pid,
+        process_name: pid.and_then(process_name),
+    }
+}
+
+/// Process holding a UDP socket bound to `port`, found by matching the
+/// socket's inode in `/proc/net/udp*` against every process's open files.
+#[cfg(target_os = "linux")]
+fn port_owner(port: u16) -> Option<u32> {
+    let port = format!(":{port:04X}");
+    let inode = ["/proc/net/udp", "/proc/net/udp6"]
+        .into_iter()
+        .filter_map(|table| std::fs::read_to_string(table).ok())
+        .find_map(|table| {
+            table.lines().skip(1).find_map(|line| {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                let local = fields.get(1)?;
+                let inode = fields.get(9)?;
+                (local.ends_with(&port) && *inode != "0").then(|| inode.to_string())
+            })
+        })?;
+    let socket = PathBuf::from(format!("socket:[{inode}]"));
+    std::fs::read_dir("/proc")
+        .ok()?
+        .flatten()
+        .find_map(|entry| {
+            let pid: u32 = entry.file_name().to_str()?.parse().ok()?;
+            std::fs::read_dir(entry.path().join("fd"))
+                .ok()?
+                .flatten()
+                .any(|fd| std::fs::read_link(fd.path()).is_ok_and(|target| target == socket))
+                .then_some(pid)
+        })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn port_owner(_port: u16) -> Option<u32> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn process_name(pid: u32) -> Option<String> {
+    let name = std::fs::read_to_string(format!("/proc/{pid}/comm")).ok()?;
+    Some(name.trim_end().to_string())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_name(_pid: u32) -> Option<String> {
+    None
+}
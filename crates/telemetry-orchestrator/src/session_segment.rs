@@ -0,0 +1,431 @@
+//! Session and lap boundaries inferred from a frame stream.
+//!
+//! A recording or live stream rarely says where one race ends and the next
+//! begins. [`SessionSegmenter`] works that out from [`NormalizedTelemetry`]
+//! alone, with no per-game hooks:
+//!
+//! - a session starts on the first frame where the car is not sitting at zero
+//!   speed and zero rpm;
+//! - it ends when the track, car or session id changes, when frame timestamps
+//!   jump by more than [`SegmenterConfig::max_frame_gap`], when speed and rpm
+//!   stay at zero for [`SegmenterConfig::idle_timeout`], or when the checkered
+//!   flag comes out.
+//!
+//! The gap and idle thresholds are the hysteresis: a pause shorter than either
+//! keeps the session going. After a checkered flag no new session starts until
+//! the flag clears or the car has sat idle, so the cool-down lap is not a
+//! session of its own.
+//!
+//! Laps complete when the lap counter (`timing.lap_number`, else `lap`)
+//! advances, or, if the counter lags, when the current lap time drops back
+//! towards zero. Two signals for the same crossing are reported once.
+
+use std::ops::Range;
+use std::time::Duration;
+
+use racing_wheel_telemetry_adapters::{NormalizedTelemetry, TelemetryFrame};
+use serde::{Deserialize, Serialize};
+
+/// A lap-time drop smaller than this is jitter, not a new lap.
+const LAP_TIMER_ROLLOVER_MS: f64 = 1_000.0;
+
+/// Tunables for [`SessionSegmenter`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SegmenterConfig {
+    /// A timestamp jump longer than this ends the session, e.g. time spent in
+    /// a menu that sends no telemetry.
+    pub max_frame_gap: Duration,
+    /// Speed and rpm must stay at zero this long to end the session.
+    pub idle_timeout: Duration,
+    /// Speed, in m/s, at or below which the car counts as stopped.
+    pub idle_speed_ms: f32,
+    /// Rpm at or below which the engine counts as off.
+    pub idle_rpm: f32,
+    /// End the session when the checkered flag comes out.
+    pub end_on_checkered: bool,
+    /// Lap signals this soon after a reported lap are the same crossing.
+    pub lap_debounce: Duration,
+}
+
+impl Default for SegmenterConfig {
+    fn default() -> Self {
+        Self {
+            max_frame_gap: Duration::from_secs(10),
+            idle_timeout: Duration::from_secs(30),
+            idle_speed_ms: 0.1,
+            idle_rpm: 1.0,
+            end_on_checkered: true,
+            lap_debounce: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Why a session ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionEndReason {
+    /// The track, car or session id changed.
+    IdentityChanged,
+    /// Frames stopped for longer than [`SegmenterConfig::max_frame_gap`].
+    FrameGap,
+    /// The car sat at zero speed and rpm for [`SegmenterConfig::idle_timeout`].
+    Idle,
+    /// The checkered flag came out.
+    Checkered,
+    /// The stream ended with the session still open.
+    StreamEnded,
+}
+
+/// A completed lap.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LapRecord {
+    /// The lap that was completed, counting from 1.
+    pub lap_number: u32,
+    /// Its time, if the game reported one or the lap timer was seen running.
+    pub lap_time_ms: Option<f64>,
+    /// `timestamp_ns` of the frame on which the lap completed.
+    pub completed_ns: u64,
+}
+
+/// One session, as found by [`segment`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionSpan {
+    /// Indices of the session's frames in the segmented stream. Trailing idle
+    /// frames before an [`SessionEndReason::Idle`] end are excluded.
+    pub frames: Range<usize>,
+    pub start_ns: u64,
+    pub end_ns: u64,
+    pub track_id: Option<String>,
+    pub car_id: Option<String>,
+    pub session_id: Option<String>,
+    pub laps: Vec<LapRecord>,
+    pub end_reason: SessionEndReason,
+}
+
+impl SessionSpan {
+    /// Time from the first to the last frame of the session.
+    pub fn duration(&self) -> Duration {
+        Duration::from_nanos(self.end_ns.saturating_sub(self.start_ns))
+    }
+}
+
+/// What a frame changed, as returned by [`SessionSegmenter::push`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SessionEvent {
+    SessionStarted {
+        start_ns: u64,
+        track_id: Option<String>,
+        car_id: Option<String>,
+    },
+    LapCompleted {
+        lap_number: u32,
+        lap_time_ms: Option<f64>,
+    },
+    /// The finished session, including its laps.
+    SessionEnded(SessionSpan),
+}
+
+/// Streaming session and lap detector; see the module docs for the rules.
+#[derive(Debug, Default)]
+pub struct SessionSegmenter {
+    config: SegmenterConfig,
+    session: Option<OpenSession>,
+    next_index: usize,
+    last_ns: Option<u64>,
+    /// Start of the current run of idle frames.
+    idle_since: Option<u64>,
+    previous_checkered: bool,
+    /// A checkered flag ended the last session and the car has not left it.
+    finished: bool,
+}
+
+#[derive(Debug)]
+struct OpenSession {
+    start_index: usize,
+    start_ns: u64,
+    last_index: usize,
+    last_ns: u64,
+    last_active_index: usize,
+    last_active_ns: u64,
+    track_id: Option<String>,
+    car_id: Option<String>,
+    session_id: Option<String>,
+    laps: LapTracker,
+}
+
+impl SessionSegmenter {
+    pub fn new(config: SegmenterConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    pub fn config(&self) -> &SegmenterConfig {
+        &self.config
+    }
+
+    /// Whether a session is in progress.
+    pub fn in_session(&self) -> bool {
+        self.session.is_some()
+    }
+
+    /// Feed the next frame, in stream order, and return what it changed.
+    pub fn push(&mut self, frame: &TelemetryFrame) -> Vec<SessionEvent> {
+        let index = self.next_index;
+        self.next_index += 1;
+        let timestamp_ns = frame.timestamp_ns;
+        let data = &frame.data;
+        let mut events = Vec::new();
+
+        let gap = self.last_ns.is_some_and(|last| {
+            Duration::from_nanos(timestamp_ns.saturating_sub(last)) > self.config.max_frame_gap
+        });
+        self.last_ns = Some(timestamp_ns);
+        let idle = data.speed_ms.abs() <= self.config.idle_speed_ms
+            && data.rpm.abs() <= self.config.idle_rpm;
+        if gap || !idle {
+            self.idle_since = None;
+        }
+        if idle {
+            self.idle_since.get_or_insert(timestamp_ns);
+        }
+        let idled_out = self.idle_since.is_some_and(|since| {
+            Duration::from_nanos(timestamp_ns.saturating_sub(since)) >= self.config.idle_timeout
+        });
+
+        let end_reason = self.session.as_ref().and_then(|session| {
+            if gap {
+                Some(SessionEndReason::FrameGap)
+            } else if session.identity_changed(data) {
+                Some(SessionEndReason::IdentityChanged)
+            } else if idled_out {
+                Some(SessionEndReason::Idle)
+            } else {
+                None
+            }
+        });
+        if let Some(reason) = end_reason {
+            events.extend(self.end(reason));
+        }
+
+        let checkered = data.flags.checkered_flag;
+        if gap || idled_out || !checkered || end_reason == Some(SessionEndReason::IdentityChanged) {
+            self.finished = false;
+        }
+        if self.session.is_none() && !idle && !self.finished {
+            self.session = Some(OpenSession::start(index, timestamp_ns, data));
+            events.push(SessionEvent::SessionStarted {
+                start_ns: timestamp_ns,
+                track_id: data.track_id.clone(),
+                car_id: data.car_id.clone(),
+            });
+        }
+
+        if let Some(session) = &mut self.session {
+            if let Some(lap) = session.observe(index, timestamp_ns, data, idle, &self.config) {
+                events.push(SessionEvent::LapCompleted {
+                    lap_number: lap.lap_number,
+                    lap_time_ms: lap.lap_time_ms,
+                });
+            }
+            if self.config.end_on_checkered && checkered && !self.previous_checkered {
+                events.extend(self.end(SessionEndReason::Checkered));
+                self.finished = true;
+            }
+        }
+        self.previous_checkered = checkered;
+        events
+    }
+
+    /// Close the open session, if any, at the end of the stream.
+    pub fn finish(&mut self) -> Option<SessionEvent> {
+        self.end(SessionEndReason::StreamEnded)
+    }
+
+    fn end(&mut self, reason: SessionEndReason) -> Option<SessionEvent> {
+        let session = self.session.take()?;
+        let (last_index, end_ns) = if reason == SessionEndReason::Idle {
+            (session.last_active_index, session.last_active_ns)
+        } else {
+            (session.last_index, session.last_ns)
+        };
+        Some(SessionEvent::SessionEnded(SessionSpan {
+            frames: session.start_index..last_index + 1,
+            start_ns: session.start_ns,
+            end_ns,
+            track_id: session.track_id,
+            car_id: session.car_id,
+            session_id: session.session_id,
+            laps: session.laps.completed,
+            end_reason: reason,
+        }))
+    }
+}
+
+impl OpenSession {
+    fn start(index: usize, timestamp_ns: u64, data: &NormalizedTelemetry) -> Self {
+        Self {
+            start_index: index,
+            start_ns: timestamp_ns,
+            last_index: index,
+            last_ns: timestamp_ns,
+            last_active_index: index,
+            last_active_ns: timestamp_ns,
+            track_id: data.track_id.clone(),
+            car_id: data.car_id.clone(),
+            session_id: data.session_id.clone(),
+            laps: LapTracker::default(),
+        }
+    }
+
+    /// A known id that differs from the session's. An id the session has
+    /// not seen yet is adopted instead.
+    fn identity_changed(&self, data: &NormalizedTelemetry) -> bool {
+        let differs = |ours: &Option<String>, theirs: &Option<String>| matches!((ours, theirs), (Some(ours), Some(theirs)) if ours != theirs);
+        differs(&self.track_id, &data.track_id)
+            || differs(&self.car_id, &data.car_id)
+            || differs(&self.session_id, &data.session_id)
+    }
+
+    fn observe(
+        &mut self,
+        index: usize,
+        timestamp_ns: u64,
+        data: &NormalizedTelemetry,
+        idle: bool,
+        config: &SegmenterConfig,
+    ) -> Option<LapRecord> {
+        self.last_index = index;
+        self.last_ns = timestamp_ns;
+        if !idle {
+            self.last_active_index = index;
+            self.last_active_ns = timestamp_ns;
+        }
+        for (ours, theirs) in [
+            (&mut self.track_id, &data.track_id),
+            (&mut self.car_id, &data.car_id),
+            (&mut self.session_id, &data.session_id),
+        ] {
+            if ours.is_none() {
+                ours.clone_from(theirs);
+            }
+        }
+        self.laps.observe(timestamp_ns, data, config.lap_debounce)
+    }
+}
+
+#[derive(Debug, Default)]
+struct LapTracker {
+    /// Current lap, counting from 1, as of the previous frame.
+    lap: Option<u32>,
+    current_lap_ms: Option<f64>,
+    last_lap_ms: Option<f64>,
+    last_completed_ns: Option<u64>,
+    completed: Vec<LapRecord>,
+}
+
+impl LapTracker {
+    fn observe(
+        &mut self,
+        timestamp_ns: u64,
+        data: &NormalizedTelemetry,
+        debounce: Duration,
+    ) -> Option<LapRecord> {
+        let lap = current_lap(data);
+        let current_lap_ms = current_lap_ms(data);
+        let last_lap_ms = last_lap_ms(data);
+
+        let counter_completed = match (self.lap, lap) {
+            (Some(previous), Some(now)) if now > previous => Some(previous),
+            _ => None,
+        };
+        let timer_rolled = match (self.current_lap_ms, current_lap_ms) {
+            (Some(previous), Some(now)) => {
+                previous - now > LAP_TIMER_ROLLOVER_MS && now < previous / 2.0
+            }
+            _ => false,
+        };
+        let completed_lap = counter_completed.or_else(|| {
+            // A lagging counter still names the lap just finished; one that
+            // never moves, because the game does not report it, does not.
+            timer_rolled.then(|| {
+                let next = self.completed.last().map_or(1, |lap| lap.lap_number + 1);
+                lap.or(self.lap).map_or(next, |lap| lap.max(next))
+            })
+        });
+        let debounced = self
+            .last_completed_ns
+            .is_some_and(|last| Duration::from_nanos(timestamp_ns.saturating_sub(last)) < debounce);
+
+        let record = match completed_lap {
+            Some(lap_number) if !debounced => {
+                // The game's last-lap time counts once it has been updated for
+                // this crossing; otherwise the timer just before it.
+                let game_time = last_lap_ms.filter(|_| last_lap_ms != self.last_lap_ms);
+                let record = LapRecord {
+                    lap_number,
+                    lap_time_ms: game_time.or(self.current_lap_ms.and_then(positive_ms)),
+                    completed_ns: timestamp_ns,
+                };
+                self.completed.push(record);
+                self.last_completed_ns = Some(timestamp_ns);
+                Some(record)
+            }
+            _ => None,
+        };
+        self.lap = lap;
+        self.current_lap_ms = current_lap_ms;
+        self.last_lap_ms = last_lap_ms;
+        record
+    }
+}
+
+fn current_lap(data: &NormalizedTelemetry) -> Option<u32> {
+    match data.timing.and_then(|timing| timing.lap_number) {
+        Some(lap) => u32::try_from(lap).ok(),
+        None => Some(u32::from(data.lap) + 1),
+    }
+}
+
+fn positive_ms(ms: f64) -> Option<f64> {
+    (ms.is_finite() && ms > 0.0).then_some(ms)
+}
+
+/// Zero is kept: a timer that has just reset reads zero.
+fn current_lap_ms(data: &NormalizedTelemetry) -> Option<f64> {
+    data.timing
+        .and_then(|timing| timing.current_lap_ms)
+        .or_else(|| Some(f64::from(data.current_lap_time_s) * 1_000.0))
+        .filter(|ms| ms.is_finite() && *ms >= 0.0)
+}
+
+fn last_lap_ms(data: &NormalizedTelemetry) -> Option<f64> {
+    data.timing
+        .and_then(|timing| timing.last_lap_ms)
+        .or_else(|| Some(f64::from(data.last_lap_time_s) * 1_000.0))
+        .and_then(positive_ms)
+}
+
+/// Split a recorded stream into sessions with the default configuration.
+pub fn segment<'a>(frames: impl IntoIterator<Item = &'a TelemetryFrame>) -> Vec<SessionSpan> {
+    segment_with(SegmenterConfig::default(), frames)
+}
+
+/// [`segment`] with explicit thresholds.
+pub fn segment_with<'a>(
+    config: SegmenterConfig,
+    frames: impl IntoIterator<Item = &'a TelemetryFrame>,
+) -> Vec<SessionSpan> {
+    let mut segmenter = SessionSegmenter::new(config);
+    let mut spans = Vec::new();
+    let ended = |event: SessionEvent| match event {
+        SessionEvent::SessionEnded(span) => Some(span),
+        _ => None,
+    };
+    for frame in frames {
+        spans.extend(segmenter.push(frame).into_iter().filter_map(ended));
+    }
+    spans.extend(segmenter.finish().and_then(ended));
+    spans
+}
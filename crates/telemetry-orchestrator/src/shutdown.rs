@@ -0,0 +1,67 @@
+//! Stopping the whole service without losing the tail of a recording.
+//!
+//! `TelemetryService::shutdown` signals every forwarding task to drain: each
+//! stops waiting on its consumer, takes the frames still queued by its
+//! adapter through the usual pipeline, recorder included, and exits. The
+//! service then waits for the tasks, bounded by a timeout, and finalizes
+//! the recorder so its file holds every frame forwarded or drained.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use racing_wheel_telemetry_recorder::SavedRecording;
+use tokio::sync::watch;
+
+/// Outcome of `TelemetryService::shutdown`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// Games that were being forwarded, sorted.
+    pub games_stopped: Vec<String>,
+    /// Frames taken from adapters' queues after the shutdown began.
+    pub frames_drained: u64,
+    /// Whether some forwarding task or adapter had not stopped in time; such
+    /// tasks are aborted.
+    pub timed_out: bool,
+    /// Adapters whose `stop_monitoring` failed, with their errors.
+    pub stop_failures: BTreeMap<String, String>,
+    /// The finalized recording, if recording was enabled.
+    pub recording: Option<SavedRecording>,
+    /// Why the recording could not be finalized.
+    pub recording_error: Option<String>,
+}
+
+/// Tells forwarding tasks to drain, and counts what they drain.
+#[derive(Debug)]
+pub(crate) struct ShutdownSignal {
+    drain: watch::Sender<()>,
+    drained: AtomicU64,
+}
+
+impl Default for ShutdownSignal {
+    fn default() -> Self {
+        Self {
+            drain: watch::channel(()).0,
+            drained: AtomicU64::new(0),
+        }
+    }
+}
+
+impl ShutdownSignal {
+    /// Receiver that changes on the next [`trigger`](Self::trigger).
+    pub(crate) fn subscribe(&self) -> watch::Receiver<()> {
+        self.drain.subscribe()
+    }
+
+    pub(crate) fn trigger(&self) {
+        self.drain.send_replace(());
+    }
+
+    pub(crate) fn record_drained(&self) {
+        self.drained.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Frames drained since the last call.
+    pub(crate) fn take_drained(&self) -> u64 {
+        self.drained.swap(0, Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,685 @@
+//! Telemetry sink layer with per-sink encode cost accounting.
+//!
+//! Sinks are downstream consumers of the frame stream (IPC bridges, recorders,
+//! overlays). Each registered sink chooses a [`SinkEncoding`]; the hub encodes
+//! every frame once per sink, measures how long the encode took and how many
+//! bytes were emitted, and exposes those accumulators through [`SinkHub::sink_costs`].
+//!
+//! [`SinkEncoding::Auto`] starts with JSON and switches to the compact binary
+//! encoding when the average encode time over a full measurement window exceeds
+//! a fraction of the observed frame interval. The switch is one-way and is only
+//! permitted for sinks whose consumer declared support for both encodings.
+//!
+//! The service hands frames to the hub through a [`SinkQueue`], which runs the
+//! encoders and sinks on a thread of its own, so a slow sink never holds up
+//! the frame pipeline. When that thread falls [`SINK_QUEUE_CAPACITY`] frames
+//! behind, further frames skip the sinks and are counted in
+//! [`SinkHub::frames_dropped`].
+
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Instant;
+
+use anyhow::{Context, Result, bail};
+use racing_wheel_schemas::telemetry::encode_frame;
+use racing_wheel_telemetry_adapters::TelemetryFrame;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+/// Capacity of the sink event broadcast channel.
+const SINK_EVENT_CAPACITY: usize = 64;
+
+/// Frames waiting for the sink thread before further frames are dropped.
+pub const SINK_QUEUE_CAPACITY: usize = 256;
+
+/// Encoding requested for a sink at registration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SinkEncoding {
+    /// Always emit JSON.
+    Json,
+    /// Always emit the compact binary encoding.
+    Binary,
+    /// Start with JSON and switch to binary when JSON encoding is too expensive.
+    Auto,
+}
+
+/// Concrete wire encoding of an emitted payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WireEncoding {
+    /// JSON document per frame.
+    Json,
+    /// Versioned `ORTF` binary record per frame; see [`BinaryFrameEncoder`].
+    Binary,
+}
+
+/// Encodings a sink consumer can decode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SinkCapabilities {
+    /// Consumer accepts JSON payloads.
+    pub json: bool,
+    /// Consumer accepts binary payloads.
+    pub binary: bool,
+}
+
+impl SinkCapabilities {
+    /// Consumer only understands JSON.
+    pub const JSON_ONLY: Self = Self {
+        json: true,
+        binary: false,
+    };
+
+    /// Consumer only understands the binary encoding.
+    pub const BINARY_ONLY: Self = Self {
+        json: false,
+        binary: true,
+    };
+
+    /// Consumer accepts either encoding and may be switched at runtime.
+    pub const ANY: Self = Self {
+        json: true,
+        binary: true,
+    };
+
+    fn supports(self, encoding: WireEncoding) -> bool {
+        match encoding {
+            WireEncoding::Json => self.json,
+            WireEncoding::Binary => self.binary,
+        }
+    }
+}
+
+/// Thresholds used by [`SinkEncoding::Auto`] to decide when to switch encodings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AutoEncodingPolicy {
+    /// Fraction of the observed frame interval a sink may spend encoding each frame.
+    pub budget_fraction: f64,
+    /// Number of frames averaged before the budget is evaluated.
+    pub window_frames: u32,
+}
+
+impl Default for AutoEncodingPolicy {
+    fn default() -> Self {
+        Self {
+            budget_fraction: 0.05,
+            window_frames: 120,
+        }
+    }
+}
+
+/// Frame encoder used by the sink hub.
+pub trait FrameEncoder: Send + Sync {
+    /// Append the encoded representation of `frame` to `out`.
+    fn encode(&self, frame: &TelemetryFrame, out: &mut Vec<u8>) -> Result<()>;
+}
+
+/// JSON frame encoder backed by `serde_json`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct JsonFrameEncoder;
+
+impl FrameEncoder for JsonFrameEncoder {
+    fn encode(&self, frame: &TelemetryFrame, out: &mut Vec<u8>) -> Result<()> {
+        serde_json::to_writer(out, frame)?;
+        Ok(())
+    }
+}
+
+/// Binary frame encoder writing the versioned `ORTF` record of the
+/// `racing_wheel_telemetry_contracts::codec` layout, the same one binary
+/// recordings use. Consumers read it back with
+/// [`racing_wheel_schemas::telemetry::decode_frame`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BinaryFrameEncoder;
+
+impl FrameEncoder for BinaryFrameEncoder {
+    fn encode(&self, frame: &TelemetryFrame, out: &mut Vec<u8>) -> Result<()> {
+        out.extend_from_slice(&encode_frame(frame)?);
+        Ok(())
+    }
+}
+
+/// Downstream consumer of encoded telemetry frames.
+pub trait TelemetrySink: Send {
+    /// Deliver one encoded frame.
+    fn write(&mut self, encoding: WireEncoding, payload: &[u8]) -> Result<()>;
+}
+
+/// Registration request for a sink.
+pub struct SinkRegistration {
+    /// Stable sink name used in cost reports and events.
+    pub name: String,
+    /// Requested encoding.
+    pub encoding: SinkEncoding,
+    /// Encodings the consumer declared it can decode.
+    pub capabilities: SinkCapabilities,
+    /// The sink itself.
+    pub sink: Box<dyn TelemetrySink>,
+}
+
+impl SinkRegistration {
+    /// Create a registration with the given encoding and capabilities.
+    pub fn new(
+        name: impl Into<String>,
+        encoding: SinkEncoding,
+        capabilities: SinkCapabilities,
+        sink: Box<dyn TelemetrySink>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            encoding,
+            capabilities,
+            sink,
+        }
+    }
+}
+
+/// Per-sink encode overhead summary for status displays.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SinkCost {
+    /// Sink name.
+    pub name: String,
+    /// Encoding requested at registration.
+    pub requested_encoding: SinkEncoding,
+    /// Encoding currently used for emitted payloads.
+    pub active_encoding: WireEncoding,
+    /// Frames encoded for this sink.
+    pub frames_encoded: u64,
+    /// Total time spent encoding, in nanoseconds.
+    pub encode_ns_total: u64,
+    /// Total payload bytes emitted.
+    pub bytes_emitted: u64,
+    /// Encode or write failures.
+    pub errors: u64,
+    /// Number of encoding switches performed by [`SinkEncoding::Auto`].
+    pub encoding_switches: u32,
+}
+
+impl SinkCost {
+    /// Mean encode time per frame in nanoseconds.
+    pub fn mean_encode_ns(&self) -> f64 {
+        if self.frames_encoded == 0 {
+            return 0.0;
+        }
+        self.encode_ns_total as f64 / self.frames_encoded as f64
+    }
+
+    /// Mean payload size per frame in bytes.
+    pub fn mean_bytes_per_frame(&self) -> f64 {
+        if self.frames_encoded == 0 {
+            return 0.0;
+        }
+        self.bytes_emitted as f64 / self.frames_encoded as f64
+    }
+}
+
+/// Events emitted by the sink hub.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SinkEvent {
+    /// An auto-encoding sink changed its wire encoding.
+    EncodingSwitched {
+        /// Sink name.
+        sink: String,
+        /// Previous encoding.
+        from: WireEncoding,
+        /// New encoding.
+        to: WireEncoding,
+        /// Mean encode time per frame over the triggering window.
+        mean_encode_ns: u64,
+        /// Mean frame interval over the triggering window.
+        mean_frame_interval_ns: u64,
+        /// Mean payload size per frame over the triggering window.
+        mean_bytes_per_frame: u64,
+    },
+}
+
+#[derive(Default)]
+struct CostWindow {
+    frames: u32,
+    encode_ns: u64,
+    bytes: u64,
+}
+
+struct SinkSlot {
+    name: String,
+    requested: SinkEncoding,
+    capabilities: SinkCapabilities,
+    active: WireEncoding,
+    sink: Box<dyn TelemetrySink>,
+    cost: SinkCost,
+    window: CostWindow,
+}
+
+struct HubState {
+    slots: Vec<SinkSlot>,
+    buffer: Vec<u8>,
+    last_arrival: Option<Instant>,
+    window_interval_ns: u64,
+    window_intervals: u32,
+}
+
+/// Fan-out point that encodes frames for every registered sink.
+pub struct SinkHub {
+    state: Mutex<HubState>,
+    policy: AutoEncodingPolicy,
+    json: Arc<dyn FrameEncoder>,
+    binary: Arc<dyn FrameEncoder>,
+    events: broadcast::Sender<SinkEvent>,
+    /// Mirrors the number of slots, so the pipeline can skip an idle hub
+    /// without waiting on a dispatch in progress.
+    registered: AtomicUsize,
+    dropped: AtomicU64,
+}
+
+impl Default for SinkHub {
+    fn default() -> Self {
+        Self::new(AutoEncodingPolicy::default())
+    }
+}
+
+impl SinkHub {
+    /// Create a hub using the built-in JSON and binary encoders.
+    pub fn new(policy: AutoEncodingPolicy) -> Self {
+        Self::with_encoders(
+            policy,
+            Arc::new(JsonFrameEncoder),
+            Arc::new(BinaryFrameEncoder),
+        )
+    }
+
+    /// Create a hub with custom encoder implementations.
+    pub fn with_encoders(
+        policy: AutoEncodingPolicy,
+        json: Arc<dyn FrameEncoder>,
+        binary: Arc<dyn FrameEncoder>,
+    ) -> Self {
+        let (events, _) = broadcast::channel(SINK_EVENT_CAPACITY);
+        Self {
+            state: Mutex::new(HubState {
+                slots: Vec::new(),
+                buffer: Vec::with_capacity(1024),
+                last_arrival: None,
+                window_interval_ns: 0,
+                window_intervals: 0,
+            }),
+            policy,
+            json,
+            binary,
+            events,
+            registered: AtomicUsize::new(0),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HubState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Register a sink. Fails when the requested encoding is not supported by the consumer.
+    pub fn register(&self, registration: SinkRegistration) -> Result<()> {
+        let SinkRegistration {
+            name,
+            encoding,
+            capabilities,
+            sink,
+        } = registration;
+
+        let active = match encoding {
+            SinkEncoding::Json | SinkEncoding::Auto if capabilities.json => WireEncoding::Json,
+            SinkEncoding::Binary | SinkEncoding::Auto if capabilities.binary => {
+                WireEncoding::Binary
+            }
+            _ => bail!(
+                "Sink '{}' requested {:?} encoding but declared capabilities {:?}",
+                name,
+                encoding,
+                capabilities
+            ),
+        };
+
+        let mut state = self.lock();
+        if state.slots.iter().any(|slot| slot.name == name) {
+            bail!("Sink '{}' is already registered", name);
+        }
+
+        state.slots.push(SinkSlot {
+            cost: SinkCost {
+                name: name.clone(),
+                requested_encoding: encoding,
+                active_encoding: active,
+                frames_encoded: 0,
+                encode_ns_total: 0,
+                bytes_emitted: 0,
+                errors: 0,
+                encoding_switches: 0,
+            },
+            name,
+            requested: encoding,
+            capabilities,
+            active,
+            sink,
+            window: CostWindow::default(),
+        });
+        self.registered.store(state.slots.len(), Ordering::Release);
+        Ok(())
+    }
+
+    /// Remove a sink by name. Returns whether a sink was removed.
+    pub fn unregister(&self, name: &str) -> bool {
+        let mut state = self.lock();
+        let before = state.slots.len();
+        state.slots.retain(|slot| slot.name != name);
+        self.registered.store(state.slots.len(), Ordering::Release);
+        state.slots.len() != before
+    }
+
+    /// Number of registered sinks.
+    pub fn sink_count(&self) -> usize {
+        self.lock().slots.len()
+    }
+
+    /// Frames that skipped every sink because the sink thread was
+    /// [`SINK_QUEUE_CAPACITY`] frames behind.
+    pub fn frames_dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Subscribe to encoding switch events.
+    pub fn subscribe(&self) -> broadcast::Receiver<SinkEvent> {
+        self.events.subscribe()
+    }
+
+    /// Per-sink encode overhead summary.
+    pub fn sink_costs(&self) -> Vec<SinkCost> {
+        self.lock()
+            .slots
+            .iter()
+            .map(|slot| slot.cost.clone())
+            .collect()
+    }
+
+    /// Encode `frame` for every registered sink and deliver it.
+    pub fn dispatch(&self, frame: &TelemetryFrame) {
+        self.dispatch_arrived(frame, Instant::now());
+    }
+
+    /// Like [`dispatch`](Self::dispatch), but pace the auto-switch window by
+    /// when `frame` arrived rather than when the sink thread got to it, so a
+    /// backlog in the queue does not look like a faster frame rate.
+    pub(crate) fn dispatch_arrived(&self, frame: &TelemetryFrame, arrived: Instant) {
+        let mut guard = self.lock();
+        let state = &mut *guard;
+        if state.slots.is_empty() {
+            return;
+        }
+
+        if let Some(last) = state.last_arrival {
+            let interval = arrived.saturating_duration_since(last).as_nanos();
+            state.window_interval_ns = state
+                .window_interval_ns
+                .saturating_add(u64::try_from(interval).unwrap_or(u64::MAX));
+            state.window_intervals += 1;
+        }
+        state.last_arrival = Some(arrived);
+
+        let mean_interval_ns = if state.window_intervals == 0 {
+            None
+        } else {
+            Some(state.window_interval_ns / u64::from(state.window_intervals))
+        };
+
+        let mut window_complete = false;
+        for slot in &mut state.slots {
+            let encoder = match slot.active {
+                WireEncoding::Json => &self.json,
+                WireEncoding::Binary => &self.binary,
+            };
+
+            state.buffer.clear();
+            let started = Instant::now();
+            let encoded = encoder.encode(frame, &mut state.buffer);
+            let elapsed_ns = u64::try_from(started.elapsed().as_nanos()).unwrap_or(u64::MAX);
+
+            slot.cost.frames_encoded += 1;
+            slot.cost.encode_ns_total = slot.cost.encode_ns_total.saturating_add(elapsed_ns);
+            slot.window.frames += 1;
+            slot.window.encode_ns = slot.window.encode_ns.saturating_add(elapsed_ns);
+
+            match encoded.and_then(|()| slot.sink.write(slot.active, &state.buffer)) {
+                Ok(()) => {
+                    let len = state.buffer.len() as u64;
+                    slot.cost.bytes_emitted += len;
+                    slot.window.bytes += len;
+                }
+                Err(error) => {
+                    slot.cost.errors += 1;
+                    warn!(sink = %slot.name, error = %error, "Telemetry sink write failed");
+                }
+            }
+
+            if slot.window.frames >= self.policy.window_frames {
+                window_complete = true;
+                if let Some(interval_ns) = mean_interval_ns {
+                    self.evaluate_auto_switch(slot, interval_ns);
+                }
+                slot.window = CostWindow::default();
+            }
+        }
+
+        if window_complete {
+            state.window_interval_ns = 0;
+            state.window_intervals = 0;
+        }
+    }
+
+    fn has_sinks(&self) -> bool {
+        self.registered.load(Ordering::Acquire) > 0
+    }
+
+    fn evaluate_auto_switch(&self, slot: &mut SinkSlot, mean_interval_ns: u64) {
+        if slot.requested != SinkEncoding::Auto
+            || slot.active != WireEncoding::Json
+            || !slot.capabilities.supports(WireEncoding::Binary)
+            || slot.window.frames == 0
+        {
+            return;
+        }
+
+        let frames = u64::from(slot.window.frames);
+        let mean_encode_ns = slot.window.encode_ns / frames;
+        let budget_ns = mean_interval_ns as f64 * self.policy.budget_fraction;
+        if (mean_encode_ns as f64) <= budget_ns {
+            return;
+        }
+
+        let mean_bytes_per_frame = slot.window.bytes / frames;
+        slot.active = WireEncoding::Binary;
+        slot.cost.active_encoding = WireEncoding::Binary;
+        slot.cost.encoding_switches += 1;
+
+        info!(
+            sink = %slot.name,
+            mean_encode_ns,
+            mean_frame_interval_ns = mean_interval_ns,
+            mean_bytes_per_frame,
+            "Switching telemetry sink from JSON to binary encoding"
+        );
+
+        let _ = self.events.send(SinkEvent::EncodingSwitched {
+            sink: slot.name.clone(),
+            from: WireEncoding::Json,
+            to: WireEncoding::Binary,
+            mean_encode_ns,
+            mean_frame_interval_ns: mean_interval_ns,
+            mean_bytes_per_frame,
+        });
+    }
+}
+
+/// Feeds a [`SinkHub`] from a thread of its own, which ends once the queue is
+/// dropped.
+pub(crate) struct SinkQueue {
+    frames: SyncSender<(TelemetryFrame, Instant)>,
+    hub: Arc<SinkHub>,
+}
+
+impl SinkQueue {
+    pub(crate) fn spawn(hub: Arc<SinkHub>) -> Result<Self> {
+        let (frames, queued) = mpsc::sync_channel::<(TelemetryFrame, Instant)>(SINK_QUEUE_CAPACITY);
+        let worker = Arc::clone(&hub);
+        std::thread::Builder::new()
+            .name("telemetry-sinks".to_string())
+            .spawn(move || {
+                for (frame, arrived) in queued {
+                    worker.dispatch_arrived(&frame, arrived);
+                }
+            })
+            .context("Failed to spawn the telemetry sink thread")?;
+        Ok(Self { frames, hub })
+    }
+
+    /// Queue `frame` for every sink without waiting; dropped and counted if
+    /// the sink thread is too far behind.
+    pub(crate) fn push(&self, frame: &TelemetryFrame) {
+        if !self.hub.has_sinks() {
+            return;
+        }
+        match self.frames.try_send((frame.clone(), Instant::now())) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => {
+                self.hub.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                warn!("Telemetry sink thread has stopped; frame not delivered to sinks");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use racing_wheel_schemas::telemetry::has_binary_magic;
+    use racing_wheel_telemetry_adapters::{NormalizedTelemetry, TelemetryValue};
+
+    struct CountingSink(Arc<Mutex<Vec<WireEncoding>>>);
+
+    impl TelemetrySink for CountingSink {
+        fn write(&mut self, encoding: WireEncoding, _payload: &[u8]) -> Result<()> {
+            if let Ok(mut seen) = self.0.lock() {
+                seen.push(encoding);
+            }
+            Ok(())
+        }
+    }
+
+    fn frame() -> TelemetryFrame {
+        let mut data = NormalizedTelemetry::builder().rpm(6500.0).gear(3).build();
+        data.extended
+            .insert("tc_level".to_string(), TelemetryValue::Integer(4));
+        TelemetryFrame::new(data, 1_000, 1, 64)
+    }
+
+    #[test]
+    fn binary_encoding_is_smaller_than_json() -> Result<()> {
+        let mut json = Vec::new();
+        let mut binary = Vec::new();
+        JsonFrameEncoder.encode(&frame(), &mut json)?;
+        BinaryFrameEncoder.encode(&frame(), &mut binary)?;
+        assert!(binary.len() < json.len());
+        assert!(has_binary_magic(&binary));
+        Ok(())
+    }
+
+    #[test]
+    fn binary_request_without_capability_is_rejected() {
+        let hub = SinkHub::default();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let result = hub.register(SinkRegistration::new(
+            "json_only",
+            SinkEncoding::Binary,
+            SinkCapabilities::JSON_ONLY,
+            Box::new(CountingSink(seen)),
+        ));
+        assert!(result.is_err());
+        assert_eq!(hub.sink_count(), 0);
+    }
+
+    #[test]
+    fn duplicate_sink_names_are_rejected() -> Result<()> {
+        let hub = SinkHub::default();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        hub.register(SinkRegistration::new(
+            "overlay",
+            SinkEncoding::Json,
+            SinkCapabilities::ANY,
+            Box::new(CountingSink(seen.clone())),
+        ))?;
+        let duplicate = hub.register(SinkRegistration::new(
+            "overlay",
+            SinkEncoding::Json,
+            SinkCapabilities::ANY,
+            Box::new(CountingSink(seen)),
+        ));
+        assert!(duplicate.is_err());
+        assert!(hub.unregister("overlay"));
+        assert!(!hub.unregister("overlay"));
+        Ok(())
+    }
+
+    #[test]
+    fn dispatch_accumulates_costs() -> Result<()> {
+        let hub = SinkHub::default();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        hub.register(SinkRegistration::new(
+            "binary",
+            SinkEncoding::Binary,
+            SinkCapabilities::BINARY_ONLY,
+            Box::new(CountingSink(seen.clone())),
+        ))?;
+
+        for _ in 0..5 {
+            hub.dispatch(&frame());
+        }
+
+        let costs = hub.sink_costs();
+        assert_eq!(costs.len(), 1);
+        assert_eq!(costs[0].frames_encoded, 5);
+        assert!(costs[0].bytes_emitted > 0);
+        assert!(costs[0].mean_bytes_per_frame() > 0.0);
+        assert_eq!(costs[0].active_encoding, WireEncoding::Binary);
+        let seen = seen.lock().map_err(|_| anyhow::anyhow!("poisoned"))?;
+        assert_eq!(seen.len(), 5);
+        Ok(())
+    }
+
+    #[test]
+    fn frame_interval_follows_arrival_not_dispatch() -> Result<()> {
+        let hub = SinkHub::default();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        hub.register(SinkRegistration::new(
+            "binary",
+            SinkEncoding::Binary,
+            SinkCapabilities::BINARY_ONLY,
+            Box::new(CountingSink(seen)),
+        ))?;
+
+        // Frames drained back-to-back from a backlog still report the rate
+        // they arrived at.
+        let start = Instant::now();
+        let step = std::time::Duration::from_millis(16);
+        for n in 0..3 {
+            hub.dispatch_arrived(&frame(), start + step * n);
+        }
+
+        let state = hub.lock();
+        assert_eq!(state.window_intervals, 2);
+        assert_eq!(
+            state.window_interval_ns / u64::from(state.window_intervals),
+            16_000_000
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,394 @@
+//! Latest frame per game, and a small request/response endpoint serving it.
+//!
+//! Consumers that poll a few times a second, such as a companion app, do not
+//! want the full stream. Every monitored game's forwarding task stores each
+//! frame it forwards in that game's slot of a [`TelemetrySnapshotCache`], and
+//! readers take a copy of the most recent one together with its age.
+//!
+//! The store never waits: if a reader is copying the previous frame at that
+//! instant, the new frame is skipped and the next one replaces it.
+//!
+//! [`serve`] answers newline-delimited JSON requests over a Unix domain
+//! socket or a TCP address, one response line per request line:
+//!
+//! ```json
+//! {"cmd": "latest", "game_id": "acc"}
+//! {"game_id": "acc", "age_ms": 12, "frame": {...}}
+//!
+//! {"cmd": "latest_all"}
+//! {"games": {"acc": {"age_ms": 12, "frame": {...}}}}
+//! ```
+//!
+//! A request that cannot be answered gets `{"error": "..."}` and the
+//! connection stays open.
+
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, SocketAddr};
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, PoisonError, RwLock, TryLockError};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use racing_wheel_telemetry_adapters::TelemetryFrame;
+use racing_wheel_telemetry_support::normalize_game_id;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+#[cfg(unix)]
+use tokio::net::UnixListener;
+use tokio::sync::watch;
+use tokio::task::{JoinHandle, JoinSet};
+use tracing::{debug, warn};
+
+/// Longest request line accepted; a connection sending more is closed.
+pub const MAX_SNAPSHOT_REQUEST_BYTES: usize = 4096;
+
+/// TCP port of [`SnapshotEndpoint::platform_default`] where Unix domain
+/// sockets are unavailable.
+pub const DEFAULT_SNAPSHOT_TCP_PORT: u16 = 28_777;
+
+/// A game's most recent frame and how long ago it was stored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrameSnapshot {
+    /// Milliseconds since the frame was forwarded; grows while the game is
+    /// quiet.
+    pub age_ms: u64,
+    pub frame: TelemetryFrame,
+}
+
+/// The latest forwarded frame of every game monitored so far.
+#[derive(Debug, Default)]
+pub struct TelemetrySnapshotCache {
+    slots: RwLock<HashMap<String, Arc<SnapshotSlot>>>,
+}
+
+impl TelemetrySnapshotCache {
+    /// The slot `game_id`'s forwarding task stores into, created on first use.
+    pub(crate) fn slot(&self, game_id: &str) -> Arc<SnapshotSlot> {
+        let mut slots = self.slots.write().unwrap_or_else(PoisonError::into_inner);
+        Arc::clone(slots.entry(game_id.to_string()).or_default())
+    }
+
+    fn get(&self, game_id: &str) -> Option<Arc<SnapshotSlot>> {
+        self.slots
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(normalize_game_id(game_id))
+            .cloned()
+    }
+
+    /// Most recent frame of `game_id`, if it has forwarded one.
+    pub fn latest(&self, game_id: &str) -> Option<TelemetryFrame> {
+        self.snapshot(game_id).map(|snapshot| snapshot.frame)
+    }
+
+    /// Most recent frame of every game that has forwarded one.
+    pub fn latest_all(&self) -> HashMap<String, TelemetryFrame> {
+        self.snapshot_all()
+            .into_iter()
+            .map(|(game_id, snapshot)| (game_id, snapshot.frame))
+            .collect()
+    }
+
+    /// Time since `game_id` last forwarded a frame.
+    pub fn age(&self, game_id: &str) -> Option<Duration> {
+        self.get(game_id)?.age()
+    }
+
+    /// Most recent frame of `game_id` with its age.
+    pub fn snapshot(&self, game_id: &str) -> Option<FrameSnapshot> {
+        self.get(game_id)?.snapshot()
+    }
+
+    /// [`Self::snapshot`] of every game that has forwarded a frame.
+    pub fn snapshot_all(&self) -> HashMap<String, FrameSnapshot> {
+        let slots: Vec<_> = self
+            .slots
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(game_id, slot)| (game_id.clone(), Arc::clone(slot)))
+            .collect();
+        slots
+            .into_iter()
+            .filter_map(|(game_id, slot)| Some((game_id, slot.snapshot()?)))
+            .collect()
+    }
+
+    /// Frames not stored because a reader held the slot; a high count means
+    /// readers are polling far more often than they need to.
+    pub fn skipped_stores(&self) -> u64 {
+        self.slots
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .values()
+            .map(|slot| slot.skipped.load(Ordering::Relaxed))
+            .sum()
+    }
+}
+
+/// One game's latest frame.
+#[derive(Debug, Default)]
+pub(crate) struct SnapshotSlot {
+    latest: RwLock<Option<StoredFrame>>,
+    skipped: AtomicU64,
+}
+
+#[derive(Debug)]
+struct StoredFrame {
+    frame: TelemetryFrame,
+    stored_at: Instant,
+}
+
+impl SnapshotSlot {
+    /// Replace the latest frame, unless a reader holds the slot right now.
+    pub(crate) fn store(&self, frame: &TelemetryFrame) {
+        let stored = StoredFrame {
+            frame: frame.clone(),
+            stored_at: Instant::now(),
+        };
+        let mut latest = match self.latest.try_write() {
+            Ok(latest) => latest,
+            Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+            Err(TryLockError::WouldBlock) => {
+                self.skipped.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+        };
+        *latest = Some(stored);
+    }
+
+    fn age(&self) -> Option<Duration> {
+        self.latest
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .map(|stored| stored.stored_at.elapsed())
+    }
+
+    fn snapshot(&self) -> Option<FrameSnapshot> {
+        let latest = self.latest.read().unwrap_or_else(PoisonError::into_inner);
+        let stored = latest.as_ref()?;
+        Some(FrameSnapshot {
+            age_ms: u64::try_from(stored.stored_at.elapsed().as_millis()).unwrap_or(u64::MAX),
+            frame: stored.frame.clone(),
+        })
+    }
+}
+
+/// Where a snapshot server listens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotEndpoint {
+    Tcp(SocketAddr),
+    /// A Unix domain socket; a stale socket file at the path is replaced.
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl SnapshotEndpoint {
+    /// A socket in the temp directory on Unix, localhost TCP elsewhere.
+    pub fn platform_default() -> Self {
+        #[cfg(unix)]
+        {
+            Self::Unix(std::env::temp_dir().join("openracing-telemetry.sock"))
+        }
+        #[cfg(not(unix))]
+        {
+            Self::Tcp((Ipv4Addr::LOCALHOST, DEFAULT_SNAPSHOT_TCP_PORT).into())
+        }
+    }
+
+    /// Localhost TCP on a free port.
+    pub fn localhost_any_port() -> Self {
+        Self::Tcp((Ipv4Addr::LOCALHOST, 0).into())
+    }
+}
+
+/// A running snapshot server. Dropping the handle stops it without waiting;
+/// [`SnapshotServerHandle::shutdown`] waits for open connections to close.
+pub struct SnapshotServerHandle {
+    endpoint: SnapshotEndpoint,
+    shutdown: watch::Sender<bool>,
+    task: JoinHandle<()>,
+}
+
+impl SnapshotServerHandle {
+    /// The bound endpoint, with the port resolved if `0` was requested.
+    pub fn endpoint(&self) -> &SnapshotEndpoint {
+        &self.endpoint
+    }
+
+    /// Stop accepting and wait for open connections to close.
+    pub async fn shutdown(self) -> Result<()> {
+        let _ = self.shutdown.send(true);
+        self.task.await.context("Snapshot server task failed")
+    }
+}
+
+trait Connection: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Connection for T {}
+
+enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener, PathBuf),
+}
+
+impl Listener {
+    async fn accept(&self) -> std::io::Result<Box<dyn Connection>> {
+        match self {
+            Self::Tcp(listener) => Ok(Box::new(listener.accept().await?.0)),
+            #[cfg(unix)]
+            Self::Unix(listener, _) => Ok(Box::new(listener.accept().await?.0)),
+        }
+    }
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Self::Unix(_, path) = self {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Serve `cache` on `endpoint`; see the module docs for the protocol.
+pub async fn serve(
+    cache: Arc<TelemetrySnapshotCache>,
+    endpoint: SnapshotEndpoint,
+) -> Result<SnapshotServerHandle> {
+    let (listener, endpoint) = match endpoint {
+        SnapshotEndpoint::Tcp(bind) => {
+            let listener = TcpListener::bind(bind)
+                .await
+                .with_context(|| format!("Failed to bind snapshot server to {bind}"))?;
+            let local = listener.local_addr()?;
+            (Listener::Tcp(listener), SnapshotEndpoint::Tcp(local))
+        }
+        #[cfg(unix)]
+        SnapshotEndpoint::Unix(path) => {
+            match std::fs::remove_file(&path) {
+                Ok(()) => debug!(path = %path.display(), "Replaced stale snapshot socket"),
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+                Err(error) => {
+                    return Err(error).with_context(|| {
+                        format!("Failed to remove stale socket {}", path.display())
+                    });
+                }
+            }
+            let listener = UnixListener::bind(&path)
+                .with_context(|| format!("Failed to bind snapshot server to {}", path.display()))?;
+            (
+                Listener::Unix(listener, path.clone()),
+                SnapshotEndpoint::Unix(path),
+            )
+        }
+    };
+    let (shutdown, shutdown_rx) = watch::channel(false);
+    let task = tokio::spawn(accept_loop(listener, cache, shutdown_rx));
+    Ok(SnapshotServerHandle {
+        endpoint,
+        shutdown,
+        task,
+    })
+}
+
+async fn accept_loop(
+    listener: Listener,
+    cache: Arc<TelemetrySnapshotCache>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    let mut connections = JoinSet::new();
+    loop {
+        tokio::select! {
+            // A dropped handle reads as a shutdown too.
+            _ = shutdown.changed() => break,
+            accepted = listener.accept() => match accepted {
+                Ok(stream) => {
+                    connections.spawn(serve_client(stream, Arc::clone(&cache), shutdown.clone()));
+                }
+                Err(error) => warn!(error = %error, "Snapshot server accept failed"),
+            },
+            Some(_) = connections.join_next(), if !connections.is_empty() => {}
+        }
+    }
+    drop(listener);
+    while connections.join_next().await.is_some() {}
+}
+
+async fn serve_client(
+    stream: Box<dyn Connection>,
+    cache: Arc<TelemetrySnapshotCache>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut reader = BufReader::new(reader);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        let mut limited = (&mut reader).take(MAX_SNAPSHOT_REQUEST_BYTES as u64 + 1);
+        let read = tokio::select! {
+            _ = shutdown.changed() => return,
+            read = limited.read_until(b'\n', &mut line) => read,
+        };
+        match read {
+            Ok(0) => return,
+            Ok(_) => {}
+            Err(error) => {
+                debug!(error = %error, "Snapshot client read failed");
+                return;
+            }
+        }
+        let oversized = line.len() > MAX_SNAPSHOT_REQUEST_BYTES;
+        let response = if oversized {
+            error_response(format!(
+                "request exceeds {MAX_SNAPSHOT_REQUEST_BYTES} bytes"
+            ))
+        } else {
+            respond(&cache, &line)
+        };
+        let mut out = response.to_string();
+        out.push('\n');
+        if writer.write_all(out.as_bytes()).await.is_err() || oversized {
+            return;
+        }
+    }
+}
+
+fn error_response(message: impl Into<String>) -> Value {
+    json!({ "error": message.into() })
+}
+
+fn respond(cache: &TelemetrySnapshotCache, line: &[u8]) -> Value {
+    let request: Value = match serde_json::from_slice(line) {
+        Ok(request) => request,
+        Err(error) => return error_response(format!("invalid request: {error}")),
+    };
+    let Some(cmd) = request.get("cmd").and_then(Value::as_str) else {
+        return error_response("request needs a string \"cmd\"");
+    };
+    let encoded = match cmd {
+        "latest" => {
+            let Some(game_id) = request.get("game_id").and_then(Value::as_str) else {
+                return error_response("\"latest\" needs a string \"game_id\"");
+            };
+            let Some(snapshot) = cache.snapshot(game_id) else {
+                return error_response(format!("no telemetry for game {game_id}"));
+            };
+            serde_json::to_value(snapshot).map(|mut value| {
+                value["game_id"] = json!(normalize_game_id(game_id));
+                value
+            })
+        }
+        "latest_all" => {
+            serde_json::to_value(cache.snapshot_all()).map(|games| json!({ "games": games }))
+        }
+        other => return error_response(format!("unknown command: {other}")),
+    };
+    encoded.unwrap_or_else(|error| error_response(format!("failed to encode frame: {error}")))
+}
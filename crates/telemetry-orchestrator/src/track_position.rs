@@ -0,0 +1,317 @@
+//! Normalized track position and live delta-time for comparison overlays.
+//!
+//! Ghost and delta tools align laps by how far around the circuit each frame
+//! is, which only some games report. [`TrackPositionEstimator`] publishes that
+//! fraction under [`ExtendedKey::LAP_POSITION`], taking it straight from the
+//! game when possible and otherwise integrating speed between lap boundaries.
+//! [`DeltaComputer`] compares the live lap against a reference lap sampled on
+//! the same scale.
+
+use std::time::Duration;
+
+use racing_wheel_telemetry_adapters::{
+    ExtendedKey, NormalizedTelemetry, TelemetryFrame, TelemetryValue,
+};
+
+use crate::game_clock::{ClockDiagnostics, GameClock};
+use crate::transforms::FrameTransform;
+
+/// Longest gap between frames that is still integrated; longer gaps make the
+/// current lap's distance unreliable.
+const MAX_INTEGRATION_STEP: Duration = Duration::from_secs(1);
+
+/// How a published lap position was obtained.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PositionConfidence {
+    /// Read from lap distance, spline or stage progress sent by the game.
+    Exact,
+    /// Integrated from speed and scaled by the last full lap's distance.
+    Estimated,
+    /// The car is in the pit lane; the last on-track estimate is held.
+    Pit,
+}
+
+impl PositionConfidence {
+    /// Tag written under [`ExtendedKey::LAP_POSITION_SOURCE`].
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Exact => "exact",
+            Self::Estimated => "estimated",
+            Self::Pit => "pit",
+        }
+    }
+
+    /// Parse a [`ExtendedKey::LAP_POSITION_SOURCE`] tag.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "exact" => Some(Self::Exact),
+            "estimated" => Some(Self::Estimated),
+            "pit" => Some(Self::Pit),
+            _ => None,
+        }
+    }
+}
+
+/// A lap position together with how it was obtained.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrackPosition {
+    /// Fraction of the lap completed, in `0.0..=1.0`.
+    pub fraction: f32,
+    /// Where the fraction came from.
+    pub confidence: PositionConfidence,
+}
+
+impl TrackPosition {
+    /// Read the position a [`TrackPositionEstimator`] published on `telemetry`.
+    pub fn from_telemetry(telemetry: &NormalizedTelemetry) -> Option<Self> {
+        let fraction = float(telemetry, ExtendedKey::LAP_POSITION)?;
+        let confidence = match telemetry
+            .extended
+            .get(ExtendedKey::LAP_POSITION_SOURCE.name)
+        {
+            Some(TelemetryValue::String(tag)) => PositionConfidence::from_tag(tag)?,
+            _ => return None,
+        };
+        Some(Self {
+            fraction,
+            confidence,
+        })
+    }
+}
+
+fn float(telemetry: &NormalizedTelemetry, key: ExtendedKey) -> Option<f32> {
+    match telemetry.extended.get(key.name) {
+        Some(TelemetryValue::Float(value)) if value.is_finite() => Some(*value),
+        _ => None,
+    }
+}
+
+/// Position read from game-supplied fields, if the frame has any.
+fn exact_fraction(telemetry: &NormalizedTelemetry) -> Option<f32> {
+    let from_distance = || {
+        let distance = float(telemetry, ExtendedKey::LAP_DISTANCE_M)?;
+        let length = float(telemetry, ExtendedKey::TRACK_LENGTH_M).filter(|len| *len > 0.0)?;
+        Some(distance / length)
+    };
+    from_distance()
+        .or_else(|| float(telemetry, ExtendedKey::SPLINE_POSITION))
+        .or_else(|| float(telemetry, ExtendedKey::STAGE_PROGRESS))
+        .map(|fraction| fraction.clamp(0.0, 1.0))
+}
+
+/// Frame transform publishing a normalized lap position on every frame it can.
+///
+/// Games that send lap distance and track length, a spline position or stage
+/// progress get [`PositionConfidence::Exact`] values. For the rest, distance is
+/// integrated from `speed_ms` between lap-counter changes; once one complete,
+/// uninterrupted lap has been observed its distance scales later laps and the
+/// output is tagged [`PositionConfidence::Estimated`]. Nothing is published
+/// before that. While `flags.in_pits` is set the last on-track estimate is held
+/// and tagged [`PositionConfidence::Pit`], and laps that included a pit visit
+/// or a frame gap do not update the learned lap distance.
+///
+/// Steps are measured on the game clock when the adapter reports one (see
+/// [`GameClock`]), so arrival jitter and pauses do not add phantom distance.
+/// Frames whose game and arrival steps disagree are annotated with
+/// [`ExtendedKey::GAME_CLOCK_DIVERGENCE_S`].
+#[derive(Debug, Default)]
+pub struct TrackPositionEstimator {
+    lap: Option<u16>,
+    clock: GameClock,
+    divergence_s: Option<f64>,
+    lap_distance_m: f64,
+    /// The current lap started at an observed boundary and has had no pit
+    /// visit or gap, so its distance is a valid full-lap measurement.
+    lap_clean: bool,
+    full_lap_m: Option<f64>,
+    last_fraction: Option<f32>,
+}
+
+impl TrackPositionEstimator {
+    /// Create an estimator that has not seen a lap yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Distance of the last clean lap, used to scale integrated positions.
+    pub fn learned_lap_length_m(&self) -> Option<f64> {
+        self.full_lap_m
+    }
+
+    /// How the integration steps have been measured so far.
+    pub fn clock_diagnostics(&self) -> ClockDiagnostics {
+        self.clock.diagnostics()
+    }
+
+    /// Position for `frame`, updating the integration state.
+    pub fn update(&mut self, frame: &TelemetryFrame) -> Option<TrackPosition> {
+        let data = &frame.data;
+        let step = self.clock.step(frame);
+        self.divergence_s = step
+            .filter(|step| self.clock.is_diverged(step))
+            .and_then(|step| step.divergence_s);
+        let dt = step.map(|step| step.dt);
+
+        match self.lap {
+            Some(lap) if data.lap < lap => {
+                // The counter went backwards: a restart, not a lap completed.
+                let clock = std::mem::take(&mut self.clock);
+                let divergence_s = self.divergence_s;
+                self.reset();
+                self.clock = clock;
+                self.divergence_s = divergence_s;
+                self.lap = Some(data.lap);
+            }
+            Some(lap) if data.lap != lap => {
+                if self.lap_clean && self.lap_distance_m > 0.0 {
+                    self.full_lap_m = Some(self.lap_distance_m);
+                }
+                self.lap = Some(data.lap);
+                self.lap_distance_m = 0.0;
+                self.lap_clean = true;
+            }
+            Some(_) => {}
+            None => self.lap = Some(data.lap),
+        }
+        match dt {
+            Some(step) if step <= MAX_INTEGRATION_STEP => {
+                self.lap_distance_m += f64::from(data.speed_ms.max(0.0)) * step.as_secs_f64();
+            }
+            Some(_) => self.lap_clean = false,
+            None => {}
+        }
+        if data.flags.in_pits {
+            self.lap_clean = false;
+        }
+
+        if let Some(fraction) = exact_fraction(data) {
+            self.last_fraction = Some(fraction);
+            return Some(TrackPosition {
+                fraction,
+                confidence: PositionConfidence::Exact,
+            });
+        }
+
+        if data.flags.in_pits {
+            return self.last_fraction.map(|fraction| TrackPosition {
+                fraction,
+                confidence: PositionConfidence::Pit,
+            });
+        }
+
+        let full_lap_m = self.full_lap_m?;
+        let fraction = (self.lap_distance_m / full_lap_m).clamp(0.0, 1.0) as f32;
+        self.last_fraction = Some(fraction);
+        Some(TrackPosition {
+            fraction,
+            confidence: PositionConfidence::Estimated,
+        })
+    }
+}
+
+impl FrameTransform for TrackPositionEstimator {
+    fn name(&self) -> &str {
+        "track_position"
+    }
+
+    fn apply(&mut self, frame: &mut TelemetryFrame) {
+        let position = self.update(frame);
+        if let Some(divergence_s) = self.divergence_s {
+            frame.data.extended.insert(
+                ExtendedKey::GAME_CLOCK_DIVERGENCE_S.name.to_string(),
+                TelemetryValue::Float(divergence_s as f32),
+            );
+        }
+        let Some(position) = position else {
+            return;
+        };
+        let extended = &mut frame.data.extended;
+        extended.insert(
+            ExtendedKey::LAP_POSITION.name.to_string(),
+            TelemetryValue::Float(position.fraction),
+        );
+        extended.insert(
+            ExtendedKey::LAP_POSITION_SOURCE.name.to_string(),
+            TelemetryValue::String(position.confidence.as_str().to_string()),
+        );
+    }
+
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Error building a [`DeltaComputer`] from reference samples.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ReferenceLapError {
+    /// Interpolation needs at least two samples.
+    #[error("reference lap needs at least two samples, got {0}")]
+    TooFewSamples(usize),
+    /// Positions must increase strictly and stay within `0.0..=1.0`.
+    #[error("reference sample {0} is out of order or outside 0..=1")]
+    InvalidSample(usize),
+}
+
+/// Live delta-time against a reference lap.
+///
+/// The reference is a list of `(lap position, elapsed lap time in seconds)`
+/// samples; the reference time at any position is linearly interpolated.
+/// As a transform it reads [`ExtendedKey::LAP_POSITION`] and
+/// `current_lap_time_s`, so register it after [`TrackPositionEstimator`].
+#[derive(Debug, Clone)]
+pub struct DeltaComputer {
+    samples: Vec<(f32, f32)>,
+}
+
+impl DeltaComputer {
+    /// Build from `(position, elapsed_s)` samples ordered by position.
+    pub fn new(samples: Vec<(f32, f32)>) -> Result<Self, ReferenceLapError> {
+        if samples.len() < 2 {
+            return Err(ReferenceLapError::TooFewSamples(samples.len()));
+        }
+        for (index, (position, elapsed)) in samples.iter().enumerate() {
+            let ordered = index == 0 || samples[index - 1].0 < *position;
+            if !ordered || !(0.0..=1.0).contains(position) || !elapsed.is_finite() {
+                return Err(ReferenceLapError::InvalidSample(index));
+            }
+        }
+        Ok(Self { samples })
+    }
+
+    /// Reference lap time at `position`, or `None` outside the sampled range.
+    pub fn reference_time_at(&self, position: f32) -> Option<f32> {
+        let after = self
+            .samples
+            .partition_point(|(sample, _)| *sample < position);
+        let (p1, t1) = *self.samples.get(after)?;
+        if p1 == position {
+            return Some(t1);
+        }
+        let (p0, t0) = *self.samples.get(after.checked_sub(1)?)?;
+        Some(t0 + (t1 - t0) * (position - p0) / (p1 - p0))
+    }
+
+    /// Seconds behind (positive) or ahead of (negative) the reference.
+    pub fn delta(&self, position: f32, elapsed_s: f32) -> Option<f32> {
+        self.reference_time_at(position)
+            .map(|reference| elapsed_s - reference)
+    }
+}
+
+impl FrameTransform for DeltaComputer {
+    fn name(&self) -> &str {
+        "reference_delta"
+    }
+
+    fn apply(&mut self, frame: &mut TelemetryFrame) {
+        let Some(position) = float(&frame.data, ExtendedKey::LAP_POSITION) else {
+            return;
+        };
+        if let Some(delta) = self.delta(position, frame.data.current_lap_time_s) {
+            frame.data.extended.insert(
+                ExtendedKey::REFERENCE_DELTA_S.name.to_string(),
+                TelemetryValue::Float(delta),
+            );
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Per-game frame transforms applied before sink dispatch.
+//!
+//! Transforms run on the orchestrator's forwarding task, in registration order,
+//! after the adapter has produced a frame and before any sink or downstream
+//! receiver sees it.
+
+use racing_wheel_telemetry_adapters::TelemetryFrame;
+
+/// A stateful, in-place rewrite of telemetry frames.
+pub trait FrameTransform: Send {
+    /// Stable name used in logs and events.
+    fn name(&self) -> &str;
+
+    /// Rewrite `frame` in place.
+    fn apply(&mut self, frame: &mut TelemetryFrame);
+
+    /// Drop any per-session state. Called when the frame's session id changes.
+    fn reset(&mut self) {}
+}
+
+/// Ordered transforms for one game, with session-boundary tracking.
+#[derive(Default)]
+pub struct TransformChain {
+    transforms: Vec<Box<dyn FrameTransform>>,
+    session_id: Option<String>,
+}
+
+impl TransformChain {
+    /// Create an empty chain.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a transform; it runs after every transform already registered.
+    pub fn push(&mut self, transform: Box<dyn FrameTransform>) {
+        self.transforms.push(transform);
+    }
+
+    /// Insert a transform ahead of every transform already registered.
+    pub fn push_front(&mut self, transform: Box<dyn FrameTransform>) {
+        self.transforms.insert(0, transform);
+    }
+
+    /// Number of registered transforms.
+    pub fn len(&self) -> usize {
+        self.transforms.len()
+    }
+
+    /// Whether no transforms are registered.
+    pub fn is_empty(&self) -> bool {
+        self.transforms.is_empty()
+    }
+
+    /// Names of the registered transforms, in execution order.
+    pub fn names(&self) -> Vec<String> {
+        self.transforms
+            .iter()
+            .map(|transform| transform.name().to_string())
+            .collect()
+    }
+
+    /// Run every transform over `frame`, resetting them first on a session change.
+    pub fn apply(&mut self, frame: &mut TelemetryFrame) {
+        if frame.data.session_id != self.session_id {
+            for transform in &mut self.transforms {
+                transform.reset();
+            }
+            self.session_id.clone_from(&frame.data.session_id);
+        }
+
+        for transform in &mut self.transforms {
+            transform.apply(frame);
+        }
+    }
+
+    /// Reset every transform without waiting for a session change.
+    pub fn reset(&mut self) {
+        self.session_id = None;
+        for transform in &mut self.transforms {
+            transform.reset();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use racing_wheel_telemetry_adapters::{NormalizedTelemetry, TelemetryValue};
+
+    struct Counter {
+        count: i32,
+    }
+
+    impl FrameTransform for Counter {
+        fn name(&self) -> &str {
+            "counter"
+        }
+
+        fn apply(&mut self, frame: &mut TelemetryFrame) {
+            self.count += 1;
+            frame
+                .data
+                .extended
+                .insert("count".to_string(), TelemetryValue::Integer(self.count));
+        }
+
+        fn reset(&mut self) {
+            self.count = 0;
+        }
+    }
+
+    fn frame(session: &str) -> TelemetryFrame {
+        let data = NormalizedTelemetry::builder().session_id(session).build();
+        TelemetryFrame::new(data, 0, 0, 0)
+    }
+
+    #[test]
+    fn chain_resets_transforms_on_session_change() {
+        let mut chain = TransformChain::new();
+        chain.push(Box::new(Counter { count: 0 }));
+
+        let mut last = frame("a");
+        for session in ["a", "a", "b"] {
+            last = frame(session);
+            chain.apply(&mut last);
+        }
+
+        assert_eq!(
+            last.data.extended.get("count"),
+            Some(&TelemetryValue::Integer(1))
+        );
+        assert_eq!(chain.names(), vec!["counter"]);
+    }
+}
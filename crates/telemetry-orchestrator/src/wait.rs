@@ -0,0 +1,129 @@
+//! Waiting for a game's telemetry to reach a condition.
+//!
+//! Startup flows and tests need "the first frame" or "the first frame with
+//! speed above zero" without polling in a sleep loop. Each monitored game has
+//! a [`FrameTap`] the forwarding task publishes to while anyone is waiting;
+//! a wait subscribes when it is created and unsubscribes when its future is
+//! dropped, whether it resolved, timed out or was cancelled.
+
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+use racing_wheel_telemetry_adapters::{NormalizedTelemetry, TelemetryFrame};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// Frames buffered per waiter; a waiter that falls further behind skips ahead.
+const FRAME_TAP_CAPACITY: usize = 64;
+
+/// Error returned by the `TelemetryService::wait_for_*` helpers.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum WaitError {
+    /// No matching frame arrived within the timeout.
+    #[error("no matching telemetry frame from `{game_id}` within {timeout:?}")]
+    Timeout { game_id: String, timeout: Duration },
+    /// The game's source disconnected before a frame matched.
+    #[error("telemetry source for `{0}` disconnected while waiting")]
+    Disconnected(String),
+    /// The game had no running forwarding task when the wait started.
+    #[error("game `{0}` is not being monitored")]
+    NotMonitored(String),
+    /// The blocking variant could not start its timer runtime.
+    #[error("cannot run a blocking wait: {0}")]
+    Runtime(String),
+}
+
+/// Per-game fan-out of forwarded frames to waiters.
+#[derive(Debug, Default)]
+pub(crate) struct FrameTap {
+    sender: Mutex<Option<broadcast::Sender<TelemetryFrame>>>,
+}
+
+impl FrameTap {
+    /// Start a channel for a new forwarding task, which keeps the returned
+    /// sender and hands it back to [`FrameTap::close`] when it ends.
+    pub(crate) fn open(&self) -> broadcast::Sender<TelemetryFrame> {
+        let sender = broadcast::channel(FRAME_TAP_CAPACITY).0;
+        *self.lock() = Some(sender.clone());
+        sender
+    }
+
+    /// Close `sender`'s channel so its waiters see a disconnect, unless a
+    /// newer forwarding task has already replaced it.
+    pub(crate) fn close(&self, sender: broadcast::Sender<TelemetryFrame>) {
+        let mut current = self.lock();
+        if current
+            .as_ref()
+            .is_some_and(|current| current.same_channel(&sender))
+        {
+            *current = None;
+        }
+    }
+
+    pub(crate) fn subscribe(&self) -> Option<broadcast::Receiver<TelemetryFrame>> {
+        self.lock().as_ref().map(broadcast::Sender::subscribe)
+    }
+
+    pub(crate) fn subscriber_count(&self) -> usize {
+        self.lock()
+            .as_ref()
+            .map_or(0, broadcast::Sender::receiver_count)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<broadcast::Sender<TelemetryFrame>>> {
+        self.sender.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Hand `frame` to waiters, cloning it only if there are any.
+pub(crate) fn publish(sender: &broadcast::Sender<TelemetryFrame>, frame: &TelemetryFrame) {
+    if sender.receiver_count() > 0 {
+        // Waiters may leave between the check and the send; that is fine.
+        let _ = sender.send(frame.clone());
+    }
+}
+
+/// Subscribe to `tap` now and resolve with the first frame `predicate` accepts.
+pub(crate) fn wait_for(
+    tap: Option<&FrameTap>,
+    game_id: String,
+    predicate: impl Fn(&NormalizedTelemetry) -> bool + Send + 'static,
+    timeout: Duration,
+) -> impl Future<Output = Result<TelemetryFrame, WaitError>> + Send + 'static {
+    let subscription = tap
+        .and_then(FrameTap::subscribe)
+        .ok_or_else(|| WaitError::NotMonitored(game_id.clone()));
+    async move {
+        let mut frames = subscription?;
+        let disconnected = WaitError::Disconnected(game_id.clone());
+        let matched = async move {
+            loop {
+                match frames.recv().await {
+                    Ok(frame) if predicate(&frame.data) => return Ok(frame),
+                    Ok(_) | Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => return Err(disconnected),
+                }
+            }
+        };
+        match tokio::time::timeout(timeout, matched).await {
+            Ok(result) => result,
+            Err(_) => Err(WaitError::Timeout { game_id, timeout }),
+        }
+    }
+}
+
+/// Drive `wait` to completion on the calling thread, which must not be
+/// running a Tokio runtime itself.
+pub(crate) fn block_on(
+    wait: impl Future<Output = Result<TelemetryFrame, WaitError>>,
+) -> Result<TelemetryFrame, WaitError> {
+    if tokio::runtime::Handle::try_current().is_ok() {
+        return Err(WaitError::Runtime(
+            "called from within a Tokio runtime; await the async variant instead".to_string(),
+        ));
+    }
+    tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .map_err(|err| WaitError::Runtime(err.to_string()))?
+        .block_on(wait)
+}
@@ -0,0 +1,491 @@
+//! WebSocket broadcast of forwarded frames for browser overlays.
+//!
+//! [`TelemetryService::serve_websocket`](crate::TelemetryService::serve_websocket)
+//! accepts WebSocket clients on a TCP address. A client receives nothing
+//! until it sends a subscribe message:
+//!
+//! ```json
+//! {"subscribe": "all"}
+//! {"subscribe": ["acc", "iracing"], "encoding": "binary"}
+//! ```
+//!
+//! The server answers with `{"subscribed": ..., "encoding": ...}`, or with
+//! `{"error": "..."}` for a message it cannot parse, and then pushes every
+//! matching frame. JSON frames are text messages `{"game_id": ..., "frame":
+//! ...}`. Binary frames are binary messages: a `u8` game id length, the game
+//! id, then the frame as one record of the `racing_wheel_telemetry_contracts`
+//! binary codec: the `ORTF` magic, the `u32` schema version and the encoded
+//! frame. Clients decode the record with
+//! [`racing_wheel_schemas::telemetry::decode_frame`], which rejects records
+//! of another schema version. Subscribing again replaces the selection.
+//!
+//! The forwarding task only ever `try_send`s into each client's bounded
+//! queue, so a stalled browser cannot hold up the frame path. What happens to
+//! a client whose queue is full is its [`SlowClientPolicy`].
+
+use std::collections::BTreeSet;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use futures::{SinkExt, StreamExt};
+use racing_wheel_schemas::telemetry::encode_frame;
+use racing_wheel_telemetry_adapters::TelemetryFrame;
+use racing_wheel_telemetry_support::normalize_game_id;
+use serde::{Deserialize, Serialize};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, watch};
+use tokio::task::{JoinHandle, JoinSet};
+use tokio::time::timeout;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tracing::{debug, warn};
+
+use crate::sinks::WireEncoding;
+
+/// What to do with a client whose queue is full when a frame arrives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SlowClientPolicy {
+    /// Close the client's connection; it can reconnect once it keeps up.
+    #[default]
+    Disconnect,
+    /// Keep the client and skip frames until its queue has room.
+    DropFrames,
+}
+
+/// Settings for a WebSocket broadcast server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WsOptions {
+    /// Frames queued per client before [`Self::slow_client`] applies.
+    pub client_queue: usize,
+    /// Policy for clients that fall behind.
+    pub slow_client: SlowClientPolicy,
+    /// Longest a single write to a client may take; a client that does not
+    /// drain its socket for this long is disconnected.
+    pub write_timeout: Duration,
+}
+
+impl Default for WsOptions {
+    fn default() -> Self {
+        Self {
+            client_queue: 64,
+            slow_client: SlowClientPolicy::Disconnect,
+            write_timeout: Duration::from_secs(2),
+        }
+    }
+}
+
+/// A running WebSocket server. Dropping the handle stops accepting clients
+/// and closes the connected ones without waiting; [`WsHandle::shutdown`]
+/// waits for them.
+pub struct WsHandle {
+    local_addr: SocketAddr,
+    server: Arc<ServerState>,
+    shutdown: watch::Sender<bool>,
+    task: JoinHandle<()>,
+}
+
+impl WsHandle {
+    /// The bound address, with the port resolved if `0` was requested.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Clients that have subscribed and are still connected.
+    pub fn client_count(&self) -> usize {
+        self.server.subscribed.load(Ordering::Relaxed)
+    }
+
+    /// Clients disconnected for falling behind.
+    pub fn slow_clients_dropped(&self) -> u64 {
+        self.server.slow_clients_dropped.load(Ordering::Relaxed)
+    }
+
+    /// Frames skipped for clients under [`SlowClientPolicy::DropFrames`].
+    pub fn frames_dropped(&self) -> u64 {
+        self.server.frames_dropped.load(Ordering::Relaxed)
+    }
+
+    /// Stop accepting, send every client a close frame and wait for their
+    /// connections to finish. A client stalled mid-write holds this up for
+    /// at most [`WsOptions::write_timeout`].
+    pub async fn shutdown(self) -> Result<()> {
+        // The accept loop may already have exited; that is fine.
+        let _ = self.shutdown.send(true);
+        self.task.await.context("WebSocket server task failed")
+    }
+}
+
+/// Per-server options and counters, shared by its connections.
+#[derive(Debug)]
+struct ServerState {
+    options: WsOptions,
+    subscribed: AtomicUsize,
+    slow_clients_dropped: AtomicU64,
+    frames_dropped: AtomicU64,
+}
+
+/// Fan-out of forwarded frames to subscribed WebSocket clients of every
+/// server started from one service.
+#[derive(Debug, Default)]
+pub(crate) struct WsHub {
+    clients: Mutex<Vec<ClientSlot>>,
+    next_id: AtomicU64,
+}
+
+#[derive(Debug)]
+struct ClientSlot {
+    id: u64,
+    /// `None` subscribes to every game.
+    games: Option<BTreeSet<String>>,
+    encoding: WireEncoding,
+    queue: mpsc::Sender<Message>,
+    server: Arc<ServerState>,
+}
+
+impl ClientSlot {
+    fn wants(&self, game_id: &str) -> bool {
+        self.games
+            .as_ref()
+            .is_none_or(|games| games.contains(game_id))
+    }
+}
+
+impl WsHub {
+    fn lock(&self) -> MutexGuard<'_, Vec<ClientSlot>> {
+        self.clients.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Queue `frame` for every client subscribed to `game_id`, encoding it
+    /// at most once per encoding. Never waits on a client.
+    pub(crate) fn publish(&self, game_id: &str, frame: &TelemetryFrame) {
+        let mut clients = self.lock();
+        if clients.is_empty() {
+            return;
+        }
+        let mut json = None;
+        let mut binary = None;
+        clients.retain(|client| {
+            if !client.wants(game_id) {
+                return true;
+            }
+            let message = match client.encoding {
+                WireEncoding::Json => json.get_or_insert_with(|| json_message(game_id, frame)),
+                WireEncoding::Binary => {
+                    binary.get_or_insert_with(|| binary_message(game_id, frame))
+                }
+            };
+            let Some(message) = message.clone() else {
+                return true;
+            };
+            match client.queue.try_send(message) {
+                Ok(()) => true,
+                Err(mpsc::error::TrySendError::Full(_)) => {
+                    match client.server.options.slow_client {
+                        SlowClientPolicy::Disconnect => {
+                            warn!(
+                                client = client.id,
+                                "Dropping slow WebSocket telemetry client"
+                            );
+                            client
+                                .server
+                                .slow_clients_dropped
+                                .fetch_add(1, Ordering::Relaxed);
+                            client.server.subscribed.fetch_sub(1, Ordering::Relaxed);
+                            false
+                        }
+                        SlowClientPolicy::DropFrames => {
+                            client.server.frames_dropped.fetch_add(1, Ordering::Relaxed);
+                            true
+                        }
+                    }
+                }
+                Err(mpsc::error::TrySendError::Closed(_)) => {
+                    client.server.subscribed.fetch_sub(1, Ordering::Relaxed);
+                    false
+                }
+            }
+        });
+    }
+
+    fn next_id(&self) -> u64 {
+        self.next_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Add the client on its first subscribe, handing over its queue, or
+    /// change its selection. Returns `false` if the client was dropped.
+    fn subscribe(
+        &self,
+        id: u64,
+        server: &Arc<ServerState>,
+        request: &SubscribeRequest,
+        queue: &mut Option<mpsc::Sender<Message>>,
+    ) -> bool {
+        let games = request.games();
+        let encoding = request.encoding.into();
+        let mut clients = self.lock();
+        if let Some(queue) = queue.take() {
+            server.subscribed.fetch_add(1, Ordering::Relaxed);
+            clients.push(ClientSlot {
+                id,
+                games,
+                encoding,
+                queue,
+                server: Arc::clone(server),
+            });
+            return true;
+        }
+        match clients.iter_mut().find(|client| client.id == id) {
+            Some(client) => {
+                client.games = games;
+                client.encoding = encoding;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Forget the client, returning whether it was still subscribed.
+    fn remove(&self, id: u64) -> bool {
+        let mut clients = self.lock();
+        let Some(index) = clients.iter().position(|client| client.id == id) else {
+            return false;
+        };
+        let client = clients.swap_remove(index);
+        client.server.subscribed.fetch_sub(1, Ordering::Relaxed);
+        true
+    }
+}
+
+/// Bind `bind` and serve clients from `hub` until the handle shuts down.
+pub(crate) async fn serve(
+    hub: Arc<WsHub>,
+    bind: SocketAddr,
+    options: WsOptions,
+) -> Result<WsHandle> {
+    let listener = TcpListener::bind(bind)
+        .await
+        .with_context(|| format!("Failed to bind WebSocket server to {bind}"))?;
+    let local_addr = listener.local_addr()?;
+    let server = Arc::new(ServerState {
+        options,
+        subscribed: AtomicUsize::new(0),
+        slow_clients_dropped: AtomicU64::new(0),
+        frames_dropped: AtomicU64::new(0),
+    });
+    let (shutdown, shutdown_rx) = watch::channel(false);
+    let task = tokio::spawn(accept_loop(listener, hub, Arc::clone(&server), shutdown_rx));
+    Ok(WsHandle {
+        local_addr,
+        server,
+        shutdown,
+        task,
+    })
+}
+
+async fn accept_loop(
+    listener: TcpListener,
+    hub: Arc<WsHub>,
+    server: Arc<ServerState>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    let mut connections = JoinSet::new();
+    loop {
+        tokio::select! {
+            // A dropped handle reads as a shutdown too.
+            _ = shutdown.changed() => break,
+            accepted = listener.accept() => match accepted {
+                Ok((stream, peer)) => {
+                    debug!(%peer, "WebSocket telemetry client connected");
+                    connections.spawn(serve_client(
+                        stream,
+                        Arc::clone(&hub),
+                        Arc::clone(&server),
+                        shutdown.clone(),
+                    ));
+                }
+                Err(error) => warn!(error = %error, "WebSocket accept failed"),
+            },
+            Some(_) = connections.join_next(), if !connections.is_empty() => {}
+        }
+    }
+    drop(listener);
+    while connections.join_next().await.is_some() {}
+}
+
+async fn serve_client(
+    stream: TcpStream,
+    hub: Arc<WsHub>,
+    server: Arc<ServerState>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    let write_timeout = server.options.write_timeout;
+    let socket = match timeout(write_timeout, tokio_tungstenite::accept_async(stream)).await {
+        Ok(Ok(socket)) => socket,
+        Ok(Err(error)) => {
+            debug!(error = %error, "WebSocket handshake failed");
+            return;
+        }
+        Err(_) => {
+            debug!("WebSocket handshake timed out");
+            return;
+        }
+    };
+    let (mut outgoing, mut incoming) = socket.split();
+    let (queue, mut queued) = mpsc::channel(server.options.client_queue.max(1));
+    // Held here until the first subscribe hands it to the hub.
+    let mut queue = Some(queue);
+    let id = hub.next_id();
+
+    let close = loop {
+        let outbound = tokio::select! {
+            _ = shutdown.changed() => break Some((CloseCode::Away, "server shutting down")),
+            message = incoming.next() => match message {
+                Some(Ok(Message::Text(text))) => match serde_json::from_str::<SubscribeRequest>(&text) {
+                    Ok(request) => {
+                        if !hub.subscribe(id, &server, &request, &mut queue) {
+                            break Some((CloseCode::Policy, "client too slow"));
+                        }
+                        request.ack()
+                    }
+                    Err(error) => error_message(&error),
+                },
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break None,
+                // Pings are answered by the protocol layer; nothing else is expected.
+                Some(Ok(_)) => continue,
+            },
+            message = queued.recv() => match message {
+                Some(message) => message,
+                // The hub let go of the queue: the client fell behind.
+                None => break Some((CloseCode::Policy, "client too slow")),
+            },
+        };
+        match timeout(write_timeout, outgoing.send(outbound)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(error)) => {
+                debug!(client = id, error = %error, "WebSocket telemetry client went away");
+                break None;
+            }
+            Err(_) => {
+                warn!(client = id, "WebSocket telemetry client stopped reading");
+                // Unless the hub already dropped it for the same reason.
+                if hub.remove(id) {
+                    server.slow_clients_dropped.fetch_add(1, Ordering::Relaxed);
+                }
+                break None;
+            }
+        }
+    };
+
+    hub.remove(id);
+    if let Some((code, reason)) = close {
+        let frame = CloseFrame {
+            code,
+            reason: reason.into(),
+        };
+        // Best effort: a stalled client gets its socket closed regardless.
+        let _ = timeout(write_timeout, outgoing.send(Message::Close(Some(frame)))).await;
+    }
+}
+
+/// `"all"`, one game id, or a list of them.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum GameSelection {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ClientEncoding {
+    #[default]
+    Json,
+    Binary,
+}
+
+impl From<ClientEncoding> for WireEncoding {
+    fn from(encoding: ClientEncoding) -> Self {
+        match encoding {
+            ClientEncoding::Json => Self::Json,
+            ClientEncoding::Binary => Self::Binary,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SubscribeRequest {
+    subscribe: GameSelection,
+    #[serde(default)]
+    encoding: ClientEncoding,
+}
+
+impl SubscribeRequest {
+    fn games(&self) -> Option<BTreeSet<String>> {
+        let game_ids = match &self.subscribe {
+            GameSelection::One(game_id) if game_id == "all" => return None,
+            GameSelection::One(game_id) => std::slice::from_ref(game_id),
+            GameSelection::Many(game_ids) => game_ids.as_slice(),
+        };
+        Some(
+            game_ids
+                .iter()
+                .map(|game_id| normalize_game_id(game_id).to_string())
+                .collect(),
+        )
+    }
+
+    fn ack(&self) -> Message {
+        let subscribed = match self.games() {
+            None => serde_json::Value::from("all"),
+            Some(games) => serde_json::Value::from(games.into_iter().collect::<Vec<_>>()),
+        };
+        let ack = serde_json::json!({"subscribed": subscribed, "encoding": self.encoding});
+        Message::text(ack.to_string())
+    }
+}
+
+fn error_message(error: &serde_json::Error) -> Message {
+    let reply = serde_json::json!({"error": format!("invalid subscribe message: {error}")});
+    Message::text(reply.to_string())
+}
+
+#[derive(Serialize)]
+struct FrameMessage<'a> {
+    game_id: &'a str,
+    frame: &'a TelemetryFrame,
+}
+
+fn json_message(game_id: &str, frame: &TelemetryFrame) -> Option<Message> {
+    match serde_json::to_string(&FrameMessage { game_id, frame }) {
+        Ok(text) => Some(Message::text(text)),
+        Err(error) => {
+            warn!(game_id, error = %error, "Failed to encode telemetry frame for WebSocket");
+            None
+        }
+    }
+}
+
+fn binary_message(game_id: &str, frame: &TelemetryFrame) -> Option<Message> {
+    let Ok(len) = u8::try_from(game_id.len()) else {
+        warn!(game_id, "Game id too long for a binary WebSocket frame");
+        return None;
+    };
+    match encode_frame(frame) {
+        Ok(record) => {
+            let mut out = Vec::with_capacity(1 + game_id.len() + record.len());
+            out.push(len);
+            out.extend_from_slice(game_id.as_bytes());
+            out.extend_from_slice(&record);
+            Some(Message::binary(out))
+        }
+        Err(error) => {
+            warn!(game_id, error = %error, "Failed to encode telemetry frame for WebSocket");
+            None
+        }
+    }
+}
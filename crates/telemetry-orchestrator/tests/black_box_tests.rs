@@ -0,0 +1,132 @@
+//! Saving the black box's recent frames from a running service.
+
+mod common;
+
+use std::time::Duration;
+
+use anyhow::Result;
+use racing_wheel_telemetry_adapters::{NormalizedTelemetry, TelemetryFrame};
+use racing_wheel_telemetry_orchestrator::TelemetryService;
+use racing_wheel_telemetry_recorder::TelemetryRecorder;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+use common::MockAdapter;
+
+const SECOND_NS: u64 = 1_000_000_000;
+
+/// Register `game_id` without a rate limit in the way and start it, returning
+/// the adapter's sender and the forwarded stream.
+async fn monitor(
+    service: &mut TelemetryService,
+    game_id: &'static str,
+) -> Result<(mpsc::Sender<TelemetryFrame>, mpsc::Receiver<TelemetryFrame>)> {
+    let (tx, rx) = mpsc::channel(16);
+    service.register_adapter(Box::new(
+        MockAdapter::new(game_id)
+            .with_stream(rx)
+            .with_update_rate(Duration::from_millis(100)),
+    ));
+    service.set_rate_limit(game_id, 1_000_000);
+    let frames = service.start_monitoring(game_id).await?;
+    Ok((tx, frames))
+}
+
+async fn send_and_forward(
+    tx: &mpsc::Sender<TelemetryFrame>,
+    frames: &mut mpsc::Receiver<TelemetryFrame>,
+    timestamp_ns: u64,
+    sequence: u64,
+) -> Result<()> {
+    tx.send(TelemetryFrame::new(
+        NormalizedTelemetry::default(),
+        timestamp_ns,
+        sequence,
+        0,
+    ))
+    .await?;
+    timeout(Duration::from_secs(1), frames.recv())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("forwarding ended"))?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn save_recent_writes_only_the_last_minute() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.enable_black_box(Duration::from_secs(60));
+    let (tx, mut frames) = monitor(&mut service, "boxed_source").await?;
+
+    // Three minutes at 10 Hz.
+    for sequence in 0..=1800 {
+        send_and_forward(&tx, &mut frames, sequence * SECOND_NS / 10, sequence).await?;
+    }
+
+    let dir = tempfile::tempdir()?;
+    let report = service.save_recent(dir.path().join("crash"))?;
+    assert_eq!(report.frame_count(), 601);
+    assert_eq!(report.span(), Duration::from_secs(60));
+
+    let loaded = TelemetryRecorder::load_recording(dir.path().join("crash/boxed_source.json"))?;
+    assert_eq!(loaded.metadata.game_id, "boxed_source");
+    assert_eq!(loaded.frames.len(), 601);
+    assert_eq!(
+        loaded.frames.first().map(|f| f.timestamp_ns),
+        Some(120 * SECOND_NS)
+    );
+    assert!(
+        loaded
+            .frames
+            .windows(2)
+            .all(|pair| pair[0].timestamp_ns < pair[1].timestamp_ns)
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn each_game_gets_its_own_labeled_recording() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.enable_black_box(Duration::from_secs(60));
+    let (first_tx, mut first) = monitor(&mut service, "first_source").await?;
+    let (second_tx, mut second) = monitor(&mut service, "second_source").await?;
+
+    for sequence in 0..5 {
+        send_and_forward(&first_tx, &mut first, sequence * SECOND_NS, sequence).await?;
+    }
+    send_and_forward(&second_tx, &mut second, 10 * SECOND_NS, 0).await?;
+
+    let dir = tempfile::tempdir()?;
+    let report = service.save_recent(dir.path().to_path_buf())?;
+    let games: Vec<(&str, usize)> = report
+        .recordings
+        .iter()
+        .map(|r| (r.game_id.as_str(), r.frame_count))
+        .collect();
+    assert_eq!(games, [("first_source", 5), ("second_source", 1)]);
+    assert_eq!(report.span(), Duration::from_secs(10));
+
+    // Monitoring is unaffected, and later frames land in the next save.
+    send_and_forward(&first_tx, &mut first, 5 * SECOND_NS, 5).await?;
+    let report = service.save_recent(dir.path().to_path_buf())?;
+    assert_eq!(report.frame_count(), 7);
+    let loaded = TelemetryRecorder::load_recording(dir.path().join("first_source.json"))?;
+    assert_eq!(loaded.metadata.game_id, "first_source");
+    assert_eq!(loaded.frames.len(), 6);
+    Ok(())
+}
+
+#[test]
+fn save_recent_requires_the_black_box() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    let dir = tempfile::tempdir()?;
+    assert!(service.save_recent(dir.path().to_path_buf()).is_err());
+
+    service.enable_black_box(Duration::from_secs(60));
+    assert_eq!(
+        service.save_recent(dir.path().to_path_buf())?.frame_count(),
+        0
+    );
+    service.disable_black_box();
+    assert!(service.save_recent(dir.path().to_path_buf()).is_err());
+    Ok(())
+}
@@ -0,0 +1,154 @@
+//! The blocking facade driven from plain threads, without a Tokio runtime of
+//! the caller's own.
+#![cfg(feature = "blocking")]
+
+use std::sync::Mutex;
+use std::sync::mpsc as std_mpsc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use racing_wheel_telemetry_adapters::{
+    MockAdapter, NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver,
+};
+use racing_wheel_telemetry_core::ConnectionState;
+use racing_wheel_telemetry_orchestrator::{
+    BlockingRecvError, BlockingTelemetryService, TelemetryService,
+};
+use tokio::sync::mpsc;
+
+const FRAME_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Adapter fed from a test-owned channel.
+struct ChannelAdapter {
+    rx: Mutex<Option<TelemetryReceiver>>,
+}
+
+#[async_trait]
+impl TelemetryAdapter for ChannelAdapter {
+    fn game_id(&self) -> &str {
+        "quiet_source"
+    }
+
+    async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        self.rx
+            .lock()
+            .map_err(|_| anyhow::anyhow!("poisoned"))?
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("already monitoring"))
+    }
+
+    async fn stop_monitoring(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn normalize(&self, _raw: &[u8]) -> Result<NormalizedTelemetry> {
+        Ok(NormalizedTelemetry::default())
+    }
+
+    fn expected_update_rate(&self) -> Duration {
+        Duration::from_millis(50)
+    }
+
+    async fn is_game_running(&self) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+fn mock_service() -> Result<BlockingTelemetryService> {
+    let mut adapter = MockAdapter::with_update_rate("mock".to_string(), Duration::from_millis(5));
+    adapter.set_running(true);
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(adapter));
+    BlockingTelemetryService::new(service)
+}
+
+#[test]
+fn mock_frames_flow_through_the_iterator() -> Result<()> {
+    let mut service = mock_service()?;
+    assert!(service.supported_games().contains(&"mock".to_string()));
+    assert!(service.is_game_running("mock")?);
+
+    let frames = service
+        .start_monitoring("mock")?
+        .with_timeout(FRAME_TIMEOUT);
+    let sequences: Vec<_> = frames.take(5).map(|frame| frame.sequence).collect();
+    assert_eq!(sequences, [0, 1, 2, 3, 4]);
+
+    let Some(health) = service.health().remove("mock") else {
+        return Err(anyhow::anyhow!("no health for mock"));
+    };
+    assert_eq!(health.connection_state, ConnectionState::Connected);
+    assert!(health.frames_received >= 5);
+    service.stop_monitoring("mock")?;
+    assert!(service.is_game_running("unknown").is_err());
+    Ok(())
+}
+
+#[test]
+fn recv_times_out_and_then_resumes() -> Result<()> {
+    let (tx, rx) = mpsc::channel(4);
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(ChannelAdapter {
+        rx: Mutex::new(Some(rx)),
+    }));
+    let mut service = BlockingTelemetryService::new(service)?;
+    let mut frames = service
+        .start_monitoring("quiet_source")?
+        .with_timeout(Duration::from_millis(50));
+
+    let started = Instant::now();
+    assert_eq!(
+        frames.recv().err(),
+        Some(BlockingRecvError::Timeout(Duration::from_millis(50)))
+    );
+    assert!(started.elapsed() >= Duration::from_millis(50));
+    assert!(frames.next().is_none());
+
+    tx.blocking_send(TelemetryFrame::new(NormalizedTelemetry::default(), 0, 7, 0))?;
+    frames.set_timeout(Some(FRAME_TIMEOUT));
+    assert_eq!(frames.recv()?.sequence, 7);
+
+    drop(tx);
+    assert_eq!(frames.recv().err(), Some(BlockingRecvError::Closed));
+    Ok(())
+}
+
+#[test]
+fn dropping_the_service_ends_iteration() -> Result<()> {
+    let mut service = mock_service()?;
+    let frames = service.start_monitoring("mock")?;
+    let (first_tx, first_rx) = std_mpsc::channel();
+    let (done_tx, done_rx) = std_mpsc::channel();
+    let consumer = std::thread::spawn(move || {
+        let mut count = 0usize;
+        for _frame in frames {
+            count += 1;
+            if count == 1 {
+                let _ = first_tx.send(());
+            }
+        }
+        let _ = done_tx.send(count);
+    });
+
+    first_rx.recv_timeout(FRAME_TIMEOUT)?;
+    let dropping = Instant::now();
+    drop(service);
+    assert!(dropping.elapsed() < Duration::from_secs(3));
+
+    let count = done_rx.recv_timeout(FRAME_TIMEOUT)?;
+    assert!(count >= 1);
+    consumer
+        .join()
+        .map_err(|_| anyhow::anyhow!("consumer thread panicked"))?;
+    Ok(())
+}
+
+#[test]
+fn calls_from_inside_a_runtime_fail() -> Result<()> {
+    let service = mock_service()?;
+    let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+    let result = runtime.block_on(async { service.is_game_running("mock") });
+    assert!(result.is_err());
+    Ok(())
+}
@@ -0,0 +1,163 @@
+//! Channel-fed adapter fixture shared by the service integration tests.
+
+// Each test binary uses its own subset of the helpers.
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use racing_wheel_telemetry_adapters::{
+    NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver,
+};
+use racing_wheel_telemetry_core::ConnectionStateEvent;
+use racing_wheel_telemetry_orchestrator::{
+    AdapterHealth, HealthEvent, HealthEventReceiver, TelemetryService,
+};
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+/// Error every failed [`MockAdapter`] start reports.
+pub const START_ERROR: &str = "bind failed";
+
+/// Adapter whose successive `start_monitoring` calls hand out the scripted
+/// streams in order; `None` entries, and calls past the script, fail with
+/// [`START_ERROR`]. Nominally 20 Hz and always running unless configured
+/// otherwise.
+pub struct MockAdapter {
+    game_id: &'static str,
+    starts: Mutex<VecDeque<Option<TelemetryReceiver>>>,
+    update_rate: Duration,
+    dedup: bool,
+    running: Arc<AtomicBool>,
+    hang: bool,
+}
+
+impl MockAdapter {
+    pub fn new(game_id: &'static str) -> Self {
+        Self {
+            game_id,
+            starts: Mutex::new(VecDeque::new()),
+            update_rate: Duration::from_millis(50),
+            dedup: false,
+            running: Arc::new(AtomicBool::new(true)),
+            hang: false,
+        }
+    }
+
+    /// Hand out `rx` on the first start.
+    pub fn with_stream(self, rx: TelemetryReceiver) -> Self {
+        self.with_starts([Some(rx)])
+    }
+
+    pub fn with_starts(
+        mut self,
+        starts: impl IntoIterator<Item = Option<TelemetryReceiver>>,
+    ) -> Self {
+        self.starts = Mutex::new(starts.into_iter().collect());
+        self
+    }
+
+    pub fn with_update_rate(mut self, update_rate: Duration) -> Self {
+        self.update_rate = update_rate;
+        self
+    }
+
+    /// Prefer dedup when `dedup` is set, like a shared-memory poller.
+    pub fn with_dedup(mut self, dedup: bool) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    /// Report the game running while `running` is set.
+    pub fn with_running(mut self, running: Arc<AtomicBool>) -> Self {
+        self.running = running;
+        self
+    }
+
+    /// Never answer whether the game is running, like an adapter blocked on
+    /// a socket bind.
+    pub fn hanging(mut self) -> Self {
+        self.hang = true;
+        self
+    }
+}
+
+#[async_trait]
+impl TelemetryAdapter for MockAdapter {
+    fn game_id(&self) -> &str {
+        self.game_id
+    }
+
+    async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        self.starts
+            .lock()
+            .map_err(|_| anyhow::anyhow!("poisoned"))?
+            .pop_front()
+            .flatten()
+            .ok_or_else(|| anyhow::anyhow!(START_ERROR))
+    }
+
+    async fn stop_monitoring(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn normalize(&self, _raw: &[u8]) -> Result<NormalizedTelemetry> {
+        Ok(NormalizedTelemetry::default())
+    }
+
+    fn expected_update_rate(&self) -> Duration {
+        self.update_rate
+    }
+
+    async fn is_game_running(&self) -> Result<bool> {
+        if self.hang {
+            std::future::pending::<()>().await;
+        }
+        Ok(self.running.load(Ordering::Relaxed))
+    }
+
+    fn prefers_dedup(&self) -> bool {
+        self.dedup
+    }
+}
+
+/// Register a 20 Hz [`MockAdapter`] for `game_id`, returning its sender.
+pub fn register(
+    service: &mut TelemetryService,
+    game_id: &'static str,
+) -> mpsc::Sender<TelemetryFrame> {
+    let (tx, rx) = mpsc::channel(128);
+    service.register_adapter(Box::new(MockAdapter::new(game_id).with_stream(rx)));
+    tx
+}
+
+/// Register a [`MockAdapter`] for `game_id` that fails to start.
+pub fn register_broken(service: &mut TelemetryService, game_id: &'static str) {
+    service.register_adapter(Box::new(MockAdapter::new(game_id)));
+}
+
+pub fn frame(sequence: u64) -> TelemetryFrame {
+    TelemetryFrame::new(NormalizedTelemetry::default(), 0, sequence, 0)
+}
+
+pub fn health_of(service: &TelemetryService, game_id: &str) -> Result<AdapterHealth> {
+    service
+        .health()
+        .remove(game_id)
+        .ok_or_else(|| anyhow::anyhow!("no health for {game_id}"))
+}
+
+pub async fn next_event(events: &mut HealthEventReceiver) -> Result<ConnectionStateEvent> {
+    let event = timeout(Duration::from_secs(2), events.recv())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("health events closed"))?;
+    match event {
+        HealthEvent::ConnectionState(event) => Ok(event),
+        other => Err(anyhow::anyhow!("unexpected health event {other:?}")),
+    }
+}
@@ -3,8 +3,9 @@
 //! Exercises adapter registration, routing, lifecycle management, error propagation,
 //! matrix-driven selection, and recording integration.
 
+use racing_wheel_telemetry_adapters::{F1_25Adapter, ListenMode, Unit};
 use racing_wheel_telemetry_orchestrator::TelemetryService;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 type TestResult = Result<(), Box<dyn std::error::Error>>;
 
@@ -58,6 +59,45 @@ fn adapter_ids_are_unique() -> TestResult {
     Ok(())
 }
 
+#[test]
+fn listen_modes_cover_udp_adapters_only() -> TestResult {
+    let mut service = TelemetryService::new();
+    service.register_adapter(Box::new(
+        F1_25Adapter::new().with_listen_mode(ListenMode::Broadcast),
+    ));
+
+    let modes: HashMap<String, ListenMode> = service.listen_modes().into_iter().collect();
+    assert_eq!(modes.get("f1_25"), Some(&ListenMode::Broadcast));
+    assert_eq!(modes.get("forza_motorsport"), Some(&ListenMode::Unicast));
+    assert!(
+        !modes.contains_key("iracing"),
+        "shared-memory adapters have no listen mode"
+    );
+    Ok(())
+}
+
+#[test]
+fn unit_manifests_are_exposed_per_game() -> TestResult {
+    let service = TelemetryService::new();
+    let manifests = service.unit_manifests();
+    let ids: Vec<&str> = manifests.iter().map(|(id, _)| id.as_str()).collect();
+    let mut sorted = ids.clone();
+    sorted.sort_unstable();
+    assert_eq!(ids, sorted);
+    assert!(ids.contains(&"iracing") && ids.contains(&"f1_25"));
+
+    let f1 = service
+        .unit_manifest("f1_25")
+        .ok_or("f1_25 declares no unit manifest")?;
+    let speed = f1.get("speed_ms").ok_or("speed_ms undeclared")?;
+    assert_eq!(
+        (speed.source, speed.canonical),
+        (Unit::KilometersPerHour, Unit::MetersPerSecond)
+    );
+    assert!(service.unit_manifest("no_such_game").is_none());
+    Ok(())
+}
+
 #[test]
 fn known_games_are_registered() -> TestResult {
     let service = TelemetryService::new();
@@ -191,6 +231,24 @@ fn runtime_coverage_report_present_with_matrix() -> TestResult {
     Ok(())
 }
 
+#[test]
+fn assetto_corsa_has_a_config_writer() -> TestResult {
+    let service = TelemetryService::new();
+    let report = service
+        .runtime_coverage_report()
+        .ok_or("no coverage report")?;
+    assert!(
+        !report
+            .writer_coverage
+            .missing_in_registry
+            .iter()
+            .any(|game_id| game_id == "assetto_corsa"),
+        "missing writers: {:?}",
+        report.writer_coverage.missing_in_registry
+    );
+    Ok(())
+}
+
 #[test]
 fn runtime_bdd_metrics_present_with_matrix() -> TestResult {
     let service = TelemetryService::new();
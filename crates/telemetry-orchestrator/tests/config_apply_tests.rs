@@ -0,0 +1,91 @@
+//! Applying config to every installed game: partial success and matrix gating.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use racing_wheel_telemetry_config_writers::TelemetryConfig;
+use racing_wheel_telemetry_orchestrator::ConfigWriterOrchestrator;
+use racing_wheel_telemetry_support::load_default_matrix;
+use tempfile::tempdir;
+
+fn config() -> TelemetryConfig {
+    TelemetryConfig {
+        enabled: true,
+        update_rate_hz: 60,
+        output_method: "udp".to_string(),
+        output_target: "127.0.0.1:9996".to_string(),
+        fields: vec!["rpm".to_string(), "speed_ms".to_string()],
+        enable_high_rate_iracing_360hz: false,
+        allow_port_sharing: false,
+    }
+}
+
+#[test]
+fn one_failing_game_does_not_stop_the_others() -> Result<()> {
+    let acc = tempdir()?;
+    let iracing = tempdir()?;
+    // iRacing's config lives under Documents/, which cannot be created here.
+    fs::write(iracing.path().join("Documents"), "not a directory")?;
+    let uninstalled = acc.path().join("no-such-install");
+
+    let roots = HashMap::from([
+        ("acc".to_string(), acc.path().to_path_buf()),
+        ("iracing".to_string(), iracing.path().to_path_buf()),
+        ("ams2".to_string(), uninstalled),
+    ]);
+    let orchestrator = ConfigWriterOrchestrator::from_support_matrix(Some(load_default_matrix()?));
+
+    let results = orchestrator.apply_all(&roots, &config());
+    let outcomes: BTreeMap<&str, bool> = results
+        .iter()
+        .map(|(game_id, result)| (game_id.as_str(), result.is_ok()))
+        .collect();
+    assert_eq!(
+        outcomes,
+        BTreeMap::from([("acc", true), ("iracing", false)])
+    );
+
+    assert_eq!(
+        orchestrator.validate_all(&roots),
+        BTreeMap::from([("acc".to_string(), true), ("iracing".to_string(), false)])
+    );
+    Ok(())
+}
+
+#[test]
+fn games_missing_from_the_matrix_are_skipped() -> Result<()> {
+    let acc = tempdir()?;
+    let iracing = tempdir()?;
+    let roots = HashMap::from([
+        ("acc".to_string(), acc.path().to_path_buf()),
+        ("iracing".to_string(), iracing.path().to_path_buf()),
+    ]);
+
+    let mut matrix = load_default_matrix()?;
+    matrix.games.remove("acc");
+    let results =
+        ConfigWriterOrchestrator::from_support_matrix(Some(matrix)).apply_all(&roots, &config());
+    let applied: Vec<&str> = results
+        .iter()
+        .map(|(game_id, _)| game_id.as_str())
+        .collect();
+    assert_eq!(applied, ["iracing"]);
+    assert_eq!(fs::read_dir(acc.path())?.count(), 0);
+
+    // Without a matrix every registered writer with a root is applied.
+    let results = ConfigWriterOrchestrator::from_support_matrix(None).apply_all(&roots, &config());
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|(_, result)| result.is_ok()));
+    Ok(())
+}
+
+#[test]
+fn no_roots_applies_nothing() -> Result<()> {
+    let orchestrator = ConfigWriterOrchestrator::from_support_matrix(None);
+    let roots: HashMap<String, PathBuf> = HashMap::new();
+    assert!(orchestrator.apply_all(&roots, &config()).is_empty());
+    assert!(orchestrator.validate_all(&roots).is_empty());
+    Ok(())
+}
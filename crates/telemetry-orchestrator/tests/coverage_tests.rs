@@ -215,3 +215,30 @@ fn empty_matrix_has_no_supported_games() -> TestResult {
     assert!(service.matrix_game_ids().is_empty());
     Ok(())
 }
+
+#[test]
+fn matrix_no_config_writer_flag_exempts_the_game_from_writer_parity() -> TestResult {
+    let mut matrix =
+        load_default_matrix().map_err(|e| std::io::Error::other(format!("matrix load: {e}")))?;
+    let mut support = matrix
+        .games
+        .get("acc")
+        .cloned()
+        .ok_or_else(|| std::io::Error::other("matrix must list acc"))?;
+    support.no_config_writer = true;
+    matrix.games.insert("writerless_sim".to_string(), support);
+
+    let service = TelemetryService::from_support_matrix(Some(matrix));
+    let report = service
+        .runtime_coverage_report()
+        .ok_or_else(|| std::io::Error::other("no coverage report"))?;
+    let writers = &report.writer_coverage;
+    assert_eq!(writers.exempted_missing, ["writerless_sim"]);
+    assert!(
+        !writers
+            .missing_in_registry
+            .contains(&"writerless_sim".to_string())
+    );
+    assert!(writers.stale_exemptions.is_empty());
+    Ok(())
+}
@@ -0,0 +1,143 @@
+//! CSV export of recordings against a golden file, and its edge cases.
+#![cfg(feature = "csv")]
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::Result;
+use racing_wheel_telemetry_adapters::{NormalizedTelemetry, TelemetryFrame, TelemetryValue};
+use racing_wheel_telemetry_orchestrator::{
+    ChannelSelector, ExportStats, export_csv, export_recording_to_csv,
+};
+
+const GOLDEN: &str = include_str!("fixtures/csv_export_recording.csv");
+
+fn fixture_path() -> &'static Path {
+    Path::new(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/csv_export_recording.json"
+    ))
+}
+
+fn channels(names: &[&str]) -> Result<Vec<ChannelSelector>> {
+    Ok(names
+        .iter()
+        .map(|name| name.parse())
+        .collect::<Result<_, _>>()?)
+}
+
+fn frame_with(timestamp_ns: u64, extended: BTreeMap<String, TelemetryValue>) -> TelemetryFrame {
+    let data = NormalizedTelemetry {
+        extended,
+        ..NormalizedTelemetry::default()
+    };
+    TelemetryFrame::new(data, timestamp_ns, 0, 0)
+}
+
+/// Counts bytes without keeping them.
+#[derive(Default)]
+struct CountingWriter(u64);
+
+impl Write for CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn fixture_recording_matches_golden_csv() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let output = dir.path().join("export.csv");
+    let selected = channels(&[
+        "rpm",
+        "speed_ms",
+        "gear",
+        "ffb_scalar",
+        "flags.in_pits",
+        "car_id",
+        "extended:tire_temp_fl",
+        "extended:pit_request",
+        "extended:compound",
+        "extended:wheel_speeds",
+    ])?;
+
+    let stats = export_recording_to_csv(fixture_path(), &output, &selected)?;
+    assert_eq!(std::fs::read_to_string(&output)?, GOLDEN);
+    assert_eq!(
+        stats,
+        ExportStats {
+            rows: 4,
+            columns: 11,
+            // car_id in the first frame, tire_temp_fl in the third and
+            // wheel_speeds in all but the second.
+            empty_cells: 5,
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn strings_are_quoted_per_rfc_4180() -> Result<()> {
+    let extended = BTreeMap::from([(
+        "note".to_string(),
+        TelemetryValue::String("box \"now\"\nplease".to_string()),
+    )]);
+    let mut out = Vec::new();
+    export_csv(
+        std::iter::once(frame_with(0, extended)),
+        &channels(&["extended:note", "extended:plain"])?,
+        &mut out,
+    )?;
+    assert_eq!(
+        String::from_utf8(out)?,
+        "time_s,extended:note,extended:plain\r\n0.000000,\"box \"\"now\"\"\nplease\",\r\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn non_finite_floats_and_maps_are_handled() -> Result<()> {
+    let extended = BTreeMap::from([
+        ("ratio".to_string(), TelemetryValue::Float(f32::NAN)),
+        (
+            "ers".to_string(),
+            TelemetryValue::Map(BTreeMap::from([(
+                "mode".to_string(),
+                TelemetryValue::Integer(2),
+            )])),
+        ),
+    ]);
+    let mut out = Vec::new();
+    let stats = export_csv(
+        std::iter::once(frame_with(0, extended)),
+        &channels(&["extended:ratio", "extended:ers"])?,
+        &mut out,
+    )?;
+    let text = String::from_utf8(out)?;
+    let Some(row) = text.lines().nth(1) else {
+        return Err(anyhow::anyhow!("no data row in {text:?}"));
+    };
+    assert_eq!(
+        row,
+        r#"0.000000,,"{""mode"":{""type"":""Integer"",""value"":2}}""#
+    );
+    assert_eq!(stats.empty_cells, 1);
+    Ok(())
+}
+
+#[test]
+fn large_recordings_stream_without_collecting() -> Result<()> {
+    const FRAMES: u64 = 200_000;
+    let frames = (0..FRAMES).map(|index| frame_with(1_000 + index * 2_777_778, BTreeMap::new()));
+    let mut out = CountingWriter::default();
+    let stats = export_csv(frames, &channels(&["rpm", "gear"])?, &mut out)?;
+    assert_eq!(stats.rows, FRAMES);
+    assert!(out.0 > FRAMES * 10);
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! Unchanged repeats dropped for adapters that prefer deduplication.
+
+mod common;
+
+use std::time::Duration;
+
+use anyhow::Result;
+use racing_wheel_telemetry_adapters::{NormalizedTelemetry, TelemetryFrame};
+use racing_wheel_telemetry_orchestrator::TelemetryService;
+use tokio::sync::mpsc;
+use tokio::time::{sleep, timeout};
+
+use common::MockAdapter;
+
+const MS: u64 = 1_000_000;
+
+/// Five polls of the same frame 5 ms apart, then a gear change, returning
+/// the gears of the frames forwarded.
+async fn forward_polls(dedup: bool) -> Result<(Vec<i8>, u64)> {
+    let game_id = if dedup { "polled_game" } else { "pushed_game" };
+    let (tx, rx) = mpsc::channel(16);
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(
+        MockAdapter::new(game_id)
+            .with_stream(rx)
+            .with_update_rate(Duration::from_millis(5))
+            .with_dedup(dedup),
+    ));
+    let mut forwarded = service.start_monitoring(game_id).await?;
+
+    let gears = [3, 3, 3, 3, 3, 4];
+    for (sequence, gear) in gears.into_iter().enumerate() {
+        let data = NormalizedTelemetry {
+            rpm: 5_000.0,
+            gear,
+            ..NormalizedTelemetry::default()
+        };
+        let sequence = sequence as u64;
+        tx.send(TelemetryFrame::new(data, sequence * 5 * MS, sequence, 64))
+            .await?;
+        sleep(Duration::from_millis(5)).await;
+    }
+    drop(tx);
+
+    let mut seen = Vec::new();
+    while let Ok(Some(frame)) = timeout(Duration::from_millis(500), forwarded.recv()).await {
+        seen.push(frame.data.gear);
+    }
+    let deduplicated = service
+        .health()
+        .remove(game_id)
+        .map_or(0, |health| health.frames_deduplicated);
+    Ok((seen, deduplicated))
+}
+
+#[tokio::test]
+async fn repeats_are_dropped_for_adapters_preferring_dedup() -> Result<()> {
+    let (gears, deduplicated) = forward_polls(true).await?;
+    assert_eq!(gears, [3, 4]);
+    assert_eq!(deduplicated, 4);
+    Ok(())
+}
+
+#[tokio::test]
+async fn other_adapters_forward_every_frame() -> Result<()> {
+    let (gears, deduplicated) = forward_polls(false).await?;
+    assert_eq!(gears, [3, 3, 3, 3, 3, 4]);
+    assert_eq!(deduplicated, 0);
+    Ok(())
+}
@@ -0,0 +1,116 @@
+//! Detecting which registered games are running.
+
+mod common;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use racing_wheel_telemetry_orchestrator::{GameDetectedEvent, GameDetector, TelemetryService};
+use racing_wheel_telemetry_support::GameSupportMatrix;
+use tokio::time::timeout;
+
+use common::MockAdapter;
+
+fn empty_service() -> TelemetryService {
+    TelemetryService::from_support_matrix(Some(GameSupportMatrix {
+        games: HashMap::new(),
+    }))
+}
+
+fn register(
+    service: &mut TelemetryService,
+    game_id: &'static str,
+    running: bool,
+) -> Arc<AtomicBool> {
+    let running = Arc::new(AtomicBool::new(running));
+    service.register_adapter(Box::new(
+        MockAdapter::new(game_id).with_running(Arc::clone(&running)),
+    ));
+    running
+}
+
+fn register_hanging(service: &mut TelemetryService, game_id: &'static str) {
+    service.register_adapter(Box::new(MockAdapter::new(game_id).hanging()));
+}
+
+fn ids(events: &[GameDetectedEvent]) -> Vec<(&str, bool)> {
+    events
+        .iter()
+        .map(|event| (event.game_id.as_str(), event.detected))
+        .collect()
+}
+
+#[tokio::test]
+async fn a_hanging_adapter_does_not_stall_detection() -> Result<()> {
+    let mut service = empty_service();
+    let _running = register(&mut service, "running_game", true);
+    let _stopped = register(&mut service, "stopped_game", false);
+    register_hanging(&mut service, "hanging_game");
+
+    let started = Instant::now();
+    let running = service
+        .detect_running_games(Duration::from_millis(50))
+        .await;
+    assert_eq!(running, ["running_game"]);
+    assert!(started.elapsed() < Duration::from_secs(1));
+    Ok(())
+}
+
+#[test]
+fn detector_needs_two_polls_in_a_row_to_flip() {
+    let mut detector = GameDetector::new();
+    assert!(detector.observe(&["acc"], 1).is_empty());
+    // A single negative poll resets the count.
+    assert!(detector.observe::<&str>(&[], 2).is_empty());
+    assert!(detector.observe(&["acc"], 3).is_empty());
+
+    let events = detector.observe(&["acc"], 4);
+    assert_eq!(ids(&events), [("acc", true)]);
+    assert_eq!(events.first().map(|event| event.timestamp_ns), Some(4));
+    assert_eq!(detector.detected(), ["acc"]);
+
+    assert!(detector.observe::<&str>(&[], 5).is_empty());
+    assert!(detector.observe(&["acc"], 6).is_empty());
+    assert!(detector.observe::<&str>(&[], 7).is_empty());
+    assert_eq!(ids(&detector.observe::<&str>(&[], 8)), [("acc", false)]);
+    assert!(detector.detected().is_empty());
+}
+
+#[test]
+fn detector_orders_simultaneous_changes_by_game_id() {
+    let mut detector = GameDetector::new();
+    detector.observe(&["iracing", "acc"], 1);
+    let events = detector.observe(&["iracing", "acc"], 2);
+    assert_eq!(ids(&events), [("acc", true), ("iracing", true)]);
+
+    detector.observe(&["acc"], 3);
+    let events = detector.observe(&["acc", "dirt5"], 4);
+    assert_eq!(ids(&events), [("iracing", false)]);
+}
+
+#[tokio::test]
+async fn detection_loop_reports_games_starting_and_stopping() -> Result<()> {
+    let mut service = empty_service();
+    let flag = register(&mut service, "toggled_game", false);
+    register_hanging(&mut service, "hanging_game");
+    let mut events = service.spawn_detection_loop(Duration::from_millis(20));
+
+    flag.store(true, Ordering::Relaxed);
+    let started = timeout(Duration::from_secs(2), events.recv())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("detection loop ended"))?;
+    assert_eq!(started.game_id, "toggled_game");
+    assert!(started.detected);
+
+    flag.store(false, Ordering::Relaxed);
+    let stopped = timeout(Duration::from_secs(2), events.recv())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("detection loop ended"))?;
+    assert_eq!(stopped.game_id, "toggled_game");
+    assert!(!stopped.detected);
+    assert!(stopped.timestamp_ns > started.timestamp_ns);
+    Ok(())
+}
@@ -0,0 +1,130 @@
+//! The telemetry service against adapters that misbehave on script.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use racing_wheel_telemetry_adapters::{FaultScript, FaultyMockAdapter, TelemetryFrame};
+use racing_wheel_telemetry_core::{ConnectionState, DisconnectionConfig};
+use racing_wheel_telemetry_orchestrator::{AdapterHealth, TelemetryService};
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+const RATE: Duration = Duration::from_millis(5);
+
+fn register(service: &mut TelemetryService, game_id: &str, script: FaultScript) {
+    service.register_adapter(Box::new(
+        FaultyMockAdapter::new(game_id, script).with_update_rate(RATE),
+    ));
+}
+
+fn reconnecting() -> DisconnectionConfig {
+    DisconnectionConfig {
+        timeout_ms: 2000,
+        auto_reconnect: true,
+        max_reconnect_attempts: 0,
+        reconnect_delay_ms: 5,
+        ..DisconnectionConfig::default()
+    }
+}
+
+async fn next_frame(frames: &mut mpsc::Receiver<TelemetryFrame>) -> Result<TelemetryFrame> {
+    timeout(Duration::from_secs(2), frames.recv())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("forwarding ended"))
+}
+
+fn health_of(service: &TelemetryService, game_id: &str) -> Result<AdapterHealth> {
+    service
+        .health()
+        .remove(game_id)
+        .ok_or_else(|| anyhow::anyhow!("no health for {game_id}"))
+}
+
+#[tokio::test]
+async fn auto_reconnect_recovers_from_failed_restarts() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    // The stream ends after 10 frames and the next two restarts fail.
+    register(
+        &mut service,
+        "faulty_restarts",
+        FaultScript::new(11).end_after(10).fail_starts_from(2, 2),
+    );
+    service.set_disconnection_config("faulty_restarts", reconnecting());
+    let mut frames = service.start_monitoring("faulty_restarts").await?;
+
+    let mut sequences = Vec::new();
+    while sequences.len() < 15 {
+        sequences.push(next_frame(&mut frames).await?.sequence);
+    }
+    // The resumed stream carries on where the first one ended.
+    assert_eq!(sequences, (0..15).collect::<Vec<_>>());
+
+    let health = health_of(&service, "faulty_restarts")?;
+    assert_eq!(health.connection_state, ConnectionState::Connected);
+    assert_eq!(
+        health.last_error.as_deref(),
+        Some("scripted start failure (attempt 3)")
+    );
+    service.stop_monitoring("faulty_restarts").await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn non_finite_frames_are_sanitized_and_counted() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    register(
+        &mut service,
+        "faulty_nan",
+        FaultScript::new(5).nan_rpm(0.5).end_after(40),
+    );
+    let mut frames = service.start_monitoring("faulty_nan").await?;
+
+    let mut received = 0;
+    while let Some(frame) = timeout(Duration::from_secs(2), frames.recv()).await? {
+        assert!(frame.data.rpm.is_finite(), "frame {}", frame.sequence);
+        assert!(frame.data.all_finite(), "frame {}", frame.sequence);
+        received += 1;
+        if received == 40 {
+            break;
+        }
+    }
+    assert_eq!(received, 40);
+
+    let health = health_of(&service, "faulty_nan")?;
+    assert!(health.frames_sanitized > 0, "{health:?}");
+    assert!(health.frames_sanitized < 40, "{health:?}");
+    service.stop_monitoring("faulty_nan").await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn dropped_and_reordered_frames_show_in_stream_quality() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    register(
+        &mut service,
+        "faulty_sequence",
+        FaultScript::new(5).drop_frames(0.1).reorder_frames(0.1),
+    );
+    assert!(service.quality("faulty_sequence").is_none());
+    let mut frames = service.start_monitoring("faulty_sequence").await?;
+    for _ in 0..60 {
+        next_frame(&mut frames).await?;
+    }
+    service.stop_monitoring("faulty_sequence").await?;
+
+    let quality = service
+        .quality("faulty_sequence")
+        .ok_or_else(|| anyhow::anyhow!("no stream quality"))?;
+    assert!(quality.frames >= 60, "{quality:?}");
+    assert!(
+        quality.gaps > 0 && quality.missing_frames > 0,
+        "{quality:?}"
+    );
+    assert!(quality.out_of_order > 0, "{quality:?}");
+    assert_eq!(quality.restarts, 0);
+    assert_eq!(quality.expected_rate_hz, Some(200.0));
+
+    let health = health_of(&service, "faulty_sequence")?;
+    assert_eq!(health.stream_quality, Some(quality.summary()));
+    Ok(())
+}
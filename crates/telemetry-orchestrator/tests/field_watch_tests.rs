@@ -0,0 +1,182 @@
+//! Field watches: change-only notification, tolerance and disconnect handling.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use racing_wheel_telemetry_adapters::{
+    NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver, TelemetryValue,
+};
+use racing_wheel_telemetry_orchestrator::{FieldSelector, FieldWatchHub, TelemetryService};
+use tokio::sync::mpsc;
+
+const GAME_ID: &str = "watch_source";
+
+/// Adapter whose frames come from a test-owned channel; dropping the sender
+/// disconnects the source.
+struct ChannelAdapter {
+    rx: Mutex<Option<TelemetryReceiver>>,
+}
+
+#[async_trait]
+impl TelemetryAdapter for ChannelAdapter {
+    fn game_id(&self) -> &str {
+        GAME_ID
+    }
+
+    async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        self.rx
+            .lock()
+            .map_err(|_| anyhow::anyhow!("poisoned"))?
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("already monitoring"))
+    }
+
+    async fn stop_monitoring(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn normalize(&self, _raw: &[u8]) -> Result<NormalizedTelemetry> {
+        Ok(NormalizedTelemetry::default())
+    }
+
+    fn expected_update_rate(&self) -> Duration {
+        Duration::from_millis(1)
+    }
+
+    async fn is_game_running(&self) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+fn telemetry(gear: i8, speed_ms: f32) -> NormalizedTelemetry {
+    NormalizedTelemetry::builder()
+        .gear(gear)
+        .speed_ms(speed_ms)
+        .build()
+}
+
+#[test]
+fn gear_change_notifies_once_and_unchanged_frames_do_not() -> Result<()> {
+    let hub = FieldWatchHub::new();
+    let mut gear = hub.watch(FieldSelector::new("gear")?);
+    let mut shared = hub.watch(FieldSelector::new("gear")?);
+    assert_eq!(hub.watched_count(), 1);
+
+    hub.publish(&telemetry(3, 30.0));
+    assert!(gear.has_changed()?);
+    assert_eq!(*gear.borrow_and_update(), Some(TelemetryValue::Integer(3)));
+
+    for i in 0..100 {
+        hub.publish(&telemetry(3, 30.0 + i as f32));
+    }
+    assert!(!gear.has_changed()?);
+
+    hub.publish(&telemetry(4, 60.0));
+    assert!(gear.has_changed()?);
+    assert_eq!(*gear.borrow_and_update(), Some(TelemetryValue::Integer(4)));
+    assert!(!gear.has_changed()?);
+
+    assert_eq!(
+        *shared.borrow_and_update(),
+        Some(TelemetryValue::Integer(4))
+    );
+    Ok(())
+}
+
+#[test]
+fn tolerance_suppresses_sub_threshold_speed_jitter() -> Result<()> {
+    let hub = FieldWatchHub::new();
+    let mut speed = hub.watch(FieldSelector::new("speed_ms")?.with_tolerance(0.5));
+
+    hub.publish(&telemetry(3, 20.0));
+    assert_eq!(
+        *speed.borrow_and_update(),
+        Some(TelemetryValue::Float(20.0))
+    );
+
+    // Jitter is measured against the last published value, not the last frame.
+    for jitter in [20.1, 19.8, 20.3, 20.5, 19.6] {
+        hub.publish(&telemetry(3, jitter));
+    }
+    assert!(!speed.has_changed()?);
+
+    hub.publish(&telemetry(3, 20.6));
+    assert_eq!(
+        *speed.borrow_and_update(),
+        Some(TelemetryValue::Float(20.6))
+    );
+    Ok(())
+}
+
+#[test]
+fn flags_and_extended_keys_resolve_and_go_absent() -> Result<()> {
+    let hub = FieldWatchHub::new();
+    let mut in_pits = hub.watch("flags.in_pits".parse()?);
+    let mut bias = hub.watch("extended:brake_bias".parse()?);
+    assert!(FieldSelector::new("flags.not_a_flag").is_err());
+    assert!(FieldSelector::new("gears").is_err());
+
+    let mut with_bias = telemetry(2, 10.0);
+    with_bias.flags.in_pits = true;
+    with_bias
+        .extended
+        .insert("brake_bias".to_string(), TelemetryValue::Float(0.56));
+    hub.publish(&with_bias);
+    assert_eq!(
+        *in_pits.borrow_and_update(),
+        Some(TelemetryValue::Boolean(true))
+    );
+    assert_eq!(*bias.borrow_and_update(), Some(TelemetryValue::Float(0.56)));
+
+    hub.publish(&telemetry(2, 10.0));
+    assert_eq!(*bias.borrow_and_update(), None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn disconnect_propagates_none_to_all_watchers() -> Result<()> {
+    let (tx, source) = mpsc::channel(16);
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(ChannelAdapter {
+        rx: Mutex::new(Some(source)),
+    }));
+
+    let mut gear = service.watch_field(GAME_ID, FieldSelector::new("gear")?);
+    let mut dashboard = service.watch_fields(
+        GAME_ID,
+        &[FieldSelector::new("gear")?, FieldSelector::new("speed_ms")?],
+    );
+    let mut frames = service.start_monitoring(GAME_ID).await?;
+
+    tx.send(TelemetryFrame::new(telemetry(5, 42.0), 0, 0, 0))
+        .await?;
+    frames
+        .recv()
+        .await
+        .ok_or_else(|| anyhow::anyhow!("forwarding stopped early"))?;
+
+    let wait = Duration::from_secs(5);
+    tokio::time::timeout(wait, gear.wait_for(|value| value.is_some())).await??;
+    tokio::time::timeout(
+        wait,
+        dashboard.wait_for(|values| {
+            values
+                == &[
+                    Some(TelemetryValue::Integer(5)),
+                    Some(TelemetryValue::Float(42.0)),
+                ]
+        }),
+    )
+    .await??;
+
+    drop(tx);
+    tokio::time::timeout(wait, gear.wait_for(|value| value.is_none())).await??;
+    tokio::time::timeout(
+        wait,
+        dashboard.wait_for(|values| values.iter().all(Option::is_none)),
+    )
+    .await??;
+    Ok(())
+}
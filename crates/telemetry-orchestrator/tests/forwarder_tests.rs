@@ -0,0 +1,212 @@
+//! Frames re-broadcast over UDP reach local consumers at each target's rate.
+
+use std::net::{Ipv4Addr, SocketAddr};
+use std::time::Duration;
+
+use anyhow::Result;
+use racing_wheel_schemas::telemetry::decode_frame;
+use racing_wheel_telemetry_adapters::{
+    MockAdapter, NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryValue,
+};
+use racing_wheel_telemetry_orchestrator::sinks::WireEncoding;
+use racing_wheel_telemetry_orchestrator::{
+    ForwardError, ForwardTarget, MAX_DATAGRAM_BYTES, TelemetryForwarder, encode_datagram,
+};
+use tokio::net::UdpSocket;
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, timeout};
+
+const RECV_TIMEOUT: Duration = Duration::from_secs(5);
+
+async fn consumer() -> Result<(UdpSocket, SocketAddr)> {
+    let socket = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+    let addr = socket.local_addr()?;
+    Ok((socket, addr))
+}
+
+/// Forward a mock adapter producing a frame every `period`.
+async fn forward_mock(forwarder: &TelemetryForwarder, period: Duration) -> Result<JoinHandle<()>> {
+    let adapter = MockAdapter::with_update_rate("mock_fwd".to_string(), period);
+    let frames = adapter.start_monitoring().await?;
+    Ok(forwarder.spawn(frames))
+}
+
+async fn recv_frame(socket: &UdpSocket) -> Result<TelemetryFrame> {
+    let mut buf = vec![0u8; 64 * 1024];
+    let len = timeout(RECV_TIMEOUT, socket.recv(&mut buf)).await??;
+    Ok(serde_json::from_slice(&buf[..len])?)
+}
+
+/// Count datagrams arriving within `window`.
+async fn count_for(socket: &UdpSocket, window: Duration) -> usize {
+    let mut buf = vec![0u8; 64 * 1024];
+    let mut count = 0;
+    let _ = timeout(window, async {
+        while socket.recv(&mut buf).await.is_ok() {
+            count += 1;
+        }
+    })
+    .await;
+    count
+}
+
+#[tokio::test]
+async fn consumer_receives_forwarded_json_frames() -> Result<()> {
+    let (socket, addr) = consumer().await?;
+    let forwarder = TelemetryForwarder::new();
+    forwarder.add_target(ForwardTarget::new(addr)).await?;
+    let task = forward_mock(&forwarder, Duration::from_millis(5)).await?;
+
+    let first = recv_frame(&socket).await?;
+    let second = recv_frame(&socket).await?;
+    assert!(second.sequence > first.sequence);
+    assert!(first.data.rpm > 0.0);
+
+    let stats = forwarder.stats();
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].target.addr, addr);
+    assert!(stats[0].sent >= 2);
+    assert!(stats[0].bytes_sent > 0);
+    task.abort();
+    Ok(())
+}
+
+#[tokio::test]
+async fn binary_target_receives_codec_records() -> Result<()> {
+    let (socket, addr) = consumer().await?;
+    let forwarder = TelemetryForwarder::new();
+    forwarder
+        .add_target(ForwardTarget::new(addr).with_encoding(WireEncoding::Binary))
+        .await?;
+    let task = forward_mock(&forwarder, Duration::from_millis(5)).await?;
+
+    let mut buf = vec![0u8; 64 * 1024];
+    let len = timeout(RECV_TIMEOUT, socket.recv(&mut buf)).await??;
+    let frame = decode_frame(&buf[..len])?;
+    assert!(frame.data.rpm > 0.0);
+    task.abort();
+    Ok(())
+}
+
+#[tokio::test]
+async fn frame_over_the_datagram_limit_is_counted_not_sent() -> Result<()> {
+    let (_socket, addr) = consumer().await?;
+    let forwarder = TelemetryForwarder::new();
+    forwarder
+        .add_target(ForwardTarget::new(addr).with_encoding(WireEncoding::Binary))
+        .await?;
+
+    let mut data = NormalizedTelemetry::default();
+    data.extended.insert(
+        "setup_notes".to_string(),
+        TelemetryValue::String("x".repeat(MAX_DATAGRAM_BYTES)),
+    );
+    let frame = TelemetryFrame::new(data, 1, 1, 64);
+    assert!(matches!(
+        encode_datagram(&frame, WireEncoding::Binary),
+        Err(ForwardError::DatagramTooLarge { len, max }) if len > max
+    ));
+
+    forwarder.forward(&frame);
+    let stats = forwarder.stats();
+    assert_eq!(stats[0].oversized, 1);
+    assert_eq!(stats[0].sent, 0);
+    assert_eq!(stats[0].errors, 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn rate_limited_target_receives_fewer_frames() -> Result<()> {
+    let (full, full_addr) = consumer().await?;
+    let (limited, limited_addr) = consumer().await?;
+    let forwarder = TelemetryForwarder::new();
+    forwarder.add_target(ForwardTarget::new(full_addr)).await?;
+    forwarder
+        .add_target(ForwardTarget::new(limited_addr).with_max_rate_hz(20))
+        .await?;
+    // A fast source, well above the limited target's rate.
+    let task = forward_mock(&forwarder, Duration::from_millis(2)).await?;
+
+    let window = Duration::from_millis(500);
+    let (full_count, limited_count) =
+        tokio::join!(count_for(&full, window), count_for(&limited, window));
+    task.abort();
+
+    assert!(limited_count > 0);
+    assert!(
+        limited_count * 3 < full_count,
+        "limited {limited_count} vs full {full_count}"
+    );
+    let stats = forwarder.stats();
+    let Some(limited_stats) = stats.iter().find(|s| s.target.addr == limited_addr) else {
+        return Err(anyhow::anyhow!("limited target missing from stats"));
+    };
+    assert!(limited_stats.rate_limited > 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn removed_target_stops_receiving() -> Result<()> {
+    let (kept, kept_addr) = consumer().await?;
+    let (removed, removed_addr) = consumer().await?;
+    let forwarder = TelemetryForwarder::new();
+    forwarder.add_target(ForwardTarget::new(kept_addr)).await?;
+    forwarder
+        .add_target(ForwardTarget::new(removed_addr))
+        .await?;
+    assert!(
+        forwarder
+            .add_target(ForwardTarget::new(removed_addr))
+            .await
+            .is_err()
+    );
+    let task = forward_mock(&forwarder, Duration::from_millis(5)).await?;
+
+    recv_frame(&removed).await?;
+    assert!(forwarder.remove_target(removed_addr));
+    assert!(!forwarder.remove_target(removed_addr));
+    // Let anything already in flight land before counting.
+    sleep(Duration::from_millis(50)).await;
+    let _ = count_for(&removed, Duration::from_millis(10)).await;
+
+    let window = Duration::from_millis(200);
+    let (kept_count, removed_count) =
+        tokio::join!(count_for(&kept, window), count_for(&removed, window));
+    task.abort();
+
+    assert!(kept_count > 0);
+    assert_eq!(removed_count, 0);
+    assert_eq!(forwarder.stats().len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn unreachable_target_counts_errors_without_stopping() -> Result<()> {
+    // Bind then drop, so nothing listens on the port.
+    let (closed, closed_addr) = consumer().await?;
+    drop(closed);
+    let (live, live_addr) = consumer().await?;
+    let forwarder = TelemetryForwarder::new();
+    forwarder
+        .add_target(ForwardTarget::new(closed_addr))
+        .await?;
+    forwarder.add_target(ForwardTarget::new(live_addr)).await?;
+    let task = forward_mock(&forwarder, Duration::from_millis(2)).await?;
+
+    sleep(Duration::from_millis(200)).await;
+    let received = count_for(&live, Duration::from_millis(100)).await;
+    assert!(received > 0);
+    assert!(!task.is_finished());
+    task.abort();
+
+    let stats = forwarder.stats();
+    let Some(closed_stats) = stats.iter().find(|s| s.target.addr == closed_addr) else {
+        return Err(anyhow::anyhow!("closed target missing from stats"));
+    };
+    // Without ICMP feedback every send succeeds; with it, refusals are counted.
+    assert!(closed_stats.errors + closed_stats.sent > 0);
+    if cfg!(target_os = "linux") {
+        assert!(closed_stats.errors > 0, "{closed_stats:?}");
+    }
+    Ok(())
+}
@@ -0,0 +1,155 @@
+//! Freshness indicator: transition sequence, causes and flap damping.
+
+mod common;
+
+use std::time::Duration;
+
+use anyhow::Result;
+use racing_wheel_telemetry_adapters::{NormalizedTelemetry, TelemetryFrame};
+use racing_wheel_telemetry_orchestrator::{
+    Freshness, FreshnessCause, FreshnessEvent, FreshnessStatus, FreshnessThresholds,
+    TelemetryService,
+};
+use tokio::sync::{broadcast, mpsc};
+use tokio::time::sleep;
+
+use common::MockAdapter;
+
+const GAME_ID: &str = "freshness_source";
+const INTERVAL: Duration = Duration::from_millis(10);
+
+/// Service monitoring a [`MockAdapter`]; returns the frame sender.
+async fn monitored_service() -> Result<(TelemetryService, mpsc::Sender<TelemetryFrame>)> {
+    let (tx, rx) = mpsc::channel(16);
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(
+        MockAdapter::new(GAME_ID)
+            .with_stream(rx)
+            .with_update_rate(INTERVAL),
+    ));
+    let mut forwarded = service.start_monitoring(GAME_ID).await?;
+    tokio::spawn(async move { while forwarded.recv().await.is_some() {} });
+    Ok((service, tx))
+}
+
+async fn send_frames(
+    tx: &mpsc::Sender<TelemetryFrame>,
+    count: u64,
+    spacing: Duration,
+) -> Result<()> {
+    for sequence in 0..count {
+        let frame = TelemetryFrame::new(NormalizedTelemetry::default(), 0, sequence, 0);
+        tx.send(frame).await?;
+        sleep(spacing).await;
+    }
+    Ok(())
+}
+
+fn drain(events: &mut broadcast::Receiver<FreshnessEvent>) -> Vec<(Freshness, FreshnessCause)> {
+    std::iter::from_fn(|| events.try_recv().ok())
+        .map(|event| (event.current.freshness, event.current.cause))
+        .collect()
+}
+
+#[tokio::test(start_paused = true)]
+async fn frames_pause_silence_disconnect_walk_the_expected_states() -> Result<()> {
+    let (mut service, tx) = monitored_service().await?;
+    let mut events = service.subscribe_freshness_events();
+    let mut status = service.watch_freshness(GAME_ID);
+    assert_eq!(
+        service.freshness_status(GAME_ID),
+        Some(FreshnessStatus {
+            freshness: Freshness::Stale,
+            cause: FreshnessCause::Age
+        })
+    );
+
+    // Racing at the expected rate.
+    send_frames(&tx, 100, INTERVAL).await?;
+    assert_eq!(service.freshness(GAME_ID), Some(Freshness::Fresh));
+    // Pause menu: the game keeps sending, ten times slower.
+    send_frames(&tx, 10, INTERVAL * 10).await?;
+    assert_eq!(service.freshness(GAME_ID), Some(Freshness::Stale));
+    // Silence while the socket stays open.
+    sleep(Duration::from_secs(1)).await;
+    drop(tx);
+    status
+        .wait_for(|status| status.is_some_and(|s| s.freshness == Freshness::Dead))
+        .await?;
+
+    assert_eq!(
+        drain(&mut events),
+        vec![
+            (Freshness::Fresh, FreshnessCause::Frames),
+            (Freshness::Stale, FreshnessCause::Drift),
+            (Freshness::Stale, FreshnessCause::Age),
+            (Freshness::Dead, FreshnessCause::Disconnect),
+        ]
+    );
+    let snapshot = service.freshness_snapshot();
+    assert_eq!(snapshot.len(), 1);
+    assert_eq!(snapshot[0].game_id, GAME_ID);
+    assert_eq!(snapshot[0].status.cause, FreshnessCause::Disconnect);
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn flapping_breaker_is_damped_by_min_dwell() -> Result<()> {
+    let (mut service, tx) = monitored_service().await?;
+    let mut events = service.subscribe_freshness_events();
+    let producer = tokio::spawn(async move { send_frames(&tx, 150, INTERVAL).await });
+
+    sleep(Duration::from_millis(300)).await;
+    // Ten toggles 20 ms apart, ending closed; the default dwell is 250 ms.
+    for toggle in 0..10 {
+        service.report_breaker(GAME_ID, toggle % 2 == 0);
+        sleep(Duration::from_millis(20)).await;
+    }
+    producer.await??;
+
+    assert_eq!(
+        drain(&mut events),
+        vec![
+            (Freshness::Fresh, FreshnessCause::Frames),
+            (Freshness::Dead, FreshnessCause::Breaker),
+            (Freshness::Fresh, FreshnessCause::Frames),
+            // The producer dropping its sender ends the walk.
+            (Freshness::Dead, FreshnessCause::Disconnect),
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn thresholds_default_from_expected_rate_and_override_per_game() -> Result<()> {
+    let defaults = FreshnessThresholds::from_expected_interval(Duration::from_millis(16));
+    assert_eq!(defaults.fresh_interval, Duration::from_millis(32));
+    assert_eq!(defaults.stale_after, Duration::from_millis(500));
+    assert_eq!(defaults.min_dwell, Duration::from_millis(250));
+    let slow = FreshnessThresholds::from_expected_interval(Duration::from_millis(100));
+    assert_eq!(slow.stale_after, Duration::from_millis(2_500));
+    assert_eq!(slow.min_dwell, Duration::from_secs(1));
+
+    let (tx, rx) = mpsc::channel(16);
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(
+        MockAdapter::new(GAME_ID)
+            .with_stream(rx)
+            .with_update_rate(INTERVAL),
+    ));
+    assert_eq!(service.freshness(GAME_ID), None);
+    // A game that legitimately sends every 100 ms despite a 10 ms nominal rate.
+    service.set_freshness_thresholds(
+        GAME_ID,
+        FreshnessThresholds {
+            fresh_interval: Duration::from_millis(250),
+            ..FreshnessThresholds::from_expected_interval(INTERVAL)
+        },
+    );
+    let mut forwarded = service.start_monitoring(GAME_ID).await?;
+    tokio::spawn(async move { while forwarded.recv().await.is_some() {} });
+
+    send_frames(&tx, 20, INTERVAL * 10).await?;
+    assert_eq!(service.freshness(GAME_ID), Some(Freshness::Fresh));
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! User-defined game id aliases resolved by the service.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use racing_wheel_telemetry_adapters::MockAdapter;
+use racing_wheel_telemetry_orchestrator::TelemetryService;
+use racing_wheel_telemetry_support::{
+    GAME_ID_ALIASES_FILE, GameIdAliasError, GameIdAliases, load_default_matrix,
+};
+
+const FRAME_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn service_with(aliases: GameIdAliases) -> Result<TelemetryService> {
+    let mut adapter =
+        MockAdapter::with_update_rate("forza_horizon_5".to_string(), Duration::from_millis(5));
+    adapter.set_running(true);
+    let mut service = TelemetryService::from_support_matrix(Some(load_default_matrix()?))
+        .with_game_aliases(aliases);
+    service.register_adapter(Box::new(adapter));
+    Ok(service)
+}
+
+#[tokio::test]
+async fn steam_app_id_alias_starts_monitoring() -> Result<()> {
+    let mut aliases = GameIdAliases::for_matrix(&load_default_matrix()?);
+    aliases.add_alias("1551360", "forza_horizon_5")?;
+    let mut service = service_with(aliases)?;
+
+    assert!(service.is_game_running("1551360").await?);
+    let mut frames = service.start_monitoring("1551360").await?;
+    let frame = tokio::time::timeout(FRAME_TIMEOUT, frames.recv()).await?;
+    assert!(frame.is_some());
+    assert!(service.is_monitoring("forza_horizon_5"));
+    assert!(service.health().contains_key("forza_horizon_5"));
+
+    service.stop_monitoring("1551360").await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn aliases_load_from_a_file_beside_the_matrix() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join(GAME_ID_ALIASES_FILE);
+    std::fs::write(&path, r#"{ "1551360": "forza_horizon_5" }"#)?;
+    let mut aliases = GameIdAliases::for_matrix(&load_default_matrix()?);
+    assert_eq!(aliases.merge_file(&path)?, 1);
+
+    let service = service_with(aliases)?;
+    assert_eq!(service.game_aliases().resolve("1551360"), "forza_horizon_5");
+    // Built-in renames keep resolving alongside user aliases.
+    assert_eq!(service.game_aliases().resolve("ea_wrc"), "eawrc");
+    assert!(service.is_game_matrix_supported("1551360"));
+    assert!(service.is_game_matrix_supported("f1_2025"));
+    Ok(())
+}
+
+#[test]
+fn aliases_shadowing_matrix_ids_are_rejected() -> Result<()> {
+    let mut aliases = GameIdAliases::for_matrix(&load_default_matrix()?);
+    let result = aliases.add_alias("forza_horizon_4", "forza_horizon_5");
+    assert!(matches!(
+        result,
+        Err(GameIdAliasError::CollidesWithGameId { .. })
+    ));
+
+    let service = service_with(aliases)?;
+    assert!(!service.is_game_matrix_supported("1551360"));
+    Ok(())
+}
@@ -0,0 +1,106 @@
+//! Game-clock time steps and their arrival-time fallback.
+
+use std::time::Duration;
+
+use racing_wheel_telemetry_adapters::{NormalizedTelemetry, TelemetryFrame};
+use racing_wheel_telemetry_orchestrator::{ClockSource, GameClock};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const MS: u64 = 1_000_000;
+
+fn frame(arrival_ms: u64, game_time_s: Option<f64>, game_tick: Option<u64>) -> TelemetryFrame {
+    let data = NormalizedTelemetry {
+        game_time_s,
+        game_tick,
+        ..Default::default()
+    };
+    TelemetryFrame::new(data, arrival_ms * MS, 0, 0)
+}
+
+#[test]
+fn game_clock_is_preferred_over_jittered_arrival() -> TestResult {
+    let mut clock = GameClock::default();
+    assert_eq!(clock.step(&frame(0, Some(5.0), None)), None);
+
+    // Sent 16 ms apart by the game, received 2 ms apart.
+    let step = clock
+        .step(&frame(2, Some(5.016), None))
+        .ok_or("second frame steps")?;
+    assert_eq!(step.source, ClockSource::Game);
+    assert!((step.dt.as_secs_f64() - 0.016).abs() < 1e-9);
+    let divergence = step.divergence_s.ok_or("both clocks present")?;
+    assert!((divergence - 0.014).abs() < 1e-9);
+    assert!(!clock.is_diverged(&step));
+    assert_eq!(clock.diagnostics().game_steps, 1);
+    Ok(())
+}
+
+#[test]
+fn missing_or_rewound_game_time_falls_back_to_arrival() -> TestResult {
+    let mut clock = GameClock::default();
+    clock.step(&frame(0, None, None));
+    let step = clock
+        .step(&frame(16, None, None))
+        .ok_or("second frame steps")?;
+    assert_eq!(step.source, ClockSource::Arrival);
+    assert_eq!(step.dt, Duration::from_millis(16));
+
+    // A flashback rewinds the session clock: one arrival step, then the new
+    // clock is followed again.
+    let mut clock = GameClock::default();
+    clock.step(&frame(0, Some(120.0), None));
+    let rewound = clock
+        .step(&frame(16, Some(95.0), None))
+        .ok_or("rewound frame steps")?;
+    assert_eq!(rewound.source, ClockSource::Arrival);
+    let resumed = clock
+        .step(&frame(32, Some(95.016), None))
+        .ok_or("resumed frame steps")?;
+    assert_eq!(resumed.source, ClockSource::Game);
+
+    let diagnostics = clock.diagnostics();
+    assert_eq!((diagnostics.game_steps, diagnostics.arrival_steps), (1, 1));
+    Ok(())
+}
+
+#[test]
+fn repeated_tick_is_a_zero_step() -> TestResult {
+    let mut clock = GameClock::default();
+    clock.step(&frame(0, None, Some(41)));
+    let duplicate = clock
+        .step(&frame(8, None, Some(41)))
+        .ok_or("duplicate frame steps")?;
+    assert_eq!(duplicate.dt, Duration::ZERO);
+    assert_eq!(duplicate.source, ClockSource::Game);
+
+    let next = clock
+        .step(&frame(16, None, Some(42)))
+        .ok_or("next frame steps")?;
+    assert_eq!(
+        next.source,
+        ClockSource::Arrival,
+        "a tick alone has no rate"
+    );
+    assert_eq!(next.dt, Duration::from_millis(8));
+    Ok(())
+}
+
+#[test]
+fn divergence_above_threshold_is_counted() -> TestResult {
+    let mut clock = GameClock::new(Duration::from_millis(100));
+    clock.step(&frame(0, Some(1.0), None));
+    let stalled = clock
+        .step(&frame(400, Some(1.01), None))
+        .ok_or("stalled frame steps")?;
+    assert!(clock.is_diverged(&stalled));
+
+    let diagnostics = clock.diagnostics();
+    assert_eq!(diagnostics.diverged_steps, 1);
+    assert!((diagnostics.max_divergence_s - 0.39).abs() < 1e-9);
+
+    clock.reset();
+    assert_eq!(clock.diagnostics().diverged_steps, 0);
+    assert_eq!(clock.step(&frame(500, Some(2.0), None)), None);
+    Ok(())
+}
@@ -0,0 +1,234 @@
+//! Per-adapter health reported by the telemetry service.
+
+mod common;
+
+use std::time::Duration;
+
+use anyhow::Result;
+use racing_wheel_telemetry_adapters::{
+    CompositeAdapter, FailoverConfig, NormalizedTelemetry, TelemetryFrame, telemetry_now_ns,
+};
+use racing_wheel_telemetry_core::{ConnectionState, DisconnectionConfig, correlate};
+use racing_wheel_telemetry_orchestrator::{HealthEvent, TelemetryService};
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+use common::{MockAdapter, START_ERROR, frame, health_of, next_event, register, register_broken};
+
+#[tokio::test]
+async fn unmonitored_adapter_reports_disconnected() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    let _tx = register(&mut service, "idle_source");
+
+    let health = health_of(&service, "idle_source")?;
+    assert_eq!(health.connection_state, ConnectionState::Disconnected);
+    assert_eq!(health.frames_received, 0);
+    assert_eq!(health.last_frame_timestamp_ns, None);
+    assert_eq!(health.last_error, None);
+    assert!((health.expected_update_rate_ms - 50.0).abs() < 1e-9);
+    Ok(())
+}
+
+#[tokio::test]
+async fn frames_are_counted_and_connect_the_game() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    let tx = register(&mut service, "live_source");
+    let mut frames = service.start_monitoring("live_source").await?;
+
+    for sequence in 0..3 {
+        tx.send(frame(sequence)).await?;
+        timeout(Duration::from_secs(1), frames.recv()).await?;
+    }
+
+    let health = health_of(&service, "live_source")?;
+    assert_eq!(health.connection_state, ConnectionState::Connected);
+    assert_eq!(health.frames_received, 3);
+    assert_eq!(health.frames_dropped_rate_limit, 0);
+    assert!(health.last_frame_timestamp_ns.is_some());
+    Ok(())
+}
+
+#[tokio::test]
+async fn rate_limited_frames_are_counted_as_dropped() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    let tx = register(&mut service, "flooded_source");
+    service.set_rate_limit("flooded_source", 1);
+    let mut frames = service.start_monitoring("flooded_source").await?;
+
+    for sequence in 0..40 {
+        tx.send(frame(sequence)).await?;
+    }
+    while timeout(Duration::from_millis(200), frames.recv())
+        .await
+        .ok()
+        .flatten()
+        .is_some()
+    {}
+
+    let health = health_of(&service, "flooded_source")?;
+    assert_eq!(health.frames_received, 40);
+    // The first 32 frames ride the burst bucket.
+    assert_eq!(health.frames_dropped_rate_limit, 8);
+    Ok(())
+}
+
+#[tokio::test]
+async fn silence_past_the_timeout_disconnects_with_a_reason() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    let tx = register(&mut service, "quiet_source");
+    service.set_disconnection_config(
+        "quiet_source",
+        DisconnectionConfig {
+            auto_reconnect: false,
+            ..DisconnectionConfig::with_timeout(100)
+        },
+    );
+    let mut events = service.subscribe_health_events();
+    let _frames = service.start_monitoring("quiet_source").await?;
+
+    let connecting = next_event(&mut events).await?;
+    assert_eq!(connecting.game_id, "quiet_source");
+    assert_eq!(connecting.new_state, ConnectionState::Connecting);
+
+    tx.send(frame(0)).await?;
+    let connected = next_event(&mut events).await?;
+    assert_eq!(connected.previous_state, ConnectionState::Connecting);
+    assert_eq!(connected.new_state, ConnectionState::Connected);
+
+    let disconnected = next_event(&mut events).await?;
+    assert!(disconnected.is_disconnection());
+    assert_eq!(
+        disconnected.reason.as_deref(),
+        Some("No data received for 100ms")
+    );
+    assert_eq!(
+        health_of(&service, "quiet_source")?.connection_state,
+        ConnectionState::Disconnected
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn adaptive_timeout_scales_with_the_frame_rate() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    let tx = register(&mut service, "steady_source");
+    service.set_disconnection_config(
+        "steady_source",
+        DisconnectionConfig {
+            auto_reconnect: false,
+            ..DisconnectionConfig::adaptive(3.0)
+        },
+    );
+    let mut events = service.subscribe_health_events();
+    let mut frames = service.start_monitoring("steady_source").await?;
+
+    for sequence in 0..6 {
+        tx.send(frame(sequence)).await?;
+        timeout(Duration::from_secs(1), frames.recv()).await?;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    let health = health_of(&service, "steady_source")?;
+    let Some(rate_hz) = health.observed_rate_hz else {
+        return Err(anyhow::anyhow!("no observed rate"));
+    };
+    assert!(rate_hz > 5.0 && rate_hz <= 20.0, "{rate_hz}");
+
+    let silenced = std::time::Instant::now();
+    let disconnected = loop {
+        let event = next_event(&mut events).await?;
+        if event.is_disconnection() {
+            break event;
+        }
+    };
+    // Three 50 ms frames, well short of the 2 s fixed default.
+    assert!(silenced.elapsed() < Duration::from_secs(1));
+    assert!(disconnected.reason.is_some());
+    Ok(())
+}
+
+#[tokio::test]
+async fn connection_events_share_the_frame_clock() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    let tx = register(&mut service, "clocked_source");
+    let mut events = service.subscribe_health_events();
+    let mut frames = service.start_monitoring("clocked_source").await?;
+    assert_eq!(
+        next_event(&mut events).await?.new_state,
+        ConnectionState::Connecting
+    );
+
+    let sent = TelemetryFrame::new(NormalizedTelemetry::default(), telemetry_now_ns(), 0, 0);
+    tx.send(sent.clone()).await?;
+    timeout(Duration::from_secs(1), frames.recv()).await?;
+    let connected = next_event(&mut events).await?;
+    assert!(connected.is_connection());
+    assert!(connected.timestamp_mono_ns >= sent.timestamp_ns);
+    assert!(correlate(&connected, &sent) < Duration::from_millis(500));
+
+    let health = health_of(&service, "clocked_source")?;
+    assert_eq!(
+        health.last_state_change_ns,
+        Some(connected.timestamp_mono_ns)
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn start_failure_is_kept_as_the_last_error() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    register_broken(&mut service, "blocked_source");
+    let mut events = service.subscribe_health_events();
+
+    assert!(service.start_monitoring("blocked_source").await.is_err());
+    let health = health_of(&service, "blocked_source")?;
+    assert_eq!(health.connection_state, ConnectionState::Error);
+    assert_eq!(health.last_error.as_deref(), Some(START_ERROR));
+
+    assert_eq!(
+        next_event(&mut events).await?.new_state,
+        ConnectionState::Connecting
+    );
+    let failed = next_event(&mut events).await?;
+    assert_eq!(failed.new_state, ConnectionState::Error);
+    assert_eq!(failed.reason.as_deref(), Some(START_ERROR));
+    Ok(())
+}
+
+#[tokio::test]
+async fn composite_transport_switches_reach_health_subscribers() -> Result<()> {
+    let (primary_tx, primary_rx) = mpsc::channel(8);
+    let (_fallback_tx, fallback_rx) = mpsc::channel(8);
+    let transport = |rx| Box::new(MockAdapter::new("dual_source").with_stream(rx));
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(
+        CompositeAdapter::new("dual_source")
+            .with_transport("shared_memory", transport(primary_rx))
+            .with_transport("udp", transport(fallback_rx))
+            .with_failover(FailoverConfig {
+                probe_interval: Duration::from_secs(60),
+                ..FailoverConfig::default()
+            }),
+    ));
+    let mut events = service.subscribe_health_events();
+    let mut frames = service.start_monitoring("dual_source").await?;
+
+    primary_tx.send(frame(0)).await?;
+    timeout(Duration::from_secs(1), frames.recv()).await?;
+    drop(primary_tx);
+
+    let switch = loop {
+        let event = timeout(Duration::from_secs(2), events.recv())
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("health events closed"))?;
+        if let HealthEvent::TransportSwitched(switch) = event {
+            break switch;
+        }
+    };
+    assert_eq!(switch.game_id, "dual_source");
+    assert_eq!(
+        (switch.from.as_str(), switch.to.as_str()),
+        ("shared_memory", "udp")
+    );
+    assert_eq!(switch.reason, "stream ended");
+    Ok(())
+}
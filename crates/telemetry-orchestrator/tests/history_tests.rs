@@ -0,0 +1,91 @@
+//! Forwarded frames feed the service's downsampled history.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use racing_wheel_telemetry_adapters::{
+    NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver, telemetry_now_ns,
+};
+use racing_wheel_telemetry_core::{HistoryConfig, HistoryField, HistoryResolution};
+use racing_wheel_telemetry_orchestrator::TelemetryService;
+use tokio::sync::mpsc;
+
+const GAME_ID: &str = "history_source";
+
+struct ChannelAdapter {
+    rx: Mutex<Option<TelemetryReceiver>>,
+}
+
+#[async_trait]
+impl TelemetryAdapter for ChannelAdapter {
+    fn game_id(&self) -> &str {
+        GAME_ID
+    }
+
+    async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        self.rx
+            .lock()
+            .map_err(|_| anyhow::anyhow!("poisoned"))?
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("already monitoring"))
+    }
+
+    async fn stop_monitoring(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn normalize(&self, _raw: &[u8]) -> Result<NormalizedTelemetry> {
+        Ok(NormalizedTelemetry::default())
+    }
+
+    fn expected_update_rate(&self) -> Duration {
+        Duration::from_millis(1)
+    }
+
+    async fn is_game_running(&self) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+#[tokio::test]
+async fn forwarded_frames_are_downsampled_per_game() -> Result<()> {
+    let resolution = Duration::from_secs(1);
+    let mut service = TelemetryService::new().with_history(HistoryConfig {
+        resolutions: vec![HistoryResolution::new(resolution, Duration::from_secs(60))],
+        fields: vec![HistoryField::Rpm],
+        max_games: 2,
+    });
+    let (tx, source) = mpsc::channel(16);
+    service.register_adapter(Box::new(ChannelAdapter {
+        rx: Mutex::new(Some(source)),
+    }));
+    let mut forwarded = service.start_monitoring(GAME_ID).await?;
+
+    for (sequence, rpm) in [(0, 3000.0), (1, 5000.0), (3, 4000.0)] {
+        let data = NormalizedTelemetry::builder().rpm(rpm).build();
+        tx.send(TelemetryFrame::new(data, telemetry_now_ns(), sequence, 0))
+            .await?;
+        forwarded
+            .recv()
+            .await
+            .ok_or_else(|| anyhow::anyhow!("forwarding stopped"))?;
+    }
+
+    let buckets = service.history(
+        GAME_ID,
+        HistoryField::Rpm,
+        resolution,
+        Duration::from_secs(60),
+    );
+    assert_eq!(buckets.iter().map(|b| b.samples).sum::<u32>(), 3);
+    assert_eq!(buckets.iter().map(|b| b.drops).sum::<u32>(), 1);
+    let max = buckets.iter().map(|b| b.max).fold(f32::MIN, f32::max);
+    assert_eq!(max, 5000.0);
+
+    let summary = service.history_summary();
+    assert_eq!(summary.games.len(), 1);
+    assert_eq!(summary.games[0].game_id, GAME_ID);
+    Ok(())
+}
@@ -0,0 +1,225 @@
+//! The port inspector classifies loopback traffic from several games.
+
+use std::net::{Ipv4Addr, SocketAddr};
+use std::time::Duration;
+
+use anyhow::Result;
+use racing_wheel_telemetry_adapters::f1_native::{
+    PACKET_FORMAT_2024, build_car_telemetry_packet_native,
+};
+use racing_wheel_telemetry_adapters::{
+    BeamNGAdapter, DirtRally2Adapter, F1_25Adapter, ForzaAdapter, TelemetryAdapter,
+};
+use racing_wheel_telemetry_orchestrator::{InspectError, InspectionReport, TelemetryService};
+use tokio::net::UdpSocket;
+use tokio::time::{Instant, sleep};
+
+const WINDOW: Duration = Duration::from_millis(300);
+const SEND_INTERVAL: Duration = Duration::from_millis(5);
+
+type AdapterOnPort = fn(u16) -> Box<dyn TelemetryAdapter>;
+
+fn free_port() -> Result<u16> {
+    let probe = std::net::UdpSocket::bind((Ipv4Addr::LOCALHOST, 0))?;
+    Ok(probe.local_addr()?.port())
+}
+
+/// Inspect `port` while `packets` are sent to it round-robin.
+async fn inspect_with_traffic(
+    service: &TelemetryService,
+    port: u16,
+    packets: &[Vec<u8>],
+) -> Result<InspectionReport> {
+    let sender = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+    let target = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    let send = async {
+        let until = Instant::now() + WINDOW;
+        for packet in packets.iter().cycle() {
+            if Instant::now() >= until {
+                break;
+            }
+            sender.send_to(packet, target).await?;
+            sleep(SEND_INTERVAL).await;
+        }
+        Ok::<_, std::io::Error>(())
+    };
+    let (report, sent) = tokio::join!(service.inspect_port(port, WINDOW), send);
+    sent?;
+    Ok(report?)
+}
+
+fn f1_2024_packets() -> Vec<Vec<u8>> {
+    (0..8)
+        .map(|i| {
+            build_car_telemetry_packet_native(
+                PACKET_FORMAT_2024,
+                0,
+                200 + i,
+                6,
+                11_000,
+                1.0,
+                0.0,
+                0.1,
+                0,
+                [23.5; 4],
+            )
+        })
+        .collect()
+}
+
+fn forza_cardash_packets() -> Vec<Vec<u8>> {
+    (0..8u8)
+        .map(|i| {
+            let mut buf = vec![0u8; 311];
+            buf[0..4].copy_from_slice(&1i32.to_le_bytes());
+            buf[16..20].copy_from_slice(&(6000.0f32 + f32::from(i)).to_le_bytes());
+            buf[244..248].copy_from_slice(&40.0f32.to_le_bytes());
+            buf[307] = 3;
+            buf
+        })
+        .collect()
+}
+
+fn codemasters_mode1_packets() -> Vec<Vec<u8>> {
+    (0..8u8)
+        .map(|i| {
+            let mut buf = vec![0u8; 264];
+            buf[132..136].copy_from_slice(&3.0f32.to_le_bytes()); // gear
+            buf[148..152].copy_from_slice(&(5000.0f32 + f32::from(i)).to_le_bytes()); // rpm
+            buf
+        })
+        .collect()
+}
+
+fn outgauge_packets() -> Vec<Vec<u8>> {
+    (0..8u8)
+        .map(|i| {
+            let mut buf = vec![0u8; 92];
+            buf[4..8].copy_from_slice(b"beam");
+            buf[10] = 3;
+            buf[12..16].copy_from_slice(&(20.0f32 + f32::from(i)).to_le_bytes());
+            buf
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn wrong_adapter_is_explained_with_the_recognized_format() -> Result<()> {
+    let port = free_port()?;
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(F1_25Adapter::new().with_port(port)));
+
+    let report = inspect_with_traffic(&service, port, &f1_2024_packets()).await?;
+    let top = report
+        .top()
+        .ok_or_else(|| anyhow::anyhow!("nothing recognized: {report:?}"))?;
+    assert_eq!(top.game_id, "f1_native");
+    assert_eq!(top.format, "F1 2024 format");
+    assert_eq!(report.selected, ["f1_25"]);
+    assert!(
+        report.candidates.iter().all(|c| c.game_id != "f1_25"),
+        "the F1 25 adapter must reject 2024-format headers"
+    );
+    assert_eq!(
+        report.explanation,
+        "95% match: F1 2024 format (f1_native) — you selected f1_25"
+    );
+    assert!(report.packets_received >= report.distinct_packets as u64);
+    assert_eq!(report.distinct_packets, 8);
+    assert!(report.packets_per_second > 0.0);
+    assert!(
+        report
+            .sources
+            .iter()
+            .all(|source| source.ip().is_loopback())
+    );
+    assert_eq!(report.packet_sizes.len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn fixtures_from_several_games_classify_to_their_adapter() -> Result<()> {
+    let cases: [(&str, AdapterOnPort, Vec<Vec<u8>>, &str); 3] = [
+        (
+            "forza_motorsport",
+            |port| Box::new(ForzaAdapter::new().with_port(port)),
+            forza_cardash_packets(),
+            "Forza CarDash format",
+        ),
+        (
+            "dirt_rally_2",
+            |port| Box::new(DirtRally2Adapter::new().with_port(port)),
+            codemasters_mode1_packets(),
+            "Codemasters Mode 1 format",
+        ),
+        (
+            "beamng_drive",
+            |port| Box::new(BeamNGAdapter::new().with_port(port)),
+            outgauge_packets(),
+            "OutGauge format",
+        ),
+    ];
+    for (game_id, adapter, packets, format) in cases {
+        let port = free_port()?;
+        let mut service = TelemetryService::from_support_matrix(None);
+        service.register_adapter(adapter(port));
+
+        let report = inspect_with_traffic(&service, port, &packets).await?;
+        let top = report
+            .top()
+            .ok_or_else(|| anyhow::anyhow!("{game_id}: nothing recognized"))?;
+        assert_eq!(
+            (top.game_id.as_str(), top.format.as_str()),
+            (game_id, format)
+        );
+        assert!(
+            report
+                .explanation
+                .ends_with(&format!("matches the selected {game_id}")),
+            "{}",
+            report.explanation
+        );
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn silent_port_report_is_kept_for_diagnostics() -> Result<()> {
+    let port = free_port()?;
+    let service = TelemetryService::from_support_matrix(None);
+    let report = service
+        .inspect_port(port, Duration::from_millis(50))
+        .await?;
+    assert_eq!(report.packets_received, 0);
+    assert!(report.candidates.is_empty() && report.selected.is_empty());
+    assert!(
+        report
+            .explanation
+            .starts_with(&format!("No packets arrived on UDP port {port}")),
+        "{}",
+        report.explanation
+    );
+
+    let json = serde_json::to_string(&report)?;
+    assert_eq!(serde_json::from_str::<InspectionReport>(&json)?, report);
+    assert_eq!(service.inspection_reports(), [report]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn unicast_monitor_on_the_port_is_not_displaced() -> Result<()> {
+    let port = free_port()?;
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(F1_25Adapter::new().with_port(port)));
+    let _forwarded = service.start_monitoring("f1_25").await?;
+
+    let result = service.inspect_port(port, Duration::from_millis(50)).await;
+    assert_eq!(
+        result,
+        Err(InspectError::PortBusy {
+            port,
+            game_id: "f1_25".to_string(),
+        })
+    );
+    Ok(())
+}
@@ -0,0 +1,156 @@
+//! Pipeline latency measured per stage and end to end.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use racing_wheel_telemetry_adapters::{MockAdapter, TelemetryFrame};
+use racing_wheel_telemetry_orchestrator::{
+    FrameTransform, LATENCY_RING_CAPACITY, LatencyProbe, LatencyStage, TelemetryService,
+};
+use tokio::time::timeout;
+
+const MS: u64 = 1_000_000;
+const GAME_ID: &str = "mock_latency";
+const TRANSFORM_DELAY: Duration = Duration::from_millis(4);
+const FRAMES: usize = 20;
+
+#[test]
+fn synthetic_stage_delays_show_up_in_the_percentiles() -> Result<()> {
+    let probe = LatencyProbe::new();
+    // Every seventh sequence number, so the ring sees gaps.
+    for (n, sequence) in (0..100u64).map(|n| (n, n * 7)) {
+        let received = 1_000 * MS + n * 50 * MS;
+        // One frame in ten waits an extra 20 ms in the transforms.
+        let processing = if n % 10 == 9 { 25 * MS } else { 5 * MS };
+        probe.begin(sequence, received);
+        probe.stamp_at(sequence, LatencyStage::RateLimiter, received + MS);
+        probe.stamp_at(
+            sequence,
+            LatencyStage::Processors,
+            received + MS + processing,
+        );
+        probe.stamp_at(
+            sequence,
+            LatencyStage::Forwarder,
+            received + 3 * MS + processing,
+        );
+    }
+
+    let report = probe.report();
+    let rate_limiter = report
+        .stage(LatencyStage::RateLimiter)
+        .ok_or_else(|| anyhow::anyhow!("no rate limiter samples"))?;
+    assert_eq!(rate_limiter.samples, 100);
+    assert_eq!(rate_limiter.max_ns, MS);
+
+    let processors = report
+        .stage(LatencyStage::Processors)
+        .ok_or_else(|| anyhow::anyhow!("no processor samples"))?;
+    assert_eq!(processors.p50_ns, 5 * MS);
+    assert_eq!(processors.p95_ns, 25 * MS);
+    assert_eq!(processors.p99_ns, 25 * MS);
+
+    // Unstamped recorder stage: the forwarder measures from the transforms.
+    assert!(report.stage(LatencyStage::Recorder).is_none());
+    let forwarder = report
+        .stage(LatencyStage::Forwarder)
+        .ok_or_else(|| anyhow::anyhow!("no forwarder samples"))?;
+    assert_eq!(forwarder.max_ns, 2 * MS);
+
+    let end_to_end = report
+        .end_to_end
+        .ok_or_else(|| anyhow::anyhow!("no end-to-end samples"))?;
+    assert_eq!(end_to_end.p50_ns, 8 * MS);
+    assert_eq!(end_to_end.p99_ns, 28 * MS);
+    assert_eq!(end_to_end.max_ns, 28 * MS);
+    Ok(())
+}
+
+#[test]
+fn stamps_for_overwritten_or_unknown_frames_are_ignored() -> Result<()> {
+    let probe = LatencyProbe::new();
+    let lapped = 3;
+    probe.begin(lapped, 0);
+    // Shares the lapped frame's slot.
+    probe.begin(lapped + LATENCY_RING_CAPACITY as u64, 10 * MS);
+    probe.stamp_at(lapped, LatencyStage::Forwarder, 500 * MS);
+    probe.stamp_at(99, LatencyStage::RateLimiter, 500 * MS);
+    assert!(probe.report().stages.is_empty());
+
+    probe.stamp_at(
+        lapped + LATENCY_RING_CAPACITY as u64,
+        LatencyStage::Forwarder,
+        12 * MS,
+    );
+    // A frame ends at the forwarder; later stamps are ignored.
+    probe.stamp_at(
+        lapped + LATENCY_RING_CAPACITY as u64,
+        LatencyStage::Forwarder,
+        90 * MS,
+    );
+    let end_to_end = probe
+        .report()
+        .end_to_end
+        .ok_or_else(|| anyhow::anyhow!("no end-to-end samples"))?;
+    assert_eq!((end_to_end.samples, end_to_end.max_ns), (1, 2 * MS));
+    Ok(())
+}
+
+/// Holds every frame up in the transform stage.
+struct SlowTransform;
+
+impl FrameTransform for SlowTransform {
+    fn name(&self) -> &str {
+        "slow"
+    }
+
+    fn apply(&mut self, _frame: &mut TelemetryFrame) {
+        std::thread::sleep(TRANSFORM_DELAY);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn slow_transform_shows_up_in_the_service_latency_report() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(MockAdapter::new(GAME_ID.to_string())));
+    service.register_transform(GAME_ID, Box::new(SlowTransform));
+    assert_eq!(service.latency_report(GAME_ID), None);
+
+    let mut rx = service.start_monitoring(GAME_ID).await?;
+    for _ in 0..FRAMES {
+        timeout(Duration::from_secs(2), rx.recv())
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("stream ended early"))?;
+    }
+
+    let report = service
+        .latency_report(GAME_ID)
+        .ok_or_else(|| anyhow::anyhow!("no latency report"))?;
+    let processors = report
+        .stage(LatencyStage::Processors)
+        .ok_or_else(|| anyhow::anyhow!("no processor samples"))?;
+    let delay_ns = TRANSFORM_DELAY.as_nanos() as u64;
+    assert!(processors.samples >= FRAMES - 1, "{report:?}");
+    assert!(processors.p50_ns >= delay_ns, "{report:?}");
+    // Scheduler tolerance for a loaded test machine.
+    assert!(processors.p50_ns < delay_ns + 50 * MS, "{report:?}");
+    let end_to_end = report
+        .end_to_end
+        .ok_or_else(|| anyhow::anyhow!("no end-to-end samples"))?;
+    assert!(end_to_end.p50_ns >= processors.p50_ns, "{report:?}");
+
+    let health = service
+        .health()
+        .remove(GAME_ID)
+        .ok_or_else(|| anyhow::anyhow!("no health for {GAME_ID}"))?;
+    let p99_ms = health
+        .latency_p99_ms
+        .ok_or_else(|| anyhow::anyhow!("no latency in health"))?;
+    assert!(
+        p99_ms >= TRANSFORM_DELAY.as_secs_f64() * 1000.0,
+        "{health:?}"
+    );
+
+    service.stop_monitoring(GAME_ID).await?;
+    Ok(())
+}
@@ -0,0 +1,129 @@
+//! Reloading the game support matrix into a running service.
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use racing_wheel_telemetry_adapters::TelemetryFrame;
+use racing_wheel_telemetry_orchestrator::{
+    MatrixReloadReport, TelemetryService, watch_matrix_file,
+};
+use racing_wheel_telemetry_support::{
+    GameSupportMatrix, TELEMETRY_SUPPORT_MATRIX_YAML, load_default_matrix,
+};
+use tokio::sync::mpsc;
+
+use common::MockAdapter;
+
+/// The shipped matrix cut down to `game_ids`.
+fn matrix_of(game_ids: &[&str]) -> Result<GameSupportMatrix> {
+    let mut matrix = load_default_matrix()?;
+    matrix
+        .games
+        .retain(|game_id, _| game_ids.contains(&game_id.as_str()));
+    Ok(matrix)
+}
+
+#[test]
+fn reload_registers_games_the_matrix_gained() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(Some(matrix_of(&["acc"])?));
+    assert_eq!(service.adapter_ids(), ["acc"]);
+
+    let report = service.reload_support_matrix(matrix_of(&["acc", "iracing"])?)?;
+    assert_eq!(
+        report,
+        MatrixReloadReport {
+            added: vec!["iracing".to_string()],
+            ..MatrixReloadReport::default()
+        }
+    );
+    assert_eq!(service.adapter_ids(), ["acc", "iracing"]);
+    let coverage = service
+        .runtime_coverage_report()
+        .ok_or_else(|| anyhow::anyhow!("reload should recompute coverage"))?;
+    assert_eq!(coverage.matrix_game_ids, ["acc", "iracing"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn removing_a_monitored_game_is_deferred_until_it_stops() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(Some(matrix_of(&["acc", "iracing"])?));
+    let (tx, rx) = mpsc::channel::<TelemetryFrame>(8);
+    service.register_adapter(Box::new(MockAdapter::new("acc").with_stream(rx)));
+    let _frames = service.start_monitoring("acc").await?;
+    assert!(service.is_monitoring("acc"));
+
+    let report = service.reload_support_matrix(matrix_of(&[])?)?;
+    assert_eq!(report.removed, ["iracing"]);
+    assert_eq!(report.deferred, ["acc"]);
+    assert_eq!(service.adapter_ids(), ["acc"]);
+
+    // Closing the source ends monitoring.
+    drop(tx);
+    tokio::time::timeout(Duration::from_secs(1), async {
+        while service.is_monitoring("acc") {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await?;
+
+    let report = service.reload_support_matrix(matrix_of(&[])?)?;
+    assert_eq!(report.removed, ["acc"]);
+    assert!(report.deferred.is_empty());
+    assert!(service.adapter_ids().is_empty());
+    Ok(())
+}
+
+#[test]
+fn reloading_the_same_matrix_changes_nothing() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(Some(matrix_of(&["acc", "iracing"])?));
+    let report = service.reload_support_matrix(matrix_of(&["acc", "iracing"])?)?;
+    assert!(report.is_unchanged());
+    assert_eq!(report, MatrixReloadReport::default());
+    assert_eq!(service.adapter_ids(), ["acc", "iracing"]);
+    Ok(())
+}
+
+#[test]
+fn matrix_with_a_legacy_game_id_is_rejected() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(Some(matrix_of(&["acc"])?));
+    let mut matrix = matrix_of(&["eawrc"])?;
+    let support = matrix
+        .games
+        .remove("eawrc")
+        .ok_or_else(|| anyhow::anyhow!("eawrc missing from the shipped matrix"))?;
+    matrix.games.insert("ea_wrc".to_string(), support);
+
+    assert!(service.reload_support_matrix(matrix).is_err());
+    assert_eq!(service.adapter_ids(), ["acc"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn watcher_applies_the_matrix_file() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("game_support_matrix.yaml");
+    std::fs::write(&path, "not: [a matrix")?;
+    let service = Arc::new(tokio::sync::Mutex::new(
+        TelemetryService::from_support_matrix(Some(matrix_of(&["acc"])?)),
+    ));
+    let watcher = watch_matrix_file(Arc::clone(&service), &path, Duration::from_millis(10));
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(service.lock().await.adapter_ids(), ["acc"]);
+
+    std::fs::write(&path, TELEMETRY_SUPPORT_MATRIX_YAML)?;
+    let expected = load_default_matrix()?.game_ids().len();
+    let applied = tokio::time::timeout(Duration::from_secs(2), async {
+        while service.lock().await.support_matrix().map(|m| m.games.len()) != Some(expected) {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await;
+    watcher.abort();
+    applied?;
+    assert!(service.lock().await.adapter_ids().len() > 1);
+    Ok(())
+}
@@ -0,0 +1,171 @@
+//! Service metrics snapshots and their Prometheus rendering.
+
+mod common;
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use anyhow::Result;
+use racing_wheel_telemetry_adapters::{NormalizedTelemetry, TelemetryFrame, TrafficStats};
+use racing_wheel_telemetry_orchestrator::{
+    CoverageMetrics, GameMetrics, RegistryMetrics, ServiceMetrics, TelemetryService,
+    render_prometheus,
+};
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+use common::MockAdapter;
+
+const GOLDEN: &str = include_str!("fixtures/service_metrics.prom.golden");
+
+fn registry(count: usize, missing: usize, bdd_parity_ok: bool) -> RegistryMetrics {
+    RegistryMetrics {
+        registry_game_count: count,
+        missing_count: missing,
+        extra_count: 0,
+        matrix_coverage_ratio: (count - missing) as f64 / count as f64,
+        registry_coverage_ratio: 1.0,
+        bdd_parity_ok,
+    }
+}
+
+fn sample_metrics() -> ServiceMetrics {
+    let games = BTreeMap::from([
+        (
+            "acc".to_string(),
+            GameMetrics {
+                frames_received_total: 12_345,
+                frames_dropped_total: 17,
+                reconnect_attempts_total: 2,
+                last_frame_age_ms: Some(4.5),
+                traffic: Some(TrafficStats {
+                    total_packets: 12_400,
+                    total_bytes: 3_856_400,
+                    ..TrafficStats::default()
+                }),
+            },
+        ),
+        ("idle".to_string(), GameMetrics::default()),
+        (
+            "mod\\\"quoted\"\nline".to_string(),
+            GameMetrics {
+                frames_received_total: 1,
+                frames_dropped_total: 0,
+                reconnect_attempts_total: 0,
+                last_frame_age_ms: Some(f64::INFINITY),
+                traffic: None,
+            },
+        ),
+    ]);
+    ServiceMetrics {
+        games,
+        coverage: Some(CoverageMetrics {
+            matrix_game_count: 4,
+            adapter: registry(4, 0, true),
+            writer: registry(4, 1, false),
+            parity_ok: false,
+        }),
+    }
+}
+
+#[test]
+fn rendering_matches_the_golden_file() {
+    assert_eq!(render_prometheus(&sample_metrics()), GOLDEN);
+}
+
+#[test]
+fn every_sample_follows_a_type_line() {
+    let rendered = render_prometheus(&sample_metrics());
+    let mut typed = Vec::new();
+    for line in rendered.lines() {
+        if let Some(declaration) = line.strip_prefix("# TYPE ") {
+            let mut parts = declaration.split(' ');
+            typed.extend(parts.next());
+            assert!(matches!(parts.next(), Some("counter" | "gauge")), "{line}");
+        } else if !line.starts_with("# HELP ") {
+            let name = line.split(['{', ' ']).next().unwrap_or_default();
+            assert!(typed.contains(&name), "{line}");
+        }
+    }
+}
+
+#[test]
+fn snapshot_without_a_matrix_has_no_coverage() {
+    let service = TelemetryService::from_support_matrix(None);
+    let metrics = service.metrics_snapshot();
+    assert!(metrics.coverage.is_none());
+    assert!(!render_prometheus(&metrics).contains("parity_ok"));
+
+    let service = TelemetryService::new();
+    let coverage = service.metrics_snapshot().coverage;
+    assert!(coverage.is_some_and(|c| c.matrix_game_count > 0));
+}
+
+#[tokio::test]
+async fn counters_never_decrease_across_snapshots_and_restarts() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    let (first_tx, first_rx) = mpsc::channel(64);
+    let (second_tx, second_rx) = mpsc::channel(64);
+    service.register_adapter(Box::new(
+        MockAdapter::new("counted_source")
+            .with_starts([Some(first_rx), Some(second_rx)])
+            .with_update_rate(Duration::from_millis(10)),
+    ));
+    service.set_rate_limit("counted_source", 1);
+
+    let mut frames = service.start_monitoring("counted_source").await?;
+    for sequence in 0..40 {
+        first_tx
+            .send(TelemetryFrame::new(
+                NormalizedTelemetry::default(),
+                0,
+                sequence,
+                0,
+            ))
+            .await?;
+    }
+    drop(first_tx);
+    while timeout(Duration::from_secs(1), frames.recv())
+        .await?
+        .is_some()
+    {}
+    let first = service.metrics_snapshot();
+    let again = service.metrics_snapshot();
+
+    // A restart gives the game a fresh rate limiter.
+    let mut frames = service.start_monitoring("counted_source").await?;
+    for sequence in 0..40 {
+        second_tx
+            .send(TelemetryFrame::new(
+                NormalizedTelemetry::default(),
+                0,
+                sequence,
+                0,
+            ))
+            .await?;
+    }
+    drop(second_tx);
+    while timeout(Duration::from_secs(1), frames.recv())
+        .await?
+        .is_some()
+    {}
+    let second = service.metrics_snapshot();
+
+    let game = |metrics: &ServiceMetrics| {
+        metrics
+            .games
+            .get("counted_source")
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("no metrics for counted_source"))
+    };
+    let (first, again, second) = (game(&first)?, game(&again)?, game(&second)?);
+    assert_eq!(first.frames_received_total, 40);
+    assert!(first.frames_dropped_total > 0);
+    assert_eq!(again.frames_received_total, first.frames_received_total);
+    assert_eq!(again.frames_dropped_total, first.frames_dropped_total);
+    assert_eq!(second.frames_received_total, 80);
+    assert_eq!(second.frames_dropped_total, 2 * first.frames_dropped_total);
+    assert!(second.reconnect_attempts_total >= first.reconnect_attempts_total);
+    assert!(second.last_frame_age_ms.is_some());
+    Ok(())
+}
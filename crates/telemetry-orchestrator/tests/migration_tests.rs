@@ -0,0 +1,244 @@
+//! Offline game-id migration of a temporary data directory.
+
+use std::fs;
+use std::path::Path;
+
+use racing_wheel_telemetry_adapters::{NormalizedTelemetry, TelemetryFrame};
+use racing_wheel_telemetry_orchestrator::{
+    MigrationMode, RewriteKind, TelemetryService, migrate_persisted_data,
+};
+use racing_wheel_telemetry_recorder::{RecordingMetadata, TelemetryRecording};
+use racing_wheel_telemetry_support::{GAME_ID_MIGRATIONS, normalize_game_id};
+use serde_json::{Value, json};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+/// Relative path and contents of each artifact.
+type Snapshot = Vec<(String, Vec<u8>)>;
+
+const PROFILE: &str = "profiles/rally.json";
+const SERVICE_STATE: &str = "configured_games.json";
+const REDLINE_OVERLAY: &str = "overlays/redlines.json";
+const AUDIT_LOG: &str = "audit/session.jsonl";
+const JSON_RECORDING: &str = "recordings/stage.json";
+const BINARY_RECORDING: &str = "recordings/stage.bin";
+const RAW_CAPTURE: &str = "captures/raw.pcap";
+
+fn recording(game_id: &str) -> TelemetryRecording {
+    TelemetryRecording {
+        metadata: RecordingMetadata {
+            game_id: game_id.to_string(),
+            timestamp: 1_700_000_000,
+            duration_seconds: 0.02,
+            frame_count: 2,
+            average_fps: 100.0,
+            car_id: Some("gr_yaris".to_string()),
+            track_id: None,
+            description: None,
+        },
+        frames: (0..2)
+            .map(|sequence| TelemetryFrame::new(NormalizedTelemetry::default(), 0, sequence, 0))
+            .collect(),
+    }
+}
+
+fn write(root: &Path, relative: &str, contents: impl AsRef<[u8]>) -> TestResult {
+    let path = root.join(relative);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+/// One artifact of every kind, all still using retired ids.
+fn populate(root: &Path) -> TestResult {
+    write(
+        root,
+        PROFILE,
+        serde_json::to_vec_pretty(&json!({
+            "schema": "wheel.profile/1",
+            "scope": { "game": "ea_wrc", "car": null, "track": null },
+            "base": { "ffbGain": 0.7 }
+        }))?,
+    )?;
+    write(
+        root,
+        SERVICE_STATE,
+        serde_json::to_vec(&json!({ "configured": ["EA-WRC", "iracing"] }))?,
+    )?;
+    write(
+        root,
+        REDLINE_OVERLAY,
+        serde_json::to_vec_pretty(&json!([
+            { "game_id": "f1_2025", "car_id": "mclaren", "redline_rpm": 12_500 },
+            { "game_id": "acc", "car_id": "porsche_992", "redline_rpm": 9_250 }
+        ]))?,
+    )?;
+    write(
+        root,
+        AUDIT_LOG,
+        "{\"event\":\"configured\",\"game_id\":\"ea wrc\"}\n\
+         -- log rotated --\n\
+         {\"event\":\"started\",\"game_id\":\"f1-2025\"}\n",
+    )?;
+    write(
+        root,
+        JSON_RECORDING,
+        serde_json::to_vec_pretty(&recording("f1_2025"))?,
+    )?;
+    write(root, BINARY_RECORDING, recording("ea_wrc").to_binary()?)?;
+    write(root, RAW_CAPTURE, [0xd4, 0xc3, 0xb2, 0xa1, 0x00, 0xff])?;
+    Ok(())
+}
+
+fn snapshot(root: &Path) -> Result<Snapshot, Box<dyn std::error::Error>> {
+    let mut files = Vec::new();
+    for relative in [
+        PROFILE,
+        SERVICE_STATE,
+        REDLINE_OVERLAY,
+        AUDIT_LOG,
+        JSON_RECORDING,
+        BINARY_RECORDING,
+        RAW_CAPTURE,
+    ] {
+        files.push((relative.to_string(), fs::read(root.join(relative))?));
+    }
+    Ok(files)
+}
+
+fn read_json(path: &Path) -> Result<Value, Box<dyn std::error::Error>> {
+    Ok(serde_json::from_slice(&fs::read(path)?)?)
+}
+
+#[test]
+fn every_artifact_type_is_migrated() -> TestResult {
+    let data = tempfile::tempdir()?;
+    let root = data.path();
+    populate(root)?;
+    let original_binary = fs::read(root.join(BINARY_RECORDING))?;
+
+    let report = migrate_persisted_data(root, GAME_ID_MIGRATIONS, MigrationMode::Apply)?;
+
+    assert_eq!(report.files_scanned, 7);
+    assert_eq!(report.migrated.len(), 6);
+    assert_eq!(report.ids_rewritten(), 7);
+    assert_eq!(report.unrecognized.len(), 1);
+    assert!(report.unrecognized[0].path.ends_with(RAW_CAPTURE));
+    for file in &report.migrated {
+        let backup = file.backup.as_ref().ok_or("missing backup")?;
+        assert!(backup.exists(), "{}", backup.display());
+    }
+
+    let profile = read_json(&root.join(PROFILE))?;
+    assert_eq!(profile["scope"]["game"], "eawrc");
+    let state = read_json(&root.join(SERVICE_STATE))?;
+    assert_eq!(state["configured"], json!(["eawrc", "iracing"]));
+    let overlay = read_json(&root.join(REDLINE_OVERLAY))?;
+    assert_eq!(overlay[0]["game_id"], "f1_25");
+    assert_eq!(overlay[1]["game_id"], "acc");
+
+    let audit = fs::read_to_string(root.join(AUDIT_LOG))?;
+    let lines: Vec<&str> = audit.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[1], "-- log rotated --");
+    assert!(lines[0].contains("\"eawrc\"") && lines[2].contains("\"f1_25\""));
+
+    let json_recording: TelemetryRecording =
+        serde_json::from_slice(&fs::read(root.join(JSON_RECORDING))?)?;
+    assert_eq!(json_recording.metadata.game_id, "f1_25");
+    assert_eq!(json_recording.frames.len(), 2);
+
+    let binary = fs::read(root.join(BINARY_RECORDING))?;
+    let migrated = TelemetryRecording::from_binary(&binary)?;
+    assert_eq!(migrated.metadata.game_id, "eawrc");
+    assert_eq!(migrated.metadata.car_id.as_deref(), Some("gr_yaris"));
+    // Only the header changed: the frame payload is byte-identical.
+    let old_frames = original_binary.len() - header_len(&original_binary)?;
+    let new_frames = binary.len() - header_len(&binary)?;
+    assert_eq!(
+        original_binary.get(original_binary.len() - old_frames..),
+        binary.get(binary.len() - new_frames..)
+    );
+    let binary_entry = report
+        .migrated
+        .iter()
+        .find(|file| file.path.ends_with(BINARY_RECORDING))
+        .ok_or("binary recording not reported")?;
+    assert_eq!(binary_entry.kind, RewriteKind::RecordingHeader);
+    assert_eq!(binary_entry.rewrites.get("ea_wrc -> eawrc"), Some(&1));
+    Ok(())
+}
+
+fn header_len(bytes: &[u8]) -> Result<usize, Box<dyn std::error::Error>> {
+    let len = bytes.first_chunk::<4>().ok_or("short recording")?;
+    Ok(4 + usize::try_from(u32::from_le_bytes(*len))?)
+}
+
+#[test]
+fn dry_run_reports_without_touching_files() -> TestResult {
+    let data = tempfile::tempdir()?;
+    let root = data.path();
+    populate(root)?;
+    let before = snapshot(root)?;
+
+    let report = migrate_persisted_data(root, GAME_ID_MIGRATIONS, MigrationMode::DryRun)?;
+
+    assert_eq!(report.migrated.len(), 6);
+    assert_eq!(report.ids_rewritten(), 7);
+    assert!(report.migrated.iter().all(|file| file.backup.is_none()));
+    assert_eq!(snapshot(root)?, before);
+    let entries = fs::read_dir(root.join("recordings"))?.count();
+    assert_eq!(entries, 2, "no backups are written in a dry run");
+    Ok(())
+}
+
+#[test]
+fn second_run_is_a_noop() -> TestResult {
+    let data = tempfile::tempdir()?;
+    let root = data.path();
+    populate(root)?;
+    migrate_persisted_data(root, GAME_ID_MIGRATIONS, MigrationMode::Apply)?;
+    let after_first = snapshot(root)?;
+
+    let report = migrate_persisted_data(root, GAME_ID_MIGRATIONS, MigrationMode::Apply)?;
+
+    assert!(report.is_noop());
+    assert_eq!(report.ids_rewritten(), 0);
+    // Backups from the first run are neither rescanned nor migrated.
+    assert_eq!(report.files_scanned, 7);
+    assert_eq!(snapshot(root)?, after_first);
+    Ok(())
+}
+
+#[test]
+fn locked_files_are_left_for_a_later_run() -> TestResult {
+    let data = tempfile::tempdir()?;
+    let root = data.path();
+    populate(root)?;
+    let profile = root.join(PROFILE);
+    let before = fs::read(&profile)?;
+
+    let report = {
+        let _lock = openracing_file_lock::FileLock::acquire(&profile)?;
+        migrate_persisted_data(root, GAME_ID_MIGRATIONS, MigrationMode::Apply)?
+    };
+
+    assert_eq!(report.locked, vec![profile.clone()]);
+    assert_eq!(fs::read(&profile)?, before);
+    let retry = migrate_persisted_data(root, GAME_ID_MIGRATIONS, MigrationMode::Apply)?;
+    assert_eq!(retry.migrated.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn old_ids_resolve_live_through_the_alias_path() {
+    for migration in GAME_ID_MIGRATIONS {
+        assert_eq!(normalize_game_id(migration.old), migration.new);
+        let shouted = migration.old.to_uppercase().replace('_', "-");
+        assert_eq!(normalize_game_id(&shouted), migration.new);
+    }
+    let service = TelemetryService::new();
+    assert!(service.is_game_matrix_supported("ea_wrc"));
+    assert!(service.is_game_matrix_supported("F1-2025"));
+}
@@ -0,0 +1,200 @@
+//! Monitoring sessions: stopping on drop, one session per game, and counters.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use racing_wheel_telemetry_adapters::{
+    MockAdapter, NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver,
+};
+use racing_wheel_telemetry_orchestrator::{SessionError, TelemetryService};
+use tokio::time::{sleep, timeout};
+
+const GAME_ID: &str = "session_source";
+const INTERVAL: Duration = Duration::from_millis(5);
+const RECV_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Emits frames from a task that counts itself in `live` while running.
+struct TrackedAdapter {
+    inner: MockAdapter,
+    live: Arc<AtomicUsize>,
+    stops: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl TelemetryAdapter for TrackedAdapter {
+    fn game_id(&self) -> &str {
+        self.inner.game_id()
+    }
+
+    async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let (tx, rx) = tokio::sync::mpsc::channel(8);
+        let live = Arc::clone(&self.live);
+        live.fetch_add(1, Ordering::SeqCst);
+        tokio::spawn(async move {
+            let mut sequence = 0u64;
+            loop {
+                let frame = TelemetryFrame::new(NormalizedTelemetry::default(), 0, sequence, 0);
+                if tx.send(frame).await.is_err() {
+                    break;
+                }
+                sequence += 1;
+                sleep(INTERVAL).await;
+            }
+            live.fetch_sub(1, Ordering::SeqCst);
+        });
+        Ok(rx)
+    }
+
+    async fn stop_monitoring(&self) -> Result<()> {
+        self.stops.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn normalize(&self, raw: &[u8]) -> Result<NormalizedTelemetry> {
+        self.inner.normalize(raw)
+    }
+
+    fn expected_update_rate(&self) -> Duration {
+        INTERVAL
+    }
+
+    async fn is_game_running(&self) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+struct Fixture {
+    service: TelemetryService,
+    live: Arc<AtomicUsize>,
+    stops: Arc<AtomicUsize>,
+}
+
+fn fixture() -> Fixture {
+    let live = Arc::new(AtomicUsize::new(0));
+    let stops = Arc::new(AtomicUsize::new(0));
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(TrackedAdapter {
+        inner: MockAdapter::new(GAME_ID.to_string()),
+        live: Arc::clone(&live),
+        stops: Arc::clone(&stops),
+    }));
+    Fixture {
+        service,
+        live,
+        stops,
+    }
+}
+
+async fn wait_until(condition: impl Fn() -> bool) -> Result<()> {
+    timeout(RECV_TIMEOUT, async {
+        while !condition() {
+            sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn dropping_a_session_stops_the_adapter_task() -> Result<()> {
+    let mut fx = fixture();
+    let mut session = fx.service.start_monitoring_session(GAME_ID).await?;
+    timeout(RECV_TIMEOUT, session.recv())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("stream ended early"))?;
+    assert_eq!(fx.live.load(Ordering::SeqCst), 1);
+
+    drop(session);
+    wait_until(|| fx.live.load(Ordering::SeqCst) == 0).await?;
+    wait_until(|| fx.stops.load(Ordering::SeqCst) == 1).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn a_second_session_is_refused_until_the_first_stops() -> Result<()> {
+    let mut fx = fixture();
+    let mut first = fx.service.start_monitoring_session(GAME_ID).await?;
+
+    let refused = fx
+        .service
+        .start_monitoring_session(GAME_ID)
+        .await
+        .err()
+        .ok_or_else(|| anyhow::anyhow!("second session started"))?;
+    assert_eq!(
+        refused.downcast_ref::<SessionError>(),
+        Some(&SessionError::AlreadyActive(GAME_ID.to_string()))
+    );
+    assert!(!first.is_stopped());
+
+    first.stop().await?;
+    first.stop().await?;
+    assert!(first.is_stopped());
+    assert_eq!(fx.stops.load(Ordering::SeqCst), 1);
+
+    let mut second = fx.service.start_monitoring_session(GAME_ID).await?;
+    timeout(RECV_TIMEOUT, second.recv())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("stream ended early"))?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn stopped_session_ends_its_stream() -> Result<()> {
+    let mut fx = fixture();
+    let mut session = fx.service.start_monitoring_session(GAME_ID).await?;
+    session.stop().await?;
+    // Buffered frames drain, then the stream ends.
+    timeout(RECV_TIMEOUT, async {
+        while session.recv().await.is_some() {}
+    })
+    .await?;
+    wait_until(|| fx.live.load(Ordering::SeqCst) == 0).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn stats_count_received_and_dropped_frames() -> Result<()> {
+    let mut fx = fixture();
+    let mut session = fx.service.start_monitoring_session(GAME_ID).await?;
+    assert_eq!(session.game_id(), GAME_ID);
+    for _ in 0..10 {
+        timeout(RECV_TIMEOUT, session.recv())
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("stream ended early"))?;
+    }
+    let stats = session.stats();
+    assert_eq!((stats.frames_received, stats.frames_dropped), (10, 0));
+    assert!(stats.duration > Duration::ZERO);
+
+    fx.service.pause_monitoring(GAME_ID)?;
+    wait_until(|| session.stats().frames_dropped >= 3).await?;
+    fx.service.resume_monitoring(GAME_ID)?;
+
+    session.stop().await?;
+    let stopped = session.stats();
+    assert_eq!(stopped.frames_received, 10);
+    sleep(Duration::from_millis(20)).await;
+    assert_eq!(session.stats().duration, stopped.duration);
+    Ok(())
+}
+
+#[tokio::test]
+async fn unknown_games_do_not_leave_a_claim_behind() -> Result<()> {
+    let mut fx = fixture();
+    assert!(
+        fx.service
+            .start_monitoring_session("no_such_game")
+            .await
+            .is_err()
+    );
+    // The plain receiver API is unaffected by sessions.
+    let mut rx = fx.service.start_monitoring(GAME_ID).await?;
+    timeout(RECV_TIMEOUT, rx.recv())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("stream ended early"))?;
+    Ok(())
+}
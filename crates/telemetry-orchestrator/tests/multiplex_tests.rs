@@ -0,0 +1,116 @@
+//! Several games monitored at once through one labeled stream.
+
+mod common;
+
+use std::time::Duration;
+
+use anyhow::Result;
+use racing_wheel_telemetry_orchestrator::TelemetryService;
+use tokio::time::timeout;
+
+use common::{START_ERROR, frame, register, register_broken};
+
+#[tokio::test]
+async fn frames_are_labeled_with_their_game() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    let first_tx = register(&mut service, "first_source");
+    let second_tx = register(&mut service, "second_source");
+    let mut merged = service
+        .start_monitoring_games(&["first_source", "second_source"])
+        .await?;
+
+    first_tx.send(frame(7)).await?;
+    let (game_id, received) = timeout(Duration::from_secs(1), merged.recv())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("merged stream closed"))?;
+    assert_eq!(game_id, "first_source");
+    assert_eq!(received.sequence, 7);
+
+    second_tx.send(frame(3)).await?;
+    let (game_id, _) = timeout(Duration::from_secs(1), merged.recv())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("merged stream closed"))?;
+    assert_eq!(game_id, "second_source");
+    Ok(())
+}
+
+#[tokio::test]
+async fn a_failing_game_does_not_stop_the_others() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    let working_tx = register(&mut service, "working_source");
+    register_broken(&mut service, "broken_source");
+    let mut merged = service
+        .start_monitoring_games(&["broken_source", "working_source"])
+        .await?;
+
+    let failures = service.multiplex_failures();
+    assert_eq!(failures.len(), 1);
+    assert!(
+        failures
+            .get("broken_source")
+            .is_some_and(|error| error.contains(START_ERROR))
+    );
+
+    working_tx.send(frame(0)).await?;
+    let (game_id, _) = timeout(Duration::from_secs(1), merged.recv())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("merged stream closed"))?;
+    assert_eq!(game_id, "working_source");
+    Ok(())
+}
+
+#[tokio::test]
+async fn starting_fails_when_no_game_starts() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    register_broken(&mut service, "broken_source");
+    assert!(
+        service
+            .start_monitoring_games(&["broken_source", "unregistered_source"])
+            .await
+            .is_err()
+    );
+    assert_eq!(service.multiplex_failures().len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn active_sources_only_lists_recent_senders() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    let talking_tx = register(&mut service, "talking_source");
+    let _silent_tx = register(&mut service, "silent_source");
+    let mut merged = service
+        .start_monitoring_games(&["talking_source", "silent_source"])
+        .await?;
+    assert!(service.active_sources(Duration::from_secs(60)).is_empty());
+
+    talking_tx.send(frame(0)).await?;
+    timeout(Duration::from_secs(1), merged.recv()).await?;
+    assert_eq!(
+        service.active_sources(Duration::from_secs(60)),
+        ["talking_source"]
+    );
+
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    assert!(service.active_sources(Duration::from_millis(10)).is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn stop_monitoring_all_closes_the_merged_stream() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    let _first_tx = register(&mut service, "first_source");
+    let _second_tx = register(&mut service, "second_source");
+    let mut merged = service
+        .start_monitoring_games(&["first_source", "second_source"])
+        .await?;
+
+    service.stop_monitoring_all().await?;
+    assert!(
+        timeout(Duration::from_secs(1), merged.recv())
+            .await?
+            .is_none()
+    );
+    // Stopping again is a no-op.
+    service.stop_monitoring_all().await?;
+    Ok(())
+}
@@ -0,0 +1,193 @@
+//! Pausing and resuming monitoring keeps the adapter's transport alive.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use racing_wheel_telemetry_adapters::{
+    MockAdapter, NormalizedTelemetry, TelemetryAdapter, TelemetryReceiver,
+};
+use racing_wheel_telemetry_orchestrator::{
+    Freshness, FreshnessCause, FreshnessThresholds, PauseError, PauseState, TelemetryService,
+};
+use tokio::time::{sleep, timeout};
+
+const GAME_ID: &str = "pause_source";
+const INTERVAL: Duration = Duration::from_millis(5);
+const RECV_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// [`MockAdapter`] that counts how often its transport is set up.
+struct CountingAdapter {
+    inner: MockAdapter,
+    starts: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl TelemetryAdapter for CountingAdapter {
+    fn game_id(&self) -> &str {
+        self.inner.game_id()
+    }
+
+    async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        self.starts.fetch_add(1, Ordering::SeqCst);
+        self.inner.start_monitoring().await
+    }
+
+    async fn stop_monitoring(&self) -> Result<()> {
+        self.inner.stop_monitoring().await
+    }
+
+    fn normalize(&self, raw: &[u8]) -> Result<NormalizedTelemetry> {
+        self.inner.normalize(raw)
+    }
+
+    fn expected_update_rate(&self) -> Duration {
+        self.inner.expected_update_rate()
+    }
+
+    async fn is_game_running(&self) -> Result<bool> {
+        self.inner.is_game_running().await
+    }
+}
+
+fn counting_service() -> (TelemetryService, Arc<AtomicUsize>) {
+    let starts = Arc::new(AtomicUsize::new(0));
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(CountingAdapter {
+        inner: MockAdapter::with_update_rate(GAME_ID.to_string(), INTERVAL),
+        starts: Arc::clone(&starts),
+    }));
+    (service, starts)
+}
+
+async fn recv_frame(rx: &mut TelemetryReceiver) -> Result<()> {
+    timeout(RECV_TIMEOUT, rx.recv())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("forwarding stopped"))?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn pause_stops_delivery_and_resume_restores_it_without_restart() -> Result<()> {
+    let (mut service, starts) = counting_service();
+    let mut events = service.subscribe_pause_events();
+    let mut forwarded = service.start_monitoring(GAME_ID).await?;
+    recv_frame(&mut forwarded).await?;
+
+    service.pause_monitoring(GAME_ID)?;
+    assert!(service.is_paused(GAME_ID));
+    // Frames forwarded before the pause took effect may still be queued.
+    sleep(INTERVAL).await;
+    while forwarded.try_recv().is_ok() {}
+    assert!(
+        timeout(INTERVAL * 20, forwarded.recv()).await.is_err(),
+        "no frame may be forwarded while paused"
+    );
+    let dropped = service.paused_frames_dropped(GAME_ID);
+    assert!(dropped > 0, "paused frames are drained and counted");
+
+    service.resume_monitoring(GAME_ID)?;
+    assert!(!service.is_paused(GAME_ID));
+    recv_frame(&mut forwarded).await?;
+    assert_eq!(starts.load(Ordering::SeqCst), 1);
+
+    let paused = events.recv().await?;
+    assert_eq!(
+        (paused.game_id.as_str(), paused.state),
+        (GAME_ID, PauseState::Paused)
+    );
+    assert_eq!(paused.dropped_frames, 0);
+    let resumed = events.recv().await?;
+    assert_eq!(resumed.state, PauseState::Resumed);
+    assert!(resumed.dropped_frames >= dropped);
+    Ok(())
+}
+
+#[tokio::test]
+async fn pause_and_resume_reject_invalid_transitions() -> Result<()> {
+    let (mut service, _) = counting_service();
+    let not_monitoring = PauseError::NotMonitoring(GAME_ID.to_string());
+    assert_eq!(
+        service.pause_monitoring(GAME_ID),
+        Err(not_monitoring.clone())
+    );
+    assert_eq!(
+        service.resume_monitoring(GAME_ID),
+        Err(not_monitoring.clone())
+    );
+
+    let forwarded = service.start_monitoring(GAME_ID).await?;
+    assert_eq!(
+        service.resume_monitoring(GAME_ID),
+        Err(PauseError::NotPaused(GAME_ID.to_string()))
+    );
+    service.pause_monitoring(GAME_ID)?;
+    assert_eq!(
+        service.pause_monitoring(GAME_ID),
+        Err(PauseError::AlreadyPaused(GAME_ID.to_string()))
+    );
+    service.resume_monitoring(GAME_ID)?;
+
+    // Dropping the receiver ends the forwarding task on its next frame.
+    drop(forwarded);
+    timeout(RECV_TIMEOUT, async {
+        while service.pause_monitoring(GAME_ID).is_ok() {
+            service.resume_monitoring(GAME_ID)?;
+            sleep(INTERVAL).await;
+        }
+        Ok::<_, PauseError>(())
+    })
+    .await??;
+    assert_eq!(service.pause_monitoring(GAME_ID), Err(not_monitoring));
+    assert!(!service.is_paused(GAME_ID));
+    Ok(())
+}
+
+#[tokio::test]
+async fn paused_game_reports_paused_instead_of_stale() -> Result<()> {
+    let (mut service, _) = counting_service();
+    let thresholds = FreshnessThresholds {
+        fresh_interval: INTERVAL * 4,
+        stale_after: INTERVAL * 10,
+        min_dwell: INTERVAL * 4,
+    };
+    service.set_freshness_thresholds(GAME_ID, thresholds);
+    let mut freshness = service.watch_freshness(GAME_ID);
+    let mut forwarded = service.start_monitoring(GAME_ID).await?;
+    tokio::spawn(async move { while forwarded.recv().await.is_some() {} });
+    timeout(
+        RECV_TIMEOUT,
+        freshness.wait_for(|status| status.is_some_and(|s| s.freshness == Freshness::Fresh)),
+    )
+    .await??;
+
+    service.pause_monitoring(GAME_ID)?;
+    timeout(
+        RECV_TIMEOUT,
+        freshness.wait_for(|status| status.is_some_and(|s| s.freshness == Freshness::Paused)),
+    )
+    .await??;
+    // Well past `stale_after`, the watchdog must not have decayed it.
+    sleep(thresholds.stale_after * 3).await;
+    let status = service
+        .freshness_status(GAME_ID)
+        .ok_or_else(|| anyhow::anyhow!("no freshness status"))?;
+    assert_eq!(
+        (status.freshness, status.cause),
+        (Freshness::Paused, FreshnessCause::Paused)
+    );
+    assert_eq!(
+        service.freshness_snapshot()[0].status.freshness,
+        Freshness::Paused
+    );
+
+    service.resume_monitoring(GAME_ID)?;
+    timeout(
+        RECV_TIMEOUT,
+        freshness.wait_for(|status| status.is_some_and(|s| s.freshness == Freshness::Fresh)),
+    )
+    .await??;
+    Ok(())
+}
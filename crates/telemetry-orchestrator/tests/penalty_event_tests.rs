@@ -0,0 +1,85 @@
+//! Penalty events broadcast by the orchestrator's forwarding task.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use racing_wheel_telemetry_adapters::{
+    MockAdapter, PenaltyEvent, PenaltyKind, PenaltyState, TelemetryFrame,
+};
+use racing_wheel_telemetry_orchestrator::{FrameTransform, GamePenaltyEvent, TelemetryService};
+
+const GAME_ID: &str = "mock_penalties";
+
+/// Replays a fixed penalty script over the mock adapter's frames: it stays on
+/// its final state once exhausted.
+struct PenaltyScript {
+    states: Vec<PenaltyState>,
+    index: usize,
+}
+
+impl FrameTransform for PenaltyScript {
+    fn name(&self) -> &str {
+        "penalty_script"
+    }
+
+    fn apply(&mut self, frame: &mut TelemetryFrame) {
+        let last = self.states.len().saturating_sub(1);
+        frame.data.penalties = self.states.get(self.index.min(last)).copied();
+        self.index += 1;
+    }
+}
+
+#[tokio::test]
+async fn repeated_states_broadcast_each_change_exactly_once() -> Result<()> {
+    let clean = PenaltyState::default();
+    let warned = PenaltyState {
+        track_limit_warnings: 1,
+        ..clean
+    };
+    let drive_through = PenaltyState {
+        active_penalty_kind: PenaltyKind::DriveThrough,
+        drive_through_pending: true,
+        ..warned
+    };
+
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(MockAdapter::with_update_rate(
+        GAME_ID.to_string(),
+        Duration::from_millis(1),
+    )));
+    service.register_transform(
+        GAME_ID,
+        Box::new(PenaltyScript {
+            states: vec![clean, warned, warned, drive_through, drive_through, warned],
+            index: 0,
+        }),
+    );
+    let mut events = service.subscribe_penalty_events();
+
+    let mut rx = service.start_monitoring(GAME_ID).await?;
+    for _ in 0..12 {
+        tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("telemetry stream closed early"))?;
+    }
+
+    let mut seen = Vec::new();
+    while let Ok(GamePenaltyEvent { game_id, event }) = events.try_recv() {
+        assert_eq!(game_id, GAME_ID);
+        seen.push(event);
+    }
+    assert_eq!(
+        seen,
+        vec![
+            PenaltyEvent::TrackLimitWarning { total: 1 },
+            PenaltyEvent::PenaltyAwarded {
+                kind: PenaltyKind::DriveThrough,
+                time_s: 0.0,
+            },
+            PenaltyEvent::PenaltyServed {
+                kind: PenaltyKind::DriveThrough,
+            },
+        ]
+    );
+    Ok(())
+}
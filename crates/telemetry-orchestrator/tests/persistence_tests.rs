@@ -0,0 +1,131 @@
+//! Field persistence: carrying intermittent fields forward with staleness marks.
+
+use std::time::Duration;
+
+use racing_wheel_telemetry_adapters::{NormalizedTelemetry, TelemetryFrame, TelemetryValue};
+use racing_wheel_telemetry_orchestrator::{
+    FieldPersistence, FrameTransform, PERSISTED_FIELDS_KEY, PersistedField, TransformChain,
+    persisted_fields,
+};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const MS: u64 = 1_000_000;
+
+fn frame_at(ms: u64, track_id: Option<&str>) -> TelemetryFrame {
+    let mut data = NormalizedTelemetry::builder().session_id("race").build();
+    data.track_id = track_id.map(str::to_string);
+    TelemetryFrame::new(data, ms * MS, ms, 0)
+}
+
+fn track_persistence(max_age: Duration) -> FieldPersistence {
+    FieldPersistence::new().persist(PersistedField::TrackId, max_age)
+}
+
+#[test]
+fn intermittent_track_id_becomes_continuous_with_ages() -> TestResult {
+    let mut persistence = track_persistence(Duration::from_secs(1));
+
+    // Track id every fourth 20 ms frame, as with a 12.5 Hz side channel.
+    for step in 0..12u64 {
+        let ms = step * 20;
+        let sent = step % 4 == 0;
+        let mut frame = frame_at(ms, sent.then_some("spa"));
+        persistence.apply(&mut frame);
+
+        assert_eq!(frame.data.track_id.as_deref(), Some("spa"), "frame {step}");
+        let carried = persisted_fields(&frame.data);
+        if sent {
+            assert!(carried.is_empty(), "fresh frame {step} marked as carried");
+            assert!(!frame.data.extended.contains_key(PERSISTED_FIELDS_KEY));
+        } else {
+            let age = Duration::from_millis((step % 4) * 20);
+            assert_eq!(carried, vec![("track_id".to_string(), age)], "frame {step}");
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn max_age_expiry_reverts_to_none() -> TestResult {
+    let mut persistence = track_persistence(Duration::from_millis(100));
+
+    let mut first = frame_at(0, Some("monza"));
+    persistence.apply(&mut first);
+
+    let mut within = frame_at(100, None);
+    persistence.apply(&mut within);
+    assert_eq!(within.data.track_id.as_deref(), Some("monza"));
+
+    let mut expired = frame_at(101, None);
+    persistence.apply(&mut expired);
+    assert_eq!(expired.data.track_id, None);
+    assert!(persisted_fields(&expired.data).is_empty());
+
+    // Once expired the old value does not come back.
+    let mut later = frame_at(120, None);
+    persistence.apply(&mut later);
+    assert_eq!(later.data.track_id, None);
+    Ok(())
+}
+
+#[test]
+fn fresh_value_always_wins_over_carried_one() -> TestResult {
+    let mut persistence = FieldPersistence::new()
+        .persist_group(PersistedField::IDENTITY, Duration::from_secs(5))
+        .persist(
+            PersistedField::Extended("tire_compound".to_string()),
+            Duration::from_secs(5),
+        );
+
+    let mut first = frame_at(0, Some("spa"));
+    first.data.car_id = Some("gt3".to_string());
+    first.data.extended.insert(
+        "tire_compound".to_string(),
+        TelemetryValue::String("soft".into()),
+    );
+    persistence.apply(&mut first);
+
+    let mut changed = frame_at(50, Some("imola"));
+    persistence.apply(&mut changed);
+    assert_eq!(changed.data.track_id.as_deref(), Some("imola"));
+    assert_eq!(changed.data.car_id.as_deref(), Some("gt3"));
+    assert_eq!(
+        changed.data.extended.get("tire_compound"),
+        Some(&TelemetryValue::String("soft".into()))
+    );
+    let labels: Vec<_> = persisted_fields(&changed.data)
+        .into_iter()
+        .map(|(field, _)| field)
+        .collect();
+    assert_eq!(labels, vec!["car_id", "extended:tire_compound"]);
+
+    // The newer fresh value is what gets carried afterwards.
+    let mut gap = frame_at(80, None);
+    persistence.apply(&mut gap);
+    assert_eq!(gap.data.track_id.as_deref(), Some("imola"));
+    Ok(())
+}
+
+#[test]
+fn session_and_source_changes_clear_carried_values() -> TestResult {
+    let mut chain = TransformChain::new();
+    chain.push(Box::new(track_persistence(Duration::from_secs(10))));
+
+    let mut first = frame_at(0, Some("spa"));
+    chain.apply(&mut first);
+
+    let mut next_session = frame_at(20, None);
+    next_session.data.session_id = Some("qualifying".to_string());
+    chain.apply(&mut next_session);
+    assert_eq!(next_session.data.track_id, None);
+
+    // Timestamps restarting means a different source.
+    let mut persistence = track_persistence(Duration::from_secs(10));
+    let mut old_source = frame_at(500, Some("spa"));
+    persistence.apply(&mut old_source);
+    let mut new_source = frame_at(10, None);
+    persistence.apply(&mut new_source);
+    assert_eq!(new_source.data.track_id, None);
+    Ok(())
+}
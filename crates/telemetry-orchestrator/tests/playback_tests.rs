@@ -0,0 +1,175 @@
+//! Replaying recorder output through the normal adapter pipeline.
+
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use racing_wheel_telemetry_adapters::{
+    NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver,
+};
+use racing_wheel_telemetry_orchestrator::{
+    PlaybackAdapter, PlaybackConfig, PlaybackEnd, PlaybackSpeed, TelemetryService,
+};
+use racing_wheel_telemetry_recorder::TelemetryRecording;
+use tokio::time::timeout;
+
+/// Frames 100 ms apart with rpm 1000, 2000 and 3000, saved as `acc`.
+fn write_fixture(dir: &tempfile::TempDir) -> Result<std::path::PathBuf> {
+    let frames = (0..3u64)
+        .map(|i| {
+            let telemetry = NormalizedTelemetry::builder()
+                .rpm(1000.0 * (i + 1) as f32)
+                .build();
+            TelemetryFrame::new(telemetry, 5_000_000_000 + i * 100_000_000, 40 + i, 64)
+        })
+        .collect();
+    let path = dir.path().join("fixture.json");
+    TelemetryRecording::from_frames("acc".to_string(), frames, None).save(&path)?;
+    Ok(path)
+}
+
+async fn next_frame(frames: &mut TelemetryReceiver) -> Result<TelemetryFrame> {
+    timeout(Duration::from_secs(2), frames.recv())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("playback ended early"))
+}
+
+fn rpms(frames: &[TelemetryFrame]) -> Vec<f32> {
+    frames.iter().map(|f| f.data.rpm).collect()
+}
+
+#[tokio::test]
+async fn replays_at_ten_times_speed_in_order() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let adapter = PlaybackAdapter::open(
+        write_fixture(&dir)?,
+        PlaybackConfig {
+            speed: PlaybackSpeed::Multiplier(10.0),
+            ..PlaybackConfig::default()
+        },
+    )?;
+    assert_eq!(adapter.game_id(), "playback:acc");
+    assert_eq!(adapter.expected_update_rate(), Duration::from_millis(10));
+
+    let started = Instant::now();
+    let mut stream = adapter.start_monitoring().await?;
+    let mut frames = Vec::new();
+    let mut arrivals = Vec::new();
+    for _ in 0..3 {
+        frames.push(next_frame(&mut stream).await?);
+        arrivals.push(started.elapsed());
+    }
+    assert!(
+        timeout(Duration::from_secs(1), stream.recv())
+            .await?
+            .is_none()
+    );
+
+    assert_eq!(rpms(&frames), [1000.0, 2000.0, 3000.0]);
+    let sequences: Vec<u64> = frames.iter().map(|f| f.sequence).collect();
+    assert_eq!(sequences, [0, 1, 2]);
+    assert!(
+        frames
+            .windows(2)
+            .all(|pair| pair[0].timestamp_ns <= pair[1].timestamp_ns)
+    );
+    // 200 ms of recording takes about 20 ms at 10x.
+    assert!(arrivals[2] >= Duration::from_millis(19), "{:?}", arrivals);
+    assert!(arrivals[2] < Duration::from_millis(150), "{:?}", arrivals);
+    assert!(arrivals[1] - arrivals[0] >= Duration::from_millis(9));
+    Ok(())
+}
+
+#[tokio::test]
+async fn looping_playback_keeps_numbering_frames() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let adapter = PlaybackAdapter::open(
+        write_fixture(&dir)?,
+        PlaybackConfig {
+            speed: PlaybackSpeed::AsFastAsPossible,
+            at_end: PlaybackEnd::Loop,
+        },
+    )?;
+    let mut stream = adapter.start_monitoring().await?;
+    let mut frames = Vec::new();
+    for _ in 0..7 {
+        frames.push(next_frame(&mut stream).await?);
+    }
+    adapter.stop_monitoring().await?;
+
+    assert_eq!(
+        rpms(&frames),
+        [1000.0, 2000.0, 3000.0, 1000.0, 2000.0, 3000.0, 1000.0]
+    );
+    let sequences: Vec<u64> = frames.iter().map(|f| f.sequence).collect();
+    assert_eq!(sequences, [0, 1, 2, 3, 4, 5, 6]);
+
+    // Stopping ends the stream once buffered frames are drained.
+    let drained = timeout(Duration::from_secs(1), async {
+        while stream.recv().await.is_some() {}
+    })
+    .await;
+    assert!(drained.is_ok());
+    Ok(())
+}
+
+#[tokio::test]
+async fn playback_flows_through_the_service() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(PlaybackAdapter::open(
+        write_fixture(&dir)?,
+        PlaybackConfig {
+            speed: PlaybackSpeed::Multiplier(10.0),
+            ..PlaybackConfig::default()
+        },
+    )?));
+
+    let mut stream = service.start_monitoring("playback:acc").await?;
+    let mut frames = Vec::new();
+    for _ in 0..3 {
+        frames.push(next_frame(&mut stream).await?);
+    }
+    assert_eq!(rpms(&frames), [1000.0, 2000.0, 3000.0]);
+    Ok(())
+}
+
+#[test]
+fn normalize_decodes_the_recorded_telemetry() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let adapter = PlaybackAdapter::open(write_fixture(&dir)?, PlaybackConfig::default())?;
+    let recorded = adapter
+        .recording()
+        .frames
+        .get(1)
+        .ok_or_else(|| anyhow::anyhow!("fixture too short"))?;
+
+    let raw = serde_json::to_vec(&recorded.data)?;
+    // The creation instant is not serialized, so compare what is.
+    assert_eq!(
+        serde_json::to_value(adapter.normalize(&raw)?)?,
+        serde_json::to_value(&recorded.data)?
+    );
+    assert!(adapter.normalize(b"\x00\x01").is_err());
+    assert!(adapter.recognize_packet(&raw).is_none());
+    Ok(())
+}
+
+#[test]
+fn invalid_recordings_and_speeds_are_rejected() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = write_fixture(&dir)?;
+    for factor in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        let config = PlaybackConfig {
+            speed: PlaybackSpeed::Multiplier(factor),
+            ..PlaybackConfig::default()
+        };
+        assert!(PlaybackAdapter::open(&path, config).is_err(), "{factor}");
+    }
+
+    let empty = TelemetryRecording::from_frames("acc".to_string(), Vec::new(), None);
+    assert!(PlaybackAdapter::from_recording(empty, PlaybackConfig::default()).is_err());
+    assert!(
+        PlaybackAdapter::open(dir.path().join("missing.json"), PlaybackConfig::default()).is_err()
+    );
+    Ok(())
+}
@@ -0,0 +1,77 @@
+//! Per-game rate limits chosen and enforced by the telemetry service.
+
+mod common;
+
+use std::time::Duration;
+
+use anyhow::Result;
+use racing_wheel_telemetry_adapters::{NormalizedTelemetry, TelemetryFrame};
+use racing_wheel_telemetry_orchestrator::TelemetryService;
+use tokio::time::timeout;
+
+use common::register;
+
+#[tokio::test]
+async fn limit_follows_expected_rate_unless_overridden() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    let _nominal = register(&mut service, "nominal_source");
+    let _fixed = register(&mut service, "fixed_source");
+
+    service.start_monitoring("nominal_source").await?;
+    // Twice the adapter's 20 Hz, for jitter.
+    assert_eq!(service.rate_limit_for("nominal_source"), 40);
+
+    service.set_rate_limit("fixed_source", 5);
+    service.start_monitoring("fixed_source").await?;
+    assert_eq!(service.rate_limit_for("fixed_source"), 5);
+    Ok(())
+}
+
+#[tokio::test]
+async fn backlog_flush_is_cut_at_the_burst_capacity_per_game() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    let flooded_tx = register(&mut service, "flooded_source");
+    let quiet_tx = register(&mut service, "quiet_source");
+    // One token a second keeps refills out of the measurement.
+    service.set_rate_limit("flooded_source", 1);
+    let mut flooded = service.start_monitoring("flooded_source").await?;
+    let mut quiet = service.start_monitoring("quiet_source").await?;
+
+    for sequence in 0..60 {
+        let frame = TelemetryFrame::new(NormalizedTelemetry::default(), 0, sequence, 0);
+        flooded_tx.send(frame).await?;
+    }
+    quiet_tx
+        .send(TelemetryFrame::new(NormalizedTelemetry::default(), 0, 0, 0))
+        .await?;
+
+    let mut forwarded = 0;
+    while timeout(Duration::from_millis(200), flooded.recv())
+        .await
+        .ok()
+        .flatten()
+        .is_some()
+    {
+        forwarded += 1;
+    }
+    assert_eq!(forwarded, 32);
+    assert!(
+        timeout(Duration::from_secs(1), quiet.recv())
+            .await?
+            .is_some()
+    );
+
+    let stats = service.rate_limit_stats();
+    let flooded_stats = stats
+        .get("flooded_source")
+        .ok_or_else(|| anyhow::anyhow!("no stats for flooded_source"))?;
+    assert_eq!(flooded_stats.processed_count, 32);
+    assert_eq!(flooded_stats.dropped_count, 28);
+    assert_eq!(flooded_stats.burst_consumed, 31);
+    let quiet_stats = stats
+        .get("quiet_source")
+        .ok_or_else(|| anyhow::anyhow!("no stats for quiet_source"))?;
+    assert_eq!(quiet_stats.processed_count, 1);
+    assert_eq!(quiet_stats.dropped_count, 0);
+    Ok(())
+}
@@ -0,0 +1,124 @@
+//! Switching an adapter's raw packet capture on and off through the service.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use racing_wheel_telemetry_adapters::{
+    MockAdapter, NormalizedTelemetry, RawCaptureCaps, RawCaptureReader, RawCaptureSink,
+    RawCaptureSlot, TelemetryAdapter, TelemetryReceiver,
+};
+use racing_wheel_telemetry_orchestrator::TelemetryService;
+use std::sync::Arc;
+
+const GAME_ID: &str = "capture_source";
+
+/// [`MockAdapter`] whose raw datagrams are fed in by the test.
+struct SyntheticAdapter {
+    inner: MockAdapter,
+    raw_capture: RawCaptureSlot,
+}
+
+#[async_trait]
+impl TelemetryAdapter for SyntheticAdapter {
+    fn game_id(&self) -> &str {
+        self.inner.game_id()
+    }
+
+    async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        self.inner.start_monitoring().await
+    }
+
+    async fn stop_monitoring(&self) -> Result<()> {
+        self.inner.stop_monitoring().await
+    }
+
+    fn normalize(&self, raw: &[u8]) -> Result<NormalizedTelemetry> {
+        self.inner.normalize(raw)
+    }
+
+    fn expected_update_rate(&self) -> Duration {
+        self.inner.expected_update_rate()
+    }
+
+    async fn is_game_running(&self) -> Result<bool> {
+        self.inner.is_game_running().await
+    }
+
+    fn set_raw_capture(&self, sink: Option<Arc<RawCaptureSink>>) {
+        self.raw_capture.set(sink);
+    }
+}
+
+fn synthetic_service() -> (TelemetryService, RawCaptureSlot) {
+    let raw_capture = RawCaptureSlot::default();
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(SyntheticAdapter {
+        inner: MockAdapter::new(GAME_ID.to_string()),
+        raw_capture: raw_capture.clone(),
+    }));
+    (service, raw_capture)
+}
+
+#[test]
+fn enabled_capture_records_until_disabled() -> Result<()> {
+    let (mut service, datagrams) = synthetic_service();
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("capture.orcap");
+
+    datagrams.record(b"before", None);
+    let sink = service.enable_raw_capture(GAME_ID, path.clone(), RawCaptureCaps::default())?;
+    assert_eq!(sink.game_id(), GAME_ID);
+    for i in 0..5u8 {
+        datagrams.record(&[i; 8], None);
+    }
+    let stats = service
+        .disable_raw_capture(GAME_ID)?
+        .ok_or_else(|| anyhow::anyhow!("capture was not running"))?;
+    datagrams.record(b"after", None);
+
+    assert_eq!(stats.records, 5);
+    assert!(datagrams.sink().is_none());
+    let reader = RawCaptureReader::open(&path)?;
+    assert_eq!(reader.game_id(), GAME_ID);
+    let payloads = reader
+        .map(|record| record.map(|record| record.payload))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(payloads, (0..5u8).map(|i| vec![i; 8]).collect::<Vec<_>>());
+    Ok(())
+}
+
+#[test]
+fn re_enabling_switches_to_the_new_file() -> Result<()> {
+    let (mut service, datagrams) = synthetic_service();
+    let dir = tempfile::tempdir()?;
+    let first = dir.path().join("first");
+    let second = dir.path().join("second");
+
+    service.enable_raw_capture(GAME_ID, first.clone(), RawCaptureCaps::default())?;
+    datagrams.record(b"one", None);
+    service.enable_raw_capture(GAME_ID, second.clone(), RawCaptureCaps::default())?;
+    datagrams.record(b"two", None);
+    service.disable_raw_capture(GAME_ID)?;
+
+    assert_eq!(RawCaptureReader::open(&first)?.count(), 1);
+    assert_eq!(RawCaptureReader::open(&second)?.count(), 1);
+    Ok(())
+}
+
+#[test]
+fn unknown_games_and_idle_captures_are_reported() -> Result<()> {
+    let (mut service, _) = synthetic_service();
+    let dir = tempfile::tempdir()?;
+    assert!(
+        service
+            .enable_raw_capture(
+                "no_such_game",
+                dir.path().join("capture"),
+                RawCaptureCaps::default()
+            )
+            .is_err()
+    );
+    assert!(service.disable_raw_capture(GAME_ID)?.is_none());
+    Ok(())
+}
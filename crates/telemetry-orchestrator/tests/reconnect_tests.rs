@@ -0,0 +1,192 @@
+//! Adapters restarted by the telemetry service when their stream dies.
+
+mod common;
+
+use std::time::Duration;
+
+use anyhow::Result;
+use racing_wheel_telemetry_adapters::TelemetryReceiver;
+use racing_wheel_telemetry_core::{ConnectionState, DisconnectionConfig};
+use racing_wheel_telemetry_orchestrator::{
+    MAX_RECONNECT_BACKOFF, TelemetryService, reconnect_backoff,
+};
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+use common::{MockAdapter, START_ERROR, frame, health_of, next_event};
+
+fn register(
+    service: &mut TelemetryService,
+    game_id: &'static str,
+    starts: Vec<Option<TelemetryReceiver>>,
+) {
+    service.register_adapter(Box::new(
+        MockAdapter::new(game_id)
+            .with_starts(starts)
+            .with_update_rate(Duration::from_millis(10)),
+    ));
+}
+
+fn reconnecting(timeout_ms: u64, max_attempts: u32) -> DisconnectionConfig {
+    DisconnectionConfig {
+        timeout_ms,
+        auto_reconnect: true,
+        max_reconnect_attempts: max_attempts,
+        reconnect_delay_ms: 5,
+        ..DisconnectionConfig::default()
+    }
+}
+
+#[test]
+fn backoff_doubles_from_the_configured_delay_up_to_the_cap() {
+    let config = DisconnectionConfig {
+        reconnect_delay_ms: 100,
+        ..DisconnectionConfig::default()
+    };
+    assert_eq!(reconnect_backoff(&config, 1), Duration::from_millis(100));
+    assert_eq!(reconnect_backoff(&config, 2), Duration::from_millis(200));
+    assert_eq!(reconnect_backoff(&config, 4), Duration::from_millis(800));
+    assert_eq!(reconnect_backoff(&config, 40), MAX_RECONNECT_BACKOFF);
+}
+
+#[tokio::test]
+async fn failed_restarts_are_retried_until_one_succeeds() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    let (first_tx, first_rx) = mpsc::channel(8);
+    let (second_tx, second_rx) = mpsc::channel(8);
+    register(
+        &mut service,
+        "flaky_source",
+        vec![Some(first_rx), None, None, Some(second_rx)],
+    );
+    service.set_disconnection_config("flaky_source", reconnecting(2000, 0));
+    let mut events = service.subscribe_health_events();
+    let mut frames = service.start_monitoring("flaky_source").await?;
+
+    first_tx.send(frame(0)).await?;
+    timeout(Duration::from_secs(1), frames.recv()).await?;
+    drop(first_tx);
+
+    let mut states = Vec::new();
+    loop {
+        let event = next_event(&mut events).await?;
+        states.push(event.new_state);
+        if event.new_state == ConnectionState::Reconnecting
+            && event.reason.as_deref() == Some("Reconnection attempt 3")
+        {
+            break;
+        }
+    }
+    use ConnectionState::{Connected, Connecting, Disconnected, Error, Reconnecting};
+    assert_eq!(
+        states,
+        [
+            Connecting,
+            Connected,
+            Disconnected,
+            Reconnecting,
+            Error,
+            Reconnecting,
+            Error,
+            Reconnecting
+        ]
+    );
+    assert_eq!(health_of(&service, "flaky_source")?.reconnect_attempts, 3);
+
+    // The caller's receiver carries on with the new stream.
+    second_tx.send(frame(1)).await?;
+    let resumed = timeout(Duration::from_secs(1), frames.recv())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("forwarding ended"))?;
+    assert_eq!(resumed.sequence, 1);
+    assert_eq!(next_event(&mut events).await?.new_state, Connected);
+
+    let health = health_of(&service, "flaky_source")?;
+    assert_eq!(health.connection_state, Connected);
+    assert_eq!(health.reconnect_attempts, 0);
+    assert_eq!(health.last_error.as_deref(), Some(START_ERROR));
+    Ok(())
+}
+
+#[tokio::test]
+async fn a_quiet_stream_is_restarted() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    let (first_tx, first_rx) = mpsc::channel(8);
+    let (second_tx, second_rx) = mpsc::channel(8);
+    register(
+        &mut service,
+        "stalled_source",
+        vec![Some(first_rx), Some(second_rx)],
+    );
+    service.set_disconnection_config("stalled_source", reconnecting(50, 0));
+    let mut events = service.subscribe_health_events();
+    let mut frames = service.start_monitoring("stalled_source").await?;
+
+    first_tx.send(frame(0)).await?;
+    timeout(Duration::from_secs(1), frames.recv()).await?;
+    let mut disconnected = None;
+    while disconnected.is_none() {
+        let event = next_event(&mut events).await?;
+        if event.new_state == ConnectionState::Disconnected {
+            disconnected = Some(event);
+        }
+    }
+    assert_eq!(
+        disconnected.and_then(|event| event.reason).as_deref(),
+        Some("No data received for 50ms")
+    );
+    assert_eq!(
+        next_event(&mut events).await?.new_state,
+        ConnectionState::Reconnecting
+    );
+
+    second_tx.send(frame(1)).await?;
+    let resumed = timeout(Duration::from_secs(1), frames.recv())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("forwarding ended"))?;
+    assert_eq!(resumed.sequence, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn retries_stop_at_the_attempt_limit() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    let (tx, rx) = mpsc::channel(8);
+    register(&mut service, "dead_source", vec![Some(rx)]);
+    service.set_disconnection_config("dead_source", reconnecting(2000, 2));
+    let mut frames = service.start_monitoring("dead_source").await?;
+
+    tx.send(frame(0)).await?;
+    timeout(Duration::from_secs(1), frames.recv()).await?;
+    drop(tx);
+
+    // Forwarding ends once both attempts have failed.
+    assert!(
+        timeout(Duration::from_secs(2), frames.recv())
+            .await?
+            .is_none()
+    );
+    let health = health_of(&service, "dead_source")?;
+    assert_eq!(health.connection_state, ConnectionState::Error);
+    assert_eq!(health.reconnect_attempts, 2);
+    assert!(!service.is_monitoring("dead_source"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn games_without_a_config_are_not_reconnected() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    let (tx, rx) = mpsc::channel(8);
+    let (_spare_tx, spare_rx) = mpsc::channel(8);
+    register(&mut service, "plain_source", vec![Some(rx), Some(spare_rx)]);
+    let mut frames = service.start_monitoring("plain_source").await?;
+
+    drop(tx);
+    assert!(
+        timeout(Duration::from_secs(1), frames.recv())
+            .await?
+            .is_none()
+    );
+    assert_eq!(health_of(&service, "plain_source")?.reconnect_attempts, 0);
+    Ok(())
+}
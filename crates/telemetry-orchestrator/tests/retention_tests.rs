@@ -0,0 +1,204 @@
+//! Retention cleanup of on-disk artifacts against a temporary data directory.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use racing_wheel_telemetry_orchestrator::{
+    ArtifactClass, CleanupReason, DiskStats, RetentionEvent, RetentionManager, RetentionPolicies,
+    RetentionPolicy,
+};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const HOUR: Duration = Duration::from_secs(3600);
+
+/// Reports a fixed amount of free space regardless of path.
+struct FixedDiskStats(u64);
+
+impl DiskStats for FixedDiskStats {
+    fn available_bytes(&self, _path: &Path) -> io::Result<u64> {
+        Ok(self.0)
+    }
+}
+
+fn write_artifact(
+    dir: &Path,
+    name: &str,
+    size: usize,
+    age: Duration,
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(name);
+    std::fs::write(&path, vec![0u8; size])?;
+    let file = std::fs::File::options().write(true).open(&path)?;
+    file.set_modified(SystemTime::now() - age)?;
+    Ok(path)
+}
+
+fn policies_for(class: ArtifactClass, policy: RetentionPolicy) -> RetentionPolicies {
+    let mut policies = RetentionPolicies::default();
+    policies.set(class, policy);
+    policies
+}
+
+#[test]
+fn size_based_cleanup_deletes_oldest_first_and_stops_at_threshold() -> TestResult {
+    let data = tempfile::tempdir()?;
+    let manager = RetentionManager::new(
+        data.path(),
+        policies_for(
+            ArtifactClass::Recordings,
+            RetentionPolicy {
+                max_total_bytes: Some(250),
+                ..RetentionPolicy::default()
+            },
+        ),
+    );
+    let dir = manager.class_dir(ArtifactClass::Recordings);
+    let oldest = write_artifact(&dir, "a.json", 100, HOUR * 3)?;
+    let older = write_artifact(&dir, "b.json", 100, HOUR * 2)?;
+    let newer = write_artifact(&dir, "c.json", 100, HOUR)?;
+    let newest = write_artifact(&dir, "d.json", 100, Duration::ZERO)?;
+    let mut events = manager.subscribe();
+
+    let summary = manager.run_cleanup()?;
+
+    assert_eq!(summary.deleted_items, 2);
+    assert_eq!(summary.reclaimed_bytes, 200);
+    assert!(!oldest.exists());
+    assert!(!older.exists());
+    assert!(newer.exists());
+    assert!(newest.exists());
+    assert_eq!(
+        events.try_recv()?,
+        RetentionEvent::CleanupCompleted(summary.clone())
+    );
+    assert_eq!(manager.last_cleanup(), Some(summary));
+    Ok(())
+}
+
+#[test]
+fn age_based_cleanup_skips_active_file() -> TestResult {
+    let data = tempfile::tempdir()?;
+    let manager = RetentionManager::new(
+        data.path(),
+        policies_for(
+            ArtifactClass::SinkOutputs,
+            RetentionPolicy {
+                max_age_secs: Some(HOUR.as_secs()),
+                ..RetentionPolicy::default()
+            },
+        ),
+    );
+    let dir = manager.class_dir(ArtifactClass::SinkOutputs);
+    let stale = write_artifact(&dir, "old.jsonl", 10, HOUR * 5)?;
+    let active = write_artifact(&dir, "live.jsonl", 10, HOUR * 5)?;
+    let fresh = write_artifact(&dir, "fresh.jsonl", 10, Duration::ZERO)?;
+    manager.mark_active(&active);
+
+    let summary = manager.run_cleanup()?;
+
+    assert_eq!(summary.deleted_items, 1);
+    assert!(!stale.exists());
+    assert!(active.exists());
+    assert!(fresh.exists());
+    Ok(())
+}
+
+#[test]
+fn dry_run_lists_candidates_without_deleting() -> TestResult {
+    let data = tempfile::tempdir()?;
+    let manager = RetentionManager::new(
+        data.path(),
+        policies_for(
+            ArtifactClass::AuditLogs,
+            RetentionPolicy {
+                max_age_secs: Some(HOUR.as_secs()),
+                ..RetentionPolicy::default()
+            },
+        ),
+    );
+    let dir = manager.class_dir(ArtifactClass::AuditLogs);
+    let stale = write_artifact(&dir, "audit-1.log", 42, HOUR * 2)?;
+    write_artifact(&dir, "audit-2.log", 42, Duration::ZERO)?;
+
+    let candidates = manager.dry_run_cleanup()?;
+
+    assert_eq!(candidates.len(), 1);
+    assert_eq!(candidates[0].path, stale);
+    assert_eq!(candidates[0].reason, CleanupReason::MaxAge);
+    assert_eq!(candidates[0].size_bytes, 42);
+    assert!(stale.exists());
+    assert_eq!(manager.last_cleanup(), None);
+    Ok(())
+}
+
+#[test]
+fn min_free_space_trigger_uses_injected_disk_stats() -> TestResult {
+    let data = tempfile::tempdir()?;
+    let policy = RetentionPolicy {
+        min_free_bytes: Some(1_000),
+        ..RetentionPolicy::default()
+    };
+    let low_disk = RetentionManager::new(
+        data.path(),
+        policies_for(ArtifactClass::RawCaptures, policy),
+    )
+    .with_disk_stats(Arc::new(FixedDiskStats(700)));
+    let dir = low_disk.class_dir(ArtifactClass::RawCaptures);
+    let first = write_artifact(&dir, "cap-1.bin", 200, HOUR * 3)?;
+    let second = write_artifact(&dir, "cap-2.bin", 200, HOUR * 2)?;
+    write_artifact(&dir, "cap-3.bin", 200, HOUR)?;
+
+    // 700 free + 200 + 200 reclaimed reaches the 1000 byte floor.
+    let candidates = low_disk.dry_run_cleanup()?;
+    let paths: Vec<_> = candidates.iter().map(|c| c.path.clone()).collect();
+    assert_eq!(paths, vec![first, second]);
+    assert!(
+        candidates
+            .iter()
+            .all(|c| c.reason == CleanupReason::MinFreeSpace)
+    );
+
+    let plenty = RetentionManager::new(
+        data.path(),
+        policies_for(ArtifactClass::RawCaptures, policy),
+    )
+    .with_disk_stats(Arc::new(FixedDiskStats(5_000)));
+    assert!(plenty.dry_run_cleanup()?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn default_policies_delete_nothing_and_round_trip() -> TestResult {
+    let data = tempfile::tempdir()?;
+    let profiles = tempfile::tempdir()?;
+    assert_eq!(
+        RetentionPolicies::load(profiles.path())?,
+        RetentionPolicies::default()
+    );
+
+    let manager = RetentionManager::new(data.path(), RetentionPolicies::load(profiles.path())?);
+    let old = write_artifact(
+        &manager.class_dir(ArtifactClass::DiagnosticsBundles),
+        "bundle.zip",
+        1_000,
+        HOUR * 24 * 365,
+    )?;
+    assert!(manager.dry_run_cleanup()?.is_empty());
+    assert_eq!(manager.run_cleanup()?.deleted_items, 0);
+    assert!(old.exists());
+
+    let policies = policies_for(
+        ArtifactClass::DiagnosticsBundles,
+        RetentionPolicy {
+            max_total_bytes: Some(1 << 30),
+            ..RetentionPolicy::default()
+        },
+    );
+    policies.save(profiles.path())?;
+    assert_eq!(RetentionPolicies::load(profiles.path())?, policies);
+    Ok(())
+}
@@ -0,0 +1,278 @@
+//! Behaviour of WebAssembly-scripted frame transforms.
+
+#![cfg(feature = "scripting")]
+
+use std::time::{Duration, Instant};
+
+use racing_wheel_telemetry_adapters::{NormalizedTelemetry, TelemetryFrame, TelemetryValue};
+use racing_wheel_telemetry_orchestrator::{
+    FrameTransform, ScriptBudget, ScriptDisableReason, ScriptEvent, ScriptProfiles,
+    ScriptTransform, TelemetryService, TransformChain,
+};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const BRAKE_BIAS_SCRIPT: &str = r#"
+(module
+  (import "openracing" "ext_get" (func $ext_get (param i32 i32) (result f32)))
+  (import "openracing" "ext_set" (func $ext_set (param i32 i32 f32) (result i32)))
+  (memory (export "memory") 1)
+  (data (i32.const 0) "brake_pressure_front")
+  (data (i32.const 32) "brake_pressure_rear")
+  (data (i32.const 64) "brake_bias")
+  (func (export "on_frame")
+    (local $front f32)
+    (local $rear f32)
+    (local.set $front (call $ext_get (i32.const 0) (i32.const 20)))
+    (local.set $rear (call $ext_get (i32.const 32) (i32.const 19)))
+    (drop (call $ext_set (i32.const 64) (i32.const 10)
+      (f32.div (local.get $front) (f32.add (local.get $front) (local.get $rear)))))))
+"#;
+
+const INFINITE_LOOP_SCRIPT: &str = r#"
+(module
+  (memory (export "memory") 1)
+  (func (export "on_frame")
+    (loop $spin (br $spin))))
+"#;
+
+const FRAME_COUNTER_SCRIPT: &str = r#"
+(module
+  (import "openracing" "state_get" (func $state_get (param i32 i32) (result f64)))
+  (import "openracing" "state_set" (func $state_set (param i32 i32 f64) (result i32)))
+  (import "openracing" "ext_set" (func $ext_set (param i32 i32 f32) (result i32)))
+  (import "openracing" "field" (func $field (param i32 i32) (result f32)))
+  (import "openracing" "emit" (func $emit (param i32 i32 f32) (result i32)))
+  (memory (export "memory") 1)
+  (data (i32.const 0) "frames")
+  (data (i32.const 16) "frame_count")
+  (data (i32.const 32) "rpm")
+  (data (i32.const 48) "over_rev")
+  (func (export "on_frame")
+    (local $count f64)
+    (local.set $count (call $state_get (i32.const 0) (i32.const 6)))
+    (if (f64.ne (local.get $count) (local.get $count))
+      (then (local.set $count (f64.const 0))))
+    (local.set $count (f64.add (local.get $count) (f64.const 1)))
+    (drop (call $state_set (i32.const 0) (i32.const 6) (local.get $count)))
+    (drop (call $ext_set (i32.const 16) (i32.const 11) (f32.demote_f64 (local.get $count))))
+    (if (f32.gt (call $field (i32.const 32) (i32.const 3)) (f32.const 8000))
+      (then (drop (call $emit (i32.const 48) (i32.const 8) (f32.const 1)))))))
+"#;
+
+fn frame(data: NormalizedTelemetry) -> TelemetryFrame {
+    TelemetryFrame::new(data, 0, 0, 0)
+}
+
+#[test]
+fn derived_ratio_script_writes_extended_value() -> TestResult {
+    let mut script =
+        ScriptTransform::from_source("brake_bias", BRAKE_BIAS_SCRIPT, ScriptBudget::default())?;
+    let mut frame = frame(
+        NormalizedTelemetry::builder()
+            .extended("brake_pressure_front", TelemetryValue::Float(60.0))
+            .extended("brake_pressure_rear", TelemetryValue::Float(40.0))
+            .build(),
+    );
+
+    script.apply(&mut frame);
+
+    match frame.data.extended.get("brake_bias") {
+        Some(TelemetryValue::Float(bias)) => assert!((bias - 0.6).abs() < 1e-6),
+        other => return Err(format!("expected brake_bias float, got {other:?}").into()),
+    }
+    Ok(())
+}
+
+#[test]
+fn infinite_loop_script_is_disabled_within_budget() -> TestResult {
+    let budget = ScriptBudget {
+        fuel_per_frame: 50_000,
+        ..ScriptBudget::default()
+    };
+    let mut script = ScriptTransform::from_source("spin", INFINITE_LOOP_SCRIPT, budget)?;
+    let mut events = script.subscribe();
+    let mut frame = frame(NormalizedTelemetry::default());
+
+    let started = Instant::now();
+    script.apply(&mut frame);
+    assert!(started.elapsed() < Duration::from_secs(1));
+
+    assert!(!script.is_enabled());
+    assert_eq!(
+        events.try_recv()?,
+        ScriptEvent::Disabled {
+            script: "spin".to_string(),
+            reason: ScriptDisableReason::BudgetExceeded {
+                fuel_per_frame: 50_000
+            },
+        }
+    );
+
+    // Disabled scripts are skipped without further events.
+    script.apply(&mut frame);
+    assert!(events.try_recv().is_err());
+    Ok(())
+}
+
+#[test]
+fn state_persists_across_frames_and_resets_on_session_change() -> TestResult {
+    let script =
+        ScriptTransform::from_source("counter", FRAME_COUNTER_SCRIPT, ScriptBudget::default())?;
+    let mut events = script.subscribe();
+    let mut chain = TransformChain::new();
+    chain.push(Box::new(script));
+
+    let count_after = |chain: &mut TransformChain, session: &str, rpm: f32| {
+        let mut frame = frame(
+            NormalizedTelemetry::builder()
+                .session_id(session)
+                .rpm(rpm)
+                .build(),
+        );
+        chain.apply(&mut frame);
+        frame.data.extended.get("frame_count").cloned()
+    };
+
+    count_after(&mut chain, "practice", 5000.0);
+    count_after(&mut chain, "practice", 5000.0);
+    assert_eq!(
+        count_after(&mut chain, "practice", 9000.0),
+        Some(TelemetryValue::Float(3.0))
+    );
+    assert_eq!(
+        count_after(&mut chain, "race", 5000.0),
+        Some(TelemetryValue::Float(1.0))
+    );
+
+    assert_eq!(
+        events.try_recv()?,
+        ScriptEvent::Emitted {
+            script: "counter".to_string(),
+            name: "over_rev".to_string(),
+            value: 1.0,
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn scripts_requesting_wasi_are_rejected() {
+    let wasi_script = r#"
+(module
+  (import "wasi_snapshot_preview1" "fd_write"
+    (func (param i32 i32 i32 i32) (result i32)))
+  (memory (export "memory") 1)
+  (func (export "on_frame")))
+"#;
+
+    let result = ScriptTransform::from_source("wasi", wasi_script, ScriptBudget::default());
+    assert!(result.is_err());
+}
+
+/// Apply frames until `done` holds, as the forwarding task would.
+fn apply_until(
+    script: &mut ScriptTransform,
+    done: impl Fn(&ScriptTransform) -> bool,
+) -> Result<(), String> {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !done(script) {
+        if Instant::now() > deadline {
+            return Err("script was not reloaded in time".to_string());
+        }
+        script.apply(&mut frame(NormalizedTelemetry::default()));
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    Ok(())
+}
+
+#[test]
+fn file_scripts_hot_reload_and_reenable() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("spin.wat");
+    std::fs::write(&path, INFINITE_LOOP_SCRIPT)?;
+
+    let mut script = ScriptTransform::from_file(&path, ScriptBudget::default())?
+        .with_reload_interval(Duration::from_millis(10));
+    let mut events = script.subscribe();
+    script.apply(&mut frame(NormalizedTelemetry::default()));
+    assert!(!script.is_enabled());
+
+    std::fs::write(&path, BRAKE_BIAS_SCRIPT)?;
+    let file = std::fs::File::options().write(true).open(&path)?;
+    file.set_modified(std::time::SystemTime::now() + Duration::from_secs(5))?;
+
+    apply_until(&mut script, ScriptTransform::is_enabled)?;
+    let reloaded = std::iter::from_fn(|| events.try_recv().ok()).any(|event| {
+        event
+            == ScriptEvent::Reloaded {
+                script: "spin".to_string(),
+            }
+    });
+    assert!(reloaded);
+    Ok(())
+}
+
+#[test]
+fn broken_new_version_keeps_the_running_script() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("counter.wat");
+    std::fs::write(&path, FRAME_COUNTER_SCRIPT)?;
+    let mut script = ScriptTransform::from_file(&path, ScriptBudget::default())?
+        .with_reload_interval(Duration::from_millis(10));
+    script.apply(&mut frame(NormalizedTelemetry::default()));
+
+    std::fs::write(&path, "(module (func (export \"on_frame\")")?;
+    let file = std::fs::File::options().write(true).open(&path)?;
+    file.set_modified(std::time::SystemTime::now() + Duration::from_secs(5))?;
+    std::thread::sleep(Duration::from_millis(100));
+
+    let mut next = frame(NormalizedTelemetry::default());
+    script.apply(&mut next);
+    assert!(script.is_enabled());
+    assert_eq!(
+        next.data.extended.get("frame_count"),
+        Some(&TelemetryValue::Float(2.0))
+    );
+    Ok(())
+}
+
+#[test]
+fn profile_scripts_are_registered_per_game() -> TestResult {
+    let profile_dir = tempfile::tempdir()?;
+    std::fs::create_dir(profile_dir.path().join("scripts"))?;
+    std::fs::write(
+        profile_dir.path().join("scripts/brake_bias.wat"),
+        BRAKE_BIAS_SCRIPT,
+    )?;
+    let counter = profile_dir.path().join("scripts/counter.wat");
+    std::fs::write(&counter, FRAME_COUNTER_SCRIPT)?;
+
+    let mut profiles = ScriptProfiles::default();
+    profiles.register("acc", "scripts/brake_bias.wat");
+    profiles.register("acc", &counter);
+    profiles.register("iracing", "scripts/counter.wat");
+    profiles.save(profile_dir.path())?;
+    assert_eq!(ScriptProfiles::load(profile_dir.path())?, profiles);
+
+    let mut service = TelemetryService::new();
+    assert_eq!(service.load_profile_scripts(profile_dir.path())?, 3);
+    assert_eq!(service.transform_names("acc"), ["brake_bias", "counter"]);
+    assert_eq!(service.transform_names("iracing"), ["counter"]);
+    assert!(service.transform_names("rfactor2").is_empty());
+    Ok(())
+}
+
+#[test]
+fn missing_script_profiles_register_nothing() -> TestResult {
+    let profile_dir = tempfile::tempdir()?;
+    assert_eq!(
+        ScriptProfiles::load(profile_dir.path())?,
+        ScriptProfiles::default()
+    );
+    assert_eq!(
+        TelemetryService::new().load_profile_scripts(profile_dir.path())?,
+        0
+    );
+    Ok(())
+}
@@ -0,0 +1,98 @@
+//! Self-tests walk registry, config, transport and first frame in order.
+
+use std::net::{Ipv4Addr, UdpSocket};
+use std::time::Duration;
+
+use racing_wheel_telemetry_adapters::{F1_25Adapter, MockAdapter};
+use racing_wheel_telemetry_orchestrator::{
+    SelfTestOptions, SelfTestStep, StepOutcome, TelemetryService,
+};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn quick() -> SelfTestOptions {
+    SelfTestOptions {
+        frame_timeout: Duration::from_secs(2),
+        ..SelfTestOptions::default()
+    }
+}
+
+#[tokio::test]
+async fn game_without_a_config_writer_fails_the_registry_step() -> TestResult {
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(MockAdapter::new("bench_rig".to_string())));
+
+    let report = service.self_test("bench_rig", quick()).await;
+    assert!(!report.passed());
+    let registry = report
+        .step(SelfTestStep::Registry)
+        .ok_or("no registry step")?;
+    assert_eq!(registry.outcome, StepOutcome::Failed);
+    assert!(
+        registry.hint.contains("no config writer"),
+        "{}",
+        registry.hint
+    );
+    assert_eq!(report.outcome(SelfTestStep::Config), StepOutcome::Skipped);
+    // The rest of the path is still checked.
+    assert_eq!(
+        report.outcome(SelfTestStep::FirstFrame),
+        StepOutcome::Passed
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn occupied_port_is_reported_apart_from_silence() -> TestResult {
+    let holder = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+    let port = holder.local_addr()?.port();
+    let mut service = TelemetryService::new();
+    service.register_adapter(Box::new(F1_25Adapter::new().with_port(port)));
+
+    let report = service.self_test("f1_25", quick()).await;
+    assert_eq!(report.outcome(SelfTestStep::Registry), StepOutcome::Passed);
+    let transport = report
+        .step(SelfTestStep::Transport)
+        .ok_or("no transport step")?;
+    assert_eq!(transport.outcome, StepOutcome::Failed);
+    assert!(
+        transport
+            .hint
+            .contains(&format!("port {port} is in use by")),
+        "{}",
+        transport.hint
+    );
+    let conflict = report.port_conflict.as_ref().ok_or("no port conflict")?;
+    assert_eq!(conflict.port, port);
+    assert!(conflict.pid.is_none_or(|pid| pid == std::process::id()));
+    // Nothing is waited for on a port the adapter cannot bind.
+    assert_eq!(
+        report.outcome(SelfTestStep::FirstFrame),
+        StepOutcome::Skipped
+    );
+    assert!(report.first_frame.is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn mock_frames_pass_every_step_that_runs() -> TestResult {
+    let mut service = TelemetryService::new();
+    service.register_adapter(Box::new(MockAdapter::new("acc".to_string())));
+
+    let report = service.self_test("acc", quick()).await;
+    assert!(report.passed(), "{report:?}");
+    let outcomes: Vec<_> = report.steps.iter().map(|step| step.outcome).collect();
+    assert_eq!(
+        outcomes,
+        [
+            StepOutcome::Passed,
+            StepOutcome::Skipped,
+            StepOutcome::Skipped,
+            StepOutcome::Passed
+        ]
+    );
+    let frame = report.first_frame.ok_or("no first frame")?;
+    assert_eq!(frame.sequence, 0);
+    assert!(frame.rpm > 0.0);
+    Ok(())
+}
@@ -0,0 +1,241 @@
+//! Session and lap boundaries found in synthetic streams.
+
+use anyhow::Result;
+use racing_wheel_telemetry_adapters::{
+    NormalizedTelemetry, SessionTiming, TelemetryFlags, TelemetryFrame,
+};
+use racing_wheel_telemetry_orchestrator::{
+    SegmenterConfig, SessionEndReason, SessionEvent, SessionSegmenter, segment,
+};
+
+const HZ: u64 = 10;
+const STEP_NS: u64 = 1_000_000_000 / HZ;
+
+/// Builds a 10 Hz stream one frame at a time.
+struct Stream {
+    frames: Vec<TelemetryFrame>,
+    timestamp_ns: u64,
+    track: &'static str,
+}
+
+impl Stream {
+    fn new(track: &'static str) -> Self {
+        Self {
+            frames: Vec::new(),
+            timestamp_ns: 1_000_000_000,
+            track,
+        }
+    }
+
+    fn push(&mut self, mut data: NormalizedTelemetry) {
+        data.track_id = Some(self.track.to_string());
+        data.car_id = Some("gt3".to_string());
+        let sequence = self.frames.len() as u64;
+        self.frames
+            .push(TelemetryFrame::new(data, self.timestamp_ns, sequence, 0));
+        self.timestamp_ns += STEP_NS;
+    }
+
+    fn drive(&mut self, seconds: u64) {
+        for _ in 0..seconds * HZ {
+            self.push(NormalizedTelemetry {
+                speed_ms: 45.0,
+                rpm: 6500.0,
+                ..NormalizedTelemetry::default()
+            });
+        }
+    }
+
+    fn stop(&mut self, seconds: u64) {
+        for _ in 0..seconds * HZ {
+            self.push(NormalizedTelemetry::default());
+        }
+    }
+
+    fn skip(&mut self, seconds: u64) {
+        self.timestamp_ns += seconds * 1_000_000_000;
+    }
+}
+
+fn lap_frame(
+    lap_number: i32,
+    current_lap_ms: f64,
+    last_lap_ms: Option<f64>,
+) -> NormalizedTelemetry {
+    NormalizedTelemetry {
+        speed_ms: 50.0,
+        rpm: 7000.0,
+        timing: Some(SessionTiming {
+            lap_number: Some(lap_number),
+            current_lap_ms: Some(current_lap_ms),
+            last_lap_ms,
+            ..SessionTiming::default()
+        }),
+        ..NormalizedTelemetry::default()
+    }
+}
+
+#[test]
+fn brief_pause_does_not_split_a_session() -> Result<()> {
+    let mut stream = Stream::new("spa");
+    stream.drive(60);
+    stream.stop(10);
+    stream.drive(60);
+
+    let spans = segment(&stream.frames);
+    assert_eq!(spans.len(), 1);
+    let Some(span) = spans.first() else {
+        return Err(anyhow::anyhow!("no session found"));
+    };
+    assert_eq!(span.frames, 0..stream.frames.len());
+    assert_eq!(span.end_reason, SessionEndReason::StreamEnded);
+    assert_eq!(span.track_id.as_deref(), Some("spa"));
+    Ok(())
+}
+
+#[test]
+fn long_idle_and_frame_gap_split_sessions() -> Result<()> {
+    let mut stream = Stream::new("spa");
+    stream.drive(20);
+    stream.stop(45);
+    stream.drive(20);
+    stream.skip(120);
+    stream.drive(20);
+
+    let spans = segment(&stream.frames);
+    let reasons: Vec<_> = spans.iter().map(|span| span.end_reason).collect();
+    assert_eq!(
+        reasons,
+        [
+            SessionEndReason::Idle,
+            SessionEndReason::FrameGap,
+            SessionEndReason::StreamEnded
+        ]
+    );
+    // Trailing idle frames are not part of the session that idled out.
+    assert_eq!(spans[0].frames, 0..200);
+    assert_eq!(spans[1].frames, 650..850);
+    assert_eq!(spans[2].frames, 850..1050);
+    Ok(())
+}
+
+#[test]
+fn track_change_starts_a_new_session() -> Result<()> {
+    let mut stream = Stream::new("spa");
+    stream.drive(20);
+    stream.track = "imola";
+    stream.drive(20);
+
+    let spans = segment(&stream.frames);
+    assert_eq!(spans.len(), 2);
+    assert_eq!(spans[0].end_reason, SessionEndReason::IdentityChanged);
+    assert_eq!(spans[0].track_id.as_deref(), Some("spa"));
+    assert_eq!(spans[1].track_id.as_deref(), Some("imola"));
+    assert_eq!(spans[0].frames.end, spans[1].frames.start);
+    Ok(())
+}
+
+#[test]
+fn two_lap_run_reports_each_lap_once() -> Result<()> {
+    let mut stream = Stream::new("spa");
+    let lap_ms = [92_400.0, 91_800.0];
+    let mut last_lap = None;
+    for (index, duration) in lap_ms.iter().enumerate() {
+        let lap_number = index as i32 + 1;
+        let mut elapsed = 0.0;
+        while elapsed < *duration {
+            stream.push(lap_frame(lap_number, elapsed, last_lap));
+            elapsed += 100.0;
+        }
+        // The counter advances a frame before the game publishes the time.
+        stream.push(lap_frame(lap_number + 1, 20.0, last_lap));
+        last_lap = Some(*duration);
+        stream.push(lap_frame(lap_number + 1, 120.0, last_lap));
+    }
+    let mut finish = lap_frame(3, 220.0, last_lap);
+    finish.flags = TelemetryFlags {
+        checkered_flag: true,
+        ..TelemetryFlags::default()
+    };
+    stream.push(finish);
+    // Cool-down lap under the checkered flag.
+    for step in 0..300 {
+        let mut cool_down = lap_frame(3, 320.0 + f64::from(step) * 100.0, last_lap);
+        cool_down.flags.checkered_flag = true;
+        stream.push(cool_down);
+    }
+
+    let mut segmenter = SessionSegmenter::new(SegmenterConfig::default());
+    let events: Vec<_> = stream
+        .frames
+        .iter()
+        .flat_map(|frame| segmenter.push(frame))
+        .collect();
+    assert!(segmenter.finish().is_none());
+
+    let laps: Vec<_> = events
+        .iter()
+        .filter_map(|event| match event {
+            SessionEvent::LapCompleted {
+                lap_number,
+                lap_time_ms,
+            } => Some((*lap_number, *lap_time_ms)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(laps.len(), 2, "{laps:?}");
+    assert_eq!(laps[0].0, 1);
+    assert_eq!(laps[1].0, 2);
+    for ((_, measured), expected) in laps.iter().zip(lap_ms) {
+        let Some(measured) = measured else {
+            return Err(anyhow::anyhow!("lap without a time"));
+        };
+        assert!(
+            (measured - expected).abs() <= 100.0,
+            "{measured} vs {expected}"
+        );
+    }
+
+    assert!(matches!(
+        events.first(),
+        Some(SessionEvent::SessionStarted { .. })
+    ));
+    let ended: Vec<_> = events
+        .iter()
+        .filter_map(|event| match event {
+            SessionEvent::SessionEnded(span) => Some(span),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(ended.len(), 1);
+    assert_eq!(ended[0].end_reason, SessionEndReason::Checkered);
+    assert_eq!(ended[0].laps.len(), 2);
+    Ok(())
+}
+
+#[test]
+fn lap_timer_rollover_counts_laps_without_a_counter() -> Result<()> {
+    let mut stream = Stream::new("spa");
+    for _lap in 0..3 {
+        for step in 0..600 {
+            stream.push(NormalizedTelemetry {
+                speed_ms: 40.0,
+                rpm: 6000.0,
+                current_lap_time_s: step as f32 * 0.1,
+                ..NormalizedTelemetry::default()
+            });
+        }
+    }
+
+    let spans = segment(&stream.frames);
+    let Some(span) = spans.first() else {
+        return Err(anyhow::anyhow!("no session found"));
+    };
+    let numbers: Vec<_> = span.laps.iter().map(|lap| lap.lap_number).collect();
+    assert_eq!(numbers, [1, 2]);
+    let Some(time) = span.laps[0].lap_time_ms else {
+        return Err(anyhow::anyhow!("lap without a time"));
+    };
+    assert!((time - 59_900.0).abs() < 1.0, "{time}");
+    Ok(())
+}
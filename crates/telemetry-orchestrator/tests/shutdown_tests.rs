@@ -0,0 +1,193 @@
+//! Service shutdown: queued frames reach the recording, and tasks stop.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use racing_wheel_telemetry_adapters::{
+    MockAdapter, NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver,
+};
+use racing_wheel_telemetry_orchestrator::TelemetryService;
+use racing_wheel_telemetry_recorder::TelemetryRecorder;
+use tokio::time::{sleep, timeout};
+
+const GAME_ID: &str = "burst_source";
+const BURST_FRAMES: usize = 150;
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Sends [`BURST_FRAMES`] frames, then holds its stream open until the
+/// receiver goes away.
+struct BurstAdapter {
+    inner: MockAdapter,
+    sent: Arc<AtomicUsize>,
+    live: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl TelemetryAdapter for BurstAdapter {
+    fn game_id(&self) -> &str {
+        self.inner.game_id()
+    }
+
+    async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        let (tx, rx) = tokio::sync::mpsc::channel(BURST_FRAMES + 1);
+        let sent = Arc::clone(&self.sent);
+        let live = Arc::clone(&self.live);
+        live.fetch_add(1, Ordering::SeqCst);
+        tokio::spawn(async move {
+            for sequence in 0..BURST_FRAMES as u64 {
+                let frame = TelemetryFrame::new(
+                    NormalizedTelemetry::default(),
+                    sequence * 1_000_000,
+                    sequence,
+                    0,
+                );
+                if tx.send(frame).await.is_err() {
+                    break;
+                }
+                sent.fetch_add(1, Ordering::SeqCst);
+                sleep(Duration::from_millis(1)).await;
+            }
+            tx.closed().await;
+            live.fetch_sub(1, Ordering::SeqCst);
+        });
+        Ok(rx)
+    }
+
+    async fn stop_monitoring(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn normalize(&self, raw: &[u8]) -> Result<NormalizedTelemetry> {
+        self.inner.normalize(raw)
+    }
+
+    fn expected_update_rate(&self) -> Duration {
+        Duration::from_millis(1)
+    }
+
+    async fn is_game_running(&self) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+async fn wait_until(condition: impl Fn() -> bool) -> Result<()> {
+    timeout(SHUTDOWN_TIMEOUT, async {
+        while !condition() {
+            sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn shutdown_records_every_frame_queued_before_it() -> Result<()> {
+    let sent = Arc::new(AtomicUsize::new(0));
+    let live = Arc::new(AtomicUsize::new(0));
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(BurstAdapter {
+        inner: MockAdapter::new(GAME_ID.to_string()),
+        sent: Arc::clone(&sent),
+        live: Arc::clone(&live),
+    }));
+    service.set_rate_limit(GAME_ID, 100_000);
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("shutdown.json");
+    service.enable_recording(path.clone())?;
+
+    // Never read: the forwarder fills this channel and the rest of the
+    // burst stays queued in the adapter's.
+    let _unread = service.start_monitoring(GAME_ID).await?;
+    wait_until(|| sent.load(Ordering::SeqCst) == BURST_FRAMES).await?;
+
+    let report = service.shutdown(SHUTDOWN_TIMEOUT).await;
+    assert_eq!(report.games_stopped, [GAME_ID]);
+    assert!(!report.timed_out);
+    assert!(report.frames_drained > 0, "{report:?}");
+    assert_eq!(
+        report.recording.as_ref().map(|saved| saved.frame_count),
+        Some(BURST_FRAMES)
+    );
+
+    let recording = TelemetryRecorder::load_recording(&path)?;
+    assert_eq!(recording.metadata.game_id, GAME_ID);
+    assert_eq!(recording.metadata.frame_count, BURST_FRAMES);
+    let sequences: Vec<u64> = recording.frames.iter().map(|f| f.sequence).collect();
+    assert_eq!(sequences, (0..BURST_FRAMES as u64).collect::<Vec<_>>());
+
+    // Dropping its queue is the adapter task's cue to stop.
+    wait_until(|| live.load(Ordering::SeqCst) == 0).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn shutdown_during_mock_monitoring_leaves_a_loadable_recording() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(MockAdapter::new("mock_shutdown".to_string())));
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("nested").join("mock.json");
+    service.enable_recording(path.clone())?;
+
+    let mut rx = service.start_monitoring("mock_shutdown").await?;
+    let mut received = Vec::new();
+    for _ in 0..3 {
+        let frame = timeout(SHUTDOWN_TIMEOUT, rx.recv())
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("stream ended early"))?;
+        received.push(frame.sequence);
+    }
+
+    let report = service.shutdown(SHUTDOWN_TIMEOUT).await;
+    assert_eq!(report.games_stopped, ["mock_shutdown"]);
+    assert!(!report.timed_out);
+    assert!(report.recording_error.is_none());
+
+    let recording = TelemetryRecorder::load_recording(&path)?;
+    assert_eq!(recording.metadata.frame_count, recording.frames.len());
+    let recorded: Vec<u64> = recording.frames.iter().map(|f| f.sequence).collect();
+    assert!(recorded.starts_with(&received), "{recorded:?}");
+    assert!(recorded.windows(2).all(|pair| pair[1] == pair[0] + 1));
+
+    // The forwarder is gone, so the stream ends once drained.
+    timeout(SHUTDOWN_TIMEOUT, async {
+        while rx.recv().await.is_some() {}
+    })
+    .await?;
+    assert!(!service.is_monitoring("mock_shutdown"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn shutdown_ends_sessions_and_leaves_the_service_usable() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(MockAdapter::new("mock_shutdown".to_string())));
+
+    let mut session = service.start_monitoring_session("mock_shutdown").await?;
+    let report = service.shutdown(SHUTDOWN_TIMEOUT).await;
+    assert_eq!(report.games_stopped, ["mock_shutdown"]);
+    assert_eq!(report.recording, None);
+    timeout(SHUTDOWN_TIMEOUT, async {
+        while session.recv().await.is_some() {}
+    })
+    .await?;
+    session.stop().await?;
+
+    let mut rx = service.start_monitoring("mock_shutdown").await?;
+    timeout(SHUTDOWN_TIMEOUT, rx.recv())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("stream ended early"))?;
+
+    let idle = service.shutdown(SHUTDOWN_TIMEOUT).await;
+    assert_eq!(idle.games_stopped, ["mock_shutdown"]);
+    assert!(
+        service
+            .shutdown(SHUTDOWN_TIMEOUT)
+            .await
+            .games_stopped
+            .is_empty()
+    );
+    Ok(())
+}
@@ -0,0 +1,244 @@
+//! Sink encoding auto-selection tests driven by a high-rate `MockAdapter`.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use racing_wheel_schemas::telemetry::decode_frame;
+use racing_wheel_telemetry_adapters::{
+    MockAdapter, NormalizedTelemetry, OpponentSnapshot, SessionTiming, TelemetryFrame,
+    TelemetryValue, TireCorner, TireData,
+};
+use racing_wheel_telemetry_orchestrator::sinks::{BinaryFrameEncoder, JsonFrameEncoder};
+use racing_wheel_telemetry_orchestrator::{
+    AutoEncodingPolicy, FrameEncoder, SinkCapabilities, SinkCost, SinkEncoding, SinkEvent, SinkHub,
+    SinkRegistration, TelemetryService, TelemetrySink, WireEncoding,
+};
+use tokio::sync::broadcast::error::TryRecvError;
+
+const GAME_ID: &str = "mock_high_rate";
+const FRAMES: usize = 120;
+
+/// JSON encoder that burns a fixed amount of time per frame.
+struct SlowJsonEncoder {
+    delay: Duration,
+}
+
+impl FrameEncoder for SlowJsonEncoder {
+    fn encode(&self, frame: &TelemetryFrame, out: &mut Vec<u8>) -> Result<()> {
+        std::thread::sleep(self.delay);
+        JsonFrameEncoder.encode(frame, out)
+    }
+}
+
+struct NullSink;
+
+impl TelemetrySink for NullSink {
+    fn write(&mut self, _encoding: WireEncoding, _payload: &[u8]) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Sink taking far longer per frame than the adapter's frame interval.
+struct StallingSink;
+
+impl TelemetrySink for StallingSink {
+    fn write(&mut self, _encoding: WireEncoding, _payload: &[u8]) -> Result<()> {
+        std::thread::sleep(Duration::from_millis(50));
+        Ok(())
+    }
+}
+
+/// Sink keeping every payload it is handed.
+struct CapturingSink(Arc<Mutex<Vec<Vec<u8>>>>);
+
+impl TelemetrySink for CapturingSink {
+    fn write(&mut self, _encoding: WireEncoding, payload: &[u8]) -> Result<()> {
+        self.0
+            .lock()
+            .map_err(|_| anyhow::anyhow!("poisoned"))?
+            .push(payload.to_vec());
+        Ok(())
+    }
+}
+
+fn slowed_service() -> TelemetryService {
+    let hub = SinkHub::with_encoders(
+        AutoEncodingPolicy {
+            budget_fraction: 0.1,
+            window_frames: 20,
+        },
+        Arc::new(SlowJsonEncoder {
+            delay: Duration::from_millis(2),
+        }),
+        Arc::new(BinaryFrameEncoder),
+    );
+    let mut service = TelemetryService::from_support_matrix(None).with_sink_hub(hub);
+    service.register_adapter(Box::new(MockAdapter::with_update_rate(
+        GAME_ID.to_string(),
+        Duration::from_millis(2),
+    )));
+    service
+}
+
+async fn pump(service: &mut TelemetryService, frames: usize) -> Result<()> {
+    let mut rx = service.start_monitoring(GAME_ID).await?;
+    for _ in 0..frames {
+        tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("telemetry stream closed early"))?;
+    }
+    Ok(())
+}
+
+/// Wait for the sink thread to encode at least `frames` frames for every sink.
+async fn settled_costs(service: &TelemetryService, frames: usize) -> Result<Vec<SinkCost>> {
+    let settled = async {
+        loop {
+            let costs = service.sink_costs();
+            if costs
+                .iter()
+                .all(|cost| cost.frames_encoded >= frames as u64)
+            {
+                return costs;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    };
+    Ok(tokio::time::timeout(Duration::from_secs(5), settled).await?)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn slow_json_auto_sink_switches_exactly_once() -> Result<()> {
+    let mut service = slowed_service();
+    let mut events = service.subscribe_sink_events();
+    service.register_sink(SinkRegistration::new(
+        "overlay",
+        SinkEncoding::Auto,
+        SinkCapabilities::ANY,
+        Box::new(NullSink),
+    ))?;
+
+    pump(&mut service, FRAMES).await?;
+    let costs = settled_costs(&service, FRAMES).await?;
+
+    let event = events.try_recv()?;
+    let SinkEvent::EncodingSwitched {
+        sink,
+        from,
+        to,
+        mean_encode_ns,
+        mean_frame_interval_ns,
+        mean_bytes_per_frame,
+    } = event;
+    assert_eq!(sink, "overlay");
+    assert_eq!(from, WireEncoding::Json);
+    assert_eq!(to, WireEncoding::Binary);
+    assert!(mean_encode_ns >= 2_000_000);
+    assert!(mean_frame_interval_ns > 0);
+    assert!(mean_bytes_per_frame > 0);
+    assert!(matches!(events.try_recv(), Err(TryRecvError::Empty)));
+
+    assert_eq!(costs.len(), 1);
+    let cost = &costs[0];
+    assert_eq!(cost.active_encoding, WireEncoding::Binary);
+    assert_eq!(cost.encoding_switches, 1);
+    assert!(cost.frames_encoded >= FRAMES as u64);
+    assert!(cost.bytes_emitted > 0);
+    assert!(cost.encode_ns_total > 0);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn json_only_consumer_never_switches() -> Result<()> {
+    let mut service = slowed_service();
+    let mut events = service.subscribe_sink_events();
+    service.register_sink(SinkRegistration::new(
+        "legacy_bridge",
+        SinkEncoding::Auto,
+        SinkCapabilities::JSON_ONLY,
+        Box::new(NullSink),
+    ))?;
+
+    pump(&mut service, FRAMES).await?;
+    let costs = settled_costs(&service, FRAMES).await?;
+
+    assert!(matches!(events.try_recv(), Err(TryRecvError::Empty)));
+    assert_eq!(costs.len(), 1);
+    assert_eq!(costs[0].active_encoding, WireEncoding::Json);
+    assert_eq!(costs[0].encoding_switches, 0);
+    assert!(costs[0].mean_encode_ns() >= 2_000_000.0);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn slow_sink_does_not_hold_up_forwarding() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(MockAdapter::with_update_rate(
+        GAME_ID.to_string(),
+        Duration::from_millis(2),
+    )));
+    service.register_sink(SinkRegistration::new(
+        "stalled",
+        SinkEncoding::Json,
+        SinkCapabilities::ANY,
+        Box::new(StallingSink),
+    ))?;
+
+    let started = Instant::now();
+    pump(&mut service, 40).await?;
+    // Delivered inline, 40 frames would take at least two seconds.
+    let elapsed = started.elapsed();
+    assert!(elapsed < Duration::from_secs(1), "{elapsed:?}");
+    Ok(())
+}
+
+#[test]
+fn binary_sink_payload_decodes_to_the_dispatched_frame() -> Result<()> {
+    let corner = TireCorner {
+        surface_temp_c: Some(87.5),
+        pressure_kpa: Some(172.0),
+        wear_fraction: Some(0.1),
+        slip_ratio: None,
+    };
+    let mut data = NormalizedTelemetry::builder().rpm(7_200.0).gear(4).build();
+    data.tires = Some(TireData::from_corners([corner; 4]));
+    data.timing = Some(SessionTiming {
+        current_lap_ms: Some(61_250.0),
+        lap_number: Some(9),
+        ..SessionTiming::default()
+    });
+    data.world_position = Some([120.5, 3.0, -44.25]);
+    data.lap_distance_fraction = Some(0.62);
+    data.extended
+        .insert("tc_level".to_string(), TelemetryValue::Integer(4));
+    let mut frame = TelemetryFrame::new(data, 5_000_000, 17, 1_024);
+    frame.opponents = vec![OpponentSnapshot {
+        car_index: 3,
+        position: 2,
+        gap_to_player_ms: Some(1_850),
+        speed_ms: 64.0,
+        lap_number: 9,
+        in_pits: false,
+        driver_name: Some("Rival".to_string()),
+    }];
+
+    let payloads = Arc::new(Mutex::new(Vec::new()));
+    let hub = SinkHub::default();
+    hub.register(SinkRegistration::new(
+        "recorder",
+        SinkEncoding::Binary,
+        SinkCapabilities::BINARY_ONLY,
+        Box::new(CapturingSink(Arc::clone(&payloads))),
+    ))?;
+    hub.dispatch(&frame);
+
+    let payloads = payloads.lock().map_err(|_| anyhow::anyhow!("poisoned"))?;
+    assert_eq!(payloads.len(), 1);
+    let decoded = decode_frame(&payloads[0])?;
+    assert_eq!(
+        serde_json::to_value(&decoded)?,
+        serde_json::to_value(&frame)?
+    );
+    Ok(())
+}
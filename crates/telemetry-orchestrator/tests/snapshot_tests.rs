@@ -0,0 +1,204 @@
+//! The snapshot cache tracks each game's latest frame, and the endpoint
+//! serves it over newline-delimited JSON.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use racing_wheel_telemetry_adapters::{
+    NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver,
+};
+use racing_wheel_telemetry_orchestrator::{SnapshotEndpoint, TelemetryService};
+use serde_json::{Value, json};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio::time::{sleep, timeout};
+
+const RECV_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Adapter fed from a test-owned channel.
+struct ChannelAdapter {
+    game_id: &'static str,
+    rx: Mutex<Option<TelemetryReceiver>>,
+}
+
+#[async_trait]
+impl TelemetryAdapter for ChannelAdapter {
+    fn game_id(&self) -> &str {
+        self.game_id
+    }
+
+    async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        self.rx
+            .lock()
+            .map_err(|_| anyhow::anyhow!("poisoned"))?
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("already monitoring"))
+    }
+
+    async fn stop_monitoring(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn normalize(&self, _raw: &[u8]) -> Result<NormalizedTelemetry> {
+        Ok(NormalizedTelemetry::default())
+    }
+
+    fn expected_update_rate(&self) -> Duration {
+        Duration::from_millis(100)
+    }
+
+    async fn is_game_running(&self) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+struct Feed {
+    tx: mpsc::Sender<TelemetryFrame>,
+    forwarded: TelemetryReceiver,
+}
+
+impl Feed {
+    /// Push a frame with `rpm` through the service and wait until it is
+    /// forwarded, so the cache has stored it.
+    async fn push(&mut self, sequence: u64, rpm: f32) -> Result<()> {
+        let data = NormalizedTelemetry {
+            rpm,
+            ..NormalizedTelemetry::default()
+        };
+        self.tx
+            .send(TelemetryFrame::new(data, sequence, sequence, 0))
+            .await?;
+        timeout(RECV_TIMEOUT, self.forwarded.recv())
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("forwarding stopped"))?;
+        Ok(())
+    }
+}
+
+async fn monitor(service: &mut TelemetryService, game_id: &'static str) -> Result<Feed> {
+    let (tx, rx) = mpsc::channel(16);
+    service.register_adapter(Box::new(ChannelAdapter {
+        game_id,
+        rx: Mutex::new(Some(rx)),
+    }));
+    let forwarded = service.start_monitoring(game_id).await?;
+    Ok(Feed { tx, forwarded })
+}
+
+async fn request<S>(client: &mut BufReader<S>, line: &str) -> Result<Value>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    client.get_mut().write_all(line.as_bytes()).await?;
+    client.get_mut().write_all(b"\n").await?;
+    let mut response = String::new();
+    timeout(RECV_TIMEOUT, client.read_line(&mut response)).await??;
+    Ok(serde_json::from_str(&response)?)
+}
+
+#[tokio::test]
+async fn cache_updates_as_frames_arrive() -> Result<()> {
+    let mut service = TelemetryService::new();
+    let mut acc = monitor(&mut service, "acc").await?;
+    let mut iracing = monitor(&mut service, "iracing").await?;
+    let cache = service.snapshot_cache();
+    assert!(cache.latest("acc").is_none());
+
+    acc.push(1, 4000.0).await?;
+    let Some(latest) = cache.latest("acc") else {
+        return Err(anyhow::anyhow!("no frame cached for acc"));
+    };
+    assert_eq!(latest.sequence, 1);
+    assert!(cache.latest("iracing").is_none());
+
+    acc.push(2, 5000.0).await?;
+    iracing.push(9, 6000.0).await?;
+    assert_eq!(
+        cache.latest("acc").map(|frame| frame.data.rpm),
+        Some(5000.0)
+    );
+    let all = cache.latest_all();
+    assert_eq!(all.len(), 2);
+    assert_eq!(all.get("iracing").map(|frame| frame.sequence), Some(9));
+    Ok(())
+}
+
+#[tokio::test]
+async fn age_grows_while_frames_stop() -> Result<()> {
+    let mut service = TelemetryService::new();
+    let mut acc = monitor(&mut service, "acc").await?;
+    let cache = service.snapshot_cache();
+
+    acc.push(1, 4000.0).await?;
+    let fresh = cache
+        .age("acc")
+        .ok_or_else(|| anyhow::anyhow!("no age for acc"))?;
+    sleep(Duration::from_millis(60)).await;
+    let Some(stale) = cache.snapshot("acc") else {
+        return Err(anyhow::anyhow!("no snapshot for acc"));
+    };
+    assert!(Duration::from_millis(stale.age_ms) >= fresh + Duration::from_millis(50));
+
+    acc.push(2, 4100.0).await?;
+    let Some(renewed) = cache.snapshot("acc") else {
+        return Err(anyhow::anyhow!("no snapshot for acc"));
+    };
+    assert!(renewed.age_ms < stale.age_ms);
+    Ok(())
+}
+
+#[tokio::test]
+async fn tcp_endpoint_serves_latest_and_rejects_unknown_commands() -> Result<()> {
+    let mut service = TelemetryService::new();
+    let mut acc = monitor(&mut service, "acc").await?;
+    acc.push(3, 7200.0).await?;
+    let handle = service
+        .serve_snapshots(SnapshotEndpoint::localhost_any_port())
+        .await?;
+    let SnapshotEndpoint::Tcp(addr) = handle.endpoint().clone() else {
+        return Err(anyhow::anyhow!("expected a TCP endpoint"));
+    };
+    let mut client = BufReader::new(TcpStream::connect(addr).await?);
+
+    let latest = request(&mut client, r#"{"cmd":"latest","game_id":"acc"}"#).await?;
+    assert_eq!(latest["game_id"], json!("acc"));
+    assert_eq!(latest["frame"]["sequence"], json!(3));
+    assert!(latest["age_ms"].is_u64());
+
+    // Errors leave the connection usable.
+    let unknown = request(&mut client, r#"{"cmd":"reboot"}"#).await?;
+    assert_eq!(unknown, json!({"error": "unknown command: reboot"}));
+    let missing = request(&mut client, r#"{"cmd":"latest","game_id":"rf2"}"#).await?;
+    assert!(missing["error"].is_string(), "{missing}");
+    let garbage = request(&mut client, "not json").await?;
+    assert!(garbage["error"].is_string(), "{garbage}");
+
+    let all = request(&mut client, r#"{"cmd":"latest_all"}"#).await?;
+    assert_eq!(all["games"]["acc"]["frame"]["data"]["rpm"], json!(7200.0));
+    handle.shutdown().await?;
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn unix_socket_endpoint_serves_latest() -> Result<()> {
+    let mut service = TelemetryService::new();
+    let mut acc = monitor(&mut service, "acc").await?;
+    acc.push(5, 3000.0).await?;
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("snapshots.sock");
+    let handle = service
+        .serve_snapshots(SnapshotEndpoint::Unix(path.clone()))
+        .await?;
+
+    let mut client = BufReader::new(tokio::net::UnixStream::connect(&path).await?);
+    let latest = request(&mut client, r#"{"cmd":"latest","game_id":"acc"}"#).await?;
+    assert_eq!(latest["frame"]["sequence"], json!(5));
+
+    handle.shutdown().await?;
+    assert!(!path.exists());
+    Ok(())
+}
@@ -0,0 +1,240 @@
+//! Track position estimation and reference-lap delta.
+
+use racing_wheel_telemetry_adapters::{
+    ExtendedKey, NormalizedTelemetry, TelemetryFrame, TelemetryValue,
+};
+use racing_wheel_telemetry_orchestrator::{
+    DeltaComputer, FrameTransform, PositionConfidence, ReferenceLapError, TrackPosition,
+    TrackPositionEstimator,
+};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const STEP_NS: u64 = 10_000_000;
+
+fn frame(step: u64, lap: u16, speed_ms: f32) -> TelemetryFrame {
+    let data = NormalizedTelemetry::builder()
+        .lap(lap)
+        .speed_ms(speed_ms)
+        .build();
+    TelemetryFrame::new(data, step * STEP_NS, step, 0)
+}
+
+/// A frame stamped with both an arrival time and a game clock.
+fn clocked(arrival_ns: u64, game_time_s: f64, lap: u16, speed_ms: f32) -> TelemetryFrame {
+    let data = NormalizedTelemetry::builder()
+        .lap(lap)
+        .speed_ms(speed_ms)
+        .game_time_s(game_time_s)
+        .build();
+    TelemetryFrame::new(data, arrival_ns, 0, 0)
+}
+
+fn with_float(mut frame: TelemetryFrame, key: ExtendedKey, value: f32) -> TelemetryFrame {
+    frame
+        .data
+        .extended
+        .insert(key.name.to_string(), TelemetryValue::Float(value));
+    frame
+}
+
+#[test]
+fn exact_sources_produce_exact_positions() -> TestResult {
+    let mut estimator = TrackPositionEstimator::new();
+
+    let mut distance = with_float(frame(0, 1, 50.0), ExtendedKey::LAP_DISTANCE_M, 1_750.0);
+    distance = with_float(distance, ExtendedKey::TRACK_LENGTH_M, 7_000.0);
+    estimator.apply(&mut distance);
+    assert_eq!(
+        TrackPosition::from_telemetry(&distance.data),
+        Some(TrackPosition {
+            fraction: 0.25,
+            confidence: PositionConfidence::Exact
+        })
+    );
+
+    let mut spline = with_float(frame(1, 1, 50.0), ExtendedKey::SPLINE_POSITION, 0.625);
+    estimator.apply(&mut spline);
+    assert_eq!(
+        spline.data.extended.get(ExtendedKey::LAP_POSITION.name),
+        Some(&TelemetryValue::Float(0.625))
+    );
+
+    let mut stage = with_float(frame(2, 0, 20.0), ExtendedKey::STAGE_PROGRESS, 0.4);
+    estimator.apply(&mut stage);
+    let position = TrackPosition::from_telemetry(&stage.data).ok_or("stage has a position")?;
+    assert_eq!(position.fraction, 0.4);
+    assert_eq!(position.confidence, PositionConfidence::Exact);
+    Ok(())
+}
+
+#[test]
+fn integrated_estimate_converges_on_constant_speed_lap() -> TestResult {
+    // 50 m/s around a 3000 m lap: one lap every 60 s, one frame every 10 ms.
+    const SPEED_MS: f32 = 50.0;
+    const LAP_STEPS: u64 = 6_000;
+    // Error bound stated for this synthetic lap: 0.5 % of a lap.
+    const MAX_ERROR: f32 = 0.005;
+
+    let mut estimator = TrackPositionEstimator::new();
+    // Join mid-lap: nothing can be published until a full lap is seen.
+    let mut step = 0;
+    for _ in 0..LAP_STEPS / 3 {
+        assert_eq!(estimator.update(&frame(step, 1, SPEED_MS)), None);
+        step += 1;
+    }
+    for _ in 0..LAP_STEPS {
+        assert_eq!(estimator.update(&frame(step, 2, SPEED_MS)), None);
+        step += 1;
+    }
+
+    for offset in 0..LAP_STEPS {
+        let position = estimator
+            .update(&frame(step, 3, SPEED_MS))
+            .ok_or("estimates follow a learned lap")?;
+        step += 1;
+        let expected = offset as f32 / LAP_STEPS as f32;
+        assert_eq!(position.confidence, PositionConfidence::Estimated);
+        assert!(
+            (position.fraction - expected).abs() <= MAX_ERROR,
+            "offset {offset}: {} vs {expected}",
+            position.fraction
+        );
+    }
+
+    // Lap 2 was the first one seen from start to finish.
+    let learned = estimator
+        .learned_lap_length_m()
+        .ok_or("a complete lap was observed")?;
+    assert!((learned - 3_000.0).abs() < 3_000.0 * f64::from(MAX_ERROR));
+    Ok(())
+}
+
+#[test]
+fn game_clock_pause_adds_no_distance() -> TestResult {
+    // 50 m/s for 60 s of game time, with a 5 s pause in the middle of lap 2
+    // during which frames keep arriving with the speed frozen.
+    let mut estimator = TrackPositionEstimator::new();
+    let mut arrival_ns = 0;
+    let mut game_ms = 0u64;
+    let mut push = |estimator: &mut TrackPositionEstimator, lap: u16, advance_game: bool| {
+        estimator.update(&clocked(arrival_ns, game_ms as f64 / 1000.0, lap, 50.0));
+        arrival_ns += STEP_NS;
+        if advance_game {
+            game_ms += 10;
+        }
+    };
+    push(&mut estimator, 1, true);
+    for step in 0..6_000 {
+        push(&mut estimator, 2, true);
+        if step == 3_000 {
+            for _ in 0..500 {
+                push(&mut estimator, 2, false);
+            }
+        }
+    }
+    push(&mut estimator, 3, true);
+
+    let learned = estimator
+        .learned_lap_length_m()
+        .ok_or("a complete lap was observed")?;
+    assert!((learned - 3_000.0).abs() < 1.0, "learned {learned} m");
+    let diagnostics = estimator.clock_diagnostics();
+    assert_eq!(diagnostics.arrival_steps, 0);
+    assert_eq!(diagnostics.diverged_steps, 0);
+    Ok(())
+}
+
+#[test]
+fn diverging_clocks_are_annotated() -> TestResult {
+    let mut estimator = TrackPositionEstimator::new();
+    let mut first = clocked(0, 10.0, 1, 40.0);
+    estimator.apply(&mut first);
+    let mut steady = clocked(STEP_NS, 10.01, 1, 40.0);
+    estimator.apply(&mut steady);
+    assert!(
+        !steady
+            .data
+            .extended
+            .contains_key(ExtendedKey::GAME_CLOCK_DIVERGENCE_S.name)
+    );
+
+    // Delivered 600 ms late although only 10 ms passed in the game.
+    let mut stalled = clocked(STEP_NS + 600_000_000, 10.02, 1, 40.0);
+    estimator.apply(&mut stalled);
+    let divergence = match stalled
+        .data
+        .extended
+        .get(ExtendedKey::GAME_CLOCK_DIVERGENCE_S.name)
+    {
+        Some(TelemetryValue::Float(value)) => *value,
+        other => return Err(format!("missing divergence annotation: {other:?}").into()),
+    };
+    assert!((divergence + 0.59).abs() < 1e-3, "divergence {divergence}");
+    assert_eq!(estimator.clock_diagnostics().diverged_steps, 1);
+    Ok(())
+}
+
+#[test]
+fn pit_lane_holds_position_and_does_not_relearn_length() -> TestResult {
+    let mut estimator = TrackPositionEstimator::new();
+    let mut step = 0;
+    for lap in 1..=2 {
+        for _ in 0..1_000 {
+            estimator.update(&frame(step, lap, 30.0));
+            step += 1;
+        }
+    }
+    for _ in 0..500 {
+        estimator.update(&frame(step, 3, 30.0));
+        step += 1;
+    }
+    let learned = estimator.learned_lap_length_m();
+    assert!(learned.is_some());
+    let mut pit = frame(step, 3, 16.0);
+    pit.data.flags.in_pits = true;
+    let held = estimator.update(&pit).ok_or("position held in pits")?;
+    assert_eq!(held.confidence, PositionConfidence::Pit);
+    assert!((held.fraction - 0.5).abs() < 0.01);
+
+    for _ in 0..2_000 {
+        step += 1;
+        let mut pit = frame(step, 3, 16.0);
+        pit.data.flags.in_pits = true;
+        assert_eq!(estimator.update(&pit), Some(held));
+    }
+    estimator.update(&frame(step + 1, 4, 30.0));
+    assert_eq!(estimator.learned_lap_length_m(), learned);
+    Ok(())
+}
+
+#[test]
+fn delta_matches_hand_computed_reference() -> TestResult {
+    let reference = DeltaComputer::new(vec![(0.0, 0.0), (0.5, 40.0), (1.0, 90.0)])?;
+
+    assert_eq!(reference.reference_time_at(0.25), Some(20.0));
+    assert_eq!(reference.reference_time_at(0.5), Some(40.0));
+    assert_eq!(reference.delta(0.25, 21.0), Some(1.0));
+    assert_eq!(reference.delta(0.75, 63.0), Some(-2.0));
+    assert_eq!(reference.delta(1.0, 90.0), Some(0.0));
+
+    let mut delta = reference.clone();
+    let mut live = with_float(frame(0, 2, 40.0), ExtendedKey::LAP_POSITION, 0.6);
+    live.data.current_lap_time_s = 52.0;
+    delta.apply(&mut live);
+    // Reference at 0.6 is 40 + 50 * 0.2 = 50 s.
+    assert_eq!(
+        live.data.extended.get(ExtendedKey::REFERENCE_DELTA_S.name),
+        Some(&TelemetryValue::Float(2.0))
+    );
+
+    assert_eq!(
+        DeltaComputer::new(vec![(0.0, 0.0)]).err(),
+        Some(ReferenceLapError::TooFewSamples(1))
+    );
+    assert_eq!(
+        DeltaComputer::new(vec![(0.0, 0.0), (0.5, 40.0), (0.4, 45.0)]).err(),
+        Some(ReferenceLapError::InvalidSample(2))
+    );
+    Ok(())
+}
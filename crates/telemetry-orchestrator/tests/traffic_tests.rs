@@ -0,0 +1,90 @@
+//! Per-game traffic accounting seen through the service.
+
+use std::net::{Ipv4Addr, SocketAddr};
+use std::time::Duration;
+
+use racing_wheel_telemetry_adapters::{ForzaAdapter, MockAdapter};
+use racing_wheel_telemetry_orchestrator::TelemetryService;
+use tokio::net::UdpSocket;
+use tokio::time::{sleep, timeout};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const PACKETS: usize = 20;
+
+fn free_port() -> std::io::Result<u16> {
+    let probe = std::net::UdpSocket::bind((Ipv4Addr::LOCALHOST, 0))?;
+    Ok(probe.local_addr()?.port())
+}
+
+/// 311-byte Forza CarDash packet with the race on.
+fn cardash_packet(rpm: f32) -> Vec<u8> {
+    let mut buf = vec![0u8; 311];
+    buf[0..4].copy_from_slice(&1i32.to_le_bytes());
+    buf[16..20].copy_from_slice(&rpm.to_le_bytes());
+    buf[244..248].copy_from_slice(&40.0f32.to_le_bytes());
+    buf[307] = 3;
+    buf
+}
+
+#[tokio::test]
+async fn accountant_matches_raw_size_of_forwarded_frames() -> TestResult {
+    let port = free_port()?;
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(ForzaAdapter::new().with_port(port)));
+    service.set_rate_limit("forza_motorsport", 10_000);
+    let mut frames = service.start_monitoring("forza_motorsport").await?;
+
+    let sender = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+    let target = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    // Resend until the adapter's socket is up and a frame comes back.
+    let first = loop {
+        sender.send_to(&cardash_packet(5_000.0), target).await?;
+        if let Ok(frame) = timeout(Duration::from_millis(100), frames.recv()).await {
+            break frame.ok_or("stream ended")?;
+        }
+    };
+    for i in 1..PACKETS {
+        sender
+            .send_to(&cardash_packet(5_000.0 + i as f32), target)
+            .await?;
+        sleep(Duration::from_millis(5)).await;
+    }
+
+    // Every datagram that reached the adapter decodes to one frame.
+    let mut received = 1;
+    let mut raw_bytes = first.raw_size as u64;
+    let traffic = loop {
+        let traffic = service
+            .traffic("forza_motorsport")
+            .ok_or("no traffic for forza")?;
+        if traffic.total_packets == received {
+            break traffic;
+        }
+        let frame = timeout(Duration::from_secs(2), frames.recv())
+            .await?
+            .ok_or("stream ended")?;
+        received += 1;
+        raw_bytes += frame.raw_size as u64;
+    };
+    assert!(received >= PACKETS as u64);
+    assert_eq!(traffic.total_bytes, raw_bytes);
+    assert_eq!(traffic.total_bytes, received * 311);
+
+    let snapshot = service.metrics_snapshot();
+    let reported = snapshot.games["forza_motorsport"]
+        .traffic
+        .ok_or("no traffic in the metrics snapshot")?;
+    assert_eq!(reported.total_packets, traffic.total_packets);
+    service.stop_monitoring("forza_motorsport").await?;
+    Ok(())
+}
+
+#[test]
+fn adapters_without_a_receive_slot_report_no_traffic() {
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(MockAdapter::new("mock".to_string())));
+    assert_eq!(service.traffic("mock"), None);
+    assert_eq!(service.traffic("not_registered"), None);
+    assert_eq!(service.metrics_snapshot().games["mock"].traffic, None);
+}
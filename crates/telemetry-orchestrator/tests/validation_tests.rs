@@ -0,0 +1,85 @@
+//! Frames checked by the game's validator before rate limiting.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use racing_wheel_telemetry_adapters::{MockAdapter, NormalizedTelemetry, TelemetryFrame};
+use racing_wheel_telemetry_core::{TelemetryValidator, ValidationPolicy};
+use racing_wheel_telemetry_orchestrator::{AdapterHealth, TelemetryService};
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+/// Every odd frame reports an impossible gear, like a struct-literal adapter
+/// reading the wrong byte.
+fn odd_frames_in_gear_117(sequence: u64) -> NormalizedTelemetry {
+    NormalizedTelemetry {
+        rpm: 6_000.0,
+        gear: if sequence % 2 == 1 { 117 } else { 3 },
+        ..NormalizedTelemetry::default()
+    }
+}
+
+fn register(service: &mut TelemetryService, game_id: &str) {
+    service.register_adapter(Box::new(
+        MockAdapter::with_update_rate(game_id.to_string(), Duration::from_millis(2))
+            .with_frame_source(odd_frames_in_gear_117),
+    ));
+}
+
+async fn next_frame(frames: &mut mpsc::Receiver<TelemetryFrame>) -> Result<TelemetryFrame> {
+    timeout(Duration::from_secs(2), frames.recv())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("forwarding ended"))
+}
+
+fn health_of(service: &TelemetryService, game_id: &str) -> Result<AdapterHealth> {
+    service
+        .health()
+        .remove(game_id)
+        .ok_or_else(|| anyhow::anyhow!("no health for {game_id}"))
+}
+
+#[tokio::test]
+async fn bad_readings_are_sanitized_by_default_and_counted() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    register(&mut service, "validated_source");
+    let mut frames = service.start_monitoring("validated_source").await?;
+
+    for sequence in 0..6 {
+        let frame = next_frame(&mut frames).await?;
+        assert_eq!(frame.sequence, sequence);
+        let expected_gear = if sequence % 2 == 1 { 12 } else { 3 };
+        assert_eq!(frame.data.gear, expected_gear);
+    }
+
+    let health = health_of(&service, "validated_source")?;
+    assert!(health.validation_issues >= 3, "{health:?}");
+    assert_eq!(health.frames_rejected_invalid, 0);
+    service.stop_monitoring("validated_source").await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_policy_drops_bad_frames() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    register(&mut service, "strict_source");
+    service.set_validator(
+        "strict_source",
+        TelemetryValidator::new()
+            .with_gear_range(-1..=8)
+            .with_policy(ValidationPolicy::Reject),
+    );
+    let mut frames = service.start_monitoring("strict_source").await?;
+
+    for sequence in [0, 2, 4, 6] {
+        let frame = next_frame(&mut frames).await?;
+        assert_eq!((frame.sequence, frame.data.gear), (sequence, 3));
+    }
+
+    let health = health_of(&service, "strict_source")?;
+    assert!(health.frames_rejected_invalid >= 3, "{health:?}");
+    assert_eq!(health.validation_issues, health.frames_rejected_invalid);
+    assert_eq!(health.frames_sanitized, 0);
+    service.stop_monitoring("strict_source").await?;
+    Ok(())
+}
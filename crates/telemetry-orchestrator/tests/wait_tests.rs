@@ -0,0 +1,228 @@
+//! Waiting for frames: matching, timeouts, disconnects and cancellation.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use racing_wheel_telemetry_adapters::{
+    MockAdapter, NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver,
+};
+use racing_wheel_telemetry_orchestrator::{TelemetryService, WaitError};
+use tokio::sync::mpsc;
+
+const MOCK_ID: &str = "mock_wait";
+const CHANNEL_ID: &str = "wait_source";
+
+/// Adapter whose frames come from a test-owned channel; dropping the sender
+/// disconnects the source.
+struct ChannelAdapter {
+    rx: Mutex<Option<TelemetryReceiver>>,
+}
+
+#[async_trait]
+impl TelemetryAdapter for ChannelAdapter {
+    fn game_id(&self) -> &str {
+        CHANNEL_ID
+    }
+
+    async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        self.rx
+            .lock()
+            .map_err(|_| anyhow::anyhow!("poisoned"))?
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("already monitoring"))
+    }
+
+    async fn stop_monitoring(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn normalize(&self, _raw: &[u8]) -> Result<NormalizedTelemetry> {
+        Ok(NormalizedTelemetry::default())
+    }
+
+    fn expected_update_rate(&self) -> Duration {
+        Duration::from_millis(1)
+    }
+
+    async fn is_game_running(&self) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+/// Drain the forwarded frames so the forwarding task never blocks.
+fn drain(mut frames: TelemetryReceiver) {
+    tokio::spawn(async move { while frames.recv().await.is_some() {} });
+}
+
+async fn mock_service() -> Result<TelemetryService> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(MockAdapter::with_update_rate(
+        MOCK_ID.to_string(),
+        Duration::from_millis(2),
+    )));
+    drain(service.start_monitoring(MOCK_ID).await?);
+    Ok(service)
+}
+
+async fn channel_service() -> Result<(TelemetryService, mpsc::Sender<TelemetryFrame>)> {
+    let (tx, source) = mpsc::channel(16);
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(ChannelAdapter {
+        rx: Mutex::new(Some(source)),
+    }));
+    drain(service.start_monitoring(CHANNEL_ID).await?);
+    Ok((service, tx))
+}
+
+fn at_speed(sequence: u64, speed_ms: f32) -> TelemetryFrame {
+    let data = NormalizedTelemetry::builder().speed_ms(speed_ms).build();
+    TelemetryFrame::new(data, 0, sequence, 0)
+}
+
+#[tokio::test]
+async fn condition_met_on_the_nth_mock_frame_resolves_with_it() -> Result<()> {
+    let service = mock_service().await?;
+    let first = service
+        .wait_for_first_frame(MOCK_ID, Duration::from_secs(2))
+        .await?;
+
+    let seen = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&seen);
+    let frame = service
+        .wait_for_condition(
+            MOCK_ID,
+            move |_| counter.fetch_add(1, Ordering::Relaxed) + 1 == 5,
+            Duration::from_secs(2),
+        )
+        .await?;
+    assert_eq!(seen.load(Ordering::Relaxed), 5);
+    assert!(frame.sequence >= first.sequence + 5);
+    assert_eq!(frame.data.car_id.as_deref(), Some("mock_car"));
+    assert_eq!(service.frame_subscriber_count(MOCK_ID), 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn matching_frame_is_returned_and_other_consumers_still_get_every_frame() -> Result<()> {
+    let (tx, source) = mpsc::channel(16);
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(ChannelAdapter {
+        rx: Mutex::new(Some(source)),
+    }));
+    let mut frames = service.start_monitoring(CHANNEL_ID).await?;
+
+    let moving = service.wait_for_condition(
+        CHANNEL_ID,
+        |telemetry| telemetry.speed_ms > 0.0,
+        Duration::from_secs(2),
+    );
+    for (sequence, speed) in [0.0, 0.0, 0.0, 12.5, 20.0].into_iter().enumerate() {
+        tx.send(at_speed(sequence as u64, speed)).await?;
+    }
+    assert_eq!(moving.await?.sequence, 3);
+
+    for expected in 0..5 {
+        let frame = frames
+            .recv()
+            .await
+            .ok_or_else(|| anyhow::anyhow!("forwarding stopped"))?;
+        assert_eq!(frame.sequence, expected);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn silence_times_out_and_releases_the_subscription() -> Result<()> {
+    let (service, _tx) = channel_service().await?;
+    let baseline = service.frame_subscriber_count(CHANNEL_ID);
+
+    let wait = service.wait_for_first_frame(CHANNEL_ID, Duration::from_millis(50));
+    assert_eq!(service.frame_subscriber_count(CHANNEL_ID), baseline + 1);
+    assert_eq!(
+        wait.await.err(),
+        Some(WaitError::Timeout {
+            game_id: CHANNEL_ID.to_string(),
+            timeout: Duration::from_millis(50),
+        })
+    );
+    assert_eq!(service.frame_subscriber_count(CHANNEL_ID), baseline);
+
+    // Cancelling a wait before it resolves releases it too.
+    let cancelled = service.wait_for_first_frame(CHANNEL_ID, Duration::from_secs(60));
+    assert!(
+        tokio::time::timeout(Duration::from_millis(20), cancelled)
+            .await
+            .is_err()
+    );
+    assert_eq!(service.frame_subscriber_count(CHANNEL_ID), baseline);
+    Ok(())
+}
+
+#[tokio::test]
+async fn disconnect_fails_the_wait_without_waiting_out_the_timeout() -> Result<()> {
+    let (service, tx) = channel_service().await?;
+    let wait = service.wait_for_first_frame(CHANNEL_ID, Duration::from_secs(30));
+
+    let started = Instant::now();
+    drop(tx);
+    assert_eq!(
+        wait.await.err(),
+        Some(WaitError::Disconnected(CHANNEL_ID.to_string()))
+    );
+    assert!(started.elapsed() < Duration::from_secs(5));
+
+    // Once the source is gone the game is no longer monitored.
+    assert_eq!(
+        service
+            .wait_for_first_frame(CHANNEL_ID, Duration::from_secs(1))
+            .await
+            .err(),
+        Some(WaitError::NotMonitored(CHANNEL_ID.to_string()))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn waiting_on_an_unmonitored_game_fails_immediately() -> Result<()> {
+    let mut service = TelemetryService::from_support_matrix(None);
+    service.register_adapter(Box::new(MockAdapter::new(MOCK_ID.to_string())));
+    assert_eq!(
+        service
+            .wait_for_first_frame(MOCK_ID, Duration::from_secs(30))
+            .await
+            .err(),
+        Some(WaitError::NotMonitored(MOCK_ID.to_string()))
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn blocking_wait_resolves_outside_the_runtime() -> Result<()> {
+    let service = mock_service().await?;
+    let speed = std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                service.wait_for_condition_blocking(
+                    MOCK_ID,
+                    |telemetry| telemetry.speed_ms > 0.0,
+                    Duration::from_secs(2),
+                )
+            })
+            .join()
+    })
+    .map_err(|_| anyhow::anyhow!("blocking wait panicked"))??
+    .data
+    .speed_ms;
+    assert!(speed > 0.0);
+
+    // Inside the runtime the blocking variant refuses rather than deadlocking.
+    assert!(matches!(
+        service.wait_for_condition_blocking(MOCK_ID, |_| true, Duration::from_millis(10)),
+        Err(WaitError::Runtime(_))
+    ));
+    Ok(())
+}
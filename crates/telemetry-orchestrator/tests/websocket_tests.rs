@@ -0,0 +1,274 @@
+//! Browser overlays subscribe to forwarded frames over WebSocket.
+#![cfg(feature = "websocket")]
+
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::{SinkExt, StreamExt};
+use racing_wheel_schemas::telemetry::decode_frame;
+use racing_wheel_telemetry_adapters::{
+    MockAdapter, NormalizedTelemetry, TelemetryAdapter, TelemetryFrame, TelemetryReceiver,
+    TelemetryValue,
+};
+use racing_wheel_telemetry_orchestrator::{TelemetryService, WsHandle, WsOptions};
+use serde_json::{Value, json};
+use tokio::net::{TcpSocket, TcpStream};
+use tokio::sync::mpsc;
+use tokio::time::{Instant, sleep, timeout};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+const RECV_TIMEOUT: Duration = Duration::from_secs(5);
+
+type Client<S> = WebSocketStream<S>;
+
+/// Adapter fed from a test-owned channel.
+struct ChannelAdapter {
+    game_id: &'static str,
+    rx: Mutex<Option<TelemetryReceiver>>,
+}
+
+#[async_trait]
+impl TelemetryAdapter for ChannelAdapter {
+    fn game_id(&self) -> &str {
+        self.game_id
+    }
+
+    async fn start_monitoring(&self) -> Result<TelemetryReceiver> {
+        self.rx
+            .lock()
+            .map_err(|_| anyhow::anyhow!("poisoned"))?
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("already monitoring"))
+    }
+
+    async fn stop_monitoring(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn normalize(&self, _raw: &[u8]) -> Result<NormalizedTelemetry> {
+        Ok(NormalizedTelemetry::default())
+    }
+
+    fn expected_update_rate(&self) -> Duration {
+        Duration::from_millis(100)
+    }
+
+    async fn is_game_running(&self) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+fn localhost() -> SocketAddr {
+    (Ipv4Addr::LOCALHOST, 0).into()
+}
+
+/// Start `game_id` from a stock mock adapter and drain what the service
+/// forwards, so only the WebSocket side is under test.
+async fn monitor_mock(service: &mut TelemetryService, game_id: &str) -> Result<()> {
+    service.register_adapter(Box::new(MockAdapter::with_update_rate(
+        game_id.to_string(),
+        Duration::from_millis(2),
+    )));
+    service.set_rate_limit(game_id, 1_000_000);
+    let mut frames = service.start_monitoring(game_id).await?;
+    tokio::spawn(async move { while frames.recv().await.is_some() {} });
+    Ok(())
+}
+
+async fn connect(handle: &WsHandle) -> Result<Client<MaybeTlsStream<TcpStream>>> {
+    let url = format!("ws://{}", handle.local_addr());
+    Ok(tokio_tungstenite::connect_async(url).await?.0)
+}
+
+async fn next_message<S>(client: &mut Client<S>) -> Result<Message>
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    timeout(RECV_TIMEOUT, client.next())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("connection closed"))?
+        .map_err(Into::into)
+}
+
+async fn next_json<S>(client: &mut Client<S>) -> Result<Value>
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    match next_message(client).await? {
+        Message::Text(text) => Ok(serde_json::from_str(&text)?),
+        other => Err(anyhow::anyhow!("expected a text message, got {other:?}")),
+    }
+}
+
+async fn subscribe<S>(client: &mut Client<S>, request: Value) -> Result<Value>
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    client.send(Message::text(request.to_string())).await?;
+    next_json(client).await
+}
+
+#[tokio::test]
+async fn subscribed_game_is_delivered_and_others_filtered() -> Result<()> {
+    let mut service = TelemetryService::new();
+    monitor_mock(&mut service, "mock_a").await?;
+    monitor_mock(&mut service, "mock_b").await?;
+    let handle = service
+        .serve_websocket(localhost(), WsOptions::default())
+        .await?;
+
+    let mut only_a = connect(&handle).await?;
+    let ack = subscribe(&mut only_a, json!({"subscribe": ["mock_a"]})).await?;
+    assert_eq!(ack, json!({"subscribed": ["mock_a"], "encoding": "json"}));
+    let mut all = connect(&handle).await?;
+    let ack = subscribe(&mut all, json!({"subscribe": "all"})).await?;
+    assert_eq!(ack["subscribed"], json!("all"));
+
+    for _ in 0..50 {
+        let message = next_json(&mut only_a).await?;
+        assert_eq!(message["game_id"], json!("mock_a"));
+        assert!(message["frame"]["data"].is_object());
+    }
+    let mut seen = std::collections::BTreeSet::new();
+    while seen.len() < 2 {
+        let message = next_json(&mut all).await?;
+        seen.insert(message["game_id"].as_str().unwrap_or_default().to_string());
+    }
+    assert_eq!(handle.client_count(), 2);
+    handle.shutdown().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn binary_subscribers_get_game_prefixed_records() -> Result<()> {
+    let mut service = TelemetryService::new();
+    monitor_mock(&mut service, "mock_a").await?;
+    let handle = service
+        .serve_websocket(localhost(), WsOptions::default())
+        .await?;
+
+    let mut client = connect(&handle).await?;
+    let ack = subscribe(
+        &mut client,
+        json!({"subscribe": "mock_a", "encoding": "binary"}),
+    )
+    .await?;
+    assert_eq!(ack["encoding"], json!("binary"));
+
+    let Message::Binary(payload) = next_message(&mut client).await? else {
+        return Err(anyhow::anyhow!("expected a binary message"));
+    };
+    let game_len = usize::from(*payload.first().ok_or_else(|| anyhow::anyhow!("empty"))?);
+    assert_eq!(payload.get(1..1 + game_len), Some(b"mock_a".as_slice()));
+    let frame = decode_frame(&payload[1 + game_len..])?;
+    assert!(frame.data.rpm >= 2_000.0, "{:?}", frame.data);
+    assert!(frame.data.gear > 0);
+
+    client
+        .send(Message::text(json!({"subscribe": 7}).to_string()))
+        .await?;
+    // Frames already queued may arrive before the reply.
+    let reply = loop {
+        if let Message::Text(text) = next_message(&mut client).await? {
+            break serde_json::from_str::<Value>(&text)?;
+        }
+    };
+    assert!(reply["error"].is_string(), "{reply}");
+    handle.shutdown().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn slow_reader_is_dropped_without_stalling_the_sender() -> Result<()> {
+    const FRAMES: u64 = 400;
+
+    let mut service = TelemetryService::new();
+    let (tx, rx) = mpsc::channel(16);
+    service.register_adapter(Box::new(ChannelAdapter {
+        game_id: "bulk",
+        rx: Mutex::new(Some(rx)),
+    }));
+    service.set_rate_limit("bulk", 1_000_000);
+    let mut forwarded = service.start_monitoring("bulk").await?;
+    let handle = service
+        .serve_websocket(
+            localhost(),
+            WsOptions {
+                client_queue: 16,
+                ..WsOptions::default()
+            },
+        )
+        .await?;
+
+    // A tiny receive window so the stalled client backs up quickly.
+    let socket = TcpSocket::new_v4()?;
+    socket.set_recv_buffer_size(4096)?;
+    let stream = socket.connect(handle.local_addr()).await?;
+    let url = format!("ws://{}", handle.local_addr());
+    let (mut slow, _) = tokio_tungstenite::client_async(url, stream).await?;
+    subscribe(&mut slow, json!({"subscribe": "bulk"})).await?;
+
+    let mut fast = connect(&handle).await?;
+    subscribe(&mut fast, json!({"subscribe": "all"})).await?;
+    let reader = tokio::spawn(async move {
+        let mut received = 0u64;
+        while received < FRAMES {
+            match next_message(&mut fast).await {
+                Ok(Message::Text(_)) => received += 1,
+                Ok(_) => {}
+                Err(_) => break,
+            }
+        }
+        received
+    });
+
+    // Large frames, so the slow client's socket fills within a few of them.
+    let payload = TelemetryValue::FloatArray(vec![0.125; 2048]);
+    let started = Instant::now();
+    for sequence in 0..FRAMES {
+        let mut data = NormalizedTelemetry::default();
+        data.extended.insert("samples".to_string(), payload.clone());
+        tx.send(TelemetryFrame::new(data, sequence, sequence, 0))
+            .await?;
+        timeout(RECV_TIMEOUT, forwarded.recv())
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("forwarding stopped"))?;
+        sleep(Duration::from_millis(1)).await;
+    }
+    assert!(started.elapsed() < Duration::from_secs(20));
+
+    assert_eq!(timeout(RECV_TIMEOUT, reader).await??, FRAMES);
+    assert_eq!(handle.slow_clients_dropped(), 1);
+    assert_eq!(handle.client_count(), 1);
+    handle.shutdown().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn shutdown_closes_client_sockets() -> Result<()> {
+    let mut service = TelemetryService::new();
+    monitor_mock(&mut service, "mock_a").await?;
+    let handle = service
+        .serve_websocket(localhost(), WsOptions::default())
+        .await?;
+    let addr = handle.local_addr();
+
+    let mut client = connect(&handle).await?;
+    subscribe(&mut client, json!({"subscribe": "all"})).await?;
+    timeout(RECV_TIMEOUT, handle.shutdown()).await??;
+
+    let close = loop {
+        match next_message(&mut client).await? {
+            Message::Close(frame) => break frame,
+            _ => continue,
+        }
+    };
+    assert_eq!(close.map(|frame| frame.code), Some(CloseCode::Away));
+    assert!(TcpStream::connect(addr).await.is_err());
+    Ok(())
+}
@@ -5,15 +5,29 @@
 
 #![deny(static_mut_refs)]
 
+use std::collections::{BTreeMap, HashMap};
 use std::time::{Duration, Instant};
 
 /// Rate limiter to protect RT-adjacent paths from telemetry parsing bursts.
+///
+/// By default frames closer together than `1 / max_rate_hz` are dropped.
+/// [`RateLimiter::with_burst`] switches to a token bucket instead, so a
+/// short backlog flush after a reconnect is let through.
 pub struct RateLimiter {
     max_rate_hz: u32,
     min_interval: Duration,
     last_processed: Option<Instant>,
     dropped_count: u64,
     processed_count: u64,
+    burst: Option<TokenBucket>,
+    burst_consumed: u64,
+}
+
+/// Tokens refill at the limiter's rate up to `capacity`; each frame spends one.
+struct TokenBucket {
+    capacity: u32,
+    tokens: f64,
+    refilled_at: Option<Instant>,
 }
 
 impl RateLimiter {
@@ -28,19 +42,56 @@ impl RateLimiter {
             last_processed: None,
             dropped_count: 0,
             processed_count: 0,
+            burst: None,
+            burst_consumed: 0,
         }
     }
 
+    /// Create a limiter that accepts up to `capacity` back-to-back frames
+    /// before falling back to `max_rate_hz`. The bucket starts full.
+    pub fn with_burst(max_rate_hz: u32, capacity: u32) -> Self {
+        let mut limiter = Self::new(max_rate_hz);
+        limiter.burst = (capacity > 0).then_some(TokenBucket {
+            capacity,
+            tokens: f64::from(capacity),
+            refilled_at: None,
+        });
+        limiter
+    }
+
     /// Returns true if processing should proceed at this instant.
     pub fn should_process(&mut self) -> bool {
-        let now = Instant::now();
-
-        if let Some(last) = self.last_processed {
-            let elapsed = now.duration_since(last);
-            if elapsed < self.min_interval {
+        self.should_process_at(Instant::now())
+    }
+
+    /// Returns true if a frame arriving at `now` should be processed.
+    pub fn should_process_at(&mut self, now: Instant) -> bool {
+        let early = self
+            .last_processed
+            .is_some_and(|last| now.saturating_duration_since(last) < self.min_interval);
+        let rate_hz = f64::from(self.max_rate_hz.max(1));
+        if let Some(bucket) = &mut self.burst {
+            if let Some(refilled_at) = bucket.refilled_at {
+                let elapsed = now.saturating_duration_since(refilled_at).as_secs_f64();
+                bucket.tokens = (bucket.tokens + elapsed * rate_hz).min(f64::from(bucket.capacity));
+            }
+            bucket.refilled_at = Some(now);
+            if bucket.tokens < 1.0 {
                 self.dropped_count += 1;
                 return false;
             }
+            bucket.tokens -= 1.0;
+            if early {
+                self.burst_consumed += 1;
+            }
+            self.last_processed = Some(now);
+            self.processed_count += 1;
+            return true;
+        }
+
+        if early {
+            self.dropped_count += 1;
+            return false;
         }
 
         self.last_processed = Some(now);
@@ -74,6 +125,17 @@ impl RateLimiter {
         self.processed_count
     }
 
+    /// Frames accepted sooner than `1 / max_rate_hz` after the previous one,
+    /// using the burst allowance.
+    pub fn burst_consumed(&self) -> u64 {
+        self.burst_consumed
+    }
+
+    /// Burst capacity in frames; zero without a burst bucket.
+    pub fn burst_capacity(&self) -> u32 {
+        self.burst.as_ref().map_or(0, |bucket| bucket.capacity)
+    }
+
     /// Current drop rate in percent.
     pub fn drop_rate_percent(&self) -> f32 {
         let total = self.dropped_count + self.processed_count;
@@ -88,6 +150,7 @@ impl RateLimiter {
     pub fn reset_stats(&mut self) {
         self.dropped_count = 0;
         self.processed_count = 0;
+        self.burst_consumed = 0;
     }
 
     /// Current max configured rate.
@@ -110,6 +173,7 @@ pub struct RateLimiterStats {
     pub processed_count: u64,
     pub dropped_count: u64,
     pub drop_rate_percent: f32,
+    pub burst_consumed: u64,
 }
 
 impl From<&RateLimiter> for RateLimiterStats {
@@ -119,6 +183,7 @@ impl From<&RateLimiter> for RateLimiterStats {
             processed_count: limiter.processed_count,
             dropped_count: limiter.dropped_count,
             drop_rate_percent: limiter.drop_rate_percent(),
+            burst_consumed: limiter.burst_consumed,
         }
     }
 }
@@ -176,6 +241,97 @@ impl AdaptiveRateLimiter {
     }
 }
 
+/// Per-game rate limiters with optional overrides.
+///
+/// A game's limit is its override from [`set_limit`](Self::set_limit) if one
+/// is set, otherwise the rate it was [`register`](Self::register)ed with,
+/// otherwise the registry default. Every limiter shares one burst capacity.
+pub struct RateLimiterRegistry {
+    default_rate_hz: u32,
+    burst_capacity: u32,
+    overrides: HashMap<String, u32>,
+    limiters: HashMap<String, RateLimiter>,
+}
+
+impl RateLimiterRegistry {
+    /// Create a registry limiting unknown games to `default_rate_hz`.
+    pub fn new(default_rate_hz: u32) -> Self {
+        Self {
+            default_rate_hz,
+            burst_capacity: 0,
+            overrides: HashMap::new(),
+            limiters: HashMap::new(),
+        }
+    }
+
+    /// Give every limiter a token bucket of `capacity` frames.
+    pub fn with_burst_capacity(mut self, capacity: u32) -> Self {
+        self.burst_capacity = capacity;
+        self
+    }
+
+    /// Fix `game_id`'s limit regardless of what it registers with.
+    pub fn set_limit(&mut self, game_id: &str, rate_hz: u32) {
+        self.overrides.insert(game_id.to_string(), rate_hz);
+        if let Some(limiter) = self.limiters.get_mut(game_id) {
+            limiter.set_max_rate_hz(rate_hz);
+        }
+    }
+
+    /// Remove an override set with [`set_limit`](Self::set_limit). A running
+    /// limiter keeps its rate until the game registers again.
+    pub fn clear_limit(&mut self, game_id: &str) -> Option<u32> {
+        self.overrides.remove(game_id)
+    }
+
+    /// Limit that applies to `game_id` now.
+    pub fn limit_for(&self, game_id: &str) -> u32 {
+        self.overrides
+            .get(game_id)
+            .copied()
+            .or_else(|| self.limiters.get(game_id).map(RateLimiter::max_rate_hz))
+            .unwrap_or(self.default_rate_hz)
+    }
+
+    /// Start `game_id` on a fresh limiter, at its override if set and
+    /// `fallback_hz` otherwise. Returns the limit chosen.
+    pub fn register(&mut self, game_id: &str, fallback_hz: u32) -> u32 {
+        let rate_hz = self.overrides.get(game_id).copied().unwrap_or(fallback_hz);
+        self.limiters
+            .insert(game_id.to_string(), self.limiter(rate_hz));
+        rate_hz
+    }
+
+    /// Returns true if a frame of `game_id` should be processed now.
+    pub fn should_process(&mut self, game_id: &str) -> bool {
+        self.should_process_at(game_id, Instant::now())
+    }
+
+    /// Returns true if a frame of `game_id` arriving at `now` should be
+    /// processed. Unregistered games get a limiter at [`limit_for`](Self::limit_for).
+    pub fn should_process_at(&mut self, game_id: &str, now: Instant) -> bool {
+        if !self.limiters.contains_key(game_id) {
+            let limiter = self.limiter(self.limit_for(game_id));
+            self.limiters.insert(game_id.to_string(), limiter);
+        }
+        self.limiters
+            .get_mut(game_id)
+            .is_some_and(|limiter| limiter.should_process_at(now))
+    }
+
+    /// Per-game counters, keyed by game id, for metrics export.
+    pub fn snapshot(&self) -> BTreeMap<String, RateLimiterStats> {
+        self.limiters
+            .iter()
+            .map(|(game_id, limiter)| (game_id.clone(), RateLimiterStats::from(limiter)))
+            .collect()
+    }
+
+    fn limiter(&self, rate_hz: u32) -> RateLimiter {
+        RateLimiter::with_burst(rate_hz, self.burst_capacity)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,109 @@
+//! Burst allowance and per-game limits.
+
+use std::time::{Duration, Instant};
+
+use racing_wheel_telemetry_rate_limiter::{RateLimiter, RateLimiterRegistry};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+#[test]
+fn burst_of_fifty_with_capacity_twenty_drops_exactly_thirty() -> TestResult {
+    let mut limiter = RateLimiter::with_burst(60, 20);
+    let t0 = Instant::now();
+    let accepted = (0..50).filter(|_| limiter.should_process_at(t0)).count();
+
+    assert_eq!(accepted, 20);
+    assert_eq!(limiter.dropped_count(), 30);
+    // The first frame is on schedule; the other 19 used the burst.
+    assert_eq!(limiter.burst_consumed(), 19);
+    Ok(())
+}
+
+#[test]
+fn burst_bucket_refills_at_the_limit_rate() -> TestResult {
+    let mut limiter = RateLimiter::with_burst(100, 5);
+    let t0 = Instant::now();
+    while limiter.should_process_at(t0) {}
+    assert_eq!(limiter.processed_count(), 5);
+
+    assert!(!limiter.should_process_at(t0 + Duration::from_millis(5)));
+    assert!(limiter.should_process_at(t0 + Duration::from_millis(10)));
+    assert!(!limiter.should_process_at(t0 + Duration::from_millis(11)));
+
+    // A long pause refills to capacity, never beyond.
+    let later = t0 + Duration::from_secs(10);
+    let accepted = (0..10).filter(|_| limiter.should_process_at(later)).count();
+    assert_eq!(accepted, 5);
+    Ok(())
+}
+
+#[test]
+fn zero_capacity_keeps_the_interval_limiter() -> TestResult {
+    let mut limiter = RateLimiter::with_burst(60, 0);
+    let t0 = Instant::now();
+    assert!(limiter.should_process_at(t0));
+    assert!(!limiter.should_process_at(t0));
+    assert_eq!(limiter.burst_capacity(), 0);
+    assert_eq!(limiter.burst_consumed(), 0);
+    Ok(())
+}
+
+#[test]
+fn games_are_limited_independently() -> TestResult {
+    let mut registry = RateLimiterRegistry::new(1000).with_burst_capacity(20);
+    registry.register("iracing", 720);
+    registry.register("eawrc", 20);
+    let t0 = Instant::now();
+
+    let flooded = (0..50)
+        .filter(|_| registry.should_process_at("eawrc", t0))
+        .count();
+    assert_eq!(flooded, 20);
+    assert!(registry.should_process_at("iracing", t0));
+
+    let snapshot = registry.snapshot();
+    let eawrc = snapshot.get("eawrc").ok_or("eawrc missing")?;
+    assert_eq!(eawrc.processed_count, 20);
+    assert_eq!(eawrc.dropped_count, 30);
+    assert_eq!(eawrc.burst_consumed, 19);
+    assert_eq!(eawrc.max_rate_hz, 20);
+    let iracing = snapshot.get("iracing").ok_or("iracing missing")?;
+    assert_eq!(iracing.processed_count, 1);
+    assert_eq!(iracing.dropped_count, 0);
+    Ok(())
+}
+
+#[test]
+fn overrides_win_over_registered_and_default_rates() -> TestResult {
+    let mut registry = RateLimiterRegistry::new(1000);
+    assert_eq!(registry.limit_for("acc"), 1000);
+
+    registry.set_limit("acc", 200);
+    assert_eq!(registry.register("acc", 100), 200);
+    assert_eq!(registry.limit_for("acc"), 200);
+
+    assert_eq!(registry.register("gt7", 120), 120);
+    assert_eq!(registry.limit_for("gt7"), 120);
+
+    // Overriding a running game retunes its limiter in place.
+    registry.set_limit("gt7", 30);
+    assert_eq!(
+        registry.snapshot().get("gt7").map(|s| s.max_rate_hz),
+        Some(30)
+    );
+
+    assert_eq!(registry.clear_limit("acc"), Some(200));
+    assert_eq!(registry.register("acc", 100), 100);
+    Ok(())
+}
+
+#[test]
+fn unregistered_games_use_the_default_limit() -> TestResult {
+    let mut registry = RateLimiterRegistry::new(50);
+    let t0 = Instant::now();
+    assert!(registry.should_process_at("rbr", t0));
+    assert!(!registry.should_process_at("rbr", t0));
+    assert!(registry.should_process_at("rbr", t0 + Duration::from_millis(20)));
+    assert_eq!(registry.limit_for("rbr"), 50);
+    Ok(())
+}
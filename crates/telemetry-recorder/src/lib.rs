@@ -2,10 +2,16 @@
 
 #![deny(static_mut_refs)]
 
-use racing_wheel_schemas::telemetry::{NormalizedTelemetry, TelemetryFlags, TelemetryFrame};
+use anyhow::Context;
+use openracing_file_lock::FileLock;
+use racing_wheel_schemas::telemetry::{
+    FrameReader, FrameWriter, NormalizedTelemetry, TELEMETRY_SCHEMA_VERSION, TelemetryFlags,
+    TelemetryFrame, has_binary_magic, migrate_frame,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::fs::File;
-use std::io::{BufReader, BufWriter};
+use std::io::{BufWriter, Write};
 use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
@@ -29,36 +35,215 @@ pub struct RecordingMetadata {
     pub description: Option<String>,
 }
 
+/// Most frames a [`FrameRing`] holds, whatever its duration.
+pub const MAX_RING_FRAMES: usize = 100_000;
+
+/// How a [`TelemetryRecorder`] keeps the frames it is given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RecorderMode {
+    /// Keep every frame from `start_recording` to `stop_recording`.
+    #[default]
+    Continuous,
+    /// Keep only the newest `capacity` of frames, by `timestamp_ns`, for
+    /// dumping on demand with [`TelemetryRecorder::save_recent`].
+    RingBuffer { capacity: Duration },
+}
+
+/// How a saved recording is encoded on disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RecordingFormat {
+    /// Pretty-printed JSON: readable, diffable, and upgradable across schema
+    /// versions.
+    #[default]
+    Json,
+    /// The compact codec from `racing_wheel_schemas::telemetry::codec`: a
+    /// header, a metadata record, then one record per frame. Much smaller
+    /// for long captures, but readable only at the schema version it was
+    /// written with.
+    Binary,
+}
+
+/// Frames ordered by `timestamp_ns`, holding at most `capacity` of telemetry
+/// time and [`MAX_RING_FRAMES`] frames; the oldest are evicted first.
+#[derive(Debug, Clone)]
+pub struct FrameRing {
+    capacity: Duration,
+    max_frames: usize,
+    frames: VecDeque<TelemetryFrame>,
+}
+
+impl FrameRing {
+    pub fn new(capacity: Duration) -> Self {
+        Self::with_max_frames(capacity, MAX_RING_FRAMES)
+    }
+
+    /// A ring holding fewer than [`MAX_RING_FRAMES`] frames.
+    pub fn with_max_frames(capacity: Duration, max_frames: usize) -> Self {
+        Self {
+            capacity,
+            max_frames: max_frames.clamp(1, MAX_RING_FRAMES),
+            frames: VecDeque::new(),
+        }
+    }
+
+    /// Insert `frame` in timestamp order, then evict frames older than
+    /// `capacity` before the newest one and any beyond the frame limit.
+    pub fn push(&mut self, frame: TelemetryFrame) {
+        let at = self
+            .frames
+            .partition_point(|f| f.timestamp_ns <= frame.timestamp_ns);
+        self.frames.insert(at, frame);
+
+        let capacity_ns = u64::try_from(self.capacity.as_nanos()).unwrap_or(u64::MAX);
+        let cutoff = self
+            .last_timestamp_ns()
+            .map_or(0, |newest| newest.saturating_sub(capacity_ns));
+        while self.frames.front().is_some_and(|f| f.timestamp_ns < cutoff) {
+            self.frames.pop_front();
+        }
+        while self.frames.len() > self.max_frames {
+            self.frames.pop_front();
+        }
+    }
+
+    /// Copy of the buffered frames, oldest first.
+    pub fn frames(&self) -> Vec<TelemetryFrame> {
+        self.frames.iter().cloned().collect()
+    }
+
+    pub fn first_timestamp_ns(&self) -> Option<u64> {
+        self.frames.front().map(|f| f.timestamp_ns)
+    }
+
+    pub fn last_timestamp_ns(&self) -> Option<u64> {
+        self.frames.back().map(|f| f.timestamp_ns)
+    }
+
+    pub fn capacity(&self) -> Duration {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+}
+
+/// What a recording saved to disk contains.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedRecording {
+    pub path: PathBuf,
+    pub game_id: String,
+    pub frame_count: usize,
+    pub first_timestamp_ns: Option<u64>,
+    pub last_timestamp_ns: Option<u64>,
+}
+
+impl SavedRecording {
+    /// Telemetry time between the first and last frame written.
+    pub fn span(&self) -> Duration {
+        match (self.first_timestamp_ns, self.last_timestamp_ns) {
+            (Some(first), Some(last)) => Duration::from_nanos(last.saturating_sub(first)),
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+enum RecordedFrames {
+    Continuous(Vec<TelemetryFrame>),
+    Ring(FrameRing),
+}
+
+impl RecordedFrames {
+    fn for_mode(mode: RecorderMode) -> Self {
+        match mode {
+            RecorderMode::Continuous => Self::Continuous(Vec::new()),
+            RecorderMode::RingBuffer { capacity } => Self::Ring(FrameRing::new(capacity)),
+        }
+    }
+
+    fn push(&mut self, frame: TelemetryFrame) {
+        match self {
+            Self::Continuous(frames) => frames.push(frame),
+            Self::Ring(ring) => ring.push(frame),
+        }
+    }
+
+    fn to_vec(&self) -> Vec<TelemetryFrame> {
+        match self {
+            Self::Continuous(frames) => frames.clone(),
+            Self::Ring(ring) => ring.frames(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::Continuous(frames) => frames.len(),
+            Self::Ring(ring) => ring.len(),
+        }
+    }
+
+    fn clear(&mut self) {
+        match self {
+            Self::Continuous(frames) => frames.clear(),
+            Self::Ring(ring) => ring.clear(),
+        }
+    }
+}
+
 /// Telemetry recorder for creating and persisting fixtures.
 pub struct TelemetryRecorder {
     output_path: PathBuf,
-    frames: Vec<TelemetryFrame>,
+    mode: RecorderMode,
+    format: RecordingFormat,
+    frames: RecordedFrames,
     start_time: Option<SystemTime>,
     game_id: String,
 }
 
 impl TelemetryRecorder {
     pub fn new(output_path: PathBuf) -> anyhow::Result<Self> {
+        Self::with_mode(output_path, RecorderMode::Continuous)
+    }
+
+    pub fn with_mode(output_path: PathBuf, mode: RecorderMode) -> anyhow::Result<Self> {
         if let Some(parent) = output_path.parent() {
             std::fs::create_dir_all(parent)?;
         }
 
         Ok(Self {
             output_path,
-            frames: Vec::new(),
+            mode,
+            format: RecordingFormat::Json,
+            frames: RecordedFrames::for_mode(mode),
             start_time: None,
             game_id: "unknown".to_string(),
         })
     }
 
+    /// Save recordings in `format` instead of JSON.
+    pub fn with_format(mut self, format: RecordingFormat) -> Self {
+        self.format = format;
+        self
+    }
+
     pub fn start_recording(&mut self, game_id: String) {
         self.game_id = game_id;
         self.start_time = Some(SystemTime::now());
         self.frames.clear();
     }
 
-    pub fn record_frame(&mut self, frame: TelemetryFrame) {
+    /// Buffer `frame`, stamped with the schema version this build writes.
+    pub fn record_frame(&mut self, mut frame: TelemetryFrame) {
         if self.start_time.is_some() {
+            frame.version = TELEMETRY_SCHEMA_VERSION;
             self.frames.push(frame);
         }
     }
@@ -66,6 +251,32 @@ impl TelemetryRecorder {
     pub fn stop_recording(
         &mut self,
         description: Option<String>,
+    ) -> anyhow::Result<TelemetryRecording> {
+        let recording = self.take_recording(description)?;
+        recording.save_as(&self.output_path, self.format)?;
+        Ok(recording)
+    }
+
+    /// End the recording, if any, and leave a complete, loadable file at the
+    /// output path, synced to disk. Before `start_recording` that is an empty
+    /// recording. The metadata's `frame_count` matches the frames written.
+    pub fn finalize(&mut self, description: Option<String>) -> anyhow::Result<SavedRecording> {
+        let recording = if self.is_recording() {
+            self.take_recording(description)?
+        } else {
+            TelemetryRecording::from_frames(self.game_id.clone(), self.frames.to_vec(), description)
+        };
+        let saved = recording.save_as(&self.output_path, self.format)?;
+        std::fs::OpenOptions::new()
+            .write(true)
+            .open(&self.output_path)?
+            .sync_all()?;
+        Ok(saved)
+    }
+
+    fn take_recording(
+        &mut self,
+        description: Option<String>,
     ) -> anyhow::Result<TelemetryRecording> {
         let start_time = self
             .start_time
@@ -74,17 +285,18 @@ impl TelemetryRecorder {
 
         let end_time = SystemTime::now();
         let duration = end_time.duration_since(start_time)?;
+        let frames = self.frames.to_vec();
 
-        let car_id = self.frames.iter().find_map(|f| f.data.car_id.clone());
-        let track_id = self.frames.iter().find_map(|f| f.data.track_id.clone());
+        let car_id = frames.iter().find_map(|f| f.data.car_id.clone());
+        let track_id = frames.iter().find_map(|f| f.data.track_id.clone());
 
         let metadata = RecordingMetadata {
             game_id: self.game_id.clone(),
             timestamp: start_time.duration_since(UNIX_EPOCH)?.as_secs(),
             duration_seconds: duration.as_secs_f64(),
-            frame_count: self.frames.len(),
+            frame_count: frames.len(),
             average_fps: if duration.as_secs_f64() > 0.0 {
-                self.frames.len() as f32 / duration.as_secs_f64() as f32
+                frames.len() as f32 / duration.as_secs_f64() as f32
             } else {
                 0.0
             },
@@ -93,26 +305,29 @@ impl TelemetryRecorder {
             description,
         };
 
-        let recording = TelemetryRecording {
-            metadata,
-            frames: self.frames.clone(),
-        };
-
-        self.save_recording(&recording)?;
-        Ok(recording)
+        Ok(TelemetryRecording { metadata, frames })
     }
 
-    fn save_recording(&self, recording: &TelemetryRecording) -> anyhow::Result<()> {
-        let file = File::create(&self.output_path)?;
-        let writer = BufWriter::new(file);
-        serde_json::to_writer_pretty(writer, recording)?;
-        Ok(())
+    /// Write the frames buffered so far to `path` without stopping the
+    /// recording; in ring buffer mode, that is the last `capacity` of them.
+    pub fn save_recent(&self, path: &Path) -> anyhow::Result<SavedRecording> {
+        TelemetryRecording::from_frames(self.game_id.clone(), self.frames.to_vec(), None)
+            .save_as(path, self.format)
     }
 
+    /// Read a recording saved by [`TelemetryRecording::save_as`] in either
+    /// format, telling them apart by the binary magic. JSON frames recorded
+    /// under an older schema version are upgraded.
     pub fn load_recording<P: AsRef<Path>>(path: P) -> anyhow::Result<TelemetryRecording> {
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-        Ok(serde_json::from_reader(reader)?)
+        let bytes = std::fs::read(path)?;
+        if has_binary_magic(&bytes) {
+            return TelemetryRecording::read_binary(&bytes);
+        }
+        let stored: StoredRecording = serde_json::from_slice(&bytes)?;
+        Ok(TelemetryRecording {
+            metadata: stored.metadata,
+            frames: migrate_frames(stored.frames)?,
+        })
     }
 
     pub fn frame_count(&self) -> usize {
@@ -122,6 +337,127 @@ impl TelemetryRecorder {
     pub fn is_recording(&self) -> bool {
         self.start_time.is_some()
     }
+
+    pub fn mode(&self) -> RecorderMode {
+        self.mode
+    }
+
+    pub fn format(&self) -> RecordingFormat {
+        self.format
+    }
+
+    pub fn output_path(&self) -> &Path {
+        &self.output_path
+    }
+}
+
+/// A recording as stored, before its frames are brought up to date.
+#[derive(Deserialize)]
+struct StoredRecording {
+    metadata: RecordingMetadata,
+    frames: Vec<serde_json::Value>,
+}
+
+fn migrate_frames(frames: Vec<serde_json::Value>) -> anyhow::Result<Vec<TelemetryFrame>> {
+    frames
+        .into_iter()
+        .enumerate()
+        .map(|(index, frame)| {
+            migrate_frame(frame).with_context(|| format!("recorded frame {index}"))
+        })
+        .collect()
+}
+
+impl TelemetryRecording {
+    /// Wrap frames captured outside a recorder session, such as a ring
+    /// buffer's, taking the duration from their first and last timestamps.
+    pub fn from_frames(
+        game_id: String,
+        mut frames: Vec<TelemetryFrame>,
+        description: Option<String>,
+    ) -> Self {
+        frames.sort_by_key(|f| f.timestamp_ns);
+        for frame in &mut frames {
+            frame.version = TELEMETRY_SCHEMA_VERSION;
+        }
+        let span = match (frames.first(), frames.last()) {
+            (Some(first), Some(last)) => {
+                Duration::from_nanos(last.timestamp_ns.saturating_sub(first.timestamp_ns))
+            }
+            _ => Duration::ZERO,
+        };
+        let started = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .saturating_sub(span);
+
+        let metadata = RecordingMetadata {
+            game_id,
+            timestamp: started.as_secs(),
+            duration_seconds: span.as_secs_f64(),
+            frame_count: frames.len(),
+            average_fps: if span.as_secs_f64() > 0.0 {
+                frames.len() as f32 / span.as_secs_f32()
+            } else {
+                0.0
+            },
+            car_id: frames.iter().find_map(|f| f.data.car_id.clone()),
+            track_id: frames.iter().find_map(|f| f.data.track_id.clone()),
+            description,
+        };
+
+        Self { metadata, frames }
+    }
+
+    /// Write the recording to `path` as pretty-printed JSON.
+    pub fn save(&self, path: &Path) -> anyhow::Result<SavedRecording> {
+        self.save_as(path, RecordingFormat::Json)
+    }
+
+    /// Write the recording to `path` in `format`; either is read back by
+    /// [`TelemetryRecorder::load_recording`].
+    pub fn save_as(&self, path: &Path, format: RecordingFormat) -> anyhow::Result<SavedRecording> {
+        let _lock = FileLock::acquire(path)?;
+        let file = File::create(path)?;
+        let mut writer = BufWriter::new(file);
+        match format {
+            RecordingFormat::Json => serde_json::to_writer_pretty(&mut writer, self)?,
+            RecordingFormat::Binary => self.write_binary(&mut writer)?,
+        }
+        writer.flush()?;
+        Ok(SavedRecording {
+            path: path.to_path_buf(),
+            game_id: self.metadata.game_id.clone(),
+            frame_count: self.frames.len(),
+            first_timestamp_ns: self.frames.first().map(|f| f.timestamp_ns),
+            last_timestamp_ns: self.frames.last().map(|f| f.timestamp_ns),
+        })
+    }
+}
+
+impl TelemetryRecording {
+    fn write_binary(&self, writer: impl Write) -> anyhow::Result<()> {
+        let mut records = FrameWriter::new(writer)?;
+        records.write_record(&serde_json::to_vec(&self.metadata)?)?;
+        for frame in &self.frames {
+            records.write_frame(frame)?;
+        }
+        records.flush()?;
+        Ok(())
+    }
+
+    fn read_binary(bytes: &[u8]) -> anyhow::Result<Self> {
+        let mut records = FrameReader::<_, TelemetryFrame>::new(bytes)?;
+        let metadata = records
+            .read_record()?
+            .context("binary recording has no metadata record")?;
+        let metadata = serde_json::from_slice(&metadata).context("recording metadata")?;
+        let frames = records
+            .enumerate()
+            .map(|(index, frame)| frame.with_context(|| format!("recorded frame {index}")))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { metadata, frames })
+    }
 }
 
 /// Telemetry playback helper for recordings.
@@ -447,8 +783,11 @@ impl TelemetryRecording {
             return Err(anyhow::anyhow!("binary data truncated (metadata)"));
         }
         let metadata: RecordingMetadata = serde_json::from_slice(&bytes[4..4 + meta_len])?;
-        let frames: Vec<TelemetryFrame> = serde_json::from_slice(&bytes[4 + meta_len..])?;
-        Ok(Self { metadata, frames })
+        let frames: Vec<serde_json::Value> = serde_json::from_slice(&bytes[4 + meta_len..])?;
+        Ok(Self {
+            metadata,
+            frames: migrate_frames(frames)?,
+        })
     }
 }
 
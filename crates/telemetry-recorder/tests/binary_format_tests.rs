@@ -0,0 +1,228 @@
+//! Binary recordings round-trip and are detected by their magic on load.
+
+use std::collections::BTreeMap;
+
+use racing_wheel_schemas::telemetry::{
+    BinaryCodecError, NormalizedTelemetry, PenaltyKind, PenaltyState, SessionTiming,
+    TelemetryFlags, TelemetryFrame, TelemetryValue, TireCorner, TireData, decode_frame,
+    encode_frame,
+};
+use racing_wheel_telemetry_recorder::{RecordingFormat, TelemetryRecorder, TelemetryRecording};
+use tempfile::tempdir;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn corner(temp: f32) -> TireCorner {
+    TireCorner {
+        surface_temp_c: Some(temp),
+        pressure_kpa: Some(172.4),
+        wear_fraction: Some(0.08),
+        slip_ratio: None,
+    }
+}
+
+fn representative_frame(sequence: u64) -> TelemetryFrame {
+    let progress = sequence as f32 * 0.01;
+    let extended = BTreeMap::from([
+        ("tire_temp_fl".to_string(), TelemetryValue::Float(88.5)),
+        ("fuel_laps".to_string(), TelemetryValue::Integer(14)),
+        ("pit_request".to_string(), TelemetryValue::Boolean(false)),
+        (
+            "tyre_compound".to_string(),
+            TelemetryValue::String("medium".to_string()),
+        ),
+        (
+            "suspension_travel".to_string(),
+            TelemetryValue::FloatArray(vec![0.041, 0.043, 0.038, 0.039]),
+        ),
+        (
+            "sector_times_ms".to_string(),
+            TelemetryValue::IntArray(vec![31_204, 44_918]),
+        ),
+        (
+            "ers".to_string(),
+            TelemetryValue::Map(BTreeMap::from([
+                ("store_j".to_string(), TelemetryValue::Float(3.1e6)),
+                ("deploy_mode".to_string(), TelemetryValue::Integer(2)),
+            ])),
+        ),
+    ]);
+    let data = NormalizedTelemetry {
+        speed_ms: 61.2 + progress,
+        steering_angle: -0.18,
+        throttle: 0.97,
+        clutch: 0.0,
+        rpm: 7350.0 + progress * 100.0,
+        max_rpm: 8500.0,
+        gear: 5,
+        num_gears: 6,
+        lateral_g: 1.6,
+        longitudinal_g: -0.3,
+        vertical_g: 0.02,
+        slip_ratio: 0.04,
+        slip_angle_fl: 0.02,
+        slip_angle_fr: 0.021,
+        slip_angle_rl: 0.015,
+        slip_angle_rr: 0.016,
+        tire_temps_c: [88, 90, 84, 85],
+        tire_pressures_psi: [25.1, 25.0, 24.6, 24.7],
+        ffb_scalar: 0.42,
+        ffb_torque_nm: 7.8,
+        flags: TelemetryFlags {
+            drs_available: true,
+            traction_control: true,
+            ..TelemetryFlags::default()
+        },
+        car_id: Some("ferrari_296_gt3".to_string()),
+        track_id: Some("spa".to_string()),
+        session_id: Some("race-1".to_string()),
+        penalties: Some(PenaltyState {
+            active_penalty_kind: PenaltyKind::TimePenalty,
+            penalty_time_s: 5.0,
+            track_limit_warnings: 2,
+            ..PenaltyState::default()
+        }),
+        tires: Some(TireData::from_corners([
+            corner(88.5),
+            corner(90.1),
+            corner(84.0),
+            corner(85.2),
+        ])),
+        timing: Some(SessionTiming {
+            current_lap_ms: Some(83_412.5),
+            best_lap_ms: Some(137_905.0),
+            lap_number: Some(12),
+            sector: Some(2),
+            ..SessionTiming::default()
+        }),
+        position: 3,
+        lap: 12,
+        fuel_percent: 0.41,
+        engine_temp_c: 96.0,
+        extended,
+        game_time_s: Some(2_412.75),
+        game_tick: Some(144_765),
+        sequence,
+        ..NormalizedTelemetry::default()
+    };
+    TelemetryFrame::new(data, 1_000_000_000 + sequence * 16_666_667, sequence, 1_347)
+}
+
+fn recording(frames: u64) -> TelemetryRecording {
+    TelemetryRecording::from_frames(
+        "acc".to_string(),
+        (0..frames).map(representative_frame).collect(),
+        Some("binary codec fixture".to_string()),
+    )
+}
+
+#[test]
+fn frame_with_every_value_variant_round_trips() -> TestResult {
+    let frame = representative_frame(7);
+    let decoded = decode_frame(&encode_frame(&frame)?)?;
+    assert_eq!(
+        serde_json::to_value(&decoded)?,
+        serde_json::to_value(&frame)?
+    );
+
+    let sparse = TelemetryFrame::new(NormalizedTelemetry::default(), 0, 0, 0);
+    let decoded = decode_frame(&encode_frame(&sparse)?)?;
+    assert_eq!(
+        serde_json::to_value(&decoded)?,
+        serde_json::to_value(&sparse)?
+    );
+    Ok(())
+}
+
+#[test]
+fn binary_recording_loads_by_magic() -> TestResult {
+    let dir = tempdir()?;
+    let path = dir.path().join("session.ortf");
+    let mut recorder = TelemetryRecorder::new(path.clone())?.with_format(RecordingFormat::Binary);
+    recorder.start_recording("acc".to_string());
+    for sequence in 0..5 {
+        recorder.record_frame(representative_frame(sequence));
+    }
+    let recorded = recorder.stop_recording(None)?;
+
+    let loaded = TelemetryRecorder::load_recording(&path)?;
+    assert_eq!(loaded.metadata.frame_count, 5);
+    assert_eq!(
+        serde_json::to_value(&loaded.frames)?,
+        serde_json::to_value(&recorded.frames)?
+    );
+    Ok(())
+}
+
+#[test]
+fn both_formats_load_the_same_recording() -> TestResult {
+    let dir = tempdir()?;
+    let source = recording(3);
+    let json = dir.path().join("session.json");
+    let binary = dir.path().join("session.bin");
+    source.save_as(&json, RecordingFormat::Json)?;
+    source.save_as(&binary, RecordingFormat::Binary)?;
+
+    let from_json = TelemetryRecorder::load_recording(&json)?;
+    let from_binary = TelemetryRecorder::load_recording(&binary)?;
+    assert_eq!(
+        serde_json::to_value(&from_json.frames)?,
+        serde_json::to_value(&from_binary.frames)?
+    );
+    assert_eq!(
+        from_binary.metadata.description,
+        source.metadata.description
+    );
+    Ok(())
+}
+
+#[test]
+fn corrupted_length_prefix_fails_to_load() -> TestResult {
+    let dir = tempdir()?;
+    let path = dir.path().join("session.bin");
+    recording(2).save_as(&path, RecordingFormat::Binary)?;
+
+    let mut bytes = std::fs::read(&path)?;
+    let metadata_len = u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]) as usize;
+    let first_frame = 12 + metadata_len;
+    bytes[first_frame..first_frame + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+    std::fs::write(&path, &bytes)?;
+
+    let Err(error) = TelemetryRecorder::load_recording(&path) else {
+        return Err("a corrupted length prefix must not load".into());
+    };
+    assert!(
+        format!("{error:#}").contains("recorded frame 0"),
+        "{error:#}"
+    );
+    assert!(matches!(
+        error.downcast_ref::<BinaryCodecError>(),
+        Some(BinaryCodecError::RecordTooLarge { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn binary_recording_is_at_least_forty_percent_smaller() -> TestResult {
+    let frame = representative_frame(42);
+    let binary = encode_frame(&frame)?.len();
+    let json = serde_json::to_vec(&frame)?.len();
+    assert!(
+        binary * 10 <= json * 6,
+        "binary {binary} bytes vs JSON {json} bytes"
+    );
+
+    let dir = tempdir()?;
+    let source = recording(60);
+    let json_path = dir.path().join("session.json");
+    let binary_path = dir.path().join("session.bin");
+    source.save_as(&json_path, RecordingFormat::Json)?;
+    source.save_as(&binary_path, RecordingFormat::Binary)?;
+    let json_len = std::fs::metadata(&json_path)?.len();
+    let binary_len = std::fs::metadata(&binary_path)?.len();
+    assert!(
+        binary_len * 10 <= json_len * 6,
+        "{binary_len} vs {json_len}"
+    );
+    Ok(())
+}
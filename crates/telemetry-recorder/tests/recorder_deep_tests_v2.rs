@@ -707,6 +707,7 @@ fn recording_preserves_all_flag_fields() -> TestResult {
         abs_active: true,
         engine_limiter: true,
         safety_car: true,
+        virtual_safety_car: true,
         formation_lap: true,
         session_paused: true,
     };
@@ -732,6 +733,7 @@ fn recording_preserves_all_flag_fields() -> TestResult {
     assert!(f.abs_active);
     assert!(f.engine_limiter);
     assert!(f.safety_car);
+    assert!(f.virtual_safety_car);
     assert!(f.formation_lap);
     assert!(f.session_paused);
     Ok(())
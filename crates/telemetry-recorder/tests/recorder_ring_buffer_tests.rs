@@ -0,0 +1,154 @@
+//! Ring buffer ("black box") recording tests.
+//!
+//! Covers: duration and frame-count eviction, out-of-order frames, and
+//! saving the buffered window while recording continues.
+
+use std::time::Duration;
+
+use racing_wheel_schemas::telemetry::{NormalizedTelemetry, TelemetryFrame};
+use racing_wheel_telemetry_recorder::{
+    FrameRing, MAX_RING_FRAMES, RecorderMode, TelemetryRecorder,
+};
+use tempfile::tempdir;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const SECOND_NS: u64 = 1_000_000_000;
+
+fn frame_at(timestamp_ns: u64, sequence: u64) -> TelemetryFrame {
+    let telemetry = NormalizedTelemetry::builder().rpm(5000.0).build();
+    TelemetryFrame::new(telemetry, timestamp_ns, sequence, 64)
+}
+
+fn sixty_second_ring(path: std::path::PathBuf) -> anyhow::Result<TelemetryRecorder> {
+    TelemetryRecorder::with_mode(
+        path,
+        RecorderMode::RingBuffer {
+            capacity: Duration::from_secs(60),
+        },
+    )
+}
+
+#[test]
+fn save_recent_keeps_only_the_last_sixty_seconds_in_order() -> TestResult {
+    let dir = tempdir()?;
+    let mut recorder = sixty_second_ring(dir.path().join("unused.json"))?;
+    recorder.start_recording("acc".to_string());
+
+    // Three minutes at 10 Hz.
+    for i in 0..=1800u64 {
+        recorder.record_frame(frame_at(i * SECOND_NS / 10, i));
+    }
+
+    let path = dir.path().join("recent.json");
+    let saved = recorder.save_recent(&path)?;
+    assert_eq!(saved.game_id, "acc");
+    assert_eq!(saved.frame_count, 601);
+    assert_eq!(saved.first_timestamp_ns, Some(120 * SECOND_NS));
+    assert_eq!(saved.last_timestamp_ns, Some(180 * SECOND_NS));
+    assert_eq!(saved.span(), Duration::from_secs(60));
+
+    let loaded = TelemetryRecorder::load_recording(&path)?;
+    assert_eq!(loaded.metadata.game_id, "acc");
+    assert_eq!(loaded.metadata.frame_count, 601);
+    assert!((loaded.metadata.duration_seconds - 60.0).abs() < 1e-9);
+    assert!(
+        loaded
+            .frames
+            .windows(2)
+            .all(|pair| pair[0].timestamp_ns < pair[1].timestamp_ns)
+    );
+    assert!(
+        loaded
+            .frames
+            .iter()
+            .all(|f| f.timestamp_ns >= 120 * SECOND_NS)
+    );
+
+    // Saving does not end the recording.
+    assert!(recorder.is_recording());
+    recorder.record_frame(frame_at(181 * SECOND_NS, 1810));
+    assert_eq!(recorder.frame_count(), 592);
+    Ok(())
+}
+
+#[test]
+fn ring_buffer_stop_recording_writes_the_window() -> TestResult {
+    let dir = tempdir()?;
+    let path = dir.path().join("ring.json");
+    let mut recorder = sixty_second_ring(path.clone())?;
+    assert_eq!(
+        recorder.mode(),
+        RecorderMode::RingBuffer {
+            capacity: Duration::from_secs(60)
+        }
+    );
+    recorder.start_recording("iracing".to_string());
+    for i in 0..=90u64 {
+        recorder.record_frame(frame_at(i * SECOND_NS, i));
+    }
+
+    let recording = recorder.stop_recording(None)?;
+    assert_eq!(recording.frames.len(), 61);
+    assert_eq!(
+        recording.frames.first().map(|f| f.timestamp_ns),
+        Some(30 * SECOND_NS)
+    );
+    assert_eq!(TelemetryRecorder::load_recording(&path)?.frames.len(), 61);
+    Ok(())
+}
+
+#[test]
+fn late_frames_are_inserted_in_timestamp_order() {
+    let mut ring = FrameRing::new(Duration::from_secs(60));
+    for (timestamp_s, sequence) in [(1, 0), (3, 1), (2, 2), (70, 3), (5, 4)] {
+        ring.push(frame_at(timestamp_s * SECOND_NS, sequence));
+    }
+    // Everything before 10 s falls outside the window ending at 70 s.
+    let sequences: Vec<u64> = ring.frames().iter().map(|f| f.sequence).collect();
+    assert_eq!(sequences, [3]);
+
+    let mut ring = FrameRing::new(Duration::from_secs(60));
+    for (timestamp_s, sequence) in [(1, 0), (3, 1), (2, 2)] {
+        ring.push(frame_at(timestamp_s * SECOND_NS, sequence));
+    }
+    let sequences: Vec<u64> = ring.frames().iter().map(|f| f.sequence).collect();
+    assert_eq!(sequences, [0, 2, 1]);
+}
+
+#[test]
+fn frame_limit_bounds_the_ring_regardless_of_duration() {
+    let mut ring = FrameRing::with_max_frames(Duration::from_secs(3600), 100);
+    for i in 0..250u64 {
+        ring.push(frame_at(i * 1_000_000, i));
+    }
+    assert_eq!(ring.len(), 100);
+    assert_eq!(ring.first_timestamp_ns(), Some(150 * 1_000_000));
+    assert_eq!(ring.last_timestamp_ns(), Some(249 * 1_000_000));
+
+    let ring = FrameRing::with_max_frames(Duration::from_secs(1), usize::MAX);
+    assert!(ring.is_empty());
+    assert_eq!(ring.capacity(), Duration::from_secs(1));
+    // Larger limits are clamped to the crate-wide cap.
+    let mut ring = ring;
+    for i in 0..(MAX_RING_FRAMES as u64 + 10) {
+        ring.push(frame_at(i, i));
+    }
+    assert_eq!(ring.len(), MAX_RING_FRAMES);
+}
+
+#[test]
+fn continuous_save_recent_writes_everything_so_far() -> TestResult {
+    let dir = tempdir()?;
+    let mut recorder = TelemetryRecorder::new(dir.path().join("session.json"))?;
+    assert_eq!(recorder.mode(), RecorderMode::Continuous);
+    recorder.start_recording("acc".to_string());
+    for i in 0..=180u64 {
+        recorder.record_frame(frame_at(i * SECOND_NS, i));
+    }
+
+    let saved = recorder.save_recent(&dir.path().join("so_far.json"))?;
+    assert_eq!(saved.frame_count, 181);
+    assert_eq!(saved.span(), Duration::from_secs(180));
+    Ok(())
+}
@@ -0,0 +1,152 @@
+//! Recordings made before frames were versioned load as current-version frames.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use racing_wheel_schemas::telemetry::{
+    NormalizedTelemetry, TELEMETRY_SCHEMA_VERSION, TelemetryFlags, TelemetryFrame, TelemetryValue,
+};
+use racing_wheel_telemetry_recorder::{TelemetryRecorder, TelemetryRecording};
+use tempfile::tempdir;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn expected_frame(
+    sequence: u64,
+    speed_ms: f32,
+    steering_angle: f32,
+    throttle: f32,
+    rpm: f32,
+    tire_temp: f32,
+) -> TelemetryFrame {
+    let data = NormalizedTelemetry {
+        speed_ms,
+        steering_angle,
+        throttle,
+        rpm,
+        gear: 4,
+        flags: TelemetryFlags {
+            yellow_flag: true,
+            green_flag: false,
+            traction_control: true,
+            ..TelemetryFlags::default()
+        },
+        extended: BTreeMap::from([("tire_temp_fl".to_string(), TelemetryValue::Float(tire_temp))]),
+        sequence,
+        ..NormalizedTelemetry::default()
+    };
+    TelemetryFrame::new(data, 1_000_000_000 + sequence * 16_000_000, sequence, 1024)
+}
+
+fn expected_frames() -> Result<serde_json::Value, serde_json::Error> {
+    serde_json::to_value(vec![
+        expected_frame(0, 41.5, -0.12, 0.9, 6200.0, 85.0),
+        expected_frame(1, 41.9, -0.1, 0.92, 6280.0, 85.5),
+    ])
+}
+
+#[test]
+fn unversioned_recording_with_every_original_flag_loads_as_current() -> TestResult {
+    let recording =
+        TelemetryRecorder::load_recording(fixture("recording_unversioned_full_flags.json"))?;
+    assert!(
+        recording
+            .frames
+            .iter()
+            .all(|frame| frame.version == TELEMETRY_SCHEMA_VERSION)
+    );
+    assert_eq!(serde_json::to_value(&recording.frames)?, expected_frames()?);
+    Ok(())
+}
+
+#[test]
+fn unversioned_recording_with_partial_flags_and_tyre_keys_loads_as_current() -> TestResult {
+    let recording =
+        TelemetryRecorder::load_recording(fixture("recording_unversioned_tyre_keys.json"))?;
+    let frame = recording.frames.first().ok_or("fixture has no frames")?;
+    assert!(!frame.data.extended.contains_key("tyre_temp_fl"));
+    assert!(!frame.data.flags.ers_active);
+    assert_eq!(serde_json::to_value(&recording.frames)?, expected_frames()?);
+    Ok(())
+}
+
+#[test]
+fn historical_shapes_load_identically() -> TestResult {
+    let full = TelemetryRecorder::load_recording(fixture("recording_unversioned_full_flags.json"))?;
+    let renamed =
+        TelemetryRecorder::load_recording(fixture("recording_unversioned_tyre_keys.json"))?;
+    assert_eq!(
+        serde_json::to_value(&full.frames)?,
+        serde_json::to_value(&renamed.frames)?
+    );
+    Ok(())
+}
+
+#[test]
+fn saved_frames_carry_the_current_version() -> TestResult {
+    let dir = tempdir()?;
+    let path = dir.path().join("recording.json");
+    let mut recorder = TelemetryRecorder::new(path.clone())?;
+    recorder.start_recording("acc".to_string());
+    let mut stale = expected_frame(0, 41.5, -0.12, 0.9, 6200.0, 85.0);
+    stale.version = 1;
+    recorder.record_frame(stale);
+    recorder.stop_recording(None)?;
+
+    let saved: serde_json::Value = serde_json::from_slice(&std::fs::read(&path)?)?;
+    assert_eq!(
+        saved["frames"][0]["version"],
+        serde_json::json!(TELEMETRY_SCHEMA_VERSION)
+    );
+    Ok(())
+}
+
+#[test]
+fn future_version_recording_is_rejected() -> TestResult {
+    let mut recording: serde_json::Value = serde_json::from_slice(&std::fs::read(fixture(
+        "recording_unversioned_full_flags.json",
+    ))?)?;
+    let future = TELEMETRY_SCHEMA_VERSION + 1;
+    recording["frames"][1]["version"] = serde_json::json!(future);
+
+    let dir = tempdir()?;
+    let path = dir.path().join("future.json");
+    std::fs::write(&path, serde_json::to_vec(&recording)?)?;
+
+    let Err(error) = TelemetryRecorder::load_recording(&path) else {
+        return Err("a frame from a newer schema must not load".into());
+    };
+    let message = format!("{error:#}");
+    assert!(message.contains("recorded frame 1"), "{message}");
+    assert!(message.contains(&format!("version {future}")), "{message}");
+    Ok(())
+}
+
+#[test]
+fn binary_import_migrates_frames() -> TestResult {
+    let recording =
+        TelemetryRecorder::load_recording(fixture("recording_unversioned_tyre_keys.json"))?;
+    let mut frames = serde_json::to_value(&recording.frames)?;
+    if let Some(frames) = frames.as_array_mut() {
+        for frame in frames {
+            if let Some(frame) = frame.as_object_mut() {
+                frame.remove("version");
+            }
+        }
+    }
+    let meta = serde_json::to_vec(&recording.metadata)?;
+    let mut bytes = u32::try_from(meta.len())?.to_le_bytes().to_vec();
+    bytes.extend_from_slice(&meta);
+    bytes.extend_from_slice(&serde_json::to_vec(&frames)?);
+
+    let imported = TelemetryRecording::from_binary(&bytes)?;
+    assert_eq!(serde_json::to_value(&imported.frames)?, expected_frames()?);
+    Ok(())
+}
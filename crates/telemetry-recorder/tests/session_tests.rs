@@ -6,8 +6,8 @@
 
 use racing_wheel_schemas::telemetry::{NormalizedTelemetry, TelemetryFlags, TelemetryFrame};
 use racing_wheel_telemetry_recorder::{
-    FieldDiff, RecordingMetadata, TelemetryPlayer, TelemetryRecorder, TelemetryRecording,
-    TestFixtureGenerator, TestScenario,
+    FieldDiff, RecordingFormat, RecordingMetadata, TelemetryPlayer, TelemetryRecorder,
+    TelemetryRecording, TestFixtureGenerator, TestScenario,
 };
 use std::thread;
 use std::time::Duration;
@@ -156,6 +156,40 @@ fn session_frames_before_start_are_ignored() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn session_finalize_writes_a_loadable_file() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let path = dir.path().join("session.bin");
+    let mut rec = TelemetryRecorder::new(path.clone())?.with_format(RecordingFormat::Binary);
+
+    rec.start_recording("acc".to_string());
+    for seq in 0..5 {
+        rec.record_frame(make_frame(4000.0, 30.0, 3, seq * 1_000, seq));
+    }
+    let saved = rec.finalize(Some("shutdown".to_string()))?;
+    assert!(!rec.is_recording());
+    assert_eq!(saved.frame_count, 5);
+
+    let loaded = TelemetryRecorder::load_recording(&path)?;
+    assert_eq!(loaded.metadata.frame_count, loaded.frames.len());
+    assert_eq!(loaded.frames.len(), 5);
+    assert_eq!(loaded.metadata.game_id, "acc");
+    Ok(())
+}
+
+#[test]
+fn session_finalize_before_start_writes_an_empty_recording() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let path = dir.path().join("session.json");
+    let mut rec = TelemetryRecorder::new(path.clone())?;
+
+    let saved = rec.finalize(None)?;
+    assert_eq!(saved.frame_count, 0);
+    let loaded = TelemetryRecorder::load_recording(&path)?;
+    assert_eq!((loaded.metadata.frame_count, loaded.frames.len()), (0, 0));
+    Ok(())
+}
+
 // ──────────────────────────────────────────────────────────────────────────────
 // 2. Recording format — save, load roundtrip
 // ──────────────────────────────────────────────────────────────────────────────
@@ -0,0 +1,345 @@
+//! Game id aliases: the built-in [`GAME_ID_MIGRATIONS`] plus user-defined
+//! names for the same integrations, such as Steam app ids.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::{GAME_ID_MIGRATIONS, GameSupportMatrix};
+
+/// File name of the user alias table kept beside a support matrix.
+pub const GAME_ID_ALIASES_FILE: &str = "aliases.json";
+
+/// Why an alias could not be added or an alias file could not be merged.
+#[derive(Debug)]
+pub enum GameIdAliasError {
+    /// The alias is empty once trimmed.
+    EmptyAlias,
+    /// The alias is itself a canonical game id.
+    CollidesWithGameId { alias: String },
+    /// The alias already resolves to a different game.
+    Conflict {
+        alias: String,
+        existing: String,
+        requested: String,
+    },
+    /// The target is not a game id of the registry's matrix.
+    UnknownGameId { alias: String, game_id: String },
+    /// The alias file could not be read.
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// The alias file is not a JSON object of alias to game id strings.
+    Parse {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+}
+
+impl fmt::Display for GameIdAliasError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyAlias => write!(f, "game id alias is empty"),
+            Self::CollidesWithGameId { alias } => {
+                write!(f, "alias `{alias}` is already a canonical game id")
+            }
+            Self::Conflict {
+                alias,
+                existing,
+                requested,
+            } => write!(
+                f,
+                "alias `{alias}` already resolves to `{existing}`, not `{requested}`"
+            ),
+            Self::UnknownGameId { alias, game_id } => {
+                write!(f, "alias `{alias}` targets unknown game id `{game_id}`")
+            }
+            Self::Io { path, source } => {
+                write!(f, "failed to read alias file {}: {source}", path.display())
+            }
+            Self::Parse { path, source } => {
+                write!(f, "invalid alias file {}: {source}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for GameIdAliasError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::Parse { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Alias registry resolving alternative game ids to canonical ones.
+///
+/// Matching follows [`crate::GameIdMigration::matches`]: ASCII case is ignored
+/// and `_`, `-` and spaces are the same separator. Built-in renames always
+/// win over user aliases.
+#[derive(Debug, Clone, Default)]
+pub struct GameIdAliases {
+    /// Ids aliases must not shadow and may target; empty accepts any target.
+    canonical_ids: BTreeSet<String>,
+    /// User aliases keyed by their folded form.
+    user: BTreeMap<String, String>,
+}
+
+impl GameIdAliases {
+    /// The built-in renames only, accepting user aliases to any game id.
+    pub const fn built_in() -> Self {
+        Self {
+            canonical_ids: BTreeSet::new(),
+            user: BTreeMap::new(),
+        }
+    }
+
+    /// The built-in renames, with user aliases restricted to `matrix`'s games.
+    pub fn for_matrix(matrix: &GameSupportMatrix) -> Self {
+        Self {
+            canonical_ids: matrix.games.keys().cloned().collect(),
+            user: BTreeMap::new(),
+        }
+    }
+
+    /// Canonical id for `game_id`, or `game_id` trimmed when it is no alias.
+    pub fn resolve<'a>(&'a self, game_id: &'a str) -> &'a str {
+        let game_id = game_id.trim();
+        if let Some(migration) = GAME_ID_MIGRATIONS
+            .iter()
+            .find(|migration| migration.matches(game_id))
+        {
+            return migration.new;
+        }
+        if self.user.is_empty() {
+            return game_id;
+        }
+        self.user
+            .get(&fold(game_id))
+            .map_or(game_id, String::as_str)
+    }
+
+    /// Add `alias` for `canonical`, which may itself be an alias.
+    ///
+    /// Re-adding an alias for the game it already resolves to is a no-op.
+    pub fn add_alias(&mut self, alias: &str, canonical: &str) -> Result<(), GameIdAliasError> {
+        let alias = alias.trim();
+        if alias.is_empty() {
+            return Err(GameIdAliasError::EmptyAlias);
+        }
+        let target = self.resolve(canonical).to_string();
+        if !self.canonical_ids.is_empty() && !self.canonical_ids.contains(&target) {
+            return Err(GameIdAliasError::UnknownGameId {
+                alias: alias.to_string(),
+                game_id: target,
+            });
+        }
+
+        let key = fold(alias);
+        let is_canonical = self
+            .canonical_ids
+            .iter()
+            .map(String::as_str)
+            .chain(GAME_ID_MIGRATIONS.iter().map(|migration| migration.new))
+            .any(|game_id| fold(game_id) == key);
+        if is_canonical {
+            return Err(GameIdAliasError::CollidesWithGameId {
+                alias: alias.to_string(),
+            });
+        }
+
+        let existing = self.resolve(alias);
+        if existing != alias {
+            return if existing == target {
+                Ok(())
+            } else {
+                Err(GameIdAliasError::Conflict {
+                    alias: alias.to_string(),
+                    existing: existing.to_string(),
+                    requested: target,
+                })
+            };
+        }
+        self.user.insert(key, target);
+        Ok(())
+    }
+
+    /// Every alias and the id it resolves to, built-in renames first.
+    pub fn aliases(&self) -> impl Iterator<Item = (&str, &str)> {
+        GAME_ID_MIGRATIONS
+            .iter()
+            .map(|migration| (migration.old, migration.new))
+            .chain(
+                self.user
+                    .iter()
+                    .map(|(alias, game_id)| (alias.as_str(), game_id.as_str())),
+            )
+    }
+
+    /// Add every `(alias, game id)` pair, or none if one is rejected.
+    ///
+    /// Returns how many pairs were merged.
+    pub fn merge<A, G>(
+        &mut self,
+        entries: impl IntoIterator<Item = (A, G)>,
+    ) -> Result<usize, GameIdAliasError>
+    where
+        A: AsRef<str>,
+        G: AsRef<str>,
+    {
+        let mut merged = self.clone();
+        let mut count = 0;
+        for (alias, game_id) in entries {
+            merged.add_alias(alias.as_ref(), game_id.as_ref())?;
+            count += 1;
+        }
+        *self = merged;
+        Ok(count)
+    }
+
+    /// Merge the JSON object of alias to game id at `path`; see [`Self::merge`].
+    pub fn merge_file(&mut self, path: &Path) -> Result<usize, GameIdAliasError> {
+        let json = std::fs::read_to_string(path).map_err(|source| GameIdAliasError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let entries: BTreeMap<String, String> =
+            serde_json::from_str(&json).map_err(|source| GameIdAliasError::Parse {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        self.merge(entries)
+    }
+}
+
+/// Where the alias file of the matrix at `matrix_path` lives.
+pub fn aliases_path_beside(matrix_path: &Path) -> PathBuf {
+    matrix_path.with_file_name(GAME_ID_ALIASES_FILE)
+}
+
+fn fold(game_id: &str) -> String {
+    game_id
+        .chars()
+        .map(|c| match c {
+            '-' | ' ' => '_',
+            c => c.to_ascii_lowercase(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GAME_ID_ALIASES_FILE, GameIdAliasError, GameIdAliases, aliases_path_beside};
+    use crate::load_default_matrix;
+    use std::path::Path;
+
+    #[test]
+    fn built_in_renames_still_resolve() {
+        let aliases = GameIdAliases::built_in();
+        assert_eq!(aliases.resolve("EA-WRC"), "eawrc");
+        assert_eq!(aliases.resolve(" f1_2025 "), "f1_25");
+        assert_eq!(aliases.resolve("iracing"), "iracing");
+        assert_eq!(aliases.aliases().count(), crate::GAME_ID_MIGRATIONS.len());
+    }
+
+    #[test]
+    fn user_aliases_resolve_with_folded_separators() -> Result<(), Box<dyn std::error::Error>> {
+        let mut aliases = GameIdAliases::for_matrix(&load_default_matrix()?);
+        aliases.add_alias("1551360", "forza_horizon_5")?;
+        aliases.add_alias("Forza Horizon", "forza_horizon_5")?;
+        // Targets may be built-in aliases themselves.
+        aliases.add_alias("wrc23", "ea_wrc")?;
+
+        assert_eq!(aliases.resolve("1551360"), "forza_horizon_5");
+        assert_eq!(aliases.resolve("forza-horizon"), "forza_horizon_5");
+        assert_eq!(aliases.resolve("WRC23"), "eawrc");
+        assert!(
+            aliases
+                .aliases()
+                .any(|pair| pair == ("1551360", "forza_horizon_5"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn conflicting_aliases_are_rejected() -> Result<(), Box<dyn std::error::Error>> {
+        let mut aliases = GameIdAliases::for_matrix(&load_default_matrix()?);
+        aliases.add_alias("1551360", "forza_horizon_5")?;
+        aliases.add_alias("1551360", "forza_horizon_5")?;
+
+        assert!(matches!(
+            aliases.add_alias("1551360", "acc"),
+            Err(GameIdAliasError::Conflict { .. })
+        ));
+        assert!(matches!(
+            aliases.add_alias("ea_wrc", "acc"),
+            Err(GameIdAliasError::Conflict { .. })
+        ));
+        assert!(matches!(
+            aliases.add_alias("ACC", "iracing"),
+            Err(GameIdAliasError::CollidesWithGameId { .. })
+        ));
+        assert!(matches!(
+            aliases.add_alias("123", "not_a_game"),
+            Err(GameIdAliasError::UnknownGameId { .. })
+        ));
+        assert!(matches!(
+            aliases.add_alias("  ", "acc"),
+            Err(GameIdAliasError::EmptyAlias)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn merging_is_all_or_nothing() -> Result<(), Box<dyn std::error::Error>> {
+        let mut aliases = GameIdAliases::for_matrix(&load_default_matrix()?);
+        assert!(
+            aliases
+                .merge([("1551360", "forza_horizon_5"), ("acc", "iracing")])
+                .is_err()
+        );
+        assert_eq!(aliases.resolve("1551360"), "1551360");
+
+        assert_eq!(aliases.merge([("1551360", "forza_horizon_5")])?, 1);
+        assert_eq!(aliases.resolve("1551360"), "forza_horizon_5");
+        Ok(())
+    }
+
+    #[test]
+    fn alias_files_are_json_objects() -> Result<(), Box<dyn std::error::Error>> {
+        let path = std::env::temp_dir().join(format!(
+            "openracing-aliases-{}-{}",
+            std::process::id(),
+            GAME_ID_ALIASES_FILE
+        ));
+        std::fs::write(
+            &path,
+            r#"{"1551360": "forza_horizon_5", "fh4": "forza_horizon_4"}"#,
+        )?;
+        let mut aliases = GameIdAliases::for_matrix(&load_default_matrix()?);
+        let merged = aliases.merge_file(&path);
+        std::fs::write(&path, "[\"1551360\"]")?;
+        let malformed = aliases.merge_file(&path);
+        std::fs::remove_file(&path)?;
+
+        assert_eq!(merged?, 2);
+        assert_eq!(aliases.resolve("FH4"), "forza_horizon_4");
+        assert!(matches!(malformed, Err(GameIdAliasError::Parse { .. })));
+        assert!(matches!(
+            aliases.merge_file(&path),
+            Err(GameIdAliasError::Io { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn alias_file_sits_beside_the_matrix() {
+        assert_eq!(
+            aliases_path_beside(Path::new("/etc/openracing/game_support_matrix.yaml")),
+            Path::new("/etc/openracing/aliases.json")
+        );
+    }
+}
@@ -5,6 +5,10 @@
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
+mod aliases;
+
+pub use aliases::{GAME_ID_ALIASES_FILE, GameIdAliasError, GameIdAliases, aliases_path_beside};
+
 pub const TELEMETRY_SUPPORT_MATRIX_YAML: &str = include_str!("game_support_matrix.yaml");
 
 /// Supported game matrix loaded from a static configuration source.
@@ -31,9 +35,115 @@ pub struct GameSupport {
     #[serde(default)]
     pub status: GameSupportStatus,
     pub config_writer: String,
+    /// The game is known to have no config writer, so registry parity checks
+    /// exempt it rather than report it missing.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub no_config_writer: bool,
+    /// Integration facts beyond what the rest of the entry implies; see
+    /// [`GameSupport::effective_capabilities`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<GameCapabilities>,
     pub auto_detect: AutoDetectConfig,
 }
 
+/// How telemetry gets from a game to OpenRacing.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TransportKind {
+    /// The game sends UDP packets.
+    Udp,
+    /// The game publishes a shared memory map.
+    SharedMemory,
+    /// A third-party tool, such as SimHub, relays the game's data.
+    Bridge,
+}
+
+/// What a UI needs to explain an integration: how data arrives, on which
+/// port, and whether anything has to be installed first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GameCapabilities {
+    #[serde(default)]
+    pub transport: Vec<TransportKind>,
+    /// Port the game sends to by default, for UDP and bridge transports.
+    #[serde(default)]
+    pub default_port: Option<u16>,
+    /// A game plugin or bridge tool must be installed.
+    #[serde(default)]
+    pub requires_plugin: bool,
+    /// Normalized field names the integration can provide.
+    #[serde(default)]
+    pub supported_fields: Vec<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+impl GameSupport {
+    /// This game's capabilities. Transport, default port and supported fields
+    /// left empty in the `capabilities` block, or the whole block when it is
+    /// absent, are derived from the telemetry method, output target and
+    /// per-version field lists.
+    pub fn effective_capabilities(&self) -> GameCapabilities {
+        let derived = self.derived_capabilities();
+        let Some(explicit) = &self.capabilities else {
+            return derived;
+        };
+        let mut capabilities = explicit.clone();
+        if capabilities.transport.is_empty() {
+            capabilities.transport = derived.transport;
+        }
+        if capabilities.default_port.is_none() {
+            capabilities.default_port = derived.default_port;
+        }
+        if capabilities.supported_fields.is_empty() {
+            capabilities.supported_fields = derived.supported_fields;
+        }
+        capabilities
+    }
+
+    fn derived_capabilities(&self) -> GameCapabilities {
+        let method = self.telemetry.method.as_str();
+        let transport: Vec<TransportKind> = if method.contains("simhub") {
+            vec![TransportKind::Bridge]
+        } else if method.contains("shared_memory") {
+            vec![TransportKind::SharedMemory]
+        } else if method.contains("udp") {
+            vec![TransportKind::Udp]
+        } else {
+            Vec::new()
+        };
+        let networked = transport
+            .iter()
+            .any(|kind| matches!(kind, TransportKind::Udp | TransportKind::Bridge));
+        let default_port = self
+            .telemetry
+            .output_target
+            .as_deref()
+            .filter(|_| networked)
+            .and_then(|target| target.rsplit_once(':'))
+            .and_then(|(_, port)| port.parse::<u16>().ok())
+            .filter(|port| *port != 0);
+
+        let mut supported_fields: Vec<String> = Vec::new();
+        for field in self
+            .versions
+            .iter()
+            .flat_map(|version| &version.supported_fields)
+        {
+            if !supported_fields.contains(field) {
+                supported_fields.push(field.clone());
+            }
+        }
+
+        GameCapabilities {
+            requires_plugin: transport.contains(&TransportKind::Bridge),
+            transport,
+            default_port,
+            supported_fields,
+            notes: None,
+        }
+    }
+}
+
 /// Version-specific game support details.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameVersion {
@@ -55,6 +165,23 @@ pub struct TelemetrySupport {
     pub high_rate_update_rate_hz: Option<u32>,
     pub output_target: Option<String>,
     pub fields: TelemetryFieldMapping,
+    #[serde(default)]
+    pub game_time: GameTimeSemantics,
+}
+
+/// What the adapter's `game_time_s` / `game_tick` values count.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum GameTimeSemantics {
+    /// The game reports no clock; consumers fall back to arrival time.
+    #[default]
+    Unavailable,
+    /// Seconds elapsed since the session started; pauses stop the clock.
+    SessionElapsed,
+    /// In-game time of day; may jump or run accelerated.
+    TimeOfDay,
+    /// Only a frame or packet counter is available.
+    TickOnly,
 }
 
 /// Mapping of normalized telemetry fields to game-specific fields.
@@ -78,29 +205,70 @@ pub struct AutoDetectConfig {
     pub install_paths: Vec<String>,
 }
 
-/// Normalize game IDs at the boundary (historical alias support).
-pub fn normalize_game_id(game_id: &str) -> &str {
-    let game_id = game_id.trim();
+/// A renamed game integration.
+///
+/// Callers and persisted data that still use `old` resolve to `new` through
+/// [`normalize_game_id`]; `migrate_persisted_data` in the orchestrator rewrites
+/// stored ids offline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameIdMigration {
+    /// Retired game id.
+    pub old: &'static str,
+    /// Canonical game id replacing it.
+    pub new: &'static str,
+    /// Contract version from which `new` is the canonical id.
+    pub since: &'static str,
+}
 
-    if game_id.eq_ignore_ascii_case("ea_wrc")
-        || game_id.eq_ignore_ascii_case("ea-wrc")
-        || game_id.eq_ignore_ascii_case("ea wrc")
-    {
-        "eawrc"
-    } else if game_id.eq_ignore_ascii_case("f1_2025")
-        || game_id.eq_ignore_ascii_case("f1-2025")
-        || game_id.eq_ignore_ascii_case("f1 2025")
-    {
-        // f1_2025 is an alias for the native EA protocol adapter (f1_25)
-        "f1_25"
-    } else {
-        game_id
+impl GameIdMigration {
+    /// Whether `game_id` names the retired id, ignoring ASCII case and treating
+    /// `_`, `-` and spaces as the same separator.
+    pub fn matches(&self, game_id: &str) -> bool {
+        let canonical = |c: char| match c {
+            '-' | ' ' => '_',
+            c => c.to_ascii_lowercase(),
+        };
+        game_id.len() == self.old.len()
+            && game_id
+                .chars()
+                .map(canonical)
+                .eq(self.old.chars().map(canonical))
     }
 }
 
+/// Game id renames, oldest first. Add a row here when an integration is renamed.
+pub const GAME_ID_MIGRATIONS: &[GameIdMigration] = &[
+    GameIdMigration {
+        old: "ea_wrc",
+        new: "eawrc",
+        since: "0.1.0",
+    },
+    // f1_2025 is an alias for the native EA protocol adapter (f1_25)
+    GameIdMigration {
+        old: "f1_2025",
+        new: "f1_25",
+        since: "0.1.0",
+    },
+];
+
+static BUILT_IN_ALIASES: GameIdAliases = GameIdAliases::built_in();
+
+/// Normalize game IDs at the boundary (historical alias support).
+///
+/// Resolves the built-in renames only; use a [`GameIdAliases`] for
+/// user-defined aliases.
+pub fn normalize_game_id(game_id: &str) -> &str {
+    BUILT_IN_ALIASES.resolve(game_id)
+}
+
 /// Load the canonical game support matrix.
 pub fn load_default_matrix() -> Result<GameSupportMatrix, serde_yaml::Error> {
-    serde_yaml::from_str(TELEMETRY_SUPPORT_MATRIX_YAML)
+    parse_matrix_yaml(TELEMETRY_SUPPORT_MATRIX_YAML)
+}
+
+/// Parse a game support matrix in the same YAML layout as the canonical one.
+pub fn parse_matrix_yaml(yaml: &str) -> Result<GameSupportMatrix, serde_yaml::Error> {
+    serde_yaml::from_str(yaml)
 }
 
 /// Load game identifiers from the canonical telemetry matrix.
@@ -130,6 +298,13 @@ impl GameSupportMatrix {
         self.games.contains_key(game_id)
     }
 
+    /// Capabilities of `game_id`, if the matrix lists it.
+    pub fn capabilities(&self, game_id: &str) -> Option<GameCapabilities> {
+        self.games
+            .get(game_id)
+            .map(GameSupport::effective_capabilities)
+    }
+
     /// Return game ids by status, sorted alphabetically.
     pub fn game_ids_by_status(&self, status: GameSupportStatus) -> Vec<String> {
         let mut game_ids: Vec<String> = self
@@ -150,11 +325,25 @@ impl GameSupportMatrix {
     pub fn experimental_games(&self) -> Vec<String> {
         self.game_ids_by_status(GameSupportStatus::Experimental)
     }
+
+    /// Return games flagged `no_config_writer`, sorted alphabetically.
+    pub fn games_without_config_writer(&self) -> Vec<String> {
+        let mut game_ids: Vec<String> = self
+            .games
+            .iter()
+            .filter_map(|(game_id, support)| support.no_config_writer.then_some(game_id.clone()))
+            .collect();
+        game_ids.sort_unstable();
+        game_ids
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{GameSupportStatus, load_default_matrix, matrix_game_ids, normalize_game_id};
+    use super::{
+        GAME_ID_MIGRATIONS, GameCapabilities, GameSupportStatus, TransportKind,
+        load_default_matrix, matrix_game_ids, normalize_game_id, parse_matrix_yaml,
+    };
 
     #[test]
     fn matrix_metadata_game_ids_is_sorted_and_non_empty() -> Result<(), Box<dyn std::error::Error>>
@@ -208,6 +397,19 @@ mod tests {
         assert_eq!(normalize_game_id("f1"), "f1");
     }
 
+    #[test]
+    fn game_id_migrations_point_at_live_matrix_ids() -> Result<(), Box<dyn std::error::Error>> {
+        let matrix = load_default_matrix()?;
+        for migration in GAME_ID_MIGRATIONS {
+            assert!(matrix.has_game_id(migration.new), "{}", migration.new);
+            assert!(!matrix.has_game_id(migration.old), "{}", migration.old);
+            assert_eq!(normalize_game_id(migration.old), migration.new);
+            // A migrated id is already canonical, so chains cannot form.
+            assert_eq!(normalize_game_id(migration.new), migration.new);
+        }
+        Ok(())
+    }
+
     #[test]
     fn game_count_meets_minimum_regression_threshold() -> Result<(), Box<dyn std::error::Error>> {
         let game_ids = matrix_game_ids()?;
@@ -557,6 +759,8 @@ games:
         assert!(!game.telemetry.supports_360hz_option); // default false
         assert!(game.telemetry.high_rate_update_rate_hz.is_none());
         assert!(game.telemetry.output_target.is_none());
+        assert!(!game.no_config_writer); // default false
+        assert!(matrix.games_without_config_writer().is_empty());
         Ok(())
     }
 
@@ -682,4 +886,90 @@ games:
         );
         Ok(())
     }
+
+    #[test]
+    fn matrix_without_capabilities_still_loads() -> Result<(), Box<dyn std::error::Error>> {
+        let matrix = parse_matrix_yaml(
+            r#"
+games:
+  legacy_udp:
+    name: "Legacy UDP Sim"
+    versions:
+      - version: "1.x"
+        config_paths: []
+        executable_patterns: ["legacy.exe"]
+        telemetry_method: "udp_legacy"
+        supported_fields: ["rpm", "speed_ms", "rpm"]
+    telemetry:
+      method: "udp_legacy"
+      update_rate_hz: 60
+      output_target: "127.0.0.1:20777"
+      fields:
+        ffb_scalar: null
+        rpm: "rpm"
+        speed_ms: "speed"
+        slip_ratio: null
+        gear: null
+        flags: null
+        car_id: null
+        track_id: null
+    config_writer: "legacy_udp"
+    auto_detect:
+      process_names: []
+      install_registry_keys: []
+      install_paths: []
+"#,
+        )?;
+        let game = matrix.games.get("legacy_udp").ok_or("legacy_udp missing")?;
+        assert!(game.capabilities.is_none());
+        assert_eq!(
+            matrix.capabilities("legacy_udp"),
+            Some(GameCapabilities {
+                transport: vec![TransportKind::Udp],
+                default_port: Some(20777),
+                requires_plugin: false,
+                supported_fields: vec!["rpm".to_string(), "speed_ms".to_string()],
+                notes: None,
+            })
+        );
+        assert_eq!(matrix.capabilities("unknown"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn known_games_report_transport_port_and_plugin() -> Result<(), Box<dyn std::error::Error>> {
+        let matrix = load_default_matrix()?;
+
+        let iracing = matrix.capabilities("iracing").ok_or("iracing missing")?;
+        assert_eq!(iracing.transport, [TransportKind::SharedMemory]);
+        assert_eq!(iracing.default_port, None);
+        assert!(!iracing.requires_plugin);
+        assert!(iracing.supported_fields.contains(&"rpm".to_string()));
+
+        let dirt = matrix
+            .capabilities("dirt_rally_2")
+            .ok_or("dirt_rally_2 missing")?;
+        assert_eq!(dirt.transport, [TransportKind::Udp]);
+        assert_eq!(dirt.default_port, Some(20777));
+
+        let rf2 = matrix.capabilities("rfactor2").ok_or("rfactor2 missing")?;
+        assert!(rf2.requires_plugin);
+        assert!(rf2.notes.is_some());
+        assert!(!rf2.supported_fields.is_empty());
+
+        let rbr = matrix.capabilities("rbr").ok_or("rbr missing")?;
+        assert_eq!(rbr.transport, [TransportKind::Udp]);
+        assert_eq!(rbr.default_port, Some(6776));
+        assert!(rbr.requires_plugin);
+
+        let mudrunner = matrix
+            .capabilities("mudrunner")
+            .ok_or("mudrunner missing")?;
+        assert_eq!(mudrunner.transport, [TransportKind::Bridge]);
+        assert!(mudrunner.requires_plugin);
+
+        let acc2 = matrix.capabilities("acc2").ok_or("acc2 missing")?;
+        assert!(acc2.transport.is_empty());
+        Ok(())
+    }
 }
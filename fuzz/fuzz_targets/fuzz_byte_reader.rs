@@ -0,0 +1,46 @@
+//! Fuzzes the shared `openracing-byte-reader` cursor that the UDP and HID
+//! packet parsers read through.
+//!
+//! The first bytes choose offsets and lengths; the rest is the buffer. Every
+//! read must return `Ok` or `Err`, never panic, and a failed cursor read must
+//! leave the position unchanged.
+//!
+//! Run with:
+//!   cargo +nightly fuzz run fuzz_byte_reader
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use openracing_byte_reader::ByteReader;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((header, buffer)) = data.split_first_chunk::<4>() else {
+        return;
+    };
+    let offset = usize::from(u16::from_le_bytes([header[0], header[1]]));
+    let len = usize::from(header[2]);
+
+    let mut reader = ByteReader::at(buffer, offset);
+    let _ = reader.u64_le_at(offset);
+    let _ = reader.f32_le_finite_at(offset);
+    let _ = reader.bytes_at(offset, len);
+    assert!(reader.bytes_up_to(offset, len).len() <= len);
+
+    reader = ByteReader::new(buffer);
+    loop {
+        let before = reader.position();
+        let step = match header[3] % 4 {
+            0 => reader.u8().map(|_| ()),
+            1 => reader.u16_le().map(|_| ()),
+            2 => reader.f32_le().map(|_| ()),
+            _ => reader.skip(len).map(|_| ()),
+        };
+        if step.is_err() {
+            assert_eq!(reader.position(), before);
+            break;
+        }
+        if reader.position() == before {
+            break;
+        }
+    }
+});
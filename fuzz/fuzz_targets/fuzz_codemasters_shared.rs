@@ -0,0 +1,20 @@
+//! Fuzzes the Codemasters Mode 1 and extradata parsers shared by the DiRT,
+//! GRID and legacy F1 adapters.
+//!
+//! Every parse must return `Ok` or `Err`, never panic, whatever the length.
+//!
+//! Run with:
+//!   cargo +nightly fuzz run fuzz_codemasters_shared
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use racing_wheel_telemetry_adapters::codemasters_shared::{
+    parse_codemasters_extradata, parse_codemasters_mode1_common, read_f32,
+};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = parse_codemasters_mode1_common(data, "fuzz");
+    let _ = parse_codemasters_extradata(data, "fuzz");
+    for offset in (0..data.len().saturating_add(8)).step_by(4) {
+        let _ = read_f32(data, offset);
+    }
+});
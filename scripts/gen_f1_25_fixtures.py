@@ -18,6 +18,8 @@ PACKET_VER = 1
 NUM_CARS = 22
 CAR_TELEMETRY_ENTRY_SIZE = 60
 CAR_STATUS_ENTRY_SIZE = 55
+CAR_MOTION_ENTRY_SIZE = 60
+MOTION_EX_PACKET_SIZE = 273
 HEADER_SIZE = 29
 
 
@@ -172,6 +174,42 @@ def main():
         f.write(status_packet)
     print(f"Written {path} ({len(status_packet)} bytes)")
 
+    # --- Motion (packet_id=0) ---
+    # Player in slot 3 so decoders must honour the header's player index.
+    motion_player_index = 3
+    header_motion = build_header(0, motion_player_index)
+
+    cars_motion = bytearray()
+    for i in range(NUM_CARS):
+        if i == motion_player_index:
+            entry = bytearray(36)  # position, velocity, forward/right dirs
+            entry += struct.pack('<3f', 1.75, -2.5, 1.02)  # G lat/long/vert
+            entry += struct.pack('<3f', 0.0, 0.0, 0.0)  # yaw, pitch, roll
+        else:
+            entry = bytearray(36) + struct.pack('<3f', 9.0, 9.0, 9.0) + bytearray(12)
+        assert len(entry) == CAR_MOTION_ENTRY_SIZE
+        cars_motion += entry
+
+    motion_packet = header_motion + bytes(cars_motion)
+    assert len(motion_packet) == 29 + 22 * 60, f"Unexpected size {len(motion_packet)}"
+
+    path = os.path.join(out_dir, 'motion_packet.bin')
+    with open(path, 'wb') as f:
+        f.write(motion_packet)
+    print(f"Written {path} ({len(motion_packet)} bytes)")
+
+    # --- Motion Ex (packet_id=13) ---
+    motion_ex = bytearray(build_header(13, motion_player_index))
+    motion_ex += bytes(16 * 4)  # suspension position/velocity/acceleration, wheel speed
+    motion_ex += struct.pack('<4f', 0.08, 0.12, 0.02, 0.04)  # slip RL, RR, FL, FR
+    motion_ex += bytes(MOTION_EX_PACKET_SIZE - len(motion_ex))
+    assert len(motion_ex) == MOTION_EX_PACKET_SIZE, f"Unexpected size {len(motion_ex)}"
+
+    path = os.path.join(out_dir, 'motion_ex_packet.bin')
+    with open(path, 'wb') as f:
+        f.write(motion_ex)
+    print(f"Written {path} ({len(motion_ex)} bytes)")
+
 
 if __name__ == '__main__':
     main()
@@ -0,0 +1,121 @@
+#!/usr/bin/env python3
+"""Generate encrypted Gran Turismo 7 fixture packets for testing.
+
+Builds plaintext SimulatorInterface packets, encrypts them the way the
+console does (Salsa20 keyed with the protocol string, nonce from the IV at
+0x40) and writes them to crates/telemetry-adapters/tests/fixtures/gran_turismo_7.
+"""
+import os
+import struct
+
+KEY = b"Simulator Interface Packet GT7 ver 0.0"[:32]
+MAGIC = 0x47375330
+XOR_KEY_TYPE1 = 0xDEADBEAF
+XOR_KEY_TYPE2 = 0xDEADBEEF
+PACKET_SIZE = 0x128
+PACKET_SIZE_TYPE2 = 0x13C
+
+FLAG_CAR_ON_TRACK = 1 << 0
+FLAG_TCS_ACTIVE = 1 << 11
+
+OUT_DIR = os.path.join(
+    os.path.dirname(__file__),
+    "..",
+    "crates",
+    "telemetry-adapters",
+    "tests",
+    "fixtures",
+    "gran_turismo_7",
+)
+
+
+def rotl(value, shift):
+    return ((value << shift) | (value >> (32 - shift))) & 0xFFFFFFFF
+
+
+def quarter_round(s, a, b, c, d):
+    s[b] ^= rotl((s[a] + s[d]) & 0xFFFFFFFF, 7)
+    s[c] ^= rotl((s[b] + s[a]) & 0xFFFFFFFF, 9)
+    s[d] ^= rotl((s[c] + s[b]) & 0xFFFFFFFF, 13)
+    s[a] ^= rotl((s[d] + s[c]) & 0xFFFFFFFF, 18)
+
+
+def salsa20_block(nonce, counter):
+    k = struct.unpack("<8I", KEY)
+    n = struct.unpack("<2I", nonce)
+    state = [
+        0x61707865, k[0], k[1], k[2],
+        k[3], 0x3320646E, n[0], n[1],
+        counter & 0xFFFFFFFF, counter >> 32, 0x79622D32, k[4],
+        k[5], k[6], k[7], 0x6B206574,
+    ]
+    working = list(state)
+    for _ in range(10):
+        quarter_round(working, 0, 4, 8, 12)
+        quarter_round(working, 5, 9, 13, 1)
+        quarter_round(working, 10, 14, 2, 6)
+        quarter_round(working, 15, 3, 7, 11)
+        quarter_round(working, 0, 1, 2, 3)
+        quarter_round(working, 5, 6, 7, 4)
+        quarter_round(working, 10, 11, 8, 9)
+        quarter_round(working, 15, 12, 13, 14)
+    return struct.pack("<16I", *((w + s) & 0xFFFFFFFF for w, s in zip(working, state)))
+
+
+def encrypt(plain, xor_key, iv1):
+    nonce = struct.pack("<II", iv1 ^ xor_key, iv1)
+    out = bytearray(len(plain))
+    for block in range(0, len(plain), 64):
+        stream = salsa20_block(nonce, block // 64)
+        for i, byte in enumerate(plain[block:block + 64]):
+            out[block + i] = byte ^ stream[i]
+    # The IV travels in the clear so the receiver can rebuild the nonce.
+    out[0x40:0x44] = struct.pack("<I", iv1)
+    return bytes(out)
+
+
+def build_plain(size):
+    buf = bytearray(size)
+    struct.pack_into("<I", buf, 0x00, MAGIC)
+    struct.pack_into("<f", buf, 0x3C, 6500.0)  # engine rpm
+    struct.pack_into("<f", buf, 0x44, 42.5)  # fuel, litres
+    struct.pack_into("<f", buf, 0x48, 100.0)  # fuel capacity
+    struct.pack_into("<f", buf, 0x4C, 55.5)  # speed, m/s
+    struct.pack_into("<f", buf, 0x58, 88.0)  # water temp
+    struct.pack_into("<4f", buf, 0x60, 80.0, 81.0, 82.0, 83.0)  # tyre temps
+    struct.pack_into("<i", buf, 0x70, 1234)  # packet id
+    struct.pack_into("<h", buf, 0x74, 3)  # lap count
+    struct.pack_into("<i", buf, 0x78, 95000)  # best lap
+    struct.pack_into("<i", buf, 0x7C, 96500)  # last lap
+    struct.pack_into("<i", buf, 0x80, 30250)  # current lap
+    struct.pack_into("<h", buf, 0x84, 4)  # position
+    struct.pack_into("<h", buf, 0x86, 16)  # cars at race start
+    struct.pack_into("<H", buf, 0x8A, 7800)  # max alert rpm
+    struct.pack_into("<H", buf, 0x8E, FLAG_CAR_ON_TRACK | FLAG_TCS_ACTIVE)
+    buf[0x90] = 0x43  # suggested 4th, in 3rd
+    buf[0x91] = 204  # throttle
+    buf[0x92] = 0  # brake
+    struct.pack_into("<i", buf, 0x124, 3273)  # car code
+    if size >= PACKET_SIZE_TYPE2:
+        struct.pack_into("<f", buf, 0x128, 0.3)  # wheel rotation
+        struct.pack_into("<f", buf, 0x130, 0.5)  # sway
+        struct.pack_into("<f", buf, 0x134, -0.1)  # heave
+        struct.pack_into("<f", buf, 0x138, 0.8)  # surge
+    return bytes(buf)
+
+
+def main():
+    os.makedirs(OUT_DIR, exist_ok=True)
+    fixtures = {
+        "type1_packet.bin": encrypt(build_plain(PACKET_SIZE), XOR_KEY_TYPE1, 0x1234_5678),
+        "type2_packet.bin": encrypt(build_plain(PACKET_SIZE_TYPE2), XOR_KEY_TYPE2, 0x0BAD_F00D),
+    }
+    for name, data in fixtures.items():
+        path = os.path.join(OUT_DIR, name)
+        with open(path, "wb") as f:
+            f.write(data)
+        print(f"wrote {path} ({len(data)} bytes)")
+
+
+if __name__ == "__main__":
+    main()